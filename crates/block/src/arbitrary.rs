@@ -0,0 +1,296 @@
+//! [Arbitrary] impls of blocks and certificates, so property based tests
+//! across crates can generate them.
+
+use std::str::FromStr;
+
+use block_verifier::{LEGACY_HEADER_FORMAT, TAGGED_HEADER_FORMAT};
+use primitives::{QuorumId, QuorumPubkey, ValidatorSecretKey, ELECTION_ELIGIBILITY_SCALE};
+use proptest::{collection, option, prelude::*, strategy::LazyJust};
+use reward::reward::Reward;
+use ritelinked::{LinkedHashMap, LinkedHashSet};
+use vrrb_core::{
+    arbitrary::{txn_digest, u256},
+    claim::Claim,
+    transactions::{QuorumCertifiedTxn, Transaction, TransactionKind},
+};
+
+use crate::{
+    header::BlockHeader, root_chain::RootChain, Block, Certificate, ClaimList, ConvergenceBlock,
+    GenesisBlock, ProposalBlock, QuorumPubkeys,
+};
+
+fn block_hash() -> impl Strategy<Value = String> {
+    "[0-9a-f]{64}"
+}
+
+fn reward() -> impl Strategy<Value = Reward> {
+    (
+        any::<u128>(),
+        any::<u128>(),
+        any::<u128>(),
+        option::of("[a-zA-Z0-9]{1,32}"),
+        any::<u128>(),
+    )
+        .prop_map(
+            |(epoch, next_epoch_block, current_block, miner, amount)| Reward {
+                epoch,
+                next_epoch_block,
+                current_block,
+                miner,
+                amount,
+            },
+        )
+}
+
+fn claim_list() -> impl Strategy<Value = ClaimList> {
+    collection::vec(any::<Claim>(), 0..3).prop_map(|claims| {
+        claims
+            .into_iter()
+            .map(|claim| (claim.hash, claim))
+            .collect()
+    })
+}
+
+fn quorum_pubkeys() -> impl Strategy<Value = QuorumPubkeys> {
+    // NOTE: quorum pubkeys have to be valid BLS public keys, which random bytes
+    // aren't
+    let pubkey = LazyJust::new(|| QuorumPubkey::from(ValidatorSecretKey::random().public_key()));
+
+    collection::vec(("[a-zA-Z0-9_-]{1,16}", pubkey), 0..3).prop_map(|quorums| {
+        quorums
+            .into_iter()
+            .map(|(quorum_id, pubkey)| {
+                let quorum_id = QuorumId::from_str(&quorum_id).expect("quorum ids are valid");
+
+                (quorum_id, pubkey)
+            })
+            .collect()
+    })
+}
+
+impl Arbitrary for Certificate {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            "[0-9a-f]{0,192}",
+            option::of(quorum_pubkeys()),
+            block_hash(),
+            block_hash(),
+            block_hash(),
+        )
+            .prop_map(
+                |(signature, inauguration, root_hash, next_root_hash, block_hash)| Certificate {
+                    signature,
+                    inauguration,
+                    root_hash,
+                    next_root_hash,
+                    block_hash,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for BlockHeader {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            (
+                collection::vec(block_hash(), 0..3),
+                any::<u128>(),
+                any::<u128>(),
+                any::<u64>(),
+                any::<u64>(),
+                any::<u128>(),
+                any::<i64>(),
+                prop_oneof![Just(LEGACY_HEADER_FORMAT), Just(TAGGED_HEADER_FORMAT)],
+            ),
+            (
+                block_hash(),
+                any::<Claim>(),
+                block_hash(),
+                reward(),
+                reward(),
+                any::<u64>(),
+                prop_oneof![Just(String::new()), block_hash()],
+                collection::vec(block_hash(), 0..4),
+                any::<u16>(),
+                0..=ELECTION_ELIGIBILITY_SCALE,
+                prop_oneof![Just(String::new()), block_hash()],
+                "[0-9a-f]{0,144}",
+            ),
+        )
+            .prop_map(
+                |(
+                    (
+                        ref_hashes,
+                        epoch,
+                        round,
+                        block_seed,
+                        next_block_seed,
+                        block_height,
+                        timestamp,
+                        format_version,
+                    ),
+                    (
+                        txn_hash,
+                        miner_claim,
+                        claim_list_hash,
+                        block_reward,
+                        next_block_reward,
+                        block_weight,
+                        state_root,
+                        ancestor_state_roots,
+                        fallback_slot,
+                        election_eligibility,
+                        delegation_root,
+                        miner_signature,
+                    ),
+                )| BlockHeader {
+                    ref_hashes,
+                    epoch,
+                    round,
+                    block_seed,
+                    next_block_seed,
+                    block_height,
+                    timestamp,
+                    txn_hash,
+                    miner_claim,
+                    claim_list_hash,
+                    block_reward,
+                    next_block_reward,
+                    format_version,
+                    block_weight,
+                    state_root,
+                    root_chain: ancestor_state_roots
+                        .iter()
+                        .fold(RootChain::default(), |root_chain, state_root| {
+                            root_chain.appended(state_root)
+                        }),
+                    fallback_slot,
+                    election_eligibility,
+                    delegation_root,
+                    miner_signature,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for ProposalBlock {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            block_hash(),
+            any::<u128>(),
+            any::<u128>(),
+            collection::vec(any::<QuorumCertifiedTxn>(), 0..3),
+            claim_list(),
+            collection::vec(txn_digest(), 0..3),
+            any::<Claim>(),
+            block_hash(),
+            "[0-9a-f]{0,192}",
+        )
+            .prop_map(
+                |(ref_block, round, epoch, txns, claims, inclusion_list, from, hash, signature)| {
+                    ProposalBlock {
+                        ref_block,
+                        round,
+                        epoch,
+                        txns: txns.into_iter().map(|txn| (txn.txn().id(), txn)).collect(),
+                        claims,
+                        inclusion_list,
+                        halt_directives: vec![],
+                        from,
+                        hash,
+                        signature,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for ConvergenceBlock {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let txns = collection::vec((block_hash(), collection::vec(txn_digest(), 0..3)), 0..3)
+            .prop_map(|txns| {
+                txns.into_iter()
+                    .map(|(ref_hash, digests)| (ref_hash, LinkedHashSet::from_iter(digests)))
+                    .collect::<LinkedHashMap<_, _>>()
+            });
+
+        let claims = collection::vec((block_hash(), collection::vec(u256(), 0..3)), 0..3).prop_map(
+            |claims| {
+                claims
+                    .into_iter()
+                    .map(|(ref_hash, hashes)| (ref_hash, LinkedHashSet::from_iter(hashes)))
+                    .collect::<LinkedHashMap<_, _>>()
+            },
+        );
+
+        (
+            any::<BlockHeader>(),
+            txns,
+            claims,
+            block_hash(),
+            option::of(any::<Certificate>()),
+        )
+            .prop_map(
+                |(header, txns, claims, hash, certificate)| ConvergenceBlock {
+                    header,
+                    txns,
+                    claims,
+                    hash,
+                    certificate,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for GenesisBlock {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<BlockHeader>(),
+            collection::vec(any::<TransactionKind>(), 0..3),
+            claim_list(),
+            block_hash(),
+            option::of(any::<Certificate>()),
+        )
+            .prop_map(|(header, txns, claims, hash, certificate)| GenesisBlock {
+                header,
+                txns: txns.into_iter().map(|txn| (txn.id(), txn)).collect(),
+                claims,
+                hash,
+                certificate,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Block {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<ConvergenceBlock>().prop_map(|block| Block::Convergence { block }),
+            any::<ProposalBlock>().prop_map(|block| Block::Proposal { block }),
+            any::<GenesisBlock>().prop_map(|block| Block::Genesis { block }),
+        ]
+        .boxed()
+    }
+}
@@ -0,0 +1,97 @@
+//! Checkpoints are compact, quorum-signed commitments to the chain that
+//! external verifiers, such as bridges and auditors, can check without
+//! running a node.
+
+use std::collections::BTreeMap;
+
+use primitives::{Epoch, NodeId, QuorumPubkey, Round, ValidatorPublicKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{header::BlockHeader, BlockHash, Certificate};
+
+/// A commitment to the chain as of a certified convergence block.
+///
+/// The quorum's threshold signature covers the block hash only. The block's
+/// header is carried along so verifiers can check the hash commits to the
+/// round, state root and txn root the checkpoint claims.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub round: Round,
+    pub epoch: Epoch,
+    pub block_hash: BlockHash,
+
+    /// Hex encoded root of the state trie once the block is applied
+    pub state_root: String,
+
+    /// Hash of the txns the block includes, as committed to by its header
+    pub txn_root: String,
+
+    /// Hex encoded hash of the quorum that certified the block, see
+    /// [validator_set_hash]
+    pub validator_set_hash: String,
+    pub quorum_public_key: QuorumPubkey,
+
+    /// Hex encoded threshold signature of the quorum over the block hash
+    pub signature: String,
+    pub header: BlockHeader,
+}
+
+impl Checkpoint {
+    /// Builds a checkpoint out of a certified block's header, the certificate
+    /// it was certified with and the quorum that signed it
+    pub fn new(
+        header: BlockHeader,
+        certificate: &Certificate,
+        quorum_public_key: QuorumPubkey,
+        members: &BTreeMap<NodeId, ValidatorPublicKey>,
+    ) -> Self {
+        Self {
+            round: header.round,
+            epoch: header.epoch,
+            block_hash: certificate.block_hash.clone(),
+            state_root: header.state_root.clone(),
+            txn_root: header.txn_hash.clone(),
+            validator_set_hash: validator_set_hash(&quorum_public_key, members),
+            quorum_public_key,
+            signature: certificate.signature.clone(),
+            header,
+        }
+    }
+
+    /// Returns whether the header hashes to the checkpointed block hash and
+    /// commits to the checkpointed round, epoch, state root and txn root
+    pub fn matches_header(&self) -> bool {
+        self.header.hash() == self.block_hash
+            && self.header.round == self.round
+            && self.header.epoch == self.epoch
+            && self.header.state_root == self.state_root
+            && self.header.txn_hash == self.txn_root
+    }
+
+    /// Returns whether the checkpoint was signed by the quorum made up of
+    /// `members`
+    pub fn commits_to_validator_set(&self, members: &BTreeMap<NodeId, ValidatorPublicKey>) -> bool {
+        validator_set_hash(&self.quorum_public_key, members) == self.validator_set_hash
+    }
+}
+
+/// Hashes a quorum's group public key along with its members' public keys.
+///
+/// The preimage is the group public key followed by every member, in node id
+/// order, encoded as the length of its node id as 8 little-endian bytes, the
+/// node id itself and its public key.
+pub fn validator_set_hash(
+    group_public_key: &QuorumPubkey,
+    members: &BTreeMap<NodeId, ValidatorPublicKey>,
+) -> String {
+    let mut preimage = group_public_key.as_bytes().to_vec();
+
+    for (node_id, public_key) in members {
+        preimage.extend((node_id.len() as u64).to_le_bytes());
+        preimage.extend(node_id.as_bytes());
+        preimage.extend(public_key.to_bytes());
+    }
+
+    hex::encode(Sha256::digest(&preimage))
+}
@@ -1,14 +1,16 @@
-use primitives::{Epoch, SecretKey as SecretKeyBytes};
+use primitives::{Epoch, SecretKey as SecretKeyBytes, Weight};
 use reward::reward::Reward;
 #[cfg(mainnet)]
 use reward::reward::GENESIS_REWARD;
 use ritelinked::{LinkedHashMap, LinkedHashSet};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use vrrb_core::claim::Claim;
 use vrrb_core::transactions::{TransactionDigest, TransactionKind};
 
 use crate::{
-    header::BlockHeader, Block, BlockHash, Certificate, ConsolidatedClaims, ConsolidatedTxns,
+    header::BlockHeader, txn_order::is_canonical_consolidated_txn_order, Block, BlockHash,
+    Certificate, ConsolidatedClaims, ConsolidatedTxns, ProposalBlock,
 };
 
 pub struct MineArgs<'a> {
@@ -46,4 +48,47 @@ impl ConvergenceBlock {
     pub fn txn_id_set(&self) -> LinkedHashSet<&TransactionDigest> {
         self.txns.iter().flat_map(|(_, set)| set).collect()
     }
+
+    /// Returns whether the block lists the txns it consolidates in canonical
+    /// order, see [crate::txn_order]
+    pub fn has_canonical_txn_order(&self) -> bool {
+        is_canonical_consolidated_txn_order(&self.txns)
+    }
+
+    /// Returns whether the block's header still hashes to the block's hash
+    /// and commits to the block's txns and claims, hashed the way the miner
+    /// hashed them
+    pub fn matches_hash(&self) -> bool {
+        fn json_hash<T: Serialize>(value: &T) -> Option<String> {
+            let serialized = serde_json::to_string(value).ok()?;
+
+            Some(format!("{:x}", Sha256::digest(serialized.as_bytes())))
+        }
+
+        self.header.hash() == self.hash
+            && json_hash(&self.txns).as_ref() == Some(&self.header.txn_hash)
+            && json_hash(&self.claims).as_ref() == Some(&self.header.claim_list_hash)
+    }
+}
+
+/// Returns the cumulative weight of consolidated `txns`, looking each txn up
+/// in the proposal block it was consolidated from. Returns `None` if one of
+/// those proposal blocks, or one of the txns within it, is missing from
+/// `proposals`.
+pub fn consolidated_txns_weight(
+    txns: &ConsolidatedTxns,
+    proposals: &[ProposalBlock],
+) -> Option<Weight> {
+    let mut weight: Weight = 0;
+
+    for (ref_hash, txn_ids) in txns.iter() {
+        let proposal = proposals.iter().find(|block| &block.hash == ref_hash)?;
+
+        for txn_id in txn_ids.iter() {
+            let txn = proposal.txns.get(txn_id)?;
+            weight = weight.saturating_add(txn.txn().weight());
+        }
+    }
+
+    Some(weight)
 }
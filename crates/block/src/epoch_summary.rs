@@ -0,0 +1,100 @@
+//! Epoch summaries condense an epoch into a single quorum-signed artifact,
+//! chained to the summary of the epoch before it, so light clients can sync
+//! epoch by epoch instead of checking the certificate of every round.
+
+use primitives::{Epoch, QuorumPubkey, Round};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{header::BlockHeader, BlockHash};
+
+/// A commitment to the chain as of the last certified convergence block of
+/// an epoch, along with what happened over the epoch.
+///
+/// Like a [crate::Checkpoint], the quorum's threshold signature covers the
+/// block hash only, and the block's header is carried along so verifiers can
+/// check the hash commits to the round and state root the summary claims.
+/// The txn count and reward totals are tallied by the node that produced the
+/// summary and aren't covered by the signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochSummary {
+    pub epoch: Epoch,
+
+    /// Hash of the summary of the latest epoch summarized before this one,
+    /// empty for the first epoch summarized
+    pub parent_hash: String,
+
+    /// Round of the last block of the epoch
+    pub round: Round,
+    pub block_hash: BlockHash,
+
+    /// Hex encoded root of the state trie once the last block is applied
+    pub state_root: String,
+
+    /// Hex encoded hash of the quorum that certified the last block, see
+    /// [crate::validator_set_hash]
+    pub validator_set_hash: String,
+    pub quorum_public_key: QuorumPubkey,
+
+    /// Txns included by the certified convergence blocks of the epoch
+    pub txn_count: u64,
+
+    /// Rewards validators earned from the blocks of the epoch
+    pub block_rewards: u128,
+
+    /// Fees validators earned from the txns of the epoch
+    pub fee_shares: u128,
+
+    /// Hex encoded threshold signature of the quorum over the last block's
+    /// hash, taken from its certificate
+    pub signature: String,
+    pub header: BlockHeader,
+}
+
+impl EpochSummary {
+    /// Hashes the summary, which the summary of the next epoch names as its
+    /// parent.
+    ///
+    /// The preimage is the epoch as 16 little-endian bytes, the parent hash,
+    /// the round as 16 little-endian bytes, the block hash, the state root,
+    /// the validator set hash, the txn count as 8 little-endian bytes and the
+    /// reward totals as 16 little-endian bytes each. Every string is prefixed
+    /// with its length as 8 little-endian bytes.
+    pub fn hash(&self) -> String {
+        fn push_str(preimage: &mut Vec<u8>, value: &str) {
+            preimage.extend((value.len() as u64).to_le_bytes());
+            preimage.extend(value.as_bytes());
+        }
+
+        let mut preimage = self.epoch.to_le_bytes().to_vec();
+
+        push_str(&mut preimage, &self.parent_hash);
+        preimage.extend(self.round.to_le_bytes());
+        push_str(&mut preimage, &self.block_hash);
+        push_str(&mut preimage, &self.state_root);
+        push_str(&mut preimage, &self.validator_set_hash);
+        preimage.extend(self.txn_count.to_le_bytes());
+        preimage.extend(self.block_rewards.to_le_bytes());
+        preimage.extend(self.fee_shares.to_le_bytes());
+
+        hex::encode(Sha256::digest(&preimage))
+    }
+
+    /// Returns whether the header hashes to the summarized block hash and
+    /// commits to the summarized epoch, round and state root
+    pub fn matches_header(&self) -> bool {
+        self.header.hash() == self.block_hash
+            && self.header.epoch == self.epoch
+            && self.header.round == self.round
+            && self.header.state_root == self.state_root
+    }
+
+    /// Returns whether the summary names `parent` as its parent and comes
+    /// after it.
+    ///
+    /// Epochs whose last block the node didn't see certified aren't
+    /// summarized, so a summary can follow one more than an epoch older
+    pub fn follows(&self, parent: &EpochSummary) -> bool {
+        self.parent_hash == parent.hash() && self.epoch > parent.epoch && self.round > parent.round
+    }
+}
@@ -0,0 +1,72 @@
+//! Halt directives let the harvester quorum stop block production across
+//! the network, for instance while a critical bug is being fixed, and start
+//! it again afterwards.
+
+use std::fmt;
+
+use primitives::{QuorumPubkey, Round};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum HaltAction {
+    Halt,
+    Resume,
+}
+
+impl HaltAction {
+    fn tag(&self) -> &'static [u8] {
+        match self {
+            HaltAction::Halt => b"halt",
+            HaltAction::Resume => b"resume",
+        }
+    }
+}
+
+impl fmt::Display for HaltAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HaltAction::Halt => write!(f, "halt"),
+            HaltAction::Resume => write!(f, "resume"),
+        }
+    }
+}
+
+/// Tells every node to stop, or start again, mining and certifying blocks.
+///
+/// Directives name the round they were issued at, so a directive that was
+/// already acted on can't be replayed to halt the network again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HaltDirective {
+    pub action: HaltAction,
+    pub round: Round,
+
+    /// Why the directive was issued, for the audit trail
+    pub reason: String,
+}
+
+impl HaltDirective {
+    /// Returns what the quorum signs to issue the directive.
+    ///
+    /// The preimage is the action's tag (`halt` or `resume`), the round as 16
+    /// big-endian bytes and the reason.
+    pub fn payload_hash(&self) -> Vec<u8> {
+        let mut preimage = self.action.tag().to_vec();
+        preimage.extend(self.round.to_be_bytes());
+        preimage.extend(self.reason.as_bytes());
+
+        Sha256::digest(&preimage).to_vec()
+    }
+}
+
+/// A halt directive along with the harvester quorum's threshold signature
+/// over its payload hash.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SignedHaltDirective {
+    pub directive: HaltDirective,
+    pub quorum_public_key: QuorumPubkey,
+
+    /// Hex encoded threshold signature of the quorum over
+    /// [HaltDirective::payload_hash]
+    pub signature: String,
+}
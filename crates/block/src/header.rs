@@ -1,8 +1,17 @@
 use std::fmt::Debug;
 // FEATURE TAG(S): Block Structure, Rewards
+use block_verifier::{
+    HeaderFields, RewardFields, VerifyError, LEGACY_HEADER_FORMAT, TAGGED_HEADER_FORMAT,
+};
 use chrono;
-use primitives::{Epoch, SecretKey};
-use reward::reward::Reward;
+use primitives::{
+    ElectionDifficultyParams, Epoch, HeaderFormatParams, SecretKey, Weight,
+    ELECTION_ELIGIBILITY_SCALE,
+};
+use reward::{
+    reward::Reward,
+    schedule::{RewardError, RewardSchedule},
+};
 use secp256k1::{
     hashes::{sha256 as s256, Hash},
     Message,
@@ -12,7 +21,14 @@ use utils::{create_payload, hash_data};
 use vrrb_core::claim::Claim;
 use vrrb_vrf::{vrng::VRNG, vvrf::VVRF};
 
-use crate::{block::Block, InnerBlock, NextEpochAdjustment};
+use crate::{
+    block::Block,
+    invalid::{BlockError, InvalidBlockErrorReason},
+    root_chain::RootChain,
+    BlockHash,
+    InnerBlock,
+    NextEpochAdjustment,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Hash, PartialEq, Eq)]
 pub struct BlockHeader {
@@ -30,6 +46,46 @@ pub struct BlockHeader {
     pub claim_list_hash: String,
     pub block_reward: Reward,
     pub next_block_reward: Reward,
+
+    /// Layout the header is signed and hashed with, see
+    /// [HeaderFields::signing_preimage]. Follows from the block's height, as
+    /// [HeaderFormatParams] set it. The fields below are only committed to
+    /// by headers in the tagged layout
+    #[serde(default)]
+    pub format_version: u16,
+
+    /// Cumulative weight of the txns included in the block
+    pub block_weight: Weight,
+
+    /// Hex encoded root of the state trie once the block's txns are applied.
+    /// Empty for genesis blocks and blocks predating the commitment
+    #[serde(default)]
+    pub state_root: String,
+
+    /// Accumulates the state roots committed to by the block's ancestors,
+    /// see [crate::root_chain]. Empty for genesis blocks and blocks predating
+    /// the commitment
+    #[serde(default)]
+    pub root_chain: RootChain,
+
+    /// Slot of the round's miner schedule the block was mined in, see
+    /// [primitives::MinerFallbackParams]. Zero when the election winner mined
+    /// it, which all blocks predating fallback miners were
+    #[serde(default)]
+    pub fallback_slot: u16,
+
+    /// Share of the election result space, in parts per million, the
+    /// election of the block's child starts from, see
+    /// [primitives::ElectionDifficultyParams]. Full for genesis blocks and
+    /// blocks predating election difficulty
+    #[serde(default = "full_election_eligibility")]
+    pub election_eligibility: u64,
+
+    /// Hex encoded root the delegation state of every claim commits to once
+    /// the block is applied, see [primitives::DelegationParams]. Empty for
+    /// genesis blocks and blocks mined while delegation isn't active
+    #[serde(default)]
+    pub delegation_root: String,
     pub miner_signature: String,
 }
 
@@ -42,6 +98,8 @@ impl BlockHeader {
         miner_claim: Claim,
         secret_key: SecretKey,
         claim_list_hash: String,
+        reward_schedule: &RewardSchedule,
+        header_format: &HeaderFormatParams,
     ) -> BlockHeader {
         //TODO: Determine data fields to be used as message in VPRNG, must be
         // known/revealed within block but cannot be predictable or gameable.
@@ -61,15 +119,15 @@ impl BlockHeader {
 
         let timestamp = chrono::Utc::now().timestamp();
         let txn_hash = hex::encode(hash_data!("Genesis_Txn_Hash".to_string()));
-        let block_reward = Reward::genesis(Some(miner_claim.address.to_string()));
+        let block_reward = reward_schedule.genesis_reward(Some(miner_claim.address.to_string()));
         let block_height = 0;
-        let next_block_reward = Reward::default();
+        let next_block_reward = reward_schedule.next_reward(&block_reward, 0);
 
-        let payload = create_payload!(
+        let mut header = BlockHeader {
             ref_hashes,
             round,
             epoch,
-            seed,
+            block_seed: seed,
             next_block_seed,
             block_height,
             timestamp,
@@ -77,26 +135,20 @@ impl BlockHeader {
             miner_claim,
             claim_list_hash,
             block_reward,
-            next_block_reward
-        );
+            next_block_reward,
+            format_version: format_version_at(header_format, block_height),
+            block_weight: 0,
+            state_root: String::new(),
+            root_chain: RootChain::default(),
+            fallback_slot: 0,
+            election_eligibility: ELECTION_ELIGIBILITY_SCALE,
+            delegation_root: String::new(),
+            miner_signature: String::new(),
+        };
 
-        let miner_signature = secret_key.sign_ecdsa(payload).to_string();
+        header.sign(&secret_key);
 
-        BlockHeader {
-            ref_hashes,
-            round,
-            epoch,
-            block_seed: 0,
-            next_block_seed,
-            block_height: 0,
-            timestamp,
-            txn_hash,
-            miner_claim,
-            claim_list_hash,
-            block_reward,
-            next_block_reward,
-            miner_signature,
-        }
+        header
     }
 
     pub fn new(
@@ -107,6 +159,10 @@ impl BlockHeader {
         txn_hash: String,
         claim_list_hash: String,
         adjustment_next_epoch: NextEpochAdjustment,
+        reward_schedule: &RewardSchedule,
+        header_format: &HeaderFormatParams,
+        election_difficulty: &ElectionDifficultyParams,
+        block_weight: Weight,
     ) -> Option<BlockHeader> {
         // Get the last block
         let last_block: &dyn InnerBlock<Header = BlockHeader, RewardType = Reward> = {
@@ -145,7 +201,7 @@ impl BlockHeader {
 
         // Create the next block reward, which is a clone of the current
         // reward, unless there's an epoch change
-        let next_block_reward = block_reward.generate_next_reward(adjustment_next_epoch);
+        let next_block_reward = reward_schedule.next_reward(&block_reward, adjustment_next_epoch);
 
         // Append the miner to the current block reward
         block_reward.miner = Some(miner_claim.address.to_string());
@@ -156,42 +212,203 @@ impl BlockHeader {
         // Get the reward for current block which is last_block.round + 1
         let round = last_block.get_header().round + 1;
 
-        let payload = create_payload!(
-            ref_hashes,
-            round,
-            epoch,
-            block_seed,
-            next_block_seed,
-            block_height,
-            timestamp,
-            txn_hash,
-            miner_claim,
-            claim_list_hash,
-            block_reward,
-            next_block_reward
-        );
+        let root_chain = last_block.get_header().next_root_chain();
 
-        let miner_signature = secret_key.sign_ecdsa(payload).to_string();
+        let election_eligibility = election_difficulty.retarget(
+            last_block.get_header().election_eligibility,
+            timestamp - last_block.get_header().timestamp,
+        );
 
-        let block_header = BlockHeader {
+        let mut block_header = BlockHeader {
             ref_hashes,
             round,
             epoch,
             block_seed,
             next_block_seed,
-            block_height: last_block.get_header().block_height + 1,
+            block_height,
             timestamp,
             txn_hash,
             miner_claim,
             claim_list_hash,
             block_reward,
             next_block_reward,
-            miner_signature,
+            format_version: format_version_at(header_format, block_height),
+            block_weight,
+            state_root: String::new(),
+            root_chain,
+            fallback_slot: 0,
+            election_eligibility,
+            delegation_root: String::new(),
+            miner_signature: String::new(),
         };
 
+        block_header.sign(&secret_key);
+
         Some(block_header)
     }
 
+    /// Returns the fields of the header its hash commits to, as
+    /// [block_verifier] checks them. `root_chain_commitment` must be the
+    /// [RootChain::commitment] of the header's root chain, if it isn't empty
+    fn verifier_fields<'a>(&'a self, root_chain_commitment: Option<&'a str>) -> HeaderFields<'a> {
+        let mut miner_claim_hash = [0u8; 32];
+        self.miner_claim.hash.to_big_endian(&mut miner_claim_hash);
+
+        HeaderFields {
+            ref_hashes: &self.ref_hashes,
+            epoch: self.epoch,
+            round: self.round,
+            block_seed: self.block_seed,
+            next_block_seed: self.next_block_seed,
+            block_height: self.block_height,
+            timestamp: self.timestamp,
+            txn_hash: &self.txn_hash,
+            miner_claim_hash,
+            miner_public_key: self.miner_claim.public_key.serialize(),
+            claim_list_hash: &self.claim_list_hash,
+            block_reward: reward_fields(&self.block_reward),
+            next_block_reward: reward_fields(&self.next_block_reward),
+            format_version: self.format_version,
+            block_weight: self.block_weight,
+            state_root: &self.state_root,
+            root_chain_commitment,
+            fallback_slot: self.fallback_slot,
+            election_eligibility: self.election_eligibility,
+            delegation_root: &self.delegation_root,
+        }
+    }
+
+    fn root_chain_commitment(&self) -> Option<String> {
+        (!self.root_chain.is_empty()).then(|| self.root_chain.commitment())
+    }
+
+    /// Returns the canonical encoding of every field of the header but the
+    /// miner's signature, which is what the miner signs. See
+    /// [HeaderFields::signing_preimage] for the encoding, where the root
+    /// chain is encoded as its [RootChain::commitment] unless it is empty.
+    pub fn signing_preimage(&self) -> Vec<u8> {
+        let root_chain_commitment = self.root_chain_commitment();
+
+        self.verifier_fields(root_chain_commitment.as_deref())
+            .signing_preimage()
+    }
+
+    /// Returns the message the miner signs, the SHA-256 digest of
+    /// [BlockHeader::signing_preimage]
+    pub fn signing_payload(&self) -> Message {
+        Message::from(s256::Hash::hash(&self.signing_preimage()))
+    }
+
+    /// Returns the root chain the headers of child blocks carry: this
+    /// header's own, with its state root appended if it commits to one
+    pub fn next_root_chain(&self) -> RootChain {
+        if self.state_root.is_empty() {
+            return self.root_chain.clone();
+        }
+
+        self.root_chain.appended(&self.state_root)
+    }
+
+    /// Checks that the header carries the root chain of `previous`, the
+    /// header of the block it builds upon, extended with its state root
+    pub fn verify_root_chain(&self, previous: &BlockHeader) -> Result<(), BlockError> {
+        if self.root_chain != previous.next_root_chain() {
+            return Err(BlockError::new(InvalidBlockErrorReason::InvalidRootChain));
+        }
+
+        Ok(())
+    }
+
+    /// Commits the header to the root of the state trie once the block's txns
+    /// are applied, signing it again with the miner's secret key
+    pub fn commit_state_root(&mut self, state_root: String, secret_key: &SecretKey) {
+        self.state_root = state_root;
+        self.sign(secret_key);
+    }
+
+    /// Commits the header to the root the delegation state of every claim
+    /// has once the block is applied, signing it again with the miner's
+    /// secret key
+    pub fn commit_delegation_root(&mut self, delegation_root: String, secret_key: &SecretKey) {
+        self.delegation_root = delegation_root;
+        self.sign(secret_key);
+    }
+
+    /// Records the slot of the round's miner schedule the block is mined in,
+    /// signing the header again with the miner's secret key
+    pub fn assign_fallback_slot(&mut self, fallback_slot: u16, secret_key: &SecretKey) {
+        self.fallback_slot = fallback_slot;
+        self.sign(secret_key);
+    }
+
+    /// Signs the header with the miner's secret key
+    pub fn sign(&mut self, secret_key: &SecretKey) {
+        self.miner_signature = secret_key.sign_ecdsa(self.signing_payload()).to_string();
+    }
+
+    /// Returns the hash of the block the header belongs to: the hex encoded
+    /// SHA-256 digest of [BlockHeader::signing_preimage] followed by the
+    /// miner's signature, encoded as a string.
+    pub fn hash(&self) -> BlockHash {
+        let root_chain_commitment = self.root_chain_commitment();
+
+        self.verifier_fields(root_chain_commitment.as_deref())
+            .hash(&self.miner_signature)
+    }
+
+    /// Checks the header was signed by the miner whose claim it carries and
+    /// that `block_hash` is the hash of the block it belongs to.
+    pub fn verify(&self, block_hash: &str) -> Result<(), BlockError> {
+        let root_chain_commitment = self.root_chain_commitment();
+
+        self.verifier_fields(root_chain_commitment.as_deref())
+            .verify(&self.miner_signature, block_hash)
+            .map_err(|err| match err {
+                VerifyError::BlockHashMismatch { .. } => {
+                    BlockError::new(InvalidBlockErrorReason::InvalidBlockHash)
+                },
+                VerifyError::UnsupportedHeaderFormat(_) => {
+                    BlockError::new(InvalidBlockErrorReason::InvalidHeaderFormat)
+                },
+                _ => BlockError::new(InvalidBlockErrorReason::InvalidBlockSignature),
+            })
+    }
+
+    /// Checks that the header uses the layout `header_format` sets for its
+    /// height, so headers can't drop the fields the layout commits to
+    pub fn verify_format(&self, header_format: &HeaderFormatParams) -> Result<(), BlockError> {
+        if self.format_version != format_version_at(header_format, self.block_height) {
+            return Err(BlockError::new(
+                InvalidBlockErrorReason::InvalidHeaderFormat,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the block weight does not exceed `max_block_weight`
+    pub fn verify_weight(&self, max_block_weight: Weight) -> Result<(), BlockError> {
+        if self.block_weight > max_block_weight {
+            return Err(BlockError::new(InvalidBlockErrorReason::BlockTooHeavy));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the block and next block rewards of this header follow the
+    /// reward schedule, given the header of the block it builds upon.
+    pub fn verify_rewards(
+        &self,
+        previous: &BlockHeader,
+        reward_schedule: &RewardSchedule,
+    ) -> Result<(), RewardError> {
+        reward_schedule.verify(
+            &previous.next_block_reward,
+            &self.block_reward,
+            &self.next_block_reward,
+        )
+    }
+
     pub fn get_payload(&self) -> Message {
         create_payload!(
             self.ref_hashes,
@@ -228,3 +445,220 @@ impl BlockHeader {
         serde_json::from_str(data).unwrap()
     }
 }
+
+fn full_election_eligibility() -> u64 {
+    ELECTION_ELIGIBILITY_SCALE
+}
+
+fn format_version_at(header_format: &HeaderFormatParams, block_height: u128) -> u16 {
+    if header_format.is_active_at(block_height) {
+        TAGGED_HEADER_FORMAT
+    } else {
+        LEGACY_HEADER_FORMAT
+    }
+}
+
+fn reward_fields(reward: &Reward) -> RewardFields {
+    RewardFields {
+        epoch: reward.epoch,
+        next_epoch_block: reward.next_epoch_block,
+        current_block: reward.current_block,
+        miner: reward.miner.as_deref(),
+        amount: reward.amount,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use primitives::Address;
+    use secp256k1::Secp256k1;
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+
+    const GOLDEN_SIGNING_PAYLOAD: &str =
+        "e982cce511d262edf83156a4568bfccdfbdef472a9e364212fd8a28c532ac300";
+    const GOLDEN_BLOCK_HASH: &str =
+        "c45524f5a8686e6079007043dd43c26b3869af5bd52162cbd9fb42c45e10be79";
+
+    fn secret_key() -> SecretKey {
+        let mut bytes = [0u8; 32];
+        bytes[31] = 1;
+
+        SecretKey::from_slice(&bytes).unwrap()
+    }
+
+    fn golden_header() -> BlockHeader {
+        let secret_key = secret_key();
+        let public_key = secret_key.public_key(&Secp256k1::new());
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            secret_key.secret_bytes().to_vec(),
+        )
+        .unwrap();
+
+        let miner_claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature,
+            "node-1".to_string(),
+        )
+        .unwrap();
+
+        BlockHeader {
+            ref_hashes: vec!["ref-1".to_string(), "ref-2".to_string()],
+            epoch: 1,
+            round: 2,
+            block_seed: 3,
+            next_block_seed: 4,
+            block_height: 5,
+            timestamp: 1_700_000_000,
+            txn_hash: "txn-hash".to_string(),
+            miner_claim,
+            claim_list_hash: "claim-list-hash".to_string(),
+            block_reward: Reward {
+                epoch: 1,
+                next_epoch_block: 30_000_000,
+                current_block: 5,
+                miner: Some("miner".to_string()),
+                amount: 20,
+            },
+            next_block_reward: Reward {
+                epoch: 1,
+                next_epoch_block: 30_000_000,
+                current_block: 6,
+                miner: None,
+                amount: 20,
+            },
+            format_version: LEGACY_HEADER_FORMAT,
+            block_weight: 1_234,
+            state_root: String::new(),
+            root_chain: RootChain::default(),
+            fallback_slot: 0,
+            election_eligibility: ELECTION_ELIGIBILITY_SCALE,
+            delegation_root: String::new(),
+            miner_signature: "signature".to_string(),
+        }
+    }
+
+    fn tagged_header() -> BlockHeader {
+        BlockHeader {
+            format_version: TAGGED_HEADER_FORMAT,
+            ..golden_header()
+        }
+    }
+
+    #[test]
+    fn header_hashes_match_golden_vectors() {
+        let header = golden_header();
+
+        assert_eq!(
+            hex::encode(Sha256::digest(header.signing_preimage())),
+            GOLDEN_SIGNING_PAYLOAD
+        );
+        assert_eq!(
+            header.signing_payload(),
+            Message::from_slice(&hex::decode(GOLDEN_SIGNING_PAYLOAD).unwrap()).unwrap()
+        );
+        assert_eq!(header.hash(), GOLDEN_BLOCK_HASH);
+    }
+
+    #[test]
+    fn signed_headers_verify_until_tampered_with() {
+        let mut header = golden_header();
+        header.sign(&secret_key());
+
+        let hash = header.hash();
+        assert!(header.verify(&hash).is_ok());
+
+        let err = header.verify(GOLDEN_BLOCK_HASH).unwrap_err();
+        assert!(matches!(
+            err.reason,
+            InvalidBlockErrorReason::InvalidBlockHash
+        ));
+
+        header.block_height += 1;
+        let err = header.verify(&header.hash()).unwrap_err();
+        assert!(matches!(
+            err.reason,
+            InvalidBlockErrorReason::InvalidBlockSignature
+        ));
+    }
+
+    #[test]
+    fn only_tagged_headers_commit_to_their_weight() {
+        let mut legacy = golden_header();
+        legacy.block_weight += 1;
+        assert_eq!(legacy.hash(), GOLDEN_BLOCK_HASH);
+
+        let mut tagged = tagged_header();
+        let hash = tagged.hash();
+        assert_ne!(hash, GOLDEN_BLOCK_HASH);
+
+        tagged.block_weight += 1;
+        assert_ne!(tagged.hash(), hash);
+    }
+
+    #[test]
+    fn headers_use_the_layout_active_at_their_height() {
+        let header_format = HeaderFormatParams {
+            tagged_fields_activation_height: 5,
+            ..Default::default()
+        };
+
+        assert!(tagged_header().verify_format(&header_format).is_ok());
+
+        let err = golden_header().verify_format(&header_format).unwrap_err();
+        assert!(matches!(
+            err.reason,
+            InvalidBlockErrorReason::InvalidHeaderFormat
+        ));
+
+        let mut below_activation = golden_header();
+        below_activation.block_height = 4;
+        assert!(below_activation.verify_format(&header_format).is_ok());
+    }
+
+    #[test]
+    fn headers_commit_to_their_state_root() {
+        let mut header = tagged_header();
+        header.commit_state_root("state-root".to_string(), &secret_key());
+
+        let hash = header.hash();
+        assert_ne!(hash, GOLDEN_BLOCK_HASH);
+        assert!(header.verify(&hash).is_ok());
+
+        header.state_root = "other-state-root".to_string();
+        let err = header.verify(&header.hash()).unwrap_err();
+        assert!(matches!(
+            err.reason,
+            InvalidBlockErrorReason::InvalidBlockSignature
+        ));
+    }
+
+    #[test]
+    fn headers_commit_to_their_fallback_slot() {
+        let mut header = tagged_header();
+        header.sign(&secret_key());
+        let winner_hash = header.hash();
+
+        header.assign_fallback_slot(2, &secret_key());
+
+        let hash = header.hash();
+        assert_ne!(hash, winner_hash);
+        assert!(header.verify(&hash).is_ok());
+
+        header.fallback_slot = 0;
+        let err = header.verify(&header.hash()).unwrap_err();
+        assert!(matches!(
+            err.reason,
+            InvalidBlockErrorReason::InvalidBlockSignature
+        ));
+    }
+}
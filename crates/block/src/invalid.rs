@@ -29,8 +29,16 @@ pub enum InvalidBlockErrorReason {
     InvalidNextBlockReward,
     #[error("invalid block signature")]
     InvalidBlockSignature,
+    #[error("invalid block hash")]
+    InvalidBlockHash,
     #[error("too many txns in block")]
     InvalidBlockSize,
+    #[error("block weight exceeds the block weight cap")]
+    BlockTooHeavy,
+    #[error("root chain does not extend the previous block's")]
+    InvalidRootChain,
+    #[error("header does not use the layout required at its height")]
+    InvalidHeaderFormat,
     #[error("general invalid block error")]
     General,
 }
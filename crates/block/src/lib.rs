@@ -1,15 +1,23 @@
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
 pub mod block;
+pub mod checkpoint;
 pub mod convergence_block;
+pub mod epoch_summary;
 pub mod genesis;
+pub mod halt;
 pub mod header;
 pub mod invalid;
 pub mod proposal_block;
+pub mod root_chain;
+pub mod txn_order;
 pub mod vesting;
 
 mod types;
 
 pub use crate::{
-    block::*, convergence_block::*, genesis::*, proposal_block::*, types::*, vesting::*,
+    block::*, checkpoint::*, convergence_block::*, epoch_summary::*, genesis::*, halt::*,
+    proposal_block::*, types::*, vesting::*,
 };
 
 pub mod valid {
@@ -105,6 +113,8 @@ pub mod valid {
                 self.epoch,
                 hashable_txns,
                 self.claims,
+                self.inclusion_list,
+                self.halt_directives,
                 self.from
             )
             .to_vec()
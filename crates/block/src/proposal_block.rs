@@ -1,6 +1,6 @@
 use hbbft::crypto::SIG_SIZE;
 use hex::FromHexError;
-use primitives::Epoch;
+use primitives::{Epoch, Weight};
 use ritelinked::LinkedHashSet;
 use serde::{Deserialize, Serialize};
 use utils::hash_data;
@@ -10,7 +10,10 @@ use vrrb_core::{
     keypair::{Keypair, MinerSk},
 };
 
-use crate::{BlockHash, ClaimList, ConvergenceBlock, QuorumCertifiedTxnList, RefHash};
+use crate::{
+    txn_order::{canonical_txn_order, is_strictly_ascending},
+    BlockHash, ClaimList, ConvergenceBlock, QuorumCertifiedTxnList, RefHash, SignedHaltDirective,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize, Hash, Eq, PartialEq)]
 #[repr(C)]
@@ -20,6 +23,17 @@ pub struct ProposalBlock {
     pub epoch: Epoch,
     pub txns: QuorumCertifiedTxnList,
     pub claims: ClaimList,
+
+    /// Certified txns the harvester saw farmers list for inclusion, which
+    /// convergence blocks may only leave out for a bounded number of rounds
+    /// once a certified block references this one
+    #[serde(default)]
+    pub inclusion_list: Vec<TransactionDigest>,
+
+    /// Halt directives the harvester quorum certified that no block recorded
+    /// yet. Every node logs them once a certified block references this one
+    #[serde(default)]
+    pub halt_directives: Vec<SignedHaltDirective>,
     pub from: Claim,
     pub hash: BlockHash,
     pub signature: String,
@@ -43,6 +57,9 @@ impl ProposalBlock {
     /// * `claims`: `claims` is a list of claims made by validators in the
     ///   network. It is used as one of
     /// the inputs to calculate the hash of the block being proposed.
+    /// * `inclusion_list`: digests of the certified txns farmers listed for
+    ///   inclusion, signed along with the rest of the block.
+    /// * `halt_directives`: certified halt directives for the chain to record.
     /// * `from`: The `from` parameter is of type `Claim` and represents the
     ///   claim of the harvester who is
     /// proposing the block. It is used to sign the block proposal and ensure
@@ -54,22 +71,50 @@ impl ProposalBlock {
     ///
     /// Returns:
     ///
-    /// a `ProposalBlock` object.
+    /// a `ProposalBlock` object, listing `txns` and `inclusion_list` in
+    /// canonical order and `halt_directives` by the round they were issued at,
+    /// then by their payload hash.
+    #[allow(clippy::too_many_arguments)]
     pub fn build(
         ref_block: RefHash,
         round: u128,
         epoch: Epoch,
         txns: QuorumCertifiedTxnList,
         claims: ClaimList,
+        mut inclusion_list: Vec<TransactionDigest>,
+        mut halt_directives: Vec<SignedHaltDirective>,
         from: Claim,
         secret_key: &MinerSk,
     ) -> ProposalBlock {
+        let txns = canonical_txn_order(txns);
         let hashable_txns: Vec<(String, QuorumCertifiedTxn)> = {
             txns.iter()
                 .map(|(k, v)| (k.digest_string(), v.clone()))
                 .collect()
         };
-        let payload = hash_data!(round, epoch, hashable_txns, claims, from);
+        inclusion_list.sort();
+        inclusion_list.dedup();
+        // NOTE: copies of a directive certified under different quorum keys are
+        // ordered by key and signature, so every proposer lists them alike
+        halt_directives.sort_by_cached_key(|signed| {
+            (
+                signed.directive.round,
+                signed.directive.payload_hash(),
+                signed.quorum_public_key,
+                signed.signature.clone(),
+            )
+        });
+        halt_directives.dedup();
+
+        let payload = hash_data!(
+            round,
+            epoch,
+            hashable_txns,
+            claims,
+            inclusion_list,
+            halt_directives,
+            from
+        );
         let signature = if let Ok(signature) =
             Keypair::ecdsa_sign(&payload, secret_key.secret_bytes().to_vec())
         {
@@ -83,6 +128,8 @@ impl ProposalBlock {
             epoch,
             hashable_txns,
             claims,
+            inclusion_list,
+            halt_directives,
             from,
             signature
         ));
@@ -93,12 +140,31 @@ impl ProposalBlock {
             epoch,
             txns,
             claims,
+            inclusion_list,
+            halt_directives,
             hash,
             from,
             signature,
         }
     }
 
+    /// Returns whether hashing the block's contents along with its signature
+    /// again yields the block's hash, like [ProposalBlock::build] hashed them
+    pub fn matches_hash(&self) -> bool {
+        let hash = hex::encode(hash_data!(
+            self.round,
+            self.epoch,
+            self.get_hashable_txns(),
+            self.claims,
+            self.inclusion_list,
+            self.halt_directives,
+            self.from,
+            self.signature
+        ));
+
+        hash == self.hash
+    }
+
     pub fn is_current_round(&self, round: u128) -> bool {
         self.round == round
     }
@@ -161,4 +227,17 @@ impl ProposalBlock {
     pub fn txn_id_set(&self) -> LinkedHashSet<TransactionDigest> {
         self.txns.iter().map(|(id, _)| id.clone()).collect()
     }
+
+    /// Returns whether the block lists its txns in canonical order, see
+    /// [crate::txn_order]
+    pub fn has_canonical_txn_order(&self) -> bool {
+        is_strictly_ascending(self.txns.keys())
+    }
+
+    /// Returns the cumulative weight of the block's txns
+    pub fn weight(&self) -> Weight {
+        self.txns
+            .values()
+            .fold(0, |weight, txn| weight.saturating_add(txn.txn().weight()))
+    }
 }
@@ -0,0 +1,225 @@
+//! The root chain commits every convergence header to the state roots of
+//! the blocks before it, so light clients holding a single trusted header can
+//! check that a historical state root belongs to the canonical chain and ask
+//! for account proofs against it.
+//!
+//! The roots are accumulated in a Merkle mountain range: a list of perfect
+//! binary Merkle trees, the peaks, of strictly decreasing height. Appending a
+//! root only touches the smallest peaks, so every header can derive its root
+//! chain from its parent's alone.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+type Hash = [u8; 32];
+
+fn leaf_hash(state_root: &str) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update(state_root.as_bytes());
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn decode_hash(value: &str) -> Option<Hash> {
+    hex::decode(value).ok()?.try_into().ok()
+}
+
+/// Finds the mountain holding leaf `index` of a range of `len` leaves.
+/// Returns its position among the peaks, its height and the index of its
+/// first leaf
+fn locate_mountain(len: u64, index: u64) -> Option<(usize, u32, u64)> {
+    let mut offset = 0;
+
+    (0..u64::BITS)
+        .rev()
+        .filter(|height| (len >> height) & 1 == 1)
+        .enumerate()
+        .find_map(|(peak_index, height)| {
+            let start = offset;
+            offset += 1 << height;
+
+            (index < offset).then_some((peak_index, height, start))
+        })
+}
+
+/// Merkle mountain range over the state roots of a chain, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RootChain {
+    /// Number of state roots accumulated
+    pub len: u64,
+
+    /// Hex encoded roots of the mountains, tallest first
+    pub peaks: Vec<String>,
+}
+
+impl RootChain {
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends the next state root of the chain
+    pub fn append(&mut self, state_root: &str) {
+        let mut peak = leaf_hash(state_root);
+        let mut height = 0;
+
+        while (self.len >> height) & 1 == 1 {
+            let Some(left) = self.peaks.pop().as_deref().and_then(decode_hash) else {
+                break;
+            };
+
+            peak = node_hash(&left, &peak);
+            height += 1;
+        }
+
+        self.peaks.push(hex::encode(peak));
+        self.len += 1;
+    }
+
+    /// Returns the root chain once `state_root` is appended to it
+    pub fn appended(&self, state_root: &str) -> Self {
+        let mut root_chain = self.clone();
+        root_chain.append(state_root);
+        root_chain
+    }
+
+    /// Returns the hex encoded SHA-256 digest of the number of roots, as 8
+    /// little-endian bytes, followed by every peak. This is what headers
+    /// commit to
+    pub fn commitment(&self) -> String {
+        block_verifier::root_chain_commitment(self.len, &self.peaks)
+    }
+
+    /// Proves that the root at `index` of `state_roots`, the full list of
+    /// roots the chain accumulated, belongs to it. Returns None if `index` is
+    /// out of bounds
+    pub fn prove(state_roots: &[String], index: u64) -> Option<RootChainProof> {
+        let len = state_roots.len() as u64;
+        if index >= len {
+            return None;
+        }
+
+        let (_, height, offset) = locate_mountain(len, index)?;
+
+        let mut level: Vec<Hash> = state_roots[offset as usize..(offset + (1 << height)) as usize]
+            .iter()
+            .map(|state_root| leaf_hash(state_root))
+            .collect();
+
+        let mut position = (index - offset) as usize;
+        let mut siblings = Vec::with_capacity(height as usize);
+
+        while level.len() > 1 {
+            siblings.push(hex::encode(level[position ^ 1]));
+
+            level = level
+                .chunks(2)
+                .map(|pair| node_hash(&pair[0], &pair[1]))
+                .collect();
+            position /= 2;
+        }
+
+        let mut root_chain = RootChain::default();
+        state_roots
+            .iter()
+            .for_each(|state_root| root_chain.append(state_root));
+
+        Some(RootChainProof {
+            index,
+            siblings,
+            root_chain,
+        })
+    }
+}
+
+/// Shows a state root was accumulated by a root chain at a given position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RootChainProof {
+    /// Position of the root within the chain, oldest first
+    pub index: u64,
+
+    /// Hex encoded siblings on the path from the root's leaf up to the peak
+    /// of its mountain, lowest first
+    pub siblings: Vec<String>,
+
+    /// The root chain the proof was made against
+    pub root_chain: RootChain,
+}
+
+impl RootChainProof {
+    /// Checks `state_root` is accumulated at the proof's position by the root
+    /// chain a header commits to with `commitment`
+    pub fn verify(&self, state_root: &str, commitment: &str) -> bool {
+        let root_chain = &self.root_chain;
+
+        if root_chain.commitment() != commitment {
+            return false;
+        }
+
+        let Some((peak_index, height, offset)) = locate_mountain(root_chain.len, self.index) else {
+            return false;
+        };
+
+        if self.siblings.len() != height as usize {
+            return false;
+        }
+
+        let mut position = self.index - offset;
+        let mut hash = leaf_hash(state_root);
+
+        for sibling in self.siblings.iter() {
+            let Some(sibling) = decode_hash(sibling) else {
+                return false;
+            };
+
+            hash = if position & 1 == 0 {
+                node_hash(&hash, &sibling)
+            } else {
+                node_hash(&sibling, &hash)
+            };
+            position >>= 1;
+        }
+
+        root_chain
+            .peaks
+            .get(peak_index)
+            .map_or(false, |peak| *peak == hex::encode(hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_accumulated_root_can_be_proven_against_the_commitment() {
+        let state_roots: Vec<String> = (0..11).map(|round| format!("root-{round}")).collect();
+
+        let mut root_chain = RootChain::default();
+        state_roots
+            .iter()
+            .for_each(|state_root| root_chain = root_chain.appended(state_root));
+
+        // NOTE: 11 roots make mountains of 8, 2 and 1 leaves
+        assert_eq!(root_chain.peaks.len(), 3);
+
+        let commitment = root_chain.commitment();
+
+        for (index, state_root) in state_roots.iter().enumerate() {
+            let proof = RootChain::prove(&state_roots, index as u64).unwrap();
+
+            assert_eq!(proof.root_chain, root_chain);
+            assert!(proof.verify(state_root, &commitment));
+            assert!(!proof.verify("forged-root", &commitment));
+        }
+
+        assert!(RootChain::prove(&state_roots, 11).is_none());
+    }
+}
@@ -0,0 +1,117 @@
+//! Canonical order of the txns within blocks.
+//!
+//! Proposal blocks list their txns in ascending digest order. Convergence
+//! blocks list the proposal blocks they consolidate in ascending hash order
+//! and, under each of them, its txns in ascending digest order. Blocks are
+//! built in this order and rejected when they aren't in it, so every node
+//! hashes and executes a block's txns the same way, no matter the order the
+//! txns reached it in.
+
+use ritelinked::{LinkedHashMap, LinkedHashSet};
+use vrrb_core::transactions::TransactionDigest;
+
+use crate::{ConsolidatedTxns, RefHash};
+
+/// Collects `txns` into a map listing them in canonical order
+pub fn canonical_txn_order<T>(
+    txns: impl IntoIterator<Item = (TransactionDigest, T)>,
+) -> LinkedHashMap<TransactionDigest, T> {
+    let mut txns: Vec<(TransactionDigest, T)> = txns.into_iter().collect();
+    txns.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    txns.into_iter().collect()
+}
+
+/// Collects consolidated txns into a map listing them in canonical order
+pub fn canonical_consolidated_txn_order(
+    txns: impl IntoIterator<Item = (RefHash, LinkedHashSet<TransactionDigest>)>,
+) -> ConsolidatedTxns {
+    let mut txns: Vec<(RefHash, Vec<TransactionDigest>)> = txns
+        .into_iter()
+        .map(|(ref_hash, digests)| (ref_hash, digests.into_iter().collect()))
+        .collect();
+
+    txns.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    txns.into_iter()
+        .map(|(ref_hash, mut digests)| {
+            digests.sort();
+            (ref_hash, digests.into_iter().collect())
+        })
+        .collect()
+}
+
+/// Returns whether `items` are in strictly ascending order, which also rules
+/// out duplicates
+pub fn is_strictly_ascending<'a, T: Ord + 'a>(items: impl IntoIterator<Item = &'a T>) -> bool {
+    let mut items = items.into_iter();
+
+    let Some(mut previous) = items.next() else {
+        return true;
+    };
+
+    for item in items {
+        if item <= previous {
+            return false;
+        }
+
+        previous = item;
+    }
+
+    true
+}
+
+/// Returns whether consolidated txns are listed in canonical order
+pub fn is_canonical_consolidated_txn_order(txns: &ConsolidatedTxns) -> bool {
+    is_strictly_ascending(txns.keys())
+        && txns
+            .values()
+            .all(|digests| is_strictly_ascending(digests.iter()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(byte: u8) -> TransactionDigest {
+        TransactionDigest::from(vec![byte; 32])
+    }
+
+    #[test]
+    fn txns_are_ordered_by_digest_whatever_order_they_were_collected_in() {
+        let txns = canonical_txn_order(vec![(digest(3), "c"), (digest(1), "a"), (digest(2), "b")]);
+
+        assert!(is_strictly_ascending(txns.keys()));
+        assert_eq!(
+            txns.values().copied().collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn consolidated_txns_are_ordered_by_ref_hash_then_digest() {
+        let txns = canonical_consolidated_txn_order(vec![
+            (
+                "proposal-b".to_string(),
+                LinkedHashSet::from_iter([digest(2), digest(1)]),
+            ),
+            (
+                "proposal-a".to_string(),
+                LinkedHashSet::from_iter([digest(4), digest(3)]),
+            ),
+        ]);
+
+        assert!(is_canonical_consolidated_txn_order(&txns));
+        assert_eq!(
+            txns.keys().cloned().collect::<Vec<_>>(),
+            vec!["proposal-a".to_string(), "proposal-b".to_string()]
+        );
+
+        let mut reordered = txns.clone();
+        reordered.insert(
+            "proposal-c".to_string(),
+            LinkedHashSet::from_iter([digest(6), digest(5)]),
+        );
+        assert!(!is_canonical_consolidated_txn_order(&reordered));
+    }
+}
@@ -8,7 +8,7 @@ use std::{
 };
 
 use hex::FromHexError;
-use primitives::RawSignature;
+use primitives::{QuorumId, QuorumPubkey, RawSignature};
 #[cfg(mainnet)]
 use reward::reward::GENESIS_REWARD;
 use ritelinked::{LinkedHashMap, LinkedHashSet};
@@ -24,7 +24,7 @@ use crate::genesis;
 
 pub const GROSS_UTILITY_PERCENTAGE: f64 = 0.01;
 pub const PERCENTAGE_CHANGE_SUPPLY_CAP: f64 = 0.25;
-pub const EPOCH_BLOCK: u32 = 30_000_000;
+pub const EPOCH_BLOCK: u32 = primitives::DEFAULT_EPOCH_LENGTH as u32;
 
 pub type CurrentUtility = i128;
 pub type NextEpochAdjustment = i128;
@@ -36,8 +36,6 @@ pub type ClaimList = LinkedHashMap<ClaimHash, Claim>;
 pub type ConsolidatedTxns = LinkedHashMap<RefHash, LinkedHashSet<TransactionDigest>>;
 pub type ConsolidatedClaims = LinkedHashMap<RefHash, LinkedHashSet<ClaimHash>>;
 pub type BlockHash = String;
-pub type QuorumId = String;
-pub type QuorumPubkey = String;
 pub type QuorumPubkeys = LinkedHashMap<QuorumId, QuorumPubkey>;
 pub type ConflictList = HashMap<TransactionDigest, Conflict>;
 pub type ResolvedConflicts = Vec<JoinHandle<Result<Conflict, Box<dyn Error>>>>;
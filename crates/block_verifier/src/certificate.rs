@@ -0,0 +1,121 @@
+//! Checks of the threshold signatures quorums certify blocks and other
+//! payloads with.
+use hbbft::crypto::{PublicKey, Signature, PK_SIZE, SIG_SIZE};
+
+use crate::{
+    error::{VerifyError, VerifyResult},
+    header::HeaderFields,
+};
+
+/// Checks that `signature` is the threshold signature of the quorum holding
+/// `group_public_key` over `payload`
+pub fn verify_threshold_signature(
+    group_public_key: &[u8],
+    payload: &[u8],
+    signature: &[u8],
+) -> VerifyResult<()> {
+    let public_key = TryInto::<[u8; PK_SIZE]>::try_into(group_public_key)
+        .map_err(|_| VerifyError::InvalidGroupPublicKey(format!("size must be {PK_SIZE} bytes")))
+        .and_then(|public_key| {
+            PublicKey::from_bytes(public_key)
+                .map_err(|err| VerifyError::InvalidGroupPublicKey(err.to_string()))
+        })?;
+
+    let signature = TryInto::<[u8; SIG_SIZE]>::try_into(signature)
+        .map_err(|_| {
+            VerifyError::InvalidThresholdSignature(format!("size must be {SIG_SIZE} bytes"))
+        })
+        .and_then(|signature| {
+            Signature::from_bytes(signature)
+                .map_err(|err| VerifyError::InvalidThresholdSignature(err.to_string()))
+        })?;
+
+    if !public_key.verify(&signature, payload) {
+        return Err(VerifyError::ThresholdSignatureMismatch);
+    }
+
+    Ok(())
+}
+
+/// Checks that the hex encoded `signature` is the threshold signature of the
+/// quorum holding `group_public_key` over the hex encoded `block_hash`, which
+/// is what harvesters sign when they certify a block
+pub fn verify_block_signature(
+    group_public_key: &[u8],
+    block_hash: &str,
+    signature: &str,
+) -> VerifyResult<()> {
+    let payload =
+        hex::decode(block_hash).map_err(|err| VerifyError::InvalidBlockHash(err.to_string()))?;
+
+    let signature = hex::decode(signature)
+        .map_err(|err| VerifyError::InvalidThresholdSignature(err.to_string()))?;
+
+    verify_threshold_signature(group_public_key, &payload, &signature)
+}
+
+/// Checks a block end to end: that its header was signed by its miner, that
+/// `block_hash` is the hash of the block, and that the quorum holding
+/// `group_public_key` certified that hash
+pub fn verify_certified_header(
+    header: &HeaderFields,
+    miner_signature: &str,
+    block_hash: &str,
+    certificate_signature: &str,
+    group_public_key: &[u8],
+) -> VerifyResult<()> {
+    header.verify(miner_signature, block_hash)?;
+
+    verify_block_signature(group_public_key, block_hash, certificate_signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use hbbft::crypto::SecretKey;
+    use secp256k1::Secp256k1;
+
+    use super::*;
+    use crate::header::tests::{fields, secret_key, sign};
+
+    #[test]
+    fn only_blocks_certified_by_the_trusted_quorum_verify() {
+        let group_secret_key = SecretKey::random();
+        let group_public_key = group_secret_key.public_key().to_bytes();
+
+        let miner_secret_key = secret_key();
+        let miner_public_key = miner_secret_key.public_key(&Secp256k1::new()).serialize();
+        let ref_hashes = vec!["ref-1".to_string()];
+
+        let header = fields(&ref_hashes, miner_public_key);
+        let miner_signature = sign(&header, &miner_secret_key);
+        let block_hash = header.hash(&miner_signature);
+
+        let payload = hex::decode(&block_hash).unwrap();
+        let certificate_signature = hex::encode(group_secret_key.sign(&payload).to_bytes());
+
+        verify_certified_header(
+            &header,
+            &miner_signature,
+            &block_hash,
+            &certificate_signature,
+            &group_public_key,
+        )
+        .unwrap();
+
+        let other_public_key = SecretKey::random().public_key().to_bytes();
+        assert_eq!(
+            verify_block_signature(&other_public_key, &block_hash, &certificate_signature),
+            Err(VerifyError::ThresholdSignatureMismatch)
+        );
+
+        assert!(matches!(
+            verify_block_signature(&group_public_key, &block_hash, "abcd"),
+            Err(VerifyError::InvalidThresholdSignature(_))
+        ));
+
+        assert!(matches!(
+            verify_threshold_signature(&group_public_key[1..], &payload, &[0; SIG_SIZE]),
+            Err(VerifyError::InvalidGroupPublicKey(_))
+        ));
+    }
+}
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+/// Reasons a block, certificate or txn fails verification.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VerifyError {
+    #[error("invalid miner signature: {0}")]
+    InvalidMinerSignature(String),
+
+    #[error("block hash {claimed} doesn't match the header, which hashes to {actual}")]
+    BlockHashMismatch { claimed: String, actual: String },
+
+    #[error("header format {0} is not supported")]
+    UnsupportedHeaderFormat(u16),
+
+    #[error("invalid group public key: {0}")]
+    InvalidGroupPublicKey(String),
+
+    #[error("invalid block hash: {0}")]
+    InvalidBlockHash(String),
+
+    #[error("invalid threshold signature: {0}")]
+    InvalidThresholdSignature(String),
+
+    #[error("quorum signature doesn't match the signed payload")]
+    ThresholdSignatureMismatch,
+
+    #[error("txn digest {claimed} doesn't match the txn, which digests to {actual}")]
+    TxnDigestMismatch { claimed: String, actual: String },
+}
+
+pub type VerifyResult<T> = Result<T, VerifyError>;
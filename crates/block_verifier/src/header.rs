@@ -0,0 +1,420 @@
+//! Hashing and miner signature checks of block headers.
+use std::str::FromStr;
+
+use secp256k1::{ecdsa::Signature, Message, PublicKey, Secp256k1};
+use sha2::{Digest, Sha256};
+
+use crate::error::{VerifyError, VerifyResult};
+
+/// Layout of headers mined before the tagged one activated, which leaves out
+/// every field added to headers since launch
+pub const LEGACY_HEADER_FORMAT: u16 = 0;
+
+/// Layout that follows the legacy fields with the ones added since launch,
+/// each behind a tag
+pub const TAGGED_HEADER_FORMAT: u16 = 1;
+
+const BLOCK_WEIGHT_TAG: u8 = 1;
+const STATE_ROOT_TAG: u8 = 2;
+const ROOT_CHAIN_COMMITMENT_TAG: u8 = 3;
+const FALLBACK_SLOT_TAG: u8 = 4;
+const ELECTION_ELIGIBILITY_TAG: u8 = 5;
+const DELEGATION_ROOT_TAG: u8 = 6;
+
+/// A block reward, as recorded by a header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RewardFields<'a> {
+    pub epoch: u128,
+    pub next_epoch_block: u128,
+    pub current_block: u128,
+
+    /// Address of the miner rewarded, if any
+    pub miner: Option<&'a str>,
+    pub amount: u128,
+}
+
+/// The fields of a block header its hash commits to, borrowed from however
+/// the header was decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderFields<'a> {
+    pub ref_hashes: &'a [String],
+    pub epoch: u128,
+    pub round: u128,
+    pub block_seed: u64,
+    pub next_block_seed: u64,
+    pub block_height: u128,
+    pub timestamp: i64,
+    pub txn_hash: &'a str,
+
+    /// Hash of the miner's claim, as 32 big-endian bytes
+    pub miner_claim_hash: [u8; 32],
+
+    /// Compressed public key of the miner's claim, which the miner signs the
+    /// header with
+    pub miner_public_key: [u8; 33],
+    pub claim_list_hash: &'a str,
+    pub block_reward: RewardFields<'a>,
+    pub next_block_reward: RewardFields<'a>,
+
+    /// Either [LEGACY_HEADER_FORMAT] or [TAGGED_HEADER_FORMAT]. The fields
+    /// below are only committed to by the tagged layout
+    pub format_version: u16,
+    pub block_weight: u64,
+
+    /// Hex encoded state root, empty for headers predating the commitment
+    pub state_root: &'a str,
+
+    /// [root_chain_commitment] of the header's root chain, None if the chain
+    /// is empty
+    pub root_chain_commitment: Option<&'a str>,
+    pub fallback_slot: u16,
+
+    /// Share of the election result space, in parts per million, the
+    /// election of the block's child starts from
+    pub election_eligibility: u64,
+
+    /// Hex encoded delegation root, empty for headers predating delegation
+    pub delegation_root: &'a str,
+}
+
+impl HeaderFields<'_> {
+    /// Returns the canonical encoding of the header, which is what the miner
+    /// signs.
+    ///
+    /// Fields are encoded in declaration order. Integers are little-endian
+    /// and fixed-width, strings are their UTF-8 bytes prefixed by their length
+    /// as a `u64`, and lists are prefixed by their element count as a `u64`.
+    /// The miner's claim is encoded as its hash followed by its public key.
+    /// Rewards are encoded field by field, with a missing miner encoded as a
+    /// single `0` byte and a present one as a `1` byte followed by the
+    /// miner's address.
+    ///
+    /// Headers in the legacy layout end there, so blocks mined before the
+    /// tagged layout keep their hashes. Headers in any other layout go on with
+    /// their format version, then the block weight, state root, root chain
+    /// commitment, fallback slot, election eligibility and delegation root.
+    /// Each of those is encoded as a tag byte naming it and a presence byte,
+    /// followed, when the presence byte is `1`, by the length of the field as
+    /// a `u64` and its bytes. An empty state root or delegation root and a
+    /// missing root chain commitment are absent, the other fields are always
+    /// present.
+    pub fn signing_preimage(&self) -> Vec<u8> {
+        let mut preimage = Vec::new();
+
+        preimage.extend((self.ref_hashes.len() as u64).to_le_bytes());
+        self.ref_hashes
+            .iter()
+            .for_each(|ref_hash| encode_str(&mut preimage, ref_hash));
+
+        preimage.extend(self.epoch.to_le_bytes());
+        preimage.extend(self.round.to_le_bytes());
+        preimage.extend(self.block_seed.to_le_bytes());
+        preimage.extend(self.next_block_seed.to_le_bytes());
+        preimage.extend(self.block_height.to_le_bytes());
+        preimage.extend(self.timestamp.to_le_bytes());
+        encode_str(&mut preimage, self.txn_hash);
+
+        preimage.extend(self.miner_claim_hash);
+        preimage.extend(self.miner_public_key);
+
+        encode_str(&mut preimage, self.claim_list_hash);
+        encode_reward(&mut preimage, &self.block_reward);
+        encode_reward(&mut preimage, &self.next_block_reward);
+
+        if self.format_version == LEGACY_HEADER_FORMAT {
+            return preimage;
+        }
+
+        preimage.extend(self.format_version.to_le_bytes());
+
+        encode_tagged(
+            &mut preimage,
+            BLOCK_WEIGHT_TAG,
+            Some(&self.block_weight.to_le_bytes()),
+        );
+        encode_tagged(
+            &mut preimage,
+            STATE_ROOT_TAG,
+            (!self.state_root.is_empty()).then_some(self.state_root.as_bytes()),
+        );
+        encode_tagged(
+            &mut preimage,
+            ROOT_CHAIN_COMMITMENT_TAG,
+            self.root_chain_commitment.map(str::as_bytes),
+        );
+        encode_tagged(
+            &mut preimage,
+            FALLBACK_SLOT_TAG,
+            Some(&self.fallback_slot.to_le_bytes()),
+        );
+        encode_tagged(
+            &mut preimage,
+            ELECTION_ELIGIBILITY_TAG,
+            Some(&self.election_eligibility.to_le_bytes()),
+        );
+        encode_tagged(
+            &mut preimage,
+            DELEGATION_ROOT_TAG,
+            (!self.delegation_root.is_empty()).then_some(self.delegation_root.as_bytes()),
+        );
+
+        preimage
+    }
+
+    /// Returns the SHA-256 digest of [HeaderFields::signing_preimage], which
+    /// is the message the miner signs
+    pub fn signing_digest(&self) -> [u8; 32] {
+        Sha256::digest(self.signing_preimage()).into()
+    }
+
+    /// Returns the hash of the block the header belongs to: the hex encoded
+    /// SHA-256 digest of [HeaderFields::signing_preimage] followed by the
+    /// miner's signature, encoded as a string
+    pub fn hash(&self, miner_signature: &str) -> String {
+        let mut preimage = self.signing_preimage();
+        encode_str(&mut preimage, miner_signature);
+
+        hex::encode(Sha256::digest(&preimage))
+    }
+
+    /// Checks the header was signed by the miner whose claim it carries.
+    /// `miner_signature` is the DER encoded ECDSA signature, hex encoded
+    pub fn verify_miner_signature(&self, miner_signature: &str) -> VerifyResult<()> {
+        if self.format_version > TAGGED_HEADER_FORMAT {
+            return Err(VerifyError::UnsupportedHeaderFormat(self.format_version));
+        }
+
+        let signature = Signature::from_str(miner_signature)
+            .map_err(|err| VerifyError::InvalidMinerSignature(err.to_string()))?;
+
+        let public_key = PublicKey::from_slice(&self.miner_public_key)
+            .map_err(|err| VerifyError::InvalidMinerSignature(err.to_string()))?;
+
+        let message = Message::from_slice(&self.signing_digest())
+            .map_err(|err| VerifyError::InvalidMinerSignature(err.to_string()))?;
+
+        Secp256k1::verification_only()
+            .verify_ecdsa(&message, &signature, &public_key)
+            .map_err(|err| VerifyError::InvalidMinerSignature(err.to_string()))
+    }
+
+    /// Checks the header was signed by its miner and that `block_hash` is the
+    /// hash of the block it belongs to
+    pub fn verify(&self, miner_signature: &str, block_hash: &str) -> VerifyResult<()> {
+        self.verify_miner_signature(miner_signature)?;
+
+        let actual = self.hash(miner_signature);
+        if actual != block_hash {
+            return Err(VerifyError::BlockHashMismatch {
+                claimed: block_hash.to_string(),
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns what headers commit to of a root chain holding `len` state roots
+/// whose mountains have the hex encoded `peaks`, tallest first: the hex
+/// encoded SHA-256 digest of `len`, as 8 little-endian bytes, followed by
+/// every peak
+pub fn root_chain_commitment(len: u64, peaks: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(len.to_le_bytes());
+    peaks.iter().for_each(|peak| hasher.update(peak.as_bytes()));
+
+    hex::encode(hasher.finalize())
+}
+
+fn encode_str(preimage: &mut Vec<u8>, value: &str) {
+    preimage.extend((value.len() as u64).to_le_bytes());
+    preimage.extend(value.as_bytes());
+}
+
+fn encode_tagged(preimage: &mut Vec<u8>, tag: u8, value: Option<&[u8]>) {
+    preimage.push(tag);
+
+    match value {
+        Some(value) => {
+            preimage.push(1);
+            preimage.extend((value.len() as u64).to_le_bytes());
+            preimage.extend(value);
+        },
+        None => preimage.push(0),
+    }
+}
+
+fn encode_reward(preimage: &mut Vec<u8>, reward: &RewardFields) {
+    preimage.extend(reward.epoch.to_le_bytes());
+    preimage.extend(reward.next_epoch_block.to_le_bytes());
+    preimage.extend(reward.current_block.to_le_bytes());
+
+    match reward.miner {
+        Some(miner) => {
+            preimage.push(1);
+            encode_str(preimage, miner);
+        },
+        None => preimage.push(0),
+    }
+
+    preimage.extend(reward.amount.to_le_bytes());
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use secp256k1::SecretKey;
+
+    use super::*;
+
+    pub(crate) fn secret_key() -> SecretKey {
+        let mut bytes = [0u8; 32];
+        bytes[31] = 1;
+
+        SecretKey::from_slice(&bytes).unwrap()
+    }
+
+    pub(crate) fn fields(ref_hashes: &[String], public_key: [u8; 33]) -> HeaderFields<'_> {
+        let reward = RewardFields {
+            epoch: 1,
+            next_epoch_block: 30_000_000,
+            current_block: 4,
+            miner: Some("miner-address"),
+            amount: 20,
+        };
+
+        HeaderFields {
+            ref_hashes,
+            epoch: 1,
+            round: 2,
+            block_seed: 3,
+            next_block_seed: 4,
+            block_height: 4,
+            timestamp: 1_700_000_000,
+            txn_hash: "txn-hash",
+            miner_claim_hash: [7; 32],
+            miner_public_key: public_key,
+            claim_list_hash: "claim-list-hash",
+            block_reward: reward,
+            next_block_reward: RewardFields {
+                current_block: 5,
+                miner: None,
+                ..reward
+            },
+            format_version: TAGGED_HEADER_FORMAT,
+            block_weight: 1_000,
+            state_root: "",
+            root_chain_commitment: None,
+            fallback_slot: 0,
+            election_eligibility: 1_000_000,
+            delegation_root: "",
+        }
+    }
+
+    /// Signs `fields` like miners do, returning the signature
+    pub(crate) fn sign(fields: &HeaderFields, secret_key: &SecretKey) -> String {
+        let message = Message::from_slice(&fields.signing_digest()).unwrap();
+
+        secret_key.sign_ecdsa(message).to_string()
+    }
+
+    #[test]
+    fn headers_signed_by_their_miner_verify() {
+        let secret_key = secret_key();
+        let public_key = secret_key.public_key(&Secp256k1::new()).serialize();
+        let ref_hashes = vec!["ref-1".to_string(), "ref-2".to_string()];
+
+        let fields = fields(&ref_hashes, public_key);
+        let miner_signature = sign(&fields, &secret_key);
+        let block_hash = fields.hash(&miner_signature);
+
+        fields.verify(&miner_signature, &block_hash).unwrap();
+
+        assert!(matches!(
+            fields.verify(&miner_signature, "not-the-hash"),
+            Err(VerifyError::BlockHashMismatch { .. })
+        ));
+
+        let tampered = HeaderFields {
+            block_weight: 1_001,
+            ..fields
+        };
+        assert!(matches!(
+            tampered.verify(&miner_signature, &block_hash),
+            Err(VerifyError::InvalidMinerSignature(_))
+        ));
+    }
+
+    #[test]
+    fn legacy_headers_leave_out_the_fields_added_since_launch() {
+        let public_key = secret_key().public_key(&Secp256k1::new()).serialize();
+        let ref_hashes = vec!["ref-1".to_string()];
+
+        let tagged = fields(&ref_hashes, public_key);
+        let legacy = HeaderFields {
+            format_version: LEGACY_HEADER_FORMAT,
+            ..tagged
+        };
+
+        assert!(tagged
+            .signing_preimage()
+            .starts_with(&legacy.signing_preimage()));
+
+        let weighed_differently = HeaderFields {
+            block_weight: 2_000,
+            ..legacy
+        };
+        assert_eq!(
+            weighed_differently.signing_preimage(),
+            legacy.signing_preimage()
+        );
+
+        assert!(matches!(
+            HeaderFields {
+                format_version: TAGGED_HEADER_FORMAT + 1,
+                ..tagged
+            }
+            .verify_miner_signature(&sign(&tagged, &secret_key())),
+            Err(VerifyError::UnsupportedHeaderFormat(_))
+        ));
+    }
+
+    #[test]
+    fn tagged_fields_encode_unambiguously() {
+        let public_key = secret_key().public_key(&Secp256k1::new()).serialize();
+        let ref_hashes = vec!["ref-1".to_string()];
+
+        let fields = fields(&ref_hashes, public_key);
+
+        let with_state_root = HeaderFields {
+            state_root: "root",
+            ..fields
+        };
+        let with_root_chain = HeaderFields {
+            root_chain_commitment: Some("root"),
+            ..fields
+        };
+        let with_delegation_root = HeaderFields {
+            delegation_root: "root",
+            ..fields
+        };
+
+        assert_ne!(
+            with_state_root.signing_preimage(),
+            with_root_chain.signing_preimage()
+        );
+        assert_ne!(
+            with_state_root.signing_preimage(),
+            with_delegation_root.signing_preimage()
+        );
+        assert_ne!(with_state_root.hash("sig"), fields.hash("sig"));
+        assert_ne!(
+            HeaderFields {
+                fallback_slot: 1,
+                ..fields
+            }
+            .hash("sig"),
+            fields.hash("sig")
+        );
+    }
+}
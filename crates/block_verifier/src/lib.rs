@@ -0,0 +1,14 @@
+//! Verification of blocks, block certificates and txn digests for services
+//! that don't run a node, such as exchanges crediting deposits.
+//!
+//! Everything here is pure: no IO, no async runtime and no node state, only
+//! the hashing and signature checks nodes themselves run. Inputs are plain
+//! borrowed fields rather than node types, so callers can decode blocks and
+//! txns however they like, and the API doesn't change along with the node's
+//! internals.
+pub mod certificate;
+pub mod error;
+pub mod header;
+pub mod txn;
+
+pub use crate::{certificate::*, error::*, header::*, txn::*};
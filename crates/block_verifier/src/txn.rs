@@ -0,0 +1,114 @@
+//! Checks of the digests transfers are identified and signed by.
+use sha2::{Digest, Sha256};
+
+use crate::error::{VerifyError, VerifyResult};
+
+/// The token a transfer moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenFields<'a> {
+    pub name: &'a str,
+    pub symbol: &'a str,
+    pub decimals: u8,
+}
+
+/// The fields of a transfer its digest commits to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferFields<'a> {
+    pub timestamp: i64,
+    pub sender_address: &'a str,
+
+    /// Hex encoded compressed public key of the sender
+    pub sender_public_key: &'a str,
+    pub receiver_address: &'a str,
+    pub token: TokenFields<'a>,
+    pub amount: u128,
+    pub nonce: u128,
+    pub memo: Option<&'a [u8]>,
+}
+
+impl TransferFields<'_> {
+    /// Returns the string the transfer's digest is the SHA-256 hash of: its
+    /// fields joined by commas, with the token written out like
+    /// `Token { name: "VRRB", symbol: "VRRB", decimals: 18 }`, and the hex
+    /// encoded memo appended if there is one
+    pub fn digest_preimage(&self) -> String {
+        let mut preimage = format!(
+            "{},{},{},{},{},Token {{ name: {:?}, symbol: {:?}, decimals: {:?} }},{}",
+            self.timestamp,
+            self.sender_address,
+            self.sender_public_key,
+            self.receiver_address,
+            self.amount,
+            self.token.name,
+            self.token.symbol,
+            self.token.decimals,
+            self.nonce
+        );
+
+        // NOTE: transfers without a memo keep the digest they had before memos existed
+        if let Some(memo) = self.memo {
+            preimage.push_str(&format!(",{}", hex::encode(memo)));
+        }
+
+        preimage
+    }
+
+    /// Returns the digest the transfer is identified and signed by
+    pub fn digest(&self) -> [u8; 32] {
+        Sha256::digest(self.digest_preimage()).into()
+    }
+
+    /// Checks that the hex encoded `digest` is the digest of the transfer
+    pub fn verify_digest(&self, digest: &str) -> VerifyResult<()> {
+        let actual = hex::encode(self.digest());
+        if !actual.eq_ignore_ascii_case(digest) {
+            return Err(VerifyError::TxnDigestMismatch {
+                claimed: digest.to_string(),
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfer_digests_commit_to_every_field() {
+        let transfer = TransferFields {
+            timestamp: 1_700_000_000,
+            sender_address: "sender",
+            sender_public_key: "02abcd",
+            receiver_address: "receiver",
+            token: TokenFields {
+                name: "VRRB",
+                symbol: "VRRB",
+                decimals: 18,
+            },
+            amount: 10,
+            nonce: 1,
+            memo: None,
+        };
+
+        assert_eq!(
+            transfer.digest_preimage(),
+            "1700000000,sender,02abcd,receiver,10,Token { name: \"VRRB\", symbol: \"VRRB\", \
+             decimals: 18 },1"
+        );
+
+        let digest = hex::encode(transfer.digest());
+        transfer.verify_digest(&digest).unwrap();
+
+        let with_memo = TransferFields {
+            memo: Some(b"deposit-42"),
+            ..transfer
+        };
+        assert!(matches!(
+            with_memo.verify_digest(&digest),
+            Err(VerifyError::TxnDigestMismatch { .. })
+        ));
+    }
+}
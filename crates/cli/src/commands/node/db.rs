@@ -0,0 +1,154 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+};
+
+use clap::{Parser, Subcommand};
+use primitives::{DbBackend, DEFAULT_VRRB_DB_PATH};
+use serde::Serialize;
+use storage::vrrbdb::{self, MigrationRegistry, VrrbDbConfig, DEFAULT_STATE_ARCHIVE_CHUNK_SIZE};
+
+use crate::result::{CliError, Result};
+
+#[derive(Debug, Subcommand)]
+pub enum DbCmd {
+    /// Shows the trie roots, record counts and latest certified block stored
+    /// in the database. The DAG and mempool are kept in memory only, so
+    /// they can't be inspected here
+    Inspect,
+
+    /// Hashes the values of every trie again and compares the result to the
+    /// roots the tries hold
+    Verify,
+
+    /// Deletes every record that can't be decoded. Whatever those records held
+    /// is lost
+    Repair,
+
+    /// Reclaims the space held by overwritten and deleted records
+    Compact,
+
+    /// Streams the state to a versioned, checksummed archive that nodes
+    /// running later releases can import
+    ExportState {
+        #[clap(long, value_parser)]
+        output: PathBuf,
+
+        /// Number of accounts per chunk of the archive
+        #[clap(long, value_parser, default_value_t = DEFAULT_STATE_ARCHIVE_CHUNK_SIZE)]
+        chunk_size: usize,
+    },
+
+    /// Imports a state archive into a database whose state is empty,
+    /// migrating accounts exported by older releases
+    ImportState {
+        #[clap(long, value_parser)]
+        input: PathBuf,
+    },
+
+    /// Compares the state to a state archive exported by a peer, listing the
+    /// accounts whose values differ and where the state tries part ways
+    DiffState {
+        #[clap(long, value_parser)]
+        peer: PathBuf,
+    },
+}
+
+/// Inspects and repairs the database of a node that is not running
+#[derive(Parser, Debug)]
+pub struct DbOpts {
+    #[clap(long, value_parser, default_value = DEFAULT_VRRB_DB_PATH)]
+    pub db_path: PathBuf,
+
+    /// Key-value store the database is kept in, either `rocksdb` or `sled`
+    #[clap(long, value_parser, default_value = "rocksdb")]
+    pub db_backend: DbBackend,
+
+    #[clap(subcommand)]
+    pub subcommand: DbCmd,
+}
+
+pub fn exec_db(opts: DbOpts) -> Result<()> {
+    let config = VrrbDbConfig::default()
+        .with_path(opts.db_path)
+        .with_backend(opts.db_backend);
+
+    // NOTE: state archives are usually imported into a brand new database
+    let imports_state = matches!(opts.subcommand, DbCmd::ImportState { .. });
+
+    if !imports_state && !config.path.exists() {
+        return Err(CliError::OptsError(format!(
+            "no database found at {}",
+            config.path.display()
+        )));
+    }
+
+    match opts.subcommand {
+        DbCmd::Inspect => print_json(&vrrbdb::inspect_db(&config)?),
+        DbCmd::Verify => {
+            let verifications = vrrbdb::verify_db(&config)?;
+
+            print_json(&verifications)?;
+
+            let inconsistent = verifications
+                .iter()
+                .filter(|verification| !verification.is_consistent())
+                .count();
+
+            if inconsistent > 0 {
+                return Err(CliError::Other(format!(
+                    "{inconsistent} trie(s) don't match their stored root"
+                )));
+            }
+
+            Ok(())
+        },
+        DbCmd::Repair => print_json(&vrrbdb::repair_db(&config)?),
+        DbCmd::Compact => {
+            vrrbdb::compact_db(&config)?;
+
+            println!("compacted {}", config.path.display());
+
+            Ok(())
+        },
+        DbCmd::ExportState { output, chunk_size } => {
+            let archive = BufWriter::new(File::create(output)?);
+
+            print_json(&vrrbdb::export_state_archive(&config, archive, chunk_size)?)
+        },
+        DbCmd::ImportState { input } => {
+            let archive = BufReader::new(File::open(input)?);
+
+            print_json(&vrrbdb::import_state_archive(
+                &config,
+                archive,
+                &MigrationRegistry::default(),
+            )?)
+        },
+        DbCmd::DiffState { peer } => {
+            let archive = BufReader::new(File::open(peer)?);
+            let diff = vrrbdb::diff_state_archive(&config, archive, &MigrationRegistry::default())?;
+
+            print_json(&diff)?;
+
+            if !diff.is_empty() {
+                return Err(CliError::Other(format!(
+                    "states diverge on {} account(s)",
+                    diff.accounts.divergences.len()
+                )));
+            }
+
+            Ok(())
+        },
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    let value = serde_json::to_string_pretty(value)
+        .map_err(|err| CliError::Other(format!("unable to serialize report: {err}")))?;
+
+    println!("{value}");
+
+    Ok(())
+}
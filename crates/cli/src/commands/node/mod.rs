@@ -1,7 +1,9 @@
+mod db;
 mod info;
 mod run;
 
 use clap::{Parser, Subcommand};
+pub use db::*;
 pub use info::*;
 pub use run::*;
 
@@ -17,6 +19,9 @@ pub enum NodeCmd {
 
     /// Stops any node currrently running in dettached mode
     Stop,
+
+    /// Inspects, verifies and repairs a node's local database
+    Db(DbOpts),
 }
 
 #[derive(Parser, Debug)]
@@ -31,6 +36,7 @@ pub async fn exec(args: NodeOpts) -> Result<()> {
     match sub_cmd {
         NodeCmd::Run(opts) => run(*opts).await,
         NodeCmd::Info => Ok(()),
+        NodeCmd::Db(opts) => exec_db(opts),
         _ => Err(CliError::InvalidCommand(format!("{sub_cmd:?}"))),
     }
 }
@@ -5,11 +5,14 @@ use std::{
 
 use config::{Config, ConfigError, File};
 use node::Node;
-use primitives::{NodeType, DEFAULT_VRRB_DATA_DIR_PATH, DEFAULT_VRRB_DB_PATH};
+use primitives::{
+    AddressFamily, ChainSpec, Locality, NodeType, PruningMode, QuorumAssignmentStrategy,
+    DEFAULT_VRRB_DATA_DIR_PATH, DEFAULT_VRRB_DB_PATH,
+};
 use serde::Deserialize;
 use telemetry::{info, warn};
 use uuid::Uuid;
-use vrrb_config::NodeConfig;
+use vrrb_config::{NetworkAddressConfig, NodeConfig};
 use vrrb_core::keypair::{read_keypair_file, write_keypair_file, Keypair};
 
 use crate::result::{CliError, Result};
@@ -83,6 +86,11 @@ pub struct RunOpts {
     #[clap(long, action, default_value = "false")]
     pub disable_networking: bool,
 
+    /// Serves chain data over a read-only REST gateway on the HTTP API
+    /// address
+    #[clap(long, action, default_value = "false")]
+    pub enable_rest_gateway: bool,
+
     #[clap(long, value_parser ,default_value=DEFAULT_OS_ASSIGNED_PORT_ADDRESS)]
     pub rendezvous_local_address: SocketAddr,
 
@@ -91,6 +99,47 @@ pub struct RunOpts {
 
     #[clap(long, value_parser, default_value = DEFAULT_OS_ASSIGNED_PORT_ADDRESS)]
     pub public_ip_address: SocketAddr,
+
+    /// Path to a JSON chain spec describing the network to join
+    #[clap(long, value_parser)]
+    pub chain_spec: Option<PathBuf>,
+
+    /// Keeps the state of every past round queryable instead of pruning old
+    /// rounds
+    #[clap(long, action, default_value = "false")]
+    pub archive: bool,
+
+    /// Region this node runs in, e.g. `us-east`
+    #[clap(long, value_parser)]
+    pub region: Option<String>,
+
+    /// Availability zone this node runs in, e.g. `us-east-1a`
+    #[clap(long, value_parser)]
+    pub zone: Option<String>,
+
+    /// How validators are spread across quorums when this node forms them,
+    /// either `sequential` or `region_spread`
+    #[clap(long, value_parser)]
+    pub quorum_assignment_strategy: Option<QuorumAssignmentStrategy>,
+
+    /// Listens on both IPv4 and IPv6 wherever the node is configured to
+    /// listen on the unspecified IPv4 address
+    #[clap(long, action, default_value = "false")]
+    pub dual_stack: bool,
+
+    /// Logs secret keys, key shares, signatures and peer payloads as they are.
+    /// Only allowed in local environments
+    #[clap(long, action, default_value = "false")]
+    pub disable_log_redaction: bool,
+
+    /// Address family tried first when dialing peers, either `ipv4` or `ipv6`
+    #[clap(long, value_parser)]
+    pub preferred_address_family: Option<AddressFamily>,
+
+    /// Further addresses peers can reach the node's UDP gossip at. A port of 0
+    /// stands for the port the node binds to
+    #[clap(long, value_parser)]
+    pub advertised_udp_gossip_addresses: Option<Vec<SocketAddr>>,
 }
 
 impl From<RunOpts> for NodeConfig {
@@ -108,6 +157,12 @@ impl From<RunOpts> for NodeConfig {
             default_node_config.http_api_title.clone()
         };
 
+        let pruning_mode = if opts.archive {
+            PruningMode::Archive
+        } else {
+            default_node_config.pruning_mode
+        };
+
         Self {
             id: opts.id.unwrap_or(default_node_config.id),
             data_dir: opts.data_dir,
@@ -121,8 +176,10 @@ impl From<RunOpts> for NodeConfig {
             http_api_version: opts.http_api_version,
             http_api_shutdown_timeout: default_node_config.http_api_shutdown_timeout,
             jsonrpc_server_address: opts.jsonrpc_api_address,
+            public_rpc_config: default_node_config.public_rpc_config,
             preload_mock_state: default_node_config.preload_mock_state,
             bootstrap_config: default_node_config.bootstrap_config,
+            bootstrap_failover_config: default_node_config.bootstrap_failover_config,
             kademlia_liveness_address: default_node_config.kademlia_liveness_address,
             kademlia_peer_id: default_node_config.kademlia_peer_id,
 
@@ -138,7 +195,45 @@ impl From<RunOpts> for NodeConfig {
             bootstrap_quorum_config: default_node_config.bootstrap_quorum_config,
             quorum_config: default_node_config.quorum_config,
             enable_block_indexing: default_node_config.enable_block_indexing,
+            enable_rest_gateway: opts.enable_rest_gateway,
             threshold_config: default_node_config.threshold_config,
+            quorum_safety_mode: default_node_config.quorum_safety_mode,
+            mailbox_config: default_node_config.mailbox_config,
+            mempool_sync_config: default_node_config.mempool_sync_config,
+            mempool_admission_config: default_node_config.mempool_admission_config,
+            mempool_compaction_config: default_node_config.mempool_compaction_config,
+            chain_scrubber_config: default_node_config.chain_scrubber_config,
+            txn_validity_config: default_node_config.txn_validity_config,
+            signing_pool_config: default_node_config.signing_pool_config,
+            certificate_sync_config: default_node_config.certificate_sync_config,
+            peer_registration_config: default_node_config.peer_registration_config,
+            peer_store_config: default_node_config.peer_store_config,
+            message_credits_config: default_node_config.message_credits_config,
+            rebroadcast_config: default_node_config.rebroadcast_config,
+            notification_config: default_node_config.notification_config,
+            round_gc_config: default_node_config.round_gc_config,
+            block_import_config: default_node_config.block_import_config,
+            invariants_config: default_node_config.invariants_config,
+            checkpoint_config: default_node_config.checkpoint_config,
+            replication_config: default_node_config.replication_config,
+            preflight_config: default_node_config.preflight_config,
+            chain_spec: default_node_config.chain_spec,
+            pruning_mode,
+            certification_retention: default_node_config.certification_retention,
+            consensus_timeline_rounds: default_node_config.consensus_timeline_rounds,
+            db_backend: default_node_config.db_backend,
+            locality: Locality::new(opts.region, opts.zone),
+            quorum_assignment_strategy: opts
+                .quorum_assignment_strategy
+                .unwrap_or(default_node_config.quorum_assignment_strategy),
+            network_address_config: NetworkAddressConfig {
+                dual_stack: opts.dual_stack,
+                preferred_family: opts.preferred_address_family.unwrap_or_default(),
+                advertised_udp_gossip_addrs: opts
+                    .advertised_udp_gossip_addresses
+                    .unwrap_or_default(),
+            },
+            disable_log_redaction: opts.disable_log_redaction,
         }
     }
 }
@@ -167,9 +262,19 @@ impl Default for RunOpts {
             http_api_version: Default::default(),
             gui: Default::default(),
             disable_networking: Default::default(),
+            enable_rest_gateway: Default::default(),
             rendezvous_local_address: ipv4_localhost_with_random_port,
             rendezvous_server_address: ipv4_localhost_with_random_port,
             public_ip_address: ipv4_localhost_with_random_port,
+            chain_spec: Default::default(),
+            archive: Default::default(),
+            region: Default::default(),
+            zone: Default::default(),
+            quorum_assignment_strategy: Default::default(),
+            dual_stack: Default::default(),
+            disable_log_redaction: Default::default(),
+            preferred_address_family: Default::default(),
+            advertised_udp_gossip_addresses: Default::default(),
         }
     }
 }
@@ -255,9 +360,26 @@ impl RunOpts {
             http_api_version,
             gui: false,
             disable_networking: false,
+            enable_rest_gateway: self.enable_rest_gateway || other.enable_rest_gateway,
             rendezvous_local_address: other.rendezvous_local_address,
             rendezvous_server_address: other.rendezvous_server_address,
             public_ip_address: other.public_ip_address,
+            chain_spec: self.chain_spec.clone().or(other.chain_spec.clone()),
+            archive: self.archive || other.archive,
+            region: self.region.clone().or(other.region.clone()),
+            zone: self.zone.clone().or(other.zone.clone()),
+            quorum_assignment_strategy: self
+                .quorum_assignment_strategy
+                .or(other.quorum_assignment_strategy),
+            dual_stack: self.dual_stack || other.dual_stack,
+            disable_log_redaction: self.disable_log_redaction || other.disable_log_redaction,
+            preferred_address_family: self
+                .preferred_address_family
+                .or(other.preferred_address_family),
+            advertised_udp_gossip_addresses: self
+                .advertised_udp_gossip_addresses
+                .clone()
+                .or(other.advertised_udp_gossip_addresses.clone()),
         }
     }
 }
@@ -286,6 +408,10 @@ pub async fn run(args: RunOpts) -> Result<()> {
     let mut node_config = NodeConfig::from(args.clone());
     node_config.keypair = keypair;
 
+    if let Some(chain_spec_path) = &args.chain_spec {
+        node_config.chain_spec = ChainSpec::from_file(chain_spec_path)?;
+    }
+
     if args.debug_config {
         dbg!(&node_config);
     }
@@ -20,6 +20,9 @@ pub enum CliError {
     #[error("storage error: {0}")]
     Storage(#[from] vrrb_core::storage_utils::StorageError),
 
+    #[error("state archive error: {0}")]
+    StateArchive(#[from] storage::vrrbdb::StateArchiveError),
+
     #[error("primitive error: {0}")]
     Primitive(#[from] primitives::Error),
 
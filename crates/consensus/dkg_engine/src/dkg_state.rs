@@ -7,10 +7,9 @@ use hbbft::{
     sync_key_gen::{Ack, Part, SyncKeyGen},
 };
 use primitives::NodeId;
-use rand::rngs::OsRng;
 
 use crate::{
-    prelude::{ReceiverId, SenderId},
+    prelude::{DkgRng, ReceiverId, SenderId},
 };
 
 #[derive(Debug, Default)]
@@ -21,7 +20,8 @@ pub struct DkgState {
     public_key_set: Option<PublicKeySet>,
     secret_key_share: Option<SecretKeyShare>,
     sync_key_gen: Option<SyncKeyGen<NodeId>>,
-    random_number_gen: Option<OsRng>,
+    random_number_gen: Option<DkgRng>,
+    threshold: usize,
 }
 
 impl DkgState {
@@ -37,6 +37,7 @@ impl DkgState {
         self.public_key_set = None;
         self.peer_public_keys.clear();
         self.secret_key_share = None;
+        self.threshold = 0;
     }
 
     pub fn part_message_store_owned(&self) -> HashMap<NodeId, Part> {
@@ -134,22 +135,32 @@ impl DkgState {
         self.sync_key_gen = sync_key_gen;
     }
 
-    pub fn random_number_gen_owned(&self) -> Option<OsRng> {
+    pub fn random_number_gen_owned(&self) -> Option<DkgRng> {
         self.random_number_gen.clone()
     }
 
-    pub fn random_number_gen(&self) -> &Option<OsRng> {
+    pub fn random_number_gen(&self) -> &Option<DkgRng> {
         &self.random_number_gen
     }
 
-    pub fn random_number_gen_mut(&mut self) -> &mut Option<OsRng> {
+    pub fn random_number_gen_mut(&mut self) -> &mut Option<DkgRng> {
         &mut self.random_number_gen
     }
 
-    pub fn set_random_number_gen(&mut self, random_number_gen: Option<OsRng>) {
+    pub fn set_random_number_gen(&mut self, random_number_gen: Option<DkgRng>) {
         self.random_number_gen = random_number_gen;
     }
 
+    /// Threshold of the part commitment this node generated for the current
+    /// round
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn set_threshold(&mut self, threshold: usize) {
+        self.threshold = threshold;
+    }
+
     pub fn add_peer_public_key(&mut self, node_id: NodeId, public_key: PublicKey) {
         self.peer_public_keys.insert(node_id, public_key);
     }
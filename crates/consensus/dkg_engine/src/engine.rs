@@ -1,15 +1,15 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use hbbft::{
     crypto::{PublicKey, SecretKey},
-    sync_key_gen::{Ack, Part, PartOutcome, SyncKeyGen},
+    sync_key_gen::{Ack, AckOutcome, Part, PartOutcome, SyncKeyGen},
 };
 use primitives::{NodeId, NodeType, ValidatorPublicKey};
-use rand::rngs::OsRng;
+use rayon::prelude::*;
 use vrrb_config::ThresholdConfig;
 
 use crate::{
-    prelude::{DkgGenerator, DkgState, ReceiverId, SenderId},
+    prelude::{DkgGenerator, DkgRng, DkgState, ReceiverId, SenderId},
     DkgError, Result,
 };
 
@@ -43,22 +43,26 @@ pub struct DkgEngine {
 
     /// Harvester Distributed  Group public key
     pub harvester_public_key: Option<PublicKey>,
+
+    /// randomness behind every part commitment and ack this node produces
+    pub rng: DkgRng,
 }
 
 impl Clone for DkgEngine {
     fn clone(&self) -> Self {
         let peer_public_keys = Arc::new(self.dkg_state.peer_public_keys().clone());
 
-        // TODO: fix unwraps
-        let mut rng = OsRng::new()
-            .map_err(|err| DkgError::Unknown(err.to_string()))
-            .unwrap();
+        // NOTE: a clone must not replay the randomness of the engine it was cloned from,
+        // so it draws its own from the OS
+        let mut rng = DkgRng::from_entropy();
+        let threshold = self.threshold_config().threshold as usize;
 
+        // TODO: fix unwraps
         let (sync_key_gen, _) = SyncKeyGen::new(
             self.node_id(),
             self.secret_key.clone(),
             peer_public_keys,
-            self.threshold_config().threshold as usize,
+            threshold,
             &mut rng,
         )
         .unwrap();
@@ -71,7 +75,13 @@ impl Clone for DkgEngine {
         dkg_state.set_public_key_set(self.dkg_state.public_key_set_owned());
         dkg_state.set_secret_key_share(self.dkg_state.secret_key_share_owned());
         dkg_state.set_sync_key_gen(Some(sync_key_gen));
-        dkg_state.set_random_number_gen(self.dkg_state.random_number_gen_owned());
+        dkg_state.set_threshold(threshold);
+        dkg_state.set_random_number_gen(
+            self.dkg_state
+                .random_number_gen()
+                .as_ref()
+                .map(|_| rng.fork()),
+        );
 
         Self {
             node_id: self.node_id.clone(),
@@ -80,6 +90,7 @@ impl Clone for DkgEngine {
             secret_key: self.secret_key.clone(),
             dkg_state,
             harvester_public_key: self.harvester_public_key,
+            rng,
         }
     }
 }
@@ -101,6 +112,7 @@ impl DkgEngine {
             threshold_config: config.threshold_config,
             dkg_state: DkgState::default(),
             harvester_public_key: None,
+            rng: DkgRng::from_entropy(),
         }
     }
 
@@ -126,6 +138,112 @@ impl DkgEngine {
     pub fn clear_state(&mut self) {
         self.dkg_state.clear();
     }
+
+    /// Checks the given acks across the rayon pool before any of them reaches
+    /// the `SyncKeyGen` instance, which can only take them one at a time.
+    ///
+    /// Acks are grouped by the part they acknowledge and every group is run
+    /// through a `SyncKeyGen` instance of its own. Acks are expected sorted by
+    /// `(receiver, sender)`, the first invalid ack in that order is the one
+    /// reported whichever thread caught it.
+    fn verify_acks(&self, acks: &[(ReceiverId, SenderId, Ack)]) -> Result<()> {
+        let peer_public_keys = Arc::new(self.dkg_state.peer_public_keys().clone());
+
+        let mut acks_by_part: BTreeMap<&SenderId, Vec<(usize, &ReceiverId, &Ack)>> =
+            BTreeMap::new();
+
+        for (position, (receiver_id, sender_id, ack)) in acks.iter().enumerate() {
+            acks_by_part
+                .entry(sender_id)
+                .or_default()
+                .push((position, receiver_id, ack));
+        }
+
+        acks_by_part
+            .into_par_iter()
+            .filter_map(|(sender_id, acks)| {
+                self.verify_part_acks(sender_id, acks, peer_public_keys.clone())
+                    .err()
+            })
+            .min_by_key(|(position, _)| *position)
+            .map_or(Ok(()), |(_, err)| Err(err))
+    }
+
+    /// Handles the part `sender_id` committed to on a throwaway `SyncKeyGen`
+    /// instance, then the acks for that part in order.
+    ///
+    /// Returns the position of the first invalid ack along with its error.
+    fn verify_part_acks(
+        &self,
+        sender_id: &SenderId,
+        acks: Vec<(usize, &ReceiverId, &Ack)>,
+        peer_public_keys: Arc<BTreeMap<NodeId, PublicKey>>,
+    ) -> std::result::Result<(), (usize, DkgError)> {
+        let first_position = acks.first().map_or(0, |(position, _, _)| *position);
+
+        let part = self
+            .dkg_state
+            .part_message_store()
+            .get(sender_id)
+            .cloned()
+            .ok_or_else(|| {
+                (
+                    first_position,
+                    DkgError::PartMsgMissingForNode(sender_id.clone()),
+                )
+            })?;
+
+        // NOTE: nothing this instance generates outlives the check, so it
+        // doesn't draw from the engine's (possibly seeded) randomness
+        let mut rng = DkgRng::from_entropy();
+
+        let (mut keygen, _) = SyncKeyGen::new(
+            self.node_id(),
+            self.secret_key.clone(),
+            peer_public_keys,
+            self.dkg_state.threshold(),
+            &mut rng,
+        )
+        .map_err(|err| {
+            (
+                first_position,
+                DkgError::SyncKeyGenError(format!("Failed to create verifying instance: {err}")),
+            )
+        })?;
+
+        match keygen.handle_part(sender_id, part, &mut rng) {
+            Ok(PartOutcome::Valid(_)) => {},
+            Ok(PartOutcome::Invalid(fault)) => {
+                return Err((
+                    first_position,
+                    DkgError::InvalidPartMessage(fault.to_string()),
+                ));
+            },
+            Err(err) => {
+                return Err((
+                    first_position,
+                    DkgError::Unknown(format!("failed to handle part from {sender_id}: {err}")),
+                ));
+            },
+        }
+
+        for (position, receiver_id, ack) in acks {
+            let problem = match keygen.handle_ack(receiver_id, ack.clone()) {
+                Ok(AckOutcome::Valid) => continue,
+                Ok(AckOutcome::Invalid(fault)) => fault.to_string(),
+                Err(err) => err.to_string(),
+            };
+
+            return Err((
+                position,
+                DkgError::InvalidAckMessage(format!(
+                    "from {sender_id} to {receiver_id}: {problem}"
+                )),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 impl DkgGenerator for DkgEngine {
@@ -149,14 +267,13 @@ impl DkgGenerator for DkgEngine {
         let node_id = self.node_id();
         let secret_key = self.secret_key.clone();
         let peer_public_keys = Arc::new(self.dkg_state.peer_public_keys().clone());
-        let mut rng = OsRng::new().map_err(|err| DkgError::Unknown(err.to_string()))?;
 
         let (sync_key_gen, opt_part) = SyncKeyGen::new(
             node_id.clone(),
             secret_key,
             peer_public_keys,
             threshold,
-            &mut rng,
+            &mut self.rng,
         )
         .map_err(|err| {
             DkgError::SyncKeyGenError(format!(
@@ -167,12 +284,13 @@ impl DkgGenerator for DkgEngine {
 
         let part_commitment = opt_part.ok_or(DkgError::PartCommitmentNotGenerated)?;
 
-        self.dkg_state.set_random_number_gen(Some(rng.clone()));
+        self.dkg_state.set_random_number_gen(Some(self.rng.fork()));
         self.dkg_state
             .part_message_store_mut()
             .insert(node_id.clone(), part_commitment.clone());
 
         self.dkg_state.set_sync_key_gen(Some(sync_key_gen));
+        self.dkg_state.set_threshold(threshold);
 
         // part_commitment has to be multicasted to all Farmers/Harvester Peers
         // within the Quorum
@@ -223,6 +341,8 @@ impl DkgGenerator for DkgEngine {
         let handed_part_result =
             node.handle_part(&sender_node_id, part_commitment.clone(), &mut rng);
 
+        self.dkg_state.set_random_number_gen(Some(rng));
+
         match handed_part_result {
             Ok(part_outcome) => match part_outcome {
                 PartOutcome::Valid(Some(ack)) => {
@@ -254,7 +374,13 @@ impl DkgGenerator for DkgEngine {
             .map(|((receiver_id, sender_id), ack)| (receiver_id, sender_id, ack))
             .collect::<Vec<(ReceiverId, SenderId, Ack)>>();
 
-        ack_message_store.sort_by_key(|entry| entry.0.to_owned());
+        ack_message_store.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+        if self.dkg_state.sync_key_gen().is_none() {
+            return Err(DkgError::SyncKeyGenInstanceNotCreated);
+        }
+
+        self.verify_acks(&ack_message_store)?;
 
         let keygen = self
             .dkg_state
@@ -270,8 +396,8 @@ impl DkgGenerator for DkgEngine {
                 })?;
 
             match result {
-                hbbft::sync_key_gen::AckOutcome::Valid => {},
-                hbbft::sync_key_gen::AckOutcome::Invalid(fault) => {
+                AckOutcome::Valid => {},
+                AckOutcome::Invalid(fault) => {
                     return Err(DkgError::InvalidAckMessage(format!(
                         "Invalid Ack Outcome for Node {:?},Fault: {:?} ,Idx:{:?}",
                         sender_id,
@@ -2,6 +2,8 @@ pub mod dkg;
 pub mod dkg_state;
 pub mod engine;
 pub mod result;
+pub mod rng;
+#[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
 
 pub use crate::result::*;
@@ -10,18 +12,26 @@ pub mod prelude {
     pub use crate::dkg::*;
     pub use crate::dkg_state::*;
     pub use crate::engine::*;
+    pub use crate::rng::*;
 }
 
 #[cfg(test)]
 mod tests {
     use std::{borrow::BorrowMut, collections::HashMap};
 
-    use hbbft::sync_key_gen::Ack;
+    use hbbft::{
+        crypto::{Ciphertext, PublicKeySet},
+        sync_key_gen::Ack,
+    };
     use primitives::{NodeId, NodeType};
     use vrrb_core::is_enum_variant;
 
     use crate::dkg::DkgGenerator;
-    use crate::{prelude::*, result::DkgError, test_utils::generate_dkg_engines};
+    use crate::{
+        prelude::*,
+        result::DkgError,
+        test_utils::{generate_dkg_engines, generate_seeded_dkg_engines},
+    };
 
     #[tokio::test]
     #[ignore]
@@ -286,6 +296,92 @@ mod tests {
         assert!(dkg_engine_node1.dkg_state.secret_key_share().is_some());
     }
 
+    #[tokio::test]
+    async fn seeded_dkg_rounds_are_reproducible() {
+        let first_run =
+            run_dkg_round(generate_seeded_dkg_engines(4, NodeType::MasterNode, 42).await);
+        let second_run =
+            run_dkg_round(generate_seeded_dkg_engines(4, NodeType::MasterNode, 42).await);
+        let other_seed_run =
+            run_dkg_round(generate_seeded_dkg_engines(4, NodeType::MasterNode, 43).await);
+
+        assert_eq!(first_run, second_run);
+        assert_ne!(first_run, other_seed_run);
+    }
+
+    #[tokio::test]
+    async fn clones_do_not_replay_the_randomness_of_their_engine() {
+        let mut dkg_engines = generate_seeded_dkg_engines(4, NodeType::MasterNode, 42).await;
+        let dkg_engine = dkg_engines.get_mut(0).unwrap();
+        let mut cloned_dkg_engine = dkg_engine.clone();
+
+        let (part, _) = dkg_engine.generate_partial_commitment(1).unwrap();
+        let (cloned_part, _) = cloned_dkg_engine.generate_partial_commitment(1).unwrap();
+
+        assert_ne!(
+            bincode::serialize(&part).unwrap(),
+            bincode::serialize(&cloned_part).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_acks_are_reported_in_receiver_sender_order() {
+        let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
+        let dkg_engine = dkg_engines.get_mut(0).unwrap();
+        let (_, node_id) = dkg_engine.generate_partial_commitment(1).unwrap();
+        dkg_engine.ack_partial_commitment(node_id.clone()).unwrap();
+
+        let malformed_ack: Ack =
+            bincode::deserialize(&bincode::serialize(&(0u64, Vec::<Ciphertext>::new())).unwrap())
+                .unwrap();
+
+        for receiver_id in ["node-3", "node-1", "node-2"] {
+            dkg_engine.dkg_state.ack_message_store_mut().insert(
+                (receiver_id.to_string(), node_id.clone()),
+                malformed_ack.clone(),
+            );
+        }
+
+        let err = dkg_engine.handle_ack_messages().unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with("Invalid ack message: from node-0 to node-1: "));
+    }
+
+    fn run_dkg_round(mut dkg_engines: Vec<DkgEngine>) -> PublicKeySet {
+        let parts = dkg_engines
+            .iter_mut()
+            .map(|dkg_engine| dkg_engine.generate_partial_commitment(1).unwrap())
+            .collect::<Vec<_>>();
+
+        for dkg_engine in dkg_engines.iter_mut() {
+            for (part, node_id) in parts.iter() {
+                dkg_engine
+                    .dkg_state
+                    .part_message_store_mut()
+                    .insert(node_id.clone(), part.clone());
+            }
+        }
+
+        let mut acks = HashMap::new();
+        for dkg_engine in dkg_engines.iter_mut() {
+            for (_, node_id) in parts.iter() {
+                let (receiver_id, sender_id, ack) =
+                    dkg_engine.ack_partial_commitment(node_id.clone()).unwrap();
+                acks.insert((receiver_id, sender_id), ack);
+            }
+        }
+
+        for dkg_engine in dkg_engines.iter_mut() {
+            dkg_engine.dkg_state.set_ack_message_store(acks.clone());
+            dkg_engine.handle_ack_messages().unwrap();
+            dkg_engine.generate_key_sets().unwrap();
+        }
+
+        dkg_engines[0].dkg_state.public_key_set_owned().unwrap()
+    }
+
     fn add_part_commitment_to_node_dkg_state(
         dkg_engine_node1: &mut DkgEngine,
         dkg_engine_node2: &mut DkgEngine,
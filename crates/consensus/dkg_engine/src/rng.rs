@@ -0,0 +1,74 @@
+use rand::{rngs::StdRng, CryptoRng, FromEntropy, RngCore, SeedableRng};
+
+/// Source of randomness used while generating and acknowledging DKG part
+/// commitments.
+///
+/// Seeding it makes entire DKG rounds reproducible, which is useful in tests
+/// and simulations. Unseeded instances draw their seed from the OS.
+#[derive(Debug, Clone)]
+pub struct DkgRng(StdRng);
+
+impl DkgRng {
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn new(seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => Self::seeded(seed),
+            None => Self::from_entropy(),
+        }
+    }
+
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+
+    pub fn from_entropy() -> Self {
+        Self(StdRng::from_entropy())
+    }
+
+    /// Derives an independent generator from this one, advancing its state
+    pub fn fork(&mut self) -> Self {
+        Self(StdRng::from_rng(&mut self.0).expect("seeding from a PRNG cannot fail"))
+    }
+}
+
+impl Default for DkgRng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+impl RngCore for DkgRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl CryptoRng for DkgRng {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_generators_are_reproducible() {
+        let mut a = DkgRng::seeded(7);
+        let mut b = DkgRng::seeded(7);
+
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_eq!(a.fork().next_u64(), b.fork().next_u64());
+        assert_ne!(a.next_u64(), DkgRng::seeded(8).next_u64());
+    }
+}
@@ -1,17 +1,18 @@
 use std::collections::{BTreeMap, HashMap};
 
 use hbbft::{
-    crypto::{serde_impl::SerdeSecret, PublicKey, SecretKey},
+    crypto::{serde_impl::SerdeSecret, PublicKey, SecretKey, Signature},
     sync_key_gen::Ack,
 };
 use primitives::{NodeId, NodeType};
+use rand::Rng;
 use vrrb_config::valid_threshold_config;
 
 use crate::{
     dkg::DkgGenerator,
     dkg_state::DkgState,
     engine::DkgEngine,
-    prelude::{ReceiverId, SenderId},
+    prelude::{DkgRng, ReceiverId, SenderId},
 };
 
 /// It generates a vector of secret keys and a map of public keys
@@ -20,7 +21,16 @@ use crate::{
 ///
 /// * `no_of_nodes`: The number of nodes in the network.
 pub fn generate_key_sets(number_of_nodes: u16) -> (Vec<SecretKey>, BTreeMap<NodeId, PublicKey>) {
-    let sec_keys: Vec<SecretKey> = (0..number_of_nodes).map(|_| rand::random()).collect();
+    generate_key_sets_with_rng(number_of_nodes, &mut DkgRng::from_entropy())
+}
+
+/// Same as [generate_key_sets] but draws the secret keys from `rng`, so seeded
+/// generators always yield the same key sets
+pub fn generate_key_sets_with_rng(
+    number_of_nodes: u16,
+    rng: &mut DkgRng,
+) -> (Vec<SecretKey>, BTreeMap<NodeId, PublicKey>) {
+    let sec_keys: Vec<SecretKey> = (0..number_of_nodes).map(|_| rng.gen()).collect();
 
     let pub_keys = sec_keys
         .iter()
@@ -47,7 +57,25 @@ pub fn generate_key_sets(number_of_nodes: u16) -> (Vec<SecretKey>, BTreeMap<Node
 ///
 /// A DkgEngine struct with a node_info field that is an Arc<RwLock<Node>>.
 pub async fn generate_dkg_engines(total_nodes: u16, node_type: NodeType) -> Vec<DkgEngine> {
-    let (sec_keys, pub_keys) = generate_key_sets(total_nodes);
+    generate_dkg_engines_with_rng(total_nodes, node_type, DkgRng::from_entropy()).await
+}
+
+/// Generates DKG engines whose keys and randomness all derive from `seed`, so
+/// the DKG rounds they run can be replayed exactly
+pub async fn generate_seeded_dkg_engines(
+    total_nodes: u16,
+    node_type: NodeType,
+    seed: u64,
+) -> Vec<DkgEngine> {
+    generate_dkg_engines_with_rng(total_nodes, node_type, DkgRng::seeded(seed)).await
+}
+
+async fn generate_dkg_engines_with_rng(
+    total_nodes: u16,
+    node_type: NodeType,
+    mut rng: DkgRng,
+) -> Vec<DkgEngine> {
+    let (sec_keys, pub_keys) = generate_key_sets_with_rng(total_nodes, &mut rng);
     let mut dkg_instances = vec![];
 
     for i in 0..total_nodes {
@@ -64,6 +92,7 @@ pub async fn generate_dkg_engines(total_nodes: u16, node_type: NodeType) -> Vec<
             secret_key: sec_keys.get(i as usize).unwrap().clone(),
             dkg_state,
             harvester_public_key: None,
+            rng: rng.fork(),
         });
     }
 
@@ -71,7 +100,36 @@ pub async fn generate_dkg_engines(total_nodes: u16, node_type: NodeType) -> Vec<
 }
 
 pub async fn generate_dkg_engine_with_states() -> Vec<DkgEngine> {
-    let mut dkg_engines = generate_dkg_engines(4, NodeType::Full).await;
+    run_dkg(generate_dkg_engines(4, NodeType::Full).await)
+}
+
+/// Same as [generate_dkg_engine_with_states] but every key and all the
+/// randomness of the DKG derive from `seed`, so the resulting key sets are
+/// always the same
+pub async fn generate_seeded_dkg_engine_with_states(seed: u64) -> Vec<DkgEngine> {
+    run_dkg(generate_seeded_dkg_engines(4, NodeType::Full, seed).await)
+}
+
+/// Signs `payload` on behalf of the quorum the DKG of `dkg_engines` formed,
+/// combining the signature shares of just enough of its members
+pub fn threshold_sign(dkg_engines: &[DkgEngine], payload: impl AsRef<[u8]>) -> Signature {
+    let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+
+    let shares = dkg_engines
+        .iter()
+        .enumerate()
+        .take(public_key_set.threshold() + 1)
+        .map(|(node_idx, engine)| {
+            let secret_key_share = engine.dkg_state.secret_key_share_owned().unwrap();
+
+            (node_idx, secret_key_share.sign(payload.as_ref()))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    public_key_set.combine_signatures(&shares).unwrap()
+}
+
+fn run_dkg(mut dkg_engines: Vec<DkgEngine>) -> Vec<DkgEngine> {
     let mut dkg_engine_node4 = dkg_engines.pop().unwrap();
     let mut dkg_engine_node3 = dkg_engines.pop().unwrap();
     let mut dkg_engine_node2 = dkg_engines.pop().unwrap();
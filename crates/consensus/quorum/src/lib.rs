@@ -9,7 +9,9 @@ mod tests {
         net::SocketAddr,
     };
 
-    use primitives::{Address, NodeId};
+    use primitives::{Address, NodeId, DEFAULT_MIN_DELEGATION};
+    use rand::{seq::SliceRandom, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
     use sha256::digest;
     use vrrb_core::{claim::Claim, keypair::KeyPair};
 
@@ -335,4 +337,107 @@ mod tests {
             }
         }
     }
+
+    fn random_claim() -> Claim {
+        let keypair = KeyPair::random();
+        let public_key = keypair.get_miner_public_key().clone();
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key.clone(),
+            ip_address,
+            keypair.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+
+        Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn elections_are_deterministic_across_input_orders_and_threads() {
+        let mut claims = (0..30)
+            .map(|idx| {
+                // NOTE: claims of every stake, including stake too small to weigh
+                // on their results
+                let mut claim = random_claim();
+                claim.delegate(
+                    claim.address.clone(),
+                    DEFAULT_MIN_DELEGATION * (idx % 4) + idx,
+                );
+
+                claim
+            })
+            .collect::<Vec<Claim>>();
+
+        // NOTE: a claim sharing another's hash, and therefore its election result,
+        // so the tie between them has to be broken the same way every time
+        let mut colliding_claim = claims[0].clone();
+        colliding_claim.public_key = *KeyPair::random().get_miner_public_key();
+        claims.push(colliding_claim);
+
+        let elect = |claims: Vec<Claim>| {
+            let mut quorum = Quorum::new(0x5eed, 11).unwrap();
+            let quorum = quorum.get_final_quorum(claims).unwrap();
+
+            format!("{quorum:?}").into_bytes()
+        };
+
+        let expected = elect(claims.clone());
+
+        std::thread::scope(|scope| {
+            let handles = (0..8)
+                .map(|thread| {
+                    let claims = &claims;
+                    scope.spawn(move || {
+                        let mut rng = ChaCha20Rng::seed_from_u64(thread);
+
+                        (0..64)
+                            .map(|_| {
+                                let mut permuted = claims.clone();
+                                permuted.shuffle(&mut rng);
+
+                                elect(permuted)
+                            })
+                            .collect::<Vec<Vec<u8>>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for handle in handles {
+                for result in handle.join().unwrap() {
+                    assert_eq!(result, expected);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn quorums_elected_from_ranked_claims_match_full_elections() {
+        let claims = (0..30).map(|_| random_claim()).collect::<Vec<Claim>>();
+
+        let mut expected = Quorum::new(0x5eed, 11).unwrap();
+        expected.get_final_quorum(claims.clone()).unwrap();
+
+        let mut ranked = claims
+            .iter()
+            .map(|claim| (claim.get_weighted_election_result(0x5eed), claim.clone()))
+            .collect::<Vec<_>>();
+        ranked.sort_by_key(|(result, _)| *result);
+        ranked.truncate(Quorum::seats(claims.len()));
+
+        let mut quorum = Quorum::new(0x5eed, 11).unwrap();
+        quorum
+            .elect_from_ranked(ranked.clone(), claims.len())
+            .unwrap();
+        assert_eq!(quorum, expected);
+
+        ranked.pop();
+        assert!(quorum.elect_from_ranked(ranked, claims.len()).is_err());
+    }
 }
@@ -1,6 +1,7 @@
-use std::collections::BTreeMap;
+use std::collections::{btree_map::Entry, BTreeMap};
 
 use ethereum_types::U256;
+use primitives::{PublicKey, QuorumPubkey};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use vrrb_core::{
@@ -36,11 +37,16 @@ pub enum QuorumError {
 #[derive(Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq)]
 pub struct Quorum {
     pub quorum_seed: u64,
-    pub master_pubkeys: Vec<String>,
-    pub quorum_pk: String,
+    /// Public keys of the claims elected to the quorum
+    pub master_pubkeys: Vec<PublicKey>,
+    /// Threshold public key of the quorum, once its members generated it
+    pub quorum_pk: Option<QuorumPubkey>,
     pub election_block_height: u128,
 }
 
+/// Fewest eligible claims a quorum can be elected from
+pub const MIN_ELIGIBLE_CLAIMS: usize = 20;
+
 ///generic types from Election trait defined here for Quorums
 type Height = u128;
 type BlockHash = String;
@@ -106,7 +112,7 @@ impl Quorum {
             Ok(Quorum {
                 quorum_seed: seed,
                 master_pubkeys: Vec::new(),
-                quorum_pk: String::new(),
+                quorum_pk: None,
                 election_block_height: height,
             })
         }
@@ -133,7 +139,7 @@ impl Quorum {
                 eligible_claims.push(claim);
             });
 
-        if eligible_claims.len() < 20 {
+        if eligible_claims.len() < MIN_ELIGIBLE_CLAIMS {
             return Err(QuorumError::InsufficientNodesError);
         }
 
@@ -142,33 +148,73 @@ impl Quorum {
         Ok(eligible_claims)
     }
 
-    /// Gets the final quorum by getting 51% of master nodes with lowest pointer
-    /// sums
+    /// Returns the number of seats of a quorum elected among `eligible`
+    /// claims, 51% of them rounded up
+    pub fn seats(eligible: usize) -> usize {
+        // NOTE: integer math, so every node rounds the thresholds the same way
+        (eligible * 51 + 99) / 100
+    }
+
+    /// Gets the final quorum by getting 51% of master nodes with lowest
+    /// weighted election results, see [Claim::get_weighted_election_result]
     pub fn get_final_quorum(&mut self, claims: Vec<Claim>) -> Result<&Quorum, QuorumError> {
         if self.quorum_seed == 0 {
             return Err(QuorumError::NoSeedError);
         }
 
-        let num_claims = ((claims.len() as f32) * 0.51).ceil() as usize;
-
-        let election_results: BTreeMap<U256, Claim> = claims
-            .iter()
-            .map(|claim| (claim.get_election_result(self.quorum_seed), claim.clone()))
-            .collect();
+        let min_distinct_results = (claims.len() * 65 + 99) / 100;
+
+        // NOTE: claims with the same election result are tied by their public key
+        // rather than by the order they were passed in, which differs across nodes
+        let mut election_results: BTreeMap<U256, Claim> = BTreeMap::new();
+        for claim in claims.iter() {
+            match election_results.entry(claim.get_weighted_election_result(self.quorum_seed)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(claim.clone());
+                },
+                Entry::Occupied(mut entry) => {
+                    if claim.public_key.serialize() < entry.get().public_key.serialize() {
+                        entry.insert(claim.clone());
+                    }
+                },
+            }
+        }
 
-        if election_results.len() < (((claims.len() as f32) * 0.65).ceil() as usize) {
+        if election_results.len() < min_distinct_results {
             return Err(QuorumError::InvalidPointerSumError(claims));
         }
 
-        let pubkeys: Vec<String> = election_results
-            .values()
-            .map(|claim| claim.public_key.clone().to_string())
-            .take(num_claims)
+        let ranked = election_results
+            .into_iter()
+            .take(Quorum::seats(claims.len()))
             .collect();
 
-        let final_pubkeys = Vec::from_iter(pubkeys[0..num_claims].iter().cloned());
+        self.elect_from_ranked(ranked, claims.len())
+    }
 
-        self.master_pubkeys = final_pubkeys;
+    /// Elects the quorum out of `ranked`, the claims with the lowest election
+    /// results for the quorum's seed among the `eligible` ones, in ascending
+    /// order. Lets elections be run from an index of the claims ranked ahead
+    /// of time rather than from every claim
+    pub fn elect_from_ranked(
+        &mut self,
+        ranked: Vec<(U256, Claim)>,
+        eligible: usize,
+    ) -> Result<&Quorum, QuorumError> {
+        if self.quorum_seed == 0 {
+            return Err(QuorumError::NoSeedError);
+        }
+
+        let num_claims = Quorum::seats(eligible);
+        if ranked.len() < num_claims {
+            return Err(QuorumError::InsufficientNodesError);
+        }
+
+        self.master_pubkeys = ranked
+            .into_iter()
+            .take(num_claims)
+            .map(|(_, claim)| claim.public_key)
+            .collect();
 
         Ok(self)
     }
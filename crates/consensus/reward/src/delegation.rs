@@ -0,0 +1,107 @@
+//! Sharing of validator rewards with the accounts that delegated stake to
+//! them.
+//!
+//! The validator takes its commission off the top of the reward, and the rest
+//! is split between the validator's own stake and every delegation in
+//! proportion to their size. Whatever the split leaves over after rounding
+//! down goes to the validator, so no token is minted or lost on the way.
+use std::collections::BTreeMap;
+
+use primitives::Address;
+
+/// How a reward is shared between a validator and its delegators.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RewardSplit {
+    pub validator: u128,
+    pub delegators: BTreeMap<Address, u128>,
+}
+
+impl RewardSplit {
+    /// Sum of every share, which equals the reward that was split
+    pub fn total(&self) -> u128 {
+        self.delegators
+            .values()
+            .fold(self.validator, |total, share| total.saturating_add(*share))
+    }
+}
+
+/// `amount * numerator / denominator`, without overflowing on large rewards
+/// and stakes
+fn pro_rata(amount: u128, numerator: u128, denominator: u128) -> u128 {
+    if denominator == 0 {
+        return 0;
+    }
+
+    let (quotient, remainder) = (amount / denominator, amount % denominator);
+
+    // NOTE: remainder < denominator, so this only overflows when the stake
+    // bonded to a single validator does
+    quotient.saturating_mul(numerator).saturating_add(
+        remainder
+            .checked_mul(numerator)
+            .map_or(0, |scaled| scaled / denominator),
+    )
+}
+
+/// Splits `amount`, earned by a validator holding `own_stake` and bonded
+/// `delegations`, keeping `commission_percent` of it for the validator.
+pub fn split_reward(
+    amount: u128,
+    own_stake: u128,
+    delegations: &BTreeMap<Address, u128>,
+    commission_percent: u8,
+) -> RewardSplit {
+    let commission = pro_rata(amount, commission_percent.min(100) as u128, 100);
+    let shared = amount - commission;
+
+    let total_stake = delegations
+        .values()
+        .fold(own_stake, |total, stake| total.saturating_add(*stake));
+
+    let delegators: BTreeMap<Address, u128> = delegations
+        .iter()
+        .map(|(delegator, stake)| (delegator.clone(), pro_rata(shared, *stake, total_stake)))
+        .filter(|(_, share)| *share > 0)
+        .collect();
+
+    let delegated = delegators
+        .values()
+        .fold(0u128, |total, share| total.saturating_add(*share));
+
+    RewardSplit {
+        validator: amount - delegated,
+        delegators,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrrb_core::keypair::KeyPair;
+
+    use super::*;
+
+    fn address() -> Address {
+        Address::new(*KeyPair::random().get_miner_public_key())
+    }
+
+    #[test]
+    fn rewards_are_shared_by_stake_once_the_commission_is_taken() {
+        let (alice, bob) = (address(), address());
+        let delegations = BTreeMap::from([(alice.clone(), 300), (bob.clone(), 100)]);
+
+        let split = split_reward(1_000, 100, &delegations, 10);
+
+        // NOTE: 100 commission, then 900 shared across a total stake of 500
+        assert_eq!(split.delegators[&alice], 540);
+        assert_eq!(split.delegators[&bob], 180);
+        assert_eq!(split.validator, 280);
+        assert_eq!(split.total(), 1_000);
+
+        let split = split_reward(1_001, 0, &delegations, 0);
+        assert_eq!(split.total(), 1_001);
+
+        let split = split_reward(1_000, 100, &BTreeMap::new(), 10);
+        assert_eq!(split.validator, 1_000);
+        assert!(split.delegators.is_empty());
+    }
+}
@@ -1,4 +1,6 @@
+pub mod delegation;
 pub mod reward;
+pub mod schedule;
 
 #[cfg(test)]
 mod tests {
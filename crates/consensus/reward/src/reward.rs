@@ -1,5 +1,6 @@
 //FEATURE TAG(S): Rewards, Block Structure
 
+use primitives::Amount;
 use serde::{Deserialize, Serialize};
 use vrrb_core::accountable::Accountable;
 
@@ -14,12 +15,12 @@ pub const VRRB: u128 = 1000 * PSIGMA;
 
 // Generate a random variable reward to include in new blocks
 
-pub const MAX_REWARD_ADJUSTMENT: f32 = 0.25;
-pub const BASELINE_REWARD: u128 = 20;
-pub const MIN_BASELINE_REWARD: u128 = 15;
-pub const MAX_BASELINE_REWARD: u128 = 25;
-pub const NUMBER_OF_BLOCKS_PER_EPOCH: u128 = 30000000;
-pub const GENESIS_REWARD: u128 = 400_000_000;
+pub const MAX_REWARD_ADJUSTMENT: f32 = primitives::DEFAULT_MAX_REWARD_ADJUSTMENT;
+pub const BASELINE_REWARD: u128 = primitives::DEFAULT_BASELINE_REWARD;
+pub const MIN_BASELINE_REWARD: u128 = primitives::DEFAULT_MIN_BASELINE_REWARD;
+pub const MAX_BASELINE_REWARD: u128 = primitives::DEFAULT_MAX_BASELINE_REWARD;
+pub const NUMBER_OF_BLOCKS_PER_EPOCH: u128 = primitives::DEFAULT_EPOCH_LENGTH;
+pub const GENESIS_REWARD: u128 = primitives::DEFAULT_GENESIS_REWARD;
 
 /// `Reward` is a struct that contains the epoch, next epoch block, current
 /// block, miner, and amount.
@@ -65,17 +66,16 @@ impl Reward {
         if rem == 0 {
             let nr_epoch = self.epoch + 1;
             let nr_next_epoch_block = self.next_epoch_block + NUMBER_OF_BLOCKS_PER_EPOCH;
-            let mut nr_amount = {
-                (self.amount as i128
-                    + (adjustment_to_next_epoch / NUMBER_OF_BLOCKS_PER_EPOCH as i128))
-                    as u128
-            };
-
-            if nr_amount < MIN_BASELINE_REWARD {
-                nr_amount = MIN_BASELINE_REWARD;
-            } else if nr_amount > MAX_BASELINE_REWARD {
-                nr_amount = MAX_BASELINE_REWARD;
+            let adjustment = adjustment_to_next_epoch / NUMBER_OF_BLOCKS_PER_EPOCH as i128;
+
+            // NOTE: adjustments past zero or the largest amount are clamped
+            // like any other out of bounds reward
+            let nr_amount = match Amount::new(self.amount).checked_add_signed(adjustment) {
+                Ok(amount) => amount.get(),
+                Err(_) if adjustment < 0 => MIN_BASELINE_REWARD,
+                Err(_) => MAX_BASELINE_REWARD,
             }
+            .clamp(MIN_BASELINE_REWARD, MAX_BASELINE_REWARD);
 
             Reward {
                 current_block: self.current_block,
@@ -115,15 +115,13 @@ impl Reward {
         self.next_epoch_block += NUMBER_OF_BLOCKS_PER_EPOCH;
 
         //Adjust Block Reward
-        let amount =
-            self.amount as i128 + adjustment_to_next_epoch / NUMBER_OF_BLOCKS_PER_EPOCH as i128;
-        if amount <= 0 {
-            self.amount = MIN_BASELINE_REWARD;
-        } else if amount >= MAX_BASELINE_REWARD as i128 {
-            self.amount = MAX_BASELINE_REWARD;
-        } else {
-            self.amount = amount as u128;
-        }
+        let adjustment = adjustment_to_next_epoch / NUMBER_OF_BLOCKS_PER_EPOCH as i128;
+        self.amount = match Amount::new(self.amount).checked_add_signed(adjustment) {
+            Ok(amount) if amount.is_zero() => MIN_BASELINE_REWARD,
+            Ok(amount) => amount.get().min(MAX_BASELINE_REWARD),
+            Err(_) if adjustment < 0 => MIN_BASELINE_REWARD,
+            Err(_) => MAX_BASELINE_REWARD,
+        };
     }
 
     #[allow(clippy::inherent_to_string)]
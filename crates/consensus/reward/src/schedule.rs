@@ -0,0 +1,419 @@
+//! Reward schedule for convergence blocks.
+//!
+//! Block rewards are carried in every [BlockHeader](../../block) as the reward
+//! for the block itself and the reward the next block is expected to pay out.
+//! The reward only changes on epoch boundaries, where the miner may nudge it
+//! by the epoch's utility adjustment within the bounds allowed by the chain
+//! spec's emission curve.
+//!
+//! Every node must compute the exact same rewards, so the emission curve is
+//! evaluated in fixed-point integer arithmetic rather than with floats.
+use primitives::{
+    Amount, ChainSpec, EmissionCurve, Epoch, RewardParams, EMISSION_DECAY_RATE_DENOMINATOR,
+};
+
+use crate::reward::Reward;
+
+/// The epoch the first block reward belongs to
+pub const FIRST_REWARD_EPOCH: Epoch = 1;
+
+/// Fixed-point representation of an emission factor of one, i.e. of the full
+/// baseline reward
+const FULL_EMISSION: u128 = 1_000_000_000_000_000_000;
+
+pub type Result<T> = std::result::Result<T, RewardError>;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RewardError {
+    #[error("block reward {0} does not match the previous block's next reward")]
+    UnexpectedBlockReward(String),
+
+    #[error("next block reward {0} does not follow the reward schedule")]
+    InvalidNextReward(String),
+
+    #[error("reward {amount} for epoch {epoch} is outside of the permitted range [{min}, {max}]")]
+    OutOfBounds {
+        epoch: Epoch,
+        amount: u128,
+        min: u128,
+        max: u128,
+    },
+}
+
+/// Computes and verifies block rewards as a function of block height and
+/// epoch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewardSchedule {
+    params: RewardParams,
+    epoch_length: u128,
+}
+
+impl Default for RewardSchedule {
+    fn default() -> Self {
+        Self::new(&ChainSpec::default())
+    }
+}
+
+impl RewardSchedule {
+    pub fn new(chain_spec: &ChainSpec) -> Self {
+        Self {
+            params: chain_spec.reward.clone(),
+            epoch_length: chain_spec.epoch_length.max(1),
+        }
+    }
+
+    pub fn epoch_length(&self) -> u128 {
+        self.epoch_length
+    }
+
+    /// Returns the reward epoch the given block height falls into.
+    pub fn epoch_at(&self, block_height: u128) -> Epoch {
+        FIRST_REWARD_EPOCH + block_height / self.epoch_length
+    }
+
+    /// Fraction of the baseline reward still being paid out in `epoch`, in
+    /// units of [FULL_EMISSION].
+    fn emission_factor(&self, epoch: Epoch) -> u128 {
+        let elapsed = epoch.saturating_sub(FIRST_REWARD_EPOCH);
+
+        match self.params.emission_curve {
+            EmissionCurve::Flat => FULL_EMISSION,
+            EmissionCurve::Halving { interval } => {
+                let halvings = elapsed / interval.max(1);
+
+                u32::try_from(halvings)
+                    .ok()
+                    .and_then(|halvings| FULL_EMISSION.checked_shr(halvings))
+                    .unwrap_or_default()
+            },
+            EmissionCurve::Decay { rate_ppm } => {
+                let denominator = EMISSION_DECAY_RATE_DENOMINATOR as u128;
+                let retained = denominator.saturating_sub(rate_ppm as u128);
+
+                fixed_pow(retained * (FULL_EMISSION / denominator), elapsed)
+            },
+        }
+    }
+
+    fn scale(&self, amount: u128, epoch: Epoch) -> u128 {
+        fixed_mul(amount, self.emission_factor(epoch))
+    }
+
+    /// Baseline reward paid out during `epoch` before any utility adjustment.
+    pub fn baseline_reward(&self, epoch: Epoch) -> u128 {
+        self.scale(self.params.baseline_reward, epoch)
+    }
+
+    /// Smallest and largest reward a block of `epoch` may pay out.
+    pub fn bounds(&self, epoch: Epoch) -> (u128, u128) {
+        (
+            self.scale(self.params.min_baseline_reward, epoch),
+            self.scale(self.params.max_baseline_reward, epoch),
+        )
+    }
+
+    /// Reward paid out by the genesis block.
+    pub fn genesis_reward(&self, miner: Option<String>) -> Reward {
+        Reward {
+            current_block: 0,
+            epoch: FIRST_REWARD_EPOCH,
+            next_epoch_block: self.epoch_length,
+            miner,
+            amount: self.baseline_reward(FIRST_REWARD_EPOCH),
+        }
+    }
+
+    /// Computes the reward of the block following the one paying out
+    /// `reward`. It is the same reward unless that block starts a new epoch,
+    /// in which case the amount follows the emission curve and is adjusted by
+    /// `adjustment_to_next_epoch` spread over the epoch's blocks.
+    pub fn next_reward(&self, reward: &Reward, adjustment_to_next_epoch: i128) -> Reward {
+        let mut next_reward = reward.clone();
+        next_reward.miner = None;
+
+        if !self.is_epoch_boundary(reward.current_block) {
+            return next_reward;
+        }
+
+        let epoch = reward.epoch + 1;
+        let (min, max) = self.bounds(epoch);
+
+        // Past adjustments are carried over, shrunk along with the baseline
+        let previous_factor = self.emission_factor(reward.epoch);
+        let baseline = i128::try_from(self.baseline_reward(reward.epoch)).unwrap_or(i128::MAX);
+        let deviation = i128::try_from(reward.amount)
+            .unwrap_or(i128::MAX)
+            .saturating_sub(baseline);
+        let carried_over = if previous_factor > 0 {
+            deviation.saturating_mul(self.emission_factor(epoch) as i128) / previous_factor as i128
+        } else {
+            0
+        };
+
+        let adjustment =
+            carried_over.saturating_add(adjustment_to_next_epoch / self.epoch_length as i128);
+
+        // NOTE: adjustments past zero or the largest amount are clamped like
+        // any other out of bounds reward
+        let adjusted = match Amount::new(self.baseline_reward(epoch)).checked_add_signed(adjustment)
+        {
+            Ok(amount) => amount.get(),
+            Err(_) if adjustment < 0 => min,
+            Err(_) => max,
+        };
+
+        next_reward.epoch = epoch;
+        next_reward.next_epoch_block = reward.next_epoch_block.saturating_add(self.epoch_length);
+        next_reward.amount = adjusted.clamp(min, max);
+        next_reward
+    }
+
+    /// Checks the rewards carried by a block header against the schedule.
+    ///
+    /// Arguments:
+    ///
+    /// * `previous_next_reward`: next block reward of the block being built
+    ///   upon
+    /// * `block_reward`: reward the block pays out
+    /// * `next_block_reward`: reward the block announces for its successor
+    pub fn verify(
+        &self,
+        previous_next_reward: &Reward,
+        block_reward: &Reward,
+        next_block_reward: &Reward,
+    ) -> Result<()> {
+        if block_reward.epoch != previous_next_reward.epoch
+            || block_reward.next_epoch_block != previous_next_reward.next_epoch_block
+            || block_reward.amount != previous_next_reward.amount
+        {
+            return Err(RewardError::UnexpectedBlockReward(block_reward.to_string()));
+        }
+
+        self.verify_amount(block_reward)?;
+
+        if !self.is_epoch_boundary(block_reward.current_block) {
+            if next_block_reward.epoch != block_reward.epoch
+                || next_block_reward.next_epoch_block != block_reward.next_epoch_block
+                || next_block_reward.amount != block_reward.amount
+            {
+                return Err(RewardError::InvalidNextReward(
+                    next_block_reward.to_string(),
+                ));
+            }
+
+            return Ok(());
+        }
+
+        if next_block_reward.epoch != block_reward.epoch + 1
+            || next_block_reward.next_epoch_block
+                != block_reward.next_epoch_block + self.epoch_length
+        {
+            return Err(RewardError::InvalidNextReward(
+                next_block_reward.to_string(),
+            ));
+        }
+
+        self.verify_amount(next_block_reward)
+    }
+
+    fn verify_amount(&self, reward: &Reward) -> Result<()> {
+        let (min, max) = self.bounds(reward.epoch);
+
+        if reward.amount < min || reward.amount > max {
+            return Err(RewardError::OutOfBounds {
+                epoch: reward.epoch,
+                amount: reward.amount,
+                min,
+                max,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Whether the block following `block_height` starts a new epoch.
+    fn is_epoch_boundary(&self, block_height: u128) -> bool {
+        (block_height + 1) % self.epoch_length == 0
+    }
+}
+
+/// Multiplies `amount` by the fixed-point `factor`, rounding down. Splits the
+/// amount so large amounts don't overflow along the way.
+fn fixed_mul(amount: u128, factor: u128) -> u128 {
+    let whole = (amount / FULL_EMISSION).saturating_mul(factor);
+    let fraction = (amount % FULL_EMISSION) * factor / FULL_EMISSION;
+
+    whole.saturating_add(fraction)
+}
+
+/// Raises the fixed-point `base`, at most [FULL_EMISSION], to the power of
+/// `exponent` by squaring, rounding down after every multiplication.
+fn fixed_pow(mut base: u128, mut exponent: u128) -> u128 {
+    let mut result = FULL_EMISSION;
+
+    while exponent > 0 && result > 0 {
+        if exponent & 1 == 1 {
+            result = fixed_mul(result, base);
+        }
+
+        base = fixed_mul(base, base);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reward::{BASELINE_REWARD, MAX_BASELINE_REWARD, MIN_BASELINE_REWARD};
+
+    fn schedule(emission_curve: EmissionCurve, epoch_length: u128) -> RewardSchedule {
+        let mut chain_spec = ChainSpec {
+            epoch_length,
+            ..Default::default()
+        };
+        chain_spec.reward.emission_curve = emission_curve;
+
+        RewardSchedule::new(&chain_spec)
+    }
+
+    /// Produces the reward of the last block of `epochs` consecutive epochs,
+    /// building every block on top of the previous one.
+    fn run_epochs(schedule: &RewardSchedule, epochs: u128, adjustment: i128) -> Vec<Reward> {
+        let mut next_reward = schedule.genesis_reward(None);
+        let mut rewards = vec![];
+
+        for height in 1..=(epochs * schedule.epoch_length()) {
+            let mut block_reward = next_reward.clone();
+            block_reward.current_block = height;
+
+            let next = schedule.next_reward(&block_reward, adjustment);
+            schedule.verify(&next_reward, &block_reward, &next).unwrap();
+
+            if next.epoch != block_reward.epoch {
+                rewards.push(block_reward);
+            }
+
+            next_reward = next;
+        }
+
+        rewards
+    }
+
+    #[test]
+    fn flat_schedule_matches_legacy_rewards() {
+        let schedule = RewardSchedule::default();
+        let genesis = schedule.genesis_reward(Some("MINER".to_string()));
+
+        assert_eq!(genesis, Reward::genesis(Some("MINER".to_string())));
+
+        let mut reward = genesis;
+        reward.current_block = schedule.epoch_length() - 1;
+
+        for adjustment in [-30_000_000, 0, 30_000_000, 900_000_000] {
+            let expected = reward.generate_next_reward(adjustment);
+            let next = schedule.next_reward(&reward, adjustment);
+
+            assert_eq!(next.epoch, expected.epoch);
+            assert_eq!(next.next_epoch_block, expected.next_epoch_block);
+            assert_eq!(next.amount, expected.amount);
+        }
+    }
+
+    #[test]
+    fn halving_schedule_halves_rewards_every_interval() {
+        let schedule = schedule(EmissionCurve::Halving { interval: 2 }, 4);
+        let amounts: Vec<u128> = run_epochs(&schedule, 6, 0)
+            .iter()
+            .map(|reward| reward.amount)
+            .collect();
+
+        assert_eq!(amounts, vec![20, 20, 10, 10, 5, 5]);
+        assert_eq!(schedule.bounds(3), (7, 12));
+    }
+
+    #[test]
+    fn decay_schedule_shrinks_rewards_every_epoch() {
+        let schedule = schedule(EmissionCurve::Decay { rate_ppm: 100_000 }, 3);
+        let rewards = run_epochs(&schedule, 5, 0);
+
+        assert!(rewards
+            .windows(2)
+            .all(|pair| pair[1].amount <= pair[0].amount));
+        assert_eq!(rewards.last().unwrap().amount, schedule.baseline_reward(5));
+
+        // NOTE: 20 * 0.9^4 = 13.122
+        assert_eq!(schedule.baseline_reward(5), 13);
+        assert_eq!(schedule.emission_factor(5), 656_100_000_000_000_000);
+    }
+
+    #[test]
+    fn emission_factors_are_exact_and_never_overflow() {
+        let halving = schedule(EmissionCurve::Halving { interval: 1 }, 1);
+        assert_eq!(halving.emission_factor(3), FULL_EMISSION / 4);
+        assert_eq!(halving.emission_factor(Epoch::MAX), 0);
+
+        let decay = schedule(
+            EmissionCurve::Decay {
+                rate_ppm: EMISSION_DECAY_RATE_DENOMINATOR - 1,
+            },
+            1,
+        );
+        assert_eq!(decay.emission_factor(Epoch::MAX), 0);
+
+        assert_eq!(fixed_mul(u128::MAX, FULL_EMISSION), u128::MAX);
+        assert_eq!(fixed_mul(u128::MAX, FULL_EMISSION / 2), u128::MAX / 2);
+    }
+
+    #[test]
+    fn adjustments_are_clamped_to_the_epoch_bounds() {
+        let schedule = schedule(EmissionCurve::Flat, 2);
+
+        let raised = run_epochs(&schedule, 3, i128::MAX / 4);
+        assert_eq!(raised.last().unwrap().amount, MAX_BASELINE_REWARD);
+
+        let lowered = run_epochs(&schedule, 3, i128::MIN / 4);
+        assert_eq!(lowered.last().unwrap().amount, MIN_BASELINE_REWARD);
+
+        // NOTE: adjustments too large for any amount don't overflow either
+        let raised = run_epochs(&schedule, 3, i128::MAX);
+        assert_eq!(raised.last().unwrap().amount, MAX_BASELINE_REWARD);
+
+        let lowered = run_epochs(&schedule, 3, i128::MIN);
+        assert_eq!(lowered.last().unwrap().amount, MIN_BASELINE_REWARD);
+    }
+
+    #[test]
+    fn tampered_rewards_are_rejected() {
+        let schedule = schedule(EmissionCurve::Flat, 10);
+        let previous = schedule.genesis_reward(None);
+
+        let mut block_reward = previous.clone();
+        block_reward.current_block = 1;
+        block_reward.amount = BASELINE_REWARD + 1;
+
+        assert!(matches!(
+            schedule.verify(&previous, &block_reward, &block_reward),
+            Err(RewardError::UnexpectedBlockReward(_))
+        ));
+
+        block_reward.amount = BASELINE_REWARD;
+        let mut next_reward = schedule.next_reward(&block_reward, 0);
+        next_reward.epoch += 1;
+
+        assert!(matches!(
+            schedule.verify(&previous, &block_reward, &next_reward),
+            Err(RewardError::InvalidNextReward(_))
+        ));
+
+        block_reward.current_block = 9;
+        let mut next_reward = schedule.next_reward(&block_reward, 0);
+        next_reward.amount = MAX_BASELINE_REWARD + 1;
+
+        assert!(matches!(
+            schedule.verify(&previous, &block_reward, &next_reward),
+            Err(RewardError::OutOfBounds { .. })
+        ));
+    }
+}
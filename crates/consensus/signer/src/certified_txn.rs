@@ -0,0 +1,95 @@
+//! Verification of the certificates farmer quorums attach to the txns they
+//! vote on, for wallets and auditors that don't run a node.
+use block_verifier::verify_threshold_signature;
+use primitives::QuorumPubkey;
+use vrrb_core::transactions::QuorumCertifiedTxn;
+
+use crate::types::{SignerError, SignerResult};
+
+/// Checks that a certified txn carries the threshold signature of the farmer
+/// quorum holding `group_public_key`.
+///
+/// Farmers sign the bincode encoding of the txn whatever their verdict is, so
+/// the signature vouches for the txn having been certified but not for
+/// [QuorumCertifiedTxn::is_txn_valid].
+pub fn verify_certified_txn(
+    certified_txn: &QuorumCertifiedTxn,
+    group_public_key: &QuorumPubkey,
+) -> SignerResult<()> {
+    match certified_txn.quorum_public_key() {
+        Some(quorum_public_key) if quorum_public_key == group_public_key => {},
+        Some(quorum_public_key) => {
+            return Err(SignerError::CertifiedTxnError(format!(
+                "txn was certified by quorum {quorum_public_key}, not {group_public_key}"
+            )))
+        },
+        None => {
+            return Err(SignerError::CertifiedTxnError(
+                "txn doesn't name the quorum that certified it".to_string(),
+            ))
+        },
+    }
+
+    let payload = bincode::serialize(&certified_txn.txn())
+        .map_err(|err| SignerError::CertifiedTxnError(format!("failed to encode txn: {err}")))?;
+
+    verify_threshold_signature(
+        group_public_key.as_bytes(),
+        &payload,
+        certified_txn.signature(),
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use dkg_engine::test_utils::{generate_dkg_engine_with_states, threshold_sign};
+    use hbbft::crypto::SecretKey;
+    use vrrb_core::transactions::TransactionKind;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn only_txns_certified_by_the_trusted_quorum_verify() {
+        let dkg_engines = generate_dkg_engine_with_states().await;
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+        let group_public_key = QuorumPubkey::from(public_key_set.public_key());
+
+        let txn = TransactionKind::default();
+        let payload = bincode::serialize(&txn).unwrap();
+
+        let signature = threshold_sign(&dkg_engines, &payload);
+
+        let certified_txn = QuorumCertifiedTxn::new(
+            vec![],
+            vec![],
+            txn,
+            signature.to_bytes().to_vec(),
+            Some(group_public_key),
+            true,
+        );
+
+        verify_certified_txn(&certified_txn, &group_public_key).unwrap();
+
+        let other_quorum = QuorumPubkey::from(SecretKey::random().public_key());
+        assert!(matches!(
+            verify_certified_txn(&certified_txn, &other_quorum),
+            Err(SignerError::CertifiedTxnError(_))
+        ));
+
+        // NOTE: naming the trusted quorum doesn't help a forged signature
+        let forged = QuorumCertifiedTxn::new(
+            vec![],
+            vec![],
+            TransactionKind::default(),
+            SecretKey::random().sign(&payload).to_bytes().to_vec(),
+            Some(group_public_key),
+            true,
+        );
+        assert!(matches!(
+            verify_certified_txn(&forged, &group_public_key),
+            Err(SignerError::SignatureVerificationError(_))
+        ));
+    }
+}
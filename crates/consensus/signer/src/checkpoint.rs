@@ -0,0 +1,196 @@
+//! Verification of the checkpoints nodes export for bridges and auditors.
+//!
+//! [verify_checkpoint] only needs the group public key of the quorum the
+//! verifier trusts, so checkpoints can be checked without running a node or
+//! holding any DKG state.
+use std::collections::BTreeMap;
+
+pub use block::checkpoint::{validator_set_hash, Checkpoint};
+use block_verifier::verify_threshold_signature;
+use primitives::{NodeId, QuorumPubkey, ValidatorPublicKey};
+
+use crate::types::{SignerError, SignerResult};
+
+/// Checks that a checkpoint was signed by the quorum holding
+/// `group_public_key`, and that the block hash it signed commits to the
+/// round, state root and txn root the checkpoint claims.
+pub fn verify_checkpoint(
+    checkpoint: &Checkpoint,
+    group_public_key: &QuorumPubkey,
+) -> SignerResult<()> {
+    if &checkpoint.quorum_public_key != group_public_key {
+        return Err(SignerError::CheckpointError(format!(
+            "checkpoint was signed by quorum {}, not {group_public_key}",
+            checkpoint.quorum_public_key
+        )));
+    }
+
+    if !checkpoint.matches_header() {
+        return Err(SignerError::CheckpointError(
+            "checkpoint doesn't match the header of the block it was taken at".to_string(),
+        ));
+    }
+
+    let payload_hash = hex::decode(&checkpoint.block_hash)
+        .map_err(|err| SignerError::CheckpointError(format!("invalid block hash: {err}")))?;
+
+    let signature = hex::decode(&checkpoint.signature)
+        .map_err(|err| SignerError::CheckpointError(format!("invalid signature: {err}")))?;
+
+    verify_threshold_signature(group_public_key.as_bytes(), &payload_hash, &signature)?;
+
+    Ok(())
+}
+
+/// Checks a checkpoint like [verify_checkpoint] does, and that the quorum
+/// that signed it is made up of `members`.
+pub fn verify_checkpoint_with_validator_set(
+    checkpoint: &Checkpoint,
+    group_public_key: &QuorumPubkey,
+    members: &BTreeMap<NodeId, ValidatorPublicKey>,
+) -> SignerResult<()> {
+    verify_checkpoint(checkpoint, group_public_key)?;
+
+    if !checkpoint.commits_to_validator_set(members) {
+        return Err(SignerError::CheckpointError(
+            "checkpoint doesn't commit to the given validator set".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use block::header::BlockHeader;
+    use dkg_engine::test_utils::{generate_dkg_engine_with_states, threshold_sign};
+    use hbbft::crypto::PublicKeySet;
+    use primitives::{Address, HeaderFormatParams};
+    use reward::schedule::RewardSchedule;
+    use vrrb_core::{claim::Claim, keypair::Keypair};
+
+    use super::*;
+
+    fn header() -> BlockHeader {
+        let keypair = Keypair::random();
+        let secret_key = *keypair.get_miner_secret_key();
+        let public_key = *keypair.get_miner_public_key();
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            secret_key.secret_bytes().to_vec(),
+        )
+        .unwrap();
+
+        let miner_claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature,
+            "node-1".to_string(),
+        )
+        .unwrap();
+
+        let mut header = BlockHeader::genesis(
+            0,
+            100,
+            0,
+            miner_claim,
+            secret_key,
+            String::new(),
+            &RewardSchedule::default(),
+            &HeaderFormatParams::default(),
+        );
+        header.commit_state_root(hex::encode([7u8; 32]), &secret_key);
+
+        header
+    }
+
+    async fn signed_checkpoint() -> (
+        Checkpoint,
+        PublicKeySet,
+        BTreeMap<NodeId, ValidatorPublicKey>,
+    ) {
+        let dkg_engines = generate_dkg_engine_with_states().await;
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+        let members = dkg_engines[0].dkg_state.peer_public_keys_owned();
+
+        let header = header();
+        let block_hash = header.hash();
+        let payload_hash = hex::decode(&block_hash).unwrap();
+
+        let signature = threshold_sign(&dkg_engines, &payload_hash);
+
+        let certificate = block::Certificate {
+            signature: hex::encode(signature.to_bytes()),
+            inauguration: None,
+            root_hash: String::new(),
+            next_root_hash: header.state_root.clone(),
+            block_hash,
+        };
+
+        let checkpoint = Checkpoint::new(
+            header,
+            &certificate,
+            QuorumPubkey::from(public_key_set.public_key()),
+            &members,
+        );
+
+        (checkpoint, public_key_set, members)
+    }
+
+    #[tokio::test]
+    async fn checkpoints_signed_by_the_trusted_quorum_verify() {
+        let (checkpoint, public_key_set, members) = signed_checkpoint().await;
+        let group_public_key = QuorumPubkey::from(public_key_set.public_key());
+
+        verify_checkpoint(&checkpoint, &group_public_key).unwrap();
+        verify_checkpoint_with_validator_set(&checkpoint, &group_public_key, &members).unwrap();
+
+        let decoded: Checkpoint =
+            serde_json::from_str(&serde_json::to_string(&checkpoint).unwrap()).unwrap();
+        verify_checkpoint(&decoded, &group_public_key).unwrap();
+    }
+
+    #[tokio::test]
+    async fn tampered_checkpoints_are_rejected() {
+        let (checkpoint, public_key_set, members) = signed_checkpoint().await;
+        let group_public_key = QuorumPubkey::from(public_key_set.public_key());
+
+        let mut tampered = checkpoint.clone();
+        tampered.state_root = hex::encode([8u8; 32]);
+        assert!(matches!(
+            verify_checkpoint(&tampered, &group_public_key),
+            Err(SignerError::CheckpointError(_))
+        ));
+
+        // NOTE: a header that matches the claimed state root no longer hashes
+        // to the signed block hash
+        tampered.header.state_root = tampered.state_root.clone();
+        tampered.block_hash = tampered.header.hash();
+        assert!(matches!(
+            verify_checkpoint(&tampered, &group_public_key),
+            Err(SignerError::SignatureVerificationError(_))
+        ));
+
+        let (other, ..) = signed_checkpoint().await;
+        assert!(matches!(
+            verify_checkpoint(&checkpoint, &other.quorum_public_key),
+            Err(SignerError::CheckpointError(_))
+        ));
+
+        let mut members = members;
+        members.insert(
+            "intruder".to_string(),
+            Keypair::random().validator_public_key_owned(),
+        );
+        assert!(matches!(
+            verify_checkpoint_with_validator_set(&checkpoint, &group_public_key, &members),
+            Err(SignerError::CheckpointError(_))
+        ));
+    }
+}
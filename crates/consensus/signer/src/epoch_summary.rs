@@ -0,0 +1,226 @@
+//! Verification of the epoch summaries light clients sync from.
+//!
+//! Like checkpoints, summaries can be checked with nothing but the group
+//! public key of the quorum the client trusts, one summary per epoch rather
+//! than one certificate per round.
+pub use block::epoch_summary::EpochSummary;
+use block_verifier::verify_threshold_signature;
+use primitives::QuorumPubkey;
+
+use crate::types::{SignerError, SignerResult};
+
+/// Checks that an epoch summary was signed by the quorum holding
+/// `group_public_key`, and that the block hash it signed commits to the
+/// epoch, round and state root the summary claims.
+pub fn verify_epoch_summary(
+    summary: &EpochSummary,
+    group_public_key: &QuorumPubkey,
+) -> SignerResult<()> {
+    if &summary.quorum_public_key != group_public_key {
+        return Err(SignerError::EpochSummaryError(format!(
+            "summary was signed by quorum {}, not {group_public_key}",
+            summary.quorum_public_key
+        )));
+    }
+
+    if !summary.matches_header() {
+        return Err(SignerError::EpochSummaryError(
+            "summary doesn't match the header of the last block of its epoch".to_string(),
+        ));
+    }
+
+    let payload_hash = hex::decode(&summary.block_hash)
+        .map_err(|err| SignerError::EpochSummaryError(format!("invalid block hash: {err}")))?;
+
+    let signature = hex::decode(&summary.signature)
+        .map_err(|err| SignerError::EpochSummaryError(format!("invalid signature: {err}")))?;
+
+    verify_threshold_signature(group_public_key.as_bytes(), &payload_hash, &signature)?;
+
+    Ok(())
+}
+
+/// Checks every summary of `summaries`, oldest first, like
+/// [verify_epoch_summary] does, and that each of them follows the one before
+/// it. Returns the latest summary, which a light client syncs up to.
+///
+/// Summaries signed by a quorum other than the trusted one are rejected, so
+/// clients must learn of a new quorum by other means before syncing past it.
+pub fn verify_epoch_summary_chain<'a>(
+    summaries: &'a [EpochSummary],
+    group_public_key: &QuorumPubkey,
+) -> SignerResult<&'a EpochSummary> {
+    let mut parent: Option<&EpochSummary> = None;
+
+    for summary in summaries {
+        verify_epoch_summary(summary, group_public_key)?;
+
+        if let Some(parent) = parent {
+            if !summary.follows(parent) {
+                return Err(SignerError::EpochSummaryError(format!(
+                    "summary of epoch {} doesn't follow the summary of epoch {}",
+                    summary.epoch, parent.epoch
+                )));
+            }
+        }
+
+        parent = Some(summary);
+    }
+
+    parent.ok_or_else(|| SignerError::EpochSummaryError("no summary to verify".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use block::{checkpoint::validator_set_hash, header::BlockHeader};
+    use dkg_engine::{
+        prelude::DkgEngine,
+        test_utils::{generate_dkg_engine_with_states, threshold_sign},
+    };
+    use primitives::{Address, Epoch, HeaderFormatParams, Round};
+    use reward::schedule::RewardSchedule;
+    use vrrb_core::{claim::Claim, keypair::Keypair};
+
+    use super::*;
+
+    fn header(epoch: Epoch, round: Round) -> BlockHeader {
+        let keypair = Keypair::random();
+        let secret_key = *keypair.get_miner_secret_key();
+        let public_key = *keypair.get_miner_public_key();
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            secret_key.secret_bytes().to_vec(),
+        )
+        .unwrap();
+
+        let miner_claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature,
+            "node-1".to_string(),
+        )
+        .unwrap();
+
+        let mut header = BlockHeader::genesis(
+            0,
+            100,
+            0,
+            miner_claim,
+            secret_key,
+            String::new(),
+            &RewardSchedule::default(),
+            &HeaderFormatParams::default(),
+        );
+        header.epoch = epoch;
+        header.round = round;
+        header.commit_state_root(hex::encode([round as u8; 32]), &secret_key);
+
+        header
+    }
+
+    /// Summarizes `epoch` as of a block at `round`, signed by the quorum of
+    /// `dkg_engines`
+    fn signed_summary(
+        dkg_engines: &[DkgEngine],
+        epoch: Epoch,
+        round: Round,
+        parent: Option<&EpochSummary>,
+    ) -> EpochSummary {
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+        let members = dkg_engines[0].dkg_state.peer_public_keys_owned();
+
+        let header = header(epoch, round);
+        let block_hash = header.hash();
+        let payload_hash = hex::decode(&block_hash).unwrap();
+
+        let signature = threshold_sign(dkg_engines, &payload_hash);
+        let quorum_public_key = QuorumPubkey::from(public_key_set.public_key());
+
+        EpochSummary {
+            epoch,
+            parent_hash: parent.map(EpochSummary::hash).unwrap_or_default(),
+            round,
+            block_hash,
+            state_root: header.state_root.clone(),
+            validator_set_hash: validator_set_hash(&quorum_public_key, &members),
+            quorum_public_key,
+            txn_count: 10,
+            block_rewards: 1_000,
+            fee_shares: 50,
+            signature: hex::encode(signature.to_bytes()),
+            header,
+        }
+    }
+
+    #[tokio::test]
+    async fn chains_of_summaries_signed_by_the_trusted_quorum_verify() {
+        let dkg_engines = generate_dkg_engine_with_states().await;
+
+        let first = signed_summary(&dkg_engines, 0, 9, None);
+        let second = signed_summary(&dkg_engines, 1, 19, Some(&first));
+        let group_public_key = first.quorum_public_key.clone();
+
+        let chain = vec![first, second];
+        let latest = verify_epoch_summary_chain(&chain, &group_public_key).unwrap();
+        assert_eq!(latest.epoch, 1);
+
+        let decoded: Vec<EpochSummary> =
+            serde_json::from_str(&serde_json::to_string(&chain).unwrap()).unwrap();
+        verify_epoch_summary_chain(&decoded, &group_public_key).unwrap();
+
+        assert!(verify_epoch_summary_chain(&[], &group_public_key).is_err());
+    }
+
+    #[tokio::test]
+    async fn broken_or_tampered_chains_are_rejected() {
+        let dkg_engines = generate_dkg_engine_with_states().await;
+
+        let first = signed_summary(&dkg_engines, 0, 9, None);
+        let second = signed_summary(&dkg_engines, 1, 19, Some(&first));
+        let group_public_key = first.quorum_public_key.clone();
+
+        // NOTE: tallies aren't signed, but the next summary commits to them
+        let mut inflated = first.clone();
+        inflated.block_rewards += 1;
+        verify_epoch_summary(&inflated, &group_public_key).unwrap();
+        assert!(matches!(
+            verify_epoch_summary_chain(&[inflated, second.clone()], &group_public_key),
+            Err(SignerError::EpochSummaryError(_))
+        ));
+
+        let orphan = signed_summary(&dkg_engines, 2, 29, None);
+        assert!(matches!(
+            verify_epoch_summary_chain(&[first.clone(), orphan], &group_public_key),
+            Err(SignerError::EpochSummaryError(_))
+        ));
+
+        let mut tampered = second.clone();
+        tampered.state_root = hex::encode([8u8; 32]);
+        assert!(matches!(
+            verify_epoch_summary(&tampered, &group_public_key),
+            Err(SignerError::EpochSummaryError(_))
+        ));
+
+        // NOTE: a header that matches the claimed state root no longer hashes
+        // to the signed block hash
+        tampered.header.state_root = tampered.state_root.clone();
+        tampered.block_hash = tampered.header.hash();
+        assert!(matches!(
+            verify_epoch_summary(&tampered, &group_public_key),
+            Err(SignerError::SignatureVerificationError(_))
+        ));
+
+        let other_quorum = generate_dkg_engine_with_states().await;
+        let foreign = signed_summary(&other_quorum, 1, 19, Some(&first));
+        assert!(matches!(
+            verify_epoch_summary_chain(&[first, foreign], &group_public_key),
+            Err(SignerError::EpochSummaryError(_))
+        ));
+    }
+}
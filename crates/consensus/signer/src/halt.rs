@@ -0,0 +1,77 @@
+//! Verification of the halt directives the harvester quorum issues to stop
+//! and resume block production.
+pub use block::halt::{HaltAction, HaltDirective, SignedHaltDirective};
+use block_verifier::verify_threshold_signature;
+use primitives::QuorumPubkey;
+
+use crate::types::{SignerError, SignerResult};
+
+/// Checks that a halt directive was signed by the quorum holding
+/// `group_public_key`.
+pub fn verify_halt_directive(
+    signed: &SignedHaltDirective,
+    group_public_key: &QuorumPubkey,
+) -> SignerResult<()> {
+    if &signed.quorum_public_key != group_public_key {
+        return Err(SignerError::HaltDirectiveError(format!(
+            "directive was signed by quorum {}, not {group_public_key}",
+            signed.quorum_public_key
+        )));
+    }
+
+    let signature = hex::decode(&signed.signature)
+        .map_err(|err| SignerError::HaltDirectiveError(format!("invalid signature: {err}")))?;
+
+    verify_threshold_signature(
+        group_public_key.as_bytes(),
+        &signed.directive.payload_hash(),
+        &signature,
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use dkg_engine::test_utils::{generate_dkg_engine_with_states, threshold_sign};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn only_directives_signed_by_the_trusted_quorum_verify() {
+        let dkg_engines = generate_dkg_engine_with_states().await;
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+        let group_public_key = QuorumPubkey::from(public_key_set.public_key());
+
+        let directive = HaltDirective {
+            action: HaltAction::Halt,
+            round: 42,
+            reason: "critical bug in txn validation".to_string(),
+        };
+
+        let signature = threshold_sign(&dkg_engines, directive.payload_hash());
+
+        let signed = SignedHaltDirective {
+            directive,
+            quorum_public_key: group_public_key,
+            signature: hex::encode(signature.to_bytes()),
+        };
+
+        verify_halt_directive(&signed, &group_public_key).unwrap();
+
+        // NOTE: a signed halt must not double as a resume
+        let mut tampered = signed.clone();
+        tampered.directive.action = HaltAction::Resume;
+        assert!(matches!(
+            verify_halt_directive(&tampered, &group_public_key),
+            Err(SignerError::SignatureVerificationError(_))
+        ));
+
+        let mut replayed = signed;
+        replayed.directive.round = 43;
+        assert!(matches!(
+            verify_halt_directive(&replayed, &group_public_key),
+            Err(SignerError::SignatureVerificationError(_))
+        ));
+    }
+}
@@ -1,2 +1,9 @@
+pub mod certified_txn;
+pub mod checkpoint;
+pub mod epoch_summary;
+pub mod halt;
+pub mod parameters;
+pub mod shares;
 pub mod signer;
 pub mod types;
+pub mod test_vectors;
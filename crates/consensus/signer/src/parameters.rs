@@ -0,0 +1,101 @@
+//! Verification of the protocol parameter changes the harvester quorum
+//! approves.
+use block_verifier::verify_threshold_signature;
+use primitives::QuorumPubkey;
+use vrrb_core::transactions::ParameterChange;
+
+use crate::types::{SignerError, SignerResult};
+
+/// Checks that the proposal a parameter change carries was signed by the
+/// quorum holding `group_public_key`, and stays within the parameter's
+/// bounds.
+pub fn verify_parameter_change(
+    change: &ParameterChange,
+    group_public_key: &QuorumPubkey,
+) -> SignerResult<()> {
+    if &change.quorum_public_key != group_public_key {
+        return Err(SignerError::ParameterChangeError(format!(
+            "change was signed by quorum {}, not {group_public_key}",
+            change.quorum_public_key
+        )));
+    }
+
+    change
+        .proposal
+        .parameter
+        .check(change.proposal.value)
+        .map_err(|err| SignerError::ParameterChangeError(err.to_string()))?;
+
+    let signature = hex::decode(&change.quorum_signature)
+        .map_err(|err| SignerError::ParameterChangeError(format!("invalid signature: {err}")))?;
+
+    verify_threshold_signature(
+        group_public_key.as_bytes(),
+        &change.proposal.payload_hash(),
+        &signature,
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use dkg_engine::test_utils::{generate_dkg_engine_with_states, threshold_sign};
+    use primitives::{generate_account_keypair, Address, ProtocolParameter, Signature};
+    use vrrb_core::transactions::{NewParameterChangeArgs, ParameterChangeProposal, Transaction};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn only_changes_signed_by_the_trusted_quorum_verify() {
+        let dkg_engines = generate_dkg_engine_with_states().await;
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+        let group_public_key = QuorumPubkey::from(public_key_set.public_key());
+
+        let proposal = ParameterChangeProposal {
+            parameter: ProtocolParameter::MaxBlockWeight,
+            value: 2_000_000,
+            effective_epoch: 7,
+            reason: "make room for larger proposals".to_string(),
+        };
+
+        let signature = threshold_sign(&dkg_engines, proposal.payload_hash());
+
+        let (secret_key, public_key) = generate_account_keypair();
+        let mut change = ParameterChange::new(NewParameterChangeArgs {
+            timestamp: 0,
+            sender_address: Address::new(public_key),
+            sender_public_key: public_key,
+            proposal,
+            quorum_public_key: group_public_key,
+            quorum_signature: hex::encode(signature.to_bytes()),
+            signature: Signature::from_compact(&[1; 64]).unwrap(),
+            nonce: 0,
+        });
+        change.sign(&secret_key);
+
+        verify_parameter_change(&change, &group_public_key).unwrap();
+
+        // NOTE: the signature doesn't carry over to another value or epoch
+        let mut tampered = change.clone();
+        tampered.proposal.value = 3_000_000;
+        assert!(matches!(
+            verify_parameter_change(&tampered, &group_public_key),
+            Err(SignerError::SignatureVerificationError(_))
+        ));
+
+        let mut replayed = change.clone();
+        replayed.proposal.effective_epoch = 8;
+        assert!(matches!(
+            verify_parameter_change(&replayed, &group_public_key),
+            Err(SignerError::SignatureVerificationError(_))
+        ));
+
+        let mut out_of_bounds = change;
+        out_of_bounds.proposal.value = 0;
+        assert!(matches!(
+            verify_parameter_change(&out_of_bounds, &group_public_key),
+            Err(SignerError::ParameterChangeError(_))
+        ));
+    }
+}
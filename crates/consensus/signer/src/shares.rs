@@ -0,0 +1,128 @@
+//! Validation of the partial signatures quorum members exchange, so junk
+//! shares are turned away as they arrive instead of failing aggregation
+//! later on.
+use hbbft::crypto::{PublicKeySet, PublicKeyShare, SignatureShare, PK_SIZE, SIG_SIZE};
+use primitives::NodeIdx;
+
+use crate::types::{SignerError, SignerResult};
+
+/// Checks a share sent by the quorum member at `node_idx`: the share and the
+/// public key share it came with must have the right length and decode to
+/// valid group elements, the key share must be the one the member was dealt
+/// in `public_key_set`, and the share must sign `payload_hash` under it.
+pub fn verify_signature_share(
+    public_key_set: &PublicKeySet,
+    node_idx: NodeIdx,
+    public_key_share: &[u8],
+    signature: &[u8],
+    payload_hash: &[u8],
+) -> SignerResult<()> {
+    let public_key_share = TryInto::<[u8; PK_SIZE]>::try_into(public_key_share)
+        .map_err(|_| {
+            SignerError::CorruptSignatureShare(format!(
+                "Invalid Public Key Share, Size must be {PK_SIZE} bytes"
+            ))
+        })
+        .and_then(|public_key_share| {
+            PublicKeyShare::from_bytes(public_key_share)
+                .map_err(|err| SignerError::CorruptSignatureShare(err.to_string()))
+        })?;
+
+    let signature = TryInto::<[u8; SIG_SIZE]>::try_into(signature)
+        .map_err(|_| {
+            SignerError::CorruptSignatureShare(format!(
+                "Invalid Signature, Size must be {SIG_SIZE} bytes"
+            ))
+        })
+        .and_then(|signature| {
+            SignatureShare::from_bytes(signature)
+                .map_err(|err| SignerError::CorruptSignatureShare(err.to_string()))
+        })?;
+
+    if public_key_share != public_key_set.public_key_share(node_idx as usize) {
+        return Err(SignerError::SignatureVerificationError(format!(
+            "public key share isn't the one dealt to node {node_idx}"
+        )));
+    }
+
+    if !public_key_share.verify(&signature, payload_hash) {
+        return Err(SignerError::SignatureVerificationError(format!(
+            "share of node {node_idx} doesn't sign the payload"
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use dkg_engine::test_utils::generate_dkg_engine_with_states;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn only_shares_of_the_sender_over_the_payload_verify() {
+        let dkg_engines = generate_dkg_engine_with_states().await;
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+        let payload_hash = b"convergence block hash".to_vec();
+
+        let secret_key_share = dkg_engines[1].dkg_state.secret_key_share_owned().unwrap();
+        let public_key_share = secret_key_share.public_key_share().to_bytes();
+        let signature = secret_key_share.sign(&payload_hash).to_bytes();
+
+        verify_signature_share(
+            &public_key_set,
+            1,
+            &public_key_share,
+            &signature,
+            &payload_hash,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            verify_signature_share(
+                &public_key_set,
+                1,
+                &public_key_share,
+                &signature[..SIG_SIZE - 1],
+                &payload_hash
+            ),
+            Err(SignerError::CorruptSignatureShare(_))
+        ));
+
+        // NOTE: all ones isn't the encoding of any point of the curve
+        assert!(matches!(
+            verify_signature_share(
+                &public_key_set,
+                1,
+                &public_key_share,
+                &[0xff; SIG_SIZE],
+                &payload_hash
+            ),
+            Err(SignerError::CorruptSignatureShare(_))
+        ));
+
+        // NOTE: a member can't pass its share off as another's
+        assert!(matches!(
+            verify_signature_share(
+                &public_key_set,
+                2,
+                &public_key_share,
+                &signature,
+                &payload_hash
+            ),
+            Err(SignerError::SignatureVerificationError(_))
+        ));
+
+        assert!(matches!(
+            verify_signature_share(
+                &public_key_set,
+                1,
+                &public_key_share,
+                &signature,
+                b"another block hash"
+            ),
+            Err(SignerError::SignatureVerificationError(_))
+        ));
+    }
+}
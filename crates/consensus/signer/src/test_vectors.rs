@@ -0,0 +1,352 @@
+//! Test vectors for threshold signature interoperability.
+//!
+//! A vector captures a fixed DKG outcome (the group's public key set and each
+//! member's secret key share), a message, and the partial and combined
+//! signatures this crate produces for it. Alternative client implementations
+//! can replay vectors to check they are compatible, and the golden vectors
+//! shipped with this crate catch any change to the signatures produced by
+//! [SignatureProvider].
+//!
+//! Golden vectors live under `vectors/` in this crate and are written by the
+//! ignored `regenerate_golden_vectors` test. Their DKG is seeded, so
+//! regenerating them only changes the file when the signatures do.
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+use dkg_engine::prelude::DkgState;
+use hbbft::crypto::{serde_impl::SerdeSecret, PublicKeySet, SecretKeyShare};
+use primitives::{NodeIdx, RawSignature, SignatureType};
+use serde::{Deserialize, Serialize};
+use vrrb_config::ThresholdConfig;
+
+use crate::{
+    signer::{SignatureProvider, Signer},
+    types::{SignerError, SignerResult},
+};
+
+/// A single threshold signature test vector. All binary fields are hex
+/// encoded; keys are bincode-serialized before encoding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThresholdSignatureVector {
+    pub name: String,
+
+    /// Degree of the group's polynomial, `threshold + 1` shares are required
+    /// to produce a combined signature
+    pub threshold: u16,
+
+    pub public_key_set: String,
+    pub secret_key_shares: BTreeMap<NodeIdx, String>,
+    pub message: String,
+
+    /// Expected partial signature of `message` for each secret key share
+    pub partial_signatures: BTreeMap<NodeIdx, String>,
+
+    /// Expected signature obtained by combining the first `threshold + 1`
+    /// partial signatures
+    pub combined_signature: String,
+}
+
+impl ThresholdSignatureVector {
+    /// Records the signatures the current [SignatureProvider] produces for
+    /// `message` given a fixed DKG outcome.
+    pub fn generate(
+        name: &str,
+        threshold: u16,
+        public_key_set: PublicKeySet,
+        secret_key_shares: BTreeMap<NodeIdx, SecretKeyShare>,
+        message: &[u8],
+    ) -> SignerResult<Self> {
+        let threshold_config = ThresholdConfig {
+            threshold,
+            upper_bound: secret_key_shares.len() as u16,
+        };
+        let mut partial_signatures = BTreeMap::new();
+        let mut encoded_shares = BTreeMap::new();
+
+        for (node_idx, secret_key_share) in secret_key_shares {
+            let provider = signature_provider(
+                &threshold_config,
+                public_key_set.clone(),
+                Some(secret_key_share.clone()),
+            );
+            let partial_signature = provider.generate_partial_signature(message.to_vec())?;
+
+            encoded_shares.insert(node_idx, encode_secret_key_share(&secret_key_share)?);
+            partial_signatures.insert(node_idx, partial_signature);
+        }
+
+        let provider = signature_provider(&threshold_config, public_key_set.clone(), None);
+        let combined_signature = combine(&provider, threshold, &partial_signatures)?;
+
+        Ok(Self {
+            name: name.to_string(),
+            threshold,
+            public_key_set: hex::encode(
+                bincode::serialize(&public_key_set)
+                    .map_err(|err| SignerError::TestVectorError(err.to_string()))?,
+            ),
+            secret_key_shares: encoded_shares,
+            message: hex::encode(message),
+            partial_signatures: partial_signatures
+                .into_iter()
+                .map(|(node_idx, sig)| (node_idx, hex::encode(sig)))
+                .collect(),
+            combined_signature: hex::encode(combined_signature),
+        })
+    }
+}
+
+/// Checks that a [ThresholdSignatureVector] is reproduced exactly by this
+/// crate:
+///
+/// * every secret key share signs `message` into the expected partial
+///   signature
+/// * every expected partial signature verifies against the public key set
+/// * combining the first `threshold + 1` partial signatures yields the expected
+///   combined signature, which verifies against the group public key
+pub fn verify_test_vector(vector: &ThresholdSignatureVector) -> SignerResult<()> {
+    let public_key_set: PublicKeySet = bincode::deserialize(&decode(&vector.public_key_set)?)
+        .map_err(|err| SignerError::TestVectorError(err.to_string()))?;
+    let message = decode(&vector.message)?;
+    let threshold_config = ThresholdConfig {
+        threshold: vector.threshold,
+        upper_bound: vector.partial_signatures.len() as u16,
+    };
+
+    for (node_idx, encoded_share) in vector.secret_key_shares.iter() {
+        let secret_key_share = decode_secret_key_share(encoded_share)?;
+        let provider = signature_provider(
+            &threshold_config,
+            public_key_set.clone(),
+            Some(secret_key_share),
+        );
+
+        let partial_signature = provider.generate_partial_signature(message.clone())?;
+        let expected = vector.partial_signatures.get(node_idx).ok_or_else(|| {
+            SignerError::TestVectorError(format!(
+                "{}: missing partial signature for node {node_idx}",
+                vector.name
+            ))
+        })?;
+
+        if hex::encode(&partial_signature) != *expected {
+            return Err(SignerError::TestVectorError(format!(
+                "{}: partial signature mismatch for node {node_idx}",
+                vector.name
+            )));
+        }
+    }
+
+    let provider = signature_provider(&threshold_config, public_key_set, None);
+    let mut partial_signatures = BTreeMap::new();
+
+    for (node_idx, encoded_sig) in vector.partial_signatures.iter() {
+        let signature = decode(encoded_sig)?;
+        let is_valid = provider.verify_signature(
+            *node_idx,
+            message.clone(),
+            signature.clone(),
+            SignatureType::PartialSignature,
+        )?;
+
+        if !is_valid {
+            return Err(SignerError::TestVectorError(format!(
+                "{}: partial signature for node {node_idx} does not verify",
+                vector.name
+            )));
+        }
+
+        partial_signatures.insert(*node_idx, signature);
+    }
+
+    let combined_signature = combine(&provider, vector.threshold, &partial_signatures)?;
+
+    if hex::encode(&combined_signature) != vector.combined_signature {
+        return Err(SignerError::TestVectorError(format!(
+            "{}: combined signature mismatch",
+            vector.name
+        )));
+    }
+
+    // NOTE: the node index is ignored when verifying threshold signatures
+    let is_valid = provider.verify_signature(
+        0,
+        message,
+        combined_signature,
+        SignatureType::ThresholdSignature,
+    )?;
+
+    if !is_valid {
+        return Err(SignerError::TestVectorError(format!(
+            "{}: combined signature does not verify",
+            vector.name
+        )));
+    }
+
+    Ok(())
+}
+
+/// Combines the first `threshold + 1` partial signatures, so vectors don't
+/// depend on which subset of shares a client happens to pick.
+fn combine(
+    provider: &SignatureProvider,
+    threshold: u16,
+    partial_signatures: &BTreeMap<NodeIdx, RawSignature>,
+) -> SignerResult<RawSignature> {
+    let shares: BTreeMap<NodeIdx, RawSignature> = partial_signatures
+        .iter()
+        .take(threshold as usize + 1)
+        .map(|(node_idx, sig)| (*node_idx, sig.clone()))
+        .collect();
+
+    provider.generate_quorum_signature(threshold + 1, shares)
+}
+
+fn signature_provider(
+    threshold_config: &ThresholdConfig,
+    public_key_set: PublicKeySet,
+    secret_key_share: Option<SecretKeyShare>,
+) -> SignatureProvider {
+    let mut dkg_state = DkgState::new();
+    dkg_state.set_public_key_set(Some(public_key_set));
+    dkg_state.set_secret_key_share(secret_key_share);
+
+    SignatureProvider::new(Arc::new(RwLock::new(dkg_state)), threshold_config.clone())
+}
+
+fn decode(value: &str) -> SignerResult<Vec<u8>> {
+    hex::decode(value).map_err(|err| SignerError::TestVectorError(err.to_string()))
+}
+
+fn encode_secret_key_share(secret_key_share: &SecretKeyShare) -> SignerResult<String> {
+    bincode::serialize(&SerdeSecret(secret_key_share))
+        .map(hex::encode)
+        .map_err(|err| SignerError::TestVectorError(err.to_string()))
+}
+
+fn decode_secret_key_share(value: &str) -> SignerResult<SecretKeyShare> {
+    bincode::deserialize::<SerdeSecret<SecretKeyShare>>(&decode(value)?)
+        .map(|secret_key_share| secret_key_share.0)
+        .map_err(|err| SignerError::TestVectorError(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use dkg_engine::test_utils::generate_seeded_dkg_engine_with_states;
+
+    use super::*;
+
+    const MESSAGE: &str = "This is test message";
+
+    /// Seeds of the DKGs behind the golden vectors
+    const GOLDEN_SEEDS: [(&str, u64); 2] = [
+        ("four-nodes-threshold-one", 0x6011d),
+        ("four-nodes-threshold-one-reseeded", 0x6011e),
+    ];
+
+    fn golden_vectors_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("vectors")
+            .join("threshold_signatures.json")
+    }
+
+    async fn generate_vector(name: &str, seed: u64) -> ThresholdSignatureVector {
+        let dkg_engines = generate_seeded_dkg_engine_with_states(seed).await;
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+
+        let secret_key_shares = dkg_engines
+            .into_iter()
+            .enumerate()
+            .map(|(node_idx, engine)| {
+                (
+                    node_idx as NodeIdx,
+                    engine.dkg_state.secret_key_share_owned().unwrap(),
+                )
+            })
+            .collect();
+
+        ThresholdSignatureVector::generate(
+            name,
+            1,
+            public_key_set,
+            secret_key_shares,
+            MESSAGE.as_bytes(),
+        )
+        .unwrap()
+    }
+
+    async fn generate_golden_vectors() -> Vec<ThresholdSignatureVector> {
+        let mut vectors = Vec::new();
+        for (name, seed) in GOLDEN_SEEDS {
+            vectors.push(generate_vector(name, seed).await);
+        }
+
+        vectors
+    }
+
+    #[tokio::test]
+    async fn generated_vectors_survive_a_json_round_trip() {
+        let vector = generate_vector("round-trip", 1).await;
+
+        let encoded = serde_json::to_string_pretty(&vector).unwrap();
+        let decoded: ThresholdSignatureVector = serde_json::from_str(&encoded).unwrap();
+
+        assert_eq!(decoded, vector);
+        verify_test_vector(&decoded).unwrap();
+    }
+
+    #[tokio::test]
+    async fn tampered_vectors_are_rejected() {
+        let mut vector = generate_vector("tampered", 1).await;
+        let other = generate_vector("other", 2).await;
+
+        vector.combined_signature = other.combined_signature;
+
+        assert!(matches!(
+            verify_test_vector(&vector),
+            Err(SignerError::TestVectorError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn golden_vectors_are_reproduced() {
+        let path = golden_vectors_path();
+        let contents = fs::read_to_string(&path).unwrap_or_else(|err| {
+            panic!(
+                "{}: {err}, run the ignored regenerate_golden_vectors test to write it",
+                path.display()
+            )
+        });
+        let vectors: Vec<ThresholdSignatureVector> = serde_json::from_str(&contents).unwrap();
+
+        assert_eq!(vectors.len(), GOLDEN_SEEDS.len());
+        for vector in vectors.iter() {
+            if let Err(err) = verify_test_vector(vector) {
+                panic!("{}: {err}", path.display());
+            }
+        }
+
+        // NOTE: the DKG behind the vectors is seeded, so the same keys are generated
+        // and sign into the same signatures unless the scheme changed
+        assert_eq!(generate_golden_vectors().await, vectors);
+    }
+
+    /// Writes a fresh set of golden vectors. Only run this when the signature
+    /// scheme is intentionally changed, and commit the resulting file.
+    #[tokio::test]
+    #[ignore]
+    async fn regenerate_golden_vectors() {
+        let path = golden_vectors_path();
+
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            path,
+            serde_json::to_string_pretty(&generate_golden_vectors().await).unwrap(),
+        )
+        .unwrap();
+    }
+}
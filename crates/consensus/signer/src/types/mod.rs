@@ -1,3 +1,4 @@
+use block_verifier::VerifyError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -17,6 +18,29 @@ pub enum SignerError {
     SignatureVerificationError(String),
     #[error("SignerError: ")]
     CorruptSignatureShare(String),
+    #[error("SignerError: Test vector check failed: {0}")]
+    TestVectorError(String),
+    #[error("SignerError: Checkpoint check failed: {0}")]
+    CheckpointError(String),
+    #[error("SignerError: Epoch summary check failed: {0}")]
+    EpochSummaryError(String),
+    #[error("SignerError: Halt directive check failed: {0}")]
+    HaltDirectiveError(String),
+    #[error("SignerError: Parameter change check failed: {0}")]
+    ParameterChangeError(String),
+    #[error("SignerError: Certified txn check failed: {0}")]
+    CertifiedTxnError(String),
+}
+
+impl From<VerifyError> for SignerError {
+    fn from(err: VerifyError) -> Self {
+        match err {
+            VerifyError::InvalidThresholdSignature(err) => {
+                SignerError::ThresholdSignatureError(err)
+            },
+            err => SignerError::SignatureVerificationError(err.to_string()),
+        }
+    }
 }
 
 pub type SignerResult<T> = Result<T, SignerError>;
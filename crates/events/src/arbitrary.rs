@@ -0,0 +1,46 @@
+//! [Arbitrary] impl of events, so property based tests can generate them.
+
+use std::net::SocketAddr;
+
+use block::{header::BlockHeader, Block, Certificate, ConvergenceBlock, ProposalBlock};
+use proptest::{collection, prelude::*};
+use vrrb_core::{
+    arbitrary::{node_id, txn_digest},
+    claim::Claim,
+    transactions::TransactionKind,
+};
+
+use crate::Event;
+
+/// Generates the events carrying blocks, certificates, claims and txns,
+/// along with a few carrying plain values. Other variants aren't generated.
+impl Arbitrary for Event {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(Event::NoOp),
+            Just(Event::Stop),
+            node_id().prop_map(Event::Ping),
+            any::<bool>().prop_map(Event::MaintenanceModeRequested),
+            "[0-9a-f]{64}".prop_map(Event::UpdateState),
+            collection::vec(any::<SocketAddr>(), 0..3).prop_map(Event::PeerSyncFailed),
+            txn_digest().prop_map(Event::TxnAddedToMempool),
+            any::<TransactionKind>().prop_map(Event::NewTxnCreated),
+            any::<TransactionKind>().prop_map(Event::RelayedTxnReceived),
+            Just(Event::MinerElectionRetryDue),
+            any::<TransactionKind>().prop_map(Event::TxnValidated),
+            any::<Claim>().prop_map(Event::ClaimReceived),
+            (node_id(), any::<Claim>())
+                .prop_map(|(node_id, claim)| Event::ClaimAbandoned(node_id, claim)),
+            any::<Block>().prop_map(Event::BlockReceived),
+            any::<BlockHeader>().prop_map(Event::MinerElectionStarted),
+            any::<ProposalBlock>().prop_map(Event::ProposalBlockCreated),
+            any::<ConvergenceBlock>().prop_map(Event::ConvergenceBlockCertified),
+            any::<Certificate>().prop_map(Event::BlockCertificateCreated),
+            collection::vec(any::<Certificate>(), 0..3).prop_map(Event::CertificatesReceived),
+        ]
+        .boxed()
+    }
+}
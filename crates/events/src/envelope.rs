@@ -0,0 +1,430 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{
+    de::{self, DeserializeOwned, Visitor},
+    forward_to_deserialize_any, Deserialize, Deserializer, Serialize,
+};
+use serde_json::Value;
+use telemetry::warn;
+use thiserror::Error;
+
+use crate::{Event, PeerData};
+
+/// Version 1 of the event schema: an [Event] serialized as bare JSON.
+pub const LEGACY_EVENT_SCHEMA_VERSION: u16 = 1;
+
+/// Newest version of the event schema this node understands. Events are only
+/// encoded with it for peers that advertise it, see [Event::encode_for].
+pub const EVENT_SCHEMA_VERSION: u16 = 2;
+
+/// Versioned wrapper around a serialized [Event], or any other externally
+/// tagged enum nodes exchange, see [EventEnvelope::wrap].
+///
+/// Variants are tagged by name rather than by position, so nodes can skip
+/// events introduced by newer versions of the schema instead of failing to
+/// decode everything else they receive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    pub version: u16,
+    pub kind: String,
+    #[serde(default)]
+    pub payload: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EventDecodeError {
+    #[error("event is neither a versioned envelope nor a legacy event: {0}")]
+    Malformed(String),
+
+    #[error("event schema version {0} is not supported")]
+    UnsupportedVersion(u16),
+
+    #[error("unknown event kind: {0}")]
+    UnknownVariant(String),
+}
+
+/// Point in time view of the event decoding counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventDecodeStats {
+    pub decoded: u64,
+    pub legacy_decoded: u64,
+    pub unknown_variants: u64,
+    pub unsupported_versions: u64,
+    pub malformed: u64,
+}
+
+/// Counters updated whenever an event is decoded through
+/// [Event::decode_or_noop].
+#[derive(Debug, Default)]
+pub struct EventDecodeMetrics {
+    decoded: AtomicU64,
+    legacy_decoded: AtomicU64,
+    unknown_variants: AtomicU64,
+    unsupported_versions: AtomicU64,
+    malformed: AtomicU64,
+}
+
+impl EventDecodeMetrics {
+    const fn new() -> Self {
+        Self {
+            decoded: AtomicU64::new(0),
+            legacy_decoded: AtomicU64::new(0),
+            unknown_variants: AtomicU64::new(0),
+            unsupported_versions: AtomicU64::new(0),
+            malformed: AtomicU64::new(0),
+        }
+    }
+
+    pub fn stats(&self) -> EventDecodeStats {
+        EventDecodeStats {
+            decoded: self.decoded.load(Ordering::Relaxed),
+            legacy_decoded: self.legacy_decoded.load(Ordering::Relaxed),
+            unknown_variants: self.unknown_variants.load(Ordering::Relaxed),
+            unsupported_versions: self.unsupported_versions.load(Ordering::Relaxed),
+            malformed: self.malformed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+static EVENT_DECODE_METRICS: EventDecodeMetrics = EventDecodeMetrics::new();
+
+/// Returns the counters of every event decoded by this process so far
+pub fn event_decode_stats() -> EventDecodeStats {
+    EVENT_DECODE_METRICS.stats()
+}
+
+impl EventEnvelope {
+    pub fn new(event: &Event) -> Self {
+        Self::wrap(event)
+    }
+
+    /// Wraps a variant of an externally tagged enum, e.g. the events sent to
+    /// peers over the network
+    pub fn wrap<T: Serialize>(value: &T) -> Self {
+        // Externally tagged enums serialize unit variants as their name and
+        // every other variant as a single entry map from name to payload
+        let (kind, payload) = match serde_json::to_value(value).unwrap_or_default() {
+            Value::String(kind) => (kind, Value::Null),
+            Value::Object(map) => map.into_iter().next().unwrap_or_default(),
+            _ => (String::from("NoOp"), Value::Null),
+        };
+
+        Self {
+            version: EVENT_SCHEMA_VERSION,
+            kind,
+            payload,
+        }
+    }
+
+    pub fn into_event(self) -> Result<Event, EventDecodeError> {
+        self.into_inner()
+    }
+
+    /// Decodes the enum variant the envelope wraps, see [EventEnvelope::wrap]
+    pub fn into_inner<T: DeserializeOwned>(self) -> Result<T, EventDecodeError> {
+        if !(LEGACY_EVENT_SCHEMA_VERSION..=EVENT_SCHEMA_VERSION).contains(&self.version) {
+            return Err(EventDecodeError::UnsupportedVersion(self.version));
+        }
+
+        if self.payload.is_null() {
+            if let Ok(event) = decode_tagged(Value::String(self.kind.clone())) {
+                return Ok(event);
+            }
+        }
+
+        decode_tagged(Value::Object(
+            [(self.kind, self.payload)].into_iter().collect(),
+        ))
+    }
+}
+
+impl Event {
+    /// Encodes the event with the given schema version, so it can be sent to
+    /// nodes that have not been upgraded yet.
+    pub fn encode_with_version(&self, version: u16) -> Vec<u8> {
+        if version == LEGACY_EVENT_SCHEMA_VERSION {
+            return serde_json::to_vec(self).unwrap_or_default();
+        }
+
+        serde_json::to_vec(&EventEnvelope::new(self)).unwrap_or_default()
+    }
+
+    /// Encodes the event with the legacy schema version, which every node
+    /// decodes
+    pub fn encode(&self) -> Vec<u8> {
+        self.encode_with_version(LEGACY_EVENT_SCHEMA_VERSION)
+    }
+
+    /// Encodes the event with the newest schema version both this node and
+    /// `peer` understand. Peers stay on the legacy schema until they advertise
+    /// a newer one
+    pub fn encode_for(&self, peer: &PeerData) -> Vec<u8> {
+        let version = peer
+            .event_schema_version
+            .clamp(LEGACY_EVENT_SCHEMA_VERSION, EVENT_SCHEMA_VERSION);
+
+        self.encode_with_version(version)
+    }
+
+    /// Decodes an event encoded with the current or any earlier supported
+    /// schema version.
+    pub fn decode(data: &[u8]) -> Result<Event, EventDecodeError> {
+        decode_event(data).map(|(event, _)| event)
+    }
+
+    /// Decodes an event like [Event::decode], falling back to [Event::NoOp]
+    /// when it can't be understood. Every outcome is counted in the event
+    /// decoding metrics.
+    pub fn decode_or_noop(data: &[u8]) -> Event {
+        let metrics = &EVENT_DECODE_METRICS;
+
+        match decode_event(data) {
+            Ok((event, version)) => {
+                metrics.decoded.fetch_add(1, Ordering::Relaxed);
+
+                if version == LEGACY_EVENT_SCHEMA_VERSION {
+                    metrics.legacy_decoded.fetch_add(1, Ordering::Relaxed);
+                }
+
+                event
+            },
+            Err(err) => {
+                let counter = match err {
+                    EventDecodeError::UnknownVariant(_) => &metrics.unknown_variants,
+                    EventDecodeError::UnsupportedVersion(_) => &metrics.unsupported_versions,
+                    EventDecodeError::Malformed(_) => &metrics.malformed,
+                };
+                counter.fetch_add(1, Ordering::Relaxed);
+
+                warn!("dropping undecodable event: {err}");
+
+                Event::NoOp
+            },
+        }
+    }
+}
+
+/// Decodes an event, returning it along with the schema version it was
+/// encoded with
+fn decode_event(data: &[u8]) -> Result<(Event, u16), EventDecodeError> {
+    if let Ok(envelope) = serde_json::from_slice::<EventEnvelope>(data) {
+        let version = envelope.version;
+        return envelope.into_event().map(|event| (event, version));
+    }
+
+    let tagged = serde_json::from_slice::<Value>(data)
+        .map_err(|err| EventDecodeError::Malformed(err.to_string()))?;
+
+    decode_tagged(tagged).map(|event| (event, LEGACY_EVENT_SCHEMA_VERSION))
+}
+
+fn decode_tagged<T: DeserializeOwned>(tagged: Value) -> Result<T, EventDecodeError> {
+    let kind = match &tagged {
+        Value::String(kind) => kind.clone(),
+        Value::Object(map) if map.len() == 1 => map.keys().next().cloned().unwrap_or_default(),
+        _ => return Err(EventDecodeError::Malformed(tagged.to_string())),
+    };
+
+    if !variant_names::<T>().contains(&kind.as_str()) {
+        return Err(EventDecodeError::UnknownVariant(kind));
+    }
+
+    serde_json::from_value::<T>(tagged).map_err(|err| EventDecodeError::Malformed(err.to_string()))
+}
+
+/// Returns the names every variant of the enum `T` is tagged with
+fn variant_names<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut kinds: &'static [&'static str] = &[];
+    let _ = T::deserialize(VariantNames(&mut kinds));
+
+    kinds
+}
+
+/// Deserializer that records the variant names an enum's derived
+/// [Deserialize] impl hands over, then bails out without decoding anything
+struct VariantNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de, 'a> Deserializer<'de> for VariantNames<'a> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V>(self, _: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::custom("only enums have variant names"))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        variants: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        *self.0 = variants;
+
+        Err(de::Error::custom("variant names recorded"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use primitives::{KademliaPeerId, NodeType};
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+
+    fn peer(event_schema_version: u16) -> PeerData {
+        let addr = "127.0.0.1:0".parse().unwrap();
+
+        PeerData {
+            node_id: "node-1".to_string(),
+            node_type: NodeType::Validator,
+            kademlia_peer_id: KademliaPeerId::rand(),
+            udp_gossip_addr: addr,
+            raptorq_gossip_addr: addr,
+            kademlia_liveness_addr: addr,
+            alternate_udp_gossip_addrs: vec![],
+            validator_public_key: Keypair::random().validator_public_key_owned(),
+            locality: Default::default(),
+            claim_signature: None,
+            event_schema_version,
+        }
+    }
+
+    fn sample_events() -> Vec<Event> {
+        vec![
+            Event::NoOp,
+            Event::Stop,
+            Event::MempoolReconciliationDue,
+            Event::Ping("node-1".to_string()),
+            Event::PeerSyncFailed(vec!["127.0.0.1:8080".parse().unwrap()]),
+        ]
+    }
+
+    #[test]
+    fn events_decode_across_supported_schema_versions() {
+        for version in LEGACY_EVENT_SCHEMA_VERSION..=EVENT_SCHEMA_VERSION {
+            for event in sample_events() {
+                let encoded = event.encode_with_version(version);
+
+                assert_eq!(Event::decode(&encoded).unwrap(), event, "version {version}");
+            }
+        }
+    }
+
+    /// Legacy encodings of a few events, frozen so changes to [Event] that
+    /// would break nodes still on the legacy schema get caught
+    fn legacy_fixtures() -> Vec<(Event, &'static [u8])> {
+        vec![
+            (Event::NoOp, br#""NoOp""#),
+            (Event::Stop, br#""Stop""#),
+            (
+                Event::MempoolReconciliationDue,
+                br#""MempoolReconciliationDue""#,
+            ),
+            (Event::Ping("node-1".to_string()), br#"{"Ping":"node-1"}"#),
+            (
+                Event::PeerSyncFailed(vec!["127.0.0.1:8080".parse().unwrap()]),
+                br#"{"PeerSyncFailed":["127.0.0.1:8080"]}"#,
+            ),
+        ]
+    }
+
+    #[test]
+    fn legacy_encodings_match_their_fixtures() {
+        for (event, fixture) in legacy_fixtures() {
+            assert_eq!(
+                event.encode_with_version(LEGACY_EVENT_SCHEMA_VERSION),
+                fixture,
+                "{event:?}"
+            );
+            assert_eq!(Event::decode(fixture).unwrap(), event);
+        }
+    }
+
+    #[test]
+    fn events_are_wrapped_in_an_envelope_only_for_peers_that_advertise_it() {
+        let event = Event::Ping("node-1".to_string());
+
+        assert_eq!(event.encode(), br#"{"Ping":"node-1"}"#);
+
+        assert_eq!(
+            event.encode_for(&peer(LEGACY_EVENT_SCHEMA_VERSION)),
+            event.encode()
+        );
+
+        let envelope: EventEnvelope =
+            serde_json::from_slice(&event.encode_for(&peer(EVENT_SCHEMA_VERSION + 1))).unwrap();
+
+        assert_eq!(envelope.version, EVENT_SCHEMA_VERSION);
+        assert_eq!(envelope.kind, "Ping");
+    }
+
+    #[test]
+    fn malformed_payloads_of_known_kinds_are_not_unknown_variants() {
+        let envelope = EventEnvelope {
+            version: EVENT_SCHEMA_VERSION,
+            kind: "Ping".to_string(),
+            payload: Value::Bool(true),
+        };
+        let encoded = serde_json::to_vec(&envelope).unwrap();
+
+        assert!(matches!(
+            Event::decode(&encoded),
+            Err(EventDecodeError::Malformed(_))
+        ));
+        assert!(variant_names::<Event>().contains(&"BlockCertificateCreated"));
+    }
+
+    #[test]
+    fn unknown_variants_decode_as_noop() {
+        let unknown_before = event_decode_stats().unknown_variants;
+
+        let envelope = EventEnvelope {
+            version: EVENT_SCHEMA_VERSION,
+            kind: "IntroducedLater".to_string(),
+            payload: Value::Bool(true),
+        };
+        let encoded = serde_json::to_vec(&envelope).unwrap();
+
+        assert_eq!(
+            Event::decode(&encoded),
+            Err(EventDecodeError::UnknownVariant(
+                "IntroducedLater".to_string()
+            ))
+        );
+        assert_eq!(Event::decode_or_noop(&encoded), Event::NoOp);
+
+        let legacy = br#"{"IntroducedLater":true}"#;
+        assert_eq!(Event::decode_or_noop(legacy), Event::NoOp);
+
+        assert!(event_decode_stats().unknown_variants >= unknown_before + 2);
+    }
+
+    #[test]
+    fn unsupported_versions_and_garbage_decode_as_noop() {
+        let envelope = EventEnvelope {
+            version: EVENT_SCHEMA_VERSION + 1,
+            ..EventEnvelope::new(&Event::Stop)
+        };
+        let encoded = serde_json::to_vec(&envelope).unwrap();
+
+        assert_eq!(
+            Event::decode(&encoded),
+            Err(EventDecodeError::UnsupportedVersion(
+                EVENT_SCHEMA_VERSION + 1
+            ))
+        );
+        assert_eq!(Event::decode_or_noop(&encoded), Event::NoOp);
+        assert_eq!(Event::decode_or_noop(b"not json"), Event::NoOp);
+    }
+}
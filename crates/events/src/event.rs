@@ -1,16 +1,19 @@
 use std::net::SocketAddr;
 
 use block::{
-    header::BlockHeader, Block, BlockHash, Certificate, ConvergenceBlock, ProposalBlock, RefHash,
+    header::BlockHeader, Block, BlockHash, Certificate, ConvergenceBlock, HaltDirective,
+    ProposalBlock, RefHash, SignedHaltDirective,
 };
 use ethereum_types::U256;
 use hbbft::sync_key_gen::Ack;
 use hbbft::{crypto::PublicKeySet, sync_key_gen::Part};
+use mempool::{MempoolSketch, MempoolStats};
 use primitives::{
     Address, Epoch, FarmerQuorumThreshold, NodeId, NodeIdx, ProgramExecutionOutput,
     PublicKeyShareVec, RawSignature, Round, Seed, TxnValidationStatus, ValidatorPublicKeyShare,
 };
 use serde::{Deserialize, Serialize};
+use vrrb_config::{QuorumMembershipConfig, ValidatorSetDiff};
 use vrrb_core::claim::Claim;
 use vrrb_core::transactions::{TransactionDigest, TransactionKind};
 
@@ -88,8 +91,13 @@ pub enum Event {
     /// be invalid.
     ClaimAbandoned(NodeId, Claim),
 
-    /// A peer joined the network, should be added to the node's peer list
-    PeerJoined(PeerData),
+    /// A peer joined the network, should be added to the node's peer list.
+    /// `source` is the address its announcement arrived from, unless it
+    /// didn't come over the network
+    PeerJoined {
+        peer_data: PeerData,
+        source: Option<SocketAddr>,
+    },
 
     /// A peer joined the network and was added to the node's peer list
     NodeAddedToPeerList(PeerData),
@@ -119,6 +127,10 @@ pub enum Event {
     /// Signals thaa a node acknowledges belonging to a quorum
     QuorumMembershipSet(NodeId),
 
+    /// Carries the membership of the quorum a node was just configured into,
+    /// so its members can be prioritized when sending consensus messages
+    QuorumMembershipConfigured(QuorumMembershipConfig),
+
     PartCommitmentCreated(NodeId, Part),
 
     PartCommitmentAcknowledged {
@@ -151,6 +163,8 @@ pub enum Event {
         votes: Vec<Vote>,
         signature: RawSignature,
         digest: TransactionDigest,
+        /// Public key of the farmer quorum that certified the transaction
+        quorum_key: PublicKeyShareVec,
         /// OUtput of the program executed
         execution_result: ProgramExecutionOutput,
         farmer_id: NodeId,
@@ -170,6 +184,273 @@ pub enum Event {
 
     QuorumElectionStarted(BlockHeader),
 
+    /// Emitted once a node has moved its quorum into a new epoch, carrying the
+    /// validators that joined or left it so peers can update their view of
+    /// the quorum
+    ValidatorSetUpdated(ValidatorSetDiff),
+
+    /// A validator set diff broadcasted by a peer
+    ValidatorSetUpdateReceived(ValidatorSetDiff),
+
+    /// Asks the node to enter (`true`) or leave (`false`) maintenance mode,
+    /// during which it keeps following the chain without mining or voting
+    MaintenanceModeRequested(bool),
+
+    /// Emitted once a node entered or left maintenance mode, so its quorum
+    /// knows whether to expect its participation
+    MaintenanceModeChanged {
+        node_id: NodeId,
+        paused: bool,
+    },
+
+    /// A maintenance mode change announced by a peer
+    PeerMaintenanceModeChanged {
+        node_id: NodeId,
+        paused: bool,
+    },
+
+    /// Asks the node to move to a freshly generated validator key. Peers
+    /// pick the new key up from the announcement the node broadcasts
+    ValidatorKeyRotationRequested,
+
+    /// Emitted once the node moved to a new validator key, so the rotation
+    /// gets announced to its peers
+    ValidatorKeyRotated(ValidatorKeyRotation),
+
+    /// A validator key rotation announced by a peer, whose signatures were
+    /// checked on receipt
+    PeerValidatorKeyRotated(ValidatorKeyRotation),
+
+    /// Asks the node to sign a halt or resume directive with its share of the
+    /// harvester quorum's key. The directive only takes effect once enough
+    /// harvesters were asked to sign the very same one
+    HaltDirectiveRequested(HaltDirective),
+
+    /// Emitted once the node signed a directive, so its share gets broadcast
+    /// to the rest of the harvester quorum
+    HaltDirectiveSigned {
+        node_id: NodeId,
+        node_idx: NodeIdx,
+        directive: HaltDirective,
+        signature: RawSignature,
+    },
+
+    /// A share of a directive broadcast by a peer
+    PeerHaltDirectiveSigned {
+        node_id: NodeId,
+        node_idx: NodeIdx,
+        directive: HaltDirective,
+        signature: RawSignature,
+    },
+
+    /// Emitted once the shares of a directive were combined into the quorum's
+    /// signature and the node acted on it, so every other node does too
+    HaltDirectiveCertified(SignedHaltDirective),
+
+    /// A quorum-signed directive broadcast by a peer
+    PeerHaltDirectiveCertified(SignedHaltDirective),
+
+    /// Signals it's time to reconcile the node's mempool with a peer's
+    MempoolReconciliationDue,
+
+    /// Signals it's time to re-broadcast the consensus messages peers haven't
+    /// acknowledged yet
+    RebroadcastDue,
+
+    /// Peers the node is due to exchange messages with in `round`, looked up
+    /// from its duties, to be connected to before the round begins
+    RoundPeersScheduled {
+        round: Round,
+        peers: Vec<NodeId>,
+    },
+
+    /// A point-to-point consensus message was received from `sender_id`, who
+    /// awaits an acknowledgement of the message known by `digest`
+    ConsensusMessageReceived {
+        sender_id: NodeId,
+        digest: String,
+    },
+
+    /// The peer at `recipient_addr` acknowledged the consensus message known
+    /// by `digest`
+    ConsensusMessageAcknowledged {
+        recipient_addr: SocketAddr,
+        digest: String,
+    },
+
+    /// A sketch of a peer's mempool, to be compared against the local one
+    MempoolSketchReceived {
+        sender_id: NodeId,
+        sketch: MempoolSketch,
+    },
+
+    /// Digests a peer holds within the ranges where its mempool and the local
+    /// one differ
+    MempoolDigestsOffered {
+        sender_id: NodeId,
+        digests: Vec<TransactionDigest>,
+    },
+
+    /// Transactions a peer found missing from its mempool
+    MempoolTxnsRequested {
+        sender_id: NodeId,
+        digests: Vec<TransactionDigest>,
+    },
+
+    /// Transactions sent by a peer in answer to a mempool reconciliation
+    /// request
+    MempoolTxnsReceived(Vec<TransactionKind>),
+
+    /// Signals it's time to look for convergence blocks whose certificate
+    /// the node missed
+    CertificateReconciliationDue,
+
+    /// Signals it's time to check whether the mempool is worth compacting
+    MempoolCompactionDue,
+
+    /// Signals it's time to scrub another batch of the persisted chain data
+    ChainScrubDue,
+
+    /// Records of a store that were found corrupted and couldn't be repaired,
+    /// by hex encoded key. Corrupted blocks of the DAG are reported under the
+    /// `dag` store, by block hash
+    ChainDataCorrupted {
+        store: String,
+        keys: Vec<String>,
+    },
+
+    /// Convergence blocks the node holds no certificate for, to be requested
+    /// from a peer
+    MissingCertificatesRequested(Vec<BlockHash>),
+
+    /// Certificates a peer found missing for the given blocks
+    CertificatesRequested {
+        sender_id: NodeId,
+        block_hashes: Vec<BlockHash>,
+    },
+
+    /// Certificates to be sent back to the peer that requested them
+    CertificatesProvided {
+        requester: NodeId,
+        certificates: Vec<Certificate>,
+    },
+
+    /// Certificates sent by a peer in answer to a certificate request. They
+    /// are verified before being applied
+    CertificatesReceived(Vec<Certificate>),
+
+    /// Corrupted chain records the node can take a copy of from a peer, to be
+    /// requested from one
+    MissingChainRecordsRequested(Vec<ChainRecordKey>),
+
+    /// Chain records a peer found corrupted in its own stores
+    ChainRecordsRequested {
+        sender_id: NodeId,
+        keys: Vec<ChainRecordKey>,
+    },
+
+    /// Chain records to be sent back to the peer that requested them
+    ChainRecordsProvided {
+        requester: NodeId,
+        records: Vec<ChainRecord>,
+    },
+
+    /// Chain records sent by a peer in answer to a chain record request.
+    /// They are checked against the hashes the corrupted records were
+    /// committed to before replacing them
+    ChainRecordsReceived(Vec<ChainRecord>),
+
+    /// Emitted once the updates of a convergence block have been applied to
+    /// state, carrying the balances they changed
+    StateDiffApplied(StateDiff),
+
+    /// Emitted by fork choice once a block is rolled back, carrying the
+    /// transactions it included so they can return to the mempool
+    BlockRolledBack {
+        block_hash: BlockHash,
+        txns: Vec<TransactionKind>,
+    },
+
+    /// Emitted for every transaction of a rolled back block, once it was
+    /// either resurrected into the mempool or dropped
+    TxnStatusChanged(TxnStatusChange),
+
+    /// Emitted when a round is dropped from the consensus caches without any
+    /// of its convergence blocks being certified
+    RoundAbandoned(AbandonedRound),
+
+    /// Asks the node for a snapshot of its mempool and consensus backlog
+    BacklogSnapshotRequested,
+
+    /// Snapshot of the node's mempool and consensus backlog, for operators
+    /// debugging stalled certification
+    BacklogSnapshotCreated(BacklogSnapshot),
+
+    /// Asks the node for the duties of a validator over an epoch
+    ValidatorDutiesRequested {
+        node_id: NodeId,
+        epoch: Epoch,
+    },
+
+    /// Duties of a validator over an epoch, computed in answer to
+    /// `ValidatorDutiesRequested`
+    ValidatorDutiesComputed(ValidatorDuties),
+
+    /// Asks the node for the convergence blocks of rounds `start..=end`,
+    /// answered in chunks of up to `chunk_size` blocks
+    BlocksByRoundRangeRequested {
+        request_id: u64,
+        start: Round,
+        end: Round,
+        chunk_size: usize,
+    },
+
+    /// A chunk of the blocks listed in answer to `BlocksByRoundRangeRequested`
+    BlockRangeChunkCreated(BlockRangeChunk),
+
+    /// Asks the node for the peers in its address book
+    PeersRequested,
+
+    /// The peers in the node's address book, listed in answer to
+    /// `PeersRequested`
+    PeersListed(Vec<PeerRecord>),
+
+    /// Asks the node to outline the proposal block it would produce now
+    PendingBlockPreviewRequested {
+        request_id: u64,
+    },
+
+    /// Answers the `PendingBlockPreviewRequested` of the same `request_id`
+    PendingBlockPreviewCreated {
+        request_id: u64,
+        preview: PendingBlockPreview,
+    },
+
+    /// Asks the node how far its chain lags behind its peers'
+    SyncStatusRequested,
+
+    /// The node's sync status, reported in answer to `SyncStatusRequested`
+    SyncStatusReported(SyncStatus),
+
+    /// Asks the node to summarize its mempool, estimating how long a txn
+    /// paying `fee` would wait for inclusion if one is given
+    MempoolStatsRequested {
+        fee: Option<u128>,
+    },
+
+    /// Answers `MempoolStatsRequested`
+    MempoolStatsReported(MempoolStats),
+
+    /// Asks the node how far the key generation of its quorum got
+    DkgStatusRequested,
+
+    /// Answers `DkgStatusRequested`
+    DkgStatusReported(DkgStatus),
+
+    /// The key generation of the node's quorum moved on to another phase,
+    /// after gathering every part or ack, or enough of them to generate keys
+    DkgPhaseChanged(DkgStatus),
+
     // NOTE: replaces Event::Farm and pushes txns to the scheduler instead of having it pull them
     TxnsReadyForProcessing(Vec<TransactionKind>),
 
@@ -227,6 +508,13 @@ pub enum Event {
         partial_signature: RawSignature,
     },
 
+    /// Emitted when a partial signature sent by a peer failed validation and
+    /// was dropped, so the peer's reputation takes the hit
+    InvalidSignatureShareReceived {
+        node_id: NodeId,
+        reason: String,
+    },
+
     Ping(NodeId),
 
     // TODO: refactor all the events below
@@ -279,29 +567,39 @@ pub enum Event {
     /// object representing a proof that a block has been certified by a
     /// quorum. This certificate is then added to convergence block .
     BlockCertificateCreated(Certificate),
+
+    /// `RelayedTxnReceived(Txn)` is an event that is triggered when a peer
+    /// relays a transaction the node was missing. It is handled like
+    /// `NewTxnCreated`, except that the transaction counts against the
+    /// admission budget of relayed transactions.
+    RelayedTxnReceived(TransactionKind),
+
+    /// Signals it's time to retry a miner election no claim met the
+    /// threshold of, now that its eligibility widened
+    MinerElectionRetryDue,
 }
 
 impl From<&theater::Message> for Event {
     fn from(msg: &theater::Message) -> Self {
-        serde_json::from_slice(&msg.data).unwrap_or_default()
+        Event::decode_or_noop(&msg.data)
     }
 }
 
 impl From<theater::Message> for Event {
     fn from(msg: theater::Message) -> Self {
-        serde_json::from_slice(&msg.data).unwrap_or_default()
+        Event::decode_or_noop(&msg.data)
     }
 }
 
 impl From<Vec<u8>> for Event {
     fn from(data: Vec<u8>) -> Self {
-        serde_json::from_slice(&data).unwrap_or_default()
+        Event::decode_or_noop(&data)
     }
 }
 
 impl From<Event> for Vec<u8> {
     fn from(evt: Event) -> Self {
-        serde_json::to_vec(&evt).unwrap_or_default()
+        evt.encode()
     }
 }
 
@@ -1,13 +1,21 @@
-use std::net::SocketAddr;
+use std::{collections::BTreeMap, net::SocketAddr};
 
-use block::BlockHash;
+use block::{BlockHash, Certificate, ConvergenceBlock, ProposalBlock};
+use hbbft::crypto::Signature;
 use primitives::{
-    ByteVec, FarmerId, FarmerQuorumThreshold, IsTxnValid, KademliaPeerId, NodeId, NodeIdx,
-    NodeType, QuorumKind, RawSignature, ValidatorPublicKey, ValidatorPublicKeyShare,
+    Address, ByteVec, Epoch, FarmerId, FarmerQuorumThreshold, IsTxnValid, KademliaPeerId, Locality,
+    NodeId, NodeIdx, NodeType, QuorumAssignmentStrategy, QuorumKind, RawSignature, Round,
+    ValidatorPublicKey, ValidatorPublicKeyShare, ValidatorSecretKey, Weight,
 };
 use serde::{Deserialize, Serialize};
 use vrrb_config::QuorumMember;
-use vrrb_core::transactions::{TransactionDigest, TransactionKind};
+use vrrb_core::{
+    claim::{Claim, ClaimError},
+    keypair::{KeyPairError, Keypair, MinerSecretKey},
+    transactions::{TransactionDigest, TransactionKind},
+};
+
+use crate::LEGACY_EVENT_SCHEMA_VERSION;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PeerData {
@@ -17,7 +25,203 @@ pub struct PeerData {
     pub udp_gossip_addr: SocketAddr,
     pub raptorq_gossip_addr: SocketAddr,
     pub kademlia_liveness_addr: SocketAddr,
+
+    /// Further addresses the peer's UDP gossip can be reached at, e.g. over
+    /// IPv6 when `udp_gossip_addr` is an IPv4 one, in order of preference
+    #[serde(default)]
+    pub alternate_udp_gossip_addrs: Vec<SocketAddr>,
     pub validator_public_key: ValidatorPublicKey,
+
+    /// Where the peer runs, if its operator labelled it
+    #[serde(default)]
+    pub locality: Locality,
+
+    /// Signature over the peer's registration payload, made with the key of
+    /// its claim
+    #[serde(default)]
+    pub claim_signature: Option<String>,
+
+    /// Newest event schema version the peer decodes. Peers that don't
+    /// advertise one only decode the legacy schema
+    #[serde(default = "legacy_event_schema_version")]
+    pub event_schema_version: u16,
+}
+
+fn legacy_event_schema_version() -> u16 {
+    LEGACY_EVENT_SCHEMA_VERSION
+}
+
+impl PeerData {
+    /// Returns the details a peer registers with the network, as signed with
+    /// its claim's key
+    pub fn registration_payload(&self) -> Vec<u8> {
+        serde_json::to_vec(&(
+            &self.node_id,
+            &self.node_type,
+            &self.kademlia_peer_id,
+            &self.udp_gossip_addr,
+            &self.raptorq_gossip_addr,
+            &self.kademlia_liveness_addr,
+            &self.alternate_udp_gossip_addrs,
+            &self.validator_public_key,
+            &self.locality,
+        ))
+        .unwrap_or_default()
+    }
+
+    /// Signs the registration payload with the secret key of the peer's claim
+    pub fn sign_registration(
+        &mut self,
+        claim_secret_key: &MinerSecretKey,
+    ) -> Result<(), KeyPairError> {
+        let signature = Keypair::ecdsa_sign(
+            &self.registration_payload(),
+            claim_secret_key.secret_bytes().to_vec(),
+        )?;
+
+        self.claim_signature = Some(signature);
+
+        Ok(())
+    }
+
+    /// Checks the registration was signed with the key of `claim`, and that
+    /// the claim belongs to the peer
+    pub fn verify_registration(&self, claim: &Claim) -> Result<(), ClaimError> {
+        if claim.node_id != self.node_id {
+            return Err(ClaimError::Other(format!(
+                "claim of node {} does not belong to peer {}",
+                claim.node_id, self.node_id
+            )));
+        }
+
+        let signature = self
+            .claim_signature
+            .clone()
+            .ok_or(ClaimError::InvalidSignature)?;
+
+        Claim::is_valid_claim(
+            &self.registration_payload(),
+            signature,
+            claim.public_key.serialize().to_vec(),
+        )
+    }
+}
+
+/// What a node remembers of a peer across restarts. The services a peer
+/// advertises follow from its `node_type`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    pub peer_data: PeerData,
+
+    /// Unix timestamp, in seconds, of the last time the peer was heard from
+    pub last_seen: i64,
+
+    pub reputation: PeerReputation,
+}
+
+/// Summary of how a peer behaved, used to rank it within the address book.
+#[derive(Debug, Default, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerReputation {
+    /// Contributions of the peer that got certified
+    pub certified_contributions: u64,
+
+    /// Message credits the peer held when it was last heard from
+    pub message_credits: u64,
+
+    /// Partial signatures of the peer that failed validation
+    pub invalid_signature_shares: u64,
+}
+
+impl PeerReputation {
+    /// How many certified contributions each invalid signature share costs
+    /// a peer
+    pub const INVALID_SIGNATURE_SHARE_PENALTY: u64 = 10;
+
+    /// Score peers are ranked by, higher is better
+    pub fn score(&self) -> u64 {
+        self.certified_contributions.saturating_sub(
+            self.invalid_signature_shares
+                .saturating_mul(Self::INVALID_SIGNATURE_SHARE_PENALTY),
+        )
+    }
+}
+
+/// Announces a validator moved to a new validator key. It's signed with both
+/// the old and the new key, proving the validator holds each of them, so peers
+/// can swap the key they know the validator by without trusting the sender.
+///
+/// The signatures also cover the epoch the rotation was announced in and a
+/// nonce that grows with every rotation of the validator, so peers can tell
+/// a replayed announcement from a fresh one.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorKeyRotation {
+    pub node_id: NodeId,
+    pub epoch: Epoch,
+    pub nonce: u64,
+    pub old_public_key: ValidatorPublicKey,
+    pub new_public_key: ValidatorPublicKey,
+    pub old_key_signature: RawSignature,
+    pub new_key_signature: RawSignature,
+}
+
+impl ValidatorKeyRotation {
+    /// Announces `node_id` moved from `old_secret_key` to `new_secret_key`
+    /// during `epoch`, as its rotation numbered `nonce`
+    pub fn new(
+        node_id: NodeId,
+        epoch: Epoch,
+        nonce: u64,
+        old_secret_key: &ValidatorSecretKey,
+        new_secret_key: &ValidatorSecretKey,
+    ) -> Self {
+        let old_public_key = old_secret_key.public_key();
+        let new_public_key = new_secret_key.public_key();
+        let payload = Self::payload(&node_id, epoch, nonce, &old_public_key, &new_public_key);
+
+        Self {
+            node_id,
+            epoch,
+            nonce,
+            old_public_key,
+            new_public_key,
+            old_key_signature: old_secret_key.sign(&payload).to_bytes().to_vec(),
+            new_key_signature: new_secret_key.sign(&payload).to_bytes().to_vec(),
+        }
+    }
+
+    /// Returns what both keys sign
+    pub fn payload(
+        node_id: &NodeId,
+        epoch: Epoch,
+        nonce: u64,
+        old_public_key: &ValidatorPublicKey,
+        new_public_key: &ValidatorPublicKey,
+    ) -> Vec<u8> {
+        serde_json::to_vec(&(node_id, epoch, nonce, old_public_key, new_public_key))
+            .unwrap_or_default()
+    }
+
+    /// Checks the announcement was signed with both the old and the new key
+    pub fn verify(&self) -> bool {
+        let payload = Self::payload(
+            &self.node_id,
+            self.epoch,
+            self.nonce,
+            &self.old_public_key,
+            &self.new_public_key,
+        );
+
+        let verify_with = |public_key: &ValidatorPublicKey, signature: &RawSignature| {
+            <[u8; 96]>::try_from(signature.as_slice())
+                .ok()
+                .and_then(|signature| Signature::from_bytes(signature).ok())
+                .map_or(false, |signature| public_key.verify(&signature, &payload))
+        };
+
+        self.old_public_key != self.new_public_key
+            && verify_with(&self.old_public_key, &self.old_key_signature)
+            && verify_with(&self.new_public_key, &self.new_key_signature)
+    }
 }
 
 impl From<QuorumMember> for PeerData {
@@ -29,7 +233,11 @@ impl From<QuorumMember> for PeerData {
             udp_gossip_addr: value.udp_gossip_address,
             raptorq_gossip_addr: value.raptorq_gossip_address,
             kademlia_liveness_addr: value.kademlia_liveness_address,
+            alternate_udp_gossip_addrs: vec![],
             validator_public_key: value.validator_public_key,
+            locality: Locality::default(),
+            claim_signature: None,
+            event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
         }
     }
 }
@@ -47,6 +255,10 @@ pub struct SyncPeerData {
 // TODO: Replace Vec<u8>'s with proper data structs in enum wariants
 // once definitions of those are moved into primitives.
 
+/// Certified txns a farmer saw wait too long for a block, which convergence
+/// blocks must include
+pub type InclusionList = Vec<TransactionDigest>;
+
 #[derive(Debug, Deserialize, Serialize, Hash, Clone, PartialEq, Eq)]
 pub struct Vote {
     /// The identity of the voter.
@@ -60,6 +272,8 @@ pub struct Vote {
     pub is_txn_valid: bool,
     // May want to serialize this as a vector of bytes
     pub execution_result: Option<String>,
+    #[serde(default)]
+    pub inclusion_list: InclusionList,
 }
 
 pub type SerializedConvergenceBlock = ByteVec;
@@ -122,4 +336,328 @@ pub struct AssignedQuorumMembership {
     pub kademlia_peer_id: KademliaPeerId,
     pub quorum_kind: QuorumKind,
     pub peers: Vec<PeerData>,
+    pub keyset: QuorumKeysetAnnouncement,
+}
+
+/// The validator public keys of every member of a quorum, assembled by the
+/// node that formed it. Members install the whole keyset at once before
+/// generating their DKG parts, instead of learning keys one peer at a time.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct QuorumKeysetAnnouncement {
+    pub quorum_kind: QuorumKind,
+    pub members: BTreeMap<NodeId, ValidatorPublicKey>,
+
+    /// How the node that formed the quorum picked its members
+    #[serde(default)]
+    pub assignment_strategy: QuorumAssignmentStrategy,
+}
+
+impl QuorumKeysetAnnouncement {
+    pub fn new(
+        quorum_kind: QuorumKind,
+        members: &[PeerData],
+        assignment_strategy: QuorumAssignmentStrategy,
+    ) -> Self {
+        let members = members
+            .iter()
+            .map(|peer| (peer.node_id.clone(), peer.validator_public_key))
+            .collect();
+
+        Self {
+            quorum_kind,
+            members,
+            assignment_strategy,
+        }
+    }
+}
+
+/// An account's native token balance before and after a state update.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct BalanceChange {
+    pub address: Address,
+    pub previous: u128,
+    pub current: u128,
+}
+
+/// What changed in state once the updates of a convergence block were
+/// applied.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct StateDiff {
+    pub block_hash: BlockHash,
+    pub round: Round,
+    pub balance_changes: Vec<BalanceChange>,
+
+    /// Hex encoded roots of the state trie before and after the block was
+    /// applied. Replicas apply a diff only on top of the state it was taken
+    /// from
+    pub previous_state_root: String,
+    pub state_root: String,
+
+    /// Every account the block updated as it stands once applied, encoded
+    /// with bincode
+    pub updated_accounts: Vec<ByteVec>,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        self.balance_changes.is_empty()
+    }
+}
+
+/// Duties a validator can expect over an epoch, as far as the node can tell
+/// from the claims it holds and the validator set changes it knows about.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct ValidatorDuties {
+    pub node_id: NodeId,
+    pub epoch: Epoch,
+
+    /// Heights of the first and last blocks of the epoch
+    pub first_block_height: u128,
+    pub last_block_height: u128,
+
+    /// Quorum the validator serves in over the epoch, if any
+    pub quorum_kind: Option<QuorumKind>,
+
+    /// Epoch a pending registration adds the validator to the validator set
+    pub activates_at: Option<Epoch>,
+
+    /// Epoch a pending exit removes the validator from the validator set
+    pub exits_at: Option<Epoch>,
+
+    /// Height of the next block, if the validator wins its miner election.
+    /// Only the seed of the next block is known ahead of time
+    pub next_mining_slot: Option<u128>,
+
+    /// Number of blocks of the epoch the validator is expected to mine, given
+    /// its share of the miner eligible claims
+    pub expected_mining_slots: u128,
+}
+
+/// A convergence block along with the proposal blocks it references and the
+/// certificate it was certified with, as served to archive clients.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct ArchivedBlock {
+    pub convergence: ConvergenceBlock,
+
+    /// Referenced proposal blocks still within the node's DAG
+    pub proposals: Vec<ProposalBlock>,
+
+    /// `None` until the block is certified
+    pub certificate: Option<Certificate>,
+}
+
+/// One chunk of the blocks listed in answer to a request for the blocks of a
+/// range of rounds.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct BlockRangeChunk {
+    /// Identifies the request the chunk answers
+    pub request_id: u64,
+
+    /// Position of the chunk among those answering the request, from 0
+    pub index: usize,
+
+    /// Whether no further chunks answer the request
+    pub last: bool,
+
+    /// Blocks in round order, then by hash within a round
+    pub blocks: Vec<ArchivedBlock>,
+}
+
+/// What became of a transaction once the block it was included in was rolled
+/// back.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub enum TxnResurrectionStatus {
+    /// The transaction still passes validation and is back in the mempool
+    Resurrected,
+    /// The transaction no longer passes validation and was discarded
+    Dropped { reason: String },
+}
+
+/// A change in the status of a transaction caused by a rollback of the block
+/// that included it.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct TxnStatusChange {
+    pub digest: TransactionDigest,
+    pub block_hash: BlockHash,
+    pub status: TxnResurrectionStatus,
+}
+
+/// Votes and partial signatures recorded for a block that is still waiting
+/// on its certificate.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct PendingBlockVotes {
+    pub block_hash: BlockHash,
+    pub votes: usize,
+    pub approvals: usize,
+    pub partial_signatures: usize,
+}
+
+/// Signature shares gathered so far towards a convergence block's
+/// certificate.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct CertificateShares {
+    pub block_hash: BlockHash,
+    pub share_count: usize,
+}
+
+/// Why a round was dropped before any of its convergence blocks certified.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub enum RoundAbandonReason {
+    /// A block of a later round was certified first
+    Superseded { certified_round: Round },
+    /// The round fell too far behind the latest round seen by the node
+    Expired { latest_round: Round },
+}
+
+/// A round whose convergence blocks never certified, along with the shares
+/// gathered towards each of them before they were dropped.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct AbandonedRound {
+    pub round: Round,
+    pub reason: RoundAbandonReason,
+    pub blocks: Vec<CertificateShares>,
+}
+
+/// Key of a persisted chain record, within the store named `store`.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct ChainRecordKey {
+    pub store: String,
+    pub key: ByteVec,
+}
+
+/// A persisted chain record, handed over to a peer whose copy is corrupt.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct ChainRecord {
+    pub store: String,
+    pub key: ByteVec,
+    pub value: ByteVec,
+}
+
+/// Messages held by the DKG engine for the key generation in progress.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct DkgBacklog {
+    pub part_messages: usize,
+    pub ack_messages: usize,
+    pub peer_public_keys: usize,
+    pub has_public_key_set: bool,
+    pub has_secret_key_share: bool,
+}
+
+/// Stage the key generation of a node's quorum is at.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum DkgPhase {
+    /// The node isn't generating keys for any quorum
+    #[default]
+    Idle,
+
+    /// Waiting on the part commitments of the quorum's members
+    CollectingParts,
+
+    /// Every part was received, waiting on the acks of every member for each
+    /// of them
+    CollectingAcks,
+
+    /// More than `threshold` parts gathered enough acks, keys can be
+    /// generated
+    ThresholdReached,
+
+    /// The quorum's keys were generated
+    Completed,
+}
+
+/// Progress of the key generation of a node's quorum, for operators to tell
+/// a stalled DKG from a slow one.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct DkgStatus {
+    pub node_id: NodeId,
+    pub epoch: Epoch,
+    pub phase: DkgPhase,
+    pub threshold: usize,
+
+    /// Members taking part in the key generation, this node included
+    pub participants: usize,
+
+    pub parts_received: usize,
+    pub parts_expected: usize,
+    pub acks_received: usize,
+    pub acks_expected: usize,
+
+    /// Parts acked by more than twice the threshold of members
+    pub complete_parts: usize,
+
+    /// Seconds since the key generation entered its current phase
+    pub secs_in_phase: u64,
+
+    /// Members whose part or acks are still missing, or whose part was
+    /// rejected, ordered by node id
+    pub failing_peers: Vec<NodeId>,
+}
+
+/// Where the node stands in the round and epoch schedule.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct RoundSchedulerState {
+    /// Round of the last confirmed block, if any was confirmed yet
+    pub round: Option<Round>,
+    pub epoch: Epoch,
+    pub maintenance_mode: bool,
+    pub paused_peers: Vec<NodeId>,
+}
+
+/// A point in time view of the work a node has yet to get through, dumped
+/// to debug stalled certification.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct BacklogSnapshot {
+    pub node_id: NodeId,
+    pub mempool_size: usize,
+    pub quorum_certified_txns: usize,
+    pub pending_votes: Vec<PendingBlockVotes>,
+    pub convergence_block_certificates: Vec<CertificateShares>,
+    pub dkg: DkgBacklog,
+    pub scheduler: RoundSchedulerState,
+}
+
+/// Outline of the proposal block a node would produce if it mined one now,
+/// so operators can see how their fee and selection settings play out.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct PendingBlockPreview {
+    /// Round the block would be proposed at
+    pub round: Round,
+
+    /// Txns the block would include, in order
+    pub txns: Vec<TransactionDigest>,
+
+    /// Certified txns the node had to choose from
+    pub candidate_txns: usize,
+
+    pub weight: Weight,
+    pub max_block_weight: Weight,
+
+    /// Estimated encoded size of the included txns
+    pub size_bytes: usize,
+
+    /// Fees paid by the included txns
+    pub fee_revenue: u128,
+
+    /// Share of the fees that goes to the block's proposer
+    pub proposer_fee_revenue: u128,
+}
+
+/// How far a node's chain lags behind the chain its peers announced, and how
+/// soon it is expected to catch up.
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct SyncStatus {
+    /// Round of the latest block the node applied
+    pub applied_round: Round,
+
+    /// Highest round of the certified blocks received from peers
+    pub best_known_round: Round,
+
+    pub syncing: bool,
+
+    /// Blocks imported per second over the last minute, in thousandths
+    pub import_rate_millis: u64,
+
+    /// Seconds left until the node catches up at the current import rate.
+    /// Unknown while the node imports nothing
+    pub eta_secs: Option<u64>,
 }
@@ -1,10 +1,14 @@
 use messr::Router;
 use tokio::sync::{broadcast::Receiver, mpsc::Sender};
 
-pub use crate::{event::*, event_data::*};
+pub use crate::{envelope::*, event::*, event_data::*, mailbox::*};
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+mod envelope;
 mod event;
 mod event_data;
+mod mailbox;
 
 pub const DEFAULT_BUFFER: usize = 1000;
 
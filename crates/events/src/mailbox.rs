@@ -0,0 +1,488 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::{
+    sync::{
+        broadcast::{self, error::RecvError},
+        mpsc::{self, error::TrySendError},
+    },
+    task::JoinHandle,
+};
+use vrrb_config::MailboxConfig;
+
+use crate::{Event, EventMessage, EventSubscriber};
+
+/// How long delivery waits before checking again whether the actor made room
+/// for more events
+const DELIVERY_BACKOFF: Duration = Duration::from_millis(1);
+
+/// Determines how an event is treated by a mailbox once it becomes saturated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EventPriority {
+    /// Events that can be dropped without affecting protocol correctness
+    Low,
+    Normal,
+    /// Events that must never be shed, e.g. stop signals or consensus messages
+    Critical,
+}
+
+impl Event {
+    pub fn priority(&self) -> EventPriority {
+        match self {
+            Event::NoOp
+            | Event::Ping(_)
+            | Event::TxnAddedToMempool(_)
+            | Event::ClaimReceived(_)
+            | Event::PeerSyncFailed(_)
+            | Event::MempoolReconciliationDue
+            | Event::RebroadcastDue
+            | Event::RoundPeersScheduled { .. }
+            | Event::MempoolSketchReceived { .. }
+            | Event::MempoolDigestsOffered { .. }
+            | Event::MempoolTxnsRequested { .. }
+            | Event::MempoolTxnsReceived(_)
+            | Event::CertificateReconciliationDue
+            | Event::MempoolCompactionDue
+            | Event::ChainScrubDue
+            | Event::MissingCertificatesRequested(_)
+            | Event::CertificatesRequested { .. }
+            | Event::CertificatesProvided { .. }
+            | Event::CertificatesReceived(_)
+            | Event::MissingChainRecordsRequested(_)
+            | Event::ChainRecordsRequested { .. }
+            | Event::ChainRecordsProvided { .. }
+            | Event::ChainRecordsReceived(_)
+            | Event::BacklogSnapshotRequested
+            | Event::BacklogSnapshotCreated(_)
+            | Event::PeersRequested
+            | Event::PeersListed(_)
+            | Event::SyncStatusRequested
+            | Event::SyncStatusReported(_)
+            | Event::MempoolStatsRequested { .. }
+            | Event::MempoolStatsReported(_)
+            | Event::DkgStatusRequested
+            | Event::DkgStatusReported(_)
+            | Event::DkgPhaseChanged(_)
+            | Event::PendingBlockPreviewRequested { .. }
+            | Event::PendingBlockPreviewCreated { .. }
+            | Event::BlocksByRoundRangeRequested { .. }
+            | Event::BlockRangeChunkCreated(_) => EventPriority::Low,
+
+            Event::Stop
+            | Event::QuorumMembershipAssigmentCreated(_)
+            | Event::QuorumMembershipConfigured(_)
+            | Event::PartCommitmentCreated(_, _)
+            | Event::PartCommitmentAcknowledged { .. }
+            | Event::ConvergenceBlockPartialSignatureCreated { .. }
+            | Event::ConvergenceBlockPeerSignatureRequested { .. }
+            | Event::BlockCertificateCreated(_)
+            | Event::ConvergenceBlockCertified(_)
+            | Event::ValidatorSetUpdated(_)
+            | Event::ValidatorSetUpdateReceived(_)
+            | Event::MaintenanceModeRequested(_)
+            | Event::MaintenanceModeChanged { .. }
+            | Event::PeerMaintenanceModeChanged { .. }
+            | Event::ValidatorKeyRotationRequested
+            | Event::ValidatorKeyRotated(_)
+            | Event::PeerValidatorKeyRotated(_)
+            | Event::HaltDirectiveRequested(_)
+            | Event::HaltDirectiveSigned { .. }
+            | Event::PeerHaltDirectiveSigned { .. }
+            | Event::HaltDirectiveCertified(_)
+            | Event::PeerHaltDirectiveCertified(_) => EventPriority::Critical,
+
+            _ => EventPriority::Normal,
+        }
+    }
+}
+
+/// Point in time view of a mailbox's counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MailboxStats {
+    pub label: String,
+    pub capacity: usize,
+    pub depth: usize,
+    pub peak_depth: usize,
+    pub delivered: u64,
+    pub shed: u64,
+    pub lagged: u64,
+    pub saturated: bool,
+}
+
+/// Counters updated by a running mailbox. Shared with every [MailboxMonitor]
+/// so they can be reported without touching the mailbox task.
+#[derive(Debug, Default)]
+pub struct MailboxMetrics {
+    depth: AtomicUsize,
+    peak_depth: AtomicUsize,
+    delivered: AtomicU64,
+    shed: AtomicU64,
+    lagged: AtomicU64,
+    saturated: AtomicBool,
+}
+
+impl MailboxMetrics {
+    fn record_depth(&self, depth: usize) {
+        self.depth.store(depth, Ordering::Relaxed);
+        self.peak_depth.fetch_max(depth, Ordering::Relaxed);
+    }
+}
+
+/// Cloneable view over a running mailbox's counters.
+#[derive(Debug, Clone)]
+pub struct MailboxMonitor {
+    label: String,
+    capacity: usize,
+    metrics: Arc<MailboxMetrics>,
+}
+
+impl MailboxMonitor {
+    pub fn label(&self) -> String {
+        self.label.clone()
+    }
+
+    pub fn stats(&self) -> MailboxStats {
+        MailboxStats {
+            label: self.label.clone(),
+            capacity: self.capacity,
+            depth: self.metrics.depth.load(Ordering::Relaxed),
+            peak_depth: self.metrics.peak_depth.load(Ordering::Relaxed),
+            delivered: self.metrics.delivered.load(Ordering::Relaxed),
+            shed: self.metrics.shed.load(Ordering::Relaxed),
+            lagged: self.metrics.lagged.load(Ordering::Relaxed),
+            saturated: self.metrics.saturated.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MailboxHandle {
+    monitor: MailboxMonitor,
+    task_handle: JoinHandle<()>,
+}
+
+impl MailboxHandle {
+    pub fn label(&self) -> String {
+        self.monitor.label()
+    }
+
+    pub fn stats(&self) -> MailboxStats {
+        self.monitor.stats()
+    }
+
+    pub fn monitor(&self) -> MailboxMonitor {
+        self.monitor.clone()
+    }
+
+    /// Waits for the mailbox to drain its upstream subscription
+    pub async fn join(self) -> std::result::Result<(), tokio::task::JoinError> {
+        self.task_handle.await
+    }
+}
+
+/// A bounded, per-actor queue fed from a shared event router topic.
+///
+/// Each actor gets its own mailbox so a slow consumer only fills up its own
+/// queue. Once a mailbox crosses its high watermark, low priority events are
+/// shed, and if it stays saturated for longer than the configured window an
+/// alert is logged. Normal and critical events are never shed: once the
+/// queue is full, the mailbox stops reading from upstream until the actor
+/// catches up.
+#[derive(Debug)]
+pub struct Mailbox {
+    label: String,
+    capacity: usize,
+    high_watermark: usize,
+    saturation_alert_after: std::time::Duration,
+    saturated_since: Option<Instant>,
+    alerted: bool,
+    metrics: Arc<MailboxMetrics>,
+}
+
+impl Mailbox {
+    pub fn new(label: &str, config: &MailboxConfig) -> Self {
+        Self {
+            label: label.to_string(),
+            capacity: config.capacity_for(label),
+            high_watermark: config.high_watermark_for(label),
+            saturation_alert_after: config.saturation_alert_after,
+            saturated_since: None,
+            alerted: false,
+            metrics: Arc::new(MailboxMetrics::default()),
+        }
+    }
+
+    /// Spawns a task that forwards events from `upstream` into a new bounded
+    /// queue, returning the subscriber the actor should consume from.
+    pub fn spawn(self, upstream: EventSubscriber) -> (EventSubscriber, MailboxHandle) {
+        let (queue_tx, queue_rx) = mpsc::channel(self.capacity);
+        let (tx, rx) = broadcast::channel(self.capacity);
+
+        let monitor = MailboxMonitor {
+            label: self.label.clone(),
+            capacity: self.capacity,
+            metrics: self.metrics.clone(),
+        };
+
+        let delivery = deliver(queue_rx, tx.clone(), self.capacity, self.metrics.clone());
+        let task_handle = tokio::spawn(async move {
+            tokio::join!(self.run(upstream, queue_tx, tx), delivery);
+        });
+
+        let handle = MailboxHandle {
+            monitor,
+            task_handle,
+        };
+
+        (rx, handle)
+    }
+
+    async fn run(
+        mut self,
+        mut upstream: EventSubscriber,
+        queue: mpsc::Sender<EventMessage>,
+        delivered: broadcast::Sender<EventMessage>,
+    ) {
+        loop {
+            let message = match upstream.recv().await {
+                Ok(message) => message,
+                Err(RecvError::Lagged(count)) => {
+                    self.metrics.lagged.fetch_add(count, Ordering::Relaxed);
+                    telemetry::warn!("{} mailbox lagged behind by {count} events", self.label);
+                    continue;
+                },
+                Err(RecvError::Closed) => break,
+            };
+
+            let event: Event = message.clone().into();
+            let is_stop = matches!(event, Event::Stop);
+            let priority = event.priority();
+
+            if !self.admit(in_flight(self.capacity, &queue, &delivered), priority) {
+                continue;
+            }
+
+            // NOTE: sending only fails when delivery stopped because the actor dropped
+            // its receiver, in which case there's no one left to deliver to
+            let queued = match priority {
+                EventPriority::Low => match queue.try_send(message) {
+                    Ok(()) => true,
+                    Err(TrySendError::Full(_)) => {
+                        self.metrics.shed.fetch_add(1, Ordering::Relaxed);
+                        continue;
+                    },
+                    Err(TrySendError::Closed(_)) => false,
+                },
+                EventPriority::Normal | EventPriority::Critical => {
+                    queue.send(message).await.is_ok()
+                },
+            };
+
+            if !queued {
+                break;
+            }
+
+            self.metrics
+                .record_depth(in_flight(self.capacity, &queue, &delivered));
+
+            if is_stop {
+                break;
+            }
+        }
+    }
+
+    /// Decides whether an event of the given priority should be queued given
+    /// the current depth of the mailbox.
+    fn admit(&mut self, depth: usize, priority: EventPriority) -> bool {
+        self.metrics.record_depth(depth);
+
+        if depth < self.high_watermark {
+            if self.saturated_since.take().is_some() {
+                telemetry::info!("{} mailbox recovered from saturation", self.label);
+            }
+            self.alerted = false;
+            self.metrics.saturated.store(false, Ordering::Relaxed);
+            return true;
+        }
+
+        let saturated_since = *self.saturated_since.get_or_insert_with(Instant::now);
+        self.metrics.saturated.store(true, Ordering::Relaxed);
+
+        if !self.alerted && saturated_since.elapsed() >= self.saturation_alert_after {
+            self.alerted = true;
+            telemetry::error!(
+                "{} mailbox has been saturated for {:?} ({depth}/{} events queued)",
+                self.label,
+                saturated_since.elapsed(),
+                self.capacity
+            );
+        }
+
+        if priority == EventPriority::Low {
+            self.metrics.shed.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+
+        true
+    }
+}
+
+/// Counts the events a mailbox holds: those waiting in its queue and those
+/// handed over to the actor it didn't read yet
+fn in_flight(
+    capacity: usize,
+    queue: &mpsc::Sender<EventMessage>,
+    delivered: &broadcast::Sender<EventMessage>,
+) -> usize {
+    capacity - queue.capacity() + delivered.len()
+}
+
+/// Hands the queued events over to the actor, in order.
+///
+/// The actor consumes from a broadcast channel, which overwrites its oldest
+/// events once full, so events are only handed over while the actor has room
+/// for them and otherwise wait in the queue.
+async fn deliver(
+    mut queue: mpsc::Receiver<EventMessage>,
+    tx: broadcast::Sender<EventMessage>,
+    capacity: usize,
+    metrics: Arc<MailboxMetrics>,
+) {
+    while let Some(message) = queue.recv().await {
+        // NOTE: actors read through a broadcast receiver, which doesn't tell the
+        // sender when it takes an event, so there's no permit to wait on here
+        while tx.len() >= capacity {
+            if tx.receiver_count() == 0 {
+                return;
+            }
+
+            tokio::time::sleep(DELIVERY_BACKOFF).await;
+        }
+
+        let is_stop = matches!(Event::from(message.clone()), Event::Stop);
+
+        if tx.send(message).is_err() {
+            break;
+        }
+
+        metrics.delivered.fetch_add(1, Ordering::Relaxed);
+
+        if is_stop {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(capacity: usize) -> MailboxConfig {
+        let mut config = MailboxConfig {
+            saturation_alert_after: Duration::from_secs(0),
+            ..Default::default()
+        };
+        config.with_capacity("test-events", capacity);
+        config
+    }
+
+    #[test]
+    fn stop_signals_are_never_shed() {
+        assert_eq!(Event::Stop.priority(), EventPriority::Critical);
+        assert_eq!(Event::NoOp.priority(), EventPriority::Low);
+    }
+
+    #[test]
+    fn saturated_mailbox_sheds_low_priority_events_only() {
+        let mut mailbox = Mailbox::new("test-events", &config(10));
+
+        assert!(mailbox.admit(0, EventPriority::Low));
+        assert!(!mailbox.admit(8, EventPriority::Low));
+        assert!(mailbox.admit(8, EventPriority::Normal));
+        assert!(mailbox.admit(9, EventPriority::Critical));
+        assert!(mailbox.alerted);
+
+        assert_eq!(mailbox.metrics.shed.load(Ordering::Relaxed), 1);
+        assert_eq!(mailbox.metrics.peak_depth.load(Ordering::Relaxed), 9);
+
+        assert!(mailbox.admit(2, EventPriority::Low));
+        assert!(!mailbox.alerted);
+    }
+
+    #[tokio::test]
+    async fn mailbox_forwards_events_until_stopped() {
+        let (upstream_tx, upstream_rx) = broadcast::channel(10);
+        let (mut rx, handle) = Mailbox::new("test-events", &config(10)).spawn(upstream_rx);
+
+        upstream_tx.send(Event::NoOp.into()).unwrap();
+        upstream_tx.send(Event::Stop.into()).unwrap();
+
+        let first: Event = rx.recv().await.unwrap().into();
+        let second: Event = rx.recv().await.unwrap().into();
+
+        assert_eq!(first, Event::NoOp);
+        assert_eq!(second, Event::Stop);
+
+        let stats = handle.stats();
+        assert_eq!(stats.delivered, 2);
+        assert_eq!(stats.capacity, 10);
+
+        handle.join().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn depth_counts_events_the_actor_did_not_read_yet() {
+        let (upstream_tx, upstream_rx) = broadcast::channel(10);
+        let (_rx, handle) = Mailbox::new("test-events", &config(10)).spawn(upstream_rx);
+
+        for _ in 0..3 {
+            upstream_tx
+                .send(Event::TxnsReadyForProcessing(vec![]).into())
+                .unwrap();
+
+            // NOTE: gives the mailbox time to hand the event over to the actor
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // NOTE: the events left the queue, but the actor never read them
+        assert!(handle.stats().peak_depth >= 2);
+    }
+
+    #[tokio::test]
+    async fn full_mailbox_holds_normal_events_back_instead_of_dropping_them() {
+        let (upstream_tx, upstream_rx) = broadcast::channel(16);
+        let (mut rx, handle) = Mailbox::new("test-events", &config(2)).spawn(upstream_rx);
+
+        let events: Vec<Event> = (0..6)
+            .map(|_| Event::TxnsReadyForProcessing(vec![]))
+            .collect();
+        assert_eq!(events[0].priority(), EventPriority::Normal);
+
+        for event in events.iter() {
+            upstream_tx.send(event.clone().into()).unwrap();
+        }
+        upstream_tx.send(Event::Stop.into()).unwrap();
+
+        // NOTE: gives the mailbox time to fill the actor's channel past its capacity
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        for event in events {
+            let received: Event = rx.recv().await.unwrap().into();
+            assert_eq!(received, event);
+        }
+
+        let stop: Event = rx.recv().await.unwrap().into();
+        assert_eq!(stop, Event::Stop);
+
+        let stats = handle.stats();
+        assert_eq!(stats.delivered, 7);
+        assert_eq!(stats.shed, 0);
+
+        handle.join().await.unwrap();
+    }
+}
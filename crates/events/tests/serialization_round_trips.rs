@@ -0,0 +1,124 @@
+#![cfg(feature = "arbitrary")]
+
+use std::{fmt::Debug, net::SocketAddr};
+
+use block::{header::BlockHeader, Block, Certificate};
+use events::{Event, EVENT_SCHEMA_VERSION, LEGACY_EVENT_SCHEMA_VERSION};
+use proptest::{collection, prelude::*};
+use serde::{de::DeserializeOwned, Serialize};
+use vrrb_core::{
+    arbitrary::{node_id, txn_digest},
+    claim::Claim,
+    serde_helpers::{
+        decode_from_binary_byte_slice, decode_from_json_byte_slice, encode_to_binary,
+        encode_to_json,
+    },
+    transactions::TransactionKind,
+};
+
+/// Asserts `value` decodes back to itself, and that encoding it again yields
+/// the same bytes, which also catches fields left out of `PartialEq` impls
+fn assert_binary_round_trip<T>(value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let encoded = encode_to_binary(value).unwrap();
+    let decoded: T = decode_from_binary_byte_slice(&encoded).unwrap();
+
+    assert_eq!(&decoded, value);
+    assert_eq!(encode_to_binary(&decoded).unwrap(), encoded);
+}
+
+fn assert_json_round_trip<T>(value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let encoded = encode_to_json(value).unwrap();
+    let decoded: T = decode_from_json_byte_slice(&encoded).unwrap();
+
+    assert_eq!(&decoded, value);
+    assert_eq!(encode_to_json(&decoded).unwrap(), encoded);
+}
+
+/// Blocks JSON can hold. Proposal and genesis blocks key their txns by
+/// digest, which isn't a string, so only those without txns encode to JSON
+fn json_encodable_block() -> impl Strategy<Value = Block> {
+    any::<Block>().prop_map(|block| match block {
+        Block::Proposal { mut block } => {
+            block.txns.clear();
+            Block::Proposal { block }
+        },
+        Block::Genesis { mut block } => {
+            block.txns.clear();
+            Block::Genesis { block }
+        },
+        block => block,
+    })
+}
+
+/// Events the versioned envelope can carry. Its payload is a JSON value,
+/// which holds numbers up to 64 bits, so events carrying 128 bit numbers
+/// don't go through it intact
+fn enveloped_event() -> impl Strategy<Value = Event> {
+    prop_oneof![
+        Just(Event::NoOp),
+        Just(Event::Stop),
+        node_id().prop_map(Event::Ping),
+        any::<bool>().prop_map(Event::MaintenanceModeRequested),
+        "[0-9a-f]{64}".prop_map(Event::UpdateState),
+        collection::vec(any::<SocketAddr>(), 0..3).prop_map(Event::PeerSyncFailed),
+        txn_digest().prop_map(Event::TxnAddedToMempool),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn claims_round_trip(claim in any::<Claim>()) {
+        assert_binary_round_trip(&claim);
+        assert_json_round_trip(&claim);
+    }
+
+    #[test]
+    fn txns_round_trip(txn in any::<TransactionKind>()) {
+        assert_binary_round_trip(&txn);
+        assert_json_round_trip(&txn);
+    }
+
+    #[test]
+    fn certificates_round_trip(certificate in any::<Certificate>()) {
+        assert_binary_round_trip(&certificate);
+        assert_json_round_trip(&certificate);
+    }
+
+    #[test]
+    fn block_headers_round_trip(header in any::<BlockHeader>()) {
+        assert_binary_round_trip(&header);
+        assert_json_round_trip(&header);
+    }
+
+    #[test]
+    fn blocks_round_trip_through_bincode(block in any::<Block>()) {
+        assert_binary_round_trip(&block);
+    }
+
+    #[test]
+    fn blocks_round_trip_through_json(block in json_encodable_block()) {
+        assert_json_round_trip(&block);
+    }
+
+    #[test]
+    fn events_round_trip_through_bincode(event in any::<Event>()) {
+        assert_binary_round_trip(&event);
+    }
+
+    #[test]
+    fn events_round_trip_through_every_envelope_version(event in enveloped_event()) {
+        for version in LEGACY_EVENT_SCHEMA_VERSION..=EVENT_SCHEMA_VERSION {
+            let encoded = event.encode_with_version(version);
+
+            prop_assert_eq!(Event::decode(&encoded).unwrap(), event.clone());
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::Instant,
+};
+
+use vrrb_config::MempoolAdmissionConfig;
+use vrrb_core::transactions::{Transaction, TransactionKind};
+
+use crate::{error::MempoolError, mempool::Result, Mempool};
+
+/// Where a transaction offered to the mempool comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdmissionOrigin {
+    /// Submitted to the node itself, through its RPC server or API
+    Local,
+
+    /// Relayed by a peer
+    Relayed,
+}
+
+/// Decides whether a transaction may enter the mempool. Caps the pending
+/// transactions of every sender, makes senders past the escalation threshold
+/// pay increasing fees and rate limits admissions globally.
+///
+/// Local and relayed transactions are rate limited apart, so that peers
+/// relaying a burst of transactions can't use up the room left for the node's
+/// own clients, nor the other way around.
+///
+/// Clones share the same rate limiting windows, so that the mempool and the
+/// read handles it hands out enforce the same global limits.
+#[derive(Debug, Clone)]
+pub struct AdmissionController {
+    config: MempoolAdmissionConfig,
+    local_window: Arc<Mutex<AdmissionWindow>>,
+    relayed_window: Arc<Mutex<AdmissionWindow>>,
+}
+
+#[derive(Debug)]
+struct AdmissionWindow {
+    started_at: Instant,
+    admitted: usize,
+}
+
+impl AdmissionWindow {
+    fn new() -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self {
+            started_at: Instant::now(),
+            admitted: 0,
+        }))
+    }
+}
+
+impl Default for AdmissionController {
+    fn default() -> Self {
+        Self::new(MempoolAdmissionConfig::default())
+    }
+}
+
+impl AdmissionController {
+    pub fn new(config: MempoolAdmissionConfig) -> Self {
+        Self {
+            config,
+            local_window: AdmissionWindow::new(),
+            relayed_window: AdmissionWindow::new(),
+        }
+    }
+
+    pub fn config(&self) -> &MempoolAdmissionConfig {
+        &self.config
+    }
+
+    /// Checks whether `txn` would be admitted into `mempool`, without counting
+    /// it against the rate limit of its origin
+    pub fn check(
+        &self,
+        txn: &TransactionKind,
+        mempool: &Mempool,
+        origin: AdmissionOrigin,
+    ) -> Result<()> {
+        self.check_sender(txn, mempool)?;
+        self.check_rate(origin, Instant::now(), false)
+    }
+
+    /// Checks whether `txn` may be admitted into `mempool` and, if so, counts
+    /// it against the rate limit of its origin
+    pub fn admit(
+        &self,
+        txn: &TransactionKind,
+        mempool: &Mempool,
+        origin: AdmissionOrigin,
+    ) -> Result<()> {
+        self.admit_at(txn, mempool, origin, Instant::now())
+    }
+
+    pub(crate) fn admit_at(
+        &self,
+        txn: &TransactionKind,
+        mempool: &Mempool,
+        origin: AdmissionOrigin,
+        now: Instant,
+    ) -> Result<()> {
+        self.check_sender(txn, mempool)?;
+        self.check_rate(origin, now, true)
+    }
+
+    fn check_sender(&self, txn: &TransactionKind, mempool: &Mempool) -> Result<()> {
+        // re-admitting a transaction doesn't grow the sender's share of the pool
+        if mempool.contains(&txn.id()) {
+            return Ok(());
+        }
+
+        let sender = txn.sender_address();
+        let pending = mempool.pending_of(&sender);
+
+        if pending >= self.config.max_pending_per_sender {
+            return Err(MempoolError::SenderLimitReached(sender, pending));
+        }
+
+        let required = self.config.required_fee(pending);
+        let offered = txn.fee();
+
+        if offered < required {
+            return Err(MempoolError::FeeTooLow { offered, required });
+        }
+
+        Ok(())
+    }
+
+    fn check_rate(&self, origin: AdmissionOrigin, now: Instant, record: bool) -> Result<()> {
+        let (window, max_admissions) = match origin {
+            AdmissionOrigin::Local => (&self.local_window, self.config.max_admissions_per_window),
+            AdmissionOrigin::Relayed => (
+                &self.relayed_window,
+                self.config.max_relayed_admissions_per_window,
+            ),
+        };
+
+        let mut window = window.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if now.saturating_duration_since(window.started_at) >= self.config.admission_window {
+            window.started_at = now;
+            window.admitted = 0;
+        }
+
+        if window.admitted >= max_admissions {
+            return Err(MempoolError::RateLimited);
+        }
+
+        if record {
+            window.admitted += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use primitives::{Address, Signature};
+    use secp256k1::ecdsa;
+    use vrrb_core::{keypair::KeyPair, transactions::BASE_FEE};
+
+    use super::*;
+    use crate::{LeftRightMempool, TxnRecord, TxnStatus};
+
+    fn txn_from(keypair: &KeyPair, nonce: u128) -> TransactionKind {
+        let signature: Signature = ecdsa::Signature::from_compact(&[1u8; 64]).unwrap();
+
+        TransactionKind::transfer_builder()
+            .timestamp(0)
+            .sender_address(Address::new(*keypair.get_miner_public_key()))
+            .sender_public_key(*keypair.get_miner_public_key())
+            .receiver_address(Address::new(*KeyPair::random().get_miner_public_key()))
+            .amount(0)
+            .validators(HashMap::<String, bool>::new())
+            .nonce(nonce)
+            .signature(signature)
+            .build_kind()
+            .expect("Failed to build transaction")
+    }
+
+    fn pool_of(txns: &[TransactionKind]) -> Mempool {
+        txns.iter().cloned().map(TxnRecord::new).collect()
+    }
+
+    #[test]
+    fn senders_are_capped_at_their_pending_limit() {
+        let controller = AdmissionController::new(MempoolAdmissionConfig {
+            max_pending_per_sender: 2,
+            fee_escalation_threshold: 2,
+            ..Default::default()
+        });
+
+        let spammer = KeyPair::random();
+        let pool = pool_of(&[txn_from(&spammer, 0), txn_from(&spammer, 1)]);
+
+        assert!(matches!(
+            controller.admit(&txn_from(&spammer, 2), &pool, AdmissionOrigin::Local),
+            Err(MempoolError::SenderLimitReached(_, 2))
+        ));
+
+        controller
+            .admit(
+                &txn_from(&KeyPair::random(), 0),
+                &pool,
+                AdmissionOrigin::Local,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn senders_past_the_threshold_must_pay_escalating_fees() {
+        let controller = AdmissionController::new(MempoolAdmissionConfig {
+            fee_escalation_threshold: 1,
+            escalated_base_fee: BASE_FEE * 2,
+            ..Default::default()
+        });
+
+        let sender = KeyPair::random();
+        let first = txn_from(&sender, 0);
+
+        controller
+            .admit(&first, &Mempool::default(), AdmissionOrigin::Local)
+            .unwrap();
+
+        assert_eq!(
+            controller.admit(
+                &txn_from(&sender, 1),
+                &pool_of(&[first]),
+                AdmissionOrigin::Local
+            ),
+            Err(MempoolError::FeeTooLow {
+                offered: BASE_FEE,
+                required: BASE_FEE * 2,
+            })
+        );
+    }
+
+    #[test]
+    fn admissions_are_rate_limited_globally() {
+        let controller = AdmissionController::new(MempoolAdmissionConfig {
+            max_admissions_per_window: 2,
+            admission_window: Duration::from_secs(10),
+            ..Default::default()
+        });
+
+        let pool = Mempool::default();
+        let now = Instant::now();
+
+        for _ in 0..2 {
+            controller
+                .admit_at(
+                    &txn_from(&KeyPair::random(), 0),
+                    &pool,
+                    AdmissionOrigin::Local,
+                    now,
+                )
+                .unwrap();
+        }
+
+        let txn = txn_from(&KeyPair::random(), 0);
+
+        assert_eq!(
+            controller.admit_at(&txn, &pool, AdmissionOrigin::Local, now),
+            Err(MempoolError::RateLimited)
+        );
+        assert_eq!(
+            controller.check(&txn, &pool, AdmissionOrigin::Local),
+            Err(MempoolError::RateLimited)
+        );
+
+        controller
+            .admit_at(
+                &txn,
+                &pool,
+                AdmissionOrigin::Local,
+                now + Duration::from_secs(10),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn relayed_and_local_admissions_have_separate_budgets() {
+        let controller = AdmissionController::new(MempoolAdmissionConfig {
+            max_admissions_per_window: 1,
+            max_relayed_admissions_per_window: 2,
+            admission_window: Duration::from_secs(10),
+            ..Default::default()
+        });
+
+        let pool = Mempool::default();
+        let now = Instant::now();
+
+        for _ in 0..2 {
+            controller
+                .admit_at(
+                    &txn_from(&KeyPair::random(), 0),
+                    &pool,
+                    AdmissionOrigin::Relayed,
+                    now,
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            controller.admit_at(
+                &txn_from(&KeyPair::random(), 0),
+                &pool,
+                AdmissionOrigin::Relayed,
+                now
+            ),
+            Err(MempoolError::RateLimited)
+        );
+
+        // NOTE: peers used up their budget, the node's own clients still have theirs
+        controller
+            .admit_at(
+                &txn_from(&KeyPair::random(), 0),
+                &pool,
+                AdmissionOrigin::Local,
+                now,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn pending_txns_are_counted_per_sender_as_they_come_and_go() {
+        let sender = KeyPair::random();
+        let sender_address = Address::new(*sender.get_miner_public_key());
+
+        let first = txn_from(&sender, 0);
+        let second = txn_from(&sender, 1);
+
+        let mut mempool = LeftRightMempool::new();
+        mempool.insert(first.clone()).unwrap();
+        mempool.insert(second).unwrap();
+        mempool.insert(txn_from(&KeyPair::random(), 0)).unwrap();
+
+        let pending_of_sender =
+            |mempool: &LeftRightMempool| mempool.read.enter().unwrap().pending_of(&sender_address);
+
+        assert_eq!(pending_of_sender(&mempool), 2);
+
+        mempool
+            .extend_with_records(
+                [TxnRecord {
+                    status: TxnStatus::Validated,
+                    ..TxnRecord::new(first.clone())
+                }]
+                .into(),
+            )
+            .unwrap();
+        assert_eq!(pending_of_sender(&mempool), 1);
+
+        mempool.remove(&first.id()).unwrap();
+        assert_eq!(pending_of_sender(&mempool), 1);
+
+        mempool.resurrect(vec![TxnRecord::new(first)]).unwrap();
+        assert_eq!(pending_of_sender(&mempool), 2);
+    }
+
+    #[test]
+    fn mempool_rejects_transactions_that_fail_admission() {
+        let mut mempool = LeftRightMempool::with_admission_config(MempoolAdmissionConfig {
+            max_pending_per_sender: 1,
+            fee_escalation_threshold: 1,
+            ..Default::default()
+        });
+
+        let sender = KeyPair::random();
+        mempool
+            .admit(txn_from(&sender, 0), AdmissionOrigin::Local)
+            .unwrap();
+
+        let rejected = txn_from(&sender, 1);
+
+        assert!(mempool.factory().check_admission(&rejected).is_err());
+        assert!(mempool.admit(rejected, AdmissionOrigin::Relayed).is_err());
+        assert_eq!(mempool.size(), 1);
+    }
+}
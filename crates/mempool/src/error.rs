@@ -1,3 +1,4 @@
+use primitives::{Address, ClassifiedError, ErrorCode};
 use vrrb_core::transactions::TransactionDigest;
 
 #[derive(thiserror::Error, PartialEq, Eq, Debug)]
@@ -14,4 +15,30 @@ pub enum MempoolError {
 
     #[error("transaction {0} already exists")]
     TransactionExists(TransactionDigest),
+
+    #[error("sender {0} already has {1} pending transactions in mempool")]
+    SenderLimitReached(Address, usize),
+
+    #[error("fee {offered} is below the {required} required from this sender")]
+    FeeTooLow { offered: u128, required: u128 },
+
+    #[error("mempool is not admitting transactions right now, try again later")]
+    RateLimited,
+}
+
+impl ClassifiedError for MempoolError {
+    #[allow(deprecated)]
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            MempoolError::TransactionMissing(_) | MempoolError::TransactionNotFound(_) => {
+                ErrorCode::NotFound
+            },
+            MempoolError::TransactionInvalid(_) => ErrorCode::InvalidTransaction,
+            MempoolError::TransactionExists(_) => ErrorCode::AlreadyExists,
+            MempoolError::SenderLimitReached(..) | MempoolError::RateLimited => {
+                ErrorCode::RateLimited
+            },
+            MempoolError::FeeTooLow { .. } => ErrorCode::FeeTooLow,
+        }
+    }
 }
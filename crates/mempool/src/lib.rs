@@ -1,10 +1,13 @@
+pub mod admission;
 pub mod error;
 pub mod mempool;
+pub mod reconciliation;
+pub mod stats;
 
 use anyhow::{Context, Result};
 use reqwest::StatusCode;
 
-pub use crate::mempool::*;
+pub use crate::{admission::*, mempool::*, reconciliation::*, stats::*};
 
 pub async fn create_tx_indexer(txn_record: &TxnRecord) -> Result<StatusCode> {
     let url = "http://localhost:3444/transactions"; // TODO: Move to config
@@ -522,4 +525,101 @@ mod tests {
                 handle.join().unwrap();
             });
     }
+
+    #[test]
+    fn resurrected_txns_keep_their_original_place_in_the_pool() {
+        let keypair = KeyPair::random();
+        let recv_keypair = KeyPair::random();
+
+        let transfer_builder = TransactionKind::transfer_builder()
+            .timestamp(0)
+            .sender_address(Address::new(keypair.get_miner_public_key().clone()))
+            .sender_public_key(keypair.get_miner_public_key().clone())
+            .receiver_address(Address::new(recv_keypair.get_miner_public_key().clone()))
+            .validators(HashMap::<String, bool>::new())
+            .nonce(0)
+            .signature(mock_txn_signature());
+
+        let rolled_back = transfer_builder
+            .clone()
+            .amount(10)
+            .build_kind()
+            .expect("Failed to build transaction");
+        let pending = transfer_builder
+            .amount(20)
+            .build_kind()
+            .expect("Failed to build transaction");
+
+        let mut mpooldb = LeftRightMempool::new();
+        mpooldb.insert(pending.clone()).unwrap();
+
+        let record = TxnRecord {
+            added_timestamp: 1,
+            status: TxnStatus::Validated,
+            validated_timestamp: 2,
+            ..TxnRecord::new(rolled_back.clone())
+        };
+
+        mpooldb.resurrect(vec![record.clone()]).unwrap();
+        mpooldb.resurrect(vec![record]).unwrap();
+
+        let pool = mpooldb.pool();
+        assert_eq!(pool.len(), 2);
+
+        let (first_id, first) = pool.first().unwrap();
+        assert_eq!(first_id, &rolled_back.id());
+        assert_eq!(first.status, TxnStatus::Pending);
+        assert_eq!(first.validated_timestamp, 0);
+        assert_eq!(pool.last().unwrap().0, &pending.id());
+    }
+
+    #[test]
+    fn compaction_gives_back_the_room_a_drained_pool_grew_into() {
+        let keypair = KeyPair::random();
+        let recv_keypair = KeyPair::random();
+
+        let transfer_builder = TransactionKind::transfer_builder()
+            .timestamp(0)
+            .sender_address(Address::new(keypair.get_miner_public_key().clone()))
+            .sender_public_key(keypair.get_miner_public_key().clone())
+            .receiver_address(Address::new(recv_keypair.get_miner_public_key().clone()))
+            .validators(HashMap::<String, bool>::new())
+            .nonce(0)
+            .signature(mock_txn_signature());
+
+        let txns = (0..12_000)
+            .map(|amount| {
+                transfer_builder
+                    .clone()
+                    .amount(amount)
+                    .build_kind()
+                    .expect("Failed to build transaction")
+            })
+            .collect::<HashSet<TransactionKind>>();
+
+        let mut mpooldb = LeftRightMempool::new();
+        mpooldb.extend(txns).unwrap();
+
+        let drained = mpooldb
+            .pool()
+            .keys()
+            .skip(10)
+            .cloned()
+            .collect::<HashSet<_>>();
+        mpooldb.remove_txns(&drained).unwrap();
+
+        let pending = mpooldb.pool().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(pending.len(), 10);
+        assert!(mpooldb.reclaimable() > 0);
+
+        let compaction = mpooldb.compact();
+        assert_eq!(compaction.txns, 10);
+        assert!(compaction.capacity_after < compaction.capacity_before);
+        assert!(compaction.reclaimed_bytes > 0);
+        assert_eq!(mpooldb.reclaimable(), 0);
+
+        // NOTE: compacting leaves the txns, and the order they were added in, as is
+        let compacted = mpooldb.pool().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(compacted, pending);
+    }
 }
@@ -7,11 +7,17 @@ use std::{
 use fxhash::FxBuildHasher;
 use indexmap::IndexMap;
 use left_right::{Absorb, ReadHandle, ReadHandleFactory, WriteHandle};
+use primitives::Address;
 use serde::{Deserialize, Serialize};
+use vrrb_config::MempoolAdmissionConfig;
 use vrrb_core::transactions::{TransactionDigest, TxTimestamp, Transaction, TransactionKind};
 
 
 use super::error::MempoolError;
+use crate::{
+    admission::{AdmissionController, AdmissionOrigin},
+    reconciliation::{BucketIndex, MempoolSketch},
+};
 
 pub type Result<T> = StdResult<T, MempoolError>;
 
@@ -64,6 +70,10 @@ pub enum TxnStatus {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Mempool {
     pool: PoolType,
+
+    /// Number of pending txns each sender has in the pool, kept up to date as
+    /// txns come and go so admission never has to walk the pool
+    pending_by_sender: HashMap<Address, usize>,
 }
 
 pub const DEFAULT_INITIAL_MEMPOOL_CAPACITY: usize = 10000;
@@ -75,6 +85,7 @@ impl Default for Mempool {
                 DEFAULT_INITIAL_MEMPOOL_CAPACITY,
                 <_>::default(),
             ),
+            pending_by_sender: HashMap::new(),
         }
     }
 }
@@ -87,22 +98,148 @@ impl Mempool {
     pub fn is_empty(&self) -> bool {
         self.pool.is_empty()
     }
+
+    /// Number of txns the pool can hold without growing
+    pub fn capacity(&self) -> usize {
+        self.pool.capacity()
+    }
+
+    pub fn contains(&self, txn_id: &TransactionDigest) -> bool {
+        self.pool.contains_key(txn_id)
+    }
+
+    /// Number of pending txns `sender` has in the pool
+    pub fn pending_of(&self, sender: &Address) -> usize {
+        self.pending_by_sender
+            .get(sender)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn insert_record(&mut self, record: TxnRecord) {
+        self.track(&record);
+
+        if let Some(replaced) = self.pool.insert(record.txn_id.clone(), record) {
+            self.untrack(&replaced);
+        }
+    }
+
+    fn remove_record(&mut self, txn_id: &TransactionDigest) {
+        if let Some(removed) = self.pool.remove(txn_id) {
+            self.untrack(&removed);
+        }
+    }
+
+    fn track(&mut self, record: &TxnRecord) {
+        if record.status == TxnStatus::Pending {
+            *self
+                .pending_by_sender
+                .entry(record.txn.sender_address())
+                .or_default() += 1;
+        }
+    }
+
+    fn untrack(&mut self, record: &TxnRecord) {
+        if record.status != TxnStatus::Pending {
+            return;
+        }
+
+        let sender = record.txn.sender_address();
+
+        if let Some(pending) = self.pending_by_sender.get_mut(&sender) {
+            *pending = pending.saturating_sub(1);
+
+            if *pending == 0 {
+                self.pending_by_sender.remove(&sender);
+            }
+        }
+    }
+}
+
+impl FromIterator<TxnRecord> for Mempool {
+    fn from_iter<I: IntoIterator<Item = TxnRecord>>(records: I) -> Self {
+        let mut mempool = Mempool::default();
+
+        for record in records {
+            mempool.insert_record(record);
+        }
+
+        mempool
+    }
+}
+
+/// Memory given back by a [LeftRightMempool::compact] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolCompaction {
+    /// Txns the pool held when it was compacted
+    pub txns: usize,
+    pub capacity_before: usize,
+    pub capacity_after: usize,
+
+    /// Estimate of the bytes freed across both copies of the pool
+    pub reclaimed_bytes: usize,
+}
+
+impl MempoolCompaction {
+    fn new(txns: usize, capacity_before: usize, capacity_after: usize) -> Self {
+        // NOTE: each slot holds an entry along with its hash, and is indexed by the
+        // hash table
+        let slot_size = std::mem::size_of::<(u64, TransactionDigest, TxnRecord)>()
+            + std::mem::size_of::<usize>();
+
+        let reclaimed_slots = capacity_before.saturating_sub(capacity_after);
+
+        Self {
+            txns,
+            capacity_before,
+            capacity_after,
+            reclaimed_bytes: 2 * reclaimed_slots * slot_size,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MempoolOp {
     Add(Box<TxnRecord>),
     Remove(TransactionDigest),
+
+    /// Puts back records of txns that left the pool, ordered by when they
+    /// were first added
+    Resurrect(Vec<TxnRecord>),
+
+    /// Rebuilds the pool to fit the txns it holds, dropping the room and the
+    /// deleted slots it accumulated
+    Compact,
 }
 
 impl Absorb<MempoolOp> for Mempool {
     fn absorb_first(&mut self, op: &mut MempoolOp, _: &Self) {
         match op {
             MempoolOp::Add(record) => {
-                self.pool.insert(record.txn_id.clone(), *record.clone());
+                self.insert_record(*record.clone());
             },
             MempoolOp::Remove(id) => {
-                self.pool.remove(id);
+                self.remove_record(id);
+            },
+            MempoolOp::Resurrect(records) => {
+                for record in records.iter() {
+                    if !self.pool.contains_key(&record.txn_id) {
+                        self.insert_record(record.clone());
+                    }
+                }
+
+                // NOTE: the sort is stable, so txns added at the same time keep their order
+                self.pool
+                    .sort_by(|_, a, _, b| a.added_timestamp.cmp(&b.added_timestamp));
+            },
+            MempoolOp::Compact => {
+                // NOTE: the pool keeps its initial capacity so it doesn't regrow from
+                // scratch as soon as txns come in again
+                let capacity = self.pool.len().max(DEFAULT_INITIAL_MEMPOOL_CAPACITY);
+                let mut pool = PoolType::with_capacity_and_hasher(capacity, <_>::default());
+
+                pool.extend(self.pool.drain(..));
+                self.pool = pool;
             },
         }
     }
@@ -141,13 +278,18 @@ impl FetchFiltered for ReadHandle<Mempool> {
 pub struct LeftRightMempool {
     pub read: ReadHandle<Mempool>,
     pub write: WriteHandle<Mempool, MempoolOp>,
+    admission: AdmissionController,
 }
 
 impl Default for LeftRightMempool {
     fn default() -> Self {
         let (write, read) = left_right::new::<Mempool, MempoolOp>();
 
-        LeftRightMempool { read, write }
+        LeftRightMempool {
+            read,
+            write,
+            admission: AdmissionController::default(),
+        }
     }
 }
 
@@ -157,6 +299,14 @@ impl LeftRightMempool {
         Self::default()
     }
 
+    /// Creates new Mempool DB that admits transactions according to `config`
+    pub fn with_admission_config(config: MempoolAdmissionConfig) -> Self {
+        Self {
+            admission: AdmissionController::new(config),
+            ..Self::default()
+        }
+    }
+
     /// Getter for Mempool DB
     pub fn pool(&self) -> PoolType {
         self.read
@@ -176,7 +326,10 @@ impl LeftRightMempool {
     pub fn factory(&self) -> MempoolReadHandleFactory {
         let factory = self.read.factory();
 
-        MempoolReadHandleFactory { factory }
+        MempoolReadHandleFactory {
+            factory,
+            admission: self.admission.clone(),
+        }
     }
 
     /// Adds a new transaction, makes sure it is unique in db.
@@ -187,6 +340,19 @@ impl LeftRightMempool {
         Ok(())
     }
 
+    /// Inserts a transaction submitted by a client or relayed by a peer, as
+    /// `origin` tells, provided it passes admission control
+    pub fn admit(&mut self, txn: TransactionKind, origin: AdmissionOrigin) -> Result<usize> {
+        let admitted = match self.read.enter() {
+            Some(mempool) => self.admission.admit(&txn, &mempool, origin),
+            None => Ok(()),
+        };
+
+        admitted?;
+
+        self.insert(txn)
+    }
+
     pub fn insert(&mut self, txn: TransactionKind) -> Result<usize> {
         let txn_record = TxnRecord::new(txn);
         self.write
@@ -196,6 +362,27 @@ impl LeftRightMempool {
         Ok(self.size_in_kilobytes())
     }
 
+    /// Puts back transactions that left the pool, such as the ones of a rolled
+    /// back block. Each keeps the place its `added_timestamp` gives it in the
+    /// pool, instead of going to the back of it. Admission control is skipped
+    /// since every record was admitted once already
+    pub fn resurrect(&mut self, records: Vec<TxnRecord>) -> Result<usize> {
+        let records = records
+            .into_iter()
+            .map(|record| TxnRecord {
+                status: TxnStatus::Pending,
+                validated_timestamp: 0,
+                rejected_timestamp: 0,
+                deleted_timestamp: 0,
+                ..record
+            })
+            .collect();
+
+        self.write.append(MempoolOp::Resurrect(records)).publish();
+
+        Ok(self.size_in_kilobytes())
+    }
+
     /// Retrieves a single transaction identified by id, makes sure it exists in
     /// db
     pub fn get_txn(&mut self, txn_hash: &TransactionDigest) -> Option<TransactionKind> {
@@ -325,6 +512,44 @@ impl LeftRightMempool {
         }
     }
 
+    /// Number of txns the mempooldb can hold without growing.
+    pub fn capacity(&self) -> usize {
+        self.read
+            .enter()
+            .map(|mempool| mempool.capacity())
+            .unwrap_or_default()
+    }
+
+    /// Number of txns the mempooldb has room for past the ones it holds and
+    /// its initial capacity, which [Self::compact] would give back.
+    pub fn reclaimable(&self) -> usize {
+        self.read
+            .enter()
+            .map(|mempool| {
+                let needed = mempool.len().max(DEFAULT_INITIAL_MEMPOOL_CAPACITY);
+
+                mempool.capacity().saturating_sub(needed)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Rebuilds the mempooldb to fit the txns it holds. Each copy is rebuilt
+    /// while readers are on the other one, so reads are never blocked.
+    /// Pushes to the ReadHandle.
+    pub fn compact(&mut self) -> MempoolCompaction {
+        let capacity_before = self.capacity();
+
+        self.write.append(MempoolOp::Compact).publish();
+
+        let txns = self
+            .read
+            .enter()
+            .map(|mempool| mempool.len())
+            .unwrap_or_default();
+
+        MempoolCompaction::new(txns, capacity_before, self.capacity())
+    }
+
     /// Retrieves actual size of the mempooldb.
     pub fn size(&self) -> usize {
         self.pool().len()
@@ -347,7 +572,11 @@ impl LeftRightMempool {
 impl From<PoolType> for LeftRightMempool {
     fn from(pool: PoolType) -> Self {
         let (write, read) = left_right::new::<Mempool, MempoolOp>();
-        let mut mempool_db = Self { read, write };
+        let mut mempool_db = Self {
+            read,
+            write,
+            admission: AdmissionController::default(),
+        };
 
         let records = pool.values().cloned().collect::<HashSet<TxnRecord>>();
 
@@ -359,13 +588,17 @@ impl From<PoolType> for LeftRightMempool {
 
 impl Clone for LeftRightMempool {
     fn clone(&self) -> Self {
-        Self::from(self.pool())
+        let mut mempool_db = Self::from(self.pool());
+        mempool_db.admission = self.admission.clone();
+
+        mempool_db
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct MempoolReadHandleFactory {
     factory: ReadHandleFactory<Mempool>,
+    admission: AdmissionController,
 }
 
 impl MempoolReadHandleFactory {
@@ -402,4 +635,45 @@ impl MempoolReadHandleFactory {
         }
         None
     }
+
+    /// Checks whether the mempool would currently admit `txn` if it was
+    /// submitted to the node, so that submitters can learn why a transaction
+    /// is turned away
+    pub fn check_admission(&self, txn: &TransactionKind) -> Result<()> {
+        match self.factory.handle().enter() {
+            Some(mempool) => self.admission.check(txn, &mempool, AdmissionOrigin::Local),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns a sketch of the transactions within the mempool, to be
+    /// reconciled against a peer's
+    pub fn sketch(&self) -> MempoolSketch {
+        MempoolSketch::new(self.handle().keys())
+    }
+
+    /// Returns up to `limit` digests of the transactions that fall within the
+    /// given ranges of a sketch
+    pub fn digests_in_buckets(
+        &self,
+        buckets: &[BucketIndex],
+        limit: usize,
+    ) -> Vec<TransactionDigest> {
+        self.handle()
+            .keys()
+            .filter(|digest| buckets.contains(&MempoolSketch::bucket_of(digest)))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the digests among `digests` that aren't in the mempool
+    pub fn missing(&self, digests: Vec<TransactionDigest>) -> Vec<TransactionDigest> {
+        let pool = self.handle();
+
+        digests
+            .into_iter()
+            .filter(|digest| !pool.contains_key(digest))
+            .collect()
+    }
 }
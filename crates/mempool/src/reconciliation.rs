@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use vrrb_core::transactions::TransactionDigest;
+
+/// Number of digest ranges a mempool sketch splits the digest space into. A
+/// digest falls into the range matching its first byte.
+pub const SKETCH_BUCKET_COUNT: usize = 256;
+
+pub type BucketIndex = u8;
+
+/// Summary of the transactions whose digest falls within a single range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SketchBucket {
+    pub count: u32,
+
+    /// XOR of part of every digest within the range, so two ranges holding
+    /// the same transactions always share a fingerprint regardless of the
+    /// order they were inserted in
+    pub fingerprint: u64,
+}
+
+impl SketchBucket {
+    fn insert(&mut self, digest: &TransactionDigest) {
+        self.count = self.count.saturating_add(1);
+        self.fingerprint ^= fingerprint_of(digest);
+    }
+}
+
+/// Compact summary of a mempool's contents, exchanged by peers to find out
+/// which digest ranges their mempools disagree on without sending every
+/// digest over the wire.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MempoolSketch {
+    buckets: Vec<SketchBucket>,
+}
+
+impl Default for MempoolSketch {
+    fn default() -> Self {
+        Self {
+            buckets: vec![SketchBucket::default(); SKETCH_BUCKET_COUNT],
+        }
+    }
+}
+
+impl MempoolSketch {
+    pub fn new<'a>(digests: impl IntoIterator<Item = &'a TransactionDigest>) -> Self {
+        let mut sketch = Self::default();
+
+        digests.into_iter().for_each(|digest| {
+            sketch.buckets[Self::bucket_of(digest) as usize].insert(digest);
+        });
+
+        sketch
+    }
+
+    /// Returns the range a digest falls into
+    pub fn bucket_of(digest: &TransactionDigest) -> BucketIndex {
+        digest.as_bytes().first().copied().unwrap_or_default()
+    }
+
+    /// Returns the ranges whose contents differ between both sketches.
+    /// Ranges missing from a malformed sketch are treated as empty.
+    pub fn differing_buckets(&self, other: &MempoolSketch) -> Vec<BucketIndex> {
+        (0..SKETCH_BUCKET_COUNT)
+            .filter(|index| {
+                let ours = self.buckets.get(*index).copied().unwrap_or_default();
+                let theirs = other.buckets.get(*index).copied().unwrap_or_default();
+
+                ours != theirs
+            })
+            .map(|index| index as BucketIndex)
+            .collect()
+    }
+
+    /// Returns the number of transactions summarized by the sketch
+    pub fn len(&self) -> usize {
+        self.buckets
+            .iter()
+            .map(|bucket| bucket.count as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Uses the bytes following the one that picks the digest's range, as that
+/// one is shared by every digest within it.
+fn fingerprint_of(digest: &TransactionDigest) -> u64 {
+    let mut fingerprint = [0u8; 8];
+
+    digest
+        .as_bytes()
+        .iter()
+        .skip(1)
+        .take(8)
+        .enumerate()
+        .for_each(|(i, byte)| fingerprint[i] = *byte);
+
+    u64::from_le_bytes(fingerprint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(first_byte: u8, seed: u8) -> TransactionDigest {
+        let mut bytes = vec![seed; 32];
+        bytes[0] = first_byte;
+
+        TransactionDigest::from(bytes)
+    }
+
+    #[test]
+    fn identical_mempools_produce_identical_sketches() {
+        let digests = vec![digest(1, 1), digest(1, 2), digest(200, 3)];
+        let reversed: Vec<TransactionDigest> = digests.iter().rev().cloned().collect();
+
+        let ours = MempoolSketch::new(&digests);
+        let theirs = MempoolSketch::new(&reversed);
+
+        assert_eq!(ours.len(), 3);
+        assert!(ours.differing_buckets(&theirs).is_empty());
+    }
+
+    #[test]
+    fn only_ranges_with_missing_txns_differ() {
+        let ours = MempoolSketch::new(&[digest(1, 1), digest(7, 2), digest(7, 3)]);
+        let theirs = MempoolSketch::new(&[digest(1, 1), digest(7, 2), digest(9, 4)]);
+
+        assert_eq!(ours.differing_buckets(&theirs), vec![7, 9]);
+    }
+
+    #[test]
+    fn malformed_sketches_are_compared_as_empty() {
+        let ours = MempoolSketch::new(&[digest(3, 1)]);
+        let truncated = MempoolSketch { buckets: vec![] };
+
+        assert_eq!(ours.differing_buckets(&truncated), vec![3]);
+    }
+}
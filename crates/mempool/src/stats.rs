@@ -0,0 +1,343 @@
+//! Summary of a mempool's contents for wallets pricing their txns: how the
+//! pending fees are spread, how long txns have been waiting and how many
+//! rounds a txn paying a given fee can expect to wait, judging by how much
+//! recent blocks included.
+
+use std::collections::VecDeque;
+
+use primitives::{Round, Weight};
+use serde::{Deserialize, Serialize};
+use vrrb_core::transactions::Transaction;
+
+use crate::TxnRecord;
+
+/// Percentiles of the pending fees reported by [MempoolStats]
+pub const FEE_PERCENTILES: [u8; 6] = [10, 25, 50, 75, 90, 99];
+
+/// Upper bounds, in seconds, of the buckets [MempoolStats] sorts pending txns
+/// into by age. Older txns fall into a last, unbounded bucket
+pub const AGE_BUCKET_BOUNDS_SECS: [u64; 6] = [10, 30, 60, 300, 900, 3600];
+
+/// Number of latest blocks the fill rate and inclusion estimates are based on
+pub const RECENT_BLOCK_FILLS: usize = 32;
+
+/// What [MempoolStats] needs to know of a pending txn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingTxn {
+    pub fee: u128,
+    pub weight: Weight,
+
+    /// Unix timestamp of when the txn was added to the mempool
+    pub added_at: i64,
+}
+
+impl From<&TxnRecord> for PendingTxn {
+    fn from(record: &TxnRecord) -> Self {
+        Self {
+            fee: record.txn.fee(),
+            weight: record.txn.weight(),
+            added_at: record.added_timestamp,
+        }
+    }
+}
+
+/// How many txns an applied block included, and how much they weighed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockFill {
+    pub round: Round,
+    pub txns: usize,
+    pub weight: Weight,
+}
+
+/// Fills of the latest [RECENT_BLOCK_FILLS] blocks applied, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct RecentBlockFills {
+    fills: VecDeque<BlockFill>,
+}
+
+impl RecentBlockFills {
+    /// Records the fill of a block, forgetting the oldest fill once there are
+    /// too many
+    pub fn record(&mut self, fill: BlockFill) {
+        self.fills.push_back(fill);
+
+        while self.fills.len() > RECENT_BLOCK_FILLS {
+            self.fills.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.fills.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fills.is_empty()
+    }
+
+    /// Average weight of the txns a recent block included, zero if no block
+    /// was applied yet
+    pub fn average_weight(&self) -> Weight {
+        let total = self
+            .fills
+            .iter()
+            .fold(0, |total: Weight, fill| total.saturating_add(fill.weight));
+
+        total / self.fills.len().max(1) as Weight
+    }
+
+    /// Average share of `max_block_weight` recent blocks filled, in
+    /// thousandths
+    pub fn fill_rate_millis(&self, max_block_weight: Weight) -> u64 {
+        if max_block_weight == 0 {
+            return 0;
+        }
+
+        let fill_rate = self.average_weight() as f64 / max_block_weight as f64;
+
+        (fill_rate * 1000.0) as u64
+    }
+
+    /// Estimates how many rounds a txn paying `fee` waits before a block
+    /// includes it, given the `pending` txns.
+    ///
+    /// Pending txns paying more go ahead of it, and blocks are assumed to
+    /// keep including as much as recent blocks did on average. Since fees
+    /// only rank txns certified within the fairness bound, this overestimates
+    /// the wait of txns that end up waiting past it
+    pub fn estimate_inclusion(&self, pending: &[PendingTxn], fee: u128) -> InclusionEstimate {
+        let weight_ahead = pending
+            .iter()
+            .filter(|txn| txn.fee > fee)
+            .fold(0, |weight: Weight, txn| weight.saturating_add(txn.weight));
+
+        // NOTE: the next round counts as the first, so a txn nothing goes
+        // ahead of is expected to be included in it
+        let throughput = self.average_weight();
+        let rounds = (throughput > 0).then(|| weight_ahead / throughput + 1);
+
+        InclusionEstimate {
+            fee,
+            weight_ahead,
+            rounds,
+        }
+    }
+}
+
+/// Fee below which lie `percentile` percent of the pending fees.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FeePercentile {
+    pub percentile: u8,
+    pub fee: u128,
+}
+
+/// Number of pending txns added to the mempool at most `max_age_secs` ago,
+/// and longer ago than the bound of the previous bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AgeBucket {
+    /// Unbounded for the last bucket
+    pub max_age_secs: Option<u64>,
+    pub txns: usize,
+}
+
+/// How long a txn paying `fee` can expect to wait for a block to include it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct InclusionEstimate {
+    pub fee: u128,
+
+    /// Cumulative weight of the pending txns paying more, which go ahead
+    pub weight_ahead: Weight,
+
+    /// Rounds until a block likely includes the txn, counting the next one as
+    /// the first. Unknown until the node has seen blocks include txns
+    pub rounds: Option<u64>,
+}
+
+/// Snapshot of the fees and ages of a mempool's pending txns, along with how
+/// full recent blocks were.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MempoolStats {
+    /// Number of pending txns
+    pub size: usize,
+    pub pending_weight: Weight,
+
+    /// Pending fees at each of [FEE_PERCENTILES], empty if no txn is pending
+    pub fee_percentiles: Vec<FeePercentile>,
+
+    /// Pending txns by age, youngest first
+    pub age_histogram: Vec<AgeBucket>,
+
+    /// Number of recent blocks the fill rate is averaged over
+    pub recent_blocks: usize,
+
+    /// Average share of the block weight limit recent blocks filled, in
+    /// thousandths
+    pub fill_rate_millis: u64,
+
+    /// Estimated wait of a txn paying the fee asked about, if any
+    pub inclusion_estimate: Option<InclusionEstimate>,
+}
+
+impl MempoolStats {
+    /// Summarizes the `pending` txns as of `now`, a unix timestamp
+    pub fn new(
+        pending: &[PendingTxn],
+        fills: &RecentBlockFills,
+        max_block_weight: Weight,
+        now: i64,
+    ) -> Self {
+        let mut fees: Vec<u128> = pending.iter().map(|txn| txn.fee).collect();
+        fees.sort_unstable();
+
+        // NOTE: nearest rank, so every percentile is a fee some txn pays
+        let fee_percentiles = if fees.is_empty() {
+            vec![]
+        } else {
+            FEE_PERCENTILES
+                .iter()
+                .map(|&percentile| {
+                    let rank = (percentile as usize * fees.len() + 99) / 100;
+
+                    FeePercentile {
+                        percentile,
+                        fee: fees[rank.max(1) - 1],
+                    }
+                })
+                .collect()
+        };
+
+        let mut age_histogram: Vec<AgeBucket> = AGE_BUCKET_BOUNDS_SECS
+            .iter()
+            .map(|&bound| Some(bound))
+            .chain([None])
+            .map(|max_age_secs| AgeBucket {
+                max_age_secs,
+                txns: 0,
+            })
+            .collect();
+
+        for txn in pending {
+            // NOTE: clocks running behind make for negative ages, which count
+            // as none
+            let age = now.saturating_sub(txn.added_at).max(0) as u64;
+
+            let bucket = AGE_BUCKET_BOUNDS_SECS
+                .iter()
+                .position(|&bound| age <= bound)
+                .unwrap_or(AGE_BUCKET_BOUNDS_SECS.len());
+
+            age_histogram[bucket].txns += 1;
+        }
+
+        let pending_weight = pending
+            .iter()
+            .fold(0, |weight: Weight, txn| weight.saturating_add(txn.weight));
+
+        Self {
+            size: pending.len(),
+            pending_weight,
+            fee_percentiles,
+            age_histogram,
+            recent_blocks: fills.len(),
+            fill_rate_millis: fills.fill_rate_millis(max_block_weight),
+            inclusion_estimate: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(fee: u128, added_at: i64) -> PendingTxn {
+        PendingTxn {
+            fee,
+            weight: 10,
+            added_at,
+        }
+    }
+
+    fn fills(weights: &[Weight]) -> RecentBlockFills {
+        let mut fills = RecentBlockFills::default();
+
+        for (round, &weight) in weights.iter().enumerate() {
+            fills.record(BlockFill {
+                round: round as Round,
+                txns: (weight / 10) as usize,
+                weight,
+            });
+        }
+
+        fills
+    }
+
+    #[test]
+    fn stats_spread_the_pending_txns_by_fee_and_age() {
+        let txns: Vec<PendingTxn> = (1..=100).map(|fee| pending(fee, 1_000)).collect();
+
+        let stats = MempoolStats::new(&txns, &fills(&[50, 100]), 100, 1_045);
+
+        assert_eq!(stats.size, 100);
+        assert_eq!(stats.pending_weight, 1_000);
+        assert_eq!(
+            stats
+                .fee_percentiles
+                .iter()
+                .map(|percentile| percentile.fee)
+                .collect::<Vec<u128>>(),
+            vec![10, 25, 50, 75, 90, 99]
+        );
+        assert_eq!(stats.age_histogram.len(), AGE_BUCKET_BOUNDS_SECS.len() + 1);
+        assert_eq!(
+            stats.age_histogram[2],
+            AgeBucket {
+                max_age_secs: Some(60),
+                txns: 100,
+            }
+        );
+        assert_eq!(stats.recent_blocks, 2);
+        assert_eq!(stats.fill_rate_millis, 750);
+
+        let stale = MempoolStats::new(&[pending(1, 0), pending(1, 10)], &fills(&[]), 100, 10_000);
+
+        assert_eq!(stale.age_histogram.last().unwrap().txns, 2);
+        assert!(MempoolStats::new(&[], &fills(&[]), 100, 0)
+            .fee_percentiles
+            .is_empty());
+    }
+
+    #[test]
+    fn higher_fees_are_estimated_to_be_included_sooner() {
+        // NOTE: 30 txns weighing 10 each, 10 of them paying each fee
+        let txns: Vec<PendingTxn> = [5, 10, 15]
+            .iter()
+            .flat_map(|&fee| (0..10).map(move |_| pending(fee, 0)))
+            .collect();
+
+        let fills = fills(&[100, 100]);
+
+        assert_eq!(fills.estimate_inclusion(&txns, 20).rounds, Some(1));
+        assert_eq!(fills.estimate_inclusion(&txns, 10).rounds, Some(2));
+
+        let estimate = fills.estimate_inclusion(&txns, 1);
+        assert_eq!(estimate.weight_ahead, 300);
+        assert_eq!(estimate.rounds, Some(4));
+
+        assert_eq!(
+            RecentBlockFills::default()
+                .estimate_inclusion(&txns, 1)
+                .rounds,
+            None
+        );
+    }
+
+    #[test]
+    fn only_the_latest_block_fills_are_kept() {
+        let mut weights = vec![0; RECENT_BLOCK_FILLS];
+        weights.push(RECENT_BLOCK_FILLS as Weight * 10);
+
+        let fills = fills(&weights);
+
+        assert_eq!(fills.len(), RECENT_BLOCK_FILLS);
+        assert_eq!(fills.average_weight(), 10);
+    }
+}
@@ -14,9 +14,9 @@ pub mod v2 {
 mod tests {
     use std::{net::SocketAddr, sync::Arc};
 
-    use block::{Block, ProposalBlock};
+    use block::{consolidated_txns_weight, Block, ProposalBlock};
     use bulldag::vertex::Vertex;
-    use primitives::{Address, NodeId};
+    use primitives::{Address, NodeId, Weight};
     use ritelinked::LinkedHashMap;
     use vrrb_core::{
         claim::Claim,
@@ -111,6 +111,8 @@ mod tests {
                 0,
                 LinkedHashMap::new(),
                 LinkedHashMap::new(),
+                vec![],
+                vec![],
                 other_miner.claim.clone(),
                 keypair.get_miner_secret_key(),
             );
@@ -387,7 +389,7 @@ mod tests {
             let convergence = miner.try_mine();
             if let Ok(Block::Convergence { ref block }) = convergence {
                 miner.last_block = Some(Arc::new(block.to_owned()));
-                assert_eq!(1, block.header.next_block_reward.epoch);
+                assert_eq!(2, block.header.next_block_reward.epoch);
             }
         }
     }
@@ -438,9 +440,62 @@ mod tests {
             let convergence = miner.try_mine();
             if let Ok(Block::Convergence { ref block }) = convergence {
                 miner.last_block = Some(Arc::new(block.to_owned()));
-                assert_eq!(1, block.header.next_block_reward.epoch);
+                assert_eq!(2, block.header.next_block_reward.epoch);
                 assert_eq!(21, block.header.next_block_reward.amount);
             }
         }
     }
+
+    #[test]
+    fn test_consolidated_txns_keep_one_instance_per_digest() {
+        let miner = create_miner();
+        let txns: LinkedHashMap<TransactionDigest, QuorumCertifiedTxn> =
+            create_txns(5).collect();
+
+        let prop1 = build_single_proposal_block_from_txns("ref".to_string(), txns.clone(), 0, 0);
+        let prop2 = build_single_proposal_block_from_txns("ref".to_string(), txns.clone(), 0, 0);
+        let owner = std::cmp::min(prop1.hash.clone(), prop2.hash.clone());
+
+        let consolidated = miner.consolidate_txns(&[prop1.clone(), prop2.clone()]);
+
+        for (digest, _) in txns.iter() {
+            let instances: Vec<&String> = consolidated
+                .iter()
+                .filter(|(_, txn_ids)| txn_ids.contains(digest))
+                .map(|(ref_hash, _)| ref_hash)
+                .collect();
+
+            assert_eq!(instances, vec![&owner]);
+        }
+
+        let total = prop1.txns.len() + prop2.txns.len() - txns.len();
+        let consolidated_total: usize = consolidated.values().map(|ids| ids.len()).sum();
+        assert_eq!(consolidated_total, total);
+    }
+
+    #[test]
+    fn test_consolidated_txns_are_capped_at_the_max_block_weight() {
+        let mut miner = create_miner();
+        let prop = build_single_proposal_block_from_txns("ref".to_string(), create_txns(5), 0, 0);
+
+        let max_block_weight: Weight = prop
+            .txns
+            .values()
+            .take(2)
+            .map(|txn| txn.txn().weight())
+            .sum();
+        miner.chain_spec.block_limits.max_block_weight = max_block_weight;
+
+        let consolidated = miner.consolidate_txns(&[prop.clone()]);
+        let capped = miner.cap_block_weight(consolidated, &[prop.clone()]);
+
+        let capped_ids: Vec<TransactionDigest> = capped.values().flatten().cloned().collect();
+        let expected_ids: Vec<TransactionDigest> = prop.txns.keys().take(2).cloned().collect();
+
+        assert_eq!(capped_ids, expected_ids);
+        assert_eq!(
+            consolidated_txns_weight(&capped, &[prop]),
+            Some(max_block_weight)
+        );
+    }
 }
@@ -4,16 +4,22 @@ use std::net::SocketAddr;
 /// nodes occur The mining of blocks can be thought of as incremental
 /// checkpoints in the state.
 //FEATURE TAG(S): Block Structure, VRF for Next Block Seed, Rewards
-use std::sync::{Arc, RwLock};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 
 use block::{
-    block::Block, header::BlockHeader, ClaimHash, ClaimList, ConsolidatedClaims, ConsolidatedTxns,
-    ConvergenceBlock, GenesisBlock, InnerBlock, ProposalBlock, QuorumCertifiedTxnList, RefHash,
+    block::Block,
+    header::BlockHeader,
+    txn_order::{canonical_consolidated_txn_order, canonical_txn_order},
+    ClaimHash, ClaimList, ConsolidatedClaims, ConsolidatedTxns, ConvergenceBlock, GenesisBlock,
+    InnerBlock, ProposalBlock, QuorumCertifiedTxnList, RefHash,
 };
 use bulldag::graph::BullDag;
 use ethereum_types::U256;
-use primitives::{Address, Epoch, NodeId, PublicKey, Signature};
-use reward::reward::Reward;
+use primitives::{Address, ChainSpec, Epoch, NodeId, PublicKey, Signature, Weight};
+use reward::{reward::Reward, schedule::RewardSchedule};
 use ritelinked::{LinkedHashMap, LinkedHashSet};
 use secp256k1::{
     hashes::{sha256 as s256, Hash},
@@ -26,6 +32,7 @@ use vrrb_core::keypair::{MinerPublicKey, MinerSecretKey};
 use vrrb_core::{
     claim::{Claim, ClaimError},
     keypair::{MinerPk, MinerSk},
+    transactions::TransactionDigest,
 };
 
 use crate::{block_builder::BlockBuilder, result::MinerError};
@@ -61,7 +68,7 @@ pub enum MinerStatus {
 /// use vrrb_core::keypair::{MinerPk, MinerSk};
 /// use std::sync::{Arc, RwLock};
 /// use bulldag::graph::BullDag;
-/// use primitives::Address;
+/// use primitives::{Address, ChainSpec};
 /// use reward::reward::Reward;
 /// use block::{Block, header::BlockHeader};
 ///
@@ -70,7 +77,8 @@ pub enum MinerStatus {
 ///     pub secret_key: MinerSk,
 ///     pub public_key: MinerPk,
 ///     pub ip_address:SocketAddr,
-///     pub dag: Arc<RwLock<BullDag<Block, String>>>
+///     pub dag: Arc<RwLock<BullDag<Block, String>>>,
+///     pub chain_spec: ChainSpec,
 /// }
 #[derive(Debug)]
 pub struct MinerConfig {
@@ -78,6 +86,7 @@ pub struct MinerConfig {
     pub public_key: MinerPk,
     pub ip_address: SocketAddr,
     pub dag: Arc<RwLock<BullDag<Block, String>>>,
+    pub chain_spec: ChainSpec,
 }
 
 /// Miner struct which exposes methods to mine convergence blocks
@@ -88,7 +97,7 @@ pub struct MinerConfig {
 /// ```
 /// use std::net::SocketAddr;
 /// use vrrb_core::{claim::Claim, keypair::{MinerPk, MinerSk}};
-/// use primitives::Address;
+/// use primitives::{Address, ChainSpec};
 /// use miner::{conflict_resolver::Resolver, block_builder::BlockBuilder, miner::MinerStatus};
 /// use block::{Block, ConvergenceBlock, header::BlockHeader, InnerBlock};
 /// use reward::reward::Reward;
@@ -106,6 +115,7 @@ pub struct MinerConfig {
 ///     pub last_block: Option<Arc<dyn InnerBlock<Header = BlockHeader, RewardType = Reward>>>,
 ///     pub status: MinerStatus,
 ///     pub next_epoch_adjustment: i128,
+///     pub chain_spec: ChainSpec,
 /// }
 #[derive(Debug, Clone)]
 pub struct Miner {
@@ -118,6 +128,7 @@ pub struct Miner {
     pub last_block: Option<Arc<dyn InnerBlock<Header = BlockHeader, RewardType = Reward>>>,
     pub status: MinerStatus,
     pub next_epoch_adjustment: i128,
+    pub chain_spec: ChainSpec,
 }
 
 pub type Result<T> = std::result::Result<T, MinerError>;
@@ -145,7 +156,7 @@ impl Miner {
     ///
     /// use bulldag::graph::BullDag;
     /// use miner::miner::{Miner, MinerConfig};
-    /// use primitives::{Address, NodeId};
+    /// use primitives::{Address, ChainSpec, NodeId};
     /// use vrrb_core::keypair::Keypair;
     ///
     /// let keypair = Keypair::random();
@@ -158,6 +169,7 @@ impl Miner {
     ///     public_key,
     ///     ip_address,
     ///     dag,
+    ///     chain_spec: ChainSpec::default(),
     /// };
     ///
     /// let miner = Miner::new(config, NodeId::default());
@@ -190,6 +202,7 @@ impl Miner {
             last_block: None,
             status: MinerStatus::Waiting,
             next_epoch_adjustment: 0,
+            chain_spec: config.chain_spec,
         })
     }
 
@@ -237,6 +250,11 @@ impl Miner {
         chrono::Utc::now().timestamp() as u128
     }
 
+    /// Returns the reward schedule of the chain this miner mines on
+    pub fn reward_schedule(&self) -> RewardSchedule {
+        RewardSchedule::new(&self.chain_spec)
+    }
+
     /// Get the next_epoch_adjustment
     pub fn next_epoch_adjustment(&self) -> i128 {
         self.next_epoch_adjustment
@@ -286,6 +304,31 @@ impl Miner {
         self.build()
     }
 
+    /// Commits a mined `ConvergenceBlock` to the root of the state trie once
+    /// its txns are applied, signing and hashing its header again
+    pub fn commit_state_root(&self, block: &mut ConvergenceBlock, state_root: String) {
+        block.header.commit_state_root(state_root, &self.secret_key);
+        block.hash = self.hash_block(&block.header);
+    }
+
+    /// Commits a mined `ConvergenceBlock` to the root of the delegation state
+    /// once it is applied, signing and hashing its header again
+    pub fn commit_delegation_root(&self, block: &mut ConvergenceBlock, delegation_root: String) {
+        block
+            .header
+            .commit_delegation_root(delegation_root, &self.secret_key);
+        block.hash = self.hash_block(&block.header);
+    }
+
+    /// Records the slot of the round's miner schedule a mined
+    /// `ConvergenceBlock` takes, signing and hashing its header again
+    pub fn assign_fallback_slot(&self, block: &mut ConvergenceBlock, fallback_slot: u16) {
+        block
+            .header
+            .assign_fallback_slot(fallback_slot, &self.secret_key);
+        block.hash = self.hash_block(&block.header);
+    }
+
     /// This method has been deprecated and will be removed soon
     #[deprecated(note = "Building proposal blocks will be done in Harvester")]
     pub fn mine_proposal_block(
@@ -297,6 +340,7 @@ impl Miner {
         claims: ClaimList,
         from: Claim,
     ) -> ProposalBlock {
+        let txns = canonical_txn_order(txns);
         let payload = create_payload!(round, epoch, txns, claims, from);
         let signature = self.secret_key.sign_ecdsa(payload).to_string();
         let hash = hash_data!(round, epoch, txns, claims, from, signature);
@@ -307,6 +351,8 @@ impl Miner {
             epoch,
             txns,
             claims,
+            inclusion_list: vec![],
+            halt_directives: vec![],
             hash: format!("{hash:x}"),
             from,
             signature,
@@ -318,7 +364,7 @@ impl Miner {
         let claim_list_hash = hash_data!(claim_list);
         let seed = 0;
         let round = 0;
-        let epoch = 0;
+        let epoch = self.chain_spec.genesis_epoch;
 
         let claim = self.generate_claim().unwrap();
 
@@ -329,22 +375,11 @@ impl Miner {
             claim.clone(),
             self.secret_key,
             format!("{claim_list_hash:x}"),
+            &self.reward_schedule(),
+            &self.chain_spec.header_format,
         );
 
-        let block_hash = hash_data!(
-            header.ref_hashes,
-            header.round,
-            header.block_seed,
-            header.next_block_seed,
-            header.block_height,
-            header.timestamp,
-            header.txn_hash,
-            header.miner_claim,
-            header.claim_list_hash,
-            header.block_reward,
-            header.next_block_reward,
-            header.miner_signature
-        );
+        let block_hash = header.hash();
 
         let mut claims = LinkedHashMap::new();
         claims.insert(claim.hash, claim);
@@ -360,7 +395,7 @@ impl Miner {
             header,
             txns,
             claims,
-            hash: format!("{block_hash:x}"),
+            hash: block_hash,
             certificate: None,
         };
 
@@ -369,13 +404,73 @@ impl Miner {
 
     /// Consolidates all the `Txn`s in unreferenced `ProposalBlock`s
     /// into a single list of `proposal_block.hash -> txn.id`
+    ///
+    /// A txn included in more than one proposal block is only kept once,
+    /// under the proposal block with the lowest hash, so every miner
+    /// consolidates the same set of proposals the same way. Txns are listed
+    /// in the canonical order of [block::txn_order].
     pub(crate) fn consolidate_txns(&self, proposals: &[ProposalBlock]) -> ConsolidatedTxns {
-        proposals
-            .iter()
-            .map(|block| {
-                let txn_list = block.txns.iter().map(|(id, _)| id.clone()).collect();
+        let mut owners: HashMap<&TransactionDigest, &RefHash> = HashMap::new();
+
+        proposals.iter().for_each(|block| {
+            block.txns.iter().for_each(|(id, _)| {
+                owners
+                    .entry(id)
+                    .and_modify(|owner| {
+                        if block.hash < **owner {
+                            *owner = &block.hash;
+                        }
+                    })
+                    .or_insert(&block.hash);
+            });
+        });
+
+        canonical_consolidated_txn_order(proposals.iter().map(|block| {
+            let txn_list = block
+                .txns
+                .iter()
+                .filter(|(id, _)| owners.get(id) == Some(&&block.hash))
+                .map(|(id, _)| id.clone())
+                .collect();
+
+            (block.hash.clone(), txn_list)
+        }))
+    }
 
-                (block.hash.clone(), txn_list)
+    /// Drops txns from the consolidated `txns`, in consolidation order, once
+    /// their cumulative weight would exceed the chain's block weight cap, so
+    /// the convergence block stays within it. Dropped txns are not confirmed
+    /// by the block.
+    pub(crate) fn cap_block_weight(
+        &self,
+        txns: ConsolidatedTxns,
+        proposals: &[ProposalBlock],
+    ) -> ConsolidatedTxns {
+        let max_block_weight = self.chain_spec.block_limits.max_block_weight;
+        let mut block_weight: Weight = 0;
+
+        txns.into_iter()
+            .map(|(ref_hash, txn_ids)| {
+                let proposal = proposals.iter().find(|block| block.hash == ref_hash);
+
+                let txn_ids = txn_ids
+                    .into_iter()
+                    .filter(|txn_id| {
+                        let weight = proposal
+                            .and_then(|block| block.txns.get(txn_id))
+                            .map(|txn| txn.txn().weight())
+                            .unwrap_or_default();
+
+                        if block_weight.saturating_add(weight) > max_block_weight {
+                            return false;
+                        }
+
+                        block_weight += weight;
+                        true
+                    })
+                    .collect();
+
+                (ref_hash, txn_ids)
             })
             .collect()
     }
@@ -438,6 +533,7 @@ impl Miner {
         ref_hashes: Vec<RefHash>,
         txns_hash: String,
         claims_hash: String,
+        block_weight: Weight,
     ) -> Option<BlockHeader> {
         if let (Some(block), None) = self.convert_last_block_to_static() {
             return BlockHeader::new(
@@ -448,6 +544,10 @@ impl Miner {
                 txns_hash,
                 claims_hash,
                 self.next_epoch_adjustment,
+                &self.reward_schedule(),
+                &self.chain_spec.header_format,
+                &self.chain_spec.election_difficulty,
+                block_weight,
             );
         }
 
@@ -460,6 +560,10 @@ impl Miner {
                 txns_hash,
                 claims_hash,
                 self.next_epoch_adjustment,
+                &self.reward_schedule(),
+                &self.chain_spec.header_format,
+                &self.chain_spec.election_difficulty,
+                block_weight,
             );
         }
 
@@ -483,22 +587,7 @@ impl Miner {
     /// Hashes the current `ConvergenceBlock` being mined using
     /// the fields from the `BlockHeader`
     pub(crate) fn hash_block(&self, header: &BlockHeader) -> String {
-        let block_hash = hash_data!(
-            header.ref_hashes,
-            header.round,
-            header.block_seed,
-            header.next_block_seed,
-            header.block_height,
-            header.timestamp,
-            header.txn_hash,
-            header.miner_claim,
-            header.claim_list_hash,
-            header.block_reward,
-            header.next_block_reward,
-            header.miner_signature
-        );
-
-        format!("{block_hash:x}")
+        header.hash()
     }
 
     /// Gets the current election `seed` from the
@@ -4,6 +4,7 @@ use std::{
 };
 
 use block::{
+    consolidated_txns_weight,
     header::BlockHeader,
     Block,
     Conflict,
@@ -44,13 +45,20 @@ impl BlockBuilder for Miner {
     fn build(&self) -> Option<Self::BlockType> {
         let proposals = self.get_references();
         if let Some(proposals) = proposals {
-            let resolved = self.resolve(&proposals, self.get_round(), self.get_seed());
+            let mut resolved = self.resolve(&proposals, self.get_round(), self.get_seed());
+
+            // NOTE: proposal blocks are consolidated in canonical order, see
+            // block::txn_order
+            resolved.sort_by(|a, b| a.hash.cmp(&b.hash));
+
             let txns = self.consolidate_txns(&resolved);
+            let txns = self.cap_block_weight(txns, &resolved);
+            let block_weight = consolidated_txns_weight(&txns, &resolved).unwrap_or_default();
             let claims = self.consolidate_claims(&resolved);
             let ref_hashes = self.get_ref_hashes(&resolved);
             let txns_hash = self.get_txn_hash(&txns);
             let claims_hash = self.get_claim_hash(&claims);
-            let header = self.build_header(ref_hashes, txns_hash, claims_hash)?;
+            let header = self.build_header(ref_hashes, txns_hash, claims_hash, block_weight)?;
             let hash = self.hash_block(&header);
 
             Some(ConvergenceBlock {
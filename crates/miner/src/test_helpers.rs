@@ -3,10 +3,10 @@ use std::{
     sync::{Arc, RwLock},
 };
 
-use block::{Block, GenesisBlock, InnerBlock, ProposalBlock};
+use block::{txn_order::canonical_txn_order, Block, GenesisBlock, InnerBlock, ProposalBlock};
 use bulldag::{graph::BullDag, vertex::Vertex};
 use ethereum_types::U256;
-use primitives::{Address, NodeId, PublicKey, SecretKey, Signature};
+use primitives::{Address, ChainSpec, NodeId, PublicKey, SecretKey, Signature};
 use ritelinked::LinkedHashMap;
 use secp256k1::Message;
 use sha2::Digest;
@@ -31,6 +31,7 @@ pub fn create_miner() -> Miner {
         public_key,
         ip_address,
         dag,
+        chain_spec: ChainSpec::default(),
     };
     Miner::new(config, NodeId::default()).unwrap()
 }
@@ -45,6 +46,7 @@ pub fn create_miner_from_keypair(kp: &Keypair) -> Miner {
         ip_address,
         public_key,
         dag,
+        chain_spec: ChainSpec::default(),
     };
     Miner::new(config, NodeId::default()).unwrap()
 }
@@ -148,12 +150,13 @@ pub(crate) fn create_txns(
             txn.token(),
             txn.amount(),
             txn.nonce(),
+            txn.memo(),
         );
 
         let digest = TransactionDigest::from(txn_digest_vec);
         (
             digest,
-            QuorumCertifiedTxn::new(vec![], vec![], txn, vec![], true),
+            QuorumCertifiedTxn::new(vec![], vec![], txn, vec![], None, true),
         )
     })
 }
@@ -220,7 +223,17 @@ pub fn build_single_proposal_block(
 ) -> ProposalBlock {
     let txns = create_txns(n_txns).collect();
     let claims = create_claims(n_claims).collect();
-    ProposalBlock::build(last_block_hash, round, epoch, txns, claims, from, sk)
+    ProposalBlock::build(
+        last_block_hash,
+        round,
+        epoch,
+        txns,
+        claims,
+        vec![],
+        vec![],
+        from,
+        sk,
+    )
 }
 
 /// A helper function to build `n` number of porposal blocks
@@ -365,6 +378,8 @@ pub fn add_genesis_to_dag(dag: &mut MinerDag) -> Option<String> {
             0,
             LinkedHashMap::new(),
             LinkedHashMap::new(),
+            vec![],
+            vec![],
             miner.claim,
             keypair.get_miner_secret_key(),
         );
@@ -482,6 +497,7 @@ pub fn build_single_proposal_block_from_txns(
     );
 
     prop.txns.extend(txns);
+    prop.txns = canonical_txn_order(std::mem::take(&mut prop.txns));
 
     prop
 }
@@ -1,12 +1,18 @@
 use std::net::SocketAddr;
 
-use events::{Event, EventPublisher, EventSubscriber};
+use events::{Event, EventPublisher, EventSubscriber, DEFAULT_BUFFER};
 use mempool::MempoolReadHandleFactory;
 use storage::vrrbdb::VrrbDbReadHandle;
 use telemetry::info;
-use tokio::task::JoinHandle;
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::JoinHandle,
+};
 use vrrb_config::NodeConfig;
-use vrrb_rpc::rpc::{JsonRpcServer, JsonRpcServerConfig};
+use vrrb_rpc::{
+    http::{HttpApiServer, HttpApiServerConfig},
+    rpc::{JsonRpcServer, JsonRpcServerConfig, PublicRpcGuard, RpcCache},
+};
 
 use crate::result::{NodeError, Result};
 
@@ -16,13 +22,42 @@ pub async fn setup_rpc_api_server(
     vrrbdb_read_handle: VrrbDbReadHandle,
     mempool_read_handle_factory: MempoolReadHandleFactory,
     mut jsonrpc_events_rx: EventSubscriber,
+    public_rpc_guard: Option<PublicRpcGuard>,
+    rpc_cache: RpcCache,
 ) -> Result<(JoinHandle<Result<()>>, SocketAddr)> {
+    let (state_diffs_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+    let (backlog_snapshots_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+    let (txn_status_changes_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+    let (validator_duties_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+    let (peers_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+    let (block_range_chunks_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+    let (sync_statuses_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+    let (pending_block_previews_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+    let (mempool_stats_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+    let (dkg_statuses_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+
     let jsonrpc_server_config = JsonRpcServerConfig {
-        address: config.jsonrpc_server_address,
+        address: config
+            .network_address_config
+            .bind_addr(config.jsonrpc_server_address),
         node_type: config.node_type,
         events_tx,
         vrrbdb_read_handle,
         mempool_read_handle_factory,
+        state_diffs_tx: state_diffs_tx.clone(),
+        backlog_snapshots_tx: backlog_snapshots_tx.clone(),
+        txn_status_changes_tx: txn_status_changes_tx.clone(),
+        validator_duties_tx: validator_duties_tx.clone(),
+        peers_tx: peers_tx.clone(),
+        block_range_chunks_tx: block_range_chunks_tx.clone(),
+        sync_statuses_tx: sync_statuses_tx.clone(),
+        pending_block_previews_tx: pending_block_previews_tx.clone(),
+        mempool_stats_tx: mempool_stats_tx.clone(),
+        dkg_statuses_tx: dkg_statuses_tx.clone(),
+        cache: rpc_cache,
+        block_limits: config.chain_spec.block_limits.clone(),
+        txn_limits: config.chain_spec.txn_limits.clone(),
+        public_rpc_guard,
     };
 
     let (jsonrpc_server_handle, resolved_jsonrpc_server_addr) =
@@ -31,12 +66,65 @@ pub async fn setup_rpc_api_server(
             .map_err(|err| NodeError::Other(format!("unable to start JSON-RPC server: {err}")))?;
 
     let jsonrpc_server_handle = tokio::spawn(async move {
-        if let Ok(evt) = jsonrpc_events_rx.recv().await {
-            if let Event::Stop = evt.into() {
-                jsonrpc_server_handle.stop().map_err(|err| {
-                    NodeError::Other(format!("JSON-RPC event has stopped: {err}"))
-                })?;
-                return Ok(());
+        loop {
+            let evt = match jsonrpc_events_rx.recv().await {
+                Ok(evt) => evt,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+
+            match evt.into() {
+                Event::StateDiffApplied(state_diff) => {
+                    // NOTE: sending only fails when there are no balance or replication
+                    // subscribers
+                    let _ = state_diffs_tx.send(state_diff);
+                },
+                Event::BacklogSnapshotCreated(snapshot) => {
+                    // NOTE: sending only fails when no request is waiting on a snapshot
+                    let _ = backlog_snapshots_tx.send(snapshot);
+                },
+                Event::TxnStatusChanged(status_change) => {
+                    // NOTE: sending only fails when there are no txn status subscribers
+                    let _ = txn_status_changes_tx.send(status_change);
+                },
+                Event::ValidatorDutiesComputed(duties) => {
+                    // NOTE: sending only fails when no request is waiting on duties
+                    let _ = validator_duties_tx.send(duties);
+                },
+                Event::PeersListed(peers) => {
+                    // NOTE: sending only fails when no request is waiting on the peers
+                    let _ = peers_tx.send(peers);
+                },
+                Event::BlockRangeChunkCreated(chunk) => {
+                    // NOTE: sending only fails when no subscriber is waiting on the blocks
+                    let _ = block_range_chunks_tx.send(chunk);
+                },
+                Event::SyncStatusReported(status) => {
+                    // NOTE: sending only fails when no request is waiting on the status
+                    let _ = sync_statuses_tx.send(status);
+                },
+                Event::PendingBlockPreviewCreated {
+                    request_id,
+                    preview,
+                } => {
+                    // NOTE: sending only fails when no request is waiting on the preview
+                    let _ = pending_block_previews_tx.send((request_id, preview));
+                },
+                Event::MempoolStatsReported(stats) => {
+                    // NOTE: sending only fails when no request is waiting on the stats
+                    let _ = mempool_stats_tx.send(stats);
+                },
+                Event::DkgStatusReported(status) => {
+                    // NOTE: sending only fails when no request is waiting on the status
+                    let _ = dkg_statuses_tx.send(status);
+                },
+                Event::Stop => {
+                    jsonrpc_server_handle.stop().map_err(|err| {
+                        NodeError::Other(format!("JSON-RPC event has stopped: {err}"))
+                    })?;
+                    return Ok(());
+                },
+                _ => {},
             }
         }
 
@@ -52,3 +140,58 @@ pub async fn setup_rpc_api_server(
 
     Ok((jsonrpc_server_handle, resolved_jsonrpc_server_addr))
 }
+
+/// Starts the read-only REST gateway, serving the same chain data the JSON-RPC
+/// API does to integrators that can't use JSON-RPC
+pub async fn setup_rest_gateway(
+    config: &NodeConfig,
+    vrrbdb_read_handle: VrrbDbReadHandle,
+    mempool_read_handle_factory: MempoolReadHandleFactory,
+    mut events_rx: EventSubscriber,
+) -> Result<(JoinHandle<Result<()>>, SocketAddr)> {
+    let http_api_server_config = HttpApiServerConfig {
+        address: config
+            .network_address_config
+            .bind_addr(config.http_api_address)
+            .to_string(),
+        api_title: config.http_api_title.clone(),
+        api_version: config.http_api_version.clone(),
+        server_timeout: config.http_api_shutdown_timeout,
+        tls_config: None,
+        vrrbdb_read_handle,
+        mempool_read_handle_factory,
+    };
+
+    let http_api_server = HttpApiServer::new(http_api_server_config)
+        .map_err(|err| NodeError::Other(format!("unable to start REST gateway: {err}")))?;
+
+    let resolved_http_api_addr = http_api_server
+        .address()
+        .map_err(|err| NodeError::Other(format!("unable to start REST gateway: {err}")))?;
+
+    let (ctrl_tx, mut ctrl_rx) = broadcast::channel(1);
+
+    let http_api_server_handle = tokio::spawn(async move {
+        let server_handle = tokio::spawn(async move { http_api_server.start(&mut ctrl_rx).await });
+
+        loop {
+            match events_rx.recv().await {
+                Ok(evt) if matches!(evt.into(), Event::Stop) => break,
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+
+        // NOTE: sending only fails when the server already stopped
+        let _ = ctrl_tx.send(Event::Stop);
+
+        server_handle
+            .await
+            .map_err(|err| NodeError::Other(format!("failed to join REST gateway: {err}")))?
+            .map_err(|err| NodeError::Other(format!("REST gateway has stopped: {err}")))
+    });
+
+    info!("REST gateway started at {}", resolved_http_api_addr);
+
+    Ok((http_api_server_handle, resolved_http_api_addr))
+}
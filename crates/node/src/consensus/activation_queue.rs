@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, HashSet};
+
+use primitives::{Epoch, NodeId, StakingParams};
+use validator::claim_validator::ClaimValidator;
+use vrrb_core::transactions::RegisterValidator;
+
+use crate::{NodeError, Result};
+
+/// Certified validator registrations waiting for the epoch in which they
+/// take effect.
+///
+/// Registrations are never activated within the epoch they were certified in,
+/// so every member of a quorum agrees on the validator set for the whole
+/// epoch. They are instead considered by the quorum election of the next one.
+#[derive(Debug, Clone, Default)]
+pub struct ActivationQueue {
+    claim_validator: ClaimValidator,
+    pending: BTreeMap<Epoch, Vec<RegisterValidator>>,
+    queued_nodes: HashSet<NodeId>,
+}
+
+impl ActivationQueue {
+    pub fn new(staking: StakingParams) -> Self {
+        Self {
+            claim_validator: ClaimValidator::new(staking),
+            ..Default::default()
+        }
+    }
+
+    /// Queues a certified registration for activation at the epoch following
+    /// `current_epoch`, returning that epoch. Registrations that don't bond
+    /// enough stake, or for nodes that are already queued, are rejected.
+    pub fn enqueue(
+        &mut self,
+        registration: RegisterValidator,
+        current_epoch: Epoch,
+    ) -> Result<Epoch> {
+        self.claim_validator
+            .validate_registration(&registration)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        if self.queued_nodes.contains(&registration.node_id) {
+            return Err(NodeError::Other(format!(
+                "node {} is already waiting to be activated",
+                registration.node_id
+            )));
+        }
+
+        let activation_epoch = current_epoch + 1;
+
+        self.queued_nodes.insert(registration.node_id.clone());
+        self.pending
+            .entry(activation_epoch)
+            .or_default()
+            .push(registration);
+
+        Ok(activation_epoch)
+    }
+
+    /// Drops the queued registration of `node_id`, if there is one.
+    pub fn cancel(&mut self, node_id: &NodeId) -> Option<RegisterValidator> {
+        if !self.queued_nodes.remove(node_id) {
+            return None;
+        }
+
+        self.pending.values_mut().find_map(|registrations| {
+            let position = registrations
+                .iter()
+                .position(|registration| &registration.node_id == node_id)?;
+
+            Some(registrations.remove(position))
+        })
+    }
+
+    pub fn is_queued(&self, node_id: &NodeId) -> bool {
+        self.queued_nodes.contains(node_id)
+    }
+
+    /// Returns the epoch the queued registration of `node_id` activates at
+    pub fn activation_epoch(&self, node_id: &NodeId) -> Option<Epoch> {
+        if !self.is_queued(node_id) {
+            return None;
+        }
+
+        self.pending.iter().find_map(|(epoch, registrations)| {
+            registrations
+                .iter()
+                .any(|registration| &registration.node_id == node_id)
+                .then_some(*epoch)
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.queued_nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queued_nodes.is_empty()
+    }
+
+    /// Removes and returns every registration due by `epoch`, oldest first.
+    pub fn activate(&mut self, epoch: Epoch) -> Vec<RegisterValidator> {
+        let not_yet_due = self.pending.split_off(&(epoch + 1));
+        let due = std::mem::replace(&mut self.pending, not_yet_due);
+
+        let activated: Vec<RegisterValidator> = due.into_values().flatten().collect();
+
+        for registration in activated.iter() {
+            self.queued_nodes.remove(&registration.node_id);
+        }
+
+        activated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use primitives::DEFAULT_MIN_STAKE_VALIDATOR;
+
+    use super::*;
+    use crate::test_utils::create_register_validator_txn;
+
+    #[test]
+    fn registrations_are_activated_at_the_next_epoch() {
+        let mut queue = ActivationQueue::new(StakingParams::default());
+        let registration =
+            create_register_validator_txn("node-1".into(), DEFAULT_MIN_STAKE_VALIDATOR);
+
+        assert_eq!(queue.enqueue(registration.clone(), 3).unwrap(), 4);
+        assert!(queue.is_queued(&registration.node_id));
+
+        assert!(queue.activate(3).is_empty());
+        assert_eq!(queue.activate(4), vec![registration]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn understaked_and_duplicate_registrations_are_rejected() {
+        let mut queue = ActivationQueue::new(StakingParams::default());
+
+        let understaked =
+            create_register_validator_txn("node-1".into(), DEFAULT_MIN_STAKE_VALIDATOR - 1);
+        assert!(queue.enqueue(understaked, 0).is_err());
+
+        let registration =
+            create_register_validator_txn("node-2".into(), DEFAULT_MIN_STAKE_VALIDATOR);
+        queue.enqueue(registration.clone(), 0).unwrap();
+        assert!(queue.enqueue(registration, 1).is_err());
+
+        assert_eq!(queue.len(), 1);
+    }
+}
@@ -1,10 +1,12 @@
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     sync::{Arc, RwLock},
+    time::Instant,
 };
 
 use block::{
-    header::BlockHeader, Block, BlockHash, Certificate, ConvergenceBlock, ProposalBlock, RefHash,
+    header::BlockHeader, Block, BlockHash, Certificate, ConvergenceBlock, HaltDirective,
+    ProposalBlock, RefHash, SignedHaltDirective,
 };
 use bulldag::node::Node;
 use chrono::Duration;
@@ -14,8 +16,9 @@ use dkg_engine::{
 };
 use ethereum_types::U256;
 use events::{
-    AssignedQuorumMembership, Event, EventMessage, EventPublisher, EventSubscriber, PeerData,
-    SyncPeerData, Vote,
+    AbandonedRound, AssignedQuorumMembership, CertificateShares, DkgBacklog, DkgStatus, Event,
+    EventMessage, EventPublisher, EventSubscriber, InclusionList, PeerData, PendingBlockPreview,
+    RoundAbandonReason, SyncPeerData, ValidatorKeyRotation, Vote,
 };
 use hbbft::{
     crypto::PublicKeyShare,
@@ -27,37 +30,47 @@ use mempool::{TxnRecord, TxnStatus};
 use primitives::{
     ByteSlice, ByteSlice32Bit, ByteSlice48Bit, ByteVec, Epoch, FarmerQuorumThreshold,
     GroupPublicKey, NodeId, NodeIdx, NodeType, NodeTypeBytes, PKShareBytes, PayloadBytes,
-    ProgramExecutionOutput, PublicKeyShareVec, QuorumPublicKey, QuorumThreshold, RawSignature,
-    Round, TxnValidationStatus, ValidatorPublicKey, ValidatorPublicKeyShare, ValidatorSecretKey,
+    ProgramExecutionOutput, PublicKeyShareVec, QuorumPubkey, QuorumPublicKey, QuorumThreshold,
+    RawSignature, Round, SignatureType, TxnValidationStatus, ValidatorPublicKey,
+    ValidatorPublicKeyShare, ValidatorSecretKey, Weight,
 };
+use reward::schedule::RewardSchedule;
 use ritelinked::LinkedHashMap;
 use serde::{Deserialize, Serialize};
-use signer::signer::{SignatureProvider, Signer};
+use signer::{
+    shares::verify_signature_share,
+    signer::{SignatureProvider, Signer},
+};
+use storage::vrrbdb::{CertifiedTxnIndexReadHandle, QuorumRecord, VrrbDbReadHandle};
 use telemetry::error;
 use theater::{Actor, ActorId, ActorState, TheaterError};
-use vrrb_config::{NodeConfig, QuorumMember, QuorumMembershipConfig};
-use vrrb_core::{bloom::Bloom, claim::Claim, keypair::Keypair};
+use vrrb_config::{
+    NodeConfig, QuorumMember, QuorumMembershipConfig, ThresholdConfig, ValidatorSetDiff,
+};
+use vrrb_core::{claim::Claim, keypair::Keypair};
 use vrrb_core::{
     cache::Cache,
-    transactions::{QuorumCertifiedTxn, Transaction, TransactionDigest, TransactionKind},
+    transactions::{
+        ExitValidator, QuorumCertifiedTxn, Transaction, TransactionDigest, TransactionKind,
+    },
 };
 
-use crate::{state_reader::StateReader, NodeError, Result};
+use crate::{state_manager::BondedRegistration, state_reader::StateReader, NodeError, Result};
 
-use super::{QuorumModule, QuorumModuleConfig};
+use super::{
+    election_threshold, eligibility_in_effect, enforce_quorum_safety, DkgProgress,
+    InclusionListTracker, ProposalPriority, QuorumModule, QuorumModuleConfig, ValidatorExit,
+};
 
 pub const PULL_TXN_BATCH_SIZE: usize = 100;
 
-// TODO: Move this to primitives
-pub type QuorumId = String;
-pub type QuorumPubkey = String;
-
 #[derive(Debug)]
 pub struct ConsensusModuleConfig {
     pub keypair: Keypair,
     pub node_config: NodeConfig,
     pub dkg_generator: DkgEngine,
     pub validator_public_key: ValidatorPublicKey,
+    pub certified_txn_index: CertifiedTxnIndexReadHandle,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -96,9 +109,16 @@ pub struct ConsensusModule {
     pub(crate) status: ActorState,
     pub(crate) quorum_certified_txns: Vec<QuorumCertifiedTxn>,
     pub(crate) keypair: Keypair,
-    pub(crate) certified_txns_filter: Bloom,
+    /// Transactions already certified by any quorum, so they are neither
+    /// voted on nor certified again
+    pub(crate) certified_txn_index: CertifiedTxnIndexReadHandle,
+    /// Certified txns the node already put in a proposal block, so they
+    /// aren't proposed again while awaiting a convergence block
+    pub(crate) proposed_txns: HashSet<TransactionDigest>,
     pub(crate) quorum_driver: QuorumModule,
     pub(crate) dkg_engine: DkgEngine,
+    /// Phase the key generation of the node's quorum is in, and since when
+    pub(crate) dkg_progress: DkgProgress,
     pub(crate) node_config: NodeConfig,
 
     // votes_pool: DashMap<(TransactionDigest, String), Vec<Vote>>,
@@ -106,11 +126,32 @@ pub struct ConsensusModule {
     pub(crate) sig_provider: SignatureProvider,
     pub(crate) convergence_block_certificates:
         Cache<BlockHash, HashSet<(NodeIdx, PublicKeyShare, RawSignature)>>,
+    /// Convergence blocks awaiting their certificate, by round, so the shares
+    /// of blocks that will never certify can be dropped
+    pub(crate) pending_convergence_blocks: BTreeMap<Round, HashSet<BlockHash>>,
+    /// Rounds dropped without a certificate that haven't been reported yet
+    pub(crate) abandoned_rounds: Vec<AbandonedRound>,
+    /// Peers that announced they are in maintenance mode and won't mine or
+    /// vote until they announce otherwise
+    pub(crate) paused_peers: HashSet<NodeId>,
+    /// Nonce of the last key rotation applied for every validator, the node
+    /// itself included, so replayed rotations can be told apart
+    pub(crate) key_rotation_nonces: HashMap<NodeId, u64>,
+    /// Last block whose child's miner election no claim won yet, retried as
+    /// the eligibility in effect grows with the time since the block
+    pub(crate) stalled_miner_election: Option<BlockHeader>,
+    /// Certified txns awaiting a block, and those farmers listed as overdue
+    pub(crate) inclusion_lists: InclusionListTracker,
+    /// Partial signatures collected for halt directives that haven't been
+    /// certified yet
+    pub(crate) halt_directive_shares: HashMap<HaltDirective, BTreeMap<NodeIdx, RawSignature>>,
+    /// Certified halt directives the node acted on that no block recorded
+    /// yet, carried in the proposal blocks it mines until one does
+    pub(crate) pending_halt_directives: BTreeMap<Round, SignedHaltDirective>,
     // dag: Arc<RwLock<BullDag<Block, String>>>,
     // sync_jobs_sender: Sender<Job>,
 
     // NOTE: harvester types
-    // pub certified_txns_filter: Bloom,
     // pub votes_pool: DashMap<(TransactionDigest, String), Vec<Vote>>,
     // pub group_public_key: GroupPublicKey,
     // pub sig_provider: Option<SignatureProvider>,
@@ -135,15 +176,25 @@ impl ConsensusModule {
             status: ActorState::Stopped,
             quorum_certified_txns: vec![],
             keypair: cfg.keypair,
-            certified_txns_filter: Bloom::new(10),
+            certified_txn_index: cfg.certified_txn_index,
+            proposed_txns: HashSet::new(),
             quorum_driver: QuorumModule::new(quorum_module_config),
             dkg_engine: cfg.dkg_generator.clone(),
+            dkg_progress: DkgProgress::default(),
             node_config: cfg.node_config.clone(),
             sig_provider: SignatureProvider::new(
                 Arc::new(RwLock::new(cfg.dkg_generator.clone().dkg_state)),
                 cfg.node_config.threshold_config.clone(),
             ),
             convergence_block_certificates: Cache::new(10, 300), // TODO: refactor into constants
+            pending_convergence_blocks: BTreeMap::new(),
+            abandoned_rounds: vec![],
+            paused_peers: HashSet::new(),
+            key_rotation_nonces: HashMap::new(),
+            stalled_miner_election: None,
+            inclusion_lists: InclusionListTracker::default(),
+            halt_directive_shares: HashMap::new(),
+            pending_halt_directives: BTreeMap::new(),
         }
     }
 
@@ -151,6 +202,93 @@ impl ConsensusModule {
         self.keypair.validator_public_key_owned()
     }
 
+    /// Picks the certified txns the node would put in a proposal block at
+    /// `round`, in the order they'd be included
+    fn select_proposal_txns(&self, round: Round) -> Vec<QuorumCertifiedTxn> {
+        // NOTE: only pull the certified txns assigned to this harvester so that
+        // members of the same quorum don't propose the same txns in a round, unless
+        // the harvester they're assigned to left them waiting
+        let partition_params = &self.node_config.chain_spec.txn_partition;
+        let partition = self.quorum_driver.txn_partition(&self.node_config.id);
+
+        // NOTE: txns that would push the proposal past the block weight cap are
+        // skipped and left for a later proposal
+        let max_block_weight = self.node_config.chain_spec.block_limits.max_block_weight;
+        let mut block_weight: Weight = 0;
+
+        // NOTE: txns farmers listed for inclusion go first so they aren't the
+        // ones left out when the proposal fills up, then those that waited too
+        // long, see [ProposalPriority]
+        let fairness = &self.node_config.chain_spec.proposal_fairness;
+        let mut candidates: Vec<&QuorumCertifiedTxn> = self.quorum_certified_txns.iter().collect();
+        candidates.sort_by_cached_key(|txn| {
+            let digest = txn.txn().id();
+
+            ProposalPriority::new(
+                self.inclusion_lists.is_listed(&digest),
+                txn.fee(),
+                self.inclusion_lists.certified_at(&digest),
+                round,
+                fairness,
+            )
+        });
+
+        candidates
+            .into_iter()
+            .filter(|txn| {
+                let digest = txn.txn().id();
+
+                partition.map_or(true, |partition| {
+                    partition.may_propose(
+                        &digest,
+                        self.inclusion_lists.certified_at(&digest),
+                        round,
+                        partition_params,
+                    )
+                })
+            })
+            .filter(|txn| !self.proposed_txns.contains(&txn.txn().id()))
+            .filter(|txn| {
+                let weight = txn.txn().weight();
+
+                if block_weight.saturating_add(weight) > max_block_weight {
+                    return false;
+                }
+
+                block_weight += weight;
+                true
+            })
+            .take(PULL_TXN_BATCH_SIZE)
+            .cloned()
+            .collect()
+    }
+
+    /// Assembles, without mining or committing anything, an outline of the
+    /// proposal block the node would produce at `round`
+    pub fn pending_block_preview(&self, round: Round) -> PendingBlockPreview {
+        let txns = self.select_proposal_txns(round);
+
+        // NOTE: the size covers the txns only, which make up the bulk of a
+        // proposal block
+        let size_bytes = txns
+            .iter()
+            .map(|txn| bincode::serialized_size(txn).unwrap_or_default() as usize)
+            .sum();
+
+        PendingBlockPreview {
+            round,
+            txns: txns.iter().map(|txn| txn.txn().id()).collect(),
+            candidate_txns: self.quorum_certified_txns.len(),
+            weight: txns.iter().fold(0, |weight: Weight, txn| {
+                weight.saturating_add(txn.txn().weight())
+            }),
+            max_block_weight: self.node_config.chain_spec.block_limits.max_block_weight,
+            size_bytes,
+            fee_revenue: txns.iter().map(|txn| txn.fee()).sum(),
+            proposer_fee_revenue: txns.iter().map(|txn| txn.proposer_fee_share()).sum(),
+        }
+    }
+
     async fn mine_proposal_block(
         &mut self,
         ref_hash: RefHash,
@@ -159,7 +297,9 @@ impl ConsensusModule {
         epoch: Epoch,
         claim: Claim,
     ) -> ProposalBlock {
-        let txns = self.quorum_certified_txns.iter().take(PULL_TXN_BATCH_SIZE);
+        let txns = self.select_proposal_txns(round);
+        self.proposed_txns
+            .extend(txns.iter().map(|txn| txn.txn().id()));
 
         // NOTE: Read updated claims
         // let claim_map = self.vrrbdb_read_handle.claim_store_values();
@@ -168,15 +308,12 @@ impl ConsensusModule {
             .map(|claim| (claim.hash, claim.clone()))
             .collect();
 
-        let txns_list: LinkedHashMap<TransactionDigest, QuorumCertifiedTxn> = txns
-            .into_iter()
-            .map(|txn| {
-                if let Err(err) = self.certified_txns_filter.push(&txn.txn().id().to_string()) {
-                    error!("Error pushing txn to certified txns filter: {}", err);
-                }
-                (txn.txn().id(), txn.clone())
-            })
-            .collect();
+        let txns_list: LinkedHashMap<TransactionDigest, QuorumCertifiedTxn> =
+            txns.into_iter().map(|txn| (txn.txn().id(), txn)).collect();
+
+        let inclusion_list = self
+            .inclusion_lists
+            .listed_overdue(round, &self.node_config.chain_spec.inclusion_list);
 
         ProposalBlock::build(
             ref_hash,
@@ -184,6 +321,8 @@ impl ConsensusModule {
             epoch,
             txns_list,
             claim_list,
+            inclusion_list,
+            self.pending_halt_directives.values().cloned().collect(),
             claim,
             self.keypair.get_miner_secret_key(),
         )
@@ -195,6 +334,27 @@ impl ConsensusModule {
         last_block_header: BlockHeader,
         // certificates_share: &HashSet<(NodeIdx, ValidatorPublicKeyShare, RawSignature)>,
     ) -> Result<Certificate> {
+        block
+            .header
+            .verify(&block.hash)
+            .map_err(|err| NodeError::Other(format!("invalid convergence block header: {err}")))?;
+
+        let reward_schedule = RewardSchedule::new(&self.node_config.chain_spec);
+        block
+            .header
+            .verify_rewards(&last_block_header, &reward_schedule)
+            .map_err(|err| NodeError::Other(format!("invalid convergence block rewards: {err}")))?;
+
+        block
+            .header
+            .verify_root_chain(&last_block_header)
+            .map_err(|err| NodeError::Other(format!("invalid convergence block header: {err}")))?;
+
+        // NOTE: the certificate vouches for the transition from the state the
+        // last block left to the one the block commits to
+        let root_hash = last_block_header.state_root.clone();
+        let next_root_hash = block.header.state_root.clone();
+
         self.precheck_convergence_block(block.clone(), last_block_header);
 
         let block = block.clone();
@@ -236,14 +396,157 @@ impl ConsensusModule {
         let certificate = Certificate {
             signature: hex::encode(signature),
             inauguration: None,
-            root_hash: "".to_string(),
-            next_root_hash: "".to_string(),
+            root_hash,
+            next_root_hash,
             block_hash,
         };
 
         Ok(certificate)
     }
 
+    /// Returns the index the node's key shares have within its quorum
+    fn quorum_node_idx(&self) -> Option<NodeIdx> {
+        self.quorum_member_idx(&self.node_config.id)
+    }
+
+    /// Returns the index the key shares of `node_id` have within the node's
+    /// quorum, if it's a member
+    fn quorum_member_idx(&self, node_id: &NodeId) -> Option<NodeIdx> {
+        self.dkg_engine
+            .dkg_state
+            .peer_public_keys()
+            .keys()
+            .position(|member| member == node_id)
+            .map(|idx| idx as NodeIdx)
+    }
+
+    /// Checks a partial signature a peer sent over a convergence block before
+    /// it's cached towards the block's certificate. Fails with
+    /// [NodeError::InvalidSignatureShare] if the share is the peer's fault
+    pub fn verify_peer_signature_share(
+        &self,
+        node_id: &NodeId,
+        block_hash: &BlockHash,
+        public_key_share: &[u8],
+        partial_signature: &[u8],
+    ) -> Result<()> {
+        let invalid = |reason: String| NodeError::InvalidSignatureShare {
+            node_id: node_id.clone(),
+            reason,
+        };
+
+        let public_key_set = self
+            .dkg_engine
+            .dkg_state
+            .public_key_set()
+            .as_ref()
+            .ok_or_else(|| NodeError::Other("quorum public key is missing".to_string()))?;
+
+        let node_idx = self
+            .quorum_member_idx(node_id)
+            .ok_or_else(|| invalid("sender isn't a member of the quorum".to_string()))?;
+
+        let block_hash_bytes = hex::decode(block_hash)
+            .map_err(|err| invalid(format!("invalid block hash {block_hash}: {err}")))?;
+
+        verify_signature_share(
+            public_key_set,
+            node_idx,
+            public_key_share,
+            partial_signature,
+            &block_hash_bytes,
+        )
+        .map_err(|err| invalid(err.to_string()))
+    }
+
+    /// Signs the node's share of a halt directive
+    pub fn sign_halt_directive(
+        &mut self,
+        directive: &HaltDirective,
+    ) -> Result<(NodeIdx, RawSignature)> {
+        let node_idx = self.quorum_node_idx().ok_or_else(|| {
+            NodeError::Other("node isn't a member of a quorum with keysets".to_string())
+        })?;
+
+        let signature = self
+            .sig_provider
+            .generate_partial_signature(directive.payload_hash())
+            .map_err(|err| NodeError::Other(format!("failed to sign halt directive: {err}")))?;
+
+        Ok((node_idx, signature))
+    }
+
+    /// Collects a quorum member's share of a halt directive. Returns the
+    /// certified directive once more than the threshold of members signed it
+    pub fn handle_halt_directive_signed(
+        &mut self,
+        directive: HaltDirective,
+        node_idx: NodeIdx,
+        signature: RawSignature,
+    ) -> Result<Option<SignedHaltDirective>> {
+        let is_valid = self
+            .sig_provider
+            .verify_signature(
+                node_idx,
+                directive.payload_hash(),
+                signature.clone(),
+                SignatureType::PartialSignature,
+            )
+            .map_err(|err| NodeError::Other(format!("invalid halt directive share: {err}")))?;
+
+        if !is_valid {
+            return Err(NodeError::Other(format!(
+                "share of node {node_idx} doesn't sign the halt directive"
+            )));
+        }
+
+        let quorum_threshold = self.node_config.threshold_config.threshold;
+
+        let shares = self
+            .halt_directive_shares
+            .entry(directive.clone())
+            .or_default();
+        shares.insert(node_idx, signature);
+
+        if shares.len() as u16 <= quorum_threshold {
+            return Ok(None);
+        }
+
+        let shares = self
+            .halt_directive_shares
+            .remove(&directive)
+            .unwrap_or_default();
+
+        let signature = self
+            .sig_provider
+            .generate_quorum_signature(quorum_threshold, shares)
+            .map_err(|err| NodeError::Other(format!("failed to certify halt directive: {err}")))?;
+
+        let quorum_public_key = self
+            .quorum_pubkey()
+            .ok_or_else(|| NodeError::Other("quorum public key is missing".to_string()))?;
+
+        Ok(Some(SignedHaltDirective {
+            directive,
+            quorum_public_key,
+            signature: hex::encode(signature),
+        }))
+    }
+
+    /// Queues a certified halt directive to be carried in the proposal blocks
+    /// the node mines, so the chain records it
+    pub fn queue_halt_directive(&mut self, signed: SignedHaltDirective) {
+        self.pending_halt_directives
+            .insert(signed.directive.round, signed);
+    }
+
+    /// Stops carrying the halt directives issued at or before `round`, once
+    /// the chain recorded a directive issued then
+    pub fn forget_recorded_halt_directives(&mut self, round: Round) {
+        self.pending_halt_directives
+            .retain(|issued_at, _| *issued_at > round);
+    }
+
     // The above code is handling an event of type `Vote` in a Rust
     // program. It checks the integrity of the vote by
     // verifying that it comes from the actual voter and prevents
@@ -253,6 +556,11 @@ impl ConsensusModule {
     // quorum threshold, it sends a job to certify the transaction
     // using the provided signature provider.
     pub fn validate_vote(&self, vote: Vote, farmer_quorum_threshold: FarmerQuorumThreshold) {
+        if self.certified_txn_index.is_certified(&vote.txn.id()) {
+            telemetry::debug!("ignoring vote on already certified txn {}", vote.txn.id());
+            return;
+        }
+
         // TODO: Harvester quorum nodes should check the integrity of the vote by verifying the vote does
         // come from the alleged voter Node.
         //
@@ -263,10 +571,11 @@ impl ConsensusModule {
         //             .get_mut(&(vote.txn.id(), farmer_quorum_key.clone()))
         //         {
         //             let txn_id = vote.txn.id();
-        //             if !self
-        //                 .certified_txns_filter
-        //                 .contains(&(txn_id.clone(),
-        // farmer_quorum_key.clone()))             {
+        //             let key = CertifiedTxnKey::new(
+        //                 txn_id.clone(),
+        //                 vote.quorum_public_key.clone(),
+        //             );
+        //             if !self.certified_txn_index.contains(&key) {
         //                 votes.push(vote.clone());
         //                 if votes.len() >= farmer_quorum_threshold {
         //                     let _ =
@@ -596,7 +905,7 @@ impl ConsensusModule {
             NodeError::Other(err_msg)
         })?;
 
-        let threshold = quorum_membership_config.quorum_members().len() / 2;
+        let threshold = self.dkg_threshold_for(&quorum_membership_config)?;
 
         // NOTE: add this node's own validator key to participate in DKG, otherwise they're considered
         // an observer and no part message is generated
@@ -636,6 +945,10 @@ impl ConsensusModule {
         &mut self,
         peer_data: PeerData,
     ) -> Result<Option<HashMap<NodeId, AssignedQuorumMembership>>> {
+        self.quorum_driver
+            .known_peers
+            .insert(peer_data.node_id.clone(), peer_data.clone());
+
         if let Some(quorum_config) = self.quorum_driver.bootstrap_quorum_config.clone() {
             let node_id = peer_data.node_id.clone();
 
@@ -675,11 +988,6 @@ impl ConsensusModule {
             }
         }
 
-        self.add_peer_public_key_to_dkg_state(
-            peer_data.node_id.clone(),
-            peer_data.validator_public_key,
-        );
-
         Ok(None)
     }
 
@@ -706,6 +1014,9 @@ impl ConsensusModule {
             )));
         }
 
+        self.validate_quorum_keyset(&assigned_membership)?;
+
+        let keyset = assigned_membership.keyset.members.clone();
         let quorum_kind = assigned_membership.quorum_kind.clone();
         let quorum_membership_config = QuorumMembershipConfig {
             quorum_members: assigned_membership
@@ -730,7 +1041,94 @@ impl ConsensusModule {
             quorum_kind,
         };
 
+        self.dkg_threshold_for(&quorum_membership_config)?;
+
         self.quorum_driver.membership_config = Some(quorum_membership_config);
+
+        // NOTE: the whole keyset is installed at once so parts are generated for every
+        // quorum member, regardless of the order peers were discovered in
+        self.dkg_engine.dkg_state.set_peer_public_keys(keyset);
+        self.dkg_progress.reset(Instant::now());
+
+        Ok(())
+    }
+
+    /// Computes the DKG threshold for a quorum from the node's
+    /// `ThresholdConfig`, checking the quorum's size against the configured
+    /// bounds. Quorum members exclude this node, so DKG runs with one more
+    /// participant than that. A part is only complete once it gathers more
+    /// than `2 * threshold` acks, so a threshold of `t` needs at least `2t + 1`
+    /// participants, and the threshold must keep the quorum safe and live,
+    /// see [enforce_quorum_safety].
+    fn dkg_threshold_for(&self, membership_config: &QuorumMembershipConfig) -> Result<usize> {
+        let ThresholdConfig {
+            threshold,
+            upper_bound,
+        } = self.node_config.threshold_config.clone();
+
+        let participants = membership_config.quorum_members().len() + 1;
+        let required_participants = 2 * threshold as usize + 1;
+
+        let reason = if participants > upper_bound as usize {
+            format!("quorum exceeds the configured upper bound of {upper_bound} participants")
+        } else if participants < required_participants {
+            format!(
+                "a threshold of {threshold} needs at least {required_participants} participants"
+            )
+        } else {
+            enforce_quorum_safety(
+                &self.node_config.threshold_config,
+                participants,
+                self.node_config.quorum_safety_mode,
+            )?;
+
+            return Ok(threshold as usize);
+        };
+
+        let err = NodeError::DkgConfigMismatch {
+            quorum_kind: membership_config.quorum_kind.clone(),
+            participants,
+            threshold,
+            upper_bound,
+            reason,
+        };
+
+        error!("{} cannot run DKG: {err}", &self.node_config.id);
+
+        Err(err)
+    }
+
+    /// Checks the keyset announced alongside a quorum assignment matches the
+    /// assigned quorum: it must hold this node's own key and exactly the keys
+    /// of its assigned peers.
+    fn validate_quorum_keyset(&self, assigned_membership: &AssignedQuorumMembership) -> Result<()> {
+        let keyset = &assigned_membership.keyset;
+
+        if keyset.quorum_kind != assigned_membership.quorum_kind {
+            return Err(NodeError::Other(format!(
+                "keyset announced for a {} quorum but node was assigned to a {} quorum",
+                keyset.quorum_kind, assigned_membership.quorum_kind
+            )));
+        }
+
+        if keyset.members.get(&self.node_config.id) != Some(&self.validator_public_key_owned()) {
+            return Err(NodeError::Other(format!(
+                "keyset announced for quorum does not hold the key of {}",
+                &self.node_config.id
+            )));
+        }
+
+        let peers_match = assigned_membership
+            .peers
+            .iter()
+            .all(|peer| keyset.members.get(&peer.node_id) == Some(&peer.validator_public_key));
+
+        if !peers_match || keyset.members.len() != assigned_membership.peers.len() + 1 {
+            return Err(NodeError::Other(
+                "keyset announced for quorum does not match its members".to_string(),
+            ));
+        }
+
         Ok(())
     }
 
@@ -844,10 +1242,6 @@ impl ConsensusModule {
         //         certificate,
         //         is_txn_valid,
         //     ));
-        //
-        //     let _ = self
-        //         .certified_txns_filter
-        //         .push(&(txn_id, farmer_quorum_key));
         // }
     }
 
@@ -873,8 +1267,12 @@ impl ConsensusModule {
             .or_insert_with(|| part);
 
         self.dkg_engine
-            .ack_partial_commitment(sender_id)
-            .map_err(|err| NodeError::Other(err.to_string()))
+            .ack_partial_commitment(sender_id.clone())
+            .map_err(|err| {
+                self.dkg_progress.record_rejected_part(&sender_id);
+
+                NodeError::Other(err.to_string())
+            })
     }
 
     pub fn handle_part_commitment_acknowledged(
@@ -903,7 +1301,39 @@ impl ConsensusModule {
             .map_err(|err| NodeError::Other(err.to_string()))
     }
 
-    pub fn handle_quorum_election_started(&mut self, header: BlockHeader) {
+    /// Returns the threshold public key of the node's quorum, once its keysets
+    /// have been generated
+    pub fn quorum_pubkey(&self) -> Option<QuorumPubkey> {
+        self.dkg_engine
+            .dkg_state
+            .public_key_set()
+            .as_ref()
+            .map(|public_key_set| QuorumPubkey::from(public_key_set.public_key()))
+    }
+
+    /// Describes the node's quorum for the current epoch, once its keysets
+    /// have been generated
+    pub fn quorum_record(&self) -> Option<QuorumRecord> {
+        let membership_config = self.quorum_driver.membership_config.as_ref()?;
+        let group_public_key = self.quorum_pubkey()?;
+
+        Some(QuorumRecord {
+            epoch: self.quorum_driver.current_epoch,
+            quorum_kind: membership_config.quorum_kind.clone(),
+            members: self.dkg_engine.dkg_state.peer_public_keys_owned(),
+            group_public_key,
+            threshold: self.node_config.threshold_config.threshold,
+            inauguration: None,
+        })
+    }
+
+    /// Moves the node's quorum into the epoch of the given block, activating
+    /// any validators whose registration is due. Returns the membership
+    /// changes that should be propagated to peers.
+    pub fn handle_quorum_election_started(
+        &mut self,
+        header: BlockHeader,
+    ) -> Option<ValidatorSetDiff> {
         //     let claims = self.vrrbdb_read_handle.claim_store_values();
         //
         //     if let Ok(quorum) = self.elect_quorum(claims, header) {
@@ -915,19 +1345,447 @@ impl ConsensusModule {
         //             telemetry::error!("{}", err);
         //         }
         //     }
+        let epoch = self.node_config.chain_spec.epoch_at(header.block_height);
+
+        self.quorum_driver.rotate_validator_set(epoch)
+    }
+
+    /// Queues a validator registration once a certified block bonded its
+    /// stake.
+    pub fn handle_validator_registration_bonded(
+        &mut self,
+        bonded: BondedRegistration,
+    ) -> Result<Epoch> {
+        self.quorum_driver
+            .queue_validator_registration(bonded.registration, bonded.claim.ip_address)
+    }
+
+    /// Schedules a validator's exit once it has been certified by a farmer
+    /// quorum. `claim` is the claim of the exiting validator.
+    pub fn handle_validator_exit_certified(
+        &mut self,
+        exit: ExitValidator,
+        claim: &Claim,
+    ) -> Result<ValidatorExit> {
+        self.quorum_driver.queue_validator_exit(&exit, claim)
+    }
+
+    /// Checks a peer's validator set changes against the ones this node
+    /// derived from the blocks it applied. Peers' diffs are never applied,
+    /// every node rotates its validator set on its own, see
+    /// [QuorumModule::rotate_validator_set]
+    pub fn handle_validator_set_update_received(&mut self, diff: ValidatorSetDiff) {
+        if !self.quorum_driver.agrees_with(&diff) {
+            telemetry::warn!(
+                "Peer's validator set for epoch {} disagrees with the local one: {} joined, {} left",
+                diff.epoch,
+                diff.added.len(),
+                diff.removed.len()
+            );
+        }
+    }
+
+    pub fn handle_peer_maintenance_mode_changed(&mut self, node_id: NodeId, paused: bool) {
+        if paused {
+            telemetry::info!("Peer {node_id} entered maintenance mode");
+            self.paused_peers.insert(node_id);
+        } else if self.paused_peers.remove(&node_id) {
+            telemetry::info!("Peer {node_id} left maintenance mode");
+        }
+    }
+
+    /// Returns whether a peer announced it is in maintenance mode
+    pub fn is_peer_paused(&self, node_id: &NodeId) -> bool {
+        self.paused_peers.contains(node_id)
+    }
+
+    /// Moves this node to a new validator key, returning the announcement its
+    /// peers need to pick the new key up. The current keyset was generated
+    /// with the old key, so the new one is only used from the next DKG round
+    /// on.
+    pub fn rotate_validator_key(
+        &mut self,
+        new_secret_key: ValidatorSecretKey,
+    ) -> ValidatorKeyRotation {
+        let node_id = self.node_config.id.clone();
+
+        // NOTE: the nonce follows the clock so it keeps growing across restarts,
+        // which forget the nonce of the last rotation
+        let last_nonce = self.key_rotation_nonces.get(&node_id).copied();
+        let nonce = last_nonce
+            .map_or(0, |nonce| nonce + 1)
+            .max(chrono::Utc::now().timestamp_millis() as u64);
+        self.key_rotation_nonces.insert(node_id.clone(), nonce);
+
+        let rotation = ValidatorKeyRotation::new(
+            node_id,
+            self.quorum_driver.current_epoch,
+            nonce,
+            self.keypair.get_validator_secret_key(),
+            &new_secret_key,
+        );
+
+        let validator_kp = (new_secret_key.clone(), rotation.new_public_key);
+        self.keypair.validator_kp = validator_kp.clone();
+        self.node_config.keypair.validator_kp = validator_kp;
+        self.dkg_engine.secret_key = new_secret_key;
+
+        rotation
+    }
+
+    /// Swaps the key a peer is known by for the one it rotated to. Returns
+    /// false if the rotation was already applied. Rotations announced before
+    /// the previous epoch, or numbered below the last one applied for the
+    /// peer, are rejected as stale.
+    pub fn handle_peer_validator_key_rotated(
+        &mut self,
+        rotation: ValidatorKeyRotation,
+    ) -> Result<bool> {
+        let node_id = rotation.node_id.clone();
+
+        if !rotation.verify() {
+            return Err(NodeError::Other(format!(
+                "key rotation of {node_id} isn't signed by both its old and new key"
+            )));
+        }
+
+        let current_epoch = self.quorum_driver.current_epoch;
+        if rotation.epoch < current_epoch.saturating_sub(1) {
+            return Err(NodeError::Other(format!(
+                "key rotation {} of {node_id} was announced in epoch {}, before the previous one",
+                rotation.nonce, rotation.epoch
+            )));
+        }
+
+        match self.key_rotation_nonces.get(&node_id) {
+            Some(last_nonce) if rotation.nonce == *last_nonce => return Ok(false),
+            Some(last_nonce) if rotation.nonce < *last_nonce => {
+                return Err(NodeError::Other(format!(
+                    "key rotation {} of {node_id} predates its last applied rotation {last_nonce}",
+                    rotation.nonce
+                )));
+            },
+            _ => {},
+        }
+
+        let known_key = self
+            .dkg_engine
+            .dkg_state
+            .peer_public_keys()
+            .get(&node_id)
+            .cloned()
+            .or_else(|| {
+                self.quorum_driver
+                    .known_peers
+                    .get(&node_id)
+                    .map(|peer| peer.validator_public_key)
+            });
+
+        match known_key {
+            Some(key) if key == rotation.new_public_key => return Ok(false),
+            Some(key) if key != rotation.old_public_key => {
+                return Err(NodeError::Other(format!(
+                    "key rotation of {node_id} starts from a key it isn't known by"
+                )));
+            },
+            _ => {},
+        }
+
+        let new_public_key = rotation.new_public_key;
+
+        self.key_rotation_nonces
+            .insert(node_id.clone(), rotation.nonce);
+
+        if let Some(key) = self
+            .dkg_engine
+            .dkg_state
+            .peer_public_keys_mut()
+            .get_mut(&node_id)
+        {
+            *key = new_public_key;
+        }
+
+        // NOTE: the signature provider verifies shares off its own copy of the
+        // DKG state
+        if let Ok(mut dkg_state) = self.sig_provider.dkg_state.write() {
+            if let Some(key) = dkg_state.peer_public_keys_mut().get_mut(&node_id) {
+                *key = new_public_key;
+            }
+        }
+
+        if let Some(peer) = self.quorum_driver.known_peers.get_mut(&node_id) {
+            peer.validator_public_key = new_public_key;
+        }
+
+        if let Some((peer, _)) = self
+            .quorum_driver
+            .bootstrap_quorum_available_nodes
+            .get_mut(&node_id)
+        {
+            peer.validator_public_key = new_public_key;
+        }
+
+        if let Some(member) = self
+            .quorum_driver
+            .membership_config
+            .as_mut()
+            .and_then(|membership| membership.quorum_members.get_mut(&node_id))
+        {
+            member.validator_public_key = new_public_key;
+        }
+
+        telemetry::info!("Peer {node_id} rotated its validator key");
+
+        Ok(true)
+    }
+
+    /// Returns how many signature shares were gathered towards the
+    /// certificate of every convergence block still held in the cache
+    pub fn certificate_shares(&self) -> Vec<CertificateShares> {
+        let mut shares: Vec<CertificateShares> = self
+            .convergence_block_certificates
+            .peek_iter()
+            .map(|(block_hash, shares)| CertificateShares {
+                block_hash: block_hash.clone(),
+                share_count: shares.len(),
+            })
+            .collect();
+
+        shares.sort_by(|a, b| a.block_hash.cmp(&b.block_hash));
+        shares
+    }
+
+    /// Remembers a txn certified in `round` as awaiting a block
+    pub fn record_certified_txn(&mut self, digest: TransactionDigest, round: Round) {
+        self.inclusion_lists.record_certified(digest, round);
+    }
+
+    /// Builds the inclusion list this node attaches to the votes it casts in
+    /// `round`
+    pub fn inclusion_list(&self, round: Round) -> InclusionList {
+        self.inclusion_lists
+            .inclusion_list(round, &self.node_config.chain_spec.inclusion_list)
+    }
+
+    /// Keeps the entries of the inclusion list a farmer attached to its vote
+    pub fn record_inclusion_list(&mut self, vote: &Vote) {
+        self.inclusion_lists.record_inclusion_list(
+            &vote.inclusion_list,
+            &self.node_config.chain_spec.inclusion_list,
+        );
+    }
+
+    /// Forgets txns once a convergence block included them, so farmers stop
+    /// listing them and the node stops proposing them
+    pub fn record_included_txns<'a>(
+        &mut self,
+        digests: impl IntoIterator<Item = &'a TransactionDigest>,
+    ) {
+        let included: HashSet<&TransactionDigest> = digests.into_iter().collect();
+
+        self.quorum_certified_txns
+            .retain(|txn| !included.contains(&txn.txn().id()));
+        self.proposed_txns
+            .retain(|digest| !included.contains(digest));
+        self.inclusion_lists.record_included(included);
+    }
+
+    /// Starts tracking a convergence block awaiting its certificate. Rounds
+    /// that fall more than `max_round_age` rounds behind it are abandoned.
+    pub fn track_convergence_block(&mut self, round: Round, block_hash: BlockHash) {
+        self.pending_convergence_blocks
+            .entry(round)
+            .or_default()
+            .insert(block_hash);
+
+        let latest_round = self
+            .pending_convergence_blocks
+            .keys()
+            .next_back()
+            .copied()
+            .unwrap_or(round);
+
+        let max_round_age = self.node_config.round_gc_config.max_round_age;
+        let expired_rounds = self
+            .pending_convergence_blocks
+            .range(..latest_round.saturating_sub(max_round_age))
+            .map(|(round, _)| *round)
+            .collect::<Vec<Round>>();
+
+        for round in expired_rounds {
+            self.abandon_round(round, RoundAbandonReason::Expired { latest_round });
+        }
+    }
+
+    /// Drops the certificate shares of a convergence block once it has been
+    /// certified, along with those of every other block of the same or
+    /// earlier rounds. Earlier rounds are reported as abandoned.
+    pub fn handle_convergence_block_finalized(&mut self, block_hash: &BlockHash) {
+        self.convergence_block_certificates.remove(block_hash);
+
+        let certified_round = self
+            .pending_convergence_blocks
+            .iter()
+            .find(|(_, block_hashes)| block_hashes.contains(block_hash))
+            .map(|(round, _)| *round);
+
+        let Some(certified_round) = certified_round else {
+            return;
+        };
+
+        if let Some(block_hashes) = self.pending_convergence_blocks.remove(&certified_round) {
+            for block_hash in block_hashes {
+                self.convergence_block_certificates.remove(&block_hash);
+            }
+        }
+
+        let superseded_rounds = self
+            .pending_convergence_blocks
+            .range(..certified_round)
+            .map(|(round, _)| *round)
+            .collect::<Vec<Round>>();
+
+        for round in superseded_rounds {
+            self.abandon_round(round, RoundAbandonReason::Superseded { certified_round });
+        }
+    }
+
+    fn abandon_round(&mut self, round: Round, reason: RoundAbandonReason) {
+        let Some(block_hashes) = self.pending_convergence_blocks.remove(&round) else {
+            return;
+        };
+
+        let mut blocks = block_hashes
+            .into_iter()
+            .map(|block_hash| {
+                let share_count = self
+                    .convergence_block_certificates
+                    .peek(&block_hash)
+                    .map(|shares| shares.len())
+                    .unwrap_or_default();
+
+                self.convergence_block_certificates.remove(&block_hash);
+
+                CertificateShares {
+                    block_hash,
+                    share_count,
+                }
+            })
+            .collect::<Vec<CertificateShares>>();
+
+        blocks.sort_by(|a, b| a.block_hash.cmp(&b.block_hash));
+
+        telemetry::warn!(
+            "Abandoning round {} with {} uncertified convergence blocks: {:?}",
+            round,
+            blocks.len(),
+            reason
+        );
+
+        self.abandoned_rounds.push(AbandonedRound {
+            round,
+            reason,
+            blocks,
+        });
+    }
+
+    /// Takes the rounds abandoned since the last call, so they can be
+    /// reported
+    pub fn drain_abandoned_rounds(&mut self) -> Vec<AbandonedRound> {
+        std::mem::take(&mut self.abandoned_rounds)
+    }
+
+    /// Returns how many messages the DKG engine holds for the key generation
+    /// in progress
+    pub fn dkg_backlog(&self) -> DkgBacklog {
+        let dkg_state = &self.dkg_engine.dkg_state;
+
+        DkgBacklog {
+            part_messages: dkg_state.part_message_store().len(),
+            ack_messages: dkg_state.ack_message_store().len(),
+            peer_public_keys: dkg_state.peer_public_keys().len(),
+            has_public_key_set: dkg_state.public_key_set().is_some(),
+            has_secret_key_share: dkg_state.secret_key_share().is_some(),
+        }
+    }
+
+    /// Reports how far the key generation of the node's quorum got
+    pub fn dkg_status(&self) -> DkgStatus {
+        self.dkg_progress.status(
+            &self.node_config.id,
+            self.quorum_driver.current_epoch,
+            &self.dkg_engine.dkg_state,
+            self.current_dkg_threshold(),
+            Instant::now(),
+        )
+    }
+
+    /// Checks whether the key generation moved on to another phase since the
+    /// last call, returning its status if it did
+    pub fn observe_dkg_progress(&mut self) -> Option<DkgStatus> {
+        let status = self.dkg_status();
+
+        self.dkg_progress
+            .advance(&status, Instant::now())
+            .then_some(status)
+    }
+
+    /// DKG threshold of the node's quorum, zero while it belongs to none or
+    /// its size doesn't allow running DKG
+    fn current_dkg_threshold(&self) -> usize {
+        self.quorum_driver
+            .membership_config
+            .as_ref()
+            .and_then(|membership_config| self.dkg_threshold_for(membership_config).ok())
+            .unwrap_or_default()
     }
 
     pub fn handle_miner_election_started(
         &mut self,
         header: BlockHeader,
-        claims: HashMap<String, Claim>,
+        claims: VrrbDbReadHandle,
     ) -> Result<(U256, Claim)> {
-        let mut election_results: BTreeMap<U256, Claim> =
-            self.quorum_driver.elect_miner(claims, header.block_seed);
+        self.stalled_miner_election = None;
+
+        let (result, claim) = self
+            .quorum_driver
+            .elect_miner(&claims, header.block_seed)
+            .ok_or(NodeError::Other(format!(
+                "no miner eligible claims to elect from for seed {}",
+                header.block_seed
+            )))?;
+
+        let elapsed_secs = chrono::Utc::now().timestamp() - header.timestamp;
+        let eligibility = eligibility_in_effect(
+            &header,
+            elapsed_secs,
+            &self.node_config.chain_spec.election_difficulty,
+        );
 
-        let winner = self.quorum_driver.get_winner(&mut election_results);
+        // NOTE: rounds in which no claim falls below the threshold produce no
+        // block, which is what stretches the block time of large networks.
+        // They are retried once the eligibility in effect grew
+        if result > election_threshold(eligibility) {
+            let seed = header.block_seed;
+            self.stalled_miner_election = Some(header);
 
-        Ok(winner)
+            return Err(NodeError::Other(format!(
+                "no claim met the election threshold for seed {seed} at an eligibility of {eligibility} ppm"
+            )));
+        }
+
+        Ok((result, claim))
+    }
+
+    /// Runs the miner election of the last stalled round again, if there is
+    /// one
+    pub fn retry_stalled_miner_election(
+        &mut self,
+        claims: VrrbDbReadHandle,
+    ) -> Option<Result<(U256, Claim)>> {
+        let header = self.stalled_miner_election.take()?;
+
+        Some(self.handle_miner_election_started(header, claims))
     }
 
     pub fn handle_txns_ready_for_processing(&mut self, txns: Vec<TransactionKind>) {
@@ -1060,6 +1918,8 @@ impl ConsensusModule {
         block: ConvergenceBlock,
         last_confirmed_block_header: BlockHeader,
     ) {
+        self.track_convergence_block(block.header.round, block.hash.clone());
+
         let claims = block.claims.clone();
         let txns = block.txns.clone();
         let proposal_block_hashes = block.header.ref_hashes.clone();
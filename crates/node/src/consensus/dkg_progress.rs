@@ -0,0 +1,246 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::Instant,
+};
+
+use dkg_engine::prelude::DkgState;
+use events::{DkgPhase, DkgStatus};
+use primitives::{Epoch, NodeId};
+
+/// Follows the key generation of the node's quorum from phase to phase,
+/// timing how long it spends in each.
+#[derive(Debug, Clone)]
+pub struct DkgProgress {
+    phase: DkgPhase,
+    phase_started_at: Instant,
+
+    /// Members whose part commitment couldn't be acknowledged
+    rejected_parts: BTreeSet<NodeId>,
+}
+
+impl Default for DkgProgress {
+    fn default() -> Self {
+        Self {
+            phase: DkgPhase::Idle,
+            phase_started_at: Instant::now(),
+            rejected_parts: BTreeSet::new(),
+        }
+    }
+}
+
+impl DkgProgress {
+    /// Records that the part commitment of `node_id` was rejected
+    pub fn record_rejected_part(&mut self, node_id: &NodeId) {
+        self.rejected_parts.insert(node_id.clone());
+    }
+
+    /// Starts following a new key generation as of `now`
+    pub fn reset(&mut self, now: Instant) {
+        self.phase = DkgPhase::Idle;
+        self.phase_started_at = now;
+        self.rejected_parts.clear();
+    }
+
+    /// Works out how far the key generation held by `dkg_state` got as of
+    /// `now`, given the DKG `threshold` of the quorum.
+    ///
+    /// Acks are keyed by the member that sent them, then by the owner of the
+    /// part they acknowledge. A part is complete once more than `2 *
+    /// threshold` members acked it, and keys can be generated once more than
+    /// `threshold` parts are complete
+    pub fn status(
+        &self,
+        node_id: &NodeId,
+        epoch: Epoch,
+        dkg_state: &DkgState,
+        threshold: usize,
+        now: Instant,
+    ) -> DkgStatus {
+        let participants = dkg_state.peer_public_keys();
+        let parts = dkg_state.part_message_store();
+
+        let mut acks_sent: HashMap<&NodeId, usize> = HashMap::new();
+        let mut acks_received: HashMap<&NodeId, usize> = HashMap::new();
+
+        for (acker, owner) in dkg_state.ack_message_store().keys() {
+            if participants.contains_key(acker) && participants.contains_key(owner) {
+                *acks_sent.entry(acker).or_default() += 1;
+                *acks_received.entry(owner).or_default() += 1;
+            }
+        }
+
+        let parts_received = participants
+            .keys()
+            .filter(|member| parts.contains_key(*member))
+            .count();
+
+        let complete_parts = acks_received
+            .values()
+            .filter(|acks| **acks > 2 * threshold)
+            .count();
+
+        let phase = if dkg_state.public_key_set().is_some() {
+            DkgPhase::Completed
+        } else if participants.is_empty() {
+            DkgPhase::Idle
+        } else if complete_parts > threshold {
+            DkgPhase::ThresholdReached
+        } else if parts_received == participants.len() {
+            DkgPhase::CollectingAcks
+        } else {
+            DkgPhase::CollectingParts
+        };
+
+        let mut failing_peers = self.rejected_parts.clone();
+
+        for member in participants.keys() {
+            let lagging = match phase {
+                DkgPhase::CollectingParts => !parts.contains_key(member),
+                DkgPhase::CollectingAcks => {
+                    acks_sent.get(member).copied().unwrap_or_default() < participants.len()
+                },
+                _ => false,
+            };
+
+            if lagging && member != node_id {
+                failing_peers.insert(member.clone());
+            }
+        }
+
+        let secs_in_phase = if phase == self.phase {
+            now.saturating_duration_since(self.phase_started_at)
+                .as_secs()
+        } else {
+            0
+        };
+
+        DkgStatus {
+            node_id: node_id.clone(),
+            epoch,
+            phase,
+            threshold,
+            participants: participants.len(),
+            parts_received,
+            parts_expected: participants.len(),
+            acks_received: acks_sent.values().sum(),
+            acks_expected: participants.len() * participants.len(),
+            complete_parts,
+            secs_in_phase,
+            failing_peers: failing_peers.into_iter().collect(),
+        }
+    }
+
+    /// Moves on to the phase `status` reports the key generation at, if it
+    /// isn't there already. Returns whether it moved
+    pub fn advance(&mut self, status: &DkgStatus, now: Instant) -> bool {
+        if status.phase == self.phase {
+            return false;
+        }
+
+        self.phase = status.phase;
+        self.phase_started_at = now;
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use dkg_engine::{prelude::DkgGenerator, test_utils::generate_dkg_engines};
+    use primitives::NodeType;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn key_generation_is_followed_through_its_phases() {
+        let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
+        let node_id = dkg_engines[0].node_id();
+        let start = Instant::now();
+
+        let mut progress = DkgProgress::default();
+        progress.reset(start);
+
+        let status = progress.status(&node_id, 1, &DkgState::default(), 1, start);
+        assert_eq!(status.phase, DkgPhase::Idle);
+        assert!(!progress.advance(&status, start));
+
+        let parts: Vec<_> = dkg_engines
+            .iter_mut()
+            .map(|dkg_engine| dkg_engine.generate_partial_commitment(1).unwrap())
+            .collect();
+
+        let status = progress.status(&node_id, 1, &dkg_engines[0].dkg_state, 1, start);
+        assert_eq!(status.phase, DkgPhase::CollectingParts);
+        assert_eq!(status.parts_received, 1);
+        assert_eq!(status.parts_expected, 4);
+        assert_eq!(status.failing_peers.len(), 3);
+        assert!(progress.advance(&status, start));
+
+        for (part, owner) in parts.iter() {
+            dkg_engines[0]
+                .dkg_state
+                .part_message_store_mut()
+                .insert(owner.clone(), part.clone());
+        }
+
+        let later = start + Duration::from_secs(30);
+        let status = progress.status(&node_id, 1, &dkg_engines[0].dkg_state, 1, later);
+        assert_eq!(status.phase, DkgPhase::CollectingAcks);
+        assert_eq!(status.secs_in_phase, 0);
+        assert_eq!(status.acks_expected, 16);
+        assert!(progress.advance(&status, later));
+
+        // NOTE: with a threshold of 1, keys can be generated once 2 parts
+        // were acked by 3 members each
+        let (_, acker) = &parts[1];
+        for (_, owner) in parts.iter() {
+            let (receiver_id, sender_id, ack) = dkg_engines[0]
+                .ack_partial_commitment(owner.clone())
+                .unwrap();
+            assert_eq!(receiver_id, node_id);
+
+            dkg_engines[0]
+                .dkg_state
+                .ack_message_store_mut()
+                .insert((acker.clone(), sender_id), ack);
+        }
+
+        let status = progress.status(&node_id, 1, &dkg_engines[0].dkg_state, 1, later);
+        assert_eq!(status.phase, DkgPhase::CollectingAcks);
+        assert_eq!(status.acks_received, 8);
+        assert_eq!(
+            status.failing_peers,
+            vec![parts[2].1.clone(), parts[3].1.clone()]
+        );
+
+        let (_, other_acker) = &parts[2];
+        for (_, owner) in parts.iter().take(2) {
+            let ack = dkg_engines[0].dkg_state.ack_message_store()
+                [&(node_id.clone(), owner.clone())]
+                .clone();
+
+            dkg_engines[0]
+                .dkg_state
+                .ack_message_store_mut()
+                .insert((other_acker.clone(), owner.clone()), ack);
+        }
+
+        let status = progress.status(&node_id, 1, &dkg_engines[0].dkg_state, 1, later);
+        assert_eq!(status.phase, DkgPhase::ThresholdReached);
+        assert_eq!(status.complete_parts, 2);
+        assert!(progress.advance(&status, later));
+
+        progress.record_rejected_part(&parts[3].1);
+        let status = progress.status(
+            &node_id,
+            1,
+            &dkg_engines[0].dkg_state,
+            1,
+            later + Duration::from_secs(5),
+        );
+        assert_eq!(status.secs_in_phase, 5);
+        assert_eq!(status.failing_peers, vec![parts[3].1.clone()]);
+    }
+}
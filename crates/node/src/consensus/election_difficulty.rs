@@ -0,0 +1,182 @@
+//! Miner elections only let claims win whose election result falls within the
+//! eligibility the chain's headers carry, see
+//! [primitives::ElectionDifficultyParams].
+//!
+//! Every header records the eligibility the election of its child starts
+//! from, retargeted from its parent's by the time between them. The
+//! eligibility in effect for an election also grows with the time passed
+//! since the last block, so a round no claim could win at first eventually
+//! opens up to every claim instead of stalling the chain.
+//!
+//! Election results are weighted by the stake of the claims, see
+//! [vrrb_core::claim::Claim::get_weighted_election_result].
+
+use block::header::BlockHeader;
+use ethereum_types::U256;
+use primitives::{ElectionDifficultyParams, ELECTION_ELIGIBILITY_SCALE};
+use vrrb_core::claim::Claim;
+
+use crate::{NodeError, Result};
+
+/// Returns the largest election result that still wins an election at an
+/// eligibility of `eligibility_ppm` parts per million
+pub fn election_threshold(eligibility_ppm: u64) -> U256 {
+    if eligibility_ppm >= ELECTION_ELIGIBILITY_SCALE {
+        return U256::MAX;
+    }
+
+    (U256::MAX / ELECTION_ELIGIBILITY_SCALE) * eligibility_ppm.max(1)
+}
+
+/// Returns the eligibility in effect for the election of the block after
+/// `last_header`, `elapsed_secs` seconds after it was mined
+pub fn eligibility_in_effect(
+    last_header: &BlockHeader,
+    elapsed_secs: i64,
+    params: &ElectionDifficultyParams,
+) -> u64 {
+    params.eligibility_after(last_header.election_eligibility, elapsed_secs)
+}
+
+/// Checks the block at `header` retargeted the eligibility of the block at
+/// `last_header` by the time between them, and that its miner's weighted
+/// election result fell within the eligibility in effect when it was mined.
+///
+/// `miner_claim` is the claim of the block's miner as the claim store holds
+/// it, rather than the copy the header carries, so miners can't inflate the
+/// stake their result is weighted by
+pub fn verify_election_threshold(
+    header: &BlockHeader,
+    last_header: &BlockHeader,
+    miner_claim: &Claim,
+    params: &ElectionDifficultyParams,
+) -> Result<()> {
+    let interval_secs = header.timestamp - last_header.timestamp;
+
+    let invalid = |reason: String| NodeError::InvalidBlock {
+        hash: header.hash(),
+        reason,
+    };
+
+    let expected = params.retarget(last_header.election_eligibility, interval_secs);
+    if header.election_eligibility != expected {
+        return Err(invalid(format!(
+            "records an election eligibility of {} ppm instead of {expected} ppm",
+            header.election_eligibility
+        )));
+    }
+
+    let eligibility = eligibility_in_effect(last_header, interval_secs, params);
+    let result = miner_claim.get_weighted_election_result(header.block_seed);
+
+    if result > election_threshold(eligibility) {
+        return Err(invalid(format!(
+            "miner {} did not meet the election threshold at an eligibility of {eligibility} ppm",
+            miner_claim.node_id
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use miner::test_helpers::{create_claims, mine_genesis};
+
+    use super::*;
+
+    fn params() -> ElectionDifficultyParams {
+        ElectionDifficultyParams {
+            stall_timeout_secs: 10,
+            ..Default::default()
+        }
+    }
+
+    fn headers(eligibility: u64, interval_secs: i64) -> (BlockHeader, BlockHeader) {
+        let mut last_header = mine_genesis().unwrap().header;
+        last_header.election_eligibility = eligibility;
+
+        let mut header = last_header.clone();
+        header.timestamp = last_header.timestamp + interval_secs;
+        header.election_eligibility = params().retarget(eligibility, interval_secs);
+
+        (header, last_header)
+    }
+
+    /// Returns a claim whose election result for `seed` is above the
+    /// threshold of `eligibility`
+    fn ineligible_claim(seed: u64, eligibility: u64) -> Claim {
+        create_claims(64)
+            .map(|(_, claim)| claim)
+            .find(|claim| {
+                claim.get_weighted_election_result(seed) > election_threshold(eligibility)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn thresholds_cover_the_eligible_share_of_results() {
+        assert_eq!(election_threshold(ELECTION_ELIGIBILITY_SCALE), U256::MAX);
+        assert!(election_threshold(ELECTION_ELIGIBILITY_SCALE / 2) < U256::MAX / 2 + 1);
+        assert!(election_threshold(1) < election_threshold(2));
+        assert_eq!(election_threshold(0), election_threshold(1));
+    }
+
+    #[test]
+    fn headers_must_retarget_their_parents_eligibility() {
+        let (mut header, last_header) = headers(ELECTION_ELIGIBILITY_SCALE, 1);
+        verify_election_threshold(&header, &last_header, &header.miner_claim, &params()).unwrap();
+
+        header.election_eligibility -= 1;
+        assert!(matches!(
+            verify_election_threshold(&header, &last_header, &header.miner_claim, &params()),
+            Err(NodeError::InvalidBlock { .. })
+        ));
+    }
+
+    #[test]
+    fn miners_above_the_threshold_are_rejected_until_the_round_stalls() {
+        let floor = params().min_eligibility_ppm;
+        let (mut header, last_header) = headers(floor, 1);
+        header.miner_claim = ineligible_claim(header.block_seed, floor);
+
+        assert!(matches!(
+            verify_election_threshold(&header, &last_header, &header.miner_claim, &params()),
+            Err(NodeError::InvalidBlock { .. })
+        ));
+
+        // NOTE: enough stall timeouts pass for every claim to become eligible
+        let (mut stalled, last_header) = headers(floor, 60);
+        stalled.miner_claim = header.miner_claim.clone();
+
+        assert_eq!(
+            eligibility_in_effect(&last_header, 60, &params()),
+            ELECTION_ELIGIBILITY_SCALE
+        );
+        verify_election_threshold(&stalled, &last_header, &stalled.miner_claim, &params()).unwrap();
+    }
+
+    #[test]
+    fn miners_are_weighted_by_the_stake_their_stored_claim_holds() {
+        let floor = params().min_eligibility_ppm;
+        let (mut header, last_header) = headers(floor, 1);
+        let claim = ineligible_claim(header.block_seed, floor);
+
+        // NOTE: the copy of the claim the header carries holds whatever stake
+        // its miner makes up
+        header.miner_claim = claim.clone();
+        header
+            .miner_claim
+            .delegate(claim.address.clone(), u128::MAX);
+
+        assert!(matches!(
+            verify_election_threshold(&header, &last_header, &claim, &params()),
+            Err(NodeError::InvalidBlock { .. })
+        ));
+
+        let mut staked = claim.clone();
+        staked.delegate(claim.address.clone(), u128::MAX);
+
+        verify_election_threshold(&header, &last_header, &staked, &params()).unwrap();
+    }
+}
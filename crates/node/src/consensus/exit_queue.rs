@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+
+use primitives::{Epoch, NodeId};
+
+use crate::{NodeError, Result};
+
+/// A validator's pending departure from the validator set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorExit {
+    pub node_id: NodeId,
+
+    /// First epoch in which the validator no longer belongs to any quorum
+    pub exit_epoch: Epoch,
+
+    /// Epoch at which the validator's stake unlocks
+    pub unlocks_at: Epoch,
+}
+
+/// Certified validator exits waiting for the epoch in which they take effect.
+///
+/// Like registrations, exits never take effect within the epoch they were
+/// certified in. The validator keeps its duties until the end of the epoch
+/// and is left out of the quorums of the next one.
+#[derive(Debug, Clone, Default)]
+pub struct ExitQueue {
+    unbonding_period: Epoch,
+    pending: BTreeMap<NodeId, ValidatorExit>,
+}
+
+impl ExitQueue {
+    pub fn new(unbonding_period: Epoch) -> Self {
+        Self {
+            unbonding_period,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Schedules `node_id` to leave the validator set at the epoch following
+    /// `current_epoch`.
+    pub fn enqueue(&mut self, node_id: NodeId, current_epoch: Epoch) -> Result<ValidatorExit> {
+        if self.pending.contains_key(&node_id) {
+            return Err(NodeError::Other(format!(
+                "node {node_id} is already leaving the validator set"
+            )));
+        }
+
+        let exit_epoch = current_epoch + 1;
+        let exit = ValidatorExit {
+            node_id: node_id.clone(),
+            exit_epoch,
+            unlocks_at: exit_epoch + self.unbonding_period,
+        };
+
+        self.pending.insert(node_id, exit.clone());
+
+        Ok(exit)
+    }
+
+    pub fn is_exiting(&self, node_id: &NodeId) -> bool {
+        self.pending.contains_key(node_id)
+    }
+
+    /// Returns the pending exit of `node_id`, if it is leaving
+    pub fn get(&self, node_id: &NodeId) -> Option<&ValidatorExit> {
+        self.pending.get(node_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Removes and returns every exit taking effect by `epoch`.
+    pub fn exits_due(&mut self, epoch: Epoch) -> Vec<ValidatorExit> {
+        let due: Vec<NodeId> = self
+            .pending
+            .iter()
+            .filter(|(_, exit)| exit.exit_epoch <= epoch)
+            .map(|(node_id, _)| node_id.clone())
+            .collect();
+
+        due.iter()
+            .filter_map(|node_id| self.pending.remove(node_id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exits_take_effect_at_the_next_epoch_and_unlock_after_unbonding() {
+        let mut queue = ExitQueue::new(3);
+
+        let exit = queue.enqueue("node-1".into(), 5).unwrap();
+        assert_eq!(exit.exit_epoch, 6);
+        assert_eq!(exit.unlocks_at, 9);
+        assert!(queue.enqueue("node-1".into(), 5).is_err());
+
+        assert!(queue.exits_due(5).is_empty());
+        assert_eq!(queue.exits_due(6), vec![exit]);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+//! Inclusion lists bound how long harvesters can censor certified txns.
+//!
+//! Farmers attach the certified txns that have waited at least
+//! `min_age_rounds` for a block to their votes. Harvesters keep the listed
+//! txns they saw certified themselves and carry the overdue ones in the
+//! proposal blocks they sign. Once a certified block references such a
+//! proposal, every node refuses blocks that keep leaving them out, see
+//! [crate::state_manager::InclusionListLedger].
+
+use std::collections::{HashMap, HashSet};
+
+use events::InclusionList;
+use primitives::{InclusionListParams, Round};
+use vrrb_core::transactions::TransactionDigest;
+
+#[derive(Debug, Clone, Default)]
+pub struct InclusionListTracker {
+    /// Certified txns no block included yet, by the round they were
+    /// certified in
+    pending: HashMap<TransactionDigest, Round>,
+
+    /// Pending txns farmers listed for inclusion
+    listed: HashSet<TransactionDigest>,
+}
+
+impl InclusionListTracker {
+    pub fn record_certified(&mut self, digest: TransactionDigest, round: Round) {
+        self.pending.entry(digest).or_insert(round);
+    }
+
+    /// Forgets txns once a block included them
+    pub fn record_included<'a>(
+        &mut self,
+        digests: impl IntoIterator<Item = &'a TransactionDigest>,
+    ) {
+        for digest in digests {
+            self.pending.remove(digest);
+            self.listed.remove(digest);
+        }
+    }
+
+    /// Keeps the entries of a farmer's inclusion list this node saw
+    /// certified too. Entries past `max_entries` are ignored so farmers can't
+    /// flood harvesters with lists
+    pub fn record_inclusion_list(&mut self, list: &InclusionList, params: &InclusionListParams) {
+        for digest in list.iter().take(params.max_entries) {
+            if self.pending.contains_key(digest) {
+                self.listed.insert(digest.clone());
+            }
+        }
+    }
+
+    pub fn is_listed(&self, digest: &TransactionDigest) -> bool {
+        self.listed.contains(digest)
+    }
+
+    /// Returns the round a txn no block included yet was certified in
+    pub fn certified_at(&self, digest: &TransactionDigest) -> Option<Round> {
+        self.pending.get(digest).copied()
+    }
+
+    /// Returns whether a txn has waited long enough by `round` to be listed
+    fn is_overdue(
+        &self,
+        digest: &TransactionDigest,
+        round: Round,
+        params: &InclusionListParams,
+    ) -> bool {
+        self.pending.get(digest).map_or(false, |certified_at| {
+            certified_at.saturating_add(params.min_age_rounds) <= round
+        })
+    }
+
+    /// Builds the inclusion list a farmer attaches to its votes in `round`,
+    /// naming the txns that have waited the longest first
+    pub fn inclusion_list(&self, round: Round, params: &InclusionListParams) -> InclusionList {
+        let mut overdue: Vec<(&Round, &TransactionDigest)> = self
+            .pending
+            .iter()
+            .filter(|(digest, _)| self.is_overdue(digest, round, params))
+            .map(|(digest, certified_at)| (certified_at, digest))
+            .collect();
+
+        overdue.sort();
+
+        overdue
+            .into_iter()
+            .take(params.max_entries)
+            .map(|(_, digest)| digest.clone())
+            .collect()
+    }
+
+    /// Returns the overdue txns farmers listed that a harvester carries in
+    /// its proposal block at `round`, naming the ones that have waited the
+    /// longest first
+    pub fn listed_overdue(&self, round: Round, params: &InclusionListParams) -> InclusionList {
+        let mut overdue: Vec<(Round, &TransactionDigest)> = self
+            .listed
+            .iter()
+            .filter(|digest| self.is_overdue(digest, round, params))
+            .filter_map(|digest| Some((self.certified_at(digest)?, digest)))
+            .collect();
+
+        overdue.sort();
+
+        overdue
+            .into_iter()
+            .take(params.max_entries)
+            .map(|(_, digest)| digest.clone())
+            .collect()
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(byte: u8) -> TransactionDigest {
+        TransactionDigest::from(vec![byte; 32])
+    }
+
+    fn params() -> InclusionListParams {
+        InclusionListParams {
+            min_age_rounds: 3,
+            max_entries: 2,
+        }
+    }
+
+    #[test]
+    fn farmers_list_the_longest_waiting_overdue_txns() {
+        let mut tracker = InclusionListTracker::default();
+        tracker.record_certified(digest(1), 4);
+        tracker.record_certified(digest(2), 1);
+        tracker.record_certified(digest(3), 2);
+        tracker.record_certified(digest(4), 6);
+
+        assert!(tracker.inclusion_list(3, &params()).is_empty());
+        assert_eq!(
+            tracker.inclusion_list(7, &params()),
+            vec![digest(2), digest(3)]
+        );
+
+        tracker.record_included([&digest(2)]);
+        assert_eq!(
+            tracker.inclusion_list(7, &params()),
+            vec![digest(3), digest(1)]
+        );
+    }
+
+    #[test]
+    fn harvesters_only_enforce_listed_txns_they_saw_certified() {
+        let mut tracker = InclusionListTracker::default();
+        tracker.record_certified(digest(1), 1);
+        tracker.record_certified(digest(2), 1);
+
+        tracker.record_inclusion_list(&vec![digest(9), digest(1), digest(2)], &params());
+
+        assert!(!tracker.is_listed(&digest(9)));
+        assert!(tracker.is_listed(&digest(1)));
+        // only the first `max_entries` entries of a list are considered
+        assert!(!tracker.is_listed(&digest(2)));
+
+        assert_eq!(tracker.listed_overdue(4, &params()), vec![digest(1)]);
+        assert!(tracker.listed_overdue(3, &params()).is_empty());
+    }
+}
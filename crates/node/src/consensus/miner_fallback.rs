@@ -0,0 +1,148 @@
+//! Fallback miners take over rounds whose elected miner never produces a
+//! block.
+//!
+//! The schedule of a round lists the miner-eligible claims with the lowest
+//! weighted election results for its seed, the winner first. Every node reads it off
+//! the same claim index, so they all agree on who may mine in which slot,
+//! and slots open one timeout apart from the previous block's timestamp.
+
+use block::header::BlockHeader;
+use primitives::MinerFallbackParams;
+use storage::vrrbdb::VrrbDbReadHandle;
+use vrrb_core::claim::Claim;
+
+use crate::{NodeError, Result};
+
+/// Returns the claims scheduled to mine the round seeded by `seed`, the
+/// election winner in slot 0 followed by one claim per fallback slot
+pub fn miner_schedule(
+    claims: &VrrbDbReadHandle,
+    seed: u64,
+    params: &MinerFallbackParams,
+) -> Vec<Claim> {
+    claims
+        .miner_election_results(seed, params.max_slots as usize + 1)
+        .into_iter()
+        .map(|(_, claim)| claim)
+        .collect()
+}
+
+/// Returns the slot `claim` holds in `schedule`, if any
+pub fn scheduled_slot(schedule: &[Claim], claim: &Claim) -> Option<u16> {
+    schedule
+        .iter()
+        .position(|scheduled| scheduled.hash == claim.hash)
+        .map(|slot| slot as u16)
+}
+
+/// Checks the block at `header` was mined by the claim holding its fallback
+/// slot in `schedule`, and no sooner than that slot opened after the block at
+/// `last_header`.
+///
+/// Slots are worked out from the timestamp the miner wrote itself, so it
+/// mustn't run more than `max_clock_drift_secs` ahead of `now`, the
+/// verifier's own clock
+pub fn verify_miner_slot(
+    schedule: &[Claim],
+    header: &BlockHeader,
+    last_header: &BlockHeader,
+    params: &MinerFallbackParams,
+    now: i64,
+) -> Result<()> {
+    let slot = header.fallback_slot;
+
+    let invalid = |reason: String| NodeError::InvalidBlock {
+        hash: header.hash(),
+        reason,
+    };
+
+    if scheduled_slot(schedule, &header.miner_claim) != Some(slot) {
+        return Err(invalid(format!(
+            "miner {} doesn't hold fallback slot {slot} of round {}",
+            header.miner_claim.node_id, header.round
+        )));
+    }
+
+    let max_clock_drift_secs = i64::try_from(params.max_clock_drift_secs).unwrap_or(i64::MAX);
+    if header.timestamp > now.saturating_add(max_clock_drift_secs) {
+        return Err(invalid(format!(
+            "timestamp {} is more than {max_clock_drift_secs}s ahead of {now}",
+            header.timestamp
+        )));
+    }
+
+    let open_slot = params.open_slot(header.timestamp - last_header.timestamp);
+
+    if slot > open_slot {
+        return Err(invalid(format!(
+            "mined in fallback slot {slot} before it opened, only slot {open_slot} was"
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use miner::test_helpers::{create_claims, mine_genesis};
+
+    use super::*;
+
+    fn header(miner_claim: Claim, timestamp: i64, fallback_slot: u16) -> BlockHeader {
+        let mut header = mine_genesis().unwrap().header;
+        header.miner_claim = miner_claim;
+        header.timestamp = timestamp;
+        header.fallback_slot = fallback_slot;
+        header
+    }
+
+    #[test]
+    fn fallback_miners_only_mine_in_their_own_open_slot() {
+        let schedule: Vec<Claim> = create_claims(3).map(|(_, claim)| claim).collect();
+        let params = MinerFallbackParams {
+            timeout_secs: 10,
+            max_slots: 2,
+            max_clock_drift_secs: 5,
+        };
+        let last_header = header(schedule[0].clone(), 100, 0);
+
+        assert_eq!(scheduled_slot(&schedule, &schedule[2]), Some(2));
+
+        let winner = header(schedule[0].clone(), 101, 0);
+        assert!(verify_miner_slot(&schedule, &winner, &last_header, &params, 130).is_ok());
+
+        let fallback = header(schedule[1].clone(), 110, 1);
+        assert!(verify_miner_slot(&schedule, &fallback, &last_header, &params, 130).is_ok());
+
+        let early = header(schedule[2].clone(), 110, 2);
+        assert!(verify_miner_slot(&schedule, &early, &last_header, &params, 130).is_err());
+
+        let wrong_slot = header(schedule[2].clone(), 130, 1);
+        assert!(verify_miner_slot(&schedule, &wrong_slot, &last_header, &params, 130).is_err());
+
+        let unscheduled = header(create_claims(1).next().unwrap().1, 130, 0);
+        assert!(verify_miner_slot(&schedule, &unscheduled, &last_header, &params, 130).is_err());
+    }
+
+    #[test]
+    fn fallback_miners_cannot_date_their_blocks_forward_into_their_slot() {
+        let schedule: Vec<Claim> = create_claims(3).map(|(_, claim)| claim).collect();
+        let params = MinerFallbackParams {
+            timeout_secs: 10,
+            max_slots: 2,
+            max_clock_drift_secs: 5,
+        };
+        let last_header = header(schedule[0].clone(), 100, 0);
+
+        // NOTE: only 2s passed on the verifier's clock, but the timestamp
+        // claims slot 2 opened already
+        let forward_dated = header(schedule[2].clone(), 120, 2);
+        assert!(matches!(
+            verify_miner_slot(&schedule, &forward_dated, &last_header, &params, 102),
+            Err(NodeError::InvalidBlock { .. })
+        ));
+
+        let within_drift = header(schedule[2].clone(), 120, 2);
+        assert!(verify_miner_slot(&schedule, &within_drift, &last_header, &params, 115).is_ok());
+    }
+}
@@ -1,6 +1,44 @@
+#[cfg(feature = "consensus")]
+mod activation_queue;
+#[cfg(feature = "consensus")]
 mod consensus_module;
+#[cfg(feature = "consensus")]
+mod dkg_progress;
+mod election_difficulty;
+#[cfg(feature = "consensus")]
+mod exit_queue;
+#[cfg(feature = "consensus")]
+mod inclusion_list;
+mod miner_fallback;
+#[cfg(feature = "consensus")]
+mod proposal_priority;
 
+#[cfg(feature = "consensus")]
 mod quorum_module;
+mod quorum_safety;
+#[cfg(feature = "consensus")]
+mod signing_pool;
+#[cfg(feature = "consensus")]
+mod txn_partition;
 
+#[cfg(feature = "consensus")]
+pub use activation_queue::*;
+#[cfg(feature = "consensus")]
 pub use consensus_module::*;
+#[cfg(feature = "consensus")]
+pub use dkg_progress::*;
+pub use election_difficulty::*;
+#[cfg(feature = "consensus")]
+pub use exit_queue::*;
+#[cfg(feature = "consensus")]
+pub use inclusion_list::*;
+pub use miner_fallback::*;
+#[cfg(feature = "consensus")]
+pub use proposal_priority::*;
+#[cfg(feature = "consensus")]
 pub use quorum_module::*;
+pub use quorum_safety::*;
+#[cfg(feature = "consensus")]
+pub use signing_pool::*;
+#[cfg(feature = "consensus")]
+pub use txn_partition::*;
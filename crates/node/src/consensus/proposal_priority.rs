@@ -0,0 +1,135 @@
+//! Order in which harvesters consider certified txns for their proposals.
+//!
+//! Txns farmers listed for inclusion come first, then txns that have waited
+//! longer than the fairness bound, oldest first, and only then the rest by
+//! fee. A txn is thus passed over for higher paying ones for at most
+//! `max_wait_rounds` rounds, after which only older txns can go ahead of it.
+
+use std::cmp::Reverse;
+
+use primitives::{ProposalFairnessParams, Round};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Tier {
+    Listed,
+    Overdue,
+    Fresh,
+}
+
+/// Sort key ranking a candidate txn of a proposal, lower keys first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProposalPriority {
+    tier: Tier,
+    fee: Reverse<u128>,
+    certified_at: Round,
+}
+
+impl ProposalPriority {
+    /// Ranks a txn paying `fee` and certified in `certified_at`, if known,
+    /// when proposing in `round`
+    pub fn new(
+        listed: bool,
+        fee: u128,
+        certified_at: Option<Round>,
+        round: Round,
+        params: &ProposalFairnessParams,
+    ) -> Self {
+        // NOTE: txns whose certification round isn't known count as fresh
+        let certified_at = certified_at.unwrap_or(round);
+        let overdue = certified_at.saturating_add(params.max_wait_rounds) <= round;
+
+        let tier = match (listed, overdue) {
+            (true, _) => Tier::Listed,
+            (false, true) => Tier::Overdue,
+            (false, false) => Tier::Fresh,
+        };
+
+        // NOTE: fees only rank fresh txns, the others go strictly by age
+        let fee = match tier {
+            Tier::Fresh => Reverse(fee),
+            Tier::Listed | Tier::Overdue => Reverse(0),
+        };
+
+        Self {
+            tier,
+            fee,
+            certified_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A txn waiting for a block: its id, fee and certification round
+    type Txn = (usize, u128, Round);
+
+    /// Picks the `capacity` best ranked of `pending` for a proposal at `round`
+    fn propose(pending: &mut Vec<Txn>, capacity: usize, round: Round) -> Vec<usize> {
+        let params = ProposalFairnessParams { max_wait_rounds: 3 };
+
+        pending.sort_by_key(|(_, fee, certified_at)| {
+            ProposalPriority::new(false, *fee, Some(*certified_at), round, &params)
+        });
+
+        pending
+            .drain(..capacity.min(pending.len()))
+            .map(|(id, ..)| id)
+            .collect()
+    }
+
+    #[test]
+    fn overdue_txns_go_ahead_of_newer_ones_whatever_their_fees() {
+        let params = ProposalFairnessParams { max_wait_rounds: 3 };
+
+        let overdue = ProposalPriority::new(false, 1, Some(2), 5, &params);
+        let rich = ProposalPriority::new(false, u128::MAX, Some(4), 5, &params);
+        let poor = ProposalPriority::new(false, 1, Some(4), 5, &params);
+        let listed = ProposalPriority::new(true, 0, Some(5), 5, &params);
+
+        assert!(listed < overdue);
+        assert!(overdue < rich);
+        assert!(rich < poor);
+        assert!(
+            ProposalPriority::new(false, 1, Some(1), 5, &params)
+                < ProposalPriority::new(false, 1_000, Some(2), 5, &params)
+        );
+    }
+
+    #[test]
+    fn high_fee_pressure_cannot_starve_low_fee_txns() {
+        let capacity = 4;
+        let mut pending: Vec<Txn> = vec![(0, 1, 0)];
+        let mut next_id = 1;
+        let mut included_at = None;
+
+        // NOTE: from the next round on, every round brings more higher paying
+        // txns than fit in a proposal
+        for round in 1..20 {
+            for _ in 0..capacity * 2 {
+                pending.push((next_id, 1_000 + next_id as u128, round));
+                next_id += 1;
+            }
+
+            if propose(&mut pending, capacity, round).contains(&0) {
+                included_at = Some(round);
+                break;
+            }
+        }
+
+        assert_eq!(included_at, Some(3));
+
+        // NOTE: starved txns make it into a proposal in the order they were
+        // certified, however many there are
+        let mut pending: Vec<Txn> = (0..10).map(|id| (id, id as u128, id as Round)).collect();
+        let mut included = vec![];
+
+        for round in 13..16 {
+            pending.extend((100..110).map(|id| (id, u128::MAX, round)));
+            included.extend(propose(&mut pending, capacity, round));
+        }
+
+        assert_eq!(included[..10], (0..10).collect::<Vec<usize>>());
+    }
+}
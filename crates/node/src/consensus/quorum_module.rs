@@ -1,19 +1,31 @@
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    net::SocketAddr,
+};
 
 use async_trait::async_trait;
 use block::header::BlockHeader;
 use ethereum_types::U256;
 use events::{
     AssignedQuorumMembership, Event, EventMessage, EventPublisher, EventSubscriber, PeerData,
+    QuorumKeysetAnnouncement, LEGACY_EVENT_SCHEMA_VERSION,
 };
-use primitives::{NodeId, NodeType, QuorumKind};
-use quorum::{
-    election::Election,
-    quorum::{Quorum, QuorumError},
+use primitives::{
+    Epoch, KademliaPeerId, Locality, NodeId, NodeType, QuorumAssignmentStrategy, QuorumKind,
 };
+use quorum::quorum::{Quorum, QuorumError, MIN_ELIGIBLE_CLAIMS};
+use storage::vrrbdb::VrrbDbReadHandle;
 use theater::{Actor, ActorId, ActorImpl, ActorState};
-use vrrb_config::{BootstrapQuorumConfig, NodeConfig, QuorumMembershipConfig};
-use vrrb_core::claim::{Claim, Eligibility};
+use utils::payload::digest_data_to_bytes;
+use vrrb_config::{
+    BootstrapQuorumConfig, NodeConfig, QuorumMember, QuorumMembershipConfig, ValidatorSetDiff,
+};
+use vrrb_core::{
+    claim::Claim,
+    transactions::{ExitValidator, RegisterValidator},
+};
+
+use super::{ActivationQueue, ExitQueue, TxnPartition, ValidatorExit};
 
 #[derive(Debug, Clone)]
 pub struct QuorumModule {
@@ -25,6 +37,17 @@ pub struct QuorumModule {
 
     /// A map of all nodes known to are available in the bootstrap quorum
     pub(crate) bootstrap_quorum_available_nodes: HashMap<NodeId, (PeerData, bool)>,
+
+    /// Every peer this node has learned about, used to resolve the network
+    /// details of validators joining a quorum
+    pub(crate) known_peers: HashMap<NodeId, PeerData>,
+
+    /// Addresses of the claims queued registrations bonded stake to, which
+    /// validators this node hasn't discovered yet are reached at
+    pub(crate) claim_addresses: HashMap<NodeId, SocketAddr>,
+    pub(crate) activation_queue: ActivationQueue,
+    pub(crate) exit_queue: ExitQueue,
+    pub(crate) current_epoch: Epoch,
 }
 
 #[derive(Debug, Clone)]
@@ -50,7 +73,11 @@ impl QuorumModule {
                         udp_gossip_addr: member.udp_gossip_address,
                         raptorq_gossip_addr: member.raptorq_gossip_address,
                         kademlia_liveness_addr: member.kademlia_liveness_address,
+                        alternate_udp_gossip_addrs: vec![],
                         validator_public_key: member.validator_public_key,
+                        locality: Locality::default(),
+                        claim_signature: None,
+                        event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
                     };
 
                     (peer.node_id.clone(), (peer, false))
@@ -65,6 +92,11 @@ impl QuorumModule {
             node_config: cfg.node_config.clone(),
             bootstrap_quorum_config: cfg.node_config.bootstrap_quorum_config.clone(),
             bootstrap_quorum_available_nodes,
+            known_peers: HashMap::new(),
+            claim_addresses: HashMap::new(),
+            activation_queue: ActivationQueue::new(cfg.node_config.chain_spec.staking.clone()),
+            exit_queue: ExitQueue::new(cfg.node_config.chain_spec.staking.unbonding_period),
+            current_epoch: cfg.node_config.chain_spec.genesis_epoch,
         }
     }
 
@@ -73,6 +105,249 @@ impl QuorumModule {
         self.membership_config = Some(membership_config);
     }
 
+    /// Queues a validator registration a certified block bonded the stake of
+    /// so it is considered when the quorum moves into the next epoch.
+    /// `claim_address` is the address of the claim the stake was bonded to.
+    pub fn queue_validator_registration(
+        &mut self,
+        registration: RegisterValidator,
+        claim_address: SocketAddr,
+    ) -> crate::Result<Epoch> {
+        if self.exit_queue.is_exiting(&registration.node_id) {
+            return Err(crate::NodeError::Other(format!(
+                "node {} is leaving the validator set",
+                registration.node_id
+            )));
+        }
+
+        let node_id = registration.node_id.clone();
+        let activation_epoch = self
+            .activation_queue
+            .enqueue(registration, self.current_epoch)?;
+
+        self.claim_addresses.insert(node_id, claim_address);
+
+        Ok(activation_epoch)
+    }
+
+    /// Schedules a validator to leave the validator set at the next epoch.
+    /// `claim` is the validator's claim, whose owner alone may request its
+    /// exit. Exits that would leave this node's quorum unable to reach its
+    /// signing threshold are rejected.
+    pub fn queue_validator_exit(
+        &mut self,
+        exit: &ExitValidator,
+        claim: &Claim,
+    ) -> crate::Result<ValidatorExit> {
+        let node_id = exit.node_id.clone();
+
+        if claim.node_id != node_id || claim.address != exit.sender_address {
+            return Err(crate::NodeError::Other(format!(
+                "{} does not own the claim of node {node_id}",
+                exit.sender_address
+            )));
+        }
+
+        if let Some(membership_config) = self.membership_config.as_ref() {
+            if membership_config.quorum_members.contains_key(&node_id) {
+                let remaining_members = membership_config
+                    .quorum_members
+                    .keys()
+                    .filter(|member_id| {
+                        *member_id != &node_id && !self.exit_queue.is_exiting(member_id)
+                    })
+                    .count();
+
+                let required_signers = self.node_config.threshold_config.threshold as usize + 1;
+
+                if remaining_members < required_signers {
+                    return Err(crate::NodeError::Other(format!(
+                        "exit of node {node_id} would leave its quorum with fewer than {required_signers} signers"
+                    )));
+                }
+            }
+        }
+
+        self.activation_queue.cancel(&node_id);
+        self.claim_addresses.remove(&node_id);
+        self.exit_queue.enqueue(node_id, self.current_epoch)
+    }
+
+    /// Moves the quorum into `epoch`, admitting every validator whose
+    /// registration became due and removing every validator whose exit took
+    /// effect. Returns the resulting membership changes, if there were any.
+    ///
+    /// Only registrations bonded by certified blocks are ever queued, so every
+    /// node derives the same changes. Validators this node hasn't discovered
+    /// yet join at the address of their claim. Exiting validators handed
+    /// their duties over when their exit was queued, see
+    /// [QuorumModule::txn_partition].
+    pub fn rotate_validator_set(&mut self, epoch: Epoch) -> Option<ValidatorSetDiff> {
+        if epoch <= self.current_epoch {
+            return None;
+        }
+
+        self.current_epoch = epoch;
+
+        // NOTE: validators join as farmers, harvester quorums only change through a
+        // full DKG
+        let mut diff = ValidatorSetDiff::new(epoch, QuorumKind::Farmer);
+
+        for exit in self.exit_queue.exits_due(epoch) {
+            diff.removed.insert(exit.node_id);
+        }
+
+        for registration in self.activation_queue.activate(epoch) {
+            let node_id = registration.node_id.clone();
+            let claim_address = self.claim_addresses.remove(&node_id);
+
+            let is_member = self
+                .membership_config
+                .as_ref()
+                .map(|config| config.quorum_members.contains_key(&node_id))
+                .unwrap_or(false);
+
+            if is_member {
+                continue;
+            }
+
+            let Some(member) = self.member_for(&registration, claim_address) else {
+                telemetry::error!("No address known for validator {}", node_id);
+                continue;
+            };
+
+            diff.added.insert(node_id, member);
+        }
+
+        if diff.is_empty() {
+            return None;
+        }
+
+        self.apply_validator_set_diff(&diff);
+
+        Some(diff)
+    }
+
+    /// Computes the share of the certified txn queue `node_id` proposes, see
+    /// [TxnPartition]. Members leaving the validator set hand their share
+    /// over to the remaining members as soon as their exit is queued, and
+    /// only pick up txns other members left waiting until they leave.
+    /// Returns `None` if the node is not part of this node's quorum.
+    pub fn txn_partition(&self, node_id: &NodeId) -> Option<TxnPartition> {
+        let membership_config = self.membership_config.as_ref()?;
+
+        if !membership_config.quorum_members.contains_key(node_id) {
+            return None;
+        }
+
+        let remaining_members: Vec<&NodeId> = membership_config
+            .quorum_members
+            .keys()
+            .filter(|member_id| !self.exit_queue.is_exiting(member_id))
+            .collect();
+
+        let member_count = remaining_members.len();
+
+        match remaining_members
+            .iter()
+            .position(|member_id| *member_id == node_id)
+        {
+            Some(member_index) => Some(TxnPartition::new(member_index, member_count)),
+            None => Some(TxnPartition::failover_only(member_count)),
+        }
+    }
+
+    /// Returns the quorum `node_id` serves in over `epoch`, from the epoch the
+    /// quorum is in onwards. Only this node's quorum and the validators
+    /// joining it are known, so members of other quorums yield `None`
+    pub fn scheduled_quorum(&self, node_id: &NodeId, epoch: Epoch) -> Option<QuorumKind> {
+        if let Some(exit) = self.exit_queue.get(node_id) {
+            if exit.exit_epoch <= epoch {
+                return None;
+            }
+        }
+
+        if let Some(membership_config) = self.membership_config.as_ref() {
+            if membership_config.quorum_members.contains_key(node_id) {
+                return Some(membership_config.quorum_kind.clone());
+            }
+        }
+
+        // NOTE: validators join as farmers, see rotate_validator_set
+        self.activation_queue
+            .activation_epoch(node_id)
+            .filter(|activation_epoch| *activation_epoch <= epoch)
+            .map(|_| QuorumKind::Farmer)
+    }
+
+    /// Returns the quorum member a registration makes of its node. Its
+    /// validator key is the registered one, and its network details are
+    /// those of the peer if this node discovered it, or else derived from its
+    /// claim's address and node id
+    fn member_for(
+        &self,
+        registration: &RegisterValidator,
+        claim_address: Option<SocketAddr>,
+    ) -> Option<QuorumMember> {
+        let node_id = registration.node_id.clone();
+
+        if let Some(peer) = self.known_peers.get(&node_id) {
+            return Some(QuorumMember {
+                node_id,
+                kademlia_peer_id: peer.kademlia_peer_id,
+                node_type: NodeType::Validator,
+                udp_gossip_address: peer.udp_gossip_addr,
+                raptorq_gossip_address: peer.raptorq_gossip_addr,
+                kademlia_liveness_address: peer.kademlia_liveness_addr,
+                validator_public_key: registration.validator_public_key,
+            });
+        }
+
+        let address = claim_address?;
+        let kademlia_peer_id = KademliaPeerId::try_from(digest_data_to_bytes(&node_id)).ok()?;
+
+        Some(QuorumMember {
+            node_id,
+            kademlia_peer_id,
+            node_type: NodeType::Validator,
+            udp_gossip_address: address,
+            raptorq_gossip_address: address,
+            kademlia_liveness_address: address,
+            validator_public_key: registration.validator_public_key,
+        })
+    }
+
+    /// Applies a validator set diff to this node's quorum membership, if the
+    /// diff concerns its quorum.
+    fn apply_validator_set_diff(&mut self, diff: &ValidatorSetDiff) -> bool {
+        self.membership_config
+            .as_mut()
+            .map(|config| config.apply_diff(diff))
+            .unwrap_or(false)
+    }
+
+    /// Returns whether a peer's validator set diff matches the changes this
+    /// node made to its quorum at the diff's epoch. Only membership is
+    /// compared, the network details of members are resolved by every node
+    /// on its own
+    pub fn agrees_with(&self, diff: &ValidatorSetDiff) -> bool {
+        let Some(membership_config) = self.membership_config.as_ref() else {
+            return true;
+        };
+
+        if diff.quorum_kind != membership_config.quorum_kind || diff.epoch > self.current_epoch {
+            return true;
+        }
+
+        diff.added
+            .keys()
+            .all(|node_id| membership_config.quorum_members.contains_key(node_id))
+            && diff
+                .removed
+                .iter()
+                .all(|node_id| !membership_config.quorum_members.contains_key(node_id))
+    }
+
     async fn assign_membership_to_quorum(
         &self,
         quorum_kind: QuorumKind,
@@ -80,6 +355,12 @@ impl QuorumModule {
         peers: Vec<PeerData>,
     ) -> crate::Result<AssignedQuorumMembership> {
         let node_id = peer_data.node_id.clone();
+        let keyset = QuorumKeysetAnnouncement::new(
+            quorum_kind.clone(),
+            &peers,
+            self.node_config.quorum_assignment_strategy,
+        );
+
         let assigned_membership = AssignedQuorumMembership {
             quorum_kind,
             node_id: node_id.clone(),
@@ -88,6 +369,7 @@ impl QuorumModule {
                 .into_iter()
                 .filter(|peer| peer.node_id != node_id)
                 .collect::<Vec<PeerData>>(),
+            keyset,
         };
 
         Ok(assigned_membership)
@@ -99,23 +381,37 @@ impl QuorumModule {
         &self,
         peer_list: HashMap<NodeId, (PeerData, bool)>,
     ) -> crate::Result<HashMap<NodeId, AssignedQuorumMembership>> {
-        let unassigned_miner_peers = peer_list
+        // NOTE: peers are sorted so assignments don't depend on the peer list's iteration
+        // order
+        let mut unassigned_miner_peers = peer_list
             .iter()
             .filter(|(_, (peer_data, _))| peer_data.node_type == NodeType::Miner)
             .map(|(_, (peer_data, _))| peer_data)
             .cloned()
             .collect::<Vec<PeerData>>();
+        unassigned_miner_peers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+
+        let strategy = self.node_config.quorum_assignment_strategy;
 
-        let mut unassigned_peers = peer_list
+        let mut validator_peers = peer_list
             .iter()
             .filter(|(_, (peer_data, _))| peer_data.node_type == NodeType::Validator)
             .map(|(_, (peer_data, _))| peer_data)
             .cloned()
             .collect::<Vec<PeerData>>();
+        validator_peers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+
+        let unassigned_peers = order_peers_for_assignment(strategy, validator_peers);
+
+        telemetry::info!(
+            "Assigning {} validators to quorums using the {} strategy",
+            unassigned_peers.len(),
+            strategy
+        );
 
         // NOTE: select 30% of nodes to be harvester nodes and make the rest farmers
         let unassigned_peers_count = unassigned_peers.len();
-        let harvester_count = (unassigned_peers_count as f64 * 0.3).ceil() as usize;
+        let harvester_count = (unassigned_peers_count * 3 + 9) / 10;
 
         // TODO: pick nodes at random
         let harvester_peers = unassigned_peers
@@ -168,50 +464,604 @@ impl QuorumModule {
         Ok(quorum_assignments)
     }
 
+    /// Elects the next quorum with the seed `header` carries by consulting
+    /// the claim store's election index rather than scanning every claim.
     fn elect_quorum(
         &self,
-        claims: HashMap<NodeId, Claim>,
+        claims: &VrrbDbReadHandle,
         header: BlockHeader,
     ) -> Result<Quorum, QuorumError> {
-        let last_block_height = header.block_height;
         let seed = header.next_block_seed;
+        let mut quorum = Quorum::new(seed, header.block_height)?;
 
-        if let Ok(mut quorum) = Quorum::new(seed, last_block_height) {
-            let claim_vec: Vec<Claim> = claims.values().cloned().collect();
-            if let Ok(elected_quorum) = quorum.run_election(claim_vec) {
-                return Ok(elected_quorum.clone());
-            }
+        let (ranked, eligible) = claims.quorum_election_results(seed, Quorum::seats);
+        if eligible < MIN_ELIGIBLE_CLAIMS {
+            return Err(QuorumError::InsufficientNodesError);
         }
 
-        Err(QuorumError::InvalidSeedError)
+        quorum.elect_from_ranked(ranked, eligible)?;
+
+        Ok(quorum)
     }
 
+    /// Elects the miner for the given seed by consulting the claim store's
+    /// election index rather than scanning every claim.
     pub(crate) fn elect_miner(
         &self,
-        claims: HashMap<NodeId, Claim>,
+        claims: &VrrbDbReadHandle,
         block_seed: u64,
-    ) -> BTreeMap<U256, Claim> {
-        claims
-            .iter()
-            .filter(|(_, claim)| claim.eligibility == Eligibility::Miner)
-            .map(|(_nodeid, claim)| Self::single_miner_results(claim, block_seed))
-            .collect()
+    ) -> Option<(U256, Claim)> {
+        claims.miner_election_winner(block_seed)
     }
+}
+
+/// Orders validators so that taking them front to back honours `strategy`.
+///
+/// With [`QuorumAssignmentStrategy::RegionSpread`] peers are grouped by
+/// region and zone, then drawn from every region in turn and from every zone
+/// in turn within a region. Any prefix of the result, such as the harvester
+/// quorum, is then spread as evenly across regions as the peer list allows.
+/// Peers without a region are treated as one more region.
+pub(crate) fn order_peers_for_assignment(
+    strategy: QuorumAssignmentStrategy,
+    peers: Vec<PeerData>,
+) -> Vec<PeerData> {
+    match strategy {
+        QuorumAssignmentStrategy::Sequential => peers,
+        QuorumAssignmentStrategy::RegionSpread => {
+            let mut regions: BTreeMap<Option<String>, BTreeMap<Option<String>, Vec<PeerData>>> =
+                BTreeMap::new();
+
+            for peer in peers {
+                regions
+                    .entry(peer.locality.region.clone())
+                    .or_default()
+                    .entry(peer.locality.zone.clone())
+                    .or_default()
+                    .push(peer);
+            }
+
+            let mut region_queues = regions
+                .into_values()
+                .map(|zones| {
+                    let mut zones = zones
+                        .into_values()
+                        .map(|mut zone_peers| {
+                            zone_peers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+                            VecDeque::from(zone_peers)
+                        })
+                        .collect::<Vec<VecDeque<PeerData>>>();
 
-    fn single_miner_results(claim: &Claim, block_seed: u64) -> (U256, Claim) {
-        (claim.get_election_result(block_seed), claim.clone())
+                    round_robin(&mut zones)
+                })
+                .map(VecDeque::from)
+                .collect::<Vec<VecDeque<PeerData>>>();
+
+            round_robin(&mut region_queues)
+        },
     }
+}
 
-    pub(crate) fn get_winner(&self, election_results: &mut BTreeMap<U256, Claim>) -> (U256, Claim) {
-        let mut iter = election_results.iter();
-        let first: (U256, Claim);
-        loop {
-            if let Some((pointer_sum, claim)) = iter.next() {
-                first = (*pointer_sum, claim.clone());
-                break;
+/// Drains the queues by taking one item from each of them in turn.
+fn round_robin<T>(queues: &mut [VecDeque<T>]) -> Vec<T> {
+    let mut drained = Vec::new();
+
+    while queues.iter().any(|queue| !queue.is_empty()) {
+        for queue in queues.iter_mut() {
+            if let Some(item) = queue.pop_front() {
+                drained.push(item);
             }
         }
+    }
+
+    drained
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use primitives::{
+        ChainSpec, ElectionDifficultyParams, EligibilityParams, EmissionCurve,
+        DEFAULT_MIN_DELEGATION, DEFAULT_MIN_STAKE_VALIDATOR, ELECTION_ELIGIBILITY_SCALE,
+    };
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+    use reward::schedule::RewardSchedule;
+    use vrrb_core::{claim::Eligibility, transactions::TransactionDigest};
+
+    use super::*;
+    use crate::{
+        consensus::election_threshold,
+        state_manager::EligibilityPolicy,
+        test_utils::{
+            create_exit_validator_txn, create_mock_full_node_config, create_register_validator_txn,
+            produce_random_claims,
+        },
+    };
+
+    fn farmer_quorum_module() -> QuorumModule {
+        let mut quorum_module = QuorumModule::new(QuorumModuleConfig {
+            membership_config: None,
+            node_config: create_mock_full_node_config(),
+        });
+
+        quorum_module.reconfigure_quorum_membership(QuorumMembershipConfig {
+            quorum_kind: QuorumKind::Farmer,
+            quorum_members: BTreeMap::new(),
+        });
+
+        quorum_module
+    }
+
+    /// Returns a claim for `node_id` along with its owner's request to exit
+    fn exit_of(node_id: &str) -> (ExitValidator, Claim) {
+        let mut claim = produce_random_claims(1).into_iter().next().unwrap();
+        claim.node_id = node_id.into();
+
+        let exit = create_exit_validator_txn(claim.address.clone(), claim.node_id.clone());
+
+        (exit, claim)
+    }
 
-        first
+    fn claim_address() -> SocketAddr {
+        "127.0.0.1:8080".parse().unwrap()
+    }
+
+    fn peer_for(registration: &RegisterValidator) -> PeerData {
+        let addr = "127.0.0.1:0".parse().unwrap();
+
+        PeerData {
+            node_id: registration.node_id.clone(),
+            node_type: NodeType::Validator,
+            kademlia_peer_id: KademliaPeerId::rand(),
+            udp_gossip_addr: addr,
+            raptorq_gossip_addr: addr,
+            kademlia_liveness_addr: addr,
+            alternate_udp_gossip_addrs: vec![],
+            validator_public_key: registration.validator_public_key,
+            locality: Locality::default(),
+            claim_signature: None,
+            event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
+        }
+    }
+
+    #[test]
+    fn registered_validators_join_the_quorum_at_the_next_epoch() {
+        let mut quorum_module = farmer_quorum_module();
+        let genesis_epoch = quorum_module.current_epoch;
+
+        let registration =
+            create_register_validator_txn("node-1".into(), DEFAULT_MIN_STAKE_VALIDATOR);
+        quorum_module
+            .known_peers
+            .insert(registration.node_id.clone(), peer_for(&registration));
+
+        let activation_epoch = quorum_module
+            .queue_validator_registration(registration.clone(), claim_address())
+            .unwrap();
+        assert_eq!(activation_epoch, genesis_epoch + 1);
+
+        let diff = quorum_module
+            .rotate_validator_set(activation_epoch)
+            .unwrap();
+
+        assert_eq!(diff.epoch, activation_epoch);
+        assert!(diff.added.contains_key(&registration.node_id));
+
+        let membership = quorum_module.membership_config.unwrap();
+        assert!(membership
+            .quorum_members
+            .contains_key(&registration.node_id));
+    }
+
+    #[test]
+    fn undiscovered_validators_join_at_their_claim_address() {
+        let mut quorum_module = farmer_quorum_module();
+        let genesis_epoch = quorum_module.current_epoch;
+
+        let registration =
+            create_register_validator_txn("node-1".into(), DEFAULT_MIN_STAKE_VALIDATOR);
+        quorum_module
+            .queue_validator_registration(registration.clone(), claim_address())
+            .unwrap();
+
+        let diff = quorum_module
+            .rotate_validator_set(genesis_epoch + 1)
+            .unwrap();
+
+        let member = &diff.added[&registration.node_id];
+        assert_eq!(member.udp_gossip_address, claim_address());
+        assert_eq!(
+            member.validator_public_key,
+            registration.validator_public_key
+        );
+        assert!(quorum_module.agrees_with(&diff));
+    }
+
+    #[test]
+    fn peer_diffs_the_node_did_not_derive_are_flagged() {
+        let mut quorum_module = farmer_quorum_module();
+        let genesis_epoch = quorum_module.current_epoch;
+
+        let registration =
+            create_register_validator_txn("node-1".into(), DEFAULT_MIN_STAKE_VALIDATOR);
+
+        let mut diff = ValidatorSetDiff::new(genesis_epoch, QuorumKind::Farmer);
+        diff.added.insert(
+            registration.node_id.clone(),
+            quorum_module
+                .member_for(&registration, Some(claim_address()))
+                .unwrap(),
+        );
+
+        assert!(!quorum_module.agrees_with(&diff));
+        assert!(quorum_module
+            .membership_config
+            .as_ref()
+            .unwrap()
+            .quorum_members
+            .is_empty());
+    }
+
+    #[test]
+    fn registered_validators_are_scheduled_from_their_activation_epoch() {
+        let mut quorum_module = farmer_quorum_module();
+        let genesis_epoch = quorum_module.current_epoch;
+
+        let registration =
+            create_register_validator_txn("node-1".into(), DEFAULT_MIN_STAKE_VALIDATOR);
+        quorum_module
+            .queue_validator_registration(registration.clone(), claim_address())
+            .unwrap();
+
+        assert_eq!(
+            quorum_module.scheduled_quorum(&registration.node_id, genesis_epoch),
+            None
+        );
+        assert_eq!(
+            quorum_module.scheduled_quorum(&registration.node_id, genesis_epoch + 1),
+            Some(QuorumKind::Farmer)
+        );
+        assert_eq!(
+            quorum_module.scheduled_quorum(&"node-2".to_string(), genesis_epoch + 1),
+            None
+        );
+    }
+
+    #[test]
+    fn exiting_validators_leave_the_quorum_at_the_next_epoch() {
+        let mut quorum_module = farmer_quorum_module();
+        let genesis_epoch = quorum_module.current_epoch;
+
+        let quorum_members = (0..4)
+            .map(|idx| {
+                let registration = create_register_validator_txn(
+                    format!("node-{idx}"),
+                    DEFAULT_MIN_STAKE_VALIDATOR,
+                );
+                let peer = peer_for(&registration);
+
+                (
+                    peer.node_id.clone(),
+                    QuorumMember {
+                        node_id: peer.node_id,
+                        kademlia_peer_id: peer.kademlia_peer_id,
+                        node_type: peer.node_type,
+                        udp_gossip_address: peer.udp_gossip_addr,
+                        raptorq_gossip_address: peer.raptorq_gossip_addr,
+                        kademlia_liveness_address: peer.kademlia_liveness_addr,
+                        validator_public_key: peer.validator_public_key,
+                    },
+                )
+            })
+            .collect();
+
+        quorum_module.reconfigure_quorum_membership(QuorumMembershipConfig {
+            quorum_kind: QuorumKind::Farmer,
+            quorum_members,
+        });
+
+        let (exit, claim) = exit_of("node-0");
+        let exit = quorum_module.queue_validator_exit(&exit, &claim).unwrap();
+        assert_eq!(exit.exit_epoch, genesis_epoch + 1);
+
+        // NOTE: the exiting member hands its share of the certified txns over
+        // right away
+        let partitions: Vec<TxnPartition> = (0..4)
+            .filter_map(|idx| quorum_module.txn_partition(&format!("node-{idx}")))
+            .collect();
+        let digest = TransactionDigest::from(vec![7; 32]);
+        assert!(!partitions[0].contains(&digest));
+        assert_eq!(
+            partitions[1..]
+                .iter()
+                .filter(|partition| partition.contains(&digest))
+                .count(),
+            1
+        );
+
+        // NOTE: the default threshold requires three signers out of the four members
+        let (exit_1, claim_1) = exit_of("node-1");
+        assert!(quorum_module
+            .queue_validator_exit(&exit_1, &claim_1)
+            .is_err());
+
+        let diff = quorum_module
+            .rotate_validator_set(exit.exit_epoch)
+            .unwrap();
+        assert!(diff.removed.contains(&exit.node_id));
+
+        let membership = quorum_module.membership_config.unwrap();
+        assert_eq!(membership.quorum_members.len(), 3);
+        assert!(!membership.quorum_members.contains_key(&exit.node_id));
+    }
+
+    #[test]
+    fn only_the_owner_of_a_claim_can_request_its_exit() {
+        let mut quorum_module = farmer_quorum_module();
+
+        let (_, claim) = exit_of("node-0");
+        let third_party = produce_random_claims(1).into_iter().next().unwrap();
+        let exit = create_exit_validator_txn(third_party.address, "node-0".into());
+
+        assert!(quorum_module.queue_validator_exit(&exit, &claim).is_err());
+        assert!(!quorum_module.exit_queue.is_exiting(&exit.node_id));
+    }
+
+    #[tokio::test]
+    async fn region_spread_assignment_draws_harvesters_from_every_region() {
+        let mut node_config = create_mock_full_node_config();
+        node_config.quorum_assignment_strategy = QuorumAssignmentStrategy::RegionSpread;
+
+        let quorum_module = QuorumModule::new(QuorumModuleConfig {
+            membership_config: None,
+            node_config,
+        });
+
+        let peer_list = (0..6)
+            .map(|idx| {
+                let registration = create_register_validator_txn(
+                    format!("node-{idx}"),
+                    DEFAULT_MIN_STAKE_VALIDATOR,
+                );
+                let mut peer = peer_for(&registration);
+                let region = if idx < 3 { "us-east" } else { "eu-west" };
+                peer.locality = Locality::new(Some(region.into()), None);
+
+                (peer.node_id.clone(), (peer, false))
+            })
+            .collect::<HashMap<NodeId, (PeerData, bool)>>();
+
+        let assignments = quorum_module
+            .assign_peer_list_to_quorums(peer_list.clone())
+            .await
+            .unwrap();
+
+        let harvester_regions = assignments
+            .values()
+            .filter(|assignment| assignment.quorum_kind == QuorumKind::Harvester)
+            .map(|assignment| {
+                assert_eq!(
+                    assignment.keyset.assignment_strategy,
+                    QuorumAssignmentStrategy::RegionSpread
+                );
+
+                peer_list[&assignment.node_id].0.locality.region.clone()
+            })
+            .collect::<Vec<Option<String>>>();
+
+        assert_eq!(harvester_regions.len(), 2);
+        assert_ne!(harvester_regions[0], harvester_regions[1]);
+    }
+
+    #[tokio::test]
+    async fn sequential_assignment_does_not_depend_on_peer_list_order() {
+        let quorum_module = QuorumModule::new(QuorumModuleConfig {
+            membership_config: None,
+            node_config: create_mock_full_node_config(),
+        });
+
+        let peers = (0..10)
+            .map(|idx| {
+                let registration = create_register_validator_txn(
+                    format!("node-{idx}"),
+                    DEFAULT_MIN_STAKE_VALIDATOR,
+                );
+
+                peer_for(&registration)
+            })
+            .collect::<Vec<PeerData>>();
+
+        let mut harvester_sets = Vec::new();
+        for rotation in 0..peers.len() {
+            // NOTE: every map gets its own hasher keys, so their iteration orders differ too
+            let mut rotated = peers.clone();
+            rotated.rotate_left(rotation);
+
+            let peer_list = rotated
+                .into_iter()
+                .map(|peer| (peer.node_id.clone(), (peer, false)))
+                .collect::<HashMap<NodeId, (PeerData, bool)>>();
+
+            let assignments = quorum_module
+                .assign_peer_list_to_quorums(peer_list)
+                .await
+                .unwrap();
+
+            let harvesters = assignments
+                .values()
+                .filter(|assignment| assignment.quorum_kind == QuorumKind::Harvester)
+                .map(|assignment| assignment.node_id.clone())
+                .collect::<BTreeSet<NodeId>>();
+
+            harvester_sets.push(harvesters);
+        }
+
+        // NOTE: 30% of ten validators, which float math used to round up to four
+        assert_eq!(harvester_sets[0].len(), 3);
+        assert!(harvester_sets.windows(2).all(|sets| sets[0] == sets[1]));
+    }
+
+    /// Replays three epochs worth of the chain state elections are run from,
+    /// handling `claims` in the order given. Returns everything the replay
+    /// derived: the eligibility every block retargeted, the rewards paid out,
+    /// the winner of every miner election, the eligibility and rewards every
+    /// claim ended up with and the quorum elected last
+    fn replay_elections(mut claims: Vec<Claim>) -> String {
+        // NOTE: a few stalled rounds so the eligibility in effect widens too
+        const INTERVALS_SECS: [i64; 4] = [2, 7, 65, 4];
+
+        let mut chain_spec = ChainSpec {
+            epoch_length: 4,
+            eligibility: EligibilityParams {
+                min_claim_age: 1,
+                min_stake_miner: 0,
+                min_uptime_percent: 50,
+            },
+            ..Default::default()
+        };
+        // NOTE: a rate the float math it replaced couldn't represent exactly
+        chain_spec.reward.emission_curve = EmissionCurve::Decay { rate_ppm: 123_457 };
+
+        let difficulty = ElectionDifficultyParams {
+            stall_timeout_secs: 30,
+            ..Default::default()
+        };
+        let policy = EligibilityPolicy::new(chain_spec.eligibility.clone());
+        let reward_schedule = RewardSchedule::new(&chain_spec);
+
+        let mut eligibility = ELECTION_ELIGIBILITY_SCALE / 8;
+        let mut next_reward = reward_schedule.next_reward(&reward_schedule.genesis_reward(None), 0);
+        let mut replay = vec![];
+
+        for height in 1..=3 * chain_spec.epoch_length {
+            let epoch = chain_spec.epoch_at(height);
+            let seed = 0x5eed_u64.wrapping_mul(height as u64);
+            let interval_secs = INTERVALS_SECS[height as usize % INTERVALS_SECS.len()];
+
+            if height == chain_spec.epoch_start(epoch) {
+                for claim in claims.iter_mut() {
+                    claim.eligibility = policy.evaluate(claim, epoch);
+                }
+            }
+
+            let mut block_reward = next_reward.clone();
+            block_reward.current_block = height;
+            next_reward = reward_schedule.next_reward(&block_reward, 0);
+
+            // NOTE: miners are scheduled by their weighted election result, with
+            // ties broken by public key, and the first one within the threshold
+            // in effect mines the block
+            let mut schedule = claims
+                .iter()
+                .enumerate()
+                .filter(|(_, claim)| claim.eligibility == Eligibility::Miner)
+                .map(|(idx, claim)| {
+                    let result = claim.get_weighted_election_result(seed);
+                    (result, claim.public_key.serialize(), idx)
+                })
+                .collect::<Vec<_>>();
+            schedule.sort();
+
+            let threshold =
+                election_threshold(difficulty.eligibility_after(eligibility, interval_secs));
+            let winner = schedule
+                .iter()
+                .position(|(result, ..)| *result <= threshold);
+
+            let held_slots = winner.map_or(0, |slot| slot + 1);
+            for (slot, (.., idx)) in schedule.iter().enumerate().take(held_slots) {
+                claims[*idx].record_mining_slot(epoch, Some(slot) == winner);
+            }
+
+            let winner = winner.map(|slot| {
+                let miner = &mut claims[schedule[slot].2];
+                miner.accrue_reward(block_reward.amount);
+                miner.node_id.clone()
+            });
+
+            eligibility = difficulty.retarget(eligibility, interval_secs);
+            replay.push(format!(
+                "{height}: {eligibility} ppm, {} paid to {winner:?}",
+                block_reward.amount
+            ));
+        }
+
+        let claim_states = claims
+            .iter()
+            .map(|claim| {
+                let state = (claim.eligibility.clone(), claim.accrued_reward());
+                (claim.node_id.clone(), state)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let quorum_claims = claims
+            .into_iter()
+            .filter(|claim| {
+                matches!(
+                    claim.eligibility,
+                    Eligibility::Harvester | Eligibility::Farmer
+                )
+            })
+            .collect();
+
+        let mut quorum = Quorum::new(0x5eed, 12).unwrap();
+        let quorum = quorum.get_final_quorum(quorum_claims).unwrap();
+
+        format!("{replay:?} {claim_states:?} {quorum:?}")
+    }
+
+    #[test]
+    fn elections_are_deterministic_across_the_chain_state_they_are_run_from() {
+        let claims = produce_random_claims(40)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, mut claim)| {
+                claim.node_id = format!("node-{idx}");
+                claim.eligibility = match idx % 4 {
+                    0 => Eligibility::Harvester,
+                    1 => Eligibility::Farmer,
+                    _ => Eligibility::Miner,
+                };
+
+                // NOTE: claims of every age and stake, including stake too small
+                // to weigh on their results
+                claim.record_first_seen(idx as Epoch % 3);
+                claim.delegate(
+                    claim.address.clone(),
+                    DEFAULT_MIN_DELEGATION * (idx as u128 % 5) + idx as u128,
+                );
+
+                claim
+            })
+            .collect::<Vec<Claim>>();
+
+        let expected = replay_elections(claims.clone());
+
+        std::thread::scope(|scope| {
+            let handles = (0..8)
+                .map(|thread| {
+                    let claims = &claims;
+                    scope.spawn(move || {
+                        let mut rng = StdRng::seed_from_u64(thread);
+
+                        (0..16)
+                            .map(|_| {
+                                let mut permuted = claims.clone();
+                                permuted.shuffle(&mut rng);
+
+                                replay_elections(permuted)
+                            })
+                            .collect::<Vec<String>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for handle in handles {
+                for replay in handle.join().unwrap() {
+                    assert_eq!(replay, expected);
+                }
+            }
+        });
     }
 }
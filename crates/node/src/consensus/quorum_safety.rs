@@ -0,0 +1,51 @@
+use telemetry::{error, warn};
+use vrrb_config::{QuorumSafetyMode, ThresholdConfig};
+
+use crate::Result;
+
+/// Checks a quorum of `quorum_size` members stays safe and live signing with
+/// `threshold_config`, see [ThresholdConfig::check_quorum_safety].
+///
+/// Violations are errors unless `mode` is [QuorumSafetyMode::Warn], in which
+/// case they're only logged and the node carries on regardless.
+pub fn enforce_quorum_safety(
+    threshold_config: &ThresholdConfig,
+    quorum_size: usize,
+    mode: QuorumSafetyMode,
+) -> Result<()> {
+    let Err(violation) = threshold_config.check_quorum_safety(quorum_size) else {
+        return Ok(());
+    };
+
+    match mode {
+        QuorumSafetyMode::Enforce => {
+            error!("Refusing unsafe quorum parameters: {violation}");
+            Err(violation.into())
+        },
+        QuorumSafetyMode::Warn => {
+            warn!("Proceeding with UNSAFE quorum parameters, checks only warn: {violation}");
+            Ok(())
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeError;
+
+    #[test]
+    fn unsafe_quorums_are_refused_unless_checks_only_warn() {
+        let config = ThresholdConfig {
+            upper_bound: 7,
+            threshold: 1,
+        };
+
+        assert!(matches!(
+            enforce_quorum_safety(&config, 7, QuorumSafetyMode::Enforce),
+            Err(NodeError::UnsafeQuorum(_))
+        ));
+        assert!(enforce_quorum_safety(&config, 7, QuorumSafetyMode::Warn).is_ok());
+        assert!(enforce_quorum_safety(&config, 4, QuorumSafetyMode::Enforce).is_ok());
+    }
+}
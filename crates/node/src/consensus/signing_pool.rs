@@ -0,0 +1,301 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use block::BlockHash;
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use events::{Event, EventMessage, EventPublisher, Vote};
+use primitives::{
+    FarmerQuorumThreshold, NodeId, NodeIdx, PublicKeyShareVec, RawSignature, TxnValidationStatus,
+};
+use signer::signer::{SignatureProvider, Signer};
+use telemetry::{error, warn};
+use vrrb_config::SigningPoolConfig;
+use vrrb_core::transactions::{TransactionDigest, TransactionKind};
+
+use crate::{NodeError, Result, RUNTIME_EVENTS_TOPIC};
+
+/// Signature work handed off to the [SigningWorkerPool].
+pub enum SigningJob {
+    /// Generates this node's partial signature over a convergence block's
+    /// hash
+    SignConvergenceBlock(SignatureProvider, BlockHash),
+
+    /// Aggregates the partial signatures of a farmer quorum's votes into a
+    /// transaction certificate
+    CertifyTxn {
+        sig_provider: SignatureProvider,
+        votes: Vec<Vote>,
+        txn_id: TransactionDigest,
+        quorum_key: PublicKeyShareVec,
+        farmer_id: NodeId,
+        txn: TransactionKind,
+        quorum_threshold: FarmerQuorumThreshold,
+    },
+}
+
+/// Runs [SigningJob]s on dedicated threads so generating and aggregating
+/// threshold signatures doesn't block the node's event loop.
+///
+/// The outcome of every job is published back to the runtime as an event:
+/// [Event::ConvergenceBlockPartialSignatureCreated] for signed blocks and
+/// [Event::TransactionCertificateCreated] for certified transactions. Jobs
+/// that fail are logged and dropped.
+#[derive(Debug, Clone)]
+pub struct SigningWorkerPool {
+    jobs_tx: Sender<SigningJob>,
+    pending_jobs: Arc<AtomicUsize>,
+}
+
+impl SigningWorkerPool {
+    /// Spawns the pool's workers. They shut down once every handle to the
+    /// pool has been dropped.
+    pub fn new(config: &SigningPoolConfig, events_tx: EventPublisher) -> Self {
+        let (jobs_tx, jobs_rx) = crossbeam_channel::bounded(config.queue_capacity);
+        let pending_jobs = Arc::new(AtomicUsize::new(0));
+
+        for worker_idx in 0..config.workers.max(1) {
+            let jobs_rx = jobs_rx.clone();
+            let events_tx = events_tx.clone();
+            let pending_jobs = pending_jobs.clone();
+
+            let spawned = thread::Builder::new()
+                .name(format!("signing-worker-{worker_idx}"))
+                .spawn(move || run_worker(jobs_rx, events_tx, pending_jobs));
+
+            if let Err(err) = spawned {
+                error!("failed to spawn signing worker {worker_idx}: {err}");
+            }
+        }
+
+        Self {
+            jobs_tx,
+            pending_jobs,
+        }
+    }
+
+    /// Queues a job without waiting for a worker to pick it up. Fails if the
+    /// queue is full.
+    pub fn submit(&self, job: SigningJob) -> Result<()> {
+        self.pending_jobs.fetch_add(1, Ordering::Relaxed);
+
+        self.jobs_tx.try_send(job).map_err(|err| {
+            self.pending_jobs.fetch_sub(1, Ordering::Relaxed);
+
+            match err {
+                TrySendError::Full(_) => NodeError::Unavailable("signing queue".to_string()),
+                TrySendError::Disconnected(_) => {
+                    NodeError::Other("signing workers have shut down".to_string())
+                },
+            }
+        })
+    }
+
+    /// Returns the number of jobs queued or running
+    pub fn pending_jobs(&self) -> usize {
+        self.pending_jobs.load(Ordering::Relaxed)
+    }
+}
+
+fn run_worker(
+    jobs_rx: Receiver<SigningJob>,
+    events_tx: EventPublisher,
+    pending_jobs: Arc<AtomicUsize>,
+) {
+    for job in jobs_rx.iter() {
+        let event = run_job(job);
+        pending_jobs.fetch_sub(1, Ordering::Relaxed);
+
+        if let Some(event) = event {
+            let em = EventMessage::new(Some(RUNTIME_EVENTS_TOPIC.into()), event);
+
+            if events_tx.blocking_send(em).is_err() {
+                warn!("signing worker stopping: events channel closed");
+                return;
+            }
+        }
+    }
+}
+
+fn run_job(job: SigningJob) -> Option<Event> {
+    match job {
+        SigningJob::SignConvergenceBlock(sig_provider, block_hash) => {
+            sign_convergence_block(&sig_provider, block_hash)
+        },
+        SigningJob::CertifyTxn {
+            sig_provider,
+            votes,
+            txn_id,
+            quorum_key,
+            farmer_id,
+            txn,
+            quorum_threshold,
+        } => certify_txn(
+            &sig_provider,
+            votes,
+            txn_id,
+            quorum_key,
+            farmer_id,
+            txn,
+            quorum_threshold,
+        ),
+    }
+}
+
+fn sign_convergence_block(
+    sig_provider: &SignatureProvider,
+    block_hash: BlockHash,
+) -> Option<Event> {
+    let block_hash_bytes = hex::decode(&block_hash)
+        .map_err(|err| error!("invalid convergence block hash {block_hash}: {err}"))
+        .ok()?;
+
+    let partial_signature = sig_provider
+        .generate_partial_signature(block_hash_bytes)
+        .map_err(|err| error!("failed to sign convergence block {block_hash}: {err:?}"))
+        .ok()?;
+
+    let public_key_share = sig_provider
+        .dkg_state
+        .read()
+        .ok()?
+        .secret_key_share_owned()?
+        .public_key_share();
+
+    Some(Event::ConvergenceBlockPartialSignatureCreated {
+        block_hash,
+        public_key_share,
+        partial_signature,
+    })
+}
+
+fn certify_txn(
+    sig_provider: &SignatureProvider,
+    votes: Vec<Vote>,
+    txn_id: TransactionDigest,
+    quorum_key: PublicKeyShareVec,
+    farmer_id: NodeId,
+    txn: TransactionKind,
+    quorum_threshold: FarmerQuorumThreshold,
+) -> Option<Event> {
+    // NOTE: votes are split by verdict and the certificate is aggregated over
+    // whichever verdict most farmers agreed on
+    let mut vote_shares: BTreeMap<bool, BTreeMap<NodeIdx, RawSignature>> = BTreeMap::new();
+    for vote in votes.iter() {
+        vote_shares
+            .entry(vote.is_txn_valid)
+            .or_default()
+            .insert(vote.farmer_node_id, vote.signature.clone());
+    }
+
+    let (is_txn_valid, signature_shares) = vote_shares
+        .into_iter()
+        .max_by_key(|(_, signature_shares)| signature_shares.len())?;
+
+    let signature = sig_provider
+        .generate_quorum_signature(quorum_threshold as u16, signature_shares)
+        .map_err(|err| error!("failed to certify transaction {txn_id}: {err:?}"))
+        .ok()?;
+
+    let execution_result = votes
+        .iter()
+        .find(|vote| vote.is_txn_valid == is_txn_valid)
+        .and_then(|vote| vote.execution_result.clone())
+        .unwrap_or_default();
+
+    let is_valid = if is_txn_valid {
+        TxnValidationStatus::Valid
+    } else {
+        TxnValidationStatus::Invalid
+    };
+
+    Some(Event::TransactionCertificateCreated {
+        votes,
+        signature,
+        digest: txn_id,
+        quorum_key,
+        execution_result,
+        farmer_id,
+        txn: Box::new(txn),
+        is_valid,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, RwLock},
+        time::Duration,
+    };
+
+    use dkg_engine::{prelude::DkgState, test_utils::generate_dkg_engine_with_states};
+    use vrrb_config::ThresholdConfig;
+
+    use super::*;
+
+    fn sig_provider(dkg_state: DkgState) -> SignatureProvider {
+        SignatureProvider::new(
+            Arc::new(RwLock::new(dkg_state)),
+            ThresholdConfig {
+                threshold: 1,
+                upper_bound: 4,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn convergence_blocks_are_signed_by_the_workers() {
+        let dkg_engine = generate_dkg_engine_with_states().await.pop().unwrap();
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(10);
+
+        let pool = SigningWorkerPool::new(&SigningPoolConfig::default(), events_tx);
+        let block_hash = hex::encode([7u8; 32]);
+
+        pool.submit(SigningJob::SignConvergenceBlock(
+            sig_provider(dkg_engine.dkg_state),
+            block_hash.clone(),
+        ))
+        .unwrap();
+
+        let em = tokio::time::timeout(Duration::from_secs(5), events_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        match em.into() {
+            Event::ConvergenceBlockPartialSignatureCreated {
+                block_hash: signed_hash,
+                partial_signature,
+                ..
+            } => {
+                assert_eq!(signed_hash, block_hash);
+                assert!(!partial_signature.is_empty());
+            },
+            event => panic!("unexpected event {event:?}"),
+        }
+
+        assert_eq!(pool.pending_jobs(), 0);
+    }
+
+    #[tokio::test]
+    async fn failed_jobs_publish_nothing() {
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(10);
+
+        let pool = SigningWorkerPool::new(&SigningPoolConfig::default(), events_tx);
+
+        pool.submit(SigningJob::SignConvergenceBlock(
+            sig_provider(DkgState::new()),
+            hex::encode([7u8; 32]),
+        ))
+        .unwrap();
+
+        let received = tokio::time::timeout(Duration::from_millis(200), events_rx.recv()).await;
+
+        assert!(received.is_err());
+    }
+}
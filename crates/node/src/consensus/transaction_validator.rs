@@ -5,7 +5,7 @@ use std::collections::{BTreeMap, HashMap};
 
 use block::ConvergenceBlock;
 use crossbeam_channel::Receiver;
-use events::{Event, EventPublisher, JobResult, Vote};
+use events::{Event, EventPublisher, InclusionList, JobResult, Vote};
 use job_scheduler::JobScheduler;
 use mempool::TxnRecord;
 use primitives::{base::PeerId as PeerID, ByteVec, FarmerQuorumThreshold, NodeIdx};
@@ -69,6 +69,7 @@ pub enum Job {
             ByteVec,
             SignatureProvider,
             FarmerQuorumThreshold,
+            InclusionList,
         ),
     ),
     CertifyTxn(
@@ -112,6 +113,7 @@ impl TransactionValidatorEngine {
         quorum_public_key: ByteVec,
         sig_provider: SignatureProvider,
         quorum_threshold: FarmerQuorumThreshold,
+        inclusion_list: InclusionList,
     ) {
         let transactions: Vec<Txn> = txns.iter().map(|x| x.1.txn.clone()).collect();
         let validated_txns: Vec<_> = self
@@ -150,6 +152,7 @@ impl TransactionValidatorEngine {
                                         quorum_threshold: farmer_quorum_threshold,
                                         execution_result: None,
                                         is_txn_valid: txn.1.is_err(),
+                                        inclusion_list: inclusion_list.clone(),
                                     });
                                 }
                             }
@@ -182,6 +185,7 @@ impl TransactionValidatorEngine {
                         quorum_public_key,
                         sig_provider,
                         farmer_quorum_threshold,
+                        inclusion_list,
                     )) => {
                         let transactions: Vec<Txn> = txns.iter().map(|x| x.1.txn.clone()).collect();
                         let validated_txns: Vec<_> = self
@@ -221,6 +225,7 @@ impl TransactionValidatorEngine {
                                                         quorum_threshold: farmer_quorum_threshold,
                                                         execution_result: None,
                                                         is_txn_valid: txn.1.is_err(),
+                                                        inclusion_list: inclusion_list.clone(),
                                                     });
                                                 }
                                             }
@@ -0,0 +1,144 @@
+use primitives::{NodeId, Round, TxnPartitionParams};
+use vrrb_config::QuorumMembershipConfig;
+use vrrb_core::transactions::TransactionDigest;
+
+/// The share of the certified transaction queue a harvester is responsible
+/// for including in its proposal blocks.
+///
+/// Transactions are assigned to harvesters by their digest, modulo the size of
+/// the quorum, so members of the same quorum never propose the same certified
+/// transaction within a round. Transactions whose harvester didn't propose
+/// them within [TxnPartitionParams::failover_rounds] are opened up to every
+/// member, so an offline harvester doesn't strand its share of the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxnPartition {
+    member_index: usize,
+    member_count: usize,
+}
+
+impl TxnPartition {
+    pub fn new(member_index: usize, member_count: usize) -> Self {
+        let member_count = member_count.max(1);
+
+        Self {
+            member_index: member_index % member_count,
+            member_count,
+        }
+    }
+
+    /// A partition without a share of its own among `member_count` members,
+    /// for members that only pick up the txns other members left waiting
+    pub fn failover_only(member_count: usize) -> Self {
+        let member_count = member_count.max(1);
+
+        Self {
+            member_index: member_count,
+            member_count,
+        }
+    }
+
+    /// Computes the partition of `node_id` within a quorum. Members are
+    /// indexed by their position within the quorum's ordered member list, so
+    /// every member derives the same assignment. Returns `None` if the node is
+    /// not part of the quorum.
+    pub fn for_member(node_id: &NodeId, membership: &QuorumMembershipConfig) -> Option<Self> {
+        let member_index = membership
+            .quorum_members
+            .keys()
+            .position(|member_id| member_id == node_id)?;
+
+        Some(Self::new(member_index, membership.quorum_members.len()))
+    }
+
+    /// Returns the index of the member a transaction is assigned to.
+    pub fn assignee(&self, digest: &TransactionDigest) -> usize {
+        let mut prefix = [0u8; 8];
+        digest
+            .as_bytes()
+            .iter()
+            .take(prefix.len())
+            .enumerate()
+            .for_each(|(idx, byte)| prefix[idx] = *byte);
+
+        (u64::from_be_bytes(prefix) % self.member_count as u64) as usize
+    }
+
+    pub fn contains(&self, digest: &TransactionDigest) -> bool {
+        self.assignee(digest) == self.member_index
+    }
+
+    /// Returns whether the member may propose a transaction at `round`,
+    /// either because it is assigned to it or because the member it is
+    /// assigned to missed the rounds it was given to propose it.
+    pub fn may_propose(
+        &self,
+        digest: &TransactionDigest,
+        certified_at: Option<Round>,
+        round: Round,
+        params: &TxnPartitionParams,
+    ) -> bool {
+        let failed_over = certified_at.map_or(false, |certified_at| {
+            certified_at.saturating_add(params.failover_rounds) <= round
+        });
+
+        failed_over || self.contains(digest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digests(count: u8) -> Vec<TransactionDigest> {
+        (0..count)
+            .map(|n| TransactionDigest::from(vec![n; 32]))
+            .collect()
+    }
+
+    #[test]
+    fn every_txn_is_assigned_to_exactly_one_member() {
+        let member_count = 4;
+        let partitions: Vec<TxnPartition> = (0..member_count)
+            .map(|idx| TxnPartition::new(idx, member_count))
+            .collect();
+
+        for digest in digests(64) {
+            let owners = partitions
+                .iter()
+                .filter(|partition| partition.contains(&digest))
+                .count();
+
+            assert_eq!(owners, 1);
+        }
+    }
+
+    #[test]
+    fn txns_fail_over_to_every_member_once_their_harvester_missed_them() {
+        let params = TxnPartitionParams { failover_rounds: 2 };
+        let partitions: Vec<TxnPartition> = (0..4).map(|idx| TxnPartition::new(idx, 4)).collect();
+
+        for digest in digests(16) {
+            let proposers = |certified_at, round| {
+                partitions
+                    .iter()
+                    .filter(|partition| {
+                        partition.may_propose(&digest, certified_at, round, &params)
+                    })
+                    .count()
+            };
+
+            assert_eq!(proposers(Some(10), 11), 1);
+            assert_eq!(proposers(Some(10), 12), 4);
+
+            // NOTE: txns whose certification wasn't witnessed never fail over
+            assert_eq!(proposers(None, 100), 1);
+        }
+    }
+
+    #[test]
+    fn single_member_quorums_own_every_txn() {
+        let partition = TxnPartition::new(0, 1);
+
+        assert!(digests(16).iter().all(|digest| partition.contains(digest)));
+    }
+}
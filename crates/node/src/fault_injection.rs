@@ -0,0 +1,293 @@
+//! Byzantine fault injection hooks for integration tests.
+//!
+//! Only compiled in when the `byzantine` feature is enabled. Every event a
+//! node's actors publish passes through the node's [FaultInjector] on its way
+//! to the router, so the test harness can make the node misbehave towards its
+//! peers at runtime. Events the node relays on behalf of its peers are left
+//! untouched.
+use std::{
+    mem::{discriminant, Discriminant},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use block::ProposalBlock;
+use events::{Event, EventMessage};
+use primitives::NodeId;
+use tokio::sync::mpsc::{self, Receiver};
+use vrrb_core::keypair::{MinerPublicKey, MinerSecretKey};
+
+/// A kind of misbehavior a node can be told to exhibit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ByzantineFault {
+    /// Withholds the node's own DKG part commitments from its peers
+    DropParts,
+
+    /// Flips the bits of every convergence block partial signature the node
+    /// produces
+    CorruptPartialSignatures,
+
+    /// Follows every proposal block the node mines with a conflicting one for
+    /// the same round, signed with the node's key
+    EquivocateProposals,
+
+    /// Holds back validated transaction votes for the given duration
+    DelayVotes(Duration),
+}
+
+/// Shared switchboard of the faults a node is currently exhibiting. Clones
+/// refer to the same set of faults, so a test can keep one around and change
+/// the node's behavior while it runs.
+#[derive(Debug, Clone)]
+pub struct FaultInjector {
+    node_id: NodeId,
+    miner_public_key: MinerPublicKey,
+    miner_secret_key: MinerSecretKey,
+    faults: Arc<RwLock<Vec<ByzantineFault>>>,
+}
+
+impl FaultInjector {
+    /// Creates an injector for the node identified by `node_id`, which signs
+    /// the blocks it mines with `miner_secret_key`.
+    pub fn new(node_id: NodeId, miner_secret_key: MinerSecretKey) -> Self {
+        let secp = secp256k1::Secp256k1::signing_only();
+
+        Self {
+            node_id,
+            miner_public_key: MinerPublicKey::from_secret_key(&secp, &miner_secret_key),
+            miner_secret_key,
+            faults: Arc::default(),
+        }
+    }
+
+    /// Enables a fault, replacing any previously injected fault of the same
+    /// kind.
+    pub fn inject(&self, fault: ByzantineFault) {
+        if let Ok(mut faults) = self.faults.write() {
+            faults.retain(|existing| discriminant(existing) != discriminant(&fault));
+            faults.push(fault);
+        }
+    }
+
+    /// Disables every fault of the same kind as the one given.
+    pub fn heal(&self, fault: &ByzantineFault) {
+        if let Ok(mut faults) = self.faults.write() {
+            faults.retain(|existing| discriminant(existing) != discriminant(fault));
+        }
+    }
+
+    /// Makes the node behave honestly again.
+    pub fn heal_all(&self) {
+        if let Ok(mut faults) = self.faults.write() {
+            faults.clear();
+        }
+    }
+
+    pub fn faults(&self) -> Vec<ByzantineFault> {
+        self.faults
+            .read()
+            .map(|faults| faults.clone())
+            .unwrap_or_default()
+    }
+
+    fn find(&self, kind: Discriminant<ByzantineFault>) -> Option<ByzantineFault> {
+        self.faults
+            .read()
+            .ok()?
+            .iter()
+            .find(|fault| discriminant(*fault) == kind)
+            .cloned()
+    }
+
+    fn is_injected(&self, fault: &ByzantineFault) -> bool {
+        self.find(discriminant(fault)).is_some()
+    }
+
+    /// Rewrites an event published by the node according to the currently
+    /// injected faults. Returns no events if it should be withheld, or more
+    /// than one if the node should equivocate.
+    pub(crate) fn apply(&self, event: Event) -> Vec<Event> {
+        match event {
+            Event::PartCommitmentCreated(ref node_id, _)
+                if *node_id == self.node_id && self.is_injected(&ByzantineFault::DropParts) =>
+            {
+                telemetry::warn!("fault injection: withholding part commitment");
+                vec![]
+            },
+
+            Event::ConvergenceBlockPartialSignatureCreated {
+                block_hash,
+                public_key_share,
+                partial_signature,
+            } if self.is_injected(&ByzantineFault::CorruptPartialSignatures) => {
+                telemetry::warn!("fault injection: corrupting partial signature");
+                vec![Event::ConvergenceBlockPartialSignatureCreated {
+                    block_hash,
+                    public_key_share,
+                    partial_signature: corrupt(partial_signature),
+                }]
+            },
+
+            Event::ProposalBlockCreated(block)
+                if block.from.public_key == self.miner_public_key
+                    && self.is_injected(&ByzantineFault::EquivocateProposals) =>
+            {
+                match self.conflicting_proposal(&block) {
+                    Some(conflicting) => {
+                        telemetry::warn!(
+                            "fault injection: equivocating on proposal block for round {}",
+                            block.round
+                        );
+
+                        vec![
+                            Event::ProposalBlockCreated(block),
+                            Event::ProposalBlockCreated(conflicting),
+                        ]
+                    },
+                    None => vec![Event::ProposalBlockCreated(block)],
+                }
+            },
+
+            event => vec![event],
+        }
+    }
+
+    /// Mines a second proposal block for the same round as `block`, leaving
+    /// out one of its txns or claims so it hashes differently. Returns `None`
+    /// for empty blocks, which leave nothing to conflict on.
+    fn conflicting_proposal(&self, block: &ProposalBlock) -> Option<ProposalBlock> {
+        let mut txns = block.txns.clone();
+        let mut claims = block.claims.clone();
+
+        if txns.pop_back().is_none() && claims.pop_back().is_none() {
+            return None;
+        }
+
+        Some(ProposalBlock::build(
+            block.ref_block.clone(),
+            block.round,
+            block.epoch,
+            txns,
+            claims,
+            block.inclusion_list.clone(),
+            block.halt_directives.clone(),
+            block.from.clone(),
+            &self.miner_secret_key,
+        ))
+    }
+
+    /// Returns how long an event should be held back before being published.
+    pub(crate) fn delay_for(&self, event: &Event) -> Option<Duration> {
+        match event {
+            Event::TxnsValidated { .. } => {
+                match self.find(discriminant(&ByzantineFault::DelayVotes(Duration::ZERO))) {
+                    Some(ByzantineFault::DelayVotes(delay)) => Some(delay),
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Spawns a task that feeds the events the node publishes through the
+    /// injector before they reach the router, returning the queue the router
+    /// should consume from instead.
+    pub fn spawn(&self, mut events_rx: Receiver<EventMessage>) -> Receiver<EventMessage> {
+        let (tx, rx) = mpsc::channel(events::DEFAULT_BUFFER);
+        let injector = self.clone();
+
+        tokio::spawn(async move {
+            while let Some(message) = events_rx.recv().await {
+                let event = match &message.data {
+                    messr::MessageData::Data(event) => event.clone(),
+                    messr::MessageData::StopSignal => Event::Stop,
+                    _ => Event::NoOp,
+                };
+
+                let is_stop = matches!(event, Event::Stop);
+
+                // NOTE: events published as they are keep their topic, only rewritten ones
+                // are republished
+                let rewritten = injector.apply(event.clone());
+                let messages = if rewritten.len() == 1 && rewritten[0] == event {
+                    vec![message]
+                } else {
+                    rewritten.into_iter().map(EventMessage::from).collect()
+                };
+
+                for message in messages {
+                    if let Some(delay) = injector.delay_for(&event) {
+                        telemetry::warn!("fault injection: delaying votes by {delay:?}");
+
+                        let tx = tx.clone();
+                        tokio::spawn(async move {
+                            tokio::time::sleep(delay).await;
+                            let _ = tx.send(message).await;
+                        });
+
+                        continue;
+                    }
+
+                    if tx.send(message).await.is_err() {
+                        return;
+                    }
+                }
+
+                if is_stop {
+                    break;
+                }
+            }
+        });
+
+        rx
+    }
+}
+
+/// Flips every bit of a signature so it keeps its length but no longer
+/// verifies.
+fn corrupt(mut signature: Vec<u8>) -> Vec<u8> {
+    signature.iter_mut().for_each(|byte| *byte = !*byte);
+    signature
+}
+
+#[cfg(test)]
+mod tests {
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+
+    fn injector() -> FaultInjector {
+        let keypair = Keypair::random();
+        FaultInjector::new("node-1".into(), *keypair.get_miner_secret_key())
+    }
+
+    #[test]
+    fn honest_injector_passes_events_through() {
+        let injector = injector();
+
+        assert_eq!(injector.apply(Event::NoOp), vec![Event::NoOp]);
+        assert!(injector.faults().is_empty());
+    }
+
+    #[test]
+    fn injected_faults_can_be_replaced_and_healed() {
+        let injector = injector();
+        let votes = Event::TxnsValidated {
+            votes: vec![],
+            quorum_threshold: 1,
+        };
+
+        injector.inject(ByzantineFault::DelayVotes(Duration::from_secs(1)));
+        injector.inject(ByzantineFault::DelayVotes(Duration::from_secs(5)));
+        injector.inject(ByzantineFault::CorruptPartialSignatures);
+
+        assert_eq!(injector.faults().len(), 2);
+        assert_eq!(injector.delay_for(&votes), Some(Duration::from_secs(5)));
+
+        injector.heal(&ByzantineFault::DelayVotes(Duration::ZERO));
+        assert_eq!(injector.delay_for(&votes), None);
+
+        injector.heal_all();
+        assert!(injector.faults().is_empty());
+    }
+}
@@ -1,6 +1,10 @@
 pub mod result;
 
+#[cfg(feature = "byzantine")]
+pub mod fault_injection;
 pub mod node;
+mod optional_module;
+mod preflight;
 mod runtime_component;
 mod runtime_module;
 
@@ -8,20 +12,32 @@ pub(crate) mod api;
 pub(crate) mod consensus;
 pub(crate) mod data_store;
 pub(crate) mod indexer_module;
+#[cfg(feature = "mining")]
 pub(crate) mod mining_module;
 pub(crate) mod network;
+pub(crate) mod notification_module;
+pub(crate) mod replication;
 pub(crate) mod runtime;
 pub(crate) mod state_manager;
 pub(crate) mod state_reader;
 pub(crate) mod ui;
 
 pub mod test_utils;
+#[cfg(feature = "time-travel")]
+mod time_travel;
 
+#[cfg(feature = "byzantine")]
+pub use fault_injection::*;
+pub use optional_module::*;
+pub use preflight::*;
 pub use result::*;
 pub use runtime::*;
 pub use runtime_component::*;
 pub use runtime_module::*;
 
+pub use network::{PreconnectMonitor, PreconnectStats, RebroadcastMonitor, RebroadcastStats};
+pub use replication::{ReplicationMonitor, ReplicationStats};
+
 pub use crate::node::*;
 
 /// Represents the number of packets that can be lost and still be able to
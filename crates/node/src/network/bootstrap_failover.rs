@@ -0,0 +1,107 @@
+use std::{net::SocketAddr, time::Duration};
+
+use primitives::{dial_order, AddressFamily, NodeId};
+use vrrb_config::BootstrapConfig;
+
+use crate::network::NetworkEvent;
+
+/// Health checks `candidates` in order and returns the first one that
+/// responds within `timeout`.
+///
+/// A candidate is healthy if it accepts a ping over QUIC, which can't
+/// complete without a handshake with the candidate. Candidates reachable at
+/// several addresses are tried at each of them, those of `preferred_family`
+/// first, and are returned with the address that responded as their UDP
+/// gossip address.
+pub async fn select_bootstrap_node(
+    dyswarm_client: &mut dyswarm::client::Client,
+    node_id: &NodeId,
+    candidates: Vec<BootstrapConfig>,
+    timeout: Duration,
+    preferred_family: AddressFamily,
+) -> Option<BootstrapConfig> {
+    for candidate in candidates {
+        for addr in dial_order(&candidate.udp_gossip_addrs(), preferred_family) {
+            let ping = dyswarm::types::Message::new(NetworkEvent::Ping(node_id.clone()));
+
+            match tokio::time::timeout(timeout, dyswarm_client.send_data_via_quic(ping, addr)).await
+            {
+                Ok(Ok(_)) => {
+                    telemetry::info!("Bootstrapping through {addr}");
+                    return Some(reachable_at(candidate, addr));
+                },
+                Ok(Err(err)) => {
+                    telemetry::warn!("Bootstrap node {addr} is unreachable: {err}");
+                },
+                Err(_) => {
+                    telemetry::warn!("Bootstrap node {addr} did not respond within {timeout:?}");
+                },
+            }
+        }
+    }
+
+    None
+}
+
+/// Makes `addr` the UDP gossip address of a bootstrap node, keeping its other
+/// addresses as alternates
+fn reachable_at(mut candidate: BootstrapConfig, addr: SocketAddr) -> BootstrapConfig {
+    let addrs = candidate.udp_gossip_addrs();
+
+    candidate.udp_gossip_addr = addr;
+    candidate.alternate_udp_gossip_addrs = addrs
+        .into_iter()
+        .filter(|alternate| *alternate != addr)
+        .collect();
+
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use events::{PeerData, LEGACY_EVENT_SCHEMA_VERSION};
+    use primitives::{DbBackend, KademliaPeerId, NodeType};
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+    use crate::network::PeerStore;
+
+    #[test]
+    fn bootstrap_nodes_are_remembered_with_every_address() {
+        let path = std::env::temp_dir().join(format!("peer-store-{}", uuid::Uuid::new_v4()));
+        let ipv4_addr: SocketAddr = "127.0.0.1:9001".parse().unwrap();
+        let ipv6_addr: SocketAddr = "[::1]:9001".parse().unwrap();
+
+        let peer = PeerData {
+            node_id: "node-9001".to_string(),
+            node_type: NodeType::Validator,
+            kademlia_peer_id: KademliaPeerId::rand(),
+            udp_gossip_addr: ipv4_addr,
+            raptorq_gossip_addr: ipv4_addr,
+            kademlia_liveness_addr: ipv4_addr,
+            alternate_udp_gossip_addrs: vec![ipv6_addr],
+            validator_public_key: Keypair::random().validator_public_key_owned(),
+            locality: Default::default(),
+            claim_signature: None,
+            event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
+        };
+
+        PeerStore::open(&path, DbBackend::default(), 2)
+            .unwrap()
+            .record_seen(&peer, 0)
+            .unwrap();
+
+        let known_peer = PeerStore::open(&path, DbBackend::default(), 2)
+            .unwrap()
+            .bootstrap_candidates(2)
+            .remove(0);
+
+        assert_eq!(known_peer.udp_gossip_addrs(), vec![ipv4_addr, ipv6_addr]);
+
+        // NOTE: a node reached over IPv6 is bootstrapped through that address,
+        // with its IPv4 one kept as a fallback
+        let reached = reachable_at(known_peer, ipv6_addr);
+        assert_eq!(reached.udp_gossip_addr, ipv6_addr);
+        assert_eq!(reached.alternate_udp_gossip_addrs, vec![ipv4_addr]);
+    }
+}
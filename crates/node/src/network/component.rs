@@ -13,6 +13,7 @@ use dyswarm::{
 use events::{AssignedQuorumMembership, Event, EventMessage, EventPublisher, EventSubscriber};
 use hbbft::{crypto::PublicKey as ThresholdSignaturePublicKey, sync_key_gen::Part};
 use kademlia_dht::{Key, Node as KademliaNode, NodeData};
+use mempool::MempoolReadHandleFactory;
 use primitives::{KademliaPeerId, NodeId, NodeType, ValidatorPublicKey};
 use storage::vrrbdb::VrrbDbReadHandle;
 use telemetry::info;
@@ -22,7 +23,7 @@ use utils::payload::digest_data_to_bytes;
 use vrrb_config::{BootstrapQuorumConfig, NodeConfig, QuorumMembershipConfig};
 use vrrb_core::claim::Claim;
 
-use super::NetworkEvent;
+use super::{NetworkEvent, PreconnectMonitor, RebroadcastMonitor};
 use crate::{
     network::DyswarmHandler, result::Result, NodeError, RuntimeComponent, RuntimeComponentHandle,
     DEFAULT_ERASURE_COUNT,
@@ -42,6 +43,7 @@ pub struct NetworkModuleComponentConfig {
     pub membership_config: Option<QuorumMembershipConfig>,
     pub bootstrap_quorum_config: Option<BootstrapQuorumConfig>,
     pub validator_public_key: ValidatorPublicKey,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
 }
 
 #[derive(Debug, Clone)]
@@ -50,6 +52,8 @@ pub struct NetworkModuleComponentResolvedData {
     pub resolved_kademlia_liveness_address: SocketAddr,
     pub resolved_udp_gossip_address: SocketAddr,
     pub resolved_raptorq_gossip_address: SocketAddr,
+    pub rebroadcast_monitor: RebroadcastMonitor,
+    pub preconnect_monitor: PreconnectMonitor,
 }
 
 #[async_trait]
@@ -60,6 +64,40 @@ impl RuntimeComponent<NetworkModuleComponentConfig, NetworkModuleComponentResolv
         args: NetworkModuleComponentConfig,
     ) -> crate::Result<RuntimeComponentHandle<NetworkModuleComponentResolvedData>> {
         let mut network_events_rx = args.network_events_rx;
+        let mempool_sync_config = args.config.mempool_sync_config.clone();
+
+        mempool_sync_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        args.config
+            .peer_registration_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        args.config
+            .message_credits_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        args.config
+            .peer_store_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        args.config
+            .bootstrap_failover_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let rebroadcast_config = args.config.rebroadcast_config.clone();
+
+        rebroadcast_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let reconciliation_events_tx = args.events_tx.clone();
+        let rebroadcast_events_tx = args.events_tx.clone();
 
         let network_module_config = NetworkModuleConfig {
             node_id: args.node_id.clone(),
@@ -69,9 +107,26 @@ impl RuntimeComponent<NetworkModuleComponentConfig, NetworkModuleComponentResolv
             kademlia_peer_id: args.config.kademlia_peer_id,
             kademlia_liveness_addr: args.config.kademlia_liveness_address,
             bootstrap_node_config: args.config.bootstrap_config,
+            bootstrap_failover_config: args.config.bootstrap_failover_config.clone(),
+            peer_store_path: args.config.db_path().clone(),
+            db_backend: args.config.db_backend,
+            peer_store_config: args.config.peer_store_config.clone(),
             events_tx: args.events_tx,
             membership_config: args.membership_config,
             validator_public_key: args.validator_public_key,
+            locality: args.config.locality.clone(),
+            claim_secret_key: args.config.keypair.miner_secret_key_owned(),
+            peer_registration_config: args.config.peer_registration_config.clone(),
+            staking: args.config.chain_spec.staking.clone(),
+            chain_id: args.config.chain_spec.chain_id.clone(),
+            chain_spec_version: args.config.chain_spec.version,
+            vrrbdb_read_handle: args.vrrbdb_read_handle.clone(),
+            bootstrap_quorum_config: args.bootstrap_quorum_config.clone(),
+            mempool_read_handle_factory: args.mempool_read_handle_factory,
+            mempool_sync_config: mempool_sync_config.clone(),
+            message_credits_config: args.config.message_credits_config.clone(),
+            network_address_config: args.config.network_address_config.clone(),
+            rebroadcast_config: rebroadcast_config.clone(),
         };
 
         let mut network_module = NetworkModule::new(network_module_config).await?;
@@ -81,6 +136,8 @@ impl RuntimeComponent<NetworkModuleComponentConfig, NetworkModuleComponentResolv
         let kademlia_dht_resolved_id = network_module.kademlia_peer_id();
         let resolved_kademlia_liveness_address = network_module.kademlia_liveness_addr();
         let resolved_raptorq_gossip_address = network_module.raptorq_gossip_addr();
+        let rebroadcast_monitor = network_module.rebroadcast_monitor();
+        let preconnect_monitor = network_module.preconnect_monitor();
 
         let is_not_bootstrap = !network_module.is_bootstrap();
 
@@ -97,6 +154,41 @@ impl RuntimeComponent<NetworkModuleComponentConfig, NetworkModuleComponentResolv
                 .map_err(|err| NodeError::Other(err.to_string()))
         });
 
+        // NOTE: periodically reconciles the local mempool with a random peer's, so txns
+        // whose broadcast was missed are eventually recovered
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(mempool_sync_config.interval);
+
+            loop {
+                interval.tick().await;
+
+                let em = EventMessage::new(
+                    Some("network-events".into()),
+                    Event::MempoolReconciliationDue,
+                );
+
+                if reconciliation_events_tx.send(em).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        // NOTE: periodically re-sends the consensus messages peers haven't acknowledged,
+        // so parts and votes lost in transit still make it through
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(rebroadcast_config.check_interval);
+
+            loop {
+                interval.tick().await;
+
+                let em = EventMessage::new(Some("network-events".into()), Event::RebroadcastDue);
+
+                if rebroadcast_events_tx.send(em).await.is_err() {
+                    break;
+                }
+            }
+        });
+
         info!("Network module is operational");
 
         let network_component_resolved_data = NetworkModuleComponentResolvedData {
@@ -104,6 +196,8 @@ impl RuntimeComponent<NetworkModuleComponentConfig, NetworkModuleComponentResolv
             resolved_kademlia_liveness_address,
             resolved_udp_gossip_address,
             resolved_raptorq_gossip_address,
+            rebroadcast_monitor,
+            preconnect_monitor,
         };
 
         let component_handle =
@@ -0,0 +1,90 @@
+use std::{collections::HashSet, net::SocketAddr};
+
+use primitives::NodeId;
+use vrrb_config::QuorumMembershipConfig;
+
+/// Orders the peers a consensus message is sent to so that members of the
+/// node's current quorum are reached first and general gossip peers after.
+///
+/// Consensus messages such as DKG parts only matter to the quorum they belong
+/// to, so delivering them to its members before anyone else shortens the time
+/// it takes the quorum to certify. Peers are never listed twice and the node
+/// itself is left out.
+pub fn prioritized_fanout(
+    node_id: &NodeId,
+    membership_config: Option<&QuorumMembershipConfig>,
+    gossip_peers: impl IntoIterator<Item = SocketAddr>,
+) -> Vec<SocketAddr> {
+    let mut seen = HashSet::new();
+
+    let quorum_peers = membership_config
+        .into_iter()
+        .flat_map(|membership_config| membership_config.quorum_members.values())
+        .filter(|member| &member.node_id != node_id)
+        .map(|member| member.udp_gossip_address);
+
+    quorum_peers
+        .chain(gossip_peers)
+        .filter(|addr| seen.insert(*addr))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, net::SocketAddr};
+
+    use primitives::{KademliaPeerId, NodeType, QuorumKind};
+    use vrrb_config::QuorumMember;
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    fn member(node_id: &str, port: u16) -> (NodeId, QuorumMember) {
+        let member = QuorumMember {
+            node_id: node_id.to_string(),
+            kademlia_peer_id: KademliaPeerId::rand(),
+            node_type: NodeType::Validator,
+            udp_gossip_address: addr(port),
+            raptorq_gossip_address: addr(port),
+            kademlia_liveness_address: addr(port),
+            validator_public_key: Keypair::random().validator_public_key_owned(),
+        };
+
+        (node_id.to_string(), member)
+    }
+
+    #[test]
+    fn quorum_members_are_reached_before_gossip_peers() {
+        let membership_config = QuorumMembershipConfig {
+            quorum_kind: QuorumKind::Harvester,
+            quorum_members: BTreeMap::from([
+                member("node-0", 9000),
+                member("node-1", 9001),
+                member("node-2", 9002),
+            ]),
+        };
+
+        let fanout = prioritized_fanout(
+            &"node-0".to_string(),
+            Some(&membership_config),
+            vec![addr(9100), addr(9002), addr(9101)],
+        );
+
+        assert_eq!(fanout, vec![addr(9001), addr(9002), addr(9100), addr(9101)]);
+    }
+
+    #[test]
+    fn nodes_outside_a_quorum_fan_out_to_gossip_peers_only() {
+        let fanout = prioritized_fanout(
+            &"node-0".to_string(),
+            None,
+            vec![addr(9100), addr(9101), addr(9100)],
+        );
+
+        assert_eq!(fanout, vec![addr(9100), addr(9101)]);
+    }
+}
@@ -4,6 +4,7 @@ use telemetry::info;
 use theater::{ActorId, ActorLabel, ActorState, Handler, TheaterError};
 
 use super::NetworkModule;
+use crate::JSON_RPC_API_CONTROL_TOPIC;
 
 #[async_trait]
 impl Handler<EventMessage> for NetworkModule {
@@ -25,7 +26,12 @@ impl Handler<EventMessage> for NetworkModule {
 
     async fn handle(&mut self, event: EventMessage) -> theater::Result<ActorState> {
         match event.into() {
-            Event::PeerJoined(peer_data) => {
+            Event::PeerJoined { peer_data, source } => {
+                if let Err(err) = self.peer_registration.admit(&peer_data, source) {
+                    telemetry::warn!("{}", err);
+                    return Ok(ActorState::Running);
+                }
+
                 info!("Storing peer information from {} in DHT", peer_data.node_id);
 
                 // TODO: revisit this insert method
@@ -34,6 +40,13 @@ impl Handler<EventMessage> for NetworkModule {
                     &peer_data.kademlia_liveness_addr.to_string(),
                 );
 
+                self.peer_addresses.record(&peer_data);
+
+                let message_credits = self.message_credits_of(peer_data.udp_gossip_addr.ip());
+                if let Err(err) = self.peer_store.record_seen(&peer_data, message_credits) {
+                    telemetry::warn!("Failed to store peer {}: {err}", peer_data.node_id);
+                }
+
                 let evt = Event::NodeAddedToPeerList(peer_data.clone());
                 let em = EventMessage::new(Some("runtime-events".into()), evt);
 
@@ -43,11 +56,25 @@ impl Handler<EventMessage> for NetworkModule {
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             },
 
+            Event::PeersRequested => {
+                let evt = Event::PeersListed(self.peer_store.peers());
+                let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), evt);
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
             Event::QuorumMembershipAssigmentCreated(assigned_membership) => {
                 self.notify_quorum_membership_assignment(assigned_membership)
                     .await?;
             },
 
+            Event::QuorumMembershipConfigured(membership_config) => {
+                self.set_membership_config(membership_config);
+            },
+
             Event::ClaimCreated(claim) => {
                 info!("Broadcasting claim to peers");
                 self.broadcast_claim(claim).await?;
@@ -69,10 +96,157 @@ impl Handler<EventMessage> for NetworkModule {
             },
 
             Event::ConvergenceBlockCertified(block) => {
+                self.reward_certified_contribution(&block.header.miner_claim.node_id);
+
                 info!("Broadcasting certified convergence block to network");
                 self.broadcast_certified_convergence_block(block).await?;
             },
 
+            Event::InvalidSignatureShareReceived { node_id, reason } => {
+                telemetry::warn!("Peer {node_id} sent an invalid signature share: {reason}");
+                self.penalize_invalid_signature_share(&node_id);
+            },
+
+            Event::ValidatorSetUpdated(diff) => {
+                info!(
+                    "Broadcasting validator set changes for epoch {}",
+                    diff.epoch
+                );
+                self.broadcast_validator_set_diff(diff).await?;
+            },
+
+            Event::MaintenanceModeChanged { node_id, paused } => {
+                info!("Announcing maintenance mode change of node {node_id} to network");
+                self.broadcast_maintenance_mode_change(node_id, paused)
+                    .await?;
+            },
+
+            Event::ValidatorKeyRotated(rotation) => {
+                info!("Announcing validator key rotation to network");
+                self.validator_public_key = rotation.new_public_key;
+                self.broadcast_validator_key_rotation(rotation).await?;
+            },
+
+            Event::PeerValidatorKeyRotated(rotation) => {
+                self.handle_peer_validator_key_rotated(&rotation);
+            },
+
+            Event::HaltDirectiveSigned {
+                node_id,
+                node_idx,
+                directive,
+                signature,
+            } => {
+                info!(
+                    "Broadcasting share of {} directive to network",
+                    directive.action
+                );
+                self.broadcast_halt_directive_share(node_id, node_idx, directive, signature)
+                    .await?;
+            },
+
+            Event::HaltDirectiveCertified(signed) => {
+                info!(
+                    "Broadcasting {} directive for round {} to network",
+                    signed.directive.action, signed.directive.round
+                );
+                self.broadcast_certified_halt_directive(signed).await?;
+            },
+
+            // NOTE: mempool reconciliation is best effort, so failing to reach a peer shouldn't
+            // stop the network module
+            Event::MempoolReconciliationDue => {
+                if let Err(err) = self.start_mempool_reconciliation().await {
+                    telemetry::warn!("Failed to start mempool reconciliation: {err}");
+                }
+            },
+
+            Event::MempoolSketchReceived { sender_id, sketch } => {
+                if let Err(err) = self.handle_mempool_sketch_received(sender_id, sketch).await {
+                    telemetry::warn!("Failed to offer mempool digests: {err}");
+                }
+            },
+
+            Event::MempoolDigestsOffered { sender_id, digests } => {
+                if let Err(err) = self
+                    .handle_mempool_digests_offered(sender_id, digests)
+                    .await
+                {
+                    telemetry::warn!("Failed to request missing mempool txns: {err}");
+                }
+            },
+
+            Event::MempoolTxnsRequested { sender_id, digests } => {
+                if let Err(err) = self.handle_mempool_txns_requested(sender_id, digests).await {
+                    telemetry::warn!("Failed to serve requested mempool txns: {err}");
+                }
+            },
+
+            Event::MempoolTxnsReceived(txns) => {
+                if let Err(err) = self.handle_mempool_txns_received(txns).await {
+                    telemetry::warn!("Failed to add reconciled txns to mempool: {err}");
+                }
+            },
+
+            // NOTE: certificate recovery is retried on every reconciliation tick, so a
+            // failed request is only logged
+            Event::MissingCertificatesRequested(block_hashes) => {
+                if let Err(err) = self.request_missing_certificates(block_hashes).await {
+                    telemetry::warn!("Failed to request missing certificates: {err}");
+                }
+            },
+
+            Event::CertificatesProvided {
+                requester,
+                certificates,
+            } => {
+                if let Err(err) = self
+                    .handle_certificates_provided(requester, certificates)
+                    .await
+                {
+                    telemetry::warn!("Failed to serve requested certificates: {err}");
+                }
+            },
+
+            // NOTE: corrupted records are requested again on every scrub, so a failed
+            // request is only logged
+            Event::MissingChainRecordsRequested(keys) => {
+                if let Err(err) = self.request_missing_chain_records(keys).await {
+                    telemetry::warn!("Failed to request corrupted chain records: {err}");
+                }
+            },
+
+            Event::ChainRecordsProvided { requester, records } => {
+                if let Err(err) = self.handle_chain_records_provided(requester, records).await {
+                    telemetry::warn!("Failed to serve requested chain records: {err}");
+                }
+            },
+
+            // NOTE: a lost message or acknowledgement only costs a re-broadcast, so
+            // failures along the way are only logged
+            Event::RebroadcastDue => {
+                if let Err(err) = self.rebroadcast_unacknowledged_messages().await {
+                    telemetry::warn!("Failed to re-broadcast consensus messages: {err}");
+                }
+            },
+
+            Event::RoundPeersScheduled { round, peers } => {
+                self.preconnect_round_peers(round, peers).await;
+            },
+
+            Event::ConsensusMessageReceived { sender_id, digest } => {
+                if let Err(err) = self.acknowledge_consensus_message(sender_id, digest).await {
+                    telemetry::warn!("Failed to acknowledge consensus message: {err}");
+                }
+            },
+
+            Event::ConsensusMessageAcknowledged {
+                recipient_addr,
+                digest,
+            } => {
+                self.rebroadcaster.acknowledge(recipient_addr, &digest);
+            },
+
             Event::Stop => {
                 // NOTE: stop the kademlia node instance
                 self.node_ref().kill();
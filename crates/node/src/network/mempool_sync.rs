@@ -0,0 +1,279 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+
+use mempool::{MempoolReadHandleFactory, MempoolSketch};
+use primitives::NodeId;
+use vrrb_config::MempoolSyncConfig;
+use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind};
+
+/// Work done on behalf of a single peer within the current window.
+#[derive(Debug, Clone, Copy, Default)]
+struct PeerAllowance {
+    requests: usize,
+    txns_requested: usize,
+    txns_served: usize,
+}
+
+/// Reconciles the local mempool with peers' through set-difference sync.
+///
+/// A node periodically sends a sketch of its mempool to a peer, which answers
+/// with the digests it holds within the ranges where both mempools differ.
+/// The node then requests only the transactions it's missing from those. Work
+/// done for any single peer is capped per window, so reconciliation can't be
+/// used to amplify traffic, and only transactions that were actually
+/// requested are accepted.
+#[derive(Debug)]
+pub struct MempoolSync {
+    config: MempoolSyncConfig,
+    mempool: MempoolReadHandleFactory,
+    window_started_at: Instant,
+    allowances: HashMap<NodeId, PeerAllowance>,
+
+    /// Digests requested during the current and previous windows, so replies
+    /// arriving right after a window rolls over are still accepted
+    requested: HashSet<TransactionDigest>,
+    previously_requested: HashSet<TransactionDigest>,
+}
+
+impl MempoolSync {
+    pub fn new(config: MempoolSyncConfig, mempool: MempoolReadHandleFactory) -> Self {
+        Self {
+            config,
+            mempool,
+            window_started_at: Instant::now(),
+            allowances: HashMap::new(),
+            requested: HashSet::new(),
+            previously_requested: HashSet::new(),
+        }
+    }
+
+    pub fn sketch(&self) -> MempoolSketch {
+        self.mempool.sketch()
+    }
+
+    /// Answers a peer's sketch with the local digests that fall within the
+    /// ranges where both mempools differ. Returns `None` when there's nothing
+    /// to offer or the peer ran out of requests for the current window.
+    pub fn offer(
+        &mut self,
+        peer_id: &NodeId,
+        sketch: &MempoolSketch,
+    ) -> Option<Vec<TransactionDigest>> {
+        if !self.take_request(peer_id) {
+            return None;
+        }
+
+        let buckets = self.sketch().differing_buckets(sketch);
+        if buckets.is_empty() {
+            return None;
+        }
+
+        let digests = self
+            .mempool
+            .digests_in_buckets(&buckets, self.config.max_digests_per_offer);
+
+        if digests.is_empty() {
+            return None;
+        }
+
+        Some(digests)
+    }
+
+    /// Picks the offered digests that are missing from the local mempool and
+    /// haven't been requested yet, up to what's left of the peer's allowance,
+    /// and remembers them as requested.
+    pub fn request_missing(
+        &mut self,
+        peer_id: &NodeId,
+        digests: Vec<TransactionDigest>,
+    ) -> Vec<TransactionDigest> {
+        self.roll_window();
+
+        let offered: HashSet<TransactionDigest> = digests
+            .into_iter()
+            .take(self.config.max_digests_per_offer)
+            .collect();
+
+        let allowance = self.allowances.entry(peer_id.clone()).or_default();
+        let budget = self
+            .config
+            .max_txns_per_peer
+            .saturating_sub(allowance.txns_requested);
+
+        let missing: Vec<TransactionDigest> = self
+            .mempool
+            .missing(offered.into_iter().collect())
+            .into_iter()
+            .filter(|digest| {
+                !self.requested.contains(digest) && !self.previously_requested.contains(digest)
+            })
+            .take(budget)
+            .collect();
+
+        allowance.txns_requested += missing.len();
+        self.requested.extend(missing.iter().cloned());
+
+        missing
+    }
+
+    /// Returns the requested transactions found in the local mempool, up to
+    /// what's left of the peer's allowance.
+    pub fn serve(
+        &mut self,
+        peer_id: &NodeId,
+        digests: Vec<TransactionDigest>,
+    ) -> Vec<TransactionKind> {
+        if !self.take_request(peer_id) {
+            return vec![];
+        }
+
+        let allowance = self.allowances.entry(peer_id.clone()).or_default();
+        let budget = self
+            .config
+            .max_txns_per_peer
+            .saturating_sub(allowance.txns_served);
+
+        let pool = self.mempool.handle();
+        let requested: HashSet<TransactionDigest> = digests.into_iter().collect();
+
+        let txns: Vec<TransactionKind> = requested
+            .iter()
+            .filter_map(|digest| pool.get(digest))
+            .map(|record| record.txn.clone())
+            .take(budget)
+            .collect();
+
+        allowance.txns_served += txns.len();
+
+        txns
+    }
+
+    /// Keeps only the received transactions that were requested from peers,
+    /// so unsolicited ones can't be pushed into the mempool.
+    pub fn accept(&mut self, txns: Vec<TransactionKind>) -> Vec<TransactionKind> {
+        txns.into_iter()
+            .filter(|txn| {
+                let digest = txn.id();
+                self.requested.remove(&digest) || self.previously_requested.remove(&digest)
+            })
+            .collect()
+    }
+
+    /// Counts a request against the peer's allowance, returning false if it
+    /// has none left for the current window.
+    fn take_request(&mut self, peer_id: &NodeId) -> bool {
+        self.roll_window();
+
+        let allowance = self.allowances.entry(peer_id.clone()).or_default();
+        if allowance.requests >= self.config.max_requests_per_peer {
+            return false;
+        }
+
+        allowance.requests += 1;
+
+        true
+    }
+
+    /// Starts a new window once the current one is over, resetting every
+    /// peer's allowance and forgetting requests that went unanswered for a
+    /// whole window.
+    fn roll_window(&mut self) {
+        if self.window_started_at.elapsed() < self.config.interval {
+            return;
+        }
+
+        self.window_started_at = Instant::now();
+        self.allowances.clear();
+        self.previously_requested = std::mem::take(&mut self.requested);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mempool::LeftRightMempool;
+    use vrrb_core::transactions::Transfer;
+
+    use super::*;
+    use crate::test_utils::create_mock_transaction_args;
+
+    fn mock_txns(n: usize) -> Vec<TransactionKind> {
+        (1..=n)
+            .map(|i| TransactionKind::Transfer(Transfer::new(create_mock_transaction_args(i))))
+            .collect()
+    }
+
+    fn mempool_with(txns: &[TransactionKind]) -> LeftRightMempool {
+        let mut mempool = LeftRightMempool::new();
+        mempool.extend(txns.iter().cloned().collect()).unwrap();
+
+        mempool
+    }
+
+    #[test]
+    fn peers_only_fetch_the_txns_they_are_missing() {
+        let txns = mock_txns(10);
+
+        let full = mempool_with(&txns);
+        let partial = mempool_with(&txns[..6]);
+
+        let mut responder = MempoolSync::new(MempoolSyncConfig::default(), full.factory());
+        let mut initiator = MempoolSync::new(MempoolSyncConfig::default(), partial.factory());
+
+        let initiator_id = NodeId::from("initiator");
+        let responder_id = NodeId::from("responder");
+
+        let offered = responder.offer(&initiator_id, &initiator.sketch()).unwrap();
+
+        let missing = initiator.request_missing(&responder_id, offered);
+        let expected: HashSet<TransactionDigest> = txns[6..].iter().map(|txn| txn.id()).collect();
+        assert_eq!(missing.iter().cloned().collect::<HashSet<_>>(), expected);
+
+        let served = responder.serve(&initiator_id, missing);
+        assert_eq!(initiator.accept(served).len(), 4);
+
+        // NOTE: identical mempools have nothing to reconcile
+        let copy = mempool_with(&txns);
+        assert!(responder
+            .offer(&initiator_id, &copy.factory().sketch())
+            .is_none());
+    }
+
+    #[test]
+    fn work_done_for_a_peer_is_capped_per_window() {
+        let txns = mock_txns(10);
+        let full = mempool_with(&txns);
+        let empty = LeftRightMempool::new();
+
+        let config = MempoolSyncConfig {
+            max_txns_per_peer: 3,
+            max_requests_per_peer: 1,
+            ..Default::default()
+        };
+
+        let mut responder = MempoolSync::new(config.clone(), full.factory());
+        let mut initiator = MempoolSync::new(config, empty.factory());
+
+        let peer_id = NodeId::from("peer");
+        let sketch = initiator.sketch();
+
+        let offered = responder.offer(&peer_id, &sketch).unwrap();
+        assert!(responder.offer(&peer_id, &sketch).is_none());
+
+        let missing = initiator.request_missing(&peer_id, offered.clone());
+        assert_eq!(missing.len(), 3);
+        assert!(initiator.request_missing(&peer_id, offered).is_empty());
+
+        let all_digests = txns.iter().map(|txn| txn.id()).collect();
+        assert!(responder.serve(&peer_id, all_digests).is_empty());
+    }
+
+    #[test]
+    fn unsolicited_txns_are_rejected() {
+        let mempool = LeftRightMempool::new();
+        let mut sync = MempoolSync::new(MempoolSyncConfig::default(), mempool.factory());
+
+        assert!(sync.accept(mock_txns(3)).is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    net::IpAddr,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use lru_time_cache::LruCache;
+use vrrb_config::MessageCreditsConfig;
+
+/// Message credits shared between the network module and the handler of
+/// inbound network messages.
+pub type SharedMessageCredits = Arc<RwLock<MessageCredits>>;
+
+#[derive(Debug, Clone, Copy)]
+struct CreditBalance {
+    credits: u64,
+    refilled_at: Instant,
+}
+
+/// Tracks how many consensus messages each peer may still send.
+///
+/// Peers spend credits on every consensus message they send and earn them
+/// back over time and through certified contributions, so peers flooding the
+/// network run dry and get rate limited while well behaved ones never notice.
+///
+/// Balances are kept by the address messages arrive from rather than the
+/// node id they claim, so a peer can't spend another's credits, and only for
+/// the most recently seen peers.
+pub struct MessageCredits {
+    config: MessageCreditsConfig,
+    balances: LruCache<IpAddr, CreditBalance>,
+}
+
+impl std::fmt::Debug for MessageCredits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageCredits")
+            .field("config", &self.config)
+            .field("balances", &self.balances.len())
+            .finish()
+    }
+}
+
+impl MessageCredits {
+    pub fn new(config: MessageCreditsConfig) -> Self {
+        let balances = LruCache::with_capacity(config.max_tracked_peers);
+
+        Self { config, balances }
+    }
+
+    pub fn shared(config: MessageCreditsConfig) -> SharedMessageCredits {
+        Arc::new(RwLock::new(Self::new(config)))
+    }
+
+    /// Credits the peer at `addr` currently holds, not counting the refill
+    /// accrued since it last sent a message
+    pub fn credits(&self, addr: &IpAddr) -> u64 {
+        self.balances
+            .peek(addr)
+            .map_or(self.config.initial_credits, |balance| balance.credits)
+    }
+
+    /// Charges the peer at `addr` for a consensus message, returning whether
+    /// it could afford it. Messages it can't afford should be dropped.
+    pub fn try_consume(&mut self, addr: IpAddr) -> bool {
+        self.try_consume_at(addr, Instant::now())
+    }
+
+    /// Rewards the peer at `addr` for a contribution that got certified
+    pub fn reward_certified_contribution(&mut self, addr: IpAddr) {
+        let now = Instant::now();
+        let reward = self.config.certified_contribution_reward;
+        let max_credits = self.config.max_credits;
+        let balance = self.refilled_balance(addr, now);
+
+        balance.credits = balance.credits.saturating_add(reward).min(max_credits);
+    }
+
+    fn try_consume_at(&mut self, addr: IpAddr, now: Instant) -> bool {
+        let cost = self.config.message_cost;
+        let balance = self.refilled_balance(addr, now);
+
+        if balance.credits < cost {
+            return false;
+        }
+
+        balance.credits -= cost;
+
+        true
+    }
+
+    fn refilled_balance(&mut self, addr: IpAddr, now: Instant) -> &mut CreditBalance {
+        let config = &self.config;
+
+        let balance = self.balances.entry(addr).or_insert_with(|| CreditBalance {
+            credits: config.initial_credits,
+            refilled_at: now,
+        });
+
+        let elapsed_secs = now.saturating_duration_since(balance.refilled_at).as_secs();
+
+        // NOTE: only whole seconds are refilled, the remainder carries over to the
+        // next refill
+        if elapsed_secs > 0 {
+            let refill = elapsed_secs.saturating_mul(config.refill_per_sec);
+
+            balance.credits = balance
+                .credits
+                .saturating_add(refill)
+                .min(config.max_credits);
+            balance.refilled_at += Duration::from_secs(elapsed_secs);
+        }
+
+        balance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credits() -> MessageCredits {
+        MessageCredits::new(MessageCreditsConfig {
+            initial_credits: 3,
+            max_credits: 10,
+            message_cost: 1,
+            refill_per_sec: 2,
+            certified_contribution_reward: 5,
+            max_tracked_peers: 2,
+        })
+    }
+
+    fn peer(octet: u8) -> IpAddr {
+        IpAddr::from([10, 0, 0, octet])
+    }
+
+    #[test]
+    fn peers_out_of_credits_are_rate_limited() {
+        let mut credits = credits();
+        let addr = peer(1);
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(credits.try_consume_at(addr, now));
+        }
+
+        assert!(!credits.try_consume_at(addr, now));
+        assert_eq!(credits.credits(&addr), 0);
+
+        // NOTE: other peers keep their own balance
+        assert!(credits.try_consume_at(peer(2), now));
+    }
+
+    #[test]
+    fn credits_refill_over_time_up_to_the_cap() {
+        let mut credits = credits();
+        let addr = peer(1);
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            credits.try_consume_at(addr, now);
+        }
+
+        assert!(credits.try_consume_at(addr, now + Duration::from_secs(1)));
+        assert_eq!(credits.credits(&addr), 1);
+
+        credits.try_consume_at(addr, now + Duration::from_secs(60));
+        assert_eq!(credits.credits(&addr), 9);
+    }
+
+    #[test]
+    fn certified_contributions_replenish_credits() {
+        let mut credits = credits();
+        let addr = peer(1);
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            credits.try_consume_at(addr, now);
+        }
+        assert!(!credits.try_consume_at(addr, now));
+
+        credits.reward_certified_contribution(addr);
+
+        assert!(credits.credits(&addr) >= 5);
+        assert!(credits.try_consume_at(addr, Instant::now()));
+    }
+
+    #[test]
+    fn only_recently_seen_peers_keep_their_balance() {
+        let mut credits = credits();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            credits.try_consume_at(peer(1), now);
+        }
+        credits.try_consume_at(peer(2), now);
+        credits.try_consume_at(peer(3), now);
+
+        assert_eq!(credits.balances.len(), 2);
+        assert_eq!(credits.credits(&peer(1)), 3);
+        assert_eq!(credits.credits(&peer(3)), 2);
+    }
+}
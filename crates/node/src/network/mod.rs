@@ -1,11 +1,29 @@
+mod bootstrap_failover;
 mod component;
+mod fanout;
 mod handler;
+mod mempool_sync;
+mod message_credits;
 mod module;
 mod network_event;
 mod network_event_handler;
+mod peer_addresses;
+mod peer_registration;
+mod peer_store;
+mod preconnect;
+mod rebroadcast;
 
+pub use bootstrap_failover::*;
 pub use component::*;
+pub use fanout::*;
 pub use handler::*;
+pub use mempool_sync::*;
+pub use message_credits::*;
 pub use module::*;
 pub use network_event::*;
 pub use network_event_handler::*;
+pub use peer_addresses::*;
+pub use peer_registration::*;
+pub use peer_store::*;
+pub use preconnect::*;
+pub use rebroadcast::*;
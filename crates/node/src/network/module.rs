@@ -1,31 +1,61 @@
 use std::{
     collections::HashMap,
-    net::{AddrParseError, SocketAddr},
+    net::{AddrParseError, IpAddr, SocketAddr},
     ops::AddAssign,
+    path::PathBuf,
+    time::Instant,
 };
 
 use async_trait::async_trait;
-use block::ConvergenceBlock;
+use block::{BlockHash, Certificate, ConvergenceBlock, HaltDirective, SignedHaltDirective};
 use dyswarm::{
     client::{BroadcastArgs, BroadcastConfig},
     server::ServerConfig,
 };
-use events::{AssignedQuorumMembership, Event, EventMessage, EventPublisher, EventSubscriber};
+use events::{
+    AssignedQuorumMembership, ChainRecord, ChainRecordKey, Event, EventMessage, EventPublisher,
+    EventSubscriber, PeerData, ValidatorKeyRotation, EVENT_SCHEMA_VERSION,
+};
 use hbbft::{
     crypto::PublicKey as ThresholdSignaturePublicKey,
     sync_key_gen::{Ack, Part},
 };
 use kademlia_dht::{Key, Node as KademliaNode, NodeData};
-use primitives::{KademliaPeerId, NodeId, NodeType, ValidatorPublicKey};
+use mempool::{MempoolReadHandleFactory, MempoolSketch};
+use primitives::{
+    DbBackend, KademliaPeerId, Locality, NodeId, NodeIdx, NodeType, RawSignature, Round,
+    StakingParams, ValidatorPublicKey,
+};
+use rand::seq::SliceRandom;
 use storage::vrrbdb::VrrbDbReadHandle;
 use telemetry::info;
 use theater::{Actor, ActorId, ActorImpl, ActorLabel, ActorState, Handler, TheaterError};
 use tracing::Subscriber;
 use utils::payload::digest_data_to_bytes;
-use vrrb_config::{BootstrapQuorumConfig, NodeConfig, QuorumMembershipConfig};
-use vrrb_core::claim::Claim;
+use vrrb_config::{
+    BootstrapFailoverConfig,
+    BootstrapQuorumConfig,
+    MempoolSyncConfig,
+    MessageCreditsConfig,
+    NetworkAddressConfig,
+    NodeConfig,
+    PeerRegistrationConfig,
+    PeerStoreConfig,
+    QuorumMembershipConfig,
+    RebroadcastConfig,
+    ValidatorSetDiff,
+};
+use vrrb_core::{
+    claim::Claim,
+    keypair::MinerSecretKey,
+    transactions::{TransactionDigest, TransactionKind},
+};
 
-use super::NetworkEvent;
+use super::{
+    prioritized_fanout, select_bootstrap_node, ConsensusMessageDigest, MempoolSync, MessageCredits,
+    NetworkEvent, PeerAddressBook, PeerRegistrationGuard, PeerStore, PreconnectMonitor,
+    Preconnector, RebroadcastMonitor, Rebroadcaster, SharedMessageCredits,
+};
 use crate::{
     network::DyswarmHandler, result::Result, NodeError, RuntimeComponent, RuntimeComponentHandle,
     DEFAULT_ERASURE_COUNT,
@@ -43,10 +73,22 @@ pub struct NetworkModule {
     pub(crate) udp_gossip_addr: SocketAddr,
     pub(crate) raptorq_gossip_addr: SocketAddr,
     pub(crate) kademlia_liveness_addr: SocketAddr,
+    pub(crate) alternate_udp_gossip_addrs: Vec<SocketAddr>,
+    pub(crate) peer_addresses: PeerAddressBook,
     pub(crate) dyswarm_server_handle: dyswarm::server::ServerHandle,
     pub(crate) dyswarm_client: dyswarm::client::Client,
     pub(crate) membership_config: Option<QuorumMembershipConfig>,
     pub(crate) validator_public_key: ValidatorPublicKey,
+    pub(crate) locality: Locality,
+    pub(crate) claim_secret_key: MinerSecretKey,
+    pub(crate) peer_registration: PeerRegistrationGuard,
+    pub(crate) mempool_sync: MempoolSync,
+    pub(crate) message_credits: SharedMessageCredits,
+    pub(crate) peer_store: PeerStore,
+    pub(crate) rebroadcaster: Rebroadcaster,
+    pub(crate) preconnector: Preconnector,
+    pub(crate) chain_id: String,
+    pub(crate) chain_spec_version: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -69,42 +111,139 @@ pub struct NetworkModuleConfig {
     /// Configuration used to connect to a bootstrap node
     pub bootstrap_node_config: Option<vrrb_config::BootstrapConfig>,
 
+    /// Bootstrap nodes tried when the one in `bootstrap_node_config` is
+    /// unreachable
+    pub bootstrap_failover_config: BootstrapFailoverConfig,
+
+    /// Directory the address book of peers is persisted within
+    pub peer_store_path: PathBuf,
+
+    /// Key-value store the address book of peers persists to
+    pub db_backend: DbBackend,
+
+    pub peer_store_config: PeerStoreConfig,
+
     pub membership_config: Option<QuorumMembershipConfig>,
 
     pub events_tx: EventPublisher,
 
     pub validator_public_key: ValidatorPublicKey,
+
+    /// Region and zone announced to peers when joining the network
+    pub locality: Locality,
+
+    /// Key of the node's claim, signs its registration with peers
+    pub claim_secret_key: MinerSecretKey,
+
+    /// Checks peers go through before they are added to the peer list
+    pub peer_registration_config: PeerRegistrationConfig,
+
+    /// Minimum stake registering peers' claims must hold
+    pub staking: StakingParams,
+
+    /// Chain the node follows and the version of its chain spec, announced
+    /// to peers when joining. Peers announcing another chain or version are
+    /// ignored
+    pub chain_id: String,
+    pub chain_spec_version: u32,
+
+    /// Used to look up the claims of registering peers
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+
+    /// Members of the bootstrap quorum may register without a claim
+    pub bootstrap_quorum_config: Option<BootstrapQuorumConfig>,
+
+    /// Used to reconcile the local mempool with peers'
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+
+    pub mempool_sync_config: MempoolSyncConfig,
+
+    /// Credits peers spend to send consensus messages
+    pub message_credits_config: MessageCreditsConfig,
+
+    /// Address families the module listens on and addresses it announces to
+    /// peers
+    pub network_address_config: NetworkAddressConfig,
+
+    /// Backoff and bounds of the re-broadcast of unacknowledged consensus
+    /// messages
+    pub rebroadcast_config: RebroadcastConfig,
 }
 
 impl NetworkModule {
     pub async fn new(config: NetworkModuleConfig) -> Result<Self> {
         let mut config = config.clone();
 
+        let network_address_config = config.network_address_config.clone();
+
         let dyswarm_server_config = ServerConfig {
-            addr: config.udp_gossip_addr,
+            addr: network_address_config.bind_addr(config.udp_gossip_addr),
         };
 
         let dyswarm_server = dyswarm::server::Server::new(dyswarm_server_config).await?;
 
         let resolved_addr = dyswarm_server.public_addr();
         config.udp_gossip_addr = resolved_addr;
+        config.kademlia_liveness_addr =
+            network_address_config.bind_addr(config.kademlia_liveness_addr);
 
         let dyswarm_client_config = dyswarm::client::Config {
             addr: config.udp_gossip_addr,
         };
 
-        let dyswarm_client = dyswarm::client::Client::new(dyswarm_client_config).await?;
+        let mut dyswarm_client = dyswarm::client::Client::new(dyswarm_client_config).await?;
+
+        let peer_store = PeerStore::open(
+            &config.peer_store_path,
+            config.db_backend,
+            config.peer_store_config.max_peers,
+        )?;
+
+        // NOTE: nodes configured without a bootstrap node become bootstrap nodes
+        // themselves, so only nodes with one configured fail over
+        if let Some(primary) = config.bootstrap_node_config.clone() {
+            let failover_config = &config.bootstrap_failover_config;
+
+            let mut candidates = vec![primary.clone()];
+            candidates.extend(failover_config.fallback_bootstrap_configs.iter().cloned());
+            candidates.extend(peer_store.bootstrap_candidates(failover_config.max_known_peers));
+
+            let selected = select_bootstrap_node(
+                &mut dyswarm_client,
+                &config.node_id,
+                candidates,
+                failover_config.health_check_timeout,
+                network_address_config.preferred_family,
+            )
+            .await;
+
+            config.bootstrap_node_config = Some(selected.unwrap_or_else(|| {
+                telemetry::warn!(
+                    "No bootstrap node is reachable, joining through {} regardless",
+                    primary.udp_gossip_addr
+                );
+                primary
+            }));
+        }
 
         let kademlia_node = Self::setup_kademlia_node(config.clone())?;
         config.kademlia_liveness_addr = kademlia_node.node_data().addr;
 
         let events_tx = config.events_tx.clone();
 
-        let handler = DyswarmHandler::new(config.node_id.clone(), events_tx.clone());
+        let message_credits = MessageCredits::shared(config.message_credits_config.clone());
+
+        let handler = DyswarmHandler::new(
+            config.node_id.clone(),
+            events_tx.clone(),
+            message_credits.clone(),
+            config.chain_id.clone(),
+            config.chain_spec_version,
+        );
 
         let dyswarm_server_handle = dyswarm_server.run(handler).await?;
 
-        let network_component = Self {
+        let mut network_component = Self {
             id: uuid::Uuid::new_v4().to_string(),
             events_tx,
             node_id: config.node_id.clone(),
@@ -117,15 +256,105 @@ impl NetworkModule {
             kademlia_liveness_addr: config.kademlia_liveness_addr,
             udp_gossip_addr: config.udp_gossip_addr,
             raptorq_gossip_addr: config.raptorq_gossip_addr,
+            alternate_udp_gossip_addrs: network_address_config
+                .advertised_udp_gossip_addrs(config.udp_gossip_addr),
+            peer_addresses: PeerAddressBook::new(network_address_config.preferred_family),
             dyswarm_server_handle,
             dyswarm_client,
             membership_config: config.membership_config.clone(),
             validator_public_key: config.validator_public_key,
+            locality: config.locality.clone(),
+            claim_secret_key: config.claim_secret_key,
+            peer_registration: PeerRegistrationGuard::new(
+                config.peer_registration_config.clone(),
+                config.staking.clone(),
+                config.vrrbdb_read_handle.clone(),
+                config
+                    .bootstrap_quorum_config
+                    .iter()
+                    .flat_map(|quorum_config| quorum_config.membership_config.quorum_members.keys())
+                    .cloned()
+                    .collect(),
+            ),
+            mempool_sync: MempoolSync::new(
+                config.mempool_sync_config.clone(),
+                config.mempool_read_handle_factory.clone(),
+            ),
+            message_credits,
+            peer_store,
+            rebroadcaster: Rebroadcaster::new(config.rebroadcast_config.clone()),
+            preconnector: Preconnector::new(),
+            chain_id: config.chain_id.clone(),
+            chain_spec_version: config.chain_spec_version,
         };
 
+        network_component.reconnect_stored_peers();
+
         Ok(network_component)
     }
 
+    /// Adds the peers remembered from previous runs back to the DHT, so the
+    /// join intent reaches them without waiting for them to be rediscovered
+    fn reconnect_stored_peers(&mut self) {
+        for record in self.peer_store.peers() {
+            let peer_data = record.peer_data;
+
+            if peer_data.node_id == self.node_id {
+                continue;
+            }
+
+            self.kademlia_node.insert(
+                peer_data.kademlia_peer_id,
+                &peer_data.kademlia_liveness_addr.to_string(),
+            );
+
+            self.peer_addresses.record(&peer_data);
+        }
+    }
+
+    /// Message credits the peer at `addr` currently holds
+    pub(crate) fn message_credits_of(&self, addr: IpAddr) -> u64 {
+        self.message_credits
+            .read()
+            .map(|message_credits| message_credits.credits(&addr))
+            .unwrap_or_default()
+    }
+
+    /// Tops up the message credits of a peer whose contribution got certified
+    pub(crate) fn reward_certified_contribution(&mut self, node_id: &NodeId) {
+        let stored_addr = self
+            .peer_store
+            .get(node_id)
+            .ok()
+            .flatten()
+            .map(|record| record.peer_data.udp_gossip_addr);
+
+        let Some(addr) = stored_addr.or_else(|| self.peer_addr(node_id)) else {
+            return;
+        };
+
+        if let Ok(mut message_credits) = self.message_credits.write() {
+            message_credits.reward_certified_contribution(addr.ip());
+        }
+
+        let message_credits = self.message_credits_of(addr.ip());
+
+        if let Err(err) = self
+            .peer_store
+            .record_certified_contribution(node_id, message_credits)
+        {
+            telemetry::warn!("Failed to record contribution of peer {node_id}: {err}");
+        }
+    }
+
+    /// Counts a partial signature that failed validation against the
+    /// reputation of the peer that sent it
+    pub(crate) fn penalize_invalid_signature_share(&mut self, node_id: &NodeId) {
+        if let Err(err) = self.peer_store.record_invalid_signature_share(node_id) {
+            telemetry::warn!("Failed to record invalid signature share of peer {node_id}: {err}");
+        }
+    }
+
     fn setup_kademlia_node(config: NetworkModuleConfig) -> Result<KademliaNode> {
         // TODO: inspect that nodes are being created with the correct config when a
         // bootstrap is provided
@@ -200,6 +429,14 @@ impl NetworkModule {
         self.raptorq_gossip_addr
     }
 
+    pub fn rebroadcast_monitor(&self) -> RebroadcastMonitor {
+        self.rebroadcaster.monitor()
+    }
+
+    pub fn preconnect_monitor(&self) -> PreconnectMonitor {
+        self.preconnector.monitor()
+    }
+
     /// ID used by Kademlia DHT to identify this node
     pub fn kademlia_peer_id(&self) -> KademliaPeerId {
         self.kademlia_node.node_data().id
@@ -227,14 +464,38 @@ impl NetworkModule {
     }
 
     pub async fn broadcast_join_intent(&mut self) -> Result<()> {
-        let msg = dyswarm::types::Message::new(NetworkEvent::PeerJoined {
+        let mut peer_data = PeerData {
             node_id: self.node_id.clone(),
             node_type: self.node_type(),
             kademlia_peer_id: self.kademlia_peer_id(),
             udp_gossip_addr: self.udp_gossip_addr(),
             raptorq_gossip_addr: self.raptorq_gossip_addr(),
             kademlia_liveness_addr: self.kademlia_liveness_addr(),
+            alternate_udp_gossip_addrs: self.alternate_udp_gossip_addrs.clone(),
             validator_public_key: self.validator_public_key(),
+            locality: self.locality.clone(),
+            claim_signature: None,
+            event_schema_version: EVENT_SCHEMA_VERSION,
+        };
+
+        peer_data
+            .sign_registration(&self.claim_secret_key)
+            .map_err(|err| NodeError::Other(format!("failed to sign registration: {err}")))?;
+
+        let msg = dyswarm::types::Message::new(NetworkEvent::PeerJoined {
+            node_id: peer_data.node_id,
+            node_type: peer_data.node_type,
+            kademlia_peer_id: peer_data.kademlia_peer_id,
+            udp_gossip_addr: peer_data.udp_gossip_addr,
+            raptorq_gossip_addr: peer_data.raptorq_gossip_addr,
+            kademlia_liveness_addr: peer_data.kademlia_liveness_addr,
+            alternate_udp_gossip_addrs: peer_data.alternate_udp_gossip_addrs,
+            validator_public_key: peer_data.validator_public_key,
+            locality: peer_data.locality,
+            claim_signature: peer_data.claim_signature,
+            chain_id: self.chain_id.clone(),
+            chain_spec_version: self.chain_spec_version,
+            event_schema_version: peer_data.event_schema_version,
         });
 
         let nid = self.kademlia_node.node_data().id;
@@ -282,15 +543,11 @@ impl NetworkModule {
 
         let addr = found_peer.udp_gossip_addr;
 
-        let message = dyswarm::types::Message::new(NetworkEvent::AssignmentToQuorumCreated {
+        let event = NetworkEvent::AssignmentToQuorumCreated {
             assigned_membership,
-        });
-
-        self.dyswarm_client
-            .send_data_via_quic(message, addr)
-            .await?;
+        };
 
-        Ok(())
+        self.send_via_quic(event, addr).await
     }
 
     pub(crate) async fn broadcast_claim(&mut self, claim: Claim) -> Result<()> {
@@ -322,32 +579,75 @@ impl NetworkModule {
     }
 
     pub async fn broadcast_part_commitment(&mut self, node_id: NodeId, part: Part) -> Result<()> {
+        self.send_consensus_message(NetworkEvent::PartCommitmentCreated(node_id, part))
+            .await
+    }
+
+    /// Delivers a consensus message directly to every member of the node's
+    /// current quorum first, then to the rest of its gossip peers. Peers that
+    /// can't be reached are skipped so one of them failing doesn't hold back
+    /// delivery to the others.
+    pub async fn send_consensus_message(&mut self, event: NetworkEvent) -> Result<()> {
         let closest_nodes = self
             .node_ref()
             .get_routing_table()
             .get_closest_nodes(&self.node_ref().node_data().id, 8);
 
-        let socket_addresses = closest_nodes
-            .iter()
-            .map(|node| node.udp_gossip_addr)
-            .collect();
+        let fanout = prioritized_fanout(
+            &self.node_id,
+            self.membership_config.as_ref(),
+            closest_nodes.iter().map(|node| node.udp_gossip_addr),
+        );
 
-        self.dyswarm_client.add_peers(socket_addresses).await?;
+        let digest = event.consensus_digest();
 
-        let message =
-            dyswarm::types::Message::new(NetworkEvent::PartCommitmentCreated(node_id, part));
+        for addr in fanout {
+            if let Err(err) = self.send_via_quic(event.clone(), addr).await {
+                telemetry::warn!("Failed to deliver consensus message to {addr}: {err}");
+            }
 
-        self.dyswarm_client
-            .broadcast(BroadcastArgs {
-                config: Default::default(),
-                message,
-                erasure_count: 0,
-            })
-            .await?;
+            // NOTE: failed deliveries are tracked as well, so they're retried
+            if let Some(digest) = digest.clone() {
+                self.rebroadcaster.track(addr, digest, &event);
+            }
+        }
 
         Ok(())
     }
 
+    /// Sends the consensus messages peers haven't acknowledged in time once
+    /// more
+    pub async fn rebroadcast_unacknowledged_messages(&mut self) -> Result<()> {
+        for (addr, event) in self.rebroadcaster.due() {
+            if let Err(err) = self.send_via_quic(event, addr).await {
+                telemetry::warn!("Failed to re-broadcast consensus message to {addr}: {err}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Acknowledges a point-to-point consensus message to the peer it came
+    /// from
+    pub async fn acknowledge_consensus_message(
+        &mut self,
+        sender_id: NodeId,
+        digest: ConsensusMessageDigest,
+    ) -> Result<()> {
+        let message = NetworkEvent::ConsensusMessageAcknowledged {
+            recipient_addr: self.udp_gossip_addr,
+            digest,
+        };
+
+        self.send_to_peer(&sender_id, message).await
+    }
+
+    /// Tracks the quorum this node currently belongs to, whose members are
+    /// prioritized when sending consensus messages
+    pub fn set_membership_config(&mut self, membership_config: QuorumMembershipConfig) {
+        self.membership_config = Some(membership_config);
+    }
+
     pub async fn broadcast_part_commitment_acknowledgement(
         &mut self,
         node_id: NodeId,
@@ -368,24 +668,116 @@ impl NetworkModule {
 
         let addr = found_peer.udp_gossip_addr;
 
-        let message = dyswarm::types::Message::new(NetworkEvent::PartCommitmentAcknowledged {
+        let event = NetworkEvent::PartCommitmentAcknowledged {
             node_id,
             sender_id,
             ack,
+        };
+
+        if let Some(digest) = event.consensus_digest() {
+            self.rebroadcaster.track(addr, digest, &event);
+        }
+
+        self.send_via_quic(event, addr).await
+    }
+
+    pub async fn broadcast_certified_convergence_block(
+        &mut self,
+        block: ConvergenceBlock,
+    ) -> Result<()> {
+        let message = dyswarm::types::Message::new(NetworkEvent::ConvergenceBlockCertified(block));
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn broadcast_validator_set_diff(&mut self, diff: ValidatorSetDiff) -> Result<()> {
+        let message = dyswarm::types::Message::new(NetworkEvent::ValidatorSetUpdated(diff));
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn broadcast_maintenance_mode_change(
+        &mut self,
+        node_id: NodeId,
+        paused: bool,
+    ) -> Result<()> {
+        let message =
+            dyswarm::types::Message::new(NetworkEvent::MaintenanceModeChanged { node_id, paused });
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn broadcast_validator_key_rotation(
+        &mut self,
+        rotation: ValidatorKeyRotation,
+    ) -> Result<()> {
+        let message = dyswarm::types::Message::new(NetworkEvent::ValidatorKeyRotated(rotation));
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn broadcast_halt_directive_share(
+        &mut self,
+        node_id: NodeId,
+        node_idx: NodeIdx,
+        directive: HaltDirective,
+        signature: RawSignature,
+    ) -> Result<()> {
+        let message = dyswarm::types::Message::new(NetworkEvent::HaltDirectiveSigned {
+            node_id,
+            node_idx,
+            directive,
+            signature,
         });
 
         self.dyswarm_client
-            .send_data_via_quic(message, addr)
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
             .await?;
 
         Ok(())
     }
 
-    pub async fn broadcast_certified_convergence_block(
+    pub async fn broadcast_certified_halt_directive(
         &mut self,
-        block: ConvergenceBlock,
+        signed: SignedHaltDirective,
     ) -> Result<()> {
-        let message = dyswarm::types::Message::new(NetworkEvent::ConvergenceBlockCertified(block));
+        let message = dyswarm::types::Message::new(NetworkEvent::HaltDirectiveCertified(signed));
 
         self.dyswarm_client
             .broadcast(BroadcastArgs {
@@ -397,4 +789,264 @@ impl NetworkModule {
 
         Ok(())
     }
+
+    /// Refreshes the key a peer is known by once it rotated its validator
+    /// key. Failed registrations of the peer are forgotten, so it gets
+    /// verified afresh once it registers with its new key.
+    pub fn handle_peer_validator_key_rotated(&mut self, rotation: &ValidatorKeyRotation) {
+        let node_id = &rotation.node_id;
+
+        if let Err(err) = self.peer_store.record_validator_key_rotation(rotation) {
+            telemetry::warn!("Failed to update the key of peer {node_id}: {err}");
+        }
+
+        if let Some(member) = self
+            .membership_config
+            .as_mut()
+            .and_then(|membership| membership.quorum_members.get_mut(node_id))
+            .filter(|member| member.validator_public_key == rotation.old_public_key)
+        {
+            member.validator_public_key = rotation.new_public_key;
+        }
+
+        if let Some(addr) = self.peer_addr(node_id) {
+            self.peer_registration.forget_failures(addr);
+        }
+    }
+
+    /// Sends a sketch of the local mempool to a random peer, which answers
+    /// with the digests of transactions the local mempool may be missing.
+    pub async fn start_mempool_reconciliation(&mut self) -> Result<()> {
+        let closest_nodes = self
+            .node_ref()
+            .get_routing_table()
+            .get_closest_nodes(&self.node_ref().node_data().id, 8);
+
+        let Some(peer) = closest_nodes.choose(&mut rand::thread_rng()) else {
+            return Ok(());
+        };
+
+        let event = NetworkEvent::MempoolSketchAnnounced {
+            sender_id: self.node_id.clone(),
+            sketch: self.mempool_sync.sketch(),
+        };
+
+        self.send_via_quic(event, peer.udp_gossip_addr).await
+    }
+
+    pub async fn handle_mempool_sketch_received(
+        &mut self,
+        sender_id: NodeId,
+        sketch: MempoolSketch,
+    ) -> Result<()> {
+        let Some(digests) = self.mempool_sync.offer(&sender_id, &sketch) else {
+            return Ok(());
+        };
+
+        let message = NetworkEvent::MempoolDigestsOffered {
+            sender_id: self.node_id.clone(),
+            digests,
+        };
+
+        self.send_to_peer(&sender_id, message).await
+    }
+
+    pub async fn handle_mempool_digests_offered(
+        &mut self,
+        sender_id: NodeId,
+        digests: Vec<TransactionDigest>,
+    ) -> Result<()> {
+        let missing = self.mempool_sync.request_missing(&sender_id, digests);
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let message = NetworkEvent::MempoolTxnsRequested {
+            sender_id: self.node_id.clone(),
+            digests: missing,
+        };
+
+        self.send_to_peer(&sender_id, message).await
+    }
+
+    pub async fn handle_mempool_txns_requested(
+        &mut self,
+        sender_id: NodeId,
+        digests: Vec<TransactionDigest>,
+    ) -> Result<()> {
+        let txns = self.mempool_sync.serve(&sender_id, digests);
+        if txns.is_empty() {
+            return Ok(());
+        }
+
+        self.send_to_peer(&sender_id, NetworkEvent::MempoolTxnsProvided(txns))
+            .await
+    }
+
+    /// Hands the requested transactions a peer sent over to the runtime so
+    /// they're added to the local mempool.
+    pub async fn handle_mempool_txns_received(&mut self, txns: Vec<TransactionKind>) -> Result<()> {
+        for txn in self.mempool_sync.accept(txns) {
+            let em = EventMessage::new(
+                Some("runtime-events".into()),
+                Event::RelayedTxnReceived(txn),
+            );
+            self.events_tx.send(em).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Asks a random peer for the certificates of convergence blocks the
+    /// local node holds none for. Peers answer with whichever ones they have.
+    pub async fn request_missing_certificates(
+        &mut self,
+        block_hashes: Vec<BlockHash>,
+    ) -> Result<()> {
+        let closest_nodes = self
+            .node_ref()
+            .get_routing_table()
+            .get_closest_nodes(&self.node_ref().node_data().id, 8);
+
+        let Some(peer) = closest_nodes.choose(&mut rand::thread_rng()) else {
+            return Ok(());
+        };
+
+        let event = NetworkEvent::CertificatesRequested {
+            sender_id: self.node_id.clone(),
+            block_hashes,
+        };
+
+        self.send_via_quic(event, peer.udp_gossip_addr).await
+    }
+
+    pub async fn handle_certificates_provided(
+        &mut self,
+        requester: NodeId,
+        certificates: Vec<Certificate>,
+    ) -> Result<()> {
+        self.send_to_peer(&requester, NetworkEvent::CertificatesProvided(certificates))
+            .await
+    }
+
+    /// Asks a random peer for copies of chain records the local node found
+    /// corrupted. Peers answer with whichever ones they have, which are
+    /// checked against the hashes the records were committed to
+    pub async fn request_missing_chain_records(&mut self, keys: Vec<ChainRecordKey>) -> Result<()> {
+        let closest_nodes = self
+            .node_ref()
+            .get_routing_table()
+            .get_closest_nodes(&self.node_ref().node_data().id, 8);
+
+        let Some(peer) = closest_nodes.choose(&mut rand::thread_rng()) else {
+            return Ok(());
+        };
+
+        let event = NetworkEvent::ChainRecordsRequested {
+            sender_id: self.node_id.clone(),
+            keys,
+        };
+
+        self.send_via_quic(event, peer.udp_gossip_addr).await
+    }
+
+    pub async fn handle_chain_records_provided(
+        &mut self,
+        requester: NodeId,
+        records: Vec<ChainRecord>,
+    ) -> Result<()> {
+        self.send_to_peer(&requester, NetworkEvent::ChainRecordsProvided(records))
+            .await
+    }
+
+    /// Connects ahead of time to the peers the node exchanges messages with
+    /// in `round`, so the round's first messages don't wait on handshakes.
+    /// Peers that can't be reached are skipped, messages to them connect on
+    /// demand like before
+    pub async fn preconnect_round_peers(&mut self, round: Round, peers: Vec<NodeId>) {
+        if !self.preconnector.schedule(round) {
+            return;
+        }
+
+        for node_id in peers.iter().filter(|node_id| **node_id != self.node_id) {
+            let Some(addr) = self.peer_addr(node_id) else {
+                telemetry::debug!("Not pre-connecting to {node_id}, its address is unknown");
+                continue;
+            };
+
+            match self.dyswarm_client.add_peers(vec![addr]).await {
+                Ok(_) => self.preconnector.record_preconnected(addr),
+                Err(err) => {
+                    telemetry::warn!("Failed to pre-connect to {node_id} at {addr}: {err}");
+                    self.preconnector.record_failed();
+                },
+            }
+        }
+    }
+
+    /// Looks up the address of a peer among the members of the node's quorum
+    /// first and the closest peers in the routing table after
+    fn peer_addr(&self, node_id: &NodeId) -> Option<SocketAddr> {
+        let quorum_member_addr = self
+            .membership_config
+            .iter()
+            .flat_map(|membership_config| membership_config.quorum_members.values())
+            .find(|member| &member.node_id == node_id)
+            .map(|member| member.udp_gossip_address);
+
+        quorum_member_addr.or_else(|| {
+            let closest_nodes = self
+                .node_ref()
+                .get_routing_table()
+                .get_closest_nodes(&self.node_ref().node_data().id, 8);
+
+            closest_nodes
+                .iter()
+                .find(|node| &node.node_id == node_id)
+                .map(|node| node.udp_gossip_addr)
+        })
+    }
+
+    /// Sends a message to a peer, looked up like [NetworkModule::peer_addr]
+    /// does
+    async fn send_to_peer(&mut self, node_id: &NodeId, event: NetworkEvent) -> Result<()> {
+        let addr = self.peer_addr(node_id).ok_or(NodeError::Other(
+            "Could not find peer in routing table".to_string(),
+        ))?;
+
+        self.send_via_quic(event, addr).await
+    }
+
+    /// Sends a message to the peer known by `addr`, falling back to the other
+    /// addresses the peer announced should it be unreachable there. The
+    /// message is encoded in the newest event schema version the peer
+    /// announced it decodes
+    async fn send_via_quic(&mut self, event: NetworkEvent, addr: SocketAddr) -> Result<()> {
+        let event_schema_version = self.peer_addresses.event_schema_version(addr);
+        let message = dyswarm::types::Message::new(event.encode_for(event_schema_version));
+
+        let mut dial_order = self.peer_addresses.dial_order(addr).into_iter().peekable();
+        let started_at = Instant::now();
+
+        while let Some(candidate) = dial_order.next() {
+            match self
+                .dyswarm_client
+                .send_data_via_quic(message.clone(), candidate)
+                .await
+            {
+                Ok(_) => {
+                    self.preconnector.record_sent(addr, started_at.elapsed());
+                    return Ok(());
+                },
+                Err(err) if dial_order.peek().is_some() => {
+                    telemetry::warn!(
+                        "Failed to reach peer at {candidate}, trying its next address: {err}"
+                    );
+                },
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -1,15 +1,26 @@
 use std::net::SocketAddr;
 
-use block::ConvergenceBlock;
-use events::AssignedQuorumMembership;
+use block::{BlockHash, Certificate, ConvergenceBlock, HaltDirective, SignedHaltDirective};
+use events::{
+    AssignedQuorumMembership, ChainRecord, ChainRecordKey, EventDecodeError, EventEnvelope,
+    ValidatorKeyRotation, EVENT_SCHEMA_VERSION, LEGACY_EVENT_SCHEMA_VERSION,
+};
 use hbbft::{
     crypto::PublicKey,
     sync_key_gen::{Ack, Part},
 };
-use mempool::TxnRecord;
-use primitives::{KademliaPeerId, NodeId, NodeType, PeerId};
+use mempool::{MempoolSketch, TxnRecord};
+use primitives::{KademliaPeerId, Locality, NodeId, NodeIdx, NodeType, PeerId, RawSignature};
 use serde::{Deserialize, Serialize};
-use vrrb_core::claim::Claim;
+use utils::payload::digest_data_to_bytes;
+use vrrb_config::ValidatorSetDiff;
+use vrrb_core::{
+    claim::Claim,
+    transactions::{TransactionDigest, TransactionKind},
+};
+
+/// Hex encoded digest a point-to-point consensus message is acknowledged by
+pub type ConsensusMessageDigest = String;
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 /// Represents data trasmitted over the VRRB network by nodes that participate
@@ -32,7 +43,27 @@ pub enum NetworkEvent {
         udp_gossip_addr: SocketAddr,
         raptorq_gossip_addr: SocketAddr,
         kademlia_liveness_addr: SocketAddr,
+        #[serde(default)]
+        alternate_udp_gossip_addrs: Vec<SocketAddr>,
         validator_public_key: PublicKey,
+        #[serde(default)]
+        locality: Locality,
+        #[serde(default)]
+        claim_signature: Option<String>,
+
+        /// Chain the peer follows, as named by its chain spec
+        #[serde(default)]
+        chain_id: String,
+
+        /// Version of the peer's chain spec, zero for peers that don't
+        /// announce one
+        #[serde(default)]
+        chain_spec_version: u32,
+
+        /// Newest event schema version the peer decodes, the legacy one for
+        /// peers that don't announce one
+        #[serde(default = "legacy_event_schema_version")]
+        event_schema_version: u16,
     },
 
     /// Peer was assigned to a specific quorum by a bootstrap node
@@ -61,8 +92,207 @@ pub enum NetworkEvent {
 
     ConvergenceBlockCertified(ConvergenceBlock),
 
+    /// Validators joined or left a quorum at the start of an epoch
+    ValidatorSetUpdated(ValidatorSetDiff),
+
+    /// A node entered or left maintenance mode
+    MaintenanceModeChanged {
+        node_id: NodeId,
+        paused: bool,
+    },
+
+    /// A validator moved to a new validator key
+    ValidatorKeyRotated(ValidatorKeyRotation),
+
+    /// A harvester's share of the quorum's signature over a halt directive
+    HaltDirectiveSigned {
+        node_id: NodeId,
+        node_idx: NodeIdx,
+        directive: HaltDirective,
+        signature: RawSignature,
+    },
+
+    /// A halt directive signed by the harvester quorum
+    HaltDirectiveCertified(SignedHaltDirective),
+
+    /// Sketch of a peer's mempool, sent to start reconciling mempools
+    MempoolSketchAnnounced {
+        sender_id: NodeId,
+        sketch: MempoolSketch,
+    },
+
+    /// Digests a peer holds within the ranges where both mempools differ
+    MempoolDigestsOffered {
+        sender_id: NodeId,
+        digests: Vec<TransactionDigest>,
+    },
+
+    MempoolTxnsRequested {
+        sender_id: NodeId,
+        digests: Vec<TransactionDigest>,
+    },
+
+    MempoolTxnsProvided(Vec<TransactionKind>),
+
+    /// Hashes of convergence blocks a peer holds no certificate for
+    CertificatesRequested {
+        sender_id: NodeId,
+        block_hashes: Vec<BlockHash>,
+    },
+
+    CertificatesProvided(Vec<Certificate>),
+
+    /// Keys of chain records a peer found corrupted in its own stores
+    ChainRecordsRequested {
+        sender_id: NodeId,
+        keys: Vec<ChainRecordKey>,
+    },
+
+    ChainRecordsProvided(Vec<ChainRecord>),
+
+    /// Acknowledges a point-to-point consensus message was received by the
+    /// peer listening at `recipient_addr`, so its sender stops re-broadcasting
+    /// it
+    ConsensusMessageAcknowledged {
+        recipient_addr: SocketAddr,
+        digest: ConsensusMessageDigest,
+    },
+
     Ping(NodeId),
 
     #[default]
     Empty,
+
+    /// Any other event wrapped in a JSON encoded [EventEnvelope], sent to
+    /// peers that decode a versioned event schema. Events are tagged by name
+    /// within it, so peers can skip kinds introduced by newer versions
+    /// instead of failing to decode the message, see
+    /// [NetworkEvent::encode_for].
+    // NOTE: kept last so every other variant keeps the position legacy peers
+    // decode it by
+    Versioned(Vec<u8>),
+}
+
+fn legacy_event_schema_version() -> u16 {
+    LEGACY_EVENT_SCHEMA_VERSION
+}
+
+impl NetworkEvent {
+    /// Encodes the event for a peer that decodes up to
+    /// `event_schema_version`. Peers on the legacy schema get the event as
+    /// is, newer ones get it wrapped in a versioned envelope. Broadcasts hand
+    /// the same message to every peer, so they stay on the legacy schema
+    pub fn encode_for(self, event_schema_version: u16) -> NetworkEvent {
+        if event_schema_version <= LEGACY_EVENT_SCHEMA_VERSION
+            || matches!(self, NetworkEvent::Versioned(_))
+        {
+            return self;
+        }
+
+        let envelope = EventEnvelope {
+            version: event_schema_version.min(EVENT_SCHEMA_VERSION),
+            ..EventEnvelope::wrap(&self)
+        };
+
+        NetworkEvent::Versioned(serde_json::to_vec(&envelope).unwrap_or_default())
+    }
+
+    /// Unwraps an event a peer sent in a versioned envelope. Events of kinds
+    /// this node doesn't know, or that can't be decoded, are dropped as
+    /// [NetworkEvent::Empty]
+    pub fn decode_versioned(self) -> NetworkEvent {
+        let NetworkEvent::Versioned(data) = self else {
+            return self;
+        };
+
+        let decoded = serde_json::from_slice::<EventEnvelope>(&data)
+            .map_err(|err| EventDecodeError::Malformed(err.to_string()))
+            .and_then(EventEnvelope::into_inner::<NetworkEvent>);
+
+        match decoded {
+            Ok(NetworkEvent::Versioned(_)) => NetworkEvent::Empty,
+            Ok(event) => event,
+            Err(err) => {
+                telemetry::warn!("Dropping undecodable network event: {err}");
+                NetworkEvent::Empty
+            },
+        }
+    }
+
+    /// Returns the peer a consensus message claims to be sent by. Other
+    /// messages return `None`.
+    ///
+    /// The claim isn't authenticated, so message credits are charged to the
+    /// address the message arrived from instead.
+    pub fn consensus_sender(&self) -> Option<&NodeId> {
+        match self {
+            NetworkEvent::PartCommitmentCreated(node_id, _) => Some(node_id),
+            NetworkEvent::PartCommitmentAcknowledged { sender_id, .. } => Some(sender_id),
+            NetworkEvent::ConvergenceBlockCertified(block) => {
+                Some(&block.header.miner_claim.node_id)
+            },
+            NetworkEvent::MaintenanceModeChanged { node_id, .. } => Some(node_id),
+            NetworkEvent::ValidatorKeyRotated(rotation) => Some(&rotation.node_id),
+            NetworkEvent::HaltDirectiveSigned { node_id, .. } => Some(node_id),
+            _ => None,
+        }
+    }
+
+    /// Returns the digest recipients acknowledge a point-to-point consensus
+    /// message by. Messages sent without expecting an acknowledgement return
+    /// `None`.
+    pub fn consensus_digest(&self) -> Option<ConsensusMessageDigest> {
+        match self {
+            NetworkEvent::PartCommitmentCreated(..)
+            | NetworkEvent::PartCommitmentAcknowledged { .. } => {
+                Some(hex::encode(digest_data_to_bytes(self)))
+            },
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_are_versioned_only_for_peers_that_advertise_it() {
+        let event = NetworkEvent::Ping("node-1".to_string());
+
+        assert!(matches!(
+            event.clone().encode_for(LEGACY_EVENT_SCHEMA_VERSION),
+            NetworkEvent::Ping(_)
+        ));
+
+        let versioned = event.encode_for(EVENT_SCHEMA_VERSION + 1);
+        let NetworkEvent::Versioned(data) = &versioned else {
+            panic!("expected the event to be wrapped in an envelope");
+        };
+
+        let envelope: EventEnvelope = serde_json::from_slice(data).unwrap();
+        assert_eq!(envelope.version, EVENT_SCHEMA_VERSION);
+        assert_eq!(envelope.kind, "Ping");
+
+        assert!(matches!(
+            versioned.decode_versioned(),
+            NetworkEvent::Ping(node_id) if node_id == "node-1"
+        ));
+    }
+
+    #[test]
+    fn unknown_versioned_events_decode_as_empty() {
+        let envelope = EventEnvelope {
+            version: EVENT_SCHEMA_VERSION,
+            kind: "IntroducedLater".to_string(),
+            payload: serde_json::Value::Bool(true),
+        };
+        let event = NetworkEvent::Versioned(serde_json::to_vec(&envelope).unwrap());
+
+        assert!(matches!(event.decode_versioned(), NetworkEvent::Empty));
+        assert!(matches!(
+            NetworkEvent::Versioned(b"not json".to_vec()).decode_versioned(),
+            NetworkEvent::Empty
+        ));
+    }
 }
@@ -3,23 +3,91 @@ use dyswarm::types::Message as DyswarmMessage;
 use events::{Event, EventMessage, EventPublisher, PeerData};
 use primitives::NodeId;
 
-use crate::{network::NetworkEvent, NodeError};
+use crate::{
+    network::{NetworkEvent, SharedMessageCredits},
+    NodeError,
+};
 
 #[derive(Debug, Clone)]
 pub struct DyswarmHandler {
     pub node_id: NodeId,
     pub events_tx: EventPublisher,
+
+    /// Consensus messages from peers out of credits are dropped
+    pub message_credits: SharedMessageCredits,
+
+    /// Peers joining with another chain id or chain spec version are ignored
+    pub chain_id: String,
+    pub chain_spec_version: u32,
 }
 
 impl DyswarmHandler {
-    pub fn new(node_id: NodeId, events_tx: EventPublisher) -> Self {
-        Self { node_id, events_tx }
+    pub fn new(
+        node_id: NodeId,
+        events_tx: EventPublisher,
+        message_credits: SharedMessageCredits,
+        chain_id: String,
+        chain_spec_version: u32,
+    ) -> Self {
+        Self {
+            node_id,
+            events_tx,
+            message_credits,
+            chain_id,
+            chain_spec_version,
+        }
+    }
+
+    /// Charges the peer a consensus message arrived from, returning whether
+    /// the message should be processed
+    fn charge_sender(&self, msg: &DyswarmMessage<NetworkEvent>) -> bool {
+        if msg.data.consensus_sender().is_none() {
+            return true;
+        }
+
+        // NOTE: the server sets the source of every message it receives, only
+        // messages handed over in-process go without one
+        let Some(source) = msg.source else {
+            return true;
+        };
+
+        // NOTE: a poisoned lock shouldn't silence every peer
+        let Ok(mut message_credits) = self.message_credits.write() else {
+            return true;
+        };
+
+        if message_credits.try_consume(source.ip()) {
+            return true;
+        }
+
+        telemetry::warn!(
+            "Dropping consensus message from {}, who ran out of message credits",
+            source
+        );
+
+        false
     }
 }
 
 #[async_trait]
 impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
-    async fn handle(&self, msg: DyswarmMessage<NetworkEvent>) -> dyswarm::types::Result<()> {
+    async fn handle(&self, mut msg: DyswarmMessage<NetworkEvent>) -> dyswarm::types::Result<()> {
+        msg.data = msg.data.decode_versioned();
+
+        if !self.charge_sender(&msg) {
+            return Ok(());
+        }
+
+        let acknowledgement = msg
+            .data
+            .consensus_sender()
+            .cloned()
+            .zip(msg.data.consensus_digest());
+
+        // NOTE: the server records the address of the connection a message
+        // arrived over, unlike anything within the message itself
+        let source = msg.source;
+
         match msg.data {
             NetworkEvent::PeerJoined {
                 node_id,
@@ -28,19 +96,48 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                 udp_gossip_addr,
                 raptorq_gossip_addr,
                 kademlia_liveness_addr,
+                alternate_udp_gossip_addrs,
                 validator_public_key,
+                locality,
+                claim_signature,
+                chain_id,
+                chain_spec_version,
+                event_schema_version,
             } => {
+                // NOTE: peers that predate chain specs announce neither, they're
+                // let in until every node announces its chain spec
+                let other_chain = !chain_id.is_empty() && chain_id != self.chain_id;
+                let other_version =
+                    chain_spec_version != 0 && chain_spec_version != self.chain_spec_version;
+
+                if other_chain || other_version {
+                    telemetry::warn!(
+                        "Ignoring node {node_id}, which follows chain {chain_id} v{chain_spec_version} instead of {} v{}",
+                        self.chain_id,
+                        self.chain_spec_version
+                    );
+
+                    return Ok(());
+                }
+
                 telemetry::info!("Node {} joined network", node_id);
 
-                let evt = Event::PeerJoined(PeerData {
-                    node_id,
-                    node_type,
-                    kademlia_peer_id,
-                    udp_gossip_addr,
-                    raptorq_gossip_addr,
-                    kademlia_liveness_addr,
-                    validator_public_key,
-                });
+                let evt = Event::PeerJoined {
+                    peer_data: PeerData {
+                        node_id,
+                        node_type,
+                        kademlia_peer_id,
+                        udp_gossip_addr,
+                        raptorq_gossip_addr,
+                        kademlia_liveness_addr,
+                        alternate_udp_gossip_addrs,
+                        validator_public_key,
+                        locality,
+                        claim_signature,
+                        event_schema_version,
+                    },
+                    source,
+                };
 
                 // TODO: once all known peers have been joined, send a `NetworkReady` event so a
                 // dkg can be started and the first quorums can be formed
@@ -100,9 +197,272 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                 self.events_tx.send(em).await.map_err(NodeError::from)?;
             },
 
+            NetworkEvent::ValidatorSetUpdated(diff) => {
+                let evt = Event::ValidatorSetUpdateReceived(diff);
+                let em = EventMessage::new(Some("runtime-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::MaintenanceModeChanged { node_id, paused } => {
+                let evt = Event::PeerMaintenanceModeChanged { node_id, paused };
+                let em = EventMessage::new(Some("runtime-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::ValidatorKeyRotated(rotation) => {
+                if !rotation.verify() {
+                    telemetry::warn!(
+                        "Dropping key rotation of {} that isn't signed by both of its keys",
+                        rotation.node_id
+                    );
+                    return Ok(());
+                }
+
+                // NOTE: the runtime refreshes the key within the DKG state and
+                // quorum, then hands fresh rotations to the network module to
+                // refresh its peer store with
+                let evt = Event::PeerValidatorKeyRotated(rotation);
+                let em = EventMessage::new(Some("runtime-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::HaltDirectiveSigned {
+                node_id,
+                node_idx,
+                directive,
+                signature,
+            } => {
+                let evt = Event::PeerHaltDirectiveSigned {
+                    node_id,
+                    node_idx,
+                    directive,
+                    signature,
+                };
+                let em = EventMessage::new(Some("runtime-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::HaltDirectiveCertified(signed) => {
+                let evt = Event::PeerHaltDirectiveCertified(signed);
+                let em = EventMessage::new(Some("runtime-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::MempoolSketchAnnounced { sender_id, sketch } => {
+                let evt = Event::MempoolSketchReceived { sender_id, sketch };
+                let em = EventMessage::new(Some("network-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::MempoolDigestsOffered { sender_id, digests } => {
+                let evt = Event::MempoolDigestsOffered { sender_id, digests };
+                let em = EventMessage::new(Some("network-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::MempoolTxnsRequested { sender_id, digests } => {
+                let evt = Event::MempoolTxnsRequested { sender_id, digests };
+                let em = EventMessage::new(Some("network-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::MempoolTxnsProvided(txns) => {
+                let evt = Event::MempoolTxnsReceived(txns);
+                let em = EventMessage::new(Some("network-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::CertificatesRequested {
+                sender_id,
+                block_hashes,
+            } => {
+                let evt = Event::CertificatesRequested {
+                    sender_id,
+                    block_hashes,
+                };
+                let em = EventMessage::new(Some("runtime-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::CertificatesProvided(certificates) => {
+                let evt = Event::CertificatesReceived(certificates);
+                let em = EventMessage::new(Some("runtime-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::ChainRecordsRequested { sender_id, keys } => {
+                let evt = Event::ChainRecordsRequested { sender_id, keys };
+                let em = EventMessage::new(Some("runtime-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::ChainRecordsProvided(records) => {
+                let evt = Event::ChainRecordsReceived(records);
+                let em = EventMessage::new(Some("runtime-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::ConsensusMessageAcknowledged {
+                recipient_addr,
+                digest,
+            } => {
+                let evt = Event::ConsensusMessageAcknowledged {
+                    recipient_addr,
+                    digest,
+                };
+                let em = EventMessage::new(Some("network-events".into()), evt);
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
             _ => {},
         }
 
+        // NOTE: the sender keeps re-broadcasting point-to-point consensus messages until
+        // they're acknowledged
+        if let Some((sender_id, digest)) = acknowledgement {
+            let evt = Event::ConsensusMessageReceived { sender_id, digest };
+            let em = EventMessage::new(Some("network-events".into()), evt);
+            self.events_tx.send(em).await.map_err(NodeError::from)?;
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dyswarm::server::Handler;
+    use events::EVENT_SCHEMA_VERSION;
+    use primitives::{KademliaPeerId, NodeType, CHAIN_SPEC_VERSION, DEFAULT_CHAIN_ID};
+    use tokio::sync::mpsc::channel;
+    use vrrb_config::MessageCreditsConfig;
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+    use crate::network::MessageCredits;
+
+    fn join_intent(node_id: &str, chain_id: &str, chain_spec_version: u32) -> NetworkEvent {
+        let addr = "127.0.0.1:0".parse().unwrap();
+
+        NetworkEvent::PeerJoined {
+            node_id: node_id.to_string(),
+            node_type: NodeType::Validator,
+            kademlia_peer_id: KademliaPeerId::rand(),
+            udp_gossip_addr: addr,
+            raptorq_gossip_addr: addr,
+            kademlia_liveness_addr: addr,
+            alternate_udp_gossip_addrs: vec![],
+            validator_public_key: Keypair::random().validator_public_key_owned(),
+            locality: Default::default(),
+            claim_signature: None,
+            chain_id: chain_id.to_string(),
+            chain_spec_version,
+            event_schema_version: EVENT_SCHEMA_VERSION,
+        }
+    }
+
+    #[tokio::test]
+    async fn peers_on_another_chain_spec_are_ignored() {
+        let (events_tx, mut events_rx) = channel(8);
+        let handler = DyswarmHandler::new(
+            "node-0".into(),
+            events_tx,
+            MessageCredits::shared(MessageCreditsConfig::default()),
+            DEFAULT_CHAIN_ID.into(),
+            CHAIN_SPEC_VERSION,
+        );
+
+        for intent in [
+            join_intent("node-1", DEFAULT_CHAIN_ID, CHAIN_SPEC_VERSION + 1),
+            join_intent("node-2", "another-chain", CHAIN_SPEC_VERSION),
+            join_intent("node-3", "another-chain", 0),
+            join_intent("node-4", DEFAULT_CHAIN_ID, CHAIN_SPEC_VERSION),
+        ] {
+            handler.handle(DyswarmMessage::new(intent)).await.unwrap();
+        }
+
+        match Event::from(events_rx.try_recv().unwrap()) {
+            Event::PeerJoined { peer_data, .. } => assert_eq!(peer_data.node_id, "node-4"),
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        assert!(events_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn peers_that_announce_no_chain_spec_are_let_in() {
+        let (events_tx, mut events_rx) = channel(8);
+        let handler = DyswarmHandler::new(
+            "node-0".into(),
+            events_tx,
+            MessageCredits::shared(MessageCreditsConfig::default()),
+            DEFAULT_CHAIN_ID.into(),
+            CHAIN_SPEC_VERSION,
+        );
+
+        for intent in [
+            join_intent("node-1", "", 0),
+            join_intent("node-2", DEFAULT_CHAIN_ID, 0),
+            join_intent("node-3", "", CHAIN_SPEC_VERSION),
+        ] {
+            handler.handle(DyswarmMessage::new(intent)).await.unwrap();
+        }
+
+        for expected in ["node-1", "node-2", "node-3"] {
+            match Event::from(events_rx.try_recv().unwrap()) {
+                Event::PeerJoined { peer_data, .. } => assert_eq!(peer_data.node_id, expected),
+                other => panic!("unexpected event: {other:?}"),
+            }
+        }
+
+        assert!(events_rx.try_recv().is_err());
+    }
+
+    fn maintenance_notice(node_id: &str, source: &str) -> DyswarmMessage<NetworkEvent> {
+        let mut msg = DyswarmMessage::new(NetworkEvent::MaintenanceModeChanged {
+            node_id: node_id.to_string(),
+            paused: true,
+        });
+        msg.source = Some(source.parse().unwrap());
+
+        msg
+    }
+
+    #[tokio::test]
+    async fn consensus_messages_are_charged_to_the_address_they_arrive_from() {
+        let (events_tx, mut events_rx) = channel(8);
+        let handler = DyswarmHandler::new(
+            "node-0".into(),
+            events_tx,
+            MessageCredits::shared(MessageCreditsConfig {
+                initial_credits: 1,
+                refill_per_sec: 0,
+                ..Default::default()
+            }),
+            DEFAULT_CHAIN_ID.into(),
+            CHAIN_SPEC_VERSION,
+        );
+
+        // NOTE: claiming other node ids doesn't earn a flooding peer fresh
+        // credits, nor spend those of the peers it impersonates
+        for (node_id, source) in [
+            ("node-1", "10.0.0.1:9000"),
+            ("node-2", "10.0.0.1:9001"),
+            ("node-2", "10.0.0.2:9000"),
+        ] {
+            handler
+                .handle(maintenance_notice(node_id, source))
+                .await
+                .unwrap();
+        }
+
+        for expected in ["node-1", "node-2"] {
+            match Event::from(events_rx.try_recv().unwrap()) {
+                Event::PeerMaintenanceModeChanged { node_id, .. } => assert_eq!(node_id, expected),
+                other => panic!("unexpected event: {other:?}"),
+            }
+        }
+
+        assert!(events_rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,151 @@
+use std::{collections::HashMap, net::SocketAddr};
+
+use events::{PeerData, LEGACY_EVENT_SCHEMA_VERSION};
+use primitives::{dial_order, AddressFamily};
+
+/// Every UDP gossip address of the peers admitted to the peer list, keyed by
+/// the address the routing table knows them by.
+///
+/// The routing table holds a single address per peer, so peers announcing
+/// addresses in more than one family are looked up here to fall back to their
+/// other addresses when the first one can't be reached. The event schema
+/// version every peer announced is kept alongside, so messages sent to the
+/// peer are encoded in a version it decodes.
+#[derive(Debug, Clone, Default)]
+pub struct PeerAddressBook {
+    preferred_family: AddressFamily,
+    alternates: HashMap<SocketAddr, Vec<SocketAddr>>,
+    event_schema_versions: HashMap<SocketAddr, u16>,
+}
+
+impl PeerAddressBook {
+    pub fn new(preferred_family: AddressFamily) -> Self {
+        Self {
+            preferred_family,
+            alternates: HashMap::new(),
+            event_schema_versions: HashMap::new(),
+        }
+    }
+
+    /// Records the addresses a peer announced when joining
+    pub fn record(&mut self, peer_data: &PeerData) {
+        self.event_schema_versions
+            .insert(peer_data.udp_gossip_addr, peer_data.event_schema_version);
+
+        if peer_data.alternate_udp_gossip_addrs.is_empty() {
+            self.alternates.remove(&peer_data.udp_gossip_addr);
+            return;
+        }
+
+        self.alternates.insert(
+            peer_data.udp_gossip_addr,
+            peer_data.alternate_udp_gossip_addrs.clone(),
+        );
+    }
+
+    /// Addresses to dial the peer known by `addr` at, in the order they
+    /// should be tried
+    pub fn dial_order(&self, addr: SocketAddr) -> Vec<SocketAddr> {
+        let mut addrs = vec![addr];
+        addrs.extend(self.alternates.get(&addr).into_iter().flatten().copied());
+
+        dial_order(&addrs, self.preferred_family)
+    }
+
+    /// Newest event schema version the peer known by `addr` decodes, the
+    /// legacy one for peers that never announced one
+    pub fn event_schema_version(&self, addr: SocketAddr) -> u16 {
+        self.event_schema_versions
+            .get(&addr)
+            .copied()
+            .unwrap_or(LEGACY_EVENT_SCHEMA_VERSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use events::EVENT_SCHEMA_VERSION;
+    use primitives::{KademliaPeerId, NodeType};
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+
+    fn peer(udp_gossip_addr: &str, alternate_udp_gossip_addrs: &[&str]) -> PeerData {
+        let addr: SocketAddr = udp_gossip_addr.parse().unwrap();
+
+        PeerData {
+            node_id: "node-1".to_string(),
+            node_type: NodeType::Validator,
+            kademlia_peer_id: KademliaPeerId::rand(),
+            udp_gossip_addr: addr,
+            raptorq_gossip_addr: addr,
+            kademlia_liveness_addr: addr,
+            alternate_udp_gossip_addrs: alternate_udp_gossip_addrs
+                .iter()
+                .map(|addr| addr.parse().unwrap())
+                .collect(),
+            validator_public_key: Keypair::random().validator_public_key_owned(),
+            locality: Default::default(),
+            claim_signature: None,
+            event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
+        }
+    }
+
+    fn addrs(addrs: &[&str]) -> Vec<SocketAddr> {
+        addrs.iter().map(|addr| addr.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn peers_are_dialed_over_the_preferred_family_first() {
+        let peer = peer("10.0.0.1:9000", &["[2001:db8::1]:9000", "10.0.0.2:9000"]);
+
+        let mut ipv4_first = PeerAddressBook::new(AddressFamily::Ipv4);
+        ipv4_first.record(&peer);
+        assert_eq!(
+            ipv4_first.dial_order(peer.udp_gossip_addr),
+            addrs(&["10.0.0.1:9000", "10.0.0.2:9000", "[2001:db8::1]:9000"])
+        );
+
+        let mut ipv6_first = PeerAddressBook::new(AddressFamily::Ipv6);
+        ipv6_first.record(&peer);
+        assert_eq!(
+            ipv6_first.dial_order(peer.udp_gossip_addr),
+            addrs(&["[2001:db8::1]:9000", "10.0.0.1:9000", "10.0.0.2:9000"])
+        );
+    }
+
+    #[test]
+    fn peers_without_alternates_are_dialed_at_their_only_address() {
+        let mut address_book = PeerAddressBook::new(AddressFamily::Ipv6);
+        address_book.record(&peer("[::1]:9000", &["127.0.0.1:9000"]));
+        address_book.record(&peer("[::1]:9000", &[]));
+
+        assert_eq!(
+            address_book.dial_order("[::1]:9000".parse().unwrap()),
+            addrs(&["[::1]:9000"])
+        );
+    }
+
+    #[test]
+    fn peers_are_sent_the_event_schema_version_they_announced() {
+        let mut address_book = PeerAddressBook::new(AddressFamily::Ipv4);
+
+        let mut upgraded = peer("10.0.0.1:9000", &[]);
+        upgraded.event_schema_version = EVENT_SCHEMA_VERSION;
+        address_book.record(&upgraded);
+        address_book.record(&peer("10.0.0.2:9000", &["10.0.0.3:9000"]));
+
+        assert_eq!(
+            address_book.event_schema_version(upgraded.udp_gossip_addr),
+            EVENT_SCHEMA_VERSION
+        );
+        assert_eq!(
+            address_book.event_schema_version("10.0.0.2:9000".parse().unwrap()),
+            LEGACY_EVENT_SCHEMA_VERSION
+        );
+        assert_eq!(
+            address_book.event_schema_version("10.0.0.4:9000".parse().unwrap()),
+            LEGACY_EVENT_SCHEMA_VERSION
+        );
+    }
+}
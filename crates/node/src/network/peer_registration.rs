@@ -0,0 +1,337 @@
+use std::{
+    collections::HashSet,
+    net::{IpAddr, SocketAddr},
+    time::Instant,
+};
+
+use events::PeerData;
+use lru_time_cache::LruCache;
+use primitives::{NodeId, NodeType, StakingParams};
+use storage::vrrbdb::VrrbDbReadHandle;
+use vrrb_config::PeerRegistrationConfig;
+use vrrb_core::claim::Claim;
+
+use crate::{NodeError, Result};
+
+/// Failed registrations from a single source address.
+#[derive(Debug, Clone, Copy, Default)]
+struct FailedAttempts {
+    count: u32,
+    throttled_until: Option<Instant>,
+}
+
+/// Admits peers into the peer list only once they proved they hold a staked
+/// claim.
+///
+/// Registrants sign their registration payload with the key of their claim,
+/// which is looked up in the claim store by node id. Sources whose
+/// registrations keep failing, for instance because their claim can't be
+/// resolved, are ignored for a while instead of having their claim looked up
+/// again on every attempt. Failures are tracked by the address registrations
+/// arrive from rather than the node id they carry, which registrants pick
+/// themselves, and only for a bounded number of recently seen addresses.
+pub struct PeerRegistrationGuard {
+    config: PeerRegistrationConfig,
+    staking: StakingParams,
+    claims: VrrbDbReadHandle,
+
+    /// Peers allowed in without a claim, since no claim is staked before
+    /// genesis
+    exempt_peers: HashSet<NodeId>,
+    failed_attempts: LruCache<IpAddr, FailedAttempts>,
+}
+
+impl std::fmt::Debug for PeerRegistrationGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PeerRegistrationGuard")
+            .field("config", &self.config)
+            .field("staking", &self.staking)
+            .field("exempt_peers", &self.exempt_peers)
+            .field("failed_attempts", &self.failed_attempts.len())
+            .finish()
+    }
+}
+
+impl PeerRegistrationGuard {
+    pub fn new(
+        config: PeerRegistrationConfig,
+        staking: StakingParams,
+        claims: VrrbDbReadHandle,
+        exempt_peers: HashSet<NodeId>,
+    ) -> Self {
+        let failed_attempts = LruCache::with_capacity(config.max_tracked_sources);
+
+        Self {
+            config,
+            staking,
+            claims,
+            exempt_peers,
+            failed_attempts,
+        }
+    }
+
+    /// Checks whether a peer may be added to the peer list. `source` is the
+    /// address the registration arrived from, if it came over the network
+    pub fn admit(&mut self, peer_data: &PeerData, source: Option<SocketAddr>) -> Result<()> {
+        if !self.config.require_claim_proof || self.exempt_peers.contains(&peer_data.node_id) {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let node_id = &peer_data.node_id;
+        let source = source.map(|source| source.ip());
+
+        if let Some(attempts) = source.and_then(|source| self.failed_attempts.get(&source)) {
+            if matches!(attempts.throttled_until, Some(until) if until > now) {
+                return Err(NodeError::PeerRegistrationThrottled(node_id.clone()));
+            }
+        }
+
+        let claim = self.claims.get_claim(node_id).ok();
+
+        match self.verify(peer_data, claim) {
+            Ok(()) => {
+                if let Some(source) = source {
+                    self.failed_attempts.remove(&source);
+                }
+
+                Ok(())
+            },
+            Err(err) => {
+                if let Some(source) = source {
+                    self.record_failure(source, node_id, now);
+                }
+
+                Err(err)
+            },
+        }
+    }
+
+    /// Forgets the failed registrations from `source`, lifting its throttle
+    pub fn forget_failures(&mut self, source: SocketAddr) {
+        self.failed_attempts.remove(&source.ip());
+    }
+
+    fn verify(&self, peer_data: &PeerData, claim: Option<Claim>) -> Result<()> {
+        let node_id = peer_data.node_id.clone();
+
+        let claim = claim.ok_or_else(|| NodeError::PeerRegistrationRejected {
+            node_id: node_id.clone(),
+            reason: "no claim is known for the peer".to_string(),
+        })?;
+
+        peer_data
+            .verify_registration(&claim)
+            .map_err(|err| NodeError::PeerRegistrationRejected {
+                node_id: node_id.clone(),
+                reason: format!("invalid claim proof: {err}"),
+            })?;
+
+        let required_stake = match peer_data.node_type {
+            NodeType::Validator => self.staking.min_stake_validator,
+            _ => self.staking.min_stake_farmer,
+        };
+
+        if claim.get_stake() < required_stake {
+            return Err(NodeError::PeerRegistrationRejected {
+                node_id,
+                reason: format!(
+                    "claim holds a stake of {}, {required_stake} is required",
+                    claim.get_stake()
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn record_failure(&mut self, source: IpAddr, node_id: &NodeId, now: Instant) {
+        let attempts = self
+            .failed_attempts
+            .entry(source)
+            .or_insert_with(FailedAttempts::default);
+
+        // NOTE: peers get a fresh set of attempts once their throttle lapses
+        if attempts.throttled_until.is_some() {
+            *attempts = FailedAttempts::default();
+        }
+
+        attempts.count += 1;
+
+        if attempts.count >= self.config.max_failed_attempts {
+            telemetry::warn!(
+                "Throttling registrations from {} after {} failed attempts, the last one as {}",
+                source,
+                attempts.count,
+                node_id
+            );
+
+            attempts.throttled_until = Some(now + self.config.throttle_period);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use events::LEGACY_EVENT_SCHEMA_VERSION;
+    use primitives::{Address, KademliaPeerId};
+    use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+    use vrrb_config::DEFAULT_PEER_REGISTRATION_MAX_FAILED_ATTEMPTS;
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+
+    fn peer(node_id: &str) -> PeerData {
+        let addr = "127.0.0.1:0".parse().unwrap();
+
+        PeerData {
+            node_id: node_id.to_string(),
+            node_type: NodeType::Validator,
+            kademlia_peer_id: KademliaPeerId::rand(),
+            udp_gossip_addr: addr,
+            raptorq_gossip_addr: addr,
+            kademlia_liveness_addr: addr,
+            alternate_udp_gossip_addrs: vec![],
+            validator_public_key: Keypair::random().validator_public_key_owned(),
+            locality: Default::default(),
+            claim_signature: None,
+            event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
+        }
+    }
+
+    fn claim_for(node_id: &str, keypair: &Keypair) -> Claim {
+        let public_key = keypair.miner_public_key_owned();
+        let ip_address = "127.0.0.1:8080".parse().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            keypair.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+
+        Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature,
+            node_id.to_string(),
+        )
+        .unwrap()
+    }
+
+    fn source(port: u16) -> Option<SocketAddr> {
+        Some(SocketAddr::from(([10, 0, 0, port as u8], port)))
+    }
+
+    fn guard_with_claims(claims: Vec<Claim>) -> (VrrbDb, PeerRegistrationGuard) {
+        guard_with_config(claims, PeerRegistrationConfig::default())
+    }
+
+    fn guard_with_config(
+        claims: Vec<Claim>,
+        config: PeerRegistrationConfig,
+    ) -> (VrrbDb, PeerRegistrationGuard) {
+        let db_path =
+            std::env::temp_dir().join(format!("peer-registration-{}", uuid::Uuid::new_v4()));
+        let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(db_path));
+
+        for claim in claims {
+            db.insert_claim(claim).unwrap();
+        }
+
+        // NOTE: claims only hold stake once certified stake txns point to them
+        let staking = StakingParams {
+            min_stake_farmer: 0,
+            min_stake_validator: 0,
+            ..Default::default()
+        };
+
+        let guard = PeerRegistrationGuard::new(config, staking, db.read_handle(), HashSet::new());
+
+        (db, guard)
+    }
+
+    #[test]
+    fn peers_signing_with_their_claim_key_are_admitted() {
+        let keypair = Keypair::random();
+        let (_db, mut guard) = guard_with_claims(vec![claim_for("node-1", &keypair)]);
+
+        let mut registrant = peer("node-1");
+        assert!(guard.admit(&registrant, source(1)).is_err());
+
+        registrant
+            .sign_registration(&Keypair::random().miner_secret_key_owned())
+            .unwrap();
+        assert!(guard.admit(&registrant, source(1)).is_err());
+
+        registrant
+            .sign_registration(&keypair.miner_secret_key_owned())
+            .unwrap();
+        guard.admit(&registrant, source(1)).unwrap();
+    }
+
+    #[test]
+    fn sources_of_unresolvable_registrations_are_throttled() {
+        let (_db, mut guard) = guard_with_claims(vec![]);
+
+        let mut registrant = peer("node-1");
+        registrant
+            .sign_registration(&Keypair::random().miner_secret_key_owned())
+            .unwrap();
+
+        for _ in 0..DEFAULT_PEER_REGISTRATION_MAX_FAILED_ATTEMPTS {
+            assert!(matches!(
+                guard.admit(&registrant, source(1)),
+                Err(NodeError::PeerRegistrationRejected { .. })
+            ));
+        }
+
+        // NOTE: registering under another node id doesn't lift the throttle
+        let mut renamed = peer("node-2");
+        renamed
+            .sign_registration(&Keypair::random().miner_secret_key_owned())
+            .unwrap();
+
+        assert!(matches!(
+            guard.admit(&renamed, source(1)),
+            Err(NodeError::PeerRegistrationThrottled(_))
+        ));
+        assert!(matches!(
+            guard.admit(&registrant, source(2)),
+            Err(NodeError::PeerRegistrationRejected { .. })
+        ));
+    }
+
+    #[test]
+    fn only_recently_seen_sources_are_tracked() {
+        let config = PeerRegistrationConfig {
+            max_failed_attempts: 1,
+            max_tracked_sources: 2,
+            ..Default::default()
+        };
+        let (_db, mut guard) = guard_with_config(vec![], config);
+
+        for port in 1..=3 {
+            assert!(guard.admit(&peer("node-1"), source(port)).is_err());
+        }
+
+        assert_eq!(guard.failed_attempts.len(), 2);
+        assert!(matches!(
+            guard.admit(&peer("node-1"), source(1)),
+            Err(NodeError::PeerRegistrationRejected { .. })
+        ));
+        assert!(matches!(
+            guard.admit(&peer("node-1"), source(3)),
+            Err(NodeError::PeerRegistrationThrottled(_))
+        ));
+    }
+
+    #[test]
+    fn bootstrap_quorum_members_need_no_claim() {
+        let (_db, mut guard) = guard_with_claims(vec![]);
+        guard.exempt_peers.insert("node-1".to_string());
+
+        guard.admit(&peer("node-1"), source(1)).unwrap();
+        assert!(guard.admit(&peer("node-2"), source(1)).is_err());
+    }
+}
@@ -0,0 +1,359 @@
+use std::path::Path;
+
+use events::{PeerData, PeerRecord, PeerReputation, ValidatorKeyRotation};
+use primitives::{DbBackend, NodeId};
+use storage::vrrbdb::{open_backend, SharedBackend};
+use vrrb_config::BootstrapConfig;
+
+use crate::{NodeError, Result};
+
+/// Address book of the peers admitted to the peer list, persisted across
+/// restarts so a restarted node can reconnect to them right away instead of
+/// waiting to rediscover them.
+///
+/// Peers are ranked by their reputation score, when the node is built with the
+/// reputation module, and then by how recently they were heard from. Once the
+/// book is full, the lowest ranked peer is evicted to make room for new ones.
+/// The best ranked peers also stand in for the bootstrap nodes should none of
+/// those be reachable.
+#[derive(Debug, Clone)]
+pub struct PeerStore {
+    backend: SharedBackend,
+    max_peers: usize,
+
+    /// Peers in the address book, so it's only ranked once there are too many
+    len: usize,
+}
+
+impl PeerStore {
+    /// Opens the address book within `path`, picking up the peers recorded by
+    /// a previous run
+    pub fn open(path: &Path, backend: DbBackend, max_peers: usize) -> Result<Self> {
+        let backend = open_backend(backend, path.join("peer_store"), "peer_store")?;
+        let len = backend.entries()?.len();
+
+        Ok(Self {
+            backend,
+            max_peers,
+            len,
+        })
+    }
+
+    /// Every peer in the address book, best ranked first
+    pub fn peers(&self) -> Vec<PeerRecord> {
+        let entries = match self.backend.entries() {
+            Ok(entries) => entries,
+            Err(err) => {
+                telemetry::warn!("Failed to read the peer store: {err}");
+                return vec![];
+            },
+        };
+
+        let mut peers: Vec<PeerRecord> = entries
+            .into_iter()
+            .filter_map(|(_, value)| match bincode::deserialize(&value) {
+                Ok(record) => Some(record),
+                Err(err) => {
+                    telemetry::warn!("Ignoring unreadable peer record: {err}");
+                    None
+                },
+            })
+            .collect();
+
+        peers.sort_by(|a, b| rank(b).cmp(&rank(a)));
+        peers
+    }
+
+    /// Up to `limit` of the best ranked peers, as bootstrap candidates of last
+    /// resort
+    pub fn bootstrap_candidates(&self, limit: usize) -> Vec<BootstrapConfig> {
+        self.peers()
+            .into_iter()
+            .take(limit)
+            .map(|record| BootstrapConfig {
+                id: record.peer_data.kademlia_peer_id,
+                udp_gossip_addr: record.peer_data.udp_gossip_addr,
+                raptorq_gossip_addr: record.peer_data.raptorq_gossip_addr,
+                kademlia_liveness_addr: record.peer_data.kademlia_liveness_addr,
+                alternate_udp_gossip_addrs: record.peer_data.alternate_udp_gossip_addrs,
+            })
+            .collect()
+    }
+
+    /// Returns the record of `node_id`, if it is in the address book
+    pub fn get(&self, node_id: &NodeId) -> Result<Option<PeerRecord>> {
+        self.backend
+            .get(node_id.as_bytes())?
+            .map(|value| {
+                bincode::deserialize(&value)
+                    .map_err(|err| NodeError::Other(format!("unreadable peer record: {err}")))
+            })
+            .transpose()
+    }
+
+    /// Records that a peer was heard from, with the addresses and services it
+    /// announced
+    pub fn record_seen(&mut self, peer_data: &PeerData, message_credits: u64) -> Result<()> {
+        self.record_seen_at(peer_data, message_credits, chrono::Utc::now().timestamp())
+    }
+
+    /// Credits a peer in the address book with a contribution that got
+    /// certified. Peers missing from the book are ignored
+    pub fn record_certified_contribution(
+        &mut self,
+        node_id: &NodeId,
+        message_credits: u64,
+    ) -> Result<()> {
+        let Some(mut record) = self.get(node_id)? else {
+            return Ok(());
+        };
+
+        record.last_seen = chrono::Utc::now().timestamp();
+        record.reputation.certified_contributions += 1;
+        record.reputation.message_credits = message_credits;
+
+        self.put(&record)
+    }
+
+    /// Counts a partial signature of a peer that failed validation against
+    /// its reputation. Peers missing from the book are ignored
+    pub fn record_invalid_signature_share(&mut self, node_id: &NodeId) -> Result<()> {
+        let Some(mut record) = self.get(node_id)? else {
+            return Ok(());
+        };
+
+        record.reputation.invalid_signature_shares += 1;
+
+        self.put(&record)
+    }
+
+    /// Moves a stored peer to the validator key it rotated to. Its claim
+    /// signature covered the old key, so it's dropped until the peer
+    /// registers again.
+    pub fn record_validator_key_rotation(&mut self, rotation: &ValidatorKeyRotation) -> Result<()> {
+        let Some(mut record) = self.get(&rotation.node_id)? else {
+            return Ok(());
+        };
+
+        if record.peer_data.validator_public_key != rotation.old_public_key {
+            return Ok(());
+        }
+
+        record.peer_data.validator_public_key = rotation.new_public_key;
+        record.peer_data.claim_signature = None;
+
+        self.put(&record)
+    }
+
+    fn record_seen_at(
+        &mut self,
+        peer_data: &PeerData,
+        message_credits: u64,
+        last_seen: i64,
+    ) -> Result<()> {
+        let stored = self.get(&peer_data.node_id)?;
+        let is_new = stored.is_none();
+        let reputation = stored.map(|record| record.reputation).unwrap_or_default();
+
+        let record = PeerRecord {
+            peer_data: peer_data.clone(),
+            last_seen,
+            reputation: PeerReputation {
+                message_credits,
+                ..reputation
+            },
+        };
+
+        self.put(&record)?;
+
+        if is_new {
+            self.len += 1;
+        }
+
+        if self.len > self.max_peers {
+            self.evict()?;
+        }
+
+        Ok(())
+    }
+
+    fn put(&mut self, record: &PeerRecord) -> Result<()> {
+        let value = bincode::serialize(record)
+            .map_err(|err| NodeError::Other(format!("failed to serialize peer record: {err}")))?;
+
+        self.backend
+            .put(record.peer_data.node_id.as_bytes(), &value)
+            .map_err(NodeError::from)
+    }
+
+    fn evict(&mut self) -> Result<()> {
+        let peers = self.peers();
+
+        for evicted in peers.iter().skip(self.max_peers) {
+            self.backend.delete(evicted.peer_data.node_id.as_bytes())?;
+        }
+
+        self.len = peers.len().min(self.max_peers);
+
+        Ok(())
+    }
+}
+
+fn rank(record: &PeerRecord) -> (u64, i64) {
+    #[cfg(feature = "reputation")]
+    let score = record.reputation.score();
+
+    #[cfg(not(feature = "reputation"))]
+    let score = 0;
+
+    (score, record.last_seen)
+}
+
+#[cfg(test)]
+mod tests {
+    use events::LEGACY_EVENT_SCHEMA_VERSION;
+    use primitives::{KademliaPeerId, NodeType};
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+
+    fn peer(port: u16) -> PeerData {
+        let addr = format!("127.0.0.1:{port}").parse().unwrap();
+
+        PeerData {
+            node_id: format!("node-{port}"),
+            node_type: NodeType::Validator,
+            kademlia_peer_id: KademliaPeerId::rand(),
+            udp_gossip_addr: addr,
+            raptorq_gossip_addr: addr,
+            kademlia_liveness_addr: addr,
+            alternate_udp_gossip_addrs: vec![],
+            validator_public_key: Keypair::random().validator_public_key_owned(),
+            locality: Default::default(),
+            claim_signature: None,
+            event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
+        }
+    }
+
+    fn peer_store_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("peer-store-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn node_ids(peer_store: &PeerStore) -> Vec<NodeId> {
+        peer_store
+            .peers()
+            .into_iter()
+            .map(|record| record.peer_data.node_id)
+            .collect()
+    }
+
+    #[test]
+    fn peers_survive_restarts() {
+        let path = peer_store_path();
+
+        let mut peer_store = PeerStore::open(&path, DbBackend::default(), 10).unwrap();
+        assert!(peer_store.peers().is_empty());
+
+        let peer = peer(9001);
+        peer_store.record_seen_at(&peer, 20, 100).unwrap();
+        peer_store
+            .record_certified_contribution(&peer.node_id, 25)
+            .unwrap();
+        drop(peer_store);
+
+        let reopened = PeerStore::open(&path, DbBackend::default(), 10).unwrap();
+        let record = reopened.get(&peer.node_id).unwrap().unwrap();
+
+        assert_eq!(record.peer_data, peer);
+        assert_eq!(
+            record.reputation,
+            PeerReputation {
+                certified_contributions: 1,
+                message_credits: 25,
+                invalid_signature_shares: 0,
+            }
+        );
+        assert!(record.last_seen > 100);
+    }
+
+    #[test]
+    fn peers_recorded_by_previous_runs_count_towards_the_limit() {
+        let path = peer_store_path();
+
+        let mut peer_store = PeerStore::open(&path, DbBackend::default(), 2).unwrap();
+        peer_store.record_seen_at(&peer(9001), 0, 100).unwrap();
+        peer_store.record_seen_at(&peer(9002), 0, 200).unwrap();
+        drop(peer_store);
+
+        let mut reopened = PeerStore::open(&path, DbBackend::default(), 2).unwrap();
+        reopened.record_seen_at(&peer(9003), 0, 300).unwrap();
+
+        assert_eq!(
+            node_ids(&reopened),
+            vec!["node-9003".to_string(), "node-9002".to_string()]
+        );
+
+        let candidates: Vec<_> = reopened
+            .bootstrap_candidates(1)
+            .into_iter()
+            .map(|candidate| candidate.udp_gossip_addr)
+            .collect();
+
+        assert_eq!(candidates, vec![peer(9003).udp_gossip_addr]);
+    }
+
+    #[test]
+    fn lowest_ranked_peers_are_evicted_first() {
+        let mut peer_store = PeerStore::open(&peer_store_path(), DbBackend::default(), 2).unwrap();
+
+        let (first, second, third) = (peer(9001), peer(9002), peer(9003));
+        peer_store.record_seen_at(&first, 0, 100).unwrap();
+        peer_store.record_seen_at(&second, 0, 200).unwrap();
+        peer_store
+            .record_certified_contribution(&first.node_id, 0)
+            .unwrap();
+
+        // NOTE: certified contributions outrank recency, so of the peers
+        // without any, the one heard from longest ago is evicted
+        peer_store.record_seen_at(&third, 0, 300).unwrap();
+
+        assert_eq!(
+            node_ids(&peer_store),
+            vec![first.node_id.clone(), third.node_id.clone()]
+        );
+    }
+
+    #[cfg(feature = "reputation")]
+    #[test]
+    fn peers_sending_invalid_signature_shares_rank_last() {
+        let mut peer_store = PeerStore::open(&peer_store_path(), DbBackend::default(), 10).unwrap();
+
+        let (honest, junk_sender) = (peer(9001), peer(9002));
+        peer_store.record_seen_at(&honest, 0, 100).unwrap();
+        peer_store.record_seen_at(&junk_sender, 0, 200).unwrap();
+
+        for peer in [&honest, &junk_sender] {
+            peer_store
+                .record_certified_contribution(&peer.node_id, 0)
+                .unwrap();
+        }
+
+        peer_store
+            .record_invalid_signature_share(&junk_sender.node_id)
+            .unwrap();
+
+        assert_eq!(
+            node_ids(&peer_store),
+            vec![honest.node_id.clone(), junk_sender.node_id.clone()]
+        );
+        assert_eq!(
+            peer_store
+                .get(&junk_sender.node_id)
+                .unwrap()
+                .unwrap()
+                .reputation
+                .score(),
+            0
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use std::{
+    collections::HashSet,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use primitives::Round;
+
+/// Counters updated by the [Preconnector]. Shared with every
+/// [PreconnectMonitor] so they can be reported without touching it.
+#[derive(Debug, Default)]
+struct PreconnectMetrics {
+    scheduled_rounds: AtomicU64,
+    preconnected: AtomicU64,
+    failed: AtomicU64,
+    warm_first_messages: AtomicU64,
+    warm_latency_micros: AtomicU64,
+    cold_first_messages: AtomicU64,
+    cold_latency_micros: AtomicU64,
+}
+
+/// Point in time view of the pre-connection counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreconnectStats {
+    /// Rounds whose peers were connected to ahead of time
+    pub scheduled_rounds: u64,
+    pub preconnected: u64,
+    pub failed: u64,
+
+    /// First messages of a round sent to peers connected to ahead of time,
+    /// and how long sending them took on average
+    pub warm_first_messages: u64,
+    pub avg_warm_first_message_latency_micros: u64,
+
+    /// First messages of a round sent to peers that weren't connected to
+    /// ahead of time, and how long sending them took on average
+    pub cold_first_messages: u64,
+    pub avg_cold_first_message_latency_micros: u64,
+}
+
+impl PreconnectStats {
+    /// How much sooner first messages reach peers connected to ahead of time
+    /// than the others, on average. None until both kinds were sent
+    pub fn first_message_latency_improvement_micros(&self) -> Option<i64> {
+        if self.warm_first_messages == 0 || self.cold_first_messages == 0 {
+            return None;
+        }
+
+        Some(
+            self.avg_cold_first_message_latency_micros as i64
+                - self.avg_warm_first_message_latency_micros as i64,
+        )
+    }
+}
+
+/// Cloneable view over the pre-connection counters.
+#[derive(Debug, Clone, Default)]
+pub struct PreconnectMonitor {
+    metrics: Arc<PreconnectMetrics>,
+}
+
+impl PreconnectMonitor {
+    pub fn stats(&self) -> PreconnectStats {
+        let metrics = &self.metrics;
+
+        let average = |total: &AtomicU64, count: u64| {
+            total
+                .load(Ordering::Relaxed)
+                .checked_div(count)
+                .unwrap_or_default()
+        };
+
+        let warm_first_messages = metrics.warm_first_messages.load(Ordering::Relaxed);
+        let cold_first_messages = metrics.cold_first_messages.load(Ordering::Relaxed);
+
+        PreconnectStats {
+            scheduled_rounds: metrics.scheduled_rounds.load(Ordering::Relaxed),
+            preconnected: metrics.preconnected.load(Ordering::Relaxed),
+            failed: metrics.failed.load(Ordering::Relaxed),
+            warm_first_messages,
+            avg_warm_first_message_latency_micros: average(
+                &metrics.warm_latency_micros,
+                warm_first_messages,
+            ),
+            cold_first_messages,
+            avg_cold_first_message_latency_micros: average(
+                &metrics.cold_latency_micros,
+                cold_first_messages,
+            ),
+        }
+    }
+}
+
+/// Tracks the peers connected to ahead of the round they're needed in, timing
+/// the first message each peer is sent during the round.
+///
+/// First messages to peers connected to ahead of time are counted apart from
+/// the others, so the latency the handshakes cost is visible in
+/// [PreconnectStats].
+#[derive(Debug, Default)]
+pub struct Preconnector {
+    round: Option<Round>,
+
+    /// Peers connected to ahead of the round that weren't sent a message yet
+    awaiting_first_message: HashSet<SocketAddr>,
+
+    /// Peers sent a message since the round was scheduled
+    contacted: HashSet<SocketAddr>,
+    monitor: PreconnectMonitor,
+}
+
+impl Preconnector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn monitor(&self) -> PreconnectMonitor {
+        self.monitor.clone()
+    }
+
+    /// Round whose peers were last connected to ahead of time
+    pub fn round(&self) -> Option<Round> {
+        self.round
+    }
+
+    /// Starts connecting to the peers of `round`. Returns false if the round
+    /// was scheduled already
+    pub fn schedule(&mut self, round: Round) -> bool {
+        if self.round.map_or(false, |scheduled| scheduled >= round) {
+            return false;
+        }
+
+        self.round = Some(round);
+        self.awaiting_first_message.clear();
+        self.contacted.clear();
+
+        self.monitor
+            .metrics
+            .scheduled_rounds
+            .fetch_add(1, Ordering::Relaxed);
+
+        true
+    }
+
+    /// Records that a connection to `addr` was established ahead of time
+    pub fn record_preconnected(&mut self, addr: SocketAddr) {
+        if self.contacted.contains(&addr) {
+            return;
+        }
+
+        if self.awaiting_first_message.insert(addr) {
+            self.monitor
+                .metrics
+                .preconnected
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Records that connecting to a peer ahead of time failed
+    pub fn record_failed(&mut self) {
+        self.monitor.metrics.failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records that a message took `latency` to reach `addr`, timing it if it
+    /// is the first the peer was sent this round
+    pub fn record_sent(&mut self, addr: SocketAddr, latency: Duration) {
+        if !self.contacted.insert(addr) {
+            return;
+        }
+
+        let metrics = &self.monitor.metrics;
+        let latency_micros = latency.as_micros() as u64;
+
+        if self.awaiting_first_message.remove(&addr) {
+            metrics.warm_first_messages.fetch_add(1, Ordering::Relaxed);
+            metrics
+                .warm_latency_micros
+                .fetch_add(latency_micros, Ordering::Relaxed);
+        } else {
+            metrics.cold_first_messages.fetch_add(1, Ordering::Relaxed);
+            metrics
+                .cold_latency_micros
+                .fetch_add(latency_micros, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_messages_are_timed_apart_for_preconnected_peers() {
+        let warm: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let cold: SocketAddr = "127.0.0.1:9001".parse().unwrap();
+
+        let mut preconnector = Preconnector::new();
+        let monitor = preconnector.monitor();
+
+        assert!(preconnector.schedule(2));
+        assert!(!preconnector.schedule(1));
+
+        preconnector.record_preconnected(warm);
+        preconnector.record_failed();
+
+        preconnector.record_sent(warm, Duration::from_micros(200));
+        preconnector.record_sent(cold, Duration::from_micros(1_000));
+
+        // NOTE: only the first message to a peer each round is timed
+        preconnector.record_sent(warm, Duration::from_micros(5_000));
+
+        let stats = monitor.stats();
+        assert_eq!(stats.scheduled_rounds, 1);
+        assert_eq!(stats.preconnected, 1);
+        assert_eq!(stats.failed, 1);
+        assert_eq!(stats.warm_first_messages, 1);
+        assert_eq!(stats.avg_warm_first_message_latency_micros, 200);
+        assert_eq!(stats.cold_first_messages, 1);
+        assert_eq!(stats.avg_cold_first_message_latency_micros, 1_000);
+        assert_eq!(stats.first_message_latency_improvement_micros(), Some(800));
+
+        assert!(preconnector.schedule(3));
+        preconnector.record_sent(warm, Duration::from_micros(300));
+
+        let stats = monitor.stats();
+        assert_eq!(stats.cold_first_messages, 2);
+        assert_eq!(stats.avg_cold_first_message_latency_micros, 650);
+    }
+}
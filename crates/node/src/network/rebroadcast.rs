@@ -0,0 +1,302 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+    time::Instant,
+};
+
+use vrrb_config::RebroadcastConfig;
+
+use super::{ConsensusMessageDigest, NetworkEvent};
+
+#[derive(Debug, Clone)]
+struct PendingMessage {
+    message: NetworkEvent,
+    attempts: u32,
+    first_sent_at: Instant,
+    due_at: Instant,
+}
+
+/// Counters updated by the [Rebroadcaster]. Shared with every
+/// [RebroadcastMonitor] so they can be reported without touching it.
+#[derive(Debug, Default)]
+struct RebroadcastMetrics {
+    pending: AtomicUsize,
+    tracked: AtomicU64,
+    acknowledged: AtomicU64,
+    rebroadcast: AtomicU64,
+    abandoned: AtomicU64,
+    rebroadcast_by_peer: RwLock<HashMap<SocketAddr, u64>>,
+}
+
+/// Point in time view of the re-broadcast counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RebroadcastStats {
+    /// Messages currently awaiting an acknowledgement
+    pub pending: usize,
+    pub tracked: u64,
+    pub acknowledged: u64,
+    pub rebroadcast: u64,
+
+    /// Messages given up on after running out of attempts
+    pub abandoned: u64,
+
+    /// Re-broadcasts per peer address. Peers piling these up sit behind lossy
+    /// links
+    pub rebroadcast_by_peer: HashMap<SocketAddr, u64>,
+}
+
+/// Cloneable view over the re-broadcast counters.
+#[derive(Debug, Clone, Default)]
+pub struct RebroadcastMonitor {
+    metrics: Arc<RebroadcastMetrics>,
+}
+
+impl RebroadcastMonitor {
+    pub fn stats(&self) -> RebroadcastStats {
+        let rebroadcast_by_peer = self
+            .metrics
+            .rebroadcast_by_peer
+            .read()
+            .map(|rebroadcast_by_peer| rebroadcast_by_peer.clone())
+            .unwrap_or_default();
+
+        RebroadcastStats {
+            pending: self.metrics.pending.load(Ordering::Relaxed),
+            tracked: self.metrics.tracked.load(Ordering::Relaxed),
+            acknowledged: self.metrics.acknowledged.load(Ordering::Relaxed),
+            rebroadcast: self.metrics.rebroadcast.load(Ordering::Relaxed),
+            abandoned: self.metrics.abandoned.load(Ordering::Relaxed),
+            rebroadcast_by_peer,
+        }
+    }
+}
+
+/// Tracks the point-to-point consensus messages sent to peers until they
+/// acknowledge them, handing the ones lost in transit back for re-broadcast.
+///
+/// Messages are sent again with exponential backoff until acknowledged or
+/// until they run out of attempts, at which point they're given up on.
+#[derive(Debug)]
+pub struct Rebroadcaster {
+    config: RebroadcastConfig,
+    pending: HashMap<(SocketAddr, ConsensusMessageDigest), PendingMessage>,
+    monitor: RebroadcastMonitor,
+}
+
+impl Rebroadcaster {
+    pub fn new(config: RebroadcastConfig) -> Self {
+        Self {
+            config,
+            pending: HashMap::new(),
+            monitor: RebroadcastMonitor::default(),
+        }
+    }
+
+    pub fn monitor(&self) -> RebroadcastMonitor {
+        self.monitor.clone()
+    }
+
+    /// Starts waiting on `addr` to acknowledge the message known by `digest`
+    /// it was just sent
+    pub fn track(
+        &mut self,
+        addr: SocketAddr,
+        digest: ConsensusMessageDigest,
+        message: &NetworkEvent,
+    ) {
+        self.track_at(addr, digest, message, Instant::now())
+    }
+
+    /// Stops waiting on `addr` to acknowledge a message, returning whether it
+    /// was awaited
+    pub fn acknowledge(&mut self, addr: SocketAddr, digest: &ConsensusMessageDigest) -> bool {
+        let acknowledged = self.pending.remove(&(addr, digest.clone())).is_some();
+
+        if acknowledged {
+            let metrics = &self.monitor.metrics;
+            metrics.acknowledged.fetch_add(1, Ordering::Relaxed);
+            metrics.pending.store(self.pending.len(), Ordering::Relaxed);
+        }
+
+        acknowledged
+    }
+
+    /// Messages due to be sent again, along with the address of the peer
+    /// each is for. Messages out of attempts are given up on instead
+    pub fn due(&mut self) -> Vec<(SocketAddr, NetworkEvent)> {
+        self.due_at(Instant::now())
+    }
+
+    fn track_at(
+        &mut self,
+        addr: SocketAddr,
+        digest: ConsensusMessageDigest,
+        message: &NetworkEvent,
+        now: Instant,
+    ) {
+        let key = (addr, digest);
+        if !self.pending.contains_key(&key) && self.pending.len() >= self.config.max_pending {
+            self.abandon_oldest();
+        }
+
+        self.pending.insert(
+            key,
+            PendingMessage {
+                message: message.clone(),
+                attempts: 1,
+                first_sent_at: now,
+                due_at: now + self.config.backoff(1),
+            },
+        );
+
+        let metrics = &self.monitor.metrics;
+        metrics.tracked.fetch_add(1, Ordering::Relaxed);
+        metrics.pending.store(self.pending.len(), Ordering::Relaxed);
+    }
+
+    fn due_at(&mut self, now: Instant) -> Vec<(SocketAddr, NetworkEvent)> {
+        let max_attempts = self.config.max_attempts;
+        let metrics = &self.monitor.metrics;
+
+        let mut due = vec![];
+        let mut abandoned = vec![];
+
+        for ((addr, digest), pending) in self.pending.iter_mut() {
+            if pending.due_at > now {
+                continue;
+            }
+
+            if pending.attempts >= max_attempts {
+                abandoned.push((*addr, digest.clone()));
+                continue;
+            }
+
+            pending.attempts += 1;
+            pending.due_at = now + self.config.backoff(pending.attempts);
+
+            due.push((*addr, pending.message.clone()));
+        }
+
+        for key in abandoned {
+            telemetry::warn!(
+                "Giving up on consensus message {} to {}, it was never acknowledged",
+                key.1,
+                key.0
+            );
+
+            self.pending.remove(&key);
+            metrics.abandoned.fetch_add(1, Ordering::Relaxed);
+        }
+
+        metrics
+            .rebroadcast
+            .fetch_add(due.len() as u64, Ordering::Relaxed);
+        metrics.pending.store(self.pending.len(), Ordering::Relaxed);
+
+        if let Ok(mut rebroadcast_by_peer) = metrics.rebroadcast_by_peer.write() {
+            for (addr, _) in due.iter() {
+                *rebroadcast_by_peer.entry(*addr).or_default() += 1;
+            }
+        }
+
+        due
+    }
+
+    fn abandon_oldest(&mut self) {
+        let oldest = self
+            .pending
+            .iter()
+            .min_by_key(|(_, pending)| pending.first_sent_at)
+            .map(|(key, _)| key.clone());
+
+        if let Some(key) = oldest {
+            self.pending.remove(&key);
+            self.monitor
+                .metrics
+                .abandoned
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    fn rebroadcaster() -> Rebroadcaster {
+        Rebroadcaster::new(RebroadcastConfig {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(3),
+            max_attempts: 3,
+            max_pending: 2,
+            ..Default::default()
+        })
+    }
+
+    fn message(node_id: &str) -> NetworkEvent {
+        NetworkEvent::Ping(node_id.to_string())
+    }
+
+    #[test]
+    fn unacknowledged_messages_are_rebroadcast_with_backoff_until_given_up_on() {
+        let mut rebroadcaster = rebroadcaster();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        rebroadcaster.track_at(addr(9001), "lost".to_string(), &message("node-0"), start);
+        rebroadcaster.track_at(addr(9002), "acked".to_string(), &message("node-0"), start);
+
+        assert!(rebroadcaster.acknowledge(addr(9002), &"acked".to_string()));
+        assert!(rebroadcaster.due_at(at(0)).is_empty());
+
+        // NOTE: waits 1s after the first send, then 2s, and gives up once the
+        // third send goes unacknowledged for 3s
+        assert_eq!(rebroadcaster.due_at(at(1)).len(), 1);
+        assert!(rebroadcaster.due_at(at(2)).is_empty());
+        assert_eq!(rebroadcaster.due_at(at(3)).len(), 1);
+        assert!(rebroadcaster.due_at(at(5)).is_empty());
+        assert!(rebroadcaster.due_at(at(6)).is_empty());
+
+        let stats = rebroadcaster.monitor().stats();
+        assert_eq!(
+            stats,
+            RebroadcastStats {
+                pending: 0,
+                tracked: 2,
+                acknowledged: 1,
+                rebroadcast: 2,
+                abandoned: 1,
+                rebroadcast_by_peer: HashMap::from([(addr(9001), 2)]),
+            }
+        );
+    }
+
+    #[test]
+    fn oldest_messages_are_given_up_on_once_too_many_are_pending() {
+        let mut rebroadcaster = rebroadcaster();
+        let start = Instant::now();
+
+        for (secs, digest) in [(0, "first"), (1, "second"), (2, "third")] {
+            rebroadcaster.track_at(
+                addr(9001),
+                digest.to_string(),
+                &message("node-0"),
+                start + Duration::from_secs(secs),
+            );
+        }
+
+        assert!(!rebroadcaster.acknowledge(addr(9001), &"first".to_string()));
+        assert!(rebroadcaster.acknowledge(addr(9001), &"second".to_string()));
+        assert_eq!(rebroadcaster.monitor().stats().abandoned, 1);
+    }
+}
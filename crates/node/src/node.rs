@@ -3,7 +3,7 @@ use std::{
     net::SocketAddr,
 };
 
-use events::{Event, EventPublisher, EventRouter, Topic};
+use events::{Event, EventPublisher, EventRouter, MailboxMonitor, MailboxStats, Topic};
 use primitives::{KademliaPeerId, NodeType};
 use telemetry::info;
 use tokio::{
@@ -14,10 +14,23 @@ use tokio_util::sync::CancellationToken;
 use vrrb_config::NodeConfig;
 use vrrb_core::keypair::{KeyPair, Keypair};
 use vrrb_core::node_health_report::NodeHealthReport;
+use vrrb_rpc::rpc::{PublicRpcMonitor, PublicRpcStats, RpcCacheMonitor, RpcCacheStats};
 
+#[cfg(feature = "byzantine")]
+use crate::FaultInjector;
 use crate::{
-    data_store::DataStore, result::Result, runtime::setup_runtime_components,
-    state_reader::StateReader, NodeError, RuntimeComponentManager,
+    data_store::DataStore,
+    network::{PreconnectMonitor, PreconnectStats, RebroadcastMonitor, RebroadcastStats},
+    preflight::run_preflight_checks,
+    replication::{ReplicationMonitor, ReplicationStats},
+    result::Result,
+    runtime::{
+        block_import::{BlockImportMonitor, BlockImportStats},
+        setup_runtime_components, JSON_RPC_API_CONTROL_TOPIC, NETWORK_EVENTS_TOPIC,
+        RUNTIME_EVENTS_TOPIC,
+    },
+    state_reader::StateReader,
+    NodeError, RuntimeComponentManager,
 };
 
 /// Node represents a member of the VRRB network and it is responsible for
@@ -31,6 +44,15 @@ pub struct Node {
 
     cancel_token: CancellationToken,
     runtime_control_handle: JoinHandle<Result<()>>,
+    mailbox_monitors: Vec<MailboxMonitor>,
+    block_import_monitor: BlockImportMonitor,
+    rebroadcast_monitor: RebroadcastMonitor,
+    preconnect_monitor: PreconnectMonitor,
+    public_rpc_monitor: PublicRpcMonitor,
+    rpc_cache_monitor: RpcCacheMonitor,
+    replication_monitor: ReplicationMonitor,
+    #[cfg(feature = "byzantine")]
+    fault_injector: FaultInjector,
 }
 
 pub type UnboundedControlEventReceiver = UnboundedReceiver<Event>;
@@ -43,14 +65,37 @@ impl Node {
 
         info!("Launching Node {}", &config.id);
 
+        // NOTE: runs before any actor starts so misconfigured nodes fail with
+        // every problem at once instead of one at a time, mid-startup
+        run_preflight_checks(&config)?;
+
+        telemetry::set_redaction_enabled(!config.disable_log_redaction)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
         let keypair = config.keypair.clone();
 
-        let (events_tx, mut events_rx) = channel(events::DEFAULT_BUFFER);
+        let (events_tx, events_rx) = channel(events::DEFAULT_BUFFER);
+
+        // NOTE: a byzantine node misbehaves in what it publishes, so faults are injected
+        // between its actors and the router rather than into what it receives
+        #[cfg(feature = "byzantine")]
+        let fault_injector = FaultInjector::new(config.id.clone(), *keypair.get_miner_secret_key());
+
+        #[cfg(feature = "byzantine")]
+        let events_rx = fault_injector.spawn(events_rx);
+
+        let mut events_rx = events_rx;
+
+        let mailbox_config = &config.mailbox_config;
 
         let mut router = EventRouter::new();
-        router.add_topic(Topic::from("json-rpc-api-control"), Some(1));
-        router.add_topic(Topic::from("network-events"), Some(1000));
-        router.add_topic(Topic::from("runtime-events"), Some(1000));
+        for topic in [
+            JSON_RPC_API_CONTROL_TOPIC,
+            NETWORK_EVENTS_TOPIC,
+            RUNTIME_EVENTS_TOPIC,
+        ] {
+            router.add_topic(Topic::from(topic), Some(mailbox_config.capacity_for(topic)));
+        }
 
         let cancel_token = CancellationToken::new();
         let cloned_token = cancel_token.clone();
@@ -58,6 +103,14 @@ impl Node {
         let (runtime_component_manager, updated_node_config) =
             setup_runtime_components(&config, &router, events_tx.clone()).await?;
 
+        let mailbox_monitors = runtime_component_manager.mailbox_monitors();
+        let block_import_monitor = runtime_component_manager.block_import_monitor();
+        let rebroadcast_monitor = runtime_component_manager.rebroadcast_monitor();
+        let preconnect_monitor = runtime_component_manager.preconnect_monitor();
+        let public_rpc_monitor = runtime_component_manager.public_rpc_monitor();
+        let rpc_cache_monitor = runtime_component_manager.rpc_cache_monitor();
+        let replication_monitor = runtime_component_manager.replication_monitor();
+
         // TODO: report error from handle
         let router_handle = tokio::spawn(async move { router.start(&mut events_rx).await });
         let runtime_control_handle = tokio::spawn(Self::run_node_main_process(
@@ -73,6 +126,15 @@ impl Node {
             keypair,
             cancel_token,
             runtime_control_handle,
+            mailbox_monitors,
+            block_import_monitor,
+            rebroadcast_monitor,
+            preconnect_monitor,
+            public_rpc_monitor,
+            rpc_cache_monitor,
+            replication_monitor,
+            #[cfg(feature = "byzantine")]
+            fault_injector,
         })
     }
 
@@ -158,6 +220,56 @@ impl Node {
         self.config.jsonrpc_server_address
     }
 
+    /// Reports the queue depth and overload counters of every actor's mailbox
+    pub fn mailbox_stats(&self) -> Vec<MailboxStats> {
+        self.mailbox_monitors
+            .iter()
+            .map(|monitor| monitor.stats())
+            .collect()
+    }
+
+    /// Reports the depth of the block import queue and how long each import
+    /// stage takes
+    pub fn block_import_stats(&self) -> BlockImportStats {
+        self.block_import_monitor.stats()
+    }
+
+    /// Reports how many consensus messages had to be re-broadcast for lack of
+    /// an acknowledgement, overall and per peer, to catch lossy links
+    pub fn rebroadcast_stats(&self) -> RebroadcastStats {
+        self.rebroadcast_monitor.stats()
+    }
+
+    /// Reports how many peers were connected to ahead of the round they were
+    /// needed in, and how much sooner the round's first messages reached them
+    pub fn preconnect_stats(&self) -> PreconnectStats {
+        self.preconnect_monitor.stats()
+    }
+
+    /// Reports the usage of every API key of the public JSON-RPC server, and
+    /// how many of their requests were turned away
+    pub fn public_rpc_stats(&self) -> PublicRpcStats {
+        self.public_rpc_monitor.stats()
+    }
+
+    /// Reports how many txn and block lookups the JSON-RPC server answered
+    /// from its cache rather than the DB
+    pub fn rpc_cache_stats(&self) -> RpcCacheStats {
+        self.rpc_cache_monitor.stats()
+    }
+
+    /// Reports how far a read replica trails its leader, and how often it had
+    /// to resync from a fresh snapshot
+    pub fn replication_stats(&self) -> ReplicationStats {
+        self.replication_monitor.stats()
+    }
+
+    /// Returns a handle tests can use to make this node misbehave
+    #[cfg(feature = "byzantine")]
+    pub fn fault_injector(&self) -> FaultInjector {
+        self.fault_injector.clone()
+    }
+
     /// Reports metrics about the node's health
     pub fn health_check(&self) -> Result<NodeHealthReport> {
         Ok(NodeHealthReport::default())
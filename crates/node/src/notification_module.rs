@@ -0,0 +1,460 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use async_trait::async_trait;
+use block::{Block, BlockHash};
+use events::{Event, EventMessage, EventSubscriber};
+use primitives::{Epoch, NodeId, QuorumKind, Round};
+use secp256k1::{Message, Secp256k1};
+use serde::Serialize;
+use telemetry::{info, warn};
+use theater::{Actor, ActorId, ActorImpl, ActorLabel, ActorState, Handler};
+use tokio::task::JoinHandle;
+use vrrb_config::{NodeConfig, NotificationConfig, NotificationKind};
+use vrrb_core::keypair::Keypair;
+use vrrb_http::webhook::{SignedPayload, WebhookClient, WebhookRetryPolicy};
+
+use crate::{NodeError, Result};
+
+/// Rounds of proposal blocks kept around to catch proposers equivocating
+pub const EQUIVOCATION_WINDOW_ROUNDS: Round = 64;
+
+/// A critical event operators are notified of.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CriticalEvent {
+    QuorumMembershipLost {
+        epoch: Epoch,
+        quorum_kind: QuorumKind,
+    },
+    DutiesMissed {
+        /// Recent convergence blocks the node holds no certificate for
+        uncertified_blocks: Vec<BlockHash>,
+    },
+    EquivocationDetected {
+        proposer: NodeId,
+        round: Round,
+        block_hashes: Vec<BlockHash>,
+    },
+    ChainDataCorrupted {
+        store: String,
+
+        /// Hex encoded keys of the corrupted records
+        keys: Vec<String>,
+    },
+}
+
+impl CriticalEvent {
+    pub fn kind(&self) -> NotificationKind {
+        match self {
+            CriticalEvent::QuorumMembershipLost { .. } => NotificationKind::QuorumMembershipLost,
+            CriticalEvent::DutiesMissed { .. } => NotificationKind::DutiesMissed,
+            CriticalEvent::EquivocationDetected { .. } => NotificationKind::EquivocationDetected,
+            CriticalEvent::ChainDataCorrupted { .. } => NotificationKind::ChainDataCorrupted,
+        }
+    }
+}
+
+/// Payload posted to the webhooks, signed by the node's key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Notification {
+    pub node_id: NodeId,
+    pub timestamp: i64,
+
+    #[serde(flatten)]
+    pub event: CriticalEvent,
+}
+
+pub struct NotificationModuleConfig {
+    pub node_id: NodeId,
+    pub keypair: Keypair,
+    pub notification_config: NotificationConfig,
+}
+
+/// Watches the events of the node for critical ones and notifies operators
+/// of them through the configured webhooks.
+#[derive(Debug)]
+pub struct NotificationModule {
+    status: ActorState,
+    label: ActorLabel,
+    id: ActorId,
+    node_id: NodeId,
+    keypair: Keypair,
+    notification_config: NotificationConfig,
+    webhooks: Vec<WebhookClient>,
+
+    /// Hashes of the proposal blocks seen from every proposer, by round
+    proposals: BTreeMap<Round, HashMap<NodeId, BTreeSet<BlockHash>>>,
+
+    /// Epoch the node was last notified to have left its quorum at
+    membership_lost_at: Option<Epoch>,
+
+    /// Uncertified blocks operators were already notified of
+    uncertified_blocks: BTreeSet<BlockHash>,
+}
+
+impl NotificationModule {
+    pub fn new(config: NotificationModuleConfig) -> Result<Self> {
+        let notification_config = config.notification_config;
+
+        let retry_policy = WebhookRetryPolicy {
+            initial_backoff: notification_config.initial_backoff,
+            max_backoff: notification_config.max_backoff,
+            max_attempts: notification_config.max_attempts,
+        };
+
+        let webhooks = notification_config
+            .webhooks
+            .iter()
+            .map(|url| WebhookClient::new(url.clone(), retry_policy.clone()))
+            .collect::<vrrb_http::Result<Vec<_>>>()
+            .map_err(|err| NodeError::Other(format!("invalid webhook: {err}")))?;
+
+        Ok(Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            status: ActorState::Stopped,
+            label: String::from("Notification"),
+            node_id: config.node_id,
+            keypair: config.keypair,
+            notification_config,
+            webhooks,
+            proposals: BTreeMap::new(),
+            membership_lost_at: None,
+            uncertified_blocks: BTreeSet::new(),
+        })
+    }
+
+    /// Returns the critical event `event` signals, if any
+    fn critical_event(&mut self, event: &Event) -> Option<CriticalEvent> {
+        match event {
+            Event::ValidatorSetUpdated(diff) | Event::ValidatorSetUpdateReceived(diff) => {
+                if !diff.removed.contains(&self.node_id)
+                    || self.membership_lost_at == Some(diff.epoch)
+                {
+                    return None;
+                }
+
+                self.membership_lost_at = Some(diff.epoch);
+
+                Some(CriticalEvent::QuorumMembershipLost {
+                    epoch: diff.epoch,
+                    quorum_kind: diff.quorum_kind.clone(),
+                })
+            },
+
+            Event::MissingCertificatesRequested(block_hashes) => {
+                let block_hashes: BTreeSet<BlockHash> = block_hashes.iter().cloned().collect();
+
+                // NOTE: blocks stay uncertified across reconciliations until recovered,
+                // operators are only told about the ones they didn't hear of yet
+                let uncertified_blocks: Vec<BlockHash> = block_hashes
+                    .difference(&self.uncertified_blocks)
+                    .cloned()
+                    .collect();
+
+                self.uncertified_blocks = block_hashes;
+
+                (!uncertified_blocks.is_empty())
+                    .then_some(CriticalEvent::DutiesMissed { uncertified_blocks })
+            },
+
+            Event::BlockReceived(Block::Proposal { block }) => {
+                let latest_round = self.proposals.keys().next_back().copied();
+                if latest_round.map_or(false, |round| {
+                    block.round.saturating_add(EQUIVOCATION_WINDOW_ROUNDS) <= round
+                }) {
+                    return None;
+                }
+
+                let proposer = block.from.node_id.clone();
+                let block_hashes = self
+                    .proposals
+                    .entry(block.round)
+                    .or_default()
+                    .entry(proposer.clone())
+                    .or_default();
+
+                // NOTE: only the first conflicting proposal is reported
+                let equivocated =
+                    block_hashes.insert(block.hash.clone()) && block_hashes.len() == 2;
+
+                let event = equivocated.then(|| CriticalEvent::EquivocationDetected {
+                    proposer,
+                    round: block.round,
+                    block_hashes: block_hashes.iter().cloned().collect(),
+                });
+
+                self.prune_proposals();
+
+                event
+            },
+
+            Event::ChainDataCorrupted { store, keys } => Some(CriticalEvent::ChainDataCorrupted {
+                store: store.clone(),
+                keys: keys.clone(),
+            }),
+
+            _ => None,
+        }
+    }
+
+    /// Forgets the proposal blocks of rounds that left the equivocation
+    /// window
+    fn prune_proposals(&mut self) {
+        if let Some(latest_round) = self.proposals.keys().next_back().copied() {
+            let oldest_round = latest_round.saturating_sub(EQUIVOCATION_WINDOW_ROUNDS - 1);
+            self.proposals = self.proposals.split_off(&oldest_round);
+        }
+    }
+
+    /// Serializes `notification` and signs it with the node's key
+    fn sign(&self, notification: &Notification) -> Result<SignedPayload> {
+        let body =
+            serde_json::to_string(notification).map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let secp = Secp256k1::new();
+        let message = Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(body.as_bytes());
+        let signature = secp.sign_ecdsa(&message, self.keypair.get_miner_secret_key());
+
+        Ok(SignedPayload {
+            body,
+            signature: signature.to_string(),
+            public_key: self.keypair.get_miner_public_key().to_string(),
+        })
+    }
+
+    /// Delivers `event` to every webhook in the background, so slow or
+    /// unreachable webhooks can't hold up the node
+    fn notify(&self, event: CriticalEvent) -> Result<()> {
+        info!("Notifying operators of {:?}", event.kind());
+
+        let notification = Notification {
+            node_id: self.node_id.clone(),
+            timestamp: chrono::Utc::now().timestamp(),
+            event,
+        };
+
+        let payload = self.sign(&notification)?;
+
+        for webhook in self.webhooks.iter().cloned() {
+            let payload = payload.clone();
+
+            tokio::spawn(async move {
+                if let Err(err) = webhook.deliver(&payload).await {
+                    warn!("Could not notify operators: {err}");
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Handler<EventMessage> for NotificationModule {
+    fn id(&self) -> ActorId {
+        self.id.clone()
+    }
+
+    fn label(&self) -> ActorLabel {
+        self.label.clone()
+    }
+
+    fn status(&self) -> ActorState {
+        self.status.clone()
+    }
+
+    fn set_status(&mut self, actor_status: ActorState) {
+        self.status = actor_status;
+    }
+
+    fn on_stop(&self) {
+        info!(
+            "{}-{} received stop signal. Stopping",
+            self.label(),
+            self.id(),
+        );
+    }
+
+    async fn handle(&mut self, event: EventMessage) -> theater::Result<ActorState> {
+        let event: Event = event.into();
+
+        if let Event::Stop = event {
+            return Ok(ActorState::Stopped);
+        }
+
+        if let Some(critical_event) = self.critical_event(&event) {
+            if self.notification_config.notifies(critical_event.kind()) {
+                if let Err(err) = self.notify(critical_event) {
+                    warn!("Could not notify operators: {err}");
+                }
+            }
+        }
+
+        Ok(ActorState::Running)
+    }
+}
+
+pub fn setup_notification_module(
+    config: &NodeConfig,
+    mut notification_events_rx: EventSubscriber,
+) -> Result<Option<JoinHandle<Result<()>>>> {
+    if !config.notification_config.is_enabled() {
+        return Ok(None);
+    }
+
+    let module = NotificationModule::new(NotificationModuleConfig {
+        node_id: config.id.clone(),
+        keypair: config.keypair.clone(),
+        notification_config: config.notification_config.clone(),
+    })?;
+
+    let mut notification_module_actor = ActorImpl::new(module);
+
+    let notification_handle = tokio::spawn(async move {
+        notification_module_actor
+            .start(&mut notification_events_rx)
+            .await
+            .map_err(|err| NodeError::Other(err.to_string()))
+    });
+
+    Ok(Some(notification_handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use vrrb_config::ValidatorSetDiff;
+    use vrrb_core::keypair::KeyPair;
+
+    use super::*;
+    use crate::test_utils::produce_proposal_blocks;
+
+    fn notification_module() -> NotificationModule {
+        NotificationModule::new(NotificationModuleConfig {
+            node_id: "node-1".to_string(),
+            keypair: Keypair::random(),
+            notification_config: NotificationConfig {
+                webhooks: vec!["http://localhost:3555/alerts".to_string()],
+                ..Default::default()
+            },
+        })
+        .unwrap()
+    }
+
+    fn proposal(round: Round, proposer: &str, hash: &str) -> Event {
+        let mut block = produce_proposal_blocks("genesis".to_string(), vec![], 1, 0).remove(0);
+        block.round = round;
+        block.from.node_id = proposer.to_string();
+        block.hash = hash.to_string();
+
+        Event::BlockReceived(Block::Proposal { block })
+    }
+
+    #[test]
+    fn conflicting_proposals_are_reported_once() {
+        let mut module = notification_module();
+
+        assert_eq!(module.critical_event(&proposal(7, "node-2", "a")), None);
+        assert_eq!(module.critical_event(&proposal(7, "node-2", "a")), None);
+        assert_eq!(module.critical_event(&proposal(7, "node-3", "b")), None);
+
+        assert_eq!(
+            module.critical_event(&proposal(7, "node-2", "b")),
+            Some(CriticalEvent::EquivocationDetected {
+                proposer: "node-2".to_string(),
+                round: 7,
+                block_hashes: vec!["a".to_string(), "b".to_string()],
+            })
+        );
+        assert_eq!(module.critical_event(&proposal(7, "node-2", "c")), None);
+
+        // NOTE: proposals from rounds outside the window are not tracked
+        module.critical_event(&proposal(7 + EQUIVOCATION_WINDOW_ROUNDS, "node-2", "d"));
+        assert_eq!(module.critical_event(&proposal(7, "node-4", "e")), None);
+        assert_eq!(module.critical_event(&proposal(7, "node-4", "f")), None);
+    }
+
+    #[test]
+    fn operators_hear_of_lost_membership_and_missed_certificates_once() {
+        let mut module = notification_module();
+
+        let mut diff = ValidatorSetDiff::new(3, QuorumKind::Harvester);
+        assert_eq!(
+            module.critical_event(&Event::ValidatorSetUpdated(diff.clone())),
+            None
+        );
+
+        diff.removed.insert("node-1".to_string());
+        assert_eq!(
+            module.critical_event(&Event::ValidatorSetUpdated(diff.clone())),
+            Some(CriticalEvent::QuorumMembershipLost {
+                epoch: 3,
+                quorum_kind: QuorumKind::Harvester,
+            })
+        );
+        assert_eq!(
+            module.critical_event(&Event::ValidatorSetUpdateReceived(diff)),
+            None
+        );
+
+        let missing = |block_hashes: &[&str]| {
+            Event::MissingCertificatesRequested(
+                block_hashes.iter().map(|hash| hash.to_string()).collect(),
+            )
+        };
+
+        assert_eq!(
+            module.critical_event(&missing(&["a", "b"])),
+            Some(CriticalEvent::DutiesMissed {
+                uncertified_blocks: vec!["a".to_string(), "b".to_string()],
+            })
+        );
+        assert_eq!(module.critical_event(&missing(&["b"])), None);
+        assert_eq!(
+            module.critical_event(&missing(&["b", "c"])),
+            Some(CriticalEvent::DutiesMissed {
+                uncertified_blocks: vec!["c".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn unrecoverable_chain_data_corruption_is_reported() {
+        let mut module = notification_module();
+
+        let event = Event::ChainDataCorrupted {
+            store: "txn_receipts".to_string(),
+            keys: vec!["0a0b".to_string()],
+        };
+
+        assert_eq!(
+            module.critical_event(&event),
+            Some(CriticalEvent::ChainDataCorrupted {
+                store: "txn_receipts".to_string(),
+                keys: vec!["0a0b".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn notifications_are_signed_by_the_node() {
+        let module = notification_module();
+
+        let notification = Notification {
+            node_id: module.node_id.clone(),
+            timestamp: 0,
+            event: CriticalEvent::DutiesMissed {
+                uncertified_blocks: vec!["a".to_string()],
+            },
+        };
+
+        let payload = module.sign(&notification).unwrap();
+
+        assert!(payload.body.contains("\"kind\":\"duties_missed\""));
+        assert!(KeyPair::verify_ecdsa_sign(
+            payload.signature,
+            payload.body.as_bytes(),
+            module.keypair.get_miner_public_key().serialize().to_vec(),
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,163 @@
+//! Modules a node can be built without.
+//!
+//! Each module is gated behind the cargo feature of the same name, along
+//! with the crates it's built on, so embedders that only need the mempool
+//! and state, such as indexers, can build nodes that leave consensus out. Events handled by a module the node
+//! was built without are skipped, and operations that need one fail with
+//! [NodeError::ModuleUnavailable].
+
+use std::fmt::Display;
+
+use events::Event;
+use primitives::NodeType;
+
+use crate::{NodeError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptionalModule {
+    /// Quorum membership, txn votes and block certification
+    Consensus,
+    /// Distributed key generation of the quorum keysets
+    Dkg,
+    /// Production of proposal, convergence and genesis blocks
+    Mining,
+    /// Ranking of peers by their certified contributions
+    Reputation,
+}
+
+impl OptionalModule {
+    pub const ALL: [OptionalModule; 4] = [
+        OptionalModule::Consensus,
+        OptionalModule::Dkg,
+        OptionalModule::Mining,
+        OptionalModule::Reputation,
+    ];
+
+    /// Returns the cargo feature that compiles the module in
+    pub fn feature(&self) -> &'static str {
+        match self {
+            OptionalModule::Consensus => "consensus",
+            OptionalModule::Dkg => "dkg",
+            OptionalModule::Mining => "mining",
+            OptionalModule::Reputation => "reputation",
+        }
+    }
+
+    /// Returns whether the node was built with the module
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            OptionalModule::Consensus => cfg!(feature = "consensus"),
+            OptionalModule::Dkg => cfg!(feature = "dkg"),
+            OptionalModule::Mining => cfg!(feature = "mining"),
+            OptionalModule::Reputation => cfg!(feature = "reputation"),
+        }
+    }
+
+    /// Every module the node was built with
+    pub fn enabled() -> Vec<OptionalModule> {
+        Self::ALL
+            .into_iter()
+            .filter(|module| module.is_enabled())
+            .collect()
+    }
+
+    /// Fails with [NodeError::ModuleUnavailable] unless the node was built
+    /// with the module
+    pub fn require(&self, action: &str) -> Result<()> {
+        if self.is_enabled() {
+            return Ok(());
+        }
+
+        Err(self.unavailable(action))
+    }
+
+    pub fn unavailable(&self, action: &str) -> NodeError {
+        NodeError::ModuleUnavailable {
+            module: *self,
+            action: action.to_string(),
+        }
+    }
+
+    /// Returns the modules a node of the given type can't run without
+    pub fn required_by(node_type: NodeType) -> Vec<OptionalModule> {
+        match node_type {
+            NodeType::Validator => vec![OptionalModule::Consensus, OptionalModule::Dkg],
+            NodeType::Miner => vec![OptionalModule::Mining],
+            NodeType::Full | NodeType::MasterNode => vec![
+                OptionalModule::Consensus,
+                OptionalModule::Dkg,
+                OptionalModule::Mining,
+            ],
+            NodeType::Bootstrap => vec![],
+        }
+    }
+
+    /// Returns the module that handles `event` within the node runtime, if
+    /// it takes an optional one
+    pub fn handling(event: &Event) -> Option<OptionalModule> {
+        match event {
+            Event::PartCommitmentCreated(..) | Event::PartCommitmentAcknowledged { .. } => {
+                Some(OptionalModule::Dkg)
+            },
+            Event::ProposalBlockMineRequestCreated { .. } => Some(OptionalModule::Mining),
+            Event::NodeAddedToPeerList(_)
+            | Event::QuorumMembershipAssigmentCreated(_)
+            | Event::QuorumElectionStarted(_)
+            | Event::ValidatorSetUpdateReceived(_)
+            | Event::PeerMaintenanceModeChanged { .. }
+            | Event::ValidatorKeyRotationRequested
+            | Event::PeerValidatorKeyRotated(_)
+            | Event::HaltDirectiveRequested(_)
+            | Event::PeerHaltDirectiveSigned { .. }
+            | Event::MinerElectionStarted(_)
+            | Event::MinerElectionRetryDue
+            | Event::TransactionCertificateRequested { .. }
+            | Event::TransactionCertificateCreated { .. }
+            | Event::ConvergenceBlockSignatureRequested(_)
+            | Event::ConvergenceBlockPartialSignatureCreated { .. }
+            | Event::ConvergenceBlockPeerSignatureRequested { .. }
+            | Event::ConvergenceBlockPrecheckRequested { .. }
+            | Event::TxnsReadyForProcessing(_)
+            | Event::TxnsValidated { .. } => Some(OptionalModule::Consensus),
+            _ => None,
+        }
+    }
+}
+
+impl Display for OptionalModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.feature())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unavailable_modules_name_the_feature_to_rebuild_with() {
+        let err = OptionalModule::Mining.unavailable("mine convergence block");
+
+        assert_eq!(
+            err.to_string(),
+            "cannot mine convergence block: this node was built without the mining module, \
+             enable the `mining` feature to use it"
+        );
+
+        assert_eq!(
+            OptionalModule::Mining
+                .require("mine convergence block")
+                .is_ok(),
+            cfg!(feature = "mining")
+        );
+    }
+
+    #[test]
+    fn bootstrap_nodes_boot_without_any_optional_module() {
+        assert!(OptionalModule::required_by(NodeType::Bootstrap).is_empty());
+        assert_eq!(
+            OptionalModule::required_by(NodeType::Miner),
+            vec![OptionalModule::Mining]
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! Checks run on a node's environment before any of its actors start.
+//!
+//! Misconfigured nodes would otherwise fail halfway through their startup, or
+//! once they are already running, with errors that rarely point at the cause.
+//! Every check runs regardless of the others failing, so the report lists
+//! every problem at once along with what to do about it.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    net::{SocketAddr, TcpListener, UdpSocket},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use vrrb_config::NodeConfig;
+
+use crate::{NodeError, Result};
+
+/// Version of the layout of the data dir, bumped whenever a node can no
+/// longer read the data dirs of the previous release
+pub const DATA_DIR_VERSION: u32 = 1;
+
+/// File within the data dir that records the node and chain it belongs to
+pub const DATA_DIR_MANIFEST_FILE: &str = "node_manifest.json";
+
+/// 2023-01-01T00:00:00Z. Clocks reading an earlier time were never set
+pub const MIN_PLAUSIBLE_UNIX_TIMESTAMP: i64 = 1_672_531_200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PreflightCheck {
+    DataDir,
+    DiskSpace,
+    PortBinding,
+    Identity,
+    Clock,
+    Genesis,
+}
+
+impl Display for PreflightCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PreflightCheck::DataDir => "data dir",
+            PreflightCheck::DiskSpace => "disk space",
+            PreflightCheck::PortBinding => "port binding",
+            PreflightCheck::Identity => "identity",
+            PreflightCheck::Clock => "clock",
+            PreflightCheck::Genesis => "genesis",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreflightFailure {
+    pub check: PreflightCheck,
+    pub problem: String,
+    pub remedy: String,
+}
+
+/// Every check that failed before a node started
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreflightReport {
+    pub failures: Vec<PreflightFailure>,
+}
+
+impl PreflightReport {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn has_failed(&self, check: PreflightCheck) -> bool {
+        self.failures.iter().any(|failure| failure.check == check)
+    }
+
+    fn fail(&mut self, check: PreflightCheck, problem: String, remedy: impl Into<String>) {
+        self.failures.push(PreflightFailure {
+            check,
+            problem,
+            remedy: remedy.into(),
+        });
+    }
+}
+
+impl Display for PreflightReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for failure in &self.failures {
+            writeln!(
+                f,
+                "  - [{}] {}: {}",
+                failure.check, failure.problem, failure.remedy
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Node and chain a data dir was initialized for. It is written on the first
+/// start and refreshed on every start that passes the checks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataDirManifest {
+    pub version: u32,
+    pub node_id: String,
+    /// Hex encoded validator public key of the node
+    pub validator_public_key: String,
+    pub chain_id: String,
+    pub genesis_hash: Option<String>,
+    /// Unix time the node last started at
+    pub last_started_at: i64,
+}
+
+impl DataDirManifest {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(DATA_DIR_MANIFEST_FILE)
+    }
+}
+
+/// Checks the node's environment is fit to run it, failing with
+/// [NodeError::PreflightFailed] and every problem found otherwise
+pub fn run_preflight_checks(config: &NodeConfig) -> Result<()> {
+    if !config.preflight_config.enabled {
+        return Ok(());
+    }
+
+    let report = check_environment(config, chrono::Utc::now().timestamp());
+
+    if !report.is_ok() {
+        return Err(NodeError::PreflightFailed(report));
+    }
+
+    Ok(())
+}
+
+/// Runs every check against `config` as of the unix time `now`
+pub fn check_environment(config: &NodeConfig, now: i64) -> PreflightReport {
+    let mut report = PreflightReport::default();
+
+    check_data_dir(config, &mut report);
+    check_disk_space(config, &mut report);
+    check_ports(config, &mut report);
+
+    if now < MIN_PLAUSIBLE_UNIX_TIMESTAMP {
+        report.fail(
+            PreflightCheck::Clock,
+            format!("the system clock reads unix time {now}, which is before 2023"),
+            "set the system clock, e.g. by syncing it through NTP",
+        );
+    }
+
+    let manifest = read_manifest(config, &mut report);
+
+    if let Some(manifest) = &manifest {
+        check_manifest(config, manifest, now, &mut report);
+    }
+
+    if report.is_ok() {
+        if let Err(err) = write_manifest(config, manifest.as_ref(), now) {
+            report.fail(
+                PreflightCheck::DataDir,
+                format!("failed to record the node's manifest: {err}"),
+                "make sure the node can write to its data dir",
+            );
+        }
+    }
+
+    report
+}
+
+fn check_data_dir(config: &NodeConfig, report: &mut PreflightReport) {
+    let data_dir = &config.data_dir;
+
+    // NOTE: the probe is named after the node so nodes sharing a data dir, as
+    // they do in tests, don't trip over each other's probes
+    let probe = data_dir.join(format!(".preflight-{}", config.id));
+
+    let writable = fs::create_dir_all(data_dir)
+        .and_then(|_| fs::write(&probe, b"preflight"))
+        .and_then(|_| fs::remove_file(&probe));
+
+    if let Err(err) = writable {
+        report.fail(
+            PreflightCheck::DataDir,
+            format!("data dir {} is not writable: {err}", data_dir.display()),
+            "create the directory and give the user running the node write access to it",
+        );
+    }
+}
+
+fn check_disk_space(config: &NodeConfig, report: &mut PreflightReport) {
+    let min_free_disk_space = config.preflight_config.min_free_disk_space;
+
+    // NOTE: a missing data dir was already reported
+    let Ok(available) = fs2::available_space(&config.data_dir) else {
+        return;
+    };
+
+    if available < min_free_disk_space {
+        report.fail(
+            PreflightCheck::DiskSpace,
+            format!(
+                "only {available} bytes are free on the volume of {}, {min_free_disk_space} are \
+                 required",
+                config.data_dir.display()
+            ),
+            "free up space, move the data dir to a larger volume or lower \
+             `preflight_config.min_free_disk_space`",
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Transport {
+    Tcp,
+    Udp,
+}
+
+/// Addresses the node will listen on, by the name of their config field.
+/// Addresses on port 0 are left out since the OS picks a free port for them
+fn listen_addresses(config: &NodeConfig) -> Vec<(&'static str, SocketAddr, Transport)> {
+    let mut addresses = vec![(
+        "jsonrpc_server_address",
+        config.jsonrpc_server_address,
+        Transport::Tcp,
+    )];
+
+    if config.enable_rest_gateway {
+        addresses.push(("http_api_address", config.http_api_address, Transport::Tcp));
+    }
+
+    if !config.disable_networking {
+        addresses.extend([
+            (
+                "udp_gossip_address",
+                config.udp_gossip_address,
+                Transport::Udp,
+            ),
+            (
+                "raptorq_gossip_address",
+                config.raptorq_gossip_address,
+                Transport::Udp,
+            ),
+            (
+                "kademlia_liveness_address",
+                config.kademlia_liveness_address,
+                Transport::Udp,
+            ),
+        ]);
+    }
+
+    addresses.retain(|(_, address, _)| address.port() != 0);
+    addresses
+}
+
+fn check_ports(config: &NodeConfig, report: &mut PreflightReport) {
+    let mut claimed: HashMap<(SocketAddr, Transport), &'static str> = HashMap::new();
+
+    for (name, address, transport) in listen_addresses(config) {
+        if let Some(other) = claimed.insert((address, transport), name) {
+            report.fail(
+                PreflightCheck::PortBinding,
+                format!("{other} and {name} are both set to {address}"),
+                "give each of them an address of its own",
+            );
+            continue;
+        }
+
+        let bound = match transport {
+            Transport::Tcp => TcpListener::bind(address).map(drop),
+            Transport::Udp => UdpSocket::bind(address).map(drop),
+        };
+
+        if let Err(err) = bound {
+            report.fail(
+                PreflightCheck::PortBinding,
+                format!("cannot bind {name} to {address}: {err}"),
+                "stop whatever holds the port or set another address",
+            );
+        }
+    }
+}
+
+fn read_manifest(config: &NodeConfig, report: &mut PreflightReport) -> Option<DataDirManifest> {
+    let path = DataDirManifest::path(&config.data_dir);
+    let contents = fs::read_to_string(&path).ok()?;
+
+    match serde_json::from_str(&contents) {
+        Ok(manifest) => Some(manifest),
+        Err(err) => {
+            report.fail(
+                PreflightCheck::DataDir,
+                format!("manifest {} is corrupt: {err}", path.display()),
+                "restore it from a backup, or delete it if the data dir belongs to this node",
+            );
+            None
+        },
+    }
+}
+
+fn check_manifest(
+    config: &NodeConfig,
+    manifest: &DataDirManifest,
+    now: i64,
+    report: &mut PreflightReport,
+) {
+    if manifest.version != DATA_DIR_VERSION {
+        report.fail(
+            PreflightCheck::DataDir,
+            format!(
+                "the data dir is laid out in version {}, this node reads version \
+                 {DATA_DIR_VERSION}",
+                manifest.version
+            ),
+            "run the release that initialized the data dir or start from a fresh one",
+        );
+    }
+
+    let validator_public_key = hex::encode(config.keypair.validator_public_key_owned().to_bytes());
+
+    if manifest.node_id == config.id && manifest.validator_public_key != validator_public_key {
+        report.fail(
+            PreflightCheck::Identity,
+            format!(
+                "node {} was initialized with validator key {}, but its keypair holds {}",
+                config.id, manifest.validator_public_key, validator_public_key
+            ),
+            "restore the node's original keypair or start it from a fresh data dir",
+        );
+    }
+
+    let rollback = manifest.last_started_at.saturating_sub(now);
+    let max_clock_rollback = config.preflight_config.max_clock_rollback.as_secs() as i64;
+
+    if rollback > max_clock_rollback {
+        report.fail(
+            PreflightCheck::Clock,
+            format!("the system clock is {rollback}s behind the time the node last started at"),
+            "sync the system clock, e.g. through NTP",
+        );
+    }
+
+    let chain_spec = &config.chain_spec;
+
+    if manifest.chain_id != chain_spec.chain_id {
+        report.fail(
+            PreflightCheck::Genesis,
+            format!(
+                "the data dir holds chain {}, but the chain spec is for {}",
+                manifest.chain_id, chain_spec.chain_id
+            ),
+            "start the node with the chain spec it was initialized with or from a fresh data dir",
+        );
+    }
+
+    if let (Some(recorded), Some(expected)) = (&manifest.genesis_hash, &chain_spec.genesis_hash) {
+        if recorded != expected {
+            report.fail(
+                PreflightCheck::Genesis,
+                format!(
+                    "the data dir holds a chain with genesis {recorded}, but the chain spec \
+                     expects {expected}"
+                ),
+                "start the node with the chain spec it was initialized with or from a fresh \
+                 data dir",
+            );
+        }
+    }
+}
+
+fn write_manifest(
+    config: &NodeConfig,
+    previous: Option<&DataDirManifest>,
+    now: i64,
+) -> std::io::Result<()> {
+    let manifest = DataDirManifest {
+        version: DATA_DIR_VERSION,
+        node_id: config.id.clone(),
+        validator_public_key: hex::encode(config.keypair.validator_public_key_owned().to_bytes()),
+        chain_id: config.chain_spec.chain_id.clone(),
+        genesis_hash: config
+            .chain_spec
+            .genesis_hash
+            .clone()
+            .or_else(|| previous.and_then(|manifest| manifest.genesis_hash.clone())),
+        last_started_at: now,
+    };
+
+    let contents = serde_json::to_vec_pretty(&manifest)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+
+    // NOTE: written aside and renamed into place so a crash, or another node
+    // starting off the same data dir, never leaves a torn manifest behind
+    let path = DataDirManifest::path(&config.data_dir);
+    let staging_path = config
+        .data_dir
+        .join(format!("{DATA_DIR_MANIFEST_FILE}.{}", config.id));
+
+    fs::write(&staging_path, contents)?;
+    fs::rename(staging_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+    use crate::test_utils::create_mock_full_node_config;
+
+    const NOW: i64 = MIN_PLAUSIBLE_UNIX_TIMESTAMP + 1_000_000;
+
+    fn config_with_fresh_data_dir() -> NodeConfig {
+        let mut config = create_mock_full_node_config();
+        config.data_dir = std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+        config.preflight_config.min_free_disk_space = 0;
+        config
+    }
+
+    #[test]
+    fn restarts_with_another_validator_key_are_refused() {
+        let mut config = config_with_fresh_data_dir();
+
+        assert!(check_environment(&config, NOW).is_ok());
+        assert!(DataDirManifest::path(&config.data_dir).exists());
+        assert!(check_environment(&config, NOW + 60).is_ok());
+
+        config.keypair = Keypair::random();
+
+        let report = check_environment(&config, NOW + 120);
+        assert_eq!(report.failures.len(), 1);
+        assert!(report.has_failed(PreflightCheck::Identity));
+    }
+
+    #[test]
+    fn every_failed_check_is_reported_at_once() {
+        let mut config = config_with_fresh_data_dir();
+        config.chain_spec.genesis_hash = Some("genesis".to_string());
+
+        assert!(check_environment(&config, NOW).is_ok());
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        config.jsonrpc_server_address = listener.local_addr().unwrap();
+        config.chain_spec.genesis_hash = Some("another-genesis".to_string());
+
+        let report = check_environment(&config, NOW - 3_600);
+
+        assert!(report.has_failed(PreflightCheck::PortBinding));
+        assert!(report.has_failed(PreflightCheck::Clock));
+        assert!(report.has_failed(PreflightCheck::Genesis));
+        assert_eq!(report.failures.len(), 3);
+
+        let err = NodeError::PreflightFailed(report).to_string();
+        assert!(err.contains("[port binding] cannot bind jsonrpc_server_address"));
+    }
+}
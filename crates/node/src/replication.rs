@@ -0,0 +1,530 @@
+//! Read replicas.
+//!
+//! A replica keeps its own copy of the state of a leader node by following
+//! the leader's `state_subscribeReplication` stream, without taking part in
+//! consensus, so it can serve read-only RPC traffic off the leader.
+//!
+//! The stream opens with a snapshot of the leader's state, followed by the
+//! diff of every block the leader applies, in order. A diff only applies on
+//! top of the state it was taken from, so a replica that misses one notices
+//! as soon as the next one arrives. It then drops the stream and
+//! resubscribes, resyncing from the fresh snapshot the new stream opens with.
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use events::{Event, EventSubscriber};
+use primitives::Round;
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+use vrrb_config::{NodeConfig, ReplicationConfig};
+use vrrb_core::account::Account;
+use vrrb_rpc::rpc::{
+    api::{RpcApiClient, RpcReplicationUpdate, RpcStateDiff},
+    client::create_client,
+};
+
+use crate::{NodeError, Result};
+
+/// Counters updated by the [Replica]. Shared with every
+/// [ReplicationMonitor] so they can be reported without touching it.
+#[derive(Debug, Default)]
+struct ReplicationMetrics {
+    applied_round: RwLock<Round>,
+    leader_round: RwLock<Round>,
+    diffs_applied: AtomicU64,
+    snapshots_applied: AtomicU64,
+    resyncs: AtomicU64,
+}
+
+/// Point in time view of the replication counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplicationStats {
+    /// Latest round the replica's state reflects
+    pub applied_round: Round,
+
+    /// Latest round the leader was seen applying
+    pub leader_round: Round,
+
+    /// How many rounds the replica's state trails the leader's by
+    pub lag_rounds: Round,
+    pub diffs_applied: u64,
+    pub snapshots_applied: u64,
+
+    /// Times the replica dropped its stream to resync from a fresh snapshot,
+    /// after a gap or after losing the connection to its leader
+    pub resyncs: u64,
+}
+
+/// Cloneable view over the replication counters.
+#[derive(Debug, Clone, Default)]
+pub struct ReplicationMonitor {
+    metrics: Arc<ReplicationMetrics>,
+}
+
+impl ReplicationMonitor {
+    pub fn stats(&self) -> ReplicationStats {
+        let applied_round = self
+            .metrics
+            .applied_round
+            .read()
+            .map(|round| *round)
+            .unwrap_or_default();
+
+        let leader_round = self
+            .metrics
+            .leader_round
+            .read()
+            .map(|round| *round)
+            .unwrap_or_default();
+
+        ReplicationStats {
+            applied_round,
+            leader_round,
+            lag_rounds: leader_round.saturating_sub(applied_round),
+            diffs_applied: self.metrics.diffs_applied.load(Ordering::Relaxed),
+            snapshots_applied: self.metrics.snapshots_applied.load(Ordering::Relaxed),
+            resyncs: self.metrics.resyncs.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// How applying a replication update went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplicationOutcome {
+    /// The page was held on to until the rest of its snapshot arrives
+    SnapshotPageReceived,
+    SnapshotApplied(Round),
+    DiffApplied(Round),
+
+    /// The diff was already reflected in the replica's state
+    DiffSkipped(Round),
+}
+
+/// A copy of a leader's state, kept up to date from its replication stream.
+#[derive(Debug)]
+pub struct Replica {
+    database: VrrbDb,
+
+    /// Round the replica's state reflects, unknown until the first snapshot
+    /// is applied
+    applied_round: Option<Round>,
+
+    /// Accounts of the snapshot being received, along with the round it was
+    /// taken at
+    pending_snapshot: Option<(Round, Vec<Account>)>,
+    monitor: ReplicationMonitor,
+}
+
+impl Replica {
+    pub fn new(database: VrrbDb) -> Self {
+        Self {
+            database,
+            applied_round: None,
+            pending_snapshot: None,
+            monitor: ReplicationMonitor::default(),
+        }
+    }
+
+    pub fn monitor(&self) -> ReplicationMonitor {
+        self.monitor.clone()
+    }
+
+    pub fn read_handle(&self) -> VrrbDbReadHandle {
+        self.database.read_handle()
+    }
+
+    /// Applies an update of the leader's replication stream. Fails when the
+    /// update doesn't follow on from the replica's state, in which case the
+    /// replica has to resync from a fresh snapshot.
+    pub fn apply(&mut self, update: RpcReplicationUpdate) -> Result<ReplicationOutcome> {
+        match update {
+            RpcReplicationUpdate::SnapshotPage {
+                round,
+                state_root,
+                accounts,
+                last,
+            } => {
+                self.observe_leader_round(round);
+                self.apply_snapshot_page(round, state_root, accounts, last)
+            },
+            RpcReplicationUpdate::Diff(state_diff) => {
+                self.observe_leader_round(state_diff.round);
+                self.apply_diff(state_diff)
+            },
+        }
+    }
+
+    /// Forgets any snapshot partially received over a stream that was
+    /// dropped
+    pub fn reset_stream(&mut self) {
+        self.pending_snapshot = None;
+    }
+
+    fn apply_snapshot_page(
+        &mut self,
+        round: Round,
+        state_root: String,
+        accounts: Vec<Account>,
+        last: bool,
+    ) -> Result<ReplicationOutcome> {
+        let pending_accounts = match self.pending_snapshot.take() {
+            Some((pending_round, mut pending_accounts)) if pending_round == round => {
+                pending_accounts.extend(accounts);
+                pending_accounts
+            },
+            _ => accounts,
+        };
+
+        if !last {
+            self.pending_snapshot = Some((round, pending_accounts));
+            return Ok(ReplicationOutcome::SnapshotPageReceived);
+        }
+
+        // NOTE: accounts are never removed from state, so overwriting every account the
+        // snapshot holds is enough to catch up with it
+        self.database.extend_accounts(
+            pending_accounts
+                .into_iter()
+                .map(|account| (account.address().clone(), Some(account)))
+                .collect(),
+        );
+
+        self.commit(round, &state_root)?;
+        self.monitor
+            .metrics
+            .snapshots_applied
+            .fetch_add(1, Ordering::Relaxed);
+
+        Ok(ReplicationOutcome::SnapshotApplied(round))
+    }
+
+    fn apply_diff(&mut self, state_diff: RpcStateDiff) -> Result<ReplicationOutcome> {
+        let round = state_diff.round;
+
+        let applied_round = self.applied_round.ok_or_else(|| {
+            NodeError::Other(format!(
+                "received the diff of round {round} before any snapshot"
+            ))
+        })?;
+
+        if round <= applied_round {
+            return Ok(ReplicationOutcome::DiffSkipped(round));
+        }
+
+        let state_root = self.state_root()?;
+
+        // NOTE: diffs applied on the leader while its snapshot was being taken are already
+        // reflected in it
+        if state_diff.state_root == state_root {
+            self.set_applied_round(round);
+            return Ok(ReplicationOutcome::DiffSkipped(round));
+        }
+
+        if state_diff.previous_state_root != state_root {
+            return Err(NodeError::Other(format!(
+                "the diff of round {round} applies on top of state root {}, but the replica is at {state_root} as of round {applied_round}",
+                state_diff.previous_state_root
+            )));
+        }
+
+        self.database.extend_accounts(
+            state_diff
+                .accounts
+                .into_iter()
+                .map(|account| (account.address().clone(), Some(account)))
+                .collect(),
+        );
+
+        self.commit(round, &state_diff.state_root)?;
+        self.monitor
+            .metrics
+            .diffs_applied
+            .fetch_add(1, Ordering::Relaxed);
+
+        Ok(ReplicationOutcome::DiffApplied(round))
+    }
+
+    /// Commits the state as of the end of `round` and checks it adds up to
+    /// the root the leader committed. An empty root, sent by leaders that
+    /// haven't committed a round yet, isn't checked
+    fn commit(&mut self, round: Round, expected_state_root: &str) -> Result<()> {
+        self.database.commit_state_at_round(round)?;
+
+        let state_root = self.state_root()?;
+
+        if !expected_state_root.is_empty() && state_root != expected_state_root {
+            self.applied_round = None;
+
+            return Err(NodeError::Other(format!(
+                "replicated state at round {round} has root {state_root}, but the leader's is {expected_state_root}"
+            )));
+        }
+
+        self.set_applied_round(round);
+
+        Ok(())
+    }
+
+    fn state_root(&self) -> Result<String> {
+        let root_hash = self.database.state_root_hash()?;
+
+        Ok(hex::encode(root_hash.0))
+    }
+
+    fn set_applied_round(&mut self, round: Round) {
+        self.applied_round = Some(round);
+
+        if let Ok(mut applied_round) = self.monitor.metrics.applied_round.write() {
+            *applied_round = round;
+        }
+    }
+
+    fn observe_leader_round(&self, round: Round) {
+        if let Ok(mut leader_round) = self.monitor.metrics.leader_round.write() {
+            *leader_round = (*leader_round).max(round);
+        }
+    }
+
+    /// Follows the leader's replication stream until it ends or an update
+    /// doesn't apply
+    async fn follow(&mut self, leader: SocketAddr, snapshot_interval_rounds: Round) -> Result<()> {
+        let client = create_client(leader)
+            .await
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let mut subscription = client
+            .subscribe_replication(Some(snapshot_interval_rounds))
+            .await
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        while let Some(update) = subscription.next().await {
+            let update = update.map_err(|err| NodeError::Other(err.to_string()))?;
+
+            self.apply(update)?;
+        }
+
+        Err(NodeError::Other("replication stream closed".to_string()))
+    }
+
+    /// Keeps following the leader until the node stops, resubscribing every
+    /// time the stream is lost or a gap is detected
+    async fn run(
+        mut self,
+        leader: SocketAddr,
+        replication_config: ReplicationConfig,
+        mut events_rx: EventSubscriber,
+    ) -> Result<()> {
+        loop {
+            let result = {
+                let follow = self.follow(leader, replication_config.snapshot_interval_rounds);
+                tokio::pin!(follow);
+
+                loop {
+                    tokio::select! {
+                        event = events_rx.recv() => match event {
+                            Ok(event) if matches!(event.into(), Event::Stop) => return Ok(()),
+                            Err(RecvError::Closed) => return Ok(()),
+                            _ => continue,
+                        },
+                        result = &mut follow => break result,
+                    }
+                }
+            };
+
+            if let Err(err) = result {
+                telemetry::warn!("Lost replication stream from {leader}: {err}, resyncing");
+            }
+
+            self.reset_stream();
+            self.monitor.metrics.resyncs.fetch_add(1, Ordering::Relaxed);
+
+            tokio::time::sleep(replication_config.retry_interval).await;
+        }
+    }
+}
+
+/// Opens the replica's own database and starts following the leader set in
+/// the node's replication config. Returns the replica's task along with a
+/// read handle to its state and the monitor of its replication counters.
+pub fn setup_replica(
+    config: &NodeConfig,
+    events_rx: EventSubscriber,
+) -> Result<(JoinHandle<Result<()>>, VrrbDbReadHandle, ReplicationMonitor)> {
+    config
+        .replication_config
+        .validate()
+        .map_err(|err| NodeError::Other(err.to_string()))?;
+
+    let leader = config
+        .replication_config
+        .leader
+        .ok_or_else(|| NodeError::Other("replicas need a leader to follow".to_string()))?;
+
+    let mut vrrbdb_config = VrrbDbConfig::default();
+
+    if config.db_path() != &vrrbdb_config.path {
+        vrrbdb_config.with_path(config.db_path().to_path_buf());
+    }
+
+    vrrbdb_config.with_pruning_mode(config.pruning_mode);
+    vrrbdb_config.with_backend(config.db_backend);
+
+    let replica = Replica::new(VrrbDb::new(vrrbdb_config));
+    let read_handle = replica.read_handle();
+    let monitor = replica.monitor();
+
+    let handle = tokio::spawn(replica.run(leader, config.replication_config.clone(), events_rx));
+
+    Ok((handle, read_handle, monitor))
+}
+
+#[cfg(test)]
+mod tests {
+    use vrrb_core::helpers::generate_random_string;
+
+    use super::*;
+    use crate::test_utils::produce_accounts;
+
+    fn database() -> VrrbDb {
+        let path = std::env::temp_dir().join(format!("replica-{}", generate_random_string()));
+
+        VrrbDb::new(VrrbDbConfig::default().with_path(path))
+    }
+
+    fn state_root(database: &VrrbDb) -> String {
+        hex::encode(database.state_root_hash().unwrap().0)
+    }
+
+    fn snapshot(leader: &VrrbDb, round: Round) -> Vec<RpcReplicationUpdate> {
+        let accounts = leader
+            .read_handle()
+            .state_store_values_at_round(round)
+            .unwrap();
+        let state_root = leader.read_handle().get_state_root(round).unwrap();
+        let state_root = hex::encode(state_root.root_hash.0);
+
+        let pages: Vec<&[Account]> = accounts.chunks(2).collect();
+        let count = pages.len();
+
+        pages
+            .into_iter()
+            .enumerate()
+            .map(|(index, page)| RpcReplicationUpdate::SnapshotPage {
+                round,
+                state_root: state_root.clone(),
+                accounts: page.to_vec(),
+                last: index + 1 == count,
+            })
+            .collect()
+    }
+
+    /// Credits an account on the leader and commits the change as `round`,
+    /// returning its diff
+    fn credit(leader: &mut VrrbDb, account: &Account, round: Round) -> RpcStateDiff {
+        let previous_state_root = state_root(leader);
+
+        let mut account = account.clone();
+        account.set_credits(round * 10);
+
+        leader.extend_accounts(vec![(account.address().clone(), Some(account.clone()))]);
+        leader.commit_state_at_round(round).unwrap();
+
+        RpcStateDiff {
+            block_hash: format!("block-{round}"),
+            round,
+            previous_state_root,
+            state_root: state_root(leader),
+            accounts: vec![account],
+        }
+    }
+
+    #[test]
+    fn replicas_follow_the_snapshot_and_diffs_of_their_leader() {
+        let accounts = produce_accounts(5);
+        let account = accounts[0].1.clone().unwrap();
+
+        let mut leader = database();
+        leader.extend_accounts(accounts);
+        leader.commit_state_at_round(1).unwrap();
+
+        let mut replica = Replica::new(database());
+
+        let outcomes: Vec<ReplicationOutcome> = snapshot(&leader, 1)
+            .into_iter()
+            .map(|update| replica.apply(update).unwrap())
+            .collect();
+
+        assert_eq!(
+            outcomes,
+            vec![
+                ReplicationOutcome::SnapshotPageReceived,
+                ReplicationOutcome::SnapshotPageReceived,
+                ReplicationOutcome::SnapshotApplied(1),
+            ]
+        );
+        assert_eq!(replica.state_root().unwrap(), state_root(&leader));
+
+        let diff = credit(&mut leader, &account, 2);
+
+        assert_eq!(
+            replica
+                .apply(RpcReplicationUpdate::Diff(diff.clone()))
+                .unwrap(),
+            ReplicationOutcome::DiffApplied(2)
+        );
+        assert_eq!(replica.state_root().unwrap(), state_root(&leader));
+
+        assert_eq!(
+            replica.apply(RpcReplicationUpdate::Diff(diff)).unwrap(),
+            ReplicationOutcome::DiffSkipped(2)
+        );
+
+        let stats = replica.monitor().stats();
+        assert_eq!(stats.applied_round, 2);
+        assert_eq!(stats.lag_rounds, 0);
+        assert_eq!(stats.snapshots_applied, 1);
+        assert_eq!(stats.diffs_applied, 1);
+    }
+
+    #[test]
+    fn replicas_missing_a_diff_need_to_resync() {
+        let accounts = produce_accounts(3);
+        let account = accounts[0].1.clone().unwrap();
+
+        let mut leader = database();
+        leader.extend_accounts(accounts);
+        leader.commit_state_at_round(1).unwrap();
+
+        let mut replica = Replica::new(database());
+
+        let _ = credit(&mut leader, &account, 2);
+        let missed = credit(&mut leader, &account, 3);
+
+        assert!(replica
+            .apply(RpcReplicationUpdate::Diff(missed.clone()))
+            .is_err());
+
+        for update in snapshot(&leader, 1) {
+            replica.apply(update).unwrap();
+        }
+
+        let diff = credit(&mut leader, &account, 4);
+
+        assert!(replica.apply(RpcReplicationUpdate::Diff(diff)).is_err());
+        assert_eq!(replica.monitor().stats().lag_rounds, 3);
+
+        // NOTE: a fresh snapshot brings the replica back in line with its leader
+        replica.reset_stream();
+        for update in snapshot(&leader, 4) {
+            replica.apply(update).unwrap();
+        }
+
+        assert_eq!(replica.state_root().unwrap(), state_root(&leader));
+        assert_eq!(replica.monitor().stats().lag_rounds, 0);
+    }
+}
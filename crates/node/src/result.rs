@@ -1,13 +1,21 @@
 use std::net::AddrParseError;
 
+#[cfg(feature = "dkg")]
 use dkg_engine::DkgError;
 use dyswarm::types::DyswarmError;
 use events::EventMessage;
+use mempool::MempoolError;
+#[cfg(feature = "mining")]
 use miner::result::MinerError;
+use primitives::{AmountError, ClassifiedError, ErrorCode, NodeId, QuorumKind};
 use theater::TheaterError;
 use thiserror::Error;
 use tokio::sync::mpsc::error::TryRecvError;
-use vrrb_core::claim::ClaimError;
+use validator::result::ValidatorError;
+use vrrb_config::QuorumSafetyViolation;
+use vrrb_core::{claim::ClaimError, transactions::TransactionDigest};
+
+use crate::{OptionalModule, PreflightReport};
 
 #[derive(Debug, Error)]
 pub enum NodeError {
@@ -47,24 +55,127 @@ pub enum NodeError {
     #[error("{0}")]
     Dyswarm(#[from] dyswarm::types::DyswarmError),
 
+    #[cfg(feature = "mining")]
     #[error("Error while creating instance of miner: {0}")]
     Miner(#[from] MinerError),
 
     #[error("Error while creating claim for node: {0}")]
     Claim(#[from] ClaimError),
 
+    #[cfg(feature = "dkg")]
     #[error("DKG error: {0}")]
     Dkg(#[from] DkgError),
 
+    #[error(
+        "DKG config mismatch for {quorum_kind} quorum of {participants} participants \
+         (threshold {threshold}, upper bound {upper_bound}): {reason}"
+    )]
+    DkgConfigMismatch {
+        quorum_kind: QuorumKind,
+        participants: usize,
+        threshold: u16,
+        upper_bound: u16,
+        reason: String,
+    },
+
+    #[error("unsafe quorum parameters: {0}")]
+    UnsafeQuorum(#[from] QuorumSafetyViolation),
+
     #[error("{0}")]
     Core(#[from] vrrb_core::Error),
 
+    #[error("{0}")]
+    Amount(#[from] AmountError),
+
+    #[error("{0}")]
+    Mempool(#[from] MempoolError),
+
+    #[error("{0}")]
+    Validator(#[from] ValidatorError),
+
+    #[error("{0} not found")]
+    NotFound(String),
+
+    #[error("invalid block {hash}: {reason}")]
+    InvalidBlock { hash: String, reason: String },
+
+    #[error("{violations} ledger invariants violated applying block {hash}")]
+    LedgerInvariantsViolated { hash: String, violations: usize },
+
+    #[error("transaction {0} was already certified")]
+    TxnAlreadyCertified(TransactionDigest),
+
+    #[error("{0} is unavailable, try again later")]
+    Unavailable(String),
+
+    #[error(
+        "cannot {action}: this node was built without the {module} module, enable the \
+         `{module}` feature to use it"
+    )]
+    ModuleUnavailable {
+        module: OptionalModule,
+        action: String,
+    },
+
+    #[error("registration of peer {node_id} rejected: {reason}")]
+    PeerRegistrationRejected { node_id: NodeId, reason: String },
+
+    #[error("registrations of peer {0} are throttled after repeated failures")]
+    PeerRegistrationThrottled(NodeId),
+
+    #[error("invalid signature share from peer {node_id}: {reason}")]
+    InvalidSignatureShare { node_id: NodeId, reason: String },
+
+    #[error("node failed its startup checks:\n{0}")]
+    PreflightFailed(PreflightReport),
+
     #[error("{0}")]
     Other(String),
 }
 
 pub type Result<T> = std::result::Result<T, NodeError>;
 
+impl ClassifiedError for NodeError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            NodeError::InvalidNodeType(_)
+            | NodeError::AddrParse(_)
+            | NodeError::PreflightFailed(_) => ErrorCode::InvalidInput,
+            NodeError::Storage(err) => err.error_code(),
+            NodeError::CoreStorage(_) => ErrorCode::Storage,
+            NodeError::Core(err) => err.error_code(),
+            NodeError::Mempool(err) => err.error_code(),
+            NodeError::Validator(err) => err.error_code(),
+            NodeError::NotFound(_) => ErrorCode::NotFound,
+            NodeError::InvalidBlock { .. } => ErrorCode::InvalidBlock,
+            NodeError::TxnAlreadyCertified(_) => ErrorCode::AlreadyExists,
+            NodeError::Unavailable(_) | NodeError::ModuleUnavailable { .. } => {
+                ErrorCode::Unavailable
+            },
+            NodeError::PeerRegistrationRejected { .. }
+            | NodeError::InvalidSignatureShare { .. } => ErrorCode::Consensus,
+            NodeError::PeerRegistrationThrottled(_) => ErrorCode::RateLimited,
+            #[cfg(feature = "dkg")]
+            NodeError::Dkg(_) => ErrorCode::Dkg,
+            NodeError::DkgConfigMismatch { .. } | NodeError::UnsafeQuorum(_) => ErrorCode::Dkg,
+            #[cfg(feature = "mining")]
+            NodeError::Miner(_) => ErrorCode::Consensus,
+            NodeError::Claim(_) => ErrorCode::Consensus,
+            NodeError::Io(_)
+            | NodeError::TryRecv(_)
+            | NodeError::BroadcastSend(_)
+            | NodeError::MpscSend(_)
+            | NodeError::TaskJoin(_)
+            | NodeError::JsonRpc(_)
+            | NodeError::Messr(_)
+            | NodeError::Dyswarm(_)
+            | NodeError::Amount(_)
+            | NodeError::LedgerInvariantsViolated { .. }
+            | NodeError::Other(_) => ErrorCode::Internal,
+        }
+    }
+}
+
 impl From<NodeError> for TheaterError {
     fn from(err: NodeError) -> Self {
         TheaterError::Other(err.to_string())
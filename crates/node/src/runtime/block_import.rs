@@ -0,0 +1,516 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use block::{Block, BlockHash};
+use events::SyncStatus;
+use primitives::Round;
+use vrrb_config::BlockImportConfig;
+
+use crate::{NodeError, Result};
+
+/// Stages every imported block goes through, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportStage {
+    Decode,
+    HeaderCheck,
+    CertificateCheck,
+    DagLinkCheck,
+    Apply,
+}
+
+impl ImportStage {
+    pub const ALL: [ImportStage; 5] = [
+        ImportStage::Decode,
+        ImportStage::HeaderCheck,
+        ImportStage::CertificateCheck,
+        ImportStage::DagLinkCheck,
+        ImportStage::Apply,
+    ];
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl fmt::Display for ImportStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stage = match self {
+            ImportStage::Decode => "decode",
+            ImportStage::HeaderCheck => "header_check",
+            ImportStage::CertificateCheck => "certificate_check",
+            ImportStage::DagLinkCheck => "dag_link_check",
+            ImportStage::Apply => "apply",
+        };
+
+        f.write_str(stage)
+    }
+}
+
+/// A block waiting to be imported, as it was received.
+#[derive(Debug, Clone)]
+pub enum IncomingBlock {
+    Encoded(Vec<u8>),
+    Decoded(Block),
+}
+
+impl IncomingBlock {
+    pub fn decode(self) -> Result<Block> {
+        match self {
+            IncomingBlock::Encoded(bytes) => bincode::deserialize(&bytes)
+                .map_err(|err| NodeError::Other(format!("failed to decode block: {err}"))),
+            IncomingBlock::Decoded(block) => Ok(block),
+        }
+    }
+}
+
+/// How an import attempt ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportOutcome {
+    Imported(BlockHash),
+
+    /// The block was held back until `parent` is imported
+    Buffered {
+        block_hash: BlockHash,
+        parent: BlockHash,
+    },
+}
+
+#[derive(Debug, Default)]
+struct StageMetrics {
+    passed: AtomicU64,
+    failed: AtomicU64,
+    total_latency_micros: AtomicU64,
+    max_latency_micros: AtomicU64,
+}
+
+/// Counters updated by the import queue. Shared with every
+/// [BlockImportMonitor] so they can be reported without touching the queue.
+#[derive(Debug, Default)]
+pub struct BlockImportMetrics {
+    stages: [StageMetrics; 5],
+    queued: AtomicUsize,
+    buffered: AtomicUsize,
+    imported: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// Point in time view of a single import stage's counters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportStageStats {
+    pub stage: ImportStage,
+    pub passed: u64,
+    pub failed: u64,
+    pub mean_latency: Duration,
+    pub max_latency: Duration,
+}
+
+/// Point in time view of the import queue's counters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockImportStats {
+    pub queued: usize,
+    pub buffered: usize,
+    pub imported: u64,
+    pub dropped: u64,
+    pub stages: Vec<ImportStageStats>,
+}
+
+/// Cloneable view over the import queue's counters.
+#[derive(Debug, Clone, Default)]
+pub struct BlockImportMonitor {
+    metrics: Arc<BlockImportMetrics>,
+}
+
+impl BlockImportMonitor {
+    /// Runs a stage of an import, recording how long it took and whether the
+    /// block made it through
+    pub fn time<T>(&self, stage: ImportStage, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        let started_at = Instant::now();
+        let result = f();
+        let elapsed = started_at.elapsed().as_micros() as u64;
+
+        let metrics = &self.metrics.stages[stage.index()];
+        metrics
+            .total_latency_micros
+            .fetch_add(elapsed, Ordering::Relaxed);
+        metrics
+            .max_latency_micros
+            .fetch_max(elapsed, Ordering::Relaxed);
+
+        if result.is_ok() {
+            metrics.passed.fetch_add(1, Ordering::Relaxed);
+        } else {
+            metrics.failed.fetch_add(1, Ordering::Relaxed);
+        }
+
+        result
+    }
+
+    pub fn stats(&self) -> BlockImportStats {
+        let stages = ImportStage::ALL
+            .iter()
+            .map(|stage| {
+                let metrics = &self.metrics.stages[stage.index()];
+                let passed = metrics.passed.load(Ordering::Relaxed);
+                let failed = metrics.failed.load(Ordering::Relaxed);
+                let total_latency = metrics.total_latency_micros.load(Ordering::Relaxed);
+                let runs = (passed + failed).max(1);
+
+                ImportStageStats {
+                    stage: *stage,
+                    passed,
+                    failed,
+                    mean_latency: Duration::from_micros(total_latency / runs),
+                    max_latency: Duration::from_micros(
+                        metrics.max_latency_micros.load(Ordering::Relaxed),
+                    ),
+                }
+            })
+            .collect();
+
+        BlockImportStats {
+            queued: self.metrics.queued.load(Ordering::Relaxed),
+            buffered: self.metrics.buffered.load(Ordering::Relaxed),
+            imported: self.metrics.imported.load(Ordering::Relaxed),
+            dropped: self.metrics.dropped.load(Ordering::Relaxed),
+            stages,
+        }
+    }
+}
+
+/// Queue blocks received from peers are imported through, one at a time.
+///
+/// Blocks whose parents haven't been imported yet are buffered and put back
+/// at the front of the queue once their parent is imported, so blocks can be
+/// received in any order.
+#[derive(Debug, Clone)]
+pub struct BlockImportQueue {
+    config: BlockImportConfig,
+    queue: VecDeque<IncomingBlock>,
+
+    /// Blocks waiting on a parent, keyed by the hash of that parent
+    buffered: HashMap<BlockHash, Vec<Block>>,
+
+    /// Parent and hash of every buffered block, longest held first
+    buffer_order: VecDeque<(BlockHash, BlockHash)>,
+    monitor: BlockImportMonitor,
+}
+
+impl BlockImportQueue {
+    pub fn new(config: BlockImportConfig) -> Self {
+        Self {
+            config,
+            queue: VecDeque::new(),
+            buffered: HashMap::new(),
+            buffer_order: VecDeque::new(),
+            monitor: BlockImportMonitor::default(),
+        }
+    }
+
+    pub fn monitor(&self) -> BlockImportMonitor {
+        self.monitor.clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn buffered_len(&self) -> usize {
+        self.buffer_order.len()
+    }
+
+    /// Queues a block for import. Returns false if the queue is full and the
+    /// block was dropped
+    pub fn enqueue(&mut self, block: IncomingBlock) -> bool {
+        if self.queue.len() >= self.config.max_queued_blocks {
+            self.monitor.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+
+        self.queue.push_back(block);
+        self.record_depths();
+
+        true
+    }
+
+    /// Takes the next block to import
+    pub fn pop(&mut self) -> Option<IncomingBlock> {
+        let block = self.queue.pop_front();
+        self.record_depths();
+
+        block
+    }
+
+    /// Holds a block back until `parent` is imported
+    pub fn buffer(
+        &mut self,
+        block: Block,
+        block_hash: BlockHash,
+        parent: BlockHash,
+    ) -> ImportOutcome {
+        while !self.buffer_order.is_empty()
+            && self.buffer_order.len() >= self.config.max_buffered_blocks
+        {
+            self.evict_longest_buffered();
+        }
+
+        self.buffered.entry(parent.clone()).or_default().push(block);
+        self.buffer_order
+            .push_back((parent.clone(), block_hash.clone()));
+        self.record_depths();
+
+        ImportOutcome::Buffered { block_hash, parent }
+    }
+
+    /// Records a block as imported and requeues the blocks that were waiting
+    /// on it, ahead of every other queued block
+    pub fn complete(&mut self, block_hash: &BlockHash) {
+        self.monitor
+            .metrics
+            .imported
+            .fetch_add(1, Ordering::Relaxed);
+
+        if let Some(children) = self.buffered.remove(block_hash) {
+            self.buffer_order.retain(|(parent, _)| parent != block_hash);
+
+            for child in children.into_iter().rev() {
+                self.queue.push_front(IncomingBlock::Decoded(child));
+            }
+        }
+
+        self.record_depths();
+    }
+
+    fn evict_longest_buffered(&mut self) {
+        let Some((parent, block_hash)) = self.buffer_order.pop_front() else {
+            return;
+        };
+
+        if let Some(children) = self.buffered.get_mut(&parent) {
+            // NOTE: children of a parent are buffered in the order they were
+            // received, so the longest held one comes first
+            children.remove(0);
+
+            if children.is_empty() {
+                self.buffered.remove(&parent);
+            }
+        }
+
+        telemetry::warn!("Dropping block {block_hash}, its parent {parent} never arrived");
+        self.monitor.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_depths(&self) {
+        let metrics = &self.monitor.metrics;
+
+        metrics.queued.store(self.queue.len(), Ordering::Relaxed);
+        metrics
+            .buffered
+            .store(self.buffer_order.len(), Ordering::Relaxed);
+    }
+}
+
+/// How long the import rate is measured over
+pub const SYNC_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// How many rounds a node may lag behind its peers before it is considered
+/// to be syncing
+pub const SYNCED_LAG_ROUNDS: Round = 1;
+
+/// Tracks how far the chain announced by peers is ahead of the node and how
+/// fast the node is catching up with it.
+#[derive(Debug, Clone, Default)]
+pub struct SyncProgress {
+    best_known_round: Round,
+
+    /// Total blocks imported as of each sample, oldest first
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl SyncProgress {
+    /// Records the round of a certified block received from a peer
+    pub fn record_round(&mut self, round: Round) {
+        self.best_known_round = self.best_known_round.max(round);
+    }
+
+    /// Records how many blocks were imported in total as of `now`
+    pub fn record_imported(&mut self, now: Instant, imported: u64) {
+        self.samples.push_back((now, imported));
+
+        while let Some((sampled_at, _)) = self.samples.front() {
+            if now.duration_since(*sampled_at) <= SYNC_RATE_WINDOW {
+                break;
+            }
+            self.samples.pop_front();
+        }
+    }
+
+    /// Blocks imported per second over the samples within the rate window
+    pub fn blocks_per_second(&self) -> f64 {
+        let (Some((first_at, first)), Some((last_at, last))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return 0.0;
+        };
+
+        let elapsed = last_at.duration_since(*first_at).as_secs_f64();
+        if elapsed == 0.0 {
+            return 0.0;
+        }
+
+        last.saturating_sub(*first) as f64 / elapsed
+    }
+
+    pub fn status(&self, applied_round: Round) -> SyncStatus {
+        let best_known_round = self.best_known_round.max(applied_round);
+        let rounds_behind = best_known_round - applied_round;
+        let blocks_per_second = self.blocks_per_second();
+
+        // NOTE: a single convergence block is certified per round
+        let eta_secs = if rounds_behind == 0 {
+            Some(0)
+        } else if blocks_per_second > 0.0 {
+            Some((rounds_behind as f64 / blocks_per_second).ceil() as u64)
+        } else {
+            None
+        };
+
+        SyncStatus {
+            applied_round,
+            best_known_round,
+            syncing: rounds_behind > SYNCED_LAG_ROUNDS,
+            import_rate_millis: (blocks_per_second * 1000.0) as u64,
+            eta_secs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::produce_genesis_block;
+
+    fn genesis() -> Block {
+        produce_genesis_block().into()
+    }
+
+    fn queue(max_queued_blocks: usize, max_buffered_blocks: usize) -> BlockImportQueue {
+        BlockImportQueue::new(BlockImportConfig {
+            max_queued_blocks,
+            max_buffered_blocks,
+        })
+    }
+
+    #[test]
+    fn blocks_beyond_the_queue_bound_are_dropped() {
+        let mut queue = queue(1, 1);
+
+        assert!(queue.enqueue(IncomingBlock::Decoded(genesis())));
+        assert!(!queue.enqueue(IncomingBlock::Decoded(genesis())));
+
+        let stats = queue.monitor().stats();
+        assert_eq!(stats.queued, 1);
+        assert_eq!(stats.dropped, 1);
+    }
+
+    #[test]
+    fn buffered_blocks_are_requeued_first_once_their_parent_is_imported() {
+        let mut queue = queue(8, 8);
+        queue.enqueue(IncomingBlock::Encoded(vec![]));
+
+        let outcome = queue.buffer(genesis(), "child".to_string(), "parent".to_string());
+        assert_eq!(
+            outcome,
+            ImportOutcome::Buffered {
+                block_hash: "child".to_string(),
+                parent: "parent".to_string()
+            }
+        );
+        assert_eq!(queue.buffered_len(), 1);
+
+        queue.complete(&"unrelated".to_string());
+        assert_eq!(queue.buffered_len(), 1);
+
+        queue.complete(&"parent".to_string());
+        assert_eq!(queue.buffered_len(), 0);
+        assert!(matches!(queue.pop(), Some(IncomingBlock::Decoded(_))));
+        assert!(matches!(queue.pop(), Some(IncomingBlock::Encoded(_))));
+        assert_eq!(queue.monitor().stats().imported, 2);
+    }
+
+    #[test]
+    fn the_longest_buffered_blocks_make_room_for_new_ones() {
+        let mut queue = queue(8, 2);
+
+        for child in ["first", "second", "third"] {
+            queue.buffer(genesis(), child.to_string(), "parent".to_string());
+        }
+
+        let stats = queue.monitor().stats();
+        assert_eq!(stats.buffered, 2);
+        assert_eq!(stats.dropped, 1);
+        assert!(queue
+            .buffer_order
+            .iter()
+            .all(|(_, block_hash)| block_hash != "first"));
+    }
+
+    #[test]
+    fn stage_latencies_and_failures_are_recorded() {
+        let queue = queue(8, 8);
+        let monitor = queue.monitor();
+
+        let decoded = monitor.time(ImportStage::Decode, || {
+            IncomingBlock::Encoded(bincode::serialize(&genesis()).unwrap()).decode()
+        });
+        assert!(decoded.is_ok());
+
+        let garbage = monitor.time(ImportStage::Decode, || {
+            IncomingBlock::Encoded(vec![1, 2, 3]).decode()
+        });
+        assert!(garbage.is_err());
+
+        let stats = monitor.stats();
+        let decode = &stats.stages[ImportStage::Decode.index()];
+        assert_eq!(decode.stage, ImportStage::Decode);
+        assert_eq!((decode.passed, decode.failed), (1, 1));
+        assert!(decode.max_latency >= decode.mean_latency);
+    }
+
+    #[test]
+    fn sync_eta_follows_the_import_rate() {
+        let mut progress = SyncProgress::default();
+        progress.record_round(110);
+
+        let status = progress.status(10);
+        assert!(status.syncing);
+        assert_eq!(status.eta_secs, None);
+
+        let started_at = Instant::now();
+        progress.record_imported(started_at, 0);
+        progress.record_imported(started_at + Duration::from_secs(10), 50);
+
+        let status = progress.status(60);
+        assert_eq!(status.best_known_round, 110);
+        assert_eq!(status.import_rate_millis, 5000);
+        assert_eq!(status.eta_secs, Some(10));
+
+        // samples older than the rate window stop counting
+        progress.record_imported(started_at + Duration::from_secs(80), 50);
+        assert_eq!(progress.blocks_per_second(), 0.0);
+
+        let status = progress.status(110);
+        assert!(!status.syncing);
+        assert_eq!(status.eta_secs, Some(0));
+    }
+}
@@ -1,10 +1,13 @@
-use events::{EventPublisher, EventSubscriber};
+use events::{Event, EventMessage, EventPublisher, EventSubscriber};
 use mempool::MempoolReadHandleFactory;
 use storage::vrrbdb::VrrbDbReadHandle;
 use theater::{Actor, ActorImpl};
 use vrrb_config::NodeConfig;
 
-use crate::{node_runtime::NodeRuntime, NodeError, RuntimeComponent, RuntimeComponentHandle};
+use crate::{
+    block_import::BlockImportMonitor, node_runtime::NodeRuntime, NodeError, RuntimeComponent,
+    RuntimeComponentHandle, RUNTIME_EVENTS_TOPIC,
+};
 
 #[derive(Debug)]
 pub struct NodeRuntimeComponentConfig {
@@ -18,6 +21,7 @@ pub struct NodeRuntimeComponentResolvedData {
     pub node_config: NodeConfig,
     pub state_read_handle: VrrbDbReadHandle,
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub block_import_monitor: BlockImportMonitor,
 }
 
 #[async_trait::async_trait]
@@ -28,10 +32,49 @@ impl RuntimeComponent<NodeRuntimeComponentConfig, NodeRuntimeComponentResolvedDa
         args: NodeRuntimeComponentConfig,
     ) -> crate::Result<RuntimeComponentHandle<NodeRuntimeComponentResolvedData>> {
         let mut events_rx = args.events_rx;
+        let certificate_sync_config = args.config.certificate_sync_config.clone();
+
+        certificate_sync_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let mempool_compaction_config = args.config.mempool_compaction_config.clone();
+
+        mempool_compaction_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let chain_scrubber_config = args.config.chain_scrubber_config.clone();
+
+        chain_scrubber_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        args.config
+            .round_gc_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        args.config
+            .block_import_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let reconciliation_events_tx = args.events_tx.clone();
+        let compaction_events_tx = args.events_tx.clone();
+        let scrub_events_tx = args.events_tx.clone();
+        let election_retry_events_tx = args.events_tx.clone();
+        let election_stall_timeout = std::time::Duration::from_secs(
+            args.config
+                .chain_spec
+                .election_difficulty
+                .stall_timeout_secs,
+        );
         let node_runtime = NodeRuntime::new(&args.config, args.events_tx).await?;
 
         let state_read_handle = node_runtime.state_read_handle();
         let mempool_read_handle_factory = node_runtime.mempool_read_handle_factory();
+        let block_import_monitor = node_runtime.block_import_monitor();
 
         let mut node_runtime_actor = ActorImpl::new(node_runtime);
 
@@ -42,12 +85,86 @@ impl RuntimeComponent<NodeRuntimeComponentConfig, NodeRuntimeComponentResolvedDa
                 .map_err(|err| NodeError::Other(err.to_string()))
         });
 
+        // NOTE: periodically looks for convergence blocks whose certificate broadcast
+        // was missed, so they're recovered from peers instead of staying uncertified
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(certificate_sync_config.interval);
+
+            loop {
+                interval.tick().await;
+
+                let em = EventMessage::new(
+                    Some(RUNTIME_EVENTS_TOPIC.into()),
+                    Event::CertificateReconciliationDue,
+                );
+
+                if reconciliation_events_tx.send(em).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        // NOTE: periodically gives back the memory the mempool grew into, so
+        // long-running nodes don't keep the footprint of their busiest bursts
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(mempool_compaction_config.interval);
+
+            loop {
+                interval.tick().await;
+
+                let em = EventMessage::new(
+                    Some(RUNTIME_EVENTS_TOPIC.into()),
+                    Event::MempoolCompactionDue,
+                );
+
+                if compaction_events_tx.send(em).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        // NOTE: periodically checks a batch of the persisted chain data, so
+        // corrupted records are caught before they are next read
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(chain_scrubber_config.interval);
+
+            loop {
+                interval.tick().await;
+
+                let em = EventMessage::new(Some(RUNTIME_EVENTS_TOPIC.into()), Event::ChainScrubDue);
+
+                if scrub_events_tx.send(em).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        // NOTE: periodically retries miner elections no claim met the threshold
+        // of, since their eligibility widens the longer the round stalls
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(election_stall_timeout);
+
+            loop {
+                interval.tick().await;
+
+                let em = EventMessage::new(
+                    Some(RUNTIME_EVENTS_TOPIC.into()),
+                    Event::MinerElectionRetryDue,
+                );
+
+                if election_retry_events_tx.send(em).await.is_err() {
+                    break;
+                }
+            }
+        });
+
         telemetry::info!("NodeRuntime module is operational");
 
         let node_runtime_resolved_data = NodeRuntimeComponentResolvedData {
             node_config: args.config,
             state_read_handle,
             mempool_read_handle_factory,
+            block_import_monitor,
         };
 
         let component_handle = RuntimeComponentHandle::new(
@@ -1,24 +1,33 @@
-use events::{Event, EventPublisher, EventRouter};
+use events::{Event, EventPublisher, EventRouter, EventSubscriber, Mailbox};
+use mempool::LeftRightMempool;
 use telemetry::info;
 use vrrb_config::NodeConfig;
+use vrrb_rpc::rpc::{PublicRpcGuard, RpcCache};
 
 use crate::{
-    api::setup_rpc_api_server,
+    api::{setup_rest_gateway, setup_rpc_api_server},
     component::NodeRuntimeComponentConfig,
     indexer_module::setup_indexer_module,
     network::{NetworkModule, NetworkModuleComponentConfig},
     node_runtime::NodeRuntime,
+    notification_module::setup_notification_module,
+    replication::setup_replica,
     result::Result,
     ui::setup_node_gui,
-    RuntimeComponent, RuntimeComponentManager,
+    NodeError, OptionalModule, RuntimeComponent, RuntimeComponentManager,
 };
 
+pub mod block_import;
 pub mod component;
 pub mod node_runtime;
 pub mod node_runtime_handler;
 
 pub const PULL_TXN_BATCH_SIZE: usize = 100;
 
+pub const RUNTIME_EVENTS_TOPIC: &str = "runtime-events";
+pub const NETWORK_EVENTS_TOPIC: &str = "network-events";
+pub const JSON_RPC_API_CONTROL_TOPIC: &str = "json-rpc-api-control";
+
 pub async fn setup_runtime_components(
     original_config: &NodeConfig,
     router: &EventRouter,
@@ -26,13 +35,49 @@ pub async fn setup_runtime_components(
 ) -> Result<(RuntimeComponentManager, NodeConfig)> {
     let mut config = original_config.clone();
 
-    let runtime_events_rx = router.subscribe(Some("runtime-events".into()))?;
-    let network_events_rx = router.subscribe(Some("network-events".into()))?;
-    let jsonrpc_events_rx = router.subscribe(Some("json-rpc-api-control".into()))?;
-    let indexer_events_rx = router.subscribe(None)?;
-
     let mut runtime_manager = RuntimeComponentManager::new();
 
+    // NOTE: every actor consumes from its own bounded mailbox so a slow module can only
+    // saturate its own queue instead of stalling the shared router topics
+    let mut subscribe = |topic: &str| -> Result<EventSubscriber> {
+        let upstream = router.subscribe(Some(topic.into()))?;
+        let (events_rx, mailbox_handle) =
+            Mailbox::new(topic, &original_config.mailbox_config).spawn(upstream);
+
+        runtime_manager.register_mailbox(mailbox_handle);
+
+        Ok(events_rx)
+    };
+
+    if config.replication_config.is_replica() {
+        let replica_events_rx = subscribe(RUNTIME_EVENTS_TOPIC)?;
+        let jsonrpc_events_rx = subscribe(JSON_RPC_API_CONTROL_TOPIC)?;
+
+        return setup_replica_components(
+            config,
+            runtime_manager,
+            events_tx,
+            replica_events_rx,
+            jsonrpc_events_rx,
+        )
+        .await;
+    }
+
+    info!(
+        "Node modules: {}",
+        OptionalModule::enabled()
+            .iter()
+            .map(|module| module.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let runtime_events_rx = subscribe(RUNTIME_EVENTS_TOPIC)?;
+    let network_events_rx = subscribe(NETWORK_EVENTS_TOPIC)?;
+    let jsonrpc_events_rx = subscribe(JSON_RPC_API_CONTROL_TOPIC)?;
+    let indexer_events_rx = router.subscribe(None)?;
+    let notification_events_rx = router.subscribe(None)?;
+
     let node_runtime_component_handle = NodeRuntime::setup(NodeRuntimeComponentConfig {
         config: config.clone(),
         events_tx: events_tx.clone(),
@@ -49,6 +94,8 @@ pub async fn setup_runtime_components(
     let mempool_read_handle_factory = handle_data.mempool_read_handle_factory;
     let state_read_handle = handle_data.state_read_handle;
 
+    runtime_manager.register_block_import_monitor(handle_data.block_import_monitor);
+
     runtime_manager.register_component(
         node_runtime_component_handle.label(),
         node_runtime_component_handle.handle(),
@@ -63,12 +110,16 @@ pub async fn setup_runtime_components(
         bootstrap_quorum_config: config.bootstrap_quorum_config.clone(),
         membership_config: config.quorum_config.clone(),
         validator_public_key: config.keypair.validator_public_key_owned(),
+        mempool_read_handle_factory: mempool_read_handle_factory.clone(),
     })
     .await?;
 
     let resolved_network_data = network_component_handle.data();
     let network_component_handle_label = network_component_handle.label();
 
+    runtime_manager.register_rebroadcast_monitor(resolved_network_data.rebroadcast_monitor.clone());
+    runtime_manager.register_preconnect_monitor(resolved_network_data.preconnect_monitor.clone());
+
     runtime_manager.register_component(
         network_component_handle_label,
         network_component_handle.handle(),
@@ -79,12 +130,31 @@ pub async fn setup_runtime_components(
     config.raptorq_gossip_address = resolved_network_data.resolved_raptorq_gossip_address;
     config.kademlia_liveness_address = resolved_network_data.resolved_kademlia_liveness_address;
 
+    config
+        .public_rpc_config
+        .validate()
+        .map_err(|err| NodeError::Other(err.to_string()))?;
+
+    let public_rpc_guard = config
+        .public_rpc_config
+        .enabled
+        .then(|| PublicRpcGuard::new(&config.public_rpc_config));
+
+    if let Some(public_rpc_guard) = public_rpc_guard.as_ref() {
+        runtime_manager.register_public_rpc_monitor(public_rpc_guard.monitor());
+    }
+
+    let rpc_cache = RpcCache::new();
+    runtime_manager.register_rpc_cache_monitor(rpc_cache.monitor());
+
     let (jsonrpc_server_handle, resolved_jsonrpc_server_addr) = setup_rpc_api_server(
         &config,
         events_tx.clone(),
         state_read_handle.clone(),
         mempool_read_handle_factory.clone(),
         jsonrpc_events_rx,
+        public_rpc_guard,
+        rpc_cache,
     )
     .await?;
 
@@ -94,6 +164,20 @@ pub async fn setup_runtime_components(
 
     runtime_manager.register_component("API".to_string(), jsonrpc_server_handle);
 
+    if config.enable_rest_gateway {
+        let (rest_gateway_handle, resolved_http_api_addr) = setup_rest_gateway(
+            &config,
+            state_read_handle.clone(),
+            mempool_read_handle_factory.clone(),
+            router.subscribe(None)?,
+        )
+        .await?;
+
+        config.http_api_address = resolved_http_api_addr;
+
+        runtime_manager.register_component("REST".to_string(), rest_gateway_handle);
+    }
+
     if config.enable_block_indexing {
         let handle = setup_indexer_module(&config, indexer_events_rx, mempool_read_handle_factory)?;
         // TODO: udpate this to return the proper component handle type
@@ -101,6 +185,13 @@ pub async fn setup_runtime_components(
         // TODO: register indexer module handle
     }
 
+    if setup_notification_module(&config, notification_events_rx)?.is_some() {
+        info!(
+            "Notifying {} webhooks of critical events",
+            config.notification_config.webhooks.len()
+        );
+    }
+
     let mut node_gui_handle = None;
     if config.gui {
         node_gui_handle = setup_node_gui(&config).await?;
@@ -110,17 +201,76 @@ pub async fn setup_runtime_components(
     Ok((runtime_manager, config))
 }
 
+/// Sets up a read replica. Replicas follow the state of their leader instead
+/// of taking part in consensus, so they only serve JSON-RPC requests off
+/// their own copy of the state.
+async fn setup_replica_components(
+    mut config: NodeConfig,
+    mut runtime_manager: RuntimeComponentManager,
+    events_tx: EventPublisher,
+    replica_events_rx: EventSubscriber,
+    jsonrpc_events_rx: EventSubscriber,
+) -> Result<(RuntimeComponentManager, NodeConfig)> {
+    let (replica_handle, state_read_handle, replication_monitor) =
+        setup_replica(&config, replica_events_rx)?;
+
+    runtime_manager.register_replication_monitor(replication_monitor);
+    runtime_manager.register_component("Replica".to_string(), replica_handle);
+
+    let rpc_cache = RpcCache::new();
+    runtime_manager.register_rpc_cache_monitor(rpc_cache.monitor());
+
+    // NOTE: replicas don't accept txns, so their mempool stays empty
+    let (jsonrpc_server_handle, resolved_jsonrpc_server_addr) = setup_rpc_api_server(
+        &config,
+        events_tx,
+        state_read_handle,
+        LeftRightMempool::default().factory(),
+        jsonrpc_events_rx,
+        None,
+        rpc_cache,
+    )
+    .await?;
+
+    config.jsonrpc_server_address = resolved_jsonrpc_server_addr;
+
+    info!(
+        "Replica JSON-RPC server address: {}",
+        config.jsonrpc_server_address
+    );
+
+    runtime_manager.register_component("API".to_string(), jsonrpc_server_handle);
+
+    Ok((runtime_manager, config))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use block::{Block, ConvergenceBlock};
-    use events::{AssignedQuorumMembership, Event, PeerData, DEFAULT_BUFFER};
+    use block::{Block, Certificate, ConvergenceBlock};
+    use events::{
+        AssignedQuorumMembership, ChainRecord, ChainRecordKey, Event, PeerData,
+        QuorumKeysetAnnouncement, RoundAbandonReason, ValidatorKeyRotation, DEFAULT_BUFFER,
+        LEGACY_EVENT_SCHEMA_VERSION,
+    };
     use hbbft::sync_key_gen::{AckOutcome, Part};
-    use primitives::{NodeId, NodeType, QuorumKind};
-    use validator::txn_validator;
-
-    use crate::{node_runtime::NodeRuntime, test_utils::create_node_runtime_network};
+    use primitives::{
+        NodeId, NodeType, QuorumAssignmentStrategy, QuorumKind, TxnValidationStatus,
+        ValidatorSecretKey, GENESIS_EPOCH,
+    };
+    use storage::vrrbdb::{CertifiedTxnKey, ScrubbedStore};
+    use validator::{result::ValidatorError, txn_validator};
+    use vrrb_core::transactions::{Transaction, TransactionKind};
+
+    use crate::{
+        node_runtime::NodeRuntime,
+        test_utils::{
+            create_node_runtime_network, create_register_validator_txn, create_txn_from_accounts,
+            produce_accounts, ActorHarness, ScriptStep,
+        },
+        NodeError,
+    };
 
     #[tokio::test]
     async fn bootstrap_node_runtime_cannot_be_assigned_to_quorum() {
@@ -135,6 +285,7 @@ mod tests {
             node_id: node.id.clone(),
             kademlia_peer_id: node.config.kademlia_peer_id.unwrap(),
             peers: vec![],
+            keyset: QuorumKeysetAnnouncement::default(),
         };
 
         let assignment_result =
@@ -144,6 +295,7 @@ mod tests {
         assert!(node.quorum_membership().is_none());
     }
 
+    #[cfg(feature = "consensus")]
     #[tokio::test]
     async fn validator_node_runtime_can_be_assigned_to_quorum() {
         let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
@@ -153,11 +305,23 @@ mod tests {
         let mut node = nodes.pop_front().unwrap();
         assert_eq!(node.config.node_type, NodeType::Validator);
 
+        // NOTE: a single participant quorum can only run DKG with a threshold of 0
+        node.consensus_driver.node_config.threshold_config.threshold = 0;
+
         let assigned_membership = AssignedQuorumMembership {
             quorum_kind: QuorumKind::Farmer,
             node_id: node.id.clone(),
             kademlia_peer_id: node.config.kademlia_peer_id.unwrap(),
             peers: vec![],
+            keyset: QuorumKeysetAnnouncement {
+                quorum_kind: QuorumKind::Farmer,
+                members: [(
+                    node.config.id.clone(),
+                    node.config.keypair.validator_public_key_owned(),
+                )]
+                .into(),
+                ..Default::default()
+            },
         };
 
         let assignment_result =
@@ -168,40 +332,651 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn validator_node_runtime_can_create_and_ack_partial_commitment() {
+    async fn validator_node_runtime_rejects_quorums_outside_its_threshold_config() {
         let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
 
         let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
         nodes.pop_front().unwrap();
         let mut node = nodes.pop_front().unwrap();
-        assert_eq!(node.config.node_type, NodeType::Validator);
 
         let assigned_membership = AssignedQuorumMembership {
             quorum_kind: QuorumKind::Farmer,
             node_id: node.id.clone(),
             kademlia_peer_id: node.config.kademlia_peer_id.unwrap(),
             peers: vec![],
+            keyset: QuorumKeysetAnnouncement {
+                quorum_kind: QuorumKind::Farmer,
+                members: [(
+                    node.config.id.clone(),
+                    node.config.keypair.validator_public_key_owned(),
+                )]
+                .into(),
+                ..Default::default()
+            },
         };
 
+        // NOTE: the default threshold of 2 needs at least 5 participants
         let assignment_result =
             node.handle_quorum_membership_assigment_created(assigned_membership);
 
-        assert!(assignment_result.is_ok());
-        assert!(node.quorum_membership().is_some());
+        assert!(matches!(
+            assignment_result,
+            Err(NodeError::DkgConfigMismatch {
+                quorum_kind: QuorumKind::Farmer,
+                participants: 1,
+                threshold: 2,
+                upper_bound: 4,
+                ..
+            })
+        ));
+        assert!(node.quorum_membership().is_none());
+    }
+
+    #[cfg(feature = "consensus")]
+    #[tokio::test]
+    async fn validator_node_runtime_rejects_keysets_not_matching_its_quorum() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(3, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let mut node = nodes.pop_front().unwrap();
+        let peer = nodes.pop_front().unwrap();
+
+        node.consensus_driver.node_config.threshold_config.threshold = 0;
+
+        let node_id = node.config.id.clone();
+        let node_key = node.config.keypair.validator_public_key_owned();
+        let peer_key = peer.config.keypair.validator_public_key_owned();
+
+        // NOTE: the announced keyset is missing the node's only peer
+        let assigned_membership = AssignedQuorumMembership {
+            quorum_kind: QuorumKind::Farmer,
+            node_id: node.id.clone(),
+            kademlia_peer_id: node.config.kademlia_peer_id.unwrap(),
+            peers: vec![PeerData {
+                node_id: peer.config.id.clone(),
+                node_type: peer.config.node_type,
+                kademlia_peer_id: peer.config.kademlia_peer_id.unwrap(),
+                udp_gossip_addr: peer.config.udp_gossip_address,
+                raptorq_gossip_addr: peer.config.raptorq_gossip_address,
+                kademlia_liveness_addr: peer.config.kademlia_liveness_address,
+                alternate_udp_gossip_addrs: vec![],
+                validator_public_key: peer_key,
+                locality: peer.config.locality.clone(),
+                claim_signature: None,
+                event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
+            }],
+            keyset: QuorumKeysetAnnouncement {
+                quorum_kind: QuorumKind::Farmer,
+                members: [(node_id.clone(), node_key)].into(),
+                ..Default::default()
+            },
+        };
+
+        assert!(node
+            .handle_quorum_membership_assigment_created(assigned_membership.clone())
+            .is_err());
+        assert!(node.quorum_membership().is_none());
+
+        let mut assigned_membership = assigned_membership;
+        assigned_membership
+            .keyset
+            .members
+            .insert(peer.config.id.clone(), peer_key);
+
+        node.handle_quorum_membership_assigment_created(assigned_membership.clone())
+            .unwrap();
+
+        assert_eq!(
+            node.consensus_driver.dkg_engine.dkg_state.peer_public_keys(),
+            &assigned_membership.keyset.members
+        );
+    }
+
+    #[cfg(feature = "consensus")]
+    #[tokio::test]
+    async fn validator_node_runtime_can_create_and_ack_partial_commitment() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let mut node = nodes.pop_front().unwrap();
+        assert_eq!(node.config.node_type, NodeType::Validator);
+
+        node.consensus_driver.node_config.threshold_config.threshold = 0;
+
+        let node_id = node.config.id.clone();
+
+        let assigned_membership = AssignedQuorumMembership {
+            quorum_kind: QuorumKind::Farmer,
+            node_id: node.id.clone(),
+            kademlia_peer_id: node.config.kademlia_peer_id.unwrap(),
+            peers: vec![],
+            keyset: QuorumKeysetAnnouncement {
+                quorum_kind: QuorumKind::Farmer,
+                members: [(
+                    node.config.id.clone(),
+                    node.config.keypair.validator_public_key_owned(),
+                )]
+                .into(),
+                ..Default::default()
+            },
+        };
+
+        let mut node = ActorHarness::for_node_runtime(node);
+
+        node.run_script(vec![
+            ScriptStep::Send(Event::QuorumMembershipAssigmentCreated(assigned_membership)),
+            ScriptStep::ExpectState(
+                "belongs to a quorum",
+                Box::new(|node: &NodeRuntime| node.quorum_membership().is_some()),
+            ),
+        ])
+        .await;
+
+        let Event::QuorumMembershipConfigured(membership_config) = node.next_emitted().await else {
+            panic!("expected the node to share its quorum membership with the network");
+        };
+        assert_eq!(membership_config.quorum_kind, QuorumKind::Farmer);
+
+        let Event::PartCommitmentCreated(part_owner_id, part) = node.next_emitted().await else {
+            panic!("expected the node to multicast its part commitment");
+        };
+        assert_eq!(part_owner_id, node_id);
+
+        node.send(Event::PartCommitmentCreated(part_owner_id, part))
+            .await
+            .unwrap();
+
+        let ack = node.next_emitted().await;
+        let Event::PartCommitmentAcknowledged {
+            node_id: acked_node_id,
+            sender_id,
+            ..
+        } = &ack
+        else {
+            panic!("expected the node to acknowledge its own part commitment");
+        };
+        assert_eq!(acked_node_id, &node_id);
+        assert_eq!(sender_id, &node_id);
+
+        node.run_script(vec![ScriptStep::Send(ack), ScriptStep::ExpectNoneEmitted])
+            .await;
+    }
+
+    #[cfg(feature = "consensus")]
+    #[tokio::test]
+    async fn acks_are_stored_under_the_acknowledging_node_then_the_part_owner() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let mut node = nodes.pop_front().unwrap();
+
+        node.consensus_driver.node_config.threshold_config.threshold = 0;
+
+        let node_id = node.config.id.clone();
+        let part_owner_id = NodeId::from("part-owner");
+
+        let assigned_membership = AssignedQuorumMembership {
+            quorum_kind: QuorumKind::Farmer,
+            node_id: node.id.clone(),
+            kademlia_peer_id: node.config.kademlia_peer_id.unwrap(),
+            peers: vec![],
+            keyset: QuorumKeysetAnnouncement {
+                quorum_kind: QuorumKind::Farmer,
+                members: [(
+                    node.config.id.clone(),
+                    node.config.keypair.validator_public_key_owned(),
+                )]
+                .into(),
+                ..Default::default()
+            },
+        };
+
+        node.handle_quorum_membership_assigment_created(assigned_membership)
+            .unwrap();
+
+        let (part, _) = node.generate_partial_commitment_message().unwrap();
+        let (_, _, ack) = node
+            .handle_part_commitment_created(node_id.clone(), part)
+            .unwrap();
 
-        let (part, node_id) = node.generate_partial_commitment_message().unwrap();
-        assert_eq!(node_id, node.config.id);
+        node.consensus_driver
+            .dkg_engine
+            .dkg_state
+            .ack_message_store_mut()
+            .clear();
+
+        let mut node = ActorHarness::for_node_runtime(node);
+
+        // NOTE: the acknowledging node and the part's owner differ, so swapping
+        // them when the ack is recorded can't go unnoticed
+        node.run_script(vec![
+            ScriptStep::Send(Event::PartCommitmentAcknowledged {
+                node_id: part_owner_id.clone(),
+                sender_id: node_id.clone(),
+                ack,
+            }),
+            ScriptStep::ExpectState(
+                "stored the ack under the acknowledging node, then the part's owner",
+                Box::new(move |node: &NodeRuntime| {
+                    let acks = node
+                        .consensus_driver
+                        .dkg_engine
+                        .dkg_state
+                        .ack_message_store();
+
+                    acks.len() == 1 && acks.contains_key(&(node_id.clone(), part_owner_id.clone()))
+                }),
+            ),
+        ])
+        .await;
+    }
+
+    #[cfg(feature = "consensus")]
+    #[tokio::test]
+    async fn validator_node_runtime_can_pause_and_resume_participation() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let node = nodes.pop_front().unwrap();
+        assert_eq!(node.config.node_type, NodeType::Validator);
+
+        let node_id = node.config.id.clone();
+        let peer_id = NodeId::from("peer-node");
+
+        let mut node = ActorHarness::for_node_runtime(node);
+
+        node.run_script(vec![
+            ScriptStep::Send(Event::MaintenanceModeRequested(true)),
+            ScriptStep::ExpectEmitted(vec![Event::MaintenanceModeChanged {
+                node_id: node_id.clone(),
+                paused: true,
+            }]),
+            ScriptStep::ExpectState(
+                "is in maintenance mode",
+                Box::new(|node: &NodeRuntime| node.is_in_maintenance_mode()),
+            ),
+            ScriptStep::Send(Event::MaintenanceModeRequested(true)),
+            ScriptStep::ExpectNoneEmitted,
+            ScriptStep::Send(Event::PeerMaintenanceModeChanged {
+                node_id: peer_id.clone(),
+                paused: true,
+            }),
+            ScriptStep::ExpectState(
+                "knows its peer is paused",
+                Box::new(move |node: &NodeRuntime| node.consensus_driver.is_peer_paused(&peer_id)),
+            ),
+            ScriptStep::Send(Event::MaintenanceModeRequested(false)),
+            ScriptStep::ExpectEmitted(vec![Event::MaintenanceModeChanged {
+                node_id,
+                paused: false,
+            }]),
+            ScriptStep::ExpectState(
+                "has resumed participation",
+                Box::new(|node: &NodeRuntime| !node.is_in_maintenance_mode()),
+            ),
+        ])
+        .await;
+    }
+
+    #[tokio::test]
+    async fn node_runtime_takes_backlog_snapshots_on_request() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let node = nodes.pop_front().unwrap();
+
+        let node_id = node.config.id.clone();
+        let peer_id = NodeId::from("peer-node");
+
+        let mut node = ActorHarness::for_node_runtime(node);
+
+        node.send(Event::PeerMaintenanceModeChanged {
+            node_id: peer_id.clone(),
+            paused: true,
+        })
+        .await
+        .unwrap();
+        node.send(Event::BacklogSnapshotRequested).await.unwrap();
+
+        let Event::BacklogSnapshotCreated(snapshot) = node.next_emitted().await else {
+            panic!("expected the node to hand its backlog snapshot to the JSON-RPC server");
+        };
+
+        assert_eq!(snapshot.node_id, node_id);
+        assert_eq!(snapshot.mempool_size, 0);
+        assert!(snapshot.pending_votes.is_empty());
+        assert!(snapshot.convergence_block_certificates.is_empty());
+        assert!(!snapshot.scheduler.maintenance_mode);
+        assert_eq!(snapshot.scheduler.paused_peers, vec![peer_id]);
+    }
+
+    #[tokio::test]
+    async fn node_runtime_answers_pending_block_previews_by_request() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let node = nodes.pop_front().unwrap();
+
+        let expected = node.pending_block_preview();
+        let max_block_weight = node.config.chain_spec.block_limits.max_block_weight;
+
+        let mut node = ActorHarness::for_node_runtime(node);
 
-        let (receiver_id, sender_id, ack) =
-            node.handle_part_commitment_created(node_id, part).unwrap();
+        // NOTE: concurrent callers tell their previews apart by request id
+        for request_id in [7, 3] {
+            node.send(Event::PendingBlockPreviewRequested { request_id })
+                .await
+                .unwrap();
+        }
+
+        for request_id in [7, 3] {
+            let Event::PendingBlockPreviewCreated {
+                request_id: answered,
+                preview,
+            } = node.next_emitted().await
+            else {
+                panic!("expected the node to hand its pending block preview to the server");
+            };
+
+            assert_eq!(answered, request_id);
+            assert_eq!(preview, expected);
+            assert!(preview.txns.is_empty());
+            assert_eq!(preview.weight, 0);
+            assert_eq!(preview.max_block_weight, max_block_weight);
+        }
+    }
 
-        assert_eq!(node.config.id, receiver_id);
-        assert_eq!(node.config.id, sender_id);
+    #[cfg(feature = "consensus")]
+    #[tokio::test]
+    async fn node_runtime_abandons_rounds_that_never_certify() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let mut node = nodes.pop_front().unwrap();
+
+        let max_round_age = node.config.round_gc_config.max_round_age;
+        let consensus = &mut node.consensus_driver;
+
+        consensus.track_convergence_block(1, "stale".into());
+        consensus.track_convergence_block(2, "certified".into());
+        consensus.track_convergence_block(2, "competing".into());
+        consensus.handle_convergence_block_finalized(&"certified".into());
+
+        let abandoned = consensus.drain_abandoned_rounds();
+        assert_eq!(abandoned.len(), 1);
+        assert_eq!(abandoned[0].round, 1);
+        assert_eq!(
+            abandoned[0].reason,
+            RoundAbandonReason::Superseded { certified_round: 2 }
+        );
+        assert_eq!(abandoned[0].blocks[0].block_hash, "stale");
+        assert!(consensus.pending_convergence_blocks.is_empty());
+
+        let latest_round = 3 + max_round_age + 1;
+        consensus.track_convergence_block(3, "expired".into());
+        consensus.track_convergence_block(latest_round, "latest".into());
+
+        let abandoned = consensus.drain_abandoned_rounds();
+        assert_eq!(abandoned.len(), 1);
+        assert_eq!(abandoned[0].round, 3);
+        assert_eq!(
+            abandoned[0].reason,
+            RoundAbandonReason::Expired { latest_round }
+        );
+        assert!(consensus
+            .pending_convergence_blocks
+            .contains_key(&latest_round));
+        assert!(consensus.drain_abandoned_rounds().is_empty());
+    }
 
-        node.handle_part_commitment_acknowledged(receiver_id, sender_id, ack)
+    #[cfg(feature = "consensus")]
+    #[tokio::test]
+    async fn node_runtime_refreshes_peer_keys_on_signed_rotations() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let mut node = nodes.pop_front().unwrap();
+
+        let peer_id = NodeId::from("peer-node");
+        let old_secret_key = ValidatorSecretKey::random();
+        let new_secret_key = ValidatorSecretKey::random();
+
+        node.add_peer_public_key_to_dkg_state(peer_id.clone(), old_secret_key.public_key());
+
+        let forged = ValidatorKeyRotation::new(
+            peer_id.clone(),
+            GENESIS_EPOCH,
+            1,
+            &ValidatorSecretKey::random(),
+            &new_secret_key,
+        );
+        assert!(node.handle_peer_validator_key_rotated(forged).is_err());
+
+        let rotation = ValidatorKeyRotation::new(
+            peer_id.clone(),
+            GENESIS_EPOCH,
+            1,
+            &old_secret_key,
+            &new_secret_key,
+        );
+        assert!(node
+            .handle_peer_validator_key_rotated(rotation.clone())
+            .unwrap());
+        assert_eq!(
+            node.consensus_driver
+                .dkg_engine
+                .dkg_state
+                .peer_public_keys()
+                .get(&peer_id),
+            Some(&new_secret_key.public_key())
+        );
+
+        // replays of an applied rotation are ignored
+        assert!(!node
+            .handle_peer_validator_key_rotated(rotation.clone())
+            .unwrap());
+
+        // NOTE: once the peer rotated back, replaying its first rotation
+        // mustn't move it off its key again
+        let rotated_back = ValidatorKeyRotation::new(
+            peer_id.clone(),
+            GENESIS_EPOCH,
+            2,
+            &new_secret_key,
+            &old_secret_key,
+        );
+        assert!(node
+            .handle_peer_validator_key_rotated(rotated_back)
+            .unwrap());
+        assert!(node.handle_peer_validator_key_rotated(rotation).is_err());
+
+        // rotations announced before the previous epoch are stale
+        node.consensus_driver.quorum_driver.current_epoch = GENESIS_EPOCH + 2;
+        let outdated = ValidatorKeyRotation::new(
+            peer_id.clone(),
+            GENESIS_EPOCH,
+            3,
+            &old_secret_key,
+            &new_secret_key,
+        );
+        assert!(node.handle_peer_validator_key_rotated(outdated).is_err());
+        assert_eq!(
+            node.consensus_driver
+                .dkg_engine
+                .dkg_state
+                .peer_public_keys()
+                .get(&peer_id),
+            Some(&old_secret_key.public_key())
+        );
+    }
+
+    #[tokio::test]
+    async fn node_runtime_rejects_invalid_locally_submitted_txns() {
+        let (events_tx, _events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(1, events_tx.clone()).await;
+        let mut node = nodes.pop_front().unwrap();
+
+        let mut accounts = produce_accounts(2);
+        let (receiver, _) = accounts.pop().unwrap();
+        let (sender, account) = accounts.pop().unwrap();
+
+        // the sender is unknown to the node
+        let txn =
+            create_txn_from_accounts((sender.clone(), account.clone()), receiver.clone(), vec![]);
+        let err = node.submit_transaction(txn.clone()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            NodeError::Validator(ValidatorError::Transaction(
+                txn_validator::TxnValidatorError::AccountNotFound(_)
+            ))
+        ));
+
+        // the sender is known but the txn is stale
+        node.state_driver
+            .insert_account(sender.clone(), account.clone().unwrap())
             .unwrap();
+        assert!(node.submit_transaction(txn.clone()).await.is_err());
+
+        assert!(node.mempool_read_handle_factory().get(&txn.id()).is_none());
     }
 
+    #[tokio::test]
+    async fn node_runtime_discards_unverifiable_certificates_from_peers() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let node = nodes.pop_front().unwrap();
+
+        let block_hash = hex::encode([7u8; 32]);
+        let certificate = Certificate {
+            signature: hex::encode([1u8; 96]),
+            inauguration: None,
+            root_hash: String::new(),
+            next_root_hash: String::new(),
+            block_hash: block_hash.clone(),
+        };
+
+        let mut node = ActorHarness::for_node_runtime(node);
+
+        node.run_script(vec![
+            ScriptStep::Send(Event::CertificateReconciliationDue),
+            ScriptStep::ExpectNoneEmitted,
+            ScriptStep::Send(Event::CertificatesReceived(vec![certificate])),
+            ScriptStep::ExpectState(
+                "did not apply the certificate",
+                Box::new(move |node: &NodeRuntime| {
+                    !node.state_driver.is_block_certified(&block_hash)
+                }),
+            ),
+            ScriptStep::Send(Event::CertificatesRequested {
+                sender_id: NodeId::from("peer-node"),
+                block_hashes: vec![hex::encode([7u8; 32])],
+            }),
+            ScriptStep::ExpectNoneEmitted,
+        ])
+        .await;
+    }
+
+    #[tokio::test]
+    async fn node_runtime_discards_unrequested_chain_records_from_peers() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let node = nodes.pop_front().unwrap();
+
+        let record = ChainRecord {
+            store: ScrubbedStore::StateTrie.to_string(),
+            key: vec![1, 2, 3],
+            value: vec![4, 5, 6],
+        };
+
+        let mut node = ActorHarness::for_node_runtime(node);
+
+        node.run_script(vec![
+            ScriptStep::Send(Event::ChainScrubDue),
+            ScriptStep::ExpectNoneEmitted,
+            ScriptStep::Send(Event::ChainRecordsReceived(vec![record])),
+            ScriptStep::ExpectState(
+                "did not store the record",
+                Box::new(|node: &NodeRuntime| {
+                    node.state_driver
+                        .get_chain_record(ScrubbedStore::StateTrie, &[1, 2, 3])
+                        .unwrap()
+                        .is_none()
+                }),
+            ),
+            // NOTE: receipts are the node's own observations, they aren't served
+            ScriptStep::Send(Event::ChainRecordsRequested {
+                sender_id: NodeId::from("peer-node"),
+                keys: vec![ChainRecordKey {
+                    store: ScrubbedStore::TxnReceipts.to_string(),
+                    key: vec![1, 2, 3],
+                }],
+            }),
+            ScriptStep::ExpectNoneEmitted,
+        ])
+        .await;
+    }
+
+    #[tokio::test]
+    async fn node_runtime_discards_duplicate_txn_certificates_across_quorums() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+
+        let mut nodes = create_node_runtime_network(2, events_tx.clone()).await;
+        nodes.pop_front().unwrap();
+        let node = nodes.pop_front().unwrap();
+
+        let txn = TransactionKind::RegisterValidator(create_register_validator_txn(
+            NodeId::from("node-3"),
+            0,
+        ));
+        let digest = txn.id();
+
+        let certificate_created = |quorum_key: Vec<u8>| Event::TransactionCertificateCreated {
+            votes: vec![],
+            signature: vec![1; 96],
+            digest: digest.clone(),
+            quorum_key,
+            execution_result: Default::default(),
+            farmer_id: NodeId::from("node-4"),
+            txn: Box::new(txn.clone()),
+            is_valid: TxnValidationStatus::Invalid,
+        };
+
+        let certified = CertifiedTxnKey::new(digest.clone(), vec![1; 48]);
+        let rotated = CertifiedTxnKey::new(digest.clone(), vec![2; 48]);
+
+        let mut node = ActorHarness::for_node_runtime(node);
+
+        node.run_script(vec![
+            ScriptStep::Send(certificate_created(certified.quorum_public_key.clone())),
+            ScriptStep::ExpectNoneEmitted,
+            ScriptStep::Send(certificate_created(rotated.quorum_public_key.clone())),
+            ScriptStep::ExpectNoneEmitted,
+            ScriptStep::ExpectState(
+                "indexed the first certificate only",
+                Box::new(move |node: &NodeRuntime| {
+                    let index = node.state_driver.read_handle().certified_txn_index();
+
+                    index.contains(&certified) && !index.contains(&rotated)
+                }),
+            ),
+        ])
+        .await;
+    }
+
+    #[cfg(feature = "consensus")]
     #[tokio::test]
     async fn validator_node_runtimes_can_generate_a_shared_key() {
         let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
@@ -217,6 +992,12 @@ mod tests {
         let mut node_2 = nodes.pop_front().unwrap();
         assert_eq!(node_2.config.node_type, NodeType::Validator);
 
+        // NOTE: two participants can't tolerate a faulty member, so DKG runs with a
+        // threshold of 0
+        for node in [&mut node_1, &mut node_2] {
+            node.consensus_driver.node_config.threshold_config.threshold = 0;
+        }
+
         let node_1_peer_data = PeerData {
             node_id: node_1.config.id.clone(),
             node_type: node_1.config.node_type,
@@ -224,7 +1005,11 @@ mod tests {
             udp_gossip_addr: node_1.config.udp_gossip_address,
             raptorq_gossip_addr: node_1.config.raptorq_gossip_address,
             kademlia_liveness_addr: node_1.config.kademlia_liveness_address,
+            alternate_udp_gossip_addrs: vec![],
             validator_public_key: node_1.config.keypair.validator_public_key_owned(),
+            locality: node_1.config.locality.clone(),
+            claim_signature: None,
+            event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
         };
 
         let node_2_peer_data = PeerData {
@@ -234,76 +1019,110 @@ mod tests {
             udp_gossip_addr: node_2.config.udp_gossip_address,
             raptorq_gossip_addr: node_2.config.raptorq_gossip_address,
             kademlia_liveness_addr: node_2.config.kademlia_liveness_address,
+            alternate_udp_gossip_addrs: vec![],
             validator_public_key: node_2.config.keypair.validator_public_key_owned(),
+            locality: node_2.config.locality.clone(),
+            claim_signature: None,
+            event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
         };
 
-        node_1
-            .handle_node_added_to_peer_list(node_2_peer_data.clone())
-            .await
-            .unwrap();
-
-        node_2
-            .handle_node_added_to_peer_list(node_1_peer_data.clone())
-            .await
-            .unwrap();
+        let keyset = QuorumKeysetAnnouncement::new(
+            QuorumKind::Farmer,
+            &[node_1_peer_data.clone(), node_2_peer_data.clone()],
+            QuorumAssignmentStrategy::Sequential,
+        );
 
         let assigned_membership_1 = AssignedQuorumMembership {
             quorum_kind: QuorumKind::Farmer,
             node_id: node_1.id.clone(),
             kademlia_peer_id: node_1.config.kademlia_peer_id.unwrap(),
-            peers: vec![node_2_peer_data],
+            peers: vec![node_2_peer_data.clone()],
+            keyset: keyset.clone(),
         };
 
-        node_1
-            .handle_quorum_membership_assigment_created(assigned_membership_1)
-            .unwrap();
-
         let assigned_membership_2 = AssignedQuorumMembership {
             quorum_kind: QuorumKind::Farmer,
             node_id: node_2.id.clone(),
             kademlia_peer_id: node_2.config.kademlia_peer_id.unwrap(),
-            peers: vec![node_1_peer_data],
+            peers: vec![node_1_peer_data.clone()],
+            keyset,
         };
 
-        node_2
-            .handle_quorum_membership_assigment_created(assigned_membership_2)
-            .unwrap();
-
-        let (part_1, node_id_1) = node_1.generate_partial_commitment_message().unwrap();
-        let (part_2, node_id_2) = node_2.generate_partial_commitment_message().unwrap();
+        let mut node_1 = ActorHarness::for_node_runtime(node_1);
+        let mut node_2 = ActorHarness::for_node_runtime(node_2);
 
-        let parts = vec![(node_id_1, part_1), (node_id_2, part_2)];
+        node_1
+            .run_script(vec![
+                ScriptStep::Send(Event::NodeAddedToPeerList(node_2_peer_data)),
+                ScriptStep::ExpectNoneEmitted,
+                ScriptStep::Send(Event::QuorumMembershipAssigmentCreated(
+                    assigned_membership_1,
+                )),
+            ])
+            .await;
 
-        let mut acks = vec![];
+        node_2
+            .run_script(vec![
+                ScriptStep::Send(Event::NodeAddedToPeerList(node_1_peer_data)),
+                ScriptStep::ExpectNoneEmitted,
+                ScriptStep::Send(Event::QuorumMembershipAssigmentCreated(
+                    assigned_membership_2,
+                )),
+            ])
+            .await;
+
+        for node in [&mut node_1, &mut node_2] {
+            assert!(matches!(
+                node.next_emitted().await,
+                Event::QuorumMembershipConfigured(_)
+            ));
+        }
 
-        for (node_id, part) in parts {
-            let (receiver_id, sender_id, ack) = node_1
-                .handle_part_commitment_created(node_id.clone(), part.clone())
-                .unwrap();
+        // NOTE: play the network's part by multicasting every emitted part
+        // commitment, then every ack, to both nodes
+        let parts = vec![node_1.next_emitted().await, node_2.next_emitted().await];
 
-            acks.push((receiver_id, sender_id, ack));
+        let mut acks = vec![];
+        for part in parts {
+            for node in [&mut node_1, &mut node_2] {
+                node.send(part.clone()).await.unwrap();
+                acks.push(node.next_emitted().await);
+            }
+        }
 
-            let (receiver_id, sender_id, ack) = node_2
-                .handle_part_commitment_created(node_id.clone(), part.clone())
-                .unwrap();
+        for node in [&mut node_1, &mut node_2] {
+            let script = acks.iter().cloned().map(ScriptStep::Send).collect();
+            node.run_script(script).await;
 
-            acks.push((receiver_id, sender_id, ack));
+            node.actor_mut().handle_all_ack_messages().unwrap();
+            node.actor_mut().generate_keysets().unwrap();
         }
 
-        let mut farmer_nodes = vec![&mut node_1, &mut node_2];
+        let public_key_set = |node: &ActorHarness<NodeRuntime>| {
+            node.actor()
+                .consensus_driver
+                .dkg_engine
+                .dkg_state
+                .public_key_set_owned()
+        };
 
-        for node in farmer_nodes.iter_mut() {
-            for (receiver_id, sender_id, ack) in acks.iter().cloned() {
-                node.handle_part_commitment_acknowledged(receiver_id, sender_id, ack)
-                    .unwrap();
-            }
-        }
+        assert!(public_key_set(&node_1).is_some());
+        assert_eq!(public_key_set(&node_1), public_key_set(&node_2));
 
-        for node in farmer_nodes.iter_mut() {
-            node.handle_all_ack_messages().unwrap();
-        }
-        for node in farmer_nodes.iter_mut() {
-            node.generate_keysets().unwrap();
+        for node in [&node_1, &node_2] {
+            let quorum = node
+                .actor()
+                .state_driver
+                .read_handle()
+                .current_quorum()
+                .unwrap();
+
+            assert_eq!(quorum.quorum_kind, QuorumKind::Farmer);
+            assert_eq!(quorum.members.len(), 2);
+            assert_eq!(
+                Some(quorum.group_public_key),
+                node.actor().consensus_driver.quorum_pubkey()
+            );
         }
     }
 
@@ -478,6 +1297,7 @@ mod tests {
         panic!();
     }
 
+    #[cfg(feature = "consensus")]
     #[tokio::test]
     #[ignore = "broken atm"]
     async fn node_runtime_can_form_quorum_with_valid_config() {
@@ -489,6 +1309,7 @@ mod tests {
         run_dkg_process(farmers);
     }
 
+    #[cfg(feature = "consensus")]
     fn run_dkg_process(mut nodes: HashMap<NodeId, NodeRuntime>) {
         let mut parts = HashMap::new();
 
@@ -574,7 +1395,11 @@ mod tests {
                 udp_gossip_addr: node.config.udp_gossip_address,
                 raptorq_gossip_addr: node.config.raptorq_gossip_address,
                 kademlia_liveness_addr: node.config.kademlia_liveness_address,
+                alternate_udp_gossip_addrs: vec![],
                 validator_public_key: node.config.keypair.validator_public_key_owned(),
+                locality: node.config.locality.clone(),
+                claim_signature: None,
+                event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
             };
 
             let assignments = node_0
@@ -603,7 +1428,11 @@ mod tests {
                     udp_gossip_addr: other_node.config.udp_gossip_address,
                     raptorq_gossip_addr: other_node.config.raptorq_gossip_address,
                     kademlia_liveness_addr: other_node.config.kademlia_liveness_address,
+                    alternate_udp_gossip_addrs: vec![],
                     validator_public_key: other_node.config.keypair.validator_public_key_owned(),
+                    locality: other_node.config.locality.clone(),
+                    claim_signature: None,
+                    event_schema_version: LEGACY_EVENT_SCHEMA_VERSION,
                 };
 
                 node.handle_node_added_to_peer_list(peer_data.clone())
@@ -1,45 +1,82 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fmt::format,
     hash::Hash,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
+    time::Instant,
 };
 
 use block::{
-    header::BlockHeader, vesting::GenesisConfig, Block, Certificate, ClaimHash, ConvergenceBlock,
-    GenesisBlock, ProposalBlock, RefHash,
+    header::BlockHeader, vesting::GenesisConfig, Block, BlockHash, Certificate, ClaimHash,
+    ConvergenceBlock, GenesisBlock, HaltAction, HaltDirective, ProposalBlock, RefHash,
+    SignedHaltDirective,
 };
 use bulldag::graph::BullDag;
+#[cfg(feature = "consensus")]
 use dkg_engine::prelude::{DkgEngine, DkgEngineConfig, ReceiverId, SenderId};
 use ethereum_types::U256;
-use events::{AssignedQuorumMembership, EventPublisher, PeerData};
-use hbbft::sync_key_gen::{Ack, Part};
-use mempool::{LeftRightMempool, MempoolReadHandleFactory, TxnRecord};
+use events::{
+    AssignedQuorumMembership, BacklogSnapshot, ChainRecord, ChainRecordKey, DkgStatus, Event,
+    EventPublisher, PeerData, PendingBlockPreview, PendingBlockVotes, RoundSchedulerState,
+    SyncStatus, TxnStatusChange, ValidatorDuties, ValidatorKeyRotation, Vote,
+};
+#[cfg(feature = "consensus")]
+use hbbft::sync_key_gen::Ack;
+use hbbft::sync_key_gen::Part;
+use mempool::{
+    BlockFill, LeftRightMempool, MempoolCompaction, MempoolReadHandleFactory, MempoolStats,
+    PendingTxn, RecentBlockFills, TxnRecord, TxnStatus,
+};
+#[cfg(feature = "mining")]
 use miner::{Miner, MinerConfig};
 use primitives::{
-    Address, Epoch, NodeId, NodeType, PublicKey, QuorumKind, Round, ValidatorPublicKey,
+    Address, ChainSpec, Epoch, FarmerQuorumThreshold, NodeId, NodeIdx, NodeType, ProtocolParameter,
+    PublicKey, PublicKeyShareVec, QuorumKind, RawSignature, Round, ValidatorPublicKey,
+    ValidatorSecretKey,
 };
+use reward::schedule::RewardSchedule;
 use ritelinked::LinkedHashMap;
 use secp256k1::Message;
-use storage::vrrbdb::{ApplyBlockResult, VrrbDbConfig, VrrbDbReadHandle};
+use storage::vrrbdb::{
+    ApplyBlockResult, BlockRecovery, ChainScrubber, CorruptRecord, RoundMilestone, ScrubbedStore,
+    VrrbDbConfig, VrrbDbReadHandle,
+};
 use theater::{ActorId, ActorState};
 use tokio::task::JoinHandle;
 use utils::payload::digest_data_to_bytes;
+use validator::{
+    result::ValidatorError,
+    txn_validator::{TxnValidator, TxnValidatorError},
+    validator_core_manager::ValidatorCoreManager,
+};
 use vrrb_config::{NodeConfig, QuorumMembershipConfig};
 use vrrb_core::{
     account::{Account, UpdateArgs},
-    claim::Claim,
+    claim::{Claim, Eligibility},
     transactions::{
         generate_transfer_digest_vec, NewTransferArgs, Token, Transaction, TransactionDigest,
         TransactionKind, Transfer,
     },
 };
 
+#[cfg(any(feature = "consensus", feature = "mining"))]
+use crate::consensus::miner_schedule;
+#[cfg(feature = "mining")]
+use crate::consensus::scheduled_slot;
+#[cfg(feature = "consensus")]
+use crate::consensus::{
+    verify_election_threshold, verify_miner_slot, ConsensusModule, ConsensusModuleConfig,
+    SigningJob, SigningWorkerPool,
+};
 use crate::{
-    consensus::{ConsensusModule, ConsensusModuleConfig},
-    mining_module::{MiningModule, MiningModuleConfig},
+    block_import::{
+        BlockImportMonitor, BlockImportQueue, ImportOutcome, ImportStage, IncomingBlock,
+        SyncProgress,
+    },
+    consensus::enforce_quorum_safety,
     result::{NodeError, Result},
     state_manager::{StateManager, StateManagerConfig},
+    OptionalModule,
 };
 
 pub const PULL_TXN_BATCH_SIZE: usize = 100;
@@ -51,14 +88,44 @@ pub struct NodeRuntime {
     pub status: ActorState,
     // TODO: make private
     pub config: NodeConfig,
+    /// Chain spec the node was configured with, which the protocol parameter
+    /// changes recorded on chain apply on top of
+    genesis_chain_spec: ChainSpec,
     pub events_tx: EventPublisher,
     pub state_driver: StateManager,
+    #[cfg(feature = "consensus")]
     pub consensus_driver: ConsensusModule,
+    #[cfg(feature = "mining")]
     pub mining_driver: Miner,
+    #[cfg(feature = "consensus")]
+    pub signing_pool: SigningWorkerPool,
+    /// Validates the txns submitted through [NodeRuntime::submit_transaction]
+    validator_core_manager: Arc<Mutex<ValidatorCoreManager>>,
+    maintenance_mode: bool,
+    /// Set while a halt directive of the harvester quorum is in force, during
+    /// which no node mines or votes
+    halted: bool,
+    /// Round of the latest halt or resume directive the node acted on
+    last_halt_round: Option<Round>,
+    block_import_queue: BlockImportQueue,
+    sync_progress: SyncProgress,
+    /// How much the latest convergence blocks included, which the mempool
+    /// stats estimate inclusion times from
+    block_fills: RecentBlockFills,
+    /// Walks the persisted chain data a batch at a time, looking for
+    /// corrupted records
+    chain_scrubber: ChainScrubber,
+    /// Corrupted records being requested from peers, along with the number
+    /// of scrubber passes completed when they were found
+    chain_records_requested: HashMap<(ScrubbedStore, Vec<u8>), (CorruptRecord, u64)>,
 }
 
 impl NodeRuntime {
     pub async fn new(config: &NodeConfig, events_tx: EventPublisher) -> Result<Self> {
+        for module in OptionalModule::required_by(config.node_type) {
+            module.require(&format!("run a {} node", config.node_type))?;
+        }
+
         let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
 
         let miner_public_key = config.keypair.get_miner_public_key().to_owned();
@@ -88,54 +155,132 @@ impl NodeRuntime {
             vrrbdb_config.with_path(config.db_path().to_path_buf());
         }
 
-        let database = storage::vrrbdb::VrrbDb::new(vrrbdb_config);
-        let mempool = LeftRightMempool::new();
+        vrrbdb_config.with_pruning_mode(config.pruning_mode);
+        vrrbdb_config.with_certification_retention(config.certification_retention);
+        vrrbdb_config.with_timeline_rounds(config.consensus_timeline_rounds);
+        vrrbdb_config.with_backend(config.db_backend);
+
+        let mut database = storage::vrrbdb::VrrbDb::new(vrrbdb_config);
+
+        match database.recover_block_application()? {
+            BlockRecovery::Clean => {},
+            BlockRecovery::Replayed { block_hash } => {
+                telemetry::warn!("Replayed block {block_hash}, its application was interrupted");
+            },
+            BlockRecovery::RolledBack { block_hash, reason } => {
+                telemetry::error!(
+                    "Rolled back interrupted block {block_hash}, replay failed: {reason}"
+                );
+            },
+        }
+
+        let chain_scrubber = database.scrubber();
+
+        config
+            .mempool_admission_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        config
+            .checkpoint_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        // NOTE: quorums are checked again as they're assigned, this catches
+        // thresholds a full quorum couldn't run with before joining any
+        enforce_quorum_safety(
+            &config.threshold_config,
+            config.threshold_config.upper_bound as usize,
+            config.quorum_safety_mode,
+        )?;
+
+        let mempool =
+            LeftRightMempool::with_admission_config(config.mempool_admission_config.clone());
 
         let state_driver = StateManager::new(StateManagerConfig {
             database,
             mempool,
             dag,
             claim,
+            chain_spec: config.chain_spec.clone(),
+            invariants_config: config.invariants_config.clone(),
         });
 
-        let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
+        // NOTE: a halted network stays halted across restarts until the quorum
+        // resumes it
+        let last_halt_directive = state_driver.read_handle().latest_halt_directive();
 
-        let (_, miner_secret_key) = config.keypair.get_secret_keys();
-        let (_, miner_public_key) = config.keypair.get_public_keys();
+        #[cfg(feature = "mining")]
+        let miner = {
+            let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
 
-        let miner_config = MinerConfig {
-            secret_key: *miner_secret_key,
-            public_key: *miner_public_key,
-            ip_address: config.public_ip_address,
-            dag,
-        };
+            let (_, miner_secret_key) = config.keypair.get_secret_keys();
+            let (_, miner_public_key) = config.keypair.get_public_keys();
 
-        let miner = miner::Miner::new(miner_config, config.id.clone()).map_err(NodeError::from)?;
+            let miner_config = MinerConfig {
+                secret_key: *miner_secret_key,
+                public_key: *miner_public_key,
+                ip_address: config.public_ip_address,
+                dag,
+                chain_spec: config.chain_spec.clone(),
+            };
 
-        let dkg_engine_config = DkgEngineConfig {
-            node_id: config.id.clone(),
-            node_type: config.node_type,
-            secret_key: config.keypair.get_validator_secret_key_owned(),
-            threshold_config: config.threshold_config.clone(),
+            miner::Miner::new(miner_config, config.id.clone()).map_err(NodeError::from)?
         };
 
-        let dkg_generator = DkgEngine::new(dkg_engine_config);
-
-        let consensus_driver = ConsensusModule::new(ConsensusModuleConfig {
-            keypair: config.keypair.clone(),
-            node_config: config.clone(),
-            dkg_generator,
-            validator_public_key: config.keypair.validator_public_key_owned(),
-        });
+        config
+            .signing_pool_config
+            .validate()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        #[cfg(feature = "consensus")]
+        let signing_pool = SigningWorkerPool::new(&config.signing_pool_config, events_tx.clone());
+
+        let validator_core_manager =
+            ValidatorCoreManager::with_config(1, &config.chain_spec, &config.txn_validity_config)?;
+
+        #[cfg(feature = "consensus")]
+        let consensus_driver = {
+            let dkg_engine_config = DkgEngineConfig {
+                node_id: config.id.clone(),
+                node_type: config.node_type,
+                secret_key: config.keypair.get_validator_secret_key_owned(),
+                threshold_config: config.threshold_config.clone(),
+            };
+
+            ConsensusModule::new(ConsensusModuleConfig {
+                keypair: config.keypair.clone(),
+                node_config: config.clone(),
+                dkg_generator: DkgEngine::new(dkg_engine_config),
+                validator_public_key: config.keypair.validator_public_key_owned(),
+                certified_txn_index: state_driver.read_handle().certified_txn_index(),
+            })
+        };
 
         Ok(Self {
             id: uuid::Uuid::new_v4().to_string(),
             status: ActorState::Stopped,
             config: config.to_owned(),
+            genesis_chain_spec: config.chain_spec.clone(),
             state_driver,
+            #[cfg(feature = "consensus")]
             consensus_driver,
             events_tx,
+            #[cfg(feature = "mining")]
             mining_driver: miner,
+            #[cfg(feature = "consensus")]
+            signing_pool,
+            validator_core_manager: Arc::new(Mutex::new(validator_core_manager)),
+            maintenance_mode: false,
+            halted: last_halt_directive
+                .as_ref()
+                .map_or(false, |signed| signed.directive.action == HaltAction::Halt),
+            last_halt_round: last_halt_directive.map(|signed| signed.directive.round),
+            block_import_queue: BlockImportQueue::new(config.block_import_config.clone()),
+            sync_progress: SyncProgress::default(),
+            block_fills: RecentBlockFills::default(),
+            chain_scrubber,
+            chain_records_requested: HashMap::new(),
         })
     }
 
@@ -159,6 +304,171 @@ impl NodeRuntime {
         Ok(None)
     }
 
+    /// Returns whether the node is in maintenance mode, during which it keeps
+    /// following the chain and serving reads but neither mines nor votes
+    pub fn is_in_maintenance_mode(&self) -> bool {
+        self.maintenance_mode
+    }
+
+    /// Enters or leaves maintenance mode, returning whether the mode changed.
+    /// The node keeps its quorum membership and DKG keys while paused, so it
+    /// can resume right away.
+    pub fn set_maintenance_mode(&mut self, paused: bool) -> bool {
+        let changed = self.maintenance_mode != paused;
+        self.maintenance_mode = paused;
+
+        changed
+    }
+
+    /// Returns whether block production was halted by the harvester quorum
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Signs the node's share of a halt directive requested by an operator
+    #[cfg(feature = "consensus")]
+    pub fn sign_halt_directive(
+        &mut self,
+        directive: &HaltDirective,
+    ) -> Result<(NodeIdx, RawSignature)> {
+        self.belongs_to_correct_quorum(QuorumKind::Harvester, "sign halt directive")?;
+
+        self.consensus_driver.sign_halt_directive(directive)
+    }
+
+    #[cfg(not(feature = "consensus"))]
+    pub fn sign_halt_directive(
+        &mut self,
+        _directive: &HaltDirective,
+    ) -> Result<(NodeIdx, RawSignature)> {
+        Err(OptionalModule::Consensus.unavailable("sign halt directive"))
+    }
+
+    /// Collects a harvester's share of a halt directive, returning the
+    /// certified directive once enough harvesters signed it
+    #[cfg(feature = "consensus")]
+    pub fn handle_halt_directive_signed(
+        &mut self,
+        directive: HaltDirective,
+        node_idx: NodeIdx,
+        signature: RawSignature,
+    ) -> Result<Option<SignedHaltDirective>> {
+        self.belongs_to_correct_quorum(QuorumKind::Harvester, "certify halt directive")?;
+
+        self.consensus_driver
+            .handle_halt_directive_signed(directive, node_idx, signature)
+    }
+
+    #[cfg(not(feature = "consensus"))]
+    pub fn handle_halt_directive_signed(
+        &mut self,
+        _directive: HaltDirective,
+        _node_idx: NodeIdx,
+        _signature: RawSignature,
+    ) -> Result<Option<SignedHaltDirective>> {
+        Err(OptionalModule::Consensus.unavailable("certify halt directive"))
+    }
+
+    /// Halts or resumes block production as a certified directive says.
+    /// Directives not newer than the last one acted on are ignored, so they
+    /// can't be replayed. Directives no block recorded yet are carried in the
+    /// proposal blocks the node mines. Returns whether the directive was
+    /// applied
+    pub fn apply_halt_directive(&mut self, signed: SignedHaltDirective) -> Result<bool> {
+        let round = signed.directive.round;
+
+        if self
+            .last_halt_round
+            .map_or(false, |last_round| round <= last_round)
+        {
+            return Ok(false);
+        }
+
+        self.state_driver.verify_halt_directive(&signed)?;
+
+        let recorded_round = self
+            .state_driver
+            .read_handle()
+            .latest_halt_directive()
+            .map(|recorded| recorded.directive.round);
+
+        // NOTE: only nodes that mine proposal blocks carry directives in them
+        #[cfg(feature = "consensus")]
+        if recorded_round.map_or(true, |recorded_round| round > recorded_round) {
+            self.consensus_driver.queue_halt_directive(signed.clone());
+        }
+
+        self.halted = signed.directive.action == HaltAction::Halt;
+        self.last_halt_round = Some(round);
+
+        Ok(true)
+    }
+
+    /// Acts on the latest halt directive the applied blocks recorded, which
+    /// nodes that missed it being gossiped learn of this way, and stops
+    /// carrying the directives recorded by then
+    pub fn apply_recorded_halt_directive(&mut self) -> Result<bool> {
+        let Some(recorded) = self.state_driver.read_handle().latest_halt_directive() else {
+            return Ok(false);
+        };
+
+        let round = recorded.directive.round;
+        let applied = self.apply_halt_directive(recorded)?;
+
+        #[cfg(feature = "consensus")]
+        self.consensus_driver.forget_recorded_halt_directives(round);
+
+        Ok(applied)
+    }
+
+    /// Returns whether handling `event` would have the node mine or vote
+    pub fn is_participation_event(event: &Event) -> bool {
+        matches!(
+            event,
+            Event::ProposalBlockMineRequestCreated { .. }
+                | Event::TxnsReadyForProcessing(_)
+                | Event::TxnsValidated { .. }
+                | Event::TransactionCertificateRequested { .. }
+                | Event::ConvergenceBlockSignatureRequested(_)
+                | Event::ConvergenceBlockPrecheckRequested { .. }
+        )
+    }
+
+    /// Moves the node to a freshly generated validator key and returns the
+    /// announcement of the rotation to broadcast to its peers
+    #[cfg(feature = "consensus")]
+    pub fn rotate_validator_key(&mut self) -> Result<ValidatorKeyRotation> {
+        let rotation = self
+            .consensus_driver
+            .rotate_validator_key(ValidatorSecretKey::random());
+
+        self.config.keypair = self.consensus_driver.keypair.clone();
+
+        Ok(rotation)
+    }
+
+    #[cfg(not(feature = "consensus"))]
+    pub fn rotate_validator_key(&mut self) -> Result<ValidatorKeyRotation> {
+        Err(OptionalModule::Consensus.unavailable("rotate validator key"))
+    }
+
+    #[cfg(feature = "consensus")]
+    pub fn handle_peer_validator_key_rotated(
+        &mut self,
+        rotation: ValidatorKeyRotation,
+    ) -> Result<bool> {
+        self.consensus_driver
+            .handle_peer_validator_key_rotated(rotation)
+    }
+
+    #[cfg(not(feature = "consensus"))]
+    pub fn handle_peer_validator_key_rotated(
+        &mut self,
+        _rotation: ValidatorKeyRotation,
+    ) -> Result<bool> {
+        Err(OptionalModule::Consensus.unavailable("track validator key rotations"))
+    }
+
     pub fn has_required_node_type(&self, intended_node_type: NodeType, action: &str) -> Result<()> {
         if !matches!(self.config.node_type, intended_node_type) {
             return Err(NodeError::Other(format!(
@@ -190,6 +500,7 @@ impl NodeRuntime {
         Ok(())
     }
 
+    #[cfg(feature = "consensus")]
     pub fn quorum_membership(&self) -> Option<QuorumMembershipConfig> {
         self.consensus_driver
             .quorum_driver
@@ -197,6 +508,32 @@ impl NodeRuntime {
             .clone()
     }
 
+    /// Nodes built without the consensus module never join a quorum
+    #[cfg(not(feature = "consensus"))]
+    pub fn quorum_membership(&self) -> Option<QuorumMembershipConfig> {
+        None
+    }
+
+    /// Returns the epoch the node's validator set is in
+    #[cfg(feature = "consensus")]
+    pub fn current_epoch(&self) -> Epoch {
+        self.consensus_driver.quorum_driver.current_epoch
+    }
+
+    /// Returns the epoch of the latest confirmed block, as nodes built without
+    /// the consensus module run no quorum elections to track epochs by
+    #[cfg(not(feature = "consensus"))]
+    pub fn current_epoch(&self) -> Epoch {
+        let chain_spec = &self.config.chain_spec;
+
+        self.state_driver
+            .dag
+            .last_confirmed_block_header()
+            .map_or(chain_spec.genesis_epoch, |header| {
+                chain_spec.epoch_at(header.block_height)
+            })
+    }
+
     pub fn state_read_handle(&self) -> VrrbDbReadHandle {
         self.state_driver.read_handle()
     }
@@ -209,6 +546,51 @@ impl NodeRuntime {
         self.mempool_read_handle_factory().entries()
     }
 
+    /// Submits a txn straight to the node, without going through its JSON-RPC
+    /// server. The txn is validated against the current state, admitted to
+    /// the mempool and announced through `TxnAddedToMempool`, just as if a
+    /// client had sent it. Returns its digest and its status in the mempool
+    pub async fn submit_transaction(
+        &mut self,
+        txn: TransactionKind,
+    ) -> Result<(TransactionDigest, TxnStatus)> {
+        let account_state = self.state_driver.read_handle().state_store_values();
+
+        // NOTE: the validator expects the sender's account to be present
+        let sender_address = txn.sender_address();
+        if !account_state.contains_key(&sender_address) {
+            return Err(ValidatorError::from(TxnValidatorError::AccountNotFound(
+                sender_address.to_string(),
+            ))
+            .into());
+        }
+
+        let validated = self
+            .validator_core_manager
+            .lock()
+            .map_err(|err| NodeError::Other(format!("txn validator is poisoned: {err}")))?
+            .validate_admission(&account_state, vec![txn.clone()]);
+
+        if let Some((_, Err(err))) = validated.into_iter().next() {
+            return Err(ValidatorError::from(err).into());
+        }
+
+        let digest = self.state_driver.handle_new_txn_created(txn)?;
+
+        self.events_tx
+            .send(Event::TxnAddedToMempool(digest.clone()).into())
+            .await?;
+
+        let status = self
+            .mempool_read_handle_factory()
+            .get(&digest)
+            .map(|record| record.status)
+            .unwrap_or_default();
+
+        Ok((digest, status))
+    }
+
+    #[cfg(feature = "consensus")]
     pub fn add_peer_public_key_to_dkg_state(
         &mut self,
         node_id: NodeId,
@@ -218,6 +600,7 @@ impl NodeRuntime {
             .add_peer_public_key_to_dkg_state(node_id, public_key);
     }
 
+    #[cfg(feature = "consensus")]
     pub fn generate_partial_commitment_message(&mut self) -> Result<(Part, NodeId)> {
         let (part, node_id) = self
             .consensus_driver
@@ -228,6 +611,12 @@ impl NodeRuntime {
         Ok((part, node_id))
     }
 
+    #[cfg(not(feature = "consensus"))]
+    pub fn generate_partial_commitment_message(&mut self) -> Result<(Part, NodeId)> {
+        Err(OptionalModule::Dkg.unavailable("generate partial commitments"))
+    }
+
+    #[cfg(feature = "consensus")]
     pub fn store_part_commitment(&mut self, node_id: NodeId, part: Part) {
         self.consensus_driver
             .dkg_engine
@@ -236,8 +625,23 @@ impl NodeRuntime {
             .entry(node_id)
             .or_insert_with(|| part);
     }
+
+    #[cfg(feature = "consensus")]
     pub fn generate_keysets(&mut self) -> Result<()> {
-        self.consensus_driver.generate_keysets()
+        self.consensus_driver.generate_keysets()?;
+
+        // NOTE: the quorum is kept on record so certificates it signs can
+        // still be verified once it rotates out
+        if let Some(record) = self.consensus_driver.quorum_record() {
+            self.state_driver.record_quorum(record)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "consensus"))]
+    pub fn generate_keysets(&mut self) -> Result<()> {
+        Err(OptionalModule::Dkg.unavailable("generate keysets"))
     }
 
     pub fn produce_genesis_transactions(
@@ -257,6 +661,7 @@ impl NodeRuntime {
         &self,
         txns: LinkedHashMap<TransactionDigest, TransactionKind>,
     ) -> Result<GenesisBlock> {
+        OptionalModule::Mining.require("mine genesis block")?;
         self.has_required_node_type(NodeType::Miner, "mine genesis block")?;
 
         let claim = self.state_driver.dag.claim();
@@ -275,47 +680,114 @@ impl NodeRuntime {
             claim.clone(),
             self.config.keypair.miner_secret_key_owned(),
             hex::encode(claim_list_hash),
+            &RewardSchedule::new(&self.config.chain_spec),
+            &self.config.chain_spec.header_format,
         );
 
-        let block_header = header.clone();
-        let block_hash = digest_data_to_bytes(&(
-            header.ref_hashes,
-            header.round,
-            header.block_seed,
-            header.next_block_seed,
-            header.block_height,
-            header.timestamp,
-            header.txn_hash,
-            header.miner_claim,
-            header.claim_list_hash,
-            header.block_reward,
-            header.next_block_reward,
-            header.miner_signature,
-        ));
+        let block_hash = header.hash();
 
         let mut claims = LinkedHashMap::new();
         claims.insert(claim.hash, claim);
 
         let genesis = GenesisBlock {
-            header: block_header,
+            header,
             txns,
             claims,
-            hash: hex::encode(block_hash),
+            hash: block_hash,
             certificate: None,
         };
 
         Ok(genesis)
     }
 
+    /// Mines a convergence block, committing its header to the roots the
+    /// state trie and the delegation state will have once the block is
+    /// applied
+    #[cfg(feature = "mining")]
     pub fn mine_convergence_block(&mut self) -> Result<ConvergenceBlock> {
         self.has_required_node_type(NodeType::Miner, "mine convergence block")?;
-        self.mining_driver
+        let mut block = self
+            .mining_driver
             .mine_convergence_block()
             .ok_or(NodeError::Other(
                 "Could not mine convergence block".to_string(),
+            ))?;
+
+        let fallback_slot = self.miner_fallback_slot(&block.header)?;
+        if fallback_slot != 0 {
+            self.mining_driver
+                .assign_fallback_slot(&mut block, fallback_slot);
+        }
+
+        let roots = self.state_driver.projected_roots(&block)?;
+        self.mining_driver
+            .commit_state_root(&mut block, roots.state_root);
+
+        if !roots.delegation_root.is_empty() {
+            self.mining_driver
+                .commit_delegation_root(&mut block, roots.delegation_root);
+        }
+
+        Ok(block)
+    }
+
+    #[cfg(not(feature = "mining"))]
+    pub fn mine_convergence_block(&mut self) -> Result<ConvergenceBlock> {
+        Err(OptionalModule::Mining.unavailable("mine convergence block"))
+    }
+
+    /// Returns the slot of the round's miner schedule the node may mine the
+    /// block at `header` in, once that slot opened. The round's election
+    /// winner holds slot 0
+    #[cfg(feature = "mining")]
+    fn miner_fallback_slot(&self, header: &BlockHeader) -> Result<u16> {
+        let Some(last_header) = self.state_driver.dag.last_confirmed_block_header() else {
+            return Ok(0);
+        };
+
+        let params = &self.config.chain_spec.miner_fallback;
+        let schedule = miner_schedule(&self.state_driver.read_handle(), header.block_seed, params);
+
+        let slot = scheduled_slot(&schedule, &header.miner_claim).ok_or_else(|| {
+            NodeError::Other(format!(
+                "Node {} isn't scheduled to mine round {}",
+                self.config.id, header.round
             ))
+        })?;
+
+        if slot > params.open_slot(header.timestamp - last_header.timestamp) {
+            return Err(NodeError::Unavailable(format!(
+                "fallback slot {slot} of round {}",
+                header.round
+            )));
+        }
+
+        Ok(slot)
     }
 
+    /// Checks the block at `header` was mined in its miner's slot of the
+    /// round's schedule by a miner that met the election threshold, see
+    /// [verify_miner_slot] and [verify_election_threshold]
+    #[cfg(feature = "consensus")]
+    fn verify_miner_election(&self, header: &BlockHeader, last_header: &BlockHeader) -> Result<()> {
+        let params = &self.config.chain_spec.miner_fallback;
+        let schedule = miner_schedule(&self.state_driver.read_handle(), header.block_seed, params);
+
+        let now = chrono::Utc::now().timestamp();
+        verify_miner_slot(&schedule, header, last_header, params, now)?;
+
+        // NOTE: the miner holds the slot of the schedule it was mined in
+        let miner_claim = &schedule[header.fallback_slot as usize];
+
+        verify_election_threshold(
+            header,
+            last_header,
+            miner_claim,
+            &self.config.chain_spec.election_difficulty,
+        )
+    }
+
+    #[cfg(feature = "consensus")]
     pub fn certify_convergence_block(&mut self, block: ConvergenceBlock) -> Result<()> {
         self.has_required_node_type(NodeType::Validator, "certify convergence block")?;
         self.belongs_to_correct_quorum(QuorumKind::Harvester, "certify convergence block")?;
@@ -329,12 +801,82 @@ impl NodeRuntime {
                     self.config.id
                 )))?;
 
+        self.verify_miner_election(&block.header, &last_block_header)?;
+
         self.consensus_driver
             .certify_convergence_block(block, last_block_header);
 
         Ok(())
     }
 
+    #[cfg(not(feature = "consensus"))]
+    pub fn certify_convergence_block(&mut self, _block: ConvergenceBlock) -> Result<()> {
+        Err(OptionalModule::Consensus.unavailable("certify convergence block"))
+    }
+
+    /// Queues the partial signature of a convergence block on the signing
+    /// pool. It is published as `ConvergenceBlockPartialSignatureCreated` once
+    /// generated.
+    #[cfg(feature = "consensus")]
+    pub fn request_convergence_block_signature(&self, block: ConvergenceBlock) -> Result<()> {
+        self.has_required_node_type(NodeType::Validator, "sign convergence block")?;
+        self.belongs_to_correct_quorum(QuorumKind::Harvester, "sign convergence block")?;
+
+        self.signing_pool.submit(SigningJob::SignConvergenceBlock(
+            self.consensus_driver.sig_provider.clone(),
+            block.hash,
+        ))
+    }
+
+    #[cfg(not(feature = "consensus"))]
+    pub fn request_convergence_block_signature(&self, _block: ConvergenceBlock) -> Result<()> {
+        Err(OptionalModule::Consensus.unavailable("sign convergence block"))
+    }
+
+    /// Queues the aggregation of a transaction's votes into a certificate on
+    /// the signing pool. It is published as `TransactionCertificateCreated`
+    /// once generated.
+    #[cfg(feature = "consensus")]
+    pub fn request_transaction_certificate(
+        &self,
+        votes: Vec<Vote>,
+        txn_id: TransactionDigest,
+        quorum_key: PublicKeyShareVec,
+        farmer_id: NodeId,
+        txn: TransactionKind,
+        quorum_threshold: FarmerQuorumThreshold,
+    ) -> Result<()> {
+        self.has_required_node_type(NodeType::Validator, "certify transaction")?;
+        self.belongs_to_correct_quorum(QuorumKind::Harvester, "certify transaction")?;
+
+        if self.state_driver.is_txn_certified(&txn_id) {
+            return Err(NodeError::TxnAlreadyCertified(txn_id));
+        }
+
+        self.signing_pool.submit(SigningJob::CertifyTxn {
+            sig_provider: self.consensus_driver.sig_provider.clone(),
+            votes,
+            txn_id,
+            quorum_key,
+            farmer_id,
+            txn,
+            quorum_threshold,
+        })
+    }
+
+    #[cfg(not(feature = "consensus"))]
+    pub fn request_transaction_certificate(
+        &self,
+        _votes: Vec<Vote>,
+        _txn_id: TransactionDigest,
+        _quorum_key: PublicKeyShareVec,
+        _farmer_id: NodeId,
+        _txn: TransactionKind,
+        _quorum_threshold: FarmerQuorumThreshold,
+    ) -> Result<()> {
+        Err(OptionalModule::Consensus.unavailable("certify transaction"))
+    }
+
     pub fn transactions_root_hash(&self) -> Result<String> {
         self.state_driver.transactions_root_hash()
     }
@@ -394,6 +936,241 @@ impl NodeRuntime {
         Ok(header.round)
     }
 
+    /// Captures the work the node has yet to get through, so operators can
+    /// tell where certification stalled
+    pub fn backlog_snapshot(&self) -> BacklogSnapshot {
+        let pending_votes = self
+            .state_driver
+            .read_handle()
+            .block_certifications()
+            .into_iter()
+            .filter(|certification| !certification.is_certified())
+            .map(|certification| PendingBlockVotes {
+                votes: certification.votes.len(),
+                approvals: certification
+                    .votes
+                    .iter()
+                    .filter(|vote| vote.approved)
+                    .count(),
+                partial_signatures: certification.partial_signatures.len(),
+                block_hash: certification.block_hash,
+            })
+            .collect();
+
+        let snapshot = BacklogSnapshot {
+            node_id: self.config.id.clone(),
+            mempool_size: self.state_driver.mempool.size(),
+            pending_votes,
+            scheduler: RoundSchedulerState {
+                round: self.get_round().ok(),
+                epoch: self.current_epoch(),
+                maintenance_mode: self.maintenance_mode,
+                paused_peers: vec![],
+            },
+            ..Default::default()
+        };
+
+        // NOTE: nodes built without the consensus module hold no consensus backlog
+        #[cfg(feature = "consensus")]
+        let snapshot = {
+            let mut paused_peers: Vec<NodeId> =
+                self.consensus_driver.paused_peers.iter().cloned().collect();
+            paused_peers.sort();
+
+            BacklogSnapshot {
+                quorum_certified_txns: self.consensus_driver.quorum_certified_txns.len(),
+                convergence_block_certificates: self.consensus_driver.certificate_shares(),
+                dkg: self.consensus_driver.dkg_backlog(),
+                scheduler: RoundSchedulerState {
+                    paused_peers,
+                    ..snapshot.scheduler
+                },
+                ..snapshot
+            }
+        };
+
+        snapshot
+    }
+
+    /// Works out the duties `node_id` can expect over `epoch`. Quorum duties
+    /// of past epochs come from the quorum history, those of the current and
+    /// later epochs from the validator set changes queued so far
+    pub fn validator_duties(&self, node_id: &NodeId, epoch: Epoch) -> ValidatorDuties {
+        let chain_spec = &self.config.chain_spec;
+        let read_handle = self.state_driver.read_handle();
+
+        let recorded_quorum_kind = || {
+            read_handle
+                .get_quorum(epoch)
+                .ok()
+                .filter(|record| record.members.contains_key(node_id))
+                .map(|record| record.quorum_kind)
+        };
+
+        // NOTE: nodes built without the consensus module know of no validator
+        // set changes, only of the quorums on record
+        #[cfg(not(feature = "consensus"))]
+        let (quorum_kind, activates_at, exits_at) = (recorded_quorum_kind(), None, None);
+
+        #[cfg(feature = "consensus")]
+        let (quorum_kind, activates_at, exits_at) = {
+            let quorum_driver = &self.consensus_driver.quorum_driver;
+
+            let quorum_kind = if epoch < quorum_driver.current_epoch {
+                recorded_quorum_kind()
+            } else {
+                quorum_driver.scheduled_quorum(node_id, epoch)
+            };
+
+            (
+                quorum_kind,
+                quorum_driver.activation_queue.activation_epoch(node_id),
+                quorum_driver
+                    .exit_queue
+                    .get(node_id)
+                    .map(|exit| exit.exit_epoch),
+            )
+        };
+
+        let claims = read_handle.claim_store_values();
+        let miners = claims
+            .values()
+            .filter(|claim| claim.eligibility == Eligibility::Miner)
+            .count() as u128;
+
+        let expected_mining_slots = match claims.get(node_id) {
+            Some(claim) if claim.eligibility == Eligibility::Miner => {
+                chain_spec.epoch_length / miners
+            },
+            _ => 0,
+        };
+
+        // NOTE: seeds come from the VRF output of the previous block, so only the
+        // next block's election can be run ahead of time
+        let next_mining_slot = self
+            .state_driver
+            .dag
+            .last_confirmed_block_header()
+            .and_then(|header| {
+                let next_block_height = header.block_height + 1;
+                if chain_spec.epoch_at(next_block_height) != epoch {
+                    return None;
+                }
+
+                let (_, winner) = read_handle.miner_election_winner(header.next_block_seed)?;
+
+                (&winner.node_id == node_id).then_some(next_block_height)
+            });
+
+        let first_block_height = chain_spec.epoch_start(epoch);
+
+        ValidatorDuties {
+            node_id: node_id.clone(),
+            epoch,
+            first_block_height,
+            last_block_height: first_block_height + chain_spec.epoch_length - 1,
+            quorum_kind,
+            activates_at,
+            exits_at,
+            next_mining_slot,
+            expected_mining_slots,
+        }
+    }
+
+    /// Applies the protocol parameter changes in effect at the current epoch
+    /// to the chain spec the node was configured with, and hands the result
+    /// to every module reading it. Returns the parameters that changed
+    pub fn refresh_protocol_parameters(&mut self) -> Result<Vec<ProtocolParameter>> {
+        let epoch = self.current_epoch();
+        let chain_spec = self
+            .state_driver
+            .read_handle()
+            .chain_spec_at(&self.genesis_chain_spec, epoch);
+
+        let changed: Vec<ProtocolParameter> = ProtocolParameter::ALL
+            .into_iter()
+            .filter(|parameter| {
+                parameter.value_in(&chain_spec) != parameter.value_in(&self.config.chain_spec)
+            })
+            .collect();
+
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+
+        *self
+            .validator_core_manager
+            .lock()
+            .map_err(|err| NodeError::Other(format!("txn validator is poisoned: {err}")))? =
+            ValidatorCoreManager::with_config(1, &chain_spec, &self.config.txn_validity_config)?;
+
+        self.state_driver.chain_spec = chain_spec.clone();
+
+        #[cfg(feature = "consensus")]
+        {
+            self.consensus_driver.node_config.chain_spec = chain_spec.clone();
+        }
+
+        #[cfg(feature = "mining")]
+        {
+            self.mining_driver.chain_spec = chain_spec.clone();
+        }
+
+        self.config.chain_spec = chain_spec;
+
+        Ok(changed)
+    }
+
+    /// Looks up who the node exchanges messages with next round from its
+    /// duties: the members of its quorum still serving in the epoch of the
+    /// next block, and the miner expected to produce it. None until a block
+    /// is confirmed, as the miner is elected with the seed it carries
+    pub fn next_round_peers(&self) -> Option<(Round, Vec<NodeId>)> {
+        let header = self.state_driver.dag.last_confirmed_block_header()?;
+
+        let mut peers: BTreeSet<NodeId> = BTreeSet::new();
+
+        // NOTE: nodes built without the consensus module belong to no quorum
+        #[cfg(feature = "consensus")]
+        {
+            let quorum_driver = &self.consensus_driver.quorum_driver;
+            let epoch = self.config.chain_spec.epoch_at(header.block_height + 1);
+
+            peers.extend(
+                quorum_driver
+                    .membership_config
+                    .iter()
+                    .flat_map(|membership_config| membership_config.quorum_members.keys())
+                    .filter(|node_id| quorum_driver.scheduled_quorum(node_id, epoch).is_some())
+                    .cloned(),
+            );
+        }
+
+        if let Some((_, miner)) = self
+            .state_driver
+            .read_handle()
+            .miner_election_winner(header.next_block_seed)
+        {
+            peers.insert(miner.node_id);
+        }
+
+        peers.remove(&self.config.id);
+
+        Some((header.round + 1, peers.into_iter().collect()))
+    }
+
+    /// Ranks the claims for the elections run with the seed of the latest
+    /// confirmed block, away from the actor, so neither the elections nor the
+    /// blocks verified against them sort every claim
+    pub fn prepare_next_elections(&self) {
+        let Some(header) = self.state_driver.dag.last_confirmed_block_header() else {
+            return;
+        };
+
+        let read_handle = self.state_driver.read_handle();
+        tokio::task::spawn_blocking(move || read_handle.prepare_elections(header.next_block_seed));
+    }
+
     pub fn get_claims_by_account_address(&self, address: &Address) -> Result<Vec<Claim>> {
         self.state_driver.get_claims_by_account_address(address)
     }
@@ -409,6 +1186,19 @@ impl NodeRuntime {
 
 impl NodeRuntime {
     pub fn handle_block_received(&mut self, block: Block) -> Result<ApplyBlockResult> {
+        let header_and_hash = match &block {
+            Block::Genesis { block } => Some((&block.header, &block.hash)),
+            Block::Convergence { block } => Some((&block.header, &block.hash)),
+            Block::Proposal { .. } => None,
+        };
+
+        if let Some((header, hash)) = header_and_hash {
+            header.verify(hash).map_err(|err| NodeError::InvalidBlock {
+                hash: hash.to_string(),
+                reason: err.to_string(),
+            })?;
+        }
+
         match block {
             Block::Genesis { block } => self.handle_genesis_block_received(block),
             Block::Proposal { block } => self.handle_proposal_block_received(block),
@@ -416,6 +1206,226 @@ impl NodeRuntime {
         }
     }
 
+    /// Records when a round reached a consensus milestone. The timeline is
+    /// only kept for post-mortems, so failing to record it is just logged
+    pub fn record_round_milestone(&mut self, round: Round, milestone: RoundMilestone) {
+        if let Err(err) = self.state_driver.record_round_milestone(round, milestone) {
+            telemetry::warn!("Failed to record {milestone:?} for round {round}: {err}");
+        }
+    }
+
+    /// Records when the round of a convergence block reached a consensus
+    /// milestone, provided the block is within the DAG
+    pub fn record_block_milestone(&mut self, block_hash: &BlockHash, milestone: RoundMilestone) {
+        match self.state_driver.dag.convergence_block(block_hash) {
+            Some(block) => self.record_round_milestone(block.header.round, milestone),
+            None => {
+                telemetry::debug!("No round known for block {block_hash}, skipping {milestone:?}")
+            },
+        }
+    }
+
+    /// Records the round of a convergence block as having gathered enough
+    /// partial signatures to be certified, once more than the quorum
+    /// threshold of them were recorded
+    pub fn record_signature_progress(&mut self, block_hash: &BlockHash) {
+        let signatures = self
+            .state_driver
+            .read_handle()
+            .get_block_certification(block_hash)
+            .map_or(0, |certification| certification.partial_signatures.len());
+
+        if signatures > self.config.threshold_config.threshold as usize {
+            self.record_block_milestone(block_hash, RoundMilestone::SignatureThresholdReached);
+        }
+    }
+
+    /// Queues a block received from a peer for import
+    pub fn enqueue_block(&mut self, block: IncomingBlock) {
+        if !self.block_import_queue.enqueue(block) {
+            telemetry::warn!("Dropping a received block, the block import queue is full");
+        }
+    }
+
+    /// Imports every queued block, returning how many were imported
+    pub fn process_block_imports(&mut self) -> usize {
+        let mut imported = 0;
+
+        while let Some(block) = self.block_import_queue.pop() {
+            match self.import_block(block) {
+                Ok(ImportOutcome::Imported(block_hash)) => {
+                    self.block_import_queue.complete(&block_hash);
+                    imported += 1;
+                },
+                Ok(ImportOutcome::Buffered { block_hash, parent }) => {
+                    telemetry::debug!("Holding back block {block_hash} until {parent} is imported");
+                },
+                Err(err) => {
+                    telemetry::warn!("Failed to import block: {err}");
+                },
+            }
+        }
+
+        let total_imported = self.block_import_queue.monitor().stats().imported;
+        self.sync_progress
+            .record_imported(Instant::now(), total_imported);
+
+        imported
+    }
+
+    /// Reports how far the node's chain lags behind the blocks received from
+    /// peers and how soon it should catch up
+    pub fn sync_status(&self) -> SyncStatus {
+        self.sync_progress
+            .status(self.get_round().unwrap_or_default())
+    }
+
+    /// Reports how far the key generation of the node's quorum got
+    #[cfg(feature = "consensus")]
+    pub fn dkg_status(&self) -> DkgStatus {
+        self.consensus_driver.dkg_status()
+    }
+
+    /// Nodes built without the consensus module never run key generation
+    #[cfg(not(feature = "consensus"))]
+    pub fn dkg_status(&self) -> DkgStatus {
+        DkgStatus {
+            node_id: self.config.id.clone(),
+            epoch: self.current_epoch(),
+            ..Default::default()
+        }
+    }
+
+    /// Outlines the proposal block the node would produce for the next round,
+    /// using the same txn selection as mining does
+    #[cfg(feature = "consensus")]
+    pub fn pending_block_preview(&self) -> PendingBlockPreview {
+        let round = self.get_round().map_or(0, |round| round + 1);
+
+        self.consensus_driver.pending_block_preview(round)
+    }
+
+    /// Nodes built without the consensus module propose no blocks, so their
+    /// preview is empty
+    #[cfg(not(feature = "consensus"))]
+    pub fn pending_block_preview(&self) -> PendingBlockPreview {
+        PendingBlockPreview {
+            round: self.get_round().map_or(0, |round| round + 1),
+            max_block_weight: self.config.chain_spec.block_limits.max_block_weight,
+            ..Default::default()
+        }
+    }
+
+    /// Summarizes the pending txns of the mempool and how full recent blocks
+    /// were, estimating how long a txn paying `fee` would wait for inclusion
+    /// if one is given
+    pub fn mempool_stats(&self, fee: Option<u128>) -> MempoolStats {
+        let pending: Vec<PendingTxn> = self
+            .mempool_snapshot()
+            .values()
+            .map(PendingTxn::from)
+            .collect();
+
+        let max_block_weight = self.state_driver.chain_spec().block_limits.max_block_weight;
+        let now = chrono::Utc::now().timestamp();
+
+        MempoolStats {
+            inclusion_estimate: fee.map(|fee| self.block_fills.estimate_inclusion(&pending, fee)),
+            ..MempoolStats::new(&pending, &self.block_fills, max_block_weight, now)
+        }
+    }
+
+    pub fn block_import_monitor(&self) -> BlockImportMonitor {
+        self.block_import_queue.monitor()
+    }
+
+    fn import_block(&mut self, block: IncomingBlock) -> Result<ImportOutcome> {
+        let monitor = self.block_import_queue.monitor();
+
+        let block = monitor.time(ImportStage::Decode, || block.decode())?;
+
+        if let Block::Proposal { block } = &block {
+            self.record_round_milestone(block.round, RoundMilestone::ProposalSeen);
+        }
+
+        if let Block::Convergence { block } = &block {
+            if block.certificate.is_some() {
+                self.sync_progress.record_round(block.header.round);
+            }
+        }
+        let block_hash = match &block {
+            Block::Genesis { block } => block.hash.clone(),
+            Block::Proposal { block } => block.hash.clone(),
+            Block::Convergence { block } => block.hash.clone(),
+        };
+
+        monitor.time(ImportStage::HeaderCheck, || {
+            self.state_driver.verify_block_header(&block)?;
+            self.state_driver.verify_txn_limits(&block)
+        })?;
+
+        monitor.time(ImportStage::CertificateCheck, || {
+            self.verify_imported_certificate(&block)
+        })?;
+
+        let missing_parents = monitor.time(ImportStage::DagLinkCheck, || {
+            Ok(self.state_driver.dag.missing_parents(&block))
+        })?;
+
+        if let Some(parent) = missing_parents.into_iter().next() {
+            return Ok(self.block_import_queue.buffer(block, block_hash, parent));
+        }
+
+        #[cfg(feature = "consensus")]
+        let included_txns: Vec<TransactionDigest> = match &block {
+            Block::Convergence { block } => block.txn_id_set().into_iter().cloned().collect(),
+            _ => vec![],
+        };
+        let fill = match &block {
+            Block::Convergence { block } => Some(block_fill(block)),
+            _ => None,
+        };
+
+        monitor.time(ImportStage::Apply, || self.state_driver.append_block(block))?;
+
+        #[cfg(feature = "consensus")]
+        self.consensus_driver.record_included_txns(&included_txns);
+
+        if let Some(fill) = fill {
+            self.block_fills.record(fill);
+        }
+
+        Ok(ImportOutcome::Imported(block_hash))
+    }
+
+    /// Checks the certificate a convergence block arrived with, if any, was
+    /// issued for it by the harvester quorum and vouches for the state root
+    /// its header commits to
+    fn verify_imported_certificate(&self, block: &Block) -> Result<()> {
+        let Block::Convergence { block } = block else {
+            return Ok(());
+        };
+
+        let Some(certificate) = &block.certificate else {
+            return Ok(());
+        };
+
+        if certificate.block_hash != block.hash
+            || certificate.next_root_hash != block.header.state_root
+            || !self
+                .state_driver
+                .dag
+                .verify_certificate_signature(certificate)
+        {
+            return Err(NodeError::InvalidBlock {
+                hash: block.hash.clone(),
+                reason: "carries an invalid certificate".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
     fn handle_genesis_block_received(&mut self, block: GenesisBlock) -> Result<ApplyBlockResult> {
         self.has_required_node_type(NodeType::Validator, "store genesis block")?;
         self.belongs_to_correct_quorum(QuorumKind::Harvester, "store genesis block")?;
@@ -433,6 +1443,10 @@ impl NodeRuntime {
     }
 
     fn handle_proposal_block_received(&mut self, block: ProposalBlock) -> Result<ApplyBlockResult> {
+        self.state_driver.verify_txn_order(&Block::Proposal {
+            block: block.clone(),
+        })?;
+
         if let Err(e) = self.state_driver.dag.append_proposal(&block) {
             let err_note = format!("Failed to append proposal block to DAG: {e:?}");
             return Err(NodeError::Other(err_note));
@@ -448,6 +1462,12 @@ impl NodeRuntime {
         self.has_required_node_type(NodeType::Validator, "certify convergence block")?;
         self.belongs_to_correct_quorum(QuorumKind::Harvester, "certify convergence block")?;
 
+        self.state_driver.verify_block_weight(&block)?;
+        self.state_driver.verify_txn_order(&Block::Convergence {
+            block: block.clone(),
+        })?;
+        self.state_driver.verify_inclusion_lists(&block)?;
+
         self.state_driver
             .dag
             .append_convergence(&block)
@@ -457,21 +1477,92 @@ impl NodeRuntime {
                 ))
             })?;
 
-        if block.certificate.is_none() {
-            if let Some(header) = self.state_driver.dag.last_confirmed_block_header() {
-                self.consensus_driver
-                    .certify_convergence_block(block.clone(), header);
+        #[cfg(feature = "consensus")]
+        {
+            if block.certificate.is_none() {
+                if let Some(header) = self.state_driver.dag.last_confirmed_block_header() {
+                    match self.verify_miner_election(&block.header, &header) {
+                        Ok(()) => {
+                            self.consensus_driver
+                                .certify_convergence_block(block.clone(), header);
+                        },
+                        Err(err) => {
+                            telemetry::warn!("Refusing to certify block {}: {err}", block.hash)
+                        },
+                    }
+                }
             }
+
+            self.consensus_driver
+                .record_included_txns(block.txn_id_set());
         }
 
+        if let Err(err) = self.state_driver.record_txn_inclusions(&block) {
+            telemetry::warn!(
+                "Failed to record the txns included in block {}: {err}",
+                block.hash
+            );
+        }
+
+        let fill = block_fill(&block);
         let apply_result = self
             .state_driver
             .apply_block(Block::Convergence { block })?;
 
+        self.block_fills.record(fill);
+
         Ok(apply_result)
     }
 
     pub fn handle_block_certificate_created(&mut self, certificate: Certificate) -> Result<()> {
+        self.state_driver
+            .record_block_certificate(certificate.clone())?;
+
+        self.record_block_milestone(&certificate.block_hash, RoundMilestone::CertificateCreated);
+
+        match self
+            .state_driver
+            .take_checkpoint(&certificate, &self.config.checkpoint_config)
+        {
+            Ok(Some(checkpoint)) => {
+                telemetry::info!(
+                    "Took checkpoint of block {} at round {}",
+                    checkpoint.block_hash,
+                    checkpoint.round
+                );
+            },
+            Ok(None) => {},
+            Err(err) => {
+                telemetry::warn!(
+                    "Failed to take a checkpoint of block {}: {err}",
+                    certificate.block_hash
+                );
+            },
+        }
+
+        match self.state_driver.take_epoch_summary(&certificate) {
+            Ok(Some(summary)) => {
+                telemetry::info!(
+                    "Summarized epoch {} as of block {} at round {}",
+                    summary.epoch,
+                    summary.block_hash,
+                    summary.round
+                );
+            },
+            Ok(None) => {},
+            Err(err) => {
+                telemetry::warn!(
+                    "Failed to summarize the epoch of block {}: {err}",
+                    certificate.block_hash
+                );
+            },
+        }
+
+        #[cfg(feature = "consensus")]
+        self.consensus_driver
+            .handle_convergence_block_finalized(&certificate.block_hash);
+
+        // TODO: append the certificate to its block within the DAG
         //
         //         let mut mine_block: Option<ConvergenceBlock> = None;
         //         let block_hash = certificate.block_hash.clone();
@@ -507,9 +1598,235 @@ impl NodeRuntime {
         //             telemetry::debug!("Missing ConvergenceBlock for certificate: {certificate:?}");
         //         }
         //
-        todo!()
+        Ok(())
+    }
+
+    /// Queues the validators whose registration bond the last applied block
+    /// confirmed for activation
+    #[cfg(feature = "consensus")]
+    pub fn queue_bonded_registrations(&mut self) {
+        for bonded in self.state_driver.take_bonded_registrations() {
+            let node_id = bonded.registration.node_id.clone();
+
+            match self
+                .consensus_driver
+                .handle_validator_registration_bonded(bonded)
+            {
+                Ok(epoch) => {
+                    telemetry::info!("Validator {node_id} queued for activation at epoch {epoch}")
+                },
+                Err(err) => {
+                    telemetry::warn!("Rejected registration of validator {node_id}: {err}")
+                },
+            }
+        }
+    }
+
+    /// Drops the bonded registrations, as nodes built without the consensus
+    /// module keep no validator set to activate them in
+    #[cfg(not(feature = "consensus"))]
+    pub fn queue_bonded_registrations(&mut self) {
+        self.state_driver.take_bonded_registrations();
+    }
+
+    /// Sends the txns of a rolled back block back to the mempool, re-validated
+    /// against the current state. They were admitted once already, so how
+    /// long ago they were created no longer matters
+    pub fn handle_block_rolled_back(
+        &mut self,
+        block_hash: BlockHash,
+        txns: Vec<TransactionKind>,
+    ) -> Result<Vec<TxnStatusChange>> {
+        let validator = TxnValidator::with_validity_config(self.config.txn_validity_config.clone());
+
+        self.state_driver
+            .resurrect_txns(&validator, block_hash, txns)
+    }
+
+    /// Compacts the mempool while it's idle, provided it grew into enough room
+    /// it no longer needs. Returns what the compaction gave back
+    pub fn compact_mempool(&mut self) -> Option<MempoolCompaction> {
+        let config = &self.config.mempool_compaction_config;
+        let mempool = &mut self.state_driver.mempool;
+
+        // NOTE: checking the room first spares copying the pool to count its txns
+        if mempool.reclaimable() < config.min_reclaimable_txns
+            || mempool.size() > config.max_pending_txns
+        {
+            return None;
+        }
+
+        Some(mempool.compact())
+    }
+
+    /// Scrubs the next batch of the persisted chain data, repairing the
+    /// corrupted records that can be. Records peers can hand a copy of are
+    /// requested from them until the scrubber finds them again on a later
+    /// pass. Returns the records that can't be repaired
+    pub fn scrub_chain_data(&mut self) -> Result<Vec<CorruptRecord>> {
+        let batch_size = self.config.chain_scrubber_config.batch_size;
+        let lookback = self.config.certificate_sync_config.lookback;
+
+        let report = self.chain_scrubber.scrub(batch_size)?;
+        let passes = self.chain_scrubber.passes();
+
+        let mut unrecoverable = Vec::new();
+        for record in report.corrupt {
+            if self.state_driver.repair_corrupt_record(&record, lookback)? {
+                telemetry::warn!(
+                    "Discarded corrupted {} record {}: {}",
+                    record.store,
+                    hex::encode(&record.key),
+                    record.reason
+                );
+                continue;
+            }
+
+            if !record.is_recoverable_from_peers() {
+                unrecoverable.push(record);
+                continue;
+            }
+
+            let key = (record.store, record.key.clone());
+            match self.chain_records_requested.get(&key) {
+                // NOTE: peers had a whole pass to hand over a copy
+                Some((_, found_at)) if *found_at < passes => {
+                    self.chain_records_requested.remove(&key);
+                    unrecoverable.push(record);
+                },
+                Some(_) => {},
+                None => {
+                    self.chain_records_requested.insert(key, (record, passes));
+                },
+            }
+        }
+
+        Ok(unrecoverable)
     }
 
+    /// Returns the hashes of recent blocks within the DAG that no longer
+    /// hash to the hash they carry
+    pub fn corrupt_dag_blocks(&self) -> Vec<BlockHash> {
+        self.state_driver
+            .corrupt_dag_blocks(self.config.certificate_sync_config.lookback)
+    }
+
+    /// Returns the keys of corrupted records to request copies of from peers,
+    /// capped to a single request's worth
+    pub fn missing_chain_records(&self) -> Vec<ChainRecordKey> {
+        self.chain_records_requested
+            .keys()
+            .take(self.config.chain_scrubber_config.max_records_per_request)
+            .map(|(store, key)| ChainRecordKey {
+                store: store.to_string(),
+                key: key.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the records the node holds among those a peer asked for,
+    /// skipping records of stores peers aren't served
+    pub fn handle_chain_records_requested(&self, keys: Vec<ChainRecordKey>) -> Vec<ChainRecord> {
+        let max_records = self.config.chain_scrubber_config.max_records_per_request;
+
+        keys.into_iter()
+            .take(max_records)
+            .filter_map(|ChainRecordKey { store, key }| {
+                let value = self
+                    .state_driver
+                    .get_chain_record(ScrubbedStore::from_name(&store)?, &key)
+                    .ok()??;
+
+                Some(ChainRecord { store, key, value })
+            })
+            .collect()
+    }
+
+    /// Replaces corrupted records with the copies a peer sent, discarding
+    /// copies of records that weren't requested or don't hash to what the
+    /// records were committed to. Returns how many were replaced
+    pub fn handle_chain_records_received(&mut self, records: Vec<ChainRecord>) -> Result<usize> {
+        let mut restored = 0;
+
+        for ChainRecord { store, key, value } in records {
+            let Some(store) = ScrubbedStore::from_name(&store) else {
+                continue;
+            };
+
+            let Some((record, _)) = self.chain_records_requested.get(&(store, key.clone())) else {
+                continue;
+            };
+
+            if !self.state_driver.restore_corrupt_record(record, &value)? {
+                telemetry::warn!(
+                    "Discarding invalid copy of corrupted {store} record {}",
+                    hex::encode(&key)
+                );
+                continue;
+            }
+
+            self.chain_records_requested.remove(&(store, key));
+            restored += 1;
+        }
+
+        Ok(restored)
+    }
+
+    /// Returns the hashes of recent convergence blocks the node missed the
+    /// certificate of, capped to a single request's worth
+    pub fn missing_block_certificates(&self) -> Vec<BlockHash> {
+        let certificate_sync_config = &self.config.certificate_sync_config;
+
+        let mut block_hashes = self
+            .state_driver
+            .uncertified_convergence_blocks(certificate_sync_config.lookback);
+
+        block_hashes.truncate(certificate_sync_config.max_blocks_per_request);
+        block_hashes
+    }
+
+    /// Returns the certificates the node holds for the blocks a peer asked
+    /// for
+    pub fn handle_certificates_requested(&self, block_hashes: Vec<BlockHash>) -> Vec<Certificate> {
+        let max_blocks = self.config.certificate_sync_config.max_blocks_per_request;
+        let block_hashes: Vec<BlockHash> = block_hashes.into_iter().take(max_blocks).collect();
+
+        self.state_driver.get_block_certificates(&block_hashes)
+    }
+
+    /// Applies the certificates a peer sent for blocks the node hasn't
+    /// certified yet, discarding those that don't verify. Returns how many
+    /// were applied
+    pub fn handle_certificates_received(
+        &mut self,
+        certificates: Vec<Certificate>,
+    ) -> Result<usize> {
+        let mut applied = 0;
+
+        for certificate in certificates {
+            if self
+                .state_driver
+                .is_block_certified(&certificate.block_hash)
+            {
+                continue;
+            }
+
+            if !self.state_driver.verify_block_certificate(&certificate) {
+                telemetry::warn!(
+                    "Discarding invalid certificate for block {}",
+                    certificate.block_hash
+                );
+                continue;
+            }
+
+            self.handle_block_certificate_created(certificate)?;
+            applied += 1;
+        }
+
+        Ok(applied)
+    }
+
+    #[cfg(feature = "consensus")]
     pub async fn handle_node_added_to_peer_list(
         &mut self,
         peer_data: PeerData,
@@ -519,6 +1836,14 @@ impl NodeRuntime {
             .await
     }
 
+    #[cfg(not(feature = "consensus"))]
+    pub async fn handle_node_added_to_peer_list(
+        &mut self,
+        _peer_data: PeerData,
+    ) -> Result<Option<HashMap<NodeId, AssignedQuorumMembership>>> {
+        Err(OptionalModule::Consensus.unavailable("assign quorum memberships"))
+    }
+
     pub fn handle_proposal_block_mine_request_created(
         &mut self,
         ref_hash: RefHash,
@@ -526,6 +1851,7 @@ impl NodeRuntime {
         epoch: Epoch,
         claim: Claim,
     ) -> Result<ProposalBlock> {
+        OptionalModule::Mining.require("create proposal block")?;
         self.has_required_node_type(NodeType::Validator, "create proposal block")?;
         self.belongs_to_correct_quorum(QuorumKind::Harvester, "create proposal block")?;
 
@@ -542,6 +1868,7 @@ impl NodeRuntime {
         todo!()
     }
 
+    #[cfg(feature = "consensus")]
     pub fn handle_part_commitment_created(
         &mut self,
         sender_id: SenderId,
@@ -551,6 +1878,7 @@ impl NodeRuntime {
             .handle_part_commitment_created(sender_id, part)
     }
 
+    #[cfg(feature = "consensus")]
     pub fn handle_part_commitment_acknowledged(
         &mut self,
         receiver_id: ReceiverId,
@@ -560,10 +1888,13 @@ impl NodeRuntime {
         self.consensus_driver
             .handle_part_commitment_acknowledged(receiver_id, sender_id, ack)
     }
+
+    #[cfg(feature = "consensus")]
     pub fn handle_all_ack_messages(&mut self) -> Result<()> {
         self.consensus_driver.handle_all_ack_messages()
     }
 
+    #[cfg(feature = "consensus")]
     pub fn handle_quorum_membership_assigment_created(
         &mut self,
         assigned_membership: AssignedQuorumMembership,
@@ -571,6 +1902,16 @@ impl NodeRuntime {
         self.consensus_driver
             .handle_quorum_membership_assigment_created(assigned_membership)
     }
+
+    #[cfg(not(feature = "consensus"))]
+    pub fn handle_quorum_membership_assigment_created(
+        &mut self,
+        _assigned_membership: AssignedQuorumMembership,
+    ) -> Result<()> {
+        Err(OptionalModule::Consensus.unavailable("join a quorum"))
+    }
+
+    #[cfg(feature = "consensus")]
     pub fn handle_convergence_block_precheck_requested(
         &mut self,
         block: ConvergenceBlock,
@@ -580,3 +1921,12 @@ impl NodeRuntime {
             .precheck_convergence_block(block, last_confirmed_block_header);
     }
 }
+
+/// Returns how many txns `block` included, and how much they weighed
+fn block_fill(block: &ConvergenceBlock) -> BlockFill {
+    BlockFill {
+        round: block.header.round,
+        txns: block.txn_id_set().len(),
+        weight: block.header.block_weight,
+    }
+}
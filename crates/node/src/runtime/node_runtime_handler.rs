@@ -1,15 +1,23 @@
 use std::collections::{BTreeMap, HashSet};
 
 use async_trait::async_trait;
+#[cfg(feature = "consensus")]
 use dkg_engine::dkg::DkgGenerator;
-use events::{Event, EventMessage, EventPublisher, EventSubscriber, Vote};
-use primitives::{NodeId, NodeType, ValidatorPublicKey};
-use telemetry::info;
+use events::{BlockRangeChunk, Event, EventMessage, EventPublisher, EventSubscriber, Vote};
+use primitives::{NodeId, NodeType, TxnValidationStatus, ValidatorPublicKey};
+use storage::vrrbdb::{CertifiedTxnKey, RoundMilestone, TxnCertificationRecord};
+use telemetry::{error, info};
 use theater::{Actor, ActorId, ActorImpl, ActorLabel, ActorState, Handler, TheaterError};
+use validator::txn_validator::TxnValidator;
 use vrrb_config::{QuorumMember, QuorumMembershipConfig};
-use vrrb_core::serde_helpers::decode_from_binary_byte_slice;
+use vrrb_core::{serde_helpers::decode_from_binary_byte_slice, transactions::TransactionKind};
 
-use crate::{consensus::ConsensusModule, node_runtime::NodeRuntime, state_reader::StateReader};
+#[cfg(feature = "consensus")]
+use crate::consensus::ConsensusModule;
+use crate::{
+    block_import::IncomingBlock, node_runtime::NodeRuntime, state_reader::StateReader, NodeError,
+    OptionalModule, JSON_RPC_API_CONTROL_TOPIC,
+};
 
 #[async_trait]
 impl Handler<EventMessage> for NodeRuntime {
@@ -38,7 +46,40 @@ impl Handler<EventMessage> for NodeRuntime {
     }
 
     async fn handle(&mut self, event: EventMessage) -> theater::Result<ActorState> {
-        match event.into() {
+        let event: Event = event.into();
+
+        if self.is_in_maintenance_mode() && NodeRuntime::is_participation_event(&event) {
+            telemetry::debug!("{} is in maintenance mode, skipping event", self.label());
+            return Ok(ActorState::Running);
+        }
+
+        if self.is_halted() && NodeRuntime::is_participation_event(&event) {
+            telemetry::debug!("{} is halted by its quorum, skipping event", self.label());
+            return Ok(ActorState::Running);
+        }
+
+        if let Some(module) = OptionalModule::handling(&event) {
+            if !module.is_enabled() {
+                telemetry::debug!(
+                    "{} was built without the {module} module, skipping event",
+                    self.label()
+                );
+                return Ok(ActorState::Running);
+            }
+        }
+
+        #[cfg(feature = "consensus")]
+        let advances_dkg = matches!(
+            event,
+            Event::QuorumMembershipAssigmentCreated(_)
+                | Event::PartCommitmentCreated(..)
+                | Event::PartCommitmentAcknowledged { .. }
+        );
+
+        let relayed = matches!(event, Event::RelayedTxnReceived(_));
+
+        match event {
+            #[cfg(feature = "consensus")]
             Event::NodeAddedToPeerList(peer_data) => {
                 let assigments = self
                     .handle_node_added_to_peer_list(peer_data.clone())
@@ -56,8 +97,23 @@ impl Handler<EventMessage> for NodeRuntime {
                     }
                 }
             },
+            #[cfg(feature = "consensus")]
             Event::QuorumMembershipAssigmentCreated(assigned_membership) => {
-                self.handle_quorum_membership_assigment_created(assigned_membership.clone());
+                self.handle_quorum_membership_assigment_created(assigned_membership.clone())
+                    .map_err(|err| {
+                        telemetry::error!("{}", err);
+                        TheaterError::Other(err.to_string())
+                    })?;
+
+                if let Some(membership_config) = self.quorum_membership() {
+                    let event = Event::QuorumMembershipConfigured(membership_config);
+                    let em = EventMessage::new(Some("network-events".into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
 
                 let (part, node_id) =
                     self.generate_partial_commitment_message().map_err(|err| {
@@ -75,6 +131,7 @@ impl Handler<EventMessage> for NodeRuntime {
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             },
 
+            #[cfg(feature = "consensus")]
             Event::PartCommitmentCreated(node_id, part) => {
                 let (receiver_id, sender_id, ack) = self
                     .handle_part_commitment_created(node_id.clone(), part)
@@ -94,21 +151,221 @@ impl Handler<EventMessage> for NodeRuntime {
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             },
 
+            #[cfg(feature = "consensus")]
             Event::PartCommitmentAcknowledged {
                 node_id,
                 sender_id,
                 ack,
             } => {
+                // NOTE: the DKG engine keys acks by the node that acknowledged the
+                // commitment first, then by the commitment's owner
                 self.consensus_driver
-                    .handle_part_commitment_acknowledged(node_id, sender_id, ack)?;
+                    .handle_part_commitment_acknowledged(sender_id, node_id, ack)?;
             },
 
+            #[cfg(feature = "consensus")]
             Event::QuorumElectionStarted(header) => {
-                self.consensus_driver.handle_quorum_election_started(header);
+                if let Some(diff) = self.consensus_driver.handle_quorum_election_started(header) {
+                    let event = Event::ValidatorSetUpdated(diff);
+                    let em = EventMessage::new(Some("network-events".into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+
+                match self.refresh_protocol_parameters() {
+                    Ok(changed) if changed.is_empty() => {},
+                    Ok(changed) => {
+                        for parameter in changed {
+                            info!(
+                                "Protocol parameter {parameter} is now {}",
+                                parameter.value_in(&self.config.chain_spec)
+                            );
+                        }
+                    },
+                    Err(err) => {
+                        telemetry::error!("Failed to refresh protocol parameters: {err}")
+                    },
+                }
+
+                // NOTE: looked up once per epoch so the node knows ahead of time
+                // which duties it has to get ready for
+                let epoch = self.consensus_driver.quorum_driver.current_epoch;
+                let duties = self.validator_duties(&self.config.id, epoch);
+                info!(
+                    "Duties for epoch {epoch}: quorum {:?}, {} expected mining slots, next mining slot {:?}",
+                    duties.quorum_kind, duties.expected_mining_slots, duties.next_mining_slot
+                );
+            },
+
+            #[cfg(feature = "consensus")]
+            Event::ValidatorSetUpdateReceived(diff) => {
+                self.consensus_driver
+                    .handle_validator_set_update_received(diff);
+            },
+
+            Event::MaintenanceModeRequested(paused) => {
+                if self.set_maintenance_mode(paused) {
+                    info!(
+                        "{} {} maintenance mode",
+                        self.label(),
+                        if paused { "entered" } else { "left" }
+                    );
+
+                    let event = Event::MaintenanceModeChanged {
+                        node_id: self.config.id.clone(),
+                        paused,
+                    };
+                    let em = EventMessage::new(Some("network-events".into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+
+            #[cfg(feature = "consensus")]
+            Event::PeerMaintenanceModeChanged { node_id, paused } => {
+                self.consensus_driver
+                    .handle_peer_maintenance_mode_changed(node_id, paused);
+            },
+
+            #[cfg(feature = "consensus")]
+            Event::ValidatorKeyRotationRequested => {
+                let rotation = self
+                    .rotate_validator_key()
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                info!("{} rotated its validator key", self.label());
+
+                let event = Event::ValidatorKeyRotated(rotation);
+                let em = EventMessage::new(Some("network-events".into()), event);
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            #[cfg(feature = "consensus")]
+            Event::PeerValidatorKeyRotated(rotation) => {
+                match self.handle_peer_validator_key_rotated(rotation.clone()) {
+                    // NOTE: the network module only refreshes its peer store once
+                    // the rotation was found fresh
+                    Ok(true) => {
+                        let event = Event::PeerValidatorKeyRotated(rotation);
+                        let em = EventMessage::new(Some("network-events".into()), event);
+
+                        self.events_tx
+                            .send(em)
+                            .await
+                            .map_err(|err| TheaterError::Other(err.to_string()))?;
+                    },
+                    Ok(false) => {},
+                    Err(err) => telemetry::warn!("Rejected validator key rotation: {err}"),
+                }
+            },
+
+            #[cfg(feature = "consensus")]
+            Event::HaltDirectiveRequested(directive) => {
+                let (node_idx, signature) = self
+                    .sign_halt_directive(&directive)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                info!(
+                    "{} signed directive to {} at round {}",
+                    self.label(),
+                    directive.action,
+                    directive.round
+                );
+
+                let node_id = self.config.id.clone();
+
+                // NOTE: the node's own share counts towards the certificate too
+                let share = Event::PeerHaltDirectiveSigned {
+                    node_id: node_id.clone(),
+                    node_idx,
+                    directive: directive.clone(),
+                    signature: signature.clone(),
+                };
+
+                let event = Event::HaltDirectiveSigned {
+                    node_id,
+                    node_idx,
+                    directive,
+                    signature,
+                };
+
+                for em in [
+                    EventMessage::new(Some("network-events".into()), event),
+                    EventMessage::new(Some("runtime-events".into()), share),
+                ] {
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+
+            #[cfg(feature = "consensus")]
+            Event::PeerHaltDirectiveSigned {
+                node_id,
+                node_idx,
+                directive,
+                signature,
+            } => {
+                let signed = match self.handle_halt_directive_signed(directive, node_idx, signature)
+                {
+                    Ok(Some(signed)) => signed,
+                    Ok(None) => return Ok(ActorState::Running),
+                    Err(err) => {
+                        telemetry::warn!("Rejected halt directive share of {node_id}: {err}");
+                        return Ok(ActorState::Running);
+                    },
+                };
+
+                let applied = self
+                    .apply_halt_directive(signed.clone())
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                if applied {
+                    info!(
+                        "{} certified directive to {} at round {}",
+                        self.label(),
+                        signed.directive.action,
+                        signed.directive.round
+                    );
+
+                    let event = Event::HaltDirectiveCertified(signed);
+                    let em = EventMessage::new(Some("network-events".into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+
+            Event::PeerHaltDirectiveCertified(signed) => {
+                let action = signed.directive.action;
+                let round = signed.directive.round;
+
+                match self.apply_halt_directive(signed) {
+                    Ok(true) => info!(
+                        "{} acted on directive to {action} at round {round}",
+                        self.label()
+                    ),
+                    Ok(false) => {},
+                    Err(err) => telemetry::warn!("Rejected directive to {action}: {err}"),
+                }
             },
 
+            #[cfg(feature = "consensus")]
             Event::MinerElectionStarted(header) => {
-                let claims = self.state_driver.read_handle().claim_store_values();
+                let claims = self.state_driver.read_handle();
 
                 let winner = self
                     .consensus_driver
@@ -125,6 +382,7 @@ impl Handler<EventMessage> for NodeRuntime {
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             },
 
+            #[cfg(feature = "consensus")]
             Event::TransactionCertificateRequested {
                 votes,
                 txn_id,
@@ -133,32 +391,105 @@ impl Handler<EventMessage> for NodeRuntime {
                 txn,
                 quorum_threshold,
             } => {
-                self.consensus_driver
-                    .handle_transaction_certificate_requested(
-                        votes,
-                        txn_id,
-                        quorum_key,
-                        farmer_id,
-                        txn,
-                        quorum_threshold,
-                    );
+                // NOTE: aggregation runs on the signing pool, which publishes
+                // TransactionCertificateCreated once done
+                if let Err(err) = self.request_transaction_certificate(
+                    votes,
+                    txn_id,
+                    quorum_key,
+                    farmer_id,
+                    txn,
+                    quorum_threshold,
+                ) {
+                    telemetry::error!("failed to request transaction certificate: {err}");
+                }
             },
 
             // This certifies txns once vote threshold is reached.
+            #[cfg(feature = "consensus")]
             Event::TransactionCertificateCreated {
                 votes,
                 signature,
                 digest,
+                quorum_key,
                 /// OUtput of the program executed
                 execution_result,
                 farmer_id,
                 txn,
                 is_valid,
             } => {
+                let key = CertifiedTxnKey::new(digest, quorum_key);
+
+                let newly_certified = self
+                    .state_driver
+                    .record_certified_txn(key.clone())
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                if !newly_certified {
+                    telemetry::warn!("Discarding duplicate certificate of txn {key}");
+                    return Ok(ActorState::Running);
+                }
+
+                if matches!(is_valid, TxnValidationStatus::Valid) {
+                    let round = self.get_round().unwrap_or_default();
+                    self.consensus_driver
+                        .record_certified_txn(key.digest.clone(), round);
+                }
+
+                let certification = TxnCertificationRecord {
+                    certified_at: chrono::Utc::now().timestamp(),
+                    quorum_public_key: key.quorum_public_key.clone(),
+                    farmer_id,
+                    validation_status: is_valid.clone(),
+                    votes: votes.len(),
+                    approvals: votes.iter().filter(|vote| vote.is_txn_valid).count(),
+                    quorum_threshold: votes.first().map_or(0, |vote| vote.quorum_threshold),
+                };
+
+                if let Err(err) = self
+                    .state_driver
+                    .record_txn_certification(key.digest.clone(), certification)
+                {
+                    telemetry::warn!("Failed to record certification of txn {key}: {err}");
+                }
+
                 // TODO: refactor process
+                // NOTE: validator registrations are only queued once a block bonds
+                // their stake, see the handling of Event::UpdateState
+                match (*txn, is_valid) {
+                    (TransactionKind::ExitValidator(exit), TxnValidationStatus::Valid) => {
+                        let node_id = exit.node_id.clone();
+
+                        let unbonding = self
+                            .state_driver
+                            .get_claim(&node_id)
+                            .and_then(|claim| {
+                                self.consensus_driver
+                                    .handle_validator_exit_certified(exit, &claim)
+                            })
+                            .and_then(|exit| {
+                                self.state_driver
+                                    .begin_claim_unbonding(&exit.node_id, exit.unlocks_at)
+                                    .map(|_| exit)
+                            });
+
+                        match unbonding {
+                            Ok(exit) => info!(
+                                "Validator {node_id} leaves at epoch {}, stake unlocks at epoch {}",
+                                exit.exit_epoch,
+                                exit.unlocks_at
+                            ),
+                            Err(err) => {
+                                telemetry::warn!("Rejected exit of validator {node_id}: {err}")
+                            },
+                        }
+                    },
+                    _ => {},
+                }
             },
 
             // Mines proposal block after every X seconds.
+            #[cfg(feature = "consensus")]
             Event::ProposalBlockMineRequestCreated {
                 ref_hash,
                 round,
@@ -170,23 +501,32 @@ impl Handler<EventMessage> for NodeRuntime {
             },
             // it sends a job to sign the convergence block using the signature
             // provider
+            #[cfg(feature = "consensus")]
             Event::ConvergenceBlockSignatureRequested(block) => {
-                //     TODO: merge with ConvergenceBlockPartialSignatureCreated so it all happens
-                //     within one function call
-                //     if let Some(sig_provider) = self.sig_provider.clone() {
-                //         let _ = self
-                //             .sync_jobs_sender
-                //             .send(Job::SignConvergenceBlock(sig_provider, block));
-                //     }
+                if let Err(err) = self.request_convergence_block_signature(block) {
+                    telemetry::error!("failed to request convergence block signature: {err}");
+                }
             },
 
             // Process the job result of signing convergence block and adds the
             // partial signature to the cache for certificate generation
+            #[cfg(feature = "consensus")]
             Event::ConvergenceBlockPartialSignatureCreated {
                 block_hash,
                 public_key_share,
                 partial_signature,
             } => {
+                self.state_driver
+                    .record_partial_signature(
+                        block_hash.clone(),
+                        self.config.id.clone(),
+                        public_key_share.to_bytes().to_vec(),
+                        partial_signature.clone(),
+                    )
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                self.record_signature_progress(&block_hash);
+
                 self.consensus_driver
                     .handle_convergence_block_partial_signature_created(
                         block_hash,
@@ -194,12 +534,51 @@ impl Handler<EventMessage> for NodeRuntime {
                         partial_signature,
                     );
             },
+            #[cfg(feature = "consensus")]
             Event::ConvergenceBlockPeerSignatureRequested {
                 node_id,
                 block_hash,
                 public_key_share,
                 partial_signature,
             } => {
+                // NOTE: shares are checked before they reach the certification
+                // store, so junk never takes part in an aggregation
+                match self.consensus_driver.verify_peer_signature_share(
+                    &node_id,
+                    &block_hash,
+                    &public_key_share,
+                    &partial_signature,
+                ) {
+                    Ok(()) => {},
+                    Err(NodeError::InvalidSignatureShare { node_id, reason }) => {
+                        let event = Event::InvalidSignatureShareReceived { node_id, reason };
+
+                        self.events_tx
+                            .send(EventMessage::new(Some("network-events".into()), event))
+                            .await
+                            .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                        return Ok(ActorState::Running);
+                    },
+                    Err(err) => {
+                        telemetry::warn!(
+                            "Dropping signature share of {node_id} for block {block_hash}: {err}"
+                        );
+                        return Ok(ActorState::Running);
+                    },
+                }
+
+                self.state_driver
+                    .record_partial_signature(
+                        block_hash.clone(),
+                        node_id.clone(),
+                        public_key_share.clone(),
+                        partial_signature.clone(),
+                    )
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                self.record_signature_progress(&block_hash);
+
                 self.consensus_driver
                     .handle_convergence_block_peer_signature_request(
                         node_id,
@@ -208,12 +587,14 @@ impl Handler<EventMessage> for NodeRuntime {
                         partial_signature,
                     );
             },
+            #[cfg(feature = "consensus")]
             Event::ConvergenceBlockPrecheckRequested {
                 convergence_block,
                 block_header,
             } => {
                 self.handle_convergence_block_precheck_requested(convergence_block, block_header);
             },
+            #[cfg(feature = "consensus")]
             Event::TxnsReadyForProcessing(txns) => {
                 // Receives a batch of transactions from mempool and sends
                 // them to scheduler to get it validated and voted
@@ -221,21 +602,34 @@ impl Handler<EventMessage> for NodeRuntime {
             },
 
             // Receive votes from scheduler
+            #[cfg(feature = "consensus")]
             Event::TxnsValidated {
                 votes,
                 quorum_threshold,
             } => {
                 for vote in votes.iter().flatten() {
+                    self.consensus_driver.record_inclusion_list(vote);
                     self.consensus_driver
                         .validate_vote(vote.clone(), quorum_threshold);
                 }
             },
 
-            Event::NewTxnCreated(txn) => {
-                let txn_hash = self
-                    .state_driver
-                    .handle_new_txn_created(txn)
-                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            Event::NewTxnCreated(txn) | Event::RelayedTxnReceived(txn) => {
+                let validator =
+                    TxnValidator::with_validity_config(self.config.txn_validity_config.clone());
+
+                if let Err(err) = validator.validate_timestamp(&txn) {
+                    error!("Refusing to admit txn {} to the mempool: {err}", txn.id());
+                    return Ok(ActorState::Running);
+                }
+
+                let admitted = if relayed {
+                    self.state_driver.handle_relayed_txn_received(txn)
+                } else {
+                    self.state_driver.handle_new_txn_created(txn)
+                };
+
+                let txn_hash = admitted.map_err(|err| TheaterError::Other(err.to_string()))?;
 
                 self.events_tx
                     .send(Event::TxnAddedToMempool(txn_hash.clone()).into())
@@ -258,23 +652,349 @@ impl Handler<EventMessage> for NodeRuntime {
                 //               }
                 todo!()
             },
-            Event::UpdateState(block_hash) => {
-                if let Err(err) = self.state_driver.update_state(block_hash) {
+            Event::UpdateState(block_hash) => match self.state_driver.update_state(block_hash) {
+                Ok(state_diff) => {
+                    self.record_round_milestone(state_diff.round, RoundMilestone::BlockApplied);
+
+                    self.queue_bonded_registrations();
+
+                    match self.apply_recorded_halt_directive() {
+                        Ok(true) => info!("{} acted on a directive a block recorded", self.label()),
+                        Ok(false) => {},
+                        Err(err) => telemetry::warn!("Rejected recorded halt directive: {err}"),
+                    }
+
+                    // NOTE: the JSON-RPC server notifies the clients watching the balances
+                    // that changed, and ships every diff to the read replicas following
+                    // this node
+                    let event = Event::StateDiffApplied(state_diff);
+                    let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                },
+                Err(err @ NodeError::LedgerInvariantsViolated { .. }) => {
+                    telemetry::error!("CRITICAL: {err}, halting");
+
+                    // NOTE: the node stops mining and voting on top of a ledger it can't
+                    // trust, but keeps serving reads so it can be inspected
+                    if self.set_maintenance_mode(true) {
+                        let event = Event::MaintenanceModeChanged {
+                            node_id: self.config.id.clone(),
+                            paused: true,
+                        };
+                        let em = EventMessage::new(Some("network-events".into()), event);
+
+                        self.events_tx
+                            .send(em)
+                            .await
+                            .map_err(|err| TheaterError::Other(err.to_string()))?;
+                    }
+                },
+                Err(err) => {
                     telemetry::error!("error updating state: {}", err);
+                },
+            },
+            Event::BlockRolledBack { block_hash, txns } => {
+                let status_changes = self
+                    .handle_block_rolled_back(block_hash, txns)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                // NOTE: the JSON-RPC server notifies the clients watching these txns
+                for status_change in status_changes {
+                    let event = Event::TxnStatusChanged(status_change);
+                    let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+            Event::ValidatorDutiesRequested { node_id, epoch } => {
+                let event = Event::ValidatorDutiesComputed(self.validator_duties(&node_id, epoch));
+                let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+            Event::BlocksByRoundRangeRequested {
+                request_id,
+                start,
+                end,
+                chunk_size,
+            } => {
+                let blocks = self.state_driver.get_blocks_by_round_range(start, end);
+
+                // NOTE: an empty range still gets a chunk, so the requester
+                // learns there is nothing left to wait on
+                let mut chunks: Vec<_> = blocks.chunks(chunk_size.max(1)).collect();
+                if chunks.is_empty() {
+                    chunks.push(&[]);
                 }
+
+                let count = chunks.len();
+                for (index, chunk) in chunks.into_iter().enumerate() {
+                    let event = Event::BlockRangeChunkCreated(BlockRangeChunk {
+                        request_id,
+                        index,
+                        last: index + 1 == count,
+                        blocks: chunk.to_vec(),
+                    });
+                    let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+            Event::PendingBlockPreviewRequested { request_id } => {
+                let event = Event::PendingBlockPreviewCreated {
+                    request_id,
+                    preview: self.pending_block_preview(),
+                };
+                let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+            Event::SyncStatusRequested => {
+                let event = Event::SyncStatusReported(self.sync_status());
+                let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+            Event::DkgStatusRequested => {
+                let event = Event::DkgStatusReported(self.dkg_status());
+                let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+            Event::MempoolStatsRequested { fee } => {
+                let event = Event::MempoolStatsReported(self.mempool_stats(fee));
+                let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+            Event::BacklogSnapshotRequested => {
+                let event = Event::BacklogSnapshotCreated(self.backlog_snapshot());
+                let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
             },
             Event::ClaimCreated(claim) => {},
             Event::ClaimReceived(claim) => {
                 info!("Storing claim from: {}", claim.address);
             },
             Event::BlockReceived(block) => {
-                self.state_driver
-                    .handle_block_received(block)
-                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+                self.enqueue_block(IncomingBlock::Decoded(block));
+                self.process_block_imports();
             },
             Event::BlockCertificateCreated(certificate) => {
                 self.handle_block_certificate_created(certificate)
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                self.prepare_next_elections();
+
+                // NOTE: the next round's miner is known as soon as this round's block is
+                // confirmed, so its peers are connected to while the miner is still mining
+                if let Some((round, peers)) = self.next_round_peers() {
+                    let event = Event::RoundPeersScheduled { round, peers };
+                    let em = EventMessage::new(Some("network-events".into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+
+            Event::CertificateReconciliationDue => {
+                let block_hashes = self.missing_block_certificates();
+
+                if !block_hashes.is_empty() {
+                    let event = Event::MissingCertificatesRequested(block_hashes);
+                    let em = EventMessage::new(Some("network-events".into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+
+            Event::MempoolCompactionDue => {
+                if let Some(compaction) = self.compact_mempool() {
+                    info!(
+                        "{} compacted its mempool from room for {} txns to {}, reclaiming {} bytes",
+                        self.label(),
+                        compaction.capacity_before,
+                        compaction.capacity_after,
+                        compaction.reclaimed_bytes
+                    );
+                }
+            },
+
+            #[cfg(feature = "consensus")]
+            Event::MinerElectionRetryDue => {
+                let claims = self.state_driver.read_handle();
+
+                let Some(retry) = self.consensus_driver.retry_stalled_miner_election(claims) else {
+                    return Ok(ActorState::Running);
+                };
+
+                let winner = retry.map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                let event = Event::MinerElected(winner);
+
+                let em = EventMessage::new(Some("network-events".into()), event);
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::ChainScrubDue => {
+                // NOTE: records a failed batch left unrepaired are found again later on
+                let unrecoverable = self.scrub_chain_data().unwrap_or_else(|err| {
+                    error!("{} failed to scrub chain data: {err}", self.label());
+                    Vec::new()
+                });
+
+                let mut keys_by_store: BTreeMap<String, Vec<String>> = BTreeMap::new();
+                for record in unrecoverable {
+                    error!(
+                        "{} found an unrecoverable corrupted {} record {}: {}",
+                        self.label(),
+                        record.store,
+                        hex::encode(&record.key),
+                        record.reason
+                    );
+
+                    keys_by_store
+                        .entry(record.store.to_string())
+                        .or_default()
+                        .push(hex::encode(&record.key));
+                }
+
+                // NOTE: blocks are only held within the DAG, so corrupted ones are
+                // reported by hash rather than repaired
+                for block_hash in self.corrupt_dag_blocks() {
+                    error!(
+                        "{} found block {block_hash} no longer hashes to its hash",
+                        self.label()
+                    );
+
+                    keys_by_store
+                        .entry("dag".to_string())
+                        .or_default()
+                        .push(block_hash);
+                }
+
+                for (store, keys) in keys_by_store {
+                    let event = Event::ChainDataCorrupted { store, keys };
+                    let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+
+                let keys = self.missing_chain_records();
+
+                if !keys.is_empty() {
+                    let event = Event::MissingChainRecordsRequested(keys);
+                    let em = EventMessage::new(Some("network-events".into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+
+            Event::ChainRecordsRequested { sender_id, keys } => {
+                let records = self.handle_chain_records_requested(keys);
+
+                if !records.is_empty() {
+                    let event = Event::ChainRecordsProvided {
+                        requester: sender_id,
+                        records,
+                    };
+                    let em = EventMessage::new(Some("network-events".into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+
+            Event::ChainRecordsReceived(records) => {
+                let restored = self
+                    .handle_chain_records_received(records)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                if restored > 0 {
+                    info!(
+                        "{} restored {restored} corrupted chain records from a peer",
+                        self.label()
+                    );
+                }
+            },
+
+            Event::CertificatesRequested {
+                sender_id,
+                block_hashes,
+            } => {
+                let certificates = self.handle_certificates_requested(block_hashes);
+
+                if !certificates.is_empty() {
+                    let event = Event::CertificatesProvided {
+                        requester: sender_id,
+                        certificates,
+                    };
+                    let em = EventMessage::new(Some("network-events".into()), event);
+
+                    self.events_tx
+                        .send(em)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+
+            Event::CertificatesReceived(certificates) => {
+                let applied = self
+                    .handle_certificates_received(certificates)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                if applied > 0 {
+                    info!(
+                        "{} recovered {applied} missed block certificates",
+                        self.label()
+                    );
+                }
             },
             Event::HarvesterPublicKeyReceived(public_key_set) => self
                 .state_driver
@@ -330,6 +1050,39 @@ impl Handler<EventMessage> for NodeRuntime {
             _ => {},
         }
 
+        // NOTE: rounds are abandoned while handling certificates and convergence blocks,
+        // report them to operators once the event is handled
+        #[cfg(feature = "consensus")]
+        for abandoned_round in self.consensus_driver.drain_abandoned_rounds() {
+            let event = Event::RoundAbandoned(abandoned_round);
+            let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+            self.events_tx
+                .send(em)
+                .await
+                .map_err(|err| TheaterError::Other(err.to_string()))?;
+        }
+
+        #[cfg(feature = "consensus")]
+        if advances_dkg {
+            if let Some(status) = self.consensus_driver.observe_dkg_progress() {
+                info!(
+                    "{} DKG for epoch {} is now {:?}",
+                    self.label(),
+                    status.epoch,
+                    status.phase
+                );
+
+                let event = Event::DkgPhaseChanged(status);
+                let em = EventMessage::new(Some(JSON_RPC_API_CONTROL_TOPIC.into()), event);
+
+                self.events_tx
+                    .send(em)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            }
+        }
+
         Ok(ActorState::Running)
     }
 }
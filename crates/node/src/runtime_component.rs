@@ -1,8 +1,15 @@
 use std::{collections::HashMap, thread};
 
+use events::{MailboxHandle, MailboxMonitor, MailboxStats};
 use tokio::task::JoinHandle;
+use vrrb_rpc::rpc::{PublicRpcMonitor, RpcCacheMonitor};
 
-use crate::Result;
+use crate::{
+    block_import::{BlockImportMonitor, BlockImportStats},
+    network::{PreconnectMonitor, RebroadcastMonitor},
+    replication::ReplicationMonitor,
+    Result,
+};
 
 #[derive(Debug, Clone)]
 pub struct RuntimeComponentHealthReport {}
@@ -66,6 +73,13 @@ impl<D: Sized + Clone> RuntimeComponentHandle<D> {
 #[derive(Debug, Default)]
 pub struct RuntimeComponentManager {
     components: HashMap<RuntimeComponentLabel, RuntimeHandle>,
+    mailboxes: Vec<MailboxHandle>,
+    block_import_monitor: BlockImportMonitor,
+    rebroadcast_monitor: RebroadcastMonitor,
+    preconnect_monitor: PreconnectMonitor,
+    public_rpc_monitor: PublicRpcMonitor,
+    rpc_cache_monitor: RpcCacheMonitor,
+    replication_monitor: ReplicationMonitor,
 }
 
 impl RuntimeComponentManager {
@@ -78,12 +92,95 @@ impl RuntimeComponentManager {
         self.components.insert(label, handle);
     }
 
+    /// Registers the mailbox feeding one of the manager's components.
+    pub fn register_mailbox(&mut self, handle: MailboxHandle) {
+        self.mailboxes.push(handle);
+    }
+
+    /// Registers the monitor of the queue received blocks are imported through.
+    pub fn register_block_import_monitor(&mut self, monitor: BlockImportMonitor) {
+        self.block_import_monitor = monitor;
+    }
+
+    pub fn block_import_monitor(&self) -> BlockImportMonitor {
+        self.block_import_monitor.clone()
+    }
+
+    /// Registers the monitor of the re-broadcast of unacknowledged consensus
+    /// messages.
+    pub fn register_rebroadcast_monitor(&mut self, monitor: RebroadcastMonitor) {
+        self.rebroadcast_monitor = monitor;
+    }
+
+    pub fn rebroadcast_monitor(&self) -> RebroadcastMonitor {
+        self.rebroadcast_monitor.clone()
+    }
+
+    /// Registers the monitor of the connections made to next round's peers
+    /// ahead of time.
+    pub fn register_preconnect_monitor(&mut self, monitor: PreconnectMonitor) {
+        self.preconnect_monitor = monitor;
+    }
+
+    pub fn preconnect_monitor(&self) -> PreconnectMonitor {
+        self.preconnect_monitor.clone()
+    }
+
+    /// Registers the monitor of the JSON-RPC server's public mode.
+    pub fn register_public_rpc_monitor(&mut self, monitor: PublicRpcMonitor) {
+        self.public_rpc_monitor = monitor;
+    }
+
+    pub fn public_rpc_monitor(&self) -> PublicRpcMonitor {
+        self.public_rpc_monitor.clone()
+    }
+
+    /// Registers the monitor of the cache the JSON-RPC server serves hot txns
+    /// and blocks from.
+    pub fn register_rpc_cache_monitor(&mut self, monitor: RpcCacheMonitor) {
+        self.rpc_cache_monitor = monitor;
+    }
+
+    pub fn rpc_cache_monitor(&self) -> RpcCacheMonitor {
+        self.rpc_cache_monitor.clone()
+    }
+
+    /// Registers the monitor of the replication stream read replicas follow
+    /// their leader through.
+    pub fn register_replication_monitor(&mut self, monitor: ReplicationMonitor) {
+        self.replication_monitor = monitor;
+    }
+
+    pub fn replication_monitor(&self) -> ReplicationMonitor {
+        self.replication_monitor.clone()
+    }
+
+    pub fn mailbox_monitors(&self) -> Vec<MailboxMonitor> {
+        self.mailboxes
+            .iter()
+            .map(|mailbox| mailbox.monitor())
+            .collect()
+    }
+
+    pub fn mailbox_stats(&self) -> Vec<MailboxStats> {
+        self.mailboxes
+            .iter()
+            .map(|mailbox| mailbox.stats())
+            .collect()
+    }
+
     pub async fn stop(self) -> crate::Result<()> {
         for (label, handle) in self.components {
             handle.await??;
             telemetry::info!("Shutdown complete for {label}");
         }
 
+        for mailbox in self.mailboxes {
+            let label = mailbox.label();
+            mailbox.join().await?;
+            telemetry::info!("Drained {label} mailbox");
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,37 @@
+use events::ArchivedBlock;
+use primitives::Round;
+
+use super::StateManager;
+
+impl StateManager {
+    /// Returns the convergence blocks of rounds `start..=end` within the DAG,
+    /// oldest first, along with the proposal blocks they reference and their
+    /// certificates, so indexers can backfill a range without walking it one
+    /// block hash at a time.
+    pub fn get_blocks_by_round_range(&self, start: Round, end: Round) -> Vec<ArchivedBlock> {
+        let read_handle = self.read_handle();
+
+        self.dag
+            .convergence_blocks_by_round_range(start, end)
+            .into_iter()
+            .map(|convergence| {
+                let proposals = self.dag.proposal_blocks(&convergence.header.ref_hashes);
+
+                // NOTE: certificates are recorded in the certification store
+                // rather than appended to the blocks within the DAG
+                let certificate = convergence.certificate.clone().or_else(|| {
+                    read_handle
+                        .get_block_certification(&convergence.hash)
+                        .ok()
+                        .and_then(|certification| certification.certificate)
+                });
+
+                ArchivedBlock {
+                    convergence,
+                    proposals,
+                    certificate,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,52 @@
+use block::{Certificate, Checkpoint};
+use primitives::QuorumKind;
+use vrrb_config::CheckpointConfig;
+
+use crate::{NodeError, Result};
+
+use super::StateManager;
+
+impl StateManager {
+    /// Takes a checkpoint of the convergence block `certificate` certifies,
+    /// if one is due at the block's round, and persists it. Returns the
+    /// checkpoint taken, if any.
+    pub fn take_checkpoint(
+        &mut self,
+        certificate: &Certificate,
+        config: &CheckpointConfig,
+    ) -> Result<Option<Checkpoint>> {
+        let Some(block) = self.dag.convergence_block(&certificate.block_hash) else {
+            return Ok(None);
+        };
+
+        if !config.is_due(block.header.round) {
+            return Ok(None);
+        }
+
+        // NOTE: only the quorums the node belonged to are on record, so nodes
+        // that weren't harvesters when the block was certified can't vouch for
+        // the validator set that signed it
+        let Some(quorum) = self
+            .read_handle()
+            .get_quorum(block.header.epoch)
+            .ok()
+            .filter(|quorum| quorum.quorum_kind == QuorumKind::Harvester)
+        else {
+            return Ok(None);
+        };
+
+        let checkpoint = Checkpoint::new(
+            block.header,
+            certificate,
+            quorum.group_public_key,
+            &quorum.members,
+        );
+
+        signer::checkpoint::verify_checkpoint(&checkpoint, &quorum.group_public_key)
+            .map_err(|err| NodeError::Other(format!("checkpoint doesn't verify: {err}")))?;
+
+        self.database.record_checkpoint(&checkpoint)?;
+
+        Ok(Some(checkpoint))
+    }
+}
@@ -1,9 +1,12 @@
-use std::sync::{Arc, RwLock, RwLockReadGuard};
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{Arc, RwLock, RwLockReadGuard},
+};
 
 use block::{
     header::BlockHeader,
     valid::{BlockValidationData, Valid},
-    Block, ConvergenceBlock, GenesisBlock, InnerBlock, ProposalBlock,
+    Block, Certificate, ConvergenceBlock, GenesisBlock, InnerBlock, ProposalBlock,
 };
 use bulldag::{
     graph::{BullDag, GraphError},
@@ -11,7 +14,7 @@ use bulldag::{
 };
 use events::EventPublisher;
 use hbbft::crypto::{PublicKeySet, Signature, SignatureShare, SIG_SIZE};
-use primitives::SignatureType;
+use primitives::{QuorumPubkey, Round, SignatureType};
 use signer::types::{SignerError, SignerResult};
 use theater::{ActorId, ActorState};
 use vrrb_core::claim::Claim;
@@ -80,10 +83,168 @@ impl DagModule {
         self.last_confirmed_block_header.clone()
     }
 
+    #[cfg(feature = "time-travel")]
+    pub(crate) fn set_last_confirmed_block_header(&mut self, header: BlockHeader) {
+        self.last_confirmed_block_header = Some(header);
+    }
+
     pub fn set_harvester_pubkeys(&mut self, public_key_set: PublicKeySet) {
         self.public_key_set = Some(public_key_set);
     }
 
+    /// Returns the threshold public key of the harvester quorum, once it is
+    /// known
+    pub fn harvester_quorum_pubkey(&self) -> Option<QuorumPubkey> {
+        self.public_key_set
+            .as_ref()
+            .map(|public_key_set| QuorumPubkey::from(public_key_set.public_key()))
+    }
+
+    /// Walks the DAG back from its leaves and returns up to `limit` of the
+    /// most recent convergence blocks, newest first
+    pub fn recent_convergence_blocks(&self, limit: usize) -> Vec<ConvergenceBlock> {
+        let mut blocks = Vec::new();
+
+        let Ok(guard) = self.dag.read() else {
+            return blocks;
+        };
+
+        let mut queue: VecDeque<String> = guard.get_leaves().into_iter().collect();
+        let mut visited = HashSet::new();
+
+        while let Some(idx) = queue.pop_front() {
+            if blocks.len() >= limit {
+                break;
+            }
+
+            if !visited.insert(idx.clone()) {
+                continue;
+            }
+
+            let Some(vertex) = guard.get_vertex(idx) else {
+                continue;
+            };
+
+            if let Block::Convergence { block } = vertex.get_data() {
+                blocks.push(block);
+            }
+
+            queue.extend(vertex.get_sources().iter().map(|source| source.to_string()));
+        }
+
+        blocks
+    }
+
+    /// Returns the convergence blocks within the DAG whose round falls within
+    /// `start..=end`, oldest first
+    pub fn convergence_blocks_by_round_range(
+        &self,
+        start: Round,
+        end: Round,
+    ) -> Vec<ConvergenceBlock> {
+        let mut blocks = Vec::new();
+
+        let Ok(guard) = self.dag.read() else {
+            return blocks;
+        };
+
+        let mut queue: VecDeque<String> = guard.get_leaves().into_iter().collect();
+        let mut visited = HashSet::new();
+
+        while let Some(idx) = queue.pop_front() {
+            if !visited.insert(idx.clone()) {
+                continue;
+            }
+
+            let Some(vertex) = guard.get_vertex(idx) else {
+                continue;
+            };
+
+            if let Block::Convergence { block } = vertex.get_data() {
+                if (start..=end).contains(&block.header.round) {
+                    blocks.push(block);
+                }
+            }
+
+            queue.extend(vertex.get_sources().iter().map(|source| source.to_string()));
+        }
+
+        // NOTE: competing convergence blocks of a round are ordered by hash so
+        // every call lists them the same way
+        blocks.sort_by(|a, b| (a.header.round, &a.hash).cmp(&(b.header.round, &b.hash)));
+
+        blocks
+    }
+
+    /// Returns the proposal blocks within the DAG whose hashes are listed in
+    /// `ref_hashes`, skipping those that are missing from it
+    pub fn proposal_blocks(&self, ref_hashes: &[String]) -> Vec<ProposalBlock> {
+        ref_hashes
+            .iter()
+            .filter_map(|ref_hash| self.get_reference_block(ref_hash).ok())
+            .filter_map(|vertex| match vertex.get_data() {
+                Block::Proposal { block } => Some(block),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the convergence block with the given hash, if it is within the
+    /// DAG
+    pub fn convergence_block(&self, block_hash: &str) -> Option<ConvergenceBlock> {
+        match self.get_reference_block(block_hash).ok()?.get_data() {
+            Block::Convergence { block } => Some(block),
+            _ => None,
+        }
+    }
+
+    /// Checks that a certificate belongs to a convergence block within the
+    /// DAG and carries a valid threshold signature over its hash
+    pub fn verify_certificate(&self, certificate: &Certificate) -> bool {
+        let is_known_convergence_block = self
+            .get_reference_block(&certificate.block_hash)
+            .map(|vertex| matches!(vertex.get_data(), Block::Convergence { .. }))
+            .unwrap_or(false);
+
+        is_known_convergence_block && self.verify_certificate_signature(certificate)
+    }
+
+    /// Checks that a certificate carries a valid threshold signature over the
+    /// hash of the block it certifies, whether or not the block is within the
+    /// DAG
+    pub fn verify_certificate_signature(&self, certificate: &Certificate) -> bool {
+        let (Ok(payload_hash), Ok(signature)) = (
+            hex::decode(&certificate.block_hash),
+            hex::decode(&certificate.signature),
+        ) else {
+            return false;
+        };
+
+        let validation_data = BlockValidationData {
+            node_idx: None,
+            payload_hash,
+            signature,
+            signature_type: SignatureType::ThresholdSignature,
+        };
+
+        matches!(self.verify_signature(validation_data), Ok(true))
+    }
+
+    /// Returns the hashes of the blocks `block` links to that are missing
+    /// from the DAG
+    pub fn missing_parents(&self, block: &Block) -> Vec<String> {
+        let parents = match block {
+            Block::Genesis { .. } => vec![],
+            Block::Proposal { block } => vec![block.ref_block.clone()],
+            Block::Convergence { block } => block.header.ref_hashes.clone(),
+        };
+
+        parents
+            .into_iter()
+            .filter(|parent| self.get_reference_block(parent).is_err())
+            .collect()
+    }
+
     pub fn append_genesis(&mut self, genesis: &GenesisBlock) -> GraphResult<()> {
         let valid = self.check_valid_genesis(genesis);
 
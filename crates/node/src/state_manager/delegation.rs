@@ -0,0 +1,352 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use block::{header::BlockHeader, ProposalBlock};
+use primitives::{Address, NodeId};
+use reward::delegation::split_reward;
+use telemetry::info;
+use vrrb_core::{
+    account::{Account, AccountField},
+    claim::Claim,
+    transactions::{Delegation, DelegationAction, TransactionKind},
+};
+
+use super::StateManager;
+
+/// Changes delegations make to the state on top of the txns of a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct DelegationChanges {
+    /// Accounts delegations bonded stake from or paid out to
+    pub post_images: Vec<(Address, Option<Account>)>,
+
+    /// Claims whose delegations or rewards changed
+    pub claims: Vec<Claim>,
+
+    /// Tokens taken out of delegators' balances and bonded to claims
+    pub bonded: u128,
+
+    /// Tokens credited to accounts, rewards and unlocked stake alike
+    pub credited: u128,
+
+    /// The block's reward and the node whose claim it accrued to
+    pub block_reward: Option<(NodeId, u128)>,
+}
+
+/// Returns the delegations included by `proposals`, ordered by sender and
+/// nonce so every node applies them the same way
+pub(super) fn delegations_in(proposals: &[ProposalBlock]) -> Vec<Delegation> {
+    let mut delegations: Vec<Delegation> = proposals
+        .iter()
+        .flat_map(|block| block.txns.values())
+        .filter_map(|txn| match txn.txn() {
+            TransactionKind::Delegation(delegation) => Some(delegation),
+            _ => None,
+        })
+        .collect();
+
+    delegations.sort_by(|a, b| {
+        (&a.sender_address, a.nonce, &a.id).cmp(&(&b.sender_address, b.nonce, &b.id))
+    });
+    delegations.dedup_by(|a, b| a.id == b.id);
+
+    delegations
+}
+
+/// Returns the addresses of the accounts applying `delegations` to `claims`
+/// may touch: the delegators and, if `pays_out` is set, every account the
+/// payouts of `claims` may go to
+fn delegation_addresses(
+    claims: &HashMap<NodeId, Claim>,
+    delegations: &[Delegation],
+    pays_out: bool,
+) -> BTreeSet<Address> {
+    let mut addresses: BTreeSet<Address> = delegations
+        .iter()
+        .map(|delegation| delegation.sender_address.clone())
+        .collect();
+
+    if pays_out {
+        for claim in claims.values().filter(|claim| claim.has_pending_payouts()) {
+            addresses.insert(claim.address.clone());
+            addresses.extend(claim.delegations().keys().cloned());
+            addresses.extend(
+                claim
+                    .undelegations()
+                    .iter()
+                    .map(|undelegation| undelegation.delegator.clone()),
+            );
+        }
+    }
+
+    addresses
+}
+
+/// Credits `amount` to the account at `address`, if it exists. Returns
+/// whether it was credited
+fn credit(
+    accounts: &mut HashMap<Address, Account>,
+    post_images: &mut BTreeMap<Address, Option<Account>>,
+    address: &Address,
+    amount: u128,
+) -> bool {
+    let Some(account) = accounts.get_mut(address) else {
+        return false;
+    };
+
+    if account.update_field(AccountField::Credits(amount)).is_err() {
+        return false;
+    }
+
+    post_images.insert(address.clone(), Some(account.clone()));
+    true
+}
+
+impl StateManager {
+    /// Whether a block at `header` pays out the rewards validators earned
+    /// during the previous epoch, which the first block of every epoch does
+    pub(super) fn pays_out_rewards(&self, header: &BlockHeader) -> bool {
+        header.block_height > 0 && self.chain_spec.epoch_start(header.epoch) == header.block_height
+    }
+
+    /// Applies the `delegations` of the block at `header` to `claims` and
+    /// `accounts`, the state once the txns of the block are applied.
+    ///
+    /// If the block opens an epoch, every claim's unbonded stake is first
+    /// returned to its delegators, and the rewards it accrued over the
+    /// previous epoch are split between its validator and delegators. Then
+    /// delegated stake is taken out of the delegator's balance and bonded to
+    /// the validator's claim, and undelegated stake starts unbonding.
+    /// Finally, the block's reward accrues to the claim of its miner.
+    ///
+    /// Delegations that can't be applied, to an unknown or unbonding claim,
+    /// under the minimum stake or over the delegator's balance or bonded
+    /// stake, are skipped.
+    ///
+    /// `claims` must hold every claim delegated to and the claim of the
+    /// block's miner, along with every claim holding payouts when the block
+    /// opens an epoch, see [StateManager::delegation_claims]. `accounts` must
+    /// hold every delegator, along with every account those payouts may go to.
+    pub(super) fn apply_delegations(
+        &self,
+        header: &BlockHeader,
+        delegations: &[Delegation],
+        claims: &mut HashMap<NodeId, Claim>,
+        accounts: &mut HashMap<Address, Account>,
+    ) -> DelegationChanges {
+        let params = &self.chain_spec.delegation;
+
+        if !params.is_active_at(header.epoch) {
+            return DelegationChanges::default();
+        }
+
+        let mut post_images = BTreeMap::new();
+        let mut changed_claims = BTreeSet::new();
+        let mut bonded = 0u128;
+        let mut credited = 0u128;
+
+        if self.pays_out_rewards(header) {
+            let mut node_ids: Vec<NodeId> = claims.keys().cloned().collect();
+            node_ids.sort();
+
+            for node_id in node_ids {
+                let Some(claim) = claims.get_mut(&node_id) else {
+                    continue;
+                };
+
+                let released = claim.release_undelegations(header.epoch);
+                let reward = claim.take_accrued_reward();
+
+                if released.is_empty() && reward == 0 {
+                    continue;
+                }
+
+                let split = split_reward(
+                    reward,
+                    claim.get_stake(),
+                    claim.delegations(),
+                    params.commission_percent,
+                );
+
+                // NOTE: payouts to accounts that no longer exist are forfeited
+                let payouts = released
+                    .into_iter()
+                    .map(|undelegation| (undelegation.delegator, undelegation.amount))
+                    .chain(split.delegators)
+                    .chain(std::iter::once((claim.address.clone(), split.validator)))
+                    .filter(|(_, amount)| *amount > 0);
+
+                for (address, amount) in payouts {
+                    if credit(accounts, &mut post_images, &address, amount) {
+                        credited = credited.saturating_add(amount);
+                    }
+                }
+
+                changed_claims.insert(node_id);
+            }
+        }
+
+        for delegation in delegations {
+            let Some(claim) = claims.get_mut(&delegation.node_id) else {
+                continue;
+            };
+
+            let delegator = &delegation.sender_address;
+
+            match delegation.action {
+                DelegationAction::Delegate => {
+                    if delegation.stake() < params.min_delegation
+                        || claim.unbonding_until().is_some()
+                    {
+                        continue;
+                    }
+
+                    let Some(account) = accounts.get_mut(delegator) else {
+                        continue;
+                    };
+
+                    if account
+                        .update_field(AccountField::Debits(delegation.stake()))
+                        .is_err()
+                    {
+                        continue;
+                    }
+
+                    post_images.insert(delegator.clone(), Some(account.clone()));
+                    claim.delegate(delegator.clone(), delegation.stake());
+                    bonded = bonded.saturating_add(delegation.stake());
+                },
+                DelegationAction::Undelegate => {
+                    let unlocks_at = header
+                        .epoch
+                        .saturating_add(self.chain_spec.staking.unbonding_period);
+
+                    if claim
+                        .undelegate(delegator, delegation.stake(), unlocks_at)
+                        .is_err()
+                    {
+                        continue;
+                    }
+                },
+            }
+
+            changed_claims.insert(delegation.node_id.clone());
+        }
+
+        let mut block_reward = None;
+
+        if let Some(claim) = claims.get_mut(&header.miner_claim.node_id) {
+            if header.block_reward.amount > 0 {
+                claim.accrue_reward(header.block_reward.amount);
+                changed_claims.insert(claim.node_id.clone());
+                block_reward = Some((claim.node_id.clone(), header.block_reward.amount));
+            }
+        }
+
+        DelegationChanges {
+            post_images: post_images.into_iter().collect(),
+            claims: changed_claims
+                .iter()
+                .filter_map(|node_id| claims.get(node_id).cloned())
+                .collect(),
+            bonded,
+            credited,
+            block_reward,
+        }
+    }
+
+    /// Returns the claims applying the `delegations` of the block at `header`
+    /// may change: the claims delegated to and the claim of the block's miner,
+    /// along with the claims holding payouts if the block opens an epoch.
+    /// Only those claims are read from the claim store, and none while
+    /// delegation isn't active
+    pub(super) fn delegation_claims(
+        &self,
+        header: &BlockHeader,
+        delegations: &[Delegation],
+    ) -> HashMap<NodeId, Claim> {
+        if !self.chain_spec.delegation.is_active_at(header.epoch) {
+            return HashMap::new();
+        }
+
+        let read_handle = self.read_handle();
+
+        let mut claims: HashMap<NodeId, Claim> = if self.pays_out_rewards(header) {
+            read_handle
+                .claims_with_pending_payouts()
+                .into_iter()
+                .map(|claim| (claim.node_id.clone(), claim))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        let node_ids = delegations
+            .iter()
+            .map(|delegation| &delegation.node_id)
+            .chain(std::iter::once(&header.miner_claim.node_id));
+
+        for node_id in node_ids {
+            if claims.contains_key(node_id) {
+                continue;
+            }
+
+            if let Ok(claim) = read_handle.get_claim(node_id) {
+                claims.insert(node_id.clone(), claim);
+            }
+        }
+
+        claims
+    }
+
+    /// Returns the root the delegation state of every claim commits to once
+    /// the block at `header` stored `changed`, see
+    /// [storage::vrrbdb::ClaimIndex::delegation_root]. Empty while delegation
+    /// isn't active
+    pub(crate) fn delegation_root(&self, header: &BlockHeader, changed: &[Claim]) -> String {
+        if !self.chain_spec.delegation.is_active_at(header.epoch) {
+            return String::new();
+        }
+
+        self.read_handle().delegation_root(changed)
+    }
+
+    /// Applies the delegations included by `proposals` on top of the txns of
+    /// the block at `header` the state now reflects, see
+    /// [StateManager::apply_delegations]. The changes are returned rather
+    /// than written
+    pub(super) fn delegation_changes(
+        &self,
+        header: &BlockHeader,
+        proposals: &[ProposalBlock],
+    ) -> DelegationChanges {
+        if !self.chain_spec.delegation.is_active_at(header.epoch) {
+            return DelegationChanges::default();
+        }
+
+        let delegations = delegations_in(proposals);
+        let mut claims = self.delegation_claims(header, &delegations);
+
+        let handle = self.database.state_store_factory().handle();
+
+        let addresses = delegation_addresses(&claims, &delegations, self.pays_out_rewards(header));
+
+        let mut accounts: HashMap<Address, Account> = addresses
+            .into_iter()
+            .filter_map(|address| {
+                let account = handle.get(&address).ok()?;
+
+                Some((address, account))
+            })
+            .collect();
+
+        let changes = self.apply_delegations(header, &delegations, &mut claims, &mut accounts);
+
+        if self.pays_out_rewards(header) && changes.credited > 0 {
+            info!(
+                "Paid out {} in validator rewards and unbonded stake at the start of epoch {}",
+                changes.credited, header.epoch
+            );
+        }
+
+        changes
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::{BTreeMap, HashMap};
+
+use block::header::BlockHeader;
+use primitives::{Address, DustDestination, DustPolicyParams, Epoch};
+use telemetry::info;
+use vrrb_core::account::{Account, AccountField};
+
+use super::StateManager;
+
+/// Changes the dust policy makes to the state on top of the txns of a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct DustPolicyChanges {
+    /// Accounts the policy changed, swept ones being deleted
+    pub post_images: Vec<(Address, Option<Account>)>,
+
+    /// Number of dust accounts swept
+    pub swept: usize,
+
+    /// Tokens taken out of the supply along with the swept accounts
+    pub burned: u128,
+}
+
+/// Whether `account` counts as dust during `epoch`
+fn is_dust(
+    params: &DustPolicyParams,
+    activation_epoch: Epoch,
+    epoch: Epoch,
+    account: &Account,
+) -> bool {
+    let last_active_epoch = account.last_active_epoch().unwrap_or(activation_epoch);
+
+    account.balance() < params.threshold
+        && account.code().is_none()
+        && account.storage().is_none()
+        && epoch >= last_active_epoch.saturating_add(params.inactive_epochs)
+}
+
+impl StateManager {
+    /// Whether a block at `header` sweeps dust accounts, which the first block
+    /// of every epoch does once the dust policy is active
+    fn sweeps_dust(&self, header: &BlockHeader) -> bool {
+        header.block_height > 0
+            && self.chain_spec.dust.is_active_at(header.epoch)
+            && self.chain_spec.epoch_start(header.epoch) == header.block_height
+    }
+
+    /// Returns the addresses of the accounts whose allowed inactivity ends
+    /// with the epoch the block at `header` opens, the only ones it may sweep.
+    /// Accounts inactive for longer were checked when theirs ended, and none
+    /// of them can turn into dust without a block touching them
+    pub(super) fn expiring_addresses(&self, header: &BlockHeader) -> Vec<Address> {
+        let params = &self.chain_spec.dust;
+
+        let (Some(activation_epoch), Some(last_active_epoch)) = (
+            params.activation_epoch,
+            header.epoch.checked_sub(params.inactive_epochs),
+        ) else {
+            return vec![];
+        };
+
+        if !self.sweeps_dust(header) {
+            return vec![];
+        }
+
+        // NOTE: accounts untouched since the policy took effect count as
+        // active during its activation epoch
+        let mut groups = vec![Some(last_active_epoch)];
+        if last_active_epoch == activation_epoch {
+            groups.push(None);
+        }
+
+        let handle = self.database.state_store_factory().handle();
+
+        groups
+            .into_iter()
+            .flat_map(|group| {
+                handle
+                    .addresses_last_active_at(group)
+                    .unwrap_or_else(|err| {
+                        telemetry::error!("Failed to read the state activity index: {err}");
+                        vec![]
+                    })
+            })
+            .collect()
+    }
+
+    /// Applies the dust policy to `accounts`, the state once the txns of the
+    /// block at `header` are applied: the accounts the block `touched` are
+    /// marked active and, if the block opens an epoch, the dust accounts
+    /// among the `expiring` ones are swept, see
+    /// [StateManager::expiring_addresses].
+    ///
+    /// `accounts` must hold every touched and expiring account, along with
+    /// the dust pool when the block sweeps dust.
+    pub(super) fn apply_dust_policy(
+        &self,
+        header: &BlockHeader,
+        touched: &[Address],
+        expiring: &[Address],
+        accounts: &mut HashMap<Address, Account>,
+    ) -> DustPolicyChanges {
+        let params = &self.chain_spec.dust;
+
+        let Some(activation_epoch) = params
+            .activation_epoch
+            .filter(|_| params.is_active_at(header.epoch))
+        else {
+            return DustPolicyChanges::default();
+        };
+
+        let mut post_images = BTreeMap::new();
+
+        for address in touched {
+            if let Some(account) = accounts.get_mut(address) {
+                account.record_activity(header.epoch);
+                post_images.insert(address.clone(), Some(account.clone()));
+            }
+        }
+
+        if !self.sweeps_dust(header) {
+            return DustPolicyChanges {
+                post_images: post_images.into_iter().collect(),
+                ..Default::default()
+            };
+        }
+
+        let pool = match &params.destination {
+            DustDestination::Pool(pool) if accounts.contains_key(pool) => Some(pool.clone()),
+            _ => None,
+        };
+
+        let dust: Vec<Address> = expiring
+            .iter()
+            .filter(|address| {
+                Some(*address) != pool.as_ref()
+                    && accounts.get(*address).map_or(false, |account| {
+                        is_dust(params, activation_epoch, header.epoch, account)
+                    })
+            })
+            .cloned()
+            .collect();
+
+        let mut amount = 0u128;
+
+        for address in dust.iter() {
+            if let Some(account) = accounts.remove(address) {
+                amount = amount.saturating_add(account.balance());
+                post_images.insert(address.clone(), None);
+            }
+        }
+
+        let mut burned = amount;
+
+        if let Some(pool) = pool.filter(|_| amount > 0) {
+            if let Some(pool_account) = accounts.get_mut(&pool) {
+                if pool_account
+                    .update_field(AccountField::Credits(amount))
+                    .is_ok()
+                {
+                    burned = 0;
+                }
+
+                post_images.insert(pool, Some(pool_account.clone()));
+            }
+        }
+
+        DustPolicyChanges {
+            post_images: post_images.into_iter().collect(),
+            swept: dust.len(),
+            burned,
+        }
+    }
+
+    /// Applies the dust policy on top of the txns of the block at `header`
+    /// the state now reflects, see [StateManager::apply_dust_policy]. The
+    /// changes are returned rather than written
+    pub(super) fn dust_policy_changes(
+        &self,
+        header: &BlockHeader,
+        touched: &[Address],
+    ) -> DustPolicyChanges {
+        if !self.chain_spec.dust.is_active_at(header.epoch) {
+            return DustPolicyChanges::default();
+        }
+
+        let expiring = self.expiring_addresses(header);
+        let pool = match &self.chain_spec.dust.destination {
+            DustDestination::Pool(pool) => Some(pool),
+            _ => None,
+        };
+
+        let handle = self.database.state_store_factory().handle();
+
+        let mut accounts: HashMap<Address, Account> = touched
+            .iter()
+            .chain(expiring.iter())
+            .chain(pool)
+            .filter_map(|address| Some((address.clone(), handle.get(address).ok()?)))
+            .collect();
+
+        let changes = self.apply_dust_policy(header, touched, &expiring, &mut accounts);
+
+        if changes.swept > 0 {
+            info!(
+                "Swept {} dust accounts at the start of epoch {}, burning {}",
+                changes.swept, header.epoch, changes.burned
+            );
+        }
+
+        changes
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::{BTreeMap, HashSet};
+
+use block::{header::BlockHeader, BlockHash, ProposalBlock};
+use primitives::{Address, NodeId};
+use storage::vrrbdb::{
+    types::{StateUpdate, UpdateAccount},
+    EarningsRecord,
+};
+
+use super::StateManager;
+
+/// Tokens a block credits to accounts out of fees and rewards, before they're
+/// attributed to the validators holding those accounts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct BlockCredits {
+    pub fee_shares: BTreeMap<Address, u128>,
+    pub rewards: BTreeMap<Address, u128>,
+}
+
+/// Returns what the fee and reward updates among `updates` credit, by
+/// account
+pub(super) fn block_credits(updates: &HashSet<StateUpdate>) -> BlockCredits {
+    let mut credits = BlockCredits::default();
+
+    for update in updates {
+        let credited = match update.update_account {
+            UpdateAccount::Fee => &mut credits.fee_shares,
+            UpdateAccount::Reward => &mut credits.rewards,
+            _ => continue,
+        };
+
+        let amount = credited.entry(update.address.clone()).or_default();
+        *amount = amount.saturating_add(update.amount);
+    }
+
+    credits
+}
+
+impl StateManager {
+    /// Returns how much stake the claims included by `proposals` lost to the
+    /// slashes among their new stake txns, by node id
+    pub(super) fn slashed_stakes(&self, proposals: &[ProposalBlock]) -> BTreeMap<NodeId, u128> {
+        let claims = self.database.claim_store_factory().handle().entries();
+        let mut slashed = BTreeMap::new();
+
+        for claim in proposals.iter().flat_map(|block| block.claims.values()) {
+            let known = claims
+                .get(&claim.node_id)
+                .map_or(0, |known| known.get_stake_txns().len());
+
+            let amount = claim.slashed_since(known);
+
+            if amount > 0 {
+                slashed.entry(claim.node_id.clone()).or_insert(amount);
+            }
+        }
+
+        slashed
+    }
+
+    /// Records what each validator earned from the block at `header` and
+    /// lost to slashing in the earnings store.
+    ///
+    /// Credits are attributed to the validator whose claim holds the account
+    /// credited. Credits to accounts without a claim aren't validator
+    /// earnings and are left out. Records that can't be written are logged
+    pub(super) fn record_validator_earnings(
+        &mut self,
+        header: &BlockHeader,
+        block_hash: &BlockHash,
+        credits: &BlockCredits,
+        block_reward: Option<(NodeId, u128)>,
+        penalties: &BTreeMap<NodeId, u128>,
+    ) {
+        let claims = self.database.claim_store_factory().handle().entries();
+
+        // NOTE: node ids are sorted so every node attributes the credits to an
+        // account holding several claims the same way
+        let mut validators: BTreeMap<Address, NodeId> = BTreeMap::new();
+        let mut node_ids: Vec<&NodeId> = claims.keys().collect();
+        node_ids.sort();
+
+        for node_id in node_ids {
+            validators
+                .entry(claims[node_id].address.clone())
+                .or_insert_with(|| node_id.clone());
+        }
+
+        let mut records: BTreeMap<NodeId, EarningsRecord> = BTreeMap::new();
+        let new_record = |node_id: &NodeId| EarningsRecord {
+            node_id: node_id.clone(),
+            epoch: header.epoch,
+            round: header.round,
+            block_hash: block_hash.clone(),
+            ..Default::default()
+        };
+
+        let credited = credits
+            .fee_shares
+            .iter()
+            .map(|(address, amount)| (address, *amount, 0))
+            .chain(
+                credits
+                    .rewards
+                    .iter()
+                    .map(|(address, amount)| (address, 0, *amount)),
+            );
+
+        for (address, fee_shares, rewards) in credited {
+            let Some(node_id) = validators.get(address) else {
+                continue;
+            };
+
+            let record = records
+                .entry(node_id.clone())
+                .or_insert_with(|| new_record(node_id));
+
+            record.fee_shares = record.fee_shares.saturating_add(fee_shares);
+            record.block_rewards = record.block_rewards.saturating_add(rewards);
+        }
+
+        if let Some((node_id, amount)) = block_reward {
+            let record = records
+                .entry(node_id.clone())
+                .or_insert_with(|| new_record(&node_id));
+
+            record.block_rewards = record.block_rewards.saturating_add(amount);
+        }
+
+        for (node_id, amount) in penalties {
+            let record = records
+                .entry(node_id.clone())
+                .or_insert_with(|| new_record(node_id));
+
+            record.penalties = record.penalties.saturating_add(*amount);
+        }
+
+        for record in records.values().filter(|record| !record.is_empty()) {
+            if let Err(err) = self.database.record_earnings(record) {
+                telemetry::warn!(
+                    "failed to record earnings of {} from block {block_hash}: {err}",
+                    record.node_id
+                );
+            }
+        }
+    }
+}
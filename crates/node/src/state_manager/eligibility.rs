@@ -0,0 +1,187 @@
+use block::header::BlockHeader;
+use primitives::{EligibilityParams, Epoch, NodeId};
+use vrrb_core::claim::{Claim, Eligibility};
+
+use super::StateManager;
+use crate::Result;
+
+/// Works out, at the first block of every epoch, which claims may take part
+/// in that epoch's miner elections.
+///
+/// Claims holding a quorum role or unbonding are left as they are, every
+/// other claim is made a miner once it is old enough, holds enough stake,
+/// delegated stake included, and its node filled enough of the mining slots
+/// it held over the previous epoch. Claim ages and mining slots are recorded
+/// by the blocks applied, so every node reaches the same outcome.
+#[derive(Debug, Clone, Default)]
+pub struct EligibilityPolicy {
+    params: EligibilityParams,
+}
+
+impl EligibilityPolicy {
+    pub fn new(params: EligibilityParams) -> Self {
+        Self { params }
+    }
+
+    /// Returns the eligibility `claim` has during `epoch`. Claims stored
+    /// before their age was recorded are old enough, and uptime requirements
+    /// are waived for claims that held no mining slot over the previous epoch
+    pub fn evaluate(&self, claim: &Claim, epoch: Epoch) -> Eligibility {
+        let holds_quorum_role = matches!(
+            claim.eligibility,
+            Eligibility::Harvester | Eligibility::Farmer
+        );
+
+        if holds_quorum_role || claim.unbonding_until().is_some() {
+            return claim.eligibility.clone();
+        }
+
+        let is_old_enough = claim
+            .age(epoch)
+            .map_or(true, |age| age >= self.params.min_claim_age);
+        let holds_enough_stake = claim.total_stake() >= self.params.min_stake_miner;
+        let was_up_enough = claim
+            .uptime_percent(epoch.saturating_sub(1))
+            .map_or(true, |uptime| uptime >= self.params.min_uptime_percent);
+
+        if is_old_enough && holds_enough_stake && was_up_enough {
+            Eligibility::Miner
+        } else {
+            Eligibility::None
+        }
+    }
+}
+
+impl StateManager {
+    /// Returns the nodes scheduled to mine the block at `header` up to its
+    /// miner, along with whether they filled their slot. Only the miner did,
+    /// the ones scheduled ahead of it missed theirs
+    pub(super) fn mining_slots(&self, header: &BlockHeader) -> Vec<(NodeId, bool)> {
+        let slot = header.fallback_slot as usize;
+        let schedule = self
+            .read_handle()
+            .miner_election_results(header.block_seed, slot + 1);
+
+        if schedule.get(slot).map(|(_, claim)| claim.hash) != Some(header.miner_claim.hash) {
+            return vec![];
+        }
+
+        schedule
+            .into_iter()
+            .enumerate()
+            .map(|(scheduled_slot, (_, claim))| (claim.node_id, scheduled_slot == slot))
+            .collect()
+    }
+
+    /// Records the mining slots the nodes scheduled to mine the block at
+    /// `header` held, see [StateManager::mining_slots]
+    pub(super) fn record_mining_slots(
+        &mut self,
+        header: &BlockHeader,
+        slots: Vec<(NodeId, bool)>,
+    ) -> Result<()> {
+        let read_handle = self.read_handle();
+
+        let claims = slots
+            .into_iter()
+            .filter_map(|(node_id, filled)| {
+                let mut claim = read_handle.get_claim(&node_id).ok()?;
+                claim.record_mining_slot(header.epoch, filled);
+
+                Some(claim)
+            })
+            .collect();
+
+        self.update_claims(claims)
+    }
+
+    /// Reevaluates which claims may take part in the miner elections of the
+    /// epoch the block at `header` opens, see [EligibilityPolicy]. Returns the
+    /// claims whose eligibility changed, which is none unless the block is
+    /// the first of its epoch
+    pub(super) fn eligibility_changes(&self, header: &BlockHeader) -> Vec<Claim> {
+        if header.block_height != self.chain_spec.epoch_start(header.epoch) {
+            return vec![];
+        }
+
+        let policy = EligibilityPolicy::new(self.chain_spec.eligibility.clone());
+
+        self.read_handle()
+            .claim_store_values()
+            .into_values()
+            .filter_map(|mut claim| {
+                let eligibility = policy.evaluate(&claim, header.epoch);
+                if eligibility == claim.eligibility {
+                    return None;
+                }
+
+                claim.eligibility = eligibility;
+                Some(claim)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::produce_random_claims;
+
+    #[test]
+    fn claims_become_miners_once_they_meet_every_requirement() {
+        let policy = EligibilityPolicy::new(EligibilityParams {
+            min_claim_age: 2,
+            min_stake_miner: 0,
+            min_uptime_percent: 50,
+        });
+
+        let mut claim = produce_random_claims(1).into_iter().next().unwrap();
+        claim.record_first_seen(3);
+
+        assert_eq!(policy.evaluate(&claim, 4), Eligibility::None);
+        assert_eq!(policy.evaluate(&claim, 5), Eligibility::Miner);
+
+        claim.record_mining_slot(4, false);
+        claim.record_mining_slot(4, true);
+        claim.record_mining_slot(4, false);
+        assert_eq!(policy.evaluate(&claim, 5), Eligibility::None);
+
+        claim.record_mining_slot(4, true);
+        assert_eq!(policy.evaluate(&claim, 5), Eligibility::Miner);
+
+        claim.eligibility = Eligibility::Farmer;
+        claim.record_mining_slot(4, false);
+        claim.record_mining_slot(4, false);
+        assert_eq!(policy.evaluate(&claim, 5), Eligibility::Farmer);
+    }
+
+    #[test]
+    fn claims_stored_before_ages_were_recorded_are_old_enough() {
+        let policy = EligibilityPolicy::new(EligibilityParams {
+            min_claim_age: 1,
+            ..Default::default()
+        });
+
+        let mut claim = produce_random_claims(1).into_iter().next().unwrap();
+        assert_eq!(policy.evaluate(&claim, 0), Eligibility::Miner);
+
+        claim.record_first_seen(7);
+        assert_eq!(policy.evaluate(&claim, 7), Eligibility::None);
+        assert_eq!(policy.evaluate(&claim, 8), Eligibility::Miner);
+    }
+
+    #[test]
+    fn delegated_stake_counts_towards_the_miner_stake_requirement() {
+        let policy = EligibilityPolicy::new(EligibilityParams {
+            min_claim_age: 0,
+            min_stake_miner: 1_000,
+            min_uptime_percent: 0,
+        });
+
+        let mut claim = produce_random_claims(1).into_iter().next().unwrap();
+        assert_eq!(policy.evaluate(&claim, 1), Eligibility::None);
+
+        claim.delegate(claim.address.clone(), 1_000);
+        assert_eq!(policy.evaluate(&claim, 1), Eligibility::Miner);
+    }
+}
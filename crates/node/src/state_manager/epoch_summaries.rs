@@ -0,0 +1,89 @@
+use block::{validator_set_hash, Certificate, EpochSummary};
+use primitives::QuorumKind;
+
+use crate::{NodeError, Result};
+
+use super::StateManager;
+
+impl StateManager {
+    /// Summarizes the epoch of the convergence block `certificate` certifies,
+    /// if the block is the last of its epoch, and persists the summary.
+    /// Returns the summary produced, if any.
+    pub fn take_epoch_summary(
+        &mut self,
+        certificate: &Certificate,
+    ) -> Result<Option<EpochSummary>> {
+        let Some(block) = self.dag.convergence_block(&certificate.block_hash) else {
+            return Ok(None);
+        };
+
+        let epoch = block.header.epoch;
+        let next_epoch_start = self.chain_spec.epoch_start(epoch.saturating_add(1));
+
+        if block.header.block_height.saturating_add(1) != next_epoch_start {
+            return Ok(None);
+        }
+
+        // NOTE: like checkpoints, only nodes that were harvesters when the
+        // block was certified can vouch for the validator set that signed it
+        let Some(quorum) = self
+            .read_handle()
+            .get_quorum(epoch)
+            .ok()
+            .filter(|quorum| quorum.quorum_kind == QuorumKind::Harvester)
+        else {
+            return Ok(None);
+        };
+
+        let read_handle = self.read_handle();
+        let parent = read_handle
+            .epoch_summaries()
+            .into_iter()
+            .rev()
+            .find(|summary| summary.epoch < epoch);
+
+        // NOTE: blocks pruned from the DAG aren't counted, nor are competing
+        // blocks that were never certified
+        let start = parent.as_ref().map_or(0, |parent| parent.round + 1);
+        let txn_count = self
+            .dag
+            .convergence_blocks_by_round_range(start, block.header.round)
+            .into_iter()
+            .filter(|other| other.header.epoch == epoch)
+            .filter(|other| other.hash == block.hash || self.is_block_certified(&other.hash))
+            .map(|other| other.txn_id_set().len() as u64)
+            .sum();
+
+        let (block_rewards, fee_shares) = read_handle.epoch_earnings(epoch).iter().fold(
+            (0u128, 0u128),
+            |(block_rewards, fee_shares), earnings| {
+                (
+                    block_rewards.saturating_add(earnings.block_rewards),
+                    fee_shares.saturating_add(earnings.fee_shares),
+                )
+            },
+        );
+
+        let summary = EpochSummary {
+            epoch,
+            parent_hash: parent.as_ref().map(EpochSummary::hash).unwrap_or_default(),
+            round: block.header.round,
+            block_hash: certificate.block_hash.clone(),
+            state_root: block.header.state_root.clone(),
+            validator_set_hash: validator_set_hash(&quorum.group_public_key, &quorum.members),
+            quorum_public_key: quorum.group_public_key,
+            txn_count,
+            block_rewards,
+            fee_shares,
+            signature: certificate.signature.clone(),
+            header: block.header,
+        };
+
+        signer::epoch_summary::verify_epoch_summary(&summary, &quorum.group_public_key)
+            .map_err(|err| NodeError::Other(format!("epoch summary doesn't verify: {err}")))?;
+
+        self.database.record_epoch_summary(&summary)?;
+
+        Ok(Some(summary))
+    }
+}
@@ -0,0 +1,49 @@
+use block::{ProposalBlock, SignedHaltDirective};
+
+use crate::{NodeError, Result};
+
+use super::StateManager;
+
+impl StateManager {
+    /// Checks the harvester quorum signed a halt directive
+    pub fn verify_halt_directive(&self, signed: &SignedHaltDirective) -> Result<()> {
+        let harvester_pubkey = self.dag.harvester_quorum_pubkey().ok_or_else(|| {
+            NodeError::Other("harvester quorum public key isn't known yet".to_string())
+        })?;
+
+        signer::halt::verify_halt_directive(signed, &harvester_pubkey)
+            .map_err(|err| NodeError::Other(format!("halt directive doesn't verify: {err}")))
+    }
+
+    /// Verifies a halt directive and records it in the ledger, so it is still
+    /// acted on after the node restarts
+    fn record_halt_directive(&mut self, signed: &SignedHaltDirective) -> Result<()> {
+        self.verify_halt_directive(signed)?;
+        self.database.record_halt_directive(signed)?;
+
+        Ok(())
+    }
+
+    /// Records the halt directives carried by the proposal blocks of an
+    /// applied convergence block, so the halt log of every node is built from
+    /// the chain alone. Directives that don't verify are skipped
+    pub(super) fn record_halt_directives(&mut self, proposals: &[ProposalBlock]) {
+        let mut directives: Vec<&SignedHaltDirective> = proposals
+            .iter()
+            .flat_map(|proposal| proposal.halt_directives.iter())
+            .collect();
+
+        directives.sort_by_key(|signed| signed.directive.round);
+        directives.dedup();
+
+        for signed in directives {
+            if let Err(err) = self.record_halt_directive(signed) {
+                telemetry::warn!(
+                    "Skipped directive to {} at round {}: {err}",
+                    signed.directive.action,
+                    signed.directive.round
+                );
+            }
+        }
+    }
+}
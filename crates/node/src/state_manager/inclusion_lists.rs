@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use block::{ConvergenceBlock, ProposalBlock};
+use primitives::{InclusionListParams, Round};
+use vrrb_core::transactions::TransactionDigest;
+
+use super::StateManager;
+use crate::{NodeError, Result};
+
+/// Txns the proposal blocks of applied convergence blocks listed for
+/// inclusion that no block included yet, by the round of the first block
+/// referencing a proposal that listed them.
+///
+/// It's only ever built from applied blocks, so every node rejects the same
+/// blocks for leaving listed txns out, see
+/// [StateManager::verify_inclusion_lists].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InclusionListLedger {
+    listed: BTreeMap<TransactionDigest, Round>,
+}
+
+impl InclusionListLedger {
+    /// Records the entries of the inclusion lists referenced by the block
+    /// applied at `round`. Entries past `max_entries` of a list, and txns
+    /// `is_included` says a block already included, are ignored
+    pub fn record_listed<'a>(
+        &mut self,
+        round: Round,
+        inclusion_lists: impl IntoIterator<Item = &'a Vec<TransactionDigest>>,
+        params: &InclusionListParams,
+        is_included: impl Fn(&TransactionDigest) -> bool,
+    ) {
+        for list in inclusion_lists {
+            for digest in list.iter().take(params.max_entries) {
+                if !is_included(digest) {
+                    self.listed.entry(digest.clone()).or_insert(round);
+                }
+            }
+        }
+    }
+
+    /// Forgets listed txns once a block included them
+    pub fn record_included<'a>(
+        &mut self,
+        digests: impl IntoIterator<Item = &'a TransactionDigest>,
+    ) {
+        for digest in digests {
+            self.listed.remove(digest);
+        }
+    }
+
+    /// Returns the listed txns a block at `round` must include but
+    /// `is_included` says it doesn't, in ascending digest order. Txns are
+    /// given `min_age_rounds` after being listed to make it into a block
+    pub fn omitted(
+        &self,
+        round: Round,
+        params: &InclusionListParams,
+        is_included: impl Fn(&TransactionDigest) -> bool,
+    ) -> Vec<TransactionDigest> {
+        self.listed
+            .iter()
+            .filter(|(_, listed_at)| listed_at.saturating_add(params.min_age_rounds) <= round)
+            .filter(|(digest, _)| !is_included(digest))
+            .map(|(digest, _)| digest.clone())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.listed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.listed.is_empty()
+    }
+}
+
+impl StateManager {
+    /// Rejects convergence blocks that leave out txns the proposal blocks of
+    /// applied blocks listed for inclusion, once they've had `min_age_rounds`
+    /// to make it into a block
+    pub fn verify_inclusion_lists(&self, block: &ConvergenceBlock) -> Result<()> {
+        let included = block.txn_id_set();
+
+        let omitted = self.inclusion_list_ledger.omitted(
+            block.header.round,
+            &self.chain_spec.inclusion_list,
+            |digest| included.contains(digest),
+        );
+
+        if !omitted.is_empty() {
+            return Err(NodeError::InvalidBlock {
+                hash: block.hash.clone(),
+                reason: format!(
+                    "omits {} txns proposal blocks listed for inclusion",
+                    omitted.len()
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Tracks the txns the proposal blocks of an applied convergence block
+    /// list for inclusion, forgetting the ones it included
+    pub(super) fn record_inclusion_lists(
+        &mut self,
+        block: &ConvergenceBlock,
+        proposals: &[ProposalBlock],
+    ) {
+        let included = block.txn_id_set();
+        self.inclusion_list_ledger.record_included(included.iter());
+
+        let read_handle = self.database.read_handle();
+        self.inclusion_list_ledger.record_listed(
+            block.header.round,
+            proposals.iter().map(|proposal| &proposal.inclusion_list),
+            &self.chain_spec.inclusion_list,
+            |digest| included.contains(digest) || read_handle.contains_transaction(digest),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(byte: u8) -> TransactionDigest {
+        TransactionDigest::from(vec![byte; 32])
+    }
+
+    fn params() -> InclusionListParams {
+        InclusionListParams {
+            min_age_rounds: 2,
+            max_entries: 2,
+        }
+    }
+
+    #[test]
+    fn listed_txns_must_be_included_once_old_enough() {
+        let mut ledger = InclusionListLedger::default();
+        ledger.record_listed(
+            5,
+            [&vec![digest(1), digest(2), digest(3)], &vec![digest(4)]],
+            &params(),
+            |included| *included == digest(4),
+        );
+
+        // only the first `max_entries` entries of a list count, and txns a
+        // block already included are never listed
+        assert_eq!(ledger.len(), 2);
+
+        assert!(ledger.omitted(6, &params(), |_| false).is_empty());
+        assert_eq!(
+            ledger.omitted(7, &params(), |_| false),
+            vec![digest(1), digest(2)]
+        );
+        assert_eq!(
+            ledger.omitted(7, &params(), |included| *included == digest(1)),
+            vec![digest(2)]
+        );
+
+        ledger.record_included([&digest(1), &digest(2)]);
+        assert!(ledger.omitted(7, &params(), |_| false).is_empty());
+        assert!(ledger.is_empty());
+    }
+
+    #[test]
+    fn txns_keep_the_round_they_were_first_listed_at() {
+        let mut ledger = InclusionListLedger::default();
+        ledger.record_listed(1, [&vec![digest(1)]], &params(), |_| false);
+        ledger.record_listed(4, [&vec![digest(1)]], &params(), |_| false);
+
+        assert_eq!(ledger.omitted(3, &params(), |_| false), vec![digest(1)]);
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::{HashMap, HashSet};
+
+use block::ProposalBlock;
+use ethereum_types::U256;
+use primitives::{Address, Amount, NodeId};
+use storage::vrrbdb::types::{StateUpdate, UpdateAccount};
+use vrrb_config::InvariantCheckMode;
+
+use crate::Result;
+
+use super::StateManager;
+
+/// A ledger invariant found violated once a block was applied.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvariantViolation {
+    #[error(
+        "supply of the accounts updated went from {previous} to {current}, but {issued} was issued"
+    )]
+    SupplyNotConserved {
+        previous: u128,
+        issued: u128,
+        current: u128,
+    },
+
+    #[error("claim {hash} of node {node_id} is missing from the claim store")]
+    ClaimMissing { node_id: NodeId, hash: U256 },
+
+    #[error("state root {committed} can't be reproduced, rebuilding the state yields {rebuilt}")]
+    StateRootIrreproducible { committed: String, rebuilt: String },
+}
+
+/// Tokens minted by a set of updates: the fees and rewards they credit
+/// without debiting any account
+pub(super) fn issued_supply(updates: &HashSet<StateUpdate>) -> Result<u128> {
+    let issued = Amount::checked_sum(
+        updates
+            .iter()
+            .filter(|update| {
+                matches!(
+                    update.update_account,
+                    UpdateAccount::Fee | UpdateAccount::Reward
+                )
+            })
+            .map(|update| update.amount),
+    )?;
+
+    Ok(issued.get())
+}
+
+impl StateManager {
+    /// Checks the ledger invariants hold once a block is applied, returning
+    /// the ones that don't.
+    ///
+    /// `previous_balances` holds the balances of the accounts the block
+    /// updated before it was applied, `issued` the tokens its fees, rewards
+    /// and unbonded stake credited and `burned` the dust and bonded stake it
+    /// took out of the balances.
+    pub(super) fn check_invariants(
+        &self,
+        previous_balances: &HashMap<Address, u128>,
+        issued: u128,
+        burned: u128,
+        proposals: &[ProposalBlock],
+    ) -> Result<Vec<InvariantViolation>> {
+        let mut violations = vec![];
+
+        if self.invariants_config.mode == InvariantCheckMode::Off {
+            return Ok(violations);
+        }
+
+        // NOTE: accounts the block left alone can't change the supply, so
+        // comparing the ones it updated is enough
+        let previous = Amount::checked_sum(previous_balances.values())?.get();
+        let current = Amount::checked_sum(
+            previous_balances
+                .keys()
+                .map(|address| self.get_balance(address)),
+        )?
+        .get();
+
+        // NOTE: burning more than the accounts held can't conserve the supply
+        let expected = Amount::new(previous)
+            .checked_add(issued)?
+            .checked_sub(burned)
+            .ok();
+
+        if expected != Some(Amount::new(current)) {
+            violations.push(InvariantViolation::SupplyNotConserved {
+                previous,
+                issued,
+                current,
+            });
+        }
+
+        let stored_claims: HashSet<U256> = self
+            .database
+            .claim_store_factory()
+            .handle()
+            .entries()
+            .into_values()
+            .map(|claim| claim.hash)
+            .collect();
+
+        for claim in proposals.iter().flat_map(|block| block.claims.values()) {
+            if !stored_claims.contains(&claim.hash) {
+                violations.push(InvariantViolation::ClaimMissing {
+                    node_id: claim.node_id.clone(),
+                    hash: claim.hash,
+                });
+            }
+        }
+
+        if self.invariants_config.mode == InvariantCheckMode::Paranoid {
+            let committed = self.state_root_hash()?;
+            let rebuilt = hex::encode(self.database.projected_state_root_hash(vec![])?.0);
+
+            if committed != rebuilt {
+                violations.push(InvariantViolation::StateRootIrreproducible { committed, rebuilt });
+            }
+        }
+
+        Ok(violations)
+    }
+}
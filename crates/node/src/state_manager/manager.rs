@@ -3,25 +3,41 @@ use std::{
     sync::{Arc, RwLock},
 };
 
-use block::{Block, BlockHash, Certificate, ClaimHash, ProposalBlock};
+use block::{
+    consolidated_txns_weight, Block, BlockHash, Certificate, ClaimHash, ConvergenceBlock,
+    ProposalBlock,
+};
 use bulldag::{graph::BullDag, vertex::Vertex};
 use ethereum_types::U256;
-use events::{Event, EventMessage, EventPublisher, Vote};
+use events::{
+    BalanceChange, Event, EventMessage, EventPublisher, StateDiff, TxnResurrectionStatus,
+    TxnStatusChange, Vote,
+};
 use hbbft::crypto::PublicKeySet;
-use mempool::{LeftRightMempool, MempoolReadHandleFactory};
+use mempool::{AdmissionOrigin, LeftRightMempool, MempoolReadHandleFactory, TxnRecord};
 use patriecia::RootHash;
 use primitives::{
-    Address, ByteSlice, ByteVec, NodeId, ProgramExecutionOutput, RawSignature, Round,
-    TxnValidationStatus,
+    Address, Amount, ByteSlice, ByteVec, ChainSpec, Epoch, NodeId, ProgramExecutionOutput,
+    RawSignature, Round, TxnValidationStatus,
 };
 use storage::vrrbdb::{types::*, ApplyBlockResult};
 use storage::{
     storage_utils::StorageError,
-    vrrbdb::{Claims, StateStoreReadHandle, VrrbDb, VrrbDbReadHandle},
+    vrrbdb::{
+        CertificationVote, CertifiedTxnKey, Claims, CorruptRecord, PartialSignatureRecord,
+        QuorumRecord, RecordOutcome, RoundMilestone, ScrubbedStore, StateStoreReadHandle,
+        TxnCertificationRecord, TxnInclusionRecord, VrrbDb, VrrbDbReadHandle,
+    },
 };
 use telemetry::info;
 use theater::{ActorId, ActorState};
-use vrrb_core::{account::Account, claim::Claim, serde_helpers::decode_from_binary_byte_slice};
+use validator::txn_validator::TxnValidator;
+use vrrb_config::InvariantsConfig;
+use vrrb_core::{
+    account::Account,
+    claim::Claim,
+    serde_helpers::{decode_from_binary_byte_slice, encode_to_binary},
+};
 use vrrb_core::{
     account::UpdateArgs,
     transactions::{Transaction, TransactionDigest, TransactionKind},
@@ -31,6 +47,11 @@ use crate::{data_store::DataStore, state_reader::StateReader};
 use crate::{NodeError, Result};
 
 use super::{
+    delegation::delegations_in,
+    earnings::block_credits,
+    inclusion_lists::InclusionListLedger,
+    invariants::issued_supply,
+    registration::{registrations_in, BondedRegistration},
     utils::{consolidate_update_args, get_update_args},
     DagModule,
 };
@@ -43,6 +64,19 @@ pub struct StateManagerConfig {
     pub dag: Arc<RwLock<BullDag<Block, String>>>,
     pub mempool: LeftRightMempool,
     pub claim: Claim,
+    pub chain_spec: ChainSpec,
+    pub invariants_config: InvariantsConfig,
+}
+
+/// Roots the header of a block commits to once the block is applied, as
+/// projected for it before it is, see [StateManager::projected_roots]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectedRoots {
+    /// Hex encoded root of the state trie
+    pub state_root: String,
+
+    /// Hex encoded delegation root, empty while delegation isn't active
+    pub delegation_root: String,
 }
 
 #[derive(Debug, Clone)]
@@ -52,6 +86,20 @@ pub struct StateManager {
     pub(crate) dag: DagModule,
     pub(crate) database: VrrbDb,
     pub(crate) mempool: LeftRightMempool,
+    pub(crate) chain_spec: ChainSpec,
+    pub(crate) invariants_config: InvariantsConfig,
+
+    /// Set once an invariant is violated and `halt_on_violation` is on, after
+    /// which no more blocks are applied
+    pub(crate) halted: bool,
+
+    /// Validator registrations the blocks applied so far bonded stake for,
+    /// yet to be queued for activation
+    pub(crate) bonded_registrations: Vec<BondedRegistration>,
+
+    /// Txns the blocks applied so far listed for inclusion and none included
+    /// yet
+    pub(crate) inclusion_list_ledger: InclusionListLedger,
 }
 
 impl StateManager {
@@ -64,11 +112,21 @@ impl StateManager {
             status: ActorState::Stopped,
             dag: dag_module,
             mempool: config.mempool,
+            chain_spec: config.chain_spec,
+            invariants_config: config.invariants_config,
+            halted: false,
+            bonded_registrations: vec![],
+            inclusion_list_ledger: InclusionListLedger::default(),
         }
     }
 
-    pub fn export_state(&self) {
-        self.database.export_state();
+    pub fn chain_spec(&self) -> &ChainSpec {
+        &self.chain_spec
+    }
+
+    /// Streams the current state to `writer`, see [VrrbDb::export_state]
+    pub fn export_state<W: std::io::Write>(&self, writer: W) -> Result<usize> {
+        Ok(self.database.export_state(writer)?)
     }
 
     /// Produces the read handle for the VrrbDb instance in this
@@ -123,28 +181,337 @@ impl StateManager {
     /// updates the StateStore, ClaimStore and TransactionStore
     /// for all new claims and transactions (excluding
     /// ClaimStaking transactions currently).
-    pub fn update_state(&mut self, block_hash: BlockHash) -> Result<()> {
-        if let Some(mut round_blocks) = self.get_proposal_blocks(block_hash) {
+    ///
+    /// Blocks committing to a state root or delegation root other than the
+    /// ones applying them yields are rejected, and the accounts they updated
+    /// are restored, see [StateManager::delegation_root]. So are blocks
+    /// leaving out txns listed for inclusion, see
+    /// [StateManager::verify_inclusion_lists].
+    ///
+    /// The dust policy is applied on top of the block's txns, see
+    /// [StateManager::apply_dust_policy], followed by the stake delegations
+    /// they include, see [StateManager::apply_delegations], and the stake of
+    /// the validator registrations they include, see
+    /// [StateManager::apply_registrations]. The protocol
+    /// parameter changes they include are recorded for the epochs they take
+    /// effect at, see [StateManager::record_parameter_change].
+    ///
+    /// The mining slots the block's miner and the claims scheduled ahead of
+    /// it held are recorded on their claims, and the first block of an epoch
+    /// reevaluates which claims are eligible to mine, see
+    /// [super::EligibilityPolicy]. The halt directives its proposal blocks
+    /// carry are logged, see [StateManager::record_halt_directives].
+    ///
+    /// Once applied, the ledger invariants are checked as configured, see
+    /// [StateManager::check_invariants].
+    pub fn update_state(&mut self, block_hash: BlockHash) -> Result<StateDiff> {
+        if self.halted {
+            return Err(NodeError::Other(
+                "state updates halted after a ledger invariant violation".to_string(),
+            ));
+        }
+
+        if let Some(mut round_blocks) = self.get_proposal_blocks(block_hash.clone()) {
+            self.verify_inclusion_lists(&round_blocks.convergence)?;
+
+            // NOTE: read off the claims the block's miner was scheduled
+            // against, before the block updates any of them
+            let mining_slots = self.mining_slots(&round_blocks.convergence.header);
+
             let update_list = self.get_update_list(&mut round_blocks);
-            let update_args = get_update_args(update_list);
-            let consolidated_update_args = consolidate_update_args(update_args);
+            let issued = issued_supply(&update_list)?;
+            let credits = block_credits(&update_list);
+            let consolidated_update_args = consolidate_update_args(get_update_args(update_list))?;
+
+            let previous_state_root = self.state_root_hash()?;
+
+            let touched: Vec<Address> = consolidated_update_args.keys().cloned().collect();
+
+            let mut pre_images: Vec<(Address, Option<Account>)> = touched
+                .iter()
+                .map(|address| (address.clone(), self.get_account(address).ok()))
+                .collect();
+
+            let mut previous_balances: HashMap<Address, u128> = pre_images
+                .iter()
+                .map(|(address, account)| {
+                    let balance = account
+                        .as_ref()
+                        .map(|account| account.balance())
+                        .unwrap_or_default();
+
+                    (address.clone(), balance)
+                })
+                .collect();
+
             consolidated_update_args.into_iter().for_each(|(_, args)| {
                 if let Err(err) = self.database.update_account(args) {
                     telemetry::error!("error updating account: {err}");
                 }
             });
 
+            let dust_policy_changes =
+                self.dust_policy_changes(&round_blocks.convergence.header, &touched);
+
+            // NOTE: swept accounts weren't touched by the block, so they still
+            // hold their pre-images
+            for (address, _) in dust_policy_changes.post_images.iter() {
+                if !previous_balances.contains_key(address) {
+                    pre_images.push((address.clone(), self.get_account(address).ok()));
+                    previous_balances.insert(address.clone(), self.get_balance(address));
+                }
+            }
+
+            if !dust_policy_changes.post_images.is_empty() {
+                self.database
+                    .extend_accounts(dust_policy_changes.post_images);
+                self.database.commit_state();
+            }
+
+            let delegation_changes =
+                self.delegation_changes(&round_blocks.convergence.header, &round_blocks.proposals);
+
+            // NOTE: the same goes for accounts delegations paid out to
+            for (address, _) in delegation_changes.post_images.iter() {
+                if !previous_balances.contains_key(address) {
+                    pre_images.push((address.clone(), self.get_account(address).ok()));
+                    previous_balances.insert(address.clone(), self.get_balance(address));
+                }
+            }
+
+            if !delegation_changes.post_images.is_empty() {
+                self.database
+                    .extend_accounts(delegation_changes.post_images);
+                self.database.commit_state();
+            }
+
+            let registration_changes = self.registration_changes(
+                &round_blocks.convergence.header,
+                &round_blocks.proposals,
+                &delegation_changes.claims,
+            );
+
+            // NOTE: and to those validators' unbonded stake returned to
+            for (address, _) in registration_changes.post_images.iter() {
+                if !previous_balances.contains_key(address) {
+                    pre_images.push((address.clone(), self.get_account(address).ok()));
+                    previous_balances.insert(address.clone(), self.get_balance(address));
+                }
+            }
+
+            if !registration_changes.post_images.is_empty() {
+                self.database
+                    .extend_accounts(registration_changes.post_images);
+                self.database.commit_state();
+            }
+
+            let header = &round_blocks.convergence.header;
+            let committed_state_root = &header.state_root;
+            let state_root = self.state_root_hash()?;
+
+            let committed_delegation_root = &header.delegation_root;
+            let delegation_root = self.delegation_root(header, &delegation_changes.claims);
+
+            // NOTE: blocks predating the commitment carry an empty state root,
+            // which blocks from its activation on can't get away with. The
+            // same goes for delegation roots once delegation is active
+            let requires_state_root = self
+                .chain_spec
+                .header_format
+                .requires_state_root_at(header.block_height);
+            let requires_delegation_root = self.chain_spec.delegation.is_active_at(header.epoch);
+
+            let mismatch = if (requires_state_root || !committed_state_root.is_empty())
+                && *committed_state_root != state_root
+            {
+                Some(format!(
+                    "commits to state root {committed_state_root}, but applying it yields {state_root}"
+                ))
+            } else if (requires_delegation_root || !committed_delegation_root.is_empty())
+                && *committed_delegation_root != delegation_root
+            {
+                Some(format!(
+                    "commits to delegation root {committed_delegation_root}, but applying it yields {delegation_root}"
+                ))
+            } else {
+                None
+            };
+
+            if let Some(reason) = mismatch {
+                self.database.extend_accounts(pre_images);
+                self.database.commit_state();
+
+                return Err(NodeError::InvalidBlock {
+                    hash: block_hash,
+                    reason,
+                });
+            }
+
+            let round = round_blocks.convergence.header.round;
+
+            self.database.commit_state_at_round(round)?;
+
             let proposals = round_blocks.proposals.clone();
 
             self.update_txn_trie(&proposals);
-            self.update_claim_store(&proposals);
 
-            return Ok(());
+            let penalties = self.slashed_stakes(&proposals);
+
+            self.update_claim_store(&proposals, round_blocks.convergence.header.epoch);
+            self.update_claims(delegation_changes.claims)?;
+            self.update_claims(registration_changes.claims)?;
+            self.record_mining_slots(&round_blocks.convergence.header, mining_slots)?;
+
+            let eligibility_changes = self.eligibility_changes(&round_blocks.convergence.header);
+            self.update_claims(eligibility_changes)?;
+            self.bonded_registrations
+                .extend(registration_changes.registrations);
+            self.record_inclusion_lists(&round_blocks.convergence, &proposals);
+            self.record_halt_directives(&proposals);
+            self.record_parameter_changes(
+                &round_blocks.convergence.header,
+                &block_hash,
+                &proposals,
+            );
+            self.record_validator_earnings(
+                &round_blocks.convergence.header,
+                &block_hash,
+                &credits,
+                delegation_changes.block_reward,
+                &penalties,
+            );
+
+            let violations = self.check_invariants(
+                &previous_balances,
+                Amount::new(issued)
+                    .checked_add(delegation_changes.credited)?
+                    .checked_add(registration_changes.released)?
+                    .get(),
+                Amount::new(dust_policy_changes.burned)
+                    .checked_add(delegation_changes.bonded)?
+                    .checked_add(registration_changes.bonded)?
+                    .get(),
+                &proposals,
+            )?;
+
+            if !violations.is_empty() {
+                for violation in violations.iter() {
+                    telemetry::error!(
+                        "CRITICAL: ledger invariant violated applying block {block_hash}: {violation}"
+                    );
+                }
+
+                if self.invariants_config.halt_on_violation {
+                    self.halted = true;
+
+                    return Err(NodeError::LedgerInvariantsViolated {
+                        hash: block_hash,
+                        violations: violations.len(),
+                    });
+                }
+            }
+
+            let updated_accounts = previous_balances
+                .keys()
+                .filter_map(|address| self.get_account(address).ok())
+                .filter_map(|account| encode_to_binary(&account).ok())
+                .collect();
+
+            let balance_changes = previous_balances
+                .into_iter()
+                .filter_map(|(address, previous)| {
+                    let current = self.get_balance(&address);
+
+                    (current != previous).then_some(BalanceChange {
+                        address,
+                        previous,
+                        current,
+                    })
+                })
+                .collect();
+
+            return Ok(StateDiff {
+                block_hash,
+                round,
+                balance_changes,
+                previous_state_root,
+                state_root,
+                updated_accounts,
+            });
         }
 
-        Err(NodeError::Other(
-            "Convergene block not found in DAG".to_string(),
-        ))
+        Err(NodeError::NotFound(format!(
+            "convergence block {block_hash} in DAG"
+        )))
+    }
+
+    /// Computes the root of the state trie once the txns of a
+    /// `ConvergenceBlock` not yet applied are, leaving the state untouched.
+    /// This is what miners commit the header of the block to, dust the block
+    /// sweeps and stake it delegates or bonds to validators included
+    pub fn projected_state_root_hash(&self, block: &ConvergenceBlock) -> Result<String> {
+        self.projected_roots(block).map(|roots| roots.state_root)
+    }
+
+    /// Computes the roots the header of a `ConvergenceBlock` not yet applied
+    /// commits to, see [StateManager::projected_state_root_hash], leaving the
+    /// state untouched
+    pub fn projected_roots(&self, block: &ConvergenceBlock) -> Result<ProjectedRoots> {
+        let mut round_blocks = RoundBlocks {
+            convergence: block.clone(),
+            proposals: self.dag.proposal_blocks(&block.header.ref_hashes),
+        };
+
+        let updates = self.get_consolidated_update_args(&mut round_blocks)?;
+        let touched: Vec<Address> = updates.keys().cloned().collect();
+
+        let expiring = self.expiring_addresses(&block.header);
+        let delegations = delegations_in(&round_blocks.proposals);
+        let mut claims = self.delegation_claims(&block.header, &delegations);
+        let registrations = registrations_in(&round_blocks.proposals);
+
+        let mut delegated_claims = vec![];
+
+        let root_hash = self.database.projected_state_root_hash_with(
+            updates.into_values().collect(),
+            |accounts| {
+                self.apply_dust_policy(&block.header, &touched, &expiring, accounts);
+
+                let delegation_changes =
+                    self.apply_delegations(&block.header, &delegations, &mut claims, accounts);
+                delegated_claims = delegation_changes.claims.clone();
+
+                let mut registration_claims = self.registration_claims(
+                    &block.header,
+                    &registrations,
+                    &round_blocks.proposals,
+                    &delegation_changes.claims,
+                );
+                self.apply_registrations(
+                    &block.header,
+                    &registrations,
+                    &mut registration_claims,
+                    accounts,
+                );
+            },
+        )?;
+
+        Ok(ProjectedRoots {
+            state_root: hex::encode(root_hash.0),
+            delegation_root: self.delegation_root(&block.header, &delegated_claims),
+        })
+    }
+
+    /// Consolidates the account updates the txns of a `ConvergenceBlock` make,
+    /// one per account
+    fn get_consolidated_update_args(
+        &self,
+        round_blocks: &mut RoundBlocks,
+    ) -> Result<HashMap<Address, UpdateArgs>> {
+        let update_list = self.get_update_list(round_blocks);
+        let update_args = get_update_args(update_list);
+
+        consolidate_update_args(update_args)
     }
 
     /// Provided a reference to an array of `ProposalBlock`s
@@ -166,8 +533,13 @@ impl StateManager {
 
     /// Provided a reference to an array of `ProposalBlock`s
     /// making up the current round's `ConvergenceBlock`, writes
-    /// all the new, conflict resolved, claims into the `ClaimStore`
-    fn update_claim_store(&mut self, proposals: &[ProposalBlock]) {
+    /// all the new, conflict resolved, claims into the `ClaimStore`.
+    ///
+    /// Claims new to the store are recorded as first seen in `epoch`, the
+    /// others keep the age and mining slots already recorded for them
+    fn update_claim_store(&mut self, proposals: &[ProposalBlock], epoch: Epoch) {
+        let read_handle = self.read_handle();
+
         let consolidated: HashSet<(U256, Option<Claim>)> = {
             let nested: Vec<HashSet<(U256, Option<Claim>)>> = {
                 proposals
@@ -176,7 +548,18 @@ impl StateManager {
                         block
                             .claims
                             .iter()
-                            .map(|(k, v)| (*k, Some(v.clone())))
+                            .map(|(k, v)| {
+                                let mut claim = v.clone();
+                                match read_handle.get_claim(&claim.node_id) {
+                                    Ok(stored) => claim.keep_chain_record(&stored),
+                                    Err(_) => {
+                                        claim.reset_chain_record();
+                                        claim.record_first_seen(epoch);
+                                    },
+                                }
+
+                                (*k, Some(claim))
+                            })
                             .collect()
                     })
                     .collect()
@@ -219,7 +602,7 @@ impl StateManager {
     pub fn insert_account(&mut self, key: Address, account: Account) -> Result<()> {
         self.database
             .insert_account(key, account)
-            .map_err(|err| NodeError::Other(err.to_string()))
+            .map_err(NodeError::from)
     }
 
     pub fn extend_accounts(&mut self, accounts: Vec<(Address, Option<Account>)>) -> Result<()> {
@@ -287,7 +670,132 @@ impl StateManager {
         proposals
     }
 
+    /// Checks that a convergence block's header uses the layout the chain
+    /// spec sets for its height
+    pub fn verify_header_format(&self, block: &ConvergenceBlock) -> Result<()> {
+        block
+            .header
+            .verify_format(&self.chain_spec.header_format)
+            .map_err(|err| NodeError::InvalidBlock {
+                hash: block.hash.clone(),
+                reason: err.to_string(),
+            })
+    }
+
+    /// Checks that a convergence block's weight is within the chain's block
+    /// weight cap and, when every proposal block it consolidates is within
+    /// the DAG, that it matches the cumulative weight of its txns
+    pub fn verify_block_weight(&self, block: &ConvergenceBlock) -> Result<()> {
+        block
+            .header
+            .verify_weight(self.chain_spec.block_limits.max_block_weight)
+            .map_err(|err| NodeError::InvalidBlock {
+                hash: block.hash.clone(),
+                reason: err.to_string(),
+            })?;
+
+        let proposals = self.dag.proposal_blocks(&block.header.ref_hashes);
+        if proposals.len() < block.header.ref_hashes.len() {
+            telemetry::debug!(
+                "cannot recompute the weight of block {}, some of its proposal blocks are missing",
+                block.hash
+            );
+
+            return Ok(());
+        }
+
+        let block_weight = consolidated_txns_weight(&block.txns, &proposals);
+        if block_weight != Some(block.header.block_weight) {
+            return Err(NodeError::InvalidBlock {
+                hash: block.hash.clone(),
+                reason: format!(
+                    "header weight {} does not match the weight of its txns {block_weight:?}",
+                    block.header.block_weight
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn handle_block_received(&mut self, block: Block) -> Result<()> {
+        self.verify_block_header(&block)?;
+        self.verify_txn_order(&block)?;
+        self.verify_txn_limits(&block)?;
+        self.append_block(block)
+    }
+
+    /// Checks a block's header is signed over its hash and, for genesis
+    /// blocks, that the block is the one the chain spec expects
+    pub(crate) fn verify_block_header(&self, block: &Block) -> Result<()> {
+        let (header, hash) = match block {
+            Block::Genesis { block } => {
+                if let Some(genesis_hash) = &self.chain_spec.genesis_hash {
+                    if block.hash != *genesis_hash {
+                        return Err(NodeError::InvalidBlock {
+                            hash: block.hash.clone(),
+                            reason: format!(
+                                "does not match chain spec genesis hash {genesis_hash}"
+                            ),
+                        });
+                    }
+                }
+
+                (&block.header, &block.hash)
+            },
+            Block::Convergence { block } => (&block.header, &block.hash),
+            Block::Proposal { .. } => return Ok(()),
+        };
+
+        header.verify(hash).map_err(|err| NodeError::InvalidBlock {
+            hash: hash.clone(),
+            reason: err.to_string(),
+        })
+    }
+
+    /// Checks a proposal or convergence block lists its txns in the canonical
+    /// order of [block::txn_order], so every node executes them the same way
+    pub fn verify_txn_order(&self, block: &Block) -> Result<()> {
+        let (is_canonical, hash) = match block {
+            Block::Proposal { block } => (block.has_canonical_txn_order(), &block.hash),
+            Block::Convergence { block } => (block.has_canonical_txn_order(), &block.hash),
+            Block::Genesis { .. } => return Ok(()),
+        };
+
+        if !is_canonical {
+            return Err(NodeError::InvalidBlock {
+                hash: hash.clone(),
+                reason: "txns are not listed in canonical order".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks every txn a proposal block carries stays within the structural
+    /// limits of the chain spec. Other blocks carry no txns of their own peers
+    /// could have submitted
+    pub(crate) fn verify_txn_limits(&self, block: &Block) -> Result<()> {
+        let Block::Proposal { block } = block else {
+            return Ok(());
+        };
+
+        let validator = TxnValidator::new().with_txn_limits(self.chain_spec.txn_limits.clone());
+
+        for (digest, certified_txn) in block.txns.iter() {
+            validator
+                .validate_limits(&certified_txn.txn())
+                .map_err(|err| NodeError::InvalidBlock {
+                    hash: block.hash.clone(),
+                    reason: format!("txn {digest} is malformed: {err}"),
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Links a block with a verified header into the DAG
+    pub(crate) fn append_block(&mut self, block: Block) -> Result<()> {
         match block {
             Block::Genesis { block } => {
                 if let Err(e) = self.dag.append_genesis(&block) {
@@ -302,6 +810,9 @@ impl StateManager {
                 }
             },
             Block::Convergence { block } => {
+                self.verify_header_format(&block)?;
+                self.verify_block_weight(&block)?;
+
                 if let Err(e) = self.dag.append_convergence(&block) {
                     let err_note = format!("Encountered GraphError: {e:?}");
                     return Err(NodeError::Other(err_note));
@@ -324,43 +835,345 @@ impl StateManager {
     }
 
     pub fn apply_block(&mut self, block: Block) -> Result<ApplyBlockResult> {
-        let apply_result = self
-            .database
-            .apply_block(block)
-            .map_err(|err| NodeError::Other(err.to_string()))?;
+        let apply_result = self.database.apply_block(block)?;
 
         Ok(apply_result)
     }
 
     pub fn handle_new_txn_created(&mut self, txn: TransactionKind) -> Result<TransactionDigest> {
+        self.admit_txn(txn, AdmissionOrigin::Local)
+    }
+
+    /// Admits a txn a peer relayed into the mempool. Relayed txns are rate
+    /// limited apart from the ones submitted to this node
+    pub fn handle_relayed_txn_received(
+        &mut self,
+        txn: TransactionKind,
+    ) -> Result<TransactionDigest> {
+        self.admit_txn(txn, AdmissionOrigin::Relayed)
+    }
+
+    fn admit_txn(
+        &mut self,
+        txn: TransactionKind,
+        origin: AdmissionOrigin,
+    ) -> Result<TransactionDigest> {
         info!("Storing transaction in mempool for validation");
 
         let txn_hash = txn.id();
 
-        let _mempool_size = self
-            .mempool
-            .insert(txn)
-            .map_err(|err| NodeError::Other(err.to_string()))?;
+        let _mempool_size = self.mempool.admit(txn, origin)?;
 
         info!("Transaction {} sent to mempool", txn_hash);
 
+        let admitted_at = chrono::Utc::now().timestamp();
+        if let Err(err) = self
+            .database
+            .record_txn_admission(txn_hash.clone(), admitted_at)
+        {
+            telemetry::warn!("Failed to record admission of txn {txn_hash}: {err}");
+        }
+
         Ok(txn_hash)
     }
 
     pub async fn handle_transaction_validated(&mut self, txn: TransactionKind) -> Result<()> {
-        self.mempool
-            .remove(&txn.id())
-            .map_err(|err| NodeError::Other(err.to_string()))?;
+        self.mempool.remove(&txn.id())?;
 
         self.confirm_txn(txn).await?;
 
         Ok(())
     }
 
+    /// Returns the txns of a rolled back block to the mempool, provided they
+    /// still pass validation against current state. Each resurrected txn is
+    /// placed by the time it was first admitted, so it keeps its priority
+    pub fn resurrect_txns(
+        &mut self,
+        validator: &TxnValidator,
+        block_hash: BlockHash,
+        txns: Vec<TransactionKind>,
+    ) -> Result<Vec<TxnStatusChange>> {
+        let read_handle = self.database.read_handle();
+        let account_state = read_handle.state_store_values();
+
+        let mut records = vec![];
+        let mut status_changes = vec![];
+
+        for txn in txns {
+            let digest = txn.id();
+
+            // NOTE: the validator expects the sender's account to be present
+            let validated = if account_state.contains_key(&txn.sender_address()) {
+                validator
+                    .validate(&account_state, &txn)
+                    .map_err(|err| err.to_string())
+            } else {
+                Err(format!("account {} not found", txn.sender_address()))
+            };
+
+            let status = match validated {
+                Ok(()) => {
+                    let admitted_at = read_handle
+                        .get_txn_receipt(&digest)
+                        .ok()
+                        .flatten()
+                        .and_then(|receipt| receipt.admitted_at)
+                        .unwrap_or_else(|| txn.timestamp());
+
+                    records.push(TxnRecord {
+                        added_timestamp: admitted_at,
+                        ..TxnRecord::new(txn)
+                    });
+
+                    TxnResurrectionStatus::Resurrected
+                },
+                Err(reason) => {
+                    info!("Dropping txn {digest} of rolled back block {block_hash}: {reason}");
+
+                    TxnResurrectionStatus::Dropped { reason }
+                },
+            };
+
+            status_changes.push(TxnStatusChange {
+                digest,
+                block_hash: block_hash.clone(),
+                status,
+            });
+        }
+
+        self.mempool.resurrect(records)?;
+
+        Ok(status_changes)
+    }
+
     pub fn handle_harvester_public_key_received(&mut self, public_key_set: PublicKeySet) {
         self.dag.set_harvester_pubkeys(public_key_set)
     }
 
+    /// Persists a validator's vote on a block for later audits
+    pub fn record_certification_vote(
+        &mut self,
+        block_hash: BlockHash,
+        vote: CertificationVote,
+    ) -> Result<()> {
+        let voter = vote.voter.clone();
+
+        if self
+            .database
+            .record_certification_vote(block_hash.clone(), vote)?
+            == RecordOutcome::Conflicting
+        {
+            telemetry::warn!("{voter} cast conflicting votes on block {block_hash}");
+        }
+
+        Ok(())
+    }
+
+    /// Persists a validator's partial signature over a block, so that which
+    /// validators took part in certifying it can be audited later
+    pub fn record_partial_signature(
+        &mut self,
+        block_hash: BlockHash,
+        signer: NodeId,
+        public_key_share: Vec<u8>,
+        signature: RawSignature,
+    ) -> Result<()> {
+        let partial_signature = PartialSignatureRecord {
+            signer: signer.clone(),
+            public_key_share,
+            signature,
+        };
+
+        if self
+            .database
+            .record_partial_signature(block_hash.clone(), partial_signature)?
+            == RecordOutcome::Conflicting
+        {
+            telemetry::warn!(
+                "{signer} produced conflicting partial signatures over block {block_hash}"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Persists the certificate a block was certified with
+    pub fn record_block_certificate(&mut self, certificate: Certificate) -> Result<()> {
+        Ok(self.database.record_certificate(certificate)?)
+    }
+
+    /// Persists the quorum this node formed at the start of an epoch
+    pub fn record_quorum(&mut self, record: QuorumRecord) -> Result<()> {
+        Ok(self.database.record_quorum(record)?)
+    }
+
+    /// Indexes a transaction certified by a farmer quorum. Returns false if
+    /// it was already certified, in which case the new certificate should be
+    /// discarded
+    pub fn record_certified_txn(&mut self, key: CertifiedTxnKey) -> Result<bool> {
+        Ok(self.database.record_certified_txn(key)?)
+    }
+
+    /// Persists how a transaction was certified, so it shows up in the
+    /// transaction's trace
+    pub fn record_txn_certification(
+        &mut self,
+        digest: TransactionDigest,
+        certification: TxnCertificationRecord,
+    ) -> Result<()> {
+        Ok(self
+            .database
+            .record_txn_certification(digest, certification)?)
+    }
+
+    /// Persists which proposal block each transaction consolidated by a
+    /// convergence block came from
+    pub fn record_txn_inclusions(&mut self, block: &ConvergenceBlock) -> Result<()> {
+        let included_at = chrono::Utc::now().timestamp();
+
+        for (proposal_block_hash, digests) in block.txns.iter() {
+            for digest in digests.iter() {
+                let inclusion = TxnInclusionRecord {
+                    included_at,
+                    proposal_block_hash: proposal_block_hash.clone(),
+                    convergence_block_hash: block.hash.clone(),
+                    round: block.header.round,
+                };
+
+                self.database
+                    .record_txn_inclusion(digest.clone(), inclusion)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Persists the time a round reached a consensus milestone, so its
+    /// latency can be looked into later
+    pub fn record_round_milestone(
+        &mut self,
+        round: Round,
+        milestone: RoundMilestone,
+    ) -> Result<()> {
+        let at = chrono::Utc::now().timestamp_millis();
+
+        Ok(self.database.record_round_milestone(round, milestone, at)?)
+    }
+
+    pub fn is_txn_certified(&self, digest: &TransactionDigest) -> bool {
+        self.read_handle()
+            .certified_txn_index()
+            .is_certified(digest)
+    }
+
+    /// Returns the hashes of the convergence blocks, among the `lookback` most
+    /// recent ones, for which no certificate has been recorded
+    pub fn uncertified_convergence_blocks(&self, lookback: usize) -> Vec<BlockHash> {
+        self.dag
+            .recent_convergence_blocks(lookback)
+            .into_iter()
+            .filter(|block| block.certificate.is_none())
+            .map(|block| block.hash)
+            .filter(|block_hash| !self.is_block_certified(block_hash))
+            .collect()
+    }
+
+    /// Returns whether a certificate has been recorded for a block
+    pub fn is_block_certified(&self, block_hash: &BlockHash) -> bool {
+        self.read_handle()
+            .get_block_certification(block_hash)
+            .map(|certification| certification.is_certified())
+            .unwrap_or(false)
+    }
+
+    /// Repairs a corrupted record found by the chain data scrubber, if it is
+    /// the certification of one of the `lookback` most recent convergence
+    /// blocks. Returns false if the record can't be repaired.
+    ///
+    /// The corrupted record is discarded. A certificate the block carries is
+    /// recorded again right away, otherwise the block is left uncertified for
+    /// the next certificate reconciliation to recover from peers
+    pub fn repair_corrupt_record(
+        &mut self,
+        record: &CorruptRecord,
+        lookback: usize,
+    ) -> Result<bool> {
+        let Some(block_hash) = record.certified_block() else {
+            return Ok(false);
+        };
+
+        let Some(block) = self
+            .dag
+            .recent_convergence_blocks(lookback)
+            .into_iter()
+            .find(|block| block.hash == block_hash)
+        else {
+            return Ok(false);
+        };
+
+        self.database.discard_corrupt_record(record)?;
+
+        if let Some(certificate) = block.certificate {
+            self.database.record_certificate(certificate)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Returns the raw chain record stored under `key`, if peers are served
+    /// records of `store`
+    pub fn get_chain_record(&self, store: ScrubbedStore, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.database.get_record(store, key)?)
+    }
+
+    /// Replaces a corrupted record with a copy a peer sent. Returns false if
+    /// the copy doesn't hash to what the record was committed to
+    pub fn restore_corrupt_record(&mut self, record: &CorruptRecord, value: &[u8]) -> Result<bool> {
+        Ok(self.database.restore_corrupt_record(record, value)?)
+    }
+
+    /// Hashes the `lookback` most recent convergence blocks and the proposal
+    /// blocks they reference again, returning the hashes of those that no
+    /// longer hash to the hash they carry
+    pub fn corrupt_dag_blocks(&self, lookback: usize) -> Vec<BlockHash> {
+        let mut corrupt = Vec::new();
+
+        for convergence in self.dag.recent_convergence_blocks(lookback) {
+            corrupt.extend(
+                self.dag
+                    .proposal_blocks(&convergence.header.ref_hashes)
+                    .into_iter()
+                    .filter(|proposal| !proposal.matches_hash())
+                    .map(|proposal| proposal.hash),
+            );
+
+            if !convergence.matches_hash() {
+                corrupt.push(convergence.hash);
+            }
+        }
+
+        corrupt
+    }
+
+    /// Returns the recorded certificates of the given blocks, skipping blocks
+    /// that haven't been certified
+    pub fn get_block_certificates(&self, block_hashes: &[BlockHash]) -> Vec<Certificate> {
+        let read_handle = self.read_handle();
+
+        block_hashes
+            .iter()
+            .filter_map(|block_hash| read_handle.get_block_certification(block_hash).ok())
+            .filter_map(|certification| certification.certificate)
+            .collect()
+    }
+
+    /// Checks a certificate received from a peer against the DAG and the
+    /// harvester quorum's public key set
+    pub fn verify_block_certificate(&self, certificate: &Certificate) -> bool {
+        self.dag.verify_certificate(certificate)
+    }
+
     pub fn get_claims(&self, claim_hashes: Vec<ClaimHash>) -> Result<Claims> {
         Ok(self
             .database
@@ -387,17 +1200,58 @@ impl StateManager {
             .collect())
     }
 
+    /// Returns the claim of `node_id`
+    pub fn get_claim(&self, node_id: &NodeId) -> Result<Claim> {
+        self.database
+            .claim_store_factory()
+            .handle()
+            .get_by_node_id(node_id)
+            .map_err(|_| NodeError::NotFound(format!("claim for node {node_id}")))
+    }
+
+    /// Takes the claim of a validator that left the validator set out of
+    /// future elections, locking its stake until `unlocks_at`.
+    pub fn begin_claim_unbonding(&mut self, node_id: &NodeId, unlocks_at: Epoch) -> Result<()> {
+        let mut claim = self.get_claim(node_id)?;
+
+        claim.begin_unbonding(unlocks_at);
+
+        self.database.insert_claim(claim).map_err(NodeError::from)
+    }
+
+    /// Returns the validator registrations applied blocks bonded stake for
+    /// since the last call, in the order they were bonded
+    pub fn take_bonded_registrations(&mut self) -> Vec<BondedRegistration> {
+        std::mem::take(&mut self.bonded_registrations)
+    }
+
+    /// Persists claims the blocks applied updated, replacing their previous
+    /// versions
+    pub fn update_claims(&mut self, claims: Vec<Claim>) -> Result<()> {
+        for claim in claims {
+            self.database.insert_claim(claim)?;
+        }
+
+        Ok(())
+    }
+
     pub fn update_account(&mut self, update_args: UpdateArgs) -> Result<()> {
         self.database
             .update_account(update_args)
-            .map_err(|err| NodeError::Other(err.to_string()))
+            .map_err(NodeError::from)
     }
 
     pub fn get_account(&self, address: &Address) -> Result<Account> {
         let handle = self.database.state_store_factory().handle();
-        handle
-            .get(address)
-            .map_err(|err| NodeError::Other(err.to_string()))
+        handle.get(address).map_err(NodeError::from)
+    }
+
+    /// Returns the native token balance of an account, or zero if it doesn't
+    /// exist yet
+    pub(super) fn get_balance(&self, address: &Address) -> u128 {
+        self.get_account(address)
+            .map(|account| account.balance())
+            .unwrap_or_default()
     }
 }
 
@@ -1,36 +1,67 @@
+mod archive;
+mod checkpoints;
 mod dag;
+mod delegation;
+mod dust;
+mod earnings;
+mod eligibility;
+mod epoch_summaries;
+mod halt;
+mod inclusion_lists;
+mod invariants;
 mod manager;
+mod parameters;
+mod registration;
 mod utils;
 
 pub use dag::*;
+pub use eligibility::EligibilityPolicy;
+pub use inclusion_lists::InclusionListLedger;
 pub use manager::*;
+pub use registration::BondedRegistration;
 
 #[cfg(test)]
 mod tests {
     use std::{
+        collections::{BTreeMap, HashMap},
         env,
         net::{IpAddr, Ipv4Addr, SocketAddr},
         sync::{Arc, RwLock},
     };
 
-    use block::{Block, BlockHash};
+    use block::{Block, BlockHash, Certificate};
     use bulldag::{graph::BullDag, vertex::Vertex};
+    use dkg_engine::test_utils::{generate_dkg_engine_with_states, threshold_sign};
+    use events::TxnResurrectionStatus;
     use integral_db::LeftRightTrie;
     use mempool::LeftRightMempool;
     use miner::test_helpers::{create_address, create_claim};
-    use primitives::Address;
+    use primitives::{
+        Address, ChainSpec, DelegationParams, DustDestination, DustPolicyParams,
+        HeaderFormatParams, ProtocolParameter, QuorumKind, QuorumPubkey,
+    };
     use serial_test::serial;
     use storage::vrrbdb::types::*;
-    use storage::vrrbdb::{RocksDbAdapter, VrrbDb, VrrbDbConfig};
+    use storage::vrrbdb::{QuorumRecord, TrieDbAdapter, VrrbDb, VrrbDbConfig};
     use theater::{Actor, ActorImpl, ActorState, Handler};
     use tokio::sync::mpsc::channel;
-    use vrrb_core::transactions::TransactionKind;
+    use validator::txn_validator::TxnValidator;
+    use vrrb_config::{CheckpointConfig, InvariantCheckMode, InvariantsConfig};
+    use vrrb_core::transactions::{
+        DelegationAction, ParameterChangeProposal, Transaction, TransactionKind,
+    };
     use vrrb_core::{account::Account, claim::Claim, keypair::KeyPair};
 
-    use super::*;
-    use crate::test_utils::{
-        create_blank_certificate, create_keypair, produce_accounts, produce_convergence_block,
-        produce_genesis_block, produce_proposal_blocks,
+    use super::{earnings::BlockCredits, invariants::InvariantViolation, *};
+    use crate::{
+        test_utils::{
+            create_blank_certificate, create_delegation_txn, create_keypair,
+            create_parameter_change_txn, create_register_validator_txn, create_txn_from_accounts,
+            produce_accounts, produce_convergence_block, produce_convergence_block_with_roots,
+            produce_convergence_block_with_state_root, produce_genesis_block,
+            produce_proposal_blocks,
+        },
+        NodeError,
     };
 
     #[tokio::test]
@@ -55,6 +86,8 @@ mod tests {
             database: db,
             dag: dag.clone(),
             claim,
+            chain_spec: ChainSpec::default(),
+            invariants_config: InvariantsConfig::default(),
         });
 
         state_module
@@ -90,6 +123,8 @@ mod tests {
             database: db,
             claim,
             dag: dag.clone(),
+            chain_spec: ChainSpec::default(),
+            invariants_config: InvariantsConfig::default(),
         };
         let mut state_module = StateManager::new(state_config);
         let state_res = state_module.extend_accounts(accounts.clone());
@@ -122,7 +157,10 @@ mod tests {
                 .for_each(|(source, reference)| guard.add_edge((source, reference)));
         }
 
-        let block_hash = produce_convergence_block(dag).unwrap();
+        let block_hash = produce_convergence_block_with_state_root(dag, |block| {
+            state_module.projected_state_root_hash(block).unwrap()
+        })
+        .unwrap();
         state_module.update_state(block_hash).unwrap();
 
         state_module.commit();
@@ -139,4 +177,810 @@ mod tests {
             assert_eq!(digests.get_stake().len(), 0);
         }
     }
+
+    /// Sets up a state manager whose DAG holds a genesis block and a round of
+    /// proposal blocks moving funds between its accounts
+    fn state_module_with_proposals(db_name: &str) -> (StateManager, StateDag) {
+        let db_config = VrrbDbConfig::default().with_path(std::env::temp_dir().join(db_name));
+        let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
+
+        let (_, pk) = create_keypair();
+        let addr = create_address(&pk);
+        let ip_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let claim = create_claim(&pk, &addr, ip_address, "signature".to_string());
+
+        let mut state_module = StateManager::new(StateManagerConfig {
+            mempool: LeftRightMempool::default(),
+            database: VrrbDb::new(db_config),
+            dag: dag.clone(),
+            claim,
+            chain_spec: ChainSpec::default(),
+            invariants_config: InvariantsConfig::default(),
+        });
+
+        let accounts = produce_accounts(5);
+        state_module.extend_accounts(accounts.clone()).unwrap();
+        state_module.commit();
+
+        let genesis = produce_genesis_block();
+        let gblock: Block = genesis.clone().into();
+        let gvtx: Vertex<Block, BlockHash> = gblock.into();
+
+        let proposals = produce_proposal_blocks(genesis.hash, accounts, 5, 5);
+        if let Ok(mut guard) = dag.write() {
+            guard.add_vertex(&gvtx);
+            proposals.into_iter().for_each(|pblock| {
+                let pblock: Block = pblock.into();
+                let pvtx: Vertex<Block, BlockHash> = pblock.into();
+                guard.add_edge((&gvtx, &pvtx));
+            });
+        }
+
+        (state_module, dag)
+    }
+
+    #[tokio::test]
+    async fn blocks_are_applied_when_they_commit_to_the_resulting_state_root() {
+        let (mut state_module, dag) = state_module_with_proposals("state-root-db");
+
+        let mut projected_state_root = String::new();
+        let block_hash = produce_convergence_block_with_state_root(dag, |block| {
+            projected_state_root = state_module.projected_state_root_hash(block).unwrap();
+            projected_state_root.clone()
+        })
+        .unwrap();
+
+        assert_ne!(
+            projected_state_root,
+            state_module.state_root_hash().unwrap()
+        );
+
+        state_module.update_state(block_hash).unwrap();
+
+        assert_eq!(
+            state_module.state_root_hash().unwrap(),
+            projected_state_root
+        );
+    }
+
+    #[tokio::test]
+    async fn ledger_invariants_hold_once_blocks_are_applied_and_catch_minted_tokens() {
+        let (mut state_module, dag) = state_module_with_proposals("invariants-db");
+        state_module.invariants_config = InvariantsConfig {
+            mode: InvariantCheckMode::Paranoid,
+            halt_on_violation: true,
+        };
+
+        let block_hash = produce_convergence_block_with_state_root(dag, |block| {
+            state_module.projected_state_root_hash(block).unwrap()
+        })
+        .unwrap();
+        state_module.update_state(block_hash).unwrap();
+
+        assert!(!state_module.halted);
+
+        let (address, mut account) = state_module
+            .read_handle()
+            .state_store_values()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        let previous = account.balance();
+        let previous_balances = HashMap::from([(address.clone(), previous)]);
+
+        account.credits += 100;
+        state_module
+            .extend_accounts(vec![(address, Some(account))])
+            .unwrap();
+        state_module.commit();
+
+        let violations = state_module
+            .check_invariants(&previous_balances, 0, 0, &[])
+            .unwrap();
+
+        assert_eq!(
+            violations,
+            vec![InvariantViolation::SupplyNotConserved {
+                previous,
+                issued: 0,
+                current: previous + 100,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn dust_accounts_are_swept_into_the_pool_at_the_start_of_an_epoch() {
+        let (mut state_module, _) = state_module_with_proposals("dust-db");
+
+        let mut accounts = state_module.read_handle().state_store_values();
+        let mut addresses: Vec<Address> = accounts.keys().cloned().collect();
+        addresses.sort();
+
+        let (pool, active) = (addresses[0].clone(), addresses[1].clone());
+        let dust = addresses[2..].to_vec();
+
+        let genesis_epoch = state_module.chain_spec.genesis_epoch;
+        state_module.chain_spec.epoch_length = 10;
+        state_module.chain_spec.dust = DustPolicyParams {
+            activation_epoch: Some(genesis_epoch),
+            threshold: u128::MAX,
+            inactive_epochs: 2,
+            destination: DustDestination::Pool(pool.clone()),
+        };
+
+        let mut header = produce_genesis_block().header;
+        header.epoch = genesis_epoch + 2;
+        header.block_height = state_module.chain_spec.epoch_start(header.epoch);
+
+        let pool_balance = accounts[&pool].balance();
+        let dust_balance: u128 = dust.iter().map(|address| accounts[address].balance()).sum();
+
+        // NOTE: no block touched the accounts since the policy took effect,
+        // so their allowed inactivity ends now
+        let mut expiring = state_module.expiring_addresses(&header);
+        expiring.sort();
+        assert_eq!(expiring, addresses);
+
+        let changes =
+            state_module.apply_dust_policy(&header, &[active.clone()], &expiring, &mut accounts);
+
+        assert_eq!(changes.swept, dust.len());
+        assert_eq!(changes.burned, 0);
+        assert!(dust.iter().all(|address| !accounts.contains_key(address)));
+        assert_eq!(accounts[&pool].balance(), pool_balance + dust_balance);
+        assert_eq!(accounts[&active].last_active_epoch(), Some(header.epoch));
+
+        // NOTE: only the first block of an epoch sweeps dust
+        header.block_height += 1;
+        assert!(state_module.expiring_addresses(&header).is_empty());
+
+        let changes = state_module.apply_dust_policy(&header, &[], &expiring, &mut accounts);
+        assert_eq!(changes.swept, 0);
+
+        // NOTE: accounts inactive for longer were checked when their allowed
+        // inactivity ended
+        header.epoch += 1;
+        header.block_height = state_module.chain_spec.epoch_start(header.epoch);
+        assert!(state_module.expiring_addresses(&header).is_empty());
+    }
+
+    #[tokio::test]
+    async fn delegated_stake_shares_in_the_rewards_of_its_validator_until_it_unbonds() {
+        let (mut state_module, _) = state_module_with_proposals("delegation-db");
+
+        let mut accounts = state_module.read_handle().state_store_values();
+        let mut addresses: Vec<Address> = accounts.keys().cloned().collect();
+        addresses.sort();
+
+        let (validator, delegator) = (addresses[0].clone(), addresses[1].clone());
+        let validator_balance = accounts[&validator].balance();
+        let delegator_balance = accounts[&delegator].balance();
+
+        let genesis_epoch = state_module.chain_spec.genesis_epoch;
+        state_module.chain_spec.epoch_length = 10;
+        state_module.chain_spec.staking.unbonding_period = 1;
+        state_module.chain_spec.delegation = DelegationParams {
+            activation_epoch: Some(genesis_epoch),
+            commission_percent: 10,
+            min_delegation: 1,
+        };
+
+        let mut header = produce_genesis_block().header;
+        header.epoch = genesis_epoch + 1;
+        header.block_height = state_module.chain_spec.epoch_start(header.epoch) + 1;
+        header.block_reward.amount = 1_000;
+        header.miner_claim.address = validator.clone();
+
+        let node_id = header.miner_claim.node_id.clone();
+        let mut claims = HashMap::from([(node_id.clone(), header.miner_claim.clone())]);
+
+        let delegate = create_delegation_txn(
+            delegator.clone(),
+            node_id.clone(),
+            DelegationAction::Delegate,
+            400,
+            1,
+        );
+
+        let changes =
+            state_module.apply_delegations(&header, &[delegate], &mut claims, &mut accounts);
+
+        assert_eq!(changes.bonded, 400);
+        assert_eq!(accounts[&delegator].balance(), delegator_balance - 400);
+        assert_eq!(claims[&node_id].total_stake(), 400);
+        assert_eq!(claims[&node_id].accrued_reward(), 1_000);
+        assert_eq!(changes.block_reward, Some((node_id.clone(), 1_000)));
+
+        // NOTE: the rewards of an epoch are split by the stakes bonded when it
+        // ends, before the txns of the next epoch's first block
+        header.epoch += 1;
+        header.block_height = state_module.chain_spec.epoch_start(header.epoch);
+        header.block_reward.amount = 0;
+
+        let undelegate = create_delegation_txn(
+            delegator.clone(),
+            node_id.clone(),
+            DelegationAction::Undelegate,
+            400,
+            2,
+        );
+
+        let changes =
+            state_module.apply_delegations(&header, &[undelegate], &mut claims, &mut accounts);
+
+        assert_eq!(changes.credited, 1_000);
+        assert_eq!(accounts[&validator].balance(), validator_balance + 100);
+        assert_eq!(
+            accounts[&delegator].balance(),
+            delegator_balance - 400 + 900
+        );
+        assert_eq!(claims[&node_id].total_stake(), 0);
+
+        header.epoch += 1;
+        header.block_height = state_module.chain_spec.epoch_start(header.epoch);
+
+        let changes = state_module.apply_delegations(&header, &[], &mut claims, &mut accounts);
+
+        assert_eq!(changes.credited, 400);
+        assert_eq!(accounts[&delegator].balance(), delegator_balance + 900);
+        assert!(claims[&node_id].undelegations().is_empty());
+    }
+
+    #[tokio::test]
+    async fn validator_registrations_bond_their_stake_to_the_registrants_claim() {
+        let (mut state_module, _) = state_module_with_proposals("registration-db");
+
+        let mut accounts = state_module.read_handle().state_store_values();
+        let mut addresses: Vec<Address> = accounts.keys().cloned().collect();
+        addresses.sort();
+
+        let (registrant, other) = (addresses[0].clone(), addresses[1].clone());
+        let registrant_balance = accounts[&registrant].balance();
+        let other_balance = accounts[&other].balance();
+
+        state_module.chain_spec.staking.min_stake_validator = 100;
+
+        let mut header = produce_genesis_block().header;
+        let mut claim = header.miner_claim.clone();
+        claim.address = registrant.clone();
+
+        let node_id = claim.node_id.clone();
+        let mut claims = HashMap::from([(node_id.clone(), claim)]);
+
+        let registration = |sender: &Address, stake: u128| {
+            let mut registration = create_register_validator_txn(node_id.clone(), stake);
+            registration.sender_address = sender.clone();
+            registration
+        };
+
+        // NOTE: only the claim's owner can bond stake to it, at least the minimum
+        // validator stake, and only once
+        let registrations = [
+            registration(&other, 400),
+            registration(&registrant, 99),
+            registration(&registrant, 400),
+            registration(&registrant, 400),
+        ];
+
+        let changes =
+            state_module.apply_registrations(&header, &registrations, &mut claims, &mut accounts);
+
+        assert_eq!(changes.bonded, 400);
+        assert_eq!(changes.registrations.len(), 1);
+        assert_eq!(changes.registrations[0].registration, registrations[2]);
+        assert_eq!(accounts[&registrant].balance(), registrant_balance - 400);
+        assert_eq!(accounts[&other].balance(), other_balance);
+        assert_eq!(claims[&node_id].get_stake(), 400);
+        assert_eq!(changes.claims, vec![claims[&node_id].clone()]);
+
+        // NOTE: the stake is only returned at the start of the epoch it unlocks at
+        let unlocks_at = state_module.chain_spec.genesis_epoch + 2;
+        claims
+            .get_mut(&node_id)
+            .unwrap()
+            .begin_unbonding(unlocks_at);
+
+        state_module.chain_spec.epoch_length = 10;
+        for epoch in [unlocks_at - 1, unlocks_at] {
+            header.epoch = epoch;
+            header.block_height = state_module.chain_spec.epoch_start(epoch);
+
+            let changes =
+                state_module.apply_registrations(&header, &[], &mut claims, &mut accounts);
+            assert_eq!(changes.released, if epoch == unlocks_at { 400 } else { 0 });
+        }
+
+        assert_eq!(accounts[&registrant].balance(), registrant_balance);
+        assert_eq!(claims[&node_id].get_stake(), 0);
+    }
+
+    #[tokio::test]
+    async fn earnings_are_attributed_to_the_validators_holding_the_accounts_credited() {
+        let (mut state_module, _) = state_module_with_proposals("earnings-db");
+
+        let mut addresses: Vec<Address> = state_module
+            .read_handle()
+            .state_store_values()
+            .into_keys()
+            .collect();
+        addresses.sort();
+
+        let (validator, holder) = (addresses[0].clone(), addresses[1].clone());
+
+        let mut header = produce_genesis_block().header;
+        header.epoch = 3;
+        header.miner_claim.address = validator.clone();
+
+        let node_id = header.miner_claim.node_id.clone();
+        state_module
+            .update_claims(vec![header.miner_claim.clone()])
+            .unwrap();
+
+        let credits = BlockCredits {
+            fee_shares: BTreeMap::from([(validator.clone(), 30), (holder, 70)]),
+            rewards: BTreeMap::new(),
+        };
+
+        state_module.record_validator_earnings(
+            &header,
+            &"block-1".to_string(),
+            &credits,
+            Some((node_id.clone(), 1_000)),
+            &BTreeMap::from([(node_id.clone(), 250)]),
+        );
+
+        // NOTE: the account without a claim isn't a validator's, so its fees
+        // aren't accounted for
+        let earnings = state_module
+            .read_handle()
+            .validator_earnings(&node_id, 0..=10);
+
+        assert_eq!(earnings.len(), 1);
+        assert_eq!(earnings[0].epoch, 3);
+        assert_eq!(earnings[0].block_rewards, 1_000);
+        assert_eq!(earnings[0].fee_shares, 30);
+        assert_eq!(earnings[0].penalties, 250);
+        assert_eq!(earnings[0].net(), 780);
+    }
+
+    #[tokio::test]
+    async fn checkpoints_are_taken_of_blocks_certified_by_a_recorded_harvester_quorum() {
+        let (mut state_module, dag) = state_module_with_proposals("checkpoints-db");
+        let block_hash = produce_convergence_block(dag).unwrap();
+        let header = state_module
+            .dag
+            .convergence_block(&block_hash)
+            .unwrap()
+            .header;
+
+        let dkg_engines = generate_dkg_engine_with_states().await;
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+
+        let quorum_signature = |payload: &[u8]| {
+            let signature = threshold_sign(&dkg_engines, payload);
+
+            hex::encode(signature.to_bytes())
+        };
+
+        let certificate = Certificate {
+            signature: quorum_signature(&hex::decode(&block_hash).unwrap()),
+            inauguration: None,
+            root_hash: String::new(),
+            next_root_hash: header.state_root.clone(),
+            block_hash: block_hash.clone(),
+        };
+
+        let config = CheckpointConfig {
+            enabled: true,
+            interval_rounds: 1,
+        };
+
+        // NOTE: the node can't vouch for a quorum it has no record of
+        assert_eq!(
+            state_module.take_checkpoint(&certificate, &config).unwrap(),
+            None
+        );
+
+        state_module
+            .record_quorum(QuorumRecord {
+                epoch: header.epoch,
+                quorum_kind: QuorumKind::Harvester,
+                members: dkg_engines[0].dkg_state.peer_public_keys_owned(),
+                group_public_key: QuorumPubkey::from(public_key_set.public_key()),
+                threshold: 1,
+                inauguration: None,
+            })
+            .unwrap();
+
+        let checkpoint = state_module
+            .take_checkpoint(&certificate, &config)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(checkpoint.round, header.round);
+        assert_eq!(checkpoint.state_root, header.state_root);
+        assert_eq!(
+            state_module.read_handle().latest_checkpoint(),
+            Some(checkpoint)
+        );
+
+        let forged_certificate = Certificate {
+            signature: quorum_signature(b"another block"),
+            ..certificate
+        };
+
+        assert!(state_module
+            .take_checkpoint(&forged_certificate, &config)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn epoch_summaries_are_taken_of_the_last_certified_block_of_an_epoch() {
+        let (mut state_module, dag) = state_module_with_proposals("epoch-summaries-db");
+        let block_hash = produce_convergence_block(dag).unwrap();
+        let block = state_module.dag.convergence_block(&block_hash).unwrap();
+        let header = block.header.clone();
+
+        let dkg_engines = generate_dkg_engine_with_states().await;
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+
+        let quorum_signature = |payload: &[u8]| {
+            let signature = threshold_sign(&dkg_engines, payload);
+
+            hex::encode(signature.to_bytes())
+        };
+
+        let certificate = Certificate {
+            signature: quorum_signature(&hex::decode(&block_hash).unwrap()),
+            inauguration: None,
+            root_hash: String::new(),
+            next_root_hash: header.state_root.clone(),
+            block_hash: block_hash.clone(),
+        };
+
+        state_module
+            .record_quorum(QuorumRecord {
+                epoch: header.epoch,
+                quorum_kind: QuorumKind::Harvester,
+                members: dkg_engines[0].dkg_state.peer_public_keys_owned(),
+                group_public_key: QuorumPubkey::from(public_key_set.public_key()),
+                threshold: 1,
+                inauguration: None,
+            })
+            .unwrap();
+
+        // NOTE: the block isn't the last of its epoch until epochs are made
+        // to end with it
+        state_module.chain_spec.genesis_epoch = header.epoch;
+        state_module.chain_spec.epoch_length = header.block_height + 2;
+        assert_eq!(state_module.take_epoch_summary(&certificate).unwrap(), None);
+
+        state_module.chain_spec.epoch_length = header.block_height + 1;
+
+        let node_id = header.miner_claim.node_id.clone();
+        state_module.record_validator_earnings(
+            &header,
+            &block_hash,
+            &BlockCredits::default(),
+            Some((node_id, 1_000)),
+            &BTreeMap::new(),
+        );
+
+        let summary = state_module
+            .take_epoch_summary(&certificate)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(summary.epoch, header.epoch);
+        assert_eq!(summary.round, header.round);
+        assert_eq!(summary.parent_hash, String::new());
+        assert_eq!(summary.txn_count, block.txn_id_set().len() as u64);
+        assert_eq!(summary.block_rewards, 1_000);
+        assert_eq!(
+            state_module.read_handle().latest_epoch_summary(),
+            Some(summary)
+        );
+
+        let forged_certificate = Certificate {
+            signature: quorum_signature(b"another block"),
+            ..certificate
+        };
+
+        assert!(state_module
+            .take_epoch_summary(&forged_certificate)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn quorum_approved_parameter_changes_apply_from_their_effective_epoch() {
+        let (mut state_module, dag) = state_module_with_proposals("parameters-db");
+        let block_hash = produce_convergence_block(dag).unwrap();
+        let header = state_module
+            .dag
+            .convergence_block(&block_hash)
+            .unwrap()
+            .header;
+
+        let dkg_engines = generate_dkg_engine_with_states().await;
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+        let group_public_key = QuorumPubkey::from(public_key_set.public_key());
+
+        let approve = |proposal: ParameterChangeProposal| {
+            let signature = threshold_sign(&dkg_engines, proposal.payload_hash());
+
+            create_parameter_change_txn(
+                proposal,
+                group_public_key,
+                hex::encode(signature.to_bytes()),
+            )
+        };
+
+        let change = approve(ParameterChangeProposal {
+            parameter: ProtocolParameter::MaxTxnsPerProposal,
+            value: 250,
+            effective_epoch: header.epoch + 1,
+            reason: "ease proposal propagation".to_string(),
+        });
+
+        // NOTE: changes are only trusted once the harvester quorum is known
+        assert!(state_module
+            .record_parameter_change(&header, &block_hash, change.clone())
+            .is_err());
+
+        state_module.handle_harvester_public_key_received(public_key_set.clone());
+        state_module
+            .record_parameter_change(&header, &block_hash, change)
+            .unwrap();
+
+        let late_change = approve(ParameterChangeProposal {
+            parameter: ProtocolParameter::BaseFee,
+            value: 1,
+            effective_epoch: header.epoch,
+            reason: "too late".to_string(),
+        });
+        assert!(state_module
+            .record_parameter_change(&header, &block_hash, late_change)
+            .is_err());
+
+        let base = state_module.chain_spec().clone();
+        let read_handle = state_module.read_handle();
+
+        assert_eq!(read_handle.chain_spec_at(&base, header.epoch), base);
+        assert_eq!(
+            read_handle
+                .chain_spec_at(&base, header.epoch + 1)
+                .block_limits
+                .max_txns_per_proposal,
+            250
+        );
+
+        let history = read_handle.parameter_changes();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].block_hash, block_hash);
+        assert_eq!(history[0].round, header.round);
+    }
+
+    #[tokio::test]
+    async fn blocks_are_listed_by_round_range_with_their_proposals_and_certificates() {
+        let (mut state_module, dag) = state_module_with_proposals("block-range-db");
+        let block_hash = produce_convergence_block(dag).unwrap();
+        let block = state_module.dag.convergence_block(&block_hash).unwrap();
+        let round = block.header.round;
+
+        let blocks = state_module.get_blocks_by_round_range(round, round);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].convergence.hash, block_hash);
+        assert_eq!(blocks[0].proposals.len(), block.header.ref_hashes.len());
+        assert_eq!(blocks[0].certificate, None);
+
+        assert!(state_module
+            .get_blocks_by_round_range(round + 1, round + 10)
+            .is_empty());
+
+        let certificate = Certificate {
+            block_hash: block_hash.clone(),
+            ..create_blank_certificate(String::new())
+        };
+        state_module
+            .record_block_certificate(certificate.clone())
+            .unwrap();
+
+        let blocks = state_module.get_blocks_by_round_range(0, round);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].certificate, Some(certificate));
+    }
+
+    #[tokio::test]
+    async fn blocks_listing_txns_out_of_canonical_order_are_rejected() {
+        let (state_module, dag) = state_module_with_proposals("txn-order-db");
+        let block_hash = produce_convergence_block(dag).unwrap();
+        let convergence = state_module.dag.convergence_block(&block_hash).unwrap();
+        let proposal = state_module
+            .dag
+            .proposal_blocks(&convergence.header.ref_hashes)
+            .pop()
+            .unwrap();
+
+        let mut reversed_proposal = proposal.clone();
+        let mut txns: Vec<_> = reversed_proposal.txns.into_iter().collect();
+        txns.reverse();
+        reversed_proposal.txns = txns.into_iter().collect();
+
+        let mut reversed_convergence = convergence.clone();
+        let mut txns: Vec<_> = reversed_convergence.txns.into_iter().collect();
+        txns.reverse();
+        reversed_convergence.txns = txns.into_iter().collect();
+
+        for block in [
+            Block::Proposal { block: proposal },
+            Block::Convergence { block: convergence },
+        ] {
+            state_module.verify_txn_order(&block).unwrap();
+        }
+
+        for block in [
+            Block::Proposal {
+                block: reversed_proposal,
+            },
+            Block::Convergence {
+                block: reversed_convergence,
+            },
+        ] {
+            assert!(matches!(
+                state_module.verify_txn_order(&block),
+                Err(NodeError::InvalidBlock { .. })
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn blocks_committing_to_another_state_root_are_rejected() {
+        let (mut state_module, dag) = state_module_with_proposals("bad-state-root-db");
+        let pre_state_root = state_module.state_root_hash().unwrap();
+
+        let block_hash =
+            produce_convergence_block_with_state_root(dag, |_| "not-the-state-root".to_string())
+                .unwrap();
+
+        let err = state_module.update_state(block_hash.clone()).unwrap_err();
+
+        assert!(matches!(err, NodeError::InvalidBlock { hash, .. } if hash == block_hash));
+        assert_eq!(state_module.state_root_hash().unwrap(), pre_state_root);
+    }
+
+    #[tokio::test]
+    async fn blocks_must_commit_to_the_delegation_root_once_delegation_is_active() {
+        let delegation = DelegationParams {
+            activation_epoch: Some(0),
+            ..Default::default()
+        };
+
+        let (mut state_module, dag) = state_module_with_proposals("delegation-root-db");
+        state_module.chain_spec.delegation = delegation.clone();
+
+        let mut projected = ProjectedRoots::default();
+        let block_hash = produce_convergence_block_with_roots(dag, |block| {
+            projected = state_module.projected_roots(block).unwrap();
+            projected.clone()
+        })
+        .unwrap();
+
+        assert!(!projected.delegation_root.is_empty());
+        state_module.update_state(block_hash).unwrap();
+
+        let (mut state_module, dag) = state_module_with_proposals("bad-delegation-root-db");
+        state_module.chain_spec.delegation = delegation;
+        let pre_state_root = state_module.state_root_hash().unwrap();
+
+        let block_hash = produce_convergence_block_with_state_root(dag, |block| {
+            state_module.projected_state_root_hash(block).unwrap()
+        })
+        .unwrap();
+        let err = state_module.update_state(block_hash.clone()).unwrap_err();
+
+        assert!(matches!(err, NodeError::InvalidBlock { hash, .. } if hash == block_hash));
+        assert_eq!(state_module.state_root_hash().unwrap(), pre_state_root);
+    }
+
+    #[tokio::test]
+    async fn blocks_must_commit_to_a_state_root_once_it_is_required() {
+        let (mut legacy_module, dag) = state_module_with_proposals("legacy-state-root-db");
+        legacy_module.chain_spec.header_format = HeaderFormatParams {
+            state_root_activation_height: u128::MAX,
+            ..Default::default()
+        };
+
+        let block_hash = produce_convergence_block(dag).unwrap();
+        legacy_module.update_state(block_hash).unwrap();
+
+        let (mut state_module, dag) = state_module_with_proposals("required-state-root-db");
+        let pre_state_root = state_module.state_root_hash().unwrap();
+
+        let block_hash = produce_convergence_block(dag).unwrap();
+        let err = state_module.update_state(block_hash.clone()).unwrap_err();
+
+        assert!(matches!(err, NodeError::InvalidBlock { hash, .. } if hash == block_hash));
+        assert_eq!(state_module.state_root_hash().unwrap(), pre_state_root);
+    }
+
+    #[tokio::test]
+    async fn genesis_blocks_must_match_the_chain_spec() {
+        let db_config =
+            VrrbDbConfig::default().with_path(std::env::temp_dir().join("chain-spec-db"));
+        let db = VrrbDb::new(db_config);
+        let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
+
+        let keypair = KeyPair::random();
+        let pk = keypair.get_miner_public_key().clone();
+        let addr = create_address(&pk);
+        let ip_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let claim = create_claim(&pk, &addr, ip_address, "signature".to_string());
+
+        let mut chain_spec = ChainSpec::default();
+        chain_spec.genesis_hash = Some("not-the-genesis-hash".to_string());
+
+        let mut state_module = StateManager::new(StateManagerConfig {
+            mempool: LeftRightMempool::default(),
+            database: db,
+            dag,
+            claim,
+            chain_spec,
+            invariants_config: InvariantsConfig::default(),
+        });
+
+        let genesis: Block = produce_genesis_block().into();
+
+        assert!(state_module.handle_block_received(genesis).is_err());
+    }
+
+    #[tokio::test]
+    async fn rolled_back_txns_that_no_longer_validate_are_dropped() {
+        let db_config =
+            VrrbDbConfig::default().with_path(std::env::temp_dir().join("resurrection-db"));
+        let db = VrrbDb::new(db_config);
+        let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
+
+        let (_, pk) = create_keypair();
+        let addr = create_address(&pk);
+        let ip_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let claim = create_claim(&pk, &addr, ip_address, "signature".to_string());
+
+        let mut state_module = StateManager::new(StateManagerConfig {
+            mempool: LeftRightMempool::default(),
+            database: db,
+            dag,
+            claim,
+            chain_spec: ChainSpec::default(),
+            invariants_config: InvariantsConfig::default(),
+        });
+
+        let accounts = produce_accounts(2);
+        state_module.extend_accounts(accounts.clone()).unwrap();
+
+        // NOTE: the first sender is known but its txn is far too old, the second
+        // sender was never seen
+        let stale = create_txn_from_accounts(accounts[0].clone(), accounts[1].0.clone(), vec![]);
+        let unknown_sender = produce_accounts(1).remove(0);
+        let orphaned = create_txn_from_accounts(unknown_sender, accounts[1].0.clone(), vec![]);
+
+        let status_changes = state_module
+            .resurrect_txns(
+                &TxnValidator::new(),
+                "rolled-back".to_string(),
+                vec![stale.clone(), orphaned.clone()],
+            )
+            .unwrap();
+
+        assert_eq!(status_changes.len(), 2);
+        for (change, txn) in status_changes.iter().zip([&stale, &orphaned]) {
+            assert_eq!(change.digest, txn.id());
+            assert_eq!(change.block_hash, "rolled-back".to_string());
+            assert!(matches!(
+                change.status,
+                TxnResurrectionStatus::Dropped { .. }
+            ));
+        }
+        assert_eq!(state_module.mempool.size(), 0);
+    }
 }
@@ -0,0 +1,88 @@
+use block::{header::BlockHeader, BlockHash, ProposalBlock};
+use storage::vrrbdb::ParameterChangeRecord;
+use vrrb_core::transactions::{ParameterChange, TransactionKind};
+
+use crate::{NodeError, Result};
+
+use super::StateManager;
+
+/// Returns the parameter changes included by `proposals`, ordered by id so
+/// every node records them the same way
+pub(super) fn parameter_changes_in(proposals: &[ProposalBlock]) -> Vec<ParameterChange> {
+    let mut changes: Vec<ParameterChange> = proposals
+        .iter()
+        .flat_map(|block| block.txns.values())
+        .filter_map(|txn| match txn.txn() {
+            TransactionKind::ParameterChange(change) => Some(change),
+            _ => None,
+        })
+        .collect();
+
+    changes.sort_by(|a, b| a.id.cmp(&b.id));
+    changes.dedup_by(|a, b| a.id == b.id);
+
+    changes
+}
+
+impl StateManager {
+    /// Records the parameter changes included by `proposals` in the parameter
+    /// store. Changes that can't be recorded are left out and logged, see
+    /// [StateManager::record_parameter_change]
+    pub(super) fn record_parameter_changes(
+        &mut self,
+        header: &BlockHeader,
+        block_hash: &BlockHash,
+        proposals: &[ProposalBlock],
+    ) {
+        for change in parameter_changes_in(proposals) {
+            let id = change.id.clone();
+
+            if let Err(err) = self.record_parameter_change(header, block_hash, change) {
+                telemetry::warn!("skipping parameter change {id} of block {block_hash}: {err}");
+            }
+        }
+    }
+
+    /// Verifies that the harvester quorum signed a parameter change within
+    /// its parameter's bounds and records it, as included by the block at
+    /// `header`.
+    ///
+    /// Changes must take effect after the epoch of the block including them,
+    /// so no node has already read the parameter for that epoch.
+    pub fn record_parameter_change(
+        &mut self,
+        header: &BlockHeader,
+        block_hash: &BlockHash,
+        change: ParameterChange,
+    ) -> Result<()> {
+        let harvester_pubkey = self.dag.harvester_quorum_pubkey().ok_or_else(|| {
+            NodeError::Other("harvester quorum public key isn't known yet".to_string())
+        })?;
+
+        signer::parameters::verify_parameter_change(&change, &harvester_pubkey)
+            .map_err(|err| NodeError::Other(format!("parameter change doesn't verify: {err}")))?;
+
+        let effective_epoch = change.proposal.effective_epoch;
+        if effective_epoch <= header.epoch {
+            return Err(NodeError::Other(format!(
+                "parameter change takes effect at epoch {effective_epoch}, which already began"
+            )));
+        }
+
+        telemetry::info!(
+            "recording change of {} to {} from epoch {effective_epoch}: {}",
+            change.proposal.parameter,
+            change.proposal.value,
+            change.proposal.reason
+        );
+
+        self.database
+            .record_parameter_change(&ParameterChangeRecord {
+                change,
+                round: header.round,
+                block_hash: block_hash.clone(),
+            })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::{BTreeMap, HashMap};
+
+use block::{header::BlockHeader, ProposalBlock};
+use primitives::{Address, NodeId};
+use telemetry::info;
+use vrrb_core::{
+    account::{Account, AccountField},
+    claim::Claim,
+    transactions::{RegisterValidator, TransactionKind},
+};
+
+use super::StateManager;
+
+/// Changes validator registrations make to the state on top of the txns of a
+/// block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct RegistrationChanges {
+    /// Accounts registrations bonded stake from or unbonded stake returned to
+    pub post_images: Vec<(Address, Option<Account>)>,
+
+    /// Claims stake was bonded to or released from
+    pub claims: Vec<Claim>,
+
+    /// Tokens taken out of registrants' balances and bonded to their claims
+    pub bonded: u128,
+
+    /// Tokens returned to the owners of claims whose stake unlocked
+    pub released: u128,
+
+    /// Registrations whose stake was bonded, in the order they were applied
+    pub registrations: Vec<BondedRegistration>,
+}
+
+/// A validator registration whose stake a block bonded, which makes its node
+/// due for activation at the next epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BondedRegistration {
+    pub registration: RegisterValidator,
+
+    /// The claim the stake was bonded to
+    pub claim: Claim,
+}
+
+/// Returns the validator registrations included by `proposals`, ordered by
+/// sender and nonce so every node applies them the same way
+pub(super) fn registrations_in(proposals: &[ProposalBlock]) -> Vec<RegisterValidator> {
+    let mut registrations: Vec<RegisterValidator> = proposals
+        .iter()
+        .flat_map(|block| block.txns.values())
+        .filter_map(|txn| match txn.txn() {
+            TransactionKind::RegisterValidator(registration) => Some(registration),
+            _ => None,
+        })
+        .collect();
+
+    registrations.sort_by(|a, b| {
+        (&a.sender_address, a.nonce, &a.id).cmp(&(&b.sender_address, b.nonce, &b.id))
+    });
+    registrations.dedup_by(|a, b| a.id == b.id);
+
+    registrations
+}
+
+impl StateManager {
+    /// Applies `registrations` to `claims` and `accounts`, the state once the
+    /// txns of the block at `header` and its delegations are applied.
+    ///
+    /// If the block opens an epoch, the stake bonded to every claim whose
+    /// unbonding period is over is first returned to the claim's owner, see
+    /// [Claim::release_bonded_stake].
+    ///
+    /// A registration only moves its sender's funds to itself, so the stake
+    /// it names is then taken out of the sender's balance and bonded to the
+    /// claim of the node it registers.
+    ///
+    /// Registrations that can't be applied, for an unknown, unbonding or
+    /// already bonded claim, from an account other than the claim's, under
+    /// the minimum validator stake or over the sender's balance, are skipped.
+    ///
+    /// `accounts` must hold every sender, and the owner of every claim when
+    /// the block opens an epoch.
+    pub(super) fn apply_registrations(
+        &self,
+        header: &BlockHeader,
+        registrations: &[RegisterValidator],
+        claims: &mut HashMap<NodeId, Claim>,
+        accounts: &mut HashMap<Address, Account>,
+    ) -> RegistrationChanges {
+        let mut post_images = BTreeMap::new();
+        let mut changed_claims = BTreeMap::new();
+        let mut bonded = 0u128;
+        let mut released = 0u128;
+        let mut bonded_registrations = vec![];
+
+        if self.pays_out_rewards(header) {
+            let mut node_ids: Vec<NodeId> = claims.keys().cloned().collect();
+            node_ids.sort();
+
+            for node_id in node_ids {
+                let Some(claim) = claims.get_mut(&node_id) else {
+                    continue;
+                };
+
+                let amount = claim.release_bonded_stake(header.epoch);
+                if amount == 0 {
+                    continue;
+                }
+
+                let Some(account) = accounts.get_mut(&claim.address) else {
+                    continue;
+                };
+
+                if account.update_field(AccountField::Credits(amount)).is_err() {
+                    continue;
+                }
+
+                post_images.insert(claim.address.clone(), Some(account.clone()));
+                changed_claims.insert(node_id, claim.clone());
+                released = released.saturating_add(amount);
+            }
+        }
+
+        for registration in registrations {
+            let Some(claim) = claims.get_mut(&registration.node_id) else {
+                continue;
+            };
+
+            if claim.address != registration.sender_address
+                || claim.unbonding_until().is_some()
+                || claim.bonded_stake() > 0
+                || registration.stake < self.chain_spec.staking.min_stake_validator
+            {
+                continue;
+            }
+
+            let Some(account) = accounts.get_mut(&registration.sender_address) else {
+                continue;
+            };
+
+            if account
+                .update_field(AccountField::Debits(registration.stake))
+                .is_err()
+            {
+                continue;
+            }
+
+            post_images.insert(registration.sender_address.clone(), Some(account.clone()));
+            claim.bond(registration.stake);
+            changed_claims.insert(registration.node_id.clone(), claim.clone());
+            bonded = bonded.saturating_add(registration.stake);
+
+            bonded_registrations.push(BondedRegistration {
+                registration: registration.clone(),
+                claim: claim.clone(),
+            });
+        }
+
+        RegistrationChanges {
+            post_images: post_images.into_iter().collect(),
+            claims: changed_claims.into_values().collect(),
+            bonded,
+            released,
+            registrations: bonded_registrations,
+        }
+    }
+
+    /// Returns the claims `registrations` may change: those of the nodes they
+    /// register, the ones `proposals` introduce included, and every unbonding
+    /// claim if the block at `header` opens an epoch. `changed` claims,
+    /// already updated by the block, take precedence over the stored ones
+    pub(super) fn registration_claims(
+        &self,
+        header: &BlockHeader,
+        registrations: &[RegisterValidator],
+        proposals: &[ProposalBlock],
+        changed: &[Claim],
+    ) -> HashMap<NodeId, Claim> {
+        let releases_stake = self.pays_out_rewards(header);
+
+        if registrations.is_empty() && !releases_stake {
+            return HashMap::new();
+        }
+
+        let handle = self.database.claim_store_factory().handle();
+
+        let mut claims: HashMap<NodeId, Claim> = if releases_stake {
+            handle
+                .entries()
+                .into_iter()
+                .filter(|(node_id, claim)| {
+                    claim.unbonding_until().is_some()
+                        || registrations
+                            .iter()
+                            .any(|registration| registration.node_id == *node_id)
+                })
+                .collect()
+        } else {
+            registrations
+                .iter()
+                .filter_map(|registration| {
+                    let claim = handle.get_by_node_id(&registration.node_id).ok()?;
+
+                    Some((registration.node_id.clone(), claim))
+                })
+                .collect()
+        };
+
+        for claim in proposals.iter().flat_map(|block| block.claims.values()) {
+            claims
+                .entry(claim.node_id.clone())
+                .or_insert_with(|| claim.clone());
+        }
+
+        for claim in changed {
+            if claims.contains_key(&claim.node_id) {
+                claims.insert(claim.node_id.clone(), claim.clone());
+            }
+        }
+
+        claims
+    }
+
+    /// Applies the validator registrations included by `proposals` on top of
+    /// the txns and delegations of the block at `header` the state now
+    /// reflects, see [StateManager::apply_registrations]. The changes are
+    /// returned rather than written
+    pub(super) fn registration_changes(
+        &self,
+        header: &BlockHeader,
+        proposals: &[ProposalBlock],
+        changed: &[Claim],
+    ) -> RegistrationChanges {
+        let registrations = registrations_in(proposals);
+        let mut claims = self.registration_claims(header, &registrations, proposals, changed);
+
+        let handle = self.database.state_store_factory().handle();
+
+        let owners = registrations
+            .iter()
+            .map(|registration| registration.sender_address.clone())
+            .chain(claims.values().map(|claim| claim.address.clone()));
+
+        let mut accounts = owners
+            .filter_map(|address| {
+                let account = handle.get(&address).ok()?;
+
+                Some((address, account))
+            })
+            .collect();
+
+        let changes = self.apply_registrations(header, &registrations, &mut claims, &mut accounts);
+
+        if changes.released > 0 {
+            info!(
+                "Returned {} in unbonded validator stake at the start of epoch {}",
+                changes.released, header.epoch
+            );
+        }
+
+        changes
+    }
+}
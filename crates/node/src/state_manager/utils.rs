@@ -9,7 +9,7 @@ use bulldag::{graph::BullDag, vertex::Vertex};
 use ethereum_types::U256;
 use events::{Event, EventPublisher};
 use mempool::LeftRightMempool;
-use primitives::Address;
+use primitives::{Address, Amount};
 use storage::vrrbdb::types::*;
 use storage::vrrbdb::{StateStoreReadHandle, VrrbDb, VrrbDbReadHandle};
 use telemetry::info;
@@ -32,38 +32,37 @@ pub(super) fn get_update_args(updates: HashSet<StateUpdate>) -> HashSet<UpdateAr
 /// activity in a given round.
 pub(super) fn consolidate_update_args(
     updates: HashSet<UpdateArgs>,
-) -> HashMap<Address, UpdateArgs> {
+) -> Result<HashMap<Address, UpdateArgs>> {
     let mut consolidated_updates: HashMap<Address, UpdateArgs> = HashMap::new();
 
     for update in updates.into_iter() {
-        let address = update.address.clone();
+        let Some(existing_update) = consolidated_updates.get_mut(&update.address) else {
+            consolidated_updates.insert(update.address.clone(), update);
+            continue;
+        };
 
-        consolidated_updates
-            .entry(address)
-            .and_modify(|existing_update| {
-                existing_update.nonce = existing_update.nonce.max(update.nonce);
-                existing_update.credits = match (existing_update.credits, update.credits) {
-                    (Some(a), Some(b)) => Some(a + b),
-                    (a, None) => a,
-                    (_, b) => b,
-                };
-                existing_update.debits = match (existing_update.debits, update.debits) {
-                    (Some(a), Some(b)) => Some(a + b),
-                    (a, None) => a,
-                    (_, b) => b,
-                };
-                existing_update.storage = update.storage.clone(); // TODO: Update this to use the most recent value
-                existing_update.code = update.code.clone(); // TODO: Update this to use the most recent value
-                if let Some(digests) = update.digests.clone() {
-                    if let Some(ref mut existing_digests) = existing_update.digests {
-                        existing_digests.extend_all(digests);
-                    } else {
-                        existing_update.digests = Some(digests);
-                    }
-                }
-            })
-            .or_insert(update);
+        existing_update.nonce = existing_update.nonce.max(update.nonce);
+        existing_update.credits = sum_updates(existing_update.credits, update.credits)?;
+        existing_update.debits = sum_updates(existing_update.debits, update.debits)?;
+        existing_update.storage = update.storage.clone(); // TODO: Update this to use the most recent value
+        existing_update.code = update.code.clone(); // TODO: Update this to use the most recent value
+        if let Some(digests) = update.digests.clone() {
+            if let Some(ref mut existing_digests) = existing_update.digests {
+                existing_digests.extend_all(digests);
+            } else {
+                existing_update.digests = Some(digests);
+            }
+        }
     }
 
-    consolidated_updates
+    Ok(consolidated_updates)
+}
+
+/// Adds up the credits or debits of two updates to the same account
+fn sum_updates(existing: Option<u128>, update: Option<u128>) -> Result<Option<u128>> {
+    Ok(match (existing, update) {
+        (Some(a), Some(b)) => Some(Amount::new(a).checked_add(b)?.get()),
+        (a, None) => a,
+        (_, b) => b,
+    })
 }
@@ -7,17 +7,23 @@ use std::{
 };
 
 use async_trait::async_trait;
-use block::{Block, BlockHash, ClaimHash, GenesisBlock, InnerBlock, ProposalBlock};
+use block::{
+    Block, BlockHash, ClaimHash, ConvergenceBlock, GenesisBlock, InnerBlock, ProposalBlock,
+};
 use bulldag::{graph::BullDag, vertex::Vertex};
 
-use events::EventPublisher;
+use events::{Event, EventMessage, EventPublisher, EventSubscriber, DEFAULT_BUFFER};
+// NOTE: the miner crate is a dev-dependency too, so the node's own tests can
+// mine blocks whichever features it was built with
+#[cfg(any(test, feature = "mining"))]
 pub use miner::test_helpers::{create_address, create_claim, create_miner};
 use primitives::{
-    generate_account_keypair, Address, KademliaPeerId, NodeId, NodeType, QuorumKind, RawSignature,
-    Round, ValidatorSecretKey,
+    generate_account_keypair, Address, KademliaPeerId, NodeId, NodeType, QuorumKind, QuorumPubkey,
+    RawSignature, Round, ValidatorSecretKey,
 };
 use secp256k1::{Message, PublicKey, SecretKey};
 use storage::vrrbdb::Claims;
+use theater::{ActorState, Handler};
 use uuid::Uuid;
 use vrrb_config::{
     BootstrapQuorumConfig, NodeConfig, NodeConfigBuilder, QuorumMember, QuorumMembershipConfig,
@@ -28,7 +34,7 @@ use vrrb_rpc::rpc::{api::RpcApiClient, client::create_client};
 
 use crate::{
     data_store::DataStore, network::NetworkEvent, node_runtime::NodeRuntime,
-    state_reader::StateReader, Node, NodeError, Result,
+    state_manager::ProjectedRoots, state_reader::StateReader, Node, NodeError, Result,
 };
 
 pub fn create_mock_full_node_config() -> NodeConfig {
@@ -106,7 +112,7 @@ pub fn produce_accounts(n: usize) -> Vec<(Address, Option<Account>)> {
         .collect()
 }
 
-fn produce_random_claims(n: usize) -> HashSet<Claim> {
+pub fn produce_random_claims(n: usize) -> HashSet<Claim> {
     (0..n)
         .map(|_| {
             let kp = Keypair::random();
@@ -158,6 +164,7 @@ fn produce_random_txs(accounts: &Vec<(Address, Option<Account>)>) -> HashSet<Tra
         .collect()
 }
 
+#[cfg(any(test, feature = "mining"))]
 pub fn produce_genesis_block() -> GenesisBlock {
     let genesis = miner::test_helpers::mine_genesis();
     genesis.unwrap()
@@ -202,6 +209,7 @@ pub fn produce_proposal_blocks(
                         Vec::new(),
                         txn,
                         RawSignature::new(),
+                        None,
                         true,
                     );
 
@@ -222,6 +230,8 @@ pub fn produce_proposal_blocks(
                 0,
                 txn_list,
                 claim_list,
+                vec![],
+                vec![],
                 from,
                 keypair.get_miner_secret_key(),
             )
@@ -229,7 +239,37 @@ pub fn produce_proposal_blocks(
         .collect()
 }
 
+#[cfg(any(test, feature = "mining"))]
 pub fn produce_convergence_block(dag: Arc<RwLock<BullDag<Block, BlockHash>>>) -> Option<BlockHash> {
+    produce_convergence_block_with_state_root(dag, |_| String::new())
+}
+
+/// Mines a convergence block on top of the genesis block in `dag`,
+/// committing its header to the state root `state_root` returns for it
+#[cfg(any(test, feature = "mining"))]
+pub fn produce_convergence_block_with_state_root<F>(
+    dag: Arc<RwLock<BullDag<Block, BlockHash>>>,
+    state_root: F,
+) -> Option<BlockHash>
+where
+    F: FnOnce(&ConvergenceBlock) -> String,
+{
+    produce_convergence_block_with_roots(dag, |block| ProjectedRoots {
+        state_root: state_root(block),
+        ..Default::default()
+    })
+}
+
+/// Mines a convergence block on top of the genesis block in `dag`,
+/// committing its header to the roots `roots` returns for it
+#[cfg(any(test, feature = "mining"))]
+pub fn produce_convergence_block_with_roots<F>(
+    dag: Arc<RwLock<BullDag<Block, BlockHash>>>,
+    roots: F,
+) -> Option<BlockHash>
+where
+    F: FnOnce(&ConvergenceBlock) -> ProjectedRoots,
+{
     let keypair = Keypair::random();
     let mut miner = miner::test_helpers::create_miner_from_keypair(&keypair);
     miner.dag = dag.clone();
@@ -239,27 +279,33 @@ pub fn produce_convergence_block(dag: Arc<RwLock<BullDag<Block, BlockHash>>>) ->
         miner.last_block = Some(Arc::new(block));
     }
 
-    if let Ok(cblock) = miner.try_mine() {
-        if let Block::Convergence { ref block } = cblock.clone() {
-            let cvtx: Vertex<Block, String> = cblock.into();
-            let mut edges: Vec<(Vertex<Block, String>, Vertex<Block, String>)> = vec![];
-            if let Ok(guard) = dag.read() {
-                block.clone().get_ref_hashes().iter().for_each(|t| {
-                    if let Some(pvtx) = guard.get_vertex(t.clone()) {
-                        edges.push((pvtx.clone(), cvtx.clone()));
-                    }
-                });
-            }
+    if let Ok(Block::Convergence { mut block }) = miner.try_mine() {
+        let roots = roots(&block);
+        miner.commit_state_root(&mut block, roots.state_root);
 
-            if let Ok(mut guard) = dag.write() {
-                let edges = edges
-                    .iter()
-                    .map(|(source, reference)| (source, reference))
-                    .collect();
+        if !roots.delegation_root.is_empty() {
+            miner.commit_delegation_root(&mut block, roots.delegation_root);
+        }
 
-                guard.extend_from_edges(edges);
-                return Some(block.get_hash());
-            }
+        let cblock: Block = block.clone().into();
+        let cvtx: Vertex<Block, String> = cblock.into();
+        let mut edges: Vec<(Vertex<Block, String>, Vertex<Block, String>)> = vec![];
+        if let Ok(guard) = dag.read() {
+            block.clone().get_ref_hashes().iter().for_each(|t| {
+                if let Some(pvtx) = guard.get_vertex(t.clone()) {
+                    edges.push((pvtx.clone(), cvtx.clone()));
+                }
+            });
+        }
+
+        if let Ok(mut guard) = dag.write() {
+            let edges = edges
+                .iter()
+                .map(|(source, reference)| (source, reference))
+                .collect();
+
+            guard.extend_from_edges(edges);
+            return Some(block.get_hash());
         }
     }
 
@@ -271,6 +317,103 @@ pub fn create_keypair() -> (SecretKey, PublicKey) {
     kp.miner_kp
 }
 
+/// Creates a signed request for `node_id` to join the validator set, bonding
+/// `stake` tokens.
+pub fn create_register_validator_txn(node_id: NodeId, stake: u128) -> RegisterValidator {
+    let keypair = Keypair::random();
+    let (sk, pk) = keypair.miner_kp;
+
+    let mut txn = RegisterValidator::new(NewRegisterValidatorArgs {
+        timestamp: chrono::Utc::now().timestamp(),
+        sender_address: Address::new(pk),
+        sender_public_key: pk,
+        node_id,
+        validator_public_key: keypair.validator_kp.1,
+        stake,
+        signature: sk
+            .sign_ecdsa(Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb")),
+        nonce: 0,
+    });
+
+    txn.sign(&sk);
+
+    txn
+}
+
+/// Creates a signed request from `sender` for `node_id` to leave the
+/// validator set
+pub fn create_exit_validator_txn(sender: Address, node_id: NodeId) -> ExitValidator {
+    let (sk, pk) = create_keypair();
+
+    let mut txn = ExitValidator::new(NewExitValidatorArgs {
+        timestamp: chrono::Utc::now().timestamp(),
+        sender_address: sender,
+        sender_public_key: pk,
+        node_id,
+        signature: sk
+            .sign_ecdsa(Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb")),
+        nonce: 0,
+    });
+
+    txn.sign(&sk);
+
+    txn
+}
+
+/// Creates a signed delegation from `sender` to the validator running
+/// `node_id`
+pub fn create_delegation_txn(
+    sender: Address,
+    node_id: NodeId,
+    action: DelegationAction,
+    amount: u128,
+    nonce: u128,
+) -> Delegation {
+    let (sk, pk) = create_keypair();
+
+    let mut txn = Delegation::new(NewDelegationArgs {
+        timestamp: chrono::Utc::now().timestamp(),
+        sender_address: sender,
+        sender_public_key: pk,
+        node_id,
+        action,
+        amount,
+        signature: sk
+            .sign_ecdsa(Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb")),
+        nonce,
+    });
+
+    txn.sign(&sk);
+
+    txn
+}
+
+/// Creates a signed txn carrying a parameter change the quorum holding
+/// `quorum_public_key` approved with `quorum_signature`
+pub fn create_parameter_change_txn(
+    proposal: ParameterChangeProposal,
+    quorum_public_key: QuorumPubkey,
+    quorum_signature: String,
+) -> ParameterChange {
+    let (sk, pk) = create_keypair();
+
+    let mut txn = ParameterChange::new(NewParameterChangeArgs {
+        timestamp: chrono::Utc::now().timestamp(),
+        sender_address: Address::new(pk),
+        sender_public_key: pk,
+        proposal,
+        quorum_public_key,
+        quorum_signature,
+        signature: sk
+            .sign_ecdsa(Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb")),
+        nonce: 0,
+    });
+
+    txn.sign(&sk);
+
+    txn
+}
+
 pub fn create_txn_from_accounts(
     sender: (Address, Option<Account>),
     receiver: Address,
@@ -298,6 +441,7 @@ pub fn create_txn_from_accounts(
             .sign_ecdsa(Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb")),
         validators: Some(validators),
         nonce: sender.1.unwrap().nonce() + 1,
+        memo: None,
     };
 
     let mut txn = TransactionKind::Transfer(Transfer::new(txn_args));
@@ -312,6 +456,7 @@ pub fn create_txn_from_accounts(
         txn.token().clone(),
         txn.amount(),
         txn.nonce(),
+        txn.memo(),
     );
 
     let _digest = TransactionDigest::from(txn_digest_vec);
@@ -358,6 +503,7 @@ pub async fn send_data_over_quic(data: String, addr: SocketAddr) -> Result<()> {
     let msg = dyswarm::types::Message {
         id: dyswarm::types::MessageId::new_v4(),
         timestamp: 0i64,
+        source: None,
         data: NetworkEvent::Ping(data),
     };
 
@@ -368,7 +514,9 @@ pub async fn send_data_over_quic(data: String, addr: SocketAddr) -> Result<()> {
 
 use rand::{seq::SliceRandom, thread_rng};
 use vrrb_core::transactions::{
-    generate_transfer_digest_vec, NewTransferArgs, QuorumCertifiedTxn, Transaction,
+    generate_transfer_digest_vec, Delegation, DelegationAction, ExitValidator, NewDelegationArgs,
+    NewExitValidatorArgs, NewParameterChangeArgs, NewRegisterValidatorArgs, NewTransferArgs,
+    ParameterChange, ParameterChangeProposal, QuorumCertifiedTxn, RegisterValidator, Transaction,
     TransactionDigest, TransactionKind, Transfer,
 };
 
@@ -397,6 +545,7 @@ pub async fn create_node_rpc_client(rpc_addr: SocketAddr) -> impl RpcApiClient {
 }
 
 /// Creates a mock `NewTxnArgs` struct meant to be used for testing.
+#[cfg(any(test, feature = "mining"))]
 pub fn create_mock_transaction_args(n: usize) -> NewTransferArgs {
     let (sk, pk) = create_keypair();
     let (_, rpk) = create_keypair();
@@ -416,6 +565,7 @@ pub fn create_mock_transaction_args(n: usize) -> NewTransferArgs {
             .sign_ecdsa(Message::from_hashed_data::<secp256k1::hashes::sha256::Hash>(b"vrrb")),
         validators: None,
         nonce: n as u128,
+        memo: None,
     }
 }
 
@@ -579,6 +729,7 @@ pub async fn create_test_network(n: u16) -> Vec<Node> {
         udp_gossip_addr: addr,
         raptorq_gossip_addr: addr,
         kademlia_liveness_addr: addr,
+        alternate_udp_gossip_addrs: vec![],
     };
 
     bootstrap_node_config.udp_gossip_addr = node_0.udp_gossip_address();
@@ -688,6 +839,7 @@ pub async fn create_node_runtime_network(
         udp_gossip_addr: addr,
         raptorq_gossip_addr: addr,
         kademlia_liveness_addr: addr,
+        alternate_udp_gossip_addrs: vec![],
     };
 
     bootstrap_node_config.udp_gossip_addr = node_0.config.udp_gossip_address;
@@ -737,3 +889,155 @@ pub async fn create_node_runtime_network(
 
     nodes
 }
+
+/// How long an [ActorHarness] waits for its actor to handle an event or to
+/// emit the events a test expects
+pub const DEFAULT_ACTOR_HARNESS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A single step of a scripted event sequence, see [ActorHarness::run_script]
+pub enum ScriptStep<H> {
+    /// Feeds an event to the actor, which must handle it successfully
+    Send(Event),
+
+    /// Feeds an event to the actor, which must fail to handle it
+    SendExpectingError(Event),
+
+    /// Expects the actor to emit exactly these events, in this order
+    ExpectEmitted(Vec<Event>),
+
+    /// Expects the next event the actor emits to satisfy a predicate
+    ExpectEmittedMatching(&'static str, Box<dyn Fn(&Event) -> bool>),
+
+    /// Expects the actor to not have emitted anything that wasn't consumed
+    /// by a previous step
+    ExpectNoneEmitted,
+
+    /// Expects the actor's state to satisfy a predicate
+    ExpectState(&'static str, Box<dyn Fn(&H) -> bool>),
+}
+
+/// Drives a single actor's handler directly, without spawning it or wiring
+/// it to a router, and captures every event it emits.
+pub struct ActorHarness<H> {
+    actor: H,
+    emitted_rx: EventSubscriber,
+    timeout: Duration,
+}
+
+impl<H: Handler<EventMessage>> ActorHarness<H> {
+    /// Wraps an actor whose emitted events end up in `emitted_rx`
+    pub fn new(actor: H, emitted_rx: EventSubscriber) -> Self {
+        Self {
+            actor,
+            emitted_rx,
+            timeout: DEFAULT_ACTOR_HARNESS_TIMEOUT,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn actor(&self) -> &H {
+        &self.actor
+    }
+
+    pub fn actor_mut(&mut self) -> &mut H {
+        &mut self.actor
+    }
+
+    pub fn into_actor(self) -> H {
+        self.actor
+    }
+
+    /// Feeds `event` to the actor and returns the outcome of handling it
+    pub async fn send(&mut self, event: Event) -> theater::Result<ActorState> {
+        let description = format!("{event:?}");
+        let label = self.actor.label();
+
+        tokio::time::timeout(self.timeout, self.actor.handle(event.into()))
+            .await
+            .unwrap_or_else(|_| {
+                panic!(
+                    "{label} took longer than {:?} to handle {description}",
+                    self.timeout
+                )
+            })
+    }
+
+    /// Waits for the next event the actor emits
+    pub async fn next_emitted(&mut self) -> Event {
+        let label = self.actor.label();
+
+        match tokio::time::timeout(self.timeout, self.emitted_rx.recv()).await {
+            Ok(Some(message)) => message.into(),
+            Ok(None) => panic!("{label} dropped its event publisher"),
+            Err(_) => panic!("{label} emitted nothing within {:?}", self.timeout),
+        }
+    }
+
+    /// Plays `script` step by step, panicking with the failing step and a
+    /// readable description of what was expected otherwise
+    pub async fn run_script(&mut self, script: Vec<ScriptStep<H>>) {
+        let label = self.actor.label();
+
+        for (index, step) in script.into_iter().enumerate() {
+            match step {
+                ScriptStep::Send(event) => {
+                    let description = format!("{event:#?}");
+                    if let Err(err) = self.send(event).await {
+                        panic!("step {index}: {label} failed to handle {description}: {err}");
+                    }
+                },
+                ScriptStep::SendExpectingError(event) => {
+                    let description = format!("{event:#?}");
+                    if self.send(event).await.is_ok() {
+                        panic!("step {index}: {label} unexpectedly handled {description}");
+                    }
+                },
+                ScriptStep::ExpectEmitted(expected) => {
+                    let mut emitted = Vec::with_capacity(expected.len());
+                    for _ in 0..expected.len() {
+                        emitted.push(self.next_emitted().await);
+                    }
+
+                    if emitted != expected {
+                        panic!(
+                            "step {index}: {label} emitted\n{emitted:#?}\nbut was expected to emit\n{expected:#?}"
+                        );
+                    }
+                },
+                ScriptStep::ExpectEmittedMatching(description, predicate) => {
+                    let emitted = self.next_emitted().await;
+                    if !predicate(&emitted) {
+                        panic!(
+                            "step {index}: expected {label} to emit {description}, got\n{emitted:#?}"
+                        );
+                    }
+                },
+                ScriptStep::ExpectNoneEmitted => {
+                    if let Ok(message) = self.emitted_rx.try_recv() {
+                        let emitted: Event = message.into();
+                        panic!("step {index}: {label} unexpectedly emitted\n{emitted:#?}");
+                    }
+                },
+                ScriptStep::ExpectState(description, predicate) => {
+                    if !predicate(&self.actor) {
+                        panic!("step {index}: expected {label} state to satisfy: {description}");
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl ActorHarness<NodeRuntime> {
+    /// Wraps a node runtime, redirecting everything it emits to the harness
+    pub fn for_node_runtime(mut node: NodeRuntime) -> Self {
+        let (events_tx, events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+        node.events_tx = events_tx;
+
+        Self::new(node, events_rx)
+    }
+}
@@ -0,0 +1,113 @@
+//! Time travel utilities for tests.
+//!
+//! Only compiled in when the `time-travel` feature is enabled. Epoch rotation
+//! and reward changes only happen every `epoch_length` blocks, so these let
+//! tests move a node's rounds and epochs forward directly instead of mining
+//! every block in between.
+use block::header::BlockHeader;
+use primitives::{Epoch, Round};
+use reward::schedule::RewardSchedule;
+use secp256k1::SecretKey;
+use vrrb_config::ValidatorSetDiff;
+
+use crate::{node_runtime::NodeRuntime, state_manager::StateManager, Result};
+
+impl StateManager {
+    /// Moves the state manager `rounds` rounds forward, as if an empty block
+    /// had been confirmed in each of them, and returns the resulting last
+    /// confirmed block header.
+    ///
+    /// The header's round, height, epoch and rewards follow the skipped blocks
+    /// the way mining them would have, and the state is committed at the
+    /// resulting round. A genesis header is started from if no block was
+    /// confirmed yet.
+    pub fn advance_rounds(&mut self, rounds: Round, secret_key: &SecretKey) -> Result<BlockHeader> {
+        let reward_schedule = RewardSchedule::new(&self.chain_spec);
+
+        let mut header = self.dag.last_confirmed_block_header().unwrap_or_else(|| {
+            BlockHeader::genesis(
+                0,
+                0,
+                self.chain_spec.genesis_epoch,
+                self.dag.claim(),
+                *secret_key,
+                String::new(),
+                &reward_schedule,
+                &self.chain_spec.header_format,
+            )
+        });
+
+        if rounds > 0 {
+            let last_block_hash = header.hash();
+
+            for _ in 0..rounds {
+                header.round += 1;
+                header.block_height += 1;
+
+                let mut block_reward = header.next_block_reward.clone();
+                block_reward.current_block = header.block_height;
+
+                header.next_block_reward = reward_schedule.next_reward(&block_reward, 0);
+
+                block_reward.miner = Some(header.miner_claim.address.to_string());
+                header.block_reward = block_reward;
+            }
+
+            header.ref_hashes = vec![last_block_hash];
+            header.epoch = self.chain_spec.epoch_at(header.block_height);
+            header.block_seed = header.next_block_seed;
+            header.timestamp = chrono::Utc::now().timestamp();
+            header.block_weight = 0;
+
+            let state_root = self.database.commit_state_at_round(header.round)?;
+            header.commit_state_root(hex::encode(state_root.0), secret_key);
+
+            let delegation_root = self.delegation_root(&header, &[]);
+            header.commit_delegation_root(delegation_root, secret_key);
+        }
+
+        self.dag.set_last_confirmed_block_header(header.clone());
+
+        Ok(header)
+    }
+}
+
+impl NodeRuntime {
+    /// Moves the node `rounds` rounds forward without mining the blocks in
+    /// between. Crossing into a new epoch rotates the validator set the way a
+    /// quorum election would, returning the resulting membership changes.
+    pub fn advance_rounds(&mut self, rounds: Round) -> Result<Option<ValidatorSetDiff>> {
+        let secret_key = self.config.keypair.miner_secret_key_owned();
+        let header = self.state_driver.advance_rounds(rounds, &secret_key)?;
+
+        #[cfg(feature = "consensus")]
+        let diff = self.consensus_driver.handle_quorum_election_started(header);
+
+        // NOTE: nodes built without consensus follow no validator set
+        #[cfg(not(feature = "consensus"))]
+        let diff = {
+            let _ = header;
+            None
+        };
+
+        Ok(diff)
+    }
+
+    /// Moves the node forward to the first block of `epoch`. Nodes already in
+    /// or past that epoch are left where they are.
+    pub fn advance_to_epoch(&mut self, epoch: Epoch) -> Result<Option<ValidatorSetDiff>> {
+        let block_height = self
+            .state_driver
+            .dag
+            .last_confirmed_block_header()
+            .map(|header| header.block_height)
+            .unwrap_or_default();
+
+        let epoch_start = self.config.chain_spec.epoch_start(epoch);
+        if epoch_start <= block_height {
+            return Ok(None);
+        }
+
+        self.advance_rounds(epoch_start - block_height)
+    }
+}
@@ -34,6 +34,7 @@ async fn process_full_node_event_flow() {
                 amount: 0,
                 signature,
                 nonce: 0,
+                memo: None,
                 validators: None,
             })
             .await
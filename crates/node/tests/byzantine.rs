@@ -0,0 +1,127 @@
+#![cfg(feature = "byzantine")]
+
+use std::{net::SocketAddr, time::Duration};
+
+use block::ProposalBlock;
+use events::{Event, EventMessage, DEFAULT_BUFFER};
+use node::{
+    test_utils::{create_test_network, produce_accounts, produce_proposal_blocks},
+    ByzantineFault, FaultInjector,
+};
+use primitives::Address;
+use tokio::sync::mpsc::channel;
+use vrrb_core::{claim::Claim, keypair::Keypair};
+
+/// Mines a proposal block with a few txns and claims on behalf of `keypair`
+fn proposal_mined_by(keypair: &Keypair, node_id: &str) -> ProposalBlock {
+    let template = produce_proposal_blocks("genesis".into(), produce_accounts(3), 1, 3)
+        .pop()
+        .unwrap();
+
+    let public_key = *keypair.get_miner_public_key();
+    let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    let signature = Claim::signature_for_valid_claim(
+        public_key,
+        ip_address,
+        keypair.get_miner_secret_key().secret_bytes().to_vec(),
+    )
+    .unwrap();
+
+    let from = Claim::new(
+        public_key,
+        Address::new(public_key),
+        ip_address,
+        signature,
+        node_id.into(),
+    )
+    .unwrap();
+
+    ProposalBlock::build(
+        template.ref_block,
+        template.round,
+        template.epoch,
+        template.txns,
+        template.claims,
+        template.inclusion_list,
+        template.halt_directives,
+        from,
+        keypair.get_miner_secret_key(),
+    )
+}
+
+#[tokio::test]
+async fn network_keeps_running_with_a_byzantine_node() {
+    let mut nodes = create_test_network(8).await;
+
+    let byzantine_node = nodes.pop().unwrap();
+    let fault_injector = byzantine_node.fault_injector();
+
+    fault_injector.inject(ByzantineFault::DropParts);
+    fault_injector.inject(ByzantineFault::CorruptPartialSignatures);
+    fault_injector.inject(ByzantineFault::EquivocateProposals);
+    fault_injector.inject(ByzantineFault::DelayVotes(Duration::from_millis(500)));
+
+    assert_eq!(fault_injector.faults().len(), 4);
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    byzantine_node.stop().await.unwrap();
+
+    for node in nodes {
+        node.stop().await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn byzantine_node_publishes_conflicting_signed_proposals() {
+    let keypair = Keypair::random();
+    let fault_injector = FaultInjector::new("node-1".into(), *keypair.get_miner_secret_key());
+    fault_injector.inject(ByzantineFault::EquivocateProposals);
+
+    let (events_tx, events_rx) = channel(DEFAULT_BUFFER);
+    let mut published_rx = fault_injector.spawn(events_rx);
+
+    let mined = proposal_mined_by(&keypair, "node-1");
+    let relayed = proposal_mined_by(&Keypair::random(), "node-2");
+
+    for block in [mined.clone(), relayed.clone()] {
+        events_tx
+            .send(EventMessage::from(Event::ProposalBlockCreated(block)))
+            .await
+            .unwrap();
+    }
+
+    let mut published = vec![];
+    for _ in 0..3 {
+        match Event::from(published_rx.recv().await.unwrap()) {
+            Event::ProposalBlockCreated(block) => published.push(block),
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    let [original, conflicting, passed_through]: [ProposalBlock; 3] = published.try_into().unwrap();
+
+    assert_eq!(original, mined);
+    assert_eq!(passed_through, relayed);
+
+    // NOTE: the conflicting block is a genuine block for the same round, signed and hashed
+    // with the byzantine node's own key rather than a tampered copy
+    assert_eq!(conflicting.round, mined.round);
+    assert_ne!(conflicting.hash, mined.hash);
+
+    let mut txns = mined.txns.clone();
+    txns.pop_back();
+
+    let rebuilt = ProposalBlock::build(
+        mined.ref_block.clone(),
+        mined.round,
+        mined.epoch,
+        txns,
+        mined.claims.clone(),
+        mined.inclusion_list.clone(),
+        mined.halt_directives.clone(),
+        mined.from.clone(),
+        keypair.get_miner_secret_key(),
+    );
+    assert_eq!(conflicting, rebuilt);
+}
@@ -42,6 +42,7 @@ async fn nodes_can_synchronize_state() {
                 amount: 0,
                 signature,
                 nonce: 0,
+                memo: None,
                 validators: None,
             })
             .await
@@ -0,0 +1,31 @@
+#![cfg(feature = "time-travel")]
+
+use events::DEFAULT_BUFFER;
+use node::{node_runtime::NodeRuntime, test_utils::create_mock_full_node_config};
+
+#[tokio::test]
+async fn nodes_can_be_moved_across_epoch_boundaries_without_mining() {
+    let mut config = create_mock_full_node_config();
+    config.chain_spec.epoch_length = 10;
+
+    let (events_tx, _events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+    let mut node = NodeRuntime::new(&config, events_tx).await.unwrap();
+
+    let genesis_epoch = config.chain_spec.genesis_epoch;
+
+    node.advance_rounds(5).unwrap();
+
+    let scheduler = node.backlog_snapshot().scheduler;
+    assert_eq!(scheduler.round, Some(5));
+    assert_eq!(scheduler.epoch, genesis_epoch);
+
+    node.advance_to_epoch(genesis_epoch + 3).unwrap();
+
+    let scheduler = node.backlog_snapshot().scheduler;
+    assert_eq!(scheduler.round, Some(30));
+    assert_eq!(scheduler.epoch, genesis_epoch + 3);
+
+    // NOTE: moving back in time is not supported, so earlier epochs are ignored
+    assert!(node.advance_to_epoch(genesis_epoch + 1).unwrap().is_none());
+    assert_eq!(node.get_round().unwrap(), 30);
+}
@@ -0,0 +1,175 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ClassifiedError, ErrorCode};
+
+/// Arithmetic on an [Amount] whose result doesn't fit in one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum AmountError {
+    #[error("{lhs} + {rhs} overflows")]
+    Overflow { lhs: u128, rhs: u128 },
+
+    #[error("{lhs} - {rhs} underflows")]
+    Underflow { lhs: u128, rhs: u128 },
+
+    #[error("{lhs} * {rhs} overflows")]
+    MulOverflow { lhs: u128, rhs: u128 },
+
+    #[error("{lhs} / 0 is undefined")]
+    DivisionByZero { lhs: u128 },
+
+    #[error("{lhs} + {rhs} is out of range")]
+    SignedOutOfRange { lhs: u128, rhs: i128 },
+}
+
+impl ClassifiedError for AmountError {
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::InvalidInput
+    }
+}
+
+/// A quantity of tokens: a balance, fee, reward or stake.
+///
+/// Arithmetic on amounts is checked and fails with an [AmountError] instead
+/// of panicking or wrapping around. Amounts serialize as the integer they
+/// wrap, so they can replace raw `u128`s without changing any format.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Amount(u128);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+    pub const MAX: Amount = Amount(u128::MAX);
+
+    pub const fn new(amount: u128) -> Self {
+        Self(amount)
+    }
+
+    pub const fn get(self) -> u128 {
+        self.0
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn checked_add(self, rhs: impl Into<Amount>) -> Result<Amount, AmountError> {
+        let rhs = rhs.into().0;
+
+        self.0
+            .checked_add(rhs)
+            .map(Amount)
+            .ok_or(AmountError::Overflow { lhs: self.0, rhs })
+    }
+
+    pub fn checked_sub(self, rhs: impl Into<Amount>) -> Result<Amount, AmountError> {
+        let rhs = rhs.into().0;
+
+        self.0
+            .checked_sub(rhs)
+            .map(Amount)
+            .ok_or(AmountError::Underflow { lhs: self.0, rhs })
+    }
+
+    pub fn checked_mul(self, rhs: u128) -> Result<Amount, AmountError> {
+        self.0
+            .checked_mul(rhs)
+            .map(Amount)
+            .ok_or(AmountError::MulOverflow { lhs: self.0, rhs })
+    }
+
+    pub fn checked_div(self, rhs: u128) -> Result<Amount, AmountError> {
+        self.0
+            .checked_div(rhs)
+            .map(Amount)
+            .ok_or(AmountError::DivisionByZero { lhs: self.0 })
+    }
+
+    /// Adds a signed adjustment, failing if the result is negative or
+    /// overflows
+    pub fn checked_add_signed(self, rhs: i128) -> Result<Amount, AmountError> {
+        self.0
+            .checked_add_signed(rhs)
+            .map(Amount)
+            .ok_or(AmountError::SignedOutOfRange { lhs: self.0, rhs })
+    }
+
+    pub fn saturating_add(self, rhs: impl Into<Amount>) -> Amount {
+        Amount(self.0.saturating_add(rhs.into().0))
+    }
+
+    pub fn saturating_sub(self, rhs: impl Into<Amount>) -> Amount {
+        Amount(self.0.saturating_sub(rhs.into().0))
+    }
+
+    /// Sums `amounts`, failing on the first overflow
+    pub fn checked_sum<I, A>(amounts: I) -> Result<Amount, AmountError>
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<Amount>,
+    {
+        amounts
+            .into_iter()
+            .try_fold(Amount::ZERO, |total, amount| total.checked_add(amount))
+    }
+}
+
+impl From<u128> for Amount {
+    fn from(amount: u128) -> Self {
+        Self(amount)
+    }
+}
+
+impl From<&u128> for Amount {
+    fn from(amount: &u128) -> Self {
+        Self(*amount)
+    }
+}
+
+impl From<Amount> for u128 {
+    fn from(amount: Amount) -> Self {
+        amount.0
+    }
+}
+
+impl Display for Amount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_on_amounts_fails_instead_of_wrapping() {
+        let max = Amount::MAX;
+
+        assert_eq!(
+            max.checked_add(1),
+            Err(AmountError::Overflow {
+                lhs: u128::MAX,
+                rhs: 1
+            })
+        );
+        assert_eq!(
+            Amount::new(5).checked_sub(7),
+            Err(AmountError::Underflow { lhs: 5, rhs: 7 })
+        );
+        assert!(max.checked_mul(2).is_err());
+        assert!(Amount::new(5).checked_div(0).is_err());
+        assert!(Amount::new(5).checked_add_signed(-6).is_err());
+
+        assert_eq!(Amount::new(5).checked_add_signed(-5), Ok(Amount::ZERO));
+        assert_eq!(Amount::new(5).checked_sub(2), Ok(Amount::new(3)));
+        assert_eq!(Amount::checked_sum([1u128, 2, 3]), Ok(Amount::new(6)));
+        assert!(Amount::checked_sum([u128::MAX, 1]).is_err());
+        assert_eq!(max.saturating_add(1), max);
+
+        assert_eq!(serde_json::to_string(&Amount::new(42)).unwrap(), "42");
+    }
+}
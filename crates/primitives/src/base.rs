@@ -7,6 +7,10 @@ use serde::{Deserialize, Serialize};
 pub type Epoch = u128;
 pub type Round = u128;
 pub type Seed = u64;
+
+/// Measure of the processing cost of a transaction, or of all transactions
+/// within a block
+pub type Weight = u64;
 pub const GENESIS_EPOCH: Epoch = 0;
 pub const GROSS_UTILITY_PERCENTAGE: f64 = 0.01;
 pub const PERCENTAGE_CHANGE_SUPPLY_CAP: f64 = 0.25;
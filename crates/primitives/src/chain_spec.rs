@@ -0,0 +1,963 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, Epoch, Round, Weight, GENESIS_EPOCH};
+
+pub const DEFAULT_CHAIN_ID: &str = "vrrb-devnet";
+pub const CHAIN_SPEC_VERSION: u32 = 1;
+pub const DEFAULT_EPOCH_LENGTH: u128 = 30_000_000;
+pub const DEFAULT_GENESIS_REWARD: u128 = 400_000_000;
+pub const DEFAULT_BASELINE_REWARD: u128 = 20;
+pub const DEFAULT_MIN_BASELINE_REWARD: u128 = 15;
+pub const DEFAULT_MAX_BASELINE_REWARD: u128 = 25;
+pub const DEFAULT_MAX_REWARD_ADJUSTMENT: f32 = 0.25;
+/// Denominator of the emission decay rate, which is given in parts per million
+pub const EMISSION_DECAY_RATE_DENOMINATOR: u64 = 1_000_000;
+pub const DEFAULT_BASE_FEE: u128 = 0x2D79883D2000;
+pub const DEFAULT_MEMO_FEE_PER_BYTE: u128 = DEFAULT_BASE_FEE / 100;
+pub const DEFAULT_MAX_TXNS_PER_PROPOSAL: usize = 10_000;
+pub const DEFAULT_MAX_CLAIMS_PER_PROPOSAL: usize = 1_000;
+pub const DEFAULT_MAX_BLOCK_WEIGHT: Weight = 100_000_000;
+pub const DEFAULT_MAX_TXN_SIZE: usize = 64 * 1024;
+pub const DEFAULT_MAX_TXN_VALIDATORS: usize = 1_000;
+pub const DEFAULT_MAX_TOKEN_FIELD_LENGTH: usize = 64;
+pub const DEFAULT_MAX_MEMO_SIZE: usize = 256;
+pub const DEFAULT_MIN_STAKE_FARMER: u128 = 10_000;
+pub const DEFAULT_MIN_STAKE_VALIDATOR: u128 = 50_000;
+pub const DEFAULT_UNBONDING_PERIOD: Epoch = 2;
+pub const DEFAULT_INCLUSION_LIST_MIN_AGE: Round = 5;
+pub const DEFAULT_MAX_INCLUSION_LIST_ENTRIES: usize = 16;
+pub const DEFAULT_MAX_TXN_WAIT_ROUNDS: Round = 3;
+pub const DEFAULT_TXN_PARTITION_FAILOVER_ROUNDS: Round = 2;
+pub const DEFAULT_MIN_CLAIM_AGE: Epoch = 1;
+pub const DEFAULT_MIN_STAKE_MINER: u128 = 0;
+pub const DEFAULT_MIN_UPTIME_PERCENT: u8 = 0;
+pub const DEFAULT_DUST_THRESHOLD: u128 = DEFAULT_BASE_FEE;
+pub const DEFAULT_DUST_INACTIVE_EPOCHS: Epoch = 12;
+pub const DEFAULT_DELEGATION_COMMISSION_PERCENT: u8 = 10;
+pub const DEFAULT_MIN_DELEGATION: u128 = DEFAULT_MIN_STAKE_FARMER / 10;
+pub const DEFAULT_MINER_FALLBACK_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_MINER_FALLBACK_SLOTS: u16 = 3;
+pub const DEFAULT_MAX_CLOCK_DRIFT_SECS: u64 = 5;
+pub const DEFAULT_TAGGED_HEADER_FIELDS_ACTIVATION_HEIGHT: u128 = 0;
+pub const DEFAULT_STATE_ROOT_ACTIVATION_HEIGHT: u128 = 0;
+/// Denominator of election eligibilities, which are given in parts per
+/// million of the election result space
+pub const ELECTION_ELIGIBILITY_SCALE: u64 = 1_000_000;
+pub const DEFAULT_TARGET_BLOCK_TIME_SECS: u64 = 1;
+pub const DEFAULT_ELIGIBILITY_ADJUSTMENT_WINDOW: u64 = 32;
+pub const DEFAULT_MAX_ELIGIBILITY_ADJUSTMENT: u64 = 4;
+pub const DEFAULT_MIN_ELIGIBILITY_PPM: u64 = ELECTION_ELIGIBILITY_SCALE / 100;
+pub const DEFAULT_ELECTION_STALL_TIMEOUT_SECS: u64 = 10;
+
+/// How the baseline block reward shrinks as epochs go by.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmissionCurve {
+    /// The baseline reward never changes
+    #[default]
+    Flat,
+
+    /// The baseline reward is halved every `interval` epochs
+    Halving { interval: u128 },
+
+    /// The baseline reward shrinks by `rate_ppm` parts per million every
+    /// epoch. The rate is an integer so every node computes the exact same
+    /// rewards from it
+    Decay { rate_ppm: u64 },
+}
+
+/// Parameters of the block reward paid out to miners.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RewardParams {
+    /// Total amount of tokens set aside to be paid out as block rewards
+    pub genesis_reward: u128,
+    pub baseline_reward: u128,
+    pub min_baseline_reward: u128,
+    pub max_baseline_reward: u128,
+
+    /// Largest fraction the baseline reward can move by from one epoch to the
+    /// next
+    pub max_reward_adjustment: f32,
+
+    #[serde(default)]
+    pub emission_curve: EmissionCurve,
+}
+
+impl Default for RewardParams {
+    fn default() -> Self {
+        Self {
+            genesis_reward: DEFAULT_GENESIS_REWARD,
+            baseline_reward: DEFAULT_BASELINE_REWARD,
+            min_baseline_reward: DEFAULT_MIN_BASELINE_REWARD,
+            max_baseline_reward: DEFAULT_MAX_BASELINE_REWARD,
+            max_reward_adjustment: DEFAULT_MAX_REWARD_ADJUSTMENT,
+            emission_curve: EmissionCurve::default(),
+        }
+    }
+}
+
+/// Parameters used to compute transaction fees.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeeParams {
+    pub base_fee: u128,
+
+    /// Charged on top of the base fee for every byte of a transfer's memo
+    pub memo_fee_per_byte: u128,
+}
+
+impl Default for FeeParams {
+    fn default() -> Self {
+        Self {
+            base_fee: DEFAULT_BASE_FEE,
+            memo_fee_per_byte: DEFAULT_MEMO_FEE_PER_BYTE,
+        }
+    }
+}
+
+/// Upper bounds on the contents of a single proposal block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlockLimits {
+    pub max_txns_per_proposal: usize,
+    pub max_claims_per_proposal: usize,
+
+    /// Cap on the cumulative weight of the txns of a proposal block, and of
+    /// the txns a convergence block consolidates
+    pub max_block_weight: Weight,
+}
+
+impl Default for BlockLimits {
+    fn default() -> Self {
+        Self {
+            max_txns_per_proposal: DEFAULT_MAX_TXNS_PER_PROPOSAL,
+            max_claims_per_proposal: DEFAULT_MAX_CLAIMS_PER_PROPOSAL,
+            max_block_weight: DEFAULT_MAX_BLOCK_WEIGHT,
+        }
+    }
+}
+
+/// Upper bounds on the structure of a single transaction, enforced both when
+/// it is submitted and when a block carrying it is imported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TxnLimits {
+    /// Largest size of a txn once serialized, in bytes
+    pub max_txn_size: usize,
+
+    /// Largest number of validator votes a txn may carry
+    pub max_validators: usize,
+
+    /// Longest name or symbol of the token a txn moves
+    pub max_token_field_length: usize,
+
+    /// Largest memo a transfer may carry, in bytes
+    pub max_memo_size: usize,
+}
+
+impl Default for TxnLimits {
+    fn default() -> Self {
+        Self {
+            max_txn_size: DEFAULT_MAX_TXN_SIZE,
+            max_validators: DEFAULT_MAX_TXN_VALIDATORS,
+            max_token_field_length: DEFAULT_MAX_TOKEN_FIELD_LENGTH,
+            max_memo_size: DEFAULT_MAX_MEMO_SIZE,
+        }
+    }
+}
+
+/// Minimum stake a claim must hold to be eligible for each role.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StakingParams {
+    pub min_stake_farmer: u128,
+    pub min_stake_validator: u128,
+
+    /// Number of epochs a validator's stake stays locked after it leaves the
+    /// validator set
+    pub unbonding_period: Epoch,
+}
+
+impl Default for StakingParams {
+    fn default() -> Self {
+        Self {
+            min_stake_farmer: DEFAULT_MIN_STAKE_FARMER,
+            min_stake_validator: DEFAULT_MIN_STAKE_VALIDATOR,
+            unbonding_period: DEFAULT_UNBONDING_PERIOD,
+        }
+    }
+}
+
+/// Bounds on the inclusion lists farmers attach to their votes, naming
+/// certified txns that convergence blocks must not leave out any longer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InclusionListParams {
+    /// Number of rounds a certified txn may wait for a block before farmers
+    /// list it, and a listed txn may wait once a certified block referenced a
+    /// proposal carrying it before blocks omitting it are rejected
+    pub min_age_rounds: Round,
+
+    /// Largest number of txns a single inclusion list may name
+    pub max_entries: usize,
+}
+
+impl Default for InclusionListParams {
+    fn default() -> Self {
+        Self {
+            min_age_rounds: DEFAULT_INCLUSION_LIST_MIN_AGE,
+            max_entries: DEFAULT_MAX_INCLUSION_LIST_ENTRIES,
+        }
+    }
+}
+
+/// How block producers order the certified txns competing for a proposal.
+///
+/// Txns are preferred by fee, but a txn that has waited `max_wait_rounds`
+/// since it was certified goes ahead of every newer txn whatever their fees,
+/// so a steady stream of higher paying txns can't starve it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProposalFairnessParams {
+    /// Number of rounds a certified txn may be passed over for higher paying
+    /// ones
+    pub max_wait_rounds: Round,
+}
+
+impl Default for ProposalFairnessParams {
+    fn default() -> Self {
+        Self {
+            max_wait_rounds: DEFAULT_MAX_TXN_WAIT_ROUNDS,
+        }
+    }
+}
+
+/// How the certified txns of a quorum are split among its harvesters.
+///
+/// Every certified txn is assigned to a single harvester of the quorum, so
+/// harvesters don't propose the same txns in a round. Should that harvester
+/// be offline, the txn would never be proposed, so once it has waited
+/// `failover_rounds` since it was certified any harvester may propose it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TxnPartitionParams {
+    /// Number of rounds a certified txn is left to the harvester it is
+    /// assigned to
+    pub failover_rounds: Round,
+}
+
+impl Default for TxnPartitionParams {
+    fn default() -> Self {
+        Self {
+            failover_rounds: DEFAULT_TXN_PARTITION_FAILOVER_ROUNDS,
+        }
+    }
+}
+
+/// Requirements a claim must meet at the first block of an epoch to be
+/// eligible for that epoch's miner elections.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EligibilityParams {
+    /// Number of epochs since the block that first stored a claim it must
+    /// wait for. Claims stored before their age was recorded are exempt
+    pub min_claim_age: Epoch,
+
+    pub min_stake_miner: u128,
+
+    /// Share of the mining slots the claim held over the previous epoch its
+    /// node must have filled with a block, in percent. Claims that held no
+    /// slot are exempt
+    pub min_uptime_percent: u8,
+}
+
+impl Default for EligibilityParams {
+    fn default() -> Self {
+        Self {
+            min_claim_age: DEFAULT_MIN_CLAIM_AGE,
+            min_stake_miner: DEFAULT_MIN_STAKE_MINER,
+            min_uptime_percent: DEFAULT_MIN_UPTIME_PERCENT,
+        }
+    }
+}
+
+/// Where the balances of swept dust accounts go.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DustDestination {
+    /// The balances are taken out of the supply
+    #[default]
+    Burn,
+
+    /// The balances are credited to the given community pool account. Dust
+    /// is burned instead while the account doesn't exist
+    Pool(Address),
+}
+
+/// Rule removing accounts that hold next to nothing and saw no activity for
+/// a while, so they don't bloat the state trie forever.
+///
+/// The rule runs as part of the first block of every epoch: every account
+/// holding less than `threshold`, no code or storage, and untouched by any
+/// block for `inactive_epochs` epochs is deleted and its balance sent to
+/// `destination`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DustPolicyParams {
+    /// Epoch the rule takes effect from. It changes the state every node
+    /// commits to, so it stays off until the network agrees on an epoch to
+    /// turn it on at
+    pub activation_epoch: Option<Epoch>,
+
+    /// Balances below this count as dust
+    pub threshold: u128,
+
+    /// Number of epochs an account must see no activity for before it is
+    /// swept. Activity is only tracked once the rule is active, so no
+    /// account is swept before `activation_epoch + inactive_epochs`
+    pub inactive_epochs: Epoch,
+
+    pub destination: DustDestination,
+}
+
+impl Default for DustPolicyParams {
+    fn default() -> Self {
+        Self {
+            activation_epoch: None,
+            threshold: DEFAULT_DUST_THRESHOLD,
+            inactive_epochs: DEFAULT_DUST_INACTIVE_EPOCHS,
+            destination: DustDestination::default(),
+        }
+    }
+}
+
+impl DustPolicyParams {
+    /// Whether the rule is in effect during `epoch`
+    pub fn is_active_at(&self, epoch: Epoch) -> bool {
+        self.activation_epoch
+            .map_or(false, |activation_epoch| epoch >= activation_epoch)
+    }
+}
+
+/// Rules for stake delegated to validators by other accounts.
+///
+/// Delegated stake adds to the election weight of the validator it is bonded
+/// to. Every header commits to the delegation state of every claim once
+/// delegation is active, through its delegation root. The rewards a validator earns during an epoch are shared once the
+/// epoch ends: the validator keeps `commission_percent` of them, and the rest
+/// is split between it and its delegators in proportion to their stakes.
+/// Undelegated stake unlocks after the staking unbonding period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DelegationParams {
+    /// Epoch delegations are accepted from. Validator rewards are only paid
+    /// out while delegation is active, and it changes the state every node
+    /// commits to, so it stays off until the network agrees on an epoch to
+    /// turn it on at
+    pub activation_epoch: Option<Epoch>,
+
+    /// Share of the rewards validators keep before splitting the rest with
+    /// their delegators, in percent
+    pub commission_percent: u8,
+
+    /// Smallest stake a single delegation may bond
+    pub min_delegation: u128,
+}
+
+impl Default for DelegationParams {
+    fn default() -> Self {
+        Self {
+            activation_epoch: None,
+            commission_percent: DEFAULT_DELEGATION_COMMISSION_PERCENT,
+            min_delegation: DEFAULT_MIN_DELEGATION,
+        }
+    }
+}
+
+impl DelegationParams {
+    /// Whether delegation is in effect during `epoch`
+    pub fn is_active_at(&self, epoch: Epoch) -> bool {
+        self.activation_epoch
+            .map_or(false, |activation_epoch| epoch >= activation_epoch)
+    }
+}
+
+/// Controls which miners may take over a round whose elected miner never
+/// produces a block.
+///
+/// Every round has a schedule of miners, ordered by their weighted election
+/// result for the round's seed: the election winner holds slot 0 and the claims
+/// with the next-lowest results hold the fallback slots after it. Slot `n`
+/// opens `n * timeout_secs` seconds after the previous block, so every node
+/// derives the same schedule and fallback miners only step in once the
+/// miners ahead of them had their chance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MinerFallbackParams {
+    /// Seconds each slot is given before the next one opens
+    pub timeout_secs: u64,
+
+    /// Number of fallback slots after the election winner's. Zero leaves
+    /// every round to its winner
+    pub max_slots: u16,
+
+    /// Seconds a block's timestamp may run ahead of the clock of the node
+    /// verifying it. Miners pick their own timestamps, so without a bound a
+    /// fallback miner could date its block forward until its slot opened.
+    /// Must be shorter than a slot
+    pub max_clock_drift_secs: u64,
+}
+
+impl Default for MinerFallbackParams {
+    fn default() -> Self {
+        Self {
+            timeout_secs: DEFAULT_MINER_FALLBACK_TIMEOUT_SECS,
+            max_slots: DEFAULT_MINER_FALLBACK_SLOTS,
+            max_clock_drift_secs: DEFAULT_MAX_CLOCK_DRIFT_SECS,
+        }
+    }
+}
+
+impl MinerFallbackParams {
+    /// Returns the last slot open `elapsed_secs` seconds after the previous
+    /// block
+    pub fn open_slot(&self, elapsed_secs: i64) -> u16 {
+        let elapsed_secs = u64::try_from(elapsed_secs).unwrap_or_default();
+
+        (elapsed_secs / self.timeout_secs.max(1)).min(self.max_slots as u64) as u16
+    }
+}
+
+/// Controls how block headers commit to the fields added to them since
+/// launch: the block weight, the state root, the root chain, the fallback
+/// slot and the election eligibility.
+///
+/// Headers below the activation height keep the legacy layout, which leaves
+/// those fields out, so blocks mined before them keep their hashes. Headers
+/// from it on encode every one of them behind a tag telling whether it is
+/// present and how long it is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeaderFormatParams {
+    /// Height of the first block whose header uses the tagged layout. Blocks
+    /// below it don't commit to the fields added since launch, so networks
+    /// that predate them should activate it before relying on those fields
+    pub tagged_fields_activation_height: u128,
+
+    /// Height of the first block that must commit to the state root applying
+    /// it yields. Blocks below it may leave their state root empty, so it
+    /// can't come before the tagged layout signs the state root
+    pub state_root_activation_height: u128,
+}
+
+impl Default for HeaderFormatParams {
+    fn default() -> Self {
+        Self {
+            tagged_fields_activation_height: DEFAULT_TAGGED_HEADER_FIELDS_ACTIVATION_HEIGHT,
+            state_root_activation_height: DEFAULT_STATE_ROOT_ACTIVATION_HEIGHT,
+        }
+    }
+}
+
+impl HeaderFormatParams {
+    /// Whether the header of the block at `block_height` uses the tagged
+    /// layout
+    pub fn is_active_at(&self, block_height: u128) -> bool {
+        block_height >= self.tagged_fields_activation_height
+    }
+
+    /// Whether the block at `block_height` must commit to its state root
+    pub fn requires_state_root_at(&self, block_height: u128) -> bool {
+        block_height >= self.state_root_activation_height
+    }
+}
+
+/// Keeps the cadence of miner elections close to a target block time.
+///
+/// A claim only wins a miner election if its election result falls within
+/// the share of the election result space its block's parent leaves eligible,
+/// which every header records in parts per million. Each block moves the
+/// eligibility of its parent by `1 / adjustment_window` of how far the time
+/// since the parent is from the target: slow blocks widen the set of
+/// eligible claims, fast blocks narrow it. A small network thus ends up with
+/// every claim eligible, while a large one only lets a fraction of its claims
+/// win.
+///
+/// Rounds in which no claim is eligible produce no block, so the eligibility
+/// in effect also grows by `max_adjustment` for every `stall_timeout_secs`
+/// that pass without one. Everything is integer math over the chain's
+/// headers, so every node derives the same thresholds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ElectionDifficultyParams {
+    pub target_block_time_secs: u64,
+
+    /// Number of blocks a deviation from the target block time is spread
+    /// over
+    pub adjustment_window: u64,
+
+    /// Largest factor the eligibility can grow or shrink by from one block
+    /// to the next, which is also the factor it grows by per stall timeout
+    pub max_adjustment: u64,
+
+    /// Smallest eligibility, in parts per million, no matter how fast
+    /// blocks come in
+    pub min_eligibility_ppm: u64,
+
+    /// Seconds without a block after which the eligibility in effect grows
+    /// by `max_adjustment`
+    pub stall_timeout_secs: u64,
+}
+
+impl Default for ElectionDifficultyParams {
+    fn default() -> Self {
+        Self {
+            target_block_time_secs: DEFAULT_TARGET_BLOCK_TIME_SECS,
+            adjustment_window: DEFAULT_ELIGIBILITY_ADJUSTMENT_WINDOW,
+            max_adjustment: DEFAULT_MAX_ELIGIBILITY_ADJUSTMENT,
+            min_eligibility_ppm: DEFAULT_MIN_ELIGIBILITY_PPM,
+            stall_timeout_secs: DEFAULT_ELECTION_STALL_TIMEOUT_SECS,
+        }
+    }
+}
+
+impl ElectionDifficultyParams {
+    /// Returns the eligibility of a block mined `interval_secs` seconds after
+    /// a parent with an eligibility of `eligibility_ppm`
+    pub fn retarget(&self, eligibility_ppm: u64, interval_secs: i64) -> u64 {
+        let eligibility = eligibility_ppm as u128;
+        let interval = u64::try_from(interval_secs).unwrap_or_default() as u128;
+        let target = self.target_block_time_secs.max(1) as u128;
+        let window = self.adjustment_window.max(1) as u128;
+        let max_adjustment = self.max_adjustment.max(1) as u128;
+
+        let retargeted = eligibility * (target * (window - 1) + interval) / (target * window);
+
+        self.bounded(retargeted.clamp(eligibility / max_adjustment, eligibility * max_adjustment))
+    }
+
+    /// Returns the eligibility in effect `elapsed_secs` seconds after a block
+    /// with an eligibility of `eligibility_ppm`, grown by `max_adjustment`
+    /// for every stall timeout that passed
+    pub fn eligibility_after(&self, eligibility_ppm: u64, elapsed_secs: i64) -> u64 {
+        let elapsed_secs = u64::try_from(elapsed_secs).unwrap_or_default();
+        let stalls = elapsed_secs / self.stall_timeout_secs.max(1);
+
+        let mut eligibility = self.bounded(eligibility_ppm as u128) as u128;
+
+        // NOTE: the eligibility is capped at the scale, which a few stalls
+        // reach from any eligibility, so the loop is short
+        for _ in 0..stalls {
+            if eligibility >= ELECTION_ELIGIBILITY_SCALE as u128 {
+                break;
+            }
+
+            eligibility *= self.max_adjustment.max(2) as u128;
+        }
+
+        self.bounded(eligibility)
+    }
+
+    fn bounded(&self, eligibility: u128) -> u64 {
+        eligibility.clamp(
+            self.min_eligibility_ppm as u128,
+            ELECTION_ELIGIBILITY_SCALE as u128,
+        ) as u64
+    }
+}
+
+/// Describes the chain a node takes part in. Every node of a network must be
+/// started with the same spec, which is loaded once at startup and handed to
+/// the components that need it instead of each of them hardcoding its own
+/// constants.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChainSpec {
+    pub chain_id: String,
+
+    /// Version of the rules the spec describes, bumped whenever they change
+    /// in a way nodes must agree on. Peers only admit nodes that announce the
+    /// same chain id and version during the handshake
+    pub version: u32,
+
+    /// Hash of the genesis block, if it is known ahead of time. Nodes
+    /// refuse to follow a chain whose genesis block does not match it.
+    pub genesis_hash: Option<String>,
+    pub genesis_epoch: Epoch,
+
+    /// Number of blocks within a single epoch
+    pub epoch_length: u128,
+    pub reward: RewardParams,
+    pub fees: FeeParams,
+    pub block_limits: BlockLimits,
+    pub txn_limits: TxnLimits,
+    pub staking: StakingParams,
+    pub inclusion_list: InclusionListParams,
+    pub proposal_fairness: ProposalFairnessParams,
+    pub txn_partition: TxnPartitionParams,
+    pub eligibility: EligibilityParams,
+    pub dust: DustPolicyParams,
+    pub delegation: DelegationParams,
+    pub miner_fallback: MinerFallbackParams,
+    pub header_format: HeaderFormatParams,
+    pub election_difficulty: ElectionDifficultyParams,
+}
+
+impl Default for ChainSpec {
+    fn default() -> Self {
+        Self {
+            chain_id: DEFAULT_CHAIN_ID.to_string(),
+            version: CHAIN_SPEC_VERSION,
+            genesis_hash: None,
+            genesis_epoch: GENESIS_EPOCH,
+            epoch_length: DEFAULT_EPOCH_LENGTH,
+            reward: RewardParams::default(),
+            fees: FeeParams::default(),
+            block_limits: BlockLimits::default(),
+            txn_limits: TxnLimits::default(),
+            staking: StakingParams::default(),
+            inclusion_list: InclusionListParams::default(),
+            proposal_fairness: ProposalFairnessParams::default(),
+            txn_partition: TxnPartitionParams::default(),
+            eligibility: EligibilityParams::default(),
+            dust: DustPolicyParams::default(),
+            delegation: DelegationParams::default(),
+            miner_fallback: MinerFallbackParams::default(),
+            header_format: HeaderFormatParams::default(),
+            election_difficulty: ElectionDifficultyParams::default(),
+        }
+    }
+}
+
+impl ChainSpec {
+    /// Reads a chain spec from a JSON file. Fields missing from the file take
+    /// their default values.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, crate::Error> {
+        let contents = fs::read_to_string(path.as_ref()).map_err(|err| {
+            crate::Error::Other(format!(
+                "failed to read chain spec from {}: {err}",
+                path.as_ref().display()
+            ))
+        })?;
+
+        let spec: Self = serde_json::from_str(&contents)
+            .map_err(|err| crate::Error::Other(format!("invalid chain spec: {err}")))?;
+
+        spec.validate()?;
+
+        Ok(spec)
+    }
+
+    pub fn validate(&self) -> Result<(), crate::Error> {
+        if self.chain_id.is_empty() {
+            return Err(crate::Error::Other("chain id cannot be empty".into()));
+        }
+
+        if self.version == 0 {
+            return Err(crate::Error::Other(
+                "chain spec version must be at least 1".into(),
+            ));
+        }
+
+        if self.epoch_length == 0 {
+            return Err(crate::Error::Other(
+                "epoch length must be at least one block".into(),
+            ));
+        }
+
+        let reward = &self.reward;
+        if reward.min_baseline_reward > reward.baseline_reward
+            || reward.baseline_reward > reward.max_baseline_reward
+        {
+            return Err(crate::Error::Other(format!(
+                "baseline reward {} is outside of the permitted range [{}, {}]",
+                reward.baseline_reward, reward.min_baseline_reward, reward.max_baseline_reward
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&reward.max_reward_adjustment) {
+            return Err(crate::Error::Other(
+                "max reward adjustment must be between 0 and 1".into(),
+            ));
+        }
+
+        match reward.emission_curve {
+            EmissionCurve::Halving { interval } if interval == 0 => {
+                return Err(crate::Error::Other(
+                    "halving interval must be at least one epoch".into(),
+                ));
+            },
+            EmissionCurve::Decay { rate_ppm } if rate_ppm >= EMISSION_DECAY_RATE_DENOMINATOR => {
+                return Err(crate::Error::Other(format!(
+                    "reward decay rate must be below {EMISSION_DECAY_RATE_DENOMINATOR} parts per million"
+                )));
+            },
+            _ => {},
+        }
+
+        if self.block_limits.max_txns_per_proposal == 0 {
+            return Err(crate::Error::Other(
+                "proposal blocks must be allowed to hold transactions".into(),
+            ));
+        }
+
+        if self.block_limits.max_block_weight == 0 {
+            return Err(crate::Error::Other(
+                "max block weight must be greater than zero".into(),
+            ));
+        }
+
+        if self.txn_limits.max_txn_size == 0 {
+            return Err(crate::Error::Other(
+                "max txn size must be greater than zero".into(),
+            ));
+        }
+
+        if self.inclusion_list.min_age_rounds == 0 {
+            return Err(crate::Error::Other(
+                "txns must be given at least one round before they can be listed for inclusion"
+                    .into(),
+            ));
+        }
+
+        if self.txn_partition.failover_rounds == 0 {
+            return Err(crate::Error::Other(
+                "certified txns must be left to their harvester for at least one round".into(),
+            ));
+        }
+
+        if self.eligibility.min_uptime_percent > 100 {
+            return Err(crate::Error::Other(
+                "min uptime cannot exceed 100 percent".into(),
+            ));
+        }
+
+        if self.dust.activation_epoch.is_some() && self.dust.inactive_epochs == 0 {
+            return Err(crate::Error::Other(
+                "accounts must be given at least one epoch before they are swept as dust".into(),
+            ));
+        }
+
+        if self.delegation.commission_percent > 100 {
+            return Err(crate::Error::Other(
+                "delegation commission cannot exceed 100 percent".into(),
+            ));
+        }
+
+        if self.delegation.min_delegation == 0 {
+            return Err(crate::Error::Other(
+                "delegations must bond some stake".into(),
+            ));
+        }
+
+        if self.miner_fallback.timeout_secs == 0 {
+            return Err(crate::Error::Other(
+                "miner fallback slots must last at least one second".into(),
+            ));
+        }
+
+        if self.miner_fallback.max_clock_drift_secs >= self.miner_fallback.timeout_secs {
+            return Err(crate::Error::Other(format!(
+                "a clock drift of {}s would let fallback miners skip {}s slots",
+                self.miner_fallback.max_clock_drift_secs, self.miner_fallback.timeout_secs
+            )));
+        }
+
+        let header_format = &self.header_format;
+        if header_format.state_root_activation_height
+            < header_format.tagged_fields_activation_height
+        {
+            return Err(crate::Error::Other(format!(
+                "state roots can't be required from height {} before headers sign them from height {}",
+                header_format.state_root_activation_height,
+                header_format.tagged_fields_activation_height
+            )));
+        }
+
+        let election_difficulty = &self.election_difficulty;
+        if election_difficulty.target_block_time_secs == 0
+            || election_difficulty.stall_timeout_secs == 0
+        {
+            return Err(crate::Error::Other(
+                "target block time and election stall timeout must be at least one second".into(),
+            ));
+        }
+
+        if election_difficulty.adjustment_window == 0 {
+            return Err(crate::Error::Other(
+                "eligibility adjustment window must span at least one block".into(),
+            ));
+        }
+
+        if election_difficulty.max_adjustment < 2 {
+            return Err(crate::Error::Other(
+                "max eligibility adjustment must be at least 2".into(),
+            ));
+        }
+
+        if !(1..=ELECTION_ELIGIBILITY_SCALE).contains(&election_difficulty.min_eligibility_ppm) {
+            return Err(crate::Error::Other(format!(
+                "min eligibility must be in the range [1, {ELECTION_ELIGIBILITY_SCALE}] parts per million"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the epoch the given block height falls into.
+    pub fn epoch_at(&self, block_height: u128) -> Epoch {
+        self.genesis_epoch + block_height / self.epoch_length
+    }
+
+    /// Returns the height of the first block of the given epoch.
+    pub fn epoch_start(&self, epoch: Epoch) -> u128 {
+        epoch.saturating_sub(self.genesis_epoch) * self.epoch_length
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_spec_is_valid() {
+        ChainSpec::default().validate().unwrap();
+    }
+
+    #[test]
+    fn partial_specs_fall_back_to_defaults() {
+        let spec: ChainSpec =
+            serde_json::from_str(r#"{ "chain_id": "testnet", "epoch_length": 10 }"#).unwrap();
+
+        assert_eq!(spec.chain_id, "testnet");
+        assert_eq!(spec.epoch_length, 10);
+        assert_eq!(spec.fees, FeeParams::default());
+        assert_eq!(spec.epoch_at(25), GENESIS_EPOCH + 2);
+        assert_eq!(spec.epoch_start(GENESIS_EPOCH + 2), 20);
+    }
+
+    #[test]
+    fn specs_must_be_versioned() {
+        let mut spec: ChainSpec = serde_json::from_str(r#"{ "chain_id": "testnet" }"#).unwrap();
+        assert_eq!(spec.version, CHAIN_SPEC_VERSION);
+
+        spec.version = 0;
+        assert!(spec.validate().is_err());
+    }
+
+    #[test]
+    fn invalid_reward_ranges_are_rejected() {
+        let mut spec = ChainSpec::default();
+        spec.reward.baseline_reward = spec.reward.max_baseline_reward + 1;
+
+        assert!(spec.validate().is_err());
+    }
+
+    #[test]
+    fn txn_limits_must_permit_some_txns() {
+        let mut spec = ChainSpec::default();
+        spec.txn_limits.max_txn_size = 0;
+
+        assert!(spec.validate().is_err());
+    }
+
+    #[test]
+    fn dust_policy_stays_off_until_activated() {
+        let mut spec = ChainSpec::default();
+        assert!(!spec.dust.is_active_at(Epoch::MAX));
+
+        spec.dust.activation_epoch = Some(GENESIS_EPOCH + 3);
+        assert!(!spec.dust.is_active_at(GENESIS_EPOCH + 2));
+        assert!(spec.dust.is_active_at(GENESIS_EPOCH + 3));
+
+        spec.dust.inactive_epochs = 0;
+        assert!(spec.validate().is_err());
+    }
+
+    #[test]
+    fn delegation_stays_off_until_activated() {
+        let mut spec = ChainSpec::default();
+        assert!(!spec.delegation.is_active_at(Epoch::MAX));
+
+        spec.delegation.activation_epoch = Some(GENESIS_EPOCH + 1);
+        assert!(spec.delegation.is_active_at(GENESIS_EPOCH + 1));
+
+        spec.delegation.commission_percent = 101;
+        assert!(spec.validate().is_err());
+    }
+
+    #[test]
+    fn fallback_slots_open_one_timeout_apart() {
+        let mut spec = ChainSpec::default();
+        spec.miner_fallback = MinerFallbackParams {
+            timeout_secs: 10,
+            max_slots: 2,
+            max_clock_drift_secs: 5,
+        };
+
+        assert_eq!(spec.miner_fallback.open_slot(-5), 0);
+        assert_eq!(spec.miner_fallback.open_slot(9), 0);
+        assert_eq!(spec.miner_fallback.open_slot(10), 1);
+        assert_eq!(spec.miner_fallback.open_slot(1_000), 2);
+        spec.validate().unwrap();
+
+        spec.miner_fallback.max_clock_drift_secs = 10;
+        assert!(spec.validate().is_err());
+
+        spec.miner_fallback.timeout_secs = 0;
+        assert!(spec.validate().is_err());
+    }
+
+    #[test]
+    fn state_roots_are_only_required_once_headers_sign_them() {
+        let mut spec = ChainSpec::default();
+        assert!(spec.header_format.requires_state_root_at(0));
+
+        spec.header_format = HeaderFormatParams {
+            tagged_fields_activation_height: 10,
+            state_root_activation_height: 20,
+        };
+        spec.validate().unwrap();
+        assert!(!spec.header_format.requires_state_root_at(19));
+        assert!(spec.header_format.requires_state_root_at(20));
+
+        spec.header_format.state_root_activation_height = 9;
+        assert!(spec.validate().is_err());
+    }
+
+    #[test]
+    fn eligibility_follows_the_time_between_blocks() {
+        let params = ElectionDifficultyParams::default();
+
+        assert_eq!(params.retarget(500_000, 1), 500_000);
+        assert_eq!(params.retarget(500_000, 0), 484_375);
+        assert_eq!(params.retarget(500_000, 33), ELECTION_ELIGIBILITY_SCALE);
+        assert_eq!(params.retarget(100_000, 1_000), 400_000);
+
+        let floor = (0..1_000).fold(ELECTION_ELIGIBILITY_SCALE, |eligibility, _| {
+            params.retarget(eligibility, 0)
+        });
+        assert_eq!(floor, params.min_eligibility_ppm);
+    }
+
+    #[test]
+    fn stalled_rounds_widen_the_eligibility_in_effect() {
+        let mut spec = ChainSpec::default();
+        let params = &spec.election_difficulty;
+        let floor = params.min_eligibility_ppm;
+
+        assert_eq!(params.eligibility_after(floor, -5), floor);
+        assert_eq!(params.eligibility_after(floor, 9), floor);
+        assert_eq!(params.eligibility_after(floor, 10), floor * 4);
+        assert_eq!(params.eligibility_after(floor, 35), floor * 64);
+        assert_eq!(
+            params.eligibility_after(floor, i64::MAX),
+            ELECTION_ELIGIBILITY_SCALE
+        );
+
+        spec.election_difficulty.max_adjustment = 1;
+        assert!(spec.validate().is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use std::{error::Error as StdError, fmt};
+
+use serde::{Deserialize, Serialize};
+
+/// JSON-RPC 2.0 code for requests whose parameters could not be used
+pub const JSON_RPC_INVALID_PARAMS: i32 = -32602;
+
+/// JSON-RPC 2.0 code for failures internal to the server
+pub const JSON_RPC_INTERNAL_ERROR: i32 = -32603;
+
+/// Message returned to clients in place of errors that may expose node
+/// internals
+pub const INTERNAL_ERROR_MESSAGE: &str = "internal error";
+
+/// Stable, numeric classification of errors surfaced to clients.
+///
+/// Codes are grouped by domain (1xxx general, 2xxx storage, 3xxx
+/// transactions, 4xxx consensus) and must never be renumbered once released,
+/// clients match on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(i32)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Internal = 1000,
+    InvalidInput = 1001,
+    NotFound = 1002,
+    AlreadyExists = 1003,
+    Unavailable = 1004,
+    Unauthorized = 1005,
+    Forbidden = 1006,
+
+    Storage = 2000,
+
+    InvalidTransaction = 3000,
+    InvalidSignature = 3001,
+    FeeTooLow = 3002,
+    RateLimited = 3003,
+
+    Consensus = 4000,
+    InvalidBlock = 4001,
+    Dkg = 4002,
+}
+
+impl ErrorCode {
+    /// Returns the stable numeric value of this code
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Returns the code reported in JSON-RPC error objects. Invalid input and
+    /// internal failures use the codes reserved for them by the JSON-RPC
+    /// spec, every other code is application defined and reported as is.
+    pub fn json_rpc_code(self) -> i32 {
+        match self {
+            ErrorCode::InvalidInput => JSON_RPC_INVALID_PARAMS,
+            ErrorCode::Internal => JSON_RPC_INTERNAL_ERROR,
+            _ => self.code(),
+        }
+    }
+
+    /// Returns true if errors with this code describe node internals rather
+    /// than something the client can act on
+    pub fn is_internal(self) -> bool {
+        matches!(
+            self,
+            ErrorCode::Internal | ErrorCode::Storage | ErrorCode::Consensus | ErrorCode::Dkg
+        )
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} ({})", self, self.code())
+    }
+}
+
+/// Errors that can be reported to clients under a stable [`ErrorCode`].
+pub trait ClassifiedError: StdError {
+    /// Returns the code clients can match this error on
+    fn error_code(&self) -> ErrorCode;
+
+    /// Returns a concise message that is safe to hand to clients. Errors
+    /// classified as internal are replaced by a generic message, their
+    /// details only belong in the node's logs.
+    fn client_message(&self) -> String {
+        if self.error_code().is_internal() {
+            INTERNAL_ERROR_MESSAGE.to_string()
+        } else {
+            self.to_string()
+        }
+    }
+}
+
+/// Renders an error followed by every error in its source chain, outermost
+/// first, for logging.
+pub fn error_chain(err: &dyn StdError) -> String {
+    let mut chain = err.to_string();
+    let mut source = err.source();
+
+    while let Some(cause) = source {
+        chain.push_str(": ");
+        chain.push_str(&cause.to_string());
+        source = cause.source();
+    }
+
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, thiserror::Error)]
+    enum TestError {
+        #[error("account {0} not found")]
+        NotFound(String),
+
+        #[error("failed to read from disk")]
+        Io(#[source] std::io::Error),
+    }
+
+    impl ClassifiedError for TestError {
+        fn error_code(&self) -> ErrorCode {
+            match self {
+                TestError::NotFound(_) => ErrorCode::NotFound,
+                TestError::Io(_) => ErrorCode::Storage,
+            }
+        }
+    }
+
+    #[test]
+    fn internal_errors_are_hidden_from_clients() {
+        let err = TestError::Io(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "/var/lib/vrrb/db is corrupt",
+        ));
+
+        assert_eq!(err.client_message(), INTERNAL_ERROR_MESSAGE);
+        assert_eq!(
+            error_chain(&err),
+            "failed to read from disk: /var/lib/vrrb/db is corrupt"
+        );
+
+        let err = TestError::NotFound("0x192abc".to_string());
+
+        assert_eq!(err.client_message(), "account 0x192abc not found");
+        assert_eq!(err.error_code().json_rpc_code(), 1002);
+    }
+
+    #[test]
+    fn reserved_json_rpc_codes_are_used_where_the_spec_defines_them() {
+        assert_eq!(
+            ErrorCode::InvalidInput.json_rpc_code(),
+            JSON_RPC_INVALID_PARAMS
+        );
+        assert_eq!(ErrorCode::Internal.json_rpc_code(), JSON_RPC_INTERNAL_ERROR);
+        assert_eq!(ErrorCode::FeeTooLow.json_rpc_code(), 3002);
+    }
+}
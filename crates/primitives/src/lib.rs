@@ -1,15 +1,25 @@
 pub mod address;
+pub mod amount;
 pub mod base;
+pub mod chain_spec;
 pub mod crypto;
 pub mod digest;
 pub mod environment;
+pub mod error_code;
 pub mod node;
+pub mod protocol_params;
+pub mod quorum;
 pub mod signal;
 
 pub use address::*;
+pub use amount::*;
 pub use base::*;
+pub use chain_spec::*;
 pub use crypto::*;
 pub use digest::*;
 pub use environment::*;
+pub use error_code::*;
 pub use node::*;
+pub use protocol_params::*;
+pub use quorum::*;
 pub use signal::*;
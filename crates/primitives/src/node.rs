@@ -1,7 +1,23 @@
-use std::{fmt, str::FromStr};
+use std::{
+    fmt,
+    net::{IpAddr, Ipv6Addr, SocketAddr},
+    str::FromStr,
+};
 
 use serde::{Deserialize, Serialize};
 
+use crate::Round;
+
+/// Number of rounds of state history kept by nodes that prune it
+pub const DEFAULT_RETAINED_ROUNDS: Round = 1024;
+
+/// Number of blocks whose certification records are kept by nodes that prune
+/// them
+pub const DEFAULT_RETAINED_CERTIFICATIONS: usize = 4096;
+
+/// Number of rounds whose consensus timeline is kept for post-mortems
+pub const DEFAULT_RETAINED_TIMELINE_ROUNDS: usize = 1024;
+
 // Represents a UUID serialized into a string
 pub type NodeId = String;
 pub type NodeIdx = u16;
@@ -80,3 +96,205 @@ impl From<usize> for NodeType {
         }
     }
 }
+
+/// How much of the state history a node keeps around to serve queries about
+/// past rounds.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PruningMode {
+    /// Keeps the state of every round since genesis
+    Archive,
+    /// Keeps the state of the most recent rounds only
+    KeepRecent(Round),
+}
+
+impl Default for PruningMode {
+    fn default() -> Self {
+        PruningMode::KeepRecent(DEFAULT_RETAINED_ROUNDS)
+    }
+}
+
+impl PruningMode {
+    /// Returns the oldest round still retained once `latest_round` is
+    /// committed.
+    pub fn oldest_retained_round(&self, latest_round: Round) -> Round {
+        match self {
+            PruningMode::Archive => 0,
+            PruningMode::KeepRecent(rounds) => {
+                latest_round.saturating_sub(rounds.saturating_sub(1))
+            },
+        }
+    }
+}
+
+/// Key-value store a node's database persists to. Which ones are available
+/// depends on the features the storage crates were built with.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DbBackend {
+    /// RocksDB, through its C++ library
+    #[default]
+    #[serde(rename = "rocksdb")]
+    RocksDb,
+    /// sled, written in pure Rust and thus easier to build on any platform
+    Sled,
+}
+
+impl FromStr for DbBackend {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "rocksdb" => Ok(DbBackend::RocksDb),
+            "sled" => Ok(DbBackend::Sled),
+            _ => Err(Error::Other("invalid database backend".into())),
+        }
+    }
+}
+
+/// How many blocks' votes, partial signatures and certificates a node keeps
+/// around for audits and dispute resolution.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CertificationRetention {
+    /// Keeps the certification records of every block
+    Archive,
+    /// Keeps the certification records of the most recently certified blocks
+    /// only
+    KeepRecent(usize),
+}
+
+impl Default for CertificationRetention {
+    fn default() -> Self {
+        CertificationRetention::KeepRecent(DEFAULT_RETAINED_CERTIFICATIONS)
+    }
+}
+
+impl CertificationRetention {
+    /// Returns whether `count` certification records exceed what is retained
+    pub fn exceeded_by(&self, count: usize) -> bool {
+        match self {
+            CertificationRetention::Archive => false,
+            CertificationRetention::KeepRecent(blocks) => count > *blocks,
+        }
+    }
+}
+
+/// Where a node runs, as labelled by its operator. Both labels are optional
+/// and free form, e.g. `us-east` and `us-east-1a`.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Locality {
+    pub region: Option<String>,
+    pub zone: Option<String>,
+}
+
+impl Locality {
+    pub fn new(region: Option<String>, zone: Option<String>) -> Self {
+        Self { region, zone }
+    }
+}
+
+/// How a bootstrap node spreads validators across the genesis quorums.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum QuorumAssignmentStrategy {
+    /// Fills quorums with peers in the order they're found in the peer list,
+    /// regardless of where they run
+    #[default]
+    Sequential,
+    /// Takes peers from every region in turn, and from every zone in turn
+    /// within a region, so no quorum depends on a single region staying up
+    RegionSpread,
+}
+
+impl fmt::Display for QuorumAssignmentStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QuorumAssignmentStrategy::Sequential => write!(f, "sequential"),
+            QuorumAssignmentStrategy::RegionSpread => write!(f, "region_spread"),
+        }
+    }
+}
+
+impl FromStr for QuorumAssignmentStrategy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sequential" => Ok(QuorumAssignmentStrategy::Sequential),
+            "region_spread" | "region-spread" => Ok(QuorumAssignmentStrategy::RegionSpread),
+            _ => Err(Error::Other("invalid quorum assignment strategy".into())),
+        }
+    }
+}
+
+/// IP family of a network address.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressFamily {
+    #[default]
+    Ipv4,
+    Ipv6,
+}
+
+impl AddressFamily {
+    pub fn of(addr: &SocketAddr) -> Self {
+        match addr {
+            SocketAddr::V4(_) => AddressFamily::Ipv4,
+            SocketAddr::V6(_) => AddressFamily::Ipv6,
+        }
+    }
+}
+
+impl fmt::Display for AddressFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddressFamily::Ipv4 => write!(f, "ipv4"),
+            AddressFamily::Ipv6 => write!(f, "ipv6"),
+        }
+    }
+}
+
+impl FromStr for AddressFamily {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ipv4" | "v4" => Ok(AddressFamily::Ipv4),
+            "ipv6" | "v6" => Ok(AddressFamily::Ipv6),
+            _ => Err(Error::Other("invalid address family".into())),
+        }
+    }
+}
+
+/// Returns the address a service configured to listen on `addr` binds to
+/// when dual-stack binding is enabled. The unspecified IPv4 address is swapped
+/// for the unspecified IPv6 one, which also accepts IPv4 connections on hosts
+/// that don't restrict IPv6 sockets to IPv6 only
+pub fn dual_stack_addr(addr: SocketAddr) -> SocketAddr {
+    match addr.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => {
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), addr.port())
+        },
+        _ => addr,
+    }
+}
+
+/// Orders the addresses a peer is reachable at for dialing: those of the
+/// `preferred` family first, then the others, each in the order the peer
+/// listed them. Duplicates are dropped
+pub fn dial_order(addrs: &[SocketAddr], preferred: AddressFamily) -> Vec<SocketAddr> {
+    let mut ordered: Vec<SocketAddr> = Vec::with_capacity(addrs.len());
+
+    let (preferred_addrs, fallback_addrs): (Vec<&SocketAddr>, Vec<&SocketAddr>) = addrs
+        .iter()
+        .partition(|addr| AddressFamily::of(addr) == preferred);
+
+    for addr in preferred_addrs.into_iter().chain(fallback_addrs) {
+        if !ordered.contains(addr) {
+            ordered.push(*addr);
+        }
+    }
+
+    ordered
+}
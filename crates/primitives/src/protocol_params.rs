@@ -0,0 +1,192 @@
+//! Protocol parameters governance may change on chain, without a release.
+//!
+//! Each parameter maps onto a field of the [ChainSpec] and comes with the
+//! bounds a change must stay within, so a change the quorum signed by mistake
+//! can't make the chain unusable.
+
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ChainSpec, ClassifiedError, ErrorCode, DEFAULT_BASE_FEE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolParameter {
+    BaseFee,
+    MemoFeePerByte,
+    MaxTxnsPerProposal,
+    MaxClaimsPerProposal,
+    MaxBlockWeight,
+    MaxTxnSize,
+    MaxMemoSize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParameterChangeError {
+    #[error("unknown protocol parameter {0}")]
+    UnknownParameter(String),
+
+    #[error("{parameter} must be within [{min}, {max}], got {value}")]
+    OutOfBounds {
+        parameter: ProtocolParameter,
+        value: u128,
+        min: u128,
+        max: u128,
+    },
+}
+
+impl ClassifiedError for ParameterChangeError {
+    fn error_code(&self) -> ErrorCode {
+        ErrorCode::InvalidInput
+    }
+}
+
+impl ProtocolParameter {
+    pub const ALL: [ProtocolParameter; 7] = [
+        ProtocolParameter::BaseFee,
+        ProtocolParameter::MemoFeePerByte,
+        ProtocolParameter::MaxTxnsPerProposal,
+        ProtocolParameter::MaxClaimsPerProposal,
+        ProtocolParameter::MaxBlockWeight,
+        ProtocolParameter::MaxTxnSize,
+        ProtocolParameter::MaxMemoSize,
+    ];
+
+    /// Returns the values the parameter may be changed to
+    pub fn bounds(&self) -> RangeInclusive<u128> {
+        match self {
+            ProtocolParameter::BaseFee => 1..=DEFAULT_BASE_FEE * 100,
+            ProtocolParameter::MemoFeePerByte => 0..=DEFAULT_BASE_FEE,
+            ProtocolParameter::MaxTxnsPerProposal => 1..=100_000,
+            ProtocolParameter::MaxClaimsPerProposal => 1..=10_000,
+            ProtocolParameter::MaxBlockWeight => 1_000_000..=1_000_000_000,
+            ProtocolParameter::MaxTxnSize => 1024..=1024 * 1024,
+            ProtocolParameter::MaxMemoSize => 0..=4096,
+        }
+    }
+
+    /// Checks that `value` is within the parameter's bounds
+    pub fn check(&self, value: u128) -> Result<(), ParameterChangeError> {
+        let bounds = self.bounds();
+
+        if !bounds.contains(&value) {
+            return Err(ParameterChangeError::OutOfBounds {
+                parameter: *self,
+                value,
+                min: *bounds.start(),
+                max: *bounds.end(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the parameter's value in `chain_spec`
+    pub fn value_in(&self, chain_spec: &ChainSpec) -> u128 {
+        match self {
+            ProtocolParameter::BaseFee => chain_spec.fees.base_fee,
+            ProtocolParameter::MemoFeePerByte => chain_spec.fees.memo_fee_per_byte,
+            ProtocolParameter::MaxTxnsPerProposal => {
+                chain_spec.block_limits.max_txns_per_proposal as u128
+            },
+            ProtocolParameter::MaxClaimsPerProposal => {
+                chain_spec.block_limits.max_claims_per_proposal as u128
+            },
+            ProtocolParameter::MaxBlockWeight => chain_spec.block_limits.max_block_weight as u128,
+            ProtocolParameter::MaxTxnSize => chain_spec.txn_limits.max_txn_size as u128,
+            ProtocolParameter::MaxMemoSize => chain_spec.txn_limits.max_memo_size as u128,
+        }
+    }
+
+    /// Sets the parameter to `value` in `chain_spec`, once it was checked to
+    /// be within bounds
+    pub fn apply(
+        &self,
+        chain_spec: &mut ChainSpec,
+        value: u128,
+    ) -> Result<(), ParameterChangeError> {
+        self.check(value)?;
+
+        // NOTE: the bounds of every parameter fit the type of its field
+        match self {
+            ProtocolParameter::BaseFee => chain_spec.fees.base_fee = value,
+            ProtocolParameter::MemoFeePerByte => chain_spec.fees.memo_fee_per_byte = value,
+            ProtocolParameter::MaxTxnsPerProposal => {
+                chain_spec.block_limits.max_txns_per_proposal = value as usize
+            },
+            ProtocolParameter::MaxClaimsPerProposal => {
+                chain_spec.block_limits.max_claims_per_proposal = value as usize
+            },
+            ProtocolParameter::MaxBlockWeight => {
+                chain_spec.block_limits.max_block_weight = value as u64
+            },
+            ProtocolParameter::MaxTxnSize => chain_spec.txn_limits.max_txn_size = value as usize,
+            ProtocolParameter::MaxMemoSize => chain_spec.txn_limits.max_memo_size = value as usize,
+        }
+
+        Ok(())
+    }
+
+    /// Returns the tag the parameter is signed and stored as
+    pub fn tag(&self) -> &'static str {
+        match self {
+            ProtocolParameter::BaseFee => "base_fee",
+            ProtocolParameter::MemoFeePerByte => "memo_fee_per_byte",
+            ProtocolParameter::MaxTxnsPerProposal => "max_txns_per_proposal",
+            ProtocolParameter::MaxClaimsPerProposal => "max_claims_per_proposal",
+            ProtocolParameter::MaxBlockWeight => "max_block_weight",
+            ProtocolParameter::MaxTxnSize => "max_txn_size",
+            ProtocolParameter::MaxMemoSize => "max_memo_size",
+        }
+    }
+}
+
+impl fmt::Display for ProtocolParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tag())
+    }
+}
+
+impl FromStr for ProtocolParameter {
+    type Err = ParameterChangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ProtocolParameter::ALL
+            .into_iter()
+            .find(|parameter| parameter.tag() == s)
+            .ok_or_else(|| ParameterChangeError::UnknownParameter(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parameters_only_change_within_their_bounds() {
+        let mut chain_spec = ChainSpec::default();
+
+        ProtocolParameter::MaxBlockWeight
+            .apply(&mut chain_spec, 2_000_000)
+            .unwrap();
+        assert_eq!(chain_spec.block_limits.max_block_weight, 2_000_000);
+        assert_eq!(
+            ProtocolParameter::MaxBlockWeight.value_in(&chain_spec),
+            2_000_000
+        );
+
+        assert!(matches!(
+            ProtocolParameter::MaxBlockWeight.apply(&mut chain_spec, 0),
+            Err(ParameterChangeError::OutOfBounds { value: 0, .. })
+        ));
+        assert_eq!(chain_spec.block_limits.max_block_weight, 2_000_000);
+
+        for parameter in ProtocolParameter::ALL {
+            assert_eq!(parameter.tag().parse::<ProtocolParameter>(), Ok(parameter));
+            assert!(parameter
+                .bounds()
+                .contains(&parameter.value_in(&ChainSpec::default())));
+        }
+    }
+}
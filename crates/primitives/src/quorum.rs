@@ -0,0 +1,189 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ByteSlice48Bit, ValidatorPublicKey};
+
+/// Maximum length of a quorum identifier
+pub const MAX_QUORUM_ID_LENGTH: usize = 64;
+
+/// Length of a quorum's threshold public key, in bytes
+pub const QUORUM_PUBKEY_LENGTH: usize = 48;
+
+/// Identifies a quorum. Made of at most [MAX_QUORUM_ID_LENGTH] ASCII
+/// alphanumeric characters, dashes or underscores.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct QuorumId(String);
+
+impl QuorumId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for QuorumId {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(crate::Error::Other("quorum id cannot be empty".into()));
+        }
+
+        if s.len() > MAX_QUORUM_ID_LENGTH {
+            return Err(crate::Error::Other(format!(
+                "quorum id cannot be longer than {MAX_QUORUM_ID_LENGTH} characters"
+            )));
+        }
+
+        let is_valid = s
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+        if !is_valid {
+            return Err(crate::Error::Other(format!("invalid quorum id: {s}")));
+        }
+
+        Ok(Self(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for QuorumId {
+    type Error = crate::Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
+impl From<QuorumId> for String {
+    fn from(id: QuorumId) -> Self {
+        id.0
+    }
+}
+
+impl AsRef<str> for QuorumId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for QuorumId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A quorum's threshold public key. Serialized as a hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct QuorumPubkey(ByteSlice48Bit);
+
+impl QuorumPubkey {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the threshold public key the quorum signs with
+    pub fn to_public_key(&self) -> Result<ValidatorPublicKey, crate::Error> {
+        ValidatorPublicKey::from_bytes(self.0)
+            .map_err(|err| crate::Error::Other(format!("invalid quorum public key: {err}")))
+    }
+}
+
+impl From<ValidatorPublicKey> for QuorumPubkey {
+    fn from(public_key: ValidatorPublicKey) -> Self {
+        Self(public_key.to_bytes())
+    }
+}
+
+impl TryFrom<&[u8]> for QuorumPubkey {
+    type Error = crate::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = ByteSlice48Bit::try_from(bytes).map_err(|_| {
+            crate::Error::Other(format!(
+                "quorum public key must be {QUORUM_PUBKEY_LENGTH} bytes long, got {}",
+                bytes.len()
+            ))
+        })?;
+
+        let pubkey = Self(bytes);
+        pubkey.to_public_key()?;
+
+        Ok(pubkey)
+    }
+}
+
+impl FromStr for QuorumPubkey {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s)
+            .map_err(|err| crate::Error::Other(format!("invalid quorum public key: {err}")))?;
+
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl TryFrom<String> for QuorumPubkey {
+    type Error = crate::Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
+impl From<QuorumPubkey> for String {
+    fn from(pubkey: QuorumPubkey) -> Self {
+        pubkey.to_string()
+    }
+}
+
+impl fmt::Display for QuorumPubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValidatorSecretKey;
+
+    #[test]
+    fn quorum_ids_are_validated() {
+        let id: QuorumId = "harvester-quorum_1".parse().unwrap();
+        assert_eq!(id.as_str(), "harvester-quorum_1");
+
+        assert!("".parse::<QuorumId>().is_err());
+        assert!("not a quorum id".parse::<QuorumId>().is_err());
+        assert!("a"
+            .repeat(MAX_QUORUM_ID_LENGTH + 1)
+            .parse::<QuorumId>()
+            .is_err());
+
+        let serialized = serde_json::to_string(&id).unwrap();
+        assert_eq!(serialized, "\"harvester-quorum_1\"");
+        assert!(serde_json::from_str::<QuorumId>("\"\"").is_err());
+    }
+
+    #[test]
+    fn quorum_pubkeys_round_trip_through_hex() {
+        let public_key = ValidatorSecretKey::random().public_key();
+        let pubkey = QuorumPubkey::from(public_key);
+
+        let parsed: QuorumPubkey = pubkey.to_string().parse().unwrap();
+        assert_eq!(parsed, pubkey);
+        assert_eq!(parsed.to_public_key().unwrap(), public_key);
+
+        let serialized = serde_json::to_string(&pubkey).unwrap();
+        assert_eq!(
+            serde_json::from_str::<QuorumPubkey>(&serialized).unwrap(),
+            pubkey
+        );
+
+        assert!("zz".parse::<QuorumPubkey>().is_err());
+        assert!(hex::encode([1u8; 32]).parse::<QuorumPubkey>().is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use primitives::{ClassifiedError, ErrorCode};
+
 #[derive(Debug, thiserror::Error)]
 pub enum StorageError {
     #[error("{0}")]
@@ -16,4 +18,16 @@ pub enum StorageError {
     Unknown,
 }
 
+impl ClassifiedError for StorageError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            StorageError::RecordExists => ErrorCode::AlreadyExists,
+            StorageError::NotFound(_) => ErrorCode::NotFound,
+            StorageError::Io(_) | StorageError::Other(_) | StorageError::Unknown => {
+                ErrorCode::Storage
+            },
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, StorageError>;
@@ -0,0 +1,61 @@
+use std::{fmt::Debug, path::PathBuf, sync::Arc};
+
+use primitives::DbBackend;
+use storage_utils::{Result, StorageError};
+
+#[cfg(feature = "rocksdb-backend")]
+mod rocksdb_backend;
+#[cfg(feature = "sled-backend")]
+mod sled_backend;
+
+#[cfg(feature = "rocksdb-backend")]
+pub use rocksdb_backend::*;
+#[cfg(feature = "sled-backend")]
+pub use sled_backend::*;
+
+/// Raw key-value storage that every store within [crate::VrrbDb] persists to.
+///
+/// Implementations must behave identically, so that a node's database works
+/// the same regardless of the backend it was configured with. The shared
+/// conformance suite under `tests/` holds every backend to that.
+pub trait KeyValueBackend: Debug + Send + Sync {
+    /// Returns the value stored under `key`, if any
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// Stores `value` under `key`, replacing any previous value
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()>;
+
+    /// Removes the value stored under `key`. Removing a missing key is not an
+    /// error
+    fn delete(&self, key: &[u8]) -> Result<()>;
+
+    /// Returns every stored entry, ordered by key
+    fn entries(&self) -> Result<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Returns up to `limit` entries stored under keys that sort after
+    /// `after`, ordered by key. Starts from the first entry if `after` is
+    /// None, so a store can be walked a few entries at a time
+    fn entries_after(&self, after: Option<&[u8]>, limit: usize) -> Result<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Reclaims the space held by overwritten and deleted entries
+    fn compact(&self) -> Result<()>;
+}
+
+pub type SharedBackend = Arc<dyn KeyValueBackend>;
+
+/// Opens the backend of the given kind at `path`. `namespace` names the
+/// store the backend is opened for.
+pub fn open_backend(backend: DbBackend, path: PathBuf, namespace: &str) -> Result<SharedBackend> {
+    match backend {
+        #[cfg(feature = "rocksdb-backend")]
+        DbBackend::RocksDb => Ok(Arc::new(RocksDbBackend::open(path, namespace)?)),
+
+        #[cfg(feature = "sled-backend")]
+        DbBackend::Sled => Ok(Arc::new(SledBackend::open(path, namespace)?)),
+
+        #[allow(unreachable_patterns)]
+        backend => Err(StorageError::Other(format!(
+            "vrrbdb was built without support for the {backend:?} backend"
+        ))),
+    }
+}
@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+use primitives::{get_vrrb_environment, Environment};
+use rocksdb::{Direction, IteratorMode, DB, DEFAULT_COLUMN_FAMILY_NAME};
+use storage_utils::{get_node_data_dir, Result, StorageError};
+use telemetry::error;
+
+use crate::KeyValueBackend;
+
+/// [KeyValueBackend] over a RocksDB instance. Requires RocksDB's native
+/// library, which can make it awkward to cross-compile.
+#[derive(Debug)]
+pub struct RocksDbBackend {
+    db: DB,
+}
+
+impl RocksDbBackend {
+    pub fn open(path: PathBuf, column_family: &str) -> Result<Self> {
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = new_db_instance(options, path, column_family)?;
+
+        Ok(Self { db })
+    }
+}
+
+impl KeyValueBackend for RocksDbBackend {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        self.db
+            .get(key)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.db
+            .put(key, value)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<()> {
+        self.db
+            .delete(key)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    fn entries(&self) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.db
+            .iterator(IteratorMode::Start)
+            .map(|entry| {
+                entry
+                    .map(|(key, value)| (key.into_vec(), value.into_vec()))
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .collect()
+    }
+
+    fn entries_after(&self, after: Option<&[u8]>, limit: usize) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let mode = match after {
+            Some(key) => IteratorMode::From(key, Direction::Forward),
+            None => IteratorMode::Start,
+        };
+
+        // NOTE: iterating from a key starts at the key itself, if it's stored
+        self.db
+            .iterator(mode)
+            .map(|entry| {
+                entry
+                    .map(|(key, value)| (key.into_vec(), value.into_vec()))
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .filter(|entry| {
+                !matches!((entry, after), (Ok((key, _)), Some(after)) if key.as_slice() == after)
+            })
+            .take(limit)
+            .collect()
+    }
+
+    fn compact(&self) -> Result<()> {
+        self.db.compact_range::<&[u8], &[u8]>(None, None);
+
+        Ok(())
+    }
+}
+
+fn base_db_options() -> rocksdb::Options {
+    let mut options = rocksdb::Options::default();
+
+    let environ = get_vrrb_environment();
+
+    if matches!(environ, Environment::Local) {
+        options.set_keep_log_file_num(3);
+    }
+
+    match get_node_data_dir() {
+        Ok(node_data_dir) => {
+            let log_path = node_data_dir.join("db").join("log");
+            options.set_db_log_dir(log_path);
+        },
+        Err(err) => {
+            error!("could not get node data directory: {}", err);
+            let default_data_dir = std::path::PathBuf::default();
+            let log_path = default_data_dir.join("db").join("log");
+            options.set_db_log_dir(log_path);
+        },
+    }
+
+    options
+}
+
+fn new_db_instance(
+    options: rocksdb::Options,
+    path: std::path::PathBuf,
+    column_family: &str,
+) -> storage_utils::Result<DB> {
+    let cfs = match rocksdb::DB::list_cf(&options, &path) {
+        Ok(cfs) => cfs,
+        Err(err) => {
+            error!(
+                "could not find column families at {}: {}",
+                path.display(),
+                err.into_string()
+            );
+            vec![]
+        },
+    };
+
+    let column_family_exists = cfs.iter().any(|cf| cf == column_family);
+
+    let mut instance = rocksdb::DB::open_cf(&options, &path, cfs)
+        .map_err(|err| StorageError::Other(err.to_string()))?;
+
+    if !column_family_exists {
+        if column_family != DEFAULT_COLUMN_FAMILY_NAME {
+            instance
+                .create_cf(column_family, &options)
+                .map_err(|err| StorageError::Other(err.to_string()))?;
+        }
+    }
+
+    Ok(instance)
+}
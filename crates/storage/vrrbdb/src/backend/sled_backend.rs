@@ -0,0 +1,96 @@
+use std::{ops::Bound, path::PathBuf};
+
+use storage_utils::{Result, StorageError};
+use telemetry::error;
+
+use crate::KeyValueBackend;
+
+/// [KeyValueBackend] over a sled database. sled is written in pure Rust, so
+/// this backend builds for any target the rest of the node builds for.
+#[derive(Debug)]
+pub struct SledBackend {
+    tree: sled::Tree,
+}
+
+impl SledBackend {
+    pub fn open(path: PathBuf, tree_name: &str) -> Result<Self> {
+        let db = sled::open(path).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let tree = db
+            .open_tree(tree_name)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        Ok(Self { tree })
+    }
+}
+
+impl Drop for SledBackend {
+    fn drop(&mut self) {
+        // sled writes asynchronously, so make sure everything stored through
+        // this backend is on disk before the database can be reopened
+        if let Err(err) = self.tree.flush() {
+            error!("failed to flush sled tree: {err}");
+        }
+    }
+}
+
+impl KeyValueBackend for SledBackend {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        self.tree
+            .get(key)
+            .map(|value| value.map(|value| value.to_vec()))
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.tree
+            .insert(key, value)
+            .map(|_| ())
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<()> {
+        self.tree
+            .remove(key)
+            .map(|_| ())
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    fn entries(&self) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.tree
+            .iter()
+            .map(|entry| {
+                entry
+                    .map(|(key, value)| (key.to_vec(), value.to_vec()))
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .collect()
+    }
+
+    fn entries_after(&self, after: Option<&[u8]>, limit: usize) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let entries = match after {
+            Some(key) => self
+                .tree
+                .range::<&[u8], _>((Bound::Excluded(key), Bound::Unbounded)),
+            None => self.tree.iter(),
+        };
+
+        entries
+            .take(limit)
+            .map(|entry| {
+                entry
+                    .map(|(key, value)| (key.to_vec(), value.to_vec()))
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .collect()
+    }
+
+    fn compact(&self) -> Result<()> {
+        // sled reclaims space on its own as it writes, so all there is left
+        // to do is to make sure nothing is pending
+        self.tree
+            .flush()
+            .map(|_| ())
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+}
@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use primitives::DbBackend;
+use storage_utils::{Result, StorageError};
+
+use crate::{open_backend, SharedBackend};
+
+mod types;
+pub use types::*;
+
+/// Key the block application in flight is logged under. Blocks are applied
+/// one at a time, so there is never more than one.
+const PENDING_BLOCK_KEY: &[u8] = b"pending";
+
+/// Write-ahead log of block applications.
+///
+/// Applying a block commits to the state and transaction stores once per
+/// transaction, so a node dying halfway through a block leaves the stores
+/// holding part of it. The block and the accounts it touches are logged
+/// before it is applied and the entry is only cleared once the block is
+/// fully committed, so an entry found on startup marks an application that
+/// has to be recovered.
+#[derive(Debug, Clone)]
+pub struct BlockWal {
+    backend: SharedBackend,
+}
+
+impl Default for BlockWal {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(&db_path, DbBackend::default()).expect("failed to open the default block wal")
+    }
+}
+
+impl BlockWal {
+    /// Opens the log within `path`, picking up an application left
+    /// incomplete by a previous run.
+    pub fn new(path: &Path, backend: DbBackend) -> Result<Self> {
+        let backend = open_backend(backend, path.join("block_wal"), "block_wal")?;
+
+        Ok(Self { backend })
+    }
+
+    /// Logs a block application about to start
+    pub fn begin(&mut self, entry: &BlockWalEntry) -> Result<()> {
+        let value =
+            bincode::serialize(entry).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend.put(PENDING_BLOCK_KEY, &value)
+    }
+
+    /// Returns the block application that was started but never completed,
+    /// if any
+    pub fn pending(&self) -> Result<Option<BlockWalEntry>> {
+        self.backend
+            .get(PENDING_BLOCK_KEY)?
+            .map(|value| {
+                bincode::deserialize::<BlockWalEntry>(&value)
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Marks the logged block application as complete
+    pub fn complete(&mut self) -> Result<()> {
+        self.backend.delete(PENDING_BLOCK_KEY)
+    }
+}
@@ -0,0 +1,34 @@
+use block::BlockHash;
+use primitives::Address;
+use serde::{Deserialize, Serialize};
+use vrrb_core::{account::Account, transactions::TransactionKind};
+
+/// A block application logged before any of its mutations reach the stores.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockWalEntry {
+    pub block_hash: BlockHash,
+
+    /// The accounts the block mutates as they were before it was applied.
+    /// Accounts that didn't exist yet are recorded as `None`
+    pub pre_images: Vec<(Address, Option<Account>)>,
+
+    /// The transactions the block applies, in order
+    pub txns: Vec<TransactionKind>,
+}
+
+/// What recovery found of a block application interrupted by a crash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockRecovery {
+    /// Every block application ran to completion
+    Clean,
+
+    /// The interrupted block was rolled back and applied again in full
+    Replayed { block_hash: BlockHash },
+
+    /// The interrupted block could not be applied again, so the stores were
+    /// left as they were before it
+    RolledBack {
+        block_hash: BlockHash,
+        reason: String,
+    },
+}
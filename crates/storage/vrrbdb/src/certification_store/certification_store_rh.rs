@@ -0,0 +1,55 @@
+use block::BlockHash;
+use storage_utils::{Result, StorageError};
+
+use crate::{BlockCertification, SharedBackend, StoredCertification};
+
+#[derive(Debug, Clone)]
+pub struct CertificationStoreReadHandle {
+    backend: SharedBackend,
+}
+
+impl CertificationStoreReadHandle {
+    pub(crate) fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Returns the certification record of a block
+    pub fn get(&self, block_hash: &BlockHash) -> Result<BlockCertification> {
+        self.get_stored(block_hash)?
+            .map(|stored| stored.certification)
+            .ok_or_else(|| StorageError::NotFound(block_hash.clone()))
+    }
+
+    /// Returns the certification records of every retained block, oldest
+    /// first
+    pub fn entries(&self) -> Vec<BlockCertification> {
+        self.stored()
+            .into_iter()
+            .map(|stored| stored.certification)
+            .collect()
+    }
+
+    pub(crate) fn get_stored(&self, block_hash: &BlockHash) -> Result<Option<StoredCertification>> {
+        self.backend
+            .get(block_hash.as_bytes())?
+            .map(|value| {
+                bincode::deserialize::<StoredCertification>(&value)
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()
+    }
+
+    pub(crate) fn stored(&self) -> Vec<StoredCertification> {
+        let mut stored = self
+            .backend
+            .entries()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(_, value)| bincode::deserialize::<StoredCertification>(&value).ok())
+            .collect::<Vec<StoredCertification>>();
+
+        stored.sort_by_key(|stored| stored.sequence);
+
+        stored
+    }
+}
@@ -0,0 +1,211 @@
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+use block::{BlockHash, Certificate};
+use parking_lot::RwLock;
+use primitives::{CertificationRetention, DbBackend};
+use storage_utils::{Result, StorageError};
+
+use crate::{open_backend, SharedBackend};
+
+mod certification_store_rh;
+mod types;
+pub use certification_store_rh::*;
+pub use types::*;
+
+/// Persists the votes, partial signatures and certificates gathered while
+/// certifying blocks, so they can be audited or used as slashing evidence
+/// after the fact.
+///
+/// Records are keyed by block hash. Every record is tagged with the order in
+/// which its block was first seen, and the oldest records are deleted once the
+/// retention policy is exceeded.
+#[derive(Debug, Clone)]
+pub struct CertificationStore {
+    backend: SharedBackend,
+    retention: CertificationRetention,
+    order: Arc<RwLock<BTreeMap<u64, BlockHash>>>,
+}
+
+impl Default for CertificationStore {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(
+            &db_path,
+            DbBackend::default(),
+            CertificationRetention::default(),
+        )
+        .expect("failed to open the default certification store")
+    }
+}
+
+impl CertificationStore {
+    /// Opens the certification store within `path`, picking up the records
+    /// persisted by previous runs.
+    pub fn new(path: &Path, backend: DbBackend, retention: CertificationRetention) -> Result<Self> {
+        let backend = open_backend(backend, path.join("certifications"), "certifications")?;
+
+        let order = CertificationStoreReadHandle::new(backend.clone())
+            .stored()
+            .into_iter()
+            .map(|stored| (stored.sequence, stored.certification.block_hash))
+            .collect();
+
+        Ok(Self {
+            backend,
+            retention,
+            order: Arc::new(RwLock::new(order)),
+        })
+    }
+
+    pub fn read_handle(&self) -> CertificationStoreReadHandle {
+        CertificationStoreReadHandle::new(self.backend.clone())
+    }
+
+    pub fn retention(&self) -> CertificationRetention {
+        self.retention
+    }
+
+    pub(crate) fn backend(&self) -> SharedBackend {
+        self.backend.clone()
+    }
+
+    /// Records a validator's vote on a block. A vote that differs from the
+    /// ones the validator already cast on the block is kept alongside them and
+    /// reported as conflicting.
+    pub fn record_vote(
+        &mut self,
+        block_hash: BlockHash,
+        vote: CertificationVote,
+    ) -> Result<RecordOutcome> {
+        self.update(block_hash, |certification| {
+            let votes = &mut certification.votes;
+
+            if votes.contains(&vote) {
+                return RecordOutcome::Duplicate;
+            }
+
+            let outcome = if votes.iter().any(|cast| cast.voter == vote.voter) {
+                RecordOutcome::Conflicting
+            } else {
+                RecordOutcome::Recorded
+            };
+
+            votes.push(vote);
+
+            outcome
+        })
+    }
+
+    /// Records a validator's partial signature over a block. A partial
+    /// signature that differs from the ones the validator already produced for
+    /// the block is kept alongside them and reported as conflicting.
+    pub fn record_partial_signature(
+        &mut self,
+        block_hash: BlockHash,
+        partial_signature: PartialSignatureRecord,
+    ) -> Result<RecordOutcome> {
+        self.update(block_hash, |certification| {
+            let partial_signatures = &mut certification.partial_signatures;
+
+            if partial_signatures.contains(&partial_signature) {
+                return RecordOutcome::Duplicate;
+            }
+
+            let outcome = if partial_signatures
+                .iter()
+                .any(|recorded| recorded.signer == partial_signature.signer)
+            {
+                RecordOutcome::Conflicting
+            } else {
+                RecordOutcome::Recorded
+            };
+
+            partial_signatures.push(partial_signature);
+
+            outcome
+        })
+    }
+
+    /// Records the certificate a block was finally certified with.
+    pub fn record_certificate(&mut self, certificate: Certificate) -> Result<()> {
+        self.update(certificate.block_hash.clone(), |certification| {
+            certification.certificate = Some(certificate);
+        })
+    }
+
+    /// Deletes the record stored under `key`, whether or not it decodes, so
+    /// the block it was stored for can be certified again
+    pub fn discard(&mut self, key: &[u8]) -> Result<()> {
+        self.backend.delete(key)?;
+
+        self.order
+            .write()
+            .retain(|_, block_hash| block_hash.as_bytes() != key);
+
+        Ok(())
+    }
+
+    /// Returns the number of blocks whose certification records are retained
+    pub fn len(&self) -> usize {
+        self.order.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.read().is_empty()
+    }
+
+    fn update<F, T>(&mut self, block_hash: BlockHash, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut BlockCertification) -> T,
+    {
+        let mut stored = match self.read_handle().get_stored(&block_hash)? {
+            Some(stored) => stored,
+            None => StoredCertification {
+                sequence: self.next_sequence(),
+                certification: BlockCertification::new(block_hash.clone()),
+            },
+        };
+
+        let updated = f(&mut stored.certification);
+
+        let value =
+            bincode::serialize(&stored).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend.put(block_hash.as_bytes(), &value)?;
+
+        self.order.write().insert(stored.sequence, block_hash);
+
+        self.prune()?;
+
+        Ok(updated)
+    }
+
+    fn next_sequence(&self) -> u64 {
+        self.order
+            .read()
+            .keys()
+            .next_back()
+            .map(|sequence| sequence + 1)
+            .unwrap_or_default()
+    }
+
+    /// Deletes the records of the oldest blocks until the retention policy is
+    /// satisfied again
+    fn prune(&mut self) -> Result<()> {
+        let mut order = self.order.write();
+
+        while self.retention.exceeded_by(order.len()) {
+            let Some((_, block_hash)) = order.pop_first() else {
+                break;
+            };
+
+            self.backend.delete(block_hash.as_bytes())?;
+        }
+
+        Ok(())
+    }
+}
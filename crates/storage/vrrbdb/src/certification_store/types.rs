@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+
+use block::{BlockHash, Certificate};
+use primitives::{NodeId, RawSignature};
+use serde::{Deserialize, Serialize};
+
+/// A validator's vote on whether a block should be certified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificationVote {
+    pub voter: NodeId,
+    pub approved: bool,
+    pub signature: RawSignature,
+}
+
+/// A validator's share of the threshold signature certifying a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignatureRecord {
+    pub signer: NodeId,
+    pub public_key_share: Vec<u8>,
+    pub signature: RawSignature,
+}
+
+/// What recording a vote or a partial signature on a block found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordOutcome {
+    /// It is the first one its validator produced for the block
+    Recorded,
+
+    /// The very same one was already recorded, so it was dropped
+    Duplicate,
+
+    /// Its validator already produced a different one for the block. Both
+    /// are kept as evidence of the equivocation
+    Conflicting,
+}
+
+/// Everything gathered while certifying a block: the votes cast on it, the
+/// partial signatures over it and the certificate it ended up with, if any.
+///
+/// Every distinct vote and partial signature a validator produced is kept, so
+/// validators that equivocated can be told apart, see
+/// [BlockCertification::conflicting_voters] and
+/// [BlockCertification::conflicting_signers].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockCertification {
+    pub block_hash: BlockHash,
+    pub votes: Vec<CertificationVote>,
+    pub partial_signatures: Vec<PartialSignatureRecord>,
+    pub certificate: Option<Certificate>,
+}
+
+impl BlockCertification {
+    pub fn new(block_hash: BlockHash) -> Self {
+        Self {
+            block_hash,
+            votes: Vec::new(),
+            partial_signatures: Vec::new(),
+            certificate: None,
+        }
+    }
+
+    /// Returns the validators that contributed a partial signature to the
+    /// block's certificate
+    pub fn contributors(&self) -> BTreeSet<NodeId> {
+        self.partial_signatures
+            .iter()
+            .map(|partial_signature| partial_signature.signer.clone())
+            .collect()
+    }
+
+    /// Returns the validators that cast more than one distinct vote on the
+    /// block
+    pub fn conflicting_voters(&self) -> BTreeSet<NodeId> {
+        repeated(self.votes.iter().map(|vote| &vote.voter))
+    }
+
+    /// Returns the validators that produced more than one distinct partial
+    /// signature over the block
+    pub fn conflicting_signers(&self) -> BTreeSet<NodeId> {
+        repeated(
+            self.partial_signatures
+                .iter()
+                .map(|partial_signature| &partial_signature.signer),
+        )
+    }
+
+    pub fn is_certified(&self) -> bool {
+        self.certificate.is_some()
+    }
+}
+
+fn repeated<'a>(node_ids: impl Iterator<Item = &'a NodeId>) -> BTreeSet<NodeId> {
+    let mut seen = BTreeSet::new();
+
+    node_ids
+        .filter(|node_id| !seen.insert(*node_id))
+        .cloned()
+        .collect()
+}
+
+/// A block certification as persisted, tagged with the order in which its
+/// block was first recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct StoredCertification {
+    pub(crate) sequence: u64,
+    pub(crate) certification: BlockCertification,
+}
@@ -0,0 +1,54 @@
+use storage_utils::{Result, StorageError};
+use vrrb_core::transactions::TransactionDigest;
+
+use crate::{CertifiedTxnKey, SharedBackend, SharedCertifiedTxnFilter};
+
+#[derive(Debug, Clone)]
+pub struct CertifiedTxnIndexReadHandle {
+    backend: SharedBackend,
+    filter: SharedCertifiedTxnFilter,
+}
+
+impl CertifiedTxnIndexReadHandle {
+    pub(crate) fn new(backend: SharedBackend, filter: SharedCertifiedTxnFilter) -> Self {
+        Self { backend, filter }
+    }
+
+    /// Returns the key a transaction was certified under, if it was certified
+    /// by any quorum
+    pub fn get(&self, digest: &TransactionDigest) -> Result<Option<CertifiedTxnKey>> {
+        if !self.filter.read().may_contain(digest) {
+            return Ok(None);
+        }
+
+        self.backend
+            .get(digest.as_bytes())?
+            .map(|value| {
+                bincode::deserialize::<CertifiedTxnKey>(&value)
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Returns true if the transaction was certified, regardless of the
+    /// quorum that certified it
+    pub fn is_certified(&self, digest: &TransactionDigest) -> bool {
+        matches!(self.get(digest), Ok(Some(_)))
+    }
+
+    /// Returns true if the transaction was certified by exactly the quorum
+    /// named in `key`
+    pub fn contains(&self, key: &CertifiedTxnKey) -> bool {
+        matches!(self.get(&key.digest), Ok(Some(indexed)) if &indexed == key)
+    }
+
+    /// Returns every indexed key, ordered by digest
+    pub fn keys(&self) -> Vec<CertifiedTxnKey> {
+        self.backend
+            .entries()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(_, value)| bincode::deserialize::<CertifiedTxnKey>(&value).ok())
+            .collect()
+    }
+}
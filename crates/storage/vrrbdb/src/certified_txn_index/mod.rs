@@ -0,0 +1,115 @@
+use std::{path::Path, sync::Arc};
+
+use parking_lot::RwLock;
+use primitives::DbBackend;
+use storage_utils::{Result, StorageError};
+use vrrb_core::{bloom::Bloom, transactions::TransactionDigest};
+
+use crate::{open_backend, SharedBackend};
+
+mod certified_txn_index_rh;
+mod types;
+pub use certified_txn_index_rh::*;
+pub use types::*;
+
+/// How many digests the in-memory filter holds before lookups fall back to
+/// the backend
+pub const CERTIFIED_TXN_FILTER_CAPACITY: usize = 1 << 20;
+
+/// Persists which transactions have been certified and by which quorum, so a
+/// transaction certified before a quorum rotation or a restart is never
+/// certified, or proposed, a second time.
+///
+/// Entries are keyed by transaction digest. A cuckoo filter over the indexed
+/// digests, rebuilt from the backend when the index is opened, answers most
+/// lookups for uncertified transactions without touching the backend.
+#[derive(Debug, Clone)]
+pub struct CertifiedTxnIndex {
+    backend: SharedBackend,
+    filter: SharedCertifiedTxnFilter,
+}
+
+impl Default for CertifiedTxnIndex {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(&db_path, DbBackend::default())
+            .expect("failed to open the default certified txn index")
+    }
+}
+
+impl CertifiedTxnIndex {
+    /// Opens the index within `path`, picking up the certifications persisted
+    /// by previous runs.
+    pub fn new(path: &Path, backend: DbBackend) -> Result<Self> {
+        let backend = open_backend(backend, path.join("certified_txns"), "certified_txns")?;
+
+        let index = Self {
+            backend,
+            filter: Arc::new(RwLock::new(CertifiedTxnFilter::default())),
+        };
+
+        let keys = index.read_handle().keys();
+        {
+            let mut filter = index.filter.write();
+            for key in keys.iter() {
+                filter.insert(&key.digest);
+            }
+        }
+
+        Ok(index)
+    }
+
+    pub fn read_handle(&self) -> CertifiedTxnIndexReadHandle {
+        CertifiedTxnIndexReadHandle::new(self.backend.clone(), self.filter.clone())
+    }
+
+    /// Indexes a certified transaction. Returns false, leaving the index
+    /// untouched, if the transaction was already certified by any quorum.
+    pub fn insert(&mut self, key: CertifiedTxnKey) -> Result<bool> {
+        if self.read_handle().is_certified(&key.digest) {
+            return Ok(false);
+        }
+
+        let value = bincode::serialize(&key).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend.put(key.digest.as_bytes(), &value)?;
+        self.filter.write().insert(&key.digest);
+
+        Ok(true)
+    }
+}
+
+pub(crate) type SharedCertifiedTxnFilter = Arc<RwLock<CertifiedTxnFilter>>;
+
+/// Tracks the digests held by a [CertifiedTxnIndex]. Once the filter is full
+/// it can no longer rule digests out, and every lookup goes to the backend.
+#[derive(Debug, Clone)]
+pub(crate) struct CertifiedTxnFilter {
+    bloom: Bloom,
+    saturated: bool,
+}
+
+impl Default for CertifiedTxnFilter {
+    fn default() -> Self {
+        Self {
+            bloom: Bloom::new(CERTIFIED_TXN_FILTER_CAPACITY),
+            saturated: false,
+        }
+    }
+}
+
+impl CertifiedTxnFilter {
+    fn insert(&mut self, digest: &TransactionDigest) {
+        if self.bloom.push(digest).is_err() {
+            self.saturated = true;
+        }
+    }
+
+    pub(crate) fn may_contain(&self, digest: &TransactionDigest) -> bool {
+        self.saturated || self.bloom.contains(digest)
+    }
+}
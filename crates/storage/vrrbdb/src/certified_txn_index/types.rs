@@ -0,0 +1,28 @@
+use std::fmt::{self, Display, Formatter};
+
+use primitives::QuorumPublicKey;
+use serde::{Deserialize, Serialize};
+use vrrb_core::transactions::TransactionDigest;
+
+/// The canonical key a certified transaction is indexed by: the transaction's
+/// digest and the public key of the farmer quorum that certified it.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertifiedTxnKey {
+    pub digest: TransactionDigest,
+    pub quorum_public_key: QuorumPublicKey,
+}
+
+impl CertifiedTxnKey {
+    pub fn new(digest: TransactionDigest, quorum_public_key: QuorumPublicKey) -> Self {
+        Self {
+            digest,
+            quorum_public_key,
+        }
+    }
+}
+
+impl Display for CertifiedTxnKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.digest, hex::encode(&self.quorum_public_key))
+    }
+}
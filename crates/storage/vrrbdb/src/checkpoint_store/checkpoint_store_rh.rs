@@ -0,0 +1,43 @@
+use block::Checkpoint;
+use primitives::Round;
+use storage_utils::{Result, StorageError};
+
+use crate::SharedBackend;
+
+#[derive(Debug, Clone)]
+pub struct CheckpointStoreReadHandle {
+    backend: SharedBackend,
+}
+
+impl CheckpointStoreReadHandle {
+    pub(crate) fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Returns the checkpoint taken at `round`
+    pub fn get(&self, round: Round) -> Result<Checkpoint> {
+        self.backend
+            .get(&round.to_be_bytes())?
+            .map(|value| {
+                bincode::deserialize::<Checkpoint>(&value)
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()?
+            .ok_or_else(|| StorageError::NotFound(format!("checkpoint at round {round}")))
+    }
+
+    /// Returns the most recently taken checkpoint
+    pub fn latest(&self) -> Option<Checkpoint> {
+        self.entries().pop()
+    }
+
+    /// Returns every recorded checkpoint, oldest first
+    pub fn entries(&self) -> Vec<Checkpoint> {
+        self.backend
+            .entries()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(_, value)| bincode::deserialize::<Checkpoint>(&value).ok())
+            .collect()
+    }
+}
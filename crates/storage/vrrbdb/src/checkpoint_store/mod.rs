@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use block::Checkpoint;
+use primitives::DbBackend;
+use storage_utils::{Result, StorageError};
+
+use crate::{open_backend, SharedBackend};
+
+mod checkpoint_store_rh;
+pub use checkpoint_store_rh::*;
+
+/// Persists the quorum-signed checkpoints a node exports for bridges and
+/// other external verifiers.
+///
+/// Checkpoints are keyed by the big-endian round they were taken at, so the
+/// backend keeps them in round order. They are taken every few rounds only,
+/// so none of them are pruned.
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    backend: SharedBackend,
+}
+
+impl Default for CheckpointStore {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(&db_path, DbBackend::default())
+            .expect("failed to open the default checkpoint store")
+    }
+}
+
+impl CheckpointStore {
+    /// Opens the checkpoint store within `path`, picking up the checkpoints
+    /// persisted by previous runs.
+    pub fn new(path: &Path, backend: DbBackend) -> Result<Self> {
+        let backend = open_backend(backend, path.join("checkpoints"), "checkpoints")?;
+
+        Ok(Self { backend })
+    }
+
+    pub fn read_handle(&self) -> CheckpointStoreReadHandle {
+        CheckpointStoreReadHandle::new(self.backend.clone())
+    }
+
+    pub(crate) fn backend(&self) -> SharedBackend {
+        self.backend.clone()
+    }
+
+    /// Records a checkpoint, replacing any checkpoint taken at the same round
+    pub fn record_checkpoint(&mut self, checkpoint: &Checkpoint) -> Result<()> {
+        let value =
+            bincode::serialize(checkpoint).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend.put(&checkpoint.round.to_be_bytes(), &value)
+    }
+}
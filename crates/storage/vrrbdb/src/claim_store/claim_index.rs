@@ -0,0 +1,495 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, VecDeque},
+    sync::Arc,
+};
+
+use ethereum_types::U256;
+use parking_lot::RwLock;
+use primitives::NodeId;
+use sha2::{Digest, Sha256};
+use vrrb_core::claim::{Claim, Eligibility};
+
+/// Number of seeded elections kept ranked at once, enough for the miner and
+/// quorum elections of the current and the next seed
+const PREPARED_ELECTIONS: usize = 4;
+
+/// Times ranking an election ahead of time is retried when the claims change
+/// while it's being ranked
+const PREPARE_ATTEMPTS: usize = 3;
+
+/// The elections claims are ranked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElectionKind {
+    /// Elects the miner of the next block among the miner-eligible claims
+    Miner,
+
+    /// Elects the next quorum among the harvester and farmer claims
+    Quorum,
+}
+
+impl ElectionKind {
+    fn admits(&self, claim: &Claim) -> bool {
+        match self {
+            ElectionKind::Miner => claim.eligibility == Eligibility::Miner,
+            ElectionKind::Quorum => matches!(
+                claim.eligibility,
+                Eligibility::Harvester | Eligibility::Farmer
+            ),
+        }
+    }
+}
+
+/// Claims eligible for an election, ranked by their weighted election result
+/// for a given seed, see [Claim::get_weighted_election_result]. Ties are
+/// broken by claim hash.
+#[derive(Debug, Clone)]
+struct SeededElection {
+    kind: ElectionKind,
+    seed: u64,
+    results: BTreeSet<(U256, U256)>,
+}
+
+/// In-memory companion to the on-disk claim trie.
+///
+/// Keeps every claim eligible for an election keyed by its hash, so running
+/// an election does not require scanning and deserializing the whole trie.
+/// The hash of every claim is kept by node id too, so a single claim can be
+/// looked up without scanning the trie either.
+/// The digest of every claim's delegation state is kept as well, along with
+/// the claims holding payouts due at the start of the next epoch, so the
+/// delegation root can be worked out and payouts made without reading every
+/// claim either, see [ClaimIndex::delegation_root].
+/// Elections can be ranked for a seed ahead of time through
+/// [ClaimIndex::prepare], which sorts the claims without holding the index's
+/// lock. Ranked elections are then maintained incrementally as claims are
+/// inserted or removed. Looking up the results of an election only ever reads
+/// the index, falling back to a partial scan for seeds that weren't prepared.
+#[derive(Debug, Clone, Default)]
+pub struct ClaimIndex {
+    miners: HashMap<U256, Claim>,
+    validators: HashMap<U256, Claim>,
+    prepared: VecDeque<SeededElection>,
+
+    /// Hash of every claim in the trie, eligible or not, by node id
+    hashes: HashMap<NodeId, U256>,
+    node_ids: HashMap<U256, NodeId>,
+
+    /// Digest of the delegation state of every claim holding any, see
+    /// [Claim::delegation_digest]
+    delegation_digests: BTreeMap<NodeId, [u8; 32]>,
+
+    /// Claims holding rewards or undelegated stake to pay out
+    pending_payouts: BTreeSet<NodeId>,
+
+    /// Bumped on every change to the indexed claims
+    generation: u64,
+}
+
+impl ClaimIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn claims(&self, kind: ElectionKind) -> &HashMap<U256, Claim> {
+        match kind {
+            ElectionKind::Miner => &self.miners,
+            ElectionKind::Quorum => &self.validators,
+        }
+    }
+
+    /// Adds or replaces a claim within the index. Claims that are not
+    /// eligible for any election are dropped from it.
+    pub fn insert(&mut self, claim: Claim) {
+        self.remove(&claim.hash);
+
+        self.hashes.insert(claim.node_id.clone(), claim.hash);
+        self.node_ids.insert(claim.hash, claim.node_id.clone());
+
+        if let Some(digest) = claim.delegation_digest() {
+            self.delegation_digests
+                .insert(claim.node_id.clone(), digest);
+        }
+
+        if claim.has_pending_payouts() {
+            self.pending_payouts.insert(claim.node_id.clone());
+        }
+
+        for kind in [ElectionKind::Miner, ElectionKind::Quorum] {
+            if !kind.admits(&claim) {
+                continue;
+            }
+
+            self.prepared
+                .iter_mut()
+                .filter(|election| election.kind == kind)
+                .for_each(|election| {
+                    election.results.insert((
+                        claim.get_weighted_election_result(election.seed),
+                        claim.hash,
+                    ));
+                });
+
+            match kind {
+                ElectionKind::Miner => self.miners.insert(claim.hash, claim.clone()),
+                ElectionKind::Quorum => self.validators.insert(claim.hash, claim.clone()),
+            };
+        }
+
+        self.generation += 1;
+    }
+
+    /// Removes a claim from the index, returning it if it was present.
+    pub fn remove(&mut self, hash: &U256) -> Option<Claim> {
+        if let Some(node_id) = self.node_ids.remove(hash) {
+            self.hashes.remove(&node_id);
+            self.delegation_digests.remove(&node_id);
+            self.pending_payouts.remove(&node_id);
+        }
+
+        let miner = self.miners.remove(hash);
+        let validator = self.validators.remove(hash);
+        let claim = miner.or(validator)?;
+
+        self.prepared.iter_mut().for_each(|election| {
+            election.results.remove(&(
+                claim.get_weighted_election_result(election.seed),
+                claim.hash,
+            ));
+        });
+
+        self.generation += 1;
+
+        Some(claim)
+    }
+
+    /// Returns the hash of the claim of `node_id`, eligible or not
+    pub fn hash_of(&self, node_id: &NodeId) -> Option<U256> {
+        self.hashes.get(node_id).copied()
+    }
+
+    /// Returns the node ids of the claims holding rewards or undelegated
+    /// stake to pay out, in order
+    pub fn pending_payouts(&self) -> Vec<NodeId> {
+        self.pending_payouts.iter().cloned().collect()
+    }
+
+    /// Returns the hex encoded root the delegation state of every claim
+    /// commits to, once `changed` replaced the claims they're for.
+    ///
+    /// It is the SHA-256 digest of every claim holding delegations,
+    /// undelegations or accrued rewards, in node id order, each encoded as its
+    /// node id prefixed by its length as a little-endian `u64`, followed by its
+    /// [Claim::delegation_digest].
+    pub fn delegation_root(&self, changed: &[Claim]) -> String {
+        let mut digests: BTreeMap<&NodeId, Option<[u8; 32]>> = self
+            .delegation_digests
+            .iter()
+            .map(|(node_id, digest)| (node_id, Some(*digest)))
+            .collect();
+
+        for claim in changed {
+            digests.insert(&claim.node_id, claim.delegation_digest());
+        }
+
+        let mut hasher = Sha256::new();
+
+        for (node_id, digest) in digests {
+            let Some(digest) = digest else {
+                continue;
+            };
+
+            hasher.update((node_id.len() as u64).to_le_bytes());
+            hasher.update(node_id.as_bytes());
+            hasher.update(digest);
+        }
+
+        hex::encode(hasher.finalize())
+    }
+
+    /// Returns the number of miner-eligible claims in the index
+    pub fn len(&self) -> usize {
+        self.miners.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.miners.is_empty()
+    }
+
+    /// Returns the number of claims eligible for `kind` of election
+    pub fn eligible(&self, kind: ElectionKind) -> usize {
+        self.claims(kind).len()
+    }
+
+    /// Returns whether `kind` of election was ranked ahead of time for `seed`
+    pub fn is_prepared(&self, kind: ElectionKind, seed: u64) -> bool {
+        self.prepared
+            .iter()
+            .any(|election| election.kind == kind && election.seed == seed)
+    }
+
+    /// Returns the miner-eligible claim with the lowest election result for
+    /// the given seed.
+    pub fn winner(&self, seed: u64) -> Option<(U256, Claim)> {
+        self.top(seed, 1).into_iter().next()
+    }
+
+    /// Returns up to `limit` miner-eligible claims with the lowest election
+    /// results for the given seed, in ascending order.
+    pub fn top(&self, seed: u64, limit: usize) -> Vec<(U256, Claim)> {
+        self.ranked(ElectionKind::Miner, seed, limit)
+    }
+
+    /// Returns up to `limit` claims eligible for `kind` of election with the
+    /// lowest election results for the given seed, in ascending order.
+    pub fn ranked(&self, kind: ElectionKind, seed: u64, limit: usize) -> Vec<(U256, Claim)> {
+        let claims = self.claims(kind);
+
+        let prepared = self
+            .prepared
+            .iter()
+            .find(|election| election.kind == kind && election.seed == seed);
+
+        let ranked: Vec<(U256, U256)> = match prepared {
+            Some(election) => election.results.iter().take(limit).copied().collect(),
+            None => lowest_results(claims.values(), seed, limit),
+        };
+
+        ranked
+            .into_iter()
+            .filter_map(|(result, hash)| claims.get(&hash).map(|claim| (result, claim.clone())))
+            .collect()
+    }
+
+    /// Ranks `kind` of election for `seed` ahead of time.
+    ///
+    /// The election results are computed while holding a read lock on the
+    /// index and sorted without holding any, so neither elections nor claim
+    /// updates wait on the sort. The ranking is only installed if the claims
+    /// didn't change meanwhile, and is otherwise computed again.
+    pub fn prepare(index: &SharedClaimIndex, kind: ElectionKind, seed: u64) {
+        for _ in 0..PREPARE_ATTEMPTS {
+            let (generation, results) = {
+                let index = index.read();
+
+                if index.is_prepared(kind, seed) {
+                    return;
+                }
+
+                let results: Vec<(U256, U256)> = index
+                    .claims(kind)
+                    .values()
+                    .map(|claim| (claim.get_weighted_election_result(seed), claim.hash))
+                    .collect();
+
+                (index.generation, results)
+            };
+
+            let results: BTreeSet<(U256, U256)> = results.into_iter().collect();
+
+            let mut index = index.write();
+            if index.generation != generation {
+                continue;
+            }
+
+            if index.prepared.len() == PREPARED_ELECTIONS {
+                index.prepared.pop_front();
+            }
+
+            index.prepared.push_back(SeededElection {
+                kind,
+                seed,
+                results,
+            });
+
+            return;
+        }
+
+        telemetry::warn!(
+            "Gave up ranking the {kind:?} election for seed {seed} ahead of time, claims kept changing"
+        );
+    }
+}
+
+/// Returns the `limit` lowest election results of `claims` for `seed`, in
+/// ascending order, without sorting every claim
+fn lowest_results<'a>(
+    claims: impl Iterator<Item = &'a Claim>,
+    seed: u64,
+    limit: usize,
+) -> Vec<(U256, U256)> {
+    let mut lowest = BinaryHeap::with_capacity(limit.saturating_add(1).min(1024));
+
+    for claim in claims {
+        lowest.push((claim.get_weighted_election_result(seed), claim.hash));
+
+        if lowest.len() > limit {
+            lowest.pop();
+        }
+    }
+
+    lowest.into_sorted_vec()
+}
+
+/// A claim index shared between a [crate::ClaimStore] and the read handles it
+/// hands out.
+pub type SharedClaimIndex = Arc<RwLock<ClaimIndex>>;
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, net::SocketAddr};
+
+    use primitives::Address;
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+
+    fn claim(eligibility: Eligibility) -> Claim {
+        let keypair = Keypair::random();
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let public_key = *keypair.get_miner_public_key();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            keypair.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+
+        let mut claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature.clone(),
+            signature,
+        )
+        .unwrap();
+
+        claim.eligibility = eligibility;
+        claim
+    }
+
+    fn miner_claim() -> Claim {
+        claim(Eligibility::Miner)
+    }
+
+    #[test]
+    fn winner_matches_a_full_election_scan() {
+        let claims: Vec<Claim> = (0..20).map(|_| miner_claim()).collect();
+
+        let mut index = ClaimIndex::new();
+        claims.iter().cloned().for_each(|claim| index.insert(claim));
+
+        for seed in [0, 7, u64::MAX] {
+            let expected: BTreeMap<U256, Claim> = claims
+                .iter()
+                .map(|claim| (claim.get_weighted_election_result(seed), claim.clone()))
+                .collect();
+
+            let winner = index.winner(seed).unwrap();
+            assert_eq!(Some((&winner.0, &winner.1)), expected.iter().next());
+        }
+    }
+
+    #[test]
+    fn index_tracks_changes_after_being_seeded() {
+        let index: SharedClaimIndex = Arc::default();
+        let first = miner_claim();
+        index.write().insert(first.clone());
+
+        ClaimIndex::prepare(&index, ElectionKind::Miner, 42);
+        assert!(index.read().is_prepared(ElectionKind::Miner, 42));
+        assert_eq!(index.read().winner(42).unwrap().1, first);
+
+        let mut index = index.write();
+
+        index.insert(claim(Eligibility::Farmer));
+
+        let second = miner_claim();
+        index.insert(second.clone());
+
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.top(42, 10).len(), 2);
+
+        assert_eq!(index.hash_of(&first.node_id), Some(first.hash));
+
+        index.remove(&first.hash);
+        assert_eq!(index.winner(42).unwrap().1, second);
+        assert_eq!(index.hash_of(&first.node_id), None);
+
+        index.remove(&second.hash);
+        assert!(index.winner(42).is_none());
+    }
+
+    #[test]
+    fn delegation_roots_follow_the_delegation_state_of_claims() {
+        let mut index = ClaimIndex::new();
+        let mut claim = miner_claim();
+        index.insert(claim.clone());
+
+        let empty_root = index.delegation_root(&[]);
+        assert!(index.pending_payouts().is_empty());
+
+        claim.delegate(claim.address.clone(), 10);
+        let delegated_root = index.delegation_root(std::slice::from_ref(&claim));
+        assert_ne!(delegated_root, empty_root);
+
+        index.insert(claim.clone());
+        assert_eq!(index.delegation_root(&[]), delegated_root);
+        assert!(index.pending_payouts().is_empty());
+
+        claim.accrue_reward(5);
+        index.insert(claim.clone());
+        assert_eq!(index.pending_payouts(), vec![claim.node_id.clone()]);
+        assert_ne!(index.delegation_root(&[]), delegated_root);
+
+        index.remove(&claim.hash);
+        assert_eq!(index.delegation_root(&[]), empty_root);
+        assert!(index.pending_payouts().is_empty());
+    }
+
+    #[test]
+    fn prepared_and_unprepared_rankings_agree_for_both_elections() {
+        let claims: Vec<Claim> = (0..10)
+            .map(|_| miner_claim())
+            .chain((0..10).map(|_| claim(Eligibility::Harvester)))
+            .chain((0..10).map(|_| claim(Eligibility::Farmer)))
+            .collect();
+
+        let index: SharedClaimIndex = Arc::default();
+        claims
+            .iter()
+            .cloned()
+            .for_each(|claim| index.write().insert(claim));
+
+        assert_eq!(index.read().eligible(ElectionKind::Miner), 10);
+        assert_eq!(index.read().eligible(ElectionKind::Quorum), 20);
+
+        for kind in [ElectionKind::Miner, ElectionKind::Quorum] {
+            let scanned = index.read().ranked(kind, 9, 5);
+
+            ClaimIndex::prepare(&index, kind, 9);
+            assert_eq!(index.read().ranked(kind, 9, 5), scanned);
+
+            let mut expected: Vec<(U256, U256)> = claims
+                .iter()
+                .filter(|claim| kind.admits(claim))
+                .map(|claim| (claim.get_weighted_election_result(9), claim.hash))
+                .collect();
+            expected.sort();
+            expected.truncate(5);
+
+            assert_eq!(
+                scanned
+                    .iter()
+                    .map(|(result, claim)| (*result, claim.hash))
+                    .collect::<Vec<_>>(),
+                expected
+            );
+        }
+
+        // NOTE: only the most recent rankings are kept around
+        for seed in 10..10 + PREPARED_ELECTIONS as u64 {
+            ClaimIndex::prepare(&index, ElectionKind::Miner, seed);
+        }
+        assert!(!index.read().is_prepared(ElectionKind::Quorum, 9));
+    }
+}
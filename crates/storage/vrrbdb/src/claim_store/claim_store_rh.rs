@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use ethereum_types::U256;
 use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
 use patriecia::{JellyfishMerkleTree, Version};
 use primitives::NodeId;
@@ -7,16 +8,20 @@ use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::claim::Claim;
 
-use crate::RocksDbAdapter;
+use crate::{ClaimIndex, ElectionKind, SharedClaimIndex, TrieDbAdapter};
 
 #[derive(Debug, Clone)]
 pub struct ClaimStoreReadHandle {
-    inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>,
+    inner: JellyfishMerkleTreeWrapper<TrieDbAdapter, Sha256>,
+    index: SharedClaimIndex,
 }
 
 impl ClaimStoreReadHandle {
-    pub fn new(inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: JellyfishMerkleTreeWrapper<TrieDbAdapter, Sha256>,
+        index: SharedClaimIndex,
+    ) -> Self {
+        Self { inner, index }
     }
 
     /// Returns `Some(Claim)` if an account exist under given PublicKey.
@@ -27,6 +32,20 @@ impl ClaimStoreReadHandle {
             .map_err(|err| StorageError::Other(err.to_string()))
     }
 
+    /// Returns the claim of `node_id`, looking its hash up in the claim index
+    /// rather than scanning the trie
+    pub fn get_by_node_id(&self, node_id: &NodeId) -> Result<Claim> {
+        let hash = self
+            .index
+            .read()
+            .hash_of(node_id)
+            .ok_or_else(|| StorageError::Other(format!("no claim for node {node_id}")))?;
+
+        self.inner
+            .get(&hash, self.inner.version())
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
     /// Get a batch of claims by providing Vec of PublicKeysHash
     ///
     /// Returns HashMap indexed by PublicKeys and containing either
@@ -59,6 +78,59 @@ impl ClaimStoreReadHandle {
             .collect()
     }
 
+    /// Returns the miner-eligible claim with the lowest weighted election
+    /// result for the given seed, without scanning the claim trie.
+    pub fn election_winner(&self, seed: u64) -> Option<(U256, Claim)> {
+        self.index.read().winner(seed)
+    }
+
+    /// Returns up to `limit` miner-eligible claims ordered by their weighted
+    /// election result for the given seed.
+    pub fn election_results(&self, seed: u64, limit: usize) -> Vec<(U256, Claim)> {
+        self.index.read().top(seed, limit)
+    }
+
+    /// Returns the claims eligible for quorum elections with the lowest
+    /// weighted election results for the given seed, as many as `seats` yields for the
+    /// number of claims eligible, along with that number
+    pub fn quorum_election_results(
+        &self,
+        seed: u64,
+        seats: impl FnOnce(usize) -> usize,
+    ) -> (Vec<(U256, Claim)>, usize) {
+        let index = self.index.read();
+        let eligible = index.eligible(ElectionKind::Quorum);
+
+        (
+            index.ranked(ElectionKind::Quorum, seed, seats(eligible)),
+            eligible,
+        )
+    }
+
+    /// Ranks the miner and quorum elections for `seed` ahead of time, so the
+    /// elections don't have to
+    pub fn prepare_elections(&self, seed: u64) {
+        ClaimIndex::prepare(&self.index, ElectionKind::Miner, seed);
+        ClaimIndex::prepare(&self.index, ElectionKind::Quorum, seed);
+    }
+
+    /// Returns the claims holding rewards or undelegated stake to pay out,
+    /// looked up through the claim index rather than by scanning the trie
+    pub fn claims_with_pending_payouts(&self) -> Vec<Claim> {
+        let node_ids = self.index.read().pending_payouts();
+
+        node_ids
+            .iter()
+            .filter_map(|node_id| self.get_by_node_id(node_id).ok())
+            .collect()
+    }
+
+    /// Returns the root the delegation state of every claim commits to once
+    /// `changed` are stored, see [ClaimIndex::delegation_root]
+    pub fn delegation_root(&self, changed: &[Claim]) -> String {
+        self.index.read().delegation_root(changed)
+    }
+
     /// Returns a number of initialized claims in the database
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -72,12 +144,16 @@ impl ClaimStoreReadHandle {
 
 #[derive(Debug, Clone)]
 pub struct ClaimStoreReadHandleFactory {
-    inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+    inner: ReadHandleFactory<JellyfishMerkleTree<TrieDbAdapter, Sha256>>,
+    index: SharedClaimIndex,
 }
 
 impl ClaimStoreReadHandleFactory {
-    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: ReadHandleFactory<JellyfishMerkleTree<TrieDbAdapter, Sha256>>,
+        index: SharedClaimIndex,
+    ) -> Self {
+        Self { inner, index }
     }
 
     pub fn handle(&self) -> ClaimStoreReadHandle {
@@ -90,6 +166,9 @@ impl ClaimStoreReadHandleFactory {
 
         let inner = JellyfishMerkleTreeWrapper::new(handle);
 
-        ClaimStoreReadHandle { inner }
+        ClaimStoreReadHandle {
+            inner,
+            index: self.index.clone(),
+        }
     }
 }
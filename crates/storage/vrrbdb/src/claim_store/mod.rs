@@ -2,14 +2,18 @@ use std::{path::Path, sync::Arc};
 
 use ethereum_types::U256;
 use integral_db::LeftRightTrie;
+use parking_lot::RwLock;
 use patriecia::RootHash;
+use primitives::DbBackend;
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::claim::Claim;
 
-use crate::RocksDbAdapter;
+use crate::{SharedBackend, TrieDbAdapter};
 
+mod claim_index;
 mod claim_store_rh;
+pub use claim_index::*;
 pub use claim_store_rh::*;
 
 pub type Claims = Vec<Claim>;
@@ -17,7 +21,11 @@ pub type FailedClaimUpdates = Vec<(U256, Claims, Result<()>)>;
 
 #[derive(Debug, Clone)]
 pub struct ClaimStore {
-    trie: LeftRightTrie<'static, U256, Claim, RocksDbAdapter, Sha256>,
+    trie: LeftRightTrie<'static, U256, Claim, TrieDbAdapter, Sha256>,
+    index: SharedClaimIndex,
+
+    /// Backend the nodes of the trie are persisted to
+    nodes: SharedBackend,
 }
 
 impl Default for ClaimStore {
@@ -27,29 +35,56 @@ impl Default for ClaimStore {
             .join("db")
             .join("claim");
 
-        let db_adapter = RocksDbAdapter::new(db_path, "claims").unwrap_or_default();
+        let db_adapter = TrieDbAdapter::new(DbBackend::default(), db_path, "claims").unwrap_or_default();
 
+        let nodes = db_adapter.backend();
         let trie = LeftRightTrie::new(Arc::new(db_adapter));
 
-        Self { trie }
+        Self::with_trie(trie, nodes)
     }
 }
 
 impl ClaimStore {
     /// Returns new, empty instance of ClaimDb
     pub fn new(path: &Path) -> Self {
+        Self::new_with_backend(path, DbBackend::default())
+    }
+
+    /// Returns new, empty instance of ClaimDb persisted to the given `backend`
+    pub fn new_with_backend(path: &Path, backend: DbBackend) -> Self {
         let path = path.join("claims");
-        let db_adapter = RocksDbAdapter::new(path, "claims").unwrap_or_default();
+        let db_adapter = TrieDbAdapter::new(backend, path, "claims").unwrap_or_default();
+        let nodes = db_adapter.backend();
         let trie = LeftRightTrie::new(Arc::new(db_adapter));
 
-        Self { trie }
+        Self::with_trie(trie, nodes)
+    }
+
+    /// Wraps a claim trie, hydrating the in-memory claim index from whatever
+    /// claims were already persisted to it.
+    fn with_trie(
+        trie: LeftRightTrie<'static, U256, Claim, TrieDbAdapter, Sha256>,
+        nodes: SharedBackend,
+    ) -> Self {
+        let mut index = ClaimIndex::new();
+
+        ClaimStoreReadHandle::new(trie.handle(), SharedClaimIndex::default())
+            .entries()
+            .into_values()
+            .for_each(|claim| index.insert(claim));
+
+        Self {
+            trie,
+            index: Arc::new(RwLock::new(index)),
+            nodes,
+        }
     }
 
     /// Returns new ReadHandle to the VrrDb data. As long as the returned value
     /// lives, no write to the database will be committed.
     pub fn read_handle(&self) -> ClaimStoreReadHandle {
         let inner = self.trie.handle();
-        ClaimStoreReadHandle::new(inner)
+        ClaimStoreReadHandle::new(inner, self.index.clone())
     }
 
     /// Commits uncommitted changes to the underlying trie by calling
@@ -58,6 +93,10 @@ impl ClaimStore {
         self.trie.publish();
     }
 
+    pub(crate) fn node_backend(&self) -> SharedBackend {
+        self.nodes.clone()
+    }
+
     // Maybe initialize is better name for that?
     fn insert_uncommited(&mut self, claim: Claim) -> Result<()> {
         //        if claim.debits != 0 {
@@ -72,6 +111,7 @@ impl ClaimStore {
         //            ));
         //        }
 
+        self.index.write().insert(claim.clone());
         self.trie.insert(claim.hash, claim);
 
         Ok(())
@@ -282,12 +322,22 @@ impl ClaimStore {
     }
 
     pub fn extend(&mut self, claims: Vec<(U256, Option<Claim>)>) {
+        {
+            let mut index = self.index.write();
+            claims.iter().for_each(|(hash, claim)| match claim {
+                Some(claim) => index.insert(claim.clone()),
+                None => {
+                    index.remove(hash);
+                },
+            });
+        }
+
         self.trie.extend(claims)
     }
 
     pub fn factory(&self) -> ClaimStoreReadHandleFactory {
         let inner = self.trie.factory();
 
-        ClaimStoreReadHandleFactory::new(inner)
+        ClaimStoreReadHandleFactory::new(inner, self.index.clone())
     }
 }
@@ -0,0 +1,41 @@
+use primitives::Round;
+use storage_utils::{Result, StorageError};
+
+use crate::{RoundTimeline, SharedBackend};
+
+#[derive(Debug, Clone)]
+pub struct ConsensusTimelineStoreReadHandle {
+    backend: SharedBackend,
+}
+
+impl ConsensusTimelineStoreReadHandle {
+    pub(crate) fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Returns the timeline of a round, if any of its milestones was recorded
+    pub fn get(&self, round: Round) -> Result<Option<RoundTimeline>> {
+        self.backend
+            .get(&round.to_be_bytes())?
+            .map(|value| {
+                bincode::deserialize::<RoundTimeline>(&value)
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Returns the timelines of every retained round, oldest first
+    pub fn entries(&self) -> Vec<RoundTimeline> {
+        let mut timelines = self
+            .backend
+            .entries()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(_, value)| bincode::deserialize::<RoundTimeline>(&value).ok())
+            .collect::<Vec<RoundTimeline>>();
+
+        timelines.sort_by_key(|timeline| timeline.round);
+
+        timelines
+    }
+}
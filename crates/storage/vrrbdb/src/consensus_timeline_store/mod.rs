@@ -0,0 +1,109 @@
+use std::{collections::BTreeSet, path::Path, sync::Arc};
+
+use parking_lot::RwLock;
+use primitives::{DbBackend, Round, DEFAULT_RETAINED_TIMELINE_ROUNDS};
+use storage_utils::{Result, StorageError};
+
+use crate::{open_backend, SharedBackend};
+
+mod consensus_timeline_store_rh;
+mod types;
+pub use consensus_timeline_store_rh::*;
+pub use types::*;
+
+/// Persists when every round went through each step of consensus, so slow
+/// rounds can be dissected after the fact without external tracing.
+///
+/// Timelines are keyed by the big-endian round they describe and filled in
+/// as the milestones happen. Only the `retained_rounds` most recent rounds
+/// are kept.
+#[derive(Debug, Clone)]
+pub struct ConsensusTimelineStore {
+    backend: SharedBackend,
+    retained_rounds: usize,
+    rounds: Arc<RwLock<BTreeSet<Round>>>,
+}
+
+impl Default for ConsensusTimelineStore {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(
+            &db_path,
+            DbBackend::default(),
+            DEFAULT_RETAINED_TIMELINE_ROUNDS,
+        )
+        .expect("failed to open the default consensus timeline store")
+    }
+}
+
+impl ConsensusTimelineStore {
+    /// Opens the timeline store within `path`, picking up the timelines
+    /// persisted by previous runs.
+    pub fn new(path: &Path, backend: DbBackend, retained_rounds: usize) -> Result<Self> {
+        let backend = open_backend(
+            backend,
+            path.join("consensus_timeline"),
+            "consensus_timeline",
+        )?;
+
+        let rounds = ConsensusTimelineStoreReadHandle::new(backend.clone())
+            .entries()
+            .into_iter()
+            .map(|timeline| timeline.round)
+            .collect();
+
+        Ok(Self {
+            backend,
+            retained_rounds,
+            rounds: Arc::new(RwLock::new(rounds)),
+        })
+    }
+
+    pub fn read_handle(&self) -> ConsensusTimelineStoreReadHandle {
+        ConsensusTimelineStoreReadHandle::new(self.backend.clone())
+    }
+
+    /// Records when a round reached a milestone. Only the first time it was
+    /// reached is kept
+    pub fn record(&mut self, round: Round, milestone: RoundMilestone, at: i64) -> Result<()> {
+        let mut timeline = self
+            .read_handle()
+            .get(round)?
+            .unwrap_or_else(|| RoundTimeline::new(round));
+
+        if timeline.reached_at(milestone).is_some() {
+            return Ok(());
+        }
+
+        timeline.reach(milestone, at);
+
+        let value =
+            bincode::serialize(&timeline).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend.put(&round.to_be_bytes(), &value)?;
+
+        self.rounds.write().insert(round);
+
+        self.prune()
+    }
+
+    /// Deletes the timelines of the oldest rounds until at most
+    /// `retained_rounds` are left
+    fn prune(&mut self) -> Result<()> {
+        let mut rounds = self.rounds.write();
+
+        while rounds.len() > self.retained_rounds {
+            let Some(round) = rounds.pop_first() else {
+                break;
+            };
+
+            self.backend.delete(&round.to_be_bytes())?;
+        }
+
+        Ok(())
+    }
+}
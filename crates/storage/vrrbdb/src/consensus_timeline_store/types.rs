@@ -0,0 +1,71 @@
+use primitives::Round;
+use serde::{Deserialize, Serialize};
+
+/// Steps a round goes through on its way to being applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundMilestone {
+    /// A proposal block of the round was received
+    ProposalSeen,
+
+    /// Enough partial signatures were gathered over the round's convergence
+    /// block to certify it
+    SignatureThresholdReached,
+
+    /// The round's convergence block was certified
+    CertificateCreated,
+
+    /// The round's convergence block was applied to the ledger
+    BlockApplied,
+}
+
+/// When a node witnessed every milestone of a round, as Unix timestamps in
+/// milliseconds. Milestones the node didn't witness are left unset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundTimeline {
+    pub round: Round,
+    pub first_proposal_seen_at: Option<i64>,
+    pub signature_threshold_reached_at: Option<i64>,
+    pub certificate_created_at: Option<i64>,
+    pub block_applied_at: Option<i64>,
+}
+
+impl RoundTimeline {
+    pub fn new(round: Round) -> Self {
+        Self {
+            round,
+            first_proposal_seen_at: None,
+            signature_threshold_reached_at: None,
+            certificate_created_at: None,
+            block_applied_at: None,
+        }
+    }
+
+    /// Returns when `milestone` was reached, if it was
+    pub fn reached_at(&self, milestone: RoundMilestone) -> Option<i64> {
+        match milestone {
+            RoundMilestone::ProposalSeen => self.first_proposal_seen_at,
+            RoundMilestone::SignatureThresholdReached => self.signature_threshold_reached_at,
+            RoundMilestone::CertificateCreated => self.certificate_created_at,
+            RoundMilestone::BlockApplied => self.block_applied_at,
+        }
+    }
+
+    /// Records `milestone` as reached at `at`, unless it was reached earlier
+    pub fn reach(&mut self, milestone: RoundMilestone, at: i64) {
+        let reached_at = match milestone {
+            RoundMilestone::ProposalSeen => &mut self.first_proposal_seen_at,
+            RoundMilestone::SignatureThresholdReached => &mut self.signature_threshold_reached_at,
+            RoundMilestone::CertificateCreated => &mut self.certificate_created_at,
+            RoundMilestone::BlockApplied => &mut self.block_applied_at,
+        };
+
+        reached_at.get_or_insert(at);
+    }
+
+    /// Returns the milliseconds elapsed between two milestones, if both were
+    /// reached
+    pub fn elapsed(&self, from: RoundMilestone, to: RoundMilestone) -> Option<i64> {
+        Some(self.reached_at(to)? - self.reached_at(from)?)
+    }
+}
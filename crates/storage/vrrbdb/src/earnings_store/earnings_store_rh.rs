@@ -0,0 +1,61 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use primitives::{Epoch, NodeId};
+
+use crate::{earnings_by_epoch, EarningsRecord, SharedBackend, ValidatorEarnings};
+
+#[derive(Debug, Clone)]
+pub struct EarningsStoreReadHandle {
+    backend: SharedBackend,
+}
+
+impl EarningsStoreReadHandle {
+    pub(crate) fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Returns what `node_id` earned during each of `epochs` it has records
+    /// for, in epoch order
+    pub fn validator_earnings(
+        &self,
+        node_id: &NodeId,
+        epochs: RangeInclusive<Epoch>,
+    ) -> Vec<ValidatorEarnings> {
+        let records: Vec<EarningsRecord> = self
+            .entries()
+            .into_iter()
+            .filter(|record| &record.node_id == node_id && epochs.contains(&record.epoch))
+            .collect();
+
+        earnings_by_epoch(&records)
+    }
+
+    /// Returns what each validator earned during `epoch`, in node id order
+    pub fn epoch_earnings(&self, epoch: Epoch) -> Vec<ValidatorEarnings> {
+        let mut by_validator: BTreeMap<NodeId, Vec<EarningsRecord>> = BTreeMap::new();
+
+        for record in self.entries() {
+            if record.epoch == epoch {
+                by_validator
+                    .entry(record.node_id.clone())
+                    .or_default()
+                    .push(record);
+            }
+        }
+
+        by_validator
+            .values()
+            .flat_map(|records| earnings_by_epoch(records))
+            .collect()
+    }
+
+    /// Returns every record, grouped by validator
+    pub fn entries(&self) -> Vec<EarningsRecord> {
+        self.backend
+            .entries()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(_, value)| bincode::deserialize::<EarningsRecord>(&value).ok())
+            .collect()
+    }
+}
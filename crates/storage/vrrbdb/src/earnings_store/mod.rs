@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use primitives::DbBackend;
+use storage_utils::{Result, StorageError};
+
+use crate::{open_backend, SharedBackend};
+
+mod earnings_store_rh;
+mod types;
+pub use earnings_store_rh::*;
+pub use types::*;
+
+/// Persists what each validator earned from block rewards and fee shares,
+/// and lost to slashing, block by block.
+///
+/// Records are keyed by node id, then the big-endian epoch and round of the
+/// block and its hash, so the records of a validator are kept together in
+/// the order they were earned.
+#[derive(Debug, Clone)]
+pub struct EarningsStore {
+    backend: SharedBackend,
+}
+
+impl Default for EarningsStore {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(&db_path, DbBackend::default())
+            .expect("failed to open the default earnings store")
+    }
+}
+
+impl EarningsStore {
+    /// Opens the earnings store within `path`, picking up the records
+    /// persisted by previous runs.
+    pub fn new(path: &Path, backend: DbBackend) -> Result<Self> {
+        let backend = open_backend(backend, path.join("earnings"), "earnings")?;
+
+        Ok(Self { backend })
+    }
+
+    pub fn read_handle(&self) -> EarningsStoreReadHandle {
+        EarningsStoreReadHandle::new(self.backend.clone())
+    }
+
+    /// Records what a validator earned from a block, replacing the record of
+    /// the same block if it is applied again
+    pub fn record_earnings(&mut self, record: &EarningsRecord) -> Result<()> {
+        let value =
+            bincode::serialize(record).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend.put(&record.key(), &value)
+    }
+}
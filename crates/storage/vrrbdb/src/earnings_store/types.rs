@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use block::BlockHash;
+use primitives::{Epoch, NodeId, Round};
+use serde::{Deserialize, Serialize};
+
+/// What a validator earned, and lost, from a single block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EarningsRecord {
+    pub node_id: NodeId,
+    pub epoch: Epoch,
+    pub round: Round,
+    pub block_hash: BlockHash,
+
+    /// Reward accrued to the validator's claim for mining the block
+    pub block_rewards: u128,
+
+    /// Fees credited to the validator for proposing the block's txns or
+    /// voting on them
+    pub fee_shares: u128,
+
+    /// Stake slashed from the validator's claim
+    pub penalties: u128,
+}
+
+impl EarningsRecord {
+    pub(crate) fn key(&self) -> Vec<u8> {
+        let mut key = self.node_id.as_bytes().to_vec();
+        key.push(0);
+        key.extend(self.epoch.to_be_bytes());
+        key.extend(self.round.to_be_bytes());
+        key.extend(self.block_hash.as_bytes());
+        key
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.block_rewards == 0 && self.fee_shares == 0 && self.penalties == 0
+    }
+}
+
+/// What a validator earned, and lost, over an epoch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorEarnings {
+    pub node_id: NodeId,
+    pub epoch: Epoch,
+    pub block_rewards: u128,
+    pub fee_shares: u128,
+    pub penalties: u128,
+
+    /// Blocks the validator earned or lost anything from
+    pub blocks: u64,
+}
+
+impl ValidatorEarnings {
+    /// What the validator earned over the epoch once its penalties are taken
+    /// out, which is negative if they outweigh its earnings
+    pub fn net(&self) -> i128 {
+        let earned = self.block_rewards.saturating_add(self.fee_shares);
+
+        (earned as i128).saturating_sub(self.penalties as i128)
+    }
+}
+
+/// Sums `records` up per epoch, in epoch order
+pub fn earnings_by_epoch<'a>(
+    records: impl IntoIterator<Item = &'a EarningsRecord>,
+) -> Vec<ValidatorEarnings> {
+    let mut by_epoch: BTreeMap<Epoch, ValidatorEarnings> = BTreeMap::new();
+
+    for record in records {
+        let earnings = by_epoch
+            .entry(record.epoch)
+            .or_insert_with(|| ValidatorEarnings {
+                node_id: record.node_id.clone(),
+                epoch: record.epoch,
+                ..Default::default()
+            });
+
+        earnings.block_rewards = earnings.block_rewards.saturating_add(record.block_rewards);
+        earnings.fee_shares = earnings.fee_shares.saturating_add(record.fee_shares);
+        earnings.penalties = earnings.penalties.saturating_add(record.penalties);
+        earnings.blocks += 1;
+    }
+
+    by_epoch.into_values().collect()
+}
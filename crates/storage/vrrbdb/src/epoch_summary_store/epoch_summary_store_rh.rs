@@ -0,0 +1,43 @@
+use block::EpochSummary;
+use primitives::Epoch;
+use storage_utils::{Result, StorageError};
+
+use crate::SharedBackend;
+
+#[derive(Debug, Clone)]
+pub struct EpochSummaryStoreReadHandle {
+    backend: SharedBackend,
+}
+
+impl EpochSummaryStoreReadHandle {
+    pub(crate) fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Returns the summary of `epoch`
+    pub fn get(&self, epoch: Epoch) -> Result<EpochSummary> {
+        self.backend
+            .get(&epoch.to_be_bytes())?
+            .map(|value| {
+                bincode::deserialize::<EpochSummary>(&value)
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()?
+            .ok_or_else(|| StorageError::NotFound(format!("summary of epoch {epoch}")))
+    }
+
+    /// Returns the summary of the latest epoch summarized
+    pub fn latest(&self) -> Option<EpochSummary> {
+        self.entries().pop()
+    }
+
+    /// Returns every recorded summary, oldest first
+    pub fn entries(&self) -> Vec<EpochSummary> {
+        self.backend
+            .entries()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(_, value)| bincode::deserialize::<EpochSummary>(&value).ok())
+            .collect()
+    }
+}
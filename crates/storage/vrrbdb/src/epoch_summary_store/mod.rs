@@ -0,0 +1,58 @@
+use std::path::Path;
+
+use block::EpochSummary;
+use primitives::DbBackend;
+use storage_utils::{Result, StorageError};
+
+use crate::{open_backend, SharedBackend};
+
+mod epoch_summary_store_rh;
+pub use epoch_summary_store_rh::*;
+
+/// Persists the chain of epoch summaries light clients sync from.
+///
+/// Summaries are keyed by the big-endian epoch they summarize, so the backend
+/// keeps them in epoch order. A single summary is produced per epoch, so none
+/// of them are pruned.
+#[derive(Debug, Clone)]
+pub struct EpochSummaryStore {
+    backend: SharedBackend,
+}
+
+impl Default for EpochSummaryStore {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(&db_path, DbBackend::default())
+            .expect("failed to open the default epoch summary store")
+    }
+}
+
+impl EpochSummaryStore {
+    /// Opens the epoch summary store within `path`, picking up the summaries
+    /// persisted by previous runs.
+    pub fn new(path: &Path, backend: DbBackend) -> Result<Self> {
+        let backend = open_backend(backend, path.join("epoch_summaries"), "epoch_summaries")?;
+
+        Ok(Self { backend })
+    }
+
+    pub fn read_handle(&self) -> EpochSummaryStoreReadHandle {
+        EpochSummaryStoreReadHandle::new(self.backend.clone())
+    }
+
+    pub(crate) fn backend(&self) -> SharedBackend {
+        self.backend.clone()
+    }
+
+    /// Records a summary, replacing any summary of the same epoch
+    pub fn record_epoch_summary(&mut self, summary: &EpochSummary) -> Result<()> {
+        let value =
+            bincode::serialize(summary).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend.put(&summary.epoch.to_be_bytes(), &value)
+    }
+}
@@ -0,0 +1,29 @@
+use block::SignedHaltDirective;
+
+use crate::SharedBackend;
+
+#[derive(Debug, Clone)]
+pub struct HaltLogStoreReadHandle {
+    backend: SharedBackend,
+}
+
+impl HaltLogStoreReadHandle {
+    pub(crate) fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Returns the directive most recently acted on
+    pub fn latest(&self) -> Option<SignedHaltDirective> {
+        self.entries().pop()
+    }
+
+    /// Returns every recorded directive, oldest first
+    pub fn entries(&self) -> Vec<SignedHaltDirective> {
+        self.backend
+            .entries()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(_, value)| bincode::deserialize::<SignedHaltDirective>(&value).ok())
+            .collect()
+    }
+}
@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use block::SignedHaltDirective;
+use primitives::DbBackend;
+use storage_utils::{Result, StorageError};
+
+use crate::{open_backend, SharedBackend};
+
+mod halt_log_store_rh;
+pub use halt_log_store_rh::*;
+
+/// Persists every halt and resume directive the harvester quorum issued, so
+/// operators and auditors can tell when and why block production stopped.
+/// It's only written as the blocks carrying the directives are applied, so
+/// every node rebuilds the same log from the chain.
+///
+/// Directives are keyed by the big-endian round they were issued at, so the
+/// backend keeps them in the order they took effect.
+#[derive(Debug, Clone)]
+pub struct HaltLogStore {
+    backend: SharedBackend,
+}
+
+impl Default for HaltLogStore {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(&db_path, DbBackend::default()).expect("failed to open the default halt log")
+    }
+}
+
+impl HaltLogStore {
+    /// Opens the halt log within `path`, picking up the directives persisted
+    /// by previous runs.
+    pub fn new(path: &Path, backend: DbBackend) -> Result<Self> {
+        let backend = open_backend(backend, path.join("halt_log"), "halt_log")?;
+
+        Ok(Self { backend })
+    }
+
+    pub fn read_handle(&self) -> HaltLogStoreReadHandle {
+        HaltLogStoreReadHandle::new(self.backend.clone())
+    }
+
+    /// Records a directive the node acted on
+    pub fn record_directive(&mut self, signed: &SignedHaltDirective) -> Result<()> {
+        let value =
+            bincode::serialize(signed).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend
+            .put(&signed.directive.round.to_be_bytes(), &value)
+    }
+}
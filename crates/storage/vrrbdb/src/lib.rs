@@ -1,18 +1,53 @@
+#[cfg(not(any(feature = "rocksdb-backend", feature = "sled-backend")))]
+compile_error!("vrrbdb needs at least one of the rocksdb-backend and sled-backend features");
+
+mod backend;
+mod block_wal;
+mod certification_store;
+mod certified_txn_index;
+mod checkpoint_store;
 mod claim_store;
+mod consensus_timeline_store;
+mod earnings_store;
+mod epoch_summary_store;
+mod halt_log_store;
+mod maintenance;
+mod parameter_store;
+mod quorum_history_store;
 pub mod result;
-mod rocksdb_adapter;
+mod scrubber;
+mod state_archive;
+mod state_diff;
 mod state_store;
 pub mod test_utils;
 mod transaction_store;
+mod trie_db_adapter;
+mod txn_receipt_store;
 pub mod types;
 mod vrrbdb;
 mod vrrbdb_read_handle;
 mod vrrbdb_serialized_values;
 
+pub use backend::*;
+pub use block_wal::*;
+pub use certification_store::*;
+pub use certified_txn_index::*;
+pub use checkpoint_store::*;
 pub use claim_store::*;
-pub use rocksdb_adapter::*;
+pub use consensus_timeline_store::*;
+pub use earnings_store::*;
+pub use epoch_summary_store::*;
+pub use halt_log_store::*;
+pub use maintenance::*;
+pub use parameter_store::*;
+pub use quorum_history_store::*;
+pub use scrubber::*;
+pub use state_archive::*;
+pub use state_diff::*;
 pub use state_store::*;
 pub use transaction_store::*;
+pub use trie_db_adapter::*;
+pub use txn_receipt_store::*;
 pub use types::*;
 pub use vrrbdb_read_handle::*;
 pub use vrrbdb_serialized_values::*;
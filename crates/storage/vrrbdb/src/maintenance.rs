@@ -0,0 +1,296 @@
+//! Offline maintenance of a node's database. None of these may run while a
+//! node has the database open.
+
+use std::fs;
+
+use block::BlockHash;
+use patriecia::{Node, NodeKey, RootHash};
+use primitives::PruningMode;
+use serde::{Deserialize, Serialize};
+use storage_utils::Result;
+
+use crate::{
+    open_backend, ClaimStore, SharedBackend, StateStore, StoredCertification, TransactionStore,
+    VrrbDb, VrrbDbConfig,
+};
+
+const STATE_STORE: &str = "state";
+const TRANSACTION_STORE: &str = "transactions";
+const CLAIM_STORE: &str = "claims";
+const CERTIFICATION_STORE: &str = "certifications";
+
+/// Stores whose records are nodes of a merkle trie
+const TRIE_STORES: [&str; 3] = [STATE_STORE, TRANSACTION_STORE, CLAIM_STORE];
+
+/// Directory within the database the tries are rebuilt in while verifying them
+const VERIFICATION_SCRATCH_DIR: &str = "verification";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreInspection {
+    pub store: String,
+    /// Root hash of the trie kept in the store, if it is a trie and has one
+    pub root_hash: Option<String>,
+    /// Number of values readable through the store
+    pub entries: usize,
+    /// Number of raw records persisted by the store
+    pub records: usize,
+    /// Number of raw records that can't be decoded
+    pub corrupted_records: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VrrbDbInspection {
+    pub stores: Vec<StoreInspection>,
+    pub certified_blocks: usize,
+    /// The most recently certified block, i.e. the tip of the chain as far as
+    /// this database knows
+    pub latest_certified_block: Option<BlockHash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RootVerification {
+    pub store: String,
+    pub stored_root: Option<String>,
+    pub recomputed_root: Option<String>,
+}
+
+impl RootVerification {
+    /// Returns whether hashing the store's values again yields the root it
+    /// holds
+    pub fn is_consistent(&self) -> bool {
+        self.stored_root == self.recomputed_root
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreRepair {
+    pub store: String,
+    pub removed_records: usize,
+}
+
+/// Summarizes what the database at `config.path` holds.
+pub fn inspect_db(config: &VrrbDbConfig) -> Result<VrrbDbInspection> {
+    let db = VrrbDb::new(config.clone());
+    let read_handle = db.read_handle();
+
+    let tries = [
+        (
+            STATE_STORE,
+            db.state_root_hash(),
+            read_handle.state_store_values().len(),
+        ),
+        (
+            TRANSACTION_STORE,
+            db.transactions_root_hash(),
+            read_handle.transaction_store_values().len(),
+        ),
+        (
+            CLAIM_STORE,
+            db.claims_root_hash(),
+            read_handle.claim_store_values().len(),
+        ),
+    ];
+
+    let certifications = read_handle.block_certifications();
+    let certified_blocks = certifications
+        .iter()
+        .filter(|certification| certification.is_certified())
+        .count();
+    let latest_certified_block = certifications
+        .iter()
+        .rev()
+        .find(|certification| certification.is_certified())
+        .map(|certification| certification.block_hash.clone());
+
+    let certification_entries = certifications.len();
+
+    // the stores have to be closed before their backends can be opened again
+    drop(read_handle);
+    drop(db);
+
+    let mut stores = Vec::new();
+
+    for (store, root_hash, entries) in tries {
+        let (records, corrupted_records) = count_records(config, store, is_trie_record)?;
+
+        stores.push(StoreInspection {
+            store: store.to_string(),
+            root_hash: root_hash.ok().map(encode_root),
+            entries,
+            records,
+            corrupted_records,
+        });
+    }
+
+    let (records, corrupted_records) =
+        count_records(config, CERTIFICATION_STORE, is_certification_record)?;
+
+    stores.push(StoreInspection {
+        store: CERTIFICATION_STORE.to_string(),
+        root_hash: None,
+        entries: certification_entries,
+        records,
+        corrupted_records,
+    });
+
+    Ok(VrrbDbInspection {
+        stores,
+        certified_blocks,
+        latest_certified_block,
+    })
+}
+
+/// Hashes the values of every trie again, from scratch, and compares the
+/// resulting roots to the ones the tries hold.
+pub fn verify_db(config: &VrrbDbConfig) -> Result<Vec<RootVerification>> {
+    let db = VrrbDb::new(config.clone());
+    let read_handle = db.read_handle();
+
+    let scratch_path = config.path.join(VERIFICATION_SCRATCH_DIR);
+
+    // a previous verification may have been interrupted before cleaning up
+    if scratch_path.exists() {
+        fs::remove_dir_all(&scratch_path)?;
+    }
+
+    let mut state_store =
+        StateStore::new_with_backend(&scratch_path, config.backend, PruningMode::default());
+    state_store.extend(
+        read_handle
+            .state_store_values()
+            .into_iter()
+            .map(|(address, account)| (address, Some(account)))
+            .collect(),
+    );
+    state_store.commit();
+
+    let mut transaction_store = TransactionStore::new_with_backend(&scratch_path, config.backend);
+    transaction_store.extend(
+        read_handle
+            .transaction_store_values()
+            .into_values()
+            .collect(),
+    );
+    transaction_store.commit();
+
+    let mut claim_store = ClaimStore::new_with_backend(&scratch_path, config.backend);
+    claim_store.extend(
+        read_handle
+            .claim_store_values()
+            .into_values()
+            .map(|claim| (claim.hash, Some(claim)))
+            .collect(),
+    );
+    claim_store.commit();
+
+    let verifications = vec![
+        root_verification(STATE_STORE, db.state_root_hash(), state_store.root_hash()),
+        root_verification(
+            TRANSACTION_STORE,
+            db.transactions_root_hash(),
+            transaction_store.root_hash(),
+        ),
+        root_verification(CLAIM_STORE, db.claims_root_hash(), claim_store.root_hash()),
+    ];
+
+    drop(state_store);
+    drop(transaction_store);
+    drop(claim_store);
+
+    fs::remove_dir_all(&scratch_path)?;
+
+    Ok(verifications)
+}
+
+/// Deletes every record that can't be decoded, so the stores can be opened
+/// again. Whatever those records held is lost.
+pub fn repair_db(config: &VrrbDbConfig) -> Result<Vec<StoreRepair>> {
+    let mut repairs = Vec::new();
+
+    for store in TRIE_STORES {
+        repairs.push(remove_corrupted_records(config, store, is_trie_record)?);
+    }
+
+    repairs.push(remove_corrupted_records(
+        config,
+        CERTIFICATION_STORE,
+        is_certification_record,
+    )?);
+
+    Ok(repairs)
+}
+
+/// Reclaims the space held by overwritten and deleted records in every store.
+pub fn compact_db(config: &VrrbDbConfig) -> Result<()> {
+    for store in TRIE_STORES.into_iter().chain([CERTIFICATION_STORE]) {
+        open_store(config, store)?.compact()?;
+    }
+
+    Ok(())
+}
+
+fn open_store(config: &VrrbDbConfig, store: &str) -> Result<SharedBackend> {
+    open_backend(config.backend, config.path.join(store), store)
+}
+
+fn is_trie_record(key: &[u8], value: &[u8]) -> bool {
+    bincode::deserialize::<NodeKey>(key).is_ok() && bincode::deserialize::<Node>(value).is_ok()
+}
+
+fn is_certification_record(_key: &[u8], value: &[u8]) -> bool {
+    bincode::deserialize::<StoredCertification>(value).is_ok()
+}
+
+/// Returns how many records a store holds, and how many of those are corrupted
+fn count_records(
+    config: &VrrbDbConfig,
+    store: &str,
+    is_valid: fn(&[u8], &[u8]) -> bool,
+) -> Result<(usize, usize)> {
+    let entries = open_store(config, store)?.entries()?;
+
+    let corrupted = entries
+        .iter()
+        .filter(|(key, value)| !is_valid(key, value))
+        .count();
+
+    Ok((entries.len(), corrupted))
+}
+
+fn remove_corrupted_records(
+    config: &VrrbDbConfig,
+    store: &str,
+    is_valid: fn(&[u8], &[u8]) -> bool,
+) -> Result<StoreRepair> {
+    let backend = open_store(config, store)?;
+
+    let mut removed_records = 0;
+
+    for (key, value) in backend.entries()? {
+        if !is_valid(&key, &value) {
+            backend.delete(&key)?;
+            removed_records += 1;
+        }
+    }
+
+    Ok(StoreRepair {
+        store: store.to_string(),
+        removed_records,
+    })
+}
+
+fn encode_root(root_hash: RootHash) -> String {
+    hex::encode(root_hash.0)
+}
+
+fn root_verification(
+    store: &str,
+    stored_root: Result<RootHash>,
+    recomputed_root: Result<RootHash>,
+) -> RootVerification {
+    RootVerification {
+        store: store.to_string(),
+        stored_root: stored_root.ok().map(encode_root),
+        recomputed_root: recomputed_root.ok().map(encode_root),
+    }
+}
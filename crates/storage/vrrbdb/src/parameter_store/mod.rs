@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use primitives::DbBackend;
+use storage_utils::{Result, StorageError};
+
+use crate::{open_backend, SharedBackend};
+
+mod parameter_store_rh;
+mod types;
+pub use parameter_store_rh::*;
+pub use types::*;
+
+/// Persists every quorum-approved protocol parameter change included in a
+/// block, which doubles as their audit history.
+///
+/// Records are keyed by the big-endian epoch they take effect at, then the
+/// round they were included in and their txn id, so the backend keeps them
+/// in the order they apply.
+#[derive(Debug, Clone)]
+pub struct ParameterStore {
+    backend: SharedBackend,
+}
+
+impl Default for ParameterStore {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(&db_path, DbBackend::default())
+            .expect("failed to open the default parameter store")
+    }
+}
+
+impl ParameterStore {
+    /// Opens the parameter store within `path`, picking up the changes
+    /// persisted by previous runs.
+    pub fn new(path: &Path, backend: DbBackend) -> Result<Self> {
+        let backend = open_backend(backend, path.join("parameters"), "parameters")?;
+
+        Ok(Self { backend })
+    }
+
+    pub fn read_handle(&self) -> ParameterStoreReadHandle {
+        ParameterStoreReadHandle::new(self.backend.clone())
+    }
+
+    /// Records a change included in a block, replacing the record of the same
+    /// change if the block is applied again
+    pub fn record_change(&mut self, record: &ParameterChangeRecord) -> Result<()> {
+        let value =
+            bincode::serialize(record).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend.put(&record.key(), &value)
+    }
+}
@@ -0,0 +1,29 @@
+use primitives::{ChainSpec, Epoch};
+
+use crate::{apply_parameter_changes, ParameterChangeRecord, SharedBackend};
+
+#[derive(Debug, Clone)]
+pub struct ParameterStoreReadHandle {
+    backend: SharedBackend,
+}
+
+impl ParameterStoreReadHandle {
+    pub(crate) fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Returns `base` with every change in effect at `epoch` applied
+    pub fn chain_spec_at(&self, base: &ChainSpec, epoch: Epoch) -> ChainSpec {
+        apply_parameter_changes(base, &self.entries(), epoch)
+    }
+
+    /// Returns every recorded change, in the order they take effect
+    pub fn entries(&self) -> Vec<ParameterChangeRecord> {
+        self.backend
+            .entries()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(_, value)| bincode::deserialize::<ParameterChangeRecord>(&value).ok())
+            .collect()
+    }
+}
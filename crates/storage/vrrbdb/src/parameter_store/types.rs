@@ -0,0 +1,114 @@
+use block::BlockHash;
+use primitives::{ChainSpec, Epoch, Round};
+use serde::{Deserialize, Serialize};
+use vrrb_core::transactions::ParameterChange;
+
+/// A parameter change as it was included in a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterChangeRecord {
+    pub change: ParameterChange,
+    pub round: Round,
+    pub block_hash: BlockHash,
+}
+
+impl ParameterChangeRecord {
+    pub fn effective_epoch(&self) -> Epoch {
+        self.change.proposal.effective_epoch
+    }
+
+    pub(crate) fn key(&self) -> Vec<u8> {
+        let mut key = self.effective_epoch().to_be_bytes().to_vec();
+        key.extend(self.round.to_be_bytes());
+        key.extend(self.change.id.as_bytes());
+        key
+    }
+}
+
+/// Returns `base` with the changes of `records` in effect at `epoch` applied,
+/// in order, so the last change to a parameter wins.
+pub fn apply_parameter_changes(
+    base: &ChainSpec,
+    records: &[ParameterChangeRecord],
+    epoch: Epoch,
+) -> ChainSpec {
+    let mut chain_spec = base.clone();
+
+    for record in records
+        .iter()
+        .filter(|record| record.effective_epoch() <= epoch)
+    {
+        let proposal = &record.change.proposal;
+
+        // NOTE: changes are bounds checked before they're recorded, this only
+        // guards against records written by older releases with wider bounds
+        if let Err(err) = proposal.parameter.apply(&mut chain_spec, proposal.value) {
+            telemetry::warn!(
+                "skipping recorded parameter change {}: {err}",
+                record.change.id
+            );
+        }
+    }
+
+    chain_spec
+}
+
+#[cfg(test)]
+mod tests {
+    use primitives::{
+        generate_account_keypair, Address, ProtocolParameter, QuorumPubkey, Signature,
+        ValidatorSecretKey,
+    };
+    use vrrb_core::transactions::{NewParameterChangeArgs, ParameterChangeProposal};
+
+    use super::*;
+
+    fn record(
+        parameter: ProtocolParameter,
+        value: u128,
+        effective_epoch: Epoch,
+    ) -> ParameterChangeRecord {
+        let (_, public_key) = generate_account_keypair();
+
+        let change = ParameterChange::new(NewParameterChangeArgs {
+            timestamp: 0,
+            sender_address: Address::new(public_key),
+            sender_public_key: public_key,
+            proposal: ParameterChangeProposal {
+                parameter,
+                value,
+                effective_epoch,
+                reason: String::new(),
+            },
+            quorum_public_key: QuorumPubkey::from(ValidatorSecretKey::random().public_key()),
+            quorum_signature: String::new(),
+            signature: Signature::from_compact(&[1; 64]).unwrap(),
+            nonce: 0,
+        });
+
+        ParameterChangeRecord {
+            change,
+            round: 0,
+            block_hash: BlockHash::default(),
+        }
+    }
+
+    #[test]
+    fn changes_only_apply_from_their_effective_epoch() {
+        let base = ChainSpec::default();
+        let records = vec![
+            record(ProtocolParameter::MaxTxnsPerProposal, 500, 2),
+            record(ProtocolParameter::MaxTxnsPerProposal, 800, 4),
+            record(ProtocolParameter::BaseFee, 7, 4),
+        ];
+
+        assert_eq!(apply_parameter_changes(&base, &records, 1), base);
+
+        let at_epoch_3 = apply_parameter_changes(&base, &records, 3);
+        assert_eq!(at_epoch_3.block_limits.max_txns_per_proposal, 500);
+        assert_eq!(at_epoch_3.fees.base_fee, base.fees.base_fee);
+
+        let at_epoch_4 = apply_parameter_changes(&base, &records, 4);
+        assert_eq!(at_epoch_4.block_limits.max_txns_per_proposal, 800);
+        assert_eq!(at_epoch_4.fees.base_fee, 7);
+    }
+}
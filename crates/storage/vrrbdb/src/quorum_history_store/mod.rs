@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use block::Certificate;
+use primitives::DbBackend;
+use storage_utils::{Result, StorageError};
+
+use crate::{open_backend, SharedBackend};
+
+mod quorum_history_store_rh;
+mod types;
+pub use quorum_history_store_rh::*;
+pub use types::*;
+
+/// Persists every quorum a node has belonged to, so the group public key a
+/// certificate was signed with can be looked up for any past epoch.
+///
+/// Records are keyed by the big-endian epoch they were formed at, so the
+/// backend keeps them in epoch order.
+#[derive(Debug, Clone)]
+pub struct QuorumHistoryStore {
+    backend: SharedBackend,
+}
+
+impl Default for QuorumHistoryStore {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(&db_path, DbBackend::default())
+            .expect("failed to open the default quorum history store")
+    }
+}
+
+impl QuorumHistoryStore {
+    /// Opens the quorum history within `path`, picking up the quorums
+    /// persisted by previous runs.
+    pub fn new(path: &Path, backend: DbBackend) -> Result<Self> {
+        let backend = open_backend(backend, path.join("quorum_history"), "quorum_history")?;
+
+        Ok(Self { backend })
+    }
+
+    pub fn read_handle(&self) -> QuorumHistoryStoreReadHandle {
+        QuorumHistoryStoreReadHandle::new(self.backend.clone())
+    }
+
+    /// Records the quorum formed at `record.epoch`, replacing any quorum
+    /// recorded for the same epoch
+    pub fn record_quorum(&mut self, record: QuorumRecord) -> Result<()> {
+        self.put(&record)
+    }
+
+    /// Attaches `certificate` to every recorded quorum it inaugurates that
+    /// doesn't have an inauguration certificate yet
+    pub fn record_inauguration(&mut self, certificate: &Certificate) -> Result<()> {
+        for mut record in self.read_handle().entries() {
+            if record.inauguration.is_none() && record.is_inaugurated_by(certificate) {
+                record.inauguration = Some(certificate.clone());
+                self.put(&record)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn put(&mut self, record: &QuorumRecord) -> Result<()> {
+        let value =
+            bincode::serialize(record).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend.put(&record.epoch.to_be_bytes(), &value)
+    }
+}
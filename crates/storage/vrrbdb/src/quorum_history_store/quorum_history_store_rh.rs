@@ -0,0 +1,40 @@
+use primitives::Epoch;
+use storage_utils::{Result, StorageError};
+
+use crate::{QuorumRecord, SharedBackend};
+
+#[derive(Debug, Clone)]
+pub struct QuorumHistoryStoreReadHandle {
+    backend: SharedBackend,
+}
+
+impl QuorumHistoryStoreReadHandle {
+    pub(crate) fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Returns the quorum in effect at `epoch`: the most recent one formed at
+    /// or before it
+    pub fn get(&self, epoch: Epoch) -> Result<QuorumRecord> {
+        self.entries()
+            .into_iter()
+            .rev()
+            .find(|record| record.epoch <= epoch)
+            .ok_or_else(|| StorageError::NotFound(format!("quorum at epoch {epoch}")))
+    }
+
+    /// Returns the most recently formed quorum
+    pub fn latest(&self) -> Option<QuorumRecord> {
+        self.entries().pop()
+    }
+
+    /// Returns every recorded quorum, oldest first
+    pub fn entries(&self) -> Vec<QuorumRecord> {
+        self.backend
+            .entries()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(_, value)| bincode::deserialize::<QuorumRecord>(&value).ok())
+            .collect()
+    }
+}
@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+
+use block::Certificate;
+use primitives::{Epoch, NodeId, QuorumKind, QuorumPubkey, ValidatorPublicKey};
+use serde::{Deserialize, Serialize};
+
+/// A quorum as it was formed at the start of an epoch: its members' keys, the
+/// group public key their certificates verify against and the number of
+/// partial signatures beyond which a certificate can be produced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuorumRecord {
+    pub epoch: Epoch,
+    pub quorum_kind: QuorumKind,
+    pub members: BTreeMap<NodeId, ValidatorPublicKey>,
+    pub group_public_key: QuorumPubkey,
+    pub threshold: u16,
+
+    /// The block certificate, signed by the previous quorum, that announced
+    /// this quorum's group public key
+    pub inauguration: Option<Certificate>,
+}
+
+impl QuorumRecord {
+    /// Returns true if `certificate` announces this quorum's group public key
+    pub fn is_inaugurated_by(&self, certificate: &Certificate) -> bool {
+        certificate
+            .inauguration
+            .as_ref()
+            .map_or(false, |inaugurated| {
+                inaugurated
+                    .values()
+                    .any(|group_public_key| group_public_key == &self.group_public_key)
+            })
+    }
+}
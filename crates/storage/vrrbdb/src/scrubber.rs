@@ -0,0 +1,470 @@
+//! Online scrubbing of the records a node persisted. Bit rot and partial
+//! writes are caught a few records at a time while the node runs, rather than
+//! once a corrupted record is next needed.
+
+use std::fmt;
+
+use block::{BlockHash, Checkpoint, EpochSummary};
+use patriecia::{Node, NodeKey};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use storage_utils::Result;
+
+use crate::{SharedBackend, StoredCertification, StoredTxnReceipt};
+
+/// The stores a [ChainScrubber] walks, in the order it walks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScrubbedStore {
+    Checkpoints,
+    EpochSummaries,
+    Certifications,
+    TxnReceipts,
+    StateTrie,
+    TransactionTrie,
+    ClaimTrie,
+}
+
+impl ScrubbedStore {
+    /// Name of the directory the store is persisted to within the database
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScrubbedStore::Checkpoints => "checkpoints",
+            ScrubbedStore::EpochSummaries => "epoch_summaries",
+            ScrubbedStore::Certifications => "certifications",
+            ScrubbedStore::TxnReceipts => "txn_receipts",
+            ScrubbedStore::StateTrie => "state",
+            ScrubbedStore::TransactionTrie => "transactions",
+            ScrubbedStore::ClaimTrie => "claims",
+        }
+    }
+
+    /// Returns the store persisted to the directory called `name`, see
+    /// [ScrubbedStore::name]
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            ScrubbedStore::Checkpoints,
+            ScrubbedStore::EpochSummaries,
+            ScrubbedStore::Certifications,
+            ScrubbedStore::TxnReceipts,
+            ScrubbedStore::StateTrie,
+            ScrubbedStore::TransactionTrie,
+            ScrubbedStore::ClaimTrie,
+        ]
+        .into_iter()
+        .find(|store| store.name() == name)
+    }
+
+    fn is_trie(&self) -> bool {
+        matches!(
+            self,
+            ScrubbedStore::StateTrie | ScrubbedStore::TransactionTrie | ScrubbedStore::ClaimTrie
+        )
+    }
+
+    /// Returns whether records of the store can be handed over by peers. A
+    /// copy is only taken in place of a corrupted record once it hashes to
+    /// what another record committed to, so peers can't slip in records of
+    /// their own
+    pub fn is_shared_with_peers(&self) -> bool {
+        self.is_trie() || *self == ScrubbedStore::EpochSummaries
+    }
+}
+
+impl fmt::Display for ScrubbedStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A record that doesn't match what was recorded along with it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorruptRecord {
+    pub store: ScrubbedStore,
+    pub key: Vec<u8>,
+
+    /// What the record failed to match
+    pub reason: String,
+
+    /// Hex encoded hash another record committed the record to, if any. A
+    /// copy fetched from peers replaces the record once it hashes to it
+    pub expected_hash: Option<String>,
+}
+
+impl CorruptRecord {
+    /// Returns the block whose certification the record held, if it is a
+    /// certification record. Certificates can be fetched from peers again, so
+    /// such records can be recovered once discarded
+    pub fn certified_block(&self) -> Option<BlockHash> {
+        if self.store != ScrubbedStore::Certifications {
+            return None;
+        }
+
+        String::from_utf8(self.key.clone()).ok()
+    }
+
+    /// Returns whether a copy of the record can be fetched from peers, which
+    /// takes a store peers share records of and a hash to check the copy
+    /// against
+    pub fn is_recoverable_from_peers(&self) -> bool {
+        self.store.is_shared_with_peers() && self.expected_hash.is_some()
+    }
+
+    /// Returns whether `value` can take the place of the record, i.e. it
+    /// hashes to the hash committed to the record and is stored under the
+    /// right key
+    pub fn accepts(&self, value: &[u8]) -> bool {
+        if !self.is_recoverable_from_peers() {
+            return false;
+        }
+
+        let hash = match self.store {
+            ScrubbedStore::EpochSummaries => decode::<EpochSummary>(value)
+                .ok()
+                .filter(|summary| check_epoch_summary(&self.key, summary).is_ok())
+                .map(|summary| summary.hash()),
+            _ => decode::<Node>(value).ok().map(|node| trie_node_hash(&node)),
+        };
+
+        hash.is_some() && hash == self.expected_hash
+    }
+}
+
+/// What a batch of the scrubber found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScrubReport {
+    /// Number of records checked
+    pub scrubbed: usize,
+    pub corrupt: Vec<CorruptRecord>,
+
+    /// Whether the batch finished a pass over every store
+    pub completed_pass: bool,
+}
+
+/// Walks the stores of a [crate::VrrbDb] a batch of records at a time,
+/// checking every record against the hashes and keys recorded along with it:
+///
+/// - checkpoints are stored under their round, and their header hashes to the
+///   block hash they were taken of and commits to the roots they recorded
+/// - epoch summaries are stored under their epoch, their header matches them
+///   and they hash to the parent hash the summary after them recorded
+/// - certifications are stored under the block they certify, which their
+///   certificate is for too
+/// - txn receipts are stored under the digest of the txn they trace and hash
+///   to the checksum recorded along with them
+/// - the children of internal trie nodes hash to the hashes their parent
+///   recorded of them. Root nodes, which no other node records, are checked
+///   for decoding only
+///
+/// The scrubber only reads. Corrupt records are left in place until
+/// discarded through [crate::VrrbDb::discard_corrupt_record] or replaced
+/// through [crate::VrrbDb::restore_corrupt_record].
+#[derive(Debug, Clone)]
+pub struct ChainScrubber {
+    stores: Vec<(ScrubbedStore, SharedBackend)>,
+
+    /// Index of the store being walked
+    position: usize,
+
+    /// Key of the last record scrubbed within the store being walked
+    cursor: Option<Vec<u8>>,
+
+    /// Number of passes over every store completed so far
+    passes: u64,
+}
+
+impl ChainScrubber {
+    pub(crate) fn new(stores: Vec<(ScrubbedStore, SharedBackend)>) -> Self {
+        Self {
+            stores,
+            position: 0,
+            cursor: None,
+            passes: 0,
+        }
+    }
+
+    /// Returns the number of passes over every store completed so far
+    pub fn passes(&self) -> u64 {
+        self.passes
+    }
+
+    /// Checks up to `batch_size` records, picking up where the previous batch
+    /// left off and moving on to the next store once one is done. A batch
+    /// never goes past the end of a pass, so every pass is reported
+    pub fn scrub(&mut self, batch_size: usize) -> Result<ScrubReport> {
+        let mut report = ScrubReport::default();
+
+        while report.scrubbed < batch_size && !report.completed_pass {
+            let Some((store, backend)) = self.stores.get(self.position) else {
+                break;
+            };
+
+            let store = *store;
+            let limit = batch_size - report.scrubbed;
+            let entries = backend.entries_after(self.cursor.as_deref(), limit)?;
+
+            report.scrubbed += entries.len();
+            for (key, value) in entries.iter() {
+                for record in scrub_record(store, backend, key, value) {
+                    // NOTE: a trie node is reported once per parent recording it
+                    if !report.corrupt.contains(&record) {
+                        report.corrupt.push(record);
+                    }
+                }
+            }
+
+            if entries.len() < limit {
+                self.position = (self.position + 1) % self.stores.len();
+                self.cursor = None;
+                report.completed_pass = self.position == 0;
+            } else {
+                self.cursor = entries.last().map(|(key, _)| key.clone());
+            }
+        }
+
+        if report.completed_pass {
+            self.passes += 1;
+        }
+
+        Ok(report)
+    }
+}
+
+/// Returns the records of `store` that scrubbing the record under `key`
+/// found corrupt, which can be records it commits to rather than itself
+fn scrub_record(
+    store: ScrubbedStore,
+    backend: &SharedBackend,
+    key: &[u8],
+    value: &[u8],
+) -> Vec<CorruptRecord> {
+    let corrupt = |key: &[u8], reason: String, expected_hash: Option<String>| CorruptRecord {
+        store,
+        key: key.to_vec(),
+        reason,
+        expected_hash,
+    };
+
+    match store {
+        ScrubbedStore::EpochSummaries => check_epoch_summary_chain(backend, key, value)
+            .into_iter()
+            .map(|(reason, expected_hash)| corrupt(key, reason, expected_hash))
+            .collect(),
+        _ if store.is_trie() => check_trie_node(backend, key, value)
+            .into_iter()
+            .map(|(key, reason, expected_hash)| corrupt(&key, reason, expected_hash))
+            .collect(),
+        _ => check_record(store, key, value)
+            .err()
+            .map(|reason| corrupt(key, reason, None))
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Checks a record of `store` against what was recorded along with it,
+/// returning why it doesn't match if it doesn't
+fn check_record(store: ScrubbedStore, key: &[u8], value: &[u8]) -> std::result::Result<(), String> {
+    match store {
+        ScrubbedStore::Checkpoints => {
+            let checkpoint = decode::<Checkpoint>(value)?;
+
+            if key != checkpoint.round.to_be_bytes() {
+                return Err(format!(
+                    "checkpoint of round {} is stored under another round",
+                    checkpoint.round
+                ));
+            }
+
+            if !checkpoint.matches_header() {
+                return Err(format!(
+                    "header doesn't hash to the recorded block hash {} or commit to the \
+                     recorded roots",
+                    checkpoint.block_hash
+                ));
+            }
+        },
+        ScrubbedStore::EpochSummaries => {
+            check_epoch_summary(key, &decode::<EpochSummary>(value)?)?;
+        },
+        ScrubbedStore::Certifications => {
+            let certification = decode::<StoredCertification>(value)?.certification;
+
+            if key != certification.block_hash.as_bytes() {
+                return Err(format!(
+                    "certification of block {} is stored under another block",
+                    certification.block_hash
+                ));
+            }
+
+            if let Some(certificate) = certification.certificate {
+                if certificate.block_hash != certification.block_hash {
+                    return Err(format!(
+                        "certificate is for block {} rather than for the recorded block {}",
+                        certificate.block_hash, certification.block_hash
+                    ));
+                }
+            }
+        },
+        ScrubbedStore::TxnReceipts => {
+            let stored = decode::<StoredTxnReceipt>(value)?;
+
+            if !stored.matches_checksum() {
+                return Err(format!(
+                    "receipt doesn't hash to the recorded checksum {}",
+                    stored.checksum
+                ));
+            }
+
+            if key != stored.receipt.digest.as_bytes() {
+                return Err(format!(
+                    "receipt of txn {} is stored under another txn",
+                    stored.receipt.digest
+                ));
+            }
+        },
+        ScrubbedStore::StateTrie | ScrubbedStore::TransactionTrie | ScrubbedStore::ClaimTrie => {
+            decode::<NodeKey>(key)?;
+            decode::<Node>(value)?;
+        },
+    }
+
+    Ok(())
+}
+
+fn check_epoch_summary(key: &[u8], summary: &EpochSummary) -> std::result::Result<(), String> {
+    if key != summary.epoch.to_be_bytes() {
+        return Err(format!(
+            "summary of epoch {} is stored under another epoch",
+            summary.epoch
+        ));
+    }
+
+    if !summary.matches_header() {
+        return Err(format!(
+            "header doesn't hash to the summarized block hash {} or commit to the summarized \
+             epoch, round and state root",
+            summary.block_hash
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks the epoch summary under `key`, then checks it hashes to the parent
+/// hash the next summary recorded. Returns why it doesn't match, along with
+/// the hash it should have if the next summary tells
+fn check_epoch_summary_chain(
+    backend: &SharedBackend,
+    key: &[u8],
+    value: &[u8],
+) -> Option<(String, Option<String>)> {
+    let next_summary = backend
+        .entries_after(Some(key), 1)
+        .ok()
+        .and_then(|entries| entries.into_iter().next())
+        .and_then(|(next_key, next_value)| {
+            let next_summary = decode::<EpochSummary>(&next_value).ok()?;
+            check_epoch_summary(&next_key, &next_summary).ok()?;
+
+            Some(next_summary)
+        });
+
+    let expected_hash = next_summary
+        .as_ref()
+        .map(|next_summary| next_summary.parent_hash.clone());
+
+    let summary = match decode::<EpochSummary>(value) {
+        Ok(summary) => summary,
+        Err(reason) => return Some((reason, expected_hash)),
+    };
+
+    if let Err(reason) = check_epoch_summary(key, &summary) {
+        return Some((reason, expected_hash));
+    }
+
+    let next_summary = next_summary?;
+    if summary.hash() != next_summary.parent_hash {
+        return Some((
+            format!(
+                "summary hashes to {} but the summary of epoch {} names {} as its parent",
+                summary.hash(),
+                next_summary.epoch,
+                next_summary.parent_hash
+            ),
+            expected_hash,
+        ));
+    }
+
+    None
+}
+
+/// Checks the trie node under `key`, then hashes its children again and
+/// compares them to the hashes it recorded of them. Returns the key of every
+/// node found corrupt, why, and the hash it should have if its parent tells.
+///
+/// Nodes are only checked for decoding on their own if they are roots, other
+/// nodes are checked through their parent, which also catches nodes that
+/// went missing
+fn check_trie_node(
+    backend: &SharedBackend,
+    key: &[u8],
+    value: &[u8],
+) -> Vec<(Vec<u8>, String, Option<String>)> {
+    let node_key = match decode::<NodeKey>(key) {
+        Ok(node_key) => node_key,
+        Err(reason) => return vec![(key.to_vec(), reason, None)],
+    };
+
+    let node = match decode::<Node>(value) {
+        Ok(node) => node,
+        Err(reason) if node_key.nibble_path().num_nibbles() == 0 => {
+            return vec![(key.to_vec(), reason, None)];
+        },
+        Err(_) => return vec![],
+    };
+
+    let Node::Internal(internal) = node else {
+        return vec![];
+    };
+
+    let mut corrupt = vec![];
+
+    for (nibble, child) in internal.children_sorted() {
+        let child_key = node_key.gen_child_node_key(child.version, *nibble);
+        let Ok(child_key) = bincode::serialize(&child_key) else {
+            continue;
+        };
+
+        let expected_hash = hex::encode(child.hash);
+
+        let reason = match backend.get(&child_key) {
+            Ok(Some(child_value)) => match decode::<Node>(&child_value) {
+                Ok(child_node) => {
+                    let hash = trie_node_hash(&child_node);
+                    if hash == expected_hash {
+                        continue;
+                    }
+
+                    format!("node hashes to {hash} but its parent recorded {expected_hash}")
+                },
+                Err(reason) => reason,
+            },
+            Ok(None) => "node its parent recorded is missing".to_string(),
+            // NOTE: read failures aren't corruption, the next pass reads the node again
+            Err(_) => continue,
+        };
+
+        corrupt.push((child_key, reason, Some(expected_hash)));
+    }
+
+    corrupt
+}
+
+/// Hex encoded hash of a trie node, as its parent records it
+fn trie_node_hash(node: &Node) -> String {
+    hex::encode(node.hash::<Sha256>())
+}
+
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> std::result::Result<T, String> {
+    bincode::deserialize(bytes).map_err(|err| format!("undecodable record: {err}"))
+}
@@ -0,0 +1,394 @@
+//! Versioned state archives, used to carry a node's state across releases
+//! that change how accounts are stored.
+//!
+//! An archive is newline-delimited JSON: a header naming the schema version
+//! the accounts were encoded with, chunks of accounts and a trailer. Each
+//! chunk carries the SHA-256 checksum of its records and the trailer one of
+//! every chunk checksum, so truncated or corrupted archives are refused
+//! instead of imported. Accounts are kept as the hex encoded bytes of the
+//! schema they were exported with, and imports run them through the
+//! [MigrationRegistry] to bring them up to the current schema.
+//!
+//! None of these may run while a node has the database open.
+
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, Write},
+};
+
+use primitives::Address;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use storage_utils::StorageError;
+use vrrb_core::account::Account;
+
+use crate::{VrrbDb, VrrbDbConfig};
+
+/// Version of the schema this node stores accounts with. Bump it, and
+/// register a migration from the previous version, whenever [Account]
+/// changes in a way that breaks decoding accounts stored by older nodes.
+pub const STATE_SCHEMA_VERSION: u32 = 1;
+
+/// Number of accounts per chunk when none is given
+pub const DEFAULT_STATE_ARCHIVE_CHUNK_SIZE: usize = 1_000;
+
+/// Turns a record encoded with one schema version into the same record
+/// encoded with the next version.
+pub type RecordMigration = fn(&[u8]) -> Result<Vec<u8>>;
+
+pub type Result<T> = std::result::Result<T, StateArchiveError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum StateArchiveError {
+    #[error("{0}")]
+    Storage(#[from] StorageError),
+
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("malformed archive on line {line}: {reason}")]
+    Malformed { line: usize, reason: String },
+
+    #[error("chunk {index} doesn't match its checksum")]
+    ChecksumMismatch { index: u64 },
+
+    #[error("archive is truncated: {0}")]
+    Truncated(String),
+
+    #[error("archive was exported with schema version {0}, newer than this node supports")]
+    UnsupportedVersion(u32),
+
+    #[error("no migration registered from schema version {0}")]
+    MissingMigration(u32),
+
+    #[error("migrating a record from schema version {version} failed: {reason}")]
+    MigrationFailed { version: u32, reason: String },
+
+    #[error("the state of the target database isn't empty")]
+    TargetNotEmpty,
+
+    #[error("imported state root {imported} doesn't match the exported root {exported}")]
+    StateRootMismatch { exported: String, imported: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateArchiveHeader {
+    pub schema_version: u32,
+    /// Root of the state trie the archive was exported from
+    pub state_root: String,
+    pub chunk_size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateArchiveChunk {
+    pub index: u64,
+    /// Hex encoded accounts, as of the archive's schema version
+    pub records: Vec<String>,
+    pub checksum: String,
+}
+
+impl StateArchiveChunk {
+    pub fn new(index: u64, records: Vec<String>) -> Self {
+        let checksum = checksum(&records);
+
+        Self {
+            index,
+            records,
+            checksum,
+        }
+    }
+
+    /// Whether the records still match the checksum they were written with
+    pub fn is_intact(&self) -> bool {
+        checksum(&self.records) == self.checksum
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateArchiveTrailer {
+    pub chunks: u64,
+    pub records: u64,
+    /// Checksum of the checksums of every chunk, in order
+    pub checksum: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "frame", rename_all = "snake_case")]
+pub enum StateArchiveFrame {
+    Header(StateArchiveHeader),
+    Chunk(StateArchiveChunk),
+    Trailer(StateArchiveTrailer),
+}
+
+/// What an export or import went through.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateArchiveSummary {
+    pub schema_version: u32,
+    pub state_root: String,
+    pub chunks: u64,
+    pub records: u64,
+    /// Schema version the records were migrated from on import, if any
+    pub migrated_from: Option<u32>,
+}
+
+/// Migrations between consecutive schema versions, keyed by the version
+/// they migrate from.
+#[derive(Debug, Clone, Default)]
+pub struct MigrationRegistry {
+    migrations: BTreeMap<u32, RecordMigration>,
+}
+
+impl MigrationRegistry {
+    /// Registers `migration` as the way records of schema version `from`
+    /// become records of version `from + 1`
+    pub fn register(&mut self, from: u32, migration: RecordMigration) -> &mut Self {
+        self.migrations.insert(from, migration);
+        self
+    }
+
+    /// Checks every migration from schema version `from` up to `to` is
+    /// registered
+    pub fn check_path(&self, from: u32, to: u32) -> Result<()> {
+        if let Some(missing) = (from..to).find(|version| !self.migrations.contains_key(version)) {
+            return Err(StateArchiveError::MissingMigration(missing));
+        }
+
+        Ok(())
+    }
+
+    /// Migrates a record encoded with schema version `from` to version `to`
+    pub fn migrate(&self, record: Vec<u8>, from: u32, to: u32) -> Result<Vec<u8>> {
+        (from..to).try_fold(record, |record, version| {
+            let migration = self
+                .migrations
+                .get(&version)
+                .ok_or(StateArchiveError::MissingMigration(version))?;
+
+            migration(&record)
+        })
+    }
+}
+
+fn checksum(records: &[String]) -> String {
+    let mut hasher = Sha256::new();
+
+    for record in records {
+        hasher.update(record.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+fn write_frame<W: Write>(writer: &mut W, frame: &StateArchiveFrame) -> Result<()> {
+    serde_json::to_writer(&mut *writer, frame)
+        .map_err(|err| StorageError::Other(err.to_string()))?;
+    writer.write_all(b"\n")?;
+
+    Ok(())
+}
+
+fn encode_account(account: &Account) -> Result<String> {
+    let bytes = bincode::serialize(account).map_err(|err| StorageError::Other(err.to_string()))?;
+
+    Ok(hex::encode(bytes))
+}
+
+/// Streams the state of the database at `config.path` to `writer` as an
+/// archive of the current schema version, `chunk_size` accounts at a time.
+pub fn export_state_archive<W: Write>(
+    config: &VrrbDbConfig,
+    mut writer: W,
+    chunk_size: usize,
+) -> Result<StateArchiveSummary> {
+    let chunk_size = chunk_size.max(1);
+    let db = VrrbDb::new(config.clone());
+    let state_root = hex::encode(db.state_root_hash()?.0);
+
+    write_frame(
+        &mut writer,
+        &StateArchiveFrame::Header(StateArchiveHeader {
+            schema_version: STATE_SCHEMA_VERSION,
+            state_root: state_root.clone(),
+            chunk_size,
+        }),
+    )?;
+
+    let handle = db.state_store_factory().handle();
+    let mut accounts = handle.iter()?;
+    let mut trailer_hasher = Sha256::new();
+    let (mut chunks, mut records) = (0u64, 0u64);
+
+    loop {
+        let chunk = accounts
+            .by_ref()
+            .take(chunk_size)
+            .map(|account| encode_account(&account?))
+            .collect::<Result<Vec<String>>>()?;
+
+        if chunk.is_empty() {
+            break;
+        }
+
+        let chunk = StateArchiveChunk::new(chunks, chunk);
+        trailer_hasher.update(chunk.checksum.as_bytes());
+        records += chunk.records.len() as u64;
+
+        write_frame(&mut writer, &StateArchiveFrame::Chunk(chunk))?;
+
+        chunks += 1;
+    }
+
+    write_frame(
+        &mut writer,
+        &StateArchiveFrame::Trailer(StateArchiveTrailer {
+            chunks,
+            records,
+            checksum: hex::encode(trailer_hasher.finalize()),
+        }),
+    )?;
+    writer.flush()?;
+
+    Ok(StateArchiveSummary {
+        schema_version: STATE_SCHEMA_VERSION,
+        state_root,
+        chunks,
+        records,
+        migrated_from: None,
+    })
+}
+
+/// Imports the archive read from `reader` into the database at
+/// `config.path`, whose state must be empty. Records exported with an older
+/// schema version are migrated through `migrations` on the way in.
+///
+/// Chunks are checked and written one at a time, so an archive that turns
+/// out to be corrupted part way through leaves a partially imported state
+/// behind: the target database should be discarded if the import fails.
+/// Unless records were migrated, the imported state must also hash to the
+/// root the archive was exported from.
+pub fn import_state_archive<R: BufRead>(
+    config: &VrrbDbConfig,
+    reader: R,
+    migrations: &MigrationRegistry,
+) -> Result<StateArchiveSummary> {
+    let mut db = VrrbDb::new(config.clone());
+
+    if db.state_store_factory().handle().iter()?.next().is_some() {
+        return Err(StateArchiveError::TargetNotEmpty);
+    }
+
+    let mut frames = reader.lines().enumerate().map(|(line, frame)| {
+        let frame = frame?;
+
+        serde_json::from_str::<StateArchiveFrame>(&frame).map_err(|err| {
+            StateArchiveError::Malformed {
+                line: line + 1,
+                reason: err.to_string(),
+            }
+        })
+    });
+
+    let header = match frames.next().transpose()? {
+        Some(StateArchiveFrame::Header(header)) => header,
+        _ => {
+            return Err(StateArchiveError::Truncated(
+                "header is missing".to_string(),
+            ))
+        },
+    };
+
+    if header.schema_version > STATE_SCHEMA_VERSION {
+        return Err(StateArchiveError::UnsupportedVersion(header.schema_version));
+    }
+
+    migrations.check_path(header.schema_version, STATE_SCHEMA_VERSION)?;
+
+    let mut trailer_hasher = Sha256::new();
+    let (mut chunks, mut records) = (0u64, 0u64);
+
+    let trailer = loop {
+        let chunk = match frames.next().transpose()? {
+            Some(StateArchiveFrame::Chunk(chunk)) => chunk,
+            Some(StateArchiveFrame::Trailer(trailer)) => break trailer,
+            Some(StateArchiveFrame::Header(_)) => {
+                return Err(StateArchiveError::Malformed {
+                    line: chunks as usize + 2,
+                    reason: "unexpected header".to_string(),
+                })
+            },
+            None => {
+                return Err(StateArchiveError::Truncated(
+                    "trailer is missing".to_string(),
+                ))
+            },
+        };
+
+        if chunk.index != chunks {
+            return Err(StateArchiveError::Truncated(format!(
+                "expected chunk {chunks}, found chunk {}",
+                chunk.index
+            )));
+        }
+
+        if !chunk.is_intact() {
+            return Err(StateArchiveError::ChecksumMismatch { index: chunk.index });
+        }
+
+        trailer_hasher.update(chunk.checksum.as_bytes());
+
+        let mut accounts: Vec<(Address, Option<Account>)> = Vec::with_capacity(chunk.records.len());
+
+        for record in chunk.records {
+            let bytes = hex::decode(&record).map_err(|err| StateArchiveError::Malformed {
+                line: chunks as usize + 2,
+                reason: err.to_string(),
+            })?;
+
+            let bytes = migrations.migrate(bytes, header.schema_version, STATE_SCHEMA_VERSION)?;
+
+            let account: Account =
+                bincode::deserialize(&bytes).map_err(|err| StateArchiveError::MigrationFailed {
+                    version: header.schema_version,
+                    reason: err.to_string(),
+                })?;
+
+            accounts.push((account.address().clone(), Some(account)));
+        }
+
+        records += accounts.len() as u64;
+        chunks += 1;
+
+        db.extend_accounts(accounts);
+    };
+
+    if trailer.chunks != chunks
+        || trailer.records != records
+        || trailer.checksum != hex::encode(trailer_hasher.finalize())
+    {
+        return Err(StateArchiveError::Truncated(format!(
+            "trailer announces {} records in {} chunks, read {records} in {chunks}",
+            trailer.records, trailer.chunks
+        )));
+    }
+
+    db.commit_state();
+
+    let state_root = hex::encode(db.state_root_hash()?.0);
+    let migrated = header.schema_version != STATE_SCHEMA_VERSION;
+
+    if !migrated && state_root != header.state_root {
+        return Err(StateArchiveError::StateRootMismatch {
+            exported: header.state_root,
+            imported: state_root,
+        });
+    }
+
+    Ok(StateArchiveSummary {
+        schema_version: STATE_SCHEMA_VERSION,
+        state_root,
+        chunks,
+        records,
+        migrated_from: migrated.then_some(header.schema_version),
+    })
+}
@@ -0,0 +1,225 @@
+//! Comparison of two node states, to find out where nodes that disagree on a
+//! state root diverged.
+//!
+//! Snapshots hold the accounts and, when known, the claims of a node. Diffing
+//! them lists every key whose value differs along with both values, and the
+//! nibble path of the deepest trie node every divergent key sits under, below
+//! which the two tries only differ along the paths of those keys.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::BufRead,
+};
+
+use ethereum_types::U256;
+use primitives::{Address, NodeId};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use vrrb_core::{account::Account, claim::Claim};
+
+use crate::{import_state_archive, MigrationRegistry, VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
+
+/// Directory within the database peer archives are imported into while
+/// diffing them
+const STATE_DIFF_SCRATCH_DIR: &str = "state-diff";
+
+/// The state of a node at some point.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub accounts: BTreeMap<Address, Account>,
+
+    /// `None` when the snapshot doesn't cover claims, as with snapshots read
+    /// from state archives
+    pub claims: Option<BTreeMap<NodeId, Claim>>,
+}
+
+impl StateSnapshot {
+    /// Takes a snapshot of the accounts and claims of a database
+    pub fn from_read_handle(read_handle: &VrrbDbReadHandle) -> Self {
+        Self {
+            accounts: read_handle.state_store_values().into_iter().collect(),
+            claims: Some(read_handle.claim_store_values().into_iter().collect()),
+        }
+    }
+}
+
+/// A key whose value differs between two snapshots. Keys missing from one
+/// of them have no value on that side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Divergence<K, V> {
+    pub key: K,
+    pub local: Option<V>,
+    pub peer: Option<V>,
+}
+
+/// How the values stored in one trie differ between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrieDiff<K, V> {
+    /// Hex nibble path, from the root, of the deepest node every divergent
+    /// key sits under. Empty if they already part ways at the root
+    pub divergent_path: Option<String>,
+    pub divergences: Vec<Divergence<K, V>>,
+}
+
+impl<K, V> TrieDiff<K, V> {
+    pub fn is_empty(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateSnapshotDiff {
+    pub accounts: TrieDiff<Address, Account>,
+
+    /// `None` unless both snapshots cover claims
+    pub claims: Option<TrieDiff<NodeId, Claim>>,
+}
+
+impl StateSnapshotDiff {
+    /// Returns whether the snapshots hold the same state
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.claims.as_ref().map_or(true, TrieDiff::is_empty)
+    }
+}
+
+/// Lists the keys whose values differ between `local` and `peer`, in key
+/// order
+fn divergences<K: Ord + Clone, V: PartialEq + Clone>(
+    local: &BTreeMap<K, V>,
+    peer: &BTreeMap<K, V>,
+) -> Vec<Divergence<K, V>> {
+    local
+        .keys()
+        .chain(peer.keys())
+        .collect::<BTreeSet<&K>>()
+        .into_iter()
+        .filter_map(|key| {
+            let (local, peer) = (local.get(key), peer.get(key));
+
+            (local != peer).then(|| Divergence {
+                key: key.clone(),
+                local: local.cloned(),
+                peer: peer.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the hex nibble path the tries store `key` under
+fn key_path<K: Serialize>(key: &K) -> Option<String> {
+    // NOTE: the tries key their nodes by the SHA-256 hash of the encoded key
+    let key = bincode::serialize(key).ok()?;
+
+    Some(hex::encode(Sha256::digest(key)))
+}
+
+/// Returns the longest path all of `paths` start with, if there are any
+fn common_path(paths: impl IntoIterator<Item = String>) -> Option<String> {
+    paths.into_iter().reduce(|common, path| {
+        common
+            .chars()
+            .zip(path.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(nibble, _)| nibble)
+            .collect()
+    })
+}
+
+/// Compares two snapshots, `local` usually being this node's and `peer` the
+/// one of a node that disagrees with it.
+pub fn diff_state_snapshots(local: &StateSnapshot, peer: &StateSnapshot) -> StateSnapshotDiff {
+    let accounts = divergences(&local.accounts, &peer.accounts);
+    let account_paths = accounts
+        .iter()
+        .filter_map(|divergence| key_path(&divergence.key));
+
+    let accounts = TrieDiff {
+        divergent_path: common_path(account_paths),
+        divergences: accounts,
+    };
+
+    let claims = local
+        .claims
+        .as_ref()
+        .zip(peer.claims.as_ref())
+        .map(|(local, peer)| {
+            let claims = divergences(local, peer);
+
+            // NOTE: claims are keyed by their hash in the claim trie, which
+            // differs between the two sides if anything about the claim does
+            let claim_paths = claims
+                .iter()
+                .flat_map(|divergence| [&divergence.local, &divergence.peer])
+                .flatten()
+                .filter_map(|claim: &Claim| key_path::<U256>(&claim.hash));
+
+            TrieDiff {
+                divergent_path: common_path(claim_paths),
+                divergences: claims,
+            }
+        });
+
+    StateSnapshotDiff { accounts, claims }
+}
+
+/// Compares the state of the database at `config.path` to the one of a state
+/// archive read from `reader`, usually exported from a peer. Archives only
+/// hold accounts, so claims aren't compared.
+///
+/// The archive is imported into a scratch database within `config.path`,
+/// which is removed once the comparison is done.
+pub fn diff_state_archive<R: BufRead>(
+    config: &VrrbDbConfig,
+    reader: R,
+    migrations: &MigrationRegistry,
+) -> crate::state_archive::Result<StateSnapshotDiff> {
+    let local = {
+        let db = VrrbDb::new(config.clone());
+        let mut snapshot = StateSnapshot::from_read_handle(&db.read_handle());
+        snapshot.claims = None;
+        snapshot
+    };
+
+    let scratch_path = config.path.join(STATE_DIFF_SCRATCH_DIR);
+
+    // a previous comparison may have been interrupted before cleaning up
+    if scratch_path.exists() {
+        fs::remove_dir_all(&scratch_path)?;
+    }
+
+    let scratch_config = config.clone().with_path(scratch_path.clone());
+    let imported = import_state_archive(&scratch_config, reader, migrations);
+
+    let peer = imported.map(|_| {
+        let db = VrrbDb::new(scratch_config);
+
+        StateSnapshot {
+            accounts: db.read_handle().state_store_values().into_iter().collect(),
+            claims: None,
+        }
+    });
+
+    fs::remove_dir_all(&scratch_path)?;
+
+    Ok(diff_state_snapshots(&local, &peer?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn divergent_paths_are_the_longest_prefix_of_the_divergent_keys() {
+        assert_eq!(common_path(Vec::<String>::new()), None);
+        assert_eq!(common_path(["ab12".to_string()]), Some("ab12".to_string()));
+        assert_eq!(
+            common_path(["ab12".to_string(), "ab1f".to_string(), "ab34".to_string()]),
+            Some("ab".to_string())
+        );
+        assert_eq!(
+            common_path(["ab12".to_string(), "cd12".to_string()]),
+            Some(String::new())
+        );
+    }
+}
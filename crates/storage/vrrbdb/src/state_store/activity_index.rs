@@ -0,0 +1,123 @@
+use std::str::FromStr;
+
+use primitives::{Address, Epoch};
+use storage_utils::{Result, StorageError};
+
+use crate::SharedBackend;
+
+/// Number of entries read from the backend at a time while listing a group
+const ACTIVITY_INDEX_PAGE_SIZE: usize = 256;
+
+/// Prefix of the keys listing the addresses of a group
+const GROUP_PREFIX: u8 = b'g';
+
+/// Prefix of the keys mapping an address to its group
+const ADDRESS_PREFIX: u8 = b'a';
+
+/// Addresses of the accounts within the state trie, grouped by the last epoch
+/// a block touched them, kept in their own backend.
+///
+/// The dust policy only ever sweeps the accounts that just reached the end of
+/// their allowed inactivity, so the index lets it read that one group rather
+/// than walk the whole state. Every address also maps back to its group, so
+/// an account can be moved between groups without knowing where it was.
+#[derive(Debug, Clone)]
+pub struct ActivityIndex {
+    backend: SharedBackend,
+}
+
+/// Key shared by the entries of the group of accounts last active during
+/// `last_active_epoch`. Accounts never active form a group of their own
+fn group_key(last_active_epoch: Option<Epoch>) -> Vec<u8> {
+    match last_active_epoch {
+        None => vec![GROUP_PREFIX, 0],
+        Some(epoch) => [&[GROUP_PREFIX, 1][..], &epoch.to_be_bytes()].concat(),
+    }
+}
+
+fn address_key(address: &Address) -> Vec<u8> {
+    [&[ADDRESS_PREFIX][..], address.to_string().as_bytes()].concat()
+}
+
+fn entry_key(group: &[u8], address: &Address) -> Vec<u8> {
+    [group, address.to_string().as_bytes()].concat()
+}
+
+impl ActivityIndex {
+    pub fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Files `address` under the group of accounts last active during
+    /// `last_active_epoch`, taking it out of the group it was in before
+    pub fn insert(&self, address: &Address, last_active_epoch: Option<Epoch>) -> Result<()> {
+        let group = group_key(last_active_epoch);
+        let address_key = address_key(address);
+
+        if let Some(previous) = self.backend.get(&address_key)? {
+            if previous == group {
+                return Ok(());
+            }
+
+            self.backend.delete(&entry_key(&previous, address))?;
+        }
+
+        self.backend.put(&entry_key(&group, address), &[])?;
+        self.backend.put(&address_key, &group)
+    }
+
+    pub fn remove(&self, address: &Address) -> Result<()> {
+        let address_key = address_key(address);
+
+        if let Some(previous) = self.backend.get(&address_key)? {
+            self.backend.delete(&entry_key(&previous, address))?;
+            self.backend.delete(&address_key)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        self.backend
+            .entries_after(None, 1)
+            .map(|entries| entries.is_empty())
+    }
+
+    /// Returns the addresses of the accounts last active during
+    /// `last_active_epoch`, or never active if `None`, in order
+    pub fn addresses_last_active_at(
+        &self,
+        last_active_epoch: Option<Epoch>,
+    ) -> Result<Vec<Address>> {
+        let group = group_key(last_active_epoch);
+
+        let mut addresses = vec![];
+        let mut cursor = group.clone();
+
+        loop {
+            let entries = self
+                .backend
+                .entries_after(Some(&cursor), ACTIVITY_INDEX_PAGE_SIZE)?;
+            let exhausted = entries.len() < ACTIVITY_INDEX_PAGE_SIZE;
+
+            for (key, _) in entries {
+                if !key.starts_with(&group) {
+                    return Ok(addresses);
+                }
+
+                let address = std::str::from_utf8(&key[group.len()..])
+                    .map_err(|err| StorageError::Other(err.to_string()))
+                    .and_then(|key| {
+                        Address::from_str(key).map_err(|err| StorageError::Other(err.to_string()))
+                    })?;
+
+                addresses.push(address);
+                cursor = key;
+            }
+
+            if exhausted {
+                return Ok(addresses);
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::str::FromStr;
+
+use primitives::Address;
+use storage_utils::{Result, StorageError};
+
+use crate::SharedBackend;
+
+/// Number of addresses read from the backend at a time while walking the
+/// index
+const ADDRESS_INDEX_PAGE_SIZE: usize = 256;
+
+/// Addresses of the accounts within the state trie, kept in their own backend
+/// under their string form.
+///
+/// The trie orders accounts by the hash of their address, so finding the
+/// accounts under an address prefix would mean walking all of it. The index
+/// keeps addresses in order instead, letting prefix lookups and scans seek
+/// straight to the first address they need.
+#[derive(Debug, Clone)]
+pub struct AddressIndex {
+    backend: SharedBackend,
+}
+
+impl AddressIndex {
+    pub fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    pub fn insert(&self, address: &Address) -> Result<()> {
+        self.backend.put(address.to_string().as_bytes(), &[])
+    }
+
+    pub fn remove(&self, address: &Address) -> Result<()> {
+        self.backend.delete(address.to_string().as_bytes())
+    }
+
+    pub fn contains(&self, address: &Address) -> Result<bool> {
+        self.backend
+            .get(address.to_string().as_bytes())
+            .map(|value| value.is_some())
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        self.backend
+            .entries_after(None, 1)
+            .map(|entries| entries.is_empty())
+    }
+
+    /// Streams the indexed addresses starting with `prefix` in order, from
+    /// the first one after `start_after` if given.
+    pub fn addresses<'a>(
+        &'a self,
+        prefix: &'a str,
+        start_after: Option<&Address>,
+    ) -> Result<IndexedAddresses<'a>> {
+        let start_after = start_after.map(|address| address.to_string().into_bytes());
+
+        let mut addresses = IndexedAddresses {
+            index: self,
+            prefix: prefix.as_bytes(),
+            cursor: None,
+            page: vec![].into_iter(),
+            exhausted: false,
+        };
+
+        match start_after {
+            Some(cursor) if cursor.as_slice() >= prefix.as_bytes() => {
+                addresses.cursor = Some(cursor);
+            },
+            _ if prefix.is_empty() => {},
+            _ => {
+                // NOTE: the backend seeks past the key it is given, so an
+                // address equal to the prefix has to be looked up on its own
+                if self.backend.get(prefix.as_bytes())?.is_some() {
+                    addresses.page = vec![(prefix.as_bytes().to_vec(), vec![])].into_iter();
+                }
+
+                addresses.cursor = Some(prefix.as_bytes().to_vec());
+            },
+        }
+
+        Ok(addresses)
+    }
+}
+
+/// Iterator over the addresses of an [AddressIndex] under a prefix, see
+/// [AddressIndex::addresses]
+#[derive(Debug)]
+pub struct IndexedAddresses<'a> {
+    index: &'a AddressIndex,
+    prefix: &'a [u8],
+    cursor: Option<Vec<u8>>,
+    page: std::vec::IntoIter<(Vec<u8>, Vec<u8>)>,
+    exhausted: bool,
+}
+
+impl<'a> Iterator for IndexedAddresses<'a> {
+    type Item = Result<Address>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, _)) = self.page.next() {
+                if !key.starts_with(self.prefix) {
+                    self.exhausted = true;
+                    self.page = vec![].into_iter();
+
+                    return None;
+                }
+
+                let address = String::from_utf8(key)
+                    .map_err(|err| StorageError::Other(err.to_string()))
+                    .and_then(|key| {
+                        Address::from_str(&key).map_err(|err| StorageError::Other(err.to_string()))
+                    });
+
+                return Some(address);
+            }
+
+            if self.exhausted {
+                return None;
+            }
+
+            let entries = match self
+                .index
+                .backend
+                .entries_after(self.cursor.as_deref(), ADDRESS_INDEX_PAGE_SIZE)
+            {
+                Ok(entries) => entries,
+                Err(err) => {
+                    self.exhausted = true;
+
+                    return Some(Err(err));
+                },
+            };
+
+            self.exhausted = entries.len() < ADDRESS_INDEX_PAGE_SIZE;
+
+            if let Some((key, _)) = entries.last() {
+                self.cursor = Some(key.clone());
+            }
+
+            self.page = entries.into_iter();
+        }
+    }
+}
@@ -1,15 +1,22 @@
 use std::{collections::HashMap, path::Path, sync::Arc};
 
 use integral_db::LeftRightTrie;
+use parking_lot::RwLock;
 use patriecia::RootHash;
-use primitives::Address;
+use primitives::{Address, DbBackend, PruningMode, Round};
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::account::{Account, UpdateArgs};
 
-use crate::RocksDbAdapter;
+use crate::{open_backend, SharedBackend, TrieDbAdapter};
 
+mod activity_index;
+mod address_index;
+mod state_root_history;
 mod state_store_rh;
+pub use activity_index::*;
+pub use address_index::*;
+pub use state_root_history::*;
 pub use state_store_rh::*;
 
 pub type Accounts = Vec<Account>;
@@ -17,7 +24,27 @@ pub type FailedAccountUpdates = Vec<(Address, Vec<UpdateArgs>, Result<()>)>;
 
 #[derive(Debug, Clone)]
 pub struct StateStore {
-    trie: LeftRightTrie<'static, Address, Account, RocksDbAdapter, Sha256>,
+    trie: LeftRightTrie<'static, Address, Account, TrieDbAdapter, Sha256>,
+    history: SharedStateRootHistory,
+
+    /// The versioned database backing the trie, pruned along with the
+    /// history
+    db: Arc<TrieDbAdapter>,
+
+    /// Backend the nodes of the trie are persisted to
+    nodes: SharedBackend,
+
+    /// Addresses of the accounts within the published trie, in order
+    addresses: AddressIndex,
+
+    /// Addresses of the accounts within the published trie, by the last
+    /// epoch they were active
+    activity: ActivityIndex,
+
+    /// Changes to the address index waiting for the trie to be published.
+    /// `true` for addresses that gained an account, `false` for those that
+    /// lost theirs
+    pending_addresses: HashMap<Address, bool>,
 }
 
 impl Default for StateStore {
@@ -27,11 +54,37 @@ impl Default for StateStore {
             .join("db")
             .join("state");
 
-        let db_adapter = RocksDbAdapter::new(db_path, "state").unwrap_or_default();
-
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
-
-        Self { trie }
+        let db_adapter = TrieDbAdapter::new(DbBackend::default(), db_path, "state").unwrap_or_default();
+
+        let nodes = db_adapter.backend();
+        let db = Arc::new(db_adapter);
+        let trie = LeftRightTrie::new(db.clone());
+
+        let addresses = open_backend(
+            DbBackend::default(),
+            db_path.with_file_name("state_addresses"),
+            "state_addresses",
+        )
+        .map(AddressIndex::new)
+        .expect("failed to open the state address index");
+
+        let activity = open_backend(
+            DbBackend::default(),
+            db_path.with_file_name("state_activity"),
+            "state_activity",
+        )
+        .map(ActivityIndex::new)
+        .expect("failed to open the state activity index");
+
+        Self {
+            trie,
+            history: SharedStateRootHistory::default(),
+            db,
+            nodes,
+            addresses,
+            activity,
+            pending_addresses: HashMap::new(),
+        }
     }
 }
 
@@ -39,22 +92,133 @@ impl StateStore {
     /// Returns new, empty instance of StateDb
 
     pub fn new(path: &Path) -> Self {
-        let path = path.join("state");
-        let db_adapter = RocksDbAdapter::new(path, "state").unwrap_or_default();
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
+        Self::new_with_pruning_mode(path, PruningMode::default())
+    }
+
+    /// Returns new, empty instance of StateDb that retains the state roots of
+    /// past rounds according to `pruning_mode`
+    pub fn new_with_pruning_mode(path: &Path, pruning_mode: PruningMode) -> Self {
+        Self::new_with_backend(path, DbBackend::default(), pruning_mode)
+    }
+
+    /// Returns new, empty instance of StateDb persisted to the given
+    /// `backend`
+    pub fn new_with_backend(path: &Path, backend: DbBackend, pruning_mode: PruningMode) -> Self {
+        let db_adapter =
+            TrieDbAdapter::new(backend, path.join("state"), "state").unwrap_or_default();
+        let nodes = db_adapter.backend();
+        let db = Arc::new(db_adapter);
+        let trie = LeftRightTrie::new(db.clone());
+
+        // NOTE: the history is kept apart from the trie, whose backend holds
+        // nothing but trie nodes
+        let history = open_backend(backend, path.join("state_roots"), "state_roots")
+            .and_then(|roots| StateRootHistory::open(pruning_mode, roots))
+            .unwrap_or_else(|err| {
+                telemetry::error!("Failed to open the state root history: {err}");
+                StateRootHistory::new(pruning_mode)
+            });
+
+        let addresses = open_backend(backend, path.join("state_addresses"), "state_addresses")
+            .map(AddressIndex::new)
+            .expect("failed to open the state address index");
+
+        let activity = open_backend(backend, path.join("state_activity"), "state_activity")
+            .map(ActivityIndex::new)
+            .expect("failed to open the state activity index");
+
+        let store = Self {
+            trie,
+            history: Arc::new(RwLock::new(history)),
+            db,
+            nodes,
+            addresses,
+            activity,
+            pending_addresses: HashMap::new(),
+        };
+
+        if let Err(err) = store.index_existing_accounts() {
+            telemetry::error!("Failed to index the addresses of the state: {err}");
+        }
 
-        Self { trie }
+        store
     }
 
     /// Returns new ReadHandle to the VrrDb data. As long as the returned value
     /// lives, no write to the database will be committed.
     pub fn read_handle(&self) -> StateStoreReadHandle {
         let inner = self.trie.handle();
-        StateStoreReadHandle::new(inner)
+        StateStoreReadHandle::new(
+            inner,
+            self.history.clone(),
+            self.addresses.clone(),
+            self.activity.clone(),
+        )
     }
 
     pub fn commit(&mut self) {
-        self.trie.publish();
+        self.commit_changes();
+    }
+
+    /// Fills empty address and activity indexes with the accounts already in
+    /// the trie, for databases written before the indexes existed
+    fn index_existing_accounts(&self) -> Result<()> {
+        let index_addresses = self.addresses.is_empty()?;
+        let index_activity = self.activity.is_empty()?;
+
+        if !index_addresses && !index_activity {
+            return Ok(());
+        }
+
+        for account in self.read_handle().iter()? {
+            let account = account?;
+
+            if index_addresses {
+                self.addresses.insert(account.address())?;
+            }
+
+            if index_activity {
+                self.activity
+                    .insert(account.address(), account.last_active_epoch())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn node_backend(&self) -> SharedBackend {
+        self.nodes.clone()
+    }
+
+    /// Commits pending changes as the state at the end of `round`, and records
+    /// the resulting root so the state can later be read as of that round.
+    /// The trie versions of the rounds the pruning mode no longer retains are
+    /// pruned.
+    pub fn commit_round(&mut self, round: Round) -> Result<RootHash> {
+        self.commit_changes();
+
+        let version = self.read_handle().inner.version();
+        let root_hash = self.root_hash()?;
+
+        let mut history = self.history.write();
+        let pruned = history.record(
+            round,
+            RoundStateRoot {
+                version,
+                root_hash: root_hash.clone(),
+            },
+        )?;
+
+        match history.earliest_version() {
+            Some(oldest_retained) if !pruned.is_empty() => {
+                self.db
+                    .prune(oldest_retained)
+                    .map_err(|err| StorageError::Other(err.to_string()))?;
+            },
+            _ => {},
+        }
+
+        Ok(root_hash)
     }
 
     pub fn get_account(&self, key: &Address) -> Result<Account> {
@@ -64,8 +228,31 @@ impl StateStore {
 
     /// Commits uncommitted changes to the underlying trie by calling
     /// `publish()` Will wait for EACH ReadHandle to be consumed.
+    ///
+    /// The address and activity indexes are brought up to date once the trie
+    /// is published, so they never list accounts readers can't see yet.
     fn commit_changes(&mut self) {
         self.trie.publish();
+
+        let read_handle = self.read_handle();
+
+        for (address, present) in self.pending_addresses.drain() {
+            let indexed = if present {
+                self.addresses.insert(&address).and_then(|_| {
+                    let last_active_epoch = read_handle.get(&address)?.last_active_epoch();
+
+                    self.activity.insert(&address, last_active_epoch)
+                })
+            } else {
+                self.addresses
+                    .remove(&address)
+                    .and_then(|_| self.activity.remove(&address))
+            };
+
+            if let Err(err) = indexed {
+                telemetry::error!("Failed to index the state address {address}: {err}");
+            }
+        }
     }
 
     // Maybe initialize is better name for that?
@@ -82,6 +269,7 @@ impl StateStore {
             ));
         }
 
+        self.pending_addresses.insert(key.clone(), true);
         self.trie.insert(key, account);
 
         Ok(())
@@ -288,13 +476,81 @@ impl StateStore {
             .map_err(|e| StorageError::Other(e.to_string()))
     }
 
+    /// Computes the root the trie would have once `updates` were applied,
+    /// without touching the trie itself.
+    ///
+    /// Updates are applied the way [StateStore::update] applies them, updates
+    /// to missing accounts or that fail to apply are left out.
+    // NOTE: the projection is built on a scratch trie holding a copy of every
+    // account, so it costs as much as the state is large
+    pub fn projected_root_hash(&self, updates: Vec<UpdateArgs>) -> Result<RootHash> {
+        self.projected_root_hash_with(updates, |_| {})
+    }
+
+    /// Like [StateStore::projected_root_hash], but lets `finalize` change the
+    /// accounts once `updates` are applied, for the changes protocol rules
+    /// make on top of txns. Accounts `finalize` removes are projected as
+    /// deleted
+    pub fn projected_root_hash_with<F>(
+        &self,
+        updates: Vec<UpdateArgs>,
+        finalize: F,
+    ) -> Result<RootHash>
+    where
+        F: FnOnce(&mut HashMap<Address, Account>),
+    {
+        let mut accounts = self.read_handle().entries();
+
+        for update in updates {
+            if let Some(account) = accounts.get_mut(&update.address) {
+                let mut updated = account.clone();
+                if updated.update(update).is_ok() {
+                    *account = updated;
+                }
+            }
+        }
+
+        finalize(&mut accounts);
+
+        let scratch_path = std::env::temp_dir().join(format!(
+            "projected-state-{}",
+            vrrb_core::helpers::generate_random_string()
+        ));
+
+        let root_hash = {
+            let mut scratch = StateStore::new(&scratch_path);
+            scratch.extend(
+                accounts
+                    .into_iter()
+                    .map(|(address, account)| (address, Some(account)))
+                    .collect(),
+            );
+            scratch.commit_changes();
+            scratch.root_hash()
+        };
+
+        let _ = std::fs::remove_dir_all(&scratch_path);
+
+        root_hash
+    }
+
     pub fn extend(&mut self, accounts: Vec<(Address, Option<Account>)>) {
+        for (address, account) in accounts.iter() {
+            self.pending_addresses
+                .insert(address.clone(), account.is_some());
+        }
+
         self.trie.extend(accounts)
     }
 
     pub fn factory(&self) -> StateStoreReadHandleFactory {
         let inner = self.trie.factory();
 
-        StateStoreReadHandleFactory::new(inner)
+        StateStoreReadHandleFactory::new(
+            inner,
+            self.history.clone(),
+            self.addresses.clone(),
+            self.activity.clone(),
+        )
     }
 }
@@ -0,0 +1,224 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use parking_lot::RwLock;
+use patriecia::{RootHash, Version};
+use primitives::{PruningMode, Round};
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+
+use crate::SharedBackend;
+
+/// The state trie's version and root hash as committed at the end of a round.
+#[derive(Debug, Clone)]
+pub struct RoundStateRoot {
+    pub version: Version,
+    pub root_hash: RootHash,
+}
+
+/// A [RoundStateRoot] as persisted to the backend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredStateRoot {
+    version: Version,
+    root_hash: [u8; 32],
+}
+
+/// Record of the state root committed at the end of every round.
+///
+/// Older versions of the state trie remain readable through the versioned
+/// database backing it, so keeping the version each round ended at is enough
+/// to read the state as it was at that round. Only the rounds retained by the
+/// node's pruning mode are kept.
+///
+/// Roots are persisted to their own backend, keyed by the big-endian round,
+/// so a restarted node can still read the state of the rounds it retained.
+#[derive(Debug, Clone, Default)]
+pub struct StateRootHistory {
+    pruning_mode: PruningMode,
+    roots: BTreeMap<Round, RoundStateRoot>,
+    backend: Option<SharedBackend>,
+}
+
+impl StateRootHistory {
+    pub fn new(pruning_mode: PruningMode) -> Self {
+        Self {
+            pruning_mode,
+            roots: BTreeMap::new(),
+            backend: None,
+        }
+    }
+
+    /// Opens the history persisted to `backend`, picking up the roots
+    /// recorded by previous runs
+    pub fn open(pruning_mode: PruningMode, backend: SharedBackend) -> Result<Self> {
+        let roots = backend
+            .entries()?
+            .into_iter()
+            .map(|(key, value)| {
+                let round = key
+                    .try_into()
+                    .map(Round::from_be_bytes)
+                    .map_err(|_| StorageError::Other("malformed state root key".to_string()))?;
+
+                let stored: StoredStateRoot = bincode::deserialize(&value)
+                    .map_err(|err| StorageError::Other(err.to_string()))?;
+
+                let root = RoundStateRoot {
+                    version: stored.version,
+                    root_hash: RootHash(stored.root_hash),
+                };
+
+                Ok((round, root))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            pruning_mode,
+            roots,
+            backend: Some(backend),
+        })
+    }
+
+    pub fn pruning_mode(&self) -> PruningMode {
+        self.pruning_mode
+    }
+
+    /// Records the state root committed at the end of `round`, forgetting
+    /// every round the pruning mode no longer retains, on disk too. Returns
+    /// the rounds that were pruned.
+    pub fn record(&mut self, round: Round, root: RoundStateRoot) -> Result<Vec<Round>> {
+        if let Some(backend) = self.backend.as_ref() {
+            let stored = StoredStateRoot {
+                version: root.version,
+                root_hash: root.root_hash.0,
+            };
+            let value =
+                bincode::serialize(&stored).map_err(|err| StorageError::Other(err.to_string()))?;
+
+            backend.put(&round.to_be_bytes(), &value)?;
+        }
+
+        self.roots.insert(round, root);
+
+        let latest_round = self.latest_round().unwrap_or(round);
+        let oldest_retained = self.pruning_mode.oldest_retained_round(latest_round);
+
+        let retained = self.roots.split_off(&oldest_retained);
+        let pruned: Vec<Round> = std::mem::replace(&mut self.roots, retained)
+            .into_keys()
+            .collect();
+
+        if let Some(backend) = self.backend.as_ref() {
+            for round in pruned.iter() {
+                backend.delete(&round.to_be_bytes())?;
+            }
+        }
+
+        Ok(pruned)
+    }
+
+    /// Returns the trie version of the oldest round still retained
+    pub fn earliest_version(&self) -> Option<Version> {
+        self.roots.values().next().map(|root| root.version)
+    }
+
+    /// Returns the state root committed at the end of `round`, unless it was
+    /// never recorded or has since been pruned.
+    pub fn get(&self, round: Round) -> Option<&RoundStateRoot> {
+        self.roots.get(&round)
+    }
+
+    /// Returns the oldest round whose state is still retained
+    pub fn earliest_round(&self) -> Option<Round> {
+        self.roots.keys().next().copied()
+    }
+
+    /// Returns the most recently committed round
+    pub fn latest_round(&self) -> Option<Round> {
+        self.roots.keys().next_back().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+}
+
+/// A state root history shared between a [crate::StateStore] and the read
+/// handles it hands out.
+pub type SharedStateRootHistory = Arc<RwLock<StateRootHistory>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root_at(version: Version) -> RoundStateRoot {
+        RoundStateRoot {
+            version,
+            root_hash: RootHash([version as u8; 32]),
+        }
+    }
+
+    #[test]
+    fn archive_nodes_retain_every_round() {
+        let mut history = StateRootHistory::new(PruningMode::Archive);
+
+        (0..100).for_each(|round| {
+            history.record(round, root_at(round as Version)).unwrap();
+        });
+
+        assert_eq!(history.len(), 100);
+        assert_eq!(history.earliest_round(), Some(0));
+        assert_eq!(history.get(42).unwrap().version, 42);
+    }
+
+    #[test]
+    fn pruning_nodes_only_retain_recent_rounds() {
+        let mut history = StateRootHistory::new(PruningMode::KeepRecent(3));
+
+        let pruned: Vec<Round> = (0..10)
+            .flat_map(|round| history.record(round, root_at(round as Version)).unwrap())
+            .collect();
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.earliest_round(), Some(7));
+        assert_eq!(history.latest_round(), Some(9));
+        assert!(history.get(6).is_none());
+        assert_eq!(history.get(8).unwrap().version, 8);
+        assert_eq!(pruned, (0..7).collect::<Vec<Round>>());
+    }
+
+    #[test]
+    fn retained_roots_survive_a_restart() {
+        let path = std::env::temp_dir().join(format!(
+            "state-roots-{}",
+            vrrb_core::helpers::generate_random_string()
+        ));
+        let open = || {
+            let backend = crate::open_backend(
+                primitives::DbBackend::default(),
+                path.clone(),
+                "state_roots",
+            )
+            .unwrap();
+
+            StateRootHistory::open(PruningMode::KeepRecent(3), backend).unwrap()
+        };
+
+        {
+            let mut history = open();
+            (0..10).for_each(|round| {
+                history.record(round, root_at(round as Version)).unwrap();
+            });
+        }
+
+        let history = open();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.earliest_round(), Some(7));
+        assert_eq!(history.get(9).unwrap().root_hash, RootHash([9; 32]));
+
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
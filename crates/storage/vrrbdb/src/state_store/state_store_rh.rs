@@ -2,21 +2,45 @@ use std::collections::HashMap;
 
 use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
 use patriecia::JellyfishMerkleTree;
-use primitives::Address;
+use primitives::{Address, Epoch, Round};
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::account::Account;
+use vrrb_core::transactions::Token;
 
-use crate::RocksDbAdapter;
+use crate::{ActivityIndex, AddressIndex, RoundStateRoot, SharedStateRootHistory, TrieDbAdapter};
+
+/// A bounded slice of the state trie, as returned by
+/// [StateStoreReadHandle::scan]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatePage {
+    pub accounts: Vec<Account>,
+
+    /// Address to resume the scan after, if more accounts remain
+    pub next_cursor: Option<Address>,
+}
 
 #[derive(Debug, Clone)]
 pub struct StateStoreReadHandle {
-    pub inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>,
+    pub inner: JellyfishMerkleTreeWrapper<TrieDbAdapter, Sha256>,
+    history: SharedStateRootHistory,
+    addresses: AddressIndex,
+    activity: ActivityIndex,
 }
 
 impl StateStoreReadHandle {
-    pub fn new(inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: JellyfishMerkleTreeWrapper<TrieDbAdapter, Sha256>,
+        history: SharedStateRootHistory,
+        addresses: AddressIndex,
+        activity: ActivityIndex,
+    ) -> Self {
+        Self {
+            inner,
+            history,
+            addresses,
+            activity,
+        }
     }
 
     /// NOTE: outdated docs
@@ -28,6 +52,70 @@ impl StateStoreReadHandle {
             .map_err(|err| StorageError::Other(err.to_string()))
     }
 
+    /// Returns how much of `token` the account stored under `key` holds.
+    /// Accounts only hold the native token for now.
+    pub fn get_balance(&self, key: &Address, token: &Token) -> Result<u128> {
+        if token != &Token::default() {
+            return Err(StorageError::Other(format!("unsupported token: {token}")));
+        }
+
+        self.get(key).map(|account| account.balance())
+    }
+
+    /// Returns the account stored under `key` as it was at the end of `round`.
+    pub fn get_account_at_round(&self, key: &Address, round: Round) -> Result<Account> {
+        let state_root = self.get_state_root(round)?;
+
+        self.inner
+            .get(key, state_root.version)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns the state root committed at the end of `round`. Fails if the
+    /// round was never committed or has been pruned since.
+    pub fn get_state_root(&self, round: Round) -> Result<RoundStateRoot> {
+        let history = self.history.read();
+
+        if let Some(state_root) = history.get(round) {
+            return Ok(state_root.clone());
+        }
+
+        match history.earliest_round() {
+            Some(earliest_round) if round < earliest_round => Err(StorageError::Other(format!(
+                "state at round {round} was pruned, oldest retained round is {earliest_round}"
+            ))),
+            _ => Err(StorageError::NotFound(format!("state root for round {round}"))),
+        }
+    }
+
+    /// Returns the most recently committed round along with its state root
+    pub fn latest_state_root(&self) -> Option<(Round, RoundStateRoot)> {
+        let history = self.history.read();
+        let round = history.latest_round()?;
+
+        history
+            .get(round)
+            .map(|state_root| (round, state_root.clone()))
+    }
+
+    /// Returns a copy of every account as of the end of `round`, as long as
+    /// that round hasn't been pruned.
+    pub fn entries_at_round(&self, round: Round) -> Result<Vec<Account>> {
+        let state_root = self.get_state_root(round)?;
+
+        let iter = self
+            .inner
+            .iter(state_root.version)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        Ok(iter
+            .filter_map(|item| {
+                let (_, account) = item.ok()?;
+                bincode::deserialize::<Account>(&account).ok()
+            })
+            .collect())
+    }
+
     /// Get a batch of accounts by providing Vec of PublicKeysHash
     ///
     /// Returns HashMap indexed by PublicKeys and containing either
@@ -46,20 +134,105 @@ impl StateStoreReadHandle {
         accounts
     }
 
-    pub fn entries(&self) -> HashMap<Address, Account> { 
-        // TODO: revisit and refactor into inner wrapper 
-        self.inner
-            .iter(self.inner.version()).expect("unable to create iterator from merkle tree wrapper starting at key {starting_key} with version {version}") 
-            .filter_map(|item| { 
-                if let Ok((_, account)) = item {
-                    let account = bincode::deserialize::<Account>(&account).unwrap_or_default();
-                    return Some((account.address().clone(), account)); 
-                }
-                None
+    /// Returns a copy of every account in the trie. Prefer [Self::iter] or
+    /// [Self::scan] on large states. Accounts that fail to read are logged
+    /// and left out.
+    pub fn entries(&self) -> HashMap<Address, Account> {
+        self.iter()
+            .expect("unable to create iterator from merkle tree wrapper")
+            .filter_map(|account| match account {
+                Ok(account) => Some((account.address().clone(), account)),
+                Err(err) => {
+                    telemetry::error!("Failed to read an account from the state trie: {err}");
+                    None
+                },
             })
             .collect()
     }
 
+    /// Streams the accounts of the latest version of the trie, decoding them
+    /// one at a time. Accounts come in key hash order, which stays the same
+    /// for as long as this handle lives. Entries that fail to read or decode
+    /// are reported rather than skipped.
+    pub fn iter(&self) -> Result<impl Iterator<Item = Result<Account>> + '_> {
+        let iter = self
+            .inner
+            .iter(self.inner.version())
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        Ok(iter.map(|item| {
+            let (_, account) = item.map_err(|err| StorageError::Other(err.to_string()))?;
+
+            bincode::deserialize::<Account>(&account)
+                .map_err(|err| StorageError::Other(err.to_string()))
+        }))
+    }
+
+    /// Streams the accounts whose address starts with `prefix`, in address
+    /// order. Only the accounts under the prefix are read.
+    pub fn prefix_iter<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> Result<impl Iterator<Item = Result<Account>> + 'a> {
+        self.indexed_accounts(prefix, None)
+    }
+
+    /// Returns up to `limit` accounts matching `prefix` whose address comes
+    /// after `start_after`. Feeding the returned cursor back in walks the
+    /// whole trie one page at a time.
+    pub fn scan(
+        &self,
+        prefix: Option<&str>,
+        start_after: Option<&Address>,
+        limit: usize,
+    ) -> Result<StatePage> {
+        if let Some(cursor) = start_after {
+            if !self.addresses.contains(cursor)? {
+                return Err(StorageError::NotFound(format!("state cursor {cursor}")));
+            }
+        }
+
+        let mut accounts = self
+            .indexed_accounts(prefix.unwrap_or_default(), start_after)?
+            .take(limit.saturating_add(1))
+            .collect::<Result<Vec<_>>>()?;
+
+        let next_cursor = if accounts.len() > limit {
+            accounts.truncate(limit);
+            accounts.last().map(|account| account.address().clone())
+        } else {
+            None
+        };
+
+        Ok(StatePage {
+            accounts,
+            next_cursor,
+        })
+    }
+
+    /// Reads the accounts of the addresses the index holds under `prefix`,
+    /// from the first one after `start_after` if given
+    fn indexed_accounts<'a>(
+        &'a self,
+        prefix: &'a str,
+        start_after: Option<&Address>,
+    ) -> Result<impl Iterator<Item = Result<Account>> + 'a> {
+        Ok(self
+            .addresses
+            .addresses(prefix, start_after)?
+            .map(move |address| self.get(&address?)))
+    }
+
+    /// Returns the addresses of the accounts a block last touched during
+    /// `last_active_epoch`, or that no block touched since their activity
+    /// started being tracked if `None`
+    pub fn addresses_last_active_at(
+        &self,
+        last_active_epoch: Option<Epoch>,
+    ) -> Result<Vec<Address>> {
+        self.activity.addresses_last_active_at(last_active_epoch)
+    }
+
     /// Returns a number of initialized accounts in the database
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -73,12 +246,25 @@ impl StateStoreReadHandle {
 
 #[derive(Debug, Clone)]
 pub struct StateStoreReadHandleFactory {
-    inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+    inner: ReadHandleFactory<JellyfishMerkleTree<TrieDbAdapter, Sha256>>,
+    history: SharedStateRootHistory,
+    addresses: AddressIndex,
+    activity: ActivityIndex,
 }
 
 impl StateStoreReadHandleFactory {
-    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: ReadHandleFactory<JellyfishMerkleTree<TrieDbAdapter, Sha256>>,
+        history: SharedStateRootHistory,
+        addresses: AddressIndex,
+        activity: ActivityIndex,
+    ) -> Self {
+        Self {
+            inner,
+            history,
+            addresses,
+            activity,
+        }
     }
 
     pub fn handle(&self) -> StateStoreReadHandle {
@@ -91,6 +277,11 @@ impl StateStoreReadHandleFactory {
 
         let inner = JellyfishMerkleTreeWrapper::new(handle);
 
-        StateStoreReadHandle { inner }
+        StateStoreReadHandle {
+            inner,
+            history: self.history.clone(),
+            addresses: self.addresses.clone(),
+            activity: self.activity.clone(),
+        }
     }
 }
@@ -3,10 +3,11 @@ use std::{path::Path, sync::Arc};
 
 use integral_db::{LeftRightTrie, Proof, H256};
 use patriecia::RootHash;
+use primitives::DbBackend;
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 
-use crate::RocksDbAdapter;
+use crate::{SharedBackend, TrieDbAdapter};
 
 mod transaction_store_rh;
 pub use transaction_store_rh::*;
@@ -14,7 +15,10 @@ use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind, T
 
 #[derive(Debug, Clone)]
 pub struct TransactionStore {
-    trie: LeftRightTrie<'static, TransactionDigest, TransactionKind, RocksDbAdapter, Sha256>,
+    trie: LeftRightTrie<'static, TransactionDigest, TransactionKind, TrieDbAdapter, Sha256>,
+
+    /// Backend the nodes of the trie are persisted to
+    nodes: SharedBackend,
 }
 
 impl Default for TransactionStore {
@@ -24,22 +28,30 @@ impl Default for TransactionStore {
             .join("db")
             .join("transactions");
 
-        let db_adapter = RocksDbAdapter::new(db_path, "transactions").unwrap_or_default();
+        let db_adapter = TrieDbAdapter::new(DbBackend::default(), db_path, "transactions").unwrap_or_default();
 
+        let nodes = db_adapter.backend();
         let trie = LeftRightTrie::new(Arc::new(db_adapter));
 
-        Self { trie }
+        Self { trie, nodes }
     }
 }
 
 impl TransactionStore {
     /// Returns new, empty instance of TransactionStore
     pub fn new(path: &Path) -> Self {
+        Self::new_with_backend(path, DbBackend::default())
+    }
+
+    /// Returns new, empty instance of TransactionStore persisted to the given
+    /// `backend`
+    pub fn new_with_backend(path: &Path, backend: DbBackend) -> Self {
         let path = path.join("transactions");
-        let db_adapter = RocksDbAdapter::new(path, "transactions").unwrap_or_default();
+        let db_adapter = TrieDbAdapter::new(backend, path, "transactions").unwrap_or_default();
+        let nodes = db_adapter.backend();
         let trie = LeftRightTrie::new(Arc::new(db_adapter));
 
-        Self { trie }
+        Self { trie, nodes }
     }
 
     pub fn factory(&self) -> TransactionStoreReadHandleFactory {
@@ -52,6 +64,10 @@ impl TransactionStore {
         self.trie.publish();
     }
 
+    pub(crate) fn node_backend(&self) -> SharedBackend {
+        self.nodes.clone()
+    }
+
     pub fn read_handle(&self) -> TransactionStoreReadHandle {
         let inner = self.trie.handle();
         TransactionStoreReadHandle::new(inner)
@@ -71,6 +87,13 @@ impl TransactionStore {
         self.trie.extend(transactions)
     }
 
+    /// Removes transactions from the current transaction tree
+    pub fn retract(&mut self, digests: Vec<TransactionDigest>) {
+        let transactions = digests.into_iter().map(|digest| (digest, None)).collect();
+
+        self.trie.extend(transactions)
+    }
+
     pub fn root_hash(&self) -> Result<RootHash> {
         self.trie
             .root_latest()
@@ -6,15 +6,15 @@ use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind};
 
-use crate::RocksDbAdapter;
+use crate::TrieDbAdapter;
 
 #[derive(Debug, Clone)]
 pub struct TransactionStoreReadHandle {
-    inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>,
+    inner: JellyfishMerkleTreeWrapper<TrieDbAdapter, Sha256>,
 }
 
 impl TransactionStoreReadHandle {
-    pub fn new(inner: JellyfishMerkleTreeWrapper<RocksDbAdapter, Sha256>) -> Self {
+    pub fn new(inner: JellyfishMerkleTreeWrapper<TrieDbAdapter, Sha256>) -> Self {
         Self { inner }
     }
 
@@ -24,6 +24,11 @@ impl TransactionStoreReadHandle {
             .map_err(|err| StorageError::Other(err.to_string()))
     }
 
+    /// Returns whether the ledger holds the transaction with digest `key`
+    pub fn contains(&self, key: &TransactionDigest) -> bool {
+        self.inner.get(key, self.inner.version()).is_ok()
+    }
+
     pub fn batch_get(
         &self,
         keys: Vec<TransactionDigest>,
@@ -68,11 +73,11 @@ impl TransactionStoreReadHandle {
 
 #[derive(Debug, Clone)]
 pub struct TransactionStoreReadHandleFactory {
-    inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+    inner: ReadHandleFactory<JellyfishMerkleTree<TrieDbAdapter, Sha256>>,
 }
 
 impl TransactionStoreReadHandleFactory {
-    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>) -> Self {
+    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<TrieDbAdapter, Sha256>>) -> Self {
         Self { inner }
     }
 
@@ -5,30 +5,31 @@ use anyhow::{bail, Result};
 use parking_lot::RwLock;
 use patriecia::{
     KeyHash, LeafNode, Node, NodeBatch, NodeKey, OwnedValue, Preimage, StaleNodeIndex, TreeReader,
-    TreeUpdateBatch, TreeWriter, Vers, VersionedDatabase,
+    TreeUpdateBatch, TreeWriter, Vers, Version, VersionedDatabase,
 };
-use primitives::{get_vrrb_environment, Environment, DEFAULT_VRRB_DB_PATH};
-use rocksdb::{IteratorMode, DB, DEFAULT_COLUMN_FAMILY_NAME};
+use primitives::{DbBackend, DEFAULT_VRRB_DB_PATH};
 use std::sync::Arc;
-use storage_utils::{get_node_data_dir, StorageError};
-use telemetry::error;
 
+use crate::{open_backend, SharedBackend};
+
+/// Adapts a [KeyValueBackend] into the versioned database the state, claim and
+/// transaction tries are stored in. Trie nodes are persisted to the backend,
+/// while stale node indices, value history and preimages are kept in memory.
 #[derive(Debug)]
-pub struct RocksDbAdapter {
-    data: Arc<RwLock<RocksDbInner>>,
-    column: String,
+pub struct TrieDbAdapter {
+    data: Arc<RwLock<TrieDbInner>>,
 }
 #[derive(Debug)]
-pub struct RocksDbInner {
-    db: DB,
+pub struct TrieDbInner {
+    backend: SharedBackend,
     stale_nodes: BTreeSet<StaleNodeIndex>,
     value_history: HashMap<KeyHash, Vec<(Vers, Option<OwnedValue>)>>,
     preimages: HashMap<KeyHash, Preimage>,
 }
-impl RocksDbInner {
-    fn new(instance: DB) -> Self {
+impl TrieDbInner {
+    fn new(backend: SharedBackend) -> Self {
         Self {
-            db: instance,
+            backend,
             stale_nodes: BTreeSet::new(),
             value_history: HashMap::new(),
             preimages: HashMap::new(),
@@ -36,80 +37,24 @@ impl RocksDbInner {
     }
 }
 
-fn base_db_options() -> rocksdb::Options {
-    let mut options = rocksdb::Options::default();
-
-    let environ = get_vrrb_environment();
-
-    if matches!(environ, Environment::Local) {
-        options.set_keep_log_file_num(3);
-    }
-
-    match get_node_data_dir() {
-        Ok(node_data_dir) => {
-            let log_path = node_data_dir.join("db").join("log");
-            options.set_db_log_dir(log_path);
-        },
-        Err(err) => {
-            error!("could not get node data directory: {}", err);
-            let default_data_dir = std::path::PathBuf::default();
-            let log_path = default_data_dir.join("db").join("log");
-            options.set_db_log_dir(log_path);
-        },
-    }
-
-    options
-}
-
-fn new_db_instance(
-    options: rocksdb::Options,
-    path: std::path::PathBuf,
-    column_family: &str,
-) -> storage_utils::Result<DB> {
-    let cfs = match rocksdb::DB::list_cf(&options, &path) {
-        Ok(cfs) => cfs,
-        Err(err) => {
-            error!(
-                "could not find column families at {}: {}",
-                path.display(),
-                err.into_string()
-            );
-            vec![]
-        },
-    };
-
-    let column_family_exists = cfs.iter().any(|cf| cf == column_family);
-
-    let mut instance = rocksdb::DB::open_cf(&options, &path, cfs)
-        .map_err(|err| StorageError::Other(err.to_string()))?;
-
-    if !column_family_exists {
-        if column_family != DEFAULT_COLUMN_FAMILY_NAME {
-            instance
-                .create_cf(column_family, &options)
-                .map_err(|err| StorageError::Other(err.to_string()))?;
-        }
-    }
-
-    Ok(instance)
-}
-
-impl RocksDbAdapter {
-    pub fn new(path: std::path::PathBuf, column_family: &str) -> storage_utils::Result<Self> {
-        let mut options = base_db_options();
-        options.set_error_if_exists(false);
-        options.create_if_missing(true);
-        options.create_missing_column_families(true);
-
-        let instance = new_db_instance(options, path, column_family)
-            .map_err(|err| StorageError::Other(err.to_string()))?;
+impl TrieDbAdapter {
+    pub fn new(
+        backend: DbBackend,
+        path: std::path::PathBuf,
+        namespace: &str,
+    ) -> storage_utils::Result<Self> {
+        let backend = open_backend(backend, path, namespace)?;
 
         Ok(Self {
-            data: Arc::new(RwLock::new(RocksDbInner::new(instance))),
-            column: column_family.to_string(),
+            data: Arc::new(RwLock::new(TrieDbInner::new(backend))),
         })
     }
 
+    /// Returns the backend the trie nodes are persisted to
+    pub(crate) fn backend(&self) -> SharedBackend {
+        self.data.read().backend.clone()
+    }
+
     pub fn write_tree_update_batch(&self, batch: TreeUpdateBatch) -> Result<()> {
         self.write_node_batch(&batch.node_batch)?;
         batch
@@ -125,58 +70,66 @@ impl RocksDbAdapter {
         anyhow::ensure!(is_new_entry, "Duplicated retire log");
         Ok(())
     }
+
+    /// Deletes the trie nodes and value versions that only versions older
+    /// than `oldest_retained` read, so the trie can no longer be read as of
+    /// those versions. Returns how many nodes were deleted
+    pub fn prune(&self, oldest_retained: Version) -> Result<usize> {
+        let mut locked = self.data.write();
+
+        let (stale, retained): (BTreeSet<StaleNodeIndex>, BTreeSet<StaleNodeIndex>) =
+            std::mem::take(&mut locked.stale_nodes)
+                .into_iter()
+                .partition(|index| index.stale_since_version <= oldest_retained);
+
+        locked.stale_nodes = retained;
+
+        for index in stale.iter() {
+            locked
+                .backend
+                .delete(&bincode::serialize(&index.node_key)?)?;
+        }
+
+        // NOTE: the latest value as of the oldest retained version is still read
+        // at that version, only the ones it superseded can go
+        let oldest_retained: Vers = oldest_retained.into();
+        for history in locked.value_history.values_mut() {
+            let superseded = history
+                .iter()
+                .rposition(|(version, _)| *version <= oldest_retained)
+                .unwrap_or_default();
+
+            history.drain(..superseded);
+        }
+
+        Ok(stale.len())
+    }
 }
 
-// TODO: handle these unwrap
-impl Clone for RocksDbAdapter {
+impl Clone for TrieDbAdapter {
     fn clone(&self) -> Self {
         let locked = self.data.read();
-        let mut options = base_db_options();
-        options.set_error_if_exists(false);
-
-        let db = new_db_instance(options, locked.db.path().into(), self.column.as_str()).unwrap();
 
         Self {
-            data: Arc::new(RwLock::new(RocksDbInner {
-                db,
+            data: Arc::new(RwLock::new(TrieDbInner {
+                backend: locked.backend.clone(),
                 stale_nodes: locked.stale_nodes.clone(),
                 value_history: locked.value_history.clone(),
                 preimages: locked.preimages.clone(),
             })),
-            column: self.column.clone(),
         }
     }
 }
 
-impl Default for RocksDbAdapter {
+impl Default for TrieDbAdapter {
     fn default() -> Self {
-        let mut options = base_db_options();
-        options.set_error_if_exists(false);
-        options.create_if_missing(true);
-        options.create_missing_column_families(true);
-
         //
         // TODO: fix this unwrap
-        let db = new_db_instance(
-            options,
-            DEFAULT_VRRB_DB_PATH.into(),
-            DEFAULT_COLUMN_FAMILY_NAME,
-        )
-        .unwrap();
-
-        Self {
-            data: Arc::new(RwLock::new(RocksDbInner {
-                db,
-                stale_nodes: BTreeSet::new(),
-                value_history: HashMap::new(),
-                preimages: HashMap::new(),
-            })),
-            column: DEFAULT_COLUMN_FAMILY_NAME.to_string(),
-        }
+        Self::new(DbBackend::default(), DEFAULT_VRRB_DB_PATH.into(), "default").unwrap()
     }
 }
 
-impl VersionedDatabase for RocksDbAdapter {
+impl VersionedDatabase for TrieDbAdapter {
     type Version = Vers;
     type NodeIter = IntoIter<NodeKey, Node>;
     type HistoryIter = IntoIter<patriecia::KeyHash, Vec<(Vers, Option<OwnedValue>)>>;
@@ -191,21 +144,14 @@ impl VersionedDatabase for RocksDbAdapter {
 
     fn nodes(&self) -> IntoIter<NodeKey, Node> {
         let locked = self.data.read();
-        let iter = locked.db.iterator(IteratorMode::Start);
+        let entries = locked.backend.entries().unwrap_or_default();
         let mut map = HashMap::new();
-        for res in iter {
-            match res {
-                Ok((boxed_key, boxed_node)) => {
-                    let key_bytes = boxed_key.into_vec();
-                    let node_bytes = boxed_node.into_vec();
-                    if let Ok(node_key) = bincode::deserialize::<NodeKey>(&key_bytes) {
-                        if let Ok(node) = bincode::deserialize::<Node>(&node_bytes) {
-                            map.insert(node_key, node);
-                        }
-                    };
-                },
-                _ => {},
-            }
+        for (key_bytes, node_bytes) in entries {
+            if let Ok(node_key) = bincode::deserialize::<NodeKey>(&key_bytes) {
+                if let Ok(node) = bincode::deserialize::<Node>(&node_bytes) {
+                    map.insert(node_key, node);
+                }
+            };
         }
 
         map.into_iter()
@@ -220,12 +166,12 @@ impl VersionedDatabase for RocksDbAdapter {
         self.data.read().value_history.clone().into_iter()
     }
 }
-impl TreeReader for RocksDbAdapter {
+impl TreeReader for TrieDbAdapter {
     type Version = Vers;
 
     fn get_node_option(&self, node_key: &NodeKey) -> Result<Option<Node>> {
         let key_bytes = bincode::serialize(node_key)?;
-        if let Ok(Some(bytes)) = self.data.read().db.get(key_bytes) {
+        if let Ok(Some(bytes)) = self.data.read().backend.get(&key_bytes) {
             if let Ok(node) = bincode::deserialize(&bytes) {
                 Ok(Some(node))
             } else {
@@ -258,15 +204,14 @@ impl TreeReader for RocksDbAdapter {
         let locked = self.data.read();
         let mut key_and_node: Option<(NodeKey, LeafNode)> = None;
 
-        let iter = locked.db.iterator(IteratorMode::Start);
-        for res in iter {
-            if let Ok((boxed_key, boxed_value)) = res {
-                let node_key: NodeKey = bincode::deserialize(&boxed_key.into_vec())?;
-                let node_value: Node = bincode::deserialize(&boxed_value.into_vec())?;
-                if let Node::Leaf(leaf_node) = node_value {
-                    if key_and_node.is_none() || leaf_node.key_hash() > key_and_node.as_ref().unwrap().1.key_hash() {
-                        key_and_node.replace((node_key.clone(), leaf_node.clone()));
-                    }
+        for (key_bytes, value_bytes) in locked.backend.entries()? {
+            let node_key: NodeKey = bincode::deserialize(&key_bytes)?;
+            let node_value: Node = bincode::deserialize(&value_bytes)?;
+            if let Node::Leaf(leaf_node) = node_value {
+                if key_and_node.is_none()
+                    || leaf_node.key_hash() > key_and_node.as_ref().unwrap().1.key_hash()
+                {
+                    key_and_node.replace((node_key.clone(), leaf_node.clone()));
                 }
             }
         }
@@ -274,13 +219,13 @@ impl TreeReader for RocksDbAdapter {
     }
 }
 
-impl TreeWriter for RocksDbAdapter {
+impl TreeWriter for TrieDbAdapter {
     fn write_node_batch(&self, node_batch: &NodeBatch) -> Result<()> {
         let mut locked = self.data.write();
         for (node_key, node) in node_batch.nodes() {
             let node_key_bytes = bincode::serialize(&node_key)?;
             let node_bytes = bincode::serialize(&node)?;
-            locked.db.put(node_key_bytes, node_bytes)?;
+            locked.backend.put(&node_key_bytes, &node_bytes)?;
         }
 
         for ((version, key_hash), value) in node_batch.values() {
@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use primitives::DbBackend;
+use storage_utils::{Result, StorageError};
+use vrrb_core::transactions::TransactionDigest;
+
+use crate::{open_backend, SharedBackend};
+
+mod txn_receipt_store_rh;
+mod types;
+pub use txn_receipt_store_rh::*;
+pub use types::*;
+
+/// Persists the milestones a node witnessed of every transaction: its
+/// admission into the mempool, its certification and the blocks it was
+/// included in. Together with the block certification records, receipts let
+/// the lifecycle of a transaction be traced after the fact.
+///
+/// Receipts are keyed by transaction digest and filled in as the milestones
+/// happen, so a receipt may lack any of them.
+#[derive(Debug, Clone)]
+pub struct TxnReceiptStore {
+    backend: SharedBackend,
+}
+
+impl Default for TxnReceiptStore {
+    fn default() -> Self {
+        let db_path = storage_utils::get_node_data_dir()
+            .unwrap_or_default()
+            .join("db");
+
+        // TODO: fix this expect
+        Self::new(&db_path, DbBackend::default())
+            .expect("failed to open the default txn receipt store")
+    }
+}
+
+impl TxnReceiptStore {
+    /// Opens the receipt store within `path`, picking up the receipts
+    /// persisted by previous runs.
+    pub fn new(path: &Path, backend: DbBackend) -> Result<Self> {
+        let backend = open_backend(backend, path.join("txn_receipts"), "txn_receipts")?;
+
+        Ok(Self { backend })
+    }
+
+    pub fn read_handle(&self) -> TxnReceiptStoreReadHandle {
+        TxnReceiptStoreReadHandle::new(self.backend.clone())
+    }
+
+    pub(crate) fn backend(&self) -> SharedBackend {
+        self.backend.clone()
+    }
+
+    /// Records when a transaction was admitted into the mempool. Only the
+    /// first admission is kept
+    pub fn record_admission(&mut self, digest: TransactionDigest, admitted_at: i64) -> Result<()> {
+        self.update(digest, |receipt| {
+            receipt.admitted_at.get_or_insert(admitted_at);
+        })
+    }
+
+    /// Records how a transaction was certified
+    pub fn record_certification(
+        &mut self,
+        digest: TransactionDigest,
+        certification: TxnCertificationRecord,
+    ) -> Result<()> {
+        self.update(digest, |receipt| {
+            receipt.certification = Some(certification);
+        })
+    }
+
+    /// Records the blocks a transaction was included in
+    pub fn record_inclusion(
+        &mut self,
+        digest: TransactionDigest,
+        inclusion: TxnInclusionRecord,
+    ) -> Result<()> {
+        self.update(digest, |receipt| {
+            receipt.inclusion = Some(inclusion);
+        })
+    }
+
+    fn update(&mut self, digest: TransactionDigest, f: impl FnOnce(&mut TxnReceipt)) -> Result<()> {
+        let mut receipt = self
+            .read_handle()
+            .get(&digest)?
+            .unwrap_or_else(|| TxnReceipt::new(digest.clone()));
+
+        f(&mut receipt);
+
+        let stored = StoredTxnReceipt::new(receipt)?;
+        let value =
+            bincode::serialize(&stored).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.backend.put(digest.as_bytes(), &value)
+    }
+}
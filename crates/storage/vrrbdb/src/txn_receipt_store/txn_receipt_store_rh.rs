@@ -0,0 +1,27 @@
+use storage_utils::{Result, StorageError};
+use vrrb_core::transactions::TransactionDigest;
+
+use crate::{SharedBackend, StoredTxnReceipt, TxnReceipt};
+
+#[derive(Debug, Clone)]
+pub struct TxnReceiptStoreReadHandle {
+    backend: SharedBackend,
+}
+
+impl TxnReceiptStoreReadHandle {
+    pub(crate) fn new(backend: SharedBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Returns what was recorded of a transaction, if anything was
+    pub fn get(&self, digest: &TransactionDigest) -> Result<Option<TxnReceipt>> {
+        self.backend
+            .get(digest.as_bytes())?
+            .map(|value| {
+                bincode::deserialize::<StoredTxnReceipt>(&value)
+                    .map(|stored| stored.receipt)
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()
+    }
+}
@@ -0,0 +1,88 @@
+use block::BlockHash;
+use primitives::{NodeId, QuorumPublicKey, Round, TxnValidationStatus};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use storage_utils::{Result, StorageError};
+use vrrb_core::transactions::TransactionDigest;
+
+/// How a farmer quorum certified a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnCertificationRecord {
+    /// Unix timestamp of when the certificate was created
+    pub certified_at: i64,
+    pub quorum_public_key: QuorumPublicKey,
+    pub farmer_id: NodeId,
+    pub validation_status: TxnValidationStatus,
+
+    /// Number of votes the certificate was aggregated from
+    pub votes: usize,
+
+    /// Number of those votes that found the transaction valid
+    pub approvals: usize,
+    pub quorum_threshold: usize,
+}
+
+/// The blocks a transaction was included in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnInclusionRecord {
+    /// Unix timestamp of when the convergence block was received
+    pub included_at: i64,
+    pub proposal_block_hash: BlockHash,
+    pub convergence_block_hash: BlockHash,
+    pub round: Round,
+}
+
+/// What a node witnessed of a transaction on its way from the mempool to a
+/// convergence block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnReceipt {
+    pub digest: TransactionDigest,
+
+    /// Unix timestamp of when the transaction was admitted into the mempool
+    pub admitted_at: Option<i64>,
+    pub certification: Option<TxnCertificationRecord>,
+    pub inclusion: Option<TxnInclusionRecord>,
+}
+
+impl TxnReceipt {
+    pub fn new(digest: TransactionDigest) -> Self {
+        Self {
+            digest,
+            admitted_at: None,
+            certification: None,
+            inclusion: None,
+        }
+    }
+}
+
+/// A receipt as persisted, along with a checksum of its encoding. Receipts
+/// are the node's own observations, which no other record commits to, so
+/// the checksum is what lets bit rot within them be caught.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct StoredTxnReceipt {
+    pub(crate) receipt: TxnReceipt,
+
+    /// Hex encoded SHA-256 hash of the bincode encoding of `receipt`
+    pub(crate) checksum: String,
+}
+
+impl StoredTxnReceipt {
+    pub(crate) fn new(receipt: TxnReceipt) -> Result<Self> {
+        let checksum = Self::checksum_of(&receipt)?;
+
+        Ok(Self { receipt, checksum })
+    }
+
+    fn checksum_of(receipt: &TxnReceipt) -> Result<String> {
+        let encoded =
+            bincode::serialize(receipt).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        Ok(hex::encode(Sha256::digest(encoded)))
+    }
+
+    /// Returns whether the receipt still hashes to the checksum recorded
+    /// along with it
+    pub(crate) fn matches_checksum(&self) -> bool {
+        Self::checksum_of(&self.receipt).map_or(false, |checksum| checksum == self.checksum)
+    }
+}
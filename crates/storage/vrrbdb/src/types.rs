@@ -142,8 +142,9 @@ impl FromBlock for HashSet<StateUpdate> {
         let mut proposer_fees = 0u128;
 
         block.txns.into_iter().for_each(|(_digest, txn)| {
-            let fee = txn.proposer_fee_share();
-            proposer_fees += fee;
+            // NOTE: saturates rather than panicking, fees are capped per txn
+            // so no block comes close to overflowing
+            proposer_fees = proposer_fees.saturating_add(txn.proposer_fee_share());
 
             let updates = IntoUpdates::from_txn(txn.txn());
             set.insert(updates.sender_update);
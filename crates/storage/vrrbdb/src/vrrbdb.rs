@@ -1,20 +1,30 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, io::Write, path::PathBuf};
 
-use block::Block;
+use block::{Block, BlockHash, Certificate, Checkpoint, EpochSummary, SignedHaltDirective};
 use ethereum_types::U256;
 use patriecia::RootHash;
-use primitives::Address;
+use primitives::{
+    Address, CertificationRetention, DbBackend, PruningMode, Round,
+    DEFAULT_RETAINED_TIMELINE_ROUNDS,
+};
 use storage_utils::{Result, StorageError};
-use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind, Transfer};
+use vrrb_core::transactions::{
+    AccountUpdate, Transaction, TransactionDigest, TransactionKind, Transfer,
+};
 use vrrb_core::{
-    account::{Account, UpdateArgs},
+    account::{Account, AccountDigests, UpdateArgs},
     claim::Claim,
 };
 
 use crate::{
-    ClaimStore, ClaimStoreReadHandleFactory, FromTxn, IntoUpdates, StateStore,
-    StateStoreReadHandleFactory, TransactionStore, TransactionStoreReadHandleFactory,
-    VrrbDbReadHandle,
+    BlockRecovery, BlockWal, BlockWalEntry, CertificationStore, CertificationVote,
+    CertifiedTxnIndex, CertifiedTxnKey, ChainScrubber, CheckpointStore, ClaimStore,
+    ClaimStoreReadHandleFactory, ConsensusTimelineStore, CorruptRecord, EarningsRecord,
+    EarningsStore, EpochSummaryStore, FromTxn, HaltLogStore, IntoUpdates, ParameterChangeRecord,
+    ParameterStore, PartialSignatureRecord, QuorumHistoryStore, QuorumRecord, RecordOutcome,
+    RoundMilestone, ScrubbedStore, SharedBackend, StateStore, StateStoreReadHandleFactory,
+    TransactionStore, TransactionStoreReadHandleFactory, TxnCertificationRecord,
+    TxnInclusionRecord, TxnReceiptStore, VrrbDbReadHandle,
 };
 
 #[derive(Debug, Clone)]
@@ -24,6 +34,18 @@ pub struct VrrbDbConfig {
     pub transaction_store_path: Option<String>,
     pub event_store_path: Option<String>,
     pub claim_store_path: Option<String>,
+
+    /// How many rounds of state history are kept queryable
+    pub pruning_mode: PruningMode,
+
+    /// How many blocks' certification records are kept around
+    pub certification_retention: CertificationRetention,
+
+    /// How many rounds' consensus timelines are kept around
+    pub timeline_rounds: usize,
+
+    /// The key-value store every store within the database persists to
+    pub backend: DbBackend,
 }
 
 impl VrrbDbConfig {
@@ -32,6 +54,30 @@ impl VrrbDbConfig {
 
         self.clone()
     }
+
+    pub fn with_pruning_mode(&mut self, pruning_mode: PruningMode) -> Self {
+        self.pruning_mode = pruning_mode;
+
+        self.clone()
+    }
+
+    pub fn with_certification_retention(&mut self, retention: CertificationRetention) -> Self {
+        self.certification_retention = retention;
+
+        self.clone()
+    }
+
+    pub fn with_timeline_rounds(&mut self, timeline_rounds: usize) -> Self {
+        self.timeline_rounds = timeline_rounds;
+
+        self.clone()
+    }
+
+    pub fn with_backend(&mut self, backend: DbBackend) -> Self {
+        self.backend = backend;
+
+        self.clone()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -72,6 +118,10 @@ impl Default for VrrbDbConfig {
             transaction_store_path: None,
             event_store_path: None,
             claim_store_path: None,
+            pruning_mode: PruningMode::default(),
+            certification_retention: CertificationRetention::default(),
+            timeline_rounds: DEFAULT_RETAINED_TIMELINE_ROUNDS,
+            backend: DbBackend::default(),
         }
     }
 }
@@ -81,23 +131,80 @@ pub struct VrrbDb {
     state_store: StateStore,
     transaction_store: TransactionStore,
     claim_store: ClaimStore,
+    certification_store: CertificationStore,
+    certified_txn_index: CertifiedTxnIndex,
+    quorum_history_store: QuorumHistoryStore,
+    checkpoint_store: CheckpointStore,
+    halt_log_store: HaltLogStore,
+    parameter_store: ParameterStore,
+    earnings_store: EarningsStore,
+    epoch_summary_store: EpochSummaryStore,
+    txn_receipt_store: TxnReceiptStore,
+    consensus_timeline_store: ConsensusTimelineStore,
+    block_wal: BlockWal,
 }
 
 impl VrrbDb {
     pub fn new(config: VrrbDbConfig) -> Self {
-        let state_store = StateStore::new(&config.path);
-        let transaction_store = TransactionStore::new(&config.path);
-        let claim_store = ClaimStore::new(&config.path);
+        let state_store =
+            StateStore::new_with_backend(&config.path, config.backend, config.pruning_mode);
+        let transaction_store = TransactionStore::new_with_backend(&config.path, config.backend);
+        let claim_store = ClaimStore::new_with_backend(&config.path, config.backend);
+        let certification_store =
+            CertificationStore::new(&config.path, config.backend, config.certification_retention)
+                .unwrap_or_default();
+        let certified_txn_index =
+            CertifiedTxnIndex::new(&config.path, config.backend).unwrap_or_default();
+        let quorum_history_store =
+            QuorumHistoryStore::new(&config.path, config.backend).unwrap_or_default();
+        let checkpoint_store =
+            CheckpointStore::new(&config.path, config.backend).unwrap_or_default();
+        let halt_log_store = HaltLogStore::new(&config.path, config.backend).unwrap_or_default();
+        let parameter_store = ParameterStore::new(&config.path, config.backend).unwrap_or_default();
+        let earnings_store = EarningsStore::new(&config.path, config.backend).unwrap_or_default();
+        let epoch_summary_store =
+            EpochSummaryStore::new(&config.path, config.backend).unwrap_or_default();
+        let txn_receipt_store =
+            TxnReceiptStore::new(&config.path, config.backend).unwrap_or_default();
+        let consensus_timeline_store =
+            ConsensusTimelineStore::new(&config.path, config.backend, config.timeline_rounds)
+                .unwrap_or_default();
+        let block_wal = BlockWal::new(&config.path, config.backend).unwrap_or_default();
 
         Self {
             state_store,
             transaction_store,
             claim_store,
+            certification_store,
+            certified_txn_index,
+            quorum_history_store,
+            checkpoint_store,
+            halt_log_store,
+            parameter_store,
+            earnings_store,
+            epoch_summary_store,
+            txn_receipt_store,
+            consensus_timeline_store,
+            block_wal,
         }
     }
 
-    pub fn export_state(&self) {
-        todo!("implement once integral-db is ready to be consumed");
+    /// Writes every account in state to `writer` as newline-delimited JSON,
+    /// one account at a time. Returns the number of accounts exported.
+    pub fn export_state<W: Write>(&self, mut writer: W) -> Result<usize> {
+        let read_handle = self.state_store.read_handle();
+        let mut exported = 0;
+
+        for account in read_handle.iter()? {
+            serde_json::to_writer(&mut writer, &account?)
+                .map_err(|err| StorageError::Other(err.to_string()))?;
+            writer.write_all(b"\n")?;
+            exported += 1;
+        }
+
+        writer.flush()?;
+
+        Ok(exported)
     }
 
     pub fn commit_transactions(&mut self) {
@@ -108,6 +215,12 @@ impl VrrbDb {
         self.state_store.commit();
     }
 
+    /// Commits the state as of the end of `round`, keeping its root around so
+    /// the state can later be queried at that round.
+    pub fn commit_state_at_round(&mut self, round: Round) -> Result<RootHash> {
+        self.state_store.commit_round(round)
+    }
+
     pub fn commit_claims(&mut self) {
         self.claim_store.commit();
     }
@@ -117,6 +230,16 @@ impl VrrbDb {
             self.state_store.factory(),
             self.transaction_store_factory(),
             self.claim_store_factory(),
+            self.certification_store.read_handle(),
+            self.certified_txn_index.read_handle(),
+            self.quorum_history_store.read_handle(),
+            self.checkpoint_store.read_handle(),
+            self.halt_log_store.read_handle(),
+            self.parameter_store.read_handle(),
+            self.earnings_store.read_handle(),
+            self.epoch_summary_store.read_handle(),
+            self.txn_receipt_store.read_handle(),
+            self.consensus_timeline_store.read_handle(),
         )
     }
 
@@ -129,6 +252,17 @@ impl VrrbDb {
             state_store,
             transaction_store,
             claim_store,
+            certification_store: CertificationStore::default(),
+            certified_txn_index: CertifiedTxnIndex::default(),
+            quorum_history_store: QuorumHistoryStore::default(),
+            checkpoint_store: CheckpointStore::default(),
+            halt_log_store: HaltLogStore::default(),
+            parameter_store: ParameterStore::default(),
+            earnings_store: EarningsStore::default(),
+            epoch_summary_store: EpochSummaryStore::default(),
+            txn_receipt_store: TxnReceiptStore::default(),
+            consensus_timeline_store: ConsensusTimelineStore::default(),
+            block_wal: BlockWal::default(),
         }
     }
 
@@ -137,6 +271,24 @@ impl VrrbDb {
         self.state_store.root_hash()
     }
 
+    /// Returns the root hash the state store trie would have once `updates`
+    /// were applied, leaving the state untouched.
+    pub fn projected_state_root_hash(&self, updates: Vec<UpdateArgs>) -> Result<RootHash> {
+        self.state_store.projected_root_hash(updates)
+    }
+
+    /// See [StateStore::projected_root_hash_with]
+    pub fn projected_state_root_hash_with<F>(
+        &self,
+        updates: Vec<UpdateArgs>,
+        finalize: F,
+    ) -> Result<RootHash>
+    where
+        F: FnOnce(&mut HashMap<Address, Account>),
+    {
+        self.state_store.projected_root_hash_with(updates, finalize)
+    }
+
     /// Returns the transaction store trie's root hash.
     pub fn transactions_root_hash(&self) -> Result<RootHash> {
         self.transaction_store.root_hash()
@@ -226,6 +378,114 @@ impl VrrbDb {
         self.claim_store.extend(claims)
     }
 
+    /// Records a validator's vote on a block, reporting whether it conflicts
+    /// with a vote the validator already cast on it.
+    pub fn record_certification_vote(
+        &mut self,
+        block_hash: BlockHash,
+        vote: CertificationVote,
+    ) -> Result<RecordOutcome> {
+        self.certification_store.record_vote(block_hash, vote)
+    }
+
+    /// Records a validator's partial signature over a block, reporting whether
+    /// it conflicts with one the validator already produced for it.
+    pub fn record_partial_signature(
+        &mut self,
+        block_hash: BlockHash,
+        partial_signature: PartialSignatureRecord,
+    ) -> Result<RecordOutcome> {
+        self.certification_store
+            .record_partial_signature(block_hash, partial_signature)
+    }
+
+    /// Records the certificate a block was certified with, along with the
+    /// quorums it inaugurates.
+    pub fn record_certificate(&mut self, certificate: Certificate) -> Result<()> {
+        if certificate.inauguration.is_some() {
+            self.quorum_history_store
+                .record_inauguration(&certificate)?;
+        }
+
+        self.certification_store.record_certificate(certificate)
+    }
+
+    /// Records the quorum formed at the start of an epoch.
+    pub fn record_quorum(&mut self, record: QuorumRecord) -> Result<()> {
+        self.quorum_history_store.record_quorum(record)
+    }
+
+    /// Records a checkpoint taken for external verifiers.
+    pub fn record_checkpoint(&mut self, checkpoint: &Checkpoint) -> Result<()> {
+        self.checkpoint_store.record_checkpoint(checkpoint)
+    }
+
+    /// Records a halt or resume directive an applied block carried.
+    pub fn record_halt_directive(&mut self, signed: &SignedHaltDirective) -> Result<()> {
+        self.halt_log_store.record_directive(signed)
+    }
+
+    /// Records a protocol parameter change included in a block.
+    pub fn record_parameter_change(&mut self, record: &ParameterChangeRecord) -> Result<()> {
+        self.parameter_store.record_change(record)
+    }
+
+    /// Records what a validator earned, and lost, from a block.
+    pub fn record_earnings(&mut self, record: &EarningsRecord) -> Result<()> {
+        self.earnings_store.record_earnings(record)
+    }
+
+    /// Records the summary of an epoch light clients sync from.
+    pub fn record_epoch_summary(&mut self, summary: &EpochSummary) -> Result<()> {
+        self.epoch_summary_store.record_epoch_summary(summary)
+    }
+
+    /// Indexes a transaction certified by a farmer quorum. Returns false if
+    /// the transaction was already certified, by the same or any other quorum.
+    pub fn record_certified_txn(&mut self, key: CertifiedTxnKey) -> Result<bool> {
+        self.certified_txn_index.insert(key)
+    }
+
+    /// Records when a transaction was admitted into the mempool.
+    pub fn record_txn_admission(
+        &mut self,
+        digest: TransactionDigest,
+        admitted_at: i64,
+    ) -> Result<()> {
+        self.txn_receipt_store.record_admission(digest, admitted_at)
+    }
+
+    /// Records how a farmer quorum certified a transaction.
+    pub fn record_txn_certification(
+        &mut self,
+        digest: TransactionDigest,
+        certification: TxnCertificationRecord,
+    ) -> Result<()> {
+        self.txn_receipt_store
+            .record_certification(digest, certification)
+    }
+
+    /// Records the proposal and convergence blocks a transaction was included
+    /// in.
+    pub fn record_txn_inclusion(
+        &mut self,
+        digest: TransactionDigest,
+        inclusion: TxnInclusionRecord,
+    ) -> Result<()> {
+        self.txn_receipt_store.record_inclusion(digest, inclusion)
+    }
+
+    /// Records when a round reached a consensus milestone, as a Unix
+    /// timestamp in milliseconds.
+    pub fn record_round_milestone(
+        &mut self,
+        round: Round,
+        milestone: RoundMilestone,
+        at: i64,
+    ) -> Result<()> {
+        self.consensus_timeline_store.record(round, milestone, at)
+    }
+
     /// Updates a calim in the current claim trie.
     pub fn update_claim(&mut self, _key: Address, _args: UpdateArgs) {
         todo!()
@@ -257,6 +517,57 @@ impl VrrbDb {
         Ok(())
     }
 
+    /// Applies an account owner's metadata update, bumping the account's nonce,
+    /// charging the update's fee and recording the txn among the ones it sent.
+    /// Updates must carry the account's next nonce, so they can't be replayed
+    /// or applied out of order
+    fn apply_account_update(
+        &mut self,
+        read_handle: VrrbDbReadHandle,
+        txn: AccountUpdate,
+    ) -> Result<()> {
+        if !txn.is_metadata_only() || txn.args.address != txn.sender_address {
+            return Err(StorageError::Other(format!(
+                "account update {} may only change the sender's metadata",
+                txn.id()
+            )));
+        }
+
+        let address = txn.sender_address();
+        let account = read_handle.get_account_by_address(&address)?;
+
+        let next_nonce = account.nonce() + 1;
+        if txn.nonce() != next_nonce {
+            return Err(StorageError::Other(format!(
+                "account update {} has nonce {} but the account's next nonce is {next_nonce}",
+                txn.id(),
+                txn.nonce()
+            )));
+        }
+
+        let mut digests = AccountDigests::default();
+        digests.insert_sent(txn.id());
+
+        // NOTE: the account rejects debits beyond its balance
+        let update = UpdateArgs {
+            address: address.clone(),
+            nonce: Some(txn.nonce()),
+            credits: None,
+            debits: Some(txn.fee()),
+            storage: txn.args.storage.clone(),
+            code: txn.args.code.clone(),
+            digests: Some(digests),
+        };
+
+        self.state_store.update_uncommited(address, update)?;
+        self.state_store.commit();
+
+        self.transaction_store
+            .insert(TransactionKind::UpdateAccount(txn))?;
+
+        Ok(())
+    }
+
     fn apply_txn(
         &mut self,
         read_handle: VrrbDbReadHandle,
@@ -264,6 +575,7 @@ impl VrrbDb {
     ) -> Result<()> {
         match txn_kind {
             TransactionKind::Transfer(txn) => self.apply_transfer(read_handle, txn),
+            TransactionKind::UpdateAccount(txn) => self.apply_account_update(read_handle, txn),
             _ => {
                 telemetry::info!("unsupported transaction type: {:?}", txn_kind);
                 Err(StorageError::Other(
@@ -274,14 +586,14 @@ impl VrrbDb {
     }
 
     /// Applies a block of transactions updating the account states accordingly.
+    ///
+    /// The application is logged ahead of its writes, so a block either lands
+    /// in full or, should any of its transactions fail, not at all.
     pub fn apply_block(&mut self, block: Block) -> Result<ApplyBlockResult> {
-        let read_handle = self.read_handle();
-
-        match block {
+        let (block_hash, txns): (_, Vec<TransactionKind>) = match block {
             Block::Genesis { block } => {
-                for (_, txn_kind) in block.txns {
-                    self.apply_txn(read_handle.clone(), txn_kind)?;
-                }
+                let txns = block.txns.into_iter().map(|(_, txn)| txn).collect();
+                (block.hash, txns)
             },
             Block::Convergence { .. } => {
                 todo!()
@@ -290,10 +602,26 @@ impl VrrbDb {
                 telemetry::info!("unsupported block type: {:?}", block);
                 return Err(StorageError::Other("unsupported block type".to_string()));
             },
+        };
+
+        let entry = BlockWalEntry {
+            block_hash,
+            pre_images: self.pre_images(&txns),
+            txns,
+        };
+
+        self.block_wal.begin(&entry)?;
+
+        if let Err(err) = self.apply_txns(entry.txns.clone()) {
+            self.roll_back(&entry);
+            self.block_wal.complete()?;
+
+            return Err(err);
         }
 
         self.transaction_store.commit();
         self.state_store.commit();
+        self.block_wal.complete()?;
 
         let state_root_hash = self.state_store.root_hash()?;
         let transactions_root_hash = self.transaction_store.root_hash()?;
@@ -305,6 +633,147 @@ impl VrrbDb {
             transactions_root_hash,
         })
     }
+
+    /// Recovers a block application a previous run died in the middle of.
+    ///
+    /// The partially applied block is rolled back to the accounts logged
+    /// before it and then applied again. Should it no longer apply, the stores
+    /// are left as they were before the block.
+    pub fn recover_block_application(&mut self) -> Result<BlockRecovery> {
+        let Some(entry) = self.block_wal.pending()? else {
+            return Ok(BlockRecovery::Clean);
+        };
+
+        let block_hash = entry.block_hash.clone();
+
+        self.roll_back(&entry);
+
+        let recovery = match self.apply_txns(entry.txns.clone()) {
+            Ok(()) => {
+                self.transaction_store.commit();
+                self.state_store.commit();
+
+                BlockRecovery::Replayed { block_hash }
+            },
+            Err(err) => {
+                self.roll_back(&entry);
+
+                BlockRecovery::RolledBack {
+                    block_hash,
+                    reason: err.to_string(),
+                }
+            },
+        };
+
+        self.block_wal.complete()?;
+
+        Ok(recovery)
+    }
+
+    /// Returns a scrubber walking the stores whose records can be checked
+    /// against what was recorded along with them
+    pub fn scrubber(&self) -> ChainScrubber {
+        ChainScrubber::new(
+            [
+                ScrubbedStore::Checkpoints,
+                ScrubbedStore::EpochSummaries,
+                ScrubbedStore::Certifications,
+                ScrubbedStore::TxnReceipts,
+                ScrubbedStore::StateTrie,
+                ScrubbedStore::TransactionTrie,
+                ScrubbedStore::ClaimTrie,
+            ]
+            .into_iter()
+            .map(|store| (store, self.scrubbed_backend(store)))
+            .collect(),
+        )
+    }
+
+    /// Deletes a record the scrubber found corrupt, so it can be recorded
+    /// again. Whatever the record held is lost
+    pub fn discard_corrupt_record(&mut self, record: &CorruptRecord) -> Result<()> {
+        match record.store {
+            ScrubbedStore::Certifications => self.certification_store.discard(&record.key),
+            store => self.scrubbed_backend(store).delete(&record.key),
+        }
+    }
+
+    /// Returns the raw record stored under `key`, for handing over to peers
+    /// whose copy is corrupt. Only stores peers share records of are served,
+    /// see [ScrubbedStore::is_shared_with_peers]
+    pub fn get_record(&self, store: ScrubbedStore, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        if !store.is_shared_with_peers() {
+            return Ok(None);
+        }
+
+        self.scrubbed_backend(store).get(key)
+    }
+
+    /// Replaces a record the scrubber found corrupt with a copy fetched from
+    /// peers. Returns whether the copy was taken, which it is only if it
+    /// hashes to what the record was committed to, see [CorruptRecord::accepts]
+    pub fn restore_corrupt_record(&mut self, record: &CorruptRecord, value: &[u8]) -> Result<bool> {
+        if !record.accepts(value) {
+            return Ok(false);
+        }
+
+        self.scrubbed_backend(record.store)
+            .put(&record.key, value)?;
+
+        Ok(true)
+    }
+
+    fn scrubbed_backend(&self, store: ScrubbedStore) -> SharedBackend {
+        match store {
+            ScrubbedStore::Checkpoints => self.checkpoint_store.backend(),
+            ScrubbedStore::EpochSummaries => self.epoch_summary_store.backend(),
+            ScrubbedStore::Certifications => self.certification_store.backend(),
+            ScrubbedStore::TxnReceipts => self.txn_receipt_store.backend(),
+            ScrubbedStore::StateTrie => self.state_store.node_backend(),
+            ScrubbedStore::TransactionTrie => self.transaction_store.node_backend(),
+            ScrubbedStore::ClaimTrie => self.claim_store.node_backend(),
+        }
+    }
+
+    fn apply_txns(&mut self, txns: Vec<TransactionKind>) -> Result<()> {
+        let read_handle = self.read_handle();
+
+        for txn_kind in txns {
+            self.apply_txn(read_handle.clone(), txn_kind)?;
+        }
+
+        Ok(())
+    }
+
+    /// Accounts `txns` touch as they currently are
+    fn pre_images(&self, txns: &[TransactionKind]) -> Vec<(Address, Option<Account>)> {
+        let read_handle = self.read_handle();
+        let mut pre_images: Vec<(Address, Option<Account>)> = vec![];
+
+        for txn in txns {
+            for address in [txn.sender_address(), txn.receiver_address()] {
+                if pre_images.iter().any(|(known, _)| *known == address) {
+                    continue;
+                }
+
+                let account = read_handle.get_account_by_address(&address).ok();
+                pre_images.push((address, account));
+            }
+        }
+
+        pre_images
+    }
+
+    /// Undoes whatever part of a logged block application made it to the
+    /// stores
+    fn roll_back(&mut self, entry: &BlockWalEntry) {
+        self.state_store.extend(entry.pre_images.clone());
+        self.state_store.commit();
+
+        self.transaction_store
+            .retract(entry.txns.iter().map(|txn| txn.digest()).collect());
+        self.transaction_store.commit();
+    }
 }
 
 impl Clone for VrrbDb {
@@ -313,6 +782,17 @@ impl Clone for VrrbDb {
             state_store: self.state_store.clone(),
             transaction_store: self.transaction_store.clone(),
             claim_store: self.claim_store.clone(),
+            certification_store: self.certification_store.clone(),
+            certified_txn_index: self.certified_txn_index.clone(),
+            quorum_history_store: self.quorum_history_store.clone(),
+            checkpoint_store: self.checkpoint_store.clone(),
+            halt_log_store: self.halt_log_store.clone(),
+            parameter_store: self.parameter_store.clone(),
+            earnings_store: self.earnings_store.clone(),
+            epoch_summary_store: self.epoch_summary_store.clone(),
+            txn_receipt_store: self.txn_receipt_store.clone(),
+            consensus_timeline_store: self.consensus_timeline_store.clone(),
+            block_wal: self.block_wal.clone(),
         }
     }
 }
@@ -1,13 +1,20 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::RangeInclusive};
 
-use primitives::{Address, NodeId};
+use block::{BlockHash, Checkpoint, EpochSummary, SignedHaltDirective};
+use ethereum_types::U256;
+use primitives::{Address, ChainSpec, Epoch, NodeId, Round};
 use storage_utils::StorageError;
-use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind};
+use vrrb_core::transactions::{Token, Transaction, TransactionDigest, TransactionKind};
 use vrrb_core::{account::Account, claim::Claim};
 
 use crate::result::Result;
 use crate::{
-    ClaimStoreReadHandleFactory, StateStoreReadHandleFactory, TransactionStoreReadHandleFactory,
+    BlockCertification, CertificationStoreReadHandle, CertifiedTxnIndexReadHandle, CertifiedTxnKey,
+    CheckpointStoreReadHandle, ClaimStoreReadHandleFactory, ConsensusTimelineStoreReadHandle,
+    EarningsStoreReadHandle, EpochSummaryStoreReadHandle, HaltLogStoreReadHandle,
+    ParameterChangeRecord, ParameterStoreReadHandle, QuorumHistoryStoreReadHandle, QuorumRecord,
+    RoundStateRoot, RoundTimeline, StatePage, StateStoreReadHandleFactory,
+    TransactionStoreReadHandleFactory, TxnReceipt, TxnReceiptStoreReadHandle, ValidatorEarnings,
 };
 
 #[derive(Debug, Clone)]
@@ -15,18 +22,49 @@ pub struct VrrbDbReadHandle {
     state_store_handle_factory: StateStoreReadHandleFactory,
     transaction_store_handle_factory: TransactionStoreReadHandleFactory,
     claim_store_handle_factory: ClaimStoreReadHandleFactory,
+    certification_store_handle: CertificationStoreReadHandle,
+    certified_txn_index_handle: CertifiedTxnIndexReadHandle,
+    quorum_history_store_handle: QuorumHistoryStoreReadHandle,
+    checkpoint_store_handle: CheckpointStoreReadHandle,
+    halt_log_store_handle: HaltLogStoreReadHandle,
+    parameter_store_handle: ParameterStoreReadHandle,
+    earnings_store_handle: EarningsStoreReadHandle,
+    epoch_summary_store_handle: EpochSummaryStoreReadHandle,
+    txn_receipt_store_handle: TxnReceiptStoreReadHandle,
+    consensus_timeline_store_handle: ConsensusTimelineStoreReadHandle,
 }
 
 impl VrrbDbReadHandle {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         state_store_handle_factory: StateStoreReadHandleFactory,
         transaction_store_handle_factory: TransactionStoreReadHandleFactory,
         claim_store_handle_factory: ClaimStoreReadHandleFactory,
+        certification_store_handle: CertificationStoreReadHandle,
+        certified_txn_index_handle: CertifiedTxnIndexReadHandle,
+        quorum_history_store_handle: QuorumHistoryStoreReadHandle,
+        checkpoint_store_handle: CheckpointStoreReadHandle,
+        halt_log_store_handle: HaltLogStoreReadHandle,
+        parameter_store_handle: ParameterStoreReadHandle,
+        earnings_store_handle: EarningsStoreReadHandle,
+        epoch_summary_store_handle: EpochSummaryStoreReadHandle,
+        txn_receipt_store_handle: TxnReceiptStoreReadHandle,
+        consensus_timeline_store_handle: ConsensusTimelineStoreReadHandle,
     ) -> Self {
         Self {
             state_store_handle_factory,
             transaction_store_handle_factory,
             claim_store_handle_factory,
+            certification_store_handle,
+            certified_txn_index_handle,
+            quorum_history_store_handle,
+            checkpoint_store_handle,
+            halt_log_store_handle,
+            parameter_store_handle,
+            earnings_store_handle,
+            epoch_summary_store_handle,
+            txn_receipt_store_handle,
+            consensus_timeline_store_handle,
         }
     }
 
@@ -36,18 +74,102 @@ impl VrrbDbReadHandle {
         self.state_store_handle_factory.handle().entries()
     }
 
+    /// Returns up to `limit` accounts whose address starts with `prefix`,
+    /// resuming after the `start_after` cursor of a previous page
+    pub fn state_store_page(
+        &self,
+        prefix: Option<&str>,
+        start_after: Option<&Address>,
+        limit: usize,
+    ) -> Result<StatePage> {
+        self.state_store_handle_factory
+            .handle()
+            .scan(prefix, start_after, limit)
+    }
+
     // TODO: rewrite these to get start at the first key available and the latest version
     /// Returns a copy of all values stored within the state trie
     pub fn transaction_store_values(&self) -> HashMap<TransactionDigest, TransactionKind> {
         self.transaction_store_handle_factory.handle().entries()
     }
 
+    /// Returns whether a block applied so far included the transaction with
+    /// digest `digest`
+    pub fn contains_transaction(&self, digest: &TransactionDigest) -> bool {
+        self.transaction_store_handle_factory
+            .handle()
+            .contains(digest)
+    }
+
     // TODO: rewrite these to get start at the first key available and the latest version
     /// Returns a copy of all values stored within the state trie
     pub fn claim_store_values(&self) -> HashMap<NodeId, Claim> {
         self.claim_store_handle_factory.handle().entries()
     }
 
+    /// Returns the claim of `node_id`, looked up through the claim index
+    /// instead of copying the whole claim store
+    pub fn get_claim(&self, node_id: &NodeId) -> Result<Claim> {
+        self.claim_store_handle_factory
+            .handle()
+            .get_by_node_id(node_id)
+    }
+
+    /// Returns the claims holding rewards or undelegated stake to pay out,
+    /// looked up through the claim index
+    pub fn claims_with_pending_payouts(&self) -> Vec<Claim> {
+        self.claim_store_handle_factory
+            .handle()
+            .claims_with_pending_payouts()
+    }
+
+    /// Returns the root the delegation state of every claim commits to once
+    /// `changed` are stored, served from the in-memory claim index
+    pub fn delegation_root(&self, changed: &[Claim]) -> String {
+        self.claim_store_handle_factory
+            .handle()
+            .delegation_root(changed)
+    }
+
+    /// Returns the miner-eligible claim that wins the election for the given
+    /// seed, served from the in-memory claim index.
+    pub fn miner_election_winner(&self, seed: u64) -> Option<(U256, Claim)> {
+        self.claim_store_handle_factory
+            .handle()
+            .election_winner(seed)
+    }
+
+    /// Returns up to `limit` miner-eligible claims ordered by their weighted
+    /// election result for the given seed, served from the in-memory claim
+    /// index.
+    pub fn miner_election_results(&self, seed: u64, limit: usize) -> Vec<(U256, Claim)> {
+        self.claim_store_handle_factory
+            .handle()
+            .election_results(seed, limit)
+    }
+
+    /// Returns the claims with the lowest weighted quorum election results
+    /// for the given seed, as many as `seats` yields for the number of claims
+    /// eligible, along with that number. Served from the in-memory claim
+    /// index.
+    pub fn quorum_election_results(
+        &self,
+        seed: u64,
+        seats: impl FnOnce(usize) -> usize,
+    ) -> (Vec<(U256, Claim)>, usize) {
+        self.claim_store_handle_factory
+            .handle()
+            .quorum_election_results(seed, seats)
+    }
+
+    /// Ranks the elections for `seed` ahead of time. Meant to be called as
+    /// soon as the seed is known, away from the consensus path
+    pub fn prepare_elections(&self, seed: u64) {
+        self.claim_store_handle_factory
+            .handle()
+            .prepare_elections(seed)
+    }
+
     pub fn get_account_by_address(&self, address: &Address) -> Result<Account> {
         self.state_store_handle_factory
             .handle()
@@ -56,4 +178,162 @@ impl VrrbDbReadHandle {
                 StorageError::Other(format!("Failed to get account by address: {:?}", err))
             })
     }
+
+    /// Returns how much of `token` the account stored under `address` holds
+    pub fn get_balance(&self, address: &Address, token: &Token) -> Result<u128> {
+        self.state_store_handle_factory
+            .handle()
+            .get_balance(address, token)
+    }
+
+    /// Returns the account stored under `address` as it was at the end of
+    /// `round`, as long as that round hasn't been pruned.
+    pub fn get_account_at_round(&self, address: &Address, round: Round) -> Result<Account> {
+        self.state_store_handle_factory
+            .handle()
+            .get_account_at_round(address, round)
+    }
+
+    /// Returns the state root committed at the end of `round`, as long as that
+    /// round hasn't been pruned.
+    pub fn get_state_root(&self, round: Round) -> Result<RoundStateRoot> {
+        self.state_store_handle_factory
+            .handle()
+            .get_state_root(round)
+    }
+
+    /// Returns the most recently committed round along with its state root
+    pub fn latest_state_root(&self) -> Option<(Round, RoundStateRoot)> {
+        self.state_store_handle_factory.handle().latest_state_root()
+    }
+
+    /// Returns a copy of every account as of the end of `round`, as long as
+    /// that round hasn't been pruned.
+    pub fn state_store_values_at_round(&self, round: Round) -> Result<Vec<Account>> {
+        self.state_store_handle_factory
+            .handle()
+            .entries_at_round(round)
+    }
+
+    /// Returns the votes, partial signatures and certificate recorded for a
+    /// block, as long as they haven't been pruned.
+    pub fn get_block_certification(&self, block_hash: &BlockHash) -> Result<BlockCertification> {
+        self.certification_store_handle.get(block_hash)
+    }
+
+    /// Returns the certification records of every retained block, oldest
+    /// first
+    pub fn block_certifications(&self) -> Vec<BlockCertification> {
+        self.certification_store_handle.entries()
+    }
+
+    /// Returns a handle to the index of certified transactions
+    pub fn certified_txn_index(&self) -> CertifiedTxnIndexReadHandle {
+        self.certified_txn_index_handle.clone()
+    }
+
+    /// Returns the key a transaction was certified under, if any quorum
+    /// certified it
+    pub fn get_certified_txn_key(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Result<Option<CertifiedTxnKey>> {
+        self.certified_txn_index_handle.get(digest)
+    }
+
+    /// Returns what this node recorded of a transaction's way from the mempool
+    /// to a convergence block, if it witnessed any of it
+    pub fn get_txn_receipt(&self, digest: &TransactionDigest) -> Result<Option<TxnReceipt>> {
+        self.txn_receipt_store_handle.get(digest)
+    }
+
+    /// Returns the quorum in effect at `epoch`, as far as this node knows
+    pub fn get_quorum(&self, epoch: Epoch) -> Result<QuorumRecord> {
+        self.quorum_history_store_handle.get(epoch)
+    }
+
+    /// Returns the most recently formed quorum this node belongs to
+    pub fn current_quorum(&self) -> Option<QuorumRecord> {
+        self.quorum_history_store_handle.latest()
+    }
+
+    /// Returns the checkpoint taken at `round`
+    pub fn get_checkpoint(&self, round: Round) -> Result<Checkpoint> {
+        self.checkpoint_store_handle.get(round)
+    }
+
+    /// Returns the most recently taken checkpoint
+    pub fn latest_checkpoint(&self) -> Option<Checkpoint> {
+        self.checkpoint_store_handle.latest()
+    }
+
+    /// Returns every checkpoint this node took, oldest first
+    pub fn checkpoints(&self) -> Vec<Checkpoint> {
+        self.checkpoint_store_handle.entries()
+    }
+
+    /// Returns the halt or resume directive the node most recently acted on
+    pub fn latest_halt_directive(&self) -> Option<SignedHaltDirective> {
+        self.halt_log_store_handle.latest()
+    }
+
+    /// Returns every halt and resume directive the node acted on, oldest
+    /// first
+    pub fn halt_directives(&self) -> Vec<SignedHaltDirective> {
+        self.halt_log_store_handle.entries()
+    }
+
+    /// Returns every protocol parameter change included in a block, in the
+    /// order they take effect
+    pub fn parameter_changes(&self) -> Vec<ParameterChangeRecord> {
+        self.parameter_store_handle.entries()
+    }
+
+    /// Returns `base` with every protocol parameter change in effect at
+    /// `epoch` applied
+    pub fn chain_spec_at(&self, base: &ChainSpec, epoch: Epoch) -> ChainSpec {
+        self.parameter_store_handle.chain_spec_at(base, epoch)
+    }
+
+    /// Returns what `node_id` earned from block rewards and fee shares, and
+    /// lost to slashing, during each of `epochs` it has records for
+    pub fn validator_earnings(
+        &self,
+        node_id: &NodeId,
+        epochs: RangeInclusive<Epoch>,
+    ) -> Vec<ValidatorEarnings> {
+        self.earnings_store_handle
+            .validator_earnings(node_id, epochs)
+    }
+
+    /// Returns what each validator earned, and lost, during `epoch`
+    pub fn epoch_earnings(&self, epoch: Epoch) -> Vec<ValidatorEarnings> {
+        self.earnings_store_handle.epoch_earnings(epoch)
+    }
+
+    /// Returns the summary of `epoch`
+    pub fn get_epoch_summary(&self, epoch: Epoch) -> Result<EpochSummary> {
+        self.epoch_summary_store_handle.get(epoch)
+    }
+
+    /// Returns the summary of the latest epoch summarized
+    pub fn latest_epoch_summary(&self) -> Option<EpochSummary> {
+        self.epoch_summary_store_handle.latest()
+    }
+
+    /// Returns every epoch summary this node produced, oldest first
+    pub fn epoch_summaries(&self) -> Vec<EpochSummary> {
+        self.epoch_summary_store_handle.entries()
+    }
+
+    /// Returns when a round went through each step of consensus, as far as
+    /// this node witnessed it
+    pub fn round_timeline(&self, round: Round) -> Result<Option<RoundTimeline>> {
+        self.consensus_timeline_store_handle.get(round)
+    }
+
+    /// Returns the consensus timelines of every retained round, oldest first
+    pub fn consensus_timeline(&self) -> Vec<RoundTimeline> {
+        self.consensus_timeline_store_handle.entries()
+    }
 }
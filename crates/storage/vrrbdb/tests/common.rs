@@ -45,6 +45,7 @@ pub fn _generate_random_transaction(
         signature,
         validators: None,
         nonce: 10,
+        memo: None,
     }))
 }
 
@@ -68,6 +69,7 @@ pub fn _generate_random_valid_transaction() -> TransactionKind {
         signature,
         validators: None,
         nonce: 10,
+        memo: None,
     }))
 }
 
@@ -0,0 +1,216 @@
+//! Behaviour every [vrrbdb::KeyValueBackend] must share. Each check is written
+//! once against a [DbBackend] and run for every backend compiled in.
+
+use std::env;
+
+use primitives::{CertificationRetention, DbBackend};
+use vrrb_core::account::Account;
+use vrrbdb::{open_backend, CertificationVote, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{_generate_random_address, _generate_random_string, _generate_random_valid_transaction};
+use serial_test::serial;
+
+fn stores_overwrites_and_deletes_values(backend: DbBackend) {
+    let path = env::temp_dir().join(_generate_random_string());
+    let store = open_backend(backend, path, "conformance").unwrap();
+
+    assert_eq!(store.get(b"missing").unwrap(), None);
+
+    store.put(b"key", b"first").unwrap();
+    assert_eq!(store.get(b"key").unwrap(), Some(b"first".to_vec()));
+
+    store.put(b"key", b"second").unwrap();
+    assert_eq!(store.get(b"key").unwrap(), Some(b"second".to_vec()));
+
+    store.delete(b"key").unwrap();
+    assert_eq!(store.get(b"key").unwrap(), None);
+
+    store.delete(b"key").unwrap();
+}
+
+fn lists_entries_in_key_order(backend: DbBackend) {
+    let path = env::temp_dir().join(_generate_random_string());
+    let store = open_backend(backend, path, "conformance").unwrap();
+
+    for key in [b"c", b"a", b"b"] {
+        store.put(key, key).unwrap();
+    }
+
+    assert_eq!(
+        store.entries().unwrap(),
+        vec![
+            (b"a".to_vec(), b"a".to_vec()),
+            (b"b".to_vec(), b"b".to_vec()),
+            (b"c".to_vec(), b"c".to_vec()),
+        ]
+    );
+}
+
+fn pages_through_entries_in_key_order(backend: DbBackend) {
+    let path = env::temp_dir().join(_generate_random_string());
+    let store = open_backend(backend, path, "conformance").unwrap();
+
+    for key in [b"d", b"a", b"c", b"b"] {
+        store.put(key, key).unwrap();
+    }
+
+    let first_page = store.entries_after(None, 2).unwrap();
+    assert_eq!(
+        first_page,
+        vec![
+            (b"a".to_vec(), b"a".to_vec()),
+            (b"b".to_vec(), b"b".to_vec())
+        ]
+    );
+
+    let last_key = first_page.last().map(|(key, _)| key.as_slice());
+    assert_eq!(
+        store.entries_after(last_key, 5).unwrap(),
+        vec![
+            (b"c".to_vec(), b"c".to_vec()),
+            (b"d".to_vec(), b"d".to_vec())
+        ]
+    );
+
+    // NOTE: the key paged after doesn't have to be stored
+    assert_eq!(
+        store.entries_after(Some(b"bb".as_slice()), 1).unwrap(),
+        vec![(b"c".to_vec(), b"c".to_vec())]
+    );
+    assert!(store
+        .entries_after(Some(b"d".as_slice()), 5)
+        .unwrap()
+        .is_empty());
+}
+
+fn persists_values_across_reopens(backend: DbBackend) {
+    let path = env::temp_dir().join(_generate_random_string());
+
+    let store = open_backend(backend, path.clone(), "conformance").unwrap();
+    store.put(b"key", b"value").unwrap();
+    drop(store);
+
+    let store = open_backend(backend, path, "conformance").unwrap();
+    assert_eq!(store.get(b"key").unwrap(), Some(b"value".to_vec()));
+}
+
+fn stores_accounts_transactions_and_certifications(backend: DbBackend) {
+    let config = VrrbDbConfig::default()
+        .with_path(env::temp_dir().join(_generate_random_string()))
+        .with_backend(backend)
+        .with_certification_retention(CertificationRetention::KeepRecent(1));
+
+    let mut db = VrrbDb::new(config.clone());
+
+    let (_, addr) = _generate_random_address();
+    db.insert_account(addr.clone(), Account::new(addr.public_key()))
+        .unwrap();
+
+    db.extend_transactions_unchecked(vec![
+        _generate_random_valid_transaction(),
+        _generate_random_valid_transaction(),
+    ]);
+
+    for block_hash in ["block-1", "block-2"] {
+        db.record_certification_vote(
+            block_hash.to_string(),
+            CertificationVote {
+                voter: "node-1".to_string(),
+                approved: true,
+                signature: vec![1; 64],
+            },
+        )
+        .unwrap();
+    }
+
+    let read_handle = db.read_handle();
+
+    assert!(read_handle.state_store_values().contains_key(&addr));
+    assert_eq!(db.transaction_store_factory().handle().entries().len(), 2);
+    assert!(read_handle
+        .get_block_certification(&"block-1".to_string())
+        .is_err());
+
+    drop(read_handle);
+    drop(db);
+
+    let reopened = VrrbDb::new(config);
+
+    let certification = reopened
+        .read_handle()
+        .get_block_certification(&"block-2".to_string())
+        .unwrap();
+
+    assert_eq!(certification.votes.len(), 1);
+}
+
+#[cfg(feature = "rocksdb-backend")]
+mod rocksdb_backend {
+    use super::*;
+
+    #[test]
+    #[serial]
+    fn stores_overwrites_and_deletes_values() {
+        super::stores_overwrites_and_deletes_values(DbBackend::RocksDb);
+    }
+
+    #[test]
+    #[serial]
+    fn lists_entries_in_key_order() {
+        super::lists_entries_in_key_order(DbBackend::RocksDb);
+    }
+
+    #[test]
+    #[serial]
+    fn pages_through_entries_in_key_order() {
+        super::pages_through_entries_in_key_order(DbBackend::RocksDb);
+    }
+
+    #[test]
+    #[serial]
+    fn persists_values_across_reopens() {
+        super::persists_values_across_reopens(DbBackend::RocksDb);
+    }
+
+    #[test]
+    #[serial]
+    fn stores_accounts_transactions_and_certifications() {
+        super::stores_accounts_transactions_and_certifications(DbBackend::RocksDb);
+    }
+}
+
+#[cfg(feature = "sled-backend")]
+mod sled_backend {
+    use super::*;
+
+    #[test]
+    #[serial]
+    fn stores_overwrites_and_deletes_values() {
+        super::stores_overwrites_and_deletes_values(DbBackend::Sled);
+    }
+
+    #[test]
+    #[serial]
+    fn lists_entries_in_key_order() {
+        super::lists_entries_in_key_order(DbBackend::Sled);
+    }
+
+    #[test]
+    #[serial]
+    fn pages_through_entries_in_key_order() {
+        super::pages_through_entries_in_key_order(DbBackend::Sled);
+    }
+
+    #[test]
+    #[serial]
+    fn persists_values_across_reopens() {
+        super::persists_values_across_reopens(DbBackend::Sled);
+    }
+
+    #[test]
+    #[serial]
+    fn stores_accounts_transactions_and_certifications() {
+        super::stores_accounts_transactions_and_certifications(DbBackend::Sled);
+    }
+}
@@ -0,0 +1,175 @@
+use std::env;
+
+use primitives::Address;
+use vrrb_core::account::{Account, UpdateArgs};
+use vrrb_core::transactions::Transaction;
+use vrrbdb::{BlockRecovery, BlockWal, BlockWalEntry, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{_generate_random_address, _generate_random_string, _generate_random_transaction};
+use serial_test::serial;
+
+fn credit(db: &mut VrrbDb, address: &Address, credits: u128) {
+    db.update_account(UpdateArgs {
+        address: address.clone(),
+        nonce: None,
+        credits: Some(credits),
+        debits: None,
+        storage: None,
+        code: None,
+        digests: None,
+    })
+    .unwrap();
+}
+
+/// Writes what a node dying halfway through a transfer of 100 leaves behind:
+/// the sender already debited, the receiver not yet credited.
+fn debit_partially(db: &mut VrrbDb, sender: &Address) {
+    db.update_account(UpdateArgs {
+        address: sender.clone(),
+        nonce: Some(10),
+        credits: None,
+        debits: Some(100),
+        storage: None,
+        code: None,
+        digests: None,
+    })
+    .unwrap();
+}
+
+#[test]
+#[serial]
+fn interrupted_block_applications_are_replayed_on_startup() {
+    let config = VrrbDbConfig::default().with_path(env::temp_dir().join(_generate_random_string()));
+
+    let (secret_key, sender) = _generate_random_address();
+    let (_, receiver) = _generate_random_address();
+
+    let mut db = VrrbDb::new(config.clone());
+    db.insert_account(sender.clone(), Account::new(sender.public_key()))
+        .unwrap();
+    db.insert_account(receiver.clone(), Account::new(receiver.public_key()))
+        .unwrap();
+    credit(&mut db, &sender, 1000);
+
+    let read_handle = db.read_handle();
+    let pre_images = vec![
+        (
+            sender.clone(),
+            read_handle.get_account_by_address(&sender).ok(),
+        ),
+        (
+            receiver.clone(),
+            read_handle.get_account_by_address(&receiver).ok(),
+        ),
+    ];
+    drop(read_handle);
+
+    debit_partially(&mut db, &sender);
+    drop(db);
+
+    let txn = _generate_random_transaction(secret_key, sender.clone(), receiver.clone());
+    let mut block_wal = BlockWal::new(&config.path, config.backend).unwrap();
+    block_wal
+        .begin(&BlockWalEntry {
+            block_hash: "block-1".to_string(),
+            pre_images,
+            txns: vec![txn.clone()],
+        })
+        .unwrap();
+    drop(block_wal);
+
+    let mut db = VrrbDb::new(config);
+
+    assert_eq!(
+        db.recover_block_application().unwrap(),
+        BlockRecovery::Replayed {
+            block_hash: "block-1".to_string()
+        }
+    );
+
+    let read_handle = db.read_handle();
+
+    // NOTE: the sender is debited once, not once per application
+    assert_eq!(
+        read_handle
+            .get_account_by_address(&sender)
+            .unwrap()
+            .debits(),
+        100
+    );
+    assert_eq!(
+        read_handle
+            .get_account_by_address(&receiver)
+            .unwrap()
+            .credits(),
+        100
+    );
+    assert!(read_handle
+        .transaction_store_values()
+        .contains_key(&txn.digest()));
+
+    assert_eq!(
+        db.recover_block_application().unwrap(),
+        BlockRecovery::Clean
+    );
+}
+
+#[test]
+#[serial]
+fn interrupted_block_applications_that_cant_be_replayed_are_rolled_back() {
+    let config = VrrbDbConfig::default().with_path(env::temp_dir().join(_generate_random_string()));
+
+    let (secret_key, sender) = _generate_random_address();
+    let (_, receiver) = _generate_random_address();
+
+    let mut db = VrrbDb::new(config.clone());
+    db.insert_account(sender.clone(), Account::new(sender.public_key()))
+        .unwrap();
+    credit(&mut db, &sender, 1000);
+
+    let pre_images = vec![
+        (
+            sender.clone(),
+            db.read_handle().get_account_by_address(&sender).ok(),
+        ),
+        (receiver.clone(), None),
+    ];
+
+    debit_partially(&mut db, &sender);
+    drop(db);
+
+    // NOTE: the receiver doesn't exist, so the transfer can't be applied again
+    let txn = _generate_random_transaction(secret_key, sender.clone(), receiver.clone());
+    let mut block_wal = BlockWal::new(&config.path, config.backend).unwrap();
+    block_wal
+        .begin(&BlockWalEntry {
+            block_hash: "block-1".to_string(),
+            pre_images,
+            txns: vec![txn],
+        })
+        .unwrap();
+    drop(block_wal);
+
+    let mut db = VrrbDb::new(config);
+
+    assert!(matches!(
+        db.recover_block_application().unwrap(),
+        BlockRecovery::RolledBack { block_hash, .. } if block_hash == "block-1"
+    ));
+
+    let read_handle = db.read_handle();
+    assert_eq!(
+        read_handle
+            .get_account_by_address(&sender)
+            .unwrap()
+            .debits(),
+        0
+    );
+    assert!(read_handle.get_account_by_address(&receiver).is_err());
+
+    assert_eq!(
+        db.recover_block_application().unwrap(),
+        BlockRecovery::Clean
+    );
+}
@@ -0,0 +1,164 @@
+use std::env;
+
+use block::Certificate;
+use primitives::CertificationRetention;
+use vrrbdb::{CertificationVote, PartialSignatureRecord, RecordOutcome, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::_generate_random_string;
+use serial_test::serial;
+
+fn partial_signature(signer: &str) -> PartialSignatureRecord {
+    PartialSignatureRecord {
+        signer: signer.to_string(),
+        public_key_share: vec![1; 48],
+        signature: vec![2; 96],
+    }
+}
+
+fn certificate(block_hash: &str) -> Certificate {
+    Certificate {
+        signature: hex::encode([3; 96]),
+        inauguration: None,
+        root_hash: "".to_string(),
+        next_root_hash: "".to_string(),
+        block_hash: block_hash.to_string(),
+    }
+}
+
+#[test]
+#[serial]
+fn block_certifications_are_persisted_and_pruned() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+
+    let config = VrrbDbConfig::default()
+        .with_path(db_path)
+        .with_certification_retention(CertificationRetention::KeepRecent(2));
+
+    let mut db = VrrbDb::new(config.clone());
+
+    for block_hash in ["block-1", "block-2", "block-3"] {
+        db.record_certification_vote(
+            block_hash.to_string(),
+            CertificationVote {
+                voter: "node-1".to_string(),
+                approved: true,
+                signature: vec![4; 64],
+            },
+        )
+        .unwrap();
+
+        db.record_partial_signature(block_hash.to_string(), partial_signature("node-1"))
+            .unwrap();
+        db.record_partial_signature(block_hash.to_string(), partial_signature("node-2"))
+            .unwrap();
+
+        assert_eq!(
+            db.record_partial_signature(block_hash.to_string(), partial_signature("node-1"))
+                .unwrap(),
+            RecordOutcome::Duplicate
+        );
+    }
+
+    db.record_certificate(certificate("block-3")).unwrap();
+
+    let read_handle = db.read_handle();
+
+    assert!(read_handle
+        .get_block_certification(&"block-1".to_string())
+        .is_err());
+
+    let pending = read_handle
+        .get_block_certification(&"block-2".to_string())
+        .unwrap();
+    assert!(!pending.is_certified());
+
+    let certified = read_handle
+        .get_block_certification(&"block-3".to_string())
+        .unwrap();
+    assert!(certified.is_certified());
+    assert_eq!(certified.votes.len(), 1);
+    assert_eq!(certified.partial_signatures.len(), 2);
+    assert_eq!(
+        certified.contributors().into_iter().collect::<Vec<_>>(),
+        vec!["node-1".to_string(), "node-2".to_string()]
+    );
+
+    drop(read_handle);
+    drop(db);
+
+    let reopened = VrrbDb::new(config);
+
+    assert_eq!(
+        reopened
+            .read_handle()
+            .get_block_certification(&"block-3".to_string())
+            .unwrap(),
+        certified
+    );
+}
+
+#[test]
+#[serial]
+fn conflicting_votes_and_partial_signatures_are_kept_and_flagged() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(db_path));
+
+    let block_hash = "block-1".to_string();
+    let vote = |approved| CertificationVote {
+        voter: "node-1".to_string(),
+        approved,
+        signature: vec![4; 64],
+    };
+
+    assert_eq!(
+        db.record_certification_vote(block_hash.clone(), vote(true))
+            .unwrap(),
+        RecordOutcome::Recorded
+    );
+    assert_eq!(
+        db.record_certification_vote(block_hash.clone(), vote(false))
+            .unwrap(),
+        RecordOutcome::Conflicting
+    );
+
+    db.record_partial_signature(block_hash.clone(), partial_signature("node-1"))
+        .unwrap();
+    db.record_partial_signature(block_hash.clone(), partial_signature("node-2"))
+        .unwrap();
+
+    assert_eq!(
+        db.record_partial_signature(
+            block_hash.clone(),
+            PartialSignatureRecord {
+                signature: vec![5; 96],
+                ..partial_signature("node-2")
+            },
+        )
+        .unwrap(),
+        RecordOutcome::Conflicting
+    );
+
+    let certification = db
+        .read_handle()
+        .get_block_certification(&block_hash)
+        .unwrap();
+
+    assert_eq!(certification.votes.len(), 2);
+    assert_eq!(certification.partial_signatures.len(), 3);
+    assert_eq!(
+        certification
+            .conflicting_voters()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec!["node-1".to_string()]
+    );
+    assert_eq!(
+        certification
+            .conflicting_signers()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec!["node-2".to_string()]
+    );
+    assert_eq!(certification.contributors().len(), 2);
+}
@@ -0,0 +1,49 @@
+use std::env;
+
+use vrrb_core::transactions::TransactionDigest;
+use vrrbdb::{CertifiedTxnKey, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::_generate_random_string;
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn txns_are_only_certified_once_across_quorums_and_restarts() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default().with_path(db_path);
+
+    let mut db = VrrbDb::new(config.clone());
+
+    let digest = TransactionDigest::from(vec![7; 32]);
+    let certified = CertifiedTxnKey::new(digest.clone(), vec![1; 48]);
+    let rotated = CertifiedTxnKey::new(digest.clone(), vec![2; 48]);
+
+    assert!(!db.read_handle().certified_txn_index().is_certified(&digest));
+
+    assert!(db.record_certified_txn(certified.clone()).unwrap());
+    assert!(!db.record_certified_txn(certified.clone()).unwrap());
+
+    // NOTE: a quorum formed after a rotation cannot certify the txn again
+    assert!(!db.record_certified_txn(rotated.clone()).unwrap());
+
+    let index = db.read_handle().certified_txn_index();
+    assert!(index.is_certified(&digest));
+    assert!(index.contains(&certified));
+    assert!(!index.contains(&rotated));
+    assert!(!index.is_certified(&TransactionDigest::from(vec![8; 32])));
+
+    drop(index);
+    drop(db);
+
+    let mut reopened = VrrbDb::new(config);
+
+    assert_eq!(
+        reopened
+            .read_handle()
+            .get_certified_txn_key(&digest)
+            .unwrap(),
+        Some(certified)
+    );
+    assert!(!reopened.record_certified_txn(rotated).unwrap());
+}
@@ -0,0 +1,77 @@
+use std::{collections::BTreeMap, env};
+
+use block::{header::BlockHeader, Certificate, Checkpoint};
+use primitives::{HeaderFormatParams, QuorumPubkey, Round, ValidatorSecretKey};
+use reward::schedule::RewardSchedule;
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{_generate_random_address, _generate_random_claim, _generate_random_string};
+use serial_test::serial;
+
+fn checkpoint(round: Round) -> Checkpoint {
+    let (secret_key, _) = _generate_random_address();
+
+    let header = BlockHeader::genesis(
+        0,
+        round,
+        0,
+        _generate_random_claim(),
+        secret_key,
+        String::new(),
+        &RewardSchedule::default(),
+        &HeaderFormatParams::default(),
+    );
+
+    let certificate = Certificate {
+        signature: hex::encode([3; 96]),
+        inauguration: None,
+        root_hash: "".to_string(),
+        next_root_hash: "".to_string(),
+        block_hash: header.hash(),
+    };
+
+    Checkpoint::new(
+        header,
+        &certificate,
+        QuorumPubkey::from(ValidatorSecretKey::random().public_key()),
+        &BTreeMap::new(),
+    )
+}
+
+#[test]
+#[serial]
+fn checkpoints_are_kept_in_round_order_across_restarts() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default().with_path(db_path);
+
+    let mut db = VrrbDb::new(config.clone());
+
+    // NOTE: rounds past 255 make sure keys sort by round rather than by their
+    // lowest byte
+    let checkpoints = vec![checkpoint(100), checkpoint(300), checkpoint(200)];
+
+    for checkpoint in checkpoints.iter() {
+        db.record_checkpoint(checkpoint).unwrap();
+    }
+
+    drop(db);
+
+    let read_handle = VrrbDb::new(config).read_handle();
+
+    assert_eq!(read_handle.get_checkpoint(200).unwrap(), checkpoints[2]);
+    assert!(read_handle.get_checkpoint(250).is_err());
+    assert_eq!(read_handle.latest_checkpoint().unwrap(), checkpoints[1]);
+    assert_eq!(
+        read_handle
+            .checkpoints()
+            .into_iter()
+            .map(|checkpoint| checkpoint.round)
+            .collect::<Vec<Round>>(),
+        vec![100, 200, 300]
+    );
+    assert!(read_handle
+        .checkpoints()
+        .iter()
+        .all(Checkpoint::matches_header));
+}
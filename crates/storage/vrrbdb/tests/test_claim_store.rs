@@ -1,11 +1,15 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+};
 
-use primitives::NodeId;
-use vrrb_core::claim::Claim;
+use ethereum_types::U256;
+use primitives::{NodeId, DEFAULT_RETAINED_TIMELINE_ROUNDS};
+use vrrb_core::claim::{Claim, Eligibility};
 use vrrbdb::{VrrbDb, VrrbDbConfig};
 
 mod common;
-use common::_generate_random_claim;
+use common::{_generate_random_claim, _generate_random_string};
 use serial_test::serial;
 
 #[test]
@@ -37,3 +41,55 @@ fn claims_can_be_added() {
 
     assert_eq!(entries.len(), 5);
 }
+
+#[test]
+#[serial]
+fn miner_elections_are_served_from_the_claim_index() {
+    let temp_dir_path = env::temp_dir();
+    let db_path = temp_dir_path.join(_generate_random_string());
+
+    let mut db = VrrbDb::new(VrrbDbConfig {
+        path: db_path,
+        state_store_path: None,
+        transaction_store_path: None,
+        event_store_path: None,
+        claim_store_path: None,
+        pruning_mode: Default::default(),
+        certification_retention: Default::default(),
+        timeline_rounds: DEFAULT_RETAINED_TIMELINE_ROUNDS,
+        backend: Default::default(),
+    });
+
+    let claims: Vec<Claim> = (0..10)
+        .map(|_| {
+            let mut claim = _generate_random_claim();
+            claim.eligibility = Eligibility::Miner;
+            claim
+        })
+        .collect();
+
+    db.extend_claims(
+        claims
+            .iter()
+            .map(|claim| (claim.hash, Some(claim.clone())))
+            .collect(),
+    );
+
+    let seed = 1234;
+
+    let expected: BTreeMap<U256, Claim> = claims
+        .iter()
+        .map(|claim| (claim.get_weighted_election_result(seed), claim.clone()))
+        .collect();
+
+    let winner = db.read_handle().miner_election_winner(seed).unwrap();
+
+    assert_eq!(Some((&winner.0, &winner.1)), expected.iter().next());
+
+    let (_, runner_up) = expected.iter().nth(1).unwrap();
+    db.extend_claims(vec![(winner.1.hash, None)]);
+
+    let winner = db.read_handle().miner_election_winner(seed).unwrap();
+
+    assert_eq!(&winner.1, runner_up);
+}
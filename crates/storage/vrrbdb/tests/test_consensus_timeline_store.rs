@@ -0,0 +1,65 @@
+use std::env;
+
+use vrrbdb::{RoundMilestone, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::_generate_random_string;
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn round_timelines_keep_the_first_time_milestones_are_reached_and_are_pruned() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default()
+        .with_path(db_path)
+        .with_timeline_rounds(2);
+
+    let mut db = VrrbDb::new(config.clone());
+
+    db.record_round_milestone(1, RoundMilestone::ProposalSeen, 1_000)
+        .unwrap();
+    db.record_round_milestone(2, RoundMilestone::ProposalSeen, 2_000)
+        .unwrap();
+    db.record_round_milestone(2, RoundMilestone::SignatureThresholdReached, 2_300)
+        .unwrap();
+
+    // NOTE: later proposals of the same round don't move its first sighting
+    db.record_round_milestone(2, RoundMilestone::ProposalSeen, 2_100)
+        .unwrap();
+
+    drop(db);
+
+    let mut db = VrrbDb::new(config);
+
+    db.record_round_milestone(2, RoundMilestone::CertificateCreated, 2_400)
+        .unwrap();
+    db.record_round_milestone(2, RoundMilestone::BlockApplied, 2_450)
+        .unwrap();
+    db.record_round_milestone(3, RoundMilestone::ProposalSeen, 3_000)
+        .unwrap();
+
+    let read_handle = db.read_handle();
+    let timelines = read_handle.consensus_timeline();
+
+    assert_eq!(
+        timelines
+            .iter()
+            .map(|timeline| timeline.round)
+            .collect::<Vec<_>>(),
+        vec![2, 3]
+    );
+    assert!(read_handle.round_timeline(1).unwrap().is_none());
+
+    let timeline = read_handle.round_timeline(2).unwrap().unwrap();
+
+    assert_eq!(timeline.first_proposal_seen_at, Some(2_000));
+    assert_eq!(timeline.signature_threshold_reached_at, Some(2_300));
+    assert_eq!(
+        timeline.elapsed(RoundMilestone::ProposalSeen, RoundMilestone::BlockApplied),
+        Some(450)
+    );
+    assert_eq!(
+        timelines[1].elapsed(RoundMilestone::ProposalSeen, RoundMilestone::BlockApplied),
+        None
+    );
+}
@@ -0,0 +1,85 @@
+use std::env;
+
+use primitives::{Epoch, Round};
+use vrrbdb::{EarningsRecord, ValidatorEarnings, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::_generate_random_string;
+use serial_test::serial;
+
+fn earnings_record(node_id: &str, epoch: Epoch, round: Round) -> EarningsRecord {
+    EarningsRecord {
+        node_id: node_id.to_string(),
+        epoch,
+        round,
+        block_hash: format!("block-{round}"),
+        block_rewards: 100,
+        fee_shares: 10,
+        penalties: 0,
+    }
+}
+
+#[test]
+#[serial]
+fn earnings_are_summed_up_per_epoch_of_the_requested_range() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default().with_path(db_path);
+
+    let mut db = VrrbDb::new(config.clone());
+
+    let slashed = EarningsRecord {
+        block_rewards: 0,
+        fee_shares: 0,
+        penalties: 500,
+        ..earnings_record("node-1", 1, 12)
+    };
+
+    for record in [
+        earnings_record("node-1", 0, 1),
+        earnings_record("node-1", 0, 2),
+        earnings_record("node-1", 1, 11),
+        slashed,
+        earnings_record("node-1", 2, 21),
+        earnings_record("node-2", 1, 11),
+    ] {
+        db.record_earnings(&record).unwrap();
+    }
+
+    // NOTE: applying a block again doesn't count its earnings twice
+    db.record_earnings(&earnings_record("node-1", 0, 2))
+        .unwrap();
+    drop(db);
+
+    let read_handle = VrrbDb::new(config).read_handle();
+    let node_id = "node-1".to_string();
+
+    let earnings = read_handle.validator_earnings(&node_id, 0..=1);
+
+    assert_eq!(
+        earnings,
+        vec![
+            ValidatorEarnings {
+                node_id: node_id.clone(),
+                epoch: 0,
+                block_rewards: 200,
+                fee_shares: 20,
+                penalties: 0,
+                blocks: 2,
+            },
+            ValidatorEarnings {
+                node_id: node_id.clone(),
+                epoch: 1,
+                block_rewards: 100,
+                fee_shares: 10,
+                penalties: 500,
+                blocks: 2,
+            },
+        ]
+    );
+    assert_eq!(earnings[1].net(), -390);
+
+    assert_eq!(read_handle.validator_earnings(&node_id, 2..=5).len(), 1);
+    assert!(read_handle
+        .validator_earnings(&"node-3".to_string(), 0..=5)
+        .is_empty());
+}
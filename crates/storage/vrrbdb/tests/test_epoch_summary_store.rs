@@ -0,0 +1,73 @@
+use std::env;
+
+use block::{header::BlockHeader, EpochSummary};
+use primitives::{Epoch, HeaderFormatParams, QuorumPubkey, ValidatorSecretKey};
+use reward::schedule::RewardSchedule;
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{_generate_random_address, _generate_random_claim, _generate_random_string};
+use serial_test::serial;
+
+fn epoch_summary(epoch: Epoch, parent: Option<&EpochSummary>) -> EpochSummary {
+    let (secret_key, _) = _generate_random_address();
+
+    let mut header = BlockHeader::genesis(
+        0,
+        epoch * 10 + 9,
+        0,
+        _generate_random_claim(),
+        secret_key,
+        String::new(),
+        &RewardSchedule::default(),
+        &HeaderFormatParams::default(),
+    );
+    header.epoch = epoch;
+
+    EpochSummary {
+        epoch,
+        parent_hash: parent.map(EpochSummary::hash).unwrap_or_default(),
+        round: header.round,
+        block_hash: header.hash(),
+        state_root: header.state_root.clone(),
+        validator_set_hash: String::new(),
+        quorum_public_key: QuorumPubkey::from(ValidatorSecretKey::random().public_key()),
+        txn_count: 5,
+        block_rewards: 100,
+        fee_shares: 10,
+        signature: hex::encode([3; 96]),
+        header,
+    }
+}
+
+#[test]
+#[serial]
+fn epoch_summaries_are_kept_in_epoch_order_across_restarts() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default().with_path(db_path);
+
+    let mut db = VrrbDb::new(config.clone());
+
+    // NOTE: epochs past 255 make sure keys sort by epoch rather than by their
+    // lowest byte
+    let first = epoch_summary(1, None);
+    let second = epoch_summary(300, Some(&first));
+    let third = epoch_summary(301, Some(&second));
+
+    for summary in [&third, &first, &second] {
+        db.record_epoch_summary(summary).unwrap();
+    }
+
+    drop(db);
+
+    let read_handle = VrrbDb::new(config).read_handle();
+
+    assert_eq!(read_handle.get_epoch_summary(300).unwrap(), second);
+    assert!(read_handle.get_epoch_summary(2).is_err());
+    assert_eq!(read_handle.latest_epoch_summary().unwrap(), third);
+
+    let summaries = read_handle.epoch_summaries();
+    assert_eq!(summaries, vec![first, second, third]);
+    assert!(summaries.windows(2).all(|pair| pair[1].follows(&pair[0])));
+    assert!(summaries.iter().all(EpochSummary::matches_header));
+}
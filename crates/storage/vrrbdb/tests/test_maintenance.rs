@@ -0,0 +1,89 @@
+use std::env;
+
+use block::Certificate;
+use vrrbdb::{compact_db, inspect_db, open_backend, repair_db, verify_db, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::_generate_random_string;
+use serial_test::serial;
+
+fn certificate(block_hash: &str) -> Certificate {
+    Certificate {
+        signature: hex::encode([3; 96]),
+        inauguration: None,
+        root_hash: "".to_string(),
+        next_root_hash: "".to_string(),
+        block_hash: block_hash.to_string(),
+    }
+}
+
+#[test]
+#[serial]
+fn corrupted_records_are_reported_and_repaired() {
+    let config = VrrbDbConfig::default().with_path(env::temp_dir().join(_generate_random_string()));
+
+    let mut db = VrrbDb::new(config.clone());
+    db.record_certificate(certificate("block-1")).unwrap();
+    db.record_certificate(certificate("block-2")).unwrap();
+    drop(db);
+
+    let certifications = open_backend(
+        config.backend,
+        config.path.join("certifications"),
+        "certifications",
+    )
+    .unwrap();
+    certifications
+        .put(b"garbage", b"not a certification")
+        .unwrap();
+    drop(certifications);
+
+    let inspection = inspect_db(&config).unwrap();
+    let certification_store = inspection
+        .stores
+        .iter()
+        .find(|store| store.store == "certifications")
+        .unwrap();
+
+    assert_eq!(inspection.certified_blocks, 2);
+    assert_eq!(
+        inspection.latest_certified_block,
+        Some("block-2".to_string())
+    );
+    assert_eq!(certification_store.records, 3);
+    assert_eq!(certification_store.corrupted_records, 1);
+
+    let repairs = repair_db(&config).unwrap();
+
+    assert_eq!(
+        repairs
+            .iter()
+            .map(|repair| repair.removed_records)
+            .sum::<usize>(),
+        1
+    );
+
+    compact_db(&config).unwrap();
+
+    let inspection = inspect_db(&config).unwrap();
+
+    assert!(inspection
+        .stores
+        .iter()
+        .all(|store| store.corrupted_records == 0));
+    assert_eq!(inspection.certified_blocks, 2);
+}
+
+#[test]
+#[serial]
+fn empty_tries_verify_and_leave_no_scratch_data_behind() {
+    let config = VrrbDbConfig::default().with_path(env::temp_dir().join(_generate_random_string()));
+
+    let verifications = verify_db(&config).unwrap();
+
+    assert_eq!(verifications.len(), 3);
+    assert!(verifications
+        .iter()
+        .all(|verification| verification.is_consistent()));
+    assert!(!config.path.join("verification").exists());
+}
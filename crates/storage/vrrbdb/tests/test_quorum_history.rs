@@ -0,0 +1,77 @@
+use std::env;
+
+use block::{Certificate, QuorumPubkeys};
+use primitives::{QuorumKind, QuorumPubkey, ValidatorSecretKey};
+use vrrbdb::{QuorumRecord, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::_generate_random_string;
+use serial_test::serial;
+
+fn quorum_record(epoch: u128) -> QuorumRecord {
+    let member_keys = (1..=3)
+        .map(|i| {
+            (
+                format!("node-{i}"),
+                ValidatorSecretKey::random().public_key(),
+            )
+        })
+        .collect();
+
+    QuorumRecord {
+        epoch,
+        quorum_kind: QuorumKind::Harvester,
+        members: member_keys,
+        group_public_key: QuorumPubkey::from(ValidatorSecretKey::random().public_key()),
+        threshold: 1,
+        inauguration: None,
+    }
+}
+
+#[test]
+#[serial]
+fn quorums_are_looked_up_by_the_epoch_they_were_in_effect() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default().with_path(db_path);
+
+    let mut db = VrrbDb::new(config.clone());
+
+    let genesis_quorum = quorum_record(0);
+    let rotated_quorum = quorum_record(5);
+
+    db.record_quorum(genesis_quorum.clone()).unwrap();
+    db.record_quorum(rotated_quorum.clone()).unwrap();
+
+    let mut inauguration = QuorumPubkeys::new();
+    inauguration.insert(
+        "harvester".parse().unwrap(),
+        rotated_quorum.group_public_key,
+    );
+
+    db.record_certificate(Certificate {
+        signature: hex::encode([3; 96]),
+        inauguration: Some(inauguration),
+        root_hash: "".to_string(),
+        next_root_hash: "".to_string(),
+        block_hash: "block-1".to_string(),
+    })
+    .unwrap();
+
+    drop(db);
+
+    let read_handle = VrrbDb::new(config).read_handle();
+
+    assert_eq!(read_handle.get_quorum(0).unwrap(), genesis_quorum);
+    assert_eq!(read_handle.get_quorum(4).unwrap(), genesis_quorum);
+
+    let current_quorum = read_handle.current_quorum().unwrap();
+    assert_eq!(read_handle.get_quorum(7).unwrap(), current_quorum);
+    assert_eq!(
+        current_quorum.group_public_key,
+        rotated_quorum.group_public_key
+    );
+    assert_eq!(
+        current_quorum.inauguration.unwrap().block_hash,
+        "block-1".to_string()
+    );
+}
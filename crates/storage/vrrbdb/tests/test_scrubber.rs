@@ -0,0 +1,296 @@
+use std::{collections::BTreeMap, env};
+
+use block::{header::BlockHeader, Certificate, Checkpoint, EpochSummary};
+use patriecia::{Node, NodeKey};
+use primitives::{Epoch, HeaderFormatParams, QuorumPubkey, Round, ValidatorSecretKey};
+use reward::schedule::RewardSchedule;
+use vrrb_core::{account::Account, transactions::TransactionDigest};
+use vrrbdb::{open_backend, CorruptRecord, ScrubbedStore, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{_generate_random_address, _generate_random_claim, _generate_random_string};
+use serial_test::serial;
+
+fn certificate(block_hash: &str) -> Certificate {
+    Certificate {
+        signature: hex::encode([3; 96]),
+        inauguration: None,
+        root_hash: "".to_string(),
+        next_root_hash: "".to_string(),
+        block_hash: block_hash.to_string(),
+    }
+}
+
+fn checkpoint(round: Round) -> Checkpoint {
+    let (secret_key, _) = _generate_random_address();
+
+    let header = BlockHeader::genesis(
+        0,
+        round,
+        0,
+        _generate_random_claim(),
+        secret_key,
+        String::new(),
+        &RewardSchedule::default(),
+        &HeaderFormatParams::default(),
+    );
+
+    let certificate = certificate(&header.hash());
+
+    Checkpoint::new(
+        header,
+        &certificate,
+        QuorumPubkey::from(ValidatorSecretKey::random().public_key()),
+        &BTreeMap::new(),
+    )
+}
+
+fn epoch_summary(epoch: Epoch, parent: Option<&EpochSummary>) -> EpochSummary {
+    let (secret_key, _) = _generate_random_address();
+
+    let mut header = BlockHeader::genesis(
+        0,
+        epoch * 10 + 9,
+        0,
+        _generate_random_claim(),
+        secret_key,
+        String::new(),
+        &RewardSchedule::default(),
+        &HeaderFormatParams::default(),
+    );
+    header.epoch = epoch;
+
+    EpochSummary {
+        epoch,
+        parent_hash: parent.map(EpochSummary::hash).unwrap_or_default(),
+        round: header.round,
+        block_hash: header.hash(),
+        state_root: header.state_root.clone(),
+        validator_set_hash: String::new(),
+        quorum_public_key: QuorumPubkey::from(ValidatorSecretKey::random().public_key()),
+        txn_count: 5,
+        block_rewards: 100,
+        fee_shares: 10,
+        signature: hex::encode([3; 96]),
+        header,
+    }
+}
+
+fn scrub_pass(db: &VrrbDb) -> Vec<CorruptRecord> {
+    let report = db.scrubber().scrub(usize::MAX).unwrap();
+    assert!(report.completed_pass);
+
+    report.corrupt
+}
+
+#[test]
+#[serial]
+fn corrupted_records_are_found_over_several_batches() {
+    let config = VrrbDbConfig::default().with_path(env::temp_dir().join(_generate_random_string()));
+
+    let mut db = VrrbDb::new(config.clone());
+    db.record_checkpoint(&checkpoint(100)).unwrap();
+    db.record_checkpoint(&checkpoint(200)).unwrap();
+    db.record_certificate(certificate("block-1")).unwrap();
+    db.record_certificate(certificate("block-2")).unwrap();
+    drop(db);
+
+    // NOTE: a checkpoint that decodes fine, but was written under another round
+    let checkpoints = open_backend(
+        config.backend,
+        config.path.join("checkpoints"),
+        "checkpoints",
+    )
+    .unwrap();
+    let misplaced = checkpoints.get(&200u128.to_be_bytes()).unwrap().unwrap();
+    checkpoints.put(&300u128.to_be_bytes(), &misplaced).unwrap();
+    drop(checkpoints);
+
+    let certifications = open_backend(
+        config.backend,
+        config.path.join("certifications"),
+        "certifications",
+    )
+    .unwrap();
+    certifications
+        .put(b"block-3", b"not a certification")
+        .unwrap();
+    drop(certifications);
+
+    let mut db = VrrbDb::new(config);
+    let mut scrubber = db.scrubber();
+
+    let mut batches = 0;
+    let mut corrupt = Vec::new();
+    loop {
+        let report = scrubber.scrub(2).unwrap();
+        assert!(report.scrubbed <= 2);
+
+        batches += 1;
+        corrupt.extend(report.corrupt);
+
+        if report.completed_pass {
+            break;
+        }
+    }
+
+    assert!(batches > 1);
+    assert_eq!(
+        corrupt
+            .iter()
+            .map(|record| (record.store, record.key.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (ScrubbedStore::Checkpoints, 300u128.to_be_bytes().to_vec()),
+            (ScrubbedStore::Certifications, b"block-3".to_vec()),
+        ]
+    );
+    assert_eq!(corrupt[0].certified_block(), None);
+    assert_eq!(corrupt[1].certified_block(), Some("block-3".to_string()));
+
+    db.discard_corrupt_record(&corrupt[1]).unwrap();
+
+    let report = db.scrubber().scrub(usize::MAX).unwrap();
+
+    assert!(report.completed_pass);
+    assert_eq!(report.corrupt.len(), 1);
+    assert_eq!(report.corrupt[0].store, ScrubbedStore::Checkpoints);
+
+    // NOTE: the discarded block can be certified again
+    db.record_certificate(certificate("block-3")).unwrap();
+    assert!(db
+        .read_handle()
+        .get_block_certification(&"block-3".to_string())
+        .unwrap()
+        .is_certified());
+}
+
+#[test]
+#[serial]
+fn receipts_that_still_decode_are_checked_against_their_checksum() {
+    let config = VrrbDbConfig::default().with_path(env::temp_dir().join(_generate_random_string()));
+
+    let digest = TransactionDigest::from(vec![7; 32]);
+
+    let mut db = VrrbDb::new(config.clone());
+    db.record_txn_admission(digest.clone(), 100).unwrap();
+    assert!(scrub_pass(&db).is_empty());
+    drop(db);
+
+    // NOTE: the checksum is encoded last, flipping one of its hex digits for
+    // another keeps the receipt decodable
+    let receipts = open_backend(
+        config.backend,
+        config.path.join("txn_receipts"),
+        "txn_receipts",
+    )
+    .unwrap();
+    let key = digest.as_bytes().to_vec();
+    let mut value = receipts.get(&key).unwrap().unwrap();
+    let last = value.last_mut().unwrap();
+    *last = if *last == b'0' { b'1' } else { b'0' };
+    receipts.put(&key, &value).unwrap();
+    drop(receipts);
+
+    let db = VrrbDb::new(config);
+    let corrupt = scrub_pass(&db);
+
+    assert_eq!(corrupt.len(), 1);
+    assert_eq!(corrupt[0].store, ScrubbedStore::TxnReceipts);
+    assert_eq!(corrupt[0].key, key);
+    assert!(!corrupt[0].is_recoverable_from_peers());
+}
+
+#[test]
+#[serial]
+fn epoch_summaries_are_checked_against_the_parent_hash_of_the_next_summary() {
+    let config = VrrbDbConfig::default().with_path(env::temp_dir().join(_generate_random_string()));
+
+    let first = epoch_summary(1, None);
+    let second = epoch_summary(2, Some(&first));
+
+    let mut db = VrrbDb::new(config.clone());
+    db.record_epoch_summary(&first).unwrap();
+    db.record_epoch_summary(&second).unwrap();
+    assert!(scrub_pass(&db).is_empty());
+
+    // NOTE: the txn count isn't covered by the header, only by the summary's
+    // hash the next summary names as its parent
+    let tampered = EpochSummary {
+        txn_count: first.txn_count + 1,
+        ..first.clone()
+    };
+    db.record_epoch_summary(&tampered).unwrap();
+
+    let corrupt = scrub_pass(&db);
+
+    assert_eq!(corrupt.len(), 1);
+    assert_eq!(corrupt[0].store, ScrubbedStore::EpochSummaries);
+    assert_eq!(corrupt[0].key, 1u128.to_be_bytes().to_vec());
+    assert_eq!(corrupt[0].expected_hash, Some(first.hash()));
+    assert!(corrupt[0].is_recoverable_from_peers());
+
+    // NOTE: peers' copies are only taken if they hash to the expected hash
+    let tampered = bincode::serialize(&tampered).unwrap();
+    assert!(!db.restore_corrupt_record(&corrupt[0], &tampered).unwrap());
+
+    let original = bincode::serialize(&first).unwrap();
+    assert_eq!(
+        db.get_record(ScrubbedStore::EpochSummaries, &corrupt[0].key)
+            .unwrap(),
+        Some(tampered)
+    );
+    assert!(db.restore_corrupt_record(&corrupt[0], &original).unwrap());
+
+    assert!(scrub_pass(&db).is_empty());
+    assert_eq!(db.read_handle().get_epoch_summary(1).unwrap(), first);
+}
+
+#[test]
+#[serial]
+fn trie_nodes_are_hashed_against_the_hashes_their_parents_recorded() {
+    let config = VrrbDbConfig::default().with_path(env::temp_dir().join(_generate_random_string()));
+
+    let mut db = VrrbDb::new(config.clone());
+    for _ in 0..8 {
+        let (_, address) = _generate_random_address();
+        db.insert_account(address.clone(), Account::new(address.public_key()))
+            .unwrap();
+    }
+    db.commit_state_at_round(1).unwrap();
+    assert!(scrub_pass(&db).is_empty());
+    drop(db);
+
+    let nodes = open_backend(config.backend, config.path.join("state"), "state").unwrap();
+    let leaves: Vec<(Vec<u8>, Vec<u8>)> = nodes
+        .entries_after(None, usize::MAX)
+        .unwrap()
+        .into_iter()
+        .filter(|(key, value)| {
+            let node_key: NodeKey = bincode::deserialize(key).unwrap();
+            let node: Node = bincode::deserialize(value).unwrap();
+
+            node_key.nibble_path().num_nibbles() > 0 && matches!(node, Node::Leaf(_))
+        })
+        .collect();
+
+    // NOTE: another leaf decodes fine, but doesn't hash to what the parent of
+    // the replaced leaf recorded
+    let (key, original) = leaves[0].clone();
+    let (_, swapped) = leaves[1].clone();
+    nodes.put(&key, &swapped).unwrap();
+    drop(nodes);
+
+    let mut db = VrrbDb::new(config);
+    let corrupt = scrub_pass(&db);
+
+    assert_eq!(corrupt.len(), 1);
+    assert_eq!(corrupt[0].store, ScrubbedStore::StateTrie);
+    assert_eq!(corrupt[0].key, key);
+    assert!(corrupt[0].is_recoverable_from_peers());
+
+    assert!(!db.restore_corrupt_record(&corrupt[0], &swapped).unwrap());
+    assert!(db.restore_corrupt_record(&corrupt[0], &original).unwrap());
+
+    assert!(scrub_pass(&db).is_empty());
+}
@@ -0,0 +1,172 @@
+use std::env;
+
+use sha2::{Digest, Sha256};
+use vrrb_core::account::{Account, AccountField};
+use vrrbdb::{
+    export_state_archive, import_state_archive, MigrationRegistry, StateArchiveChunk,
+    StateArchiveError, StateArchiveFrame, VrrbDb, VrrbDbConfig,
+};
+
+mod common;
+use common::{_generate_random_address, _generate_random_string};
+use serial_test::serial;
+
+fn new_config() -> VrrbDbConfig {
+    VrrbDbConfig::default().with_path(env::temp_dir().join(_generate_random_string()))
+}
+
+/// Exports a database holding five accounts in chunks of two
+fn exported_archive() -> (String, String) {
+    let config = new_config();
+    let mut db = VrrbDb::new(config.clone());
+
+    let accounts = (0..5u128)
+        .map(|credits| {
+            let (_, address) = _generate_random_address();
+            let mut account = Account::new(address.public_key());
+            account
+                .update_field(AccountField::Credits(u128::MAX / (credits + 1)))
+                .unwrap();
+
+            (address, Some(account))
+        })
+        .collect();
+
+    db.extend_accounts(accounts);
+    db.commit_state();
+    let state_root = hex::encode(db.state_root_hash().unwrap().0);
+    drop(db);
+
+    let mut archive = vec![];
+    let summary = export_state_archive(&config, &mut archive, 2).unwrap();
+
+    assert_eq!(summary.records, 5);
+    assert_eq!(summary.chunks, 3);
+    assert_eq!(summary.state_root, state_root);
+
+    (String::from_utf8(archive).unwrap(), state_root)
+}
+
+fn frames(archive: &str) -> Vec<StateArchiveFrame> {
+    archive
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn encode(frames: &[StateArchiveFrame]) -> String {
+    frames
+        .iter()
+        .map(|frame| serde_json::to_string(frame).unwrap() + "\n")
+        .collect()
+}
+
+#[test]
+#[serial]
+fn exported_state_imports_into_an_empty_database_with_the_same_root() {
+    let (archive, state_root) = exported_archive();
+    let config = new_config();
+
+    let summary =
+        import_state_archive(&config, archive.as_bytes(), &MigrationRegistry::default()).unwrap();
+
+    assert_eq!(summary.state_root, state_root);
+    assert_eq!(summary.records, 5);
+    assert_eq!(summary.migrated_from, None);
+
+    assert!(matches!(
+        import_state_archive(&config, archive.as_bytes(), &MigrationRegistry::default()),
+        Err(StateArchiveError::TargetNotEmpty)
+    ));
+}
+
+#[test]
+#[serial]
+fn corrupted_and_truncated_archives_are_refused() {
+    let (archive, _) = exported_archive();
+    let mut frames = frames(&archive);
+
+    if let StateArchiveFrame::Chunk(chunk) = &mut frames[2] {
+        chunk.records.swap(0, 1);
+    }
+
+    assert!(matches!(
+        import_state_archive(
+            &new_config(),
+            encode(&frames).as_bytes(),
+            &MigrationRegistry::default()
+        ),
+        Err(StateArchiveError::ChecksumMismatch { index: 1 })
+    ));
+
+    let truncated: String = archive
+        .lines()
+        .take(3)
+        .map(|line| line.to_string() + "\n")
+        .collect();
+
+    assert!(matches!(
+        import_state_archive(
+            &new_config(),
+            truncated.as_bytes(),
+            &MigrationRegistry::default()
+        ),
+        Err(StateArchiveError::Truncated(_))
+    ));
+}
+
+#[test]
+#[serial]
+fn archives_of_older_schemas_are_migrated_on_import() {
+    let (archive, _) = exported_archive();
+    let mut frames = frames(&archive);
+    let mut trailer_hasher = Sha256::new();
+
+    // NOTE: schema version 0 stores accounts byte reversed
+    for frame in frames.iter_mut() {
+        match frame {
+            StateArchiveFrame::Header(header) => header.schema_version = 0,
+            StateArchiveFrame::Chunk(chunk) => {
+                let records = chunk
+                    .records
+                    .iter()
+                    .map(|record| {
+                        let mut bytes = hex::decode(record).unwrap();
+                        bytes.reverse();
+                        hex::encode(bytes)
+                    })
+                    .collect();
+
+                *chunk = StateArchiveChunk::new(chunk.index, records);
+                trailer_hasher.update(chunk.checksum.as_bytes());
+            },
+            StateArchiveFrame::Trailer(trailer) => {
+                trailer.checksum = hex::encode(trailer_hasher.clone().finalize());
+            },
+        }
+    }
+
+    let archive = encode(&frames);
+
+    assert!(matches!(
+        import_state_archive(
+            &new_config(),
+            archive.as_bytes(),
+            &MigrationRegistry::default()
+        ),
+        Err(StateArchiveError::MissingMigration(0))
+    ));
+
+    let mut migrations = MigrationRegistry::default();
+    migrations.register(0, |record| Ok(record.iter().rev().copied().collect()));
+
+    let config = new_config();
+    let summary = import_state_archive(&config, archive.as_bytes(), &migrations).unwrap();
+
+    assert_eq!(summary.migrated_from, Some(0));
+    assert_eq!(summary.records, 5);
+    assert_eq!(
+        VrrbDb::new(config).read_handle().state_store_values().len(),
+        5
+    );
+}
@@ -0,0 +1,118 @@
+use std::env;
+
+use primitives::Address;
+use vrrb_core::account::{Account, AccountField};
+use vrrbdb::{
+    diff_state_archive, diff_state_snapshots, export_state_archive, MigrationRegistry,
+    StateSnapshot, VrrbDb, VrrbDbConfig,
+};
+
+mod common;
+use common::{_generate_random_address, _generate_random_string};
+use serial_test::serial;
+
+fn new_config() -> VrrbDbConfig {
+    VrrbDbConfig::default().with_path(env::temp_dir().join(_generate_random_string()))
+}
+
+fn new_account(credits: u128) -> (Address, Account) {
+    let (_, address) = _generate_random_address();
+    let mut account = Account::new(address.public_key());
+    account
+        .update_field(AccountField::Credits(credits))
+        .unwrap();
+
+    (address, account)
+}
+
+#[test]
+#[serial]
+fn archives_of_peers_are_diffed_against_the_local_state() {
+    let config = new_config();
+    let mut db = VrrbDb::new(config.clone());
+
+    let accounts: Vec<(Address, Account)> =
+        (1..=3).map(|credits| new_account(credits * 100)).collect();
+    db.extend_accounts(
+        accounts
+            .iter()
+            .map(|(address, account)| (address.clone(), Some(account.clone())))
+            .collect(),
+    );
+    db.commit_state();
+    drop(db);
+
+    let mut archive = vec![];
+    export_state_archive(&config, &mut archive, 2).unwrap();
+
+    let diff =
+        diff_state_archive(&config, archive.as_slice(), &MigrationRegistry::default()).unwrap();
+    assert!(diff.is_empty());
+
+    // NOTE: the local node then credits an account the peer didn't, and
+    // learns of an account the peer doesn't know
+    let mut db = VrrbDb::new(config.clone());
+    let (credited, mut account) = accounts[0].clone();
+    account.update_field(AccountField::Credits(1)).unwrap();
+    let (unknown, unknown_account) = new_account(42);
+
+    db.extend_accounts(vec![
+        (credited.clone(), Some(account.clone())),
+        (unknown.clone(), Some(unknown_account.clone())),
+    ]);
+    db.commit_state();
+    drop(db);
+
+    let diff =
+        diff_state_archive(&config, archive.as_slice(), &MigrationRegistry::default()).unwrap();
+
+    assert!(diff.claims.is_none());
+    assert!(diff.accounts.divergent_path.is_some());
+    assert_eq!(diff.accounts.divergences.len(), 2);
+
+    let credited_divergence = diff
+        .accounts
+        .divergences
+        .iter()
+        .find(|divergence| divergence.key == credited)
+        .unwrap();
+    assert_eq!(credited_divergence.local, Some(account));
+    assert_eq!(credited_divergence.peer, Some(accounts[0].1.clone()));
+
+    let unknown_divergence = diff
+        .accounts
+        .divergences
+        .iter()
+        .find(|divergence| divergence.key == unknown)
+        .unwrap();
+    assert_eq!(unknown_divergence.local, Some(unknown_account));
+    assert_eq!(unknown_divergence.peer, None);
+
+    // NOTE: the scratch database the archive was imported into is gone
+    assert!(!config.path.join("state-diff").exists());
+}
+
+#[test]
+fn snapshots_only_diff_claims_when_both_cover_them() {
+    let (address, account) = new_account(100);
+
+    let local = StateSnapshot {
+        accounts: [(address.clone(), account)].into(),
+        claims: Some(Default::default()),
+    };
+
+    let diff = diff_state_snapshots(&local, &StateSnapshot::default());
+    assert!(diff.claims.is_none());
+    assert_eq!(diff.accounts.divergences[0].key, address);
+    assert_eq!(diff.accounts.divergences[0].peer, None);
+
+    let peer = StateSnapshot {
+        claims: Some(Default::default()),
+        ..local.clone()
+    };
+
+    let diff = diff_state_snapshots(&local, &peer);
+    assert!(diff.is_empty());
+    assert_eq!(diff.accounts.divergent_path, None);
+    assert!(diff.claims.unwrap().is_empty());
+}
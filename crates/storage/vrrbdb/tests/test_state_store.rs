@@ -1,9 +1,12 @@
+use std::env;
+
 use patriecia::{KeyHash, Sha256};
-use vrrb_core::account::Account;
+use primitives::PruningMode;
+use vrrb_core::account::{Account, UpdateArgs};
 use vrrbdb::{VrrbDb, VrrbDbConfig};
 
 mod common;
-use common::_generate_random_address;
+use common::{_generate_random_address, _generate_random_string};
 use serial_test::serial;
 
 #[test]
@@ -37,3 +40,213 @@ fn accounts_can_be_added() {
 
     assert_eq!(entries.len(), 5);
 }
+
+#[test]
+#[serial]
+fn accounts_can_be_read_as_of_a_past_round() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(
+        VrrbDbConfig::default()
+            .with_path(db_path)
+            .with_pruning_mode(PruningMode::KeepRecent(2)),
+    );
+
+    let (_, addr) = _generate_random_address();
+    db.insert_account(addr.clone(), Account::new(addr.public_key()))
+        .unwrap();
+
+    let first_root = db.commit_state_at_round(1).unwrap();
+
+    for round in 2..=3 {
+        db.update_account(UpdateArgs {
+            address: addr.clone(),
+            nonce: None,
+            credits: Some(100),
+            debits: None,
+            storage: None,
+            code: None,
+            digests: None,
+        })
+        .unwrap();
+
+        db.commit_state_at_round(round).unwrap();
+    }
+
+    let read_handle = db.read_handle();
+
+    assert_eq!(
+        read_handle.get_account_at_round(&addr, 2).unwrap().credits(),
+        100
+    );
+    assert_eq!(
+        read_handle.get_account_at_round(&addr, 3).unwrap().credits(),
+        200
+    );
+    assert_eq!(
+        read_handle.get_state_root(3).unwrap().root_hash.0,
+        db.state_root_hash().unwrap().0
+    );
+
+    // Round 1 falls outside of the two most recent rounds kept by the node
+    assert_ne!(first_root.0, db.state_root_hash().unwrap().0);
+    assert!(read_handle.get_state_root(1).is_err());
+    assert!(read_handle.get_account_at_round(&addr, 1).is_err());
+}
+
+#[test]
+#[serial]
+fn state_can_be_scanned_page_by_page() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(db_path));
+
+    let addresses = (0..5)
+        .map(|_| _generate_random_address().1)
+        .collect::<Vec<_>>();
+
+    db.extend_accounts(
+        addresses
+            .iter()
+            .map(|addr| (addr.clone(), Some(Account::new(addr.public_key()))))
+            .collect(),
+    );
+
+    let read_handle = db.state_store_factory().handle();
+    assert_eq!(read_handle.iter().unwrap().count(), 5);
+
+    let mut scanned = vec![];
+    let mut cursor = None;
+    loop {
+        let page = read_handle.scan(None, cursor.as_ref(), 2).unwrap();
+        assert!(page.accounts.len() <= 2);
+
+        scanned.extend(
+            page.accounts
+                .into_iter()
+                .map(|account| account.address().clone()),
+        );
+
+        match page.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => break,
+        }
+    }
+
+    // NOTE: pages come in address order, as the scan seeks through the address
+    // index rather than walking the trie
+    assert!(scanned
+        .windows(2)
+        .all(|pair| pair[0].to_string() < pair[1].to_string()));
+
+    scanned.sort();
+    let mut expected = addresses.clone();
+    expected.sort();
+    assert_eq!(scanned, expected);
+
+    let compressed_even = read_handle
+        .prefix_iter("02")
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        compressed_even.len(),
+        addresses
+            .iter()
+            .filter(|addr| addr.to_string().starts_with("02"))
+            .count()
+    );
+
+    let prefix = addresses[0].to_string();
+    let page = read_handle.scan(Some(&prefix), None, 10).unwrap();
+    assert_eq!(page.accounts.len(), 1);
+    assert_eq!(page.accounts[0].address(), &addresses[0]);
+    assert!(page.next_cursor.is_none());
+
+    let mut exported = vec![];
+    assert_eq!(db.export_state(&mut exported).unwrap(), 5);
+    assert_eq!(String::from_utf8(exported).unwrap().lines().count(), 5);
+}
+
+#[test]
+#[serial]
+fn accounts_are_listed_by_the_last_epoch_they_were_active() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(db_path));
+
+    let (_, idle) = _generate_random_address();
+    let (_, active) = _generate_random_address();
+
+    let mut active_account = Account::new(active.public_key());
+    active_account.record_activity(3);
+
+    db.extend_accounts(vec![
+        (idle.clone(), Some(Account::new(idle.public_key()))),
+        (active.clone(), Some(active_account.clone())),
+    ]);
+    db.commit_state();
+
+    let read_handle = db.state_store_factory().handle();
+    assert_eq!(
+        read_handle.addresses_last_active_at(None).unwrap(),
+        vec![idle.clone()]
+    );
+    assert_eq!(
+        read_handle.addresses_last_active_at(Some(3)).unwrap(),
+        vec![active.clone()]
+    );
+
+    // NOTE: accounts move to the group of the epoch they were last active
+    // during, and leave the index along with the state
+    active_account.record_activity(5);
+    db.extend_accounts(vec![(active.clone(), Some(active_account)), (idle, None)]);
+    db.commit_state();
+
+    let read_handle = db.state_store_factory().handle();
+    assert!(read_handle
+        .addresses_last_active_at(None)
+        .unwrap()
+        .is_empty());
+    assert!(read_handle
+        .addresses_last_active_at(Some(3))
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        read_handle.addresses_last_active_at(Some(5)).unwrap(),
+        vec![active]
+    );
+}
+
+#[test]
+#[serial]
+fn projected_state_root_matches_the_root_after_applying_updates() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(db_path));
+
+    let (_, addr) = _generate_random_address();
+    let (_, missing) = _generate_random_address();
+    db.insert_account(addr.clone(), Account::new(addr.public_key()))
+        .unwrap();
+
+    let pre_state_root = db.state_root_hash().unwrap();
+    let credit = |address| UpdateArgs {
+        address,
+        nonce: None,
+        credits: Some(100),
+        debits: None,
+        storage: None,
+        code: None,
+        digests: None,
+    };
+
+    // NOTE: updates to accounts missing from the state are left out, just as
+    // they are when applied
+    let projected = db
+        .projected_state_root_hash(vec![credit(addr.clone()), credit(missing)])
+        .unwrap();
+
+    assert_eq!(db.state_root_hash().unwrap().0, pre_state_root.0);
+    assert_ne!(projected.0, pre_state_root.0);
+
+    db.update_account(credit(addr)).unwrap();
+
+    assert_eq!(db.state_root_hash().unwrap().0, projected.0);
+}
@@ -1,6 +1,7 @@
 use std::env;
 
 use patriecia::{KeyHash, Sha256};
+use primitives::DEFAULT_RETAINED_TIMELINE_ROUNDS;
 use serial_test::serial;
 use vrrbdb::{VrrbDb, VrrbDbConfig};
 mod common;
@@ -19,6 +20,10 @@ fn transactions_can_be_added() {
         transaction_store_path: None,
         event_store_path: None,
         claim_store_path: None,
+        pruning_mode: Default::default(),
+        certification_retention: Default::default(),
+        timeline_rounds: DEFAULT_RETAINED_TIMELINE_ROUNDS,
+        backend: Default::default(),
     });
 
     let txn1 = _generate_random_valid_transaction();
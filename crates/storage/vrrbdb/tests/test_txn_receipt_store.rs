@@ -0,0 +1,58 @@
+use std::env;
+
+use primitives::TxnValidationStatus;
+use vrrb_core::transactions::TransactionDigest;
+use vrrbdb::{TxnCertificationRecord, TxnInclusionRecord, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::_generate_random_string;
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn txn_receipts_accumulate_milestones_across_restarts() {
+    let db_path = env::temp_dir().join(_generate_random_string());
+    let config = VrrbDbConfig::default().with_path(db_path);
+
+    let mut db = VrrbDb::new(config.clone());
+
+    let digest = TransactionDigest::from(vec![7; 32]);
+    assert!(db.read_handle().get_txn_receipt(&digest).unwrap().is_none());
+
+    db.record_txn_admission(digest.clone(), 100).unwrap();
+
+    // NOTE: readmissions don't move the admission time
+    db.record_txn_admission(digest.clone(), 150).unwrap();
+
+    let certification = TxnCertificationRecord {
+        certified_at: 110,
+        quorum_public_key: vec![1; 48],
+        farmer_id: "farmer-1".to_string(),
+        validation_status: TxnValidationStatus::Valid,
+        votes: 3,
+        approvals: 3,
+        quorum_threshold: 2,
+    };
+    db.record_txn_certification(digest.clone(), certification.clone())
+        .unwrap();
+
+    drop(db);
+
+    let mut db = VrrbDb::new(config);
+
+    let inclusion = TxnInclusionRecord {
+        included_at: 120,
+        proposal_block_hash: "proposal-1".to_string(),
+        convergence_block_hash: "convergence-1".to_string(),
+        round: 4,
+    };
+    db.record_txn_inclusion(digest.clone(), inclusion.clone())
+        .unwrap();
+
+    let receipt = db.read_handle().get_txn_receipt(&digest).unwrap().unwrap();
+
+    assert_eq!(receipt.digest, digest);
+    assert_eq!(receipt.admitted_at, Some(100));
+    assert_eq!(receipt.certification, Some(certification));
+    assert_eq!(receipt.inclusion, Some(inclusion));
+}
@@ -2,7 +2,9 @@
 /// Re-exports everything on tracing to avoid having to import tracing
 /// everywhere along with this crate
 mod metrics;
+mod redaction;
 mod subscriber;
 pub use metrics::*;
+pub use redaction::*;
 pub use subscriber::*;
 pub use tracing::{self, *};
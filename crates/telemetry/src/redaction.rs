@@ -0,0 +1,109 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use primitives::{Environment, SecretKey, ValidatorSecretKey, ValidatorSecretKeyShare};
+use sha2::{Digest, Sha256};
+
+use crate::TelemetryError;
+
+/// Number of bytes of a redacted value's hash kept in logs, enough to tell
+/// values apart without revealing them
+const FINGERPRINT_LEN: usize = 4;
+
+static REDACTION_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Returns whether sensitive values are redacted from logs
+pub fn is_redaction_enabled() -> bool {
+    REDACTION_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Turns redaction of sensitive values on or off for every log line written
+/// from here on. It can only be turned off in local environments
+pub fn set_redaction_enabled(enabled: bool) -> Result<(), TelemetryError> {
+    if !enabled && !matches!(primitives::get_vrrb_environment(), Environment::Local) {
+        return Err(TelemetryError::Other(
+            "log redaction can only be disabled in local environments".to_string(),
+        ));
+    }
+
+    REDACTION_ENABLED.store(enabled, Ordering::Relaxed);
+
+    Ok(())
+}
+
+/// Values that must not end up in logs as they are, like secret keys, key
+/// shares, signatures and the raw payloads exchanged with peers.
+///
+/// Log them through [Sensitive::redacted], which prints a short fingerprint
+/// of the value unless redaction was disabled.
+pub trait Sensitive: fmt::Debug {
+    fn redacted(&self) -> Redacted<'_, Self> {
+        Redacted(self)
+    }
+}
+
+impl Sensitive for SecretKey {}
+impl Sensitive for ValidatorSecretKey {}
+impl Sensitive for ValidatorSecretKeyShare {}
+
+// NOTE: covers signatures and payloads, which are passed around as raw bytes
+impl Sensitive for Vec<u8> {}
+impl Sensitive for [u8] {}
+
+// NOTE: covers keys passed around in encoded form
+impl Sensitive for String {}
+impl Sensitive for str {}
+
+/// Formats a [Sensitive] value as `<redacted:fingerprint>`, where the
+/// fingerprint is the start of the hash of the value, so the same value can
+/// be followed across log lines.
+pub struct Redacted<'a, T: ?Sized>(&'a T);
+
+impl<T: Sensitive + ?Sized> fmt::Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !is_redaction_enabled() {
+            return write!(f, "{:?}", self.0);
+        }
+
+        let hash = Sha256::digest(format!("{:?}", self.0).as_bytes());
+
+        write!(f, "<redacted:")?;
+        for byte in &hash[..FINGERPRINT_LEN] {
+            write!(f, "{byte:02x}")?;
+        }
+        write!(f, ">")
+    }
+}
+
+impl<T: Sensitive + ?Sized> fmt::Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sensitive_values_are_logged_as_fingerprints_while_redaction_is_enabled() {
+        let signature: Vec<u8> = vec![7; 96];
+
+        let redacted = format!("{}", signature.redacted());
+        assert!(redacted.starts_with("<redacted:"));
+        assert_eq!(redacted.len(), "<redacted:>".len() + FINGERPRINT_LEN * 2);
+        assert_eq!(redacted, format!("{:?}", signature.redacted()));
+        assert_ne!(redacted, format!("{}", vec![8u8; 96].redacted()));
+
+        set_redaction_enabled(false).unwrap();
+        assert_eq!(
+            format!("{}", signature.redacted()),
+            format!("{signature:?}")
+        );
+
+        set_redaction_enabled(true).unwrap();
+        assert_eq!(format!("{}", signature.redacted()), redacted);
+    }
+}
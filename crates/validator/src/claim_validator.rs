@@ -1,10 +1,12 @@
 use std::result::Result as StdResult;
 
 use hbbft::crypto::{PublicKey, Signature};
+use primitives::{ClassifiedError, ErrorCode, StakingParams};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use vrrb_core::{
     claim::{Claim, Eligibility},
-    staking::{Stake, StakeUpdate, MIN_STAKE_FARMER, MIN_STAKE_VALIDATOR},
+    staking::{Stake, StakeUpdate},
+    transactions::RegisterValidator,
 };
 
 pub type Result<T> = StdResult<T, ClaimValidatorError>;
@@ -42,10 +44,29 @@ pub enum ClaimValidatorError {
     Jailed,
 }
 
-#[derive(Debug, Clone)]
-pub struct ClaimValidator;
+impl ClassifiedError for ClaimValidatorError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ClaimValidatorError::QuorumKeyMissing
+            | ClaimValidatorError::InvalidQuorumKey
+            | ClaimValidatorError::InvalidStakeCertificate => ErrorCode::InvalidSignature,
+            _ => ErrorCode::InvalidInput,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClaimValidator {
+    staking: StakingParams,
+}
 
 impl ClaimValidator {
+    /// Creates a validator enforcing the minimum stakes of the given chain
+    /// spec.
+    pub fn new(staking: StakingParams) -> Self {
+        Self { staking }
+    }
+
     /// The function validates a claim by checking if it is eligible, has enough
     /// stake, and verifying the stake transactions and certificates.
     ///
@@ -66,7 +87,7 @@ impl ClaimValidator {
         }
         match claim.eligibility {
             Eligibility::Harvester => {
-                if claim.get_stake() < MIN_STAKE_VALIDATOR {
+                if claim.total_stake() < self.staking.min_stake_validator {
                     return Err(ClaimValidatorError::NotEnoughStake(
                         claim.eligibility.to_string(),
                     ));
@@ -74,7 +95,7 @@ impl ClaimValidator {
             },
             Eligibility::Miner => {},
             Eligibility::Farmer => {
-                if claim.get_stake() < MIN_STAKE_FARMER {
+                if claim.total_stake() < self.staking.min_stake_farmer {
                     return Err(ClaimValidatorError::NotEnoughStake(
                         claim.eligibility.to_string(),
                     ));
@@ -129,6 +150,18 @@ impl ClaimValidator {
         Ok(())
     }
 
+    /// Checks that a node asking to join the validator set bonds at least
+    /// the minimum validator stake.
+    pub fn validate_registration(&self, registration: &RegisterValidator) -> Result<()> {
+        if registration.stake < self.staking.min_stake_validator {
+            return Err(ClaimValidatorError::NotEnoughStake(
+                Eligibility::Harvester.to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn validate_timestamp(&self, stake: &Stake) -> Result<()> {
         let timestamp = chrono::offset::Utc::now().timestamp();
         let stake_timestamp = stake.get_timestamp();
@@ -8,15 +8,24 @@ pub mod validator_core_manager;
 #[cfg(test)]
 mod tests {
 
-    use std::collections::HashMap;
+    use std::{collections::HashMap, time::Duration};
 
-    use primitives::{Address, Signature};
+    use primitives::{Address, Signature, TxnLimits};
     use rand::{rngs::StdRng, Rng};
     use secp256k1::ecdsa;
-    use vrrb_core::{account::Account, keypair::KeyPair};
-    use vrrb_core::transactions::{NewTransferArgs, TransactionKind, Transfer};
+    use vrrb_config::TxnValidityConfig;
+    use vrrb_core::{
+        account::{Account, UpdateArgs},
+        keypair::KeyPair,
+    };
+    use vrrb_core::transactions::{
+        AccountUpdate, NewAccountUpdateArgs, NewTransferArgs, Token, TransactionKind, Transfer,
+    };
 
-    use crate::validator_core_manager::ValidatorCoreManager;
+    use crate::{
+        txn_validator::{TxnValidator, TxnValidatorError},
+        validator_core_manager::ValidatorCoreManager,
+    };
 
     // TODO: Use proper txns when there will be proper txn validation
     // implemented
@@ -36,6 +45,10 @@ mod tests {
     }
 
     fn random_txn() -> TransactionKind {
+        txn_with_timestamp(0)
+    }
+
+    fn txn_with_timestamp(timestamp: i64) -> TransactionKind {
         let sender_kp = KeyPair::random();
         let recv_kp = KeyPair::random();
 
@@ -43,7 +56,7 @@ mod tests {
         let recv_address = Address::new(recv_kp.get_miner_public_key().clone());
 
         TransactionKind::Transfer(Transfer::new(NewTransferArgs {
-            timestamp: 0,
+            timestamp,
             sender_address: sender_address.clone(),
             sender_public_key: sender_kp.get_miner_public_key().clone(),
             receiver_address: recv_address.clone(),
@@ -52,6 +65,7 @@ mod tests {
             signature: mock_txn_signature(),
             validators: Some(HashMap::<String, bool>::new()),
             nonce: 0,
+            memo: None,
         }))
     }
 
@@ -80,4 +94,182 @@ mod tests {
         let validated = valcore_manager.validate(&account_state, batch);
         assert_eq!(validated, target);
     }
+
+    #[test]
+    fn txn_timestamps_must_fall_within_the_configured_window() {
+        let validator = TxnValidator::with_validity_config(TxnValidityConfig {
+            max_past_skew: Duration::from_secs(60),
+            max_future_skew: Duration::from_secs(10),
+        });
+        let now = 1_700_000_000;
+
+        for timestamp in [now - 60, now, now + 10] {
+            assert!(validator
+                .validate_timestamp_at(&txn_with_timestamp(timestamp), now)
+                .is_ok());
+        }
+
+        assert_eq!(
+            validator.validate_timestamp_at(&txn_with_timestamp(now + 11), now),
+            Err(TxnValidatorError::OutOfBoundsTimestamp(
+                now + 11,
+                now - 60,
+                now + 10
+            ))
+        );
+        assert!(validator
+            .validate_timestamp_at(&txn_with_timestamp(now - 61), now)
+            .is_err());
+        assert!(validator
+            .validate_timestamp_at(&txn_with_timestamp(0), now)
+            .is_err());
+    }
+
+    #[test]
+    fn only_admission_holds_txns_to_the_past_skew() {
+        let validator = TxnValidator::with_validity_config(TxnValidityConfig {
+            max_past_skew: Duration::from_secs(60),
+            max_future_skew: Duration::from_secs(10),
+        });
+        let now = 1_700_000_000;
+
+        assert!(validator
+            .validate_future_skew_at(&txn_with_timestamp(now - 3600), now)
+            .is_ok());
+        assert!(validator
+            .validate_timestamp_at(&txn_with_timestamp(now - 3600), now)
+            .is_err());
+        assert_eq!(
+            validator.validate_future_skew_at(&txn_with_timestamp(now + 11), now),
+            Err(TxnValidatorError::OutOfBoundsTimestamp(
+                now + 11,
+                1,
+                now + 10
+            ))
+        );
+    }
+
+    fn account_update(owner: &KeyPair, target: Address, credits: Option<u128>) -> TransactionKind {
+        let sender_address = Address::new(owner.get_miner_public_key().clone());
+
+        TransactionKind::UpdateAccount(AccountUpdate::new(NewAccountUpdateArgs {
+            timestamp: 0,
+            sender_address,
+            sender_public_key: owner.get_miner_public_key().clone(),
+            args: UpdateArgs {
+                address: target,
+                nonce: None,
+                credits,
+                debits: None,
+                storage: Some(Some("profile-key".to_string())),
+                code: None,
+                digests: None,
+            },
+            signature: mock_txn_signature(),
+            nonce: 1,
+        }))
+    }
+
+    #[test]
+    fn account_updates_are_limited_to_the_owners_metadata() {
+        let validator = TxnValidator::new();
+        let owner = KeyPair::random();
+        let owner_address = Address::new(owner.get_miner_public_key().clone());
+        let other_address = Address::new(KeyPair::random().get_miner_public_key().clone());
+
+        assert!(validator
+            .validate_account_update(&account_update(&owner, owner_address.clone(), None))
+            .is_ok());
+
+        assert_eq!(
+            validator.validate_account_update(&account_update(&owner, other_address.clone(), None)),
+            Err(TxnValidatorError::AccountNotOwned(
+                other_address.to_string()
+            ))
+        );
+
+        assert_eq!(
+            validator.validate_account_update(&account_update(&owner, owner_address, Some(100))),
+            Err(TxnValidatorError::AccountUpdateNotPermitted)
+        );
+
+        assert!(validator.validate_account_update(&random_txn()).is_ok());
+    }
+
+    fn txn_with_validators_and_token(validators: usize, token_symbol: &str) -> TransactionKind {
+        let sender_kp = KeyPair::random();
+        let recv_kp = KeyPair::random();
+
+        TransactionKind::Transfer(Transfer::new(NewTransferArgs {
+            timestamp: 0,
+            sender_address: Address::new(sender_kp.get_miner_public_key().clone()),
+            sender_public_key: sender_kp.get_miner_public_key().clone(),
+            receiver_address: Address::new(recv_kp.get_miner_public_key().clone()),
+            token: Some(Token {
+                symbol: token_symbol.to_string(),
+                ..Token::default()
+            }),
+            amount: 0,
+            signature: mock_txn_signature(),
+            validators: Some(
+                (0..validators)
+                    .map(|validator| (validator.to_string(), true))
+                    .collect(),
+            ),
+            nonce: 0,
+            memo: None,
+        }))
+    }
+
+    #[test]
+    fn txns_beyond_the_structural_limits_are_rejected() {
+        let validator = TxnValidator::new().with_txn_limits(TxnLimits {
+            max_txn_size: 4096,
+            max_validators: 2,
+            max_token_field_length: 4,
+            max_memo_size: 8,
+        });
+
+        assert!(validator
+            .validate_limits(&txn_with_validators_and_token(2, "VRRB"))
+            .is_ok());
+
+        assert_eq!(
+            validator.validate_limits(&txn_with_validators_and_token(3, "VRRB")),
+            Err(TxnValidatorError::LimitExceeded {
+                field: "validator list".to_string(),
+                size: 3,
+                max: 2,
+            })
+        );
+
+        assert!(matches!(
+            validator.validate_limits(&txn_with_validators_and_token(0, "VRRBX")),
+            Err(TxnValidatorError::LimitExceeded { field, .. }) if field == "token symbol"
+        ));
+
+        let mut with_memo = txn_with_validators_and_token(0, "VRRB");
+        if let TransactionKind::Transfer(transfer) = &mut with_memo {
+            transfer.memo = Some(vec![0; 9]);
+        }
+
+        assert_eq!(
+            validator.validate_limits(&with_memo),
+            Err(TxnValidatorError::LimitExceeded {
+                field: "memo".to_string(),
+                size: 9,
+                max: 8,
+            })
+        );
+
+        let tiny = TxnValidator::new().with_txn_limits(TxnLimits {
+            max_txn_size: 16,
+            ..TxnLimits::default()
+        });
+
+        assert!(matches!(
+            tiny.validate_limits(&random_txn()),
+            Err(TxnValidatorError::LimitExceeded { field, .. }) if field == "txn"
+        ));
+    }
 }
@@ -1,7 +1,27 @@
+use primitives::{ClassifiedError, ErrorCode};
+
+use crate::{claim_validator::ClaimValidatorError, txn_validator::TxnValidatorError};
+
 pub type Result<T> = std::result::Result<T, ValidatorError>;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ValidatorError {
+    #[error("invalid transaction: {0}")]
+    Transaction(#[from] TxnValidatorError),
+
+    #[error("invalid claim: {0}")]
+    Claim(#[from] ClaimValidatorError),
+
     #[error("validator error: {0}")]
     Other(String),
 }
+
+impl ClassifiedError for ValidatorError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ValidatorError::Transaction(err) => err.error_code(),
+            ValidatorError::Claim(err) => err.error_code(),
+            ValidatorError::Other(_) => ErrorCode::Internal,
+        }
+    }
+}
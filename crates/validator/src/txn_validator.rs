@@ -1,6 +1,7 @@
 use std::{collections::HashMap, result::Result as StdResult, str::FromStr};
 
-use primitives::Address;
+use primitives::{Address, Amount, AmountError, ClassifiedError, ErrorCode, TxnLimits};
+use vrrb_config::TxnValidityConfig;
 use vrrb_core::{account::Account, keypair::KeyPair};
 use vrrb_core::transactions::{Transaction, TransactionKind};
 
@@ -34,8 +35,8 @@ pub enum TxnValidatorError {
     #[error("invalid receiver address")]
     ReceiverAddressIncorrect,
 
-    #[error("timestamp {0} is outside of the permitted date range [0, {1}]")]
-    OutOfBoundsTimestamp(i64, i64),
+    #[error("timestamp {0} is outside of the permitted date range [{1}, {2}]")]
+    OutOfBoundsTimestamp(i64, i64, i64),
 
     #[error("value {0} is outside of the permitted range [{1}, {2}]")]
     OutOfBounds(String, String, String),
@@ -43,6 +44,9 @@ pub enum TxnValidatorError {
     #[error("invalid amount")]
     TxnAmountIncorrect,
 
+    #[error("{0}")]
+    Amount(#[from] AmountError),
+
     #[error("invalid signature")]
     TxnSignatureIncorrect,
 
@@ -54,16 +58,75 @@ pub enum TxnValidatorError {
 
     #[error("account not found within state state_snapshot: {0}")]
     AccountNotFound(String),
+
+    #[error("account {0} can only be updated by its owner")]
+    AccountNotOwned(String),
+
+    #[error("account updates may only change an account's metadata")]
+    AccountUpdateNotPermitted,
+
+    #[error("txn could not be serialized")]
+    Unserializable,
+
+    #[error("{field} is {size} long, more than the {max} permitted")]
+    LimitExceeded {
+        field: String,
+        size: usize,
+        max: usize,
+    },
+}
+
+impl ClassifiedError for TxnValidatorError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            TxnValidatorError::TxnSignatureIncorrect
+            | TxnValidatorError::TxnSignatureTresholdIncorrect => ErrorCode::InvalidSignature,
+            TxnValidatorError::NotFound | TxnValidatorError::AccountNotFound(_) => {
+                ErrorCode::NotFound
+            },
+            _ => ErrorCode::InvalidTransaction,
+        }
+    }
+}
+
+fn check_limit(field: &str, size: usize, max: usize) -> Result<()> {
+    if size > max {
+        return Err(TxnValidatorError::LimitExceeded {
+            field: field.to_string(),
+            size,
+            max,
+        });
+    }
+
+    Ok(())
 }
 
 #[derive(Debug, Clone, Default)]
-// TODO: make validator configurable
-pub struct TxnValidator;
+pub struct TxnValidator {
+    validity_config: TxnValidityConfig,
+    txn_limits: TxnLimits,
+}
 
 impl TxnValidator {
     /// Creates a new Txn validator
     pub fn new() -> TxnValidator {
-        TxnValidator
+        TxnValidator::default()
+    }
+
+    /// Creates a Txn validator that accepts timestamps within the window
+    /// described by `validity_config`
+    pub fn with_validity_config(validity_config: TxnValidityConfig) -> TxnValidator {
+        TxnValidator {
+            validity_config,
+            ..Default::default()
+        }
+    }
+
+    /// Holds txns to the structural limits of the chain they belong to
+    /// instead of the default ones
+    pub fn with_txn_limits(mut self, txn_limits: TxnLimits) -> TxnValidator {
+        self.txn_limits = txn_limits;
+        self
     }
 
     /// An entire Txn validator
@@ -72,18 +135,65 @@ impl TxnValidator {
         self.validate_structure(account_state, txn)
     }
 
+    /// Validates a txn submitted to the node before it's admitted to the
+    /// mempool. On top of [TxnValidator::validate], the txn can't be older
+    /// than the configured past skew
+    pub fn validate_admission(
+        &self,
+        account_state: &HashMap<Address, Account>,
+        txn: &TransactionKind,
+    ) -> Result<()> {
+        self.validate(account_state, txn)
+            .and_then(|_| self.validate_timestamp(txn))
+    }
+
     /// An entire Txn structure validator
     pub fn validate_structure(
         &self,
         account_state: &HashMap<Address, Account>,
         txn: &TransactionKind,
     ) -> Result<()> {
-        self.validate_amount(account_state, txn)
+        self.validate_limits(txn)
+            .and_then(|_| self.validate_amount(account_state, txn))
             .and_then(|_| self.validate_public_key(txn))
             .and_then(|_| self.validate_sender_address(txn))
             .and_then(|_| self.validate_receiver_address(txn))
             .and_then(|_| self.validate_signature(txn))
-            .and_then(|_| self.validate_timestamp(txn))
+            .and_then(|_| self.validate_future_skew(txn))
+            .and_then(|_| self.validate_account_update(txn))
+            .and_then(|_| self.validate_delegation(txn))
+            .and_then(|_| self.validate_parameter_change(txn))
+    }
+
+    /// Checks that a txn stays within the size and field limits of the chain
+    pub fn validate_limits(&self, txn: &TransactionKind) -> Result<()> {
+        let limits = &self.txn_limits;
+
+        let size =
+            bincode::serialized_size(txn).map_err(|_| TxnValidatorError::Unserializable)? as usize;
+        check_limit("txn", size, limits.max_txn_size)?;
+
+        let validators = txn.validators().map(|validators| validators.len());
+        check_limit(
+            "validator list",
+            validators.unwrap_or_default(),
+            limits.max_validators,
+        )?;
+
+        let token = txn.token();
+        check_limit(
+            "token name",
+            token.name.len(),
+            limits.max_token_field_length,
+        )?;
+        check_limit(
+            "token symbol",
+            token.symbol.len(),
+            limits.max_token_field_length,
+        )?;
+
+        let memo = txn.memo().map(|memo| memo.len());
+        check_limit("memo", memo.unwrap_or_default(), limits.max_memo_size)
     }
 
     /// Txn signature validator.
@@ -137,18 +247,118 @@ impl TxnValidator {
         }
     }
 
+    /// Checks that an account update was signed by the owner of the account
+    /// it targets and only changes that account's metadata. Other kinds of
+    /// txns pass through unchecked
+    pub fn validate_account_update(&self, txn: &TransactionKind) -> Result<()> {
+        let TransactionKind::UpdateAccount(update) = txn else {
+            return Ok(());
+        };
+
+        if update.args.address != update.sender_address
+            || Address::new(update.sender_public_key) != update.sender_address
+        {
+            return Err(TxnValidatorError::AccountNotOwned(
+                update.args.address.to_string(),
+            ));
+        }
+
+        if !update.is_metadata_only() {
+            return Err(TxnValidatorError::AccountUpdateNotPermitted);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a delegation bonds or releases some stake. Whether the
+    /// delegator holds enough of it is only known once the block including
+    /// the delegation is applied. Other kinds of txns pass through unchecked
+    pub fn validate_delegation(&self, txn: &TransactionKind) -> Result<()> {
+        let TransactionKind::Delegation(delegation) = txn else {
+            return Ok(());
+        };
+
+        if delegation.stake() == 0 {
+            return Err(TxnValidatorError::TxnAmountIncorrect);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a parameter change stays within the bounds of its
+    /// parameter, and that its reason fits in a memo. Its quorum signature is
+    /// only checked against the harvester quorum once the block including it
+    /// is applied. Other kinds of txns pass through unchecked
+    pub fn validate_parameter_change(&self, txn: &TransactionKind) -> Result<()> {
+        let TransactionKind::ParameterChange(change) = txn else {
+            return Ok(());
+        };
+
+        let proposal = &change.proposal;
+        let bounds = proposal.parameter.bounds();
+
+        if !bounds.contains(&proposal.value) {
+            return Err(TxnValidatorError::OutOfBounds(
+                proposal.value.to_string(),
+                bounds.start().to_string(),
+                bounds.end().to_string(),
+            ));
+        }
+
+        check_limit(
+            "parameter change reason",
+            proposal.reason.len(),
+            self.txn_limits.max_memo_size,
+        )
+    }
+
     /// Txn timestamp validator
     pub fn validate_timestamp(&self, txn: &TransactionKind) -> Result<()> {
-        let timestamp = chrono::offset::Utc::now().timestamp();
+        self.validate_timestamp_at(txn, chrono::offset::Utc::now().timestamp())
+    }
+
+    /// Checks that the txn isn't dated further ahead than the configured
+    /// future skew. Unlike [TxnValidator::validate_timestamp], how long ago
+    /// the txn was created doesn't matter, so txns that waited in the mempool
+    /// or come back from a rolled back block stay valid
+    pub fn validate_future_skew(&self, txn: &TransactionKind) -> Result<()> {
+        self.validate_future_skew_at(txn, chrono::offset::Utc::now().timestamp())
+    }
 
-        // TODO: revisit seconds vs nanoseconds for timestamp
-        // let timestamp = duration.as_nanos();
-        if txn.timestamp() > 0 && txn.timestamp() < timestamp {
+    /// Checks that the txn isn't dated further ahead of `now` than the
+    /// configured future skew
+    pub fn validate_future_skew_at(&self, txn: &TransactionKind, now: i64) -> Result<()> {
+        let max_future_skew = self.validity_config.max_future_skew.as_secs() as i64;
+        let latest = now.saturating_add(max_future_skew);
+
+        if (1..=latest).contains(&txn.timestamp()) {
             Ok(())
         } else {
             Err(TxnValidatorError::OutOfBoundsTimestamp(
                 txn.timestamp(),
-                timestamp,
+                1,
+                latest,
+            ))
+        }
+    }
+
+    /// Checks that the txn timestamp falls within the configured window
+    /// around `now`
+    // TODO: revisit seconds vs nanoseconds for timestamp
+    pub fn validate_timestamp_at(&self, txn: &TransactionKind, now: i64) -> Result<()> {
+        let max_past_skew = self.validity_config.max_past_skew.as_secs() as i64;
+        let max_future_skew = self.validity_config.max_future_skew.as_secs() as i64;
+
+        let earliest = now.saturating_sub(max_past_skew).max(1);
+        let latest = now.saturating_add(max_future_skew);
+
+        if (earliest..=latest).contains(&txn.timestamp()) {
+            Ok(())
+        } else {
+            Err(TxnValidatorError::OutOfBoundsTimestamp(
+                txn.timestamp(),
+                earliest,
+                latest,
             ))
         }
     }
@@ -162,13 +372,14 @@ impl TxnValidator {
     ) -> Result<()> {
         let address = txn.sender_address();
         if let Ok(address) = secp256k1::PublicKey::from_str(address.to_string().as_str()) {
-            let account = account_state.get(&Address::new(address)).unwrap();
-            if (account.credits() - account.debits())
-                .checked_sub(txn.amount())
-                .is_none()
-            {
+            let address = Address::new(address);
+            let account = account_state
+                .get(&address)
+                .ok_or_else(|| TxnValidatorError::AccountNotFound(address.to_string()))?;
+
+            if account.checked_balance()? < Amount::new(txn.amount()) {
                 return Err(TxnValidatorError::TxnAmountIncorrect);
-            };
+            }
         } else {
             return Err(TxnValidatorError::SenderAddressIncorrect);
         }
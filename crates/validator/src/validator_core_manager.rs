@@ -1,7 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
-use primitives::Address;
+use primitives::{Address, ChainSpec};
 use rayon::ThreadPoolBuilder;
+use vrrb_config::TxnValidityConfig;
 use vrrb_core::{account::Account, claim::Claim};
 use vrrb_core::transactions::TransactionKind;
 
@@ -15,10 +16,28 @@ use crate::{
 #[derive(Debug)]
 pub struct ValidatorCoreManager {
     core_pool: rayon::ThreadPool,
+    claim_validator: ClaimValidator,
+    txn_validator: TxnValidator,
 }
 
 impl ValidatorCoreManager {
     pub fn new(cores: usize) -> Result<Self> {
+        Self::with_chain_spec(cores, &ChainSpec::default())
+    }
+
+    /// Creates a core manager whose cores validate claims against the
+    /// parameters of the given chain spec.
+    pub fn with_chain_spec(cores: usize, chain_spec: &ChainSpec) -> Result<Self> {
+        Self::with_config(cores, chain_spec, &TxnValidityConfig::default())
+    }
+
+    /// Creates a core manager whose cores validate claims against the given
+    /// chain spec, and txn timestamps against the given validity window.
+    pub fn with_config(
+        cores: usize,
+        chain_spec: &ChainSpec,
+        txn_validity_config: &TxnValidityConfig,
+    ) -> Result<Self> {
         let core_pool = ThreadPoolBuilder::new()
             .num_threads(cores)
             .build()
@@ -26,7 +45,12 @@ impl ValidatorCoreManager {
                 ValidatorError::Other(format!("Failed to create validator core pool: {err}"))
             })?;
 
-        Ok(Self { core_pool })
+        Ok(Self {
+            core_pool,
+            claim_validator: ClaimValidator::new(chain_spec.staking.clone()),
+            txn_validator: TxnValidator::with_validity_config(txn_validity_config.clone())
+                .with_txn_limits(chain_spec.txn_limits.clone()),
+        })
     }
 
     pub fn validate(
@@ -38,13 +62,32 @@ impl ValidatorCoreManager {
         self.core_pool.install(|| {
             let valcore = Core::new(
                 self.core_pool.current_thread_index().unwrap_or(0) as CoreId,
-                TxnValidator::new(),
-                ClaimValidator,
+                self.txn_validator.clone(),
+                self.claim_validator.clone(),
             );
             valcore.process_transactions(account_state, batch)
         })
     }
 
+    /// Validates txns submitted to the node before they are admitted to the
+    /// mempool, see [TxnValidator::validate_admission]
+    pub fn validate_admission(
+        &mut self,
+        account_state: &HashMap<Address, Account>,
+        batch: Vec<TransactionKind>,
+    ) -> HashSet<(TransactionKind, crate::txn_validator::Result<()>)> {
+        let txn_validator = self.txn_validator.clone();
+
+        self.validate(account_state, batch)
+            .into_iter()
+            .map(|(txn, validated)| {
+                let validated = validated.and_then(|_| txn_validator.validate_timestamp(&txn));
+
+                (txn, validated)
+            })
+            .collect()
+    }
+
     pub fn validate_claims(
         &mut self,
         claims: Vec<Claim>,
@@ -52,8 +95,8 @@ impl ValidatorCoreManager {
         self.core_pool.install(|| {
             let valcore = Core::new(
                 self.core_pool.current_thread_index().unwrap_or(0) as CoreId,
-                TxnValidator::new(),
-                ClaimValidator,
+                self.txn_validator.clone(),
+                self.claim_validator.clone(),
             );
             valcore.process_claims(claims)
         })
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_BLOCK_IMPORT_MAX_QUEUED_BLOCKS: usize = 1024;
+pub const DEFAULT_BLOCK_IMPORT_MAX_BUFFERED_BLOCKS: usize = 256;
+
+/// Configures the queue blocks received from peers are imported through.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockImportConfig {
+    /// Maximum number of blocks waiting to be imported. Blocks received while
+    /// the queue is full are dropped
+    pub max_queued_blocks: usize,
+
+    /// Maximum number of blocks held back until their parents are imported.
+    /// The longest held ones are dropped to make room for new ones
+    pub max_buffered_blocks: usize,
+}
+
+impl Default for BlockImportConfig {
+    fn default() -> Self {
+        Self {
+            max_queued_blocks: DEFAULT_BLOCK_IMPORT_MAX_QUEUED_BLOCKS,
+            max_buffered_blocks: DEFAULT_BLOCK_IMPORT_MAX_BUFFERED_BLOCKS,
+        }
+    }
+}
+
+impl BlockImportConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.max_queued_blocks == 0 {
+            return Err(ConfigError::Other(
+                "block import queue must hold at least one block".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -1,14 +1,29 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
 use primitives::KademliaPeerId;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BootstrapConfig {
     pub id: KademliaPeerId,
     pub udp_gossip_addr: SocketAddr,
     pub raptorq_gossip_addr: SocketAddr,
     pub kademlia_liveness_addr: SocketAddr,
+
+    /// Further addresses the node's UDP gossip can be reached at, in order of
+    /// preference
+    #[serde(default)]
+    pub alternate_udp_gossip_addrs: Vec<SocketAddr>,
+}
+
+impl BootstrapConfig {
+    /// Every address the node's UDP gossip can be reached at, starting with
+    /// `udp_gossip_addr`
+    pub fn udp_gossip_addrs(&self) -> Vec<SocketAddr> {
+        let mut addrs = vec![self.udp_gossip_addr];
+        addrs.extend(self.alternate_udp_gossip_addrs.iter().copied());
+        addrs
+    }
 }
 
 impl Default for BootstrapConfig {
@@ -19,6 +34,7 @@ impl Default for BootstrapConfig {
             raptorq_gossip_addr: addr,
             kademlia_liveness_addr: addr,
             udp_gossip_addr: addr,
+            alternate_udp_gossip_addrs: vec![],
         }
     }
 }
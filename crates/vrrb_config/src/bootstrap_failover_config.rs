@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BootstrapConfig, ConfigError};
+
+pub const DEFAULT_BOOTSTRAP_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+pub const DEFAULT_MAX_KNOWN_PEERS: usize = 32;
+
+/// Configures how a node picks the bootstrap node it joins the network
+/// through.
+///
+/// The node health checks `bootstrap_config` first, then every fallback
+/// bootstrap node in order, and joins through the first one that responds. If
+/// none does, it falls back to the peers it knew of during its last run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BootstrapFailoverConfig {
+    /// Bootstrap nodes tried, in order, when the primary bootstrap node is
+    /// unreachable
+    pub fallback_bootstrap_configs: Vec<BootstrapConfig>,
+
+    /// How long a bootstrap node has to respond to a health check
+    pub health_check_timeout: Duration,
+
+    /// Most peers of the node's address book tried as bootstrap candidates of
+    /// last resort, best ranked first. Zero disables falling back to them
+    pub max_known_peers: usize,
+}
+
+impl Default for BootstrapFailoverConfig {
+    fn default() -> Self {
+        Self {
+            fallback_bootstrap_configs: vec![],
+            health_check_timeout: DEFAULT_BOOTSTRAP_HEALTH_CHECK_TIMEOUT,
+            max_known_peers: DEFAULT_MAX_KNOWN_PEERS,
+        }
+    }
+}
+
+impl BootstrapFailoverConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.health_check_timeout.is_zero() {
+            return Err(ConfigError::Other(
+                "bootstrap health check timeout must be greater than zero".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
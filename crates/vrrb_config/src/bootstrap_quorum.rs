@@ -1,9 +1,12 @@
-use std::{collections::BTreeMap, net::SocketAddr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    net::SocketAddr,
+};
 
-use primitives::{KademliaPeerId, NodeId, NodeType, QuorumKind, ValidatorPublicKey};
+use primitives::{Epoch, KademliaPeerId, NodeId, NodeType, QuorumKind, ValidatorPublicKey};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct QuorumMember {
     pub node_id: NodeId,
     pub kademlia_peer_id: KademliaPeerId,
@@ -16,7 +19,7 @@ pub struct QuorumMember {
 
 pub type QuorumMembers = BTreeMap<NodeId, QuorumMember>;
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct QuorumMembershipConfig {
     pub quorum_kind: QuorumKind,
     pub quorum_members: BTreeMap<NodeId, QuorumMember>,
@@ -46,4 +49,49 @@ impl QuorumMembershipConfig {
     pub fn quorum_members(&self) -> QuorumMembers {
         self.quorum_members.clone()
     }
+
+    /// Applies a validator set diff to this quorum's membership. Removed
+    /// validators leave whichever quorum they belong to, while added ones only
+    /// join quorums of the diff's kind. Returns whether the membership
+    /// changed.
+    pub fn apply_diff(&mut self, diff: &ValidatorSetDiff) -> bool {
+        let previous = self.quorum_members.clone();
+
+        for node_id in diff.removed.iter() {
+            self.quorum_members.remove(node_id);
+        }
+
+        if diff.quorum_kind == self.quorum_kind {
+            self.quorum_members.extend(diff.added.clone());
+        }
+
+        self.quorum_members != previous
+    }
+}
+
+/// Changes made to the membership of a quorum when moving into a new epoch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ValidatorSetDiff {
+    /// Epoch from which the changes take effect
+    pub epoch: Epoch,
+
+    /// Kind of quorum the added validators join
+    pub quorum_kind: QuorumKind,
+    pub added: QuorumMembers,
+    pub removed: BTreeSet<NodeId>,
+}
+
+impl ValidatorSetDiff {
+    pub fn new(epoch: Epoch, quorum_kind: QuorumKind) -> Self {
+        Self {
+            epoch,
+            quorum_kind,
+            added: QuorumMembers::new(),
+            removed: BTreeSet::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
 }
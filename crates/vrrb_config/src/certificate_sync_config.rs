@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_CERTIFICATE_SYNC_INTERVAL_SECS: u64 = 10;
+pub const DEFAULT_CERTIFICATE_SYNC_LOOKBACK: usize = 128;
+pub const DEFAULT_CERTIFICATE_SYNC_MAX_BLOCKS_PER_REQUEST: usize = 32;
+
+/// Configures the periodic recovery of convergence block certificates from
+/// quorum peers, through which nodes recover certificates whose broadcast they
+/// missed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateSyncConfig {
+    /// How often the node looks for uncertified convergence blocks and asks
+    /// its peers for their certificates
+    pub interval: Duration,
+
+    /// How many of the most recent convergence blocks are checked for a
+    /// missing certificate
+    pub lookback: usize,
+
+    /// Maximum number of block certificates requested from, or served to, a
+    /// peer at once
+    pub max_blocks_per_request: usize,
+}
+
+impl Default for CertificateSyncConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(DEFAULT_CERTIFICATE_SYNC_INTERVAL_SECS),
+            lookback: DEFAULT_CERTIFICATE_SYNC_LOOKBACK,
+            max_blocks_per_request: DEFAULT_CERTIFICATE_SYNC_MAX_BLOCKS_PER_REQUEST,
+        }
+    }
+}
+
+impl CertificateSyncConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.interval.is_zero() {
+            return Err(ConfigError::Other(
+                "certificate sync interval must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.max_blocks_per_request == 0 {
+            return Err(ConfigError::Other(
+                "certificate sync must request at least one block".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
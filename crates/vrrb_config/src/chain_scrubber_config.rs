@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_CHAIN_SCRUB_INTERVAL_SECS: u64 = 60;
+pub const DEFAULT_CHAIN_SCRUB_BATCH_SIZE: usize = 512;
+pub const DEFAULT_MAX_CHAIN_RECORDS_PER_REQUEST: usize = 64;
+
+/// Configures the background scrubbing of the chain data a node persisted,
+/// which checks stored records against their hashes a batch at a time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainScrubberConfig {
+    /// How often the node scrubs a batch of records
+    pub interval: Duration,
+
+    /// Number of records checked per batch. Kept small so scrubbing doesn't
+    /// compete with block processing for the disk
+    pub batch_size: usize,
+
+    /// Maximum number of corrupted records asked of a peer at once
+    pub max_records_per_request: usize,
+}
+
+impl Default for ChainScrubberConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(DEFAULT_CHAIN_SCRUB_INTERVAL_SECS),
+            batch_size: DEFAULT_CHAIN_SCRUB_BATCH_SIZE,
+            max_records_per_request: DEFAULT_MAX_CHAIN_RECORDS_PER_REQUEST,
+        }
+    }
+}
+
+impl ChainScrubberConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.interval.is_zero() {
+            return Err(ConfigError::Other(
+                "chain scrub interval must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.batch_size == 0 {
+            return Err(ConfigError::Other(
+                "chain scrub batches must check at least one record".to_string(),
+            ));
+        }
+
+        if self.max_records_per_request == 0 {
+            return Err(ConfigError::Other(
+                "chain record requests must ask for at least one record".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
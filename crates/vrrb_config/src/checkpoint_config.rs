@@ -0,0 +1,43 @@
+use primitives::Round;
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_CHECKPOINT_INTERVAL_ROUNDS: Round = 100;
+
+/// Configures the quorum-signed checkpoints nodes export for bridges and
+/// other external verifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointConfig {
+    pub enabled: bool,
+
+    /// A checkpoint is produced for every certified convergence block whose
+    /// round is a multiple of this interval
+    pub interval_rounds: Round,
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_rounds: DEFAULT_CHECKPOINT_INTERVAL_ROUNDS,
+        }
+    }
+}
+
+impl CheckpointConfig {
+    /// Returns whether a checkpoint is due for a block certified at `round`
+    pub fn is_due(&self, round: Round) -> bool {
+        self.enabled && self.interval_rounds > 0 && round % self.interval_rounds == 0
+    }
+
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.enabled && self.interval_rounds == 0 {
+            return Err(ConfigError::Other(
+                "checkpoints must be at least one round apart".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
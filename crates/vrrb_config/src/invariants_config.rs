@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+/// How thoroughly the ledger invariants are checked after each block is
+/// applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvariantCheckMode {
+    Off,
+
+    /// Checks token supply conservation and the consistency of the claim
+    /// store, which costs as much as the block is large
+    Debug,
+
+    /// Also rebuilds the state trie from scratch to check its root is
+    /// reproducible, which costs as much as the state is large
+    Paranoid,
+}
+
+impl Default for InvariantCheckMode {
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            Self::Debug
+        } else {
+            Self::Off
+        }
+    }
+}
+
+/// Configures the ledger invariants checked once a block is applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvariantsConfig {
+    pub mode: InvariantCheckMode,
+
+    /// Whether the node stops applying blocks, mining and voting once an
+    /// invariant is violated, instead of only raising an alert
+    pub halt_on_violation: bool,
+}
+
+impl InvariantsConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.mode != InvariantCheckMode::Off
+    }
+
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.halt_on_violation && !self.is_enabled() {
+            return Err(ConfigError::Other(
+                "halting on invariant violations requires invariant checks".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -1,16 +1,60 @@
+pub mod block_import_config;
 mod bootstrap;
+pub mod bootstrap_failover_config;
 pub mod bootstrap_quorum;
+pub mod certificate_sync_config;
+pub mod chain_scrubber_config;
+pub mod checkpoint_config;
+pub mod invariants_config;
+pub mod mailbox_config;
+pub mod mempool_admission_config;
+pub mod mempool_compaction_config;
+pub mod mempool_sync_config;
+pub mod message_credits_config;
+pub mod network_address_config;
 mod node_config;
+pub mod notification_config;
+pub mod peer_registration_config;
+pub mod peer_store_config;
+pub mod preflight_config;
+pub mod public_rpc_config;
+pub mod rebroadcast_config;
+pub mod replication_config;
 pub mod result;
+pub mod round_gc_config;
+pub mod signing_pool_config;
 pub mod test_utils;
 pub mod threshold_config;
+pub mod txn_validity_config;
 
+pub use block_import_config::*;
 pub use bootstrap::*;
+pub use bootstrap_failover_config::*;
 pub use bootstrap_quorum::*;
+pub use certificate_sync_config::*;
+pub use chain_scrubber_config::*;
+pub use checkpoint_config::*;
+pub use invariants_config::*;
+pub use mailbox_config::*;
+pub use mempool_admission_config::*;
+pub use mempool_compaction_config::*;
+pub use mempool_sync_config::*;
+pub use message_credits_config::*;
+pub use network_address_config::*;
 pub use node_config::*;
+pub use notification_config::*;
+pub use peer_registration_config::*;
+pub use peer_store_config::*;
+pub use preflight_config::*;
+pub use public_rpc_config::*;
+pub use rebroadcast_config::*;
+pub use replication_config::*;
 pub use result::*;
+pub use round_gc_config::*;
+pub use signing_pool_config::*;
 pub use test_utils::*;
 pub use threshold_config::*;
+pub use txn_validity_config::*;
 
 #[cfg(test)]
 mod tests {
@@ -65,4 +109,378 @@ mod tests {
         let valid_config = valid_threshold_config();
         valid_config.validate().unwrap();
     }
+
+    #[test]
+    fn thresholds_must_keep_quorums_safe_and_live() {
+        let config = |threshold| ThresholdConfig {
+            upper_bound: 7,
+            threshold,
+        };
+
+        // NOTE: a quorum of 7 tolerates 2 faulty members, leaving 5 honest ones
+        assert!(matches!(
+            config(1).check_quorum_safety(7),
+            Err(QuorumSafetyViolation::Unsafe { faulty: 2, .. })
+        ));
+        assert!(config(2).check_quorum_safety(7).is_ok());
+        assert!(config(4).check_quorum_safety(7).is_ok());
+        assert!(matches!(
+            config(5).check_quorum_safety(7),
+            Err(QuorumSafetyViolation::NotLive { honest: 5, .. })
+        ));
+
+        assert!(config(0).check_quorum_safety(3).is_ok());
+        assert!(valid_threshold_config().check_quorum_safety(4).is_ok());
+    }
+
+    #[test]
+    fn mailbox_config_uses_overrides_when_present() {
+        let mut config = MailboxConfig::default();
+        config.with_capacity("network-events", 50);
+
+        assert_eq!(config.capacity_for("network-events"), 50);
+        assert_eq!(config.high_watermark_for("network-events"), 40);
+        assert_eq!(
+            config.capacity_for("runtime-events"),
+            DEFAULT_MAILBOX_CAPACITY
+        );
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn mempool_sync_config_rejects_a_zero_interval() {
+        let mut config = MempoolSyncConfig::default();
+        config.validate().unwrap();
+
+        config.interval = std::time::Duration::ZERO;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn mempool_admission_fees_double_past_the_escalation_threshold() {
+        let config = MempoolAdmissionConfig {
+            max_pending_per_sender: 10,
+            fee_escalation_threshold: 4,
+            escalated_base_fee: 100,
+            ..Default::default()
+        };
+        config.validate().unwrap();
+
+        assert_eq!(config.required_fee(3), 0);
+        assert_eq!(config.required_fee(4), 100);
+        assert_eq!(config.required_fee(6), 400);
+        assert_eq!(config.required_fee(4 + 200), u128::MAX);
+
+        let invalid = MempoolAdmissionConfig {
+            fee_escalation_threshold: 11,
+            ..config
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn signing_pool_config_rejects_empty_pools() {
+        SigningPoolConfig::default().validate().unwrap();
+
+        let no_workers = SigningPoolConfig {
+            workers: 0,
+            ..Default::default()
+        };
+        assert!(no_workers.validate().is_err());
+
+        let no_queue = SigningPoolConfig {
+            queue_capacity: 0,
+            ..Default::default()
+        };
+        assert!(no_queue.validate().is_err());
+    }
+
+    #[test]
+    fn certificate_sync_config_rejects_empty_requests() {
+        let mut config = CertificateSyncConfig::default();
+        config.validate().unwrap();
+
+        config.max_blocks_per_request = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn chain_scrubber_config_rejects_empty_batches() {
+        let mut config = ChainScrubberConfig::default();
+        config.validate().unwrap();
+
+        config.batch_size = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn chain_scrubber_config_rejects_empty_record_requests() {
+        let mut config = ChainScrubberConfig::default();
+        config.max_records_per_request = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn mempool_compaction_config_rejects_reclaiming_nothing() {
+        let mut config = MempoolCompactionConfig::default();
+        config.validate().unwrap();
+
+        config.min_reclaimable_txns = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn round_gc_config_rejects_a_zero_round_age() {
+        let mut config = RoundGcConfig::default();
+        config.validate().unwrap();
+
+        config.max_round_age = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn peer_registration_config_rejects_zero_attempts_or_sources() {
+        let mut config = PeerRegistrationConfig::default();
+        config.validate().unwrap();
+
+        config.max_failed_attempts = 0;
+        assert!(config.validate().is_err());
+
+        let untracked = PeerRegistrationConfig {
+            max_tracked_sources: 0,
+            ..Default::default()
+        };
+        assert!(untracked.validate().is_err());
+    }
+
+    #[test]
+    fn block_import_config_rejects_an_empty_queue() {
+        let mut config = BlockImportConfig::default();
+        config.validate().unwrap();
+
+        config.max_queued_blocks = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn peer_store_config_rejects_an_empty_address_book() {
+        let mut config = PeerStoreConfig::default();
+        config.validate().unwrap();
+
+        config.max_peers = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn rebroadcast_backoff_doubles_up_to_its_cap() {
+        let config = RebroadcastConfig {
+            initial_backoff: std::time::Duration::from_secs(1),
+            max_backoff: std::time::Duration::from_secs(5),
+            ..Default::default()
+        };
+        config.validate().unwrap();
+
+        assert_eq!(config.backoff(1), std::time::Duration::from_secs(1));
+        assert_eq!(config.backoff(3), std::time::Duration::from_secs(4));
+        assert_eq!(config.backoff(4), std::time::Duration::from_secs(5));
+        assert_eq!(config.backoff(u32::MAX), std::time::Duration::from_secs(5));
+
+        let no_attempts = RebroadcastConfig {
+            max_attempts: 0,
+            ..Default::default()
+        };
+        assert!(no_attempts.validate().is_err());
+    }
+
+    #[test]
+    fn public_rpc_config_requires_distinct_api_keys_once_enabled() {
+        let mut config = PublicRpcConfig::default();
+        config.validate().unwrap();
+
+        config.enabled = true;
+        assert!(config.validate().is_err());
+
+        let api_key = RpcApiKey {
+            name: "explorer".to_string(),
+            key: "secret".to_string(),
+            requests_per_window: None,
+            max_concurrent_requests: None,
+        };
+        config.api_keys.push(api_key.clone());
+        config.validate().unwrap();
+
+        config.allowed_methods.push("getNodeType".to_string());
+        assert!(config.validate().is_err());
+        config.allowed_methods.pop();
+
+        config.api_keys.push(RpcApiKey {
+            name: "wallet".to_string(),
+            ..api_key
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn message_credits_config_rejects_unaffordable_messages() {
+        let mut config = MessageCreditsConfig::default();
+        config.validate().unwrap();
+
+        config.message_cost = config.max_credits + 1;
+        assert!(config.validate().is_err());
+
+        let free_messages = MessageCreditsConfig {
+            message_cost: 0,
+            ..Default::default()
+        };
+        assert!(free_messages.validate().is_err());
+
+        let untracked = MessageCreditsConfig {
+            max_tracked_peers: 0,
+            ..Default::default()
+        };
+        assert!(untracked.validate().is_err());
+    }
+
+    #[test]
+    fn bootstrap_failover_config_rejects_a_zero_timeout() {
+        let mut config = BootstrapFailoverConfig::default();
+        config.validate().unwrap();
+
+        config.health_check_timeout = std::time::Duration::ZERO;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn notification_config_only_notifies_selected_events_once_enabled() {
+        let mut config = NotificationConfig::default();
+        config.validate().unwrap();
+
+        assert!(!config.notifies(NotificationKind::DutiesMissed));
+
+        config
+            .webhooks
+            .push("https://alerts.example.com/vrrb".to_string());
+        config.events.remove(&NotificationKind::DutiesMissed);
+
+        assert!(config.notifies(NotificationKind::EquivocationDetected));
+        assert!(!config.notifies(NotificationKind::DutiesMissed));
+        config.validate().unwrap();
+
+        config.webhooks.push("alerts.example.com".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn mailbox_config_rejects_empty_mailboxes() {
+        let mut config = MailboxConfig::default();
+        config.with_capacity("network-events", 0);
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn dual_stack_nodes_listen_on_both_families() {
+        let unspecified: SocketAddr = "0.0.0.0:9000".parse().unwrap();
+        let loopback: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+
+        let config = NetworkAddressConfig {
+            dual_stack: true,
+            advertised_udp_gossip_addrs: vec![
+                "[2001:db8::1]:0".parse().unwrap(),
+                "[2001:db8::2]:9100".parse().unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(config.bind_addr(unspecified), "[::]:9000".parse().unwrap());
+        assert_eq!(config.bind_addr(loopback), loopback);
+        assert_eq!(
+            NetworkAddressConfig::default().bind_addr(unspecified),
+            unspecified
+        );
+
+        // NOTE: advertised addresses without a port take the one bound to
+        assert_eq!(
+            config.advertised_udp_gossip_addrs(loopback),
+            vec![
+                "[2001:db8::1]:9000".parse::<SocketAddr>().unwrap(),
+                "[2001:db8::2]:9100".parse::<SocketAddr>().unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn invariants_config_only_halts_when_checking_invariants() {
+        let config = InvariantsConfig {
+            mode: InvariantCheckMode::Off,
+            halt_on_violation: true,
+        };
+
+        assert!(config.validate().is_err());
+        assert!(InvariantsConfig {
+            mode: InvariantCheckMode::Paranoid,
+            ..config
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn checkpoints_are_due_every_interval() {
+        let config = CheckpointConfig {
+            enabled: true,
+            interval_rounds: 10,
+        };
+
+        assert!(config.is_due(0));
+        assert!(!config.is_due(15));
+        assert!(config.is_due(20));
+        assert!(!CheckpointConfig {
+            enabled: false,
+            ..config
+        }
+        .is_due(20));
+
+        assert!(CheckpointConfig {
+            interval_rounds: 0,
+            ..config
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn replication_config_only_replicates_with_a_leader() {
+        let config = ReplicationConfig::default();
+
+        assert!(!config.is_replica());
+        assert!(config.validate().is_ok());
+
+        let config = ReplicationConfig {
+            leader: Some("127.0.0.1:9293".parse().unwrap()),
+            ..config
+        };
+
+        assert!(config.is_replica());
+        assert!(ReplicationConfig {
+            snapshot_interval_rounds: 0,
+            ..config
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn preflight_config_requires_some_clock_rollback_tolerance() {
+        let config = PreflightConfig::default();
+
+        assert!(config.enabled);
+        assert!(config.validate().is_ok());
+        assert!(PreflightConfig {
+            max_clock_rollback: std::time::Duration::ZERO,
+            ..config
+        }
+        .validate()
+        .is_err());
+    }
 }
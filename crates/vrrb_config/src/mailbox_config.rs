@@ -0,0 +1,93 @@
+use std::{collections::HashMap, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_MAILBOX_CAPACITY: usize = 1000;
+pub const DEFAULT_MAILBOX_HIGH_WATERMARK_PERCENT: u8 = 80;
+pub const DEFAULT_MAILBOX_SATURATION_ALERT_SECS: u64 = 10;
+
+/// The JSON-RPC control topic only ever carries one request at a time
+const JSON_RPC_API_CONTROL_CAPACITY: usize = 1;
+
+/// Configures the bounded mailboxes that sit between the event router and each
+/// actor running within a node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MailboxConfig {
+    /// Capacity used by mailboxes that have no explicit override
+    pub default_capacity: usize,
+
+    /// Per-topic capacity overrides, keyed by the topic the actor subscribes
+    /// to, e.g. `network-events`
+    pub capacities: HashMap<String, usize>,
+
+    /// Percentage of a mailbox's capacity past which low priority events are
+    /// shed instead of being delivered
+    pub high_watermark_percent: u8,
+
+    /// How long a mailbox has to remain above its high watermark before an
+    /// alert is raised
+    pub saturation_alert_after: Duration,
+}
+
+impl Default for MailboxConfig {
+    fn default() -> Self {
+        Self {
+            default_capacity: DEFAULT_MAILBOX_CAPACITY,
+            capacities: HashMap::from([(
+                String::from("json-rpc-api-control"),
+                JSON_RPC_API_CONTROL_CAPACITY,
+            )]),
+            high_watermark_percent: DEFAULT_MAILBOX_HIGH_WATERMARK_PERCENT,
+            saturation_alert_after: Duration::from_secs(DEFAULT_MAILBOX_SATURATION_ALERT_SECS),
+        }
+    }
+}
+
+impl MailboxConfig {
+    /// Returns the capacity configured for the mailbox subscribed to `topic`
+    pub fn capacity_for(&self, topic: &str) -> usize {
+        self.capacities
+            .get(topic)
+            .copied()
+            .unwrap_or(self.default_capacity)
+    }
+
+    /// Returns the number of queued events past which a mailbox subscribed to
+    /// `topic` is considered saturated
+    pub fn high_watermark_for(&self, topic: &str) -> usize {
+        let capacity = self.capacity_for(topic);
+        let watermark = capacity * self.high_watermark_percent as usize / 100;
+
+        watermark.max(1)
+    }
+
+    pub fn with_capacity(&mut self, topic: &str, capacity: usize) -> &mut Self {
+        self.capacities.insert(topic.to_string(), capacity);
+        self
+    }
+
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.default_capacity == 0 {
+            return Err(ConfigError::Other(
+                "default mailbox capacity must be greater than 0".to_string(),
+            ));
+        }
+
+        if let Some((topic, _)) = self.capacities.iter().find(|(_, cap)| **cap == 0) {
+            return Err(ConfigError::Other(format!(
+                "mailbox capacity for {topic} must be greater than 0"
+            )));
+        }
+
+        if self.high_watermark_percent == 0 || self.high_watermark_percent > 100 {
+            return Err(ConfigError::Other(format!(
+                "mailbox high watermark {}% must be within 1% and 100%",
+                self.high_watermark_percent
+            )));
+        }
+
+        Ok(())
+    }
+}
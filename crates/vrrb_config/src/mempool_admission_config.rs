@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use primitives::DEFAULT_BASE_FEE;
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_MEMPOOL_MAX_PENDING_PER_SENDER: usize = 128;
+pub const DEFAULT_MEMPOOL_FEE_ESCALATION_THRESHOLD: usize = 32;
+pub const DEFAULT_MEMPOOL_MAX_ADMISSIONS_PER_WINDOW: usize = 5000;
+pub const DEFAULT_MEMPOOL_MAX_RELAYED_ADMISSIONS_PER_WINDOW: usize = 5000;
+pub const DEFAULT_MEMPOOL_ADMISSION_WINDOW_SECS: u64 = 1;
+
+/// Bounds how fast transactions enter the mempool, and how much of it a single
+/// sender may occupy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolAdmissionConfig {
+    /// Maximum number of pending transactions a single sender may have in the
+    /// mempool
+    pub max_pending_per_sender: usize,
+
+    /// Number of pending transactions past which a sender has to pay
+    /// escalating fees to get more admitted
+    pub fee_escalation_threshold: usize,
+
+    /// Minimum fee of the first transaction past the escalation threshold.
+    /// It doubles with every further pending transaction of the same sender
+    pub escalated_base_fee: u128,
+
+    /// Maximum number of transactions submitted to the node itself admitted,
+    /// across all senders, per window
+    pub max_admissions_per_window: usize,
+
+    /// Maximum number of transactions relayed by peers admitted, across all
+    /// senders, per window. Counted apart from the node's own submissions so
+    /// neither can crowd out the other
+    #[serde(default = "default_max_relayed_admissions_per_window")]
+    pub max_relayed_admissions_per_window: usize,
+
+    /// Length of the window global admissions are counted over
+    pub admission_window: Duration,
+}
+
+impl Default for MempoolAdmissionConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_per_sender: DEFAULT_MEMPOOL_MAX_PENDING_PER_SENDER,
+            fee_escalation_threshold: DEFAULT_MEMPOOL_FEE_ESCALATION_THRESHOLD,
+            escalated_base_fee: DEFAULT_BASE_FEE,
+            max_admissions_per_window: DEFAULT_MEMPOOL_MAX_ADMISSIONS_PER_WINDOW,
+            max_relayed_admissions_per_window: DEFAULT_MEMPOOL_MAX_RELAYED_ADMISSIONS_PER_WINDOW,
+            admission_window: Duration::from_secs(DEFAULT_MEMPOOL_ADMISSION_WINDOW_SECS),
+        }
+    }
+}
+
+fn default_max_relayed_admissions_per_window() -> usize {
+    DEFAULT_MEMPOOL_MAX_RELAYED_ADMISSIONS_PER_WINDOW
+}
+
+impl MempoolAdmissionConfig {
+    /// Returns the minimum fee a sender with `pending` transactions already in
+    /// the mempool has to offer for another one to be admitted
+    pub fn required_fee(&self, pending: usize) -> u128 {
+        if pending < self.fee_escalation_threshold {
+            return 0;
+        }
+
+        let doublings = (pending - self.fee_escalation_threshold) as u32;
+
+        self.escalated_base_fee
+            .checked_mul(2u128.saturating_pow(doublings))
+            .unwrap_or(u128::MAX)
+    }
+
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.max_pending_per_sender == 0 {
+            return Err(ConfigError::Other(
+                "mempool must admit at least one pending transaction per sender".to_string(),
+            ));
+        }
+
+        if self.fee_escalation_threshold > self.max_pending_per_sender {
+            return Err(ConfigError::Other(
+                "mempool fee escalation threshold cannot exceed the per-sender cap".to_string(),
+            ));
+        }
+
+        if self.max_admissions_per_window == 0
+            || self.max_relayed_admissions_per_window == 0
+            || self.admission_window.is_zero()
+        {
+            return Err(ConfigError::Other(
+                "mempool admission rate limit must allow at least one transaction".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
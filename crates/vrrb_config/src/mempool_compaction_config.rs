@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_MEMPOOL_COMPACTION_INTERVAL_SECS: u64 = 300;
+pub const DEFAULT_MEMPOOL_COMPACTION_MAX_PENDING_TXNS: usize = 1000;
+pub const DEFAULT_MEMPOOL_COMPACTION_MIN_RECLAIMABLE_TXNS: usize = 10_000;
+
+/// Configures the periodic compaction of a node's mempool, through which
+/// long-running nodes give back the memory the pool grew into during bursts
+/// of txns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolCompactionConfig {
+    /// How often the node checks whether its mempool is worth compacting
+    pub interval: Duration,
+
+    /// The mempool is only compacted while it holds at most this many txns,
+    /// so it is rebuilt when it's idle and cheap to copy
+    pub max_pending_txns: usize,
+
+    /// The mempool is only compacted once it has room for at least this many
+    /// more txns than it needs
+    pub min_reclaimable_txns: usize,
+}
+
+impl Default for MempoolCompactionConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(DEFAULT_MEMPOOL_COMPACTION_INTERVAL_SECS),
+            max_pending_txns: DEFAULT_MEMPOOL_COMPACTION_MAX_PENDING_TXNS,
+            min_reclaimable_txns: DEFAULT_MEMPOOL_COMPACTION_MIN_RECLAIMABLE_TXNS,
+        }
+    }
+}
+
+impl MempoolCompactionConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.interval.is_zero() {
+            return Err(ConfigError::Other(
+                "mempool compaction interval must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.min_reclaimable_txns == 0 {
+            return Err(ConfigError::Other(
+                "mempool compaction must reclaim room for at least one txn".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
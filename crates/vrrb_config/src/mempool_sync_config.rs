@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_MEMPOOL_SYNC_INTERVAL_SECS: u64 = 5;
+pub const DEFAULT_MEMPOOL_SYNC_MAX_DIGESTS_PER_OFFER: usize = 1000;
+pub const DEFAULT_MEMPOOL_SYNC_MAX_TXNS_PER_PEER: usize = 500;
+pub const DEFAULT_MEMPOOL_SYNC_MAX_REQUESTS_PER_PEER: usize = 4;
+
+/// Configures the periodic reconciliation of a node's mempool with its peers',
+/// through which nodes recover transactions whose broadcast they missed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolSyncConfig {
+    /// How often the node reconciles its mempool with a random peer. Rate
+    /// caps are enforced over windows of the same length
+    pub interval: Duration,
+
+    /// Maximum number of digests offered in answer to a single sketch
+    pub max_digests_per_offer: usize,
+
+    /// Maximum number of transactions requested from, or served to, a single
+    /// peer per window
+    pub max_txns_per_peer: usize,
+
+    /// Maximum number of sketches or transaction requests answered for a
+    /// single peer per window
+    pub max_requests_per_peer: usize,
+}
+
+impl Default for MempoolSyncConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(DEFAULT_MEMPOOL_SYNC_INTERVAL_SECS),
+            max_digests_per_offer: DEFAULT_MEMPOOL_SYNC_MAX_DIGESTS_PER_OFFER,
+            max_txns_per_peer: DEFAULT_MEMPOOL_SYNC_MAX_TXNS_PER_PEER,
+            max_requests_per_peer: DEFAULT_MEMPOOL_SYNC_MAX_REQUESTS_PER_PEER,
+        }
+    }
+}
+
+impl MempoolSyncConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.interval.is_zero() {
+            return Err(ConfigError::Other(
+                "mempool sync interval must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.max_requests_per_peer == 0 {
+            return Err(ConfigError::Other(
+                "mempool sync must answer at least one request per peer".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_INITIAL_MESSAGE_CREDITS: u64 = 100;
+pub const DEFAULT_MAX_MESSAGE_CREDITS: u64 = 200;
+pub const DEFAULT_MESSAGE_COST: u64 = 1;
+pub const DEFAULT_MESSAGE_CREDITS_REFILL_PER_SEC: u64 = 10;
+pub const DEFAULT_CERTIFIED_CONTRIBUTION_REWARD: u64 = 20;
+pub const DEFAULT_MESSAGE_CREDITS_MAX_TRACKED_PEERS: usize = 4096;
+
+/// Configures the message credits peers spend to send consensus messages.
+///
+/// Every consensus message received from a peer costs it `message_cost`
+/// credits, charged to the address it arrived from. Credits slowly refill over time and are topped up whenever a
+/// peer's contribution gets certified. Messages from peers out of credits are
+/// dropped until their balance recovers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageCreditsConfig {
+    /// Credits granted to a peer the first time it sends a message
+    pub initial_credits: u64,
+
+    /// Most credits a peer can hold at once
+    pub max_credits: u64,
+
+    /// Credits spent per consensus message
+    pub message_cost: u64,
+
+    /// Credits regained per second regardless of the peer's behaviour
+    pub refill_per_sec: u64,
+
+    /// Credits granted whenever a block mined by the peer is certified
+    pub certified_contribution_reward: u64,
+
+    /// Number of peer addresses whose balance is remembered. The least
+    /// recently seen ones start over from `initial_credits` beyond it
+    #[serde(default = "default_max_tracked_peers")]
+    pub max_tracked_peers: usize,
+}
+
+fn default_max_tracked_peers() -> usize {
+    DEFAULT_MESSAGE_CREDITS_MAX_TRACKED_PEERS
+}
+
+impl Default for MessageCreditsConfig {
+    fn default() -> Self {
+        Self {
+            initial_credits: DEFAULT_INITIAL_MESSAGE_CREDITS,
+            max_credits: DEFAULT_MAX_MESSAGE_CREDITS,
+            message_cost: DEFAULT_MESSAGE_COST,
+            refill_per_sec: DEFAULT_MESSAGE_CREDITS_REFILL_PER_SEC,
+            certified_contribution_reward: DEFAULT_CERTIFIED_CONTRIBUTION_REWARD,
+            max_tracked_peers: DEFAULT_MESSAGE_CREDITS_MAX_TRACKED_PEERS,
+        }
+    }
+}
+
+impl MessageCreditsConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.message_cost == 0 {
+            return Err(ConfigError::Other(
+                "consensus messages must cost at least one credit".to_string(),
+            ));
+        }
+
+        if self.max_credits < self.message_cost {
+            return Err(ConfigError::Other(format!(
+                "max credits {} can't pay for a single message costing {}",
+                self.max_credits, self.message_cost
+            )));
+        }
+
+        if self.initial_credits > self.max_credits {
+            return Err(ConfigError::Other(format!(
+                "initial credits {} exceed max credits {}",
+                self.initial_credits, self.max_credits
+            )));
+        }
+
+        if self.max_tracked_peers == 0 {
+            return Err(ConfigError::Other(
+                "message credits must be tracked for at least one peer".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
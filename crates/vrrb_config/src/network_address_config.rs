@@ -0,0 +1,51 @@
+use std::net::SocketAddr;
+
+use primitives::{dual_stack_addr, AddressFamily};
+use serde::{Deserialize, Serialize};
+
+/// Configures the address families the node's network services listen on and
+/// the addresses it announces to peers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkAddressConfig {
+    /// Binds services configured to listen on the unspecified IPv4 address to
+    /// the unspecified IPv6 address instead, so they accept connections over
+    /// both IPv4 and IPv6
+    pub dual_stack: bool,
+
+    /// Family tried first when dialing peers reachable over both
+    pub preferred_family: AddressFamily,
+
+    /// Further addresses peers can reach the node's UDP gossip at, e.g. its
+    /// IPv6 address when it listens dual-stack. Announced after the address
+    /// the node binds to, in the order given. A port of 0 stands for the port
+    /// the node ends up bound to
+    pub advertised_udp_gossip_addrs: Vec<SocketAddr>,
+}
+
+impl NetworkAddressConfig {
+    /// Returns the address a service configured to listen on `addr` binds to
+    pub fn bind_addr(&self, addr: SocketAddr) -> SocketAddr {
+        if self.dual_stack {
+            dual_stack_addr(addr)
+        } else {
+            addr
+        }
+    }
+
+    /// Returns the extra UDP gossip addresses to announce once the node is
+    /// bound to `bound_addr`
+    pub fn advertised_udp_gossip_addrs(&self, bound_addr: SocketAddr) -> Vec<SocketAddr> {
+        self.advertised_udp_gossip_addrs
+            .iter()
+            .map(|addr| {
+                if addr.port() == 0 {
+                    SocketAddr::new(addr.ip(), bound_addr.port())
+                } else {
+                    *addr
+                }
+            })
+            .filter(|addr| *addr != bound_addr)
+            .collect()
+    }
+}
@@ -6,13 +6,22 @@ use std::{
 
 use derive_builder::Builder;
 use hbbft::sync_key_gen::PublicKey;
-use primitives::{KademliaPeerId, NodeId, NodeIdx, NodeType, DEFAULT_VRRB_DATA_DIR_PATH};
+use primitives::{
+    CertificationRetention, ChainSpec, DbBackend, KademliaPeerId, Locality, NodeId, NodeIdx,
+    NodeType, PruningMode, QuorumAssignmentStrategy, DEFAULT_RETAINED_TIMELINE_ROUNDS,
+    DEFAULT_VRRB_DATA_DIR_PATH,
+};
 use serde::Deserialize;
 use uuid::Uuid;
 use vrrb_core::keypair::Keypair;
 
 use crate::{
-    bootstrap::BootstrapConfig, BootstrapQuorumConfig, QuorumMembershipConfig, ThresholdConfig,
+    bootstrap::BootstrapConfig, BlockImportConfig, BootstrapFailoverConfig, BootstrapQuorumConfig,
+    CertificateSyncConfig, ChainScrubberConfig, CheckpointConfig, InvariantsConfig, MailboxConfig,
+    MempoolAdmissionConfig, MempoolCompactionConfig, MempoolSyncConfig, MessageCreditsConfig,
+    NetworkAddressConfig, NotificationConfig, PeerRegistrationConfig, PeerStoreConfig,
+    PreflightConfig, PublicRpcConfig, QuorumMembershipConfig, QuorumSafetyMode, RebroadcastConfig,
+    ReplicationConfig, RoundGcConfig, SigningPoolConfig, ThresholdConfig, TxnValidityConfig,
 };
 
 #[derive(Builder, Debug, Clone, Deserialize)]
@@ -71,6 +80,11 @@ pub struct NodeConfig {
     /// Address the node listens for JSON-RPC connections
     pub jsonrpc_server_address: SocketAddr,
 
+    #[builder(default)]
+    /// Authentication, rate limits and method allowlist of the JSON-RPC
+    /// server when exposed publicly
+    pub public_rpc_config: PublicRpcConfig,
+
     // TODO: refactor env-aware options
     #[builder(default = "false")]
     pub preload_mock_state: bool,
@@ -78,6 +92,10 @@ pub struct NodeConfig {
     /// Bootstrap configuration used to connect to a bootstrap node.
     pub bootstrap_config: Option<BootstrapConfig>,
 
+    #[builder(default)]
+    /// Bootstrap nodes tried when the one in `bootstrap_config` is unreachable
+    pub bootstrap_failover_config: BootstrapFailoverConfig,
+
     /// Non-bootstrap pre-configured quorum membership configuration
     pub quorum_config: Option<QuorumMembershipConfig>,
 
@@ -103,7 +121,138 @@ pub struct NodeConfig {
     /// services
     pub enable_block_indexing: bool,
 
+    #[builder(default = "false")]
+    /// Serves chain data over a read-only REST gateway on `http_api_address`
+    pub enable_rest_gateway: bool,
+
     pub threshold_config: ThresholdConfig,
+
+    #[builder(default)]
+    /// Whether the node refuses thresholds that leave its quorums unsafe or
+    /// unable to make progress, or only warns about them
+    pub quorum_safety_mode: QuorumSafetyMode,
+
+    #[builder(default)]
+    /// Capacities and overload policy of the mailboxes feeding each actor
+    pub mailbox_config: MailboxConfig,
+
+    #[builder(default)]
+    /// Pace and rate caps of the mempool reconciliation with peers
+    pub mempool_sync_config: MempoolSyncConfig,
+
+    #[builder(default)]
+    /// Per-sender caps, fee escalation and rate limit applied to transactions
+    /// entering the mempool
+    pub mempool_admission_config: MempoolAdmissionConfig,
+
+    #[builder(default)]
+    /// How often and when the mempool gives back the memory it grew into
+    pub mempool_compaction_config: MempoolCompactionConfig,
+
+    #[builder(default)]
+    /// How often and how much of the persisted chain data is checked for
+    /// corruption
+    pub chain_scrubber_config: ChainScrubberConfig,
+
+    #[builder(default)]
+    /// How far transaction timestamps may drift from the node's clock
+    pub txn_validity_config: TxnValidityConfig,
+
+    #[builder(default)]
+    /// Size of the worker pool threshold signatures are produced on
+    pub signing_pool_config: SigningPoolConfig,
+
+    #[builder(default)]
+    /// Pace of the recovery of missed block certificates from peers
+    pub certificate_sync_config: CertificateSyncConfig,
+
+    #[builder(default)]
+    /// Checks peers go through before they are added to the peer list
+    pub peer_registration_config: PeerRegistrationConfig,
+
+    #[builder(default)]
+    /// Size of the address book peers are remembered in across restarts
+    pub peer_store_config: PeerStoreConfig,
+
+    #[builder(default)]
+    /// Credits peers spend to send consensus messages
+    pub message_credits_config: MessageCreditsConfig,
+
+    #[builder(default)]
+    /// Backoff and bounds of the re-broadcast of unacknowledged consensus
+    /// messages
+    pub rebroadcast_config: RebroadcastConfig,
+
+    #[builder(default)]
+    /// Webhooks operators are notified through of critical events
+    pub notification_config: NotificationConfig,
+
+    #[builder(default)]
+    /// How long certificate shares of uncertified convergence blocks are kept
+    pub round_gc_config: RoundGcConfig,
+
+    #[builder(default)]
+    /// Bounds of the queue blocks received from peers are imported through
+    pub block_import_config: BlockImportConfig,
+
+    #[builder(default)]
+    /// Ledger invariants checked after each applied block
+    pub invariants_config: InvariantsConfig,
+
+    #[builder(default)]
+    /// How often quorum-signed checkpoints are exported for external
+    /// verifiers
+    pub checkpoint_config: CheckpointConfig,
+
+    #[builder(default)]
+    /// Leader this node follows when running as a read replica
+    pub replication_config: ReplicationConfig,
+
+    #[builder(default)]
+    /// Checks run on the node's environment before it starts
+    pub preflight_config: PreflightConfig,
+
+    #[builder(default)]
+    /// Parameters of the chain this node takes part in
+    pub chain_spec: ChainSpec,
+
+    #[builder(default)]
+    /// How many past rounds of state the node keeps queryable
+    pub pruning_mode: PruningMode,
+
+    #[builder(default)]
+    /// How many blocks' votes, partial signatures and certificates the node
+    /// keeps for audits
+    pub certification_retention: CertificationRetention,
+
+    #[builder(default = "DEFAULT_RETAINED_TIMELINE_ROUNDS")]
+    /// How many rounds' consensus timelines the node keeps for latency
+    /// post-mortems
+    pub consensus_timeline_rounds: usize,
+
+    #[builder(default)]
+    /// The key-value store the node's database persists to. Only backends
+    /// compiled into the node can be used
+    pub db_backend: DbBackend,
+
+    #[builder(default)]
+    /// Region and zone this node runs in, shared with peers when joining the
+    /// network
+    pub locality: Locality,
+
+    #[builder(default)]
+    /// How validators are spread across quorums when this node forms them
+    pub quorum_assignment_strategy: QuorumAssignmentStrategy,
+
+    #[builder(default)]
+    /// Address families the node listens on and addresses it announces to
+    /// peers
+    pub network_address_config: NetworkAddressConfig,
+
+    #[builder(default = "false")]
+    /// Logs secret keys, key shares, signatures and peer payloads as they
+    /// are instead of redacting them. Only honored in local environments
+    pub disable_log_redaction: bool,
 }
 
 impl NodeConfig {
@@ -168,15 +317,47 @@ impl Default for NodeConfig {
             http_api_version: String::from("v.0.1.0"),
             http_api_shutdown_timeout: None,
             jsonrpc_server_address: ipv4_localhost_with_random_port,
+            public_rpc_config: PublicRpcConfig::default(),
             preload_mock_state: false,
             bootstrap_config: None,
+            bootstrap_failover_config: BootstrapFailoverConfig::default(),
             quorum_config: None,
             bootstrap_quorum_config: None,
             keypair: Keypair::random(),
             gui: false,
             disable_networking: false,
             threshold_config: ThresholdConfig::default(),
+            quorum_safety_mode: QuorumSafetyMode::default(),
             enable_block_indexing: false,
+            enable_rest_gateway: false,
+            mailbox_config: MailboxConfig::default(),
+            mempool_sync_config: MempoolSyncConfig::default(),
+            mempool_admission_config: MempoolAdmissionConfig::default(),
+            mempool_compaction_config: MempoolCompactionConfig::default(),
+            chain_scrubber_config: ChainScrubberConfig::default(),
+            txn_validity_config: TxnValidityConfig::default(),
+            signing_pool_config: SigningPoolConfig::default(),
+            certificate_sync_config: CertificateSyncConfig::default(),
+            peer_registration_config: PeerRegistrationConfig::default(),
+            peer_store_config: PeerStoreConfig::default(),
+            message_credits_config: MessageCreditsConfig::default(),
+            rebroadcast_config: RebroadcastConfig::default(),
+            notification_config: NotificationConfig::default(),
+            round_gc_config: RoundGcConfig::default(),
+            block_import_config: BlockImportConfig::default(),
+            invariants_config: InvariantsConfig::default(),
+            checkpoint_config: CheckpointConfig::default(),
+            replication_config: ReplicationConfig::default(),
+            preflight_config: PreflightConfig::default(),
+            chain_spec: ChainSpec::default(),
+            pruning_mode: PruningMode::default(),
+            certification_retention: CertificationRetention::default(),
+            consensus_timeline_rounds: DEFAULT_RETAINED_TIMELINE_ROUNDS,
+            db_backend: DbBackend::default(),
+            locality: Locality::default(),
+            quorum_assignment_strategy: QuorumAssignmentStrategy::default(),
+            network_address_config: NetworkAddressConfig::default(),
+            disable_log_redaction: false,
         }
     }
 }
@@ -0,0 +1,120 @@
+use std::{collections::BTreeSet, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_NOTIFICATION_INITIAL_BACKOFF_MS: u64 = 1_000;
+pub const DEFAULT_NOTIFICATION_MAX_BACKOFF_MS: u64 = 60_000;
+pub const DEFAULT_NOTIFICATION_MAX_ATTEMPTS: u32 = 5;
+
+/// Critical events operators can be notified of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// The node was removed from its quorum
+    QuorumMembershipLost,
+
+    /// The node holds no certificate for recent convergence blocks
+    DutiesMissed,
+
+    /// A peer proposed two different blocks for the same round
+    EquivocationDetected,
+
+    /// Persisted chain data was found corrupted beyond repair
+    ChainDataCorrupted,
+}
+
+impl NotificationKind {
+    pub fn all() -> BTreeSet<NotificationKind> {
+        BTreeSet::from([
+            NotificationKind::QuorumMembershipLost,
+            NotificationKind::DutiesMissed,
+            NotificationKind::EquivocationDetected,
+            NotificationKind::ChainDataCorrupted,
+        ])
+    }
+}
+
+/// Configures the webhooks operators are notified through when their node
+/// runs into a critical event.
+///
+/// Every notification is a JSON payload signed with the node's key, posted to
+/// each webhook. Failed deliveries are retried once `initial_backoff`
+/// elapses, waiting twice as long before every further attempt, up to
+/// `max_backoff`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// URLs notifications are posted to. No notifications are sent when empty
+    pub webhooks: Vec<String>,
+
+    /// Critical events operators are notified of
+    pub events: BTreeSet<NotificationKind>,
+
+    /// Time waited before retrying a failed delivery for the first time
+    pub initial_backoff: Duration,
+
+    /// Longest time waited between two deliveries of the same notification
+    pub max_backoff: Duration,
+
+    /// Most times a notification is delivered to a webhook, counting the
+    /// first delivery
+    pub max_attempts: u32,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            webhooks: Vec::new(),
+            events: NotificationKind::all(),
+            initial_backoff: Duration::from_millis(DEFAULT_NOTIFICATION_INITIAL_BACKOFF_MS),
+            max_backoff: Duration::from_millis(DEFAULT_NOTIFICATION_MAX_BACKOFF_MS),
+            max_attempts: DEFAULT_NOTIFICATION_MAX_ATTEMPTS,
+        }
+    }
+}
+
+impl NotificationConfig {
+    /// Returns whether any notification is sent at all
+    pub fn is_enabled(&self) -> bool {
+        !self.webhooks.is_empty() && !self.events.is_empty()
+    }
+
+    /// Returns whether operators are notified of events of `kind`
+    pub fn notifies(&self, kind: NotificationKind) -> bool {
+        self.is_enabled() && self.events.contains(&kind)
+    }
+
+    pub fn validate(&self) -> crate::Result<()> {
+        if let Some(webhook) = self
+            .webhooks
+            .iter()
+            .find(|webhook| !webhook.starts_with("http://") && !webhook.starts_with("https://"))
+        {
+            return Err(ConfigError::Other(format!(
+                "webhook {webhook} is not an http or https URL"
+            )));
+        }
+
+        if self.initial_backoff.is_zero() {
+            return Err(ConfigError::Other(
+                "notification backoff must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.max_backoff < self.initial_backoff {
+            return Err(ConfigError::Other(format!(
+                "max notification backoff {:?} is shorter than the initial one {:?}",
+                self.max_backoff, self.initial_backoff
+            )));
+        }
+
+        if self.max_attempts == 0 {
+            return Err(ConfigError::Other(
+                "notifications must be delivered at least once".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
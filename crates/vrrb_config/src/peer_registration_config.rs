@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_PEER_REGISTRATION_MAX_FAILED_ATTEMPTS: u32 = 3;
+pub const DEFAULT_PEER_REGISTRATION_THROTTLE_SECS: u64 = 60;
+pub const DEFAULT_PEER_REGISTRATION_MAX_TRACKED_SOURCES: usize = 4096;
+
+/// Configures the checks peers go through before a node adds them to its
+/// peer list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerRegistrationConfig {
+    /// Whether peers must sign their registration with the key of a staked
+    /// claim known to the node. Members of the bootstrap quorum are exempt,
+    /// since no claim is staked before genesis
+    pub require_claim_proof: bool,
+
+    /// Number of failed registrations after which a peer is throttled
+    pub max_failed_attempts: u32,
+
+    /// How long registrations from a throttled source are ignored
+    pub throttle_period: Duration,
+
+    /// Number of source addresses whose failed registrations are remembered.
+    /// The least recently seen ones are forgotten beyond it
+    #[serde(default = "default_max_tracked_sources")]
+    pub max_tracked_sources: usize,
+}
+
+fn default_max_tracked_sources() -> usize {
+    DEFAULT_PEER_REGISTRATION_MAX_TRACKED_SOURCES
+}
+
+impl Default for PeerRegistrationConfig {
+    fn default() -> Self {
+        Self {
+            require_claim_proof: true,
+            max_failed_attempts: DEFAULT_PEER_REGISTRATION_MAX_FAILED_ATTEMPTS,
+            throttle_period: Duration::from_secs(DEFAULT_PEER_REGISTRATION_THROTTLE_SECS),
+            max_tracked_sources: DEFAULT_PEER_REGISTRATION_MAX_TRACKED_SOURCES,
+        }
+    }
+}
+
+impl PeerRegistrationConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.max_failed_attempts == 0 {
+            return Err(ConfigError::Other(
+                "peer registration must allow at least one attempt".to_string(),
+            ));
+        }
+
+        if self.max_tracked_sources == 0 {
+            return Err(ConfigError::Other(
+                "peer registration must track at least one source".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_PEER_STORE_MAX_PEERS: usize = 1_000;
+
+/// Configures the address book a node keeps of its peers across restarts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerStoreConfig {
+    /// Number of peers kept in the address book. The lowest scored peers are
+    /// evicted first once it is full
+    pub max_peers: usize,
+}
+
+impl Default for PeerStoreConfig {
+    fn default() -> Self {
+        Self {
+            max_peers: DEFAULT_PEER_STORE_MAX_PEERS,
+        }
+    }
+}
+
+impl PeerStoreConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.max_peers == 0 {
+            return Err(ConfigError::Other(
+                "peer store must keep at least one peer".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_MIN_FREE_DISK_SPACE: u64 = 1024 * 1024 * 1024;
+pub const DEFAULT_MAX_CLOCK_ROLLBACK: Duration = Duration::from_secs(5 * 60);
+
+/// Configures the checks a node runs on its environment before starting any
+/// of its actors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreflightConfig {
+    /// Whether the checks run at all
+    pub enabled: bool,
+
+    /// Free space the data dir's volume must have left, in bytes
+    pub min_free_disk_space: u64,
+
+    /// How far the system clock may lag behind the time the node last
+    /// started at before it is considered to have gone backwards
+    pub max_clock_rollback: Duration,
+}
+
+impl Default for PreflightConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_free_disk_space: DEFAULT_MIN_FREE_DISK_SPACE,
+            max_clock_rollback: DEFAULT_MAX_CLOCK_ROLLBACK,
+        }
+    }
+}
+
+impl PreflightConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.max_clock_rollback.is_zero() {
+            return Err(ConfigError::Other(
+                "max clock rollback must be greater than zero".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use std::{collections::HashSet, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_PUBLIC_RPC_REQUESTS_PER_WINDOW: u32 = 50;
+pub const DEFAULT_PUBLIC_RPC_RATE_WINDOW_SECS: u64 = 1;
+pub const DEFAULT_PUBLIC_RPC_MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// JSON-RPC methods served in public mode unless configured otherwise, by
+/// their namespaced name. Admin methods, methods handling private keys and
+/// subscriptions are left out.
+pub const DEFAULT_PUBLIC_RPC_METHODS: &[&str] = &[
+    "state_getNodeType",
+    "state_createTxn",
+    "state_exportUnsignedTxn",
+    "state_importSignedTxn",
+    "state_getTransaction",
+    "state_listTransactions",
+    "state_getAccount",
+    "state_getBalance",
+    "state_getProjectedAccount",
+    "state_getRound",
+    "state_getBlocks",
+    "state_getTransactionCount",
+    "state_getClaimsByAccountId",
+    "state_getClaimHashes",
+    "state_getClaims",
+    "state_getLastBlock",
+    "state_getAccountAtRound",
+    "state_getStateRoot",
+    "state_getStatePage",
+    "state_getBlockCertificationDetail",
+    "state_getQuorum",
+    "state_getCheckpoint",
+    "state_listCheckpoints",
+    "state_getEpochSummary",
+    "state_listEpochSummaries",
+    "state_getHaltLog",
+    "state_estimateTxnWeight",
+    "state_getTxnTrace",
+    "state_getDuties",
+    "state_getValidatorEarnings",
+    "state_getMempoolStats",
+    "state_getSyncStatus",
+];
+
+/// Credentials of a client of the public JSON-RPC server, along with limits
+/// overriding the defaults of [PublicRpcConfig] for that client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcApiKey {
+    /// Name the key's metrics are reported under, so the key itself never
+    /// shows up in them
+    pub name: String,
+
+    /// Secret clients send in the `x-api-key` header, or as a bearer token
+    pub key: String,
+
+    #[serde(default)]
+    pub requests_per_window: Option<u32>,
+
+    #[serde(default)]
+    pub max_concurrent_requests: Option<usize>,
+}
+
+/// Configures the public mode of the JSON-RPC server.
+///
+/// Once enabled, requests have to carry one of `api_keys`, may only call
+/// `allowed_methods`, given as `namespace_method` like clients call them, and are rate limited per key: at most
+/// `requests_per_window` requests per `rate_window`, with at most
+/// `max_concurrent_requests` of them in flight at once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicRpcConfig {
+    pub enabled: bool,
+    pub api_keys: Vec<RpcApiKey>,
+    pub allowed_methods: Vec<String>,
+    pub requests_per_window: u32,
+
+    /// Length of the window requests are counted over
+    pub rate_window: Duration,
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for PublicRpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_keys: vec![],
+            allowed_methods: DEFAULT_PUBLIC_RPC_METHODS
+                .iter()
+                .map(|method| method.to_string())
+                .collect(),
+            requests_per_window: DEFAULT_PUBLIC_RPC_REQUESTS_PER_WINDOW,
+            rate_window: Duration::from_secs(DEFAULT_PUBLIC_RPC_RATE_WINDOW_SECS),
+            max_concurrent_requests: DEFAULT_PUBLIC_RPC_MAX_CONCURRENT_REQUESTS,
+        }
+    }
+}
+
+impl PublicRpcConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.api_keys.is_empty() {
+            return Err(ConfigError::Other(
+                "public JSON-RPC mode requires at least one API key".to_string(),
+            ));
+        }
+
+        if self.rate_window.is_zero() {
+            return Err(ConfigError::Other(
+                "public JSON-RPC rate window must be greater than 0".to_string(),
+            ));
+        }
+
+        if let Some(method) = self
+            .allowed_methods
+            .iter()
+            .find(|method| !method.contains('_'))
+        {
+            return Err(ConfigError::Other(format!(
+                "allowed JSON-RPC method {method} is missing its namespace, e.g. state_{method}"
+            )));
+        }
+
+        let mut names = HashSet::new();
+        let mut keys = HashSet::new();
+
+        for api_key in self.api_keys.iter() {
+            if api_key.key.is_empty() {
+                return Err(ConfigError::Other(format!(
+                    "API key {} is empty",
+                    api_key.name
+                )));
+            }
+
+            if !names.insert(&api_key.name) || !keys.insert(&api_key.key) {
+                return Err(ConfigError::Other(format!(
+                    "API key {} is configured more than once",
+                    api_key.name
+                )));
+            }
+
+            let requests_per_window = api_key
+                .requests_per_window
+                .unwrap_or(self.requests_per_window);
+
+            let max_concurrent_requests = api_key
+                .max_concurrent_requests
+                .unwrap_or(self.max_concurrent_requests);
+
+            if requests_per_window == 0 || max_concurrent_requests == 0 {
+                return Err(ConfigError::Other(format!(
+                    "API key {} must be allowed at least one request",
+                    api_key.name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_REBROADCAST_CHECK_INTERVAL_MS: u64 = 500;
+pub const DEFAULT_REBROADCAST_INITIAL_BACKOFF_MS: u64 = 1_000;
+pub const DEFAULT_REBROADCAST_MAX_BACKOFF_MS: u64 = 30_000;
+pub const DEFAULT_REBROADCAST_MAX_ATTEMPTS: u32 = 5;
+pub const DEFAULT_REBROADCAST_MAX_PENDING: usize = 10_000;
+
+/// Configures the re-broadcast of point-to-point consensus messages peers
+/// haven't acknowledged.
+///
+/// A message is sent again once `initial_backoff` elapses without an
+/// acknowledgement, waiting twice as long before every further attempt, up to
+/// `max_backoff`. Messages still unacknowledged after `max_attempts` sends
+/// are given up on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RebroadcastConfig {
+    /// How often unacknowledged messages are checked for being due again
+    pub check_interval: Duration,
+
+    /// Time waited for an acknowledgement after the first send
+    pub initial_backoff: Duration,
+
+    /// Longest time waited between two sends of the same message
+    pub max_backoff: Duration,
+
+    /// Most times a message is sent, counting the first send
+    pub max_attempts: u32,
+
+    /// Most messages awaiting an acknowledgement at once. The ones sent
+    /// longest ago are given up on first
+    pub max_pending: usize,
+}
+
+impl Default for RebroadcastConfig {
+    fn default() -> Self {
+        Self {
+            check_interval: Duration::from_millis(DEFAULT_REBROADCAST_CHECK_INTERVAL_MS),
+            initial_backoff: Duration::from_millis(DEFAULT_REBROADCAST_INITIAL_BACKOFF_MS),
+            max_backoff: Duration::from_millis(DEFAULT_REBROADCAST_MAX_BACKOFF_MS),
+            max_attempts: DEFAULT_REBROADCAST_MAX_ATTEMPTS,
+            max_pending: DEFAULT_REBROADCAST_MAX_PENDING,
+        }
+    }
+}
+
+impl RebroadcastConfig {
+    /// Time waited for an acknowledgement after a message was sent for the
+    /// `attempt`th time
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.check_interval.is_zero() || self.initial_backoff.is_zero() {
+            return Err(ConfigError::Other(
+                "rebroadcast check interval and backoff must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.max_backoff < self.initial_backoff {
+            return Err(ConfigError::Other(format!(
+                "max rebroadcast backoff {:?} is shorter than the initial one {:?}",
+                self.max_backoff, self.initial_backoff
+            )));
+        }
+
+        if self.max_attempts == 0 || self.max_pending == 0 {
+            return Err(ConfigError::Other(
+                "consensus messages must be sent at least once".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
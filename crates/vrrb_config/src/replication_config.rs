@@ -0,0 +1,60 @@
+use std::{net::SocketAddr, time::Duration};
+
+use primitives::Round;
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_REPLICATION_SNAPSHOT_INTERVAL_ROUNDS: Round = 1000;
+pub const DEFAULT_REPLICATION_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Configures read replicas, nodes that keep their own copy of the state of
+/// a leader node by following its replication stream, without taking part in
+/// consensus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicationConfig {
+    /// JSON-RPC address of the node to replicate. The node runs as a read
+    /// replica when set
+    pub leader: Option<SocketAddr>,
+
+    /// The leader sends a fresh state snapshot every time this many rounds
+    /// have passed since the last one
+    pub snapshot_interval_rounds: Round,
+
+    /// How long the replica waits before reconnecting to the leader after
+    /// losing its stream
+    pub retry_interval: Duration,
+}
+
+impl Default for ReplicationConfig {
+    fn default() -> Self {
+        Self {
+            leader: None,
+            snapshot_interval_rounds: DEFAULT_REPLICATION_SNAPSHOT_INTERVAL_ROUNDS,
+            retry_interval: DEFAULT_REPLICATION_RETRY_INTERVAL,
+        }
+    }
+}
+
+impl ReplicationConfig {
+    /// Returns whether the node runs as a read replica
+    pub fn is_replica(&self) -> bool {
+        self.leader.is_some()
+    }
+
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.snapshot_interval_rounds == 0 {
+            return Err(ConfigError::Other(
+                "replication snapshots must be at least one round apart".to_string(),
+            ));
+        }
+
+        if self.retry_interval.is_zero() {
+            return Err(ConfigError::Other(
+                "replication retry interval must be greater than zero".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
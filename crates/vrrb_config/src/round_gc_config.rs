@@ -0,0 +1,35 @@
+use primitives::Round;
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_ROUND_GC_MAX_ROUND_AGE: Round = 16;
+
+/// Configures how long the consensus module holds on to the certificate
+/// shares of convergence blocks that haven't certified yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundGcConfig {
+    /// How many rounds behind the latest round seen by the node a round may
+    /// fall before it is abandoned
+    pub max_round_age: Round,
+}
+
+impl Default for RoundGcConfig {
+    fn default() -> Self {
+        Self {
+            max_round_age: DEFAULT_ROUND_GC_MAX_ROUND_AGE,
+        }
+    }
+}
+
+impl RoundGcConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.max_round_age == 0 {
+            return Err(ConfigError::Other(
+                "round gc must keep at least one round".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_SIGNING_POOL_WORKERS: usize = 4;
+pub const DEFAULT_SIGNING_POOL_QUEUE_CAPACITY: usize = 1024;
+
+/// Configures the worker pool that generates and aggregates threshold
+/// signature shares away from the node's event loop.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningPoolConfig {
+    /// Number of threads signing jobs run on
+    pub workers: usize,
+
+    /// Maximum number of signing jobs waiting for a worker. Jobs submitted
+    /// past it are rejected
+    pub queue_capacity: usize,
+}
+
+impl Default for SigningPoolConfig {
+    fn default() -> Self {
+        Self {
+            workers: DEFAULT_SIGNING_POOL_WORKERS,
+            queue_capacity: DEFAULT_SIGNING_POOL_QUEUE_CAPACITY,
+        }
+    }
+}
+
+impl SigningPoolConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.workers == 0 {
+            return Err(ConfigError::Other(
+                "signing pool must have at least 1 worker".to_string(),
+            ));
+        }
+
+        if self.queue_capacity == 0 {
+            return Err(ConfigError::Other(
+                "signing pool queue capacity must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -2,6 +2,58 @@ use serde::{Deserialize, Serialize};
 
 use crate::ConfigError;
 
+/// What a node does when its threshold leaves a quorum unsafe or unable to
+/// make progress.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuorumSafetyMode {
+    /// Refuses to start, or to join the quorum
+    #[default]
+    Enforce,
+
+    /// Only logs the violation. Meant for dev networks too small to
+    /// tolerate any faulty member
+    Warn,
+}
+
+/// Why a threshold leaves a quorum unsafe or unable to make progress.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum QuorumSafetyViolation {
+    #[error(
+        "a threshold of {threshold} lets {signers} of {quorum_size} members sign, but up to \
+         {faulty} of them may be faulty"
+    )]
+    Unsafe {
+        quorum_size: usize,
+        threshold: u16,
+        signers: usize,
+        faulty: usize,
+    },
+
+    #[error(
+        "a threshold of {threshold} needs {signers} of {quorum_size} members to sign, but only \
+         {honest} of them are sure to be honest"
+    )]
+    NotLive {
+        quorum_size: usize,
+        threshold: u16,
+        signers: usize,
+        honest: usize,
+    },
+}
+
+/// Returns the most faulty members a quorum of `quorum_size` tolerates,
+/// the largest `f` such that `quorum_size >= 3f + 1`
+pub fn max_faulty_members(quorum_size: usize) -> usize {
+    quorum_size.saturating_sub(1) / 3
+}
+
+/// Configures the threshold signatures of quorums.
+///
+/// `threshold` is the number of faulty members a quorum tolerates: a quorum
+/// signature combines the shares of `threshold + 1` members, so faulty
+/// members can't sign on their own. `upper_bound` is the most members a
+/// quorum may have.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Eq)]
 pub struct ThresholdConfig {
     pub upper_bound: u16,
@@ -37,4 +89,39 @@ impl ThresholdConfig {
         }
         Ok(())
     }
+
+    /// Number of members whose shares a quorum signature combines
+    pub fn signers(&self) -> usize {
+        self.threshold as usize + 1
+    }
+
+    /// Checks a quorum of `quorum_size` members signing with this threshold
+    /// stays safe and live with up to [max_faulty_members] faulty members:
+    /// every set of signers must include an honest member, and the honest
+    /// members must be able to sign without the faulty ones.
+    pub fn check_quorum_safety(&self, quorum_size: usize) -> Result<(), QuorumSafetyViolation> {
+        let faulty = max_faulty_members(quorum_size);
+        let honest = quorum_size - faulty;
+        let signers = self.signers();
+
+        if signers <= faulty {
+            return Err(QuorumSafetyViolation::Unsafe {
+                quorum_size,
+                threshold: self.threshold,
+                signers,
+                faulty,
+            });
+        }
+
+        if signers > honest {
+            return Err(QuorumSafetyViolation::NotLive {
+                quorum_size,
+                threshold: self.threshold,
+                signers,
+                honest,
+            });
+        }
+
+        Ok(())
+    }
 }
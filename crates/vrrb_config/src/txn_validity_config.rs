@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_TXN_MAX_PAST_SKEW_SECS: u64 = 300;
+pub const DEFAULT_TXN_MAX_FUTURE_SKEW_SECS: u64 = 120;
+
+/// Bounds how far a transaction's timestamp may drift from the node's clock
+/// before the transaction is rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnValidityConfig {
+    /// How long ago a transaction may have been created when it's submitted
+    /// to the node. Only checked on admission to the mempool, txns that wait
+    /// there or get resurrected are not held to it again
+    pub max_past_skew: Duration,
+
+    /// How far ahead of the node's clock a transaction's timestamp may be,
+    /// to tolerate clients whose clocks run slightly fast
+    pub max_future_skew: Duration,
+}
+
+impl Default for TxnValidityConfig {
+    fn default() -> Self {
+        Self {
+            max_past_skew: Duration::from_secs(DEFAULT_TXN_MAX_PAST_SKEW_SECS),
+            max_future_skew: Duration::from_secs(DEFAULT_TXN_MAX_FUTURE_SKEW_SECS),
+        }
+    }
+}
@@ -6,7 +6,7 @@ use std::{
 };
 
 use chrono::Utc;
-use primitives::{Address, SerializedPublicKey};
+use primitives::{Address, Amount, AmountError, Epoch, SerializedPublicKey};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
@@ -122,7 +122,7 @@ impl Default for AccountDigests {
 
 /// Struct representing the desired updates to be applied to account.
 /// TODO: impl Default for UpdateArgs { ... }
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct UpdateArgs {
     pub address: Address,
     pub nonce: Option<u128>,
@@ -189,6 +189,11 @@ pub struct Account {
     digests: AccountDigests,
     created_at: i64,
     updated_at: Option<i64>,
+
+    /// Latest epoch a block touched the account in, tracked once the dust
+    /// policy is active
+    #[serde(default)]
+    last_active_epoch: Option<Epoch>,
 }
 
 impl Account {
@@ -224,6 +229,7 @@ impl Account {
             digests,
             created_at: Utc::now().timestamp(),
             updated_at: None,
+            last_active_epoch: None,
         }
     }
 
@@ -244,44 +250,20 @@ impl Account {
         self.hash = format!("{:x}", hasher.finalize());
     }
 
-    // TODO: do those safely
-    // Should we rollback credits and debits on overflow?
-    // e.g.
-    // self.credits -= self.debits;
-    // self.debits -= self.debits;
-    //
-    // This way overall balance stays the same
-    // But the numbers are fine
-    // This may be a problem since even though u64 (or whatever we end up using) are
-    // big Imagining some trading account, at one point it could fill up (with
-    // thousands of transactions per day)
-    //
-    // THOUGHT:
-    //
-    // WRT the above, maybe what we want to do is use bytes/hex strings instead of
-    // values and then just do byte/hex math for display...
-
     /// Updates single field in account struct without updating it's hash.
     /// Unsafe to use alone (hash should be recalculated).
     /// Used only in batch updates to improve speed by reducing unnecesary hash
     /// calculations. Returns error if update fails.
     fn update_single_field_no_hash(&mut self, value: AccountField) -> Result<()> {
         match value {
-            AccountField::Credits(credits) => match self.credits.checked_add(credits) {
-                Some(new_amount) => {
-                    self.credits = new_amount;
-                },
-                None => return Err(Error::Other(format!("failed to update {value:?}"))),
+            AccountField::Credits(credits) => {
+                self.credits = Amount::new(self.credits).checked_add(credits)?.get();
             },
-            AccountField::Debits(debits) => match self.debits.checked_add(debits) {
-                Some(new_amount) => {
-                    if self.credits >= new_amount {
-                        self.debits = new_amount
-                    } else {
-                        return Err(Error::Other(format!("failed to update {value:?}")));
-                    }
-                },
-                None => return Err(Error::Other(format!("failed to update {value:?}"))),
+            AccountField::Debits(debits) => {
+                // NOTE: debits can't exceed credits, an account never spends
+                // more than its balance
+                self.checked_balance()?.checked_sub(debits)?;
+                self.debits = Amount::new(self.debits).checked_add(debits)?.get();
             },
 
             // Should the storage be impossible to delete?
@@ -394,6 +376,18 @@ impl Account {
     pub fn debits(&self) -> u128 {
         self.debits
     }
+
+    /// Returns how much of the native token the account can spend
+    pub fn balance(&self) -> u128 {
+        self.credits.saturating_sub(self.debits)
+    }
+
+    /// Returns how much of the native token the account can spend, failing
+    /// if its debits somehow exceed its credits
+    pub fn checked_balance(&self) -> std::result::Result<Amount, AmountError> {
+        Amount::new(self.credits).checked_sub(self.debits)
+    }
+
     pub fn storage(&self) -> &Option<String> {
         &self.storage
     }
@@ -412,6 +406,15 @@ impl Account {
     pub fn updated_at(&self) -> Option<i64> {
         self.updated_at
     }
+
+    pub fn last_active_epoch(&self) -> Option<Epoch> {
+        self.last_active_epoch
+    }
+
+    /// Records that a block touched the account during `epoch`
+    pub fn record_activity(&mut self, epoch: Epoch) {
+        self.last_active_epoch = self.last_active_epoch.max(Some(epoch));
+    }
 }
 
 impl std::fmt::Display for Account {
@@ -435,4 +438,21 @@ mod tests {
 
         assert_eq!(account.nonce, 0);
     }
+
+    #[test]
+    fn balance_is_credits_minus_debits() {
+        let (_, pk) = generate_account_keypair();
+
+        let mut account = Account::new(pk);
+        account.credits = 100;
+        account.debits = 30;
+
+        assert_eq!(account.balance(), 70);
+        assert_eq!(account.checked_balance(), Ok(Amount::new(70)));
+
+        account.debits = 130;
+
+        assert_eq!(account.balance(), 0);
+        assert!(account.checked_balance().is_err());
+    }
 }
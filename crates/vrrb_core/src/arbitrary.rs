@@ -0,0 +1,319 @@
+//! [Arbitrary] impls and strategies producing core types, so property based
+//! tests across crates can generate them.
+
+use std::net::SocketAddr;
+
+use ethereum_types::U256;
+use primitives::{
+    Address, Epoch, ProtocolParameter, PublicKey, QuorumPubkey, SecretKey, Signature,
+    ValidatorSecretKey,
+};
+use proptest::{collection, option, prelude::*, strategy::LazyJust};
+use secp256k1::Message;
+
+use crate::{
+    claim::{Claim, Eligibility},
+    transactions::{
+        Delegation, DelegationAction, ExitValidator, ParameterChange, ParameterChangeProposal,
+        QuorumCertifiedTxn, Token, TransactionDigest, TransactionKind, Transfer,
+    },
+};
+
+pub fn secret_key() -> impl Strategy<Value = SecretKey> {
+    any::<[u8; 32]>().prop_filter_map("not a valid secret key", |bytes| {
+        SecretKey::from_slice(&bytes).ok()
+    })
+}
+
+pub fn public_key() -> impl Strategy<Value = PublicKey> {
+    secret_key()
+        .prop_map(|secret_key| PublicKey::from_secret_key(secp256k1::SECP256K1, &secret_key))
+}
+
+pub fn address() -> impl Strategy<Value = Address> {
+    public_key().prop_map(Address::new)
+}
+
+/// Signatures of random digests. They don't verify against anything, but
+/// encode like the real ones
+pub fn signature() -> impl Strategy<Value = Signature> {
+    (secret_key(), any::<[u8; 32]>()).prop_map(|(secret_key, digest)| {
+        let message = Message::from_slice(&digest).expect("digests are 32 bytes long");
+
+        secp256k1::SECP256K1.sign_ecdsa(&message, &secret_key)
+    })
+}
+
+pub fn u256() -> impl Strategy<Value = U256> {
+    any::<[u8; 32]>().prop_map(|bytes| U256::from_big_endian(&bytes))
+}
+
+pub fn txn_digest() -> impl Strategy<Value = TransactionDigest> {
+    any::<[u8; 32]>().prop_map(|bytes| TransactionDigest::from(bytes.to_vec()))
+}
+
+pub fn node_id() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9-]{1,24}"
+}
+
+impl Arbitrary for Token {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (".{0,16}", "[A-Z]{1,5}", any::<u8>())
+            .prop_map(|(name, symbol, decimals)| Token {
+                name,
+                symbol,
+                decimals,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Claim {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let eligibility = prop_oneof![
+            Just(Eligibility::Harvester),
+            Just(Eligibility::Miner),
+            Just(Eligibility::Farmer),
+            Just(Eligibility::None),
+        ];
+
+        (
+            public_key(),
+            u256(),
+            eligibility,
+            any::<SocketAddr>(),
+            "[0-9a-f]{0,64}",
+            node_id(),
+            any::<u128>(),
+            option::of(any::<Epoch>()),
+            collection::btree_map(address(), any::<u128>(), 0..3),
+        )
+            .prop_map(
+                |(
+                    public_key,
+                    hash,
+                    eligibility,
+                    ip_address,
+                    signature,
+                    node_id,
+                    stake,
+                    unbonding_until,
+                    delegations,
+                )| {
+                    Claim {
+                        public_key,
+                        address: Address::new(public_key),
+                        hash,
+                        eligibility,
+                        ip_address,
+                        signature,
+                        node_id,
+                        stake,
+                        stake_txns: vec![],
+                        bonded_stake: 0,
+                        unbonding_until,
+                        first_seen_epoch: None,
+                        mining_slots: Default::default(),
+                        delegations,
+                        undelegations: vec![],
+                        accrued_reward: 0,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+fn transfer() -> impl Strategy<Value = Transfer> {
+    (
+        any::<i64>(),
+        public_key(),
+        address(),
+        any::<Token>(),
+        any::<u128>(),
+        signature(),
+        // NOTE: at most one validator, so the map encodes the same way every time
+        option::of(collection::hash_map(node_id(), any::<bool>(), 0..2)),
+        any::<u128>(),
+    )
+        .prop_map(
+            |(
+                timestamp,
+                sender_public_key,
+                receiver_address,
+                token,
+                amount,
+                signature,
+                validators,
+                nonce,
+            )| {
+                let mut builder = Transfer::builder()
+                    .timestamp(timestamp)
+                    .sender_address(Address::new(sender_public_key))
+                    .sender_public_key(sender_public_key)
+                    .receiver_address(receiver_address)
+                    .token(token)
+                    .amount(amount)
+                    .signature(signature)
+                    .nonce(nonce);
+
+                if let Some(validators) = validators {
+                    builder = builder.validators(validators);
+                }
+
+                builder.build().expect("every field of the transfer is set")
+            },
+        )
+}
+
+fn exit_validator() -> impl Strategy<Value = ExitValidator> {
+    (
+        txn_digest(),
+        any::<i64>(),
+        public_key(),
+        node_id(),
+        signature(),
+        any::<u128>(),
+    )
+        .prop_map(
+            |(id, timestamp, sender_public_key, node_id, signature, nonce)| ExitValidator {
+                id,
+                timestamp,
+                sender_address: Address::new(sender_public_key),
+                sender_public_key,
+                node_id,
+                signature,
+                nonce,
+            },
+        )
+}
+
+fn delegation() -> impl Strategy<Value = Delegation> {
+    (
+        txn_digest(),
+        any::<i64>(),
+        public_key(),
+        node_id(),
+        prop_oneof![
+            Just(DelegationAction::Delegate),
+            Just(DelegationAction::Undelegate)
+        ],
+        any::<u128>(),
+        signature(),
+        any::<u128>(),
+    )
+        .prop_map(
+            |(id, timestamp, sender_public_key, node_id, action, amount, signature, nonce)| {
+                Delegation {
+                    id,
+                    timestamp,
+                    sender_address: Address::new(sender_public_key),
+                    sender_public_key,
+                    node_id,
+                    action,
+                    amount,
+                    signature,
+                    nonce,
+                }
+            },
+        )
+}
+
+fn parameter_change_proposal() -> impl Strategy<Value = ParameterChangeProposal> {
+    (
+        proptest::sample::select(ProtocolParameter::ALL.to_vec()),
+        any::<u128>(),
+        any::<Epoch>(),
+        "[a-z ]{0,32}",
+    )
+        .prop_map(
+            |(parameter, value, effective_epoch, reason)| ParameterChangeProposal {
+                parameter,
+                value,
+                effective_epoch,
+                reason,
+            },
+        )
+}
+
+/// Parameter changes whose quorum signature doesn't verify, see [signature]
+fn parameter_change() -> impl Strategy<Value = ParameterChange> {
+    (
+        txn_digest(),
+        any::<i64>(),
+        public_key(),
+        parameter_change_proposal(),
+        LazyJust::new(|| QuorumPubkey::from(ValidatorSecretKey::random().public_key())),
+        "[0-9a-f]{192}",
+        signature(),
+        any::<u128>(),
+    )
+        .prop_map(
+            |(
+                id,
+                timestamp,
+                sender_public_key,
+                proposal,
+                quorum_public_key,
+                quorum_signature,
+                signature,
+                nonce,
+            )| ParameterChange {
+                id,
+                timestamp,
+                sender_address: Address::new(sender_public_key),
+                sender_public_key,
+                proposal,
+                quorum_public_key,
+                quorum_signature,
+                signature,
+                nonce,
+            },
+        )
+}
+
+impl Arbitrary for TransactionKind {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            transfer().prop_map(TransactionKind::Transfer),
+            exit_validator().prop_map(TransactionKind::ExitValidator),
+            delegation().prop_map(TransactionKind::Delegation),
+            parameter_change().prop_map(TransactionKind::ParameterChange),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for QuorumCertifiedTxn {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            collection::vec(any::<u8>(), 0..32),
+            any::<TransactionKind>(),
+            collection::vec(any::<u8>(), 0..96),
+            any::<bool>(),
+        )
+            .prop_map(|(sender_farmer_id, txn, signature, is_txn_valid)| {
+                QuorumCertifiedTxn::new(
+                    sender_farmer_id,
+                    vec![],
+                    txn,
+                    signature,
+                    None,
+                    is_txn_valid,
+                )
+            })
+            .boxed()
+    }
+}
@@ -62,6 +62,17 @@ where
     pub fn remove(&mut self, key: &K) {
         self.cache.remove(key);
     }
+
+    /// Returns the entry for `key` without refreshing it
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.cache.peek(key)
+    }
+
+    /// Iterates over the entries that haven't expired without refreshing
+    /// them
+    pub fn peek_iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.cache.peek_iter()
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
-use std::net::SocketAddr;
+use std::{collections::BTreeMap, net::SocketAddr};
 
 use ethereum_types::U256;
-use primitives::{Address, NodeId, PublicKey, SerializedSecretKey};
+use primitives::{Address, Epoch, NodeId, PublicKey, SerializedSecretKey};
 use serde::{Deserialize, Serialize};
 /// a Module for creating, maintaining, and using a claim in the fair,
 /// computationally inexpensive, collission proof, fully decentralized, fully
@@ -18,6 +18,10 @@ use crate::{
 
 pub type Result<T> = std::result::Result<T, ClaimError>;
 
+/// Stake each share of a claim's election weight stands for, see
+/// [Claim::election_weight]
+pub const ELECTION_STAKE_UNIT: u128 = primitives::DEFAULT_MIN_DELEGATION;
+
 #[derive(Error, Debug)]
 pub enum ClaimError {
     #[error("Invalid signature")]
@@ -53,6 +57,61 @@ pub struct Claim {
     pub node_id: NodeId,
     stake: u128,
     stake_txns: Vec<Stake>,
+
+    /// Stake the claim's validator registration took out of its balance and
+    /// bonded to it
+    #[serde(default)]
+    bonded_stake: u128,
+
+    /// Epoch at which the stake of a validator that left the validator set
+    /// unlocks
+    #[serde(default)]
+    unbonding_until: Option<Epoch>,
+
+    /// Epoch the block that first stored the claim was mined in, which its
+    /// age is counted from. Claims stored before it was recorded have none
+    #[serde(default)]
+    first_seen_epoch: Option<Epoch>,
+
+    /// Mining slots the claim held in the blocks of the last epoch it was
+    /// scheduled to mine in
+    #[serde(default)]
+    mining_slots: MiningSlots,
+
+    /// Stake other accounts bonded to the claim, by delegator. It counts
+    /// towards the claim's election weight
+    #[serde(default)]
+    delegations: BTreeMap<Address, u128>,
+
+    /// Delegated stake released by its delegators, locked until its
+    /// unbonding period is over
+    #[serde(default)]
+    undelegations: Vec<Undelegation>,
+
+    /// Rewards earned by the claim's validator during the current epoch, yet
+    /// to be shared with its delegators
+    #[serde(default)]
+    accrued_reward: u128,
+}
+
+/// Mining slots a claim held during an epoch, and how many of them its node
+/// filled with a block. Slots a block's miner was scheduled after are filled,
+/// the ones scheduled ahead of it were missed.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MiningSlots {
+    pub epoch: Epoch,
+    pub held: u32,
+    pub filled: u32,
+}
+
+/// Delegated stake on its way back to its delegator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Undelegation {
+    pub delegator: Address,
+    pub amount: u128,
+
+    /// Epoch the stake is returned to the delegator at
+    pub unlocks_at: Epoch,
 }
 
 ///Node has privileges to be Miner/Validator,Farmer or None
@@ -108,6 +167,13 @@ impl Claim {
                 node_id,
                 stake: 0,
                 stake_txns: vec![],
+                bonded_stake: 0,
+                unbonding_until: None,
+                first_seen_epoch: None,
+                mining_slots: MiningSlots::default(),
+                delegations: BTreeMap::new(),
+                undelegations: vec![],
+                accrued_reward: 0,
             }),
             Err(e) => Err(e),
         };
@@ -231,6 +297,19 @@ impl Claim {
         U256(xor_val)
     }
 
+    /// Returns the number of shares of election weight the claim holds, one
+    /// per [ELECTION_STAKE_UNIT] of its total stake and never less than one
+    pub fn election_weight(&self) -> u128 {
+        (self.total_stake() / ELECTION_STAKE_UNIT).max(1)
+    }
+
+    /// Returns the claim's election result for `block_seed` divided by its
+    /// election weight. Elections are won by the lowest results, so a claim's
+    /// odds grow with its stake, delegated stake included
+    pub fn get_weighted_election_result(&self, block_seed: u64) -> U256 {
+        self.get_election_result(block_seed) / U256::from(self.election_weight())
+    }
+
     /// Takes a StakeUpdate enum and adds/withdrawals or slashes
     /// the given claim's stake. This method is used within the
     /// state module to update a claim that has a transaction
@@ -265,9 +344,14 @@ impl Claim {
     /// Checks the cumulative value of a nodes stake by calculating
     /// the UTXO of the stake transactions.
     fn check_stake_utxo(&self) -> u128 {
-        self.stake_txns
+        self.stake_after(0, &self.stake_txns)
+    }
+
+    /// Returns the stake left once `stake_txns` are applied to `stake`
+    fn stake_after(&self, stake: u128, stake_txns: &[Stake]) -> u128 {
+        stake_txns
             .iter()
-            .fold(0u128, |mut acc, val| match val.get_amount() {
+            .fold(stake, |mut acc, val| match val.get_amount() {
                 StakeUpdate::Add(value) => {
                     if let Some(v) = acc.checked_add(value) {
                         acc = v;
@@ -291,14 +375,272 @@ impl Claim {
         value - slash as u128
     }
 
+    /// Returns the claim's own stake, its stake txns along with the stake
+    /// its validator registration bonded
     pub fn get_stake(&self) -> u128 {
-        self.stake
+        self.stake.saturating_add(self.bonded_stake)
+    }
+
+    /// Bonds `amount` taken out of the claim owner's balance to the claim
+    pub fn bond(&mut self, amount: u128) {
+        self.bonded_stake = self.bonded_stake.saturating_add(amount);
+    }
+
+    pub fn bonded_stake(&self) -> u128 {
+        self.bonded_stake
+    }
+
+    /// Releases the stake bonded to the claim once it unlocked by `epoch`,
+    /// see [Claim::is_stake_unlocked]. Returns the amount released
+    pub fn release_bonded_stake(&mut self, epoch: Epoch) -> u128 {
+        if !self.is_stake_unlocked(epoch) {
+            return 0;
+        }
+
+        std::mem::take(&mut self.bonded_stake)
+    }
+
+    /// Returns how much stake the slashes among the claim's stake txns took,
+    /// leaving out the first `known` txns, which were already accounted for
+    pub fn slashed_since(&self, known: usize) -> u128 {
+        let (known, new) = self.stake_txns.split_at(known.min(self.stake_txns.len()));
+
+        let mut stake = self.stake_after(0, known);
+        let mut slashed = 0u128;
+
+        for stake_txn in new {
+            let next = self.stake_after(stake, std::slice::from_ref(stake_txn));
+
+            if let StakeUpdate::Slash(_) = stake_txn.get_amount() {
+                slashed = slashed.saturating_add(stake - next);
+            }
+
+            stake = next;
+        }
+
+        slashed
     }
 
     pub fn get_stake_txns(&self) -> Vec<Stake> {
         self.stake_txns.clone()
     }
 
+    /// Marks the claim as having left the validator set. It is no longer
+    /// eligible for elections, and its stake stays locked until
+    /// `unlocks_at`.
+    pub fn begin_unbonding(&mut self, unlocks_at: Epoch) {
+        self.eligibility = Eligibility::None;
+        self.unbonding_until = Some(unlocks_at);
+    }
+
+    pub fn unbonding_until(&self) -> Option<Epoch> {
+        self.unbonding_until
+    }
+
+    /// Records `epoch` as the one the claim was first seen in, unless one was
+    /// recorded already. Returns whether the claim changed
+    pub fn record_first_seen(&mut self, epoch: Epoch) -> bool {
+        if self.first_seen_epoch.is_some() {
+            return false;
+        }
+
+        self.first_seen_epoch = Some(epoch);
+        true
+    }
+
+    /// Returns how many epochs the claim has been known for as of `epoch`,
+    /// if it was stored after claim ages started being recorded
+    pub fn age(&self, epoch: Epoch) -> Option<Epoch> {
+        self.first_seen_epoch
+            .map(|first_seen| epoch.saturating_sub(first_seen))
+    }
+
+    /// Records a mining slot the claim held in a block mined during `epoch`,
+    /// and whether its node filled it
+    pub fn record_mining_slot(&mut self, epoch: Epoch, filled: bool) {
+        if self.mining_slots.epoch != epoch {
+            self.mining_slots = MiningSlots {
+                epoch,
+                ..Default::default()
+            };
+        }
+
+        self.mining_slots.held = self.mining_slots.held.saturating_add(1);
+        self.mining_slots.filled = self.mining_slots.filled.saturating_add(filled as u32);
+    }
+
+    /// Returns the share of the mining slots the claim held during `epoch`
+    /// that its node filled, in percent. Unknown if it held none
+    pub fn uptime_percent(&self, epoch: Epoch) -> Option<u8> {
+        let slots = &self.mining_slots;
+        if slots.epoch != epoch || slots.held == 0 {
+            return None;
+        }
+
+        Some((slots.filled.min(slots.held) as u64 * 100 / slots.held as u64) as u8)
+    }
+
+    /// Keeps the age, mining slots, bonded stake and delegation state the
+    /// chain recorded for `stored`, which copies of the claim relayed by peers
+    /// can't be trusted with
+    pub fn keep_chain_record(&mut self, stored: &Claim) {
+        self.first_seen_epoch = stored.first_seen_epoch;
+        self.mining_slots = stored.mining_slots;
+        self.bonded_stake = stored.bonded_stake;
+        self.unbonding_until = stored.unbonding_until;
+        self.delegations = stored.delegations.clone();
+        self.undelegations = stored.undelegations.clone();
+        self.accrued_reward = stored.accrued_reward;
+    }
+
+    /// Clears whatever a copy of a claim the chain hasn't recorded yet
+    /// carries in place of its chain record, see [Claim::keep_chain_record]
+    pub fn reset_chain_record(&mut self) {
+        self.first_seen_epoch = None;
+        self.mining_slots = MiningSlots::default();
+        self.bonded_stake = 0;
+        self.unbonding_until = None;
+        self.delegations.clear();
+        self.undelegations.clear();
+        self.accrued_reward = 0;
+    }
+
+    /// Returns whether the claim's stake can be withdrawn at the given epoch.
+    /// Stake only unlocks once the claim has finished unbonding.
+    pub fn is_stake_unlocked(&self, epoch: Epoch) -> bool {
+        matches!(self.unbonding_until, Some(unlocks_at) if epoch >= unlocks_at)
+    }
+
+    /// Bonds `amount` of `delegator`'s stake to the claim
+    pub fn delegate(&mut self, delegator: Address, amount: u128) {
+        let delegated = self.delegations.entry(delegator).or_default();
+        *delegated = delegated.saturating_add(amount);
+    }
+
+    /// Releases `amount` of the stake `delegator` bonded to the claim, which
+    /// stays locked until `unlocks_at`. Fails if the delegator bonded less
+    /// than that
+    pub fn undelegate(
+        &mut self,
+        delegator: &Address,
+        amount: u128,
+        unlocks_at: Epoch,
+    ) -> Result<()> {
+        let delegated = self.delegations.get(delegator).copied().unwrap_or_default();
+
+        let Some(remaining) = delegated.checked_sub(amount) else {
+            return Err(ClaimError::Other(format!(
+                "{delegator} delegated {delegated} to node {}, cannot undelegate {amount}",
+                self.node_id
+            )));
+        };
+
+        if remaining == 0 {
+            self.delegations.remove(delegator);
+        } else {
+            self.delegations.insert(delegator.clone(), remaining);
+        }
+
+        self.undelegations.push(Undelegation {
+            delegator: delegator.clone(),
+            amount,
+            unlocks_at,
+        });
+
+        Ok(())
+    }
+
+    /// Removes and returns the undelegated stake that unlocked by `epoch`
+    pub fn release_undelegations(&mut self, epoch: Epoch) -> Vec<Undelegation> {
+        let (released, locked) = std::mem::take(&mut self.undelegations)
+            .into_iter()
+            .partition(|undelegation| epoch >= undelegation.unlocks_at);
+
+        self.undelegations = locked;
+
+        released
+    }
+
+    pub fn delegations(&self) -> &BTreeMap<Address, u128> {
+        &self.delegations
+    }
+
+    pub fn undelegations(&self) -> &[Undelegation] {
+        &self.undelegations
+    }
+
+    /// Returns the stake delegators bonded to the claim
+    pub fn delegated_stake(&self) -> u128 {
+        self.delegations
+            .values()
+            .fold(0u128, |total, amount| total.saturating_add(*amount))
+    }
+
+    /// Returns the claim's own stake along with the stake delegated to it,
+    /// which is what its election weight is based on
+    pub fn total_stake(&self) -> u128 {
+        self.get_stake().saturating_add(self.delegated_stake())
+    }
+
+    /// Records `amount` as earned by the claim's validator
+    pub fn accrue_reward(&mut self, amount: u128) {
+        self.accrued_reward = self.accrued_reward.saturating_add(amount);
+    }
+
+    pub fn accrued_reward(&self) -> u128 {
+        self.accrued_reward
+    }
+
+    /// Returns the rewards accrued so far, resetting them
+    pub fn take_accrued_reward(&mut self) -> u128 {
+        std::mem::take(&mut self.accrued_reward)
+    }
+
+    /// Returns whether the claim holds rewards or undelegated stake to pay
+    /// out at the start of an epoch
+    pub fn has_pending_payouts(&self) -> bool {
+        self.accrued_reward > 0 || !self.undelegations.is_empty()
+    }
+
+    /// Returns the SHA-256 digest of the claim's delegations, undelegations
+    /// and accrued reward, or None if it holds none of them.
+    ///
+    /// Delegations are encoded in delegator order as the delegator's address,
+    /// prefixed by its length as a `u64`, followed by the amount, and are
+    /// prefixed by their count as a `u64`. Undelegations are encoded the same
+    /// way in the order they were made, each followed by the epoch it unlocks
+    /// at. Integers are little-endian and fixed-width.
+    pub fn delegation_digest(&self) -> Option<[u8; 32]> {
+        if self.delegations.is_empty() && !self.has_pending_payouts() {
+            return None;
+        }
+
+        fn encode_address(hasher: &mut Sha256, address: &Address) {
+            let address = address.to_string();
+            hasher.update((address.len() as u64).to_le_bytes());
+            hasher.update(address.as_bytes());
+        }
+
+        let mut hasher = Sha256::new();
+
+        hasher.update((self.delegations.len() as u64).to_le_bytes());
+        for (delegator, amount) in self.delegations.iter() {
+            encode_address(&mut hasher, delegator);
+            hasher.update(amount.to_le_bytes());
+        }
+
+        hasher.update((self.undelegations.len() as u64).to_le_bytes());
+        for undelegation in self.undelegations.iter() {
+            encode_address(&mut hasher, &undelegation.delegator);
+            hasher.update(undelegation.amount.to_le_bytes());
+            hasher.update(undelegation.unlocks_at.to_le_bytes());
+        }
+
+        hasher.update(self.accrued_reward.to_le_bytes());
+
+        Some(hasher.finalize().into())
+    }
+
     #[deprecated(note = "Please use get_election_result")]
     pub fn get_pointer(&self, block_seed: u128) -> Option<u128> {
         let block_seed_hex = format!("{block_seed:x}");
@@ -451,6 +793,13 @@ mod tests {
             node_id: NodeId::default(),
             stake: 0,
             stake_txns: vec![],
+            bonded_stake: 0,
+            unbonding_until: None,
+            first_seen_epoch: None,
+            mining_slots: MiningSlots::default(),
+            delegations: BTreeMap::new(),
+            undelegations: vec![],
+            accrued_reward: 0,
         };
         let claim = Claim::new(
             public_key,
@@ -868,6 +1217,9 @@ mod tests {
         assert!(claim.update_stake(stake).is_ok());
         assert_eq!(claim.get_stake(), 7_500u128);
         assert_eq!(claim.get_stake_txns().len(), 2);
+        assert_eq!(claim.slashed_since(0), 2_500u128);
+        assert_eq!(claim.slashed_since(1), 2_500u128);
+        assert_eq!(claim.slashed_since(2), 0u128);
     }
 
     #[test]
@@ -968,4 +1320,158 @@ mod tests {
         assert_eq!(claim.get_stake(), 90_000u128);
         assert_eq!(claim.get_stake_txns().len(), 2);
     }
+
+    #[test]
+    fn unbonding_claims_lose_eligibility_until_their_stake_unlocks() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+        claim.eligibility = Eligibility::Harvester;
+
+        assert!(!claim.is_stake_unlocked(10));
+
+        claim.begin_unbonding(5);
+
+        assert_eq!(claim.eligibility, Eligibility::None);
+        assert_eq!(claim.unbonding_until(), Some(5));
+        assert!(!claim.is_stake_unlocked(4));
+        assert!(claim.is_stake_unlocked(5));
+    }
+
+    #[test]
+    fn delegated_stake_counts_towards_the_claim_until_it_unbonds() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        let delegator = Address::new(*KeyPair::random().get_miner_public_key());
+
+        claim.delegate(delegator.clone(), 300);
+        claim.delegate(delegator.clone(), 200);
+
+        assert_eq!(claim.delegated_stake(), 500);
+        assert_eq!(claim.total_stake(), claim.get_stake() + 500);
+
+        assert!(claim.undelegate(&delegator, 600, 4).is_err());
+        claim.undelegate(&delegator, 500, 4).unwrap();
+
+        assert_eq!(claim.delegated_stake(), 0);
+        assert!(claim.delegations().is_empty());
+        assert!(claim.release_undelegations(3).is_empty());
+
+        let released = claim.release_undelegations(4);
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].delegator, delegator);
+        assert_eq!(released[0].amount, 500);
+        assert!(claim.undelegations().is_empty());
+    }
+
+    #[test]
+    fn election_results_are_weighted_by_total_stake() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        assert_eq!(claim.election_weight(), 1);
+        assert_eq!(
+            claim.get_weighted_election_result(7),
+            claim.get_election_result(7)
+        );
+        assert_eq!(claim.delegation_digest(), None);
+
+        let delegator = Address::new(*KeyPair::random().get_miner_public_key());
+        claim.delegate(delegator.clone(), ELECTION_STAKE_UNIT * 4 + 1);
+
+        assert_eq!(claim.election_weight(), 4);
+        assert_eq!(
+            claim.get_weighted_election_result(7),
+            claim.get_election_result(7) / 4
+        );
+
+        let digest = claim.delegation_digest().unwrap();
+        claim.accrue_reward(1);
+        assert_ne!(claim.delegation_digest(), Some(digest));
+        assert!(claim.has_pending_payouts());
+    }
+
+    #[test]
+    fn uptime_counts_the_mining_slots_filled_during_an_epoch() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            Address::new(public_key),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        assert_eq!(claim.age(4), None);
+        assert_eq!(claim.uptime_percent(1), None);
+
+        claim.record_first_seen(1);
+        assert!(!claim.record_first_seen(2));
+        assert_eq!(claim.age(4), Some(3));
+
+        claim.record_mining_slot(1, true);
+        claim.record_mining_slot(1, false);
+        claim.record_mining_slot(1, true);
+        claim.record_mining_slot(1, true);
+        assert_eq!(claim.uptime_percent(1), Some(75));
+        assert_eq!(claim.uptime_percent(2), None);
+
+        claim.record_mining_slot(2, false);
+        assert_eq!(claim.uptime_percent(2), Some(0));
+        assert_eq!(claim.uptime_percent(1), None);
+    }
 }
@@ -1,5 +1,7 @@
 pub mod account;
 pub mod accountable;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
 pub mod bloom;
 pub mod cache;
 pub mod claim;
@@ -1,3 +1,5 @@
+use primitives::{AmountError, ClassifiedError, ErrorCode};
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
@@ -8,6 +10,19 @@ pub enum Error {
     #[error("serde_json error: {0}")]
     SerdeJson(#[from] serde_json::Error),
 
+    #[error("{0}")]
+    Amount(#[from] AmountError),
+
     #[error("{0}")]
     Other(String),
 }
+
+impl ClassifiedError for Error {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Error::SerdeJson(_) => ErrorCode::InvalidInput,
+            Error::Amount(err) => err.error_code(),
+            Error::Io(_) | Error::Other(_) => ErrorCode::Internal,
+        }
+    }
+}
@@ -9,8 +9,8 @@ use crate::keypair::{MinerPk, MinerSk};
 /// Represents a byte array that can be converted into a
 /// ThresholdSignature
 pub type Certificate = (Vec<u8>, PayloadHash);
-pub const MIN_STAKE_FARMER: u128 = 10_000;
-pub const MIN_STAKE_VALIDATOR: u128 = 50_000;
+pub const MIN_STAKE_FARMER: u128 = primitives::DEFAULT_MIN_STAKE_FARMER;
+pub const MIN_STAKE_VALIDATOR: u128 = primitives::DEFAULT_MIN_STAKE_VALIDATOR;
 
 pub type Result<T> = std::result::Result<T, StakeError>;
 
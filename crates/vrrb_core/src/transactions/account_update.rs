@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use primitives::{Address, ByteVec, PublicKey, SecretKey};
+use secp256k1::{ecdsa::Signature, Message};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utils::hash_data;
+
+use crate::{
+    account::UpdateArgs,
+    transactions::{
+        Token,
+        Transaction,
+        TransactionDigest,
+        TxAmount,
+        TxNonce,
+        TxTimestamp,
+        BASE_FEE,
+    },
+};
+
+pub fn generate_account_update_digest_vec(
+    timestamp: TxTimestamp,
+    sender_address: String,
+    sender_public_key: PublicKey,
+    args: &UpdateArgs,
+    nonce: TxNonce,
+) -> ByteVec {
+    let payload_string = format!(
+        "{},{},{},{},{:?},{:?},{}",
+        &timestamp,
+        &sender_address,
+        &sender_public_key,
+        &args.address,
+        &args.storage,
+        &args.code,
+        &nonce
+    );
+
+    let mut hasher = Sha256::new();
+    hasher.update(payload_string);
+    let hash = hasher.finalize();
+
+    hash.to_vec()
+}
+
+/// A request from an account owner to update their account's metadata, such
+/// as the storage or code it carries. Balances, nonces and digests can't be
+/// set through it; those only change as a side effect of other transactions.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct AccountUpdate {
+    pub id: TransactionDigest,
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+
+    /// Updates to apply to the sender's account
+    pub args: UpdateArgs,
+    pub signature: Signature,
+    pub nonce: TxNonce,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewAccountUpdateArgs {
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub args: UpdateArgs,
+    pub signature: Signature,
+    pub nonce: TxNonce,
+}
+
+impl AccountUpdate {
+    pub fn new(args: NewAccountUpdateArgs) -> Self {
+        let digest_vec = generate_account_update_digest_vec(
+            args.timestamp,
+            args.sender_address.to_string(),
+            args.sender_public_key,
+            &args.args,
+            args.nonce,
+        );
+
+        Self {
+            id: TransactionDigest::from(digest_vec),
+            timestamp: args.timestamp,
+            sender_address: args.sender_address,
+            sender_public_key: args.sender_public_key,
+            args: args.args,
+            signature: args.signature,
+            nonce: args.nonce,
+        }
+    }
+
+    /// Returns true if the update only touches the account's metadata,
+    /// leaving its balance, nonce and digests alone
+    pub fn is_metadata_only(&self) -> bool {
+        self.args.nonce.is_none()
+            && self.args.credits.is_none()
+            && self.args.debits.is_none()
+            && self.args.digests.is_none()
+    }
+}
+
+impl Transaction for AccountUpdate {
+    fn id(&self) -> TransactionDigest {
+        self.id.clone()
+    }
+
+    fn timestamp(&self) -> TxTimestamp {
+        self.timestamp
+    }
+
+    fn sender_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn sender_public_key(&self) -> PublicKey {
+        self.sender_public_key
+    }
+
+    fn receiver_address(&self) -> Address {
+        self.args.address.clone()
+    }
+
+    fn token(&self) -> Token {
+        Token::default()
+    }
+
+    /// Account updates don't move any funds
+    fn amount(&self) -> TxAmount {
+        0
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    fn validators(&self) -> Option<HashMap<String, bool>> {
+        None
+    }
+
+    fn nonce(&self) -> TxNonce {
+        self.nonce
+    }
+
+    fn fee(&self) -> u128 {
+        BASE_FEE
+    }
+
+    fn validator_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn proposer_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn build_payload(&self) -> String {
+        format!(
+            "{:x}",
+            hash_data!(
+                self.sender_address,
+                self.sender_public_key,
+                self.args.address,
+                self.args.storage,
+                self.args.code,
+                self.nonce
+            )
+        )
+    }
+
+    fn digest(&self) -> TransactionDigest {
+        self.id()
+    }
+
+    fn sign(&mut self, sk: &SecretKey) {
+        let message = Message::from_slice(self.build_payload().as_bytes());
+        if let Ok(msg) = message {
+            self.signature = sk.sign_ecdsa(msg);
+        }
+    }
+}
+
+impl From<AccountUpdate> for TransactionDigest {
+    fn from(txn: AccountUpdate) -> Self {
+        txn.id()
+    }
+}
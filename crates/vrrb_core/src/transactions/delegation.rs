@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use primitives::{Address, ByteVec, NodeId, PublicKey, SecretKey};
+use secp256k1::{ecdsa::Signature, Message};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utils::hash_data;
+
+use crate::transactions::{
+    Token,
+    Transaction,
+    TransactionDigest,
+    TxAmount,
+    TxNonce,
+    TxTimestamp,
+    BASE_FEE,
+};
+
+/// Whether a delegation bonds stake to a validator or starts releasing it.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DelegationAction {
+    Delegate,
+    Undelegate,
+}
+
+impl std::fmt::Display for DelegationAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DelegationAction::Delegate => write!(f, "delegate"),
+            DelegationAction::Undelegate => write!(f, "undelegate"),
+        }
+    }
+}
+
+pub fn generate_delegation_digest_vec(
+    timestamp: TxTimestamp,
+    sender_address: String,
+    sender_public_key: PublicKey,
+    node_id: &NodeId,
+    action: DelegationAction,
+    amount: TxAmount,
+    nonce: TxNonce,
+) -> ByteVec {
+    let payload_string = format!(
+        "{},{},{},{},{},{},{}",
+        &timestamp, &sender_address, &sender_public_key, node_id, &action, &amount, &nonce
+    );
+
+    let mut hasher = Sha256::new();
+    hasher.update(payload_string);
+    let hash = hasher.finalize();
+
+    hash.to_vec()
+}
+
+/// Bonds part of the sender's balance to the claim of the validator running
+/// `node_id`, or starts releasing stake it bonded before.
+///
+/// Delegated stake counts towards the validator's election weight, and the
+/// validator shares the rewards it earns with its delegators, keeping the
+/// chain's commission. Undelegated stake unlocks after the chain's unbonding
+/// period.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Delegation {
+    pub id: TransactionDigest,
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub node_id: NodeId,
+    pub action: DelegationAction,
+    pub amount: TxAmount,
+    pub signature: Signature,
+    pub nonce: TxNonce,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewDelegationArgs {
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub node_id: NodeId,
+    pub action: DelegationAction,
+    pub amount: TxAmount,
+    pub signature: Signature,
+    pub nonce: TxNonce,
+}
+
+impl Delegation {
+    pub fn new(args: NewDelegationArgs) -> Self {
+        let digest_vec = generate_delegation_digest_vec(
+            args.timestamp,
+            args.sender_address.to_string(),
+            args.sender_public_key,
+            &args.node_id,
+            args.action,
+            args.amount,
+            args.nonce,
+        );
+
+        Self {
+            id: TransactionDigest::from(digest_vec),
+            timestamp: args.timestamp,
+            sender_address: args.sender_address,
+            sender_public_key: args.sender_public_key,
+            node_id: args.node_id,
+            action: args.action,
+            amount: args.amount,
+            signature: args.signature,
+            nonce: args.nonce,
+        }
+    }
+
+    /// Amount of stake the delegation bonds or releases
+    pub fn stake(&self) -> TxAmount {
+        self.amount
+    }
+}
+
+impl Transaction for Delegation {
+    fn id(&self) -> TransactionDigest {
+        self.id.clone()
+    }
+
+    fn timestamp(&self) -> TxTimestamp {
+        self.timestamp
+    }
+
+    fn sender_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn sender_public_key(&self) -> PublicKey {
+        self.sender_public_key
+    }
+
+    fn receiver_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn token(&self) -> Token {
+        Token::default()
+    }
+
+    /// Delegations don't transfer funds, the stake they bond or release is
+    /// moved in and out of the validator's claim when the block including
+    /// them is applied
+    fn amount(&self) -> TxAmount {
+        0
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    fn validators(&self) -> Option<HashMap<String, bool>> {
+        None
+    }
+
+    fn nonce(&self) -> TxNonce {
+        self.nonce
+    }
+
+    fn fee(&self) -> u128 {
+        BASE_FEE
+    }
+
+    fn validator_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn proposer_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn build_payload(&self) -> String {
+        format!(
+            "{:x}",
+            hash_data!(
+                self.sender_address,
+                self.sender_public_key,
+                self.node_id,
+                self.action,
+                self.amount,
+                self.nonce
+            )
+        )
+    }
+
+    fn digest(&self) -> TransactionDigest {
+        self.id()
+    }
+
+    fn sign(&mut self, sk: &SecretKey) {
+        let message = Message::from_slice(self.build_payload().as_bytes());
+        if let Ok(msg) = message {
+            self.signature = sk.sign_ecdsa(msg);
+        }
+    }
+}
+
+impl From<Delegation> for TransactionDigest {
+    fn from(txn: Delegation) -> Self {
+        txn.id()
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use primitives::{Address, ByteVec, NodeId, PublicKey, SecretKey};
+use secp256k1::{ecdsa::Signature, Message};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utils::hash_data;
+
+use crate::transactions::{
+    Token,
+    Transaction,
+    TransactionDigest,
+    TxAmount,
+    TxNonce,
+    TxTimestamp,
+    BASE_FEE,
+};
+
+pub fn generate_exit_validator_digest_vec(
+    timestamp: TxTimestamp,
+    sender_address: String,
+    sender_public_key: PublicKey,
+    node_id: &NodeId,
+    nonce: TxNonce,
+) -> ByteVec {
+    let payload_string = format!(
+        "{},{},{},{},{}",
+        &timestamp, &sender_address, &sender_public_key, node_id, &nonce
+    );
+
+    let mut hasher = Sha256::new();
+    hasher.update(payload_string);
+    let hash = hasher.finalize();
+
+    hash.to_vec()
+}
+
+/// A request from a validator to voluntarily leave the validator set. Once
+/// certified, the validator is left out of the next epoch's quorums and its
+/// stake unlocks after the chain's unbonding period.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ExitValidator {
+    pub id: TransactionDigest,
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub node_id: NodeId,
+    pub signature: Signature,
+    pub nonce: TxNonce,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewExitValidatorArgs {
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub node_id: NodeId,
+    pub signature: Signature,
+    pub nonce: TxNonce,
+}
+
+impl ExitValidator {
+    pub fn new(args: NewExitValidatorArgs) -> Self {
+        let digest_vec = generate_exit_validator_digest_vec(
+            args.timestamp,
+            args.sender_address.to_string(),
+            args.sender_public_key,
+            &args.node_id,
+            args.nonce,
+        );
+
+        Self {
+            id: TransactionDigest::from(digest_vec),
+            timestamp: args.timestamp,
+            sender_address: args.sender_address,
+            sender_public_key: args.sender_public_key,
+            node_id: args.node_id,
+            signature: args.signature,
+            nonce: args.nonce,
+        }
+    }
+}
+
+impl Transaction for ExitValidator {
+    fn id(&self) -> TransactionDigest {
+        self.id.clone()
+    }
+
+    fn timestamp(&self) -> TxTimestamp {
+        self.timestamp
+    }
+
+    fn sender_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn sender_public_key(&self) -> PublicKey {
+        self.sender_public_key
+    }
+
+    fn receiver_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn token(&self) -> Token {
+        Token::default()
+    }
+
+    /// Exits don't move any funds, the bonded stake is released once the
+    /// unbonding period is over
+    fn amount(&self) -> TxAmount {
+        0
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    fn validators(&self) -> Option<HashMap<String, bool>> {
+        None
+    }
+
+    fn nonce(&self) -> TxNonce {
+        self.nonce
+    }
+
+    fn fee(&self) -> u128 {
+        BASE_FEE
+    }
+
+    fn validator_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn proposer_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn build_payload(&self) -> String {
+        format!(
+            "{:x}",
+            hash_data!(
+                self.sender_address,
+                self.sender_public_key,
+                self.node_id,
+                self.nonce
+            )
+        )
+    }
+
+    fn digest(&self) -> TransactionDigest {
+        self.id()
+    }
+
+    fn sign(&mut self, sk: &SecretKey) {
+        let message = Message::from_slice(self.build_payload().as_bytes());
+        if let Ok(msg) = message {
+            self.signature = sk.sign_ecdsa(msg);
+        }
+    }
+}
+
+impl From<ExitValidator> for TransactionDigest {
+    fn from(txn: ExitValidator) -> Self {
+        txn.id()
+    }
+}
@@ -1,7 +1,19 @@
+pub mod account_update;
+pub mod delegation;
+pub mod exit_validator;
+pub mod parameter_change;
+pub mod register_validator;
 pub mod transaction_kind;
 pub mod transfer;
 pub mod transaction;
+pub mod unsigned_transfer;
 
+pub use account_update::*;
+pub use delegation::*;
+pub use exit_validator::*;
+pub use parameter_change::*;
+pub use register_validator::*;
 pub use transaction_kind::*;
 pub use transfer::*;
-pub use transaction::*;
\ No newline at end of file
+pub use transaction::*;
+pub use unsigned_transfer::*;
\ No newline at end of file
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use primitives::{
+    Address, ByteVec, Epoch, ProtocolParameter, PublicKey, QuorumPubkey, SecretKey,
+};
+use secp256k1::{ecdsa::Signature, Message};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utils::hash_data;
+
+use crate::transactions::{
+    Token,
+    Transaction,
+    TransactionDigest,
+    TxAmount,
+    TxNonce,
+    TxTimestamp,
+    BASE_FEE,
+};
+
+/// A change to a protocol parameter, as approved by the harvester quorum.
+///
+/// Changes name the epoch they take effect at, so every node switches to the
+/// new value at the same epoch boundary whenever the change was included.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ParameterChangeProposal {
+    pub parameter: ProtocolParameter,
+    pub value: u128,
+    pub effective_epoch: Epoch,
+
+    /// Why the change was made, for the audit history
+    pub reason: String,
+}
+
+impl ParameterChangeProposal {
+    /// Returns what the quorum signs to approve the change.
+    ///
+    /// The preimage is the parameter's tag followed by a `0` byte, the value
+    /// and the effective epoch as 16 big-endian bytes each, and the reason.
+    pub fn payload_hash(&self) -> Vec<u8> {
+        let mut preimage = self.parameter.tag().as_bytes().to_vec();
+        preimage.push(0);
+        preimage.extend(self.value.to_be_bytes());
+        preimage.extend(self.effective_epoch.to_be_bytes());
+        preimage.extend(self.reason.as_bytes());
+
+        Sha256::digest(&preimage).to_vec()
+    }
+}
+
+pub fn generate_parameter_change_digest_vec(
+    timestamp: TxTimestamp,
+    sender_address: String,
+    sender_public_key: PublicKey,
+    proposal: &ParameterChangeProposal,
+    quorum_signature: &str,
+    nonce: TxNonce,
+) -> ByteVec {
+    let payload_string = format!(
+        "{},{},{},{},{},{},{},{}",
+        &timestamp,
+        &sender_address,
+        &sender_public_key,
+        proposal.parameter,
+        proposal.value,
+        proposal.effective_epoch,
+        quorum_signature,
+        &nonce
+    );
+
+    let mut hasher = Sha256::new();
+    hasher.update(payload_string);
+    let hash = hasher.finalize();
+
+    hash.to_vec()
+}
+
+/// Carries a quorum-approved [ParameterChangeProposal] on chain.
+///
+/// Anyone may submit the txn and pay its fee, the quorum's threshold
+/// signature over the proposal is what authorizes the change. Changes whose
+/// signature doesn't verify against the harvester quorum key, or whose value
+/// is out of the parameter's bounds, are left out of the parameter store
+/// when the block including them is applied.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ParameterChange {
+    pub id: TransactionDigest,
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub proposal: ParameterChangeProposal,
+    pub quorum_public_key: QuorumPubkey,
+
+    /// Hex encoded threshold signature of the quorum over
+    /// [ParameterChangeProposal::payload_hash]
+    pub quorum_signature: String,
+    pub signature: Signature,
+    pub nonce: TxNonce,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewParameterChangeArgs {
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub proposal: ParameterChangeProposal,
+    pub quorum_public_key: QuorumPubkey,
+    pub quorum_signature: String,
+    pub signature: Signature,
+    pub nonce: TxNonce,
+}
+
+impl ParameterChange {
+    pub fn new(args: NewParameterChangeArgs) -> Self {
+        let digest_vec = generate_parameter_change_digest_vec(
+            args.timestamp,
+            args.sender_address.to_string(),
+            args.sender_public_key,
+            &args.proposal,
+            &args.quorum_signature,
+            args.nonce,
+        );
+
+        Self {
+            id: TransactionDigest::from(digest_vec),
+            timestamp: args.timestamp,
+            sender_address: args.sender_address,
+            sender_public_key: args.sender_public_key,
+            proposal: args.proposal,
+            quorum_public_key: args.quorum_public_key,
+            quorum_signature: args.quorum_signature,
+            signature: args.signature,
+            nonce: args.nonce,
+        }
+    }
+}
+
+impl Transaction for ParameterChange {
+    fn id(&self) -> TransactionDigest {
+        self.id.clone()
+    }
+
+    fn timestamp(&self) -> TxTimestamp {
+        self.timestamp
+    }
+
+    fn sender_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn sender_public_key(&self) -> PublicKey {
+        self.sender_public_key
+    }
+
+    fn receiver_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn token(&self) -> Token {
+        Token::default()
+    }
+
+    /// Parameter changes don't transfer funds
+    fn amount(&self) -> TxAmount {
+        0
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    fn validators(&self) -> Option<HashMap<String, bool>> {
+        None
+    }
+
+    fn nonce(&self) -> TxNonce {
+        self.nonce
+    }
+
+    fn fee(&self) -> u128 {
+        BASE_FEE
+    }
+
+    fn validator_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn proposer_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn build_payload(&self) -> String {
+        format!(
+            "{:x}",
+            hash_data!(
+                self.sender_address,
+                self.sender_public_key,
+                self.proposal,
+                self.quorum_public_key,
+                self.quorum_signature,
+                self.nonce
+            )
+        )
+    }
+
+    fn digest(&self) -> TransactionDigest {
+        self.id()
+    }
+
+    fn sign(&mut self, sk: &SecretKey) {
+        let message = Message::from_slice(self.build_payload().as_bytes());
+        if let Ok(msg) = message {
+            self.signature = sk.sign_ecdsa(msg);
+        }
+    }
+}
+
+impl From<ParameterChange> for TransactionDigest {
+    fn from(txn: ParameterChange) -> Self {
+        txn.id()
+    }
+}
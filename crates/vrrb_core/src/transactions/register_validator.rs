@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use primitives::{Address, ByteVec, NodeId, PublicKey, SecretKey, ValidatorPublicKey};
+use secp256k1::{ecdsa::Signature, Message};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utils::hash_data;
+
+use crate::transactions::{
+    Token,
+    Transaction,
+    TransactionDigest,
+    TxAmount,
+    TxNonce,
+    TxTimestamp,
+    BASE_FEE,
+};
+
+pub fn generate_register_validator_digest_vec(
+    timestamp: TxTimestamp,
+    sender_address: String,
+    sender_public_key: PublicKey,
+    node_id: &NodeId,
+    validator_public_key: &ValidatorPublicKey,
+    stake: TxAmount,
+    nonce: TxNonce,
+) -> ByteVec {
+    let payload_string = format!(
+        "{},{},{},{},{:?},{},{}",
+        &timestamp,
+        &sender_address,
+        &sender_public_key,
+        node_id,
+        validator_public_key,
+        &stake,
+        &nonce
+    );
+
+    let mut hasher = Sha256::new();
+    hasher.update(payload_string);
+    let hash = hasher.finalize();
+
+    hash.to_vec()
+}
+
+/// A request from a staked node to join the validator set. Once a certified
+/// block bonds its stake to the node's claim, the node is queued for
+/// activation and considered by the quorum election of the next epoch.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct RegisterValidator {
+    pub id: TransactionDigest,
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub node_id: NodeId,
+    pub validator_public_key: ValidatorPublicKey,
+
+    /// Amount the sender bonds to become a validator
+    pub stake: TxAmount,
+    pub signature: Signature,
+    pub nonce: TxNonce,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewRegisterValidatorArgs {
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub node_id: NodeId,
+    pub validator_public_key: ValidatorPublicKey,
+    pub stake: TxAmount,
+    pub signature: Signature,
+    pub nonce: TxNonce,
+}
+
+impl RegisterValidator {
+    pub fn new(args: NewRegisterValidatorArgs) -> Self {
+        let digest_vec = generate_register_validator_digest_vec(
+            args.timestamp,
+            args.sender_address.to_string(),
+            args.sender_public_key,
+            &args.node_id,
+            &args.validator_public_key,
+            args.stake,
+            args.nonce,
+        );
+
+        Self {
+            id: TransactionDigest::from(digest_vec),
+            timestamp: args.timestamp,
+            sender_address: args.sender_address,
+            sender_public_key: args.sender_public_key,
+            node_id: args.node_id,
+            validator_public_key: args.validator_public_key,
+            stake: args.stake,
+            signature: args.signature,
+            nonce: args.nonce,
+        }
+    }
+}
+
+impl Transaction for RegisterValidator {
+    fn id(&self) -> TransactionDigest {
+        self.id.clone()
+    }
+
+    fn timestamp(&self) -> TxTimestamp {
+        self.timestamp
+    }
+
+    fn sender_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn sender_public_key(&self) -> PublicKey {
+        self.sender_public_key
+    }
+
+    /// The stake stays bonded to the sender's own account
+    fn receiver_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn token(&self) -> Token {
+        Token::default()
+    }
+
+    fn amount(&self) -> TxAmount {
+        self.stake
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    fn validators(&self) -> Option<HashMap<String, bool>> {
+        None
+    }
+
+    fn nonce(&self) -> TxNonce {
+        self.nonce
+    }
+
+    fn fee(&self) -> u128 {
+        BASE_FEE
+    }
+
+    fn validator_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn proposer_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn build_payload(&self) -> String {
+        format!(
+            "{:x}",
+            hash_data!(
+                self.sender_address,
+                self.sender_public_key,
+                self.node_id,
+                self.validator_public_key,
+                self.stake,
+                self.nonce
+            )
+        )
+    }
+
+    fn digest(&self) -> TransactionDigest {
+        self.id()
+    }
+
+    fn sign(&mut self, sk: &SecretKey) {
+        let message = Message::from_slice(self.build_payload().as_bytes());
+        if let Ok(msg) = message {
+            self.signature = sk.sign_ecdsa(msg);
+        }
+    }
+}
+
+impl From<RegisterValidator> for TransactionDigest {
+    fn from(txn: RegisterValidator) -> Self {
+        txn.id()
+    }
+}
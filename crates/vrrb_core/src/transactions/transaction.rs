@@ -5,11 +5,12 @@ use std::fmt::{Display, Formatter};
 use std::hash::Hash;
 use std::str::FromStr;
 use serde::{Deserialize, Serialize};
-use primitives::{Address, ByteSlice, ByteVec, Digest as PrimitiveDigest, DIGEST_LENGTH, NodeIdx, PublicKey, RawSignature, SecretKey, Signature};
+use primitives::{Address, ByteSlice, ByteVec, Digest as PrimitiveDigest, DIGEST_LENGTH, NodeIdx, PublicKey, QuorumPubkey, RawSignature, SecretKey, Signature};
 use crate::helpers::gen_hex_encoded_string;
 use crate::transactions::{TransactionKind, TxAmount, TxNonce, TxTimestamp};
 
-pub const BASE_FEE: u128 = 0x2D79883D2000;
+pub const BASE_FEE: u128 = primitives::DEFAULT_BASE_FEE;
+pub const MEMO_FEE_PER_BYTE: u128 = primitives::DEFAULT_MEMO_FEE_PER_BYTE;
 
 pub trait Transaction {
     fn id(&self) -> TransactionDigest;
@@ -91,8 +92,12 @@ pub struct QuorumCertifiedTxn
     /// All valid vote receipts
     votes: Vec<VoteReceipt>,
     txn: TransactionKind,
-    /// Threshold Signature
+    /// Threshold signature the farmer quorum aggregated from its votes, over
+    /// the bincode encoding of the txn
     signature: RawSignature,
+    /// Group public key of the farmer quorum that certified the txn, which
+    /// the signature verifies against
+    quorum_public_key: Option<QuorumPubkey>,
     pub is_txn_valid: bool,
 }
 
@@ -103,6 +108,7 @@ impl QuorumCertifiedTxn
         votes: Vec<VoteReceipt>,
         txn: TransactionKind,
         signature: RawSignature,
+        quorum_public_key: Option<QuorumPubkey>,
         is_txn_valid: bool,
     ) -> QuorumCertifiedTxn {
         QuorumCertifiedTxn {
@@ -110,6 +116,7 @@ impl QuorumCertifiedTxn
             votes,
             txn,
             signature,
+            quorum_public_key,
             is_txn_valid,
         }
     }
@@ -118,6 +125,19 @@ impl QuorumCertifiedTxn
         self.txn.clone()
     }
 
+    pub fn votes(&self) -> &[VoteReceipt] {
+        &self.votes
+    }
+
+    /// Returns the aggregated threshold signature of the farmer quorum
+    pub fn signature(&self) -> &RawSignature {
+        &self.signature
+    }
+
+    pub fn quorum_public_key(&self) -> Option<&QuorumPubkey> {
+        self.quorum_public_key.as_ref()
+    }
+
     pub fn fee(&self) -> u128 {
         self.txn.fee()
     }
@@ -146,6 +166,11 @@ impl TransactionDigest {
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty() && self.digest_string.is_empty()
     }
+
+    /// Returns the raw bytes of the digest
+    pub fn as_bytes(&self) -> &[u8] {
+        self.inner.as_bytes()
+    }
 }
 
 impl Display for TransactionDigest {
@@ -222,4 +247,18 @@ mod tests {
 
         assert_eq!(txn_digest, txn_digest_recovered);
     }
+
+    #[test]
+    fn memos_are_signed_over_and_priced_per_byte() {
+        let txn = Transfer::default();
+
+        let mut with_memo = txn.clone();
+        with_memo.memo = Some(b"invoice 42".to_vec());
+
+        assert_ne!(txn.build_payload(), with_memo.build_payload());
+        assert_eq!(txn.build_payload_digest(), txn.id());
+        assert_ne!(txn.build_payload_digest(), with_memo.build_payload_digest());
+        assert_eq!(txn.fee(), BASE_FEE);
+        assert_eq!(with_memo.fee(), BASE_FEE + 10 * MEMO_FEE_PER_BYTE);
+    }
 }
@@ -1,18 +1,66 @@
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
-use primitives::{Address, PublicKey, SecretKey, Signature};
-use crate::transactions::{Token, Transaction, TransactionDigest, Transfer, TransferBuilder, TxAmount, TxNonce, TxTimestamp};
+use primitives::{Address, PublicKey, SecretKey, Signature, Weight};
+use crate::transactions::{AccountUpdate, Delegation, ExitValidator, ParameterChange, RegisterValidator, Token, Transaction, TransactionDigest, Transfer, TransferBuilder, TxAmount, TxMemo, TxNonce, TxTimestamp};
 
 
 #[derive(Hash, Debug, Deserialize, Clone, Serialize, Eq, PartialEq)]
 pub enum TransactionKind {
     Transfer(Transfer),
+    RegisterValidator(RegisterValidator),
+    ExitValidator(ExitValidator),
+    UpdateAccount(AccountUpdate),
+    Delegation(Delegation),
+    ParameterChange(ParameterChange),
 }
 
+/// Weight charged for every byte of a txn's encoding
+pub const WEIGHT_PER_BYTE: Weight = 16;
+
+pub const TRANSFER_BASE_WEIGHT: Weight = 20_000;
+pub const UPDATE_ACCOUNT_BASE_WEIGHT: Weight = 25_000;
+pub const DELEGATION_BASE_WEIGHT: Weight = 40_000;
+pub const EXIT_VALIDATOR_BASE_WEIGHT: Weight = 40_000;
+pub const REGISTER_VALIDATOR_BASE_WEIGHT: Weight = 60_000;
+pub const PARAMETER_CHANGE_BASE_WEIGHT: Weight = 60_000;
+
 impl TransactionKind {
     pub fn transfer_builder() -> TransferBuilder {
         Transfer::builder()
     }
+
+    /// Returns the memo attached to the txn. Only transfers carry one.
+    pub fn memo(&self) -> Option<&TxMemo> {
+        match self {
+            TransactionKind::Transfer(transfer) => transfer.memo(),
+            _ => None,
+        }
+    }
+
+    /// Returns the fixed cost of applying a txn of this kind, regardless of
+    /// its size. Validator registrations, exits and delegations cost more as
+    /// they also update claims and stakes, and parameter changes as their
+    /// quorum signature has to be verified.
+    pub fn base_weight(&self) -> Weight {
+        match self {
+            TransactionKind::Transfer(_) => TRANSFER_BASE_WEIGHT,
+            TransactionKind::RegisterValidator(_) => REGISTER_VALIDATOR_BASE_WEIGHT,
+            TransactionKind::ExitValidator(_) => EXIT_VALIDATOR_BASE_WEIGHT,
+            TransactionKind::UpdateAccount(_) => UPDATE_ACCOUNT_BASE_WEIGHT,
+            TransactionKind::Delegation(_) => DELEGATION_BASE_WEIGHT,
+            TransactionKind::ParameterChange(_) => PARAMETER_CHANGE_BASE_WEIGHT,
+        }
+    }
+
+    /// Returns the weight the txn adds to the block that includes it: its
+    /// base weight plus [WEIGHT_PER_BYTE] for every byte of its bincode
+    /// encoding.
+    pub fn weight(&self) -> Weight {
+        let size = bincode::serialized_size(self).unwrap_or_default();
+
+        self.base_weight()
+            .saturating_add(size.saturating_mul(WEIGHT_PER_BYTE))
+    }
 }
 
 impl Default for TransactionKind {
@@ -25,96 +73,176 @@ impl Transaction for TransactionKind {
     fn id(&self) -> TransactionDigest {
         match self {
             TransactionKind::Transfer(transfer) => transfer.id(),
+            TransactionKind::RegisterValidator(registration) => registration.id(),
+            TransactionKind::ExitValidator(exit) => exit.id(),
+            TransactionKind::UpdateAccount(update) => update.id(),
+            TransactionKind::Delegation(delegation) => delegation.id(),
+            TransactionKind::ParameterChange(change) => change.id(),
         }
     }
 
     fn timestamp(&self) -> TxTimestamp {
         match self {
             TransactionKind::Transfer(transfer) => transfer.timestamp(),
+            TransactionKind::RegisterValidator(registration) => registration.timestamp(),
+            TransactionKind::ExitValidator(exit) => exit.timestamp(),
+            TransactionKind::UpdateAccount(update) => update.timestamp(),
+            TransactionKind::Delegation(delegation) => delegation.timestamp(),
+            TransactionKind::ParameterChange(change) => change.timestamp(),
         }
     }
 
     fn sender_address(&self) -> Address {
         match self {
             TransactionKind::Transfer(transfer) => transfer.sender_address(),
+            TransactionKind::RegisterValidator(registration) => registration.sender_address(),
+            TransactionKind::ExitValidator(exit) => exit.sender_address(),
+            TransactionKind::UpdateAccount(update) => update.sender_address(),
+            TransactionKind::Delegation(delegation) => delegation.sender_address(),
+            TransactionKind::ParameterChange(change) => change.sender_address(),
         }
     }
 
     fn sender_public_key(&self) -> PublicKey {
         match self {
             TransactionKind::Transfer(transfer) => transfer.sender_public_key(),
+            TransactionKind::RegisterValidator(registration) => registration.sender_public_key(),
+            TransactionKind::ExitValidator(exit) => exit.sender_public_key(),
+            TransactionKind::UpdateAccount(update) => update.sender_public_key(),
+            TransactionKind::Delegation(delegation) => delegation.sender_public_key(),
+            TransactionKind::ParameterChange(change) => change.sender_public_key(),
         }
     }
 
     fn receiver_address(&self) -> Address {
         match self {
             TransactionKind::Transfer(transfer) => transfer.receiver_address(),
+            TransactionKind::RegisterValidator(registration) => registration.receiver_address(),
+            TransactionKind::ExitValidator(exit) => exit.receiver_address(),
+            TransactionKind::UpdateAccount(update) => update.receiver_address(),
+            TransactionKind::Delegation(delegation) => delegation.receiver_address(),
+            TransactionKind::ParameterChange(change) => change.receiver_address(),
         }
     }
 
     fn token(&self) -> Token {
         match self {
             TransactionKind::Transfer(transfer) => transfer.token(),
+            TransactionKind::RegisterValidator(registration) => registration.token(),
+            TransactionKind::ExitValidator(exit) => exit.token(),
+            TransactionKind::UpdateAccount(update) => update.token(),
+            TransactionKind::Delegation(delegation) => delegation.token(),
+            TransactionKind::ParameterChange(change) => change.token(),
         }
     }
 
     fn amount(&self) -> TxAmount {
         match self {
             TransactionKind::Transfer(transfer) => transfer.amount(),
+            TransactionKind::RegisterValidator(registration) => registration.amount(),
+            TransactionKind::ExitValidator(exit) => exit.amount(),
+            TransactionKind::UpdateAccount(update) => update.amount(),
+            TransactionKind::Delegation(delegation) => delegation.amount(),
+            TransactionKind::ParameterChange(change) => change.amount(),
         }
     }
 
     fn signature(&self) -> Signature {
         match self {
             TransactionKind::Transfer(transfer) => transfer.signature(),
+            TransactionKind::RegisterValidator(registration) => registration.signature(),
+            TransactionKind::ExitValidator(exit) => exit.signature(),
+            TransactionKind::UpdateAccount(update) => update.signature(),
+            TransactionKind::Delegation(delegation) => delegation.signature(),
+            TransactionKind::ParameterChange(change) => change.signature(),
         }
     }
 
     fn validators(&self) -> Option<HashMap<String, bool>> {
         match self {
             TransactionKind::Transfer(transfer) => transfer.validators(),
+            TransactionKind::RegisterValidator(registration) => registration.validators(),
+            TransactionKind::ExitValidator(exit) => exit.validators(),
+            TransactionKind::UpdateAccount(update) => update.validators(),
+            TransactionKind::Delegation(delegation) => delegation.validators(),
+            TransactionKind::ParameterChange(change) => change.validators(),
         }
     }
 
     fn nonce(&self) -> TxNonce {
         match self {
             TransactionKind::Transfer(transfer) => transfer.nonce(),
+            TransactionKind::RegisterValidator(registration) => registration.nonce(),
+            TransactionKind::ExitValidator(exit) => exit.nonce(),
+            TransactionKind::UpdateAccount(update) => update.nonce(),
+            TransactionKind::Delegation(delegation) => delegation.nonce(),
+            TransactionKind::ParameterChange(change) => change.nonce(),
         }
     }
 
     fn fee(&self) -> u128 {
         match self {
             TransactionKind::Transfer(transfer) => transfer.fee(),
+            TransactionKind::RegisterValidator(registration) => registration.fee(),
+            TransactionKind::ExitValidator(exit) => exit.fee(),
+            TransactionKind::UpdateAccount(update) => update.fee(),
+            TransactionKind::Delegation(delegation) => delegation.fee(),
+            TransactionKind::ParameterChange(change) => change.fee(),
         }
     }
 
     fn validator_fee_share(&self) -> u128 {
         match self {
             TransactionKind::Transfer(transfer) => transfer.validator_fee_share(),
+            TransactionKind::RegisterValidator(registration) => registration.validator_fee_share(),
+            TransactionKind::ExitValidator(exit) => exit.validator_fee_share(),
+            TransactionKind::UpdateAccount(update) => update.validator_fee_share(),
+            TransactionKind::Delegation(delegation) => delegation.validator_fee_share(),
+            TransactionKind::ParameterChange(change) => change.validator_fee_share(),
         }
     }
 
     fn proposer_fee_share(&self) -> u128 {
         match self {
             TransactionKind::Transfer(transfer) => transfer.proposer_fee_share(),
+            TransactionKind::RegisterValidator(registration) => registration.proposer_fee_share(),
+            TransactionKind::ExitValidator(exit) => exit.proposer_fee_share(),
+            TransactionKind::UpdateAccount(update) => update.proposer_fee_share(),
+            TransactionKind::Delegation(delegation) => delegation.proposer_fee_share(),
+            TransactionKind::ParameterChange(change) => change.proposer_fee_share(),
         }
     }
 
     fn build_payload(&self) -> String {
         match self {
             TransactionKind::Transfer(transfer) => transfer.build_payload(),
+            TransactionKind::RegisterValidator(registration) => registration.build_payload(),
+            TransactionKind::ExitValidator(exit) => exit.build_payload(),
+            TransactionKind::UpdateAccount(update) => update.build_payload(),
+            TransactionKind::Delegation(delegation) => delegation.build_payload(),
+            TransactionKind::ParameterChange(change) => change.build_payload(),
         }
     }
 
     fn digest(&self) -> TransactionDigest {
         match self {
             TransactionKind::Transfer(transfer) => transfer.digest(),
+            TransactionKind::RegisterValidator(registration) => registration.digest(),
+            TransactionKind::ExitValidator(exit) => exit.digest(),
+            TransactionKind::UpdateAccount(update) => update.digest(),
+            TransactionKind::Delegation(delegation) => delegation.digest(),
+            TransactionKind::ParameterChange(change) => change.digest(),
         }
     }
 
     fn sign(&mut self, sk: &SecretKey) {
         match self {
             TransactionKind::Transfer(transfer) => transfer.sign(sk),
+            TransactionKind::RegisterValidator(registration) => registration.sign(sk),
+            TransactionKind::ExitValidator(exit) => exit.sign(sk),
+            TransactionKind::UpdateAccount(update) => update.sign(sk),
+            TransactionKind::Delegation(delegation) => delegation.sign(sk),
+            TransactionKind::ParameterChange(change) => change.sign(sk),
         }
     }
 }
\ No newline at end of file
@@ -6,9 +6,10 @@ use std::{
     str::FromStr,
 };
 
+use block_verifier::{TokenFields, TransferFields};
 use primitives::{
-    Address, ByteSlice, ByteVec, Digest as PrimitiveDigest, NodeIdx, PublicKey, RawSignature,
-    SecretKey,
+    Address, ByteSlice, ByteVec, ClassifiedError, Digest as PrimitiveDigest, ErrorCode, NodeIdx,
+    PublicKey, RawSignature, SecretKey,
 };
 use secp256k1::{ecdsa::Signature, Message};
 use serde::{Deserialize, Serialize};
@@ -16,7 +17,7 @@ use sha2::{Digest, Sha256};
 use utils::hash_data;
 
 use crate::transactions::transaction::Transaction;
-use crate::transactions::{Token, TransactionDigest, TransactionKind, BASE_FEE};
+use crate::transactions::{Token, TransactionDigest, TransactionKind, BASE_FEE, MEMO_FEE_PER_BYTE};
 use crate::{
     helpers::gen_hex_encoded_string,
     keypair::Keypair,
@@ -32,6 +33,48 @@ use crate::{
 pub enum TransferTransactionError {
     #[error("invalid transaction: {0}")]
     InvalidTransferTransaction(String),
+
+    #[error("invalid signature: {0}")]
+    InvalidSignature(String),
+}
+
+impl ClassifiedError for TransferTransactionError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            TransferTransactionError::InvalidTransferTransaction(_) => {
+                ErrorCode::InvalidTransaction
+            },
+            TransferTransactionError::InvalidSignature(_) => ErrorCode::InvalidSignature,
+        }
+    }
+}
+
+/// Returns the string a transfer's digest is the SHA-256 hash of
+pub fn transfer_digest_preimage(
+    timestamp: TxTimestamp,
+    sender_address: String,
+    sender_public_key: PublicKey,
+    receiver_address: String,
+    token: Token,
+    amount: TxAmount,
+    nonce: TxNonce,
+    memo: Option<&TxMemo>,
+) -> String {
+    TransferFields {
+        timestamp,
+        sender_address: &sender_address,
+        sender_public_key: &sender_public_key.to_string(),
+        receiver_address: &receiver_address,
+        token: TokenFields {
+            name: &token.name,
+            symbol: &token.symbol,
+            decimals: token.decimals,
+        },
+        amount,
+        nonce,
+        memo: memo.map(|memo| memo.as_slice()),
+    }
+    .digest_preimage()
 }
 
 pub fn generate_transfer_digest_vec(
@@ -42,10 +85,17 @@ pub fn generate_transfer_digest_vec(
     token: Token,
     amount: TxAmount,
     nonce: TxNonce,
+    memo: Option<&TxMemo>,
 ) -> ByteVec {
-    let payload_string = format!(
-        "{},{},{},{},{},{:?},{}",
-        &timestamp, &sender_address, &sender_public_key, &receiver_address, &amount, &token, &nonce
+    let payload_string = transfer_digest_preimage(
+        timestamp,
+        sender_address,
+        sender_public_key,
+        receiver_address,
+        token,
+        amount,
+        nonce,
+        memo,
     );
 
     let mut hasher = Sha256::new();
@@ -60,6 +110,9 @@ pub type TxTimestamp = i64;
 pub type TxAmount = u128;
 pub type TxSignature = Vec<u8>;
 
+/// Arbitrary data attached to a transfer, such as an exchange memo
+pub type TxMemo = Vec<u8>;
+
 #[derive(Clone, Debug, Serialize, Deserialize, Eq)]
 pub struct Transfer {
     pub id: TransactionDigest,
@@ -72,6 +125,11 @@ pub struct Transfer {
     pub signature: Signature,
     pub validators: Option<HashMap<String, bool>>,
     pub nonce: TxNonce,
+
+    /// Priced per byte on top of the base fee, and bounded by the chain's
+    /// txn limits
+    #[serde(default)]
+    pub memo: Option<TxMemo>,
 }
 
 #[derive(Clone, Default)]
@@ -85,6 +143,7 @@ pub struct TransferBuilder {
     signature: Option<Signature>,
     validators: Option<HashMap<String, bool>>,
     nonce: Option<TxNonce>,
+    memo: Option<TxMemo>,
 }
 
 impl TransferBuilder {
@@ -134,6 +193,11 @@ impl TransferBuilder {
         self
     }
 
+    pub fn memo(mut self, memo: TxMemo) -> Self {
+        self.memo = Some(memo);
+        self
+    }
+
     pub fn build(self) -> Result<Transfer, &'static str> {
         let id = generate_transfer_digest_vec(
             self.timestamp.ok_or("timestamp is missing")?,
@@ -143,6 +207,7 @@ impl TransferBuilder {
             self.token.clone().unwrap_or_default(),
             self.amount.ok_or("amount is missing")?,
             self.nonce.ok_or("nonce is missing")?,
+            self.memo.as_ref(),
         );
 
         Ok(Transfer {
@@ -156,6 +221,7 @@ impl TransferBuilder {
             signature: self.signature.ok_or("signature is missing")?,
             validators: self.validators,
             nonce: self.nonce.unwrap(),
+            memo: self.memo,
         })
     }
 
@@ -177,6 +243,8 @@ pub struct NewTransferArgs {
     pub signature: Signature,
     pub validators: Option<HashMap<String, bool>>,
     pub nonce: TxNonce,
+    #[serde(default)]
+    pub memo: Option<TxMemo>,
 }
 
 impl Default for Transfer {
@@ -200,6 +268,7 @@ impl Transfer {
             token.clone(),
             args.amount.clone(),
             args.nonce.clone(),
+            args.memo.as_ref(),
         );
 
         let digest = TransactionDigest::from(digest_vec);
@@ -216,6 +285,7 @@ impl Transfer {
             signature: args.signature,
             validators: args.validators,
             nonce: args.nonce,
+            memo: args.memo,
         }
     }
 
@@ -233,6 +303,7 @@ impl Transfer {
             Token::default(),
             0,
             0,
+            None,
         );
 
         let digest = TransactionDigest::from(digest_vec);
@@ -263,6 +334,7 @@ impl Transfer {
             signature,
             validators: None,
             nonce: 0,
+            memo: None,
         }
     }
 
@@ -275,11 +347,27 @@ impl Transfer {
             self.token(),
             self.amount(),
             self.nonce(),
+            self.memo.as_ref(),
         );
 
         digest.into()
     }
 
+    pub fn memo(&self) -> Option<&TxMemo> {
+        self.memo.as_ref()
+    }
+
+    /// Returns what the memo adds to the fee of the transfer
+    pub fn memo_fee(&self) -> u128 {
+        let memo_size = self
+            .memo
+            .as_ref()
+            .map(|memo| memo.len())
+            .unwrap_or_default();
+
+        (memo_size as u128).saturating_mul(MEMO_FEE_PER_BYTE)
+    }
+
     #[deprecated]
     pub fn txn_id(&self) -> String {
         self.id().to_string()
@@ -308,6 +396,7 @@ impl Transfer {
             self.token(),
             self.amount(),
             self.nonce(),
+            self.memo.as_ref(),
         )
     }
 
@@ -374,29 +463,39 @@ impl Transaction for Transfer {
     }
 
     fn fee(&self) -> u128 {
-        BASE_FEE
+        BASE_FEE.saturating_add(self.memo_fee())
     }
 
     fn validator_fee_share(&self) -> u128 {
-        BASE_FEE / 2u128
+        self.fee() / 2u128
     }
 
     fn proposer_fee_share(&self) -> u128 {
-        BASE_FEE / 2u128
+        self.fee() / 2u128
     }
 
     fn build_payload(&self) -> String {
-        format!(
-            "{:x}",
-            hash_data!(
+        let payload = match &self.memo {
+            Some(memo) => hash_data!(
+                self.sender_address.clone(),
+                self.sender_public_key.clone(),
+                self.receiver_address.clone(),
+                self.token.clone(),
+                self.amount.clone(),
+                self.nonce.clone(),
+                memo.clone()
+            ),
+            None => hash_data!(
                 self.sender_address.clone(),
                 self.sender_public_key.clone(),
                 self.receiver_address.clone(),
                 self.token.clone(),
                 self.amount.clone(),
                 self.nonce.clone()
-            )
-        )
+            ),
+        };
+
+        format!("{:x}", payload)
     }
 
     fn digest(&self) -> TransactionDigest {
@@ -471,6 +570,7 @@ impl Hash for Transfer {
         self.amount.hash(state);
         self.signature.hash(state);
         self.nonce.hash(state);
+        self.memo.hash(state);
     }
 
     fn hash_slice<H: Hasher>(data: &[Self], state: &mut H)
@@ -0,0 +1,189 @@
+//! Cold-storage signing: a transfer is exported unsigned along with the
+//! preimage of its digest, signed on an air-gapped machine, and assembled
+//! back once the detached signature is imported.
+
+use std::{collections::HashMap, str::FromStr};
+
+use primitives::{Address, PublicKey};
+use secp256k1::{ecdsa::Signature, Message, Secp256k1};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::transactions::{
+    transfer_digest_preimage, Token, TransactionDigest, Transfer, TransferTransactionError,
+    TxAmount, TxMemo, TxNonce, TxTimestamp,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsignedTransferArgs {
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub receiver_address: Address,
+    pub token: Option<Token>,
+    pub amount: TxAmount,
+    pub nonce: TxNonce,
+    #[serde(default)]
+    pub memo: Option<TxMemo>,
+}
+
+/// A transfer waiting on the signature of its sender.
+///
+/// The sender signs the SHA-256 digest of `payload`, the transfer's digest,
+/// the same way `signTransaction` does: the digest is hashed again with
+/// SHA-256 and signed with ECDSA over secp256k1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsignedTransfer {
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub receiver_address: Address,
+    pub token: Token,
+    pub amount: TxAmount,
+    pub nonce: TxNonce,
+    #[serde(default)]
+    pub memo: Option<TxMemo>,
+
+    /// Preimage of the transfer's digest, what the offline signer hashes
+    pub payload: String,
+}
+
+impl UnsignedTransfer {
+    pub fn new(args: UnsignedTransferArgs) -> Self {
+        let token = args.token.unwrap_or_default();
+
+        let payload = transfer_digest_preimage(
+            args.timestamp,
+            args.sender_address.to_string(),
+            args.sender_public_key,
+            args.receiver_address.to_string(),
+            token.clone(),
+            args.amount,
+            args.nonce,
+            args.memo.as_ref(),
+        );
+
+        Self {
+            timestamp: args.timestamp,
+            sender_address: args.sender_address,
+            sender_public_key: args.sender_public_key,
+            receiver_address: args.receiver_address,
+            token,
+            amount: args.amount,
+            nonce: args.nonce,
+            memo: args.memo,
+            payload,
+        }
+    }
+
+    /// Returns the digest the transfer will have once signed
+    pub fn digest(&self) -> TransactionDigest {
+        TransactionDigest::from(Sha256::digest(self.payload.as_bytes()).to_vec())
+    }
+
+    /// Assembles the transfer out of the detached `signature` the sender
+    /// produced offline, given as hex encoded DER.
+    ///
+    /// Fails if the fields of the transfer no longer match the exported
+    /// payload, or if the signature wasn't made by the sender over it.
+    pub fn assemble(self, signature: &str) -> Result<Transfer, TransferTransactionError> {
+        let payload = transfer_digest_preimage(
+            self.timestamp,
+            self.sender_address.to_string(),
+            self.sender_public_key,
+            self.receiver_address.to_string(),
+            self.token.clone(),
+            self.amount,
+            self.nonce,
+            self.memo.as_ref(),
+        );
+
+        if payload != self.payload {
+            return Err(TransferTransactionError::InvalidTransferTransaction(
+                "transfer does not match the payload it was exported with".to_string(),
+            ));
+        }
+
+        let signature = Signature::from_str(signature)
+            .map_err(|err| TransferTransactionError::InvalidSignature(err.to_string()))?;
+
+        type H = secp256k1::hashes::sha256::Hash;
+        let message = Message::from_hashed_data::<H>(self.digest().as_bytes());
+
+        Secp256k1::verification_only()
+            .verify_ecdsa(&message, &signature, &self.sender_public_key)
+            .map_err(|_| {
+                TransferTransactionError::InvalidSignature(
+                    "not made by the sender over the exported payload".to_string(),
+                )
+            })?;
+
+        let mut builder = Transfer::builder()
+            .timestamp(self.timestamp)
+            .sender_address(self.sender_address)
+            .sender_public_key(self.sender_public_key)
+            .receiver_address(self.receiver_address)
+            .token(self.token)
+            .amount(self.amount)
+            .signature(signature)
+            .validators(HashMap::new())
+            .nonce(self.nonce);
+
+        if let Some(memo) = self.memo {
+            builder = builder.memo(memo);
+        }
+
+        builder
+            .build()
+            .map_err(|err| TransferTransactionError::InvalidTransferTransaction(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{keypair::KeyPair, transactions::Transaction};
+
+    #[test]
+    fn transfers_signed_offline_are_assembled_once_the_signature_checks_out() {
+        let sender = KeyPair::random();
+        let receiver = KeyPair::random();
+
+        let unsigned = UnsignedTransfer::new(UnsignedTransferArgs {
+            timestamp: 1_700_000_000,
+            sender_address: Address::new(*sender.get_miner_public_key()),
+            sender_public_key: *sender.get_miner_public_key(),
+            receiver_address: Address::new(*receiver.get_miner_public_key()),
+            token: None,
+            amount: 250,
+            nonce: 3,
+            memo: Some(b"cold".to_vec()),
+        });
+
+        // NOTE: what an air-gapped signer does with the exported payload
+        type H = secp256k1::hashes::sha256::Hash;
+        let digest = Sha256::digest(unsigned.payload.as_bytes());
+        let message = Message::from_hashed_data::<H>(&digest[..]);
+        let signature = sender
+            .get_miner_secret_key()
+            .sign_ecdsa(message)
+            .to_string();
+        let forged = receiver
+            .get_miner_secret_key()
+            .sign_ecdsa(message)
+            .to_string();
+
+        let mut tampered = unsigned.clone();
+        tampered.amount = 25_000;
+
+        assert!(tampered.assemble(&signature).is_err());
+        assert!(unsigned.clone().assemble(&forged).is_err());
+        assert!(unsigned.clone().assemble("not-a-signature").is_err());
+
+        let transfer = unsigned.clone().assemble(&signature).unwrap();
+
+        assert_eq!(transfer.id(), unsigned.digest());
+        assert_eq!(transfer.amount(), 250);
+        assert_eq!(transfer.memo(), Some(&b"cold".to_vec()));
+    }
+}
@@ -1,5 +1,6 @@
 pub mod http;
 pub mod indexer;
+pub mod webhook;
 
 // use reqwest::Error as ReqwestError;
 
@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use http::{HttpClient, HttpClientBuilder};
+use reqwest::{Method, StatusCode};
+use telemetry::warn;
+
+use crate::{http, Error, Result};
+
+/// Header carrying the hex encoded ECDSA signature of the node over the
+/// SHA-256 digest of the payload
+pub const SIGNATURE_HEADER: &str = "X-Vrrb-Signature";
+
+/// Header carrying the hex encoded public key the payload was signed with
+pub const PUBLIC_KEY_HEADER: &str = "X-Vrrb-Public-Key";
+
+/// How failed deliveries to a webhook are retried.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WebhookRetryPolicy {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+
+    /// Most deliveries attempted, counting the first one
+    pub max_attempts: u32,
+}
+
+impl WebhookRetryPolicy {
+    /// Time waited after the `attempt`th delivery failed
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// A JSON payload along with the signature receivers check it against.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SignedPayload {
+    pub body: String,
+    pub signature: String,
+    pub public_key: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookClient {
+    client: HttpClient,
+    retry_policy: WebhookRetryPolicy,
+}
+
+impl WebhookClient {
+    pub fn new(url: String, retry_policy: WebhookRetryPolicy) -> Result<Self> {
+        let client = HttpClientBuilder::new(url)?.default_headers().build();
+
+        Ok(Self {
+            client,
+            retry_policy,
+        })
+    }
+
+    async fn post(&self, payload: &SignedPayload) -> Result<StatusCode> {
+        let response = self
+            .client
+            .request(Method::POST, "")
+            .await
+            .header(SIGNATURE_HEADER, &payload.signature)
+            .header(PUBLIC_KEY_HEADER, &payload.public_key)
+            .body(payload.body.clone())
+            .send()
+            .await?;
+
+        Ok(response.status())
+    }
+
+    /// Posts `payload` to the webhook, retrying with backoff while it can't be
+    /// reached, answers with a server error or asks to slow down. Returns the
+    /// status the webhook answered the last delivery with
+    pub async fn deliver(&self, payload: &SignedPayload) -> Result<StatusCode> {
+        let max_attempts = self.retry_policy.max_attempts.max(1);
+        let mut attempt = 1;
+
+        loop {
+            let outcome = self.post(payload).await;
+
+            let retryable = match &outcome {
+                Ok(status) => status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS,
+                Err(_) => true,
+            };
+
+            if !retryable {
+                return outcome;
+            }
+
+            if attempt >= max_attempts {
+                return match outcome {
+                    Ok(status) => Err(Error::Other(format!(
+                        "webhook answered {status} to {attempt} deliveries"
+                    ))),
+                    Err(err) => Err(Error::Other(format!(
+                        "webhook unreachable after {attempt} deliveries: {err}"
+                    ))),
+                };
+            }
+
+            let backoff = self.retry_policy.backoff(attempt);
+            warn!("Webhook delivery {attempt} failed, retrying in {backoff:?}");
+
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{header, method},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    fn retry_policy(max_attempts: u32) -> WebhookRetryPolicy {
+        WebhookRetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+            max_attempts,
+        }
+    }
+
+    fn payload() -> SignedPayload {
+        SignedPayload {
+            body: "{\"kind\":\"duties_missed\"}".to_string(),
+            signature: "signature".to_string(),
+            public_key: "public-key".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn deliveries_are_retried_until_the_webhook_accepts_them() {
+        let mock_server = MockServer::start().await;
+        let url = format!("http://{}/alerts", mock_server.address());
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(header(SIGNATURE_HEADER, "signature"))
+            .and(header(PUBLIC_KEY_HEADER, "public-key"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let client = WebhookClient::new(url, retry_policy(5)).unwrap();
+        let status = client.deliver(&payload()).await.unwrap();
+
+        assert_eq!(status, StatusCode::OK);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].url.path(), "/alerts");
+        assert_eq!(requests[2].body, payload().body.into_bytes());
+    }
+
+    #[tokio::test]
+    async fn deliveries_are_given_up_on_after_the_last_attempt() {
+        let mock_server = MockServer::start().await;
+        let url = format!("http://{}/alerts", mock_server.address());
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+
+        let client = WebhookClient::new(url, retry_policy(3)).unwrap();
+
+        assert!(client.deliver(&payload()).await.is_err());
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_its_cap() {
+        let retry_policy = retry_policy(5);
+
+        assert_eq!(retry_policy.backoff(1), Duration::from_millis(1));
+        assert_eq!(retry_policy.backoff(3), Duration::from_millis(4));
+        assert_eq!(retry_policy.backoff(u32::MAX), Duration::from_millis(4));
+    }
+}
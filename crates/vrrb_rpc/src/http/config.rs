@@ -1,6 +1,8 @@
 use std::{fmt::Debug, net::SocketAddr, time::Duration};
 
 use axum_server::tls_rustls::RustlsConfig;
+use mempool::{LeftRightMempool, MempoolReadHandleFactory};
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
 
 /// Configuration store for an HttpApiServer
 // TODO: implement a builder over this config.
@@ -12,6 +14,35 @@ pub struct HttpApiServerConfig {
     pub api_version: String,
     pub server_timeout: Option<Duration>,
     pub tls_config: Option<RustlsConfig>,
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+}
+
+impl Default for HttpApiServerConfig {
+    fn default() -> Self {
+        let mut vrrbdb_config = VrrbDbConfig::default();
+
+        let temp_dir_path = std::env::temp_dir();
+        let db_path = temp_dir_path.join(vrrb_core::helpers::generate_random_string());
+
+        vrrbdb_config.path = db_path;
+
+        let vrrbdb = VrrbDb::new(vrrbdb_config);
+        let vrrbdb_read_handle = vrrbdb.read_handle();
+
+        let mempool = LeftRightMempool::default();
+        let mempool_read_handle_factory = mempool.factory();
+
+        HttpApiServerConfig {
+            address: "127.0.0.1:0".into(),
+            api_title: "Node HTTP API".into(),
+            api_version: "1.0".into(),
+            server_timeout: None,
+            tls_config: None,
+            vrrbdb_read_handle,
+            mempool_read_handle_factory,
+        }
+    }
 }
 
 /// Configuration store for an HttpApiRouter
@@ -23,4 +54,6 @@ pub struct HttpApiRouterConfig {
     pub api_title: String,
     pub api_version: String,
     pub server_timeout: Option<Duration>,
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
 }
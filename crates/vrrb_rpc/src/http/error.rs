@@ -0,0 +1,71 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use primitives::{error_chain, ClassifiedError, ErrorCode};
+use serde_json::json;
+use telemetry::{debug, error};
+
+/// An error returned by the REST gateway. Rendered as a JSON body carrying
+/// the stable error code and a client-safe message, under the HTTP status
+/// closest to the code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl RestError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the HTTP status errors with this code are reported under
+    pub fn status(&self) -> StatusCode {
+        match self.code {
+            ErrorCode::InvalidInput
+            | ErrorCode::InvalidTransaction
+            | ErrorCode::InvalidSignature
+            | ErrorCode::FeeTooLow
+            | ErrorCode::InvalidBlock => StatusCode::BAD_REQUEST,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::AlreadyExists => StatusCode::CONFLICT,
+            ErrorCode::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::Internal | ErrorCode::Storage | ErrorCode::Consensus | ErrorCode::Dkg => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            },
+        }
+    }
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        let body = Json(json!({
+            "code": self.code.code(),
+            "error": self.message,
+        }));
+
+        (self.status(), body).into_response()
+    }
+}
+
+/// Converts a classified error into a REST error. The full source chain is
+/// logged, clients only receive the error's client-safe message.
+pub fn into_rest_error<E: ClassifiedError>(err: E) -> RestError {
+    let code = err.error_code();
+
+    if code.is_internal() {
+        error!("REST request failed with {code}: {}", error_chain(&err));
+    } else {
+        debug!("REST request failed with {code}: {}", error_chain(&err));
+    }
+
+    RestError::new(code, err.client_message())
+}
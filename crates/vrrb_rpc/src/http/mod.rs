@@ -1,8 +1,12 @@
 mod config;
+mod error;
+mod openapi;
 mod router;
 mod routes;
 mod server;
 
 pub use config::*;
+pub use error::*;
+pub use openapi::*;
 pub use router::*;
 pub use server::*;
@@ -0,0 +1,105 @@
+use serde_json::{json, Map, Value};
+
+use crate::http::HttpApiRouterConfig;
+
+/// A read-only resource served by the REST gateway
+struct RestResource {
+    path: &'static str,
+    summary: &'static str,
+    parameter: Option<(&'static str, &'static str)>,
+    response: Value,
+}
+
+fn rest_resources() -> Vec<RestResource> {
+    vec![
+        RestResource {
+            path: "/accounts/{address}",
+            summary: "Returns the account stored under an address",
+            parameter: Some(("address", "Hex encoded public key the account belongs to")),
+            response: json!({ "type": "object" }),
+        },
+        RestResource {
+            path: "/blocks/{hash}",
+            summary: "Returns the votes, partial signatures and certificate recorded for a block",
+            parameter: Some(("hash", "Hash of the block")),
+            response: json!({ "type": "object" }),
+        },
+        RestResource {
+            path: "/txns/{digest}",
+            summary: "Returns a stored or pending transaction",
+            parameter: Some(("digest", "Hex encoded digest of the transaction")),
+            response: json!({ "type": "object" }),
+        },
+        RestResource {
+            path: "/mempool",
+            summary: "Returns every transaction waiting in the mempool",
+            parameter: None,
+            response: json!({ "type": "array", "items": { "type": "object" } }),
+        },
+    ]
+}
+
+/// Generates the OpenAPI 3.0 document describing the REST gateway
+pub fn openapi_document(config: &HttpApiRouterConfig) -> Value {
+    let mut paths = Map::new();
+
+    for resource in rest_resources() {
+        let parameters = match resource.parameter {
+            Some((name, description)) => json!([{
+                "name": name,
+                "in": "path",
+                "required": true,
+                "description": description,
+                "schema": { "type": "string" },
+            }]),
+            None => json!([]),
+        };
+
+        paths.insert(
+            resource.path.to_string(),
+            json!({
+                "get": {
+                    "summary": resource.summary,
+                    "parameters": parameters,
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": { "schema": resource.response },
+                            },
+                        },
+                        "default": {
+                            "description": "Error",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Error" },
+                                },
+                            },
+                        },
+                    },
+                },
+            }),
+        );
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": config.api_title,
+            "version": config.api_version,
+        },
+        "paths": paths,
+        "components": {
+            "schemas": {
+                "Error": {
+                    "type": "object",
+                    "required": ["code", "error"],
+                    "properties": {
+                        "code": { "type": "integer", "format": "int32" },
+                        "error": { "type": "string" },
+                    },
+                },
+            },
+        },
+    })
+}
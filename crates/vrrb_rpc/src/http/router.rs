@@ -1,17 +1,45 @@
-use axum::{routing::get, Router};
+use axum::{routing::get, Json, Router};
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 
 use crate::http::{
-    routes::{accounts, health},
+    openapi::openapi_document,
+    routes::{accounts, blocks, health, mempool, txns},
     HttpApiRouterConfig,
 };
 
-pub fn create_router(_config: &HttpApiRouterConfig) -> Router {
+pub fn create_router(config: &HttpApiRouterConfig) -> Router {
+    let openapi = openapi_document(config);
+
     Router::new()
         .route("/", get(|| async { "index" }))
         .route("/health", get(health::health_check))
-        .nest("/accounts", accounts::create_account_router())
+        .route(
+            "/openapi.json",
+            get(move || {
+                let openapi = openapi.clone();
+                async move { Json(openapi) }
+            }),
+        )
+        .nest(
+            "/accounts",
+            accounts::create_account_router(config.vrrbdb_read_handle.clone()),
+        )
+        .nest(
+            "/blocks",
+            blocks::create_block_router(config.vrrbdb_read_handle.clone()),
+        )
+        .nest(
+            "/txns",
+            txns::create_txn_router(
+                config.vrrbdb_read_handle.clone(),
+                config.mempool_read_handle_factory.clone(),
+            ),
+        )
+        .nest(
+            "/mempool",
+            mempool::create_mempool_router(config.mempool_read_handle_factory.clone()),
+        )
         .layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()))
 }
 
@@ -26,6 +54,7 @@ mod tests {
     use tower::{Service, ServiceExt};
 
     use super::*;
+    use crate::http::HttpApiServerConfig;
 
     #[tokio::test]
     async fn index_should_exist() {
@@ -35,22 +64,28 @@ mod tests {
         let api_title = "Node HTTP API".to_string();
         let api_version = "1.0".to_string();
 
+        let server_config = HttpApiServerConfig::default();
+
         let config = HttpApiRouterConfig {
             address,
             api_title,
             api_version,
             server_timeout: None,
+            vrrbdb_read_handle: server_config.vrrbdb_read_handle,
+            mempool_read_handle_factory: server_config.mempool_read_handle_factory,
         };
 
         let mut router = create_router(&config);
 
-        let request = Request::builder()
-            .uri("/")
-            .method("GET")
-            .body(Body::empty())
-            .unwrap();
+        for uri in ["/", "/openapi.json", "/mempool"] {
+            let request = Request::builder()
+                .uri(uri)
+                .method("GET")
+                .body(Body::empty())
+                .unwrap();
 
-        let response = router.ready().await.unwrap().call(request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::OK);
+            let response = router.ready().await.unwrap().call(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
     }
 }
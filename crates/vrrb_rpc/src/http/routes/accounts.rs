@@ -1,60 +1,30 @@
-use axum::{
-    routing::{get, post, put},
-    Extension,
-    Json,
-    Router,
-};
-use serde_json::{json, Value};
-
-pub fn create_account_router() -> Router {
-    Router::new()
-        .route("/:id", get(get_account))
-        .route("/:id", put(update_account))
-        .route("/", post(create_account))
-        .layer(Extension(String::from("account route")))
-}
-
-async fn get_account(Extension(state): Extension<String>) -> Json<Value> {
-    Json(json!({
-        "account": "dummy_account_status",
-        "state": state,
-    }))
-}
-
-async fn create_account() {
-    todo!()
-}
-
-async fn update_account() {
-    todo!()
-}
+use std::str::FromStr;
 
-async fn _create_key_router() {
-    todo!()
-}
-
-async fn _create_token_router() {
-    todo!()
-}
-
-async fn _get_key() {
-    todo!()
-}
-
-async fn _create_token() {
-    todo!()
-}
+use axum::{extract::Path, routing::get, Extension, Json, Router};
+use primitives::{Address, ErrorCode};
+use storage::vrrbdb::VrrbDbReadHandle;
+use vrrb_core::account::Account;
 
-async fn _update_token() {
-    todo!()
-}
+use crate::http::RestError;
 
-async fn _get_token() {
-    todo!()
+pub fn create_account_router(vrrbdb_read_handle: VrrbDbReadHandle) -> Router {
+    Router::new()
+        .route("/:address", get(get_account))
+        .layer(Extension(vrrbdb_read_handle))
 }
 
-async fn _delete_token() {
-    todo!()
+async fn get_account(
+    Extension(vrrbdb_read_handle): Extension<VrrbDbReadHandle>,
+    Path(address): Path<String>,
+) -> Result<Json<Account>, RestError> {
+    let address = Address::from_str(&address)
+        .map_err(|err| RestError::new(ErrorCode::InvalidInput, err.to_string()))?;
+
+    vrrbdb_read_handle
+        .state_store_values()
+        .remove(&address)
+        .map(Json)
+        .ok_or_else(|| RestError::new(ErrorCode::NotFound, "unable to find account"))
 }
 
 #[cfg(test)]
@@ -63,22 +33,65 @@ mod tests {
         body::Body,
         http::{Request, StatusCode},
     };
+    use primitives::generate_mock_account_keypair;
+    use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
     use tower::{Service, ServiceExt};
 
     use super::*;
 
-    #[tokio::test]
-    async fn get_account_returns_available_accounts() {
-        let mut router = create_account_router();
+    fn create_test_db() -> VrrbDb {
+        let path = std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+
+        VrrbDb::new(VrrbDbConfig {
+            path,
+            ..Default::default()
+        })
+    }
 
+    async fn get(router: &mut Router, uri: String) -> StatusCode {
         let request = Request::builder()
-            .uri("/:id")
+            .uri(uri)
             .method("GET")
             .body(Body::empty())
             .unwrap();
 
         let response = router.ready().await.unwrap().call(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::OK);
+        response.status()
+    }
+
+    #[tokio::test]
+    async fn get_account_returns_stored_accounts() {
+        let mut db = create_test_db();
+
+        let (_, public_key) = generate_mock_account_keypair();
+        let address = Address::new(public_key);
+        db.insert_account(address.clone(), Account::new(public_key))
+            .unwrap();
+
+        let mut router = create_account_router(db.read_handle());
+
+        assert_eq!(
+            get(&mut router, format!("/{address}")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn get_account_rejects_unknown_and_malformed_addresses() {
+        let db = create_test_db();
+        let mut router = create_account_router(db.read_handle());
+
+        let (_, public_key) = generate_mock_account_keypair();
+        let unknown = Address::new(public_key);
+
+        assert_eq!(
+            get(&mut router, format!("/{unknown}")).await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            get(&mut router, "/not-an-address".to_string()).await,
+            StatusCode::BAD_REQUEST
+        );
     }
 }
@@ -0,0 +1,27 @@
+use axum::{extract::Path, routing::get, Extension, Json, Router};
+use storage::vrrbdb::VrrbDbReadHandle;
+
+use crate::{
+    http::{into_rest_error, RestError},
+    rpc::api::RpcBlockCertificationDetail,
+};
+
+pub fn create_block_router(vrrbdb_read_handle: VrrbDbReadHandle) -> Router {
+    Router::new()
+        .route("/:hash", get(get_block))
+        .layer(Extension(vrrbdb_read_handle))
+}
+
+/// Returns the votes, partial signatures and certificate recorded for a
+/// block. Blocks themselves are not persisted, their certification records
+/// are what the node keeps around.
+async fn get_block(
+    Extension(vrrbdb_read_handle): Extension<VrrbDbReadHandle>,
+    Path(hash): Path<String>,
+) -> Result<Json<RpcBlockCertificationDetail>, RestError> {
+    let certification = vrrbdb_read_handle
+        .get_block_certification(&hash)
+        .map_err(into_rest_error)?;
+
+    Ok(Json(RpcBlockCertificationDetail::from(certification)))
+}
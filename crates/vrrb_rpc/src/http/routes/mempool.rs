@@ -0,0 +1,23 @@
+use axum::{routing::get, Extension, Json, Router};
+use mempool::MempoolReadHandleFactory;
+
+use crate::rpc::api::RpcTransactionRecord;
+
+pub fn create_mempool_router(mempool_read_handle_factory: MempoolReadHandleFactory) -> Router {
+    Router::new()
+        .route("/", get(get_pending_txns))
+        .layer(Extension(mempool_read_handle_factory))
+}
+
+/// Returns every transaction waiting in the mempool
+async fn get_pending_txns(
+    Extension(mempool_read_handle_factory): Extension<MempoolReadHandleFactory>,
+) -> Json<Vec<RpcTransactionRecord>> {
+    let txns = mempool_read_handle_factory
+        .values()
+        .into_iter()
+        .map(RpcTransactionRecord::from)
+        .collect();
+
+    Json(txns)
+}
@@ -1,2 +1,5 @@
 pub mod accounts;
+pub mod blocks;
 pub mod health;
+pub mod mempool;
+pub mod txns;
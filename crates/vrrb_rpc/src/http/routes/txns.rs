@@ -0,0 +1,81 @@
+use axum::{extract::Path, routing::get, Extension, Json, Router};
+use mempool::MempoolReadHandleFactory;
+use primitives::ErrorCode;
+use storage::vrrbdb::VrrbDbReadHandle;
+use vrrb_core::transactions::TransactionDigest;
+
+use crate::{http::RestError, rpc::api::RpcTransactionRecord};
+
+pub fn create_txn_router(
+    vrrbdb_read_handle: VrrbDbReadHandle,
+    mempool_read_handle_factory: MempoolReadHandleFactory,
+) -> Router {
+    Router::new()
+        .route("/:digest", get(get_txn))
+        .layer(Extension(vrrbdb_read_handle))
+        .layer(Extension(mempool_read_handle_factory))
+}
+
+/// Returns a transaction from the transaction store, or from the mempool if
+/// it is still pending
+async fn get_txn(
+    Extension(vrrbdb_read_handle): Extension<VrrbDbReadHandle>,
+    Extension(mempool_read_handle_factory): Extension<MempoolReadHandleFactory>,
+    Path(digest): Path<String>,
+) -> Result<Json<RpcTransactionRecord>, RestError> {
+    let digest = digest.parse::<TransactionDigest>().map_err(|_err| {
+        RestError::new(
+            ErrorCode::InvalidInput,
+            "unable to parse transaction digest",
+        )
+    })?;
+
+    if let Some(txn) = vrrbdb_read_handle
+        .transaction_store_values()
+        .remove(&digest)
+    {
+        return Ok(Json(RpcTransactionRecord::from(txn)));
+    }
+
+    mempool_read_handle_factory
+        .get(&digest)
+        .map(|record| Json(RpcTransactionRecord::from(record.txn)))
+        .ok_or_else(|| RestError::new(ErrorCode::NotFound, "unable to find transaction"))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use tower::{Service, ServiceExt};
+
+    use super::*;
+    use crate::http::HttpApiServerConfig;
+
+    #[tokio::test]
+    async fn get_txn_rejects_unknown_and_malformed_digests() {
+        let config = HttpApiServerConfig::default();
+
+        let mut router = create_txn_router(
+            config.vrrbdb_read_handle,
+            config.mempool_read_handle_factory,
+        );
+
+        for (uri, status) in [
+            (format!("/{}", "ab".repeat(32)), StatusCode::NOT_FOUND),
+            ("/not-a-digest".to_string(), StatusCode::BAD_REQUEST),
+        ] {
+            let request = Request::builder()
+                .uri(uri)
+                .method("GET")
+                .body(Body::empty())
+                .unwrap();
+
+            let response = router.ready().await.unwrap().call(request).await.unwrap();
+
+            assert_eq!(response.status(), status);
+        }
+    }
+}
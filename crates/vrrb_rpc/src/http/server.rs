@@ -39,6 +39,8 @@ impl HttpApiServer {
             api_title: config.api_title.clone(),
             api_version: config.api_version.clone(),
             server_timeout: config.server_timeout,
+            vrrbdb_read_handle: config.vrrbdb_read_handle.clone(),
+            mempool_read_handle_factory: config.mempool_read_handle_factory.clone(),
         };
 
         let tls_config = config.tls_config;
@@ -1,27 +1,96 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use block::block::Block;
-use block::ClaimHash;
+use block::{
+    BlockHash, Certificate, Checkpoint, ClaimHash, EpochSummary, HaltAction, SignedHaltDirective,
+};
+use events::{
+    BacklogSnapshot, BlockRangeChunk, DkgStatus, PeerRecord, PendingBlockPreview, StateDiff,
+    SyncStatus, TxnResurrectionStatus, TxnStatusChange, ValidatorDuties,
+};
 use jsonrpsee::{core::Error, proc_macros::rpc};
-use primitives::{Address, NodeType, Round};
+use mempool::MempoolStats;
+use primitives::{
+    Address, Epoch, NodeId, NodeType, QuorumKind, QuorumPubkey, Round, TxnValidationStatus, Weight,
+};
 use secp256k1::PublicKey;
 use serde::{Deserialize, Serialize};
-use storage::vrrbdb::Claims;
+use storage::vrrbdb::{
+    BlockCertification, Claims, QuorumRecord, RoundTimeline, StateSnapshot, StateSnapshotDiff,
+    TxnReceipt, ValidatorEarnings,
+};
 use vrrb_config::bootstrap_quorum::QuorumMembershipConfig;
-use vrrb_core::account::Account;
+use vrrb_core::account::{Account, AccountNonce};
 use vrrb_core::claim::Claim;
 use vrrb_core::node_health_report::NodeHealthReport;
+use vrrb_core::serde_helpers::decode_from_binary_byte_slice;
 use vrrb_core::transactions::{
-    NewTransferArgs, Token, Transaction, TransactionKind, TxAmount, TxNonce, TxTimestamp,
+    NewTransferArgs, Token, Transaction, TransactionKind, TxAmount, TxMemo, TxNonce, TxTimestamp,
+    UnsignedTransfer, UnsignedTransferArgs,
 };
 
-use crate::rpc::SignOpts;
+use crate::rpc::{versioning::RpcApiVersion, SignOpts};
 
 pub type ExampleHash = [u8; 32];
 pub type ExampleStorageKey = Vec<u8>;
 pub type FullStateSnapshot = HashMap<Address, Account>;
 pub type FullMempoolSnapshot = Vec<RpcTransactionRecord>;
 
+/// Largest number of accounts a single `state_getStatePage` call returns
+pub const MAX_STATE_PAGE_SIZE: usize = 1000;
+
+/// Largest number of checkpoints a single `state_listCheckpoints` call
+/// returns
+pub const MAX_CHECKPOINT_PAGE_SIZE: usize = 100;
+
+/// Largest number of epoch summaries a single `state_listEpochSummaries` call
+/// returns
+pub const MAX_EPOCH_SUMMARY_PAGE_SIZE: usize = 100;
+
+/// How long a `state_getBacklogSnapshot` call waits on the node to take the
+/// snapshot
+pub const BACKLOG_SNAPSHOT_TIMEOUT_MS: u64 = 5_000;
+
+/// How long a `state_getDuties` call waits on the node to look up the duties
+pub const DUTIES_TIMEOUT_MS: u64 = 5_000;
+
+/// Widest range of epochs a single `state_getValidatorEarnings` call covers
+pub const MAX_EARNINGS_EPOCHS: Epoch = 1000;
+
+/// How long a `state_listPeers` call waits on the node to read its address
+/// book
+pub const PEERS_TIMEOUT_MS: u64 = 5_000;
+
+/// How long a `state_getSyncStatus` call waits on the node to report how far
+/// it lags behind
+pub const SYNC_STATUS_TIMEOUT_MS: u64 = 5_000;
+
+/// How long a `state_getPendingBlockPreview` call waits on the node to
+/// assemble the preview
+pub const PENDING_BLOCK_PREVIEW_TIMEOUT_MS: u64 = 5_000;
+
+/// How long a `state_getMempoolStats` call waits on the node to summarize its
+/// mempool
+pub const MEMPOOL_STATS_TIMEOUT_MS: u64 = 5_000;
+
+/// How long a `state_getDkgStatus` call waits on the node to report how far
+/// its key generation got
+pub const DKG_STATUS_TIMEOUT_MS: u64 = 5_000;
+
+/// Widest range of rounds a single `state_getBlocksByRoundRange`
+/// subscription covers
+pub const MAX_BLOCK_RANGE_ROUNDS: Round = 1000;
+
+/// Largest number of blocks a single `blockRangeChunk` notification carries
+pub const BLOCK_RANGE_CHUNK_SIZE: usize = 20;
+
+/// How long a `state_getBlocksByRoundRange` subscription waits on the node
+/// for its next chunk of blocks
+pub const BLOCK_RANGE_TIMEOUT_MS: u64 = 10_000;
+
+/// Largest number of accounts a single replication snapshot page carries
+pub const REPLICATION_SNAPSHOT_PAGE_SIZE: usize = 500;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionRecord {
     //
@@ -46,6 +115,10 @@ pub struct RpcTransactionRecord {
     pub signature: String,
     pub validators: HashMap<String, bool>,
     pub nonce: TxNonce,
+    pub fee: u128,
+
+    /// Hex encoded memo the txn carries, if any
+    pub memo: Option<String>,
 }
 
 impl From<TransactionKind> for RpcTransactionRecord {
@@ -61,10 +134,423 @@ impl From<TransactionKind> for RpcTransactionRecord {
             signature: txn.signature().to_string(),
             validators: txn.validators().unwrap_or_default(),
             nonce: txn.nonce(),
+            fee: txn.fee(),
+            memo: txn.memo().map(hex::encode),
+        }
+    }
+}
+
+/// The state root committed at the end of a round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcStateRoot {
+    pub round: Round,
+    pub version: u64,
+    pub root_hash: String,
+}
+
+/// A page of accounts in state. Pass `next_cursor` back as `start_after` to
+/// fetch the following page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcStatePage {
+    pub accounts: Vec<Account>,
+    pub next_cursor: Option<Address>,
+}
+
+/// A validator's vote on a block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcCertificationVote {
+    pub voter: NodeId,
+    pub approved: bool,
+    pub signature: String,
+}
+
+/// A validator's partial signature over a block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcPartialSignature {
+    pub signer: NodeId,
+    pub public_key_share: String,
+    pub signature: String,
+}
+
+/// The votes, partial signatures and certificate recorded for a block, along
+/// with the validators that contributed to its certificate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcBlockCertificationDetail {
+    pub block_hash: BlockHash,
+    pub certified: bool,
+    pub contributors: Vec<NodeId>,
+    pub votes: Vec<RpcCertificationVote>,
+    pub partial_signatures: Vec<RpcPartialSignature>,
+    pub certificate: Option<Certificate>,
+}
+
+impl From<BlockCertification> for RpcBlockCertificationDetail {
+    fn from(certification: BlockCertification) -> Self {
+        Self {
+            certified: certification.is_certified(),
+            contributors: certification.contributors().into_iter().collect(),
+            votes: certification
+                .votes
+                .into_iter()
+                .map(|vote| RpcCertificationVote {
+                    voter: vote.voter,
+                    approved: vote.approved,
+                    signature: hex::encode(vote.signature),
+                })
+                .collect(),
+            partial_signatures: certification
+                .partial_signatures
+                .into_iter()
+                .map(|partial_signature| RpcPartialSignature {
+                    signer: partial_signature.signer,
+                    public_key_share: hex::encode(partial_signature.public_key_share),
+                    signature: hex::encode(partial_signature.signature),
+                })
+                .collect(),
+            block_hash: certification.block_hash,
+            certificate: certification.certificate,
+        }
+    }
+}
+
+/// How a txn was validated and certified by a farmer quorum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcTxnCertification {
+    pub certified_at: i64,
+    pub quorum_public_key: String,
+    pub farmer_id: NodeId,
+    pub validation_status: TxnValidationStatus,
+    pub votes: usize,
+    pub approvals: usize,
+    pub quorum_threshold: usize,
+}
+
+/// The timeline of a txn as witnessed by the node: when it entered the
+/// mempool, how it was certified, the blocks it was included in and the round
+/// it was finalized in. Stages the txn hasn't reached, or the node didn't
+/// witness, are left empty
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcTxnTrace {
+    pub digest: RpcTransactionDigest,
+    pub admitted_at: Option<i64>,
+
+    /// Whether the txn is still waiting in the node's mempool
+    pub pending: bool,
+    pub certification: Option<RpcTxnCertification>,
+    pub proposal_block_hash: Option<BlockHash>,
+    pub convergence_block_hash: Option<BlockHash>,
+    pub included_at: Option<i64>,
+
+    /// Round of the convergence block the txn was included in, once that
+    /// block got certified
+    pub finalized_round: Option<Round>,
+
+    /// Hex encoded memo the txn carries, if the node still holds the txn
+    pub memo: Option<String>,
+}
+
+impl RpcTxnTrace {
+    /// Builds a trace out of the receipt the node recorded for a txn and
+    /// whether the convergence block it was included in got certified
+    pub fn from_receipt(receipt: TxnReceipt, pending: bool, finalized: bool) -> Self {
+        let finalized_round = receipt
+            .inclusion
+            .as_ref()
+            .filter(|_| finalized)
+            .map(|inclusion| inclusion.round);
+
+        Self {
+            digest: receipt.digest.to_string(),
+            admitted_at: receipt.admitted_at,
+            pending,
+            certification: receipt
+                .certification
+                .map(|certification| RpcTxnCertification {
+                    certified_at: certification.certified_at,
+                    quorum_public_key: hex::encode(certification.quorum_public_key),
+                    farmer_id: certification.farmer_id,
+                    validation_status: certification.validation_status,
+                    votes: certification.votes,
+                    approvals: certification.approvals,
+                    quorum_threshold: certification.quorum_threshold,
+                }),
+            proposal_block_hash: receipt
+                .inclusion
+                .as_ref()
+                .map(|inclusion| inclusion.proposal_block_hash.clone()),
+            convergence_block_hash: receipt
+                .inclusion
+                .as_ref()
+                .map(|inclusion| inclusion.convergence_block_hash.clone()),
+            included_at: receipt.inclusion.map(|inclusion| inclusion.included_at),
+            finalized_round,
+            memo: None,
+        }
+    }
+
+    pub fn with_memo(mut self, memo: Option<&TxMemo>) -> Self {
+        self.memo = memo.map(hex::encode);
+        self
+    }
+}
+
+/// An account as it will be once the txns pending in the node's mempool are
+/// applied on top of its confirmed state, so wallets can chain txns without
+/// waiting on each one to be included.
+///
+/// The projection is unconfirmed: pending txns may still be rejected,
+/// reordered or dropped, so only the `confirmed_` fields are final.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcProjectedAccount {
+    pub address: Address,
+
+    /// Always false, the projection accounts for txns that aren't included in
+    /// a block yet
+    pub confirmed: bool,
+    pub confirmed_nonce: AccountNonce,
+    pub confirmed_balance: u128,
+    pub projected_nonce: AccountNonce,
+    pub projected_balance: u128,
+
+    /// Amount the pending txns the account sent take out of it
+    pub pending_debits: u128,
+
+    /// Amount the pending txns the account receives add to it
+    pub pending_credits: u128,
+
+    /// Pending txns the projection accounts for, the ones sent by the account
+    /// first, by nonce
+    pub pending_txns: Vec<RpcTransactionDigest>,
+}
+
+impl RpcProjectedAccount {
+    /// Lays the `pending` txns that involve `address` over its confirmed
+    /// `account`, which may not exist yet. Nonces and balances move the same
+    /// way they do once a block including the txns is applied
+    pub fn project(
+        address: Address,
+        account: Option<&Account>,
+        pending: &[TransactionKind],
+    ) -> Self {
+        let confirmed_nonce = account.map_or(0, |account| account.nonce());
+        let confirmed_balance = account.map_or(0, |account| account.balance());
+
+        let mut sent: Vec<&TransactionKind> = pending
+            .iter()
+            .filter(|txn| txn.sender_address() == address)
+            .collect();
+        sent.sort_by_key(|txn| (txn.nonce(), txn.id()));
+
+        let mut received: Vec<&TransactionKind> = pending
+            .iter()
+            .filter(|txn| txn.receiver_address() == address && txn.sender_address() != address)
+            .collect();
+        received.sort_by_key(|txn| txn.id());
+
+        let projected_nonce = sent.iter().fold(confirmed_nonce, |nonce, txn| {
+            if txn.nonce() > nonce + 1 {
+                txn.nonce()
+            } else {
+                nonce + 1
+            }
+        });
+
+        // NOTE: txns an account sends to itself debit and credit it alike
+        let pending_debits = sent
+            .iter()
+            .filter(|txn| txn.receiver_address() != address)
+            .fold(0u128, |debits, txn| debits.saturating_add(txn.amount()));
+        let pending_credits = received
+            .iter()
+            .fold(0u128, |credits, txn| credits.saturating_add(txn.amount()));
+
+        Self {
+            address,
+            confirmed: false,
+            confirmed_nonce,
+            confirmed_balance,
+            projected_nonce,
+            projected_balance: confirmed_balance
+                .saturating_add(pending_credits)
+                .saturating_sub(pending_debits),
+            pending_debits,
+            pending_credits,
+            pending_txns: sent
+                .into_iter()
+                .chain(received)
+                .map(|txn| txn.id().to_string())
+                .collect(),
+        }
+    }
+}
+
+/// The weight a txn would add to a block, next to the block weight cap and
+/// the weight of the txns already waiting in the mempool, so clients can
+/// estimate how soon it would be included
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcTxnWeight {
+    pub weight: Weight,
+    pub max_block_weight: Weight,
+    pub pending_weight: Weight,
+}
+
+/// A quorum member and the validator key it contributed to the quorum's DKG
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcQuorumMember {
+    pub node_id: NodeId,
+    pub validator_public_key: String,
+}
+
+/// A quorum as it was formed at the start of an epoch, along with the
+/// certificate, signed by the previous quorum, that inaugurated it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcQuorum {
+    pub epoch: Epoch,
+    pub quorum_kind: QuorumKind,
+    pub members: Vec<RpcQuorumMember>,
+    pub group_public_key: QuorumPubkey,
+    pub threshold: u16,
+    pub inauguration: Option<Certificate>,
+}
+
+impl From<QuorumRecord> for RpcQuorum {
+    fn from(record: QuorumRecord) -> Self {
+        Self {
+            epoch: record.epoch,
+            quorum_kind: record.quorum_kind,
+            members: record
+                .members
+                .into_iter()
+                .map(|(node_id, validator_public_key)| RpcQuorumMember {
+                    node_id,
+                    validator_public_key: hex::encode(validator_public_key.to_bytes()),
+                })
+                .collect(),
+            group_public_key: record.group_public_key,
+            threshold: record.threshold,
+            inauguration: record.inauguration,
+        }
+    }
+}
+
+/// A change in the native token balance of a watched account, pushed to
+/// `state_subscribeBalanceChanges` subscribers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcBalanceChange {
+    pub address: Address,
+    pub block_hash: BlockHash,
+    pub round: Round,
+    pub previous: u128,
+    pub current: u128,
+}
+
+impl RpcBalanceChange {
+    pub fn from_state_diff(state_diff: &StateDiff) -> Vec<Self> {
+        state_diff
+            .balance_changes
+            .iter()
+            .map(|change| Self {
+                address: change.address.clone(),
+                block_hash: state_diff.block_hash.clone(),
+                round: state_diff.round,
+                previous: change.previous,
+                current: change.current,
+            })
+            .collect()
+    }
+}
+
+/// A change in the status of a watched txn after the block it was included
+/// in was rolled back, pushed to `state_subscribeTxnStatus` subscribers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcTxnStatusChange {
+    pub digest: RpcTransactionDigest,
+    pub block_hash: BlockHash,
+    pub status: TxnResurrectionStatus,
+}
+
+impl From<TxnStatusChange> for RpcTxnStatusChange {
+    fn from(change: TxnStatusChange) -> Self {
+        Self {
+            digest: change.digest.to_string(),
+            block_hash: change.block_hash,
+            status: change.status,
+        }
+    }
+}
+
+/// The state diff of an applied block, with the accounts it updated decoded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcStateDiff {
+    pub block_hash: BlockHash,
+    pub round: Round,
+    pub previous_state_root: String,
+    pub state_root: String,
+    pub accounts: Vec<Account>,
+}
+
+impl From<StateDiff> for RpcStateDiff {
+    fn from(state_diff: StateDiff) -> Self {
+        let accounts = state_diff
+            .updated_accounts
+            .iter()
+            .filter_map(|account| decode_from_binary_byte_slice(account).ok())
+            .collect();
+
+        Self {
+            block_hash: state_diff.block_hash,
+            round: state_diff.round,
+            previous_state_root: state_diff.previous_state_root,
+            state_root: state_diff.state_root,
+            accounts,
+        }
+    }
+}
+
+/// How far a node lags behind the chain its peers announced. Load balancers
+/// can route traffic away from nodes that are still `syncing`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcSyncStatus {
+    pub applied_round: Round,
+    pub best_known_round: Round,
+    pub syncing: bool,
+    pub blocks_per_second: f64,
+
+    /// Seconds left until the node catches up, unknown while it imports
+    /// nothing
+    pub eta_secs: Option<u64>,
+}
+
+impl From<SyncStatus> for RpcSyncStatus {
+    fn from(status: SyncStatus) -> Self {
+        Self {
+            applied_round: status.applied_round,
+            best_known_round: status.best_known_round,
+            syncing: status.syncing,
+            blocks_per_second: status.import_rate_millis as f64 / 1000.0,
+            eta_secs: status.eta_secs,
         }
     }
 }
 
+/// An update pushed to `state_subscribeReplication` subscribers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RpcReplicationUpdate {
+    /// A page of the state as of the end of `round`. The page marked `last`
+    /// ends the snapshot, at which point the accounts of every page add up to
+    /// `state_root`. Nodes that haven't committed a round yet send their
+    /// genesis state with an empty root
+    SnapshotPage {
+        round: Round,
+        state_root: String,
+        accounts: Vec<Account>,
+        last: bool,
+    },
+
+    /// The state diff of a block applied after the latest snapshot
+    Diff(RpcStateDiff),
+}
+
 #[rpc(server, client, namespace = "state")]
 #[async_trait]
 pub trait RpcApi {
@@ -107,12 +593,86 @@ pub trait RpcApi {
     #[method(name = "getAccount")]
     async fn get_account(&self, address: Address) -> Result<Account, Error>;
 
+    /// Returns an account with the txns pending in the node's mempool laid
+    /// over its confirmed nonce and balance. The projection is labeled as
+    /// unconfirmed, pending txns may still be rejected or dropped
+    #[method(name = "getProjectedAccount")]
+    async fn get_projected_account(&self, address: Address) -> Result<RpcProjectedAccount, Error>;
+
+    /// Returns how much of a token an account holds. Defaults to the native
+    /// token, the only one accounts hold for now
+    #[method(name = "getBalance")]
+    async fn get_balance(&self, address: Address, token: Option<Token>) -> Result<u128, Error>;
+
+    /// Notifies the subscriber every time the balance of one of `addresses`
+    /// changes, once the block changing it has been applied to state
+    #[subscription(
+        name = "subscribeBalanceChanges" => "balanceChanged",
+        unsubscribe = "unsubscribeBalanceChanges",
+        item = RpcBalanceChange
+    )]
+    fn subscribe_balance_changes(&self, addresses: Vec<Address>);
+
+    /// Notifies the subscriber when one of the txns in `digests` is returned
+    /// to the mempool or dropped, after the block it was included in was
+    /// rolled back
+    #[subscription(
+        name = "subscribeTxnStatus" => "txnStatusChanged",
+        unsubscribe = "unsubscribeTxnStatus",
+        item = RpcTxnStatusChange
+    )]
+    fn subscribe_txn_status(&self, digests: Vec<RpcTransactionDigest>);
+
+    /// Streams the convergence blocks of rounds `start..=end` still within
+    /// the node's DAG, oldest first, each with the proposal blocks it
+    /// references and its certificate. Blocks come in chunks of up to
+    /// `BLOCK_RANGE_CHUNK_SIZE`, the chunk marked `last` ends the stream.
+    /// Ranges wider than `MAX_BLOCK_RANGE_ROUNDS` are cut short, so indexers
+    /// backfill by resubscribing from the round after the last one received
+    #[subscription(
+        name = "getBlocksByRoundRange" => "blockRangeChunk",
+        unsubscribe = "unsubscribeBlocksByRoundRange",
+        item = BlockRangeChunk
+    )]
+    fn get_blocks_by_round_range(&self, start: Round, end: Round);
+
+    /// Streams the state of the node to a read replica: a snapshot of the
+    /// state as of the latest committed round, then the diff of every block
+    /// applied since, in order. A fresh snapshot follows every
+    /// `snapshot_interval_rounds` rounds, and whenever the subscriber falls
+    /// too far behind to be sent every diff
+    #[subscription(
+        name = "subscribeReplication" => "replicationUpdate",
+        unsubscribe = "unsubscribeReplication",
+        item = RpcReplicationUpdate
+    )]
+    fn subscribe_replication(&self, snapshot_interval_rounds: Option<Round>);
+
     #[method(name = "faucetDrip")]
     async fn faucet_drip(&self, address: Address) -> Result<(), Error>;
 
     #[method(name = "signTransaction")]
     async fn sign_transaction(&self, sign_opts: SignOpts) -> Result<String, Error>;
 
+    /// Exports a transfer for signing on an air-gapped machine, along with
+    /// the preimage of its digest the sender has to sign
+    #[method(name = "exportUnsignedTxn")]
+    async fn export_unsigned_txn(
+        &self,
+        args: UnsignedTransferArgs,
+    ) -> Result<UnsignedTransfer, Error>;
+
+    /// Assembles a transfer exported with `exportUnsignedTxn` out of the
+    /// detached signature its sender produced offline and submits it. The
+    /// transfer is rejected unless the signature was made by the sender over
+    /// the exported payload
+    #[method(name = "importSignedTxn")]
+    async fn import_signed_txn(
+        &self,
+        unsigned_txn: UnsignedTransfer,
+        signature: String,
+    ) -> Result<RpcTransactionRecord, Error>;
+
     #[method(name = "getRound")]
     async fn get_round(&self) -> Result<Round, Error>;
 
@@ -131,6 +691,48 @@ pub trait RpcApi {
     #[method(name = "getNodeHealth")]
     async fn get_node_health(&self) -> Result<NodeHealthReport, Error>;
 
+    /// Pauses (`true`) or resumes (`false`) the node's participation in
+    /// consensus. A paused node keeps following the chain and serving reads
+    #[method(name = "setMaintenanceMode")]
+    async fn set_maintenance_mode(&self, paused: bool) -> Result<(), Error>;
+
+    /// Has the node sign a directive to halt or resume block production
+    /// across the network. It takes effect once more than the threshold of
+    /// the harvester quorum signed it. `round` has to be later than that of
+    /// the last directive acted on
+    #[method(name = "requestHaltDirective")]
+    async fn request_halt_directive(
+        &self,
+        action: HaltAction,
+        round: Round,
+        reason: String,
+    ) -> Result<(), Error>;
+
+    /// Returns every halt and resume directive the node acted on, oldest
+    /// first
+    #[method(name = "getHaltLog")]
+    async fn get_halt_log(&self) -> Result<Vec<SignedHaltDirective>, Error>;
+
+    /// Returns the accounts and claims the node currently holds, for a peer
+    /// that disagrees on the state root to diff against with `diffState`
+    #[method(name = "getStateSnapshot")]
+    async fn get_state_snapshot(&self) -> Result<StateSnapshot, Error>;
+
+    /// Compares the node's state to `peer`, listing the accounts and claims
+    /// whose values differ and the trie path under which they part ways
+    #[method(name = "diffState")]
+    async fn diff_state(&self, peer: StateSnapshot) -> Result<StateSnapshotDiff, Error>;
+
+    /// Returns when the node saw the first proposal of each retained round,
+    /// gathered enough partial signatures over its convergence block, created
+    /// its certificate and applied it, as Unix timestamps in milliseconds.
+    /// Only the `last` most recent rounds are returned if set, oldest first
+    #[method(name = "getConsensusTimeline")]
+    async fn get_consensus_timeline(
+        &self,
+        last: Option<usize>,
+    ) -> Result<Vec<RoundTimeline>, Error>;
+
     #[method(name = "getClaimsByAccountId")]
     async fn get_claims_by_account_id(&self, address: Address) -> Result<Claims, Error>;
 
@@ -145,4 +747,246 @@ pub trait RpcApi {
 
     #[method(name = "getLastBlock")]
     async fn get_last_block(&self) -> Result<Block, Error>;
+
+    /// Returns an account as it was at the end of a past round, as long as
+    /// the node hasn't pruned that round
+    #[method(name = "getAccountAtRound")]
+    async fn get_account_at_round(&self, address: Address, round: Round) -> Result<Account, Error>;
+
+    /// Returns the state root committed at the end of a past round, as long
+    /// as the node hasn't pruned that round
+    #[method(name = "getStateRoot")]
+    async fn get_state_root(&self, round: Round) -> Result<RpcStateRoot, Error>;
+
+    /// Returns up to `limit` accounts whose address starts with `prefix`,
+    /// resuming after the `start_after` cursor of a previous page
+    #[method(name = "getStatePage")]
+    async fn get_state_page(
+        &self,
+        prefix: Option<String>,
+        start_after: Option<Address>,
+        limit: usize,
+    ) -> Result<RpcStatePage, Error>;
+
+    /// Returns which validators voted on and signed a block, along with its
+    /// certificate, as long as the node hasn't pruned them
+    #[method(name = "getBlockCertificationDetail")]
+    async fn get_block_certification_detail(
+        &self,
+        block_hash: BlockHash,
+    ) -> Result<RpcBlockCertificationDetail, Error>;
+
+    /// Returns the quorum in effect at `epoch`, or the current quorum if no
+    /// epoch is given, with the group public key its certificates verify
+    /// against
+    #[method(name = "getQuorum")]
+    async fn get_quorum(&self, epoch: Option<Epoch>) -> Result<RpcQuorum, Error>;
+
+    /// Returns the quorum-signed checkpoint taken at `round`, or the latest
+    /// one if no round is given. Checkpoints can be checked with the `signer`
+    /// crate's `verify_checkpoint` without running a node
+    #[method(name = "getCheckpoint")]
+    async fn get_checkpoint(&self, round: Option<Round>) -> Result<Checkpoint, Error>;
+
+    /// Returns up to `limit` checkpoints, oldest first, resuming after the
+    /// round of the last checkpoint of a previous page
+    #[method(name = "listCheckpoints")]
+    async fn list_checkpoints(
+        &self,
+        start_after: Option<Round>,
+        limit: usize,
+    ) -> Result<Vec<Checkpoint>, Error>;
+
+    /// Returns the summary of `epoch`, or of the latest epoch summarized if no
+    /// epoch is given. Chains of summaries can be checked with the `signer`
+    /// crate's `verify_epoch_summary_chain` without running a node
+    #[method(name = "getEpochSummary")]
+    async fn get_epoch_summary(&self, epoch: Option<Epoch>) -> Result<EpochSummary, Error>;
+
+    /// Returns up to `limit` epoch summaries, oldest first, resuming after the
+    /// epoch of the last summary of a previous page
+    #[method(name = "listEpochSummaries")]
+    async fn list_epoch_summaries(
+        &self,
+        start_after: Option<Epoch>,
+        limit: usize,
+    ) -> Result<Vec<EpochSummary>, Error>;
+
+    /// Returns the weight the given txn would add to a block
+    #[method(name = "estimateTxnWeight")]
+    async fn estimate_txn_weight(&self, txn: TransactionKind) -> Result<RpcTxnWeight, Error>;
+
+    /// Returns a snapshot of the node's mempool and consensus backlog: the
+    /// votes and certificate shares gathered for blocks not yet certified,
+    /// the pending DKG messages and where the node stands in the round
+    /// schedule. Meant for debugging stalled certification
+    #[method(name = "getBacklogSnapshot")]
+    async fn get_backlog_snapshot(&self) -> Result<BacklogSnapshot, Error>;
+
+    /// Returns the timeline of a txn: its admission into the mempool, its
+    /// validation and certification, the blocks it was included in and the
+    /// round it was finalized in
+    #[method(name = "getTxnTrace")]
+    async fn get_txn_trace(&self, digest: RpcTransactionDigest) -> Result<RpcTxnTrace, Error>;
+
+    /// Returns the duties of a validator during `epoch`: the quorum it sits
+    /// in, when it activates or exits and the mining slots it can expect.
+    /// Only the next block's miner is known for certain, every other slot is
+    /// estimated from the validator's share of the miner claims
+    #[method(name = "getDuties")]
+    async fn get_duties(&self, node_id: NodeId, epoch: Epoch) -> Result<ValidatorDuties, Error>;
+
+    /// Returns the block rewards, fee shares and penalties a validator
+    /// accumulated during each epoch from `start_epoch` to `end_epoch`
+    /// inclusive. Epochs it neither earned nor lost anything in are left out
+    #[method(name = "getValidatorEarnings")]
+    async fn get_validator_earnings(
+        &self,
+        node_id: NodeId,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<Vec<ValidatorEarnings>, Error>;
+
+    /// Returns the peers in the node's address book, best ranked first, with
+    /// when they were last heard from and a summary of their reputation
+    #[method(name = "listPeers")]
+    async fn list_peers(&self) -> Result<Vec<PeerRecord>, Error>;
+
+    /// Returns the round of the latest block the node applied next to the
+    /// highest certified round received from peers, whether the node is still
+    /// syncing, how fast it imports blocks and how soon it should catch up
+    #[method(name = "getSyncStatus")]
+    async fn get_sync_status(&self) -> Result<RpcSyncStatus, Error>;
+
+    /// Returns the proposal block the node would produce for the next round,
+    /// without mining or committing it: the txns it would include, its
+    /// estimated weight and size and the fees it would earn. Meant for block
+    /// producers tuning their fee and selection settings
+    #[method(name = "getPendingBlockPreview")]
+    async fn get_pending_block_preview(&self) -> Result<PendingBlockPreview, Error>;
+
+    /// Returns the number and weight of the pending txns, the fees they pay
+    /// at a few percentiles, how long they have been waiting and how full
+    /// recent blocks were. Given a `fee`, also estimates how many rounds a
+    /// txn paying it would wait for a block to include it, so wallets can
+    /// price their txns
+    #[method(name = "getMempoolStats")]
+    async fn get_mempool_stats(&self, fee: Option<u128>) -> Result<MempoolStats, Error>;
+
+    /// Returns the phase the key generation of the node's quorum is in and
+    /// for how long it has been in it, how many of the expected parts and
+    /// acks the node holds, and the peers holding it up
+    #[method(name = "getDkgStatus")]
+    async fn get_dkg_status(&self) -> Result<DkgStatus, Error>;
+}
+
+/// A method of the API, with the version it was introduced in and when it was
+/// deprecated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcMethodDescription {
+    pub name: String,
+    pub since: RpcApiVersion,
+    pub deprecated_since: Option<RpcApiVersion>,
+
+    /// Method deprecated methods are superseded by, if any
+    pub replacement: Option<String>,
+}
+
+/// Versions of the API the node serves, along with every method it serves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcApiMethods {
+    /// Latest version, served to clients that don't pin one
+    pub version: RpcApiVersion,
+
+    /// Oldest version clients can still pin
+    pub min_version: RpcApiVersion,
+
+    /// Methods ordered by name
+    pub methods: Vec<RpcMethodDescription>,
+}
+
+#[rpc(server, client, namespace = "rpc")]
+#[async_trait]
+pub trait RpcDiscoveryApi {
+    /// Returns the namespaces of the API, each with the version it's served
+    /// at
+    #[method(name = "modules")]
+    async fn modules(&self) -> Result<BTreeMap<String, RpcApiVersion>, Error>;
+
+    /// Returns every method the node serves, with the version it was
+    /// introduced in and its deprecation, if any
+    #[method(name = "methods")]
+    async fn methods(&self) -> Result<RpcApiMethods, Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use primitives::Signature;
+    use secp256k1::ecdsa;
+    use vrrb_core::{account::AccountField, keypair::KeyPair};
+
+    use super::*;
+
+    fn transfer(
+        sender: &KeyPair,
+        receiver: &Address,
+        amount: u128,
+        nonce: u128,
+    ) -> TransactionKind {
+        let signature: Signature = ecdsa::Signature::from_compact(&[1u8; 64]).unwrap();
+
+        TransactionKind::transfer_builder()
+            .timestamp(0)
+            .sender_address(Address::new(*sender.get_miner_public_key()))
+            .sender_public_key(*sender.get_miner_public_key())
+            .receiver_address(receiver.clone())
+            .amount(amount)
+            .validators(HashMap::<String, bool>::new())
+            .nonce(nonce)
+            .signature(signature)
+            .build_kind()
+            .expect("Failed to build transaction")
+    }
+
+    #[test]
+    fn pending_txns_are_laid_over_the_confirmed_account() {
+        let wallet = KeyPair::random();
+        let address = Address::new(*wallet.get_miner_public_key());
+        let peer = KeyPair::random();
+        let peer_address = Address::new(*peer.get_miner_public_key());
+
+        let mut account = Account::new(*wallet.get_miner_public_key());
+        account.update_field(AccountField::Credits(1_000)).unwrap();
+        account.bump_nonce();
+
+        let pending = vec![
+            transfer(&wallet, &peer_address, 300, 3),
+            transfer(&wallet, &peer_address, 100, 2),
+            transfer(&peer, &address, 50, 1),
+            transfer(&peer, &peer_address, 900, 2),
+        ];
+
+        let projected = RpcProjectedAccount::project(address.clone(), Some(&account), &pending);
+
+        assert!(!projected.confirmed);
+        assert_eq!(projected.confirmed_nonce, 1);
+        assert_eq!(projected.confirmed_balance, 1_000);
+        assert_eq!(projected.projected_nonce, 3);
+        assert_eq!(projected.projected_balance, 650);
+        assert_eq!(projected.pending_debits, 400);
+        assert_eq!(projected.pending_credits, 50);
+        assert_eq!(
+            projected.pending_txns,
+            vec![
+                pending[1].id().to_string(),
+                pending[0].id().to_string(),
+                pending[2].id().to_string()
+            ]
+        );
+
+        // NOTE: accounts that only have txns pending towards them start out empty
+        let projected = RpcProjectedAccount::project(peer_address, None, &pending);
+        assert_eq!(projected.confirmed_balance, 0);
+        assert_eq!(projected.projected_nonce, 2);
+    }
 }
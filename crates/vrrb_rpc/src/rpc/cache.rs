@@ -0,0 +1,373 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use block::BlockHash;
+use events::{ArchivedBlock, StateDiff};
+use primitives::Round;
+use telemetry::{debug, error};
+use tokio::sync::broadcast::{self, error::RecvError};
+use vrrb_core::{cache::Cache, transactions::TransactionKind};
+
+use crate::rpc::api::{RpcBlockCertificationDetail, RpcTransactionDigest};
+
+/// Number of txns, rounds of blocks and block certifications the RPC cache
+/// holds on to each
+pub const RPC_CACHE_CAPACITY: usize = 4096;
+
+/// How long cached entries are served for before they are read again
+pub const RPC_CACHE_TTL_MS: u64 = 10 * 60 * 1000;
+
+#[derive(Debug)]
+struct CachedObjects {
+    txns: Cache<RpcTransactionDigest, TransactionKind>,
+    rounds: Cache<Round, Vec<ArchivedBlock>>,
+    certifications: Cache<BlockHash, RpcBlockCertificationDetail>,
+
+    /// Latest round a state diff was applied for. Only blocks of rounds up to
+    /// it are cached, later ones may still change
+    applied_round: Option<Round>,
+
+    /// Bumped on every invalidation, so reads that started before one can't
+    /// put stale objects back
+    generation: u64,
+}
+
+impl CachedObjects {
+    fn clear(&mut self) {
+        self.txns.clear();
+        self.rounds.clear();
+        self.certifications.clear();
+        self.generation += 1;
+    }
+}
+
+#[derive(Debug, Default)]
+struct RpcCacheMetrics {
+    txn_hits: AtomicU64,
+    txn_misses: AtomicU64,
+    block_hits: AtomicU64,
+    block_misses: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+#[derive(Debug)]
+struct RpcCacheState {
+    objects: Mutex<CachedObjects>,
+    metrics: RpcCacheMetrics,
+}
+
+impl Default for RpcCacheState {
+    fn default() -> Self {
+        Self {
+            objects: Mutex::new(CachedObjects {
+                txns: Cache::new(RPC_CACHE_CAPACITY, RPC_CACHE_TTL_MS),
+                rounds: Cache::new(RPC_CACHE_CAPACITY, RPC_CACHE_TTL_MS),
+                certifications: Cache::new(RPC_CACHE_CAPACITY, RPC_CACHE_TTL_MS),
+                applied_round: None,
+                generation: 0,
+            }),
+            metrics: RpcCacheMetrics::default(),
+        }
+    }
+}
+
+/// Point in time view of how well the RPC cache shields the DB from hot
+/// queries.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RpcCacheStats {
+    pub txn_hits: u64,
+    pub txn_misses: u64,
+
+    /// Lookups of block ranges and block certifications
+    pub block_hits: u64,
+    pub block_misses: u64,
+
+    /// Times cached objects were dropped because blocks were rolled back
+    pub invalidations: u64,
+
+    pub cached_txns: usize,
+    pub cached_rounds: usize,
+    pub cached_certifications: usize,
+}
+
+impl RpcCacheStats {
+    /// Share of all lookups served from the cache, from 0 to 1
+    pub fn hit_rate(&self) -> f64 {
+        let hits = self.txn_hits + self.block_hits;
+        let lookups = hits + self.txn_misses + self.block_misses;
+
+        if lookups == 0 {
+            return 0.0;
+        }
+
+        hits as f64 / lookups as f64
+    }
+}
+
+/// Cloneable view over the counters of the RPC cache.
+#[derive(Debug, Clone, Default)]
+pub struct RpcCacheMonitor {
+    state: Arc<RpcCacheState>,
+}
+
+impl RpcCacheMonitor {
+    pub fn stats(&self) -> RpcCacheStats {
+        let metrics = &self.state.metrics;
+        let objects = self
+            .state
+            .objects
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+
+        RpcCacheStats {
+            txn_hits: metrics.txn_hits.load(Ordering::Relaxed),
+            txn_misses: metrics.txn_misses.load(Ordering::Relaxed),
+            block_hits: metrics.block_hits.load(Ordering::Relaxed),
+            block_misses: metrics.block_misses.load(Ordering::Relaxed),
+            invalidations: metrics.invalidations.load(Ordering::Relaxed),
+            cached_txns: objects.txns.len(),
+            cached_rounds: objects.rounds.len(),
+            cached_certifications: objects.certifications.len(),
+        }
+    }
+}
+
+/// Keeps the txns, blocks and block certifications explorers keep asking
+/// for close at hand, so the JSON-RPC server doesn't read them off the DB
+/// on every request.
+///
+/// Txns and certified blocks don't change once stored, so entries are only
+/// dropped when they expire, get evicted, or a state diff shows the blocks
+/// they came from were rolled back.
+#[derive(Debug, Clone, Default)]
+pub struct RpcCache {
+    state: Arc<RpcCacheState>,
+}
+
+impl RpcCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn monitor(&self) -> RpcCacheMonitor {
+        RpcCacheMonitor {
+            state: self.state.clone(),
+        }
+    }
+
+    fn objects(&self) -> std::sync::MutexGuard<'_, CachedObjects> {
+        // NOTE: the cached objects stay consistent even if a holder of the lock
+        // panicked, as every update is a single insert or clear
+        self.state
+            .objects
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn record_lookup(hits: &AtomicU64, misses: &AtomicU64, hit: bool) {
+        if hit {
+            hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            misses.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Tags the objects read from here on, see [RpcCache::insert_rounds]
+    pub fn generation(&self) -> u64 {
+        self.objects().generation
+    }
+
+    pub fn get_txn(&self, digest: &RpcTransactionDigest) -> Option<TransactionKind> {
+        let txn = self.objects().txns.get(digest).cloned();
+
+        let metrics = &self.state.metrics;
+        Self::record_lookup(&metrics.txn_hits, &metrics.txn_misses, txn.is_some());
+
+        txn
+    }
+
+    pub fn insert_txn(&self, txn: TransactionKind) {
+        self.objects().txns.push(txn.digest().to_string(), txn);
+    }
+
+    /// Returns the blocks of every round from `start` to `end`, in round
+    /// order, as long as all of them are cached
+    pub fn get_rounds(&self, start: Round, end: Round) -> Option<Vec<ArchivedBlock>> {
+        let blocks = {
+            let mut objects = self.objects();
+            let mut blocks = Vec::new();
+
+            (start..=end)
+                .try_for_each(|round| {
+                    blocks.extend(objects.rounds.get(&round)?.iter().cloned());
+                    Some(())
+                })
+                .map(|_| blocks)
+        };
+
+        let metrics = &self.state.metrics;
+        Self::record_lookup(&metrics.block_hits, &metrics.block_misses, blocks.is_some());
+
+        blocks
+    }
+
+    /// Caches the blocks the node listed for the rounds from `start` to `end`.
+    /// Rounds no state diff was applied for yet, or holding uncertified
+    /// blocks, are left out, as are all of them if the cache was invalidated
+    /// since `generation`
+    pub fn insert_rounds(
+        &self,
+        generation: u64,
+        start: Round,
+        end: Round,
+        blocks: Vec<ArchivedBlock>,
+    ) {
+        let mut objects = self.objects();
+
+        let Some(applied_round) = objects.applied_round else {
+            return;
+        };
+
+        if objects.generation != generation || start > applied_round {
+            return;
+        }
+
+        let mut rounds: BTreeMap<Round, Vec<ArchivedBlock>> = (start..=end.min(applied_round))
+            .map(|round| (round, Vec::new()))
+            .collect();
+
+        for block in blocks {
+            if let Some(round_blocks) = rounds.get_mut(&block.convergence.header.round) {
+                round_blocks.push(block);
+            }
+        }
+
+        for (round, round_blocks) in rounds {
+            if round_blocks.iter().all(|block| block.certificate.is_some()) {
+                objects.rounds.push(round, round_blocks);
+            }
+        }
+    }
+
+    pub fn get_certification(&self, block_hash: &BlockHash) -> Option<RpcBlockCertificationDetail> {
+        let certification = self.objects().certifications.get(block_hash).cloned();
+
+        let metrics = &self.state.metrics;
+        Self::record_lookup(
+            &metrics.block_hits,
+            &metrics.block_misses,
+            certification.is_some(),
+        );
+
+        certification
+    }
+
+    /// Caches a block's certification once it is final
+    pub fn insert_certification(&self, certification: RpcBlockCertificationDetail) {
+        if certification.certified {
+            self.objects()
+                .certifications
+                .push(certification.block_hash.clone(), certification);
+        }
+    }
+
+    /// Follows the state diffs applied by the node. A diff for a round that
+    /// was applied before means the blocks from that round on were rolled
+    /// back, along with their txns, so everything cached is dropped
+    pub fn apply_state_diff(&self, state_diff: &StateDiff) {
+        let mut objects = self.objects();
+
+        if objects
+            .applied_round
+            .map_or(false, |applied_round| state_diff.round <= applied_round)
+        {
+            debug!(
+                "dropping the RPC cache as round {} was applied again",
+                state_diff.round
+            );
+
+            objects.clear();
+            self.state
+                .metrics
+                .invalidations
+                .fetch_add(1, Ordering::Relaxed);
+        }
+
+        objects.applied_round = Some(state_diff.round);
+    }
+
+    /// Keeps the cache in line with the state diffs sent over `state_diffs_tx`
+    /// until the channel closes
+    pub fn watch(&self, state_diffs_tx: &broadcast::Sender<StateDiff>) {
+        let cache = self.clone();
+        let mut state_diffs_rx = state_diffs_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                match state_diffs_rx.recv().await {
+                    Ok(state_diff) => cache.apply_state_diff(&state_diff),
+                    Err(RecvError::Lagged(count)) => {
+                        // NOTE: a skipped diff may have been a rollback
+                        error!("RPC cache lagged behind by {count} state diffs, dropping it");
+
+                        cache.objects().clear();
+                        cache
+                            .state
+                            .metrics
+                            .invalidations
+                            .fetch_add(1, Ordering::Relaxed);
+                    },
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_diff(round: Round) -> StateDiff {
+        StateDiff {
+            round,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rounds_are_cached_once_applied_and_dropped_on_rollback() {
+        let cache = RpcCache::new();
+        let monitor = cache.monitor();
+
+        cache.insert_rounds(cache.generation(), 1, 3, vec![]);
+        assert_eq!(cache.get_rounds(1, 1), None);
+
+        cache.apply_state_diff(&state_diff(2));
+
+        let generation = cache.generation();
+        cache.insert_rounds(generation, 1, 3, vec![]);
+
+        // NOTE: round 3 wasn't applied yet, so the range can't be served whole
+        assert_eq!(cache.get_rounds(1, 3), None);
+        assert_eq!(cache.get_rounds(1, 2), Some(vec![]));
+
+        cache.apply_state_diff(&state_diff(2));
+        assert_eq!(cache.get_rounds(1, 2), None);
+
+        // NOTE: blocks read before the rollback must not be cached after it
+        cache.insert_rounds(generation, 1, 2, vec![]);
+        assert_eq!(cache.get_rounds(1, 1), None);
+
+        let stats = monitor.stats();
+        assert_eq!(stats.block_hits, 1);
+        assert_eq!(stats.block_misses, 4);
+        assert_eq!(stats.invalidations, 1);
+        assert_eq!(stats.hit_rate(), 0.2);
+    }
+}
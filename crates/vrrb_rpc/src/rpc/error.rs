@@ -0,0 +1,31 @@
+use jsonrpsee::{
+    core::Error,
+    types::error::{CallError, ErrorObject},
+};
+use primitives::{error_chain, ClassifiedError, ErrorCode};
+use telemetry::{debug, error};
+
+/// Builds a JSON-RPC error carrying `code`. The stable numeric code is always
+/// attached as the error's data, since some codes are reported under the
+/// generic codes reserved by the JSON-RPC spec.
+pub fn rpc_error(code: ErrorCode, message: impl Into<String>) -> Error {
+    Error::Call(CallError::Custom(ErrorObject::owned(
+        code.json_rpc_code(),
+        message,
+        Some(code.code()),
+    )))
+}
+
+/// Converts a classified error into a JSON-RPC error. The full source chain
+/// is logged, clients only receive the error's client-safe message.
+pub fn into_rpc_error<E: ClassifiedError>(err: E) -> Error {
+    let code = err.error_code();
+
+    if code.is_internal() {
+        error!("RPC request failed with {code}: {}", error_chain(&err));
+    } else {
+        debug!("RPC request failed with {code}: {}", error_chain(&err));
+    }
+
+    rpc_error(code, err.client_message())
+}
@@ -1,13 +1,22 @@
 pub mod api;
+mod cache;
 pub mod client;
+mod error;
+mod public;
 mod server;
 mod server_impl;
+mod versioning;
+pub use cache::*;
+pub use error::*;
+pub use public::*;
 use serde::{Deserialize, Serialize};
 pub use server::*;
 pub use server_impl::*;
+use telemetry::Sensitive;
+pub use versioning::*;
 use vrrb_core::transactions::Token;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 pub struct SignOpts {
     pub timestamp: i64,
     pub sender_address: String,
@@ -18,3 +27,18 @@ pub struct SignOpts {
     pub nonce: u128,
     pub private_key: String,
 }
+
+impl std::fmt::Debug for SignOpts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignOpts")
+            .field("timestamp", &self.timestamp)
+            .field("sender_address", &self.sender_address)
+            .field("sender_public_key", &self.sender_public_key)
+            .field("receiver_address", &self.receiver_address)
+            .field("amount", &self.amount)
+            .field("token", &self.token)
+            .field("nonce", &self.nonce)
+            .field("private_key", &self.private_key.redacted())
+            .finish()
+    }
+}
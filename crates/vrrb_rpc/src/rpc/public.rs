@@ -0,0 +1,536 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use hyper::{
+    header::{AUTHORIZATION, UPGRADE},
+    Body, HeaderMap, Request, Response,
+};
+use primitives::{ClassifiedError, ErrorCode};
+use serde_json::json;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower::{Layer, Service};
+use vrrb_config::PublicRpcConfig;
+
+use crate::http::RestError;
+
+/// Header clients of the public JSON-RPC server send their API key in. Keys
+/// are accepted as bearer tokens as well.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Reasons a request to the public JSON-RPC server is turned away before it
+/// reaches the node.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PublicRpcRejection {
+    #[error("missing or unknown API key")]
+    Unauthenticated,
+
+    #[error("method {0} is not served publicly")]
+    MethodNotAllowed(String),
+
+    #[error("subscriptions are not served publicly")]
+    SubscriptionsUnavailable,
+
+    #[error("rate limit of {0} requests per window exceeded")]
+    RateLimited(u32),
+
+    #[error("too many concurrent requests")]
+    TooManyConcurrentRequests,
+}
+
+impl ClassifiedError for PublicRpcRejection {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            PublicRpcRejection::Unauthenticated => ErrorCode::Unauthorized,
+            PublicRpcRejection::MethodNotAllowed(_)
+            | PublicRpcRejection::SubscriptionsUnavailable => ErrorCode::Forbidden,
+            PublicRpcRejection::RateLimited(_) | PublicRpcRejection::TooManyConcurrentRequests => {
+                ErrorCode::RateLimited
+            },
+        }
+    }
+}
+
+impl PublicRpcRejection {
+    fn into_response(self) -> Response<Body> {
+        rejection_response(&self)
+    }
+}
+
+/// Renders a request turned away before reaching the node as a JSON-RPC
+/// error, under the HTTP status closest to its code
+pub(crate) fn rejection_response<E: ClassifiedError>(rejection: &E) -> Response<Body> {
+    let code = rejection.error_code();
+    let body = json!({
+        "jsonrpc": "2.0",
+        "error": {
+            "code": code.json_rpc_code(),
+            "message": rejection.client_message(),
+            "data": code.code(),
+        },
+        "id": null,
+    });
+
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = RestError::new(code, "").status();
+    response
+}
+
+#[derive(Debug, Default)]
+struct ApiKeyMetrics {
+    requests: AtomicU64,
+    rate_limited: AtomicU64,
+    concurrency_limited: AtomicU64,
+    forbidden_calls: AtomicU64,
+}
+
+#[derive(Debug)]
+struct RequestWindow {
+    started_at: Instant,
+    requests: u32,
+}
+
+#[derive(Debug)]
+struct ApiKeyState {
+    name: String,
+    requests_per_window: u32,
+    max_concurrent_requests: usize,
+    window: Mutex<RequestWindow>,
+    in_flight: Arc<Semaphore>,
+    metrics: ApiKeyMetrics,
+}
+
+#[derive(Debug, Default)]
+struct PublicRpcState {
+    api_keys: HashMap<String, ApiKeyState>,
+    allowed_methods: HashSet<String>,
+    rate_window: Duration,
+    unauthenticated: AtomicU64,
+}
+
+/// Usage of a single API key of the public JSON-RPC server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiKeyStats {
+    pub name: String,
+
+    /// Requests let through to the node
+    pub requests: u64,
+    pub in_flight: usize,
+    pub rate_limited: u64,
+    pub concurrency_limited: u64,
+
+    /// Requests turned away for calling methods that aren't served publicly
+    pub forbidden_calls: u64,
+}
+
+/// Point in time view of the usage of the public JSON-RPC server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublicRpcStats {
+    /// Requests turned away for lacking a known API key
+    pub unauthenticated: u64,
+
+    /// Usage of every API key, ordered by name
+    pub api_keys: Vec<ApiKeyStats>,
+}
+
+/// Cloneable view over the usage counters of the public JSON-RPC server.
+#[derive(Debug, Clone, Default)]
+pub struct PublicRpcMonitor {
+    state: Arc<PublicRpcState>,
+}
+
+impl PublicRpcMonitor {
+    pub fn stats(&self) -> PublicRpcStats {
+        let mut api_keys: Vec<ApiKeyStats> = self
+            .state
+            .api_keys
+            .values()
+            .map(|api_key| ApiKeyStats {
+                name: api_key.name.clone(),
+                requests: api_key.metrics.requests.load(Ordering::Relaxed),
+                in_flight: api_key.max_concurrent_requests - api_key.in_flight.available_permits(),
+                rate_limited: api_key.metrics.rate_limited.load(Ordering::Relaxed),
+                concurrency_limited: api_key.metrics.concurrency_limited.load(Ordering::Relaxed),
+                forbidden_calls: api_key.metrics.forbidden_calls.load(Ordering::Relaxed),
+            })
+            .collect();
+
+        api_keys.sort_by(|a, b| a.name.cmp(&b.name));
+
+        PublicRpcStats {
+            unauthenticated: self.state.unauthenticated.load(Ordering::Relaxed),
+            api_keys,
+        }
+    }
+}
+
+/// Decides which requests to the public JSON-RPC server make it through to
+/// the node, following a [PublicRpcConfig].
+///
+/// Requests have to carry a known API key and may only call allowed methods.
+/// Every key gets its own request budget per rate window and its own cap on
+/// requests in flight.
+#[derive(Debug, Clone, Default)]
+pub struct PublicRpcGuard {
+    state: Arc<PublicRpcState>,
+}
+
+impl PublicRpcGuard {
+    pub fn new(config: &PublicRpcConfig) -> Self {
+        let now = Instant::now();
+
+        let api_keys = config
+            .api_keys
+            .iter()
+            .map(|api_key| {
+                let max_concurrent_requests = api_key
+                    .max_concurrent_requests
+                    .unwrap_or(config.max_concurrent_requests);
+
+                let state = ApiKeyState {
+                    name: api_key.name.clone(),
+                    requests_per_window: api_key
+                        .requests_per_window
+                        .unwrap_or(config.requests_per_window),
+                    max_concurrent_requests,
+                    window: Mutex::new(RequestWindow {
+                        started_at: now,
+                        requests: 0,
+                    }),
+                    in_flight: Arc::new(Semaphore::new(max_concurrent_requests)),
+                    metrics: ApiKeyMetrics::default(),
+                };
+
+                (api_key.key.clone(), state)
+            })
+            .collect();
+
+        Self {
+            state: Arc::new(PublicRpcState {
+                api_keys,
+                allowed_methods: config.allowed_methods.iter().cloned().collect(),
+                rate_window: config.rate_window,
+                unauthenticated: AtomicU64::default(),
+            }),
+        }
+    }
+
+    pub fn monitor(&self) -> PublicRpcMonitor {
+        PublicRpcMonitor {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Checks a request carries a known API key
+    pub fn authenticate(&self, api_key: Option<&str>) -> Result<(), PublicRpcRejection> {
+        self.api_key_state(api_key).map(|_| ())
+    }
+
+    /// Lets a request calling `methods` through on behalf of `api_key`,
+    /// returning a permit that has to be held until the request is served
+    pub fn admit(
+        &self,
+        api_key: Option<&str>,
+        methods: &[String],
+    ) -> Result<OwnedSemaphorePermit, PublicRpcRejection> {
+        self.admit_at(api_key, methods, Instant::now())
+    }
+
+    fn api_key_state(&self, api_key: Option<&str>) -> Result<&ApiKeyState, PublicRpcRejection> {
+        match api_key.and_then(|api_key| self.state.api_keys.get(api_key)) {
+            Some(state) => Ok(state),
+            None => {
+                self.state.unauthenticated.fetch_add(1, Ordering::Relaxed);
+                Err(PublicRpcRejection::Unauthenticated)
+            },
+        }
+    }
+
+    fn admit_at(
+        &self,
+        api_key: Option<&str>,
+        methods: &[String],
+        now: Instant,
+    ) -> Result<OwnedSemaphorePermit, PublicRpcRejection> {
+        let state = self.api_key_state(api_key)?;
+        let metrics = &state.metrics;
+
+        if let Some(method) = methods
+            .iter()
+            .find(|method| !self.state.allowed_methods.contains(*method))
+        {
+            metrics.forbidden_calls.fetch_add(1, Ordering::Relaxed);
+            return Err(PublicRpcRejection::MethodNotAllowed(method.clone()));
+        }
+
+        let permit = match state.in_flight.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                metrics.concurrency_limited.fetch_add(1, Ordering::Relaxed);
+                return Err(PublicRpcRejection::TooManyConcurrentRequests);
+            },
+        };
+
+        let mut window = state
+            .window
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if now.duration_since(window.started_at) >= self.state.rate_window {
+            window.started_at = now;
+            window.requests = 0;
+        }
+
+        // NOTE: every call of a batch counts against the budget
+        let requests = methods.len().max(1) as u32;
+        if window.requests.saturating_add(requests) > state.requests_per_window {
+            metrics.rate_limited.fetch_add(1, Ordering::Relaxed);
+            return Err(PublicRpcRejection::RateLimited(state.requests_per_window));
+        }
+
+        window.requests += requests;
+        metrics.requests.fetch_add(1, Ordering::Relaxed);
+
+        Ok(permit)
+    }
+}
+
+/// Returns the API key a request carries, if any
+fn api_key(headers: &HeaderMap) -> Option<String> {
+    if let Some(api_key) = headers.get(API_KEY_HEADER) {
+        return api_key.to_str().ok().map(str::to_string);
+    }
+
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::to_string)
+}
+
+/// Returns the methods called by a JSON-RPC request or batch. Bodies that
+/// don't parse call nothing, they're left for the server to reject
+pub(crate) fn requested_methods(body: &[u8]) -> Vec<String> {
+    let method = |call: &serde_json::Value| {
+        call.get("method")
+            .and_then(|method| method.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Array(calls)) => calls.iter().map(method).collect(),
+        Ok(call) => vec![method(&call)],
+        Err(_) => vec![],
+    }
+}
+
+/// Tower layer putting a [PublicRpcGuard] in front of the JSON-RPC server.
+/// Requests go straight through when no guard is given.
+#[derive(Debug, Clone)]
+pub struct PublicRpcLayer {
+    guard: Option<PublicRpcGuard>,
+}
+
+impl PublicRpcLayer {
+    pub fn new(guard: Option<PublicRpcGuard>) -> Self {
+        Self { guard }
+    }
+}
+
+impl<S> Layer<S> for PublicRpcLayer {
+    type Service = PublicRpcService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PublicRpcService {
+            guard: self.guard.clone(),
+            inner,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PublicRpcService<S> {
+    guard: Option<PublicRpcGuard>,
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for PublicRpcService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = BoxError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let Some(guard) = self.guard.clone() else {
+            return Box::pin(self.inner.call(request));
+        };
+
+        // NOTE: the service that was polled ready is the one that has to be called
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let api_key = api_key(request.headers());
+
+            if let Err(rejection) = guard.authenticate(api_key.as_deref()) {
+                return Ok(rejection.into_response());
+            }
+
+            // NOTE: methods can only be checked per request, not per message sent over a
+            // websocket, so public clients are limited to plain HTTP
+            if request.headers().contains_key(UPGRADE) {
+                return Ok(PublicRpcRejection::SubscriptionsUnavailable.into_response());
+            }
+
+            let (parts, body) = request.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+
+            let _permit = match guard.admit(api_key.as_deref(), &requested_methods(&body)) {
+                Ok(permit) => permit,
+                Err(rejection) => return Ok(rejection.into_response()),
+            };
+
+            inner
+                .call(Request::from_parts(parts, Body::from(body)))
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrrb_config::RpcApiKey;
+
+    use super::*;
+
+    fn guard() -> PublicRpcGuard {
+        PublicRpcGuard::new(&PublicRpcConfig {
+            enabled: true,
+            api_keys: vec![
+                RpcApiKey {
+                    name: "explorer".to_string(),
+                    key: "explorer-key".to_string(),
+                    requests_per_window: Some(3),
+                    max_concurrent_requests: None,
+                },
+                RpcApiKey {
+                    name: "wallet".to_string(),
+                    key: "wallet-key".to_string(),
+                    requests_per_window: None,
+                    max_concurrent_requests: Some(1),
+                },
+            ],
+            rate_window: Duration::from_secs(1),
+            ..Default::default()
+        })
+    }
+
+    fn methods(methods: &[&str]) -> Vec<String> {
+        methods.iter().map(|method| method.to_string()).collect()
+    }
+
+    #[test]
+    fn only_allowed_methods_are_served_to_known_api_keys() {
+        let guard = guard();
+        let get_round = methods(&["state_getRound"]);
+
+        assert_eq!(
+            guard.admit(None, &get_round).unwrap_err(),
+            PublicRpcRejection::Unauthenticated
+        );
+        assert_eq!(
+            guard.admit(Some("unknown"), &get_round).unwrap_err(),
+            PublicRpcRejection::Unauthenticated
+        );
+        assert_eq!(
+            guard
+                .admit(
+                    Some("explorer-key"),
+                    &methods(&["state_getRound", "state_setMaintenanceMode"])
+                )
+                .unwrap_err(),
+            PublicRpcRejection::MethodNotAllowed("state_setMaintenanceMode".to_string())
+        );
+        assert!(guard.admit(Some("explorer-key"), &get_round).is_ok());
+
+        let stats = guard.monitor().stats();
+        assert_eq!(stats.unauthenticated, 2);
+        assert_eq!(stats.api_keys[0].requests, 1);
+        assert_eq!(stats.api_keys[0].forbidden_calls, 1);
+    }
+
+    #[test]
+    fn api_keys_are_rate_limited_independently() {
+        let guard = guard();
+        let start = Instant::now();
+        let get_round = methods(&["state_getRound"]);
+
+        for _ in 0..3 {
+            guard
+                .admit_at(Some("explorer-key"), &get_round, start)
+                .unwrap();
+        }
+        assert_eq!(
+            guard
+                .admit_at(Some("explorer-key"), &get_round, start)
+                .unwrap_err(),
+            PublicRpcRejection::RateLimited(3)
+        );
+
+        // NOTE: the wallet key only allows a single request in flight
+        let permit = guard
+            .admit_at(Some("wallet-key"), &get_round, start)
+            .unwrap();
+        assert_eq!(
+            guard
+                .admit_at(Some("wallet-key"), &get_round, start)
+                .unwrap_err(),
+            PublicRpcRejection::TooManyConcurrentRequests
+        );
+        drop(permit);
+
+        let next_window = start + Duration::from_secs(1);
+        assert!(guard
+            .admit_at(Some("explorer-key"), &get_round, next_window)
+            .is_ok());
+        assert!(guard
+            .admit_at(Some("wallet-key"), &get_round, next_window)
+            .is_ok());
+
+        let stats = guard.monitor().stats();
+        assert_eq!(stats.api_keys[0].rate_limited, 1);
+        assert_eq!(stats.api_keys[1].concurrency_limited, 1);
+    }
+
+    #[test]
+    fn methods_are_read_from_single_calls_and_batches() {
+        let call = br#"{"jsonrpc":"2.0","method":"state_getRound","params":[],"id":1}"#;
+        let batch = br#"[{"jsonrpc":"2.0","method":"state_getRound","id":1},{"jsonrpc":"2.0","method":"state_listPeers","id":2}]"#;
+
+        assert_eq!(requested_methods(call), methods(&["state_getRound"]));
+        assert_eq!(
+            requested_methods(batch),
+            methods(&["state_getRound", "state_listPeers"])
+        );
+        assert!(requested_methods(b"not json").is_empty());
+    }
+}
@@ -1,13 +1,23 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
-use events::{EventPublisher, DEFAULT_BUFFER};
+use events::{
+    BacklogSnapshot, BlockRangeChunk, DkgStatus, EventPublisher, PeerRecord, PendingBlockPreview,
+    StateDiff, SyncStatus, TxnStatusChange, ValidatorDuties, DEFAULT_BUFFER,
+};
 use jsonrpsee::server::{ServerBuilder, ServerHandle};
-use mempool::{LeftRightMempool, MempoolReadHandleFactory};
-use primitives::NodeType;
+use mempool::{LeftRightMempool, MempoolReadHandleFactory, MempoolStats};
+use primitives::{BlockLimits, NodeType, TxnLimits};
 use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
-use tokio::sync::mpsc::channel;
+use tokio::sync::{broadcast, mpsc::channel};
+use tower::ServiceBuilder;
 
-use crate::rpc::{api::RpcApiServer, server_impl::RpcServerImpl};
+use crate::rpc::{
+    api::{RpcApiServer, RpcDiscoveryApiServer},
+    cache::RpcCache,
+    public::{PublicRpcGuard, PublicRpcLayer},
+    server_impl::RpcServerImpl,
+    versioning::{RpcDiscoveryImpl, RpcVersionLayer},
+};
 
 #[derive(Debug, Clone)]
 pub struct JsonRpcServerConfig {
@@ -16,6 +26,46 @@ pub struct JsonRpcServerConfig {
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
     pub node_type: NodeType,
     pub events_tx: EventPublisher,
+    /// Feeds the diffs applied to state to balance change subscribers
+    pub state_diffs_tx: broadcast::Sender<StateDiff>,
+    /// Hands the backlog snapshots taken by the node to the requests waiting
+    /// on them
+    pub backlog_snapshots_tx: broadcast::Sender<BacklogSnapshot>,
+    /// Feeds the status changes of txns of rolled back blocks to txn status
+    /// subscribers
+    pub txn_status_changes_tx: broadcast::Sender<TxnStatusChange>,
+    /// Hands the validator duties looked up by the node to the requests
+    /// waiting on them
+    pub validator_duties_tx: broadcast::Sender<ValidatorDuties>,
+    /// Hands the peers listed from the node's address book to the requests
+    /// waiting on them
+    pub peers_tx: broadcast::Sender<Vec<PeerRecord>>,
+    /// Hands the chunks of blocks listed by the node to the subscribers
+    /// backfilling a range of rounds
+    pub block_range_chunks_tx: broadcast::Sender<BlockRangeChunk>,
+    /// Hands the sync statuses reported by the node to the requests waiting
+    /// on them
+    pub sync_statuses_tx: broadcast::Sender<SyncStatus>,
+    /// Hands the pending block previews assembled by the node to the requests
+    /// waiting on them, along with the id of the request each one answers
+    pub pending_block_previews_tx: broadcast::Sender<(u64, PendingBlockPreview)>,
+    /// Hands the mempool stats computed by the node to the requests waiting
+    /// on them
+    pub mempool_stats_tx: broadcast::Sender<MempoolStats>,
+    /// Hands the DKG statuses reported by the node to the requests waiting
+    /// on them
+    pub dkg_statuses_tx: broadcast::Sender<DkgStatus>,
+    /// Serves hot txns and blocks without reading them off the DB, kept in
+    /// line with the diffs sent over `state_diffs_tx`
+    pub cache: RpcCache,
+    pub block_limits: BlockLimits,
+
+    /// Structural limits txns are held to before they are handed to the node
+    pub txn_limits: TxnLimits,
+
+    /// Guards the server when it is exposed publicly. Every request goes
+    /// through when unset
+    pub public_rpc_guard: Option<PublicRpcGuard>,
 }
 
 #[derive(Debug)]
@@ -23,17 +73,43 @@ pub struct JsonRpcServer;
 
 impl JsonRpcServer {
     pub async fn run(config: &JsonRpcServerConfig) -> anyhow::Result<(ServerHandle, SocketAddr)> {
-        let server = ServerBuilder::default().build(config.address).await?;
+        let middleware = ServiceBuilder::new()
+            .layer(PublicRpcLayer::new(config.public_rpc_guard.clone()))
+            .layer(RpcVersionLayer);
+
+        let server = ServerBuilder::default()
+            .set_middleware(middleware)
+            .build(config.address)
+            .await?;
 
         let server_impl = RpcServerImpl {
             node_type: config.node_type,
             events_tx: config.events_tx.clone(),
+            state_diffs_tx: config.state_diffs_tx.clone(),
+            backlog_snapshots_tx: config.backlog_snapshots_tx.clone(),
+            txn_status_changes_tx: config.txn_status_changes_tx.clone(),
+            validator_duties_tx: config.validator_duties_tx.clone(),
+            peers_tx: config.peers_tx.clone(),
+            block_range_chunks_tx: config.block_range_chunks_tx.clone(),
+            sync_statuses_tx: config.sync_statuses_tx.clone(),
+            pending_block_previews_tx: config.pending_block_previews_tx.clone(),
+            mempool_stats_tx: config.mempool_stats_tx.clone(),
+            dkg_statuses_tx: config.dkg_statuses_tx.clone(),
+            cache: config.cache.clone(),
             vrrbdb_read_handle: config.vrrbdb_read_handle.clone(),
             mempool_read_handle_factory: config.mempool_read_handle_factory.clone(),
+            block_limits: config.block_limits.clone(),
+            txn_limits: config.txn_limits.clone(),
         };
 
+        config.cache.watch(&config.state_diffs_tx);
+
         let addr = server.local_addr()?;
-        let handle = server.start(server_impl.into_rpc())?;
+        let mut module = server_impl.into_rpc();
+        let discovery = RpcDiscoveryImpl::new(module.method_names());
+        module.merge(discovery.into_rpc())?;
+
+        let handle = server.start(module)?;
 
         // TODO: refactor example out of here
         // In this example we don't care about doing shutdown so let's it run forever.
@@ -60,6 +136,16 @@ impl Default for JsonRpcServerConfig {
 
         let node_type = NodeType::Full;
         let (events_tx, _) = channel(DEFAULT_BUFFER);
+        let (state_diffs_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (backlog_snapshots_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (txn_status_changes_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (validator_duties_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (peers_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (block_range_chunks_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (sync_statuses_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (pending_block_previews_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (mempool_stats_tx, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (dkg_statuses_tx, _) = broadcast::channel(DEFAULT_BUFFER);
 
         JsonRpcServerConfig {
             address,
@@ -67,6 +153,20 @@ impl Default for JsonRpcServerConfig {
             mempool_read_handle_factory,
             node_type,
             events_tx,
+            state_diffs_tx,
+            backlog_snapshots_tx,
+            txn_status_changes_tx,
+            validator_duties_tx,
+            peers_tx,
+            block_range_chunks_tx,
+            sync_statuses_tx,
+            pending_block_previews_tx,
+            mempool_stats_tx,
+            dkg_statuses_tx,
+            cache: RpcCache::default(),
+            block_limits: BlockLimits::default(),
+            txn_limits: TxnLimits::default(),
+            public_rpc_guard: None,
         }
     }
 }
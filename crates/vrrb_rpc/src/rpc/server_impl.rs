@@ -1,29 +1,119 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use block::block::Block;
-use block::ClaimHash;
-use events::{Event, EventPublisher};
-use jsonrpsee::core::Error;
-use mempool::MempoolReadHandleFactory;
-use primitives::{Address, NodeType, Round};
+use block::{
+    BlockHash, Checkpoint, ClaimHash, EpochSummary, HaltAction, HaltDirective, SignedHaltDirective,
+};
+use events::{
+    BacklogSnapshot, BlockRangeChunk, DkgStatus, Event, EventPublisher, PeerRecord,
+    PendingBlockPreview, StateDiff, SyncStatus, TxnStatusChange, ValidatorDuties,
+};
+use jsonrpsee::{core::Error, types::SubscriptionResult, SubscriptionSink};
+use mempool::{MempoolReadHandleFactory, MempoolStats, TxnStatus};
+use primitives::{
+    Address, BlockLimits, Epoch, ErrorCode, NodeId, NodeType, Round, TxnLimits, Weight,
+};
 use secp256k1::{Message, SecretKey};
 use sha2::{Digest, Sha256};
-use storage::vrrbdb::{Claims, VrrbDbReadHandle};
-use telemetry::{debug, error};
-use vrrb_config::bootstrap_quorum::QuorumMembershipConfig;
+use storage::vrrbdb::{
+    diff_state_snapshots, Claims, RoundTimeline, StateSnapshot, StateSnapshotDiff, TxnReceipt,
+    ValidatorEarnings, VrrbDbReadHandle,
+};
+use telemetry::{debug, error, Sensitive};
+use tokio::sync::broadcast::{self, error::RecvError};
+use validator::txn_validator::TxnValidator;
+use vrrb_config::{
+    bootstrap_quorum::QuorumMembershipConfig, DEFAULT_REPLICATION_SNAPSHOT_INTERVAL_ROUNDS,
+};
 use vrrb_core::claim::Claim;
 use vrrb_core::node_health_report::NodeHealthReport;
 use vrrb_core::transactions::{
-    NewTransferArgs, Transaction, TransactionDigest, TransactionKind, Transfer,
+    NewTransferArgs, Token, Transaction, TransactionDigest, TransactionKind, Transfer,
+    UnsignedTransfer, UnsignedTransferArgs,
 };
 use vrrb_core::{account::Account, serde_helpers::encode_to_binary};
 
 use super::{
     api::{FullMempoolSnapshot, RpcApiServer},
+    cache::RpcCache,
+    into_rpc_error,
+    rpc_error,
     SignOpts,
 };
-use crate::rpc::api::{FullStateSnapshot, RpcTransactionDigest, RpcTransactionRecord};
+use crate::rpc::api::{
+    FullStateSnapshot, RpcBalanceChange, RpcBlockCertificationDetail, RpcProjectedAccount,
+    RpcQuorum, RpcReplicationUpdate, RpcStatePage, RpcStateRoot, RpcSyncStatus,
+    RpcTransactionDigest, RpcTransactionRecord, RpcTxnStatusChange, RpcTxnTrace, RpcTxnWeight,
+    BACKLOG_SNAPSHOT_TIMEOUT_MS, BLOCK_RANGE_CHUNK_SIZE, BLOCK_RANGE_TIMEOUT_MS,
+    DKG_STATUS_TIMEOUT_MS, DUTIES_TIMEOUT_MS, MAX_BLOCK_RANGE_ROUNDS, MAX_CHECKPOINT_PAGE_SIZE,
+    MAX_EARNINGS_EPOCHS, MAX_EPOCH_SUMMARY_PAGE_SIZE, MAX_STATE_PAGE_SIZE,
+    MEMPOOL_STATS_TIMEOUT_MS, PEERS_TIMEOUT_MS, PENDING_BLOCK_PREVIEW_TIMEOUT_MS,
+    REPLICATION_SNAPSHOT_PAGE_SIZE, SYNC_STATUS_TIMEOUT_MS,
+};
+
+/// Tells apart the chunks answering concurrent block range requests, which
+/// share the same channel
+static NEXT_BLOCK_RANGE_REQUEST_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Tells apart the previews answering concurrent pending block preview
+/// requests, which share the same channel
+static NEXT_PENDING_BLOCK_PREVIEW_REQUEST_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Sends a replication subscriber a snapshot of the state as of the latest
+/// committed round, page by page. Returns the round the snapshot was taken
+/// at, or `None` once the subscriber is gone
+fn send_replication_snapshot(
+    sink: &mut SubscriptionSink,
+    vrrbdb_read_handle: &VrrbDbReadHandle,
+) -> Option<Round> {
+    // NOTE: accounts are read as of a committed round so they add up to the root the
+    // snapshot claims, however many blocks are applied meanwhile
+    let (round, state_root, accounts) = match vrrbdb_read_handle.latest_state_root() {
+        Some((round, state_root)) => match vrrbdb_read_handle.state_store_values_at_round(round) {
+            Ok(accounts) => (round, hex::encode(state_root.root_hash.0), accounts),
+            Err(err) => {
+                error!("could not take a replication snapshot at round {round}: {err}");
+                return None;
+            },
+        },
+        None => (
+            0,
+            String::new(),
+            vrrbdb_read_handle
+                .state_store_values()
+                .into_values()
+                .collect(),
+        ),
+    };
+
+    let mut pages: Vec<&[Account]> = accounts.chunks(REPLICATION_SNAPSHOT_PAGE_SIZE).collect();
+    if pages.is_empty() {
+        pages.push(&[]);
+    }
+
+    let count = pages.len();
+    for (index, page) in pages.into_iter().enumerate() {
+        let update = RpcReplicationUpdate::SnapshotPage {
+            round,
+            state_root: state_root.clone(),
+            accounts: page.to_vec(),
+            last: index + 1 == count,
+        };
+
+        // NOTE: sending fails or returns false once the subscriber is gone
+        if !matches!(sink.send(&update), Ok(true)) {
+            return None;
+        }
+    }
+
+    Some(round)
+}
 
 #[derive(Debug, Clone)]
 pub struct RpcServerImpl {
@@ -31,6 +121,43 @@ pub struct RpcServerImpl {
     pub vrrbdb_read_handle: VrrbDbReadHandle,
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
     pub events_tx: EventPublisher,
+    pub state_diffs_tx: broadcast::Sender<StateDiff>,
+    pub backlog_snapshots_tx: broadcast::Sender<BacklogSnapshot>,
+    pub txn_status_changes_tx: broadcast::Sender<TxnStatusChange>,
+    pub validator_duties_tx: broadcast::Sender<ValidatorDuties>,
+    pub peers_tx: broadcast::Sender<Vec<PeerRecord>>,
+    pub block_range_chunks_tx: broadcast::Sender<BlockRangeChunk>,
+    pub sync_statuses_tx: broadcast::Sender<SyncStatus>,
+    pub pending_block_previews_tx: broadcast::Sender<(u64, PendingBlockPreview)>,
+    pub mempool_stats_tx: broadcast::Sender<MempoolStats>,
+    pub dkg_statuses_tx: broadcast::Sender<DkgStatus>,
+    pub cache: RpcCache,
+    pub block_limits: BlockLimits,
+    pub txn_limits: TxnLimits,
+}
+
+impl RpcServerImpl {
+    /// Checks `txn` against the txn limits and the mempool's admission
+    /// policy, then queues it to the mempool
+    async fn submit_txn(&self, txn: TransactionKind) -> Result<RpcTransactionRecord, Error> {
+        TxnValidator::new()
+            .with_txn_limits(self.txn_limits.clone())
+            .validate_limits(&txn)
+            .map_err(into_rpc_error)?;
+
+        self.mempool_read_handle_factory
+            .check_admission(&txn)
+            .map_err(into_rpc_error)?;
+
+        let event = Event::NewTxnCreated(txn.clone());
+
+        self.events_tx.send(event.into()).await.map_err(|err| {
+            error!("could not queue transaction to mempool: {err}");
+            rpc_error(ErrorCode::Unavailable, "node is not accepting transactions")
+        })?;
+
+        Ok(RpcTransactionRecord::from(txn))
+    }
 }
 
 #[async_trait]
@@ -59,16 +186,10 @@ impl RpcApiServer for RpcServerImpl {
     //TODO: this should either exist for every transaction type or allow creating multiple types
     async fn create_txn(&self, args: NewTransferArgs) -> Result<RpcTransactionRecord, Error> {
         let txn = TransactionKind::Transfer(Transfer::new(args));
-        let event = Event::NewTxnCreated(txn.clone());
-
-        debug!("{:?}", event);
 
-        self.events_tx.send(event.into()).await.map_err(|err| {
-            error!("could not queue transaction to mempool: {err}");
-            Error::Custom(err.to_string())
-        })?;
+        debug!("Received createTxn RPC Request: txn {}", txn.id());
 
-        Ok(RpcTransactionRecord::from(txn))
+        self.submit_txn(txn).await
     }
 
     async fn get_transaction(
@@ -80,17 +201,28 @@ impl RpcApiServer for RpcServerImpl {
 
         let parsed_digest = transaction_digest
             .parse::<TransactionDigest>()
-            .map_err(|_err| Error::Custom("unable to parse transaction digest".to_string()))?;
+            .map_err(|_err| {
+                rpc_error(
+                    ErrorCode::InvalidInput,
+                    "unable to parse transaction digest",
+                )
+            })?;
+
+        if let Some(txn) = self.cache.get_txn(&parsed_digest.to_string()) {
+            return Ok(RpcTransactionRecord::from(txn));
+        }
 
         let values = self.vrrbdb_read_handle.transaction_store_values();
         let value = values.get(&parsed_digest);
 
         match value {
             Some(txn) => {
+                self.cache.insert_txn(txn.clone());
+
                 let txn_record = RpcTransactionRecord::from(txn.clone());
                 Ok(txn_record)
             },
-            None => return Err(Error::Custom("unable to find transaction".to_string())),
+            None => return Err(rpc_error(ErrorCode::NotFound, "unable to find transaction")),
         }
     }
 
@@ -102,16 +234,25 @@ impl RpcApiServer for RpcServerImpl {
 
         let mut values: HashMap<RpcTransactionDigest, RpcTransactionRecord> = HashMap::new();
 
+        // NOTE: the txn store is only read once, and only if some txn isn't cached
+        let mut stored_txns = None;
+
         digests.iter().for_each(|digest_string| {
             let parsed_digest = digest_string
                 .parse::<TransactionDigest>()
                 .unwrap_or_default(); // TODO: report this error
 
-            if let Some(txn) = self
-                .vrrbdb_read_handle
-                .transaction_store_values()
-                .get(&parsed_digest)
-            {
+            let txn = self.cache.get_txn(&parsed_digest.to_string()).or_else(|| {
+                let txn = stored_txns
+                    .get_or_insert_with(|| self.vrrbdb_read_handle.transaction_store_values())
+                    .get(&parsed_digest)
+                    .cloned()?;
+
+                self.cache.insert_txn(txn.clone());
+                Some(txn)
+            });
+
+            if let Some(txn) = txn {
                 let txn_record = RpcTransactionRecord::from(txn.clone());
 
                 values.insert(txn.digest().to_string(), txn_record);
@@ -122,19 +263,24 @@ impl RpcApiServer for RpcServerImpl {
     }
 
     async fn create_account(&self, address: Address, account: Account) -> Result<(), Error> {
-        let account_bytes =
-            encode_to_binary(&account).map_err(|err| Error::Custom(err.to_string()))?;
+        let account_bytes = encode_to_binary(&account).map_err(into_rpc_error)?;
 
-        let event = Event::CreateAccountRequested((address.clone(), account_bytes));
+        debug!(
+            "Received createAccount RPC Request for {address}: {}",
+            account_bytes.redacted()
+        );
 
-        debug!("{:?}", event);
+        let event = Event::CreateAccountRequested((address.clone(), account_bytes));
 
         self.events_tx
             .send(event.clone().into())
             .await
             .map_err(|err| {
                 error!("could not create account: {err}");
-                Error::Custom(err.to_string())
+                rpc_error(
+                    ErrorCode::Unavailable,
+                    "node is not accepting account changes",
+                )
             })?;
 
         telemetry::info!("requested account creation for address: {}", address);
@@ -145,17 +291,19 @@ impl RpcApiServer for RpcServerImpl {
     async fn update_account(&self, account: Account) -> Result<(), Error> {
         debug!("Received an updateAccount RPC request");
 
-        let account_bytes =
-            encode_to_binary(&account).map_err(|err| Error::Custom(err.to_string()))?;
+        let account_bytes = encode_to_binary(&account).map_err(into_rpc_error)?;
 
-        let addr =
-            Address::from_str(&account.hash()).map_err(|err| Error::Custom(err.to_string()))?;
+        let addr = Address::from_str(&account.hash())
+            .map_err(|err| rpc_error(ErrorCode::InvalidInput, err.to_string()))?;
 
         let event = Event::AccountUpdateRequested((addr, account_bytes));
 
         self.events_tx.send(event.into()).await.map_err(|err| {
             error!("could not update account: {err}");
-            Error::Custom(err.to_string())
+            rpc_error(
+                ErrorCode::Unavailable,
+                "node is not accepting account changes",
+            )
         })?;
 
         Ok(())
@@ -171,8 +319,267 @@ impl RpcApiServer for RpcServerImpl {
 
         match value {
             Some(account) => return Ok(account.to_owned()),
-            None => return Err(Error::Custom("unable to find account".to_string())),
+            None => return Err(rpc_error(ErrorCode::NotFound, "unable to find account")),
+        }
+    }
+
+    async fn get_projected_account(&self, address: Address) -> Result<RpcProjectedAccount, Error> {
+        debug!("Received getProjectedAccount RPC Request for account {address}");
+
+        let account = self
+            .vrrbdb_read_handle
+            .get_account_by_address(&address)
+            .ok();
+
+        let pending: Vec<TransactionKind> = self
+            .mempool_read_handle_factory
+            .entries()
+            .into_values()
+            .filter(|record| record.status != TxnStatus::Rejected)
+            .map(|record| record.txn)
+            .filter(|txn| txn.sender_address() == address || txn.receiver_address() == address)
+            .collect();
+
+        if account.is_none() && pending.is_empty() {
+            return Err(rpc_error(ErrorCode::NotFound, "unable to find account"));
+        }
+
+        Ok(RpcProjectedAccount::project(
+            address,
+            account.as_ref(),
+            &pending,
+        ))
+    }
+
+    async fn get_balance(&self, address: Address, token: Option<Token>) -> Result<u128, Error> {
+        debug!("Received getBalance RPC Request for account {address}");
+
+        self.vrrbdb_read_handle
+            .get_balance(&address, &token.unwrap_or_default())
+            .map_err(into_rpc_error)
+    }
+
+    fn subscribe_balance_changes(
+        &self,
+        mut sink: SubscriptionSink,
+        addresses: Vec<Address>,
+    ) -> SubscriptionResult {
+        sink.accept()?;
+
+        let addresses: HashSet<Address> = addresses.into_iter().collect();
+        let mut state_diffs_rx = self.state_diffs_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                let state_diff = match state_diffs_rx.recv().await {
+                    Ok(state_diff) => state_diff,
+                    Err(RecvError::Lagged(count)) => {
+                        error!("balance change subscriber lagged behind by {count} state diffs");
+                        continue;
+                    },
+                    Err(RecvError::Closed) => break,
+                };
+
+                for change in RpcBalanceChange::from_state_diff(&state_diff) {
+                    if !addresses.contains(&change.address) {
+                        continue;
+                    }
+
+                    // NOTE: sending fails or returns false once the subscriber is gone
+                    if !matches!(sink.send(&change), Ok(true)) {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn subscribe_txn_status(
+        &self,
+        mut sink: SubscriptionSink,
+        digests: Vec<RpcTransactionDigest>,
+    ) -> SubscriptionResult {
+        sink.accept()?;
+
+        let digests: HashSet<RpcTransactionDigest> = digests.into_iter().collect();
+        let mut txn_status_changes_rx = self.txn_status_changes_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                let change = match txn_status_changes_rx.recv().await {
+                    Ok(change) => RpcTxnStatusChange::from(change),
+                    Err(RecvError::Lagged(count)) => {
+                        error!("txn status subscriber lagged behind by {count} status changes");
+                        continue;
+                    },
+                    Err(RecvError::Closed) => break,
+                };
+
+                if !digests.contains(&change.digest) {
+                    continue;
+                }
+
+                // NOTE: sending fails or returns false once the subscriber is gone
+                if !matches!(sink.send(&change), Ok(true)) {
+                    return;
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn get_blocks_by_round_range(
+        &self,
+        mut sink: SubscriptionSink,
+        start: Round,
+        end: Round,
+    ) -> SubscriptionResult {
+        debug!("Received getBlocksByRoundRange RPC Request for rounds {start} to {end}");
+
+        sink.accept()?;
+
+        let end = end.min(start.saturating_add(MAX_BLOCK_RANGE_ROUNDS - 1));
+        let request_id = NEXT_BLOCK_RANGE_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(blocks) = self.cache.get_rounds(start, end) {
+            let mut chunks: Vec<_> = blocks.chunks(BLOCK_RANGE_CHUNK_SIZE).collect();
+            if chunks.is_empty() {
+                chunks.push(&[]);
+            }
+
+            let count = chunks.len();
+            for (index, blocks) in chunks.into_iter().enumerate() {
+                let chunk = BlockRangeChunk {
+                    request_id,
+                    index,
+                    last: index + 1 == count,
+                    blocks: blocks.to_vec(),
+                };
+
+                if !matches!(sink.send(&chunk), Ok(true)) {
+                    break;
+                }
+            }
+
+            return Ok(());
         }
+
+        // NOTE: subscribe before asking so none of the chunks can be missed
+        let mut block_range_chunks_rx = self.block_range_chunks_tx.subscribe();
+        let events_tx = self.events_tx.clone();
+        let cache = self.cache.clone();
+        let generation = cache.generation();
+
+        tokio::spawn(async move {
+            let request = Event::BlocksByRoundRangeRequested {
+                request_id,
+                start,
+                end,
+                chunk_size: BLOCK_RANGE_CHUNK_SIZE,
+            };
+
+            if let Err(err) = events_tx.send(request.into()).await {
+                error!("could not request blocks by round range: {err}");
+                return;
+            }
+
+            let timeout = Duration::from_millis(BLOCK_RANGE_TIMEOUT_MS);
+            let mut blocks = Vec::new();
+
+            loop {
+                let chunk = match tokio::time::timeout(timeout, block_range_chunks_rx.recv()).await
+                {
+                    // NOTE: concurrent requests share the channel
+                    Ok(Ok(chunk)) if chunk.request_id == request_id => chunk,
+                    Ok(Ok(_)) => continue,
+                    Ok(Err(RecvError::Lagged(count))) => {
+                        // NOTE: a skipped chunk would leave a gap the client can't see
+                        error!("block range subscriber lagged behind by {count} chunks");
+                        return;
+                    },
+                    Ok(Err(RecvError::Closed)) | Err(_) => return,
+                };
+
+                let last = chunk.last;
+
+                blocks.extend(chunk.blocks.iter().cloned());
+                if last {
+                    cache.insert_rounds(generation, start, end, std::mem::take(&mut blocks));
+                }
+
+                // NOTE: sending fails or returns false once the subscriber is gone
+                if !matches!(sink.send(&chunk), Ok(true)) || last {
+                    return;
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn subscribe_replication(
+        &self,
+        mut sink: SubscriptionSink,
+        snapshot_interval_rounds: Option<Round>,
+    ) -> SubscriptionResult {
+        debug!("Received subscribeReplication RPC Request");
+
+        sink.accept()?;
+
+        let snapshot_interval_rounds = snapshot_interval_rounds
+            .unwrap_or(DEFAULT_REPLICATION_SNAPSHOT_INTERVAL_ROUNDS)
+            .max(1);
+
+        // NOTE: subscribe before taking the snapshot so no diff applied in between can be
+        // missed, replicas skip the diffs the snapshot already covers
+        let mut state_diffs_rx = self.state_diffs_tx.subscribe();
+        let vrrbdb_read_handle = self.vrrbdb_read_handle.clone();
+
+        tokio::spawn(async move {
+            let Some(mut snapshot_round) =
+                send_replication_snapshot(&mut sink, &vrrbdb_read_handle)
+            else {
+                return;
+            };
+
+            loop {
+                let state_diff = match state_diffs_rx.recv().await {
+                    Ok(state_diff) => state_diff,
+                    Err(RecvError::Lagged(count)) => {
+                        // NOTE: the diffs skipped would leave a gap, a snapshot covers them
+                        error!("replication subscriber lagged behind by {count} state diffs");
+
+                        match send_replication_snapshot(&mut sink, &vrrbdb_read_handle) {
+                            Some(round) => snapshot_round = round,
+                            None => return,
+                        }
+
+                        continue;
+                    },
+                    Err(RecvError::Closed) => break,
+                };
+
+                let round = state_diff.round;
+                let update = RpcReplicationUpdate::Diff(state_diff.into());
+
+                // NOTE: sending fails or returns false once the subscriber is gone
+                if !matches!(sink.send(&update), Ok(true)) {
+                    return;
+                }
+
+                if round >= snapshot_round.saturating_add(snapshot_interval_rounds) {
+                    match send_replication_snapshot(&mut sink, &vrrbdb_read_handle) {
+                        Some(round) => snapshot_round = round,
+                        None => return,
+                    }
+                }
+            }
+        });
+
+        Ok(())
     }
 
     async fn faucet_drip(&self, address: Address) -> Result<(), Error> {
@@ -202,12 +609,46 @@ impl RpcApiServer for RpcServerImpl {
 
         let secret_key = match secret_key_result {
             Ok(secret_key) => secret_key,
-            Err(_) => return Err(Error::Custom("unable to parse secret_key".to_string())),
+            Err(_) => {
+                return Err(rpc_error(
+                    ErrorCode::InvalidInput,
+                    "unable to parse secret_key",
+                ))
+            },
         };
 
         Ok(secret_key.sign_ecdsa(msg).to_string())
     }
 
+    async fn export_unsigned_txn(
+        &self,
+        args: UnsignedTransferArgs,
+    ) -> Result<UnsignedTransfer, Error> {
+        let unsigned_txn = UnsignedTransfer::new(args);
+
+        debug!(
+            "Received exportUnsignedTxn RPC Request: txn {}",
+            unsigned_txn.digest()
+        );
+
+        Ok(unsigned_txn)
+    }
+
+    async fn import_signed_txn(
+        &self,
+        unsigned_txn: UnsignedTransfer,
+        signature: String,
+    ) -> Result<RpcTransactionRecord, Error> {
+        debug!(
+            "Received importSignedTxn RPC Request: txn {}",
+            unsigned_txn.digest()
+        );
+
+        let txn = unsigned_txn.assemble(&signature).map_err(into_rpc_error)?;
+
+        self.submit_txn(TransactionKind::Transfer(txn)).await
+    }
+
     async fn get_round(&self) -> Result<Round, Error> {
         todo!()
     }
@@ -232,6 +673,78 @@ impl RpcApiServer for RpcServerImpl {
         todo!()
     }
 
+    async fn set_maintenance_mode(&self, paused: bool) -> Result<(), Error> {
+        debug!("Received setMaintenanceMode RPC Request: {paused}");
+
+        let event = Event::MaintenanceModeRequested(paused);
+
+        self.events_tx.send(event.into()).await.map_err(|err| {
+            error!("could not request maintenance mode change: {err}");
+            rpc_error(
+                ErrorCode::Unavailable,
+                "node is not accepting maintenance requests",
+            )
+        })
+    }
+
+    async fn request_halt_directive(
+        &self,
+        action: HaltAction,
+        round: Round,
+        reason: String,
+    ) -> Result<(), Error> {
+        debug!("Received requestHaltDirective RPC Request: {action} at round {round}");
+
+        let event = Event::HaltDirectiveRequested(HaltDirective {
+            action,
+            round,
+            reason,
+        });
+
+        self.events_tx.send(event.into()).await.map_err(|err| {
+            error!("could not request halt directive: {err}");
+            rpc_error(
+                ErrorCode::Unavailable,
+                "node is not accepting halt directive requests",
+            )
+        })
+    }
+
+    async fn get_halt_log(&self) -> Result<Vec<SignedHaltDirective>, Error> {
+        debug!("Received getHaltLog RPC Request");
+
+        Ok(self.vrrbdb_read_handle.halt_directives())
+    }
+
+    async fn get_state_snapshot(&self) -> Result<StateSnapshot, Error> {
+        debug!("Received getStateSnapshot RPC Request");
+
+        Ok(StateSnapshot::from_read_handle(&self.vrrbdb_read_handle))
+    }
+
+    async fn diff_state(&self, peer: StateSnapshot) -> Result<StateSnapshotDiff, Error> {
+        debug!("Received diffState RPC Request");
+
+        let local = StateSnapshot::from_read_handle(&self.vrrbdb_read_handle);
+
+        Ok(diff_state_snapshots(&local, &peer))
+    }
+
+    async fn get_consensus_timeline(
+        &self,
+        last: Option<usize>,
+    ) -> Result<Vec<RoundTimeline>, Error> {
+        debug!("Received getConsensusTimeline RPC Request");
+
+        let mut timelines = self.vrrbdb_read_handle.consensus_timeline();
+
+        if let Some(last) = last {
+            timelines = timelines.split_off(timelines.len().saturating_sub(last));
+        }
+
+        Ok(timelines)
+    }
+
     async fn get_claims_by_account_id(&self, address: Address) -> Result<Claims, Error> {
         let claims = self.vrrbdb_read_handle.claim_store_values();
         let claims = claims
@@ -268,4 +781,521 @@ impl RpcApiServer for RpcServerImpl {
     async fn get_last_block(&self) -> Result<Block, Error> {
         todo!()
     }
+
+    async fn get_account_at_round(&self, address: Address, round: Round) -> Result<Account, Error> {
+        debug!("Received getAccountAtRound RPC Request: {address} at round {round}");
+
+        self.vrrbdb_read_handle
+            .get_account_at_round(&address, round)
+            .map_err(into_rpc_error)
+    }
+
+    async fn get_state_root(&self, round: Round) -> Result<RpcStateRoot, Error> {
+        debug!("Received getStateRoot RPC Request: round {round}");
+
+        let state_root = self
+            .vrrbdb_read_handle
+            .get_state_root(round)
+            .map_err(into_rpc_error)?;
+
+        Ok(RpcStateRoot {
+            round,
+            version: state_root.version,
+            root_hash: hex::encode(state_root.root_hash.0),
+        })
+    }
+
+    async fn get_state_page(
+        &self,
+        prefix: Option<String>,
+        start_after: Option<Address>,
+        limit: usize,
+    ) -> Result<RpcStatePage, Error> {
+        debug!("Received getStatePage RPC Request: {limit} accounts after {start_after:?}");
+
+        if limit == 0 || limit > MAX_STATE_PAGE_SIZE {
+            return Err(rpc_error(
+                ErrorCode::InvalidInput,
+                format!("limit must be between 1 and {MAX_STATE_PAGE_SIZE}"),
+            ));
+        }
+
+        let page = self
+            .vrrbdb_read_handle
+            .state_store_page(prefix.as_deref(), start_after.as_ref(), limit)
+            .map_err(into_rpc_error)?;
+
+        Ok(RpcStatePage {
+            accounts: page.accounts,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    async fn get_block_certification_detail(
+        &self,
+        block_hash: BlockHash,
+    ) -> Result<RpcBlockCertificationDetail, Error> {
+        debug!("Received getBlockCertificationDetail RPC Request for block {block_hash}");
+
+        if let Some(certification) = self.cache.get_certification(&block_hash) {
+            return Ok(certification);
+        }
+
+        let certification = self
+            .vrrbdb_read_handle
+            .get_block_certification(&block_hash)
+            .map_err(into_rpc_error)?;
+
+        let certification = RpcBlockCertificationDetail::from(certification);
+        self.cache.insert_certification(certification.clone());
+
+        Ok(certification)
+    }
+
+    async fn get_quorum(&self, epoch: Option<Epoch>) -> Result<RpcQuorum, Error> {
+        debug!("Received getQuorum RPC Request for epoch {epoch:?}");
+
+        let quorum = match epoch {
+            Some(epoch) => self
+                .vrrbdb_read_handle
+                .get_quorum(epoch)
+                .map_err(into_rpc_error)?,
+            None => self.vrrbdb_read_handle.current_quorum().ok_or_else(|| {
+                rpc_error(ErrorCode::NotFound, "node does not belong to a quorum")
+            })?,
+        };
+
+        Ok(RpcQuorum::from(quorum))
+    }
+
+    async fn get_checkpoint(&self, round: Option<Round>) -> Result<Checkpoint, Error> {
+        debug!("Received getCheckpoint RPC Request for round {round:?}");
+
+        match round {
+            Some(round) => self
+                .vrrbdb_read_handle
+                .get_checkpoint(round)
+                .map_err(into_rpc_error),
+            None => self
+                .vrrbdb_read_handle
+                .latest_checkpoint()
+                .ok_or_else(|| rpc_error(ErrorCode::NotFound, "no checkpoint was taken yet")),
+        }
+    }
+
+    async fn list_checkpoints(
+        &self,
+        start_after: Option<Round>,
+        limit: usize,
+    ) -> Result<Vec<Checkpoint>, Error> {
+        debug!("Received listCheckpoints RPC Request: {limit} checkpoints after {start_after:?}");
+
+        if limit == 0 || limit > MAX_CHECKPOINT_PAGE_SIZE {
+            return Err(rpc_error(
+                ErrorCode::InvalidInput,
+                format!("limit must be between 1 and {MAX_CHECKPOINT_PAGE_SIZE}"),
+            ));
+        }
+
+        Ok(self
+            .vrrbdb_read_handle
+            .checkpoints()
+            .into_iter()
+            .filter(|checkpoint| start_after.map_or(true, |round| checkpoint.round > round))
+            .take(limit)
+            .collect())
+    }
+
+    async fn get_epoch_summary(&self, epoch: Option<Epoch>) -> Result<EpochSummary, Error> {
+        debug!("Received getEpochSummary RPC Request for epoch {epoch:?}");
+
+        match epoch {
+            Some(epoch) => self
+                .vrrbdb_read_handle
+                .get_epoch_summary(epoch)
+                .map_err(into_rpc_error),
+            None => self
+                .vrrbdb_read_handle
+                .latest_epoch_summary()
+                .ok_or_else(|| rpc_error(ErrorCode::NotFound, "no epoch was summarized yet")),
+        }
+    }
+
+    async fn list_epoch_summaries(
+        &self,
+        start_after: Option<Epoch>,
+        limit: usize,
+    ) -> Result<Vec<EpochSummary>, Error> {
+        debug!("Received listEpochSummaries RPC Request: {limit} summaries after {start_after:?}");
+
+        if limit == 0 || limit > MAX_EPOCH_SUMMARY_PAGE_SIZE {
+            return Err(rpc_error(
+                ErrorCode::InvalidInput,
+                format!("limit must be between 1 and {MAX_EPOCH_SUMMARY_PAGE_SIZE}"),
+            ));
+        }
+
+        Ok(self
+            .vrrbdb_read_handle
+            .epoch_summaries()
+            .into_iter()
+            .filter(|summary| start_after.map_or(true, |epoch| summary.epoch > epoch))
+            .take(limit)
+            .collect())
+    }
+
+    async fn estimate_txn_weight(&self, txn: TransactionKind) -> Result<RpcTxnWeight, Error> {
+        debug!(
+            "Received estimateTxnWeight RPC Request for txn {}",
+            txn.id()
+        );
+
+        let pending_weight = self
+            .mempool_read_handle_factory
+            .values()
+            .iter()
+            .fold(0, |weight: Weight, txn| weight.saturating_add(txn.weight()));
+
+        Ok(RpcTxnWeight {
+            weight: txn.weight(),
+            max_block_weight: self.block_limits.max_block_weight,
+            pending_weight,
+        })
+    }
+
+    async fn get_backlog_snapshot(&self) -> Result<BacklogSnapshot, Error> {
+        debug!("Received getBacklogSnapshot RPC Request");
+
+        // NOTE: subscribe before asking so the snapshot can't be missed
+        let mut backlog_snapshots_rx = self.backlog_snapshots_tx.subscribe();
+
+        self.events_tx
+            .send(Event::BacklogSnapshotRequested.into())
+            .await
+            .map_err(|err| {
+                error!("could not request a backlog snapshot: {err}");
+                rpc_error(ErrorCode::Unavailable, "node is not accepting requests")
+            })?;
+
+        let timeout = Duration::from_millis(BACKLOG_SNAPSHOT_TIMEOUT_MS);
+
+        tokio::time::timeout(timeout, async {
+            loop {
+                match backlog_snapshots_rx.recv().await {
+                    Ok(snapshot) => return Some(snapshot),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(|| {
+            rpc_error(
+                ErrorCode::Unavailable,
+                "node did not take a backlog snapshot",
+            )
+        })
+    }
+
+    async fn get_txn_trace(&self, digest: RpcTransactionDigest) -> Result<RpcTxnTrace, Error> {
+        debug!("Received getTxnTrace RPC Request for txn {digest}");
+
+        let parsed_digest = digest.parse::<TransactionDigest>().map_err(|_err| {
+            rpc_error(
+                ErrorCode::InvalidInput,
+                "unable to parse transaction digest",
+            )
+        })?;
+
+        let pending_record = self.mempool_read_handle_factory.get(&parsed_digest);
+        let pending = pending_record.is_some();
+
+        // NOTE: receipts don't keep the txn itself, so its memo is looked up
+        // in the mempool or the transaction store
+        let txn = pending_record.map(|record| record.txn).or_else(|| {
+            self.vrrbdb_read_handle
+                .transaction_store_values()
+                .get(&parsed_digest)
+                .cloned()
+        });
+        let memo = txn.as_ref().and_then(|txn| txn.memo());
+
+        let receipt = self
+            .vrrbdb_read_handle
+            .get_txn_receipt(&parsed_digest)
+            .map_err(into_rpc_error)?;
+
+        let Some(receipt) = receipt else {
+            if pending {
+                return Ok(RpcTxnTrace::from_receipt(
+                    TxnReceipt::new(parsed_digest),
+                    pending,
+                    false,
+                )
+                .with_memo(memo));
+            }
+
+            return Err(rpc_error(ErrorCode::NotFound, "unable to find transaction"));
+        };
+
+        // NOTE: a txn is final once the convergence block it was included in
+        // is certified
+        let finalized = receipt.inclusion.as_ref().map_or(false, |inclusion| {
+            self.vrrbdb_read_handle
+                .get_block_certification(&inclusion.convergence_block_hash)
+                .map_or(false, |certification| certification.is_certified())
+        });
+
+        Ok(RpcTxnTrace::from_receipt(receipt, pending, finalized).with_memo(memo))
+    }
+
+    async fn get_duties(&self, node_id: NodeId, epoch: Epoch) -> Result<ValidatorDuties, Error> {
+        debug!("Received getDuties RPC Request for validator {node_id} at epoch {epoch}");
+
+        // NOTE: subscribe before asking so the duties can't be missed
+        let mut validator_duties_rx = self.validator_duties_tx.subscribe();
+
+        self.events_tx
+            .send(
+                Event::ValidatorDutiesRequested {
+                    node_id: node_id.clone(),
+                    epoch,
+                }
+                .into(),
+            )
+            .await
+            .map_err(|err| {
+                error!("could not request validator duties: {err}");
+                rpc_error(ErrorCode::Unavailable, "node is not accepting requests")
+            })?;
+
+        let timeout = Duration::from_millis(DUTIES_TIMEOUT_MS);
+
+        tokio::time::timeout(timeout, async {
+            loop {
+                match validator_duties_rx.recv().await {
+                    // NOTE: concurrent requests share the channel
+                    Ok(duties) if duties.node_id == node_id && duties.epoch == epoch => {
+                        return Some(duties)
+                    },
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(|| rpc_error(ErrorCode::Unavailable, "node did not look up the duties"))
+    }
+
+    async fn get_validator_earnings(
+        &self,
+        node_id: NodeId,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<Vec<ValidatorEarnings>, Error> {
+        debug!(
+            "Received getValidatorEarnings RPC Request for validator {node_id} from epoch \
+             {start_epoch} to {end_epoch}"
+        );
+
+        if end_epoch < start_epoch {
+            return Err(rpc_error(
+                ErrorCode::InvalidInput,
+                "end epoch must not precede start epoch",
+            ));
+        }
+
+        if end_epoch - start_epoch >= MAX_EARNINGS_EPOCHS {
+            return Err(rpc_error(
+                ErrorCode::InvalidInput,
+                format!("epoch range must cover at most {MAX_EARNINGS_EPOCHS} epochs"),
+            ));
+        }
+
+        Ok(self
+            .vrrbdb_read_handle
+            .validator_earnings(&node_id, start_epoch..=end_epoch))
+    }
+
+    async fn list_peers(&self) -> Result<Vec<PeerRecord>, Error> {
+        debug!("Received listPeers RPC Request");
+
+        // NOTE: subscribe before asking so the peers can't be missed
+        let mut peers_rx = self.peers_tx.subscribe();
+
+        self.events_tx
+            .send(Event::PeersRequested.into())
+            .await
+            .map_err(|err| {
+                error!("could not request the peer list: {err}");
+                rpc_error(ErrorCode::Unavailable, "node is not accepting requests")
+            })?;
+
+        let timeout = Duration::from_millis(PEERS_TIMEOUT_MS);
+
+        tokio::time::timeout(timeout, async {
+            loop {
+                match peers_rx.recv().await {
+                    Ok(peers) => return Some(peers),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(|| rpc_error(ErrorCode::Unavailable, "node did not list its peers"))
+    }
+
+    async fn get_sync_status(&self) -> Result<RpcSyncStatus, Error> {
+        debug!("Received getSyncStatus RPC Request");
+
+        // NOTE: subscribe before asking so the status can't be missed
+        let mut sync_statuses_rx = self.sync_statuses_tx.subscribe();
+
+        self.events_tx
+            .send(Event::SyncStatusRequested.into())
+            .await
+            .map_err(|err| {
+                error!("could not request the sync status: {err}");
+                rpc_error(ErrorCode::Unavailable, "node is not accepting requests")
+            })?;
+
+        let timeout = Duration::from_millis(SYNC_STATUS_TIMEOUT_MS);
+
+        tokio::time::timeout(timeout, async {
+            loop {
+                match sync_statuses_rx.recv().await {
+                    Ok(status) => return Some(status),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .await
+        .ok()
+        .flatten()
+        .map(RpcSyncStatus::from)
+        .ok_or_else(|| {
+            rpc_error(
+                ErrorCode::Unavailable,
+                "node did not report its sync status",
+            )
+        })
+    }
+
+    async fn get_pending_block_preview(&self) -> Result<PendingBlockPreview, Error> {
+        debug!("Received getPendingBlockPreview RPC Request");
+
+        let request_id = NEXT_PENDING_BLOCK_PREVIEW_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+
+        // NOTE: subscribe before asking so the preview can't be missed
+        let mut pending_block_previews_rx = self.pending_block_previews_tx.subscribe();
+
+        self.events_tx
+            .send(Event::PendingBlockPreviewRequested { request_id }.into())
+            .await
+            .map_err(|err| {
+                error!("could not request a pending block preview: {err}");
+                rpc_error(ErrorCode::Unavailable, "node is not accepting requests")
+            })?;
+
+        let timeout = Duration::from_millis(PENDING_BLOCK_PREVIEW_TIMEOUT_MS);
+
+        tokio::time::timeout(timeout, async {
+            loop {
+                match pending_block_previews_rx.recv().await {
+                    // NOTE: concurrent requests share the channel
+                    Ok((answered, preview)) if answered == request_id => return Some(preview),
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(|| {
+            rpc_error(
+                ErrorCode::Unavailable,
+                "node did not preview its pending block",
+            )
+        })
+    }
+
+    async fn get_mempool_stats(&self, fee: Option<u128>) -> Result<MempoolStats, Error> {
+        debug!("Received getMempoolStats RPC Request");
+
+        // NOTE: subscribe before asking so the stats can't be missed
+        let mut mempool_stats_rx = self.mempool_stats_tx.subscribe();
+
+        self.events_tx
+            .send(Event::MempoolStatsRequested { fee }.into())
+            .await
+            .map_err(|err| {
+                error!("could not request mempool stats: {err}");
+                rpc_error(ErrorCode::Unavailable, "node is not accepting requests")
+            })?;
+
+        let timeout = Duration::from_millis(MEMPOOL_STATS_TIMEOUT_MS);
+
+        tokio::time::timeout(timeout, async {
+            loop {
+                match mempool_stats_rx.recv().await {
+                    // NOTE: concurrent requests share the channel
+                    Ok(stats) if stats.inclusion_estimate.map(|estimate| estimate.fee) == fee => {
+                        return Some(stats)
+                    },
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(|| {
+            rpc_error(
+                ErrorCode::Unavailable,
+                "node did not report its mempool stats",
+            )
+        })
+    }
+
+    async fn get_dkg_status(&self) -> Result<DkgStatus, Error> {
+        debug!("Received getDkgStatus RPC Request");
+
+        // NOTE: subscribe before asking so the status can't be missed
+        let mut dkg_statuses_rx = self.dkg_statuses_tx.subscribe();
+
+        self.events_tx
+            .send(Event::DkgStatusRequested.into())
+            .await
+            .map_err(|err| {
+                error!("could not request the DKG status: {err}");
+                rpc_error(ErrorCode::Unavailable, "node is not accepting requests")
+            })?;
+
+        let timeout = Duration::from_millis(DKG_STATUS_TIMEOUT_MS);
+
+        tokio::time::timeout(timeout, async {
+            loop {
+                match dkg_statuses_rx.recv().await {
+                    Ok(status) => return Some(status),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(|| rpc_error(ErrorCode::Unavailable, "node did not report its DKG status"))
+    }
 }
@@ -0,0 +1,431 @@
+//! Versioning of the JSON-RPC API.
+//!
+//! Clients pin the version they were written against in the
+//! [RPC_API_VERSION_HEADER] header and are served any version between
+//! [MIN_RPC_API_VERSION] and [RPC_API_VERSION]. Methods introduced after the
+//! pinned version aren't served, and calls to deprecated methods are answered
+//! with deprecation warnings in the response headers.
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use hyper::{
+    header::{HeaderValue, UPGRADE, WARNING},
+    Body, HeaderMap, Request, Response,
+};
+use jsonrpsee::core::Error;
+use primitives::{ClassifiedError, ErrorCode};
+use tower::{Layer, Service};
+
+use super::{
+    api::{RpcApiMethods, RpcDiscoveryApiServer, RpcMethodDescription},
+    public::{rejection_response, requested_methods},
+};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+pub type RpcApiVersion = u32;
+
+/// Version of the JSON-RPC API served by the node. Bumped whenever methods
+/// are added, deprecated or change in ways clients would notice
+pub const RPC_API_VERSION: RpcApiVersion = 2;
+
+/// Oldest version of the API clients can still pin
+pub const MIN_RPC_API_VERSION: RpcApiVersion = 1;
+
+/// Header clients pin the API version with. Responses carry the version the
+/// request was served under in it, the latest one if none was pinned
+pub const RPC_API_VERSION_HEADER: &str = "x-rpc-api-version";
+
+/// Header set on responses to requests calling deprecated methods. The
+/// methods and their replacements are listed in `Warning` headers
+pub const DEPRECATION_HEADER: &str = "deprecation";
+
+/// Deprecation of a method that is still served.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcDeprecation {
+    pub since: RpcApiVersion,
+
+    /// Method clients should call instead, if any
+    pub replacement: Option<&'static str>,
+}
+
+impl RpcDeprecation {
+    /// Renders the deprecation of `method` as the value of a `Warning` header
+    pub fn warning(&self, method: &str) -> String {
+        let mut warning = format!(
+            "299 - \"{method} is deprecated since API version {}",
+            self.since
+        );
+
+        if let Some(replacement) = self.replacement {
+            warning.push_str(&format!(", call {replacement} instead"));
+        }
+
+        warning.push('"');
+        warning
+    }
+}
+
+/// Version of the API a method was introduced in, and when it was deprecated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcMethodVersion {
+    pub since: RpcApiVersion,
+    pub deprecated: Option<RpcDeprecation>,
+}
+
+impl Default for RpcMethodVersion {
+    fn default() -> Self {
+        Self {
+            since: MIN_RPC_API_VERSION,
+            deprecated: None,
+        }
+    }
+}
+
+/// Methods introduced after the first version of the API or deprecated since,
+/// under the names they're registered with. Every method left out has been
+/// served since the first version
+pub const RPC_METHOD_VERSIONS: &[(&str, RpcMethodVersion)] = &[
+    (
+        "rpc_modules",
+        RpcMethodVersion {
+            since: 2,
+            deprecated: None,
+        },
+    ),
+    (
+        "rpc_methods",
+        RpcMethodVersion {
+            since: 2,
+            deprecated: None,
+        },
+    ),
+    (
+        "state_getDkgStatus",
+        RpcMethodVersion {
+            since: 2,
+            deprecated: None,
+        },
+    ),
+    (
+        "state_getFullState",
+        RpcMethodVersion {
+            since: 1,
+            deprecated: Some(RpcDeprecation {
+                since: 2,
+                replacement: Some("state_getStatePage"),
+            }),
+        },
+    ),
+    (
+        "state_signTransaction",
+        RpcMethodVersion {
+            since: 1,
+            deprecated: Some(RpcDeprecation {
+                since: 2,
+                replacement: Some("state_exportUnsignedTxn"),
+            }),
+        },
+    ),
+];
+
+/// Returns the version annotations of `method`
+pub fn method_version(method: &str) -> RpcMethodVersion {
+    RPC_METHOD_VERSIONS
+        .iter()
+        .find(|(name, _)| *name == method)
+        .map(|(_, version)| *version)
+        .unwrap_or_default()
+}
+
+/// Reasons a request is turned away for the version of the API it pinned.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RpcVersionRejection {
+    #[error(
+        "API version {0} is not supported, versions {min} to {max} are",
+        min = MIN_RPC_API_VERSION,
+        max = RPC_API_VERSION
+    )]
+    UnsupportedVersion(String),
+
+    #[error("method {method} is only served from API version {since}")]
+    MethodUnavailable {
+        method: String,
+        since: RpcApiVersion,
+    },
+}
+
+impl ClassifiedError for RpcVersionRejection {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            RpcVersionRejection::UnsupportedVersion(_) => ErrorCode::InvalidInput,
+            RpcVersionRejection::MethodUnavailable { .. } => ErrorCode::NotFound,
+        }
+    }
+}
+
+/// Version of the API a request is served under, along with the deprecated
+/// methods it calls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegotiatedVersion {
+    pub version: RpcApiVersion,
+    pub deprecated: Vec<(String, RpcDeprecation)>,
+}
+
+/// Negotiates the version of the API a request calling `methods` is served
+/// under, given the version it pinned, if any
+pub fn negotiate_version(
+    pinned: Option<&str>,
+    methods: &[String],
+) -> Result<NegotiatedVersion, RpcVersionRejection> {
+    let version = match pinned {
+        Some(pinned) => pinned
+            .trim()
+            .parse::<RpcApiVersion>()
+            .ok()
+            .filter(|version| (MIN_RPC_API_VERSION..=RPC_API_VERSION).contains(version))
+            .ok_or_else(|| RpcVersionRejection::UnsupportedVersion(pinned.to_string()))?,
+        None => RPC_API_VERSION,
+    };
+
+    let mut deprecated = Vec::new();
+
+    for method in methods {
+        let method_version = method_version(method);
+
+        if method_version.since > version {
+            return Err(RpcVersionRejection::MethodUnavailable {
+                method: method.clone(),
+                since: method_version.since,
+            });
+        }
+
+        if let Some(deprecation) = method_version.deprecated {
+            deprecated.push((method.clone(), deprecation));
+        }
+    }
+
+    Ok(NegotiatedVersion {
+        version,
+        deprecated,
+    })
+}
+
+/// Sets the negotiated version and the deprecation warnings on a response
+fn annotate(headers: &mut HeaderMap, negotiated: &NegotiatedVersion) {
+    headers.insert(RPC_API_VERSION_HEADER, negotiated.version.into());
+
+    if negotiated.deprecated.is_empty() {
+        return;
+    }
+
+    headers.insert(DEPRECATION_HEADER, HeaderValue::from_static("true"));
+
+    for (method, deprecation) in &negotiated.deprecated {
+        if let Ok(warning) = HeaderValue::from_str(&deprecation.warning(method)) {
+            headers.append(WARNING, warning);
+        }
+    }
+}
+
+/// Tower layer negotiating the version of the API every request to the
+/// JSON-RPC server is served under.
+#[derive(Debug, Clone, Default)]
+pub struct RpcVersionLayer;
+
+impl<S> Layer<S> for RpcVersionLayer {
+    type Service = RpcVersionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcVersionService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RpcVersionService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for RpcVersionService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = BoxError> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        // NOTE: the service that was polled ready is the one that has to be called
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let pinned = request
+                .headers()
+                .get(RPC_API_VERSION_HEADER)
+                .map(|pinned| pinned.to_str().unwrap_or_default().to_string());
+
+            // NOTE: messages sent over a websocket can't be inspected, so
+            // subscribers only get the version they pinned checked, and learn
+            // of deprecations through `rpc_methods`
+            let (request, methods) = if request.headers().contains_key(UPGRADE) {
+                (request, vec![])
+            } else {
+                let (parts, body) = request.into_parts();
+                let body = hyper::body::to_bytes(body).await?;
+                let methods = requested_methods(&body);
+
+                (Request::from_parts(parts, Body::from(body)), methods)
+            };
+
+            let negotiated = match negotiate_version(pinned.as_deref(), &methods) {
+                Ok(negotiated) => negotiated,
+                Err(rejection) => {
+                    let mut response = rejection_response(&rejection);
+                    response
+                        .headers_mut()
+                        .insert(RPC_API_VERSION_HEADER, RPC_API_VERSION.into());
+
+                    return Ok(response);
+                },
+            };
+
+            let mut response = inner.call(request).await?;
+            annotate(response.headers_mut(), &negotiated);
+
+            Ok(response)
+        })
+    }
+}
+
+/// Serves the `rpc` namespace, through which clients discover the methods
+/// and versions of the API.
+#[derive(Debug, Clone)]
+pub struct RpcDiscoveryImpl {
+    methods: Vec<String>,
+}
+
+impl RpcDiscoveryImpl {
+    /// Describes the methods registered under `methods`, along with the
+    /// discovery methods themselves
+    pub fn new<'a>(methods: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut methods: Vec<String> = methods
+            .into_iter()
+            .chain(["rpc_modules", "rpc_methods"])
+            .map(str::to_string)
+            .collect();
+
+        methods.sort();
+        methods.dedup();
+
+        Self { methods }
+    }
+}
+
+#[async_trait]
+impl RpcDiscoveryApiServer for RpcDiscoveryImpl {
+    async fn modules(&self) -> Result<BTreeMap<String, RpcApiVersion>, Error> {
+        Ok(self
+            .methods
+            .iter()
+            .filter_map(|method| method.split_once('_'))
+            .map(|(namespace, _)| (namespace.to_string(), RPC_API_VERSION))
+            .collect())
+    }
+
+    async fn methods(&self) -> Result<RpcApiMethods, Error> {
+        let methods = self
+            .methods
+            .iter()
+            .map(|method| {
+                let version = method_version(method);
+
+                RpcMethodDescription {
+                    name: method.clone(),
+                    since: version.since,
+                    deprecated_since: version.deprecated.map(|deprecation| deprecation.since),
+                    replacement: version
+                        .deprecated
+                        .and_then(|deprecation| deprecation.replacement)
+                        .map(str::to_string),
+                }
+            })
+            .collect();
+
+        Ok(RpcApiMethods {
+            version: RPC_API_VERSION,
+            min_version: MIN_RPC_API_VERSION,
+            methods,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn methods(methods: &[&str]) -> Vec<String> {
+        methods.iter().map(|method| method.to_string()).collect()
+    }
+
+    #[test]
+    fn requests_are_served_under_the_version_they_pin() {
+        let get_round = methods(&["state_getRound"]);
+
+        assert_eq!(
+            negotiate_version(None, &get_round).unwrap().version,
+            RPC_API_VERSION
+        );
+        assert_eq!(negotiate_version(Some("1"), &get_round).unwrap().version, 1);
+
+        for pinned in ["0", "3", "latest"] {
+            assert_eq!(
+                negotiate_version(Some(pinned), &get_round).unwrap_err(),
+                RpcVersionRejection::UnsupportedVersion(pinned.to_string())
+            );
+        }
+
+        // NOTE: the discovery methods came with the second version
+        assert_eq!(
+            negotiate_version(Some("1"), &methods(&["rpc_methods"])).unwrap_err(),
+            RpcVersionRejection::MethodUnavailable {
+                method: "rpc_methods".to_string(),
+                since: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn calls_to_deprecated_methods_are_flagged() {
+        let negotiated =
+            negotiate_version(None, &methods(&["state_getRound", "state_getFullState"])).unwrap();
+
+        assert_eq!(negotiated.deprecated.len(), 1);
+
+        let (method, deprecation) = &negotiated.deprecated[0];
+        assert_eq!(method, "state_getFullState");
+        assert_eq!(
+            deprecation.warning(method),
+            "299 - \"state_getFullState is deprecated since API version 2, call \
+             state_getStatePage instead\""
+        );
+
+        let mut headers = HeaderMap::new();
+        annotate(&mut headers, &negotiated);
+
+        assert_eq!(headers[RPC_API_VERSION_HEADER], "2");
+        assert_eq!(headers[DEPRECATION_HEADER], "true");
+        assert_eq!(headers.get_all(WARNING).iter().count(), 1);
+    }
+}
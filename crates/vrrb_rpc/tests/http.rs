@@ -15,6 +15,7 @@ async fn server_starts_and_stops() {
         api_version: "1.0".into(),
         server_timeout: None,
         tls_config: None,
+        ..Default::default()
     };
 
     let api = HttpApiServer::new(config).unwrap();
@@ -61,6 +62,7 @@ async fn server_uses_https() {
         api_version: "1.0".into(),
         server_timeout: None,
         tls_config: Some(tls_config),
+        ..Default::default()
     };
 
     let api = HttpApiServer::new(config).unwrap();
@@ -6,7 +6,7 @@ use secp256k1::Message;
 use tokio::sync::mpsc::channel;
 use vrrb_core::transactions::{generate_transfer_digest_vec, NewTransferArgs, Token};
 use vrrb_rpc::rpc::{
-    api::{RpcApiClient, RpcTransactionRecord},
+    api::{RpcApiClient, RpcDiscoveryApiClient, RpcTransactionRecord},
     client::create_client,
     *,
 };
@@ -50,6 +50,7 @@ async fn server_can_publish_transactions_to_be_created() {
         token,
         amount,
         nonce,
+        None,
     );
 
     type H = secp256k1::hashes::sha256::Hash;
@@ -66,6 +67,7 @@ async fn server_can_publish_transactions_to_be_created() {
         signature: signature.clone(),
         validators: None,
         nonce: 0,
+        memo: None,
     };
 
     let rec = client.create_txn(args).await.unwrap();
@@ -84,6 +86,8 @@ async fn server_can_publish_transactions_to_be_created() {
         signature: signature.to_string().clone(),
         validators: HashMap::new(),
         nonce: 0,
+        fee: primitives::DEFAULT_BASE_FEE,
+        memo: None,
     };
 
     let result_ser = serde_json::to_string_pretty(&rec).unwrap();
@@ -93,3 +97,126 @@ async fn server_can_publish_transactions_to_be_created() {
 
     handle.stop().unwrap();
 }
+
+#[tokio::test]
+async fn public_server_only_serves_allowed_methods_to_known_api_keys() {
+    let public_rpc_config = vrrb_config::PublicRpcConfig {
+        enabled: true,
+        api_keys: vec![vrrb_config::RpcApiKey {
+            name: "explorer".to_string(),
+            key: "explorer-key".to_string(),
+            requests_per_window: None,
+            max_concurrent_requests: None,
+        }],
+        ..Default::default()
+    };
+    public_rpc_config.validate().unwrap();
+
+    let public_rpc_guard = PublicRpcGuard::new(&public_rpc_config);
+    let public_rpc_monitor = public_rpc_guard.monitor();
+
+    let mut json_rpc_server_config = JsonRpcServerConfig::default();
+    json_rpc_server_config.address = "127.0.0.1:0".parse().unwrap();
+    json_rpc_server_config.public_rpc_guard = Some(public_rpc_guard);
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+
+    let call = |method: &str, api_key: Option<&str>| {
+        let mut request = reqwest::Client::new()
+            .post(format!("http://{rpc_server_address}"))
+            .header("content-type", "application/json")
+            .body(format!(
+                r#"{{"jsonrpc":"2.0","method":"{method}","params":[],"id":1}}"#
+            ));
+
+        if let Some(api_key) = api_key {
+            request = request.header(API_KEY_HEADER, api_key);
+        }
+
+        request.send()
+    };
+
+    let response = call("state_getNodeType", None).await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let response = call("state_setMaintenanceMode", Some("explorer-key"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+
+    let response = call("state_getNodeType", Some("explorer-key"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    let body: serde_json::Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(body["result"], "full");
+
+    let stats = public_rpc_monitor.stats();
+    assert_eq!(stats.unauthenticated, 1);
+    assert_eq!(stats.api_keys[0].requests, 1);
+    assert_eq!(stats.api_keys[0].forbidden_calls, 1);
+
+    handle.stop().unwrap();
+}
+
+#[tokio::test]
+async fn clients_discover_the_methods_and_versions_they_are_served() {
+    let mut json_rpc_server_config = JsonRpcServerConfig::default();
+    json_rpc_server_config.address = "127.0.0.1:0".parse().unwrap();
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+
+    let client = create_client(rpc_server_address).await.unwrap();
+
+    let modules = client.modules().await.unwrap();
+    assert_eq!(modules.get("state"), Some(&RPC_API_VERSION));
+    assert_eq!(modules.get("rpc"), Some(&RPC_API_VERSION));
+
+    let api = client.methods().await.unwrap();
+    assert_eq!(api.version, RPC_API_VERSION);
+
+    let get_full_state = api
+        .methods
+        .iter()
+        .find(|method| method.name == "state_getFullState")
+        .unwrap();
+    assert_eq!(get_full_state.deprecated_since, Some(2));
+    assert_eq!(
+        get_full_state.replacement.as_deref(),
+        Some("state_getStatePage")
+    );
+
+    let call = |method: &str, version: Option<&str>| {
+        let mut request = reqwest::Client::new()
+            .post(format!("http://{rpc_server_address}"))
+            .header("content-type", "application/json")
+            .body(format!(
+                r#"{{"jsonrpc":"2.0","method":"{method}","params":[],"id":1}}"#
+            ));
+
+        if let Some(version) = version {
+            request = request.header(RPC_API_VERSION_HEADER, version);
+        }
+
+        request.send()
+    };
+
+    let response = call("state_getFullState", None).await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(response.headers()[RPC_API_VERSION_HEADER], "2");
+    assert_eq!(response.headers()[DEPRECATION_HEADER], "true");
+
+    let response = call("state_getNodeType", Some("1")).await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(response.headers()[RPC_API_VERSION_HEADER], "1");
+    assert!(!response.headers().contains_key(DEPRECATION_HEADER));
+
+    let response = call("rpc_methods", Some("1")).await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let response = call("state_getNodeType", Some("99")).await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    handle.stop().unwrap();
+}
@@ -171,6 +171,7 @@ impl Wallet {
             signature,
             validators: Some(HashMap::new()),
             nonce: self.nonce,
+            memo: None,
         };
 
         let txn = self